@@ -6,7 +6,9 @@
 #[cfg(feature = "agents")]
 use std::io::{self, BufRead, Write};
 
-use crate::cli::{AgentCommands, ContextCommands, FeedbackCommands, FeedbackTypeArg, MemoryCommands};
+use crate::cli::{
+    AgentCommands, ContextCommands, FeedbackCommands, FeedbackTypeArg, ImprovementCommands, MemoryCommands,
+};
 
 /// Result type for agent CLI operations.
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -40,6 +42,7 @@ pub fn execute(command: AgentCommands) -> Result<()> {
         } => rt.block_on(handle_autonomous(&task, max_iterations)),
         AgentCommands::Goals { task } => handle_goals(&task),
         AgentCommands::Context { command } => handle_context(command),
+        AgentCommands::Improvements { command } => rt.block_on(handle_improvements(command)),
     }
 }
 
@@ -56,7 +59,8 @@ async fn handle_memory(command: MemoryCommands) -> Result<()> {
     match command {
         MemoryCommands::Store { agent_id, content } => {
             let embedding = embedder.embed(&content).await?;
-            let memory = Memory::new(&agent_id, &content, embedding);
+            let memory = Memory::new(&agent_id, &content, embedding)
+                .with_metadata(commander_memory::EMBEDDING_MODEL_KEY, embedder.model_id().into());
             store.store(memory).await?;
             println!("Memory stored for agent: {}", agent_id);
         }
@@ -132,6 +136,126 @@ async fn handle_memory(command: MemoryCommands) -> Result<()> {
             }
             println!("\nStorage: {}", commander_core::config::state_dir().join("memory").display());
         }
+
+        MemoryCommands::Consolidate {
+            agent_id,
+            threshold,
+            keep_newest,
+            dry_run,
+        } => {
+            use commander_memory::{Consolidator, LlmMerger};
+            use std::sync::Arc;
+
+            let mut consolidator = Consolidator::new()
+                .with_threshold(threshold)
+                .with_dry_run(dry_run);
+            if !keep_newest {
+                consolidator = consolidator.with_merger(Arc::new(LlmMerger::from_env()), embedder);
+            }
+            let report = match agent_id {
+                Some(agent_id) => consolidator.consolidate_agent(&store, &agent_id).await?,
+                None => consolidator.consolidate_all(&store).await?,
+            };
+
+            if dry_run {
+                println!("Dry run, nothing changed.");
+            }
+            println!("Memory consolidation: {}", report);
+            if report.merged_by_llm > 0 {
+                println!("  {} cluster(s) merged by the LLM", report.merged_by_llm);
+            }
+        }
+
+        MemoryCommands::Backfill {
+            all,
+            batch_size,
+            rpm,
+            dry_run,
+        } => {
+            use commander_memory::Backfill;
+
+            if !embedder.is_real() {
+                println!("Warning: no embedding API key or local provider is configured; memories will get hash-based embeddings.");
+            }
+            println!("Embedding with {} ({} dimensions)", embedder.model_id(), embedder.dimension());
+
+            let backfill = Backfill::new(embedder)
+                .with_batch_size(batch_size)
+                .with_requests_per_minute(rpm)
+                .with_unstamped(all)
+                .with_dry_run(dry_run)
+                .with_progress(|p| {
+                    print!("\r  {}/{} memories embedded", p.done, p.total);
+                    let _ = std::io::Write::flush(&mut std::io::stdout());
+                });
+            let report = backfill.run(&store).await?;
+
+            if dry_run {
+                println!(
+                    "Dry run, nothing changed. {} of {} memories need new embeddings.",
+                    report.stale(all),
+                    report.scanned
+                );
+            } else {
+                if report.batches > 0 {
+                    println!();
+                }
+                println!("Memory backfill: {}", report);
+            }
+            if !all && report.unstamped > 0 {
+                println!(
+                    "  {} unstamped memories have the right dimension but may come from another provider; pass --all to re-embed them",
+                    report.unstamped
+                );
+            }
+        }
+
+        MemoryCommands::Export { path, agent_id, qdrant } => {
+            let mut memories = if qdrant {
+                commander_memory::QdrantStore::from_env().await?.export_all().await?
+            } else {
+                store.export_all().await?
+            };
+            if let Some(agent_id) = &agent_id {
+                memories.retain(|m| &m.agent_id == agent_id);
+            }
+
+            let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            let header = commander_memory::write_export(file, &memories)?;
+            println!("Exported {} memories to {}", header.count, path.display());
+        }
+
+        MemoryCommands::Import { path, qdrant } => {
+            let file = std::io::BufReader::new(std::fs::File::open(&path)?);
+            let (header, memories) = commander_memory::read_export(file)?;
+
+            if let Some(dim) = header.embedding_dim {
+                if dim != embedder.dimension() {
+                    println!(
+                        "Warning: imported embeddings have {} dimensions but the configured embedder produces {}; \
+                         searches will not match them until the embedding provider does.",
+                        dim,
+                        embedder.dimension()
+                    );
+                }
+            }
+
+            let imported = if qdrant {
+                let dim = header.embedding_dim.unwrap_or(commander_memory::DEFAULT_EMBEDDING_DIM);
+                commander_memory::QdrantStore::from_env_with_dimension(dim)
+                    .await?
+                    .import_all(memories)
+                    .await?
+            } else {
+                store.import_all(memories).await?
+            };
+            println!(
+                "Imported {} memories from {} (exported {})",
+                imported,
+                path.display(),
+                header.exported_at.format("%Y-%m-%d %H:%M")
+            );
+        }
     }
 
     Ok(())
@@ -318,6 +442,114 @@ async fn handle_feedback(command: FeedbackCommands) -> Result<()> {
     Ok(())
 }
 
+// =============================================================================
+// Improvement Commands
+// =============================================================================
+
+async fn handle_improvements(command: ImprovementCommands) -> Result<()> {
+    use commander_agent::eval::{EvalTarget, ImprovementGenerator, ImprovementStore, PatchStatus};
+    use commander_agent::FeedbackStore;
+
+    let store = ImprovementStore::open_default();
+
+    match command {
+        ImprovementCommands::Generate { template } => {
+            let target: EvalTarget = template.parse()?;
+            let feedback = FeedbackStore::new(commander_core::config::state_dir().join("feedback"))?;
+            let added = store
+                .propose_from_feedback(&ImprovementGenerator::new(), &feedback, &target)
+                .await?;
+            if added.is_empty() {
+                println!("No new improvements for {}.", target);
+            } else {
+                println!("Proposed {} patch(es) for {}:\n", added.len(), target);
+                for patch in &added {
+                    print_patch(patch);
+                }
+                println!("\nReview with: commander agent improvements apply|reject <id>");
+            }
+        }
+
+        ImprovementCommands::List { template, all } => {
+            let target: Option<EvalTarget> = template.map(|t| t.parse()).transpose()?;
+            let patches: Vec<_> = store
+                .patches(target.as_ref())?
+                .into_iter()
+                .filter(|p| all || p.status == PatchStatus::Proposed)
+                .collect();
+            if patches.is_empty() {
+                println!("No {}patches.", if all { "" } else { "proposed " });
+            } else {
+                for patch in &patches {
+                    print_patch(patch);
+                }
+            }
+        }
+
+        ImprovementCommands::Apply { id } => {
+            let patch = store.apply(&id)?;
+            println!(
+                "Applied {} to {}: prompt version {} is now active.",
+                patch.id,
+                patch.template,
+                patch.version.unwrap_or_default()
+            );
+            println!("Undo with: commander agent improvements rollback {}", patch.template);
+        }
+
+        ImprovementCommands::Reject { id } => {
+            let patch = store.reject(&id)?;
+            println!("Rejected {}: {}", patch.id, patch.instruction);
+        }
+
+        ImprovementCommands::History { template } => {
+            let target: EvalTarget = template.parse()?;
+            let history = store.history(&target)?;
+            let marker = |version: u32| if version == history.active { "*" } else { " " };
+            println!("Prompt versions for {} (* active):\n", target);
+            println!("{} v0  built-in", marker(0));
+            for version in &history.versions {
+                println!(
+                    "{} v{}  {}  patch {}",
+                    marker(version.version),
+                    version.version,
+                    version.created_at.format("%Y-%m-%d %H:%M"),
+                    version.patch_id.as_deref().unwrap_or("-")
+                );
+            }
+        }
+
+        ImprovementCommands::Rollback { template, to } => {
+            let target: EvalTarget = template.parse()?;
+            let version = store.rollback(&target, to)?;
+            if version == 0 {
+                println!("{} uses the built-in prompt again.", target);
+            } else {
+                println!("{} uses prompt version {} again.", target, version);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_patch(patch: &commander_agent::eval::PromptPatch) {
+    println!(
+        "[{}] {:<11} {:<12} {:<11} {}",
+        patch.id,
+        patch.template,
+        patch.improvement.category,
+        patch.status,
+        patch.instruction
+    );
+    println!(
+        "           because: {} (confidence {:.0}%, {} feedback)",
+        patch.improvement.current_behavior,
+        patch.improvement.confidence * 100.0,
+        patch.improvement.supporting_feedback.len()
+    );
+}
+
 // =============================================================================
 // Status Commands
 // =============================================================================
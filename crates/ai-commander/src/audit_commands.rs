@@ -0,0 +1,78 @@
+//! Command audit log queries for the CLI.
+//!
+//! `audit show --project api --since 1d` lists the input sent to sessions
+//! over a period, from the append-only log written by every frontend (see
+//! [`commander_core::command_audit`]): when, who sent it, where, and the
+//! hash and length of the content.
+
+use chrono::{Local, Utc};
+use commander_core::command_audit::{self, AuditQuery, CommandAuditEntry, CommandSender};
+use commander_persistence::StateStore;
+use commander_work::parse_sla;
+
+use crate::cli::AuditCommands;
+use crate::commands::Result;
+
+/// Execute an audit subcommand.
+pub fn execute(store: &StateStore, command: AuditCommands) -> Result<()> {
+    match command {
+        AuditCommands::Show { project, since, sender, json } => {
+            show(store, project.as_deref(), &since, sender.as_deref(), json)
+        }
+    }
+}
+
+fn show(store: &StateStore, project: Option<&str>, since: &str, sender: Option<&str>, json: bool) -> Result<()> {
+    let period = parse_sla(since)
+        .ok_or_else(|| format!("Invalid period: {} (expected e.g. 1h, 1d or 2w)", since))?;
+    let sender = sender.map(str::parse::<CommandSender>).transpose()?;
+    // Unknown names are matched as given, to query deleted projects
+    let project = match project {
+        Some(project) => Some(match store.find_project_by_name_or_alias(project)? {
+            Some(found) => found.name,
+            None => project.to_string(),
+        }),
+        None => None,
+    };
+
+    let query = AuditQuery {
+        project,
+        since: Some(Utc::now() - period),
+        sender,
+    };
+    let entries = command_audit::query(&query);
+
+    if json {
+        for entry in &entries {
+            println!("{}", serde_json::to_string(entry)?);
+        }
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No input sent to sessions in the last {}.", since);
+        return Ok(());
+    }
+    for entry in &entries {
+        println!("{}", format_entry(entry));
+    }
+    println!("{} input(s) in the last {}.", entries.len(), since);
+    Ok(())
+}
+
+/// "2026-01-31 14:02:11  tui       api        line  42B  sha256:3f2a9c…"
+fn format_entry(entry: &CommandAuditEntry) -> String {
+    let hash = entry.content_hash.get(..19).unwrap_or(&entry.content_hash);
+    let target = match &entry.pane {
+        Some(pane) => format!("{}:{}", entry.project, pane),
+        None => entry.project.clone(),
+    };
+    format!(
+        "{}  {:<8}  {:<16}  {:<4}  {:>6}B  {}…",
+        entry.at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+        entry.sender.to_string(),
+        target,
+        entry.kind.to_string(),
+        entry.len,
+        hash
+    )
+}
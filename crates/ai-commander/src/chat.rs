@@ -1,7 +1,11 @@
 //! Chat client for LLM interaction via OpenRouter.
 //!
-//! Provides chat functionality when not connected to a project.
+//! Provides chat functionality when not connected to a project. With
+//! tools (see [`crate::chat_tools`]), the model can also ask for local
+//! Commander actions; the caller runs them and returns their results.
 
+use commander_agent::client::{ChatTool, ChatToolCall};
+use commander_agent::{ToolCall, ToolDefinition};
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -13,36 +17,57 @@ const DEFAULT_MODEL: &str = "anthropic/claude-sonnet-4";
 pub struct Message {
     pub role: String,
     pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ChatToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
 }
 
 impl Message {
-    pub fn user(content: impl Into<String>) -> Self {
+    fn new(role: &str, content: impl Into<String>) -> Self {
         Self {
-            role: "user".to_string(),
+            role: role.to_string(),
             content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new("user", content)
+    }
+
     pub fn assistant(content: impl Into<String>) -> Self {
-        Self {
-            role: "assistant".to_string(),
-            content: content.into(),
-        }
+        Self::new("assistant", content)
     }
 
     pub fn system(content: impl Into<String>) -> Self {
+        Self::new("system", content)
+    }
+
+    /// The result of tool call `tool_call_id`.
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
         Self {
-            role: "system".to_string(),
-            content: content.into(),
+            tool_call_id: Some(tool_call_id.into()),
+            ..Self::new("tool", content)
         }
     }
 }
 
+/// The assistant's answer: text, and the tool calls it asks for.
+#[derive(Debug, Clone, Default)]
+pub struct ChatReply {
+    pub content: String,
+    pub tool_calls: Vec<ToolCall>,
+}
+
 /// OpenRouter API request body.
 #[derive(Debug, Serialize)]
 struct OpenRouterRequest {
     model: String,
     messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ChatTool>>,
 }
 
 /// OpenRouter API response.
@@ -56,15 +81,19 @@ struct Choice {
     message: ResponseMessage,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct ResponseMessage {
-    content: String,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ChatToolCall>>,
 }
 
 /// Chat client for OpenRouter API.
 pub struct ChatClient {
     api_key: Option<String>,
     model: String,
+    system_prompt: Option<String>,
     history: Vec<Message>,
     client: reqwest::Client,
 }
@@ -81,11 +110,18 @@ impl ChatClient {
         Self {
             api_key,
             model,
+            system_prompt: None,
             history: Vec::new(),
             client: reqwest::Client::new(),
         }
     }
 
+    /// Sets a system prompt sent ahead of the conversation.
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
     /// Returns true if the chat client is available (API key is set).
     pub fn is_available(&self) -> bool {
         self.api_key.is_some()
@@ -100,15 +136,46 @@ impl ChatClient {
     ///
     /// Maintains conversation history for context.
     pub async fn send(&mut self, user_message: &str) -> Result<String, ChatError> {
-        let api_key = self.api_key.as_ref().ok_or(ChatError::NoApiKey)?;
+        self.history.push(Message::user(user_message));
+        Ok(self.complete(None).await?.content)
+    }
 
-        // Add user message to history
+    /// Sends a message, offering `tools` to the model.
+    ///
+    /// When the reply has tool calls, run them and pass their results to
+    /// [`continue_with_tools`](Self::continue_with_tools).
+    pub async fn send_with_tools(&mut self, user_message: &str, tools: &[ToolDefinition]) -> Result<ChatReply, ChatError> {
         self.history.push(Message::user(user_message));
+        self.complete(Some(tools)).await
+    }
+
+    /// Returns tool results (call ID, result) to the model and gets its next reply.
+    pub async fn continue_with_tools(
+        &mut self,
+        results: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+    ) -> Result<ChatReply, ChatError> {
+        self.history
+            .extend(results.into_iter().map(|(id, content)| Message::tool(id, content)));
+        self.complete(Some(tools)).await
+    }
+
+    /// Requests a completion of the history and records the reply in it.
+    async fn complete(&mut self, tools: Option<&[ToolDefinition]>) -> Result<ChatReply, ChatError> {
+        let api_key = self.api_key.as_ref().ok_or(ChatError::NoApiKey)?;
 
         // Build request
         let request = OpenRouterRequest {
             model: self.model.clone(),
-            messages: self.history.clone(),
+            messages: self
+                .system_prompt
+                .iter()
+                .map(Message::system)
+                .chain(self.history.iter().cloned())
+                .collect(),
+            tools: tools
+                .filter(|t| !t.is_empty())
+                .map(|t| t.iter().map(ChatTool::from_definition).collect()),
         };
 
         // Send request
@@ -138,17 +205,27 @@ impl ChatClient {
             .await
             .map_err(|e| ChatError::ParseError(e.to_string()))?;
 
-        let content = response
+        let message = response
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message.content)
+            .map(|c| c.message)
             .unwrap_or_default();
+        let content = message.content.unwrap_or_default();
+        let calls = message.tool_calls.unwrap_or_default();
+        let tool_calls = calls
+            .iter()
+            .map(|c| c.to_tool_call().map_err(|e| ChatError::ParseError(e.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
 
         // Add assistant response to history
-        self.history.push(Message::assistant(&content));
+        let mut reply = Message::assistant(&content);
+        if !calls.is_empty() {
+            reply.tool_calls = Some(calls);
+        }
+        self.history.push(reply);
 
-        Ok(content)
+        Ok(ChatReply { content, tool_calls })
     }
 }
 
@@ -232,6 +309,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_messages_serialize() {
+        let json = serde_json::to_value(Message::tool("call-1", "3 projects")).unwrap();
+        assert_eq!(json["role"], "tool");
+        assert_eq!(json["tool_call_id"], "call-1");
+
+        let json = serde_json::to_value(Message::user("hi")).unwrap();
+        assert!(json.get("tool_calls").is_none() && json.get("tool_call_id").is_none());
+
+        let parsed: ResponseMessage = serde_json::from_value(serde_json::json!({
+            "content": null,
+            "tool_calls": [{"id": "c", "type": "function", "function": {"name": "list_projects", "arguments": "{}"}}]
+        }))
+        .unwrap();
+        assert_eq!(parsed.content, None);
+        assert_eq!(parsed.tool_calls.unwrap()[0].function.name, "list_projects");
+    }
+
     #[test]
     fn test_clear_history() {
         let mut client = ChatClient::new();
@@ -0,0 +1,227 @@
+//! Local Commander actions offered to chat mode as tools.
+//!
+//! When the REPL is not connected to a project, chat messages go to the
+//! [`ChatClient`](crate::chat::ChatClient) with these tools, so a request
+//! like "connect me to duetto and run the tests" becomes a `connect` call
+//! followed by `send_message`. Each call is parsed into a [`ChatAction`];
+//! actions that change anything are confirmed by the user before the REPL
+//! runs them.
+
+use commander_agent::{ToolCall, ToolDefinition};
+use commander_models::WorkPriority;
+use serde_json::json;
+
+use crate::repl::{ConnectTarget, ReplCommand};
+
+/// System prompt of chat mode.
+pub const SYSTEM_PROMPT: &str = "You are Commander, an assistant that manages AI coding sessions \
+running in tmux. Answer questions directly. When the user asks to see, connect to, or instruct \
+a project, use the tools: look up project names with list_projects instead of guessing, connect \
+before sending a message, and send the user's instruction to the session in their own words. \
+The user confirms every action that changes something; if they decline, do not retry it.";
+
+/// Tool model replies may chain before control returns to the user.
+pub const MAX_TOOL_ROUNDS: usize = 5;
+
+/// A local action requested by the model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatAction {
+    /// List registered projects and their state.
+    ListProjects,
+    /// Show the status of a project.
+    Status { project: String },
+    /// Connect the REPL to a project, starting its session if needed.
+    Connect { project: String },
+    /// Send a message to a project's session (the connected one by default).
+    SendMessage { project: Option<String>, message: String },
+    /// Add a work item to a project's queue.
+    EnqueueWork {
+        project: String,
+        task: String,
+        priority: WorkPriority,
+    },
+}
+
+impl ChatAction {
+    /// Parses a tool call; the error is returned to the model as the tool result.
+    pub fn from_call(call: &ToolCall) -> Result<Self, String> {
+        let args = &call.arguments;
+        let string = |key: &str| -> Result<String, String> {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .ok_or_else(|| format!("missing argument '{}'", key))
+        };
+
+        match call.name.as_str() {
+            "list_projects" => Ok(Self::ListProjects),
+            "project_status" => Ok(Self::Status { project: string("project")? }),
+            "connect" => Ok(Self::Connect { project: string("project")? }),
+            "send_message" => Ok(Self::SendMessage {
+                project: string("project").ok(),
+                message: string("message")?,
+            }),
+            "enqueue_work" => {
+                let priority = match string("priority").ok().as_deref() {
+                    None | Some("medium") => WorkPriority::Medium,
+                    Some("low") => WorkPriority::Low,
+                    Some("high") => WorkPriority::High,
+                    Some("critical") => WorkPriority::Critical,
+                    Some(other) => return Err(format!("unknown priority '{}'", other)),
+                };
+                Ok(Self::EnqueueWork {
+                    project: string("project")?,
+                    task: string("task")?,
+                    priority,
+                })
+            }
+            other => Err(format!("unknown tool '{}'", other)),
+        }
+    }
+
+    /// Whether the user must confirm the action first; lookups run directly.
+    pub fn needs_confirmation(&self) -> bool {
+        !matches!(self, Self::ListProjects | Self::Status { .. })
+    }
+
+    /// What the action does, as shown in the confirmation prompt.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ListProjects => "list projects".to_string(),
+            Self::Status { project } => format!("show the status of {}", project),
+            Self::Connect { project } => format!("connect to {}", project),
+            Self::SendMessage { project: Some(project), message } => format!("send to {}: {}", project, message),
+            Self::SendMessage { project: None, message } => format!("send to the connected session: {}", message),
+            Self::EnqueueWork { project, task, priority } => {
+                format!("queue {:?} priority work for {}: {}", priority, project, task)
+            }
+        }
+    }
+
+    /// The REPL command performing the action, for those that map onto one.
+    ///
+    /// `connected` is the project the REPL is connected to.
+    pub fn to_command(&self, connected: Option<&str>) -> Option<ReplCommand> {
+        match self {
+            Self::Connect { project } => Some(ReplCommand::Connect(ConnectTarget::Existing(project.clone()))),
+            Self::SendMessage { project: Some(project), message } if Some(project.as_str()) != connected => {
+                Some(ReplCommand::Route {
+                    targets: vec![project.clone()],
+                    message: message.clone(),
+                })
+            }
+            Self::SendMessage { message, .. } => Some(ReplCommand::Send(message.clone())),
+            Self::ListProjects | Self::Status { .. } | Self::EnqueueWork { .. } => None,
+        }
+    }
+}
+
+/// Definitions of the chat tools.
+pub fn tool_definitions() -> Vec<ToolDefinition> {
+    let project = json!({ "type": "string", "description": "Project name or alias" });
+    vec![
+        ToolDefinition::no_params(
+            "list_projects",
+            "List registered projects with their state and which one is connected.",
+        ),
+        ToolDefinition::new(
+            "project_status",
+            "Show a project's state, path and whether its session is running.",
+            json!({
+                "type": "object",
+                "properties": { "project": project },
+                "required": ["project"]
+            }),
+        ),
+        ToolDefinition::new(
+            "connect",
+            "Connect to a project, starting its session if needed. Later messages go to it.",
+            json!({
+                "type": "object",
+                "properties": { "project": project },
+                "required": ["project"]
+            }),
+        ),
+        ToolDefinition::new(
+            "send_message",
+            "Send an instruction to a project's AI session, the connected project by default.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "message": { "type": "string", "description": "Instruction for the session" },
+                    "project": project
+                },
+                "required": ["message"]
+            }),
+        ),
+        ToolDefinition::new(
+            "enqueue_work",
+            "Add a task to a project's work queue, to be delegated later.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "project": project,
+                    "task": { "type": "string", "description": "What needs to be done" },
+                    "priority": { "type": "string", "enum": ["low", "medium", "high", "critical"] }
+                },
+                "required": ["project", "task"]
+            }),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: serde_json::Value) -> ToolCall {
+        ToolCall::with_id("call-1", name, arguments)
+    }
+
+    #[test]
+    fn test_from_call() {
+        assert_eq!(ChatAction::from_call(&call("list_projects", json!({}))), Ok(ChatAction::ListProjects));
+        assert_eq!(
+            ChatAction::from_call(&call("connect", json!({ "project": " duetto " }))),
+            Ok(ChatAction::Connect { project: "duetto".to_string() })
+        );
+        assert_eq!(
+            ChatAction::from_call(&call("enqueue_work", json!({ "project": "api", "task": "Fix CI", "priority": "high" }))),
+            Ok(ChatAction::EnqueueWork {
+                project: "api".to_string(),
+                task: "Fix CI".to_string(),
+                priority: WorkPriority::High,
+            })
+        );
+        assert!(ChatAction::from_call(&call("connect", json!({}))).is_err());
+        assert!(ChatAction::from_call(&call("enqueue_work", json!({ "project": "a", "task": "b", "priority": "asap" }))).is_err());
+        assert!(ChatAction::from_call(&call("rm_rf", json!({}))).is_err());
+
+        let names: Vec<String> = tool_definitions().into_iter().map(|d| d.name).collect();
+        for name in &names {
+            let result = ChatAction::from_call(&call(name, json!({ "project": "p", "message": "m", "task": "t" })));
+            assert!(result.is_ok(), "{} does not parse", name);
+        }
+    }
+
+    #[test]
+    fn test_confirmation_and_commands() {
+        let send = ChatAction::SendMessage { project: None, message: "run the tests".to_string() };
+        assert!(send.needs_confirmation());
+        assert!(!ChatAction::ListProjects.needs_confirmation());
+        assert_eq!(send.to_command(Some("duetto")), Some(ReplCommand::Send("run the tests".to_string())));
+
+        let routed = ChatAction::SendMessage {
+            project: Some("api".to_string()),
+            message: "run the tests".to_string(),
+        };
+        assert_eq!(
+            routed.to_command(Some("duetto")),
+            Some(ReplCommand::Route { targets: vec!["api".to_string()], message: "run the tests".to_string() })
+        );
+        assert_eq!(routed.to_command(Some("api")), Some(ReplCommand::Send("run the tests".to_string())));
+        assert_eq!(ChatAction::ListProjects.to_command(None), None);
+    }
+}
@@ -1,8 +1,11 @@
 //! Command-line interface definition using clap.
 
 use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::engine::ArgValueCandidates;
 use std::path::PathBuf;
 
+use crate::completions::{project_candidates, CompletionShell};
+
 /// Build version string with git hash and build date.
 fn version_string() -> &'static str {
     const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -47,12 +50,40 @@ pub enum Commands {
         /// Project name (default: directory name)
         #[arg(short, long)]
         name: Option<String>,
+
+        /// Run the project's session on this SSH host ([user@]host[:port]);
+        /// the path is then a path on that host
+        #[arg(long)]
+        host: Option<String>,
+    },
+
+    /// Scaffold a new project from a template and start it
+    New {
+        /// Template name (built-in or from ~/.ai-commander/templates/projects/)
+        #[arg(required_unless_present = "list")]
+        template: Option<String>,
+
+        /// Directory to create the project in
+        #[arg(required_unless_present = "list")]
+        path: Option<PathBuf>,
+
+        /// Runtime adapter to use (default: template's adapter, else claude-code)
+        #[arg(short, long)]
+        adapter: Option<String>,
+
+        /// Project name (default: directory name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// List available templates
+        #[arg(short, long)]
+        list: bool,
     },
 
     /// Stop a running project instance
     Stop {
         /// Project ID or name
-        #[arg(required = true)]
+        #[arg(required = true, add = ArgValueCandidates::new(project_candidates))]
         project: String,
 
         /// Force stop without graceful shutdown
@@ -60,6 +91,17 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Relaunch sessions lost to a reboot and prime them with their last context
+    Restore {
+        /// Projects to restore (all lost sessions if omitted)
+        #[arg(add = ArgValueCandidates::new(project_candidates))]
+        projects: Vec<String>,
+
+        /// Only list the lost sessions
+        #[arg(short, long)]
+        list: bool,
+    },
+
     /// List all projects
     List {
         /// Show only running projects
@@ -74,17 +116,22 @@ pub enum Commands {
     /// Show status of a project
     Status {
         /// Project ID or name (shows all if omitted)
+        #[arg(add = ArgValueCandidates::new(project_candidates))]
         project: Option<String>,
 
         /// Show detailed status including events
         #[arg(short, long)]
         detailed: bool,
+
+        /// Show session slot utilization and queued starts instead
+        #[arg(long)]
+        runtime: bool,
     },
 
     /// Send a message to a project
     Send {
         /// Project ID or name
-        #[arg(required = true)]
+        #[arg(required = true, add = ArgValueCandidates::new(project_candidates))]
         project: String,
 
         /// Message to send
@@ -95,20 +142,69 @@ pub enum Commands {
     /// Start interactive REPL mode
     Repl {
         /// Connect to specific project on start
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(project_candidates))]
         project: Option<String>,
     },
 
     /// Launch interactive TUI mode
     Tui {
         /// Project to connect to on start
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(project_candidates))]
         project: Option<String>,
+
+        /// Attach to a commander daemon's API (e.g. http://desktop:8080)
+        /// instead of local tmux
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Pairing token for the remote daemon
+        #[arg(long, requires = "remote", env = "COMMANDER_TOKEN")]
+        token: Option<String>,
+
+        /// Run alongside a TUI that is already running instead of handing
+        /// the project over to it
+        #[arg(long, conflicts_with = "remote")]
+        force_standalone: bool,
+    },
+
+    /// Open the TUI connected to a project (pick one interactively if omitted)
+    Connect {
+        /// Project ID, name or alias
+        #[arg(add = ArgValueCandidates::new(project_candidates))]
+        project: Option<String>,
+
+        /// Run alongside a TUI that is already running instead of handing
+        /// the project over to it
+        #[arg(long)]
+        force_standalone: bool,
     },
 
     /// Show available runtime adapters
     Adapters,
 
+    /// Print a shell completion script
+    ///
+    /// Project arguments complete from the state store. Load it with e.g.
+    /// `source <(ai-commander completions bash)`.
+    Completions {
+        /// Shell to generate the script for
+        shell: CompletionShell,
+    },
+
+    /// Replay a session recording in the terminal (lists recordings if omitted)
+    Play {
+        /// Recording file, or its name in the recordings directory
+        recording: Option<String>,
+
+        /// Playback speed multiplier
+        #[arg(short, long, default_value_t = 1.0)]
+        speed: f64,
+
+        /// Cap pauses between frames at this many seconds
+        #[arg(long)]
+        max_idle: Option<f64>,
+    },
+
     /// Agent system commands (memory, chat, feedback)
     Agent {
         #[command(subcommand)]
@@ -121,12 +217,179 @@ pub enum Commands {
         command: DaemonCommands,
     },
 
+    /// Telegram bot health
+    Telegram {
+        #[command(subcommand)]
+        command: TelegramCommands,
+    },
+
     /// Generate a pairing code for client connections
     Pair {
         /// Session ID to pair with (optional)
         #[arg(short, long)]
         session: Option<String>,
     },
+
+    /// Manage API keys and bot tokens in the secret store
+    Secrets {
+        #[command(subcommand)]
+        command: SecretsCommands,
+    },
+
+    /// Run YAML workflows from ~/.ai-commander/workflows/
+    Workflow {
+        #[command(subcommand)]
+        command: WorkflowCommands,
+    },
+
+    /// Inspect the work queue of delegated tasks
+    Work {
+        #[command(subcommand)]
+        command: WorkCommands,
+    },
+
+    /// Sync GitHub issues with the work queue and open pull requests
+    Gh {
+        #[command(subcommand)]
+        command: GhCommands,
+    },
+
+    /// Offline response quality evals against golden transcripts
+    Eval {
+        #[command(subcommand)]
+        command: EvalCommands,
+    },
+
+    /// Browse the OpenRouter model catalog with pricing
+    Models {
+        #[command(subcommand)]
+        command: ModelsCommands,
+    },
+
+    /// Manage prompt snippets sent with /run <name> or !name
+    Snippets {
+        #[command(subcommand)]
+        command: SnippetsCommands,
+    },
+
+    /// Show structured logs of a project's session (or of every component)
+    Logs {
+        /// Project or session; omit for the combined log
+        project: Option<String>,
+
+        /// Keep printing new lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Least severe level shown
+        #[arg(short, long, value_enum, default_value_t = LogLevelArg::Info)]
+        level: LogLevelArg,
+
+        /// Number of recent lines shown
+        #[arg(short = 'n', long, default_value = "50")]
+        lines: usize,
+    },
+
+    /// Delete everything stored about a project (all of it unless categories are given)
+    Purge {
+        /// Project name, alias, or ID
+        #[arg(long)]
+        project: String,
+
+        /// Project record, events, work items and session snapshot
+        #[arg(long)]
+        state: bool,
+
+        /// Agent memories (local store, and Qdrant when QDRANT_URL is set)
+        #[arg(long)]
+        memories: bool,
+
+        /// Session transcripts and recordings
+        #[arg(long)]
+        transcripts: bool,
+
+        /// Structured logs
+        #[arg(long)]
+        logs: bool,
+
+        /// Recorded feedback
+        #[arg(long)]
+        feedback: bool,
+
+        /// Show what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Remove tmux sessions of deleted projects, stale PID files and dead worktrees
+    Gc {
+        /// Only list what would be removed
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Report sessions, completed tasks, errors, LLM spend and files changed
+    Report {
+        /// Period covered, ending now (e.g. 24h, 7d, 2w)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Only this project (name, alias, or ID)
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Md)]
+        format: ReportFormat,
+    },
+
+    /// Register the projects found under a directory in bulk
+    Import {
+        /// Directory to scan for git repositories and package manifests
+        #[arg(long)]
+        scan: PathBuf,
+
+        /// Runtime adapter for the imported projects (default: claude-code)
+        #[arg(short, long, default_value = "claude-code")]
+        adapter: String,
+
+        /// How many directory levels below the scanned one to search
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+
+        /// Register every detected project without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Query the audit log of every input sent to sessions
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+
+    /// Import a project's Claude Code history into its memories and transcript
+    ImportHistory {
+        /// Project name, alias, or ID
+        #[arg(long)]
+        project: String,
+
+        /// Directory of Claude Code transcripts (default: the project's under ~/.claude/projects)
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Show what would be imported without importing it
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Agent-related subcommands.
@@ -213,6 +476,62 @@ pub enum AgentCommands {
         #[command(subcommand)]
         command: ContextCommands,
     },
+
+    /// Review prompt patches suggested from feedback
+    Improvements {
+        #[command(subcommand)]
+        command: ImprovementCommands,
+    },
+}
+
+/// Prompt improvement subcommands.
+#[derive(Subcommand, Debug)]
+pub enum ImprovementCommands {
+    /// Analyze feedback and propose prompt patches
+    Generate {
+        /// Template to improve: user, claude_code, mpm or generic
+        #[arg(long, default_value = "user")]
+        template: String,
+    },
+
+    /// List prompt patches
+    List {
+        /// Only patches for this template
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Include applied, rejected and rolled back patches
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Apply a proposed patch as a new prompt version
+    Apply {
+        /// Patch ID or ID prefix
+        id: String,
+    },
+
+    /// Reject a proposed patch
+    Reject {
+        /// Patch ID or ID prefix
+        id: String,
+    },
+
+    /// Show a template's prompt versions
+    History {
+        /// Template: user, claude_code, mpm or generic
+        template: String,
+    },
+
+    /// Make an earlier prompt version active
+    Rollback {
+        /// Template: user, claude_code, mpm or generic
+        template: String,
+
+        /// Version to return to (0 is the built-in prompt; default: the one before the active version)
+        #[arg(long)]
+        to: Option<u32>,
+    },
 }
 
 /// Daemon management subcommands.
@@ -235,6 +554,295 @@ pub enum DaemonCommands {
     Restart,
 }
 
+/// Telegram bot subcommands.
+#[derive(Subcommand, Debug)]
+pub enum TelegramCommands {
+    /// Show whether the bot is healthy, stale or down, from its heartbeat
+    Status,
+}
+
+/// Secret store subcommands.
+#[derive(Subcommand, Debug)]
+pub enum SecretsCommands {
+    /// Store a secret (e.g. OPENROUTER_API_KEY, TELEGRAM_BOT_TOKEN)
+    Set {
+        /// Secret name
+        name: String,
+
+        /// Secret value (read from stdin if omitted)
+        value: Option<String>,
+    },
+
+    /// Print a secret's value
+    Get {
+        /// Secret name
+        name: String,
+    },
+
+    /// List stored secrets (names only)
+    List,
+
+    /// Remove a secret
+    Delete {
+        /// Secret name
+        name: String,
+    },
+}
+
+/// Workflow subcommands.
+#[derive(Subcommand, Debug)]
+pub enum WorkflowCommands {
+    /// Run a workflow on a project, resuming an unfinished run
+    Run {
+        /// Workflow name (file name without .yaml)
+        name: String,
+
+        /// Project ID or name
+        #[arg(short, long, required = true, add = ArgValueCandidates::new(project_candidates))]
+        project: String,
+
+        /// Start from the first step even if an earlier run did not finish
+        #[arg(long)]
+        restart: bool,
+    },
+
+    /// List available workflows
+    List,
+}
+
+/// Work queue subcommands.
+#[derive(Subcommand, Debug)]
+pub enum WorkCommands {
+    /// Show the dependency graph of a session's work items, with blocked
+    /// items and the critical path
+    Graph {
+        /// Session (or project) whose work items to show
+        session: String,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = GraphFormat::Ascii)]
+        format: GraphFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Set or clear the deadline of a work item
+    Deadline {
+        /// Session (or project) the work item belongs to
+        session: String,
+
+        /// Work item ID, or the start of it as shown in the graph
+        item: String,
+
+        /// Time from now until it is due (e.g. "45m", "4h", "1h30m", "2d"),
+        /// or "none" to clear the deadline
+        due: String,
+    },
+}
+
+/// GitHub subcommands.
+#[derive(Subcommand, Debug)]
+pub enum GhCommands {
+    /// Import open issues as work items of a project
+    Import {
+        /// Repository, as owner/name
+        #[arg(long)]
+        repo: String,
+
+        /// Only issues with this label (repeatable; all must match)
+        #[arg(short, long = "label")]
+        labels: Vec<String>,
+
+        /// Project the work items belong to
+        #[arg(short, long)]
+        project: String,
+    },
+
+    /// Delegate an imported issue to the project's session on a new branch
+    Delegate {
+        /// Issue number
+        issue: u64,
+
+        /// Project the issue was imported into
+        #[arg(short, long)]
+        project: String,
+
+        /// Session to send the issue to (default: the project's session)
+        #[arg(short, long)]
+        session: Option<String>,
+    },
+
+    /// Comment on linked issues whose work item changed state
+    Sync {
+        /// Project whose linked work items to report
+        #[arg(short, long)]
+        project: String,
+    },
+
+    /// Complete an issue's work item, push its branch and open a pull request
+    Complete {
+        /// Issue number
+        issue: u64,
+
+        /// Project the issue was imported into
+        #[arg(short, long)]
+        project: String,
+
+        /// Summary of the work, used in the comment and pull request
+        #[arg(short, long)]
+        result: Option<String>,
+
+        /// Base branch of the pull request (default: the repository's default branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Complete without pushing or opening a pull request
+        #[arg(long)]
+        no_pr: bool,
+    },
+}
+
+/// Rendering of a work graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Indented text tree
+    #[default]
+    Ascii,
+    /// Graphviz DOT
+    Dot,
+}
+
+/// Audit log subcommands.
+#[derive(Subcommand, Debug)]
+pub enum AuditCommands {
+    /// Show what was sent to sessions, oldest first
+    Show {
+        /// Only this project (name, alias, or ID)
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Period covered, ending now (e.g. 1h, 1d, 2w)
+        #[arg(long, default_value = "1d")]
+        since: String,
+
+        /// Only input from this sender (user, tui, gui, telegram, matrix, api, agent, daemon)
+        #[arg(long)]
+        sender: Option<String>,
+
+        /// Print the entries as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Output format of `report`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown table and task lists
+    #[default]
+    Md,
+    /// One CSV row per project
+    Csv,
+}
+
+/// Eval subcommands.
+#[derive(Subcommand, Debug)]
+pub enum EvalCommands {
+    /// Replay golden conversations, score them with an LLM judge and report regressions
+    Run {
+        /// Dataset file or directory (default: ~/.ai-commander/evals/golden/)
+        #[arg(short, long)]
+        dataset: Option<PathBuf>,
+
+        /// Only run cases for this template (user, claude_code, mpm, generic)
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Report to compare against (default: ~/.ai-commander/evals/baseline.json)
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Save this run as the new baseline
+        #[arg(long)]
+        save_baseline: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Model used as the judge
+        #[arg(long)]
+        judge_model: Option<String>,
+
+        /// Score a case needs to pass (cases may set their own)
+        #[arg(long, default_value_t = 0.7)]
+        min_score: f32,
+    },
+}
+
+/// Model catalog subcommands.
+#[derive(Subcommand, Debug)]
+pub enum ModelsCommands {
+    /// List OpenRouter models with context length and price per million tokens
+    List {
+        /// Fetch the catalog again even if the cached copy is recent
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Find models whose ID or name contains every word of the query
+    Search {
+        /// Words to look for (e.g. "claude sonnet")
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Fetch the catalog again even if the cached copy is recent
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+/// Prompt snippet subcommands.
+#[derive(Subcommand, Debug)]
+pub enum SnippetsCommands {
+    /// List snippets
+    List,
+
+    /// Add a snippet; {project}, {path}, {branch} and {args} are filled in when it is sent
+    Add {
+        /// Snippet name (letters, digits, '-' and '_')
+        name: String,
+
+        /// Text sent to the session
+        text: String,
+
+        /// Shown in listings
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Replace a snippet's text
+    Edit {
+        /// Snippet name
+        name: String,
+
+        /// New text
+        text: String,
+
+        /// New description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Remove a snippet
+    Remove {
+        /// Snippet name
+        name: String,
+    },
+}
+
 /// Context management subcommands.
 #[derive(Subcommand, Debug)]
 pub enum ContextCommands {
@@ -305,6 +913,68 @@ pub enum MemoryCommands {
 
     /// Show memory statistics
     Stats,
+
+    /// Merge near-duplicate memories and report the savings
+    Consolidate {
+        /// Only consolidate this agent's memories (default: all agents)
+        #[arg(long)]
+        agent_id: Option<String>,
+
+        /// Cosine similarity above which memories count as duplicates
+        #[arg(long, default_value_t = commander_memory::DEFAULT_SIMILARITY_THRESHOLD)]
+        threshold: f32,
+
+        /// Keep the newest memory of each cluster instead of merging with the LLM
+        #[arg(long)]
+        keep_newest: bool,
+
+        /// Show what would change without modifying the store
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Re-embed memories stored with another embedding provider
+    Backfill {
+        /// Also re-embed memories not stamped with a model (stored before stamping)
+        #[arg(long)]
+        all: bool,
+
+        /// Memories embedded per request
+        #[arg(long, default_value_t = commander_memory::DEFAULT_BACKFILL_BATCH_SIZE)]
+        batch_size: usize,
+
+        /// Maximum embedding requests per minute (0 for no limit)
+        #[arg(long, default_value = "60")]
+        rpm: u32,
+
+        /// Only count the memories that need new embeddings
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Export memories with their embeddings to a versioned JSONL file
+    Export {
+        /// File to write
+        path: PathBuf,
+
+        /// Only export this agent's memories (default: all agents)
+        #[arg(long)]
+        agent_id: Option<String>,
+
+        /// Read from Qdrant (QDRANT_URL) instead of the local store
+        #[arg(long)]
+        qdrant: bool,
+    },
+
+    /// Import memories from an export file without re-embedding them
+    Import {
+        /// Export file to read
+        path: PathBuf,
+
+        /// Write to Qdrant (QDRANT_URL) instead of the local store
+        #[arg(long)]
+        qdrant: bool,
+    },
 }
 
 /// Feedback subcommands.
@@ -365,6 +1035,29 @@ pub enum FeedbackTypeArg {
     Positive,
 }
 
+/// Log level CLI argument.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogLevelArg {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevelArg> for tracing::Level {
+    fn from(level: LogLevelArg) -> Self {
+        match level {
+            LogLevelArg::Error => tracing::Level::ERROR,
+            LogLevelArg::Warn => tracing::Level::WARN,
+            LogLevelArg::Info => tracing::Level::INFO,
+            LogLevelArg::Debug => tracing::Level::DEBUG,
+            LogLevelArg::Trace => tracing::Level::TRACE,
+        }
+    }
+}
+
 /// Output format for list commands
 #[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
 pub enum OutputFormat {
@@ -382,6 +1075,15 @@ impl Cli {
             .unwrap_or_else(commander_core::config::state_dir)
     }
 
+    /// Whether this invocation runs the daemon itself (and should log to
+    /// the rotating daemon log).
+    pub fn runs_daemon(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Commands::Daemon { command: DaemonCommands::Start { foreground: true } })
+        )
+    }
+
     /// Returns the log level based on verbosity.
     pub fn log_level(&self) -> tracing::Level {
         match self.verbose {
@@ -417,6 +1119,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_status_runtime() {
+        let cli = Cli::parse_from(["commander", "status", "--runtime"]);
+        match cli.command {
+            Some(Commands::Status { project, runtime, .. }) => {
+                assert!(project.is_none());
+                assert!(runtime);
+            }
+            _ => panic!("Expected Status command"),
+        }
+    }
+
     #[test]
     fn test_cli_parse_list() {
         let cli = Cli::parse_from(["commander", "list", "--running"]);
@@ -435,6 +1149,241 @@ mod tests {
         assert_eq!(cli.log_level(), tracing::Level::TRACE);
     }
 
+    #[test]
+    fn test_cli_parse_connect_and_completions() {
+        let cli = Cli::parse_from(["commander", "connect"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Connect { project: None, force_standalone: false })
+        ));
+
+        let cli = Cli::parse_from(["commander", "tui", "-p", "web", "--force-standalone"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Tui { project: Some(_), force_standalone: true, .. })
+        ));
+
+        let cli = Cli::parse_from(["commander", "completions", "zsh"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Completions { shell: CompletionShell::Zsh })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_workflow_run() {
+        let cli = Cli::parse_from(["commander", "workflow", "run", "release", "--project", "api", "--restart"]);
+        match cli.command {
+            Some(Commands::Workflow {
+                command: WorkflowCommands::Run { name, project, restart },
+            }) => {
+                assert_eq!(name, "release");
+                assert_eq!(project, "api");
+                assert!(restart);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_work_deadline() {
+        let cli = Cli::parse_from(["commander", "work", "deadline", "api", "3f2a", "4h"]);
+        match cli.command {
+            Some(Commands::Work {
+                command: WorkCommands::Deadline { session, item, due },
+            }) => {
+                assert_eq!(session, "api");
+                assert_eq!(item, "3f2a");
+                assert_eq!(due, "4h");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_work_graph() {
+        let cli = Cli::parse_from(["commander", "work", "graph", "api", "--format", "dot"]);
+        match cli.command {
+            Some(Commands::Work {
+                command: WorkCommands::Graph { session, format, output },
+            }) => {
+                assert_eq!(session, "api");
+                assert_eq!(format, GraphFormat::Dot);
+                assert_eq!(output, None);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_gh_import() {
+        let cli = Cli::parse_from([
+            "commander", "gh", "import", "--repo", "acme/api", "--label", "ai", "--label", "bug", "--project", "api",
+        ]);
+        match cli.command {
+            Some(Commands::Gh {
+                command: GhCommands::Import { repo, labels, project },
+            }) => {
+                assert_eq!(repo, "acme/api");
+                assert_eq!(labels, vec!["ai", "bug"]);
+                assert_eq!(project, "api");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_logs() {
+        let cli = Cli::parse_from(["commander", "logs", "api", "--follow", "--level", "warn"]);
+        match cli.command {
+            Some(Commands::Logs { project, follow, level, lines }) => {
+                assert_eq!(project.as_deref(), Some("api"));
+                assert!(follow);
+                assert_eq!(level, LogLevelArg::Warn);
+                assert_eq!(lines, 50);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_purge() {
+        let cli = Cli::parse_from(["commander", "purge", "--project", "api", "--memories", "--logs", "--dry-run"]);
+        match cli.command {
+            Some(Commands::Purge { project, state, memories, transcripts, logs, feedback, dry_run, yes }) => {
+                assert_eq!(project, "api");
+                assert!(memories && logs && dry_run);
+                assert!(!state && !transcripts && !feedback && !yes);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_gc() {
+        let cli = Cli::parse_from(["commander", "gc", "--dry-run"]);
+        assert!(matches!(cli.command, Some(Commands::Gc { dry_run: true, yes: false })));
+    }
+
+    #[test]
+    fn test_cli_parse_report() {
+        let cli = Cli::parse_from(["commander", "report", "--project", "api", "--format", "csv"]);
+        match cli.command {
+            Some(Commands::Report { since, project, format }) => {
+                assert_eq!(since, "7d");
+                assert_eq!(project.as_deref(), Some("api"));
+                assert_eq!(format, ReportFormat::Csv);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_audit_show() {
+        let cli = Cli::parse_from(["commander", "audit", "show", "--project", "api", "--since", "1d"]);
+        match cli.command {
+            Some(Commands::Audit {
+                command: AuditCommands::Show { project, since, sender, json },
+            }) => {
+                assert_eq!(project.as_deref(), Some("api"));
+                assert_eq!(since, "1d");
+                assert_eq!(sender, None);
+                assert!(!json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_import_scan() {
+        let cli = Cli::parse_from(["commander", "import", "--scan", "/code", "--adapter", "cc", "-y"]);
+        match cli.command {
+            Some(Commands::Import { scan, adapter, depth, yes }) => {
+                assert_eq!(scan, PathBuf::from("/code"));
+                assert_eq!(adapter, "cc");
+                assert_eq!(depth, 3);
+                assert!(yes);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_telegram_status() {
+        let cli = Cli::parse_from(["commander", "telegram", "status"]);
+        assert!(matches!(cli.command, Some(Commands::Telegram { command: TelegramCommands::Status })));
+    }
+
+    #[test]
+    fn test_cli_parse_import_history() {
+        let cli = Cli::parse_from(["commander", "import-history", "--project", "api", "--path", "/tmp/h", "--dry-run"]);
+        match cli.command {
+            Some(Commands::ImportHistory { project, path, dry_run }) => {
+                assert_eq!(project, "api");
+                assert_eq!(path, Some(PathBuf::from("/tmp/h")));
+                assert!(dry_run);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_restore() {
+        let cli = Cli::parse_from(["commander", "restore", "api", "web"]);
+        match cli.command {
+            Some(Commands::Restore { projects, list }) => {
+                assert_eq!(projects, vec!["api", "web"]);
+                assert!(!list);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_eval_run() {
+        let cli = Cli::parse_from(["commander", "eval", "run", "--template", "user", "--json"]);
+        match cli.command {
+            Some(Commands::Eval {
+                command: EvalCommands::Run { template, json, min_score, .. },
+            }) => {
+                assert_eq!(template.as_deref(), Some("user"));
+                assert!(json);
+                assert_eq!(min_score, 0.7);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_agent_improvements() {
+        let cli = Cli::parse_from(["commander", "agent", "improvements", "rollback", "claude_code", "--to", "2"]);
+        match cli.command {
+            Some(Commands::Agent {
+                command: AgentCommands::Improvements {
+                    command: ImprovementCommands::Rollback { template, to },
+                },
+            }) => {
+                assert_eq!(template, "claude_code");
+                assert_eq!(to, Some(2));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_models_search() {
+        let cli = Cli::parse_from(["commander", "models", "search", "claude", "sonnet", "--refresh"]);
+        match cli.command {
+            Some(Commands::Models {
+                command: ModelsCommands::Search { query, refresh },
+            }) => {
+                assert_eq!(query, vec!["claude", "sonnet"]);
+                assert!(refresh);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_cli_help() {
         // Verify help can be generated without panic
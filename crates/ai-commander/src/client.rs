@@ -0,0 +1,360 @@
+//! Session backends for the TUI.
+//!
+//! The TUI drives sessions through [`SessionBackend`]: locally that is the
+//! tmux orchestrator, and with `tui --remote` it is a [`RemoteClient`]
+//! talking to a commander daemon's REST API, so a desktop Commander can be
+//! controlled from a laptop. The local backend also reaches sessions on SSH
+//! hosts configured per project (see [`SessionBackend::set_session_host`]).
+
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use serde_json::json;
+
+use commander_tmux::{Result, SshHost, TmuxError, TmuxOrchestrator, TmuxSession};
+
+/// Timeout for a single API request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The session operations the TUI needs.
+///
+/// Signatures mirror [`TmuxOrchestrator`] so the local backend is a thin
+/// pass-through.
+pub trait SessionBackend {
+    /// List all sessions.
+    fn list_sessions(&self) -> Result<Vec<TmuxSession>>;
+
+    /// Check whether a session exists.
+    fn session_exists(&self, name: &str) -> bool;
+
+    /// Capture the last `lines` lines of a session pane.
+    fn capture_output(&self, session: &str, pane: Option<&str>, lines: Option<u32>) -> Result<String>;
+
+    /// Send a line of input to a session pane.
+    fn send_line(&self, session: &str, pane: Option<&str>, text: &str) -> Result<()>;
+
+    /// Create a session, optionally in `dir`.
+    fn create_session_in_dir(&self, name: &str, dir: Option<&str>) -> Result<TmuxSession>;
+
+    /// Create a session, optionally in `dir`, with environment variables.
+    fn create_session_with_env(&self, name: &str, dir: Option<&str>, env: &[(String, String)]) -> Result<TmuxSession>;
+
+    /// Destroy a session.
+    fn destroy_session(&self, name: &str) -> Result<()>;
+
+    /// Suspend a session's foreground processes, returning their process groups.
+    fn suspend_session(&self, name: &str) -> Result<Vec<u32>> {
+        Err(TmuxError::CommandFailed(format!("cannot suspend {} on this backend", name)))
+    }
+
+    /// Resume process groups returned by [`SessionBackend::suspend_session`].
+    fn resume_processes(&self, _groups: &[u32]) -> Result<()> {
+        Err(TmuxError::CommandFailed("cannot resume processes on this backend".into()))
+    }
+
+    /// Send keys such as `C-c` to a session pane, without Enter.
+    fn send_keys(&self, session: &str, _pane: Option<&str>, _keys: &str) -> Result<()> {
+        Err(TmuxError::CommandFailed(format!("cannot send keys to {} on this backend", session)))
+    }
+
+    /// Name of the command in the foreground of a session pane.
+    fn pane_command(&self, session: &str, _pane: Option<&str>) -> Result<String> {
+        Err(TmuxError::CommandFailed(format!("cannot inspect {} on this backend", session)))
+    }
+
+    /// Run a session on an SSH host, or locally when `host` is `None`.
+    fn set_session_host(&self, session: &str, _host: Option<SshHost>) -> Result<()> {
+        Err(TmuxError::CommandFailed(format!("cannot move {} to an SSH host on this backend", session)))
+    }
+
+    /// SSH host of a session and its round trip, once measured; `None` for
+    /// local sessions.
+    fn session_host(&self, _session: &str) -> Option<(SshHost, Option<Duration>)> {
+        None
+    }
+
+    /// How often to poll a session whose local poll interval is `base`.
+    fn poll_interval(&self, _session: &str, base: Duration) -> Duration {
+        base
+    }
+}
+
+impl SessionBackend for TmuxOrchestrator {
+    fn list_sessions(&self) -> Result<Vec<TmuxSession>> {
+        TmuxOrchestrator::list_sessions(self)
+    }
+
+    fn session_exists(&self, name: &str) -> bool {
+        TmuxOrchestrator::session_exists(self, name)
+    }
+
+    fn capture_output(&self, session: &str, pane: Option<&str>, lines: Option<u32>) -> Result<String> {
+        TmuxOrchestrator::capture_output(self, session, pane, lines)
+    }
+
+    fn send_line(&self, session: &str, pane: Option<&str>, text: &str) -> Result<()> {
+        TmuxOrchestrator::send_line(self, session, pane, text)
+    }
+
+    fn create_session_in_dir(&self, name: &str, dir: Option<&str>) -> Result<TmuxSession> {
+        TmuxOrchestrator::create_session_in_dir(self, name, dir)
+    }
+
+    fn create_session_with_env(&self, name: &str, dir: Option<&str>, env: &[(String, String)]) -> Result<TmuxSession> {
+        TmuxOrchestrator::create_session_with_env(self, name, dir, env)
+    }
+
+    fn destroy_session(&self, name: &str) -> Result<()> {
+        TmuxOrchestrator::destroy_session(self, name)
+    }
+
+    fn suspend_session(&self, name: &str) -> Result<Vec<u32>> {
+        TmuxOrchestrator::suspend_session(self, name)
+    }
+
+    fn resume_processes(&self, groups: &[u32]) -> Result<()> {
+        TmuxOrchestrator::resume_processes(self, groups)
+    }
+
+    fn send_keys(&self, session: &str, pane: Option<&str>, keys: &str) -> Result<()> {
+        TmuxOrchestrator::send_keys(self, session, pane, keys)
+    }
+
+    fn pane_command(&self, session: &str, pane: Option<&str>) -> Result<String> {
+        TmuxOrchestrator::pane_command(self, session, pane)
+    }
+
+    fn set_session_host(&self, session: &str, host: Option<SshHost>) -> Result<()> {
+        TmuxOrchestrator::set_session_host(self, session, host);
+        Ok(())
+    }
+
+    fn session_host(&self, session: &str) -> Option<(SshHost, Option<Duration>)> {
+        TmuxOrchestrator::session_host(self, session).map(|host| (host, self.session_latency(session)))
+    }
+
+    fn poll_interval(&self, session: &str, base: Duration) -> Duration {
+        TmuxOrchestrator::poll_interval(self, session, base)
+    }
+}
+
+/// Session entry from `GET /api/sessions`.
+#[derive(Debug, Deserialize)]
+struct SessionSummary {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionListResponse {
+    sessions: Vec<SessionSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionOutputResponse {
+    output: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthStatusResponse {
+    authenticated: bool,
+}
+
+/// Session backend backed by a remote commander daemon's REST API.
+#[derive(Debug, Clone)]
+pub struct RemoteClient {
+    base_url: String,
+    token: Option<String>,
+    http: Client,
+}
+
+impl RemoteClient {
+    /// Create a client for the API at `base_url` (e.g. `http://host:8080`).
+    pub fn new(base_url: &str, token: Option<String>) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| TmuxError::CommandFailed(format!("failed to build HTTP client: {}", e)))?;
+        Ok(Self {
+            base_url: normalize_base_url(base_url),
+            token,
+            http,
+        })
+    }
+
+    /// Create a client and verify the daemon is reachable and, when a token
+    /// is given, that it is accepted.
+    pub fn connect(base_url: &str, token: Option<String>) -> Result<Self> {
+        let client = Self::new(base_url, token)?;
+        client.send(client.request(reqwest::Method::GET, "/api/health"))?;
+
+        if client.token.is_some() {
+            let status: AuthStatusResponse =
+                json_body(client.send(client.request(reqwest::Method::GET, "/api/auth/status"))?)?;
+            if !status.authenticated {
+                return Err(TmuxError::CommandFailed(format!(
+                    "token rejected by {}",
+                    client.base_url
+                )));
+            }
+        }
+        Ok(client)
+    }
+
+    /// API base URL without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        let builder = self.http.request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    fn send(&self, builder: RequestBuilder) -> Result<Response> {
+        let response = builder
+            .send()
+            .map_err(|e| TmuxError::CommandFailed(format!("request to {} failed: {}", self.base_url, e)))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().unwrap_or_default();
+        Err(TmuxError::CommandFailed(format!("{}: {}", status, error_message(&body))))
+    }
+}
+
+impl SessionBackend for RemoteClient {
+    fn list_sessions(&self) -> Result<Vec<TmuxSession>> {
+        let list: SessionListResponse =
+            json_body(self.send(self.request(reqwest::Method::GET, "/api/sessions"))?)?;
+        Ok(list
+            .sessions
+            .into_iter()
+            .map(|s| TmuxSession::new(s.name, Utc::now()))
+            .collect())
+    }
+
+    fn session_exists(&self, name: &str) -> bool {
+        self.list_sessions()
+            .map(|sessions| sessions.iter().any(|s| s.name == name))
+            .unwrap_or(false)
+    }
+
+    fn capture_output(&self, session: &str, pane: Option<&str>, lines: Option<u32>) -> Result<String> {
+        let path = format!("/api/sessions/{}/capture", session_path(session));
+        let builder = self
+            .request(reqwest::Method::POST, &path)
+            .json(&json!({ "lines": lines, "pane": pane }));
+        let response = self.send(builder).map_err(|e| not_found_as_session(e, session))?;
+        let body: SessionOutputResponse = json_body(response)?;
+        Ok(body.output)
+    }
+
+    fn send_line(&self, session: &str, pane: Option<&str>, text: &str) -> Result<()> {
+        let builder = self
+            .request(reqwest::Method::POST, "/api/sessions/message")
+            .json(&json!({ "session": session, "message": text, "pane": pane }));
+        self.send(builder).map_err(|e| not_found_as_session(e, session))?;
+        Ok(())
+    }
+
+    fn create_session_in_dir(&self, name: &str, dir: Option<&str>) -> Result<TmuxSession> {
+        let builder = self
+            .request(reqwest::Method::POST, "/api/sessions")
+            .json(&json!({ "name": name, "directory": dir }));
+        self.send(builder)?;
+        Ok(TmuxSession::new(name, Utc::now()))
+    }
+
+    /// Secrets are not sent to the daemon: the variables are left out.
+    fn create_session_with_env(&self, name: &str, dir: Option<&str>, env: &[(String, String)]) -> Result<TmuxSession> {
+        if !env.is_empty() {
+            tracing::warn!(session = %name, "project environment is not set on remote sessions");
+        }
+        self.create_session_in_dir(name, dir)
+    }
+
+    fn destroy_session(&self, name: &str) -> Result<()> {
+        let path = format!("/api/sessions/{}", session_path(name));
+        self.send(self.request(reqwest::Method::DELETE, &path))
+            .map_err(|e| not_found_as_session(e, name))?;
+        Ok(())
+    }
+}
+
+/// Accept `host:port` as well as full URLs, and drop trailing slashes.
+fn normalize_base_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    }
+}
+
+/// Percent-encode characters that would break a path segment.
+fn session_path(name: &str) -> String {
+    name.replace('%', "%25").replace('/', "%2F").replace(' ', "%20")
+}
+
+/// Extract `error` from an API error body, falling back to the raw body.
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+fn not_found_as_session(error: TmuxError, session: &str) -> TmuxError {
+    match error {
+        TmuxError::CommandFailed(msg) if msg.starts_with("404") => {
+            TmuxError::SessionNotFound(session.to_string())
+        }
+        other => other,
+    }
+}
+
+fn json_body<T: serde::de::DeserializeOwned>(response: Response) -> Result<T> {
+    response
+        .json()
+        .map_err(|e| TmuxError::ParseError(format!("invalid API response: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(normalize_base_url("http://desk:8080/"), "http://desk:8080");
+        assert_eq!(normalize_base_url("desk:8080"), "http://desk:8080");
+        assert_eq!(normalize_base_url("https://desk"), "https://desk");
+    }
+
+    #[test]
+    fn test_session_path_escapes() {
+        assert_eq!(session_path("cmd-app"), "cmd-app");
+        assert_eq!(session_path("a/b c"), "a%2Fb%20c");
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        assert_eq!(error_message(r#"{"error":"session not found: x"}"#), "session not found: x");
+        assert_eq!(error_message("plain text\n"), "plain text");
+        assert!(matches!(
+            not_found_as_session(TmuxError::CommandFailed("404 Not Found: gone".into()), "x"),
+            TmuxError::SessionNotFound(name) if name == "x"
+        ));
+    }
+
+    #[test]
+    fn test_unreachable_daemon() {
+        let result = RemoteClient::connect("http://127.0.0.1:1", None);
+        assert!(matches!(result, Err(TmuxError::CommandFailed(_))));
+    }
+}
@@ -1,14 +1,40 @@
 //! Command handlers for CLI subcommands.
 
+use std::io::{self, Write};
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 use commander_adapters::AdapterRegistry;
+use commander_core::secrets::{SecretStore, KNOWN_SECRETS};
+use commander_core::{list_templates, project_templates_dir, secret_store, secrets_dir, TemplateSource};
 use commander_models::{Project, ProjectState};
-use commander_persistence::StateStore;
+use commander_persistence::{SnapshotStore, StateStore};
+use commander_runtime::SlotUsage;
+use commander_tmux::{SshHost, TmuxOrchestrator};
 use tracing::{info, warn};
 
-use crate::cli::{Commands, OutputFormat};
+use crate::audit_commands;
+use crate::cli::{Commands, OutputFormat, SecretsCommands, TelegramCommands};
+use crate::client::SessionBackend;
+use crate::completions::write_completions;
 use crate::daemon_commands;
+use crate::eval_commands;
+use crate::gc;
+use crate::gh_commands;
+use crate::import_commands;
+use crate::logs_commands;
+use crate::models_commands;
+use crate::snippets_commands;
+use crate::project_import;
+use crate::purge_commands::{self, PurgeScope};
+use crate::report_commands;
+use crate::restore;
+use crate::work_commands;
+use crate::workflow_commands;
+use crate::scaffold::{adapter_ready, scaffold_project};
+
+/// How long `commander new` waits for the adapter before giving up on the prompt.
+const PROMPT_READY_TIMEOUT: Duration = Duration::from_secs(120);
 
 /// Result type for command operations.
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -26,20 +52,84 @@ pub fn execute(command: Commands, state_dir: &Path) -> Result<()> {
             path,
             adapter,
             name,
-        } => cmd_start(&store, &path, &adapter, name.as_deref()),
-        Commands::Stop { project, force } => cmd_stop(&store, &project, force),
+            host,
+        } => cmd_start(&store, &path, &adapter, name.as_deref(), host.as_deref()),
+        Commands::New { list: true, .. } => cmd_templates(),
+        Commands::New {
+            template,
+            path,
+            adapter,
+            name,
+            list: false,
+        } => {
+            let (Some(template), Some(path)) = (template, path) else {
+                return Err("Usage: commander new <template> <path>".into());
+            };
+            cmd_new(&store, &template, &path, adapter.as_deref(), name.as_deref())
+        }
+        Commands::Stop { project, force } => cmd_stop(&store, state_dir, &project, force),
+        Commands::Restore { projects, list } => cmd_restore(&store, state_dir, &projects, list),
         Commands::List { running, format } => cmd_list(&store, running, format),
-        Commands::Status { project, detailed } => cmd_status(&store, project.as_deref(), detailed),
+        Commands::Status { runtime: true, .. } => cmd_runtime_status(state_dir),
+        Commands::Status { project, detailed, .. } => cmd_status(&store, project.as_deref(), detailed),
         Commands::Send { project, message } => cmd_send(&store, &project, &message),
         Commands::Repl { project: _ } => {
             // REPL is handled separately in main
             Ok(())
         }
-        Commands::Tui { project: _ } => {
+        Commands::Tui { .. } | Commands::Connect { .. } => {
             // TUI is handled separately in main
             Ok(())
         }
         Commands::Adapters => cmd_adapters(),
+        Commands::Completions { shell } => {
+            write_completions(shell, &mut std::io::stdout())?;
+            Ok(())
+        }
+        Commands::Play { recording, speed, max_idle } => cmd_play(recording.as_deref(), speed, max_idle),
+        Commands::Secrets { command } => cmd_secrets(&secret_store(), command),
+        Commands::Telegram { command: TelegramCommands::Status } => cmd_telegram_status(),
+        Commands::Workflow { command } => workflow_commands::execute(&store, state_dir, command),
+        Commands::Work { command } => work_commands::execute(state_dir, command),
+        Commands::Gh { command } => {
+            let rt = tokio::runtime::Runtime::new()
+                .map_err(|e| format!("Failed to create async runtime: {}", e))?;
+            rt.block_on(gh_commands::execute(&store, state_dir, command))
+        }
+        Commands::Eval { command } => eval_commands::execute(command),
+        Commands::Models { command } => models_commands::execute(command),
+        Commands::Snippets { command } => snippets_commands::execute(command),
+        Commands::Logs { project, follow, level, lines } => {
+            logs_commands::execute(project.as_deref(), follow, level.into(), lines)
+        }
+        Commands::Purge {
+            project,
+            state,
+            memories,
+            transcripts,
+            logs,
+            feedback,
+            dry_run,
+            yes,
+        } => {
+            let scope = PurgeScope { state, memories, transcripts, logs, feedback };
+            let rt = tokio::runtime::Runtime::new()
+                .map_err(|e| format!("Failed to create async runtime: {}", e))?;
+            rt.block_on(purge_commands::execute(state_dir, &project, scope, dry_run, yes))
+        }
+        Commands::Gc { dry_run, yes } => cmd_gc(&store, dry_run, yes),
+        Commands::Report { since, project, format } => {
+            report_commands::execute(state_dir, &since, project.as_deref(), format)
+        }
+        Commands::Audit { command } => audit_commands::execute(&store, command),
+        Commands::Import { scan, adapter, depth, yes } => {
+            project_import::execute(&store, &scan, &adapter, depth, yes)
+        }
+        Commands::ImportHistory { project, path, dry_run } => {
+            let rt = tokio::runtime::Runtime::new()
+                .map_err(|e| format!("Failed to create async runtime: {}", e))?;
+            rt.block_on(import_commands::execute(state_dir, &project, path.as_deref(), dry_run))
+        }
         Commands::Agent { .. } => {
             // Agent commands are handled separately in main.rs
             Ok(())
@@ -69,12 +159,13 @@ fn execute_async(command: Commands) -> Result<()> {
     })
 }
 
-fn cmd_start(store: &StateStore, path: &Path, adapter: &str, name: Option<&str>) -> Result<()> {
+fn cmd_start(store: &StateStore, path: &Path, adapter: &str, name: Option<&str>, host: Option<&str>) -> Result<()> {
     // Verify adapter exists
     let registry = AdapterRegistry::new();
     let adapter_info = registry
         .get(adapter)
         .ok_or_else(|| format!("Unknown adapter: {}", adapter))?;
+    let host = host.map(str::parse::<SshHost>).transpose()?;
 
     // Create project
     let project_name = name
@@ -82,13 +173,18 @@ fn cmd_start(store: &StateStore, path: &Path, adapter: &str, name: Option<&str>)
         .or_else(|| path.file_name().and_then(|n| n.to_str()).map(String::from))
         .unwrap_or_else(|| "unnamed".to_string());
 
-    let path_str = path
-        .canonicalize()
-        .unwrap_or_else(|_| path.to_path_buf())
-        .to_string_lossy()
-        .to_string();
+    // A remote path cannot be resolved here
+    let path_str = match host {
+        Some(_) => path.to_path_buf(),
+        None => path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+    }
+    .to_string_lossy()
+    .to_string();
 
-    let project = Project::new(path_str, project_name.clone());
+    let mut project = Project::new(path_str, project_name.clone());
+    if let Some(host) = &host {
+        project.config.insert("host".to_string(), serde_json::json!(host.to_string()));
+    }
 
     info!(
         project_id = %project.id,
@@ -103,12 +199,161 @@ fn cmd_start(store: &StateStore, path: &Path, adapter: &str, name: Option<&str>)
     println!("Started project '{}' ({})", project_name, project.id);
     println!("  Path: {}", path.display());
     println!("  Adapter: {} ({})", adapter_info.info().name, adapter);
+    if let Some(host) = &host {
+        println!("  Host: {} (over SSH)", host);
+    }
     println!("\nNote: Actual runtime spawning will be implemented in Phase 7");
 
     Ok(())
 }
 
-fn cmd_stop(store: &StateStore, project_id: &str, force: bool) -> Result<()> {
+fn cmd_new(
+    store: &StateStore,
+    template: &str,
+    path: &Path,
+    adapter: Option<&str>,
+    name: Option<&str>,
+) -> Result<()> {
+    let registry = AdapterRegistry::new();
+    let path = shellexpand::tilde(&path.to_string_lossy()).to_string();
+    let scaffolded = scaffold_project(store, &registry, template, Path::new(&path), adapter, name)?;
+    let project = &scaffolded.project;
+
+    info!(
+        project_id = %project.id,
+        project_name = %project.name,
+        template = %template,
+        adapter = %scaffolded.tool_id,
+        "Scaffolded project"
+    );
+
+    println!(
+        "Created project '{}' from template '{}' ({} files)",
+        project.name, template, scaffolded.files_created
+    );
+    println!("  Path: {}", project.path);
+    println!("  Adapter: {}", scaffolded.tool_id);
+
+    let tmux = TmuxOrchestrator::new().map_err(|e| format!("Tmux not available: {}", e))?;
+    let adapter = registry
+        .get(&scaffolded.tool_id)
+        .ok_or_else(|| format!("Unknown adapter: {}", scaffolded.tool_id))?;
+    let (cmd, cmd_args) = adapter.launch_command(&project.path);
+    let full_cmd = if cmd_args.is_empty() {
+        cmd
+    } else {
+        format!("{} {}", cmd, cmd_args.join(" "))
+    };
+
+    let session_name = project.name.replace([' ', '.', '/', ':'], "-");
+    let (env, env_warning) = crate::project_session_env(&project.path);
+    if let Some(warning) = env_warning {
+        println!("  Warning: {}", warning);
+    }
+    tmux.create_session_with_env(&session_name, Some(&project.path), &env)
+        .map_err(|e| format!("Failed to create tmux session: {}", e))?;
+    tmux.send_line(&session_name, None, &full_cmd)
+        .map_err(|e| format!("Failed to start adapter: {}", e))?;
+    println!("  Session: {}", session_name);
+
+    if let Some(prompt) = &scaffolded.prompt {
+        print!("Waiting for {} to start...", scaffolded.tool_id);
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+        let started = Instant::now();
+        loop {
+            std::thread::sleep(Duration::from_millis(500));
+            let output = tmux
+                .capture_output(&session_name, None, Some(50))
+                .unwrap_or_default();
+            if adapter_ready(Some(adapter.as_ref()), &output) {
+                tmux.send_line(&session_name, None, prompt)
+                    .map_err(|e| format!("Failed to send initial prompt: {}", e))?;
+                println!(" sent initial prompt");
+                break;
+            }
+            if started.elapsed() > PROMPT_READY_TIMEOUT {
+                println!(" timed out; initial prompt not sent");
+                warn!(session = %session_name, "Adapter not ready, skipped initial prompt");
+                break;
+            }
+        }
+    }
+
+    println!("\nConnect with: commander tui -p {}", project.name);
+    Ok(())
+}
+
+fn cmd_templates() -> Result<()> {
+    println!("Project Templates:");
+    println!();
+
+    for template in list_templates() {
+        let source = match template.source {
+            TemplateSource::BuiltIn => "built-in",
+            TemplateSource::User => "user",
+        };
+        println!("  {} ({}) - {}", template.name, source, template.description);
+    }
+
+    println!();
+    println!("User templates: {}", project_templates_dir().display());
+    Ok(())
+}
+
+fn cmd_secrets(secrets: &SecretStore, command: SecretsCommands) -> Result<()> {
+    match command {
+        SecretsCommands::Set { name, value } => {
+            let value = match value {
+                Some(value) => value,
+                None => {
+                    print!("Enter value for {}: ", name);
+                    std::io::Write::flush(&mut std::io::stdout())?;
+                    let mut line = String::new();
+                    std::io::stdin().read_line(&mut line)?;
+                    line.trim().to_string()
+                }
+            };
+            if value.is_empty() {
+                return Err("Secret value cannot be empty".into());
+            }
+            let backend = secrets.set(&name, &value)?;
+            println!("Stored {} in {}", name, backend);
+        }
+        SecretsCommands::Get { name } => match secrets.get(&name)? {
+            Some(value) => println!("{}", value),
+            None => return Err(format!("Secret not found: {}", name).into()),
+        },
+        SecretsCommands::List => {
+            let entries = secrets.list()?;
+            println!("Secrets:");
+            println!();
+            if entries.is_empty() {
+                println!("  (none stored)");
+            }
+            for entry in &entries {
+                println!("  {} ({})", entry.name, entry.backend);
+            }
+            for (name, description) in KNOWN_SECRETS {
+                if !entries.iter().any(|e| e.name == *name) {
+                    let source = if std::env::var(name).is_ok() { "from environment" } else { "not set" };
+                    println!("  {} - {} [{}]", name, description, source);
+                }
+            }
+            println!();
+            println!("Secrets dir: {}", secrets_dir().display());
+        }
+        SecretsCommands::Delete { name } => {
+            if secrets.delete(&name)? {
+                println!("Removed {}", name);
+            } else {
+                return Err(format!("Secret not found: {}", name).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_stop(store: &StateStore, state_dir: &Path, project_id: &str, force: bool) -> Result<()> {
     // Find project by ID or name
     let projects = store.load_all_projects()?;
     let project = projects
@@ -126,6 +371,8 @@ fn cmd_stop(store: &StateStore, project_id: &str, force: bool) -> Result<()> {
     let mut project = project.clone();
     project.set_state(ProjectState::Paused, Some("Stopped by user".to_string()));
     store.save_project(&project)?;
+    // A stopped session is not restored after a reboot
+    SnapshotStore::new(state_dir).delete(&project.id)?;
 
     println!("Stopped project '{}' ({})", project.name, project.id);
     if force {
@@ -136,6 +383,112 @@ fn cmd_stop(store: &StateStore, project_id: &str, force: bool) -> Result<()> {
     Ok(())
 }
 
+fn cmd_gc(store: &StateStore, dry_run: bool, yes: bool) -> Result<()> {
+    let tmux = TmuxOrchestrator::new().ok();
+    let tmux = tmux.as_ref().map(|t| t as &dyn SessionBackend);
+    if tmux.is_none() {
+        println!("Tmux not available; only checking PID files.");
+    }
+    let orphans = gc::find_orphans(store, tmux, &commander_core::config::runtime_state_dir());
+    if orphans.is_empty() {
+        println!("Nothing to clean up.");
+        return Ok(());
+    }
+
+    println!("Orphans:");
+    for line in orphans.describe() {
+        println!("  {}", line);
+    }
+    if dry_run {
+        return Ok(());
+    }
+    if !yes {
+        print!("\nRemove {}? [y/N] ", orphans.summary());
+        io::stdout().flush()?;
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        if !matches!(line.trim(), "y" | "Y" | "yes") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let report = gc::collect(&orphans, tmux);
+    println!("Removed {}.", report.removed);
+    for error in &report.errors {
+        println!("  Failed: {}", error);
+    }
+    Ok(())
+}
+
+fn cmd_restore(store: &StateStore, state_dir: &Path, projects: &[String], list_only: bool) -> Result<()> {
+    let tmux = TmuxOrchestrator::new().map_err(|e| format!("Tmux not available: {}", e))?;
+    let snapshots = SnapshotStore::new(state_dir);
+    let lost: Vec<_> = restore::detect_lost_sessions(store, &snapshots, &tmux)
+        .into_iter()
+        .filter(|s| projects.is_empty() || projects.iter().any(|p| *p == s.project || *p == s.project_id.as_str()))
+        .collect();
+
+    if lost.is_empty() {
+        println!("No lost sessions to restore.");
+        return Ok(());
+    }
+
+    if list_only {
+        println!("Lost sessions:");
+        for snapshot in &lost {
+            println!(
+                "  {} ({}) in {}, last snapshot {}",
+                snapshot.project,
+                snapshot.adapter,
+                snapshot.working_dir,
+                snapshot.captured_at.format("%Y-%m-%d %H:%M UTC")
+            );
+        }
+        println!("\nRestore with: commander restore [project...]");
+        return Ok(());
+    }
+
+    let registry = AdapterRegistry::new();
+    let mut waiting = Vec::new();
+    for snapshot in lost {
+        match restore::relaunch(&snapshot, store, &registry, &tmux) {
+            Ok(()) => {
+                println!("Relaunched '{}' ({})", snapshot.project, snapshot.adapter);
+                waiting.push(snapshot);
+            }
+            Err(e) => println!("Failed to restore '{}': {}", snapshot.project, e),
+        }
+    }
+
+    // Prime each adapter with its last context once it is ready
+    let started = Instant::now();
+    while !waiting.is_empty() {
+        std::thread::sleep(Duration::from_millis(500));
+        waiting.retain(|snapshot| {
+            let output = tmux
+                .capture_output(&snapshot.session, None, Some(50))
+                .unwrap_or_default();
+            if !adapter_ready(registry.get(&snapshot.adapter).as_deref(), &output) {
+                return true;
+            }
+            match tmux.send_line(&snapshot.session, None, &restore::priming_message(snapshot)) {
+                Ok(()) => println!("Primed '{}' with its last context", snapshot.project),
+                Err(e) => println!("Failed to prime '{}': {}", snapshot.project, e),
+            }
+            false
+        });
+        if started.elapsed() > PROMPT_READY_TIMEOUT {
+            for snapshot in &waiting {
+                println!("'{}' did not become ready; context not sent", snapshot.project);
+                warn!(session = %snapshot.session, "Adapter not ready, skipped priming message");
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
 fn cmd_list(store: &StateStore, running_only: bool, format: OutputFormat) -> Result<()> {
     let projects = store.load_all_projects()?;
 
@@ -206,6 +559,75 @@ fn cmd_status(store: &StateStore, project_id: Option<&str>, detailed: bool) -> R
     Ok(())
 }
 
+/// Print session slot utilization as last written by the daemon's runtime.
+fn cmd_runtime_status(state_dir: &Path) -> Result<()> {
+    let Some(usage) = SlotUsage::load(state_dir) else {
+        println!("No slot usage recorded. Start the runtime with: commander daemon start");
+        return Ok(());
+    };
+
+    println!("Session slots: {}", slots(usage.running, usage.limit));
+    for (name, workspace) in &usage.workspaces {
+        println!("  {}: {}", name, slots(workspace.running, workspace.limit));
+    }
+
+    if usage.queued.is_empty() {
+        println!("Queued starts: none");
+    } else {
+        println!("Queued starts:");
+        let now = chrono::Utc::now();
+        for (position, queued) in usage.queued.iter().enumerate() {
+            let workspace = queued
+                .workspace
+                .as_ref()
+                .map(|w| format!(" ({})", w))
+                .unwrap_or_default();
+            let waiting = (now - queued.queued_at).num_minutes().max(0);
+            println!("  {}. {}{}, waiting {}m", position + 1, queued.project, workspace, waiting);
+        }
+    }
+    println!("Updated: {}", usage.updated_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"));
+
+    Ok(())
+}
+
+fn cmd_telegram_status() -> Result<()> {
+    use commander_telegram::heartbeat::{self, format_uptime, Heartbeat};
+
+    println!("Telegram bot: {}", heartbeat::health());
+    let Some(beat) = Heartbeat::load() else {
+        println!("  No heartbeat recorded. Start the bot with /telegram or: commander daemon start");
+        return Ok(());
+    };
+
+    let now = chrono::Utc::now();
+    println!("  PID: {}", beat.pid);
+    println!("  Uptime: {}", format_uptime(beat.uptime()));
+    println!("  Heartbeat: {} ago", format_uptime(beat.age(now)));
+    match beat.last_update_at {
+        Some(at) => println!(
+            "  Last update: {} ago ({} processed)",
+            format_uptime((now - at).to_std().unwrap_or_default()),
+            beat.updates
+        ),
+        None => println!("  Last update: none yet"),
+    }
+    match &beat.last_error {
+        Some(error) => println!("  Errors: {} (last: {})", beat.errors, error),
+        None => println!("  Errors: {}", beat.errors),
+    }
+
+    Ok(())
+}
+
+/// `running/limit in use`, or just the count when unlimited.
+fn slots(running: usize, limit: Option<usize>) -> String {
+    match limit {
+        Some(limit) => format!("{}/{} in use", running, limit),
+        None => format!("{} running (no limit)", running),
+    }
+}
+
 fn print_project_status(project: &Project, detailed: bool) {
     println!("Project: {} ({})", project.name, project.id);
     println!("  State: {:?}", project.state);
@@ -249,6 +671,32 @@ fn cmd_adapters() -> Result<()> {
     Ok(())
 }
 
+/// Replay a recording, or list recordings when none is given.
+fn cmd_play(recording: Option<&str>, speed: f64, max_idle: Option<f64>) -> Result<()> {
+    let Some(name) = recording else {
+        let recordings = commander_core::list_recordings();
+        if recordings.is_empty() {
+            println!("No recordings in {}", commander_core::recordings_dir().display());
+            println!("Start one with /record start in the TUI");
+        } else {
+            println!("Recordings:");
+            for path in recordings {
+                if let Some(file) = path.file_name() {
+                    println!("  {}", file.to_string_lossy());
+                }
+            }
+        }
+        return Ok(());
+    };
+
+    let path = commander_core::resolve_recording(name)?;
+    let recording = commander_core::Recording::load(&path)?;
+    recording.play(&mut std::io::stdout(), speed, max_idle)?;
+    println!();
+    println!("[{} frames, {:.1}s]", recording.events.len(), recording.duration());
+    Ok(())
+}
+
 /// Truncates a string to the given length, adding "..." if truncated.
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -278,6 +726,28 @@ mod tests {
         cmd_adapters().unwrap();
     }
 
+    #[test]
+    fn test_cmd_templates() {
+        // Should not panic
+        cmd_templates().unwrap();
+    }
+
+    #[test]
+    fn test_cmd_secrets() {
+        let dir = tempdir().unwrap();
+        let secrets = SecretStore::file_only(dir.path());
+
+        cmd_secrets(
+            &secrets,
+            SecretsCommands::Set { name: "OPENROUTER_API_KEY".to_string(), value: Some("sk-or".to_string()) },
+        )
+        .unwrap();
+        assert_eq!(secrets.get("OPENROUTER_API_KEY").unwrap().as_deref(), Some("sk-or"));
+        assert!(cmd_secrets(&secrets, SecretsCommands::List).is_ok());
+        assert!(cmd_secrets(&secrets, SecretsCommands::Delete { name: "OPENROUTER_API_KEY".to_string() }).is_ok());
+        assert!(cmd_secrets(&secrets, SecretsCommands::Get { name: "OPENROUTER_API_KEY".to_string() }).is_err());
+    }
+
     #[test]
     fn test_truncate() {
         assert_eq!(truncate("hello", 10), "hello");
@@ -0,0 +1,87 @@
+//! Shell completions.
+//!
+//! `ai-commander completions <shell>` prints a registration script that calls
+//! back into the binary (with `COMPLETE=<shell>` set) whenever the shell
+//! completes a command line. Completing at runtime lets project arguments
+//! offer the names currently in the state store.
+
+use std::ffi::OsString;
+use std::io::Write;
+
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::{Bash, CompleteEnv, EnvCompleter, Fish, Zsh};
+use commander_persistence::StateStore;
+
+use crate::cli::Cli;
+
+/// Environment variable the registration scripts set when asking for
+/// completions.
+pub const COMPLETE_ENV: &str = "COMPLETE";
+
+/// Shells completion scripts can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl CompletionShell {
+    fn completer(self) -> &'static dyn EnvCompleter {
+        match self {
+            CompletionShell::Bash => &Bash,
+            CompletionShell::Zsh => &Zsh,
+            CompletionShell::Fish => &Fish,
+        }
+    }
+}
+
+/// Answer a completion request from a registration script and exit.
+///
+/// Does nothing unless `COMPLETE` is set, so this is called first thing in
+/// `main`.
+pub fn complete_from_env() {
+    CompleteEnv::with_factory(Cli::command).var(COMPLETE_ENV).complete();
+}
+
+/// Write the completion script for `shell` to `out`.
+pub fn write_completions(shell: CompletionShell, out: &mut dyn Write) -> std::io::Result<()> {
+    let name = Cli::command().get_name().to_string();
+    shell
+        .completer()
+        .write_registration(COMPLETE_ENV, &name, &name, &name, out)
+}
+
+/// Project names from the state store, with their paths as help text.
+///
+/// Used for dynamic completion of project arguments; returns nothing if the
+/// store cannot be read.
+pub fn project_candidates() -> Vec<CompletionCandidate> {
+    let store = StateStore::new(commander_core::config::state_dir());
+    let Ok(projects) = store.load_all_projects() else {
+        return Vec::new();
+    };
+    let mut projects: Vec<_> = projects.into_values().collect();
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+    projects
+        .into_iter()
+        .map(|p| CompletionCandidate::new(OsString::from(p.name)).help(Some(p.path.into())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_call_back_into_binary() {
+        for shell in CompletionShell::value_variants() {
+            let mut out = Vec::new();
+            write_completions(*shell, &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains(COMPLETE_ENV), "{:?} script: {}", shell, script);
+            assert!(script.contains("ai-commander"));
+        }
+    }
+}
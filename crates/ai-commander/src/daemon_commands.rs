@@ -1,30 +1,53 @@
 //! Daemon management commands for the CLI.
+//!
+//! `daemon start` hosts the runtime, REST API, Telegram bot, blocking-event
+//! escalation, work item SLA checks, outgoing webhooks and (with the `agents` feature) the
+//! orchestrator as supervised components of a single background process, so
+//! the TUI and REPL can attach over the API.
 
-use commander_daemon::DaemonService;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use commander_daemon::supervisor::wait_for_shutdown;
+use commander_daemon::{daemon_log_path, Component, DaemonError, DaemonService};
+use commander_core::ConfigChange;
+use commander_runtime::{
+    ConcurrencyLimits, Runtime, RuntimeConfig, RuntimeEvent, RuntimeExecutor, WebhookDispatcher,
+};
 
 use crate::cli::DaemonCommands;
 use crate::commands::Result;
 
+/// How often pending blocking events are checked for escalation.
+const ESCALATION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often work item deadlines are checked.
+const SLA_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often event segments are compacted.
+const EVENT_COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Arguments that make this binary run the daemon in the foreground.
+const FOREGROUND_ARGS: &[&str] = &["daemon", "start", "--foreground"];
+
 /// Execute daemon management commands.
 pub async fn execute(command: DaemonCommands) -> Result<()> {
     match command {
         DaemonCommands::Start { foreground } => {
-            println!("Starting daemon service...");
-
-            let service = DaemonService::new().await
-                .map_err(|e| format!("Failed to create daemon service: {}", e))?;
-
             if foreground {
                 println!("Running daemon in foreground mode. Press Ctrl+C to stop.");
-                service.run().await
-                    .map_err(|e| format!("Daemon service error: {}", e))?;
+                run_foreground().await
             } else {
-                service.daemonize().await
-                    .map_err(|e| format!("Failed to start daemon: {}", e))?;
+                println!("Starting daemon service...");
+                spawn_background().await?;
                 println!("Daemon service started successfully");
+                println!("Logs: {}", daemon_log_path().display());
+                Ok(())
             }
-
-            Ok(())
         }
 
         DaemonCommands::Stop => {
@@ -47,18 +70,569 @@ pub async fn execute(command: DaemonCommands) -> Result<()> {
 
         DaemonCommands::Restart => {
             println!("Restarting daemon service...");
-            let service = DaemonService::new().await
-                .map_err(|e| format!("Failed to create daemon service: {}", e))?;
-
-            service.restart().await
-                .map_err(|e| format!("Failed to restart daemon: {}", e))?;
-
+            match DaemonService::stop().await {
+                Ok(()) | Err(DaemonError::NotRunning) => {}
+                Err(e) => return Err(format!("Failed to stop daemon: {}", e).into()),
+            }
+            spawn_background().await?;
             println!("Daemon service restarted successfully");
             Ok(())
         }
     }
 }
 
+/// Re-run this binary as a detached foreground daemon.
+async fn spawn_background() -> Result<()> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate ai-commander binary: {}", e))?;
+    DaemonService::spawn_background(&exe, FOREGROUND_ARGS).await
+        .map_err(|e| format!("Failed to start daemon: {}", e))?;
+    Ok(())
+}
+
+/// Run the daemon with all hosted components until SIGTERM/SIGINT.
+async fn run_foreground() -> Result<()> {
+    commander_core::connectivity::start_monitor();
+    let service = DaemonService::new().await
+        .map_err(|e| format!("Failed to create daemon service: {}", e))?;
+
+    let service = hosted_components().await
+        .into_iter()
+        .fold(service, DaemonService::with_component);
+
+    service.run().await
+        .map_err(|e| format!("Daemon service error: {}", e))?;
+    Ok(())
+}
+
+/// Build the supervised components for this machine.
+///
+/// The runtime (and the webhooks and orchestrator that consume its events)
+/// needs tmux;
+/// the Telegram bot needs a token and is skipped when the standalone bot
+/// is already running.
+async fn hosted_components() -> Vec<Component> {
+    let mut components = Vec::new();
+
+    let runtime_settings = commander_core::Settings::load(&commander_core::config_file())
+        .map(|settings| settings.runtime)
+        .unwrap_or_default();
+    let config = RuntimeConfig::default()
+        .with_record_sessions(commander_core::recording::record_sessions_enabled())
+        .with_reload_config(true)
+        .with_concurrency_limits(ConcurrencyLimits::from_settings(&runtime_settings))
+        .with_control_mode(runtime_settings.control_mode)
+        .with_state_dir(commander_core::config::state_dir());
+    let runtime = match Runtime::new(config).await {
+        Ok(runtime) => Some(Arc::new(RwLock::new(runtime))),
+        Err(e) => {
+            warn!(error = %e, "Runtime unavailable, API will run without it");
+            None
+        }
+    };
+
+    if let Some(runtime) = &runtime {
+        components.push(runtime_component(Arc::clone(runtime)));
+    }
+    #[cfg(feature = "agents")]
+    let agents = match &runtime {
+        Some(_) => orchestrator::create().await,
+        None => None,
+    };
+    components.push(api_component(
+        runtime.clone(),
+        #[cfg(feature = "agents")]
+        agents.clone(),
+    ));
+
+    if commander_core::get_secret(commander_core::secrets::TELEGRAM_BOT_TOKEN).is_none() {
+        info!("TELEGRAM_BOT_TOKEN not set, Telegram bot disabled");
+    } else if commander_telegram::daemon::is_running() {
+        warn!("Standalone Telegram bot is running, not hosting a second one");
+    } else {
+        components.push(telegram_component());
+    }
+
+    components.push(escalation_component());
+    components.push(sla_component());
+    components.push(event_compaction_component());
+
+    let hooks = commander_core::Settings::load(&commander_core::config_file())
+        .map(|settings| settings.webhooks)
+        .unwrap_or_default();
+    let webhooks = WebhookDispatcher::new(hooks);
+    if let Some(runtime) = &runtime {
+        let executor = runtime.read().await.executor();
+        components.push(webhooks_component(Arc::clone(&executor), webhooks.clone()));
+        components.push(activity_component(Arc::clone(&executor)));
+        #[cfg(feature = "agents")]
+        if let Some(agents) = agents {
+            components.push(orchestrator::component(agents, executor, webhooks));
+        }
+    }
+
+    components
+}
+
+/// Runs the output poller; stops all instances on shutdown.
+fn runtime_component(runtime: Arc<RwLock<Runtime>>) -> Component {
+    Component::new("runtime", move |shutdown| {
+        let runtime = Arc::clone(&runtime);
+        async move {
+            runtime.write().await.start().await.map_err(|e| e.to_string())?;
+            wait_for_shutdown(shutdown).await;
+            runtime.write().await.shutdown().await.map_err(|e| e.to_string())
+        }
+    })
+}
+
+/// Serves the REST API (and web UI) the thin clients attach to.
+fn api_component(
+    runtime: Option<Arc<RwLock<Runtime>>>,
+    #[cfg(feature = "agents")] agents: Option<Arc<RwLock<commander_orchestrator::AgentOrchestrator>>>,
+) -> Component {
+    use commander_adapters::AdapterRegistry;
+    use commander_api::{ApiConfig, AppState};
+    use commander_events::EventManager;
+    use commander_persistence::{EventStore, WorkStore};
+    use commander_work::WorkQueue;
+
+    Component::new("api", move |shutdown| {
+        let runtime = runtime.clone();
+        #[cfg(feature = "agents")]
+        let agents = agents.clone();
+        async move {
+            let state_dir = commander_core::config::state_dir();
+            let config = ApiConfig::default();
+            let mut state = AppState::new(
+                config.clone(),
+                None,
+                EventManager::new(EventStore::new(&state_dir)),
+                WorkQueue::new(WorkStore::new(&state_dir)),
+                AdapterRegistry::new(),
+            );
+            if let Some(runtime) = runtime {
+                state = state.with_shared_runtime(runtime);
+            }
+            #[cfg(feature = "agents")]
+            if let Some(agents) = agents {
+                let memory = Arc::clone(agents.read().await.memory_store());
+                state = state
+                    .with_memory(memory, commander_memory::create_embedder())
+                    .with_orchestrator(agents);
+            }
+
+            commander_api::serve_with_shutdown(config, state, wait_for_shutdown(shutdown))
+                .await
+                .map_err(|e| format!("API server error: {}", e))
+        }
+    })
+}
+
+/// Polls Telegram for updates.
+fn telegram_component() -> Component {
+    Component::new("telegram", |shutdown| async move {
+        let bot = commander_telegram::TelegramBot::new(&commander_core::config::state_dir())
+            .map_err(|e| e.to_string())?;
+        tokio::select! {
+            result = bot.start_polling() => result.map_err(|e| e.to_string()),
+            // A stuck bot is restarted like a failed one, with backoff
+            age = commander_telegram::heartbeat::wait_until_stale() => {
+                Err(format!("heartbeat stale for {}s", age.as_secs()))
+            }
+            _ = wait_for_shutdown(shutdown) => Ok(()),
+        }
+    })
+}
+
+/// Escalates blocking events nobody acknowledged: TUI modal, then
+/// Telegram ping, repeating until `/ack`.
+///
+/// Events are re-read from disk every tick so acknowledgements made by the
+/// TUI or the Telegram bot are picked up.
+fn escalation_component() -> Component {
+    use commander_events::{EscalationChannel, EscalationPolicy, EscalationState, Escalator};
+    use commander_persistence::EventStore;
+
+    Component::new("escalation", |shutdown| async move {
+        let store = EventStore::new(commander_core::config::state_dir());
+        let state_file = commander_core::escalations_file();
+        let mut escalator =
+            Escalator::with_state(EscalationPolicy::default(), EscalationState::load(&state_file));
+        let mut ticker = tokio::time::interval(ESCALATION_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = wait_for_shutdown(shutdown.clone()) => return Ok(()),
+                _ = ticker.tick() => {}
+            }
+
+            let events = match store.list_all_events() {
+                Ok(events) => events,
+                Err(e) => {
+                    warn!(error = %e, "Failed to load events for escalation");
+                    continue;
+                }
+            };
+            for escalation in escalator.tick(&events, chrono::Utc::now()) {
+                info!(
+                    event = %escalation.event_id,
+                    channel = %escalation.channel,
+                    attempt = escalation.attempt,
+                    "Escalating blocking event"
+                );
+                if escalation.channel == EscalationChannel::Telegram {
+                    if let Err(e) = commander_core::push_critical_notification(
+                        format!("⏰ {}", escalation.summary()),
+                        Some(escalation.project_id.clone()),
+                    ) {
+                        warn!(error = %e, "Failed to queue escalation");
+                    }
+                }
+            }
+            if let Err(e) = escalator.state().save(&state_file) {
+                warn!(error = %e, "Failed to save escalation state");
+            }
+        }
+    })
+}
+
+/// Records the SLA level of work items with deadlines and announces each
+/// rise: an event for every level, and a Telegram ping once overdue.
+///
+/// Work items are re-read from disk every tick so new deadlines and
+/// completions are picked up.
+fn sla_component() -> Component {
+    use commander_events::EventManager;
+    use commander_models::{Event, EventType, SlaStatus};
+    use commander_persistence::{EventStore, WorkStore};
+    use commander_work::{SlaPolicy, WorkQueue};
+
+    Component::new("sla", |shutdown| async move {
+        let state_dir = commander_core::config::state_dir();
+        let events = EventManager::new(EventStore::new(&state_dir));
+        let policy = SlaPolicy::default();
+        let mut ticker = tokio::time::interval(SLA_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = wait_for_shutdown(shutdown.clone()) => return Ok(()),
+                _ = ticker.tick() => {}
+            }
+
+            let queue = WorkQueue::new(WorkStore::new(&state_dir));
+            if let Err(e) = queue.load_all() {
+                warn!(error = %e, "Failed to load work items for SLA check");
+                continue;
+            }
+            let now = chrono::Utc::now();
+            let escalations = match queue.check_sla(&policy, now) {
+                Ok(escalations) => escalations,
+                Err(e) => {
+                    warn!(error = %e, "Failed to check work item deadlines");
+                    continue;
+                }
+            };
+            for escalation in escalations {
+                let title = escalation.title(now);
+                info!(work = %escalation.item.id, status = ?escalation.status, "Work item SLA escalated");
+
+                let mut event = Event::new(escalation.item.project_id.clone(), EventType::WorkOverdue, &title);
+                event.priority = escalation.priority();
+                event.content = Some(escalation.item.content.clone());
+                event.context.insert("work_id".into(), escalation.item.id.as_str().into());
+                if let Err(e) = events.emit(event) {
+                    warn!(error = %e, "Failed to record SLA event");
+                }
+
+                if escalation.status >= SlaStatus::Overdue {
+                    if let Err(e) = commander_core::push_critical_notification(
+                        format!("⏰ {}", title),
+                        Some(escalation.item.project_id.to_string()),
+                    ) {
+                        warn!(error = %e, "Failed to queue SLA notification");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Compacts the event store, dropping superseded versions and events past
+/// the `[events]` retention of `config.toml`.
+fn event_compaction_component() -> Component {
+    use commander_persistence::{EventStore, RetentionPolicy};
+
+    Component::new("event-compaction", |shutdown| async move {
+        let store = EventStore::new(commander_core::config::state_dir());
+        let mut ticker = tokio::time::interval(EVENT_COMPACTION_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = wait_for_shutdown(shutdown.clone()) => return Ok(()),
+                _ = ticker.tick() => {}
+            }
+
+            let settings = commander_core::Settings::load(&commander_core::config_file())
+                .map(|settings| settings.events)
+                .unwrap_or_default();
+            let policy = RetentionPolicy {
+                max_age: (settings.retention_days > 0)
+                    .then(|| chrono::Duration::days(i64::from(settings.retention_days))),
+                keep_unresolved: settings.keep_unresolved,
+            };
+            match store.compact(&policy, chrono::Utc::now()) {
+                Ok(report) if report.segments_rewritten + report.segments_removed > 0 => info!(
+                    rewritten = report.segments_rewritten,
+                    removed = report.segments_removed,
+                    expired = report.events_removed,
+                    dropped = report.lines_dropped,
+                    "Compacted event segments"
+                ),
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Failed to compact event store"),
+            }
+        }
+    })
+}
+
+/// POSTs runtime events to the `[[webhooks]]` of `config.toml`, reloading
+/// the list when the file changes.
+fn webhooks_component(executor: Arc<RuntimeExecutor>, webhooks: WebhookDispatcher) -> Component {
+    Component::new("webhooks", move |shutdown| {
+        let mut events = executor.subscribe();
+        let webhooks = webhooks.clone();
+        async move {
+            let config_changes = commander_core::subscribe_config_changes();
+            let mut config_ticker = tokio::time::interval(commander_core::settings::CONFIG_POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = wait_for_shutdown(shutdown.clone()) => return Ok(()),
+                    _ = config_ticker.tick() => {
+                        for change in config_changes.try_iter() {
+                            if let ConfigChange::Webhooks(hooks) = change {
+                                info!(count = hooks.len(), "Webhooks reloaded");
+                                webhooks.set_hooks(hooks);
+                            }
+                        }
+                    }
+                    event = events.recv() => match event {
+                        Ok(event) => webhooks.send_event(&event),
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(skipped, "Webhooks lagged behind runtime events");
+                        }
+                        Err(RecvError::Closed) => return Err("runtime event stream closed".to_string()),
+                    },
+                }
+            }
+        }
+    })
+}
+
+/// Records session starts and stops as events, for `commander report`.
+///
+/// The events are stored resolved: they are history, not something to act on.
+fn activity_component(executor: Arc<RuntimeExecutor>) -> Component {
+    use commander_models::{Event, EventStatus, EventType};
+    use commander_persistence::EventStore;
+
+    Component::new("activity", move |shutdown| {
+        let mut events = executor.subscribe();
+        async move {
+            let store = EventStore::new(commander_core::config::state_dir());
+            loop {
+                let event = tokio::select! {
+                    _ = wait_for_shutdown(shutdown.clone()) => return Ok(()),
+                    event = events.recv() => event,
+                };
+                let mut recorded = match event {
+                    Ok(RuntimeEvent::InstanceStarted { project_id, session }) => {
+                        let mut event = Event::new(project_id, EventType::InstanceReady, "Session started");
+                        event.context.insert("session".into(), session.into());
+                        event
+                    }
+                    Ok(RuntimeEvent::InstanceStopped { project_id }) => {
+                        Event::new(project_id, EventType::InstanceStopped, "Session stopped")
+                    }
+                    Ok(RuntimeEvent::StartQueued { project_id, position }) => {
+                        let title = format!("Session start queued (position {})", position);
+                        Event::new(project_id, EventType::InstanceStarting, title)
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Activity recorder lagged behind runtime events");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return Err("runtime event stream closed".to_string()),
+                };
+                recorded.status = EventStatus::Resolved;
+                if let Err(e) = store.save_event(&recorded) {
+                    warn!(error = %e, "Failed to record session activity");
+                }
+            }
+        }
+    })
+}
+
+#[cfg(feature = "agents")]
+mod orchestrator {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::broadcast::error::RecvError;
+    use tokio::sync::RwLock;
+    use tokio::time::{Instant, MissedTickBehavior};
+    use tracing::{debug, info, warn};
+
+    use commander_daemon::supervisor::wait_for_shutdown;
+    use commander_core::ConfigChange;
+    use commander_daemon::Component;
+    use commander_events::{EventFilter, EventManager};
+    use commander_models::{Event, EventStatus, EventType, ProjectId};
+    use commander_orchestrator::AgentOrchestrator;
+    use commander_persistence::EventStore;
+    use commander_runtime::{RuntimeEvent, RuntimeExecutor, WebhookDispatcher};
+
+    /// Adapter type assumed for runtime-managed sessions.
+    const ADAPTER: &str = "claude_code";
+
+    /// Time between orchestrator maintenance runs (memory consolidation).
+    const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+    /// Create the orchestrator shared by this component and the API.
+    pub(super) async fn create() -> Option<Arc<RwLock<AgentOrchestrator>>> {
+        match AgentOrchestrator::new().await {
+            Ok(orchestrator) => Some(Arc::new(RwLock::new(orchestrator))),
+            Err(e) => {
+                warn!(error = %e, "Agent orchestrator unavailable");
+                None
+            }
+        }
+    }
+
+    /// Feeds runtime events to the session agents, queues notifications for
+    /// changes that need the user and records them as blocking events. Every
+    /// change notification is also sent to the webhooks.
+    pub(super) fn component(
+        orchestrator: Arc<RwLock<AgentOrchestrator>>,
+        executor: Arc<RuntimeExecutor>,
+        webhooks: WebhookDispatcher,
+    ) -> Component {
+        Component::new("orchestrator", move |shutdown| {
+            let mut events = executor.subscribe();
+            let webhooks = webhooks.clone();
+            let orchestrator = Arc::clone(&orchestrator);
+            async move {
+                let events_manager = EventManager::new(EventStore::new(commander_core::config::state_dir()));
+                let mut maintenance =
+                    tokio::time::interval_at(Instant::now() + MAINTENANCE_INTERVAL, MAINTENANCE_INTERVAL);
+                maintenance.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                let config_changes = commander_core::subscribe_config_changes();
+                let mut config_ticker = tokio::time::interval(commander_core::settings::CONFIG_POLL_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = wait_for_shutdown(shutdown.clone()) => return Ok(()),
+                        _ = config_ticker.tick() => {
+                            let changes: Vec<ConfigChange> = config_changes.try_iter().collect();
+                            for change in changes {
+                                if let ConfigChange::Agents(settings) = change {
+                                    orchestrator.write().await.apply_agent_settings(&settings);
+                                }
+                            }
+                        }
+                        _ = maintenance.tick() => {
+                            if let Err(e) = orchestrator.write().await.run_maintenance().await {
+                                warn!(error = %e, "Orchestrator maintenance failed");
+                            }
+                        }
+                        event = events.recv() => match event {
+                            Ok(event) => {
+                                let mut orchestrator = orchestrator.write().await;
+                                handle_event(&mut orchestrator, &events_manager, &webhooks, event).await
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                warn!(skipped, "Orchestrator lagged behind runtime events");
+                            }
+                            Err(RecvError::Closed) => return Err("runtime event stream closed".to_string()),
+                        },
+                    }
+                }
+            }
+        })
+    }
+
+    async fn handle_event(
+        orchestrator: &mut AgentOrchestrator,
+        events: &EventManager,
+        webhooks: &WebhookDispatcher,
+        event: RuntimeEvent,
+    ) {
+        match event {
+            RuntimeEvent::OutputReceived { project_id, output } => {
+                let session = project_id.as_str();
+                let result = orchestrator.process_output_change(session, ADAPTER, &output).await;
+                if let Ok(Some(notification)) = &result {
+                    webhooks.send_change(notification);
+                }
+                match result {
+                    Ok(Some(notification)) if notification.requires_action => {
+                        info!(session = %session, summary = %notification.summary, "Session needs attention");
+                        emit_blocking(events, &project_id, EventType::DecisionNeeded, &notification.summary);
+                        if let Err(e) = commander_core::push_notification(
+                            notification.summary,
+                            Some(session.to_string()),
+                        ) {
+                            warn!(error = %e, "Failed to queue notification");
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => debug!(session = %session, error = %e, "Output analysis failed"),
+                }
+            }
+            RuntimeEvent::PaneOutput { project_id, pane, output } => {
+                if let Err(e) = orchestrator.record_pane_output(project_id.as_str(), ADAPTER, &pane, &output) {
+                    debug!(pane = %pane, error = %e, "Failed to record pane output");
+                }
+            }
+            RuntimeEvent::FilesChanged { project_id, paths } => {
+                let files = paths.iter().map(|p| p.display().to_string());
+                if let Err(e) = orchestrator.record_files_changed(project_id.as_str(), ADAPTER, files) {
+                    debug!(error = %e, "Failed to record file changes");
+                }
+            }
+            RuntimeEvent::InstanceStopped { project_id } => {
+                orchestrator.remove_session(project_id.as_str());
+            }
+            RuntimeEvent::Error { project_id, error } => {
+                emit_blocking(events, &project_id, EventType::Error, &error);
+            }
+            RuntimeEvent::SessionCrashed { project_id, session } => {
+                let title = format!("Session {} crashed", session);
+                emit_blocking(events, &project_id, EventType::Error, &title);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a blocking event unless one of the same type is already
+    /// pending for the project.
+    fn emit_blocking(events: &EventManager, project_id: &ProjectId, event_type: EventType, title: &str) {
+        let pending = EventFilter::new()
+            .with_project_id(project_id.clone())
+            .with_event_type(event_type)
+            .with_status(EventStatus::Pending);
+        if let Err(e) = events.load_project(project_id) {
+            debug!(error = %e, "Failed to load project events");
+        }
+        if !events.list(Some(pending)).is_empty() {
+            return;
+        }
+        if let Err(e) = events.emit(Event::new(project_id.clone(), event_type, title)) {
+            warn!(error = %e, "Failed to record blocking event");
+        }
+    }
+}
+
 /// Generate a pairing code.
 pub async fn generate_pairing_code(session_id: Option<String>) -> Result<()> {
     println!("Generating pairing code...");
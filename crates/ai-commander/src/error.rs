@@ -0,0 +1,77 @@
+//! Mapping of command errors to [`CommanderError`].
+//!
+//! Commands return boxed errors from many crates. `classify` recovers the
+//! typed error so `main` can print a hint with it and exit with the code of
+//! its category.
+
+use std::error::Error;
+
+use commander_core::settings::ConfigError;
+use commander_core::CommanderError;
+use commander_persistence::PersistenceError;
+use commander_tmux::TmuxError;
+
+/// Map a boxed command error to a [`CommanderError`].
+///
+/// Errors that are not recognized, such as plain `String` messages, are
+/// runtime errors.
+pub fn classify(err: Box<dyn Error>) -> CommanderError {
+    downcast::<CommanderError>(err, |e| e)
+        .or_else(|err| downcast::<TmuxError>(err, tmux_error))
+        .or_else(|err| downcast::<PersistenceError>(err, persistence_error))
+        .or_else(|err| downcast::<ConfigError>(err, CommanderError::from))
+        .or_else(|err| downcast::<commander_runtime::RuntimeError>(err, CommanderError::from))
+        .or_else(|err| downcast::<commander_daemon::DaemonError>(err, CommanderError::from))
+        .or_else(|err| downcast::<commander_agent::AgentError>(err, CommanderError::from))
+        .or_else(|err| downcast::<commander_telegram::TelegramError>(err, CommanderError::from))
+        .or_else(|err| downcast::<commander_telegram::daemon::DaemonError>(err, CommanderError::from))
+        .or_else(|err| downcast::<std::io::Error>(err, CommanderError::from))
+        .unwrap_or_else(|err| CommanderError::Runtime(err.to_string()))
+}
+
+/// Map `err` with `map` if it is a `T`, or give it back.
+fn downcast<T: Error + 'static>(
+    err: Box<dyn Error>,
+    map: impl FnOnce(T) -> CommanderError,
+) -> Result<CommanderError, Box<dyn Error>> {
+    err.downcast::<T>().map(|e| map(*e))
+}
+
+/// tmux errors, except I/O errors that refuse access.
+fn tmux_error(err: TmuxError) -> CommanderError {
+    match err {
+        TmuxError::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied => e.into(),
+        _ => CommanderError::Tmux(err.to_string()),
+    }
+}
+
+/// Storage errors: reading or writing state is refused or fails.
+fn persistence_error(err: PersistenceError) -> CommanderError {
+    match err {
+        PersistenceError::ReadError { ref source, .. }
+        | PersistenceError::WriteError { ref source, .. }
+        | PersistenceError::DirectoryError { ref source, .. }
+            if source.kind() == std::io::ErrorKind::PermissionDenied =>
+        {
+            CommanderError::Permission(err.to_string())
+        }
+        _ => CommanderError::Runtime(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let err: Box<dyn Error> = Box::new(TmuxError::NotFound);
+        assert_eq!(classify(err).exit_code(), 71);
+
+        let err: Box<dyn Error> = Box::new(CommanderError::Config("bad key".into()));
+        assert_eq!(classify(err), CommanderError::Config("bad key".into()));
+
+        let err: Box<dyn Error> = "something broke".into();
+        assert_eq!(classify(err), CommanderError::Runtime("something broke".into()));
+    }
+}
@@ -0,0 +1,126 @@
+//! Eval commands for the CLI.
+//!
+//! `eval run` replays the golden transcripts in `~/.ai-commander/evals/golden/`
+//! against the current agent prompts and the models configured in
+//! `config.toml`, scores them with an LLM judge and compares the results with
+//! the saved baseline. It exits non-zero when a case fails or regresses, so it
+//! can gate prompt and model changes in CI (use `--json` for machine-readable
+//! output). Started offline, it waits for the network before running.
+
+use std::path::Path;
+use std::time::Duration;
+
+use commander_agent::eval::golden::EvalDataset;
+use commander_agent::eval::{
+    load_datasets, run_evals, EvalOptions, EvalReport, LlmJudge, LlmResponder,
+};
+use commander_agent::OpenRouterClient;
+use commander_core::{connectivity, Settings};
+use tracing::warn;
+
+use crate::cli::EvalCommands;
+use crate::commands::Result;
+
+/// How long `eval run` waits for the network when started offline.
+const OFFLINE_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// Execute eval commands.
+pub fn execute(command: EvalCommands) -> Result<()> {
+    match command {
+        EvalCommands::Run {
+            dataset,
+            template,
+            baseline,
+            save_baseline,
+            json,
+            judge_model,
+            min_score,
+        } => {
+            let evals_dir = commander_core::evals_dir();
+            let dataset = dataset.unwrap_or_else(|| evals_dir.join("golden"));
+            let baseline_path = baseline.unwrap_or_else(|| evals_dir.join("baseline.json"));
+            let options = EvalOptions {
+                min_score,
+                template: template.map(|t| t.parse()).transpose()?,
+            };
+
+            // Wait out a network outage instead of failing every case
+            if !connectivity::wait_until_online(Duration::ZERO) {
+                eprintln!(
+                    "Offline; waiting up to {} minutes for the network...",
+                    OFFLINE_WAIT.as_secs() / 60
+                );
+                if !connectivity::wait_until_online(OFFLINE_WAIT) {
+                    return Err("Offline: the model provider can't be reached".into());
+                }
+            }
+
+            let rt = tokio::runtime::Runtime::new()
+                .map_err(|e| format!("Failed to create async runtime: {}", e))?;
+            let mut report = rt.block_on(run(&dataset, judge_model, &options))?;
+
+            if baseline_path.exists() {
+                let baseline = EvalReport::load(&baseline_path)?;
+                report.compare(&baseline);
+            }
+            if save_baseline {
+                report.save(&baseline_path)?;
+            }
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.format_text());
+                println!(
+                    "\n{} cases, {} failed, {} regressed",
+                    report.cases.len(),
+                    report.failures(),
+                    report.regressions()
+                );
+                if save_baseline {
+                    println!("Saved baseline to {}", baseline_path.display());
+                }
+            }
+
+            if report.succeeded() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} failed, {} regressed",
+                    report.failures(),
+                    report.regressions()
+                )
+                .into())
+            }
+        }
+    }
+}
+
+async fn run(dataset: &Path, judge_model: Option<String>, options: &EvalOptions) -> Result<EvalReport> {
+    let datasets = if dataset.is_dir() {
+        load_datasets(dataset)?
+    } else {
+        vec![EvalDataset::load(dataset)?]
+    };
+    if datasets.iter().all(|d| d.cases.is_empty()) {
+        return Err(format!("No golden cases in {}", dataset.display()).into());
+    }
+
+    // Replay with the models the agents currently use
+    let agents = match Settings::load(&commander_core::config_file()) {
+        Ok(settings) => settings.agents,
+        Err(e) => {
+            warn!(error = %e, "Ignoring invalid config.toml");
+            Default::default()
+        }
+    };
+
+    let client = OpenRouterClient::from_env()?;
+    let responder = LlmResponder::new(client.clone()).with_models(agents.user_model, agents.session_model);
+    let judge = LlmJudge::new(
+        client,
+        judge_model.unwrap_or_else(|| LlmJudge::DEFAULT_MODEL.to_string()),
+    );
+
+    Ok(run_evals(&datasets, &responder, &judge, options).await?)
+}
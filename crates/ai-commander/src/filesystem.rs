@@ -69,6 +69,21 @@ pub enum FsCommand {
     Pwd,
 }
 
+impl FsCommand {
+    /// Whether the command changes the filesystem.
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            FsCommand::Write { .. }
+                | FsCommand::Create { .. }
+                | FsCommand::Move { .. }
+                | FsCommand::Copy { .. }
+                | FsCommand::Delete { .. }
+                | FsCommand::Mkdir { .. }
+        )
+    }
+}
+
 /// Parse a natural language command into a filesystem operation.
 pub fn parse_command(input: &str, working_dir: &Path) -> Option<FsCommand> {
     let input = input.trim();
@@ -280,6 +295,58 @@ fn resolve_path(path: &str, working_dir: &Path) -> String {
     }
 }
 
+/// Commands whose arguments are paths, for tab completion.
+pub const PATH_COMMANDS: &[&str] = &[
+    "ls", "list", "dir", "cat", "read", "show", "view", "type", "head", "tail", "touch", "create",
+    "mv", "move", "rename", "cp", "copy", "rm", "delete", "remove", "del", "mkdir", "makedir",
+    "stat", "info", "file",
+];
+
+/// Complete a partial path relative to the project `root`.
+///
+/// Returns the matching entries of the partial path's directory, as paths
+/// relative to `root` in name order, directories with a trailing `/`. Hidden
+/// entries are only offered once the name typed starts with a dot. Absolute
+/// paths and paths leaving the project are not completed.
+pub fn complete_path(partial: &str, root: &Path) -> Vec<String> {
+    let typed = Path::new(partial);
+    if typed.is_absolute() || typed.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        return Vec::new();
+    }
+
+    let (dir, name) = match partial.rfind('/') {
+        Some(i) => (&partial[..=i], &partial[i + 1..]),
+        None => ("", partial),
+    };
+    let Ok(entries) = fs::read_dir(root.join(dir)) else {
+        return Vec::new();
+    };
+
+    let mut matches: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let entry_name = entry.file_name().to_string_lossy().to_string();
+            if !entry_name.starts_with(name) || (entry_name.starts_with('.') && !name.starts_with('.')) {
+                return None;
+            }
+            let suffix = if entry.path().is_dir() { "/" } else { "" };
+            Some(format!("{}{}{}", dir, entry_name, suffix))
+        })
+        .collect();
+    matches.sort();
+    matches
+}
+
+/// Execute a filesystem command in a read-only session.
+///
+/// Commands that would change the filesystem are refused.
+pub fn execute_read_only(cmd: &FsCommand, working_dir: &Path) -> FsResult {
+    if cmd.is_mutating() {
+        return FsResult::err("Read-only session: commands that change files are disabled");
+    }
+    execute(cmd, working_dir)
+}
+
 /// Execute a filesystem command.
 pub fn execute(cmd: &FsCommand, working_dir: &Path) -> FsResult {
     match cmd {
@@ -715,6 +782,43 @@ mod tests {
         assert!(matches!(cmd, Some(FsCommand::Read { path, .. }) if path.contains("README")));
     }
 
+    #[test]
+    fn test_is_mutating() {
+        assert!(!parse_command("ls", &test_dir()).unwrap().is_mutating());
+        assert!(!parse_command("cat README.md", &test_dir()).unwrap().is_mutating());
+        assert!(parse_command("mkdir -p src/new", &test_dir()).unwrap().is_mutating());
+        assert!(parse_command("rm old.txt", &test_dir()).unwrap().is_mutating());
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/tui")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        fs::write(dir.path().join(".env"), "").unwrap();
+        fs::write(dir.path().join("README.md"), "").unwrap();
+
+        assert_eq!(complete_path("", dir.path()), ["README.md", "src/"]);
+        assert_eq!(complete_path("src/", dir.path()), ["src/lib.rs", "src/main.rs", "src/tui/"]);
+        assert_eq!(complete_path("src/m", dir.path()), ["src/main.rs"]);
+        assert_eq!(complete_path(".e", dir.path()), [".env"]);
+        assert!(complete_path("../", dir.path()).is_empty());
+        assert!(complete_path("/etc/", dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_execute_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("new");
+        let mkdir = FsCommand::Mkdir { path: target.to_string_lossy().to_string(), parents: false };
+
+        let result = execute_read_only(&mkdir, dir.path());
+        assert!(!result.success);
+        assert!(!target.exists());
+        assert!(execute_read_only(&FsCommand::Pwd, dir.path()).success);
+    }
+
     #[test]
     fn test_parse_mkdir() {
         let cmd = parse_command("mkdir -p src/new", &test_dir());
@@ -0,0 +1,287 @@
+//! Garbage collection of what deleted projects leave behind.
+//!
+//! [`find_orphans`] cross-references the `commander-*` tmux sessions with the
+//! state store and lists the sessions whose project was removed or whose
+//! directory is gone, PID files of processes that no longer run, and
+//! `.worktrees/` checkouts whose session has ended. `commander gc` destroys
+//! them after confirmation; the TUI looks for them at startup and reports or
+//! collects them according to `[gc] on_startup` in `config.toml`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use commander_models::Project;
+use commander_persistence::StateStore;
+
+use crate::client::SessionBackend;
+
+/// Prefix of the tmux sessions Commander creates for projects.
+pub const SESSION_PREFIX: &str = "commander-";
+
+/// Directory inside a project holding per-session git worktrees.
+const WORKTREES_DIR: &str = ".worktrees";
+
+/// A tmux session without a live project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanSession {
+    pub name: String,
+    pub reason: &'static str,
+}
+
+/// A session worktree whose tmux session has ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadWorktree {
+    /// Repository the worktree belongs to.
+    pub repo: PathBuf,
+    pub path: PathBuf,
+}
+
+/// Everything a collection would remove.
+#[derive(Debug, Default)]
+pub struct Orphans {
+    pub sessions: Vec<OrphanSession>,
+    pub pid_files: Vec<PathBuf>,
+    pub worktrees: Vec<DeadWorktree>,
+}
+
+impl Orphans {
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty() && self.pid_files.is_empty() && self.worktrees.is_empty()
+    }
+
+    /// One line per orphan.
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for session in &self.sessions {
+            lines.push(format!("tmux session {} ({})", session.name, session.reason));
+        }
+        for path in &self.pid_files {
+            lines.push(format!("stale PID file {}", path.display()));
+        }
+        for worktree in &self.worktrees {
+            lines.push(format!("worktree {} (session ended)", worktree.path.display()));
+        }
+        lines
+    }
+
+    /// Short summary, e.g. "2 tmux sessions, 1 PID file".
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            (self.sessions.len(), "tmux session"),
+            (self.pid_files.len(), "PID file"),
+            (self.worktrees.len(), "worktree"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, noun)| format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" }))
+        .collect();
+        parts.join(", ")
+    }
+}
+
+/// Outcome of [`collect`].
+#[derive(Debug, Default)]
+pub struct GcReport {
+    pub removed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Find orphaned sessions, stale PID files in `pid_dir` and dead worktrees.
+///
+/// Without tmux only PID files are checked: sessions and worktrees cannot be
+/// told apart from live ones.
+pub fn find_orphans(store: &StateStore, tmux: Option<&dyn SessionBackend>, pid_dir: &Path) -> Orphans {
+    let mut orphans = Orphans {
+        pid_files: stale_pid_files(pid_dir),
+        ..Default::default()
+    };
+    let Some(tmux) = tmux else {
+        return orphans;
+    };
+    let (Ok(projects), Ok(sessions)) = (store.load_all_projects(), tmux.list_sessions()) else {
+        return orphans;
+    };
+
+    let projects: Vec<Project> = projects.into_values().collect();
+    let session_names: Vec<String> = sessions.into_iter().map(|s| s.name).collect();
+    orphans.sessions = orphan_sessions(&projects, &session_names);
+    orphans.worktrees = dead_worktrees(&projects, &session_names);
+    orphans
+}
+
+/// `commander-*` sessions whose project is not in `projects`, or whose
+/// project directory no longer exists.
+pub fn orphan_sessions(projects: &[Project], sessions: &[String]) -> Vec<OrphanSession> {
+    sessions
+        .iter()
+        .filter_map(|name| {
+            let bare = name.strip_prefix(SESSION_PREFIX)?;
+            let project = projects.iter().find(|p| p.name == bare || p.session_name() == bare);
+            let reason = match project {
+                None => "project not registered",
+                Some(p) if !Path::new(&p.path).is_dir() => "project directory missing",
+                Some(_) => return None,
+            };
+            Some(OrphanSession { name: name.clone(), reason })
+        })
+        .collect()
+}
+
+/// `*.pid` files in `dir` whose process is not running.
+pub fn stale_pid_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut stale: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "pid"))
+        .filter(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|content| content.trim().parse::<u32>().ok())
+                .is_none_or(|pid| !is_process_running(pid))
+        })
+        .collect();
+    stale.sort();
+    stale
+}
+
+/// Worktrees in `<project>/.worktrees/` with no tmux session of their name.
+fn dead_worktrees(projects: &[Project], sessions: &[String]) -> Vec<DeadWorktree> {
+    let mut dead = Vec::new();
+    for project in projects {
+        let repo = PathBuf::from(&project.path);
+        let Ok(entries) = fs::read_dir(repo.join(WORKTREES_DIR)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            if path.is_dir() && !sessions.contains(&name) {
+                dead.push(DeadWorktree { repo: repo.clone(), path });
+            }
+        }
+    }
+    dead.sort_by(|a, b| a.path.cmp(&b.path));
+    dead
+}
+
+/// Remove everything in `orphans`.
+///
+/// Worktrees are removed with `git worktree remove`, which refuses to drop
+/// uncommitted changes; their branches are kept.
+pub fn collect(orphans: &Orphans, tmux: Option<&dyn SessionBackend>) -> GcReport {
+    let mut report = GcReport::default();
+    for session in &orphans.sessions {
+        let result = match tmux {
+            Some(tmux) => tmux.destroy_session(&session.name).map_err(|e| e.to_string()),
+            None => Err("tmux not available".to_string()),
+        };
+        match result {
+            Ok(()) => report.removed += 1,
+            Err(e) => report.errors.push(format!("tmux session {}: {}", session.name, e)),
+        }
+    }
+    for path in &orphans.pid_files {
+        match fs::remove_file(path) {
+            Ok(()) => report.removed += 1,
+            Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    for worktree in &orphans.worktrees {
+        match remove_worktree(worktree) {
+            Ok(()) => report.removed += 1,
+            Err(e) => report.errors.push(format!("worktree {}: {}", worktree.path.display(), e)),
+        }
+    }
+    report
+}
+
+fn remove_worktree(worktree: &DeadWorktree) -> Result<(), String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&worktree.repo)
+        .args(["worktree", "remove"])
+        .arg(&worktree.path)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Check whether a process with `pid` exists.
+fn is_process_running(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(unix))]
+    {
+        // Without a way to check, keep the file
+        let _ = pid;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_orphan_sessions() {
+        let dir = tempdir().unwrap();
+        let live = Project::new(dir.path().to_string_lossy(), "api");
+        let gone = Project::new("/nonexistent/commander-gc-test", "web");
+        let sessions: Vec<String> = ["commander-api", "commander-web", "commander-old", "scratch"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let orphans = orphan_sessions(&[live, gone], &sessions);
+        assert_eq!(
+            orphans,
+            vec![
+                OrphanSession { name: "commander-web".into(), reason: "project directory missing" },
+                OrphanSession { name: "commander-old".into(), reason: "project not registered" },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale_pid_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("live.pid"), std::process::id().to_string()).unwrap();
+        fs::write(dir.path().join("garbage.pid"), "not a pid").unwrap();
+        fs::write(dir.path().join("notes.txt"), "1").unwrap();
+
+        assert_eq!(stale_pid_files(dir.path()), vec![dir.path().join("garbage.pid")]);
+    }
+
+    #[test]
+    fn test_dead_worktrees() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".worktrees/feature-a")).unwrap();
+        fs::create_dir_all(dir.path().join(".worktrees/feature-b")).unwrap();
+        let project = Project::new(dir.path().to_string_lossy(), "api");
+
+        let dead = dead_worktrees(&[project], &["feature-a".to_string()]);
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].path, dir.path().join(".worktrees/feature-b"));
+
+        let orphans = Orphans { worktrees: dead, ..Default::default() };
+        assert_eq!(orphans.summary(), "1 worktree");
+    }
+}
@@ -0,0 +1,208 @@
+//! GitHub commands for the CLI.
+//!
+//! `gh import` turns a repository's open (labelled) issues into work items of
+//! a project. `gh delegate <n>` checks out the issue's branch in the project,
+//! sends the issue to its session and comments on the issue; `gh sync`
+//! reports later state changes as comments; `gh complete <n>` completes the
+//! work item, pushes the branch and opens a pull request closing the issue.
+//! The token is read from the `GITHUB_TOKEN` secret.
+
+use std::path::Path;
+
+use commander_core::secrets::{get_secret, GITHUB_TOKEN};
+use commander_github::{
+    delegation_prompt, git, import_issues, linked_items, pull_request_body, report_progress, update_link,
+    GitHubClient, IssueLink, RepoRef,
+};
+use commander_models::{Project, ProjectId, WorkItem, WorkState};
+use commander_persistence::{StateStore, WorkStore};
+use commander_tmux::TmuxOrchestrator;
+use commander_work::WorkQueue;
+
+use crate::cli::GhCommands;
+use crate::commands::Result;
+
+/// Execute GitHub commands.
+pub async fn execute(store: &StateStore, state_dir: &Path, command: GhCommands) -> Result<()> {
+    let client = client()?;
+    match command {
+        GhCommands::Import { repo, labels, project } => import(&client, store, state_dir, &repo, &labels, &project).await,
+        GhCommands::Delegate { issue, project, session } => {
+            delegate(&client, store, state_dir, issue, &project, session.as_deref()).await
+        }
+        GhCommands::Sync { project } => sync(&client, store, state_dir, &project).await,
+        GhCommands::Complete {
+            issue,
+            project,
+            result,
+            base,
+            no_pr,
+        } => complete(&client, store, state_dir, issue, &project, result, base.as_deref(), no_pr).await,
+    }
+}
+
+fn client() -> Result<GitHubClient> {
+    let token = get_secret(GITHUB_TOKEN)
+        .ok_or("GITHUB_TOKEN is not set. Add it with: commander secrets set GITHUB_TOKEN")?;
+    Ok(GitHubClient::new(token))
+}
+
+/// The project's record, when it has one, and its loaded work queue.
+///
+/// Work items are kept under the project name, as `work graph` expects.
+fn open_queue(store: &StateStore, state_dir: &Path, project: &str) -> Result<(Option<Project>, ProjectId, WorkQueue)> {
+    let record = match store.find_project_by_name_or_alias(project)? {
+        Some(p) => Some(p),
+        None => store.load_project_optional(&ProjectId::from(project))?,
+    };
+    let project_id = ProjectId::from(record.as_ref().map_or(project, |p| p.name.as_str()));
+    let queue = WorkQueue::new(WorkStore::new(state_dir));
+    queue.load_project(&project_id)?;
+    Ok((record, project_id, queue))
+}
+
+/// The work item of issue `number` in the project.
+fn find_linked(queue: &WorkQueue, project_id: &ProjectId, number: u64) -> Result<(WorkItem, IssueLink)> {
+    let mut matches: Vec<_> = linked_items(queue, project_id)
+        .into_iter()
+        .filter(|(_, link)| link.number == number)
+        .collect();
+    match matches.len() {
+        0 => Err(format!("Issue #{} has not been imported into {}", number, project_id).into()),
+        1 => Ok(matches.remove(0)),
+        _ => Err(format!(
+            "Issue #{} was imported from several repositories into {}",
+            number, project_id
+        )
+        .into()),
+    }
+}
+
+async fn import(
+    client: &GitHubClient,
+    store: &StateStore,
+    state_dir: &Path,
+    repo: &str,
+    labels: &[String],
+    project: &str,
+) -> Result<()> {
+    let repo: RepoRef = repo.parse()?;
+    let (_, project_id, queue) = open_queue(store, state_dir, project)?;
+    let issues = client.list_issues(&repo, labels).await?;
+    let summary = import_issues(&queue, &project_id, &repo, &issues)?;
+
+    for id in &summary.imported {
+        if let Some(link) = queue.get(id).as_ref().and_then(IssueLink::of) {
+            println!("  #{} {}", link.number, link.title);
+        }
+    }
+    println!(
+        "Imported {} issues from {} into {} ({} already imported)",
+        summary.imported.len(),
+        repo,
+        project_id,
+        summary.skipped
+    );
+    Ok(())
+}
+
+async fn delegate(
+    client: &GitHubClient,
+    store: &StateStore,
+    state_dir: &Path,
+    number: u64,
+    project: &str,
+    session: Option<&str>,
+) -> Result<()> {
+    let (record, project_id, queue) = open_queue(store, state_dir, project)?;
+    let (item, mut link) = find_linked(&queue, &project_id, number)?;
+
+    let tmux = TmuxOrchestrator::new().map_err(|e| format!("Tmux not available: {}", e))?;
+    let session = match session {
+        Some(s) => s.to_string(),
+        None => [format!("commander-{}", project_id), project_id.to_string()]
+            .into_iter()
+            .find(|s| tmux.session_exists(s))
+            .ok_or_else(|| format!("No running session for {}. Start it or pass --session", project_id))?,
+    };
+    if !tmux.session_exists(&session) {
+        return Err(format!("Session not found: {}", session).into());
+    }
+
+    let branch = link.branch_name();
+    if let Some(p) = &record {
+        git::checkout_branch(Path::new(&p.path), &branch)?;
+    }
+    link.branch = Some(branch.clone());
+    link.session = Some(session.clone());
+    update_link(&queue, &item.id, &link)?;
+
+    let item = queue.start(&item.id)?;
+    tmux.send_line(&session, None, &delegation_prompt(&item, &link))?;
+    report_progress(client, &queue, &item).await?;
+    println!("Delegated {} to {} on branch {}", link.reference(), session, branch);
+    Ok(())
+}
+
+async fn sync(client: &GitHubClient, store: &StateStore, state_dir: &Path, project: &str) -> Result<()> {
+    let (_, project_id, queue) = open_queue(store, state_dir, project)?;
+    let mut reported = 0;
+    for (item, link) in linked_items(&queue, &project_id) {
+        if report_progress(client, &queue, &item).await? {
+            println!("  {} {:?}", link.reference(), item.state);
+            reported += 1;
+        }
+    }
+    println!("Reported {} state changes for {}", reported, project_id);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn complete(
+    client: &GitHubClient,
+    store: &StateStore,
+    state_dir: &Path,
+    number: u64,
+    project: &str,
+    result: Option<String>,
+    base: Option<&str>,
+    no_pr: bool,
+) -> Result<()> {
+    let (record, project_id, queue) = open_queue(store, state_dir, project)?;
+    let (item, mut link) = find_linked(&queue, &project_id, number)?;
+    if item.state != WorkState::InProgress {
+        return Err(format!("{} is not in progress; delegate it first", link.reference()).into());
+    }
+
+    if !no_pr {
+        let branch = link
+            .branch
+            .clone()
+            .ok_or_else(|| format!("{} has no branch", link.reference()))?;
+        let record = record.ok_or_else(|| format!("No project record for {}", project_id))?;
+        git::push_branch(Path::new(&record.path), &branch)?;
+
+        let base = match base {
+            Some(b) => b.to_string(),
+            None => client.default_branch(&link.repo).await?,
+        };
+        let mut done = item.clone();
+        done.result = result.clone();
+        let pull = client
+            .create_pull(&link.repo, &branch, &base, &link.title, &pull_request_body(&done, &link))
+            .await?;
+        println!("Opened pull request #{}: {}", pull.number, pull.html_url);
+        link.pull_request = Some(pull.html_url);
+        update_link(&queue, &item.id, &link)?;
+    }
+
+    match result {
+        Some(result) => queue.complete_with_result(&item.id, result)?,
+        None => queue.complete(&item.id)?,
+    }
+    if let Some(item) = queue.get(&item.id) {
+        report_progress(client, &queue, &item).await?;
+    }
+    println!("Completed {}", link.reference());
+    Ok(())
+}
@@ -0,0 +1,214 @@
+//! Conversation search across session transcripts and memories.
+//!
+//! `/search <query>` (TUI and REPL) combines a keyword search over the
+//! persisted session transcripts with a semantic search over the memory
+//! store, and merges both into one ranked list without duplicates. Each hit
+//! carries the session and date it came from; in the TUI, `/history <n>`
+//! opens the session's transcript at hit `n`.
+
+use chrono::{DateTime, Local, TimeZone};
+use commander_agent::KNOWLEDGE_AGENT_PREFIX;
+use commander_core::{LogEntry, TranscriptMatch};
+use commander_memory::{EmbeddingGenerator, MemoryStore, SearchResult};
+
+/// Hits shown for a search.
+pub const SEARCH_LIMIT: usize = 20;
+
+/// Characters of a hit's text shown in the result list.
+const SNIPPET_LEN: usize = 100;
+
+/// Prefix of Session Agent IDs, followed by the session name.
+const SESSION_AGENT_PREFIX: &str = "session-agent-";
+
+/// Where a search hit was found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HitSource {
+    /// Entry `index` of the session's transcript.
+    Transcript { index: usize },
+    /// A memory of `agent_id`.
+    Memory { agent_id: String },
+}
+
+/// One merged search result.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// Session the hit belongs to.
+    pub session: String,
+    /// When the entry or memory was written.
+    pub at: DateTime<Local>,
+    /// Matching text.
+    pub text: String,
+    /// Relevance: fraction of query words for transcripts, similarity for memories.
+    pub score: f32,
+    /// Where the hit was found.
+    pub source: HitSource,
+}
+
+impl SearchHit {
+    /// A hit from the transcript keyword search.
+    pub fn from_transcript(hit: TranscriptMatch) -> Self {
+        Self {
+            session: hit.session,
+            at: Local
+                .timestamp_opt(hit.entry.ts, 0)
+                .single()
+                .unwrap_or_else(Local::now),
+            text: hit.entry.text,
+            score: hit.score,
+            source: HitSource::Transcript { index: hit.index },
+        }
+    }
+
+    /// A hit from the memory search.
+    pub fn from_memory(result: SearchResult) -> Self {
+        let memory = result.memory;
+        Self {
+            session: session_of_agent(&memory.agent_id).to_string(),
+            at: memory.created_at.with_timezone(&Local),
+            text: memory.content,
+            score: result.score,
+            source: HitSource::Memory { agent_id: memory.agent_id },
+        }
+    }
+
+    /// Short name of the hit's source.
+    pub fn kind(&self) -> &'static str {
+        match self.source {
+            HitSource::Transcript { .. } => "transcript",
+            HitSource::Memory { .. } => "memory",
+        }
+    }
+
+    /// One-line summary: number, session, date, source and snippet.
+    pub fn format_line(&self, number: usize) -> String {
+        let text = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut snippet: String = text.chars().take(SNIPPET_LEN).collect();
+        if text.chars().count() > SNIPPET_LEN {
+            snippet.push_str("...");
+        }
+        format!(
+            "[{}] {} · {} · {}: {}",
+            number,
+            self.session,
+            self.at.format("%Y-%m-%d %H:%M"),
+            self.kind(),
+            snippet
+        )
+    }
+}
+
+/// Session name behind a memory's agent ID.
+fn session_of_agent(agent_id: &str) -> &str {
+    agent_id
+        .strip_prefix(SESSION_AGENT_PREFIX)
+        .or_else(|| agent_id.strip_prefix(KNOWLEDGE_AGENT_PREFIX))
+        .unwrap_or(agent_id)
+}
+
+/// Text compared when deduplicating hits.
+fn dedup_key(text: &str) -> String {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Merge transcript and memory hits into one list, best first.
+///
+/// A memory with the same text as a transcript entry is dropped in favour of
+/// the entry (which can be opened in the history view), keeping the higher
+/// score. Ties are ordered newest first.
+pub fn merge(transcripts: Vec<TranscriptMatch>, memories: Vec<SearchResult>, limit: usize) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = Vec::new();
+    let candidates = transcripts
+        .into_iter()
+        .map(SearchHit::from_transcript)
+        .chain(memories.into_iter().map(SearchHit::from_memory));
+    for hit in candidates {
+        let key = dedup_key(&hit.text);
+        match hits.iter_mut().find(|h| dedup_key(&h.text) == key) {
+            Some(existing) => existing.score = existing.score.max(hit.score),
+            None => hits.push(hit),
+        }
+    }
+
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| b.at.cmp(&a.at)));
+    hits.truncate(limit);
+    hits
+}
+
+/// Semantic search over every agent's memories.
+pub async fn search_memories(store: &dyn MemoryStore, query: &str, limit: usize) -> commander_memory::Result<Vec<SearchResult>> {
+    let embedding = EmbeddingGenerator::from_env().embed(query).await?;
+    store.search_all(&embedding, limit).await
+}
+
+/// Index of the transcript entry written closest to `at`, for opening a
+/// memory hit in the history view.
+pub fn nearest_entry(entries: &[LogEntry], at: DateTime<Local>) -> Option<usize> {
+    let ts = at.timestamp();
+    entries
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, e)| (e.ts - ts).abs())
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_memory::Memory;
+
+    fn entry(ts: i64, text: &str) -> LogEntry {
+        LogEntry { ts, text: text.to_string(), hash: String::new(), kind: None }
+    }
+
+    fn transcript(session: &str, index: usize, text: &str, score: f32) -> TranscriptMatch {
+        TranscriptMatch {
+            session: session.to_string(),
+            index,
+            entry: entry(1_700_000_000 + index as i64, text),
+            score,
+        }
+    }
+
+    fn memory(agent_id: &str, text: &str, score: f32) -> SearchResult {
+        SearchResult { memory: Memory::new(agent_id, text, vec![]), score }
+    }
+
+    #[test]
+    fn test_merge_dedups_and_ranks() {
+        let hits = merge(
+            vec![
+                transcript("api", 0, "Deploy failed: missing DATABASE_URL", 0.5),
+                transcript("web", 3, "Tests pass", 1.0),
+            ],
+            vec![
+                memory("session-agent-api", "deploy failed:  missing database_url", 0.9),
+                memory("project-knowledge-web", "Web uses pnpm", 0.7),
+            ],
+            10,
+        );
+
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].session, "web");
+        assert_eq!(hits[1].source, HitSource::Transcript { index: 0 });
+        assert_eq!(hits[1].score, 0.9);
+        assert_eq!(hits[2].session, "web");
+        assert_eq!(hits[2].kind(), "memory");
+        assert_eq!(merge(vec![], vec![memory("a", "x", 0.1), memory("b", "y", 0.2)], 1).len(), 1);
+    }
+
+    #[test]
+    fn test_format_line_and_nearest_entry() {
+        let hit = SearchHit::from_transcript(transcript("api", 2, "line one\nline two", 1.0));
+        let line = hit.format_line(1);
+        assert!(line.starts_with("[1] api · "));
+        assert!(line.ends_with("transcript: line one line two"));
+
+        let entries = vec![entry(100, "a"), entry(200, "b"), entry(300, "c")];
+        let at = Local.timestamp_opt(240, 0).unwrap();
+        assert_eq!(nearest_entry(&entries, at), Some(1));
+        assert_eq!(nearest_entry(&[], at), None);
+    }
+}
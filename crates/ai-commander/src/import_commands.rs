@@ -0,0 +1,382 @@
+//! Import-history command for the CLI.
+//!
+//! `import-history --project X` reads the Claude Code transcripts kept for
+//! the project's directory (`~/.claude/projects/<encoded path>/*.jsonl`, or
+//! `--path`) and brings them into Commander:
+//!
+//! - every user and assistant message goes into the transcript of the
+//!   project's session, at its original time, so replay and `/search` see it
+//! - every exchange (a prompt and the replies to it), and every summary
+//!   Claude Code wrote, becomes a memory of the project's Session Agent, which
+//!   reads the most recent ones into its context when it starts
+//!
+//! Messages already imported are skipped, so the command can be re-run as
+//! the history grows.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use commander_agent::history::{imported_memory, imported_message};
+use commander_agent::CLAUDE_CODE_SOURCE;
+use commander_core::{import_log_entries, LogEntry};
+use commander_memory::{EmbeddingGenerator, LocalStore, MemoryStore};
+use commander_models::ProjectId;
+use commander_persistence::StateStore;
+use serde_json::Value;
+
+use crate::commands::Result;
+
+/// Exchanges imported as memories per Claude Code session, most recent first.
+const MAX_EXCHANGES_PER_SESSION: usize = 20;
+
+/// Longest prompt and reply kept in an exchange memory, in characters.
+const MAX_PROMPT_CHARS: usize = 500;
+const MAX_REPLY_CHARS: usize = 1000;
+
+/// Who wrote a transcript message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    User,
+    Assistant,
+    Summary,
+}
+
+/// One message of a Claude Code transcript.
+#[derive(Debug, Clone, PartialEq)]
+struct Message {
+    uuid: String,
+    session: String,
+    at: DateTime<Utc>,
+    role: Role,
+    text: String,
+}
+
+/// A memory to import: a prompt and its replies, or a summary.
+#[derive(Debug, Clone, PartialEq)]
+struct Exchange {
+    /// Message the memory is keyed by (the prompt or the summary).
+    message: String,
+    session: String,
+    at: DateTime<Utc>,
+    content: String,
+}
+
+/// Directory Claude Code keeps per-project transcripts in.
+fn history_root() -> PathBuf {
+    match std::env::var_os("CLAUDE_CONFIG_DIR") {
+        Some(dir) => PathBuf::from(dir).join("projects"),
+        None => dirs::home_dir().unwrap_or_default().join(".claude").join("projects"),
+    }
+}
+
+/// Name of the directory Claude Code uses for the project at `path`: every
+/// character other than a letter or digit becomes `-`.
+fn encode_project_path(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Text of a message's `content`: a string, or the text blocks of an array
+/// (tool calls and tool results are left out).
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Parse the messages of one transcript file. `session` names the session
+/// for lines that carry no `sessionId`; unreadable lines, sidechains, meta
+/// messages and command output are skipped.
+fn parse_transcript(content: &str, session: &str) -> Vec<Message> {
+    let mut messages = Vec::new();
+    let mut summaries = Vec::new();
+
+    for line in content.lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let field = |key: &str| value.get(key).and_then(Value::as_str);
+
+        if field("type") == Some("summary") {
+            if let (Some(text), Some(leaf)) = (field("summary"), field("leafUuid")) {
+                summaries.push((leaf.to_string(), text.trim().to_string()));
+            }
+            continue;
+        }
+        let role = match field("type") {
+            Some("user") => Role::User,
+            Some("assistant") => Role::Assistant,
+            _ => continue,
+        };
+        let flag = |key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(false);
+        if flag("isSidechain") || flag("isMeta") {
+            continue;
+        }
+        let (Some(uuid), Some(at)) = (
+            field("uuid"),
+            field("timestamp").and_then(|t| DateTime::parse_from_rfc3339(t).ok()),
+        ) else {
+            continue;
+        };
+        let text = value
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .map(content_text)
+            .unwrap_or_default();
+        let text = text.trim();
+        // Slash commands, their output and system reminders are wrapped in tags
+        if text.is_empty() || text.starts_with('<') {
+            continue;
+        }
+
+        messages.push(Message {
+            uuid: uuid.to_string(),
+            session: field("sessionId").unwrap_or(session).to_string(),
+            at: at.with_timezone(&Utc),
+            role,
+            text: text.to_string(),
+        });
+    }
+
+    // Summaries carry no time or session of their own; date them by the last message
+    if let Some(last) = messages.last().cloned() {
+        for (uuid, text) in summaries.into_iter().filter(|(_, text)| !text.is_empty()) {
+            messages.push(Message {
+                uuid,
+                session: last.session.clone(),
+                at: last.at,
+                role: Role::Summary,
+                text,
+            });
+        }
+    }
+    messages
+}
+
+/// Transcript entries for the user and assistant messages.
+fn log_entries(messages: &[Message]) -> Vec<LogEntry> {
+    messages
+        .iter()
+        .filter(|m| m.role != Role::Summary)
+        .map(|m| LogEntry {
+            ts: m.at.timestamp(),
+            text: m.text.clone(),
+            hash: format!("claude-{}", m.uuid),
+            kind: (m.role == Role::User).then(|| "user".to_string()),
+        })
+        .collect()
+}
+
+/// Memories to import from one session's messages: its summaries and its
+/// most recent exchanges.
+fn exchanges(messages: &[Message]) -> Vec<Exchange> {
+    let mut result = Vec::new();
+    let mut current: Option<(&Message, Vec<&str>)> = None;
+    let finish = |current: Option<(&Message, Vec<&str>)>, result: &mut Vec<Exchange>| {
+        if let Some((prompt, replies)) = current.filter(|(_, replies)| !replies.is_empty()) {
+            result.push(Exchange {
+                message: prompt.uuid.clone(),
+                session: prompt.session.clone(),
+                at: prompt.at,
+                content: format!(
+                    "Asked Claude Code: {}\nClaude Code: {}",
+                    truncate(&prompt.text, MAX_PROMPT_CHARS),
+                    truncate(&replies.join("\n"), MAX_REPLY_CHARS)
+                ),
+            });
+        }
+    };
+
+    for message in messages {
+        match message.role {
+            Role::User => finish(current.replace((message, Vec::new())), &mut result),
+            Role::Assistant => {
+                if let Some((_, replies)) = current.as_mut() {
+                    replies.push(&message.text);
+                }
+            }
+            Role::Summary => {}
+        }
+    }
+    finish(current, &mut result);
+
+    let skip = result.len().saturating_sub(MAX_EXCHANGES_PER_SESSION);
+    let mut result: Vec<Exchange> = result.into_iter().skip(skip).collect();
+    result.extend(messages.iter().filter(|m| m.role == Role::Summary).map(|m| Exchange {
+        message: m.uuid.clone(),
+        session: m.session.clone(),
+        at: m.at,
+        content: format!("Claude Code session summary: {}", m.text),
+    }));
+    result
+}
+
+/// `text` cut to at most `max` characters, marked with `...` when cut.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", cut.trim_end())
+    }
+}
+
+/// Execute the import-history command.
+pub async fn execute(state_dir: &Path, project: &str, path: Option<&Path>, dry_run: bool) -> Result<()> {
+    let store = StateStore::new(state_dir);
+    let found = match store.find_project_by_name_or_alias(project)? {
+        Some(p) => Some(p),
+        None => store.load_project_optional(&ProjectId::from(project))?,
+    };
+    let project = found.ok_or_else(|| format!("Project not found: {}", project))?;
+
+    let dir = match path {
+        Some(path) => path.to_path_buf(),
+        None => history_root().join(encode_project_path(&project.path)),
+    };
+    if !dir.is_dir() {
+        return Err(format!("No Claude Code history for '{}' in {}", project.name, dir.display()).into());
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    files.sort();
+
+    let mut entries = Vec::new();
+    let mut memories = Vec::new();
+    for file in &files {
+        let session = file.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let messages = parse_transcript(&fs::read_to_string(file)?, session);
+        entries.extend(log_entries(&messages));
+        memories.extend(exchanges(&messages));
+    }
+
+    println!(
+        "Claude Code history for '{}': {} sessions, {} messages, {} memories",
+        project.name,
+        files.len(),
+        entries.len(),
+        memories.len()
+    );
+    if dry_run {
+        println!("Dry run: nothing was imported.");
+        return Ok(());
+    }
+
+    let session = format!("commander-{}", project.name);
+    let added_entries = import_log_entries(&session, &entries)?;
+
+    let agent_id = format!("session-agent-{}", session);
+    let memory_store = LocalStore::new(state_dir.join("memory")).await?;
+    let known: HashSet<String> = memory_store
+        .list(&agent_id, usize::MAX)
+        .await?
+        .iter()
+        .filter_map(|m| imported_message(m).map(str::to_string))
+        .collect();
+    let embedder = EmbeddingGenerator::from_env();
+    let mut added_memories = 0;
+    for exchange in memories.iter().filter(|e| !known.contains(&e.message)) {
+        let embedding = embedder.embed(&exchange.content).await?;
+        let memory = imported_memory(
+            &agent_id,
+            &exchange.content,
+            embedding,
+            CLAUDE_CODE_SOURCE,
+            &exchange.session,
+            &exchange.message,
+            exchange.at,
+        );
+        memory_store.store(memory).await?;
+        added_memories += 1;
+    }
+
+    println!(
+        "Imported {} transcript messages and {} memories into '{}'.",
+        added_entries, added_memories, project.name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = r#"{"type":"summary","summary":"Fixed the login redirect","leafUuid":"a2"}
+{"type":"user","uuid":"u1","sessionId":"s1","timestamp":"2026-03-01T10:00:00Z","message":{"role":"user","content":"Why does login loop?"}}
+{"type":"assistant","uuid":"a1","sessionId":"s1","timestamp":"2026-03-01T10:00:05Z","message":{"role":"assistant","content":[{"type":"text","text":"The redirect ignores the session cookie."},{"type":"tool_use","name":"Read"}]}}
+{"type":"user","uuid":"t1","sessionId":"s1","timestamp":"2026-03-01T10:00:06Z","message":{"role":"user","content":[{"type":"tool_result","content":"file contents"}]}}
+{"type":"assistant","uuid":"a2","sessionId":"s1","timestamp":"2026-03-01T10:00:09Z","message":{"role":"assistant","content":[{"type":"text","text":"Fixed it."}]}}
+{"type":"user","uuid":"m1","sessionId":"s1","isMeta":true,"timestamp":"2026-03-01T10:01:00Z","message":{"role":"user","content":"meta"}}
+{"type":"user","uuid":"c1","sessionId":"s1","timestamp":"2026-03-01T10:01:00Z","message":{"role":"user","content":"<command-name>/clear</command-name>"}}
+{"type":"user","uuid":"u2","sessionId":"s1","isSidechain":true,"timestamp":"2026-03-01T10:02:00Z","message":{"role":"user","content":"sidechain"}}
+not json"#;
+
+    #[test]
+    fn test_encode_project_path() {
+        assert_eq!(encode_project_path("/Users/bob/my.app"), "-Users-bob-my-app");
+        assert_eq!(encode_project_path("/srv/web_2"), "-srv-web-2");
+    }
+
+    #[test]
+    fn test_parse_transcript() {
+        let messages = parse_transcript(TRANSCRIPT, "file");
+        let roles: Vec<(&str, Role)> = messages.iter().map(|m| (m.uuid.as_str(), m.role)).collect();
+        assert_eq!(
+            roles,
+            vec![("u1", Role::User), ("a1", Role::Assistant), ("a2", Role::Assistant), ("a2", Role::Summary)]
+        );
+        assert_eq!(messages[1].text, "The redirect ignores the session cookie.");
+        assert_eq!(messages[0].session, "s1");
+
+        let entries = log_entries(&messages);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].kind.as_deref(), Some("user"));
+        assert_eq!(entries[1].kind, None);
+        assert_eq!(entries[0].hash, "claude-u1");
+    }
+
+    #[test]
+    fn test_exchanges() {
+        let messages = parse_transcript(TRANSCRIPT, "file");
+        let exchanges = exchanges(&messages);
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0].message, "u1");
+        assert_eq!(
+            exchanges[0].content,
+            "Asked Claude Code: Why does login loop?\nClaude Code: The redirect ignores the session cookie.\nFixed it."
+        );
+        assert_eq!(exchanges[1].content, "Claude Code session summary: Fixed the login redirect");
+    }
+
+    #[test]
+    fn test_exchanges_keeps_most_recent() {
+        let at = Utc::now();
+        let messages: Vec<Message> = (0..MAX_EXCHANGES_PER_SESSION + 5)
+            .flat_map(|i| {
+                [Role::User, Role::Assistant].map(|role| Message {
+                    uuid: format!("{:?}{}", role, i),
+                    session: "s".to_string(),
+                    at,
+                    role,
+                    text: format!("text {}", i),
+                })
+            })
+            .collect();
+        let exchanges = exchanges(&messages);
+        assert_eq!(exchanges.len(), MAX_EXCHANGES_PER_SESSION);
+        assert_eq!(exchanges[0].message, "User5");
+    }
+}
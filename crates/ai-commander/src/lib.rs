@@ -4,13 +4,37 @@
 //! for Commander.
 
 pub mod agent_cli;
+pub mod audit_commands;
 pub mod chat;
+pub mod chat_tools;
 pub mod cli;
+pub mod client;
 pub mod commands;
+pub mod completions;
 pub mod daemon_commands;
+pub mod error;
+pub mod eval_commands;
 pub mod filesystem;
+pub mod gc;
+pub mod gh_commands;
+pub mod history_search;
+pub mod import_commands;
+pub mod logs_commands;
+pub mod models_commands;
+pub mod pause;
+pub mod picker;
+pub mod project_import;
+pub mod purge_commands;
 pub mod repl;
+pub mod report_commands;
+pub mod restore;
+pub mod sandbox;
+pub mod scaffold;
+pub mod snippets_commands;
+pub mod stop_commit;
 pub mod tui;
+pub mod work_commands;
+pub mod workflow_commands;
 
 // Re-export orchestrator when agents feature is enabled
 #[cfg(feature = "agents")]
@@ -18,6 +42,7 @@ pub use commander_orchestrator;
 
 use std::path::Path;
 
+use commander_core::CommanderError;
 use commander_telegram::daemon;
 
 /// Check if the Telegram bot daemon is running.
@@ -37,18 +62,17 @@ pub enum TelegramStartResult {
 }
 
 /// Start the Telegram bot daemon.
-pub fn start_telegram_daemon() -> Result<u32, String> {
-    daemon::start().map_err(|e| e.to_string())
+pub fn start_telegram_daemon() -> Result<u32, CommanderError> {
+    Ok(daemon::start()?)
 }
 
 /// Ensure telegram bot is running, starting it if needed.
 /// Returns the result indicating what action was taken.
-pub fn ensure_telegram_running() -> Result<TelegramStartResult, String> {
-    match daemon::ensure_running() {
-        Ok(daemon::StartResult::AlreadyRunning) => Ok(TelegramStartResult::AlreadyRunning),
-        Ok(daemon::StartResult::Started) => Ok(TelegramStartResult::Started),
-        Ok(daemon::StartResult::BuiltAndStarted) => Ok(TelegramStartResult::BuiltAndStarted),
-        Err(e) => Err(e.to_string()),
+pub fn ensure_telegram_running() -> Result<TelegramStartResult, CommanderError> {
+    match daemon::ensure_running()? {
+        daemon::StartResult::AlreadyRunning => Ok(TelegramStartResult::AlreadyRunning),
+        daemon::StartResult::Started => Ok(TelegramStartResult::Started),
+        daemon::StartResult::BuiltAndStarted => Ok(TelegramStartResult::BuiltAndStarted),
     }
 }
 
@@ -61,32 +85,54 @@ pub fn restart_telegram_if_running() {
 
 /// Validate that a project path exists, is a directory, and is accessible.
 ///
-/// Returns `Ok(())` if the path is valid, or `Err(message)` describing the issue.
-pub fn validate_project_path(path: &str) -> Result<(), String> {
+/// Returns `Ok(())` if the path is valid, or an error describing the issue.
+pub fn validate_project_path(path: &str) -> Result<(), CommanderError> {
     let path = Path::new(path);
 
     if !path.exists() {
-        return Err(format!("Project path does not exist: {}", path.display()));
+        return Err(CommanderError::Runtime(format!("Project path does not exist: {}", path.display())));
     }
 
     if !path.is_dir() {
-        return Err(format!(
+        return Err(CommanderError::Runtime(format!(
             "Project path is not a directory: {}",
             path.display()
-        ));
+        )));
     }
 
     // Check if readable by attempting to read dir
     if path.read_dir().is_err() {
-        return Err(format!(
+        return Err(CommanderError::Permission(format!(
             "Cannot access project path: {} (permission denied)",
             path.display()
-        ));
+        )));
     }
 
     Ok(())
 }
 
+/// Environment variables for a new session of the project at `path`, from
+/// `[env]` in its `.commander.toml`, with a warning about anything left out.
+pub fn project_session_env(path: &str) -> (Vec<(String, String)>, Option<String>) {
+    match commander_runtime::project_env(Path::new(path)) {
+        Ok(env) if env.missing.is_empty() => (env.vars, None),
+        Ok(env) => {
+            let warning = format!(
+                "Secrets not found, not set in the session: {} (store them with `commander secrets set`)",
+                env.missing.join(", ")
+            );
+            (env.vars, Some(warning))
+        }
+        Err(e) => (Vec::new(), Some(format!("Project environment ignored: {}", e))),
+    }
+}
+
+/// SSH host the sessions of `project` run on, from `host` in its
+/// configuration (`[user@]host[:port]`); `None` when they run locally.
+pub fn project_host(project: &commander_models::Project) -> Option<commander_tmux::SshHost> {
+    project.config.get("host")?.as_str()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,7 +148,7 @@ mod tests {
     fn test_validate_project_path_nonexistent() {
         let result = validate_project_path("/nonexistent/path/that/does/not/exist");
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("does not exist"));
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
     }
 
     #[test]
@@ -113,7 +159,7 @@ mod tests {
 
         let result = validate_project_path(file_path.to_str().unwrap());
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not a directory"));
+        assert!(result.unwrap_err().to_string().contains("not a directory"));
     }
 
     #[test]
@@ -133,7 +179,7 @@ mod tests {
 
         let result = start_telegram_daemon();
         assert!(result.is_err());
-        let err = result.unwrap_err();
+        let err = result.unwrap_err().to_string();
         // Error should mention either TELEGRAM_BOT_TOKEN (if checked first)
         // or may be about finding binary (if binary doesn't exist)
         assert!(
@@ -0,0 +1,47 @@
+//! Structured log commands for the CLI.
+//!
+//! `logs [project]` prints the recent JSON log lines of a project's session
+//! (or the combined log of every component), filtered by level, and with
+//! `--follow` keeps printing new ones.
+
+use std::thread;
+use std::time::Duration;
+
+use commander_core::config;
+use commander_core::{combined_log_path, read_log, session_log_path, LogTail};
+use tracing::Level;
+
+use crate::commands::Result;
+
+/// How often `--follow` checks for new lines.
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Execute the logs command.
+pub fn execute(project: Option<&str>, follow: bool, level: Level, lines: usize) -> Result<()> {
+    let logs_dir = config::logs_dir();
+    let path = match project {
+        Some(project) => session_log_path(&logs_dir, project),
+        None => combined_log_path(&logs_dir),
+    };
+
+    // Start following before reading so no line falls in between
+    let mut tail = LogTail::new(&path, level);
+    let records = read_log(&path, level)?;
+    if records.is_empty() && !follow {
+        println!("No logs at {} or above in {}", level, path.display());
+        return Ok(());
+    }
+    for record in &records[records.len().saturating_sub(lines)..] {
+        println!("{}", record.format_line());
+    }
+
+    if !follow {
+        return Ok(());
+    }
+    loop {
+        thread::sleep(FOLLOW_INTERVAL);
+        for record in tail.poll()? {
+            println!("{}", record.format_line());
+        }
+    }
+}
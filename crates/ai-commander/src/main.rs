@@ -1,25 +1,41 @@
 //! Commander CLI entry point.
 
 use clap::Parser;
+use commander_core::{config, SessionLogLayer};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 
 use ai_commander::agent_cli;
 use ai_commander::cli::{Cli, Commands};
+use ai_commander::client::RemoteClient;
 use ai_commander::commands;
+use ai_commander::completions;
+use ai_commander::picker::{self, PickerItem};
 use ai_commander::repl::Repl;
 use ai_commander::tui;
 
 fn main() {
+    // Answer shell completion requests before anything else prints
+    completions::complete_from_env();
+
     // Load .env.local if it exists (for OPENROUTER_API_KEY etc.)
     let _ = dotenvy::from_filename(".env.local");
 
     let cli = Cli::parse();
 
     // Initialize tracing
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new(cli.log_level().to_string()));
+    if cli.runs_daemon() {
+        init_daemon_logging(&cli);
+    } else {
+        let filter = EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new(cli.log_level().to_string()));
 
-    fmt().with_env_filter(filter).with_target(false).init();
+        tracing_subscriber::registry()
+            .with(fmt::layer().with_target(false).with_filter(filter))
+            .with(session_log_layer("cli"))
+            .init();
+    }
 
     // Get state directory
     let state_dir = cli.state_dir();
@@ -27,7 +43,12 @@ fn main() {
     // Handle command or enter REPL
     let result = match cli.command {
         Some(Commands::Repl { project }) => run_repl(&state_dir, project),
-        Some(Commands::Tui { project }) => run_tui(&state_dir, project),
+        Some(Commands::Tui { project, remote, token, force_standalone }) => {
+            run_tui(&state_dir, project, remote, token, force_standalone)
+        }
+        Some(Commands::Connect { project, force_standalone }) => {
+            run_connect(&state_dir, project, force_standalone)
+        }
         Some(Commands::Agent { command }) => agent_cli::execute(command),
         Some(cmd) => commands::execute(cmd, &state_dir),
         None => {
@@ -37,11 +58,49 @@ fn main() {
     };
 
     if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+        let err = ai_commander::error::classify(e);
+        eprintln!("Error: {}", err.user_message());
+        std::process::exit(err.exit_code());
+    }
+}
+
+/// Log to stderr and the rotating daemon log, at info level or above.
+fn init_daemon_logging(cli: &Cli) {
+    use std::sync::Mutex;
+    use tracing_subscriber::fmt::writer::MakeWriterExt;
+
+    let level = cli.log_level().max(tracing::Level::INFO);
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(level.to_string()));
+
+    match commander_daemon::RotatingLog::daemon() {
+        Ok(log) => tracing_subscriber::registry()
+            .with(
+                fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(std::io::stderr.and(Mutex::new(log)))
+                    .with_filter(filter),
+            )
+            .with(session_log_layer("daemon"))
+            .init(),
+        Err(e) => {
+            tracing_subscriber::registry()
+                .with(fmt::layer().with_filter(filter))
+                .with(session_log_layer("daemon"))
+                .init();
+            tracing::warn!(error = %e, "Failed to open daemon log, logging to stderr only");
+        }
     }
 }
 
+/// Structured JSON logs (combined and per session) at info level or above.
+fn session_log_layer<S>(component: &str) -> impl tracing_subscriber::Layer<S>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    SessionLogLayer::new(config::logs_dir(), component).with_filter(LevelFilter::INFO)
+}
+
 fn run_repl(state_dir: &std::path::Path, connect_to: Option<String>) -> commands::Result<()> {
     let mut repl = Repl::new(state_dir)?;
 
@@ -55,7 +114,48 @@ fn run_repl(state_dir: &std::path::Path, connect_to: Option<String>) -> commands
     Ok(())
 }
 
-fn run_tui(state_dir: &std::path::Path, connect_to: Option<String>) -> commands::Result<()> {
-    tui::run(state_dir, connect_to)?;
+fn run_tui(
+    state_dir: &std::path::Path,
+    connect_to: Option<String>,
+    remote: Option<String>,
+    token: Option<String>,
+    force_standalone: bool,
+) -> commands::Result<()> {
+    let remote = match remote {
+        Some(url) => Some(
+            RemoteClient::connect(&url, token)
+                .map_err(|e| format!("Failed to attach to {}: {}", url, e))?,
+        ),
+        None => None,
+    };
+    tui::run(state_dir, connect_to, remote, force_standalone)?;
     Ok(())
 }
+
+/// Open the TUI connected to `project`, or to one picked interactively.
+fn run_connect(
+    state_dir: &std::path::Path,
+    project: Option<String>,
+    force_standalone: bool,
+) -> commands::Result<()> {
+    let project = match project {
+        Some(project) => project,
+        None => {
+            let store = commander_persistence::StateStore::new(state_dir);
+            let mut items: Vec<PickerItem> = store
+                .load_all_projects()?
+                .into_values()
+                .map(|p| PickerItem::new(p.name, p.path))
+                .collect();
+            if items.is_empty() {
+                return Err("No projects yet. Create one with: ai-commander start <path>".into());
+            }
+            items.sort_by(|a, b| a.value.cmp(&b.value));
+            match picker::pick("project>", &items)? {
+                Some(item) => item.value,
+                None => return Ok(()),
+            }
+        }
+    };
+    run_tui(state_dir, Some(project), None, None, force_standalone)
+}
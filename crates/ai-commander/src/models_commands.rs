@@ -0,0 +1,100 @@
+//! Model catalog commands for the CLI.
+//!
+//! `models list` and `models search` show the OpenRouter models available to
+//! agents, with context length and price per million tokens. The catalog is
+//! cached in the runtime state directory and fetched again once a day or with
+//! `--refresh`; agents check configured model IDs against the same cache.
+
+use commander_agent::{catalog::catalog_max_age, CatalogModel, ModelCatalog};
+
+use crate::cli::ModelsCommands;
+use crate::commands::Result;
+
+/// Execute model catalog commands.
+pub fn execute(command: ModelsCommands) -> Result<()> {
+    let (refresh, query) = match command {
+        ModelsCommands::List { refresh } => (refresh, None),
+        ModelsCommands::Search { query, refresh } => (refresh, Some(query.join(" "))),
+    };
+
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| format!("Failed to create async runtime: {}", e))?;
+    let dir = commander_core::runtime_state_dir();
+    let catalog = rt.block_on(async {
+        if refresh {
+            ModelCatalog::sync(&dir).await
+        } else {
+            ModelCatalog::load_or_sync(&dir, catalog_max_age()).await
+        }
+    })?;
+
+    let models: Vec<&CatalogModel> = match &query {
+        Some(query) => catalog.search(query),
+        None => catalog.models.iter().collect(),
+    };
+    if models.is_empty() {
+        let query = query.unwrap_or_default();
+        match catalog.suggest(&query).as_slice() {
+            [] => println!("No models match '{}'", query),
+            suggestions => println!("No models match '{}'. Did you mean: {}", query, suggestions.join(", ")),
+        }
+        return Ok(());
+    }
+
+    print!("{}", format_models(&models));
+    println!(
+        "\n{} models, catalog from {}",
+        models.len(),
+        catalog.fetched_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+    );
+    Ok(())
+}
+
+/// One line per model: ID, context length, and USD per million prompt and
+/// completion tokens.
+fn format_models(models: &[&CatalogModel]) -> String {
+    let width = models.iter().map(|m| m.id.len()).max().unwrap_or(0).max(5);
+    let mut out = format!(
+        "{:<width$}  {:>9}  {:>10}  {:>10}\n",
+        "MODEL", "CONTEXT", "$/M IN", "$/M OUT"
+    );
+    for model in models {
+        let context = model
+            .context_length
+            .map(|c| format!("{}k", c / 1000))
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<width$}  {:>9}  {:>10.2}  {:>10.2}\n",
+            model.id,
+            context,
+            model.pricing.prompt * 1_000_000.0,
+            model.pricing.completion * 1_000_000.0,
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_agent::ModelPricing;
+
+    #[test]
+    fn test_format_models() {
+        let model = CatalogModel {
+            id: "anthropic/claude-sonnet-4".to_string(),
+            name: "Claude Sonnet 4".to_string(),
+            context_length: Some(200_000),
+            pricing: ModelPricing {
+                prompt: 0.000003,
+                completion: 0.000015,
+            },
+        };
+        let out = format_models(&[&model]);
+        let line = out.lines().nth(1).unwrap();
+        assert!(line.starts_with("anthropic/claude-sonnet-4"));
+        assert!(line.contains("200k"));
+        assert!(line.contains("3.00"));
+        assert!(line.ends_with("15.00"));
+    }
+}
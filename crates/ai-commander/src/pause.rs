@@ -0,0 +1,80 @@
+//! `/pause` and `/resume` for the TUI and REPL.
+//!
+//! Adapters with a native pause command (MPM) are sent it; other sessions
+//! have their processes suspended through tmux. The paused state is kept in
+//! `commander_core::pause`, so pollers skip the session and every frontend
+//! shows it as paused.
+
+use chrono::Utc;
+use commander_adapters::RuntimeAdapter;
+use commander_core::pause::{self, PauseMethod, PausedSession};
+
+use crate::client::SessionBackend;
+
+/// Pause `session` of `project`, recording `by` as the frontend.
+///
+/// Returns a line describing how it was paused.
+pub fn pause_session(
+    tmux: &dyn SessionBackend,
+    adapter: Option<&dyn RuntimeAdapter>,
+    session: &str,
+    project: &str,
+    by: &str,
+) -> Result<String, String> {
+    if pause::is_paused(session) {
+        return Err(format!("{} is already paused (/resume to continue)", project));
+    }
+
+    let (method, description) = match adapter.and_then(|a| a.pause_commands()) {
+        Some((pause_command, resume_command)) => {
+            tmux.send_line(session, None, pause_command)
+                .map_err(|e| format!("Failed to send {}: {}", pause_command, e))?;
+            (
+                PauseMethod::Command { resume_command: resume_command.to_string() },
+                format!("Paused {} with {}", project, pause_command),
+            )
+        }
+        None => {
+            let process_groups = tmux
+                .suspend_session(session)
+                .map_err(|e| format!("Failed to suspend {}: {}", session, e))?;
+            (
+                PauseMethod::Suspended { process_groups },
+                format!("Paused {} (processes suspended)", project),
+            )
+        }
+    };
+
+    pause::mark_paused(
+        session,
+        PausedSession {
+            project: project.to_string(),
+            method,
+            paused_at: Utc::now(),
+            by: by.to_string(),
+        },
+    )
+    .map_err(|e| format!("Paused, but failed to record it: {}", e))?;
+    Ok(description)
+}
+
+/// Resume `session` the way it was paused.
+///
+/// Returns a line describing the outcome.
+pub fn resume_session(tmux: &dyn SessionBackend, session: &str) -> Result<String, String> {
+    let Some(paused) = pause::paused_session(session) else {
+        return Err(format!("{} is not paused", session));
+    };
+
+    match &paused.method {
+        PauseMethod::Command { resume_command } => tmux
+            .send_line(session, None, resume_command)
+            .map_err(|e| format!("Failed to send {}: {}", resume_command, e))?,
+        PauseMethod::Suspended { process_groups } => tmux
+            .resume_processes(process_groups)
+            .map_err(|e| format!("Failed to resume {}: {}", session, e))?,
+    }
+
+    pause::mark_resumed(session).map_err(|e| format!("Resumed, but failed to record it: {}", e))?;
+    Ok(format!("Resumed {}", paused.project))
+}
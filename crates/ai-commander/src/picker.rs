@@ -0,0 +1,238 @@
+//! Interactive fuzzy picker for the CLI.
+//!
+//! A small skim-like selector drawn inline below the prompt: type to filter,
+//! move with the arrow keys (or Ctrl-P/Ctrl-N), Enter to pick, Esc to cancel.
+
+use std::io::{self, IsTerminal};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal, TerminalOptions, Viewport};
+
+/// Maximum number of candidates shown at once.
+const MAX_VISIBLE: usize = 10;
+
+/// One selectable entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerItem {
+    /// Value matched against the query and returned when picked.
+    pub value: String,
+    /// Extra text shown dimmed next to the value.
+    pub detail: String,
+}
+
+impl PickerItem {
+    pub fn new(value: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Indices of the `items` matching `query`, best match first.
+///
+/// An empty query keeps every item in its original order.
+pub fn rank(items: &[PickerItem], query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return (0..items.len()).collect();
+    }
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, usize)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| matcher.fuzzy_match(&item.value, query).map(|score| (score, i)))
+        .collect();
+    // Stable sort keeps the original order among equal scores
+    scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Picker state: the query and the highlighted match.
+struct Picker<'a> {
+    prompt: &'a str,
+    items: &'a [PickerItem],
+    query: String,
+    matches: Vec<usize>,
+    list: ListState,
+}
+
+impl<'a> Picker<'a> {
+    fn new(prompt: &'a str, items: &'a [PickerItem]) -> Self {
+        let mut picker = Self {
+            prompt,
+            items,
+            query: String::new(),
+            matches: Vec::new(),
+            list: ListState::default(),
+        };
+        picker.refilter();
+        picker
+    }
+
+    fn refilter(&mut self) {
+        self.matches = rank(self.items, &self.query);
+        self.list
+            .select(if self.matches.is_empty() { None } else { Some(0) });
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let Some(selected) = self.list.selected() else { return };
+        let last = self.matches.len() - 1;
+        let next = match (down, selected) {
+            (true, s) if s >= last => 0,
+            (true, s) => s + 1,
+            (false, 0) => last,
+            (false, s) => s - 1,
+        };
+        self.list.select(Some(next));
+    }
+
+    fn selected(&self) -> Option<&PickerItem> {
+        self.list
+            .selected()
+            .and_then(|i| self.matches.get(i))
+            .map(|&i| &self.items[i])
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [input, list] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(frame.area());
+
+        let prompt = Line::from(vec![
+            Span::styled(format!("{} ", self.prompt), Style::default().fg(Color::Cyan)),
+            Span::raw(self.query.as_str()),
+            Span::styled(
+                format!("  {}/{}", self.matches.len(), self.items.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(prompt), input);
+        frame.set_cursor_position((
+            input.x + (self.prompt.chars().count() + 1 + self.query.chars().count()) as u16,
+            input.y,
+        ));
+
+        let rows: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|&i| {
+                let item = &self.items[i];
+                ListItem::new(Line::from(vec![
+                    Span::raw(item.value.as_str()),
+                    Span::styled(format!("  {}", item.detail), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        let rows = List::new(rows)
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(rows, list, &mut self.list);
+    }
+}
+
+/// Let the user pick one of `items` interactively.
+///
+/// Returns `None` when the selection is cancelled. Fails when stdin or
+/// stdout is not a terminal.
+pub fn pick(prompt: &str, items: &[PickerItem]) -> io::Result<Option<PickerItem>> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(io::Error::other("interactive selection needs a terminal"));
+    }
+
+    let height = (items.len().min(MAX_VISIBLE) + 1) as u16;
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )?;
+
+    enable_raw_mode()?;
+    let result = run_picker(&mut terminal, Picker::new(prompt, items));
+    disable_raw_mode()?;
+    terminal.clear()?;
+    result
+}
+
+fn run_picker(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut picker: Picker,
+) -> io::Result<Option<PickerItem>> {
+    loop {
+        terminal.draw(|frame| picker.draw(frame))?;
+
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => return Ok(picker.selected().cloned()),
+            KeyCode::Up => picker.move_selection(false),
+            KeyCode::Down | KeyCode::Tab => picker.move_selection(true),
+            KeyCode::Char('p') if ctrl => picker.move_selection(false),
+            KeyCode::Char('n') if ctrl => picker.move_selection(true),
+            KeyCode::Char('u') if ctrl => {
+                picker.query.clear();
+                picker.refilter();
+            }
+            KeyCode::Backspace => {
+                picker.query.pop();
+                picker.refilter();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                picker.query.push(c);
+                picker.refilter();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<PickerItem> {
+        ["ai-commander", "website", "api-server"]
+            .iter()
+            .map(|name| PickerItem::new(*name, format!("/src/{}", name)))
+            .collect()
+    }
+
+    #[test]
+    fn test_rank_filters_and_orders() {
+        let items = items();
+        assert_eq!(rank(&items, ""), vec![0, 1, 2]);
+        assert_eq!(rank(&items, "web"), vec![1]);
+        assert_eq!(rank(&items, "api")[0], 2);
+        assert!(rank(&items, "zzz").is_empty());
+    }
+
+    #[test]
+    fn test_selection_wraps() {
+        let items = items();
+        let mut picker = Picker::new(">", &items);
+        assert_eq!(picker.selected().unwrap().value, "ai-commander");
+
+        picker.move_selection(false);
+        assert_eq!(picker.selected().unwrap().value, "api-server");
+        picker.move_selection(true);
+        assert_eq!(picker.selected().unwrap().value, "ai-commander");
+
+        picker.query = "nothing".to_string();
+        picker.refilter();
+        picker.move_selection(true);
+        assert!(picker.selected().is_none());
+    }
+}
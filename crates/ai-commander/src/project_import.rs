@@ -0,0 +1,328 @@
+//! Bulk project import for the CLI.
+//!
+//! `import --scan ~/code` walks a directory for likely projects (git
+//! repositories and directories with a package manifest), proposes a name for
+//! each, and registers the ones confirmed in the state store, so onboarding a
+//! machine with many existing checkouts takes one command instead of a
+//! `/connect` per project.
+//!
+//! Directories already registered are left out. A detected project is not
+//! searched further, so packages of a monorepo are imported as one project.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use commander_adapters::AdapterRegistry;
+use commander_models::Project;
+use commander_persistence::StateStore;
+use tracing::info;
+
+use crate::commands::Result;
+
+/// Files whose presence marks a directory as a project.
+const MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "Gemfile",
+    "composer.json",
+    "mix.exs",
+    "Package.swift",
+    "CMakeLists.txt",
+];
+
+/// Directories never searched, besides hidden ones (`.worktrees`, caches):
+/// dependencies and build output.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build", "venv", "__pycache__"];
+
+/// A directory that looks like a project.
+#[derive(Debug, Clone, PartialEq)]
+struct Candidate {
+    path: PathBuf,
+    /// Proposed project name.
+    name: String,
+    /// What marked it as a project (`git`, `Cargo.toml`, ...).
+    markers: Vec<&'static str>,
+}
+
+/// Which candidates to import, and their names where changed.
+#[derive(Debug, Default, PartialEq)]
+struct Selection {
+    picked: BTreeSet<usize>,
+    renames: HashMap<usize, String>,
+}
+
+/// Scan `root`, confirm the candidates and register them with `adapter`.
+pub fn execute(store: &StateStore, root: &Path, adapter: &str, depth: usize, yes: bool) -> Result<()> {
+    let registry = AdapterRegistry::new();
+    let tool_id = registry
+        .resolve(adapter)
+        .ok_or_else(|| format!("Unknown adapter: {}", adapter))?
+        .to_string();
+
+    let root = PathBuf::from(shellexpand::tilde(&root.to_string_lossy()).to_string());
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Cannot scan {}: {}", root.display(), e))?;
+
+    let projects = store.load_all_projects()?;
+    let registered: HashSet<PathBuf> = projects.values().map(|p| PathBuf::from(&p.path)).collect();
+    let taken: HashSet<String> = projects.values().map(|p| p.name.clone()).collect();
+
+    let found = scan(&root, depth);
+    let already = found.iter().filter(|c| registered.contains(&c.path)).count();
+    let mut candidates: Vec<Candidate> = found.into_iter().filter(|c| !registered.contains(&c.path)).collect();
+    propose_names(&mut candidates, &taken);
+
+    if already > 0 {
+        println!("{} project(s) under {} are already registered.", already, root.display());
+    }
+    if candidates.is_empty() {
+        println!("No new projects found under {}.", root.display());
+        return Ok(());
+    }
+
+    println!("Found {} project(s) under {}:", candidates.len(), root.display());
+    for (i, candidate) in candidates.iter().enumerate() {
+        let relative = candidate.path.strip_prefix(&root).unwrap_or(&candidate.path);
+        println!(
+            "  {:>3}. {:<24} {:<40} [{}]",
+            i + 1,
+            candidate.name,
+            relative.display(),
+            candidate.markers.join(", ")
+        );
+    }
+
+    let selection = if yes {
+        Selection { picked: (0..candidates.len()).collect(), renames: HashMap::new() }
+    } else {
+        print!(
+            "\nImport with adapter '{}'? [Enter all, 1-3,5 pick, !2 drop, 4=name rename, n cancel] ",
+            tool_id
+        );
+        io::stdout().flush()?;
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        match parse_selection(&line, candidates.len())? {
+            Some(selection) => selection,
+            None => {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
+    };
+
+    let mut names = taken;
+    let mut imported = 0;
+    for i in &selection.picked {
+        let candidate = &candidates[*i];
+        let name = selection.renames.get(i).unwrap_or(&candidate.name);
+        if !names.insert(name.clone()) {
+            println!("  Skipped {}: a project named '{}' already exists", candidate.path.display(), name);
+            continue;
+        }
+
+        let mut project = Project::new(candidate.path.to_string_lossy().to_string(), name.as_str());
+        project.config.insert("tool".to_string(), serde_json::json!(tool_id));
+        store.save_project(&project)?;
+        info!(project = %name, path = %candidate.path.display(), tool = %tool_id, "Imported project");
+        imported += 1;
+    }
+
+    println!("Imported {} project(s). Connect with `/connect <name>`.", imported);
+    Ok(())
+}
+
+/// Projects under `root`, at most `depth` levels down, in path order.
+fn scan(root: &Path, depth: usize) -> Vec<Candidate> {
+    let mut found = Vec::new();
+    scan_dir(root, depth, &mut found);
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+fn scan_dir(dir: &Path, depth: usize, found: &mut Vec<Candidate>) {
+    let markers = project_markers(dir);
+    if !markers.is_empty() {
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string());
+        found.push(Candidate { path: dir.to_path_buf(), name, markers });
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else { continue };
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !file_type.is_dir() || name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        scan_dir(&entry.path(), depth - 1, found);
+    }
+}
+
+/// What marks `dir` as a project: a git repository or a package manifest.
+fn project_markers(dir: &Path) -> Vec<&'static str> {
+    let mut markers = Vec::new();
+    if dir.join(".git").exists() {
+        markers.push("git");
+    }
+    markers.extend(MANIFESTS.iter().copied().filter(|m| dir.join(m).is_file()));
+    markers
+}
+
+/// Make proposed names unique among themselves and the `taken` names: a
+/// clash is prefixed with the parent directory, then numbered.
+fn propose_names(candidates: &mut [Candidate], taken: &HashSet<String>) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for candidate in candidates.iter() {
+        *counts.entry(candidate.name.clone()).or_default() += 1;
+    }
+
+    let mut used = taken.clone();
+    for candidate in candidates.iter_mut() {
+        let mut name = candidate.name.clone();
+        if counts[&name] > 1 || used.contains(&name) {
+            if let Some(parent) = candidate.path.parent().and_then(|p| p.file_name()) {
+                name = format!("{}-{}", parent.to_string_lossy(), name);
+            }
+        }
+        let base = name.clone();
+        let mut n = 2;
+        while used.contains(&name) {
+            name = format!("{}-{}", base, n);
+            n += 1;
+        }
+        used.insert(name.clone());
+        candidate.name = name;
+    }
+}
+
+/// Parse the answer to the confirm prompt for `count` candidates.
+///
+/// Empty means all; `1-3,5` picks, `!2` drops, `4=api` renames (and picks)
+/// and `n` cancels, returned as `None`. Drops and renames alone apply to all.
+fn parse_selection(input: &str, count: usize) -> Result<Option<Selection>> {
+    let input = input.trim();
+    if matches!(input.to_lowercase().as_str(), "n" | "no" | "none" | "q") {
+        return Ok(None);
+    }
+
+    let index = |s: &str| -> Result<usize> {
+        match s.trim().parse::<usize>() {
+            Ok(n) if (1..=count).contains(&n) => Ok(n - 1),
+            _ => Err(format!("Not a project number: {}", s.trim()).into()),
+        }
+    };
+
+    let mut selection = Selection::default();
+    let mut dropped = BTreeSet::new();
+    let mut picks_all = true;
+    for token in input.split([',', ' ']).map(str::trim).filter(|t| !t.is_empty()) {
+        if token.eq_ignore_ascii_case("all") || token.eq_ignore_ascii_case("y") {
+            continue;
+        } else if let Some(n) = token.strip_prefix('!') {
+            dropped.insert(index(n)?);
+        } else if let Some((n, name)) = token.split_once('=') {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("Missing name in {}", token).into());
+            }
+            selection.renames.insert(index(n)?, name.to_string());
+        } else if let Some((from, to)) = token.split_once('-') {
+            picks_all = false;
+            let (from, to) = (index(from)?, index(to)?);
+            selection.picked.extend(from.min(to)..=from.max(to));
+        } else {
+            picks_all = false;
+            selection.picked.insert(index(token)?);
+        }
+    }
+
+    if picks_all {
+        selection.picked.extend(0..count);
+    }
+    selection.picked.extend(selection.renames.keys().copied());
+    selection.picked.retain(|i| !dropped.contains(i));
+    Ok(Some(selection))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scan_detects_projects() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("api/.git")).unwrap();
+        fs::create_dir_all(root.join("api/crates/core")).unwrap();
+        fs::write(root.join("api/crates/core/Cargo.toml"), "").unwrap();
+        fs::create_dir_all(root.join("clients/web/node_modules/dep")).unwrap();
+        fs::write(root.join("clients/web/package.json"), "{}").unwrap();
+        fs::write(root.join("clients/web/node_modules/dep/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::create_dir_all(root.join("deep/a/b/c")).unwrap();
+        fs::write(root.join("deep/a/b/c/go.mod"), "").unwrap();
+
+        let found = scan(root, 3);
+        let names: Vec<&str> = found.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["api", "web"]);
+        assert_eq!(found[0].markers, ["git"]);
+        assert_eq!(found[1].markers, ["package.json"]);
+
+        assert_eq!(scan(root, 4).len(), 3);
+    }
+
+    #[test]
+    fn test_propose_names_resolves_clashes() {
+        let candidate = |path: &str| Candidate {
+            path: PathBuf::from(path),
+            name: Path::new(path).file_name().unwrap().to_string_lossy().to_string(),
+            markers: vec!["git"],
+        };
+        let mut candidates = vec![
+            candidate("/code/work/api"),
+            candidate("/code/oss/api"),
+            candidate("/code/site"),
+            candidate("/code/tools"),
+        ];
+        let taken: HashSet<String> = ["tools".to_string(), "code-tools".to_string()].into();
+        propose_names(&mut candidates, &taken);
+
+        let names: Vec<&str> = candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["work-api", "oss-api", "site", "code-tools-2"]);
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        let all = parse_selection("\n", 4).unwrap().unwrap();
+        assert_eq!(all.picked, (0..4).collect());
+
+        let picked = parse_selection("1-2, 4", 4).unwrap().unwrap();
+        assert_eq!(picked.picked, [0, 1, 3].into());
+
+        let edited = parse_selection("!2 3=backend", 4).unwrap().unwrap();
+        assert_eq!(edited.picked, [0, 2, 3].into());
+        assert_eq!(edited.renames[&2], "backend");
+
+        assert!(parse_selection("n", 4).unwrap().is_none());
+        assert!(parse_selection("5", 4).is_err());
+        assert!(parse_selection("2=", 4).is_err());
+    }
+}
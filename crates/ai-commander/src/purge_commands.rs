@@ -0,0 +1,452 @@
+//! Purge command for the CLI.
+//!
+//! `purge --project X` finds everything stored about a project: its record,
+//! events, work items and snapshot, the memories and feedback of its agents
+//! (in the local store and, when `QDRANT_URL` is set, in Qdrant), its
+//! structured logs, and its session transcripts, notes and recordings. It prints a
+//! summary and, unless `--dry-run`, deletes all of it at once: files are
+//! first moved aside and the shared stores backed up, so a failure part way
+//! restores everything. Qdrant is purged last since it cannot be restored.
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use commander_agent::{project_agent_ids, FeedbackStore};
+use commander_core::logging::session_key;
+use commander_core::{combined_log_path, config, log_dir_for, notes_path, recordings_for, session_log_path, LogRecord};
+use commander_memory::{LocalStore, MemoryFilter, MemoryStore, QdrantStore};
+use commander_models::ProjectId;
+use commander_persistence::{EventStore, SnapshotStore, StateStore, WorkStore};
+
+use crate::commands::Result;
+
+/// Suffix of files moved aside or backed up while a purge runs.
+const STAGED_SUFFIX: &str = "purging";
+
+/// What to purge; with nothing selected, everything is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeScope {
+    pub state: bool,
+    pub memories: bool,
+    pub transcripts: bool,
+    pub logs: bool,
+    pub feedback: bool,
+}
+
+impl PurgeScope {
+    /// The scope with every category selected when none was.
+    fn or_all(self) -> Self {
+        if self == Self::default() {
+            Self {
+                state: true,
+                memories: true,
+                transcripts: true,
+                logs: true,
+                feedback: true,
+            }
+        } else {
+            self
+        }
+    }
+}
+
+/// The project a purge is about, and the names its data is stored under.
+#[derive(Debug)]
+struct Target {
+    name: String,
+    project_id: Option<ProjectId>,
+    sessions: Vec<String>,
+    agent_ids: Vec<String>,
+}
+
+impl Target {
+    /// Resolve `project` (name, alias or ID) against the state store.
+    ///
+    /// A project without a record (already stopped and removed) is purged
+    /// by name.
+    fn resolve(store: &StateStore, project: &str) -> Result<Self> {
+        let found = match store.find_project_by_name_or_alias(project)? {
+            Some(p) => Some(p),
+            None => store.load_project_optional(&ProjectId::from(project))?,
+        };
+        let (name, project_id) = match found {
+            Some(p) => (p.name, Some(p.id)),
+            None => (project.to_string(), None),
+        };
+
+        let sessions = vec![format!("commander-{}", name), name.clone()];
+        let agent_ids = project_agent_ids(&name);
+
+        Ok(Self {
+            name,
+            project_id,
+            sessions,
+            agent_ids,
+        })
+    }
+
+    fn memory_filter(&self) -> MemoryFilter {
+        MemoryFilter::agents(self.agent_ids.clone())
+    }
+}
+
+/// Everything a purge would delete.
+#[derive(Debug, Default)]
+struct Plan {
+    /// State files and directories (project record, events, work, snapshot).
+    state: Vec<PathBuf>,
+    /// Memories in the local store.
+    memories: usize,
+    /// Memories in Qdrant, when configured.
+    qdrant_memories: Option<usize>,
+    /// Feedback entries.
+    feedback: usize,
+    /// Session log files.
+    logs: Vec<PathBuf>,
+    /// Lines of the combined log written by the project's sessions.
+    combined_log_lines: usize,
+    /// Transcript directories, notes and recordings.
+    transcripts: Vec<PathBuf>,
+}
+
+impl Plan {
+    fn is_empty(&self) -> bool {
+        self.state.is_empty()
+            && self.memories == 0
+            && self.qdrant_memories.unwrap_or(0) == 0
+            && self.feedback == 0
+            && self.logs.is_empty()
+            && self.combined_log_lines == 0
+            && self.transcripts.is_empty()
+    }
+
+    fn print(&self, scope: PurgeScope) {
+        if scope.state {
+            print_paths("State", &self.state);
+        }
+        if scope.memories {
+            match self.qdrant_memories {
+                Some(qdrant) => println!("Memories: {} local, {} in Qdrant", self.memories, qdrant),
+                None => println!("Memories: {}", self.memories),
+            }
+        }
+        if scope.feedback {
+            println!("Feedback: {} entries", self.feedback);
+        }
+        if scope.logs {
+            print_paths("Logs", &self.logs);
+            println!("  {} lines of {}", self.combined_log_lines, commander_core::logging::COMBINED_LOG_FILE);
+        }
+        if scope.transcripts {
+            print_paths("Transcripts", &self.transcripts);
+        }
+    }
+}
+
+fn print_paths(label: &str, paths: &[PathBuf]) {
+    println!("{}: {}", label, paths.len());
+    for path in paths {
+        println!("  {}", path.display());
+    }
+}
+
+/// Execute the purge command.
+pub async fn execute(state_dir: &Path, project: &str, scope: PurgeScope, dry_run: bool, yes: bool) -> Result<()> {
+    let scope = scope.or_all();
+    let target = Target::resolve(&StateStore::new(state_dir), project)?;
+    let qdrant = if scope.memories && std::env::var("QDRANT_URL").is_ok() {
+        Some(QdrantStore::from_env().await?)
+    } else {
+        None
+    };
+
+    let plan = enumerate(state_dir, &target, scope, qdrant.as_ref()).await?;
+    println!("Stored data for '{}':", target.name);
+    plan.print(scope);
+
+    if plan.is_empty() {
+        println!("\nNothing to purge.");
+        return Ok(());
+    }
+    if dry_run {
+        println!("\nDry run: nothing was deleted.");
+        return Ok(());
+    }
+    if !yes && !confirm(&target.name)? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let mut staged = Staging::default();
+    match purge(state_dir, &target, scope, &plan, qdrant.as_ref(), &mut staged).await {
+        Ok(()) => {
+            staged.commit();
+            println!("\nPurged '{}'.", target.name);
+            Ok(())
+        }
+        Err(e) => {
+            staged.rollback();
+            Err(format!("Purge failed, nothing was deleted: {}", e).into())
+        }
+    }
+}
+
+/// Find everything in `scope` stored about `target`.
+async fn enumerate(state_dir: &Path, target: &Target, scope: PurgeScope, qdrant: Option<&QdrantStore>) -> Result<Plan> {
+    let mut plan = Plan::default();
+
+    if scope.state {
+        if let Some(id) = &target.project_id {
+            plan.state.extend(StateStore::new(state_dir).project_paths(id));
+            plan.state.extend(EventStore::new(state_dir).project_paths(id));
+            plan.state.extend(WorkStore::new(state_dir).project_paths(id));
+            plan.state.extend(SnapshotStore::new(state_dir).project_paths(id));
+        }
+    }
+
+    if scope.memories {
+        let filter = target.memory_filter();
+        plan.memories = LocalStore::new(state_dir.join("memory")).await?.count_matching(&filter).await?;
+        if let Some(qdrant) = qdrant {
+            plan.qdrant_memories = Some(qdrant.count_matching(&filter).await?);
+        }
+    }
+
+    if scope.feedback {
+        plan.feedback = FeedbackStore::new(state_dir.join("feedback"))?.count_for_agents(&target.agent_ids);
+    }
+
+    if scope.logs {
+        let logs_dir = config::logs_dir();
+        plan.logs = existing([session_log_path(&logs_dir, &target.name)]);
+        plan.combined_log_lines = filter_combined_log(&combined_log_path(&logs_dir), &target.name)?
+            .map_or(0, |(_, removed)| removed);
+    }
+
+    if scope.transcripts {
+        plan.transcripts = existing(target.sessions.iter().map(|s| log_dir_for(s)));
+        plan.transcripts.extend(existing(target.sessions.iter().map(|s| notes_path(s))));
+        for session in &target.sessions {
+            plan.transcripts.extend(recordings_for(session));
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Delete everything in `plan`, recording what to restore in `staged`.
+async fn purge(
+    state_dir: &Path,
+    target: &Target,
+    scope: PurgeScope,
+    plan: &Plan,
+    qdrant: Option<&QdrantStore>,
+    staged: &mut Staging,
+) -> Result<()> {
+    for path in plan.state.iter().chain(&plan.logs).chain(&plan.transcripts) {
+        staged.move_aside(path)?;
+    }
+
+    if plan.combined_log_lines > 0 {
+        let path = combined_log_path(&config::logs_dir());
+        if let Some((kept, _)) = filter_combined_log(&path, &target.name)? {
+            staged.back_up(&path)?;
+            commander_persistence::atomic::atomic_write(&path, kept.as_bytes())?;
+        }
+    }
+
+    if scope.memories && plan.memories > 0 {
+        let memory_dir = state_dir.join("memory");
+        staged.back_up(&memory_dir.join("memories.json"))?;
+        LocalStore::new(memory_dir).await?.delete_matching(&target.memory_filter()).await?;
+    }
+
+    if scope.feedback && plan.feedback > 0 {
+        let feedback_dir = state_dir.join("feedback");
+        staged.back_up(&feedback_dir.join("feedback.json"))?;
+        FeedbackStore::new(feedback_dir)?.remove_agents(&target.agent_ids)?;
+    }
+
+    // Last, since there is no undoing it
+    if let Some(qdrant) = qdrant {
+        if plan.qdrant_memories.unwrap_or(0) > 0 {
+            qdrant.delete_matching(&target.memory_filter()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The combined log without `project`'s lines, and how many were removed.
+///
+/// Returns `None` when the log does not exist.
+fn filter_combined_log(path: &Path, project: &str) -> io::Result<Option<(String, usize)>> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let key = session_key(project);
+    let mut kept = String::new();
+    let mut removed = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let ours = serde_json::from_str::<LogRecord>(&line)
+            .ok()
+            .and_then(|r| r.session)
+            .is_some_and(|s| session_key(&s) == key);
+        if ours {
+            removed += 1;
+        } else {
+            kept.push_str(&line);
+            kept.push('\n');
+        }
+    }
+    Ok(Some((kept, removed)))
+}
+
+fn existing(paths: impl IntoIterator<Item = PathBuf>) -> Vec<PathBuf> {
+    paths.into_iter().filter(|p| p.exists()).collect()
+}
+
+fn confirm(name: &str) -> Result<bool> {
+    print!("\nPermanently delete all of this for '{}'? [y/N] ", name);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
+
+/// Files moved aside or backed up during a purge.
+///
+/// Staged files sit next to the original (so moving them never crosses a
+/// filesystem) until the purge is committed or rolled back.
+#[derive(Debug, Default)]
+struct Staging {
+    /// Originals moved aside, to be deleted on commit.
+    moved: Vec<(PathBuf, PathBuf)>,
+    /// Copies of files modified in place, to be restored on rollback.
+    backups: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Staging {
+    fn staged_path(path: &Path) -> PathBuf {
+        let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        path.with_file_name(format!(".{}.{}", name, STAGED_SUFFIX))
+    }
+
+    fn move_aside(&mut self, path: &Path) -> io::Result<()> {
+        let staged = Self::staged_path(path);
+        fs::rename(path, &staged)?;
+        self.moved.push((path.to_path_buf(), staged));
+        Ok(())
+    }
+
+    fn back_up(&mut self, path: &Path) -> io::Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let staged = Self::staged_path(path);
+        fs::copy(path, &staged)?;
+        self.backups.push((path.to_path_buf(), staged));
+        Ok(())
+    }
+
+    /// Delete what was moved aside and drop the backups.
+    fn commit(self) {
+        for (original, staged) in self.moved.iter().chain(&self.backups) {
+            let removed = if staged.is_dir() {
+                fs::remove_dir_all(staged)
+            } else {
+                fs::remove_file(staged)
+            };
+            if let Err(e) = removed {
+                eprintln!("Warning: could not remove {} ({}): {}", staged.display(), original.display(), e);
+            }
+        }
+    }
+
+    /// Put everything back as it was.
+    fn rollback(self) {
+        for (original, staged) in self.backups.iter().chain(&self.moved).rev() {
+            if let Err(e) = fs::rename(staged, original) {
+                eprintln!("Warning: could not restore {} from {}: {}", original.display(), staged.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_defaults_to_everything() {
+        let all = PurgeScope::default().or_all();
+        assert!(all.state && all.memories && all.transcripts && all.logs && all.feedback);
+
+        let logs = PurgeScope {
+            logs: true,
+            ..Default::default()
+        };
+        assert_eq!(logs.or_all(), logs);
+    }
+
+    #[test]
+    fn test_filter_combined_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("combined.jsonl");
+        let line = |session: &str| {
+            format!(
+                r#"{{"timestamp":"2026-10-17T12:00:00Z","level":"INFO","target":"t","component":"cli","session":"{}","message":"m","fields":{{}}}}"#,
+                session
+            )
+        };
+        fs::write(&path, format!("{}\n{}\nnot json\n", line("commander-api"), line("web"))).unwrap();
+
+        let (kept, removed) = filter_combined_log(&path, "api").unwrap().unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(kept, format!("{}\nnot json\n", line("web")));
+        assert!(filter_combined_log(&dir.path().join("missing"), "api").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_staging_rollback_restores_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let moved = dir.path().join("events");
+        fs::create_dir(&moved).unwrap();
+        fs::write(moved.join("evt.json"), "{}").unwrap();
+        let edited = dir.path().join("memories.json");
+        fs::write(&edited, "before").unwrap();
+
+        let mut staged = Staging::default();
+        staged.move_aside(&moved).unwrap();
+        staged.back_up(&edited).unwrap();
+        fs::write(&edited, "after").unwrap();
+        assert!(!moved.exists());
+
+        staged.rollback();
+        assert!(moved.join("evt.json").exists());
+        assert_eq!(fs::read_to_string(&edited).unwrap(), "before");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_staging_commit_deletes_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let moved = dir.path().join("proj.json");
+        fs::write(&moved, "{}").unwrap();
+        let edited = dir.path().join("feedback.json");
+        fs::write(&edited, "[]").unwrap();
+
+        let mut staged = Staging::default();
+        staged.move_aside(&moved).unwrap();
+        staged.back_up(&edited).unwrap();
+        staged.commit();
+
+        assert!(!moved.exists());
+        assert!(edited.exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -5,11 +5,14 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 use commander_adapters::AdapterRegistry;
+use commander_core::snippets::{self, SnippetContext};
+use commander_core::{acquire_send_lock, update_session_registry, AttachOutcome, Frontend, HandoffDirection};
 use commander_models::project::AdapterType;
-use commander_models::Project;
+use commander_models::{Project, WorkItem};
 #[cfg(feature = "agents")]
 use commander_orchestrator::AgentOrchestrator;
-use commander_persistence::StateStore;
+use commander_persistence::{StateStore, WorkStore};
+use commander_work::WorkQueue;
 use commander_tmux::TmuxOrchestrator;
 use rustyline::completion::{Completer, Pair};
 use rustyline::error::ReadlineError;
@@ -21,6 +24,8 @@ use rustyline::{Context, Editor, Helper, Result as RlResult};
 use tracing::{debug, info};
 
 use crate::chat::ChatClient;
+use crate::chat_tools::{self, ChatAction, MAX_TOOL_ROUNDS};
+use crate::stop_commit;
 use crate::validate_project_path;
 
 /// Arguments for the enhanced /connect command.
@@ -100,6 +105,17 @@ static COMMAND_HELP: &[CommandHelp] = &[
             ("/dc", "Same as /disconnect"),
         ],
     },
+    CommandHelp {
+        name: "takeover",
+        aliases: &[],
+        brief: "Claim the connected session from the TUI or Telegram",
+        description: "Marks the connected session as attached to this REPL in the shared session registry.\n\
+                      Use it when another frontend still holds the session and you want to continue here.",
+        usage: "/takeover",
+        examples: &[
+            ("/takeover", "Claim the connected session"),
+        ],
+    },
     CommandHelp {
         name: "send",
         aliases: &[],
@@ -125,11 +141,38 @@ static COMMAND_HELP: &[CommandHelp] = &[
         aliases: &[],
         brief: "Stop session (commits changes, ends tmux)",
         description: "Stops a session by first committing any uncommitted git changes in the project directory, \
-                      then destroying the tmux session. If stopping the connected session, also disconnects.",
-        usage: "/stop [session]",
+                      then destroying the tmux session. If stopping the connected session, also disconnects. \
+                      With [stop] ai_commit in config.toml, the commit message and a pull request are written \
+                      from the diff and transcript, and the branch is pushed; --no-ai-commit skips this.",
+        usage: "/stop [session] [--no-ai-commit]",
         examples: &[
             ("/stop", "Stop current connected session"),
             ("/stop duetto", "Stop the 'duetto' session"),
+            ("/stop --no-ai-commit", "Stop with a plain WIP commit"),
+        ],
+    },
+    CommandHelp {
+        name: "pause",
+        aliases: &[],
+        brief: "Pause a session until /resume",
+        description: "Pauses a project's session without ending it. Adapters with a pause command (MPM) are sent it; \
+                      other sessions have their processes suspended through tmux. Pollers in every frontend skip \
+                      paused sessions, which show a paused badge.",
+        usage: "/pause [project]",
+        examples: &[
+            ("/pause", "Pause the connected project"),
+            ("/pause duetto", "Pause the 'duetto' session"),
+        ],
+    },
+    CommandHelp {
+        name: "resume",
+        aliases: &[],
+        brief: "Resume a paused session",
+        description: "Resumes a session paused with /pause, the same way it was paused.",
+        usage: "/resume [project]",
+        examples: &[
+            ("/resume", "Resume the connected project"),
+            ("/resume duetto", "Resume the 'duetto' session"),
         ],
     },
     CommandHelp {
@@ -227,6 +270,31 @@ static COMMAND_HELP: &[CommandHelp] = &[
             ("/msgs", "Same as /messages"),
         ],
     },
+    CommandHelp {
+        name: "search",
+        aliases: &[],
+        brief: "Search session transcripts and memories",
+        description: "Keyword search over every session's persisted transcript, combined with a \
+                      semantic search over the memory store when the agent orchestrator is available.\n\
+                      Results are merged without duplicates and show the session, date and source.",
+        usage: "/search <query>",
+        examples: &[
+            ("/search migration failed", "Find where a migration failed in any session"),
+        ],
+    },
+    CommandHelp {
+        name: "run",
+        aliases: &[],
+        brief: "Send a named snippet to the connected session",
+        description: "Sends a snippet from ~/.ai-commander/snippets.toml, with {project}, {path} and {branch}\n\
+                      resolved for the connected project and {args} replaced by the text after the name.\n\
+                      `!name [args]` is a shortcut; /snippets lists them.",
+        usage: "/run <name> [args]",
+        examples: &[
+            ("/run review", "Send the 'review' snippet"),
+            ("!fix-issue 42", "Send 'fix-issue' with {args} set to 42"),
+        ],
+    },
     CommandHelp {
         name: "telegram",
         aliases: &[],
@@ -254,8 +322,8 @@ struct CommandCompleter {
 impl CommandCompleter {
     const COMMANDS: &'static [&'static str] = &[
         "/alias", "/clear", "/connect", "/disconnect", "/health", "/help", "/inspect",
-        "/instances", "/list", "/list-instances", "/messages", "/msgs", "/quit", "/register",
-        "/send", "/sessions", "/status", "/stop", "/telegram", "/unalias",
+        "/instances", "/list", "/list-instances", "/messages", "/msgs", "/pause", "/quit", "/register",
+        "/resume", "/run", "/search", "/send", "/sessions", "/snippets", "/status", "/stop", "/takeover", "/telegram", "/unalias",
         "/unregister", "/usage",
     ];
 
@@ -380,7 +448,11 @@ impl CommandCompleter {
                 }
                 self.complete_project_names(start_pos, parts.get(1).unwrap_or(&""))
             }
-            "/status" | "/s" => {
+            "/status" | "/s" | "/pause" | "/resume" => {
+                self.complete_project_names(start_pos, parts.get(1).unwrap_or(&""))
+            }
+            // First argument only: the project to alias
+            "/alias" if input[..start_pos].split_whitespace().count() == 1 => {
                 self.complete_project_names(start_pos, parts.get(1).unwrap_or(&""))
             }
             "/stop" => {
@@ -577,6 +649,8 @@ pub enum ReplCommand {
     Connect(ConnectTarget),
     /// Disconnect from current project
     Disconnect,
+    /// Claim the connected session from another frontend
+    Takeover,
     /// Send message to connected project
     Send(String),
     /// Route message to specific session(s) via @alias syntax
@@ -590,6 +664,10 @@ pub enum ReplCommand {
     Sessions,
     /// Stop a session (commits git changes, destroys tmux)
     Stop(Option<String>),
+    /// Pause a project's session
+    Pause(Option<String>),
+    /// Resume a paused session
+    Resume(Option<String>),
     /// Show help (optionally for a specific command)
     Help(Option<String>),
     /// Register a new project instance
@@ -609,6 +687,17 @@ pub enum ReplCommand {
     Telegram,
     /// Show unread MPM messages
     Messages,
+    /// Search transcripts and memories
+    Search(String),
+    /// Send a snippet from snippets.toml (`/run <name> [args]` or `!name [args]`)
+    RunSnippet {
+        /// Snippet name
+        name: String,
+        /// Text after the name, for `{args}`
+        args: String,
+    },
+    /// List snippets
+    Snippets,
     /// Run startup health checks
     Health,
     /// Show Claude plan usage report
@@ -641,6 +730,13 @@ impl ReplCommand {
             return ReplCommand::Text(String::new());
         }
 
+        if let Some((name, args)) = commander_core::snippets::parse_invocation(input) {
+            return ReplCommand::RunSnippet {
+                name: name.to_string(),
+                args: args.to_string(),
+            };
+        }
+
         // Check for slash commands
         if let Some(stripped) = input.strip_prefix('/') {
             let parts: Vec<&str> = stripped.splitn(2, ' ').collect();
@@ -652,6 +748,7 @@ impl ReplCommand {
                 "status" | "s" => ReplCommand::Status(arg),
                 "connect" | "c" => Self::parse_connect(arg),
                 "disconnect" | "dc" => ReplCommand::Disconnect,
+                "takeover" => ReplCommand::Takeover,
                 "send" => arg
                     .map(ReplCommand::Send)
                     .unwrap_or(ReplCommand::UsageError(
@@ -659,6 +756,8 @@ impl ReplCommand {
                     )),
                 "sessions" => ReplCommand::Sessions,
                 "stop" => ReplCommand::Stop(arg),
+                "pause" => ReplCommand::Pause(arg),
+                "resume" => ReplCommand::Resume(arg),
                 "register" => Self::parse_register(arg),
                 "unregister" => arg
                     .map(ReplCommand::Unregister)
@@ -667,6 +766,12 @@ impl ReplCommand {
                 "help" | "h" | "?" => ReplCommand::Help(arg),
                 "telegram" => ReplCommand::Telegram,
                 "messages" | "msgs" => ReplCommand::Messages,
+                "search" => arg
+                    .filter(|a| !a.is_empty())
+                    .map(ReplCommand::Search)
+                    .unwrap_or(ReplCommand::UsageError("Usage: /search <query>".to_string())),
+                "run" => ReplCommand::UsageError("Usage: /run <name> [args]  (see /snippets)".to_string()),
+                "snippets" => ReplCommand::Snippets,
                 "health" => ReplCommand::Health,
                 "usage" => ReplCommand::Usage,
                 "quit" | "q" | "exit" => ReplCommand::Quit,
@@ -914,6 +1019,7 @@ impl ReplCommand {
 /// REPL state
 pub struct Repl {
     editor: Editor<CommandCompleter, DefaultHistory>,
+    state_dir: PathBuf,
     store: StateStore,
     registry: AdapterRegistry,
     connected_project: Option<String>,
@@ -955,7 +1061,7 @@ impl Repl {
         editor.set_helper(Some(CommandCompleter::new(state_dir.to_path_buf())));
         let store = StateStore::new(state_dir);
         let registry = AdapterRegistry::new();
-        let chat_client = ChatClient::new();
+        let chat_client = ChatClient::new().with_system_prompt(chat_tools::SYSTEM_PROMPT);
 
         // Create tokio runtime for async operations
         let runtime = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
@@ -995,6 +1101,7 @@ impl Repl {
 
         Ok(Self {
             editor,
+            state_dir: state_dir.to_path_buf(),
             store,
             registry,
             connected_project: None,
@@ -1142,11 +1249,14 @@ impl Repl {
             }
 
             ReplCommand::Connect(target) => {
+                self.detach_handoff();
                 self.handle_connect(target)?;
+                self.attach_handoff();
                 Ok(false)
             }
 
             ReplCommand::Disconnect => {
+                self.detach_handoff();
                 if let Some(project) = self.connected_project.take() {
                     println!("Disconnected from '{}'", project);
                 } else {
@@ -1155,6 +1265,11 @@ impl Repl {
                 Ok(false)
             }
 
+            ReplCommand::Takeover => {
+                self.handle_takeover();
+                Ok(false)
+            }
+
             ReplCommand::Send(message) => {
                 // Clone values upfront to avoid borrow checker issues
                 let project = self.connected_project.clone();
@@ -1168,8 +1283,16 @@ impl Repl {
                                 .capture_output(&session, None, Some(200))
                                 .unwrap_or_default();
 
-                            match tmux.send_line(&session, None, &message) {
+                            let sent = acquire_send_lock(&session, "REPL")
+                                .map_err(|e| e.to_string())
+                                .and_then(|_lock| {
+                                    tmux.send_line(&session, None, &message).map_err(|e| e.to_string())
+                                });
+                            match sent {
                                 Ok(_) => {
+                                    let _ = update_session_registry(|r| {
+                                        r.record_query(&session, Frontend::Repl, message.as_str())
+                                    });
                                     println!("[{}] > {}", project, message);
                                     print!("[working");
                                     io::stdout().flush().ok();
@@ -1183,6 +1306,7 @@ impl Repl {
                                     let mut last_output = initial_output.clone();
                                     let mut dots_printed = 0;
                                     let mut got_response = false;
+                                    let mut response_lines: Vec<String> = Vec::new();
 
                                     while start.elapsed() < max_wait {
                                         std::thread::sleep(poll_interval);
@@ -1212,6 +1336,7 @@ impl Repl {
                                                     for line in &new_lines {
                                                         println!("[{}] {}", project, line);
                                                     }
+                                                    response_lines.extend(new_lines);
                                                     last_change_time = std::time::Instant::now();
                                                 }
 
@@ -1230,6 +1355,15 @@ impl Repl {
                                         println!("]");
                                         println!("(AI is processing - response will appear in tmux session)");
                                     } else {
+                                        let _ = update_session_registry(|r| {
+                                            r.record_message(
+                                                &session,
+                                                Frontend::Repl,
+                                                HandoffDirection::Received,
+                                                response_lines.join("\n"),
+                                            )
+                                        });
+
                                         // Try to provide orchestrator summary if available
                                         #[cfg(feature = "agents")]
                                         if let Some(summary) = self.try_orchestrator_analysis(&session, &last_output) {
@@ -1281,7 +1415,12 @@ impl Repl {
 
                         match session_name {
                             Some(session) => {
-                                match tmux.send_line(&session, None, &message) {
+                                let sent = acquire_send_lock(&session, "REPL")
+                                    .map_err(|e| e.to_string())
+                                    .and_then(|_lock| {
+                                        tmux.send_line(&session, None, &message).map_err(|e| e.to_string())
+                                    });
+                                match sent {
                                     Ok(_) => {
                                         println!("[@{}] > {}", target, message);
                                         sent_count += 1;
@@ -1352,17 +1491,34 @@ impl Repl {
                 Ok(false)
             }
 
-            ReplCommand::Stop(target) => {
+            ReplCommand::Stop(arg) => {
+                let (target, no_ai_commit) = stop_commit::parse_stop_args(arg.as_deref());
                 let name = target.or_else(|| self.connected_project.clone());
 
                 if let Some(name) = name {
-                    self.stop_session(&name)?;
+                    self.stop_session(&name, no_ai_commit)?;
                 } else {
                     println!("Usage: /stop [session] or connect to a session first");
                 }
                 Ok(false)
             }
 
+            ReplCommand::Pause(arg) => {
+                match arg.or_else(|| self.connected_project.clone()) {
+                    Some(name) => self.pause_project(&name),
+                    None => println!("Usage: /pause [project] or connect to a project first"),
+                }
+                Ok(false)
+            }
+
+            ReplCommand::Resume(arg) => {
+                match arg.or_else(|| self.connected_project.clone()) {
+                    Some(name) => self.resume_project(&name),
+                    None => println!("Usage: /resume [project] or connect to a project first"),
+                }
+                Ok(false)
+            }
+
             ReplCommand::Register { path, adapter, name } => {
                 self.handle_register(path, adapter, name)?;
                 Ok(false)
@@ -1388,6 +1544,48 @@ impl Repl {
                 Ok(false)
             }
 
+            ReplCommand::Search(query) => {
+                self.handle_search(&query);
+                Ok(false)
+            }
+
+            ReplCommand::RunSnippet { name, args } => {
+                let Some(project) = self.connected_project.clone() else {
+                    println!("Not connected to any project. Use /connect first.");
+                    return Ok(false);
+                };
+                let path = self
+                    .store
+                    .load_all_projects()?
+                    .into_values()
+                    .find(|p| p.name == project)
+                    .map(|p| p.path);
+                let context = SnippetContext::for_project(&project, path.as_deref());
+                match snippets::load_snippets().and_then(|s| snippets::expand(&s, &name, &args, &context)) {
+                    Ok(text) => self.handle_command(ReplCommand::Send(text)),
+                    Err(e) => {
+                        println!("{}", e);
+                        Ok(false)
+                    }
+                }
+            }
+
+            ReplCommand::Snippets => {
+                match snippets::load_snippets() {
+                    Ok(list) if list.is_empty() => {
+                        println!("No snippets. Add one with `commander snippets add <name> <text>`.");
+                    }
+                    Ok(list) => {
+                        println!("Snippets (/run <name> or !name):");
+                        for line in snippets::format_list(&list) {
+                            println!("  {}", line);
+                        }
+                    }
+                    Err(e) => println!("{}", e),
+                }
+                Ok(false)
+            }
+
             ReplCommand::Health => {
                 self.handle_health();
                 Ok(false)
@@ -1601,8 +1799,12 @@ impl Repl {
                     format!("{} {}", cmd, cmd_args.join(" "))
                 };
 
-                // Create tmux session in project directory
-                match tmux.create_session_in_dir(&session_name, Some(path)) {
+                // Create tmux session in project directory, with its environment
+                let (env, env_warning) = crate::project_session_env(path);
+                if let Some(warning) = env_warning {
+                    println!("Warning: {}", warning);
+                }
+                match tmux.create_session_with_env(&session_name, Some(path), &env) {
                     Ok(_) => {
                         // Send command to start the AI tool
                         if let Err(e) = tmux.send_line(&session_name, None, &full_cmd) {
@@ -1625,8 +1827,64 @@ impl Repl {
         Ok(())
     }
 
+    /// Pause a project's session with its adapter's pause command or by
+    /// suspending its processes.
+    fn pause_project(&self, name: &str) {
+        let Some(tmux) = &self.tmux else {
+            println!("Tmux not available");
+            return;
+        };
+        let session = self
+            .sessions
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.replace([' ', '.', '/', ':'], "-"));
+        if !tmux.session_exists(&session) {
+            println!("Session '{}' not found", session);
+            return;
+        }
+
+        let tool_id = self
+            .store
+            .load_all_projects()
+            .ok()
+            .and_then(|projects| {
+                projects
+                    .values()
+                    .find(|p| p.name == name)
+                    .and_then(|p| p.config.get("tool").and_then(|v| v.as_str()).map(String::from))
+            })
+            .unwrap_or_else(|| "claude-code".to_string());
+        let adapter = self.registry.get(&tool_id);
+        match crate::pause::pause_session(tmux, adapter.as_deref(), &session, name, "repl") {
+            Ok(description) => println!("⏸ {} (/resume {} to continue)", description, name),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    /// Resume a session paused with `/pause`.
+    fn resume_project(&self, name: &str) {
+        let Some(tmux) = &self.tmux else {
+            println!("Tmux not available");
+            return;
+        };
+        let session = self
+            .sessions
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.replace([' ', '.', '/', ':'], "-"));
+        match crate::pause::resume_session(tmux, &session) {
+            Ok(description) => println!("▶ {}", description),
+            Err(e) => println!("{}", e),
+        }
+    }
+
     /// Stop a session: commit git changes and destroy tmux session.
-    fn stop_session(&mut self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// With `[stop] ai_commit` (unless `no_ai_commit`), the commit message is
+    /// written from the diff and transcript, and the branch is pushed with a
+    /// pull request.
+    fn stop_session(&mut self, name: &str, no_ai_commit: bool) -> Result<(), Box<dyn std::error::Error>> {
         let session_name = name.replace([' ', '.', '/', ':'], "-");
 
         // Find project path for git operations
@@ -1641,8 +1899,37 @@ impl Repl {
         if let Some(path) = &project_path {
             println!("Checking for uncommitted changes in {}...", path);
 
-            match Self::git_commit_changes(path, name) {
-                Ok(true) => println!("Changes committed."),
+            let settings = stop_commit::stop_settings(no_ai_commit);
+            let summary = if settings.ai_commit && Self::is_git_worktree(path) {
+                println!("Writing commit message and pull request...");
+                match stop_commit::summarize_changes(path, name, &session_name, settings.model.as_deref()) {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        println!("{}; using a WIP commit.", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let message = summary
+                .as_ref()
+                .map(|s| s.commit_message.clone())
+                .unwrap_or_else(|| stop_commit::wip_message(name));
+
+            match Self::git_commit_changes(path, &message) {
+                Ok(true) => {
+                    println!("Changes committed.");
+                    if let Some(summary) = summary {
+                        println!("  {}", summary.title);
+                        if settings.push {
+                            match stop_commit::publish(path, &summary) {
+                                Ok(published) => println!("{}", stop_commit::describe_published(&published)),
+                                Err(e) => println!("Not pushed: {}", e),
+                            }
+                        }
+                    }
+                }
                 Ok(false) => println!("No changes to commit."),
                 Err(e) => println!("Git warning: {}", e),
             }
@@ -1656,6 +1943,9 @@ impl Repl {
 
                     // Remove from tracking
                     self.sessions.remove(name);
+                    if let Err(e) = commander_core::pause::mark_resumed(&session_name) {
+                        debug!(session = %session_name, error = %e, "failed to clear paused state");
+                    }
 
                     // Disconnect if it was current
                     if self.connected_project.as_deref() == Some(name) {
@@ -1684,8 +1974,8 @@ impl Repl {
             .unwrap_or(false)
     }
 
-    /// Commit any uncommitted git changes in the project directory.
-    fn git_commit_changes(path: &str, project_name: &str) -> Result<bool, String> {
+    /// Commit any uncommitted git changes in the project directory with `message`.
+    fn git_commit_changes(path: &str, message: &str) -> Result<bool, String> {
         use std::process::Command;
 
         // Skip git operations if not in a git worktree
@@ -1714,9 +2004,8 @@ impl Repl {
             .map_err(|e| format!("Failed to stage changes: {}", e))?;
 
         // Commit with message
-        let message = format!("WIP: Auto-commit from Commander session '{}'", project_name);
         let commit = Command::new("git")
-            .args(["commit", "-m", &message])
+            .args(["commit", "-m", message])
             .current_dir(path)
             .output()
             .map_err(|e| format!("Failed to commit: {}", e))?;
@@ -1730,28 +2019,157 @@ impl Repl {
     }
 
     /// Handle chat message via OpenRouter.
+    ///
+    /// The model may ask for local actions (see `crate::chat_tools`); they
+    /// run between rounds, and their results go back to the model until it
+    /// answers in text. The last round offers no tools, so it must.
     fn handle_chat(&mut self, message: &str) -> Result<(), Box<dyn std::error::Error>> {
-        print!("Thinking...");
-        io::stdout().flush()?;
-
-        let result = self.runtime.block_on(self.chat_client.send(message));
+        let tools = chat_tools::tool_definitions();
+        let mut results = None;
+
+        for round in 0..=MAX_TOOL_ROUNDS {
+            let offered = if round < MAX_TOOL_ROUNDS { tools.as_slice() } else { &[] };
+            print!("Thinking...");
+            io::stdout().flush()?;
+
+            let reply = match results.take() {
+                None => self.runtime.block_on(self.chat_client.send_with_tools(message, offered)),
+                Some(results) => self
+                    .runtime
+                    .block_on(self.chat_client.continue_with_tools(results, offered)),
+            };
 
-        // Clear "Thinking..." line
-        print!("\r            \r");
-        io::stdout().flush()?;
+            // Clear "Thinking..." line
+            print!("\r            \r");
+            io::stdout().flush()?;
 
-        match result {
-            Ok(response) => {
-                println!("{}", response);
+            let reply = match reply {
+                Ok(reply) => reply,
+                Err(e) => {
+                    println!("Chat error: {}", e);
+                    return Ok(());
+                }
+            };
+            if !reply.content.trim().is_empty() {
+                println!("{}", reply.content.trim());
             }
-            Err(e) => {
-                println!("Chat error: {}", e);
+            if reply.tool_calls.is_empty() {
+                break;
             }
+            results = Some(self.run_chat_tools(&reply.tool_calls)?);
         }
 
         Ok(())
     }
 
+    /// Run the tool calls of a chat reply, asking once to confirm those that
+    /// change anything. Returns (call ID, result) pairs for the model.
+    fn run_chat_tools(
+        &mut self,
+        calls: &[commander_agent::ToolCall],
+    ) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+        let actions: Vec<Result<ChatAction, String>> = calls.iter().map(ChatAction::from_call).collect();
+        let pending: Vec<String> = actions
+            .iter()
+            .flatten()
+            .filter(|a| a.needs_confirmation())
+            .map(ChatAction::describe)
+            .collect();
+        let approved = pending.is_empty() || self.confirm_chat_actions(&pending);
+
+        let mut results = Vec::new();
+        for (call, action) in calls.iter().zip(actions) {
+            let result = match action {
+                Err(e) => format!("Error: {}", e),
+                Ok(action) if action.needs_confirmation() && !approved => "The user declined this action.".to_string(),
+                Ok(action) => self.run_chat_action(&action)?,
+            };
+            debug!(tool = %call.name, result = %result, "Chat tool call");
+            results.push((call.id.clone(), result));
+        }
+        Ok(results)
+    }
+
+    /// Ask the user to confirm the listed actions.
+    fn confirm_chat_actions(&mut self, actions: &[String]) -> bool {
+        println!("Commander wants to:");
+        for action in actions {
+            println!("  - {}", action);
+        }
+        match self.editor.readline("Proceed? [y/N] ") {
+            Ok(answer) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+            Err(_) => false,
+        }
+    }
+
+    /// Run one chat action, returning its result for the model.
+    fn run_chat_action(&mut self, action: &ChatAction) -> Result<String, Box<dyn std::error::Error>> {
+        match action {
+            ChatAction::ListProjects => {
+                let projects = self.store.load_all_projects()?;
+                if projects.is_empty() {
+                    return Ok("No projects are registered.".to_string());
+                }
+                let mut lines: Vec<String> = projects
+                    .values()
+                    .map(|p| {
+                        let connected = if Some(&p.name) == self.connected_project.as_ref() {
+                            ", connected"
+                        } else {
+                            ""
+                        };
+                        format!("{} ({:?}{}) at {}", p.name, p.state, connected, p.path)
+                    })
+                    .collect();
+                lines.sort();
+                Ok(lines.join("\n"))
+            }
+            ChatAction::Status { project } => match self.store.find_project_by_name_or_alias(project)? {
+                Some(p) => {
+                    let running = self
+                        .tmux
+                        .as_ref()
+                        .is_some_and(|t| t.session_exists(&format!("commander-{}", p.name)) || t.session_exists(&p.name));
+                    Ok(format!(
+                        "Project {}: state {:?}, path {}, session {}",
+                        p.name,
+                        p.state,
+                        p.path,
+                        if running { "running" } else { "not running" }
+                    ))
+                }
+                None => Ok(format!("Project not found: {}", project)),
+            },
+            ChatAction::EnqueueWork { project, task, priority } => {
+                let Some(p) = self.store.find_project_by_name_or_alias(project)? else {
+                    return Ok(format!("Project not found: {}", project));
+                };
+                let queue = WorkQueue::new(WorkStore::new(&self.state_dir));
+                let id = queue.enqueue(WorkItem::with_priority(p.name.as_str(), task.as_str(), *priority))?;
+                println!("Queued work for {}: {}", p.name, task);
+                Ok(format!("Queued work item {} for {}", id, p.name))
+            }
+            ChatAction::Connect { project } => {
+                if let Some(cmd) = action.to_command(self.connected_project.as_deref()) {
+                    self.handle_command(cmd)?;
+                }
+                Ok(match &self.connected_project {
+                    Some(connected) => format!("Connected to {}", connected),
+                    None => format!("Could not connect to {}", project),
+                })
+            }
+            ChatAction::SendMessage { project, .. } => {
+                if project.is_none() && self.connected_project.is_none() {
+                    return Ok("Not connected to a project; connect first or name the project.".to_string());
+                }
+                if let Some(cmd) = action.to_command(self.connected_project.as_deref()) {
+                    self.handle_command(cmd)?;
+                }
+                Ok("Sent. The session's response was shown to the user.".to_string())
+            }
+        }
+    }
+
     /// Show unread MPM messages from the messaging store and dispatch them
     /// to active tmux sessions via `/mpm-message read`.
     fn handle_messages(&self) -> Result<(), Box<dyn std::error::Error>> {
@@ -1913,6 +2331,10 @@ impl Repl {
                 println!("  Code: {}", code);
                 println!();
                 println!("  In Telegram, send: /pair {}", code);
+                if let Some(bot) = commander_telegram::pairing::bot_username() {
+                    println!("  Or open: {}", commander_telegram::pairing::pairing_link(&bot, &code));
+                }
+                println!("  In Matrix, send: !pair {}", code);
                 println!();
                 println!("  Expires in 5 minutes");
                 if !project_name.is_empty() {
@@ -2008,6 +2430,101 @@ impl Repl {
         Ok(())
     }
 
+    /// Tmux session name of the connected project, if any.
+    fn connected_session(&self) -> Option<String> {
+        self.connected_project
+            .as_ref()
+            .and_then(|p| self.sessions.get(p))
+            .cloned()
+    }
+
+    /// Attach the connected session in the shared registry and replay activity
+    /// from other frontends so the conversation can continue here.
+    fn attach_handoff(&self) {
+        let Some(session) = self.connected_session() else {
+            return;
+        };
+
+        let result = update_session_registry(|r| {
+            let outcome = r.attach(&session, Frontend::Repl, None);
+            let context = r.get(&session).and_then(|rec| rec.resume_context(Frontend::Repl));
+            (outcome, context)
+        });
+
+        match result {
+            Ok((outcome, context)) => {
+                if let Some(context) = context {
+                    println!("Recent activity from other frontends:");
+                    for line in context.lines() {
+                        println!("  {}", line);
+                    }
+                }
+                if let AttachOutcome::HeldBy(holder) = outcome {
+                    println!(
+                        "'{}' is attached in {}. Use /takeover to claim it.",
+                        session, holder.frontend
+                    );
+                }
+            }
+            Err(e) => debug!(session = %session, error = %e, "failed to update session registry"),
+        }
+    }
+
+    /// Release the connected session in the shared registry.
+    fn detach_handoff(&self) {
+        if let Some(session) = self.connected_session() {
+            let _ = update_session_registry(|r| r.detach(&session, Frontend::Repl));
+        }
+    }
+
+    /// Handle /takeover command - claim the connected session for this REPL.
+    fn handle_takeover(&self) {
+        let Some(session) = self.connected_session() else {
+            println!("Not connected to any project. Use /connect <project> first.");
+            return;
+        };
+
+        match update_session_registry(|r| r.takeover(&session, Frontend::Repl, None)) {
+            Ok(Some(previous)) => println!("Took over '{}' from {}", session, previous.frontend),
+            Ok(None) => println!("'{}' is attached here", session),
+            Err(e) => println!("Failed to claim session: {}", e),
+        }
+    }
+
+    /// Handle /search - keyword search over transcripts merged with memory search.
+    fn handle_search(&self, query: &str) {
+        use crate::history_search::{self, SEARCH_LIMIT};
+
+        let transcripts = commander_core::search_log_entries(query, SEARCH_LIMIT);
+        #[cfg(feature = "agents")]
+        let memories = match &self.orchestrator {
+            Some(orchestrator) => self
+                .runtime
+                .block_on(history_search::search_memories(
+                    orchestrator.memory_store().as_ref(),
+                    query,
+                    SEARCH_LIMIT,
+                ))
+                .unwrap_or_else(|e| {
+                    println!("Memory search unavailable: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+        #[cfg(not(feature = "agents"))]
+        let memories = Vec::new();
+
+        let hits = history_search::merge(transcripts, memories, SEARCH_LIMIT);
+        if hits.is_empty() {
+            println!("No results for '{}'", query);
+            return;
+        }
+        println!("{} results for '{}':", hits.len(), query);
+        for (i, hit) in hits.iter().enumerate() {
+            println!("  {}", hit.format_line(i + 1));
+        }
+    }
+
     /// Handle /health command - runs startup health checks and prints a report.
     fn handle_health(&self) {
         let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
@@ -2188,7 +2705,8 @@ impl Repl {
         }
     }
 
-    /// Handle /usage — show Claude plan usage report.
+    /// Handle /usage — show Claude plan usage report and, with the agent
+    /// orchestrator, spend per OpenRouter key.
     fn handle_usage(&self) -> Result<(), Box<dyn std::error::Error>> {
         use commander_core::usage::UsageTracker;
 
@@ -2204,6 +2722,21 @@ impl Repl {
         }
 
         println!("{}", tracker.format_report());
+
+        #[cfg(feature = "agents")]
+        if let Some(orchestrator) = &self.orchestrator {
+            println!("\nOpenRouter keys:");
+            for key in orchestrator.provider_key_usage() {
+                println!(
+                    "  {:<8} {:>6} requests  {:>4} rate limited  ${:.4}{}",
+                    key.label,
+                    key.requests,
+                    key.rate_limited,
+                    key.cost_usd(),
+                    if key.cooling_down { "  (cooling down)" } else { "" }
+                );
+            }
+        }
         Ok(())
     }
 }
@@ -2586,7 +3119,9 @@ fn print_help(topic: Option<&str>) {
             println!("    /list                                    List all projects");
             println!("    /status [project]                        Show project status");
             println!("    /sessions                                List tmux sessions");
-            println!("    /stop [session]                          Stop session (commits changes, ends tmux)");
+            println!("    /stop [session] [--no-ai-commit]         Stop session (commits changes, ends tmux)");
+            println!("    /pause [project]                         Pause a session until /resume");
+            println!("    /resume [project]                        Resume a paused session");
             println!("    /register <path> [--adapter <t>] [--name <n>]  Register a project instance");
             println!("    /unregister <name-or-alias>              Remove a registered instance");
             println!("    /instances                               List all registered instances");
@@ -2602,6 +3137,13 @@ fn print_help(topic: Option<&str>) {
             println!("  MPM Messaging:");
             println!("    /messages, /msgs                         Show unread MPM messages");
             println!();
+            println!("  History:");
+            println!("    /search <query>                          Search transcripts and memories");
+            println!();
+            println!("  Snippets:");
+            println!("    /run <name> [args], !name [args]         Send a snippet from snippets.toml");
+            println!("    /snippets                                List snippets");
+            println!();
             println!("  Telegram Integration:");
             println!("    /telegram                                Generate pairing code for Telegram bot");
             println!();
@@ -2716,6 +3258,20 @@ mod tests {
         assert_eq!(ReplCommand::parse("/exit"), ReplCommand::Quit);
     }
 
+    #[test]
+    fn test_parse_snippets() {
+        assert_eq!(
+            ReplCommand::parse("!fix-issue 42"),
+            ReplCommand::RunSnippet { name: "fix-issue".to_string(), args: "42".to_string() }
+        );
+        assert_eq!(
+            ReplCommand::parse("/run review"),
+            ReplCommand::RunSnippet { name: "review".to_string(), args: String::new() }
+        );
+        assert!(matches!(ReplCommand::parse("/run"), ReplCommand::UsageError(_)));
+        assert_eq!(ReplCommand::parse("/snippets"), ReplCommand::Snippets);
+    }
+
     #[test]
     fn test_parse_sessions() {
         assert_eq!(ReplCommand::parse("/sessions"), ReplCommand::Sessions);
@@ -2734,11 +3290,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_pause_resume() {
+        assert_eq!(ReplCommand::parse("/pause"), ReplCommand::Pause(None));
+        assert_eq!(
+            ReplCommand::parse("/resume duetto"),
+            ReplCommand::Resume(Some("duetto".to_string()))
+        );
+    }
+
     #[test]
     fn test_parse_telegram() {
         assert_eq!(ReplCommand::parse("/telegram"), ReplCommand::Telegram);
     }
 
+    #[test]
+    fn test_parse_search() {
+        assert_eq!(
+            ReplCommand::parse("/search build failed"),
+            ReplCommand::Search("build failed".to_string())
+        );
+        assert!(matches!(ReplCommand::parse("/search"), ReplCommand::UsageError(_)));
+    }
+
+    #[test]
+    fn test_parse_takeover() {
+        assert_eq!(ReplCommand::parse("/takeover"), ReplCommand::Takeover);
+    }
+
     #[test]
     fn test_parse_help() {
         assert_eq!(ReplCommand::parse("/help"), ReplCommand::Help(None));
@@ -3130,6 +3709,33 @@ mod tests {
         assert!(replacements.contains(&"/stop"));
     }
 
+    #[test]
+    fn test_completer_project_arguments() {
+        use rustyline::completion::Completer;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = StateStore::new(dir.path());
+        for name in ["api", "app", "web"] {
+            store.save_project(&Project::new(format!("/code/{}", name), name)).unwrap();
+        }
+        let completer = CommandCompleter::new(dir.path().to_path_buf());
+        let history = rustyline::history::DefaultHistory::new();
+        let ctx = rustyline::Context::new(&history);
+
+        let (pos, matches) = completer.complete("/connect a", 10, &ctx).unwrap();
+        assert_eq!(pos, 9);
+        let mut names: Vec<&str> = matches.iter().map(|m| m.replacement.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["api", "app"]);
+
+        let (_, matches) = completer.complete("/status w", 9, &ctx).unwrap();
+        assert_eq!(matches[0].replacement, "web");
+        let (_, matches) = completer.complete("/alias w", 8, &ctx).unwrap();
+        assert_eq!(matches[0].replacement, "web");
+        let (_, matches) = completer.complete("/alias web ", 11, &ctx).unwrap();
+        assert!(matches.is_empty());
+    }
+
     #[test]
     fn test_completer_no_match() {
         use rustyline::completion::Completer;
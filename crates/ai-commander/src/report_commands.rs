@@ -0,0 +1,384 @@
+//! Activity report command for the CLI.
+//!
+//! `report --since 7d` summarizes what happened in each project: sessions
+//! started and stopped (recorded by the daemon), tasks completed (work items
+//! and workflow runs), errors, LLM spend from the usage tracker, and files
+//! changed according to each project's git history. The report is printed
+//! as Markdown or CSV, ready to paste into a weekly status update.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use commander_core::usage::{UsageRecord, UsageTracker};
+use commander_models::{Event, EventType, Project, WorkItem, WorkState};
+use commander_persistence::{EventStore, StateStore, WorkStore};
+use commander_work::parse_sla;
+
+use crate::cli::ReportFormat;
+use crate::commands::Result;
+
+/// Prefix of the tmux sessions Commander creates for projects.
+const SESSION_PREFIX: &str = "commander-";
+
+/// Activity of one project over the report period.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectActivity {
+    pub sessions_started: usize,
+    pub sessions_stopped: usize,
+    /// Completed tasks, oldest first.
+    pub tasks_completed: Vec<(DateTime<Utc>, String)>,
+    /// Errors, oldest first.
+    pub errors: Vec<(DateTime<Utc>, String)>,
+    pub llm_spend_usd: f64,
+    pub llm_runs: usize,
+    pub files_changed: BTreeSet<String>,
+}
+
+/// Activity of every project over a period.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Activity keyed by project name.
+    pub projects: BTreeMap<String, ProjectActivity>,
+}
+
+impl Report {
+    /// An empty report of the period from `since` to `until`.
+    pub fn new(since: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            until,
+            projects: BTreeMap::new(),
+        }
+    }
+
+    fn in_period(&self, at: DateTime<Utc>) -> bool {
+        at >= self.since && at <= self.until
+    }
+
+    fn project(&mut self, name: &str) -> &mut ProjectActivity {
+        self.projects.entry(name.to_string()).or_default()
+    }
+
+    /// Count `event` of project `name` if it falls in the period.
+    pub fn add_event(&mut self, name: &str, event: &Event) {
+        if !self.in_period(event.created_at) {
+            return;
+        }
+        match event.event_type {
+            EventType::InstanceReady => self.project(name).sessions_started += 1,
+            EventType::InstanceStopped => self.project(name).sessions_stopped += 1,
+            EventType::TaskComplete => {
+                self.project(name).tasks_completed.push((event.created_at, event.title.clone()))
+            }
+            EventType::Error | EventType::InstanceError => {
+                self.project(name).errors.push((event.created_at, event.title.clone()))
+            }
+            _ => {}
+        }
+    }
+
+    /// Count `item` of project `name` if it was completed in the period.
+    pub fn add_work(&mut self, name: &str, item: &WorkItem) {
+        let Some(completed_at) = item.completed_at else { return };
+        if item.state != WorkState::Completed || !self.in_period(completed_at) {
+            return;
+        }
+        let title = item.content.lines().next().unwrap_or_default().to_string();
+        self.project(name).tasks_completed.push((completed_at, title));
+    }
+
+    /// Count the LLM spend of `record` if it falls in the period.
+    pub fn add_usage(&mut self, name: &str, record: &UsageRecord) {
+        if !self.in_period(record.timestamp) {
+            return;
+        }
+        let project = self.project(name);
+        project.llm_spend_usd += record.cost_usd;
+        project.llm_runs += 1;
+    }
+
+    /// Record files of project `name` changed in the period.
+    pub fn add_files(&mut self, name: &str, files: impl IntoIterator<Item = String>) {
+        let mut files = files.into_iter().peekable();
+        if files.peek().is_some() {
+            self.project(name).files_changed.extend(files);
+        }
+    }
+
+    /// Sort completed tasks and errors by time.
+    fn finish(&mut self) {
+        for project in self.projects.values_mut() {
+            project.tasks_completed.sort();
+            project.errors.sort();
+        }
+    }
+
+    /// Totals over every project.
+    pub fn totals(&self) -> ProjectActivity {
+        let mut total = ProjectActivity::default();
+        for (name, project) in &self.projects {
+            total.sessions_started += project.sessions_started;
+            total.sessions_stopped += project.sessions_stopped;
+            total.tasks_completed.extend(project.tasks_completed.iter().cloned());
+            total.errors.extend(project.errors.iter().cloned());
+            total.llm_spend_usd += project.llm_spend_usd;
+            total.llm_runs += project.llm_runs;
+            total.files_changed.extend(project.files_changed.iter().map(|f| format!("{}/{}", name, f)));
+        }
+        total
+    }
+
+    /// Markdown: a summary table, then the tasks and errors of each project.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Activity report: {} to {}\n\n",
+            self.since.format("%Y-%m-%d"),
+            self.until.format("%Y-%m-%d")
+        );
+        if self.projects.is_empty() {
+            out.push_str("No activity recorded.\n");
+            return out;
+        }
+
+        out.push_str("| Project | Sessions started | Sessions stopped | Tasks completed | Errors | LLM spend | Files changed |\n");
+        out.push_str("|---|---:|---:|---:|---:|---:|---:|\n");
+        let total = self.totals();
+        let rows = self.projects.iter().map(|(name, p)| (name.as_str(), p));
+        for (name, p) in rows.chain(std::iter::once(("**Total**", &total))) {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | ${:.2} | {} |\n",
+                name.replace('|', "\\|"),
+                p.sessions_started,
+                p.sessions_stopped,
+                p.tasks_completed.len(),
+                p.errors.len(),
+                p.llm_spend_usd,
+                p.files_changed.len()
+            ));
+        }
+
+        for (name, p) in &self.projects {
+            if p.tasks_completed.is_empty() && p.errors.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n", name));
+            if !p.tasks_completed.is_empty() {
+                out.push_str("\nCompleted:\n");
+                for (at, title) in &p.tasks_completed {
+                    out.push_str(&format!("- {} {}\n", at.format("%Y-%m-%d"), title));
+                }
+            }
+            if !p.errors.is_empty() {
+                out.push_str("\nErrors:\n");
+                for (at, title) in &p.errors {
+                    out.push_str(&format!("- {} {}\n", at.format("%Y-%m-%d"), title));
+                }
+            }
+        }
+        out
+    }
+
+    /// CSV: one row per project, then a total row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "project,sessions_started,sessions_stopped,tasks_completed,errors,llm_spend_usd,llm_runs,files_changed\n",
+        );
+        let total = self.totals();
+        let rows = self.projects.iter().map(|(name, p)| (name.as_str(), p));
+        for (name, p) in rows.chain(std::iter::once(("TOTAL", &total))) {
+            out.push_str(&format!(
+                "{},{},{},{},{},{:.2},{},{}\n",
+                csv_field(name),
+                p.sessions_started,
+                p.sessions_stopped,
+                p.tasks_completed.len(),
+                p.errors.len(),
+                p.llm_spend_usd,
+                p.llm_runs,
+                p.files_changed.len()
+            ));
+        }
+        out
+    }
+}
+
+/// Execute the report command.
+pub fn execute(state_dir: &Path, since: &str, project: Option<&str>, format: ReportFormat) -> Result<()> {
+    let period = parse_sla(since)
+        .ok_or_else(|| format!("Invalid period: {} (expected e.g. 24h, 7d or 2w)", since))?;
+    let until = Utc::now();
+    let store = StateStore::new(state_dir);
+    let projects = store.load_all_projects()?;
+
+    let only = match project {
+        Some(project) => Some(match store.find_project_by_name_or_alias(project)? {
+            Some(found) => found.name,
+            None => project.strip_prefix(SESSION_PREFIX).unwrap_or(project).to_string(),
+        }),
+        None => None,
+    };
+    let names: HashMap<String, String> = projects
+        .values()
+        .map(|p| (p.id.as_str().to_string(), p.name.clone()))
+        .collect();
+    let name_of = |raw: &str| -> Option<String> {
+        let name = names
+            .get(raw)
+            .cloned()
+            .unwrap_or_else(|| raw.strip_prefix(SESSION_PREFIX).unwrap_or(raw).to_string());
+        match &only {
+            Some(only) if *only != name => None,
+            _ => Some(name),
+        }
+    };
+
+    let mut report = Report::new(until - period, until);
+    for event in EventStore::new(state_dir).list_all_events()? {
+        if let Some(name) = name_of(event.project_id.as_str()) {
+            report.add_event(&name, &event);
+        }
+    }
+    let work = WorkStore::new(state_dir);
+    for project_id in work.list_projects()? {
+        let Some(name) = name_of(project_id.as_str()) else { continue };
+        for item in work.list_work(&project_id)? {
+            report.add_work(&name, &item);
+        }
+    }
+    let usage = UsageTracker::new(commander_core::runtime_state_dir());
+    for record in usage.records_since(report.since) {
+        if let Some(name) = name_of(&record.project_name) {
+            report.add_usage(&name, record);
+        }
+    }
+    for project in projects.values() {
+        if name_of(project.id.as_str()).is_some() {
+            report.add_files(&project.name, git_files_changed(project, report.since));
+        }
+    }
+    report.finish();
+
+    match format {
+        ReportFormat::Md => print!("{}", report.to_markdown()),
+        ReportFormat::Csv => print!("{}", report.to_csv()),
+    }
+    Ok(())
+}
+
+/// Files touched by commits to `project`'s repository since `since`.
+///
+/// Empty when the project directory is missing or not a git repository.
+fn git_files_changed(project: &Project, since: DateTime<Utc>) -> Vec<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&project.path)
+        .args(["log", "--name-only", "--pretty=format:"])
+        .arg(format!("--since={}", since.to_rfc3339()))
+        .output();
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Quote `value` for CSV when it holds a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn sample_report() -> Report {
+        let now = Utc::now();
+        let mut report = Report::new(now - Duration::days(7), now + Duration::minutes(1));
+
+        let mut started = Event::new("p1", EventType::InstanceReady, "Session started");
+        started.created_at = now - Duration::days(2);
+        report.add_event("web", &started);
+        let mut old = Event::new("p1", EventType::InstanceReady, "Session started");
+        old.created_at = now - Duration::days(9);
+        report.add_event("web", &old);
+        report.add_event("web", &Event::new("p1", EventType::InstanceStopped, "Session stopped"));
+        report.add_event("api", &Event::new("p2", EventType::Error, "Build failed"));
+        report.add_event("api", &Event::new("p2", EventType::Status, "Working"));
+
+        let mut done = WorkItem::new("p1", "Fix login\nwith details");
+        done.complete(None);
+        report.add_work("web", &done);
+        report.add_work("web", &WorkItem::new("p1", "Still open"));
+
+        let record = UsageRecord {
+            project_name: "web".to_string(),
+            session_id: "s1".to_string(),
+            cost_usd: 1.25,
+            timestamp: now - Duration::hours(3),
+            adapter_type: "claude-code".to_string(),
+        };
+        report.add_usage("web", &record);
+        report.add_files("web", vec!["src/main.rs".to_string(), "src/main.rs".to_string()]);
+        report.add_files("docs", Vec::new());
+        report.finish();
+        report
+    }
+
+    #[test]
+    fn test_report_counts_activity_in_period() {
+        let report = sample_report();
+        assert_eq!(report.projects.len(), 2);
+
+        let web = &report.projects["web"];
+        assert_eq!(web.sessions_started, 1);
+        assert_eq!(web.sessions_stopped, 1);
+        assert_eq!(web.tasks_completed.len(), 1);
+        assert_eq!(web.tasks_completed[0].1, "Fix login");
+        assert_eq!(web.llm_runs, 1);
+        assert_eq!(web.files_changed.len(), 1);
+
+        let api = &report.projects["api"];
+        assert_eq!(api.errors.len(), 1);
+        assert_eq!(api.sessions_started, 0);
+    }
+
+    #[test]
+    fn test_report_markdown() {
+        let markdown = sample_report().to_markdown();
+        assert!(markdown.starts_with("# Activity report: "));
+        assert!(markdown.contains("| web | 1 | 1 | 1 | 0 | $1.25 | 1 |"));
+        assert!(markdown.contains("| **Total** | 1 | 1 | 1 | 1 | $1.25 | 1 |"));
+        assert!(markdown.contains("## api\n\nErrors:\n- "));
+        assert!(markdown.contains(" Fix login\n"));
+
+        let empty = Report::new(Utc::now() - Duration::days(1), Utc::now());
+        assert!(empty.to_markdown().ends_with("No activity recorded.\n"));
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let csv = sample_report().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "project,sessions_started,sessions_stopped,tasks_completed,errors,llm_spend_usd,llm_runs,files_changed"
+        );
+        assert_eq!(lines[1], "api,0,0,0,1,0.00,0,0");
+        assert_eq!(lines[2], "web,1,1,1,0,1.25,1,1");
+        assert_eq!(lines[3], "TOTAL,1,1,1,1,1.25,1,1");
+        assert_eq!(csv_field("a,b \"c\""), "\"a,b \"\"c\"\"\"");
+    }
+}
@@ -0,0 +1,215 @@
+//! Session snapshots and restore after a reboot.
+//!
+//! While the TUI runs, it periodically snapshots each live session: working
+//! directory, adapter, scrollback tail and Session Agent state. A reboot
+//! kills the tmux sessions but leaves the snapshots, so [`detect_lost_sessions`]
+//! can find them on the next start and `commander restore` (or `/restore` in
+//! the TUI) relaunches the adapters and primes them with where they left off.
+
+use chrono::Utc;
+use commander_adapters::AdapterRegistry;
+use commander_agent::SessionState;
+use commander_core::CommanderError;
+use commander_models::{Project, ProjectState};
+use commander_persistence::{SessionSnapshot, SnapshotStore, StateStore};
+use tracing::warn;
+
+use crate::client::SessionBackend;
+
+/// Scrollback lines kept in a snapshot.
+pub const SNAPSHOT_SCROLLBACK_LINES: u32 = 200;
+
+/// Scrollback lines quoted in the priming message.
+const PRIMING_SCROLLBACK_LINES: usize = 20;
+
+/// State reason of projects whose session was lost.
+const LOST_REASON: &str = "Session lost (reboot?); run `commander restore`";
+
+/// Snapshot `project`'s live session, or `None` if it is not running.
+///
+/// The agent state's output buffers are dropped; the scrollback covers them.
+pub fn take_snapshot(
+    tmux: &dyn SessionBackend,
+    project: &Project,
+    agent_state: Option<&SessionState>,
+) -> Option<SessionSnapshot> {
+    let session = project.name.replace([' ', '.', '/', ':'], "-");
+    if !tmux.session_exists(&session) {
+        return None;
+    }
+    let scrollback = tmux
+        .capture_output(&session, None, Some(SNAPSHOT_SCROLLBACK_LINES))
+        .ok()?;
+
+    let agent_state = agent_state.and_then(|state| {
+        let mut state = state.clone();
+        state.last_output = None;
+        state.pane_outputs.clear();
+        serde_json::to_value(state).ok()
+    });
+
+    Some(SessionSnapshot {
+        project_id: project.id.clone(),
+        project: project.name.clone(),
+        session,
+        working_dir: project.path.clone(),
+        adapter: project
+            .config
+            .get("tool")
+            .and_then(|v| v.as_str())
+            .unwrap_or("claude-code")
+            .to_string(),
+        scrollback,
+        agent_state,
+        captured_at: Utc::now(),
+    })
+}
+
+/// Snapshots of registered projects whose session no longer exists.
+///
+/// Their projects are marked [`ProjectState::Crashed`] so they stop showing
+/// as running.
+pub fn detect_lost_sessions(
+    store: &StateStore,
+    snapshots: &SnapshotStore,
+    tmux: &dyn SessionBackend,
+) -> Vec<SessionSnapshot> {
+    let (Ok(projects), Ok(all)) = (store.load_all_projects(), snapshots.list()) else {
+        return Vec::new();
+    };
+
+    let mut lost = Vec::new();
+    for snapshot in all {
+        let Some(project) = projects.get(&snapshot.project_id) else {
+            // Project was removed; its snapshot is stale
+            let _ = snapshots.delete(&snapshot.project_id);
+            continue;
+        };
+        if tmux.session_exists(&snapshot.session) {
+            continue;
+        }
+
+        if project.state != ProjectState::Crashed {
+            let mut project = project.clone();
+            project.set_state(ProjectState::Crashed, Some(LOST_REASON.to_string()));
+            if let Err(e) = store.save_project(&project) {
+                warn!(project = %project.name, error = %e, "Failed to mark lost session");
+            }
+        }
+        lost.push(snapshot);
+    }
+    lost
+}
+
+/// Relaunch a lost session's adapter in its working directory.
+///
+/// The adapter still has to become ready before [`priming_message`] is sent.
+pub fn relaunch(
+    snapshot: &SessionSnapshot,
+    store: &StateStore,
+    registry: &AdapterRegistry,
+    tmux: &dyn SessionBackend,
+) -> Result<(), CommanderError> {
+    crate::validate_project_path(&snapshot.working_dir)?;
+    let adapter = registry
+        .get(&snapshot.adapter)
+        .ok_or_else(|| CommanderError::Config(format!("Unknown adapter: {}", snapshot.adapter)))?;
+    let (cmd, cmd_args) = adapter.launch_command(&snapshot.working_dir);
+    let full_cmd = if cmd_args.is_empty() {
+        cmd
+    } else {
+        format!("{} {}", cmd, cmd_args.join(" "))
+    };
+
+    let (env, env_warning) = crate::project_session_env(&snapshot.working_dir);
+    if let Some(warning) = env_warning {
+        warn!(session = %snapshot.session, "{}", warning);
+    }
+    tmux.create_session_with_env(&snapshot.session, Some(&snapshot.working_dir), &env)
+        .map_err(|e| CommanderError::Tmux(format!("Failed to create tmux session: {}", e)))?;
+    tmux.send_line(&snapshot.session, None, &full_cmd)
+        .map_err(|e| CommanderError::Tmux(format!("Failed to start adapter: {}", e)))?;
+
+    if let Ok(mut project) = store.load_project(&snapshot.project_id) {
+        project.set_state(ProjectState::Idle, None);
+        if let Err(e) = store.save_project(&project) {
+            warn!(project = %project.name, error = %e, "Failed to update restored project");
+        }
+    }
+    Ok(())
+}
+
+/// Session Agent state saved in a snapshot.
+pub fn agent_state(snapshot: &SessionSnapshot) -> Option<SessionState> {
+    snapshot
+        .agent_state
+        .clone()
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+/// Context-priming message for a relaunched adapter.
+pub fn priming_message(snapshot: &SessionSnapshot) -> String {
+    let mut message = format!(
+        "This session was restored after a restart (last snapshot {}). ",
+        snapshot.captured_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    if let Some(state) = agent_state(snapshot) {
+        if !state.goals.is_empty() {
+            message.push_str(&format!("Goals: {}. ", state.goals.join("; ")));
+        }
+        if let Some(task) = &state.current_task {
+            message.push_str(&format!("You were working on: {}. ", task));
+        }
+        if !state.files_modified.is_empty() {
+            message.push_str(&format!("Files modified so far: {}. ", state.files_modified.join(", ")));
+        }
+        if !state.blockers.is_empty() {
+            message.push_str(&format!("Open blockers: {}. ", state.blockers.join("; ")));
+        }
+    }
+
+    let lines: Vec<&str> = snapshot
+        .scrollback
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let tail = &lines[lines.len().saturating_sub(PRIMING_SCROLLBACK_LINES)..];
+    if !tail.is_empty() {
+        // Quoted on one line: a multi-line prompt would be submitted line by line
+        message.push_str(&format!("The end of the previous session was: \"{}\". ", tail.join(" | ")));
+    }
+
+    message.push_str("Check the current state of the files, then continue where you left off.");
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_models::ProjectId;
+
+    #[test]
+    fn test_priming_message() {
+        let mut state = SessionState::new();
+        state.set_current_task("add login endpoint");
+        state.files_modified.push("src/auth.rs".to_string());
+        let snapshot = SessionSnapshot {
+            project_id: ProjectId::new(),
+            project: "api".to_string(),
+            session: "api".to_string(),
+            working_dir: "/code/api".to_string(),
+            adapter: "claude-code".to_string(),
+            scrollback: "running tests\n\n3 passed\n> ".to_string(),
+            agent_state: serde_json::to_value(&state).ok(),
+            captured_at: Utc::now(),
+        };
+
+        let message = priming_message(&snapshot);
+        assert!(message.contains("You were working on: add login endpoint"));
+        assert!(message.contains("src/auth.rs"));
+        assert!(message.contains("running tests | 3 passed | >"));
+        assert!(!message.contains('\n'));
+    }
+}
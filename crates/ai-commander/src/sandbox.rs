@@ -0,0 +1,382 @@
+//! Sandbox for filesystem commands.
+//!
+//! Commands parsed by [`crate::filesystem`] run inside a project. Before one
+//! runs, every path it names is resolved (`..`, `~` and symlinks included)
+//! and must stay under the project root. `cat`, `head` and `tail` refuse
+//! files larger than the limits in `[filesystem]`, and paths matching its
+//! `protected` patterns (by default `.git` and `.env*`) need confirmation.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use commander_core::FilesystemSettings;
+use thiserror::Error;
+
+use crate::filesystem::FsCommand;
+
+/// Why the sandbox refused a command.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SandboxError {
+    #[error("{0} is outside the project root")]
+    Escape(String),
+
+    #[error("Refusing to {0} the project root")]
+    Root(&'static str),
+
+    #[error("{path} is {size} bytes; {command} is limited to {limit} bytes")]
+    TooLarge {
+        path: String,
+        command: &'static str,
+        size: u64,
+        limit: u64,
+    },
+
+    #[error("{0} is protected")]
+    Protected(String),
+
+    #[error("Cannot resolve {path}: {reason}")]
+    Resolve { path: String, reason: String },
+}
+
+/// Confines filesystem commands to a project root.
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    root: PathBuf,
+    settings: FilesystemSettings,
+}
+
+impl Sandbox {
+    /// Create a sandbox rooted at `root`.
+    pub fn new(root: &Path, settings: FilesystemSettings) -> Self {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        Self { root, settings }
+    }
+
+    /// The canonical project root.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Check `cmd` and return it with every path resolved inside the root.
+    ///
+    /// Protected paths are refused with [`SandboxError::Protected`] unless
+    /// the user already `confirmed` the command.
+    pub fn confine(&self, cmd: &FsCommand, confirmed: bool) -> Result<FsCommand, SandboxError> {
+        let confined = match cmd {
+            FsCommand::Pwd => FsCommand::Pwd,
+            FsCommand::List { path, recursive } => FsCommand::List {
+                path: self.resolve(path, true)?,
+                recursive: *recursive,
+            },
+            FsCommand::Read { path, lines } => {
+                let path = self.resolve(path, true)?;
+                self.check_size(&path, *lines)?;
+                FsCommand::Read { path, lines: *lines }
+            }
+            FsCommand::Write { path, content, append } => FsCommand::Write {
+                path: self.resolve(path, true)?,
+                content: content.clone(),
+                append: *append,
+            },
+            FsCommand::Create { path } => FsCommand::Create {
+                path: self.resolve(path, true)?,
+            },
+            FsCommand::Move { from, to } => FsCommand::Move {
+                from: self.resolve_target(from, "move")?,
+                to: self.resolve(to, true)?,
+            },
+            FsCommand::Copy { from, to } => FsCommand::Copy {
+                from: self.resolve(from, true)?,
+                to: self.resolve(to, true)?,
+            },
+            FsCommand::Delete { path, force } => FsCommand::Delete {
+                path: self.resolve_target(path, "delete")?,
+                force: *force,
+            },
+            FsCommand::Mkdir { path, parents } => FsCommand::Mkdir {
+                path: self.resolve(path, true)?,
+                parents: *parents,
+            },
+            FsCommand::Search { pattern, path } => FsCommand::Search {
+                pattern: pattern.clone(),
+                path: Some(self.resolve(path.as_deref().unwrap_or("."), true)?),
+            },
+            FsCommand::Info { path } => FsCommand::Info {
+                path: self.resolve(path, true)?,
+            },
+        };
+
+        if !confirmed {
+            if let Some(path) = self.protected_path(&confined) {
+                return Err(SandboxError::Protected(path));
+            }
+        }
+        Ok(confined)
+    }
+
+    /// Resolve `path` against the root, failing if it lands outside.
+    ///
+    /// With `follow_final` false a symlink in the last component is kept
+    /// as is, so deleting or moving a link never touches its target.
+    fn resolve(&self, path: &str, follow_final: bool) -> Result<String, SandboxError> {
+        let expanded = shellexpand::tilde(path);
+        let mut normal = PathBuf::new();
+        for component in self.root.join(expanded.as_ref()).components() {
+            match component {
+                Component::ParentDir => {
+                    normal.pop();
+                }
+                Component::CurDir => {}
+                other => normal.push(other),
+            }
+        }
+
+        let resolved = match (normal.parent(), normal.file_name()) {
+            (Some(parent), Some(name)) if !follow_final => {
+                canonicalize_existing(parent).map(|parent| parent.join(name))
+            }
+            _ => canonicalize_existing(&normal),
+        }
+        .map_err(|e| SandboxError::Resolve {
+            path: path.to_string(),
+            reason: e.to_string(),
+        })?;
+
+        if !resolved.starts_with(&self.root) {
+            return Err(SandboxError::Escape(path.to_string()));
+        }
+        Ok(resolved.to_string_lossy().into_owned())
+    }
+
+    /// Resolve the path a command removes, which must not be the root.
+    fn resolve_target(&self, path: &str, action: &'static str) -> Result<String, SandboxError> {
+        let resolved = self.resolve(path, false)?;
+        if Path::new(&resolved) == self.root {
+            return Err(SandboxError::Root(action));
+        }
+        Ok(resolved)
+    }
+
+    /// Refuse files over the size limit of `cat`, `head` or `tail`.
+    fn check_size(&self, path: &str, lines: Option<(usize, usize)>) -> Result<(), SandboxError> {
+        let (command, limit) = match lines {
+            Some((0, _)) => ("head", self.settings.head_max_bytes),
+            Some((usize::MAX, _)) => ("tail", self.settings.tail_max_bytes),
+            _ => ("cat", self.settings.cat_max_bytes),
+        };
+        // Missing files are reported when the command runs
+        let Ok(metadata) = fs::metadata(path) else { return Ok(()) };
+        if metadata.is_file() && metadata.len() > limit {
+            return Err(SandboxError::TooLarge {
+                path: self.display(Path::new(path)),
+                command,
+                size: metadata.len(),
+                limit,
+            });
+        }
+        Ok(())
+    }
+
+    /// First protected path a resolved command reads or changes.
+    fn protected_path(&self, cmd: &FsCommand) -> Option<String> {
+        let paths: Vec<&String> = match cmd {
+            FsCommand::Read { path, .. }
+            | FsCommand::Write { path, .. }
+            | FsCommand::Create { path }
+            | FsCommand::Delete { path, .. }
+            | FsCommand::Mkdir { path, .. } => vec![path],
+            FsCommand::Move { from, to } | FsCommand::Copy { from, to } => vec![from, to],
+            FsCommand::List { .. } | FsCommand::Search { .. } | FsCommand::Info { .. } | FsCommand::Pwd => {
+                Vec::new()
+            }
+        };
+        paths
+            .into_iter()
+            .map(Path::new)
+            .find(|path| self.is_protected(path))
+            .map(|path| self.display(path))
+    }
+
+    /// Whether any component of `path` below the root matches a protected pattern.
+    fn is_protected(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else { return false };
+        relative.components().any(|component| {
+            let name: Vec<char> = component.as_os_str().to_string_lossy().chars().collect();
+            self.settings
+                .protected
+                .iter()
+                .any(|pattern| glob_match(&pattern.chars().collect::<Vec<_>>(), &name))
+        })
+    }
+
+    /// `path` relative to the root, for messages.
+    fn display(&self, path: &Path) -> String {
+        match path.strip_prefix(&self.root) {
+            Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative) => relative.to_string_lossy().into_owned(),
+            Err(_) => path.to_string_lossy().into_owned(),
+        }
+    }
+}
+
+/// Canonicalize the longest existing ancestor of `path` and append the rest.
+fn canonicalize_existing(path: &Path) -> io::Result<PathBuf> {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while existing.symlink_metadata().is_err() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut resolved = existing.canonicalize()?;
+    resolved.extend(rest.iter().rev());
+    Ok(resolved)
+}
+
+/// Match `name` against a glob supporting `*` and `?`.
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| glob_match(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && glob_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && glob_match(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filesystem::parse_command;
+    use tempfile::tempdir;
+
+    fn sandbox(root: &Path) -> Sandbox {
+        Sandbox::new(root, FilesystemSettings::default())
+    }
+
+    fn confine(sandbox: &Sandbox, input: &str) -> Result<FsCommand, SandboxError> {
+        let cmd = parse_command(input, sandbox.root()).unwrap();
+        sandbox.confine(&cmd, false)
+    }
+
+    #[test]
+    fn test_rejects_escapes() {
+        let dir = tempdir().unwrap();
+        let sandbox = sandbox(dir.path());
+
+        assert_eq!(
+            confine(&sandbox, "rm -rf ../other"),
+            Err(SandboxError::Escape(format!("{}/../other", sandbox.root().display())))
+        );
+        assert!(matches!(confine(&sandbox, "cat /etc/passwd"), Err(SandboxError::Escape(_))));
+        assert!(matches!(confine(&sandbox, "cp notes.txt ~/notes.txt"), Err(SandboxError::Escape(_))));
+        assert_eq!(confine(&sandbox, "rm -rf ."), Err(SandboxError::Root("delete")));
+    }
+
+    #[test]
+    fn test_resolves_inside_root() {
+        let dir = tempdir().unwrap();
+        let sandbox = sandbox(dir.path());
+        let root = sandbox.root().to_path_buf();
+
+        let cmd = confine(&sandbox, "mv src/../a.txt new/b.txt").unwrap();
+        assert_eq!(
+            cmd,
+            FsCommand::Move {
+                from: root.join("a.txt").to_string_lossy().into_owned(),
+                to: root.join("new/b.txt").to_string_lossy().into_owned(),
+            }
+        );
+
+        // ls without a path lists the root, not the process directory
+        let cmd = sandbox.confine(&parse_command("ls", &root).unwrap(), false).unwrap();
+        assert_eq!(
+            cmd,
+            FsCommand::List {
+                path: root.to_string_lossy().into_owned(),
+                recursive: false
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks() {
+        let outside = tempdir().unwrap();
+        fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        let dir = tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+        let sandbox = sandbox(dir.path());
+
+        assert!(matches!(confine(&sandbox, "cat link/secret.txt"), Err(SandboxError::Escape(_))));
+        // Removing the link itself stays inside the root
+        let cmd = confine(&sandbox, "rm link").unwrap();
+        assert_eq!(
+            cmd,
+            FsCommand::Delete {
+                path: sandbox.root().join("link").to_string_lossy().into_owned(),
+                force: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("big.log"), "x".repeat(100)).unwrap();
+        let settings = FilesystemSettings {
+            cat_max_bytes: 50,
+            ..FilesystemSettings::default()
+        };
+        let sandbox = Sandbox::new(dir.path(), settings);
+
+        assert_eq!(
+            confine(&sandbox, "cat big.log"),
+            Err(SandboxError::TooLarge {
+                path: "big.log".to_string(),
+                command: "cat",
+                size: 100,
+                limit: 50,
+            })
+        );
+        assert!(confine(&sandbox, "tail big.log").is_ok());
+        assert!(confine(&sandbox, "head -5 big.log").is_ok());
+    }
+
+    #[test]
+    fn test_protected_paths() {
+        let dir = tempdir().unwrap();
+        let sandbox = sandbox(dir.path());
+
+        assert_eq!(
+            confine(&sandbox, "rm -rf .git"),
+            Err(SandboxError::Protected(".git".to_string()))
+        );
+        assert_eq!(
+            confine(&sandbox, "cat config/.env.local"),
+            Err(SandboxError::Protected("config/.env.local".to_string()))
+        );
+        assert!(confine(&sandbox, "cat .environment_notes").is_err());
+        assert!(confine(&sandbox, "cat .gitignore").is_ok());
+        assert!(confine(&sandbox, "ls .git").is_ok());
+
+        let cmd = parse_command("rm .env", sandbox.root()).unwrap();
+        assert!(sandbox.confine(&cmd, true).is_ok());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let matches = |p: &str, n: &str| {
+            glob_match(&p.chars().collect::<Vec<_>>(), &n.chars().collect::<Vec<_>>())
+        };
+        assert!(matches(".env*", ".env"));
+        assert!(matches("*.pem", "server.pem"));
+        assert!(matches("id_?sa", "id_rsa"));
+        assert!(!matches(".git", ".github"));
+    }
+}
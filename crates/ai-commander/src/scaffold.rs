@@ -0,0 +1,120 @@
+//! Project scaffolding from templates.
+//!
+//! Shared by `commander new` and the TUI's `/connect <path> --template`.
+//! Scaffolding writes the template's files and registers the project; the
+//! caller starts the session and sends the template's initial prompt once
+//! the adapter is ready.
+
+use std::path::Path;
+
+use commander_adapters::{AdapterRegistry, RuntimeAdapter};
+use commander_core::{find_template, is_claude_ready};
+use commander_models::Project;
+use commander_persistence::StateStore;
+
+/// A project created from a template.
+#[derive(Debug)]
+pub struct ScaffoldedProject {
+    /// The registered project.
+    pub project: Project,
+    /// Resolved adapter ID.
+    pub tool_id: String,
+    /// Prompt to send once the adapter is ready.
+    pub prompt: Option<String>,
+    /// Number of files written.
+    pub files_created: usize,
+}
+
+/// Scaffold `template` into `path` and register the project.
+///
+/// The adapter defaults to the template's preferred adapter, then
+/// claude-code. The name defaults to the directory name.
+pub fn scaffold_project(
+    store: &StateStore,
+    registry: &AdapterRegistry,
+    template: &str,
+    path: &Path,
+    adapter: Option<&str>,
+    name: Option<&str>,
+) -> Result<ScaffoldedProject, String> {
+    let template = find_template(template).map_err(|e| e.to_string())?;
+
+    let adapter = adapter
+        .or(template.adapter.as_deref())
+        .unwrap_or("claude-code");
+    let tool_id = registry
+        .resolve(adapter)
+        .ok_or_else(|| format!("Unknown adapter: {}. Use: cc (claude-code), mpm, mpm-sdk", adapter))?
+        .to_string();
+
+    let project_name = name
+        .map(String::from)
+        .or_else(|| path.file_name().and_then(|n| n.to_str()).map(String::from))
+        .ok_or_else(|| format!("Cannot derive a project name from {}", path.display()))?;
+
+    let projects = store
+        .load_all_projects()
+        .map_err(|e| format!("Failed to load projects: {}", e))?;
+    if projects.values().any(|p| p.name == project_name) {
+        return Err(format!("Project '{}' already exists", project_name));
+    }
+
+    let created = template
+        .scaffold(path, &project_name)
+        .map_err(|e| e.to_string())?;
+
+    let path_str = path
+        .canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .to_string();
+    let mut project = Project::new(path_str, project_name.as_str());
+    project.config.insert("tool".to_string(), serde_json::json!(tool_id));
+    project
+        .config
+        .insert("template".to_string(), serde_json::json!(template.name));
+    store
+        .save_project(&project)
+        .map_err(|e| format!("Failed to save project: {}", e))?;
+
+    Ok(ScaffoldedProject {
+        prompt: template.prompt_for(&project_name),
+        project,
+        tool_id,
+        files_created: created.len(),
+    })
+}
+
+/// Whether a freshly launched adapter is ready to receive a prompt.
+pub fn adapter_ready(adapter: Option<&dyn RuntimeAdapter>, output: &str) -> bool {
+    is_claude_ready(output) || adapter.is_some_and(|a| a.is_idle(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scaffold_project_registers_project() {
+        let dir = tempdir().unwrap();
+        let store = StateStore::new(dir.path().join("state"));
+        let registry = AdapterRegistry::new();
+        let path = dir.path().join("demo");
+
+        let scaffolded =
+            scaffold_project(&store, &registry, "rust-cli", &path, Some("cc"), None).unwrap();
+        assert_eq!(scaffolded.project.name, "demo");
+        assert_eq!(scaffolded.tool_id, "claude-code");
+        assert!(scaffolded.prompt.unwrap().contains("demo"));
+        assert!(path.join("Cargo.toml").exists());
+
+        let projects = store.load_all_projects().unwrap();
+        assert!(projects.values().any(|p| p.name == "demo"));
+
+        // A second scaffold with the same name is rejected
+        let err = scaffold_project(&store, &registry, "rust-cli", &dir.path().join("other"), None, Some("demo"))
+            .unwrap_err();
+        assert!(err.contains("already exists"));
+    }
+}
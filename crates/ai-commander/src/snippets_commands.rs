@@ -0,0 +1,39 @@
+//! Prompt snippet commands for the CLI.
+//!
+//! `snippets list|add|edit|remove` manage `snippets.toml` in the state
+//! directory; the snippets are sent from the TUI, REPL and chat bots with
+//! `/run <name>` or `!name`.
+
+use commander_core::snippets;
+
+use crate::cli::SnippetsCommands;
+use crate::commands::Result;
+
+/// Execute snippet commands.
+pub fn execute(command: SnippetsCommands) -> Result<()> {
+    match command {
+        SnippetsCommands::List => {
+            let snippets = snippets::load_snippets()?;
+            if snippets.is_empty() {
+                println!("No snippets. Add one with: commander snippets add <name> <text>");
+                return Ok(());
+            }
+            for line in snippets::format_list(&snippets) {
+                println!("{}", line);
+            }
+        }
+        SnippetsCommands::Add { name, text, description } => {
+            snippets::add_snippet(&name, &text, description.as_deref())?;
+            println!("Added snippet '{}'", name);
+        }
+        SnippetsCommands::Edit { name, text, description } => {
+            snippets::edit_snippet(&name, &text, description.as_deref())?;
+            println!("Updated snippet '{}'", name);
+        }
+        SnippetsCommands::Remove { name } => {
+            snippets::remove_snippet(&name)?;
+            println!("Removed snippet '{}'", name);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! AI-written commits and pull requests when `/stop` ends a session.
+//!
+//! With `[stop] ai_commit = true` in `config.toml`, the session's changes are
+//! committed with a message the LLM writes from the staged diff and the
+//! session's transcript. With `push` (the default) the branch is then pushed
+//! and a pull request opened with the generated description, through the
+//! API when `GITHUB_TOKEN` is set. `/stop --no-ai-commit` falls back to the
+//! plain WIP commit.
+
+use std::path::Path;
+use std::process::Command;
+
+use commander_core::secrets::{get_secret, GITHUB_TOKEN};
+use commander_core::{generate_pr_summary_blocking, read_all_log_entries, PrSummary, Settings, StopSettings};
+use commander_github::{publish_branch, GitHubClient, Published};
+
+/// Flag of `/stop` that skips the AI commit.
+pub const NO_AI_COMMIT_FLAG: &str = "--no-ai-commit";
+
+/// Split `/stop` arguments into the session name and whether
+/// `--no-ai-commit` was given.
+pub fn parse_stop_args(arg: Option<&str>) -> (Option<String>, bool) {
+    let mut target = None;
+    let mut no_ai_commit = false;
+    for word in arg.unwrap_or("").split_whitespace() {
+        if word == NO_AI_COMMIT_FLAG {
+            no_ai_commit = true;
+        } else if target.is_none() {
+            target = Some(word.to_string());
+        }
+    }
+    (target, no_ai_commit)
+}
+
+/// The `[stop]` settings, with the AI commit turned off by `no_ai_commit`.
+pub fn stop_settings(no_ai_commit: bool) -> StopSettings {
+    let mut settings = Settings::load(&commander_core::config_file())
+        .unwrap_or_default()
+        .stop;
+    if no_ai_commit {
+        settings.ai_commit = false;
+    }
+    settings
+}
+
+/// Commit message used when no summary is generated.
+pub fn wip_message(project: &str) -> String {
+    format!("WIP: Auto-commit from Commander session '{}'", project)
+}
+
+/// Stage the changes in `path` and write a commit message and pull request
+/// for them from the diff and `session`'s transcript.
+///
+/// Returns `Ok(None)` when there is nothing to commit.
+pub fn summarize_changes(
+    path: &str,
+    project: &str,
+    session: &str,
+    model: Option<&str>,
+) -> Result<Option<PrSummary>, String> {
+    let add = Command::new("git")
+        .args(["add", "-A"])
+        .current_dir(path)
+        .output()
+        .map_err(|e| format!("Failed to stage changes: {}", e))?;
+    if !add.status.success() {
+        return Err(format!("git add failed: {}", String::from_utf8_lossy(&add.stderr).trim()));
+    }
+
+    let diff = commander_core::git_diff(Path::new(path), None).map_err(|e| e.to_string())?;
+    if diff.trim().is_empty() {
+        return Ok(None);
+    }
+    let transcript = read_all_log_entries(session).unwrap_or_default();
+    generate_pr_summary_blocking(project, &transcript, &diff, model)
+        .map(Some)
+        .map_err(|e| format!("Could not write commit message: {}", e))
+}
+
+/// Push the branch checked out in `path` and open a pull request for it.
+pub fn publish(path: &str, summary: &PrSummary) -> Result<Published, String> {
+    let client = get_secret(GITHUB_TOKEN).map(GitHubClient::new);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to start runtime: {}", e))?;
+    runtime
+        .block_on(publish_branch(Path::new(path), client.as_ref(), &summary.title, &summary.body))
+        .map_err(|e| e.to_string())
+}
+
+/// How a published branch is reported to the user.
+pub fn describe_published(published: &Published) -> String {
+    if published.opened {
+        format!("Pull request: {}", published.url)
+    } else {
+        format!(
+            "Pushed {}. Open a pull request: {} (set GITHUB_TOKEN to open it automatically)",
+            published.branch, published.url
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stop_args() {
+        assert_eq!(parse_stop_args(None), (None, false));
+        assert_eq!(parse_stop_args(Some("duetto")), (Some("duetto".to_string()), false));
+        assert_eq!(parse_stop_args(Some("--no-ai-commit")), (None, true));
+        assert_eq!(
+            parse_stop_args(Some("--no-ai-commit duetto")),
+            (Some("duetto".to_string()), true)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Switching a running project to another adapter.
+//!
+//! `/adapter use <name>` exits the current tool in the project's tmux session,
+//! waits for the shell to come back, saves the new tool in the project config
+//! and launches it in the same session. The Session Agent moves to the new
+//! adapter's template, and a summary of the conversation so far is sent to the
+//! new tool once it is ready so the work carries on where it left off.
+
+use std::time::{Duration, Instant};
+
+use super::app::{App, Message};
+use super::connection::PendingPrompt;
+
+/// How long to wait for the old tool to exit before giving up.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether a pane's foreground command is a login shell, i.e. the runtime
+/// that was running in it has exited.
+fn is_shell(command: &str) -> bool {
+    let name = command.trim_start_matches('-');
+    matches!(name, "bash" | "zsh" | "fish" | "sh" | "dash" | "ksh" | "tcsh")
+}
+
+/// Briefing sent to the new adapter, built from the agent's and the shared
+/// registry's view of the session.
+fn handoff_prompt(from: &str, agent_summary: Option<&str>, conversation: Option<&str>) -> Option<String> {
+    let mut sections = Vec::new();
+    if let Some(summary) = agent_summary.filter(|s| !s.trim().is_empty()) {
+        sections.push(summary.to_string());
+    }
+    if let Some(conversation) = conversation.filter(|s| !s.trim().is_empty()) {
+        sections.push(format!("Recent messages:\n{}", conversation));
+    }
+    if sections.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "This project was being worked on with {} until now. Context so far:\n\n{}\n\nContinue from here.",
+        from,
+        sections.join("\n\n")
+    ))
+}
+
+impl App {
+    /// Handle `/adapter [use <name>]`.
+    pub(super) fn handle_adapter_command(&mut self, arg: Option<&str>) {
+        let Some(project) = self.project.clone() else {
+            self.messages.push(Message::system("Not connected to a project. Use /connect first."));
+            return;
+        };
+
+        match arg.map(|a| a.split_whitespace().collect::<Vec<_>>()).as_deref() {
+            None | Some([]) => self.show_adapters(&project),
+            Some(["use", name]) => self.switch_adapter(&project, name),
+            _ => self.messages.push(Message::system("Usage: /adapter [use <name>]")),
+        }
+    }
+
+    /// Show the project's adapter and the ones it can switch to.
+    fn show_adapters(&mut self, project: &str) {
+        let current = self.project_tool_id(project);
+        let mut available: Vec<&str> = self.registry.list();
+        available.sort_unstable();
+
+        self.messages.push(Message::system(format!("Adapter for '{}': {}", project, current)));
+        self.messages.push(Message::system(format!("Available: {}", available.join(", "))));
+        self.messages.push(Message::system("Switch with /adapter use <name>"));
+    }
+
+    /// Relaunch `project`'s session with the adapter `name`.
+    fn switch_adapter(&mut self, project: &str, name: &str) {
+        let Some(new_id) = self.registry.resolve(name).map(String::from) else {
+            self.messages.push(Message::system(format!(
+                "Unknown adapter '{}'. Run /adapter to list them.",
+                name
+            )));
+            return;
+        };
+        let Some(new_adapter) = self.registry.get(&new_id) else {
+            self.messages.push(Message::system(format!(
+                "'{}' can't run in a tmux session",
+                new_id
+            )));
+            return;
+        };
+        let old_id = self.project_tool_id(project);
+        if old_id == new_id {
+            self.messages.push(Message::system(format!("'{}' already uses {}", project, new_id)));
+            return;
+        }
+        let Some(session) = self.sessions.get(project).cloned() else {
+            self.messages.push(Message::system(format!("No tmux session for '{}'", project)));
+            return;
+        };
+        let Some(mut record) = self
+            .store
+            .load_all_projects()
+            .ok()
+            .and_then(|projects| projects.into_values().find(|p| p.name == project))
+        else {
+            self.messages.push(Message::system(format!("'{}' is not a registered project", project)));
+            return;
+        };
+        let Some(tmux) = self.tmux.as_ref() else {
+            self.messages.push(Message::system("tmux is not available"));
+            return;
+        };
+
+        // Ask the old tool to exit and wait for the shell prompt.
+        self.messages.push(Message::system(format!("Stopping {} in '{}'...", old_id, project)));
+        let exit_sent = match self.registry.get(&old_id).and_then(|a| a.exit_command().map(String::from)) {
+            Some(exit) => tmux.send_line(&session, None, &exit),
+            None => tmux.send_keys(&session, None, "C-c"),
+        };
+        if let Err(e) = exit_sent {
+            self.messages.push(Message::system(format!("Failed to stop {}: {}", old_id, e)));
+            return;
+        }
+        let started = Instant::now();
+        let exited = loop {
+            match tmux.pane_command(&session, None) {
+                Ok(command) if is_shell(&command) => break true,
+                Ok(_) if started.elapsed() < EXIT_TIMEOUT => std::thread::sleep(Duration::from_millis(250)),
+                _ => break false,
+            }
+        };
+        if !exited {
+            self.messages.push(Message::system(format!(
+                "{} did not exit within {}s; adapter left unchanged",
+                old_id,
+                EXIT_TIMEOUT.as_secs()
+            )));
+            return;
+        }
+
+        record.config.insert("tool".to_string(), serde_json::Value::String(new_id.clone()));
+        if let Err(e) = self.store.save_project(&record) {
+            self.messages.push(Message::system(format!("Failed to save project: {}", e)));
+            return;
+        }
+
+        let (cmd, cmd_args) = if self.is_read_only(project) {
+            new_adapter.read_only_launch_command(&record.path)
+        } else {
+            new_adapter.launch_command(&record.path)
+        };
+        let full_cmd = if cmd_args.is_empty() {
+            cmd
+        } else {
+            format!("{} {}", cmd, cmd_args.join(" "))
+        };
+        if let Err(e) = tmux.send_line(&session, None, &full_cmd) {
+            self.messages.push(Message::system(format!("Failed to start {}: {}", new_id, e)));
+            return;
+        }
+
+        #[cfg(feature = "agents")]
+        let agent_summary = self
+            .orchestrator
+            .as_mut()
+            .and_then(|o| o.switch_session_adapter(&session, &new_id));
+        #[cfg(not(feature = "agents"))]
+        let agent_summary: Option<String> = None;
+        let conversation = commander_core::load_session_registry()
+            .get(&session)
+            .and_then(|r| r.conversation_summary());
+
+        self.messages.push(Message::system(format!(
+            "Switched '{}' from {} to {}",
+            project, old_id, new_id
+        )));
+        if let Some(prompt) = handoff_prompt(&old_id, agent_summary.as_deref(), conversation.as_deref()) {
+            self.pending_prompts.push(PendingPrompt::new(project, prompt));
+            self.messages.push(Message::system("Context will be passed on once it is ready"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_detection_and_handoff_prompt() {
+        assert!(is_shell("zsh"));
+        assert!(is_shell("-bash"));
+        assert!(!is_shell("claude"));
+        assert!(!is_shell("node"));
+
+        assert!(handoff_prompt("claude-code", None, Some("  ")).is_none());
+        let prompt = handoff_prompt("mpm", Some("Goals: ship login"), Some("User: add a login page")).unwrap();
+        assert!(prompt.contains("with mpm"));
+        assert!(prompt.contains("Goals: ship login\n\nRecent messages:\nUser: add a login page"));
+    }
+}
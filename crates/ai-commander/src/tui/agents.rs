@@ -6,7 +6,9 @@ use std::sync::Arc;
 use tokio::runtime::Handle as TokioHandle;
 
 #[cfg(feature = "agents")]
-use commander_orchestrator::AgentOrchestrator;
+use commander_agent::AgentResult;
+#[cfg(feature = "agents")]
+use commander_orchestrator::{AgentOrchestrator, TaskStatus};
 
 #[cfg(feature = "agents")]
 use super::app::{App, Message};
@@ -27,6 +29,7 @@ impl App {
             Ok(orchestrator) => {
                 self.orchestrator = Some(orchestrator);
                 self.messages.push(Message::system("Agent orchestrator initialized"));
+                self.show_recovered_tasks();
                 Ok(())
             }
             Err(e) => {
@@ -57,6 +60,7 @@ impl App {
             Ok(orchestrator) => {
                 self.orchestrator = Some(orchestrator);
                 self.messages.push(Message::system("Agent orchestrator initialized"));
+                self.show_recovered_tasks();
                 true
             }
             Err(e) => {
@@ -67,21 +71,210 @@ impl App {
         }
     }
 
+    /// Report delegated tasks the previous orchestrator left in flight,
+    /// replayed from its journal.
+    fn show_recovered_tasks(&mut self) {
+        let Some(orchestrator) = &self.orchestrator else {
+            return;
+        };
+        let lines: Vec<String> = orchestrator
+            .tracked_tasks()
+            .into_iter()
+            .filter_map(|t| {
+                let status = match &t.status {
+                    TaskStatus::InFlight => "in flight".to_string(),
+                    TaskStatus::Blocked(reason) => format!("blocked: {}", reason),
+                    TaskStatus::Orphaned => "orphaned, session gone".to_string(),
+                    TaskStatus::Completed | TaskStatus::Failed(_) => return None,
+                };
+                let task: String = t.task.lines().next().unwrap_or_default().chars().take(60).collect();
+                Some(format!("  [{}] {} ({})", t.session_id, task, status))
+            })
+            .collect();
+        if lines.is_empty() {
+            return;
+        }
+        self.messages.push(Message::system("Recovered delegated tasks:"));
+        for line in lines {
+            self.messages.push(Message::system(line));
+        }
+    }
+
     /// Process user input through the agent orchestrator (if available).
     ///
     /// Returns the processed response, or the original input if no orchestrator.
+    /// A structured result is shown as a card.
     pub async fn process_with_agent(&mut self, input: &str) -> Result<String, String> {
         if let Some(ref mut orchestrator) = self.orchestrator {
-            orchestrator
-                .process_user_input(input)
+            let result = orchestrator
+                .process_user_input_structured(input)
                 .await
-                .map_err(|e| e.to_string())
+                .map_err(|e| e.to_string());
+            match result {
+                Ok(response) => {
+                    if let Some(value) = &response.structured_output {
+                        self.show_result_card(value);
+                    }
+                    Ok(response.content)
+                }
+                Err(e) => {
+                    self.show_pending_approval();
+                    Err(e)
+                }
+            }
         } else {
             // No orchestrator - return input unchanged
             Ok(input.to_string())
         }
     }
 
+    /// Show an agent's structured result as a card.
+    fn show_result_card(&mut self, value: &serde_json::Value) {
+        let Ok(result) = AgentResult::from_value(value) else {
+            return;
+        };
+        self.messages.push(Message::system("Result:"));
+        for line in result.card_lines() {
+            self.messages.push(Message::system(format!("  {}", line)));
+        }
+    }
+
+    /// Show the approval prompt for a guarded command, if one is pending.
+    pub fn show_pending_approval(&mut self) {
+        let Some(approval) = self.orchestrator.as_ref().and_then(|o| o.pending_approval()) else {
+            return;
+        };
+
+        let prompt = format!(
+            "[!] Approval required ({}): {}\n    Command: {}\n    Use /approve to run it or /deny to block it",
+            approval.tool_name, approval.reason, approval.command
+        );
+        self.messages.push(Message::system(prompt));
+    }
+
+    /// Approve or deny the command awaiting guardrail approval.
+    pub fn resolve_pending_approval(&mut self, approve: bool) {
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        let resolved = if approve {
+            orchestrator.approve_pending()
+        } else {
+            orchestrator.deny_pending()
+        };
+
+        match resolved {
+            Some(approval) if approve => self.messages.push(Message::system(format!(
+                "Approved: {} (will run on next attempt)",
+                approval.command
+            ))),
+            Some(approval) => self
+                .messages
+                .push(Message::system(format!("Denied: {}", approval.command))),
+            None => self
+                .messages
+                .push(Message::system("No command awaiting approval")),
+        }
+    }
+
+    /// Handle `/checkpoint <label>`: snapshot the agent conversation.
+    pub fn handle_checkpoint(&mut self, label: Option<&str>) {
+        let Some(label) = label.filter(|l| !l.is_empty()) else {
+            self.messages.push(Message::system("Usage: /checkpoint <label>"));
+            return;
+        };
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        let context = orchestrator.user_agent_mut().context_mut();
+        context.checkpoint(label);
+        let count = context.recent_messages.len();
+        self.messages.push(Message::system(format!(
+            "Checkpoint '{}' saved ({} messages); /rewind {} to return here",
+            label, count, label
+        )));
+    }
+
+    /// Handle `/rewind [label]`: restore the agent conversation to a
+    /// checkpoint, or list checkpoints without a label.
+    pub fn handle_rewind(&mut self, label: Option<&str>) {
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+        let context = orchestrator.user_agent_mut().context_mut();
+
+        let Some(label) = label.filter(|l| !l.is_empty()) else {
+            if context.checkpoints.is_empty() {
+                self.messages
+                    .push(Message::system("No checkpoints. Use /checkpoint <label> to save one"));
+                return;
+            }
+            let lines: Vec<String> = context
+                .checkpoints
+                .iter()
+                .map(|c| {
+                    format!(
+                        "  {:<16} {} ({} messages)",
+                        c.label,
+                        c.created_at.with_timezone(&chrono::Local).format("%H:%M:%S"),
+                        c.recent_messages.len()
+                    )
+                })
+                .collect();
+            self.messages.push(Message::system("Checkpoints (/rewind <label>):"));
+            self.messages.extend(lines.into_iter().map(Message::system));
+            return;
+        };
+
+        if context.rewind(label) {
+            let count = context.recent_messages.len();
+            self.messages.push(Message::system(format!(
+                "Rewound agent conversation to '{}' ({} messages)",
+                label, count
+            )));
+        } else {
+            self.messages
+                .push(Message::system(format!("No checkpoint named '{}'", label)));
+        }
+    }
+
+    /// Report output the change detector should have flagged, to tune its patterns.
+    pub fn report_missed_event(&mut self, text: Option<&str>) {
+        let Some(text) = text.filter(|t| !t.trim().is_empty()) else {
+            self.messages.push(Message::system("Usage: /missed <output line that should have triggered a notification>"));
+            return;
+        };
+        let Some(session_name) = self.project.as_ref().and_then(|p| self.sessions.get(p)).cloned() else {
+            self.messages.push(Message::system("Not connected to any project"));
+            return;
+        };
+        let (Some(handle), Some(orchestrator)) = (self.runtime_handle.clone(), self.orchestrator.as_mut()) else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        match handle.block_on(orchestrator.report_missed_change(&session_name, "claude_code", text)) {
+            Ok(_) => self.messages.push(Message::system(
+                "Recorded missed event; change detection will learn from it",
+            )),
+            Err(e) => self
+                .messages
+                .push(Message::system(format!("Failed to record missed event: {}", e))),
+        }
+    }
+
+    /// Context window usage of the connected session's agent, for the status bar.
+    pub fn context_usage_label(&self) -> Option<String> {
+        let session_name = self.project.as_ref().and_then(|p| self.sessions.get(p))?;
+        let usage = self.orchestrator.as_ref()?.context_usage(session_name)?;
+        Some(usage.to_string())
+    }
+
     /// Check if the orchestrator is initialized.
     pub fn has_orchestrator(&self) -> bool {
         self.orchestrator.is_some()
@@ -102,3 +295,11 @@ impl App {
         self.runtime_handle.as_ref()
     }
 }
+
+#[cfg(not(feature = "agents"))]
+impl super::app::App {
+    /// Context usage is only tracked by session agents.
+    pub fn context_usage_label(&self) -> Option<String> {
+        None
+    }
+}
@@ -1,14 +1,24 @@
 //! TUI application state and logic.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::time::{Instant, SystemTime};
 
 use chrono::{DateTime, Utc};
 use commander_adapters::AdapterRegistry;
-use commander_persistence::StateStore;
+use commander_agent::eval::PromptPatch;
+use commander_core::{
+    ApprovalRequest, ChangeDetector, CommandSender, ConfigChange, DifferentialRequest, DifferentialSummary, FilesystemSettings,
+    NotificationSettings, Recorder, ThemeSettings,
+};
+use commander_events::Escalation;
+use commander_persistence::{SnapshotStore, StateStore};
+use commander_runtime::{ProjectWatcher, Watchdog, WatchdogConfig, WATCHDOG_FILE};
 use commander_tmux::TmuxOrchestrator;
 
+use crate::client::{RemoteClient, SessionBackend};
+
 #[cfg(feature = "agents")]
 use commander_orchestrator::AgentOrchestrator;
 #[cfg(feature = "agents")]
@@ -17,6 +27,10 @@ use std::sync::Arc;
 use tokio::runtime::Handle as TokioHandle;
 use ratatui::layout::Rect;
 
+use super::connection::PendingPrompt;
+use super::keybindings::Keybindings;
+use super::theme::Theme;
+
 /// Represents a clickable region in the TUI output.
 #[derive(Clone)]
 pub struct ClickableItem {
@@ -27,10 +41,38 @@ pub struct ClickableItem {
 }
 
 /// Action to execute when a clickable item is clicked.
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ClickAction {
     /// Connect to a session by name.
     Connect(String),
+    /// Select a row in the sessions view; clicking the selected row connects.
+    SelectSession(usize),
+    /// Collapse or expand a long message by index.
+    ToggleMessage(usize),
+    /// Footer button equivalent to F2.
+    ToggleInspect,
+    /// Footer button equivalent to F3.
+    ToggleSessions,
+    /// Open the diff view, optionally for one file.
+    ShowDiff(Option<String>),
+    /// Footer button leaving the diff view.
+    CloseDiff,
+    /// Footer button leaving the approvals pane.
+    CloseApprovals,
+    /// Footer button leaving the log viewer.
+    CloseLogs,
+    /// Footer button leaving the history view.
+    CloseHistory,
+    /// Footer button leaving the inbox.
+    CloseInbox,
+    /// Footer button leaving the improvements pane.
+    CloseImprovements,
+    /// Image path in a message, opened with `/view`.
+    ViewImage(String),
+    /// Diagnosis card line sending the suggested fix.
+    ApplyDiagnosis,
+    /// Diagnosis card line dismissing the suggested fix.
+    IgnoreDiagnosis,
 }
 
 impl ClickableItem {
@@ -63,6 +105,8 @@ pub struct Message {
     pub project: String,
     /// Message content
     pub content: String,
+    /// Whether a long message is collapsed to its first line
+    pub collapsed: bool,
 }
 
 impl Message {
@@ -73,6 +117,7 @@ impl Message {
             direction,
             project: project.into(),
             content: content.into(),
+            collapsed: false,
         }
     }
 
@@ -81,6 +126,11 @@ impl Message {
         Self::new(MessageDirection::System, "system", content)
     }
 
+    /// Create a system message showing an error and what to do about it.
+    pub fn error(err: &commander_core::CommanderError) -> Self {
+        Self::system(format!("Error: {}", err.user_message()))
+    }
+
     /// Create a sent message.
     pub fn sent(project: impl Into<String>, content: impl Into<String>) -> Self {
         Self::new(MessageDirection::Sent, project, content)
@@ -114,6 +164,18 @@ pub enum ViewMode {
     Inspect,
     /// Sessions list view
     Sessions,
+    /// Git diff of the connected project
+    Diff,
+    /// Tool calls waiting for approval
+    Approvals,
+    /// Structured logs of the connected session
+    Logs,
+    /// Transcript of a session, opened from search results
+    History,
+    /// What needs attention across all projects
+    Inbox,
+    /// Prompt patches proposed from feedback
+    Improvements,
 }
 
 /// Information about a tmux session for the sessions list view.
@@ -125,6 +187,10 @@ pub struct SessionInfo {
     pub adapter: commander_core::Adapter,
     /// Whether this session is currently connected
     pub is_connected: bool,
+    /// Whether this session is paused (`/pause`)
+    pub is_paused: bool,
+    /// Workspace of the session's project, if it is in one
+    pub workspace: Option<String>,
 }
 
 /// TUI application state.
@@ -134,14 +200,18 @@ pub struct App {
     pub project: Option<String>,
     /// Currently connected project path
     pub project_path: Option<String>,
-    /// Tmux orchestrator for session management
-    pub tmux: Option<TmuxOrchestrator>,
+    /// Session backend: local tmux, or a remote daemon with `--remote`
+    pub tmux: Option<Box<dyn SessionBackend>>,
     /// Adapter registry
     pub registry: AdapterRegistry,
     /// State store for projects
     pub store: StateStore,
+    /// State directory (work items live under `work/`)
+    pub state_dir: std::path::PathBuf,
     /// Map of project name to tmux session name
     pub sessions: HashMap<String, String>,
+    /// Workspace the list, status and broadcast commands are scoped to
+    pub workspace: Option<String>,
 
     // UI State
     /// Current input text
@@ -179,15 +249,51 @@ pub struct App {
     /// Currently selected session index
     pub session_selected: usize,
 
+    // Diff mode
+    /// `git diff` output shown in diff mode
+    pub diff_content: String,
+    /// File the diff is limited to, if any
+    pub diff_file: Option<String>,
+    /// Scroll offset for diff mode (lines from top)
+    pub diff_scroll: usize,
+
+    // Logs mode
+    /// Formatted log lines shown in logs mode
+    pub log_lines: Vec<String>,
+    /// Log shown and its minimum level
+    pub log_title: String,
+    /// Lines scrolled back from the newest; 0 follows new lines
+    pub log_scroll: usize,
+    /// Follows the log file while logs mode is open
+    pub(super) log_tail: Option<commander_core::LogTail>,
+
+    // History mode
+    /// Results of the last `/search`, opened with `/history <n>`
+    pub(super) search_results: Vec<crate::history_search::SearchHit>,
+    /// Transcript lines shown in history mode, with the entry each belongs to
+    pub history_lines: Vec<(usize, String)>,
+    /// Session whose transcript is shown
+    pub history_title: String,
+    /// Top line of the history view
+    pub history_scroll: usize,
+    /// Entry of the opened search result, highlighted
+    pub history_focus: Option<usize>,
+
     // Response summarization
     /// Buffer for collecting raw response lines
     pub(super) response_buffer: Vec<String>,
     /// When output last changed (for idle detection)
     pub(super) last_activity: Option<Instant>,
-    /// Receiver for async summarization result
-    pub(super) summarizer_rx: Option<mpsc::Receiver<String>>,
+    /// Receiver for async summarization result, with the differential step it completes
+    pub(super) summarizer_rx: Option<mpsc::Receiver<(Option<DifferentialRequest>, String)>>,
+    /// Running summary of the response buffer, updated with new output only
+    pub(super) diff_summary: DifferentialSummary,
+    /// Receiver for a background update of the running summary
+    pub(super) fold_rx: Option<mpsc::Receiver<(DifferentialRequest, Option<String>)>>,
     /// Whether we're currently summarizing
     pub(super) is_summarizing: bool,
+    /// Receiver for the "Since you left" digest of the connected session
+    pub(super) recap_rx: Option<mpsc::Receiver<(String, String)>>,
     /// The user's original query (for context in summarization)
     pub(super) pending_query: Option<String>,
 
@@ -219,6 +325,136 @@ pub struct App {
     /// Sessions that were waiting in the last full scan
     pub(super) last_scan_waiting: std::collections::HashSet<String>,
 
+    // Session handoff
+    /// Session this TUI is attached to in the shared session registry.
+    pub(super) attached_session: Option<String>,
+    /// Lock held while this is the running local TUI.
+    pub(super) instance: Option<super::instance::InstanceLock>,
+
+    // File change tracking
+    /// Watcher on the connected project's directory.
+    pub(super) file_watcher: Option<ProjectWatcher>,
+    /// Receiver for debounced batches of changed paths.
+    pub(super) file_changes_rx: Option<mpsc::Receiver<Vec<PathBuf>>>,
+    /// Files changed in the connected project since connecting.
+    pub(super) changed_files: BTreeSet<PathBuf>,
+
+    // Watchdog alerting
+    /// Stuck-session and error-loop detection.
+    pub(super) watchdog: Watchdog,
+    /// Sessions currently tracked by the watchdog.
+    pub(super) watchdog_sessions: std::collections::HashSet<String>,
+    /// Active alert banners keyed by project name.
+    pub(super) watchdog_banners: BTreeMap<String, String>,
+    /// Last time the watchdog observed sessions.
+    pub(super) last_watchdog_check: Option<Instant>,
+    /// Prompts waiting for their sessions' adapters to become ready.
+    pub(super) pending_prompts: Vec<PendingPrompt>,
+    /// Projects connected in read-only mode.
+    pub(super) read_only_projects: std::collections::HashSet<String>,
+    /// Tmux sessions paused with `/pause`, here or in another frontend.
+    pub(super) paused_sessions: std::collections::HashSet<String>,
+    /// Last time paused sessions were reloaded.
+    pub(super) last_pause_check: Option<Instant>,
+    /// Telegram bot health, when a bot token is configured.
+    pub(super) bot_health: Option<commander_telegram::BotHealth>,
+    /// Last time the bot's heartbeat was checked.
+    pub(super) last_bot_health_check: Option<Instant>,
+
+    // Session snapshots
+    /// Snapshots of live sessions, for restoring them after a reboot.
+    pub(super) snapshots: SnapshotStore,
+    /// Last time live sessions were snapshotted.
+    pub(super) last_snapshot: Option<Instant>,
+
+    // Blocking-event escalation
+    /// Escalation currently shown as a modal.
+    pub(super) escalation_modal: Option<Escalation>,
+    /// `/connect` wizard, shown as a modal while open.
+    pub(super) connect_wizard: Option<super::wizard::ConnectWizard>,
+    /// Image opened with `/view`, shown as a modal while open.
+    pub(super) image_view: Option<super::view::ImageView>,
+    /// Whether a closed image still has to be removed from the screen.
+    pub(super) image_closed: bool,
+
+    // Clipboard
+    /// System clipboard, opened on first copy and kept so X11 keeps serving it.
+    pub(super) clipboard: Option<arboard::Clipboard>,
+    /// Message at the bottom of the output area, copied by the yank key.
+    pub(super) message_in_view: Option<usize>,
+    /// Escalations already shown, as `(event id, attempt)`.
+    pub(super) seen_escalations: std::collections::HashSet<(String, u32)>,
+    /// Last time the escalation state was read.
+    pub(super) last_escalation_check: Option<Instant>,
+
+    // Approval queue
+    /// Pending approval requests shown in the approvals pane.
+    pub(super) approval_list: Vec<ApprovalRequest>,
+    /// Selected index in the approvals pane.
+    pub(super) approval_selected: usize,
+    /// Last time the approval queue was read.
+    pub(super) last_approval_check: Option<Instant>,
+
+    // Prompt patches
+    /// Proposed prompt patches shown in the improvements pane.
+    pub(super) improvement_list: Vec<PromptPatch>,
+    /// Selected index in the improvements pane.
+    pub(super) improvement_selected: usize,
+
+    // Inbox
+    /// Items shown in the inbox, most significant first.
+    pub(super) inbox_items: Vec<super::inbox::InboxItem>,
+    /// Selected index in the inbox.
+    pub(super) inbox_selected: usize,
+    /// When each waiting session was first seen waiting.
+    pub(super) inbox_waiting_since: HashMap<String, DateTime<Utc>>,
+    /// Waiting sessions and overdue items acknowledged in the inbox.
+    pub(super) inbox_hidden: std::collections::HashSet<String>,
+    /// Snoozed inbox items and when they return.
+    pub(super) inbox_snoozed: HashMap<String, DateTime<Utc>>,
+    /// Last time the open inbox was refreshed.
+    pub(super) last_inbox_refresh: Option<Instant>,
+
+    // Session recording
+    /// Active `/record` recording and the session it follows.
+    pub(super) recording: Option<(String, Recorder)>,
+    /// Last time the recorded session was captured.
+    pub(super) last_record_capture: Option<Instant>,
+
+    /// Last time the connected session's output was polled.
+    pub(super) last_output_poll: Option<Instant>,
+
+    // Live configuration
+    /// Key per global action, from `[tui.keybindings]`.
+    pub(super) keybindings: Keybindings,
+    /// Colors, from `[tui.theme]` or `/theme`.
+    pub(super) theme: Theme,
+    /// `[tui.theme]` last applied, so `/theme` survives unrelated reloads.
+    pub(super) theme_settings: ThemeSettings,
+    /// `config.toml` changes, once subscribed by the event loop.
+    pub(super) config_changes: Option<mpsc::Receiver<ConfigChange>>,
+
+    // Desktop notifications
+    /// Which projects and significance levels notify, from `[notifications]`.
+    pub(super) notification_settings: NotificationSettings,
+    /// Whether the terminal has focus. Treated as unfocused until the
+    /// terminal reports focus changes.
+    pub(super) terminal_focused: bool,
+    /// Change detector per session, fed by the status checks.
+    pub(super) change_detectors: HashMap<String, ChangeDetector>,
+    /// Last desktop notification per project.
+    pub(super) last_desktop_notification: HashMap<String, Instant>,
+    /// When the digest of desktop notifications held for do-not-disturb was
+    /// last checked for.
+    pub(super) last_dnd_check: Option<Instant>,
+
+    // Filesystem commands
+    /// Size limits and protected paths, from `[filesystem]`.
+    pub(super) filesystem_settings: FilesystemSettings,
+    /// Filesystem command on a protected path awaiting `/approve`, with
+    /// its working directory.
+    pub(super) pending_fs_command: Option<(String, PathBuf)>,
+
     // Agent orchestration (optional, behind feature flag)
     #[cfg(feature = "agents")]
     /// Agent orchestrator for multi-agent system integration.
@@ -226,6 +462,21 @@ pub struct App {
     #[cfg(feature = "agents")]
     /// Tokio runtime handle for async operations.
     pub(super) runtime_handle: Option<Arc<TokioHandle>>,
+    #[cfg(feature = "agents")]
+    /// Session whose undo is awaiting `/undo confirm`.
+    pub(super) pending_undo: Option<String>,
+    #[cfg(feature = "agents")]
+    /// Session already told that its task's budget is exhausted.
+    pub(super) budget_notified: Option<String>,
+    #[cfg(feature = "agents")]
+    /// Status updates from the plan approved with `/plan approve`.
+    pub(super) plan_rx: Option<mpsc::Receiver<commander_orchestrator::PlanEvent>>,
+    #[cfg(feature = "agents")]
+    /// Diagnosis made while analyzing a response, shown after it.
+    pub(super) diagnosis_card: Option<commander_core::ErrorDiagnosis>,
+    #[cfg(feature = "agents")]
+    /// Diagnosis whose card awaits `/fix apply` or `/fix ignore`.
+    pub(super) pending_diagnosis: Option<commander_core::ErrorDiagnosis>,
 
     // Mouse click support
     /// Clickable items in the current frame (cleared on each render).
@@ -250,7 +501,9 @@ impl App {
 
         let store = StateStore::new(state_dir);
         let registry = AdapterRegistry::new();
-        let tmux = TmuxOrchestrator::new().ok();
+        let tmux = TmuxOrchestrator::new()
+            .ok()
+            .map(|tmux| Box::new(tmux.with_sender(CommandSender::Tui)) as Box<dyn SessionBackend>);
 
         let mut app = Self {
             project: None,
@@ -259,6 +512,7 @@ impl App {
             registry,
             store,
             sessions: HashMap::new(),
+            workspace: None,
 
             input: String::new(),
             cursor_pos: 0,
@@ -278,9 +532,27 @@ impl App {
             session_list: Vec::new(),
             session_selected: 0,
 
+            diff_content: String::new(),
+            diff_file: None,
+            diff_scroll: 0,
+
+            log_lines: Vec::new(),
+            log_title: String::new(),
+            log_scroll: 0,
+            log_tail: None,
+
+            search_results: Vec::new(),
+            history_lines: Vec::new(),
+            history_title: String::new(),
+            history_scroll: 0,
+            history_focus: None,
+
             response_buffer: Vec::new(),
             last_activity: None,
             summarizer_rx: None,
+            diff_summary: DifferentialSummary::new(),
+            fold_rx: None,
+            recap_rx: None,
             is_summarizing: false,
             pending_query: None,
 
@@ -298,10 +570,82 @@ impl App {
             last_full_scan: None,
             last_scan_waiting: std::collections::HashSet::new(),
 
+            attached_session: None,
+            instance: None,
+
+            file_watcher: None,
+            file_changes_rx: None,
+            changed_files: BTreeSet::new(),
+
+            watchdog: Watchdog::new(WatchdogConfig::load_or_default(
+                &commander_core::config::config_dir().join(WATCHDOG_FILE),
+            )),
+            watchdog_sessions: std::collections::HashSet::new(),
+            watchdog_banners: BTreeMap::new(),
+            last_watchdog_check: None,
+            pending_prompts: Vec::new(),
+            read_only_projects: std::collections::HashSet::new(),
+            paused_sessions: std::collections::HashSet::new(),
+            last_pause_check: None,
+            bot_health: None,
+            last_bot_health_check: None,
+            snapshots: SnapshotStore::new(state_dir),
+            state_dir: state_dir.to_path_buf(),
+            last_snapshot: None,
+
+            escalation_modal: None,
+            connect_wizard: None,
+            image_view: None,
+            image_closed: false,
+            clipboard: None,
+            message_in_view: None,
+            seen_escalations: std::collections::HashSet::new(),
+            last_escalation_check: None,
+
+            approval_list: Vec::new(),
+            approval_selected: 0,
+            last_approval_check: None,
+            improvement_list: Vec::new(),
+            improvement_selected: 0,
+            inbox_items: Vec::new(),
+            inbox_selected: 0,
+            inbox_waiting_since: HashMap::new(),
+            inbox_hidden: std::collections::HashSet::new(),
+            inbox_snoozed: HashMap::new(),
+            last_inbox_refresh: None,
+
+            recording: None,
+            last_record_capture: None,
+            last_output_poll: None,
+
+            keybindings: Keybindings::default(),
+            theme: Theme::default(),
+            theme_settings: ThemeSettings::default(),
+            config_changes: None,
+
+            notification_settings: NotificationSettings::default(),
+            terminal_focused: false,
+            change_detectors: HashMap::new(),
+            last_desktop_notification: HashMap::new(),
+            last_dnd_check: None,
+
+            filesystem_settings: FilesystemSettings::default(),
+            pending_fs_command: None,
+
             #[cfg(feature = "agents")]
             orchestrator: None,
             #[cfg(feature = "agents")]
             runtime_handle: None,
+            #[cfg(feature = "agents")]
+            pending_undo: None,
+            #[cfg(feature = "agents")]
+            budget_notified: None,
+            #[cfg(feature = "agents")]
+            plan_rx: None,
+            #[cfg(feature = "agents")]
+            diagnosis_card: None,
+            #[cfg(feature = "agents")]
+            pending_diagnosis: None,
 
             clickable_items: Vec::new(),
             output_area: None,
@@ -318,29 +662,17 @@ impl App {
         if app.tmux.is_none() {
             app.messages.push(Message::system("Warning: tmux not available"));
         }
+        app.register_project_hosts();
 
         app
     }
 
-    /// Handle a mouse click at the given screen coordinates.
-    ///
-    /// Iterates through registered clickable items and executes the
-    /// corresponding action if the click falls within a clickable region.
-    pub fn handle_click(&mut self, x: u16, y: u16) {
-        // Find the first clickable item containing this point
-        let action = self.clickable_items.iter()
-            .find(|item| item.contains(x, y))
-            .map(|item| item.action.clone());
-
-        if let Some(action) = action {
-            match action {
-                ClickAction::Connect(session_name) => {
-                    if let Err(e) = self.connect(&session_name) {
-                        self.messages.push(Message::system(format!("Error: {}", e)));
-                    }
-                }
-            }
-        }
+    /// Drive sessions on a remote daemon instead of local tmux.
+    pub fn use_remote(&mut self, client: RemoteClient) {
+        self.messages.retain(|m| m.content != "Warning: tmux not available");
+        self.messages.push(Message::system(format!("Attached to remote daemon at {}", client.base_url())));
+        self.tmux = Some(Box::new(client));
+        self.cached_sessions = None;
     }
 
     /// Clear clickable items for the next render cycle.
@@ -475,6 +807,8 @@ mod tests {
             name: "test".to_string(),
             adapter: commander_core::Adapter::Claude,
             is_connected: false,
+            is_paused: false,
+            workspace: None,
         };
         assert_eq!(session.adapter, commander_core::Adapter::Claude);
         assert!(!session.is_connected);
@@ -491,16 +825,22 @@ mod tests {
                 name: "proj1".to_string(),
                 adapter: commander_core::Adapter::Claude,
                 is_connected: false,
+                is_paused: false,
+                workspace: None,
             },
             SessionInfo {
                 name: "proj2".to_string(),
                 adapter: commander_core::Adapter::Claude,
                 is_connected: true,
+                is_paused: false,
+                workspace: None,
             },
             SessionInfo {
                 name: "other-session".to_string(),
                 adapter: commander_core::Adapter::Shell,
                 is_connected: false,
+                is_paused: false,
+                workspace: None,
             },
         ];
         app.view_mode = ViewMode::Sessions;
@@ -620,11 +960,11 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let mut app = App::new(temp_dir.path());
 
-        // Complete /s to /send
+        // Complete /s to /search
         app.input = "/s".to_string();
         app.cursor_pos = 2;
         app.complete_command();
-        assert_eq!(app.input, "/send");
+        assert_eq!(app.input, "/search");
         assert!(!app.completions.is_empty());
 
         // Type a character - should reset completions
@@ -634,6 +974,31 @@ mod tests {
         assert!(app.completion_index.is_none());
     }
 
+    #[test]
+    fn test_tab_completion_fs_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_dir = temp_dir.path().join("api");
+        std::fs::create_dir_all(project_dir.join("src")).unwrap();
+        std::fs::write(project_dir.join("src/main.rs"), "").unwrap();
+        std::fs::write(project_dir.join("src/model.rs"), "").unwrap();
+
+        let mut app = App::new(temp_dir.path());
+        app.project = Some("api".to_string());
+        app.project_path = Some(project_dir.to_string_lossy().to_string());
+
+        app.input = "cat src/m".to_string();
+        app.complete_command();
+        assert_eq!(app.input, "cat src/main.rs");
+        app.complete_command();
+        assert_eq!(app.input, "cat src/model.rs");
+
+        // Only filesystem commands complete paths
+        app.reset_completions();
+        app.input = "fix src/m".to_string();
+        app.complete_command();
+        assert_eq!(app.input, "fix src/m");
+    }
+
     #[test]
     fn test_tab_completion_telegram() {
         let temp_dir = tempfile::tempdir().unwrap();
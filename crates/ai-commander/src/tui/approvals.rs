@@ -0,0 +1,351 @@
+//! Approval mode and the approvals pane.
+//!
+//! Projects in approval mode queue agent delegations and file-writing
+//! filesystem commands instead of running them. Queued requests are shown in
+//! the approvals pane (`/approvals`) and can also be decided from Telegram;
+//! the TUI runs every approved request.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use commander_core::approvals::{self, ApprovalRequest, DELEGATE_TOOL, FILESYSTEM_TOOL, GATED_TOOLS};
+
+use super::app::{App, Message, ViewMode};
+use crate::filesystem;
+use crate::sandbox::Sandbox;
+
+/// Minimum time between approval queue checks.
+const APPROVAL_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Name the TUI records as the decider and uses to track announcements.
+const TUI_CHANNEL: &str = "tui";
+
+/// Number of audit log entries shown by `/approvals log`.
+const AUDIT_LOG_LINES: usize = 20;
+
+impl App {
+    /// Handle `/approvals [log]`: open the approvals pane or show the audit log.
+    pub(super) fn handle_approvals_command(&mut self, arg: Option<&str>) {
+        match arg {
+            Some("log") => self.show_approval_log(),
+            Some(other) => self
+                .messages
+                .push(Message::system(format!("Unknown option '{}'. Usage: /approvals [log]", other))),
+            None => self.show_approvals(),
+        }
+    }
+
+    /// Open the approvals pane.
+    pub fn show_approvals(&mut self) {
+        self.refresh_approvals();
+        self.view_mode = ViewMode::Approvals;
+    }
+
+    /// Leave the approvals pane.
+    pub fn close_approvals(&mut self) {
+        if self.view_mode == ViewMode::Approvals {
+            self.view_mode = ViewMode::Normal;
+        }
+    }
+
+    /// Reload pending requests from the shared queue.
+    pub(super) fn refresh_approvals(&mut self) {
+        self.approval_list = approvals::pending_approvals();
+        self.approval_selected = self
+            .approval_selected
+            .min(self.approval_list.len().saturating_sub(1));
+    }
+
+    /// Select the previous request in the approvals pane.
+    pub fn approval_select_up(&mut self) {
+        self.approval_selected = self.approval_selected.saturating_sub(1);
+    }
+
+    /// Select the next request in the approvals pane.
+    pub fn approval_select_down(&mut self) {
+        if self.approval_selected + 1 < self.approval_list.len() {
+            self.approval_selected += 1;
+        }
+    }
+
+    /// Approve or deny the request selected in the approvals pane.
+    pub fn decide_selected_approval(&mut self, approve: bool) {
+        let Some(id) = self.approval_list.get(self.approval_selected).map(|r| r.id.clone()) else {
+            return;
+        };
+        self.decide_queued_approval(&id, approve);
+        self.refresh_approvals();
+    }
+
+    /// Handle `/approve [id]` and `/deny [id]`.
+    ///
+    /// With an ID, decides that queued request. Without one, resolves the
+    /// filesystem command held for a protected path, the command held by the
+    /// agent's guardrails, or the only queued request.
+    pub(super) fn handle_approve_command(&mut self, approve: bool, id: Option<&str>) {
+        if let Some(id) = id.filter(|id| !id.is_empty()) {
+            self.decide_queued_approval(id, approve);
+            return;
+        }
+
+        if self.pending_fs_command.is_some() {
+            self.resolve_pending_fs_command(approve);
+            return;
+        }
+
+        #[cfg(feature = "agents")]
+        if self.orchestrator.as_ref().is_some_and(|o| o.pending_approval().is_some()) {
+            self.resolve_pending_approval(approve);
+            return;
+        }
+
+        match approvals::pending_approvals().as_slice() {
+            [] => self.messages.push(Message::system("Nothing awaiting approval")),
+            [only] => {
+                let id = only.id.clone();
+                self.decide_queued_approval(&id, approve);
+            }
+            many => self.messages.push(Message::system(format!(
+                "{} requests awaiting approval; use /approve <id> or open /approvals",
+                many.len()
+            ))),
+        }
+    }
+
+    /// Approve or deny a queued request by ID or ID prefix.
+    pub(super) fn decide_queued_approval(&mut self, id: &str, approve: bool) {
+        match approvals::decide_approval(id, approve, TUI_CHANNEL) {
+            Ok(request) => {
+                let verb = if approve { "Approved" } else { "Denied" };
+                self.messages
+                    .push(Message::system(format!("{} {}: {}", verb, request.id, request.summary)));
+                if approve {
+                    self.run_approved_requests();
+                }
+            }
+            Err(e) => self.messages.push(Message::system(format!("Error: {}", e))),
+        }
+    }
+
+    /// Handle `/approvalmode [on|off]` for the connected project.
+    pub(super) fn handle_approval_mode(&mut self, arg: Option<&str>) {
+        let Some(project) = self.project.clone() else {
+            self.messages.push(Message::system("Connect to a project first"));
+            return;
+        };
+
+        let enabled = match arg {
+            None => {
+                let state = if approvals::approval_mode_enabled(&project) { "on" } else { "off" };
+                self.messages
+                    .push(Message::system(format!("Approval mode for {} is {}", project, state)));
+                return;
+            }
+            Some("on") => true,
+            Some("off") => false,
+            Some(other) => {
+                self.messages.push(Message::system(format!(
+                    "Unknown option '{}'. Usage: /approvalmode [on|off]",
+                    other
+                )));
+                return;
+            }
+        };
+
+        match approvals::set_approval_mode(&project, enabled) {
+            Ok(()) if enabled => self.messages.push(Message::system(format!(
+                "Approval mode on for {}: agent delegations and file writes wait for /approve",
+                project
+            ))),
+            Ok(()) => self
+                .messages
+                .push(Message::system(format!("Approval mode off for {}", project))),
+            Err(e) => self
+                .messages
+                .push(Message::system(format!("Failed to save approval mode: {}", e))),
+        }
+    }
+
+    /// Queue a file-writing filesystem command when the connected project is
+    /// in approval mode.
+    ///
+    /// Returns `true` when the command was queued (or could not be) and must
+    /// not run now.
+    pub(super) fn queue_fs_command_if_required(&mut self, input: &str, working_dir: &Path) -> bool {
+        let Some(project) = self.project.clone() else {
+            return false;
+        };
+        if !approvals::approval_mode_enabled(&project) {
+            return false;
+        }
+
+        let arguments = serde_json::json!({
+            "command": input,
+            "working_dir": working_dir.to_string_lossy(),
+        });
+        match approvals::queue_approval(&project, FILESYSTEM_TOOL, arguments, input) {
+            Ok(request) => {
+                if let Err(e) = approvals::mark_approvals_announced(TUI_CHANNEL, std::slice::from_ref(&request.id)) {
+                    tracing::warn!(error = %e, "Failed to mark approval announced");
+                }
+                self.messages.push(Message::sent(project, input));
+                self.messages.push(Message::system(format!(
+                    "Queued for approval as {} (/approve {} or /approvals)",
+                    request.id, request.id
+                )));
+            }
+            Err(e) => self
+                .messages
+                .push(Message::system(format!("Failed to queue for approval: {}", e))),
+        }
+        self.scroll_to_bottom();
+        true
+    }
+
+    /// Announce new requests and run approved ones.
+    ///
+    /// Called on every event loop tick; rate limited to
+    /// `APPROVAL_CHECK_INTERVAL`.
+    pub fn check_approvals(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_approval_check
+            .is_some_and(|last| now.duration_since(last) < APPROVAL_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_approval_check = Some(now);
+
+        let new = approvals::unannounced_approvals(TUI_CHANNEL);
+        if !new.is_empty() {
+            for request in &new {
+                self.messages.push(Message::system(format!(
+                    "[?] Approval needed {} [{}]: {}  (/approve {} | /deny {})",
+                    request.id, request.project, request.summary, request.id, request.id
+                )));
+            }
+            let ids: Vec<String> = new.into_iter().map(|r| r.id).collect();
+            if let Err(e) = approvals::mark_approvals_announced(TUI_CHANNEL, &ids) {
+                tracing::warn!(error = %e, "Failed to mark approvals announced");
+            }
+        }
+
+        self.run_approved_requests();
+        if self.view_mode == ViewMode::Approvals {
+            self.refresh_approvals();
+        }
+    }
+
+    /// Run every approved request, whichever frontend approved it.
+    fn run_approved_requests(&mut self) {
+        for request in approvals::take_approved(GATED_TOOLS) {
+            let outcome = self.run_approved(&request);
+            let (success, detail) = match &outcome {
+                Ok(detail) => (true, detail.clone()),
+                Err(e) => (false, e.clone()),
+            };
+            approvals::finish_approval(&request.id, success, detail.as_str());
+            let status = if success { "Ran" } else { "Failed" };
+            self.messages.push(Message::system(format!(
+                "{} approved {} [{}]: {}",
+                status, request.id, request.project, detail
+            )));
+        }
+    }
+
+    /// Run one approved request; returns its output or error.
+    fn run_approved(&self, request: &ApprovalRequest) -> Result<String, String> {
+        match request.tool.as_str() {
+            FILESYSTEM_TOOL => {
+                let input = request.argument("command").ok_or("missing command")?;
+                let working_dir = PathBuf::from(request.argument("working_dir").ok_or("missing working directory")?);
+                let command = filesystem::parse_command(input, &working_dir)
+                    .ok_or_else(|| format!("not a filesystem command: {}", input))?;
+                // Approval stands in for protected-path confirmation
+                let sandbox = Sandbox::new(&working_dir, self.filesystem_settings.clone());
+                let command = sandbox.confine(&command, true).map_err(|e| e.to_string())?;
+                let result = if self.is_read_only(&request.project) {
+                    filesystem::execute_read_only(&command, sandbox.root())
+                } else {
+                    filesystem::execute(&command, sandbox.root())
+                };
+                if result.success {
+                    Ok(result.message)
+                } else {
+                    Err(result.message)
+                }
+            }
+            DELEGATE_TOOL => {
+                let task = request.argument("task").ok_or("missing task")?;
+                let tmux = self.tmux.as_ref().ok_or("tmux not available")?;
+                let session = self
+                    .sessions
+                    .get(&request.project)
+                    .cloned()
+                    .unwrap_or_else(|| format!("commander-{}", request.project));
+                if !tmux.session_exists(&session) {
+                    return Err(format!("session {} not found", session));
+                }
+                tmux.send_line(&session, None, task)
+                    .map_err(|e| format!("failed to send: {}", e))?;
+                Ok(format!("sent to {}", session))
+            }
+            other => Err(format!("unsupported tool {}", other)),
+        }
+    }
+
+    /// Show the most recent approval audit log entries.
+    fn show_approval_log(&mut self) {
+        let entries = approvals::recent_audit_entries(AUDIT_LOG_LINES);
+        if entries.is_empty() {
+            self.messages.push(Message::system("Approval audit log is empty"));
+            return;
+        }
+        self.messages.push(Message::system("Approval audit log:"));
+        for entry in entries {
+            let by = entry.by.map(|by| format!(" by {}", by)).unwrap_or_default();
+            let detail = entry.detail.map(|d| format!(" - {}", d)).unwrap_or_default();
+            self.messages.push(Message::system(format!(
+                "  {} {} {:<8} [{}] {}{}{}",
+                entry.at.format("%Y-%m-%d %H:%M"),
+                entry.request_id,
+                entry.action.to_string(),
+                entry.project,
+                entry.summary,
+                by,
+                detail
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_approval_selection_bounds() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        let mut queue = approvals::ApprovalQueue::default();
+        for task in ["a", "b"] {
+            queue.push("proj", DELEGATE_TOOL, serde_json::json!({ "task": task }), task);
+        }
+        app.approval_list = queue.requests;
+
+        app.approval_select_up();
+        assert_eq!(app.approval_selected, 0);
+        app.approval_select_down();
+        app.approval_select_down();
+        assert_eq!(app.approval_selected, 1);
+    }
+
+    #[test]
+    fn test_approval_mode_requires_project() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.handle_approval_mode(Some("on"));
+        assert!(app.messages.last().unwrap().content.contains("Connect to a project"));
+    }
+}
@@ -0,0 +1,37 @@
+//! Telegram bot health in the status bar.
+//!
+//! The bot's heartbeat file is read periodically; the status bar shows
+//! "bot healthy", "bot stale" or "bot down" once a bot token is configured.
+
+use std::time::{Duration, Instant};
+
+use commander_telegram::heartbeat;
+
+use super::app::App;
+
+/// Minimum time between bot health checks.
+const BOT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+impl App {
+    /// Refresh the Telegram bot's health.
+    ///
+    /// Called on every event loop tick; rate limited to `BOT_HEALTH_CHECK_INTERVAL`.
+    pub(super) fn check_bot_health(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_bot_health_check
+            .is_some_and(|last| now.duration_since(last) < BOT_HEALTH_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_bot_health_check = Some(now);
+
+        let configured = commander_core::get_secret(commander_core::secrets::TELEGRAM_BOT_TOKEN).is_some();
+        self.bot_health = configured.then(heartbeat::health);
+    }
+
+    /// Status bar label of the bot's health, if a bot is configured.
+    pub(super) fn bot_health_label(&self) -> Option<String> {
+        self.bot_health.map(|health| format!("bot {}", health))
+    }
+}
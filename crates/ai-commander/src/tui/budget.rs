@@ -0,0 +1,87 @@
+//! Per-task budgets of delegated work (feature-gated).
+//!
+//! `/budget` shows the LLM turns, tokens and time the connected session's
+//! current task has used against `[agents.budget]`. A task that spends its
+//! budget is blocked, and `/budget approve` lets it continue for another
+//! allowance.
+
+use super::app::App;
+#[cfg(feature = "agents")]
+use super::app::Message;
+
+#[cfg(feature = "agents")]
+impl App {
+    /// Handle `/budget` (usage) and `/budget approve`.
+    pub fn handle_budget(&mut self, arg: Option<&str>) {
+        let Some(session) = self.current_session_name() else {
+            self.messages.push(Message::system("Not connected to any project"));
+            return;
+        };
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        match arg {
+            Some("approve") | Some("continue") => {
+                if orchestrator.approve_task_budget(&session) {
+                    self.budget_notified = None;
+                    let usage = orchestrator.task_budget_usage(&session);
+                    self.messages.push(Message::system(format!(
+                        "Budget extended: {}",
+                        usage.map(|u| u.to_string()).unwrap_or_default()
+                    )));
+                } else {
+                    self.messages.push(Message::system("The task's budget is not exhausted"));
+                }
+            }
+            Some(other) => {
+                self.messages.push(Message::system(format!(
+                    "Unknown /budget option '{}'. Usage: /budget [approve]",
+                    other
+                )));
+            }
+            None => match orchestrator.task_budget_usage(&session) {
+                Some(usage) if usage.exhausted.is_some() => {
+                    self.messages.push(Message::system(format!(
+                        "Budget exhausted: {}. /budget approve to continue",
+                        usage
+                    )));
+                }
+                Some(usage) => self.messages.push(Message::system(format!("Budget: {}", usage))),
+                None => self.messages.push(Message::system(
+                    "No task budget. Set limits under [agents.budget] in config.toml",
+                )),
+            },
+        }
+    }
+
+    /// Tell the user once when the session's task has spent its budget.
+    pub(super) fn notify_budget_exhausted(&mut self, session: &str) {
+        let Some(orchestrator) = self.orchestrator.as_ref() else { return };
+        let Some(usage) = orchestrator.task_budget_usage(session).filter(|u| u.exhausted.is_some()) else {
+            return;
+        };
+        if self.budget_notified.as_deref() == Some(session) {
+            return;
+        }
+        self.budget_notified = Some(session.to_string());
+        self.messages.push(Message::system(format!(
+            "⏸ Task budget exhausted ({}). /budget approve to continue",
+            usage
+        )));
+    }
+
+    /// Budget usage of the session's current task, for the work view.
+    pub(super) fn budget_line(&self, session: &str) -> Option<String> {
+        let usage = self.orchestrator.as_ref()?.task_budget_usage(session)?;
+        Some(format!("Budget: {}", usage))
+    }
+}
+
+#[cfg(not(feature = "agents"))]
+impl App {
+    pub(super) fn budget_line(&self, _session: &str) -> Option<String> {
+        None
+    }
+}
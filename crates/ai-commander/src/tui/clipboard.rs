@@ -0,0 +1,166 @@
+//! Copying messages and diffs to the system clipboard.
+//!
+//! `/copy last` copies the last response, `/copy diff` the connected
+//! project's uncommitted changes, and the yank key (Ctrl+Y by default) the
+//! message at the bottom of the output, so scrolling up selects an older one.
+//!
+//! Text goes to the system clipboard through arboard. Over SSH, or when no
+//! clipboard is reachable, it is sent to the terminal as an OSC 52 sequence
+//! instead, which most terminals (and tmux with `set-clipboard on`) put on
+//! the clipboard of the machine the user sits at.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use super::app::{App, Message, MessageDirection};
+
+/// Largest OSC 52 payload (base64 bytes); many terminals drop longer ones.
+const OSC52_MAX: usize = 100_000;
+
+/// How copied text reached the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopiedVia {
+    /// The system clipboard of this machine.
+    Clipboard,
+    /// An OSC 52 sequence to the terminal.
+    Osc52,
+}
+
+/// Whether the TUI runs over SSH, read through `var`; the clipboard of this
+/// machine is then not the user's.
+pub fn is_remote(var: impl Fn(&str) -> Option<String>) -> bool {
+    var("SSH_CONNECTION").is_some() || var("SSH_TTY").is_some()
+}
+
+/// OSC 52 sequence setting the clipboard to `text`, or `None` when `text`
+/// is too long for terminals to accept.
+pub fn osc52_sequence(text: &str) -> Option<String> {
+    let encoded = STANDARD.encode(text);
+    (encoded.len() <= OSC52_MAX).then(|| format!("\x1b]52;c;{}\x07", encoded))
+}
+
+/// "12 lines, 1.4 KB"
+fn size_label(text: &str) -> String {
+    let lines = text.lines().count();
+    let size = if text.len() < 1024 {
+        format!("{} B", text.len())
+    } else {
+        format!("{:.1} KB", text.len() as f64 / 1024.0)
+    };
+    match lines {
+        1 => format!("1 line, {}", size),
+        n => format!("{} lines, {}", n, size),
+    }
+}
+
+impl App {
+    /// Handle `/copy last` and `/copy diff`.
+    pub fn handle_copy(&mut self, arg: Option<&str>) {
+        match arg.map(str::trim).filter(|a| !a.is_empty()) {
+            None | Some("last") => {
+                let last = self
+                    .messages
+                    .iter()
+                    .rev()
+                    .find(|m| m.direction == MessageDirection::Received)
+                    .map(|m| m.content.clone());
+                match last {
+                    Some(text) => self.copy_and_report("last response", &text),
+                    None => self.messages.push(Message::system("No response to copy yet")),
+                }
+            }
+            Some("diff") => {
+                let Some(path) = self.project_path.clone() else {
+                    self.messages.push(Message::system("Connect to a project first"));
+                    return;
+                };
+                match commander_core::git_diff(Path::new(&path), None) {
+                    Ok(diff) if diff.trim().is_empty() => {
+                        self.messages.push(Message::system("No uncommitted changes to copy"));
+                    }
+                    Ok(diff) => self.copy_and_report("diff", &diff),
+                    Err(e) => self.messages.push(Message::system(format!("Error: {}", e))),
+                }
+            }
+            Some(other) => self.messages.push(Message::system(format!(
+                "Unknown /copy target '{}'. Usage: /copy [last|diff]",
+                other
+            ))),
+        }
+    }
+
+    /// Copy the message at the bottom of the output (the yank key).
+    pub fn yank_message(&mut self) {
+        let text = self
+            .message_in_view
+            .and_then(|idx| self.messages.get(idx))
+            .map(|m| m.content.clone());
+        match text {
+            Some(text) if !text.is_empty() => self.copy_and_report("message", &text),
+            _ => self.messages.push(Message::system("No message to copy")),
+        }
+    }
+
+    fn copy_and_report(&mut self, what: &str, text: &str) {
+        match self.copy_text(text) {
+            Ok(via) => {
+                let via = match via {
+                    CopiedVia::Clipboard => "",
+                    CopiedVia::Osc52 => " (via the terminal)",
+                };
+                self.messages.push(Message::system(format!(
+                    "Copied {} ({}){}",
+                    what,
+                    size_label(text),
+                    via
+                )));
+            }
+            Err(e) => self.messages.push(Message::system(format!("Copy failed: {}", e))),
+        }
+    }
+
+    /// Put `text` on the clipboard, falling back to OSC 52.
+    pub(super) fn copy_text(&mut self, text: &str) -> Result<CopiedVia, String> {
+        if !is_remote(|name| std::env::var(name).ok()) {
+            if self.clipboard.is_none() {
+                self.clipboard = arboard::Clipboard::new()
+                    .map_err(|e| tracing::debug!(error = %e, "System clipboard unavailable"))
+                    .ok();
+            }
+            if let Some(clipboard) = self.clipboard.as_mut() {
+                match clipboard.set_text(text) {
+                    Ok(()) => return Ok(CopiedVia::Clipboard),
+                    Err(e) => tracing::debug!(error = %e, "Clipboard write failed, using OSC 52"),
+                }
+            }
+        }
+
+        let sequence = osc52_sequence(text)
+            .ok_or_else(|| format!("too large to copy through the terminal ({})", size_label(text)))?;
+        let mut stdout = io::stdout();
+        stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|e| e.to_string())?;
+        Ok(CopiedVia::Osc52)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_and_remote_detection() {
+        assert_eq!(osc52_sequence("hi").unwrap(), "\x1b]52;c;aGk=\x07");
+        assert!(osc52_sequence(&"x".repeat(OSC52_MAX)).is_none());
+
+        assert!(is_remote(|name| (name == "SSH_TTY").then(|| "/dev/pts/1".to_string())));
+        assert!(!is_remote(|_| None));
+        assert_eq!(size_label("a\nb"), "2 lines, 3 B");
+        assert_eq!(size_label(&"x".repeat(1536)), "1 line, 1.5 KB");
+    }
+}
@@ -4,6 +4,7 @@
 
 use super::app::{App, Message};
 use super::connection::ConnectArgs;
+use super::keybindings::KeyAction;
 
 impl App {
     /// Handle a slash command.
@@ -15,25 +16,69 @@ impl App {
         match command.as_str() {
             "help" | "h" | "?" => {
                 self.messages.push(Message::system("=== TUI Commands ==="));
+                self.messages.push(Message::system("  /connect                           Create a project step by step"));
                 self.messages.push(Message::system("  /connect <name>                    Connect to project or tmux session"));
                 self.messages.push(Message::system("  /connect <path> -a <adapter> -n <name>  Start new project"));
+                self.messages.push(Message::system("  /connect <path> --template <name>  Scaffold project from template"));
+                self.messages.push(Message::system("  /connect <name> --read-only        Ask about a project without changing it"));
+                self.messages.push(Message::system("  /templates                         List project templates"));
                 self.messages.push(Message::system("  /disconnect                        Disconnect from project"));
+                self.messages.push(Message::system("  /takeover                          Claim session attached in REPL/Telegram"));
                 self.messages.push(Message::system("  /list                              List sessions with activity"));
                 self.messages.push(Message::system("    Indicators: [Claude] AI session, [Shell] plain shell, [?] unknown"));
                 self.messages.push(Message::system("  /status [name]                     Show project status"));
+                self.messages.push(Message::system("  /workspace [switch|assign] ...     Group projects; scope list/status/@all"));
                 self.messages.push(Message::system("  /sessions                          Session picker (F3)"));
                 self.messages.push(Message::system("  /inspect                           Toggle inspect mode (F2)"));
-                self.messages.push(Message::system("  /stop [session]                    Stop session (commits git, ends tmux)"));
+                self.messages.push(Message::system("  /stop [session] [--no-ai-commit]   Stop session (commits git, ends tmux)"));
+                self.messages.push(Message::system("  /pause [project]                   Pause a session; pollers skip it until /resume"));
+                self.messages.push(Message::system("  /resume [project]                  Resume a paused session"));
+                self.messages.push(Message::system("  /restore [project]                 Relaunch sessions lost to a reboot"));
                 self.messages.push(Message::system("  /rename <new-name>                 Rename current tmux session"));
                 self.messages.push(Message::system("  /send <msg>                        Send message to connected session"));
+                self.messages.push(Message::system("  /note <text>                       Leave a note the session's agent will read"));
+                self.messages.push(Message::system("  /notes                             List the notes on the connected session"));
+                self.messages.push(Message::system("  /run <name> [args], !name [args]   Send a snippet from snippets.toml"));
+                self.messages.push(Message::system("  /snippets                          List snippets"));
+                self.messages.push(Message::system("  /snippet add|edit <name> <text>    Add or change a snippet (rm <name> removes it)"));
+                self.messages.push(Message::system("  /retest                            Re-run the failing tests in the session's output"));
+                self.messages.push(Message::system("  /host [user@host[:port]|local]     Show or set the SSH host of the project's session"));
                 self.messages.push(Message::system("  /telegram                          Generate Telegram pairing code"));
+                self.messages.push(Message::system("  /adapter [use <name>]              Show the adapter, or relaunch with another one"));
                 self.messages.push(Message::system("  /alias [project] [alias]           List or add project aliases"));
                 self.messages.push(Message::system("  /unalias <alias>                   Remove project alias"));
+                self.messages.push(Message::system("  /approve [id]                      Approve queued request or guarded command"));
+                self.messages.push(Message::system("  /deny [id]                         Deny queued request or guarded command"));
+                self.messages.push(Message::system("  /approvals [log]                   Approvals pane, or the decision audit log"));
+                self.messages.push(Message::system("  /approvalmode [on|off]             Queue delegations and file writes for approval"));
+                self.messages.push(Message::system("  /inbox                             What needs attention across projects (F5)"));
+                self.messages.push(Message::system("  /improvements                      Review prompt patches proposed from feedback"));
+                self.messages.push(Message::system("  /undo [confirm|cancel]             Revert file changes from the last task"));
+                self.messages.push(Message::system("  /budget [approve]                  Task budget usage, or continue past it"));
+                self.messages.push(Message::system("  /checkpoint <label>                Snapshot the agent conversation"));
+                self.messages.push(Message::system("  /rewind [label]                    Restore agent conversation to a checkpoint"));
+                self.messages.push(Message::system("  /plan <goal|approve|cancel>        Plan a large goal as tasks, then run it"));
+                self.messages.push(Message::system("  /fix [apply|ignore]                Apply or dismiss the suggested fix for an error"));
+                self.messages.push(Message::system("  /knowledge [keep|drop <id>]        Review knowledge shared by session agents"));
+                self.messages.push(Message::system("  /work [session]                    Work item dependency graph and critical path"));
+                self.messages.push(Message::system("  /diff [file]                       Show uncommitted changes (git diff)"));
+                self.messages.push(Message::system("  /logs [level]                      Follow the session's structured log"));
+                self.messages.push(Message::system("  /view <image>                      Show an image inline (kitty/iTerm2)"));
+                self.messages.push(Message::system("  /search <query>                    Search transcripts and memories"));
+                self.messages.push(Message::system("  /history [n]                       Session transcript, or search result n in it"));
+                self.messages.push(Message::system("  /record [start|stop]               Record session output (asciicast)"));
+                self.messages.push(Message::system("  /ack [event-id]                    Acknowledge a blocking event (lists them without id)"));
+                self.messages.push(Message::system("  /missed <line>                     Report output that should have notified you"));
+                self.messages.push(Message::system("  /snooze [2h|off]                   Hold notifications for a while, or end the snooze"));
+                self.messages.push(Message::system("  /keys                              List keybindings ([tui.keys] in config.toml)"));
+                self.messages.push(Message::system("  /theme [name]                      List color themes, or switch to one"));
+                self.messages.push(Message::system("  /copy [last|diff]                  Copy the last response or the diff to the clipboard"));
                 self.messages.push(Message::system("  /clear                             Clear output"));
                 self.messages.push(Message::system(""));
                 self.messages.push(Message::system("=== Message Routing ==="));
                 self.messages.push(Message::system("  @alias message                     Send to specific session"));
                 self.messages.push(Message::system("  @alias1 @alias2 message            Send to multiple sessions"));
+                self.messages.push(Message::system("  @all message                       Send to all sessions in the workspace"));
                 self.messages.push(Message::system("  /quit                              Exit TUI"));
                 self.messages.push(Message::system(""));
                 self.messages.push(Message::system("=== Adapters ==="));
@@ -55,9 +100,11 @@ impl App {
                 self.messages.push(Message::system("=== Keyboard ==="));
                 self.messages.push(Message::system("  Up/Down     Command history"));
                 self.messages.push(Message::system("  PgUp/PgDn   Scroll output"));
-                self.messages.push(Message::system("  F2          Inspect mode (live tmux)"));
-                self.messages.push(Message::system("  F3          Session picker"));
-                self.messages.push(Message::system("  Ctrl+L      Clear output"));
+                for action in KeyAction::ALL {
+                    if let Some(key) = self.keybindings.label(action) {
+                        self.messages.push(Message::system(format!("  {:<11} {}", key, action.description())));
+                    }
+                }
                 self.messages.push(Message::system("  Ctrl+C      Quit"));
                 self.messages.push(Message::system(""));
                 self.messages.push(Message::system("=== CLI ==="));
@@ -74,12 +121,22 @@ impl App {
                     match self.parse_connect_args(arg_str) {
                         Ok(ConnectArgs::Existing(name)) => {
                             if let Err(e) = self.connect(&name) {
-                                self.messages.push(Message::system(format!("Error: {}", e)));
+                                self.messages.push(Message::error(&e));
+                            }
+                        }
+                        Ok(ConnectArgs::ReadOnly(name)) => {
+                            if let Err(e) = self.connect_read_only(&name) {
+                                self.messages.push(Message::error(&e));
                             }
                         }
                         Ok(ConnectArgs::New { path, adapter, name }) => {
                             if let Err(e) = self.connect_new(&path, &adapter, &name) {
-                                self.messages.push(Message::system(format!("Error: {}", e)));
+                                self.messages.push(Message::error(&e));
+                            }
+                        }
+                        Ok(ConnectArgs::Template { path, template, adapter, name }) => {
+                            if let Err(e) = self.connect_template(&path, &template, adapter.as_deref(), name.as_deref()) {
+                                self.messages.push(Message::error(&e));
                             }
                         }
                         Err(e) => {
@@ -87,12 +144,32 @@ impl App {
                         }
                     }
                 } else {
-                    self.messages.push(Message::system("Usage: /connect <name> or /connect <path> -a <adapter> -n <name>"));
+                    self.open_connect_wizard();
                 }
             }
             "disconnect" | "dc" => {
                 self.disconnect();
             }
+            "templates" => {
+                self.messages.push(Message::system("Project templates:"));
+                for template in commander_core::list_templates() {
+                    let source = match template.source {
+                        commander_core::TemplateSource::BuiltIn => "built-in",
+                        commander_core::TemplateSource::User => "user",
+                    };
+                    self.messages.push(Message::system(format!(
+                        "  {} ({}) - {}",
+                        template.name, source, template.description
+                    )));
+                }
+                self.messages.push(Message::system(format!(
+                    "User templates: {}",
+                    commander_core::project_templates_dir().display()
+                )));
+            }
+            "takeover" => {
+                self.takeover_session();
+            }
             "list" | "ls" | "l" => {
                 let Some(tmux) = &self.tmux else {
                     self.messages.push(Message::system("Tmux not available."));
@@ -107,41 +184,54 @@ impl App {
                     // Load projects for alias display
                     let projects = self.store.load_all_projects().ok();
 
-                    self.messages.push(Message::system("Sessions:"));
-                    for session in &sessions {
-                        let is_connected = self.sessions.values().any(|n| n == &session.name);
-                        let connected_marker = if is_connected { " (connected)" } else { "" };
-
-                        // Detect adapter type from screen content
-                        let adapter = tmux.capture_output(&session.name, None, Some(50))
-                            .map(|output| commander_core::detect_adapter(&output))
-                            .unwrap_or(commander_core::Adapter::Unknown);
-                        let indicator = adapter.indicator();
-
-                        // Get activity summary for this session (now works for all types)
-                        let activity = self.get_session_activity(&session.name, &adapter);
-
-                        // Check for project aliases
-                        let alias_info = if let Some(ref projs) = projects {
-                            let project_name = session.name.as_str();
-                            projs.values()
-                                .find(|p| p.name == project_name)
-                                .and_then(|p| {
-                                    if p.aliases.is_empty() {
-                                        None
-                                    } else {
-                                        Some(format!(" [aliases: {}]", p.aliases.join(", ")))
-                                    }
-                                })
-                                .unwrap_or_default()
-                        } else {
-                            String::new()
-                        };
+                    let grouped = self.group_sessions(sessions, |s| s.name.as_str());
+                    let show_groups = grouped.iter().any(|(workspace, _)| workspace.is_some());
+                    match &self.workspace {
+                        Some(workspace) => self.messages.push(Message::system(format!("Sessions in {}:", workspace))),
+                        None => self.messages.push(Message::system("Sessions:")),
+                    }
+                    for (workspace, sessions) in grouped {
+                        if show_groups {
+                            self.messages.push(Message::system(format!(
+                                " {}:",
+                                workspace.as_deref().unwrap_or("(no workspace)")
+                            )));
+                        }
+                        for session in &sessions {
+                            let is_connected = self.sessions.values().any(|n| n == &session.name);
+                            let connected_marker = if is_connected { " (connected)" } else { "" };
+
+                            // Detect adapter type from screen content
+                            let adapter = tmux.capture_output(&session.name, None, Some(50))
+                                .map(|output| commander_core::detect_adapter(&output))
+                                .unwrap_or(commander_core::Adapter::Unknown);
+                            let indicator = adapter.indicator();
+
+                            // Get activity summary for this session (now works for all types)
+                            let activity = self.get_session_activity(&session.name, &adapter);
+
+                            // Check for project aliases
+                            let alias_info = if let Some(ref projs) = projects {
+                                let project_name = session.name.as_str();
+                                projs.values()
+                                    .find(|p| p.name == project_name)
+                                    .and_then(|p| {
+                                        if p.aliases.is_empty() {
+                                            None
+                                        } else {
+                                            Some(format!(" [aliases: {}]", p.aliases.join(", ")))
+                                        }
+                                    })
+                                    .unwrap_or_default()
+                            } else {
+                                String::new()
+                            };
 
-                        self.messages.push(Message::system(format!(
-                            "  {} {}{}{} - {}",
-                            indicator, session.name, connected_marker, alias_info, activity
-                        )));
+                            self.messages.push(Message::system(format!(
+                                "  {} {}{}{} - {}",
+                                indicator, session.name, connected_marker, alias_info, activity
+                            )));
+                        }
                     }
                 }
             }
@@ -155,7 +245,8 @@ impl App {
             "stop" => {
                 // Stop a session (commit git changes and destroy tmux)
                 // Priority: arg > connected project > current tmux session
-                let target = arg.map(|s| s.to_string())
+                let (target, no_ai_commit) = crate::stop_commit::parse_stop_args(arg);
+                let target = target
                     .or_else(|| self.project.clone())
                     .or_else(|| self.get_current_tmux_session());
 
@@ -167,15 +258,24 @@ impl App {
 
                     if stopping_self {
                         self.messages.push(Message::system(format!("Stopping current session '{}'...", name)));
-                        self.stop_session(&name);
+                        self.stop_session(&name, no_ai_commit);
                         // Note: If we're running inside this tmux session, the process will be killed
                     } else {
-                        self.stop_session(&name);
+                        self.stop_session(&name, no_ai_commit);
                     }
                 } else {
                     self.messages.push(Message::system("Usage: /stop [session] or connect to a session first"));
                 }
             }
+            "pause" => {
+                self.pause_project(arg);
+            }
+            "resume" => {
+                self.resume_project(arg);
+            }
+            "restore" => {
+                self.restore_sessions(arg);
+            }
             "rename" => {
                 // Rename the current tmux session
                 if let Some(new_name) = arg {
@@ -203,7 +303,7 @@ impl App {
             "send" => {
                 if let Some(message) = arg {
                     if let Err(e) = self.send_message(message) {
-                        self.messages.push(Message::system(format!("Error: {}", e)));
+                        self.messages.push(Message::error(&e));
                     }
                 } else {
                     self.messages.push(Message::system("Usage: /send <message>"));
@@ -219,6 +319,116 @@ impl App {
                     self.messages.push(Message::system("Usage: /unalias <alias>"));
                 }
             }
+            "approve" | "deny" => {
+                self.handle_approve_command(command == "approve", arg);
+            }
+            "approvals" => {
+                self.handle_approvals_command(arg.filter(|a| !a.is_empty()));
+            }
+            "inbox" => {
+                self.show_inbox();
+            }
+            "improvements" => {
+                self.show_improvements();
+            }
+            "approvalmode" => {
+                self.handle_approval_mode(arg.filter(|a| !a.is_empty()));
+            }
+            #[cfg(feature = "agents")]
+            "undo" => {
+                self.handle_undo(arg);
+            }
+            #[cfg(feature = "agents")]
+            "budget" => {
+                self.handle_budget(arg);
+            }
+            #[cfg(feature = "agents")]
+            "checkpoint" => {
+                self.handle_checkpoint(arg);
+            }
+            #[cfg(feature = "agents")]
+            "rewind" => {
+                self.handle_rewind(arg);
+            }
+            #[cfg(feature = "agents")]
+            "plan" => {
+                self.handle_plan_command(arg);
+            }
+            #[cfg(feature = "agents")]
+            "fix" => {
+                self.handle_fix_command(arg);
+            }
+            #[cfg(feature = "agents")]
+            "knowledge" => {
+                self.handle_knowledge_command(arg);
+            }
+            "workspace" | "ws" => {
+                self.handle_workspace_command(arg);
+            }
+            "work" => {
+                self.show_work_graph(arg.filter(|a| !a.is_empty()));
+            }
+            "diff" => {
+                self.show_diff(arg.filter(|a| !a.is_empty()));
+            }
+            "logs" => {
+                self.show_logs(arg.filter(|a| !a.is_empty()));
+            }
+            "note" => {
+                self.add_session_note(arg);
+            }
+            "notes" => {
+                self.show_session_notes();
+            }
+            "run" => {
+                self.messages.push(Message::system("Usage: /run <name> [args] (see /snippets)"));
+            }
+            "snippets" => {
+                self.show_snippets();
+            }
+            "snippet" => {
+                self.handle_snippet_command(arg);
+            }
+            "retest" => {
+                self.retest_failing();
+            }
+            "host" => {
+                self.handle_host_command(arg);
+            }
+            "adapter" => {
+                self.handle_adapter_command(arg);
+            }
+            "view" => {
+                self.handle_view(arg);
+            }
+            "copy" => {
+                self.handle_copy(arg);
+            }
+            "keys" => {
+                self.list_keybindings();
+            }
+            "theme" => {
+                self.handle_theme_command(arg);
+            }
+            "search" => {
+                self.handle_search_command(arg);
+            }
+            "history" => {
+                self.show_history(arg.filter(|a| !a.is_empty()));
+            }
+            "record" => {
+                self.handle_record_command(arg);
+            }
+            "ack" => {
+                self.acknowledge_event(arg);
+            }
+            "snooze" => {
+                self.handle_snooze_command(arg);
+            }
+            #[cfg(feature = "agents")]
+            "missed" => {
+                self.report_missed_event(arg);
+            }
             _ => {
                 self.messages.push(Message::system(format!("Unknown command: /{}", command)));
             }
@@ -226,6 +436,21 @@ impl App {
         self.scroll_to_bottom();
     }
 
+    /// List every action with its key, for `/keys`.
+    fn list_keybindings(&mut self) {
+        self.messages.push(Message::system("=== Keybindings ([tui.keys] in config.toml) ==="));
+        for action in KeyAction::ALL {
+            let key = self.keybindings.label(action).unwrap_or_else(|| "(none)".to_string());
+            self.messages.push(Message::system(format!(
+                "  {:<11} {:<10} {}",
+                key,
+                action.name(),
+                action.description()
+            )));
+        }
+        self.messages.push(Message::system("  Ctrl+C      quit       Quit (cannot be rebound)"));
+    }
+
     /// List available projects.
     pub fn list_projects(&self) -> Vec<String> {
         self.store.load_all_projects()
@@ -303,9 +528,16 @@ impl App {
                     }
                 }
             }
-            None => {
-                self.messages.push(Message::system("No project specified. Use /status <project> or connect first."));
-            }
+            None => match self.workspace_projects() {
+                Some(projects) if !projects.is_empty() => {
+                    for project in projects {
+                        self.show_status(Some(&project.name));
+                    }
+                }
+                _ => {
+                    self.messages.push(Message::system("No project specified. Use /status <project> or connect first."));
+                }
+            },
         }
     }
 
@@ -339,7 +571,7 @@ impl App {
                 self.messages.push(Message::system("[ok] Built and started Telegram bot"));
             }
             Err(e) => {
-                self.messages.push(Message::system(format!("[warn] Could not start Telegram bot: {}", e)));
+                self.messages.push(Message::system(format!("[warn] Could not start Telegram bot: {}", e.user_message())));
                 self.messages.push(Message::system("  Start manually: cargo run -p commander-telegram"));
             }
         }
@@ -354,6 +586,13 @@ impl App {
                 self.messages.push(Message::system("Telegram Pairing Code"));
                 self.messages.push(Message::system(format!("  Code: {}", code)));
                 self.messages.push(Message::system(format!("  In Telegram: /pair {}", code)));
+                if let Some(bot) = commander_telegram::pairing::bot_username() {
+                    self.messages.push(Message::system(format!(
+                        "  Or open: {}",
+                        commander_telegram::pairing::pairing_link(&bot, &code)
+                    )));
+                }
+                self.messages.push(Message::system(format!("  In Matrix: !pair {}", code)));
                 self.messages.push(Message::system("  Expires in 5 minutes"));
                 if !project_name.is_empty() {
                     self.messages.push(Message::system(format!("  Auto-connects to: {}", project_name)));
@@ -372,6 +611,17 @@ impl App {
 
         match cmd {
             crate::repl::ReplCommand::Route { targets, message } => {
+                // @all broadcasts to every running session in the workspace
+                let targets = if targets.iter().any(|t| t == "all") {
+                    let targets = self.broadcast_targets();
+                    if targets.is_empty() {
+                        self.messages.push(Message::system("No running sessions to broadcast to"));
+                        return;
+                    }
+                    targets
+                } else {
+                    targets
+                };
                 if let Some(tmux) = &self.tmux {
                     let mut sent_count = 0;
                     let mut failed_targets = Vec::new();
@@ -1,15 +1,19 @@
 //! Tab completion for TUI slash commands.
 
 use super::App;
+use crate::filesystem;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
+use std::path::Path;
 use std::time::{Duration, SystemTime};
 
 /// Available slash commands for completion.
 pub const COMMANDS: &[&str] = &[
-    "/alias", "/clear", "/connect", "/disconnect", "/help", "/inspect",
-    "/list", "/quit", "/rename", "/send", "/sessions", "/status",
-    "/stop", "/telegram", "/unalias",
+    "/ack", "/adapter", "/alias", "/approvalmode", "/approvals", "/approve", "/budget", "/checkpoint", "/clear", "/connect", "/copy",
+    "/deny", "/diff", "/disconnect", "/fix", "/help", "/history", "/host", "/improvements", "/inbox", "/inspect", "/keys", "/knowledge", "/list", "/logs", "/missed", "/note", "/notes", "/pause", "/plan",
+    "/quit", "/record", "/rename", "/restore", "/resume", "/retest", "/rewind", "/run", "/search", "/send", "/sessions", "/snippet", "/snippets", "/snooze", "/status", "/stop", "/takeover",
+    "/telegram", "/templates", "/theme", "/unalias", "/undo", "/view", "/work",
+    "/workspace",
 ];
 
 impl App {
@@ -20,6 +24,7 @@ impl App {
     /// - Context-aware argument completion (project names, session names)
     /// - Flag/argument completion (-a cc|mpm)
     /// - Alias routing completion (@session_name)
+    /// - Path completion for filesystem commands, relative to the project root
     pub fn complete_command(&mut self) {
         // Build completions if not already built for this prefix
         if self.completions.is_empty() || self.completion_index.is_none() {
@@ -65,9 +70,30 @@ impl App {
             }
         }
 
+        if self.project.is_some() && input.contains(' ') {
+            return self.complete_fs_path(&input);
+        }
+
         Vec::new()
     }
 
+    /// Complete the path argument of a filesystem command (`cat src/ma`).
+    fn complete_fs_path(&self, input: &str) -> Vec<String> {
+        let command = input.split_whitespace().next().unwrap_or_default().to_lowercase();
+        if !filesystem::PATH_COMMANDS.contains(&command.as_str()) {
+            return Vec::new();
+        }
+        let Some(root) = self.project_path.as_ref() else {
+            return Vec::new();
+        };
+
+        let start = input.rfind(' ').map_or(0, |i| i + 1);
+        filesystem::complete_path(&input[start..], Path::new(root))
+            .into_iter()
+            .map(|path| format!("{}{}", &input[..start], path))
+            .collect()
+    }
+
     /// Complete command names using fuzzy matching.
     fn complete_command_name(&self, input: &str) -> Vec<String> {
         let matcher = SkimMatcherV2::default();
@@ -138,7 +164,7 @@ impl App {
                 }
                 self.complete_project_names_and_aliases(input)
             }
-            "/status" | "/s" => self.complete_project_names_and_aliases(input),
+            "/status" | "/s" | "/pause" | "/resume" => self.complete_project_names_and_aliases(input),
             "/alias" => {
                 // First argument: project name/alias, second: new alias name
                 if parts.len() == 2 {
@@ -321,7 +347,7 @@ impl App {
         let input = self.input.as_str();
 
         if input == "/connect" || input == "/c" {
-            Some("<path> -a <adapter> -n <name>  OR  <project-name>".to_string())
+            Some("<path> -a <adapter> -n <name>  OR  <project-name>  OR  Enter for the wizard".to_string())
         } else if input.starts_with("/connect ") || input.starts_with("/c ") {
             if !input.contains("-a") && !input.contains("-n") {
                 Some("[-a cc|mpm] [-n name]".to_string())
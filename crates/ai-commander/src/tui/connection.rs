@@ -3,16 +3,55 @@
 //! Contains methods for connecting to projects, disconnecting,
 //! and managing tmux sessions.
 
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use commander_core::CommanderError;
+
 use super::app::{App, Message};
+use crate::scaffold::{adapter_ready, scaffold_project};
+use crate::stop_commit;
 use crate::validate_project_path;
 
+/// How long to wait for a scaffolded project's adapter before dropping its prompt.
+const PROMPT_READY_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// Parsed connect command arguments.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub(super) enum ConnectArgs {
     /// Connect to existing project by name
     Existing(String),
+    /// Connect to existing project in read-only mode
+    ReadOnly(String),
     /// Create and connect to new project
     New { path: String, adapter: String, name: String },
+    /// Scaffold a project from a template, then connect
+    Template {
+        path: String,
+        template: String,
+        adapter: Option<String>,
+        name: Option<String>,
+    },
+}
+
+/// A prompt sent once the project's adapter is ready: a template's initial
+/// prompt or the instructions for a read-only session.
+#[derive(Debug)]
+pub(super) struct PendingPrompt {
+    project: String,
+    prompt: String,
+    since: Instant,
+}
+
+impl PendingPrompt {
+    /// Queue `prompt` for `project`'s adapter.
+    pub(super) fn new(project: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            project: project.into(),
+            prompt: prompt.into(),
+            since: Instant::now(),
+        }
+    }
 }
 
 impl App {
@@ -21,12 +60,21 @@ impl App {
     /// Fallback chain:
     /// 1. Try registered project (has adapter, path, etc.)
     /// 2. Try tmux session directly (if no project found)
-    pub fn connect(&mut self, name: &str) -> Result<(), String> {
+    pub fn connect(&mut self, name: &str) -> Result<(), CommanderError> {
+        self.connect_session(name, false)
+    }
+
+    /// Connect to a project by name in read-only mode.
+    pub fn connect_read_only(&mut self, name: &str) -> Result<(), CommanderError> {
+        self.connect_session(name, true)
+    }
+
+    fn connect_session(&mut self, name: &str, read_only: bool) -> Result<(), CommanderError> {
         let base_name = name;
 
         // Load all projects
         let projects = self.store.load_all_projects()
-            .map_err(|e| format!("Failed to load projects: {}", e))?;
+            .map_err(|e| CommanderError::Runtime(format!("Failed to load projects: {}", e)))?;
 
         // Try 1: Find registered project by name, ID, or alias
         let connected_via_alias = if projects.values()
@@ -43,13 +91,24 @@ impl App {
         if let Some(project) = projects.values()
             .find(|p| p.matches(base_name))
         {
-            // Validate project path still exists and is accessible
-            validate_project_path(&project.path)?;
+            // Validate project path still exists and is accessible (on this
+            // machine: the path of a project on an SSH host is remote)
+            let host = crate::project_host(project);
+            if host.is_none() {
+                validate_project_path(&project.path)?;
+            }
 
             let session_name = project.name.replace([' ', '.', '/', ':'], "-");
+            let read_only = read_only || self.is_read_only(&project.name);
+            let lock = if read_only { " 🔒" } else { "" };
+            let on_host = host.as_ref().map(|h| format!(" on {}", h)).unwrap_or_default();
 
             // Check if tmux session exists
             if let Some(ref tmux) = self.tmux {
+                if host.is_some() {
+                    tmux.set_session_host(&session_name, host.clone())
+                        .map_err(|e| CommanderError::Tmux(e.to_string()))?;
+                }
                 if tmux.session_exists(&session_name) {
                     self.sessions.insert(project.name.clone(), session_name.clone());
                     self.project = Some(project.name.clone());
@@ -60,11 +119,14 @@ impl App {
                         .unwrap_or(commander_core::Adapter::Unknown);
 
                     let connection_msg = if let Some(alias) = &connected_via_alias {
-                        format!("{} Connected to '{}' (alias: {})", adapter.indicator(), project.name, alias)
+                        format!("{} Connected to '{}'{}{} (alias: {})", adapter.indicator(), project.name, on_host, lock, alias)
                     } else {
-                        format!("{} Connected to '{}'", adapter.indicator(), project.name)
+                        format!("{} Connected to '{}'{}{}", adapter.indicator(), project.name, on_host, lock)
                     };
                     self.messages.push(Message::system(connection_msg));
+                    if read_only {
+                        self.enable_read_only(&project.name, &session_name);
+                    }
                     return Ok(());
                 }
 
@@ -74,21 +136,29 @@ impl App {
                     .unwrap_or("claude-code");
 
                 if let Some(adapter) = self.registry.get(tool_id) {
-                    let (cmd, cmd_args) = adapter.launch_command(&project.path);
+                    let (cmd, cmd_args) = if read_only {
+                        adapter.read_only_launch_command(&project.path)
+                    } else {
+                        adapter.launch_command(&project.path)
+                    };
                     let full_cmd = if cmd_args.is_empty() {
                         cmd
                     } else {
                         format!("{} {}", cmd, cmd_args.join(" "))
                     };
 
-                    // Create tmux session in project directory
-                    if let Err(e) = tmux.create_session_in_dir(&session_name, Some(&project.path)) {
-                        return Err(format!("Failed to create tmux session: {}", e));
+                    // Create tmux session in project directory, with its environment
+                    let (env, env_warning) = crate::project_session_env(&project.path);
+                    if let Some(warning) = env_warning {
+                        self.messages.push(Message::system(warning));
+                    }
+                    if let Err(e) = tmux.create_session_with_env(&session_name, Some(&project.path), &env) {
+                        return Err(CommanderError::Tmux(format!("Failed to create tmux session: {}", e)));
                     }
 
                     // Send launch command
                     if let Err(e) = tmux.send_line(&session_name, None, &full_cmd) {
-                        return Err(format!("Failed to start adapter: {}", e));
+                        return Err(CommanderError::Tmux(format!("Failed to start adapter: {}", e)));
                     }
 
                     self.sessions.insert(project.name.clone(), session_name.clone());
@@ -96,16 +166,19 @@ impl App {
                     self.project_path = Some(project.path.clone());
                     // New session likely Claude adapter (just started an adapter)
                     let connection_msg = if let Some(alias) = &connected_via_alias {
-                        format!("[Claude] Started and connected to '{}' (alias: {})", project.name, alias)
+                        format!("[Claude] Started and connected to '{}'{}{} (alias: {})", project.name, on_host, lock, alias)
                     } else {
-                        format!("[Claude] Started and connected to '{}'", project.name)
+                        format!("[Claude] Started and connected to '{}'{}{}", project.name, on_host, lock)
                     };
                     self.messages.push(Message::system(connection_msg));
+                    if read_only {
+                        self.enable_read_only(&project.name, &session_name);
+                    }
                     return Ok(());
                 }
             }
 
-            return Err("Tmux not available".to_string());
+            return Err(CommanderError::Tmux("Tmux not available".to_string()));
         }
 
         // Try 2: Check for tmux session directly (unregistered session)
@@ -132,12 +205,15 @@ impl App {
                     self.messages.push(Message::system(
                         format!("{} Connected to '{}'", adapter.indicator(), display_name)
                     ));
+                    if read_only || self.is_read_only(display_name) {
+                        self.enable_read_only(display_name, &session_name);
+                    }
                     return Ok(());
                 }
             }
         }
 
-        Err(format!("No project or session found: {}", name))
+        Err(CommanderError::Runtime(format!("No project or session found: {}", name)))
     }
 
     /// Parse connect command arguments.
@@ -148,55 +224,103 @@ impl App {
             return Err("connect requires arguments".to_string());
         }
 
-        // Check if this has -a or -n flags (new project syntax)
-        if parts.iter().any(|&p| p == "-a" || p == "-n") {
-            let path = shellexpand::tilde(parts[0]).to_string();
-            let mut adapter = None;
-            let mut name = None;
-
-            let mut i = 1;
-            while i < parts.len() {
-                match parts[i] {
-                    "-a" => {
-                        if i + 1 < parts.len() {
-                            adapter = Some(parts[i + 1].to_string());
-                            i += 2;
-                        } else {
-                            return Err("-a requires an adapter (cc, mpm)".to_string());
-                        }
+        let mut target = None;
+        let mut adapter = None;
+        let mut name = None;
+        let mut template = None;
+        let mut read_only = false;
+
+        let mut i = 0;
+        while i < parts.len() {
+            match parts[i] {
+                "-a" => {
+                    if i + 1 < parts.len() {
+                        adapter = Some(parts[i + 1].to_string());
+                        i += 2;
+                    } else {
+                        return Err("-a requires an adapter (cc, mpm)".to_string());
                     }
-                    "-n" => {
-                        if i + 1 < parts.len() {
-                            name = Some(parts[i + 1].to_string());
-                            i += 2;
-                        } else {
-                            return Err("-n requires a project name".to_string());
-                        }
+                }
+                "-n" => {
+                    if i + 1 < parts.len() {
+                        name = Some(parts[i + 1].to_string());
+                        i += 2;
+                    } else {
+                        return Err("-n requires a project name".to_string());
                     }
-                    _ => {
-                        return Err(format!("unknown flag: {}", parts[i]));
+                }
+                "-t" | "--template" => {
+                    if i + 1 < parts.len() {
+                        template = Some(parts[i + 1].to_string());
+                        i += 2;
+                    } else {
+                        return Err("--template requires a template name (see /templates)".to_string());
                     }
                 }
+                "--read-only" => {
+                    read_only = true;
+                    i += 1;
+                }
+                flag if flag.starts_with('-') => {
+                    return Err(format!("unknown flag: {}", flag));
+                }
+                value if target.is_none() => {
+                    target = Some(value.to_string());
+                    i += 1;
+                }
+                _ => {
+                    return Err("use '/connect <name>' or '/connect <path> -a <adapter> -n <name>'".to_string());
+                }
             }
+        }
 
-            match (adapter, name) {
-                (Some(a), Some(n)) => Ok(ConnectArgs::New { path, adapter: a, name: n }),
-                (None, _) => Err("missing -a <adapter> (cc, mpm)".to_string()),
-                (_, None) => Err("missing -n <name>".to_string()),
+        if let Some(template) = template {
+            if read_only {
+                return Err("--read-only cannot be used with --template".to_string());
             }
-        } else if parts.len() == 1 {
+            let path = target.ok_or("--template requires a path: /connect <path> --template <name>")?;
+            return Ok(ConnectArgs::Template {
+                path: shellexpand::tilde(&path).to_string(),
+                template,
+                adapter,
+                name,
+            });
+        }
+
+        let Some(target) = target else {
+            return Err("missing project name or path".to_string());
+        };
+        if adapter.is_none() && name.is_none() {
             // Existing project by name
-            Ok(ConnectArgs::Existing(parts[0].to_string()))
-        } else {
-            Err("use '/connect <name>' or '/connect <path> -a <adapter> -n <name>'".to_string())
+            return Ok(if read_only {
+                ConnectArgs::ReadOnly(target)
+            } else {
+                ConnectArgs::Existing(target)
+            });
+        }
+        if read_only {
+            return Err("--read-only connects to an existing project: /connect <name> --read-only".to_string());
+        }
+
+        let path = shellexpand::tilde(&target).to_string();
+        match (adapter, name) {
+            (Some(a), Some(n)) => Ok(ConnectArgs::New { path, adapter: a, name: n }),
+            (None, _) => Err("missing -a <adapter> (cc, mpm)".to_string()),
+            (_, None) => Err("missing -n <name>".to_string()),
         }
     }
 
     /// Connect to a new project (create and start).
-    pub fn connect_new(&mut self, path: &str, adapter: &str, name: &str) -> Result<(), String> {
+    pub fn connect_new(&mut self, path: &str, adapter: &str, name: &str) -> Result<(), CommanderError> {
+        self.create_project(path, adapter, name)?;
+        self.connect(name)
+    }
+
+    /// Register a new project using `adapter` without starting it.
+    pub(super) fn create_project(&mut self, path: &str, adapter: &str, name: &str) -> Result<(), CommanderError> {
         // Resolve adapter alias
         let tool_id = self.registry.resolve(adapter)
-            .ok_or_else(|| format!("Unknown adapter: {}. Use: cc (claude-code), mpm, mpm-sdk", adapter))?
+            .ok_or_else(|| CommanderError::Config(format!("Unknown adapter: {}. Use: cc (claude-code), mpm, mpm-sdk", adapter)))?
             .to_string();
 
         // Validate project path exists and is accessible
@@ -204,10 +328,10 @@ impl App {
 
         // Check if project already exists
         let projects = self.store.load_all_projects()
-            .map_err(|e| format!("Failed to load projects: {}", e))?;
+            .map_err(|e| CommanderError::Runtime(format!("Failed to load projects: {}", e)))?;
 
         if projects.values().any(|p| p.name == name) {
-            return Err(format!("Project '{}' already exists. Use /connect {}", name, name));
+            return Err(CommanderError::Runtime(format!("Project '{}' already exists. Use /connect {}", name, name)));
         }
 
         // Create project
@@ -216,10 +340,104 @@ impl App {
 
         // Save project
         self.store.save_project(&project)
-            .map_err(|e| format!("Failed to save project: {}", e))?;
+            .map_err(|e| CommanderError::Runtime(format!("Failed to save project: {}", e)))
+    }
 
-        // Connect to the new project
-        self.connect(name)
+    /// Scaffold a project from a template, then start and connect to it.
+    ///
+    /// The template's initial prompt is sent once the adapter is ready.
+    pub fn connect_template(
+        &mut self,
+        path: &str,
+        template: &str,
+        adapter: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<(), CommanderError> {
+        let scaffolded = scaffold_project(&self.store, &self.registry, template, Path::new(path), adapter, name)
+            .map_err(CommanderError::Runtime)?;
+        let project_name = scaffolded.project.name.clone();
+        self.messages.push(Message::system(format!(
+            "Created '{}' from template '{}' ({} files)",
+            project_name, template, scaffolded.files_created
+        )));
+
+        self.connect(&project_name)?;
+
+        if let Some(prompt) = scaffolded.prompt {
+            self.messages.push(Message::system("Initial prompt will be sent when the adapter is ready"));
+            self.pending_prompts.push(PendingPrompt::new(project_name, prompt));
+        }
+        Ok(())
+    }
+
+    /// Send pending prompts once their sessions' adapters are ready.
+    ///
+    /// Called on every event loop tick.
+    pub fn deliver_pending_prompts(&mut self) {
+        if self.pending_prompts.is_empty() {
+            return;
+        }
+
+        let mut ready = Vec::new();
+        for pending in std::mem::take(&mut self.pending_prompts) {
+            if pending.since.elapsed() > PROMPT_READY_TIMEOUT {
+                self.messages.push(Message::system(format!(
+                    "Adapter for '{}' did not become ready; prompt not sent",
+                    pending.project
+                )));
+            } else if self.adapter_ready_for(&pending.project) {
+                ready.push(pending);
+            } else {
+                self.pending_prompts.push(pending);
+            }
+        }
+
+        for pending in ready {
+            let result = if self.project.as_deref() == Some(pending.project.as_str()) {
+                self.send_message(&pending.prompt)
+            } else {
+                let session = self.sessions.get(&pending.project).cloned().unwrap_or_default();
+                self.tmux
+                    .as_ref()
+                    .map(|tmux| {
+                        tmux.send_line(&session, None, &pending.prompt)
+                            .map_err(|e| CommanderError::Tmux(e.to_string()))
+                    })
+                    .unwrap_or_else(|| Err(CommanderError::Tmux("Tmux not available".to_string())))
+            };
+            if let Err(e) = result {
+                self.messages.push(Message::system(format!(
+                    "Failed to send prompt to '{}': {}",
+                    pending.project, e
+                )));
+            }
+        }
+    }
+
+    /// Whether the adapter in `project`'s session is ready for a prompt.
+    fn adapter_ready_for(&self, project: &str) -> bool {
+        let (Some(tmux), Some(session)) = (&self.tmux, self.sessions.get(project)) else {
+            return false;
+        };
+        let Ok(output) = tmux.capture_output(session, None, Some(50)) else {
+            return false;
+        };
+        let tool_id = self.project_tool_id(project);
+        adapter_ready(self.registry.get(&tool_id).as_deref(), &output)
+    }
+
+    /// Adapter tool id of `project`, defaulting to Claude Code.
+    pub(super) fn project_tool_id(&self, project: &str) -> String {
+        self.store
+            .load_all_projects()
+            .ok()
+            .and_then(|projects| {
+                projects
+                    .values()
+                    .find(|p| p.name == project)
+                    .and_then(|p| p.config.get("tool").and_then(|v| v.as_str()).map(String::from))
+            })
+            .unwrap_or_else(|| "claude-code".to_string())
     }
 
     /// Disconnect from current project.
@@ -317,7 +535,11 @@ impl App {
     }
 
     /// Stop a session: commit git changes and destroy tmux session.
-    pub fn stop_session(&mut self, name: &str) {
+    ///
+    /// With `[stop] ai_commit` (unless `no_ai_commit`), the commit message is
+    /// written from the diff and transcript, and the branch is pushed with a
+    /// pull request.
+    pub fn stop_session(&mut self, name: &str, no_ai_commit: bool) {
         let session_name = name.replace([' ', '.', '/', ':'], "-");
 
         // Find project path for git operations
@@ -335,8 +557,39 @@ impl App {
         if let Some(path) = &project_path {
             self.messages.push(Message::system(format!("Checking for uncommitted changes in {}...", path)));
 
-            match self.git_commit_changes(path, name) {
-                Ok(Some(true)) => self.messages.push(Message::system("Changes committed.")),
+            let settings = stop_commit::stop_settings(no_ai_commit);
+            let summary = if settings.ai_commit && Self::is_git_worktree(path) {
+                self.messages.push(Message::system("Writing commit message and pull request..."));
+                match stop_commit::summarize_changes(path, name, &session_name, settings.model.as_deref()) {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        self.messages.push(Message::system(format!("{}; using a WIP commit.", e)));
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let message = summary
+                .as_ref()
+                .map(|s| s.commit_message.clone())
+                .unwrap_or_else(|| stop_commit::wip_message(name));
+
+            match self.git_commit_changes(path, &message) {
+                Ok(Some(true)) => {
+                    self.messages.push(Message::system("Changes committed."));
+                    if let Some(summary) = summary {
+                        self.messages.push(Message::system(format!("  {}", summary.title)));
+                        if settings.push {
+                            match stop_commit::publish(path, &summary) {
+                                Ok(published) => self.messages.push(Message::system(
+                                    stop_commit::describe_published(&published),
+                                )),
+                                Err(e) => self.messages.push(Message::system(format!("Not pushed: {}", e))),
+                            }
+                        }
+                    }
+                }
                 Ok(Some(false)) => self.messages.push(Message::system("No changes to commit.")),
                 Ok(None) => self.messages.push(Message::system("Not a git repository, skipping commit.")),
                 Err(e) => self.messages.push(Message::system(format!("Git warning: {}", e))),
@@ -351,6 +604,11 @@ impl App {
 
                     // Remove from tracking
                     self.sessions.remove(name);
+                    self.disable_read_only(name, &session_name);
+                    self.forget_snapshot(name);
+                    if self.paused_sessions.remove(&session_name) {
+                        let _ = commander_core::pause::mark_resumed(&session_name);
+                    }
 
                     // Disconnect if it was current
                     if self.project.as_deref() == Some(name) {
@@ -367,3 +625,64 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> (tempfile::TempDir, App) {
+        let dir = tempfile::tempdir().unwrap();
+        let app = App::new(dir.path());
+        (dir, app)
+    }
+
+    #[test]
+    fn test_parse_connect_args() {
+        let (_dir, app) = app();
+
+        assert_eq!(
+            app.parse_connect_args("myapp").unwrap(),
+            ConnectArgs::Existing("myapp".to_string())
+        );
+        assert_eq!(
+            app.parse_connect_args("/tmp/app -a cc -n app").unwrap(),
+            ConnectArgs::New {
+                path: "/tmp/app".to_string(),
+                adapter: "cc".to_string(),
+                name: "app".to_string(),
+            }
+        );
+        assert!(app.parse_connect_args("/tmp/app -a cc").is_err());
+        assert_eq!(
+            app.parse_connect_args("myapp --read-only").unwrap(),
+            ConnectArgs::ReadOnly("myapp".to_string())
+        );
+        assert!(app.parse_connect_args("/tmp/app -a cc -n app --read-only").is_err());
+        assert!(app.parse_connect_args("one two").is_err());
+    }
+
+    #[test]
+    fn test_parse_connect_template_args() {
+        let (_dir, app) = app();
+
+        assert_eq!(
+            app.parse_connect_args("/tmp/tool --template rust-cli").unwrap(),
+            ConnectArgs::Template {
+                path: "/tmp/tool".to_string(),
+                template: "rust-cli".to_string(),
+                adapter: None,
+                name: None,
+            }
+        );
+        assert_eq!(
+            app.parse_connect_args("-t node-ts /tmp/web -n web").unwrap(),
+            ConnectArgs::Template {
+                path: "/tmp/web".to_string(),
+                template: "node-ts".to_string(),
+                adapter: None,
+                name: Some("web".to_string()),
+            }
+        );
+        assert!(app.parse_connect_args("--template rust-cli").is_err());
+    }
+}
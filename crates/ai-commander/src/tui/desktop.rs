@@ -0,0 +1,150 @@
+//! Desktop notifications for sessions that need attention.
+//!
+//! While the terminal is unfocused, significant session changes (errors,
+//! completions, input prompts), sessions becoming ready and blocking-event
+//! escalations are posted as native desktop notifications. Which projects and
+//! significance levels notify is configured under `[notifications]` in
+//! `config.toml`. During do-not-disturb they are held and shown as one digest
+//! when it ends.
+
+use std::time::{Duration, Instant};
+
+use commander_core::dnd::{self, DESKTOP_CHANNEL};
+use commander_core::{ChangeType, DesktopNotification, Significance};
+
+use super::app::{App, Message};
+
+/// Minimum time between notifications for the same project, unless critical.
+const NOTIFY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// How often to check whether do-not-disturb ended with notifications held.
+const DND_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+impl App {
+    /// Run change detection on fresh output of session `name` and notify on
+    /// significant changes.
+    pub(super) fn detect_desktop_changes(&mut self, name: &str, output: &str) {
+        let first_observation = !self.change_detectors.contains_key(name);
+        let change = self.change_detectors.entry(name.to_string()).or_default().detect(output);
+
+        // The first capture is the whole screen, not a change
+        if first_observation || matches!(change.change_type, ChangeType::None) {
+            return;
+        }
+        self.notify_desktop(name, change.significance, &change.summary);
+    }
+
+    /// Post a desktop notification about `project` if the terminal is
+    /// unfocused and `[notifications]` allows it.
+    pub(super) fn notify_desktop(&mut self, project: &str, significance: Significance, detail: &str) {
+        if self.terminal_focused || !self.notification_settings.should_notify(project, significance) {
+            return;
+        }
+
+        let now = Instant::now();
+        let cooling_down = self
+            .last_desktop_notification
+            .get(project)
+            .is_some_and(|last| now.duration_since(*last) < NOTIFY_COOLDOWN);
+        if cooling_down && significance < Significance::Critical {
+            return;
+        }
+        self.last_desktop_notification.insert(project.to_string(), now);
+
+        let critical = significance == Significance::Critical;
+        if !dnd::should_deliver(&self.notification_settings.dnd, DESKTOP_CHANNEL, critical) {
+            if let Err(e) = dnd::hold(DESKTOP_CHANNEL, detail, Some(project.to_string())) {
+                tracing::warn!(error = %e, "Failed to hold desktop notification");
+            }
+            return;
+        }
+
+        let return_hint = if self.project.as_deref() == Some(project) {
+            "Switch back to the Commander terminal".to_string()
+        } else {
+            format!("In Commander: /connect {}", project)
+        };
+        DesktopNotification::new(format!("Commander: {}", project), detail)
+            .with_subtitle(return_hint)
+            .send_in_background();
+    }
+
+    /// Once do-not-disturb ends, show the desktop notifications held during
+    /// it: as one notification while the terminal is unfocused, otherwise in
+    /// the output.
+    pub(super) fn check_dnd_digest(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_dnd_check
+            .is_some_and(|last| now.duration_since(last) < DND_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_dnd_check = Some(now);
+
+        if dnd::is_quiet(&self.notification_settings.dnd, DESKTOP_CHANNEL) {
+            return;
+        }
+        let held = match dnd::take_held(DESKTOP_CHANNEL) {
+            Ok(held) if !held.is_empty() => held,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to take held desktop notifications");
+                return;
+            }
+        };
+
+        let digest = dnd::format_digest(&held);
+        if self.terminal_focused {
+            for line in digest.lines() {
+                self.messages.push(Message::system(line));
+            }
+            self.scroll_to_bottom();
+        } else {
+            let (title, body) = digest.split_once('\n').unwrap_or((&digest, ""));
+            DesktopNotification::new("Commander", body).with_subtitle(title).send_in_background();
+        }
+    }
+
+    /// `/snooze [duration|off]`: hold Telegram pushes and desktop
+    /// notifications for a while, end the snooze, or show it.
+    pub(super) fn handle_snooze_command(&mut self, arg: Option<&str>) {
+        let text = match arg.unwrap_or("").trim() {
+            "" => match dnd::snoozed_until() {
+                Some(until) => format!(
+                    "Notifications snoozed until {} (/snooze off to end)",
+                    until.with_timezone(&chrono::Local).format("%a %H:%M")
+                ),
+                None => "Notifications are not snoozed (/snooze 2h holds them for two hours)".to_string(),
+            },
+            "off" | "end" | "stop" => match dnd::end_snooze() {
+                Ok(true) => "Snooze ended; notifications held meanwhile follow as a digest".to_string(),
+                Ok(false) => "Notifications were not snoozed".to_string(),
+                Err(e) => {
+                    self.messages.push(Message::error(&e.into()));
+                    return;
+                }
+            },
+            duration => match dnd::parse_duration(duration) {
+                Some(duration) => match dnd::snooze(duration) {
+                    Ok(until) => {
+                        let mut text = format!(
+                            "Notifications snoozed until {}",
+                            until.with_timezone(&chrono::Local).format("%a %H:%M")
+                        );
+                        if self.notification_settings.dnd.critical_breaks_through {
+                            text.push_str("; critical alerts still come through");
+                        }
+                        text
+                    }
+                    Err(e) => {
+                        self.messages.push(Message::error(&e.into()));
+                        return;
+                    }
+                },
+                None => "Usage: /snooze 2h (or 30m, 1h30m, 1d), /snooze off".to_string(),
+            },
+        };
+        self.messages.push(Message::system(text));
+    }
+}
@@ -0,0 +1,111 @@
+//! Error diagnosis cards (feature-gated).
+//!
+//! When the Session Agent diagnoses an error in a response, the diagnosis is
+//! shown as a card after the response. `/fix apply` (or clicking the apply
+//! line) sends its fix prompt to the session; `/fix ignore` dismisses it.
+
+use super::app::{App, Message};
+
+/// Clickable card line that applies the suggestion.
+pub(super) const APPLY_LINE: &str = "  ▶ Apply suggestion (/fix apply)";
+
+/// Clickable card line that dismisses the suggestion.
+pub(super) const IGNORE_LINE: &str = "  ✕ Ignore (/fix ignore)";
+
+impl App {
+    /// Show the card for a diagnosis made while analyzing the last response.
+    pub(super) fn show_diagnosis_card(&mut self) {
+        let Some(diagnosis) = self.diagnosis_card.take() else {
+            return;
+        };
+        self.messages.push(Message::system(format!("Diagnosis for {}:", diagnosis.session_id)));
+        for line in diagnosis.card_lines() {
+            self.messages.push(Message::system(format!("  {}", line)));
+        }
+        self.messages.push(Message::system(APPLY_LINE));
+        self.messages.push(Message::system(IGNORE_LINE));
+        self.pending_diagnosis = Some(diagnosis);
+    }
+
+    /// Handle `/fix [apply|ignore]`.
+    pub fn handle_fix_command(&mut self, arg: Option<&str>) {
+        match arg.map(str::trim).filter(|a| !a.is_empty()) {
+            None => match &self.pending_diagnosis {
+                Some(diagnosis) => {
+                    for line in diagnosis.card_lines() {
+                        self.messages.push(Message::system(line));
+                    }
+                    self.messages.push(Message::system("Apply it with /fix apply, or dismiss it with /fix ignore"));
+                }
+                None => self.messages.push(Message::system("No suggested fix pending")),
+            },
+            Some("apply") => self.apply_diagnosis(),
+            Some("ignore") => self.ignore_diagnosis(),
+            Some(other) => self
+                .messages
+                .push(Message::system(format!("Unknown /fix action '{}'. Usage: /fix [apply|ignore]", other))),
+        }
+        self.scroll_to_bottom();
+    }
+
+    /// Send the pending diagnosis's fix prompt to its session.
+    pub(super) fn apply_diagnosis(&mut self) {
+        let Some(diagnosis) = self.pending_diagnosis.take() else {
+            self.messages.push(Message::system("No suggested fix pending"));
+            return;
+        };
+        if self.current_session_name().as_deref() != Some(diagnosis.session_id.as_str()) {
+            self.messages.push(Message::system(format!(
+                "Not connected to {} anymore; fix not sent",
+                diagnosis.session_id
+            )));
+            return;
+        }
+        if let Err(e) = self.send_message(&diagnosis.fix_prompt) {
+            self.messages.push(Message::error(&e));
+            self.pending_diagnosis = Some(diagnosis);
+        }
+    }
+
+    /// Dismiss the pending diagnosis.
+    pub(super) fn ignore_diagnosis(&mut self) {
+        match self.pending_diagnosis.take() {
+            Some(diagnosis) => self
+                .messages
+                .push(Message::system(format!("Ignored suggested fix for: {}", diagnosis.error))),
+            None => self.messages.push(Message::system("No suggested fix pending")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_core::diagnosis::DiagnosisReply;
+    use commander_core::ErrorDiagnosis;
+
+    #[test]
+    fn test_diagnosis_card_and_ignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        let reply = DiagnosisReply {
+            cause: "x is undefined".into(),
+            suggestion: "Declare x".into(),
+            fix_prompt: "Declare x before use".into(),
+        };
+        app.diagnosis_card = Some(ErrorDiagnosis::new("commander-api", "error: x", reply, Vec::new()));
+
+        app.show_diagnosis_card();
+        assert!(app.diagnosis_card.is_none());
+        assert!(app.pending_diagnosis.is_some());
+        assert!(app.messages.iter().any(|m| m.content == APPLY_LINE));
+
+        // Not connected to the session: the fix is not sent
+        app.handle_fix_command(Some("apply"));
+        assert!(app.pending_diagnosis.is_none());
+        assert!(app.pending_query.is_none());
+
+        app.handle_fix_command(Some("ignore"));
+        assert_eq!(app.messages.last().unwrap().content, "No suggested fix pending");
+    }
+}
@@ -0,0 +1,135 @@
+//! Diff view showing `git diff` for the connected project.
+//!
+//! Opened with `/diff [file]` or by clicking the header's changed-file
+//! count or a changed file listed in a response summary.
+
+use std::path::Path;
+
+use super::app::{App, Message, ViewMode};
+
+/// Prefix of changed-file lines in response summaries; such lines link
+/// to the diff view.
+pub(super) const CHANGED_FILE_PREFIX: &str = "  ~ ";
+
+impl App {
+    /// Open the diff view for the connected project, optionally for one file.
+    pub fn show_diff(&mut self, file: Option<&str>) {
+        let Some(path) = self.project_path.clone() else {
+            self.messages.push(Message::system("Connect to a project first"));
+            return;
+        };
+
+        match commander_core::git_diff(Path::new(&path), file) {
+            Ok(diff) if diff.trim().is_empty() => {
+                let target = file.map(|f| format!(" in {}", f)).unwrap_or_default();
+                self.messages.push(Message::system(format!("No uncommitted changes{}", target)));
+            }
+            Ok(diff) => {
+                self.diff_content = diff;
+                self.diff_file = file.map(str::to_string);
+                self.diff_scroll = 0;
+                self.view_mode = ViewMode::Diff;
+            }
+            Err(e) => self.messages.push(Message::system(format!("Error: {}", e))),
+        }
+    }
+
+    /// Leave the diff view.
+    pub fn close_diff(&mut self) {
+        if self.view_mode == ViewMode::Diff {
+            self.view_mode = ViewMode::Normal;
+        }
+    }
+
+    /// Scroll up in diff mode.
+    pub fn diff_scroll_up(&mut self, lines: usize) {
+        self.diff_scroll = self.diff_scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down in diff mode.
+    pub fn diff_scroll_down(&mut self, lines: usize) {
+        let max_scroll = self.diff_content.lines().count().saturating_sub(1);
+        self.diff_scroll = self.diff_scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// The file named by a changed-file summary line, if `line` is one.
+pub(super) fn changed_file_in(line: &str) -> Option<&str> {
+    line.strip_prefix(CHANGED_FILE_PREFIX)
+        .map(str::trim)
+        .filter(|path| !path.is_empty() && !path.contains(char::is_whitespace))
+}
+
+/// Comment prefix and keywords for the language of `path`, used to
+/// highlight code lines in the diff view.
+pub(super) fn syntax_for(path: &str) -> (&'static str, &'static [&'static str]) {
+    let ext = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    match ext {
+        "rs" => ("//", &[
+            "fn", "let", "mut", "pub", "impl", "struct", "enum", "trait", "use", "mod", "match",
+            "if", "else", "for", "while", "loop", "return", "self", "Self", "async", "await",
+            "const", "static", "where", "crate", "super",
+        ]),
+        "py" => ("#", &[
+            "def", "class", "import", "from", "return", "if", "elif", "else", "for", "while",
+            "with", "as", "try", "except", "finally", "async", "await", "lambda", "None", "self",
+        ]),
+        "js" | "jsx" | "ts" | "tsx" => ("//", &[
+            "function", "const", "let", "var", "return", "if", "else", "for", "while", "class",
+            "import", "export", "from", "async", "await", "new", "this", "interface", "type",
+        ]),
+        "go" => ("//", &[
+            "func", "package", "import", "var", "const", "type", "struct", "interface", "return",
+            "if", "else", "for", "range", "go", "defer", "chan", "select",
+        ]),
+        "sh" | "bash" | "zsh" => ("#", &[
+            "if", "then", "else", "fi", "for", "do", "done", "case", "esac", "function", "local",
+            "export",
+        ]),
+        "toml" | "yaml" | "yml" => ("#", &[]),
+        _ => ("", &[]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_scroll_bounds() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.diff_content = "a\nb\nc\nd\n".to_string();
+
+        app.diff_scroll_down(2);
+        assert_eq!(app.diff_scroll, 2);
+        app.diff_scroll_down(10);
+        assert_eq!(app.diff_scroll, 3);
+        app.diff_scroll_up(10);
+        assert_eq!(app.diff_scroll, 0);
+    }
+
+    #[test]
+    fn test_show_diff_requires_project() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.show_diff(None);
+        assert_eq!(app.view_mode, ViewMode::Normal);
+        assert!(app.messages.last().unwrap().content.contains("Connect to a project"));
+    }
+
+    #[test]
+    fn test_changed_file_in() {
+        assert_eq!(changed_file_in("  ~ src/main.rs"), Some("src/main.rs"));
+        assert_eq!(changed_file_in("  ~ "), None);
+        assert_eq!(changed_file_in("src/main.rs"), None);
+    }
+
+    #[test]
+    fn test_syntax_for() {
+        assert_eq!(syntax_for("src/main.rs").0, "//");
+        assert!(syntax_for("a.py").1.contains(&"def"));
+        assert_eq!(syntax_for("README"), ("", &[][..]));
+    }
+}
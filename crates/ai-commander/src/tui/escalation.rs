@@ -0,0 +1,138 @@
+//! Blocking-event escalations and `/ack` for the TUI.
+//!
+//! The daemon escalates blocking events nobody acknowledged; escalations
+//! addressed to the TUI are read from the shared escalation state and shown
+//! as a modal until acknowledged or dismissed.
+
+use std::time::{Duration, Instant};
+
+use commander_events::{short_event_id, EscalationChannel, EscalationState, EventManager};
+use commander_core::Significance;
+use commander_persistence::EventStore;
+
+use super::app::{App, Message};
+
+/// Minimum time between escalation state checks.
+const ESCALATION_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Event manager over the shared event store, with every project loaded.
+pub(super) fn shared_events() -> Result<EventManager, String> {
+    let manager = EventManager::new(EventStore::new(commander_core::config::state_dir()));
+    manager.load_all().map_err(|e| e.to_string())?;
+    Ok(manager)
+}
+
+impl App {
+    /// Show the next unseen TUI escalation as a modal.
+    ///
+    /// Called on every event loop tick; rate limited to
+    /// `ESCALATION_CHECK_INTERVAL`. Closes the modal when its event was
+    /// acknowledged elsewhere.
+    pub fn check_escalations(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_escalation_check
+            .is_some_and(|last| now.duration_since(last) < ESCALATION_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_escalation_check = Some(now);
+
+        let state = EscalationState::load(&commander_core::escalations_file());
+        if let Some(modal) = &self.escalation_modal {
+            if !state.escalations.contains_key(&modal.event_id) {
+                self.escalation_modal = None;
+            }
+        }
+        if self.escalation_modal.is_some() {
+            return;
+        }
+
+        let next = state
+            .for_channel(EscalationChannel::Tui)
+            .find(|e| !self.seen_escalations.contains(&(e.event_id.clone(), e.attempt)))
+            .cloned();
+        if let Some(escalation) = next {
+            self.seen_escalations
+                .insert((escalation.event_id.clone(), escalation.attempt));
+            let detail = format!(
+                "{}: {} (/ack {})",
+                escalation.label(),
+                escalation.title,
+                escalation.short_id()
+            );
+            self.notify_desktop(&escalation.project_id, Significance::Critical, &detail);
+            self.escalation_modal = Some(escalation);
+        }
+    }
+
+    /// Acknowledge the event shown in the escalation modal.
+    pub fn acknowledge_modal(&mut self) {
+        if let Some(escalation) = self.escalation_modal.take() {
+            self.acknowledge_event(Some(&escalation.event_id));
+        }
+    }
+
+    /// Close the escalation modal without acknowledging; the event keeps
+    /// escalating.
+    pub fn dismiss_modal(&mut self) {
+        if let Some(escalation) = self.escalation_modal.take() {
+            self.messages.push(Message::system(format!(
+                "Dismissed. Acknowledge later with /ack {}",
+                escalation.short_id()
+            )));
+        }
+    }
+
+    /// Handle `/ack [event-id]`: acknowledge an event, or list pending
+    /// blocking events when no ID is given.
+    pub(super) fn acknowledge_event(&mut self, id: Option<&str>) {
+        let manager = match shared_events() {
+            Ok(manager) => manager,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Failed to load events: {}", e)));
+                return;
+            }
+        };
+
+        let Some(id) = id.filter(|id| !id.is_empty()) else {
+            let blocking: Vec<_> = manager.list(None).into_iter().filter(|e| e.is_blocking()).collect();
+            if blocking.is_empty() {
+                self.messages.push(Message::system("No pending blocking events"));
+            } else {
+                self.messages.push(Message::system("Pending blocking events:"));
+                for event in blocking {
+                    self.messages.push(Message::system(format!(
+                        "  {}  [{}] {}",
+                        short_event_id(event.id.as_str()),
+                        event.project_id,
+                        event.title
+                    )));
+                }
+                self.messages.push(Message::system("Use /ack <event-id> to acknowledge"));
+            }
+            return;
+        };
+
+        let result = manager
+            .find_by_prefix(id)
+            .and_then(|event| manager.acknowledge(&event.id).map(|_| event));
+        match result {
+            Ok(event) => {
+                if self
+                    .escalation_modal
+                    .as_ref()
+                    .is_some_and(|m| m.event_id == event.id.as_str())
+                {
+                    self.escalation_modal = None;
+                }
+                self.messages.push(Message::system(format!(
+                    "Acknowledged {}: {}",
+                    short_event_id(event.id.as_str()),
+                    event.title
+                )));
+            }
+            Err(e) => self.messages.push(Message::system(format!("Cannot acknowledge '{}': {}", id, e))),
+        }
+    }
+}
@@ -7,8 +7,8 @@ use std::time::Duration;
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
-        MouseButton, MouseEventKind,
+        self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+        KeyCode, KeyEventKind, KeyModifiers,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -18,8 +18,13 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 #[cfg(feature = "agents")]
 use tokio::runtime::Runtime as TokioRuntime;
 
+use crate::client::RemoteClient;
+
 use super::app::{App, ViewMode};
+use super::instance::{self, Acquired};
+use super::keybindings::{KeyAction, Keybindings};
 use super::ui;
+use super::view;
 
 /// Result type for TUI operations.
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -28,7 +33,7 @@ pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -37,7 +42,12 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
 /// Restore the terminal to normal mode.
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), DisableMouseCapture, LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
     Ok(())
 }
@@ -65,7 +75,16 @@ fn restart_self() -> ! {
 }
 
 /// Run the TUI event loop.
-pub fn run(state_dir: &std::path::Path, connect_to: Option<String>) -> Result<()> {
+///
+/// With `remote`, sessions are driven through that daemon's API instead of
+/// local tmux. Unless `force_standalone`, a local TUI started while another
+/// is running hands `connect_to` over to it and exits.
+pub fn run(
+    state_dir: &std::path::Path,
+    connect_to: Option<String>,
+    remote: Option<RemoteClient>,
+    force_standalone: bool,
+) -> Result<()> {
     // Load config and check for first-run onboarding
     commander_core::load_config();
 
@@ -77,6 +96,9 @@ pub fn run(state_dir: &std::path::Path, connect_to: Option<String>) -> Result<()
         commander_core::load_config();
     }
 
+    // Watch connectivity for the offline badge and LLM fallbacks
+    commander_core::connectivity::start_monitor();
+
     // Setup SIGHUP handler for hot-restart
     let restart_flag = match setup_signal_handler() {
         Ok(flag) => Some(flag),
@@ -86,20 +108,73 @@ pub fn run(state_dir: &std::path::Path, connect_to: Option<String>) -> Result<()
         }
     };
 
+    // Refuse to start with keybindings that cannot be applied
+    let settings = commander_core::Settings::load(&commander_core::config_file()).unwrap_or_default();
+    let (_, problems) = Keybindings::from_settings(&settings.tui);
+    if !problems.is_empty() {
+        return Err(format!(
+            "Invalid [tui.keys] in {}:\n  {}",
+            commander_core::config_file().display(),
+            problems.join("\n  ")
+        )
+        .into());
+    }
+
+    // Leave the pollers to a TUI that is already running
+    let local = remote.is_none();
+    let mut instance = None;
+    if local && !force_standalone {
+        match instance::acquire(&commander_core::config::runtime_state_dir()) {
+            Ok(Acquired::Primary(lock)) => instance = Some(lock),
+            Ok(Acquired::Running(running)) => {
+                let Some(project) = connect_to else {
+                    return Err(format!(
+                        "A TUI is already running (pid {}). Switch to it, pass --project to \
+                         open a project there, or use --force-standalone.",
+                        running.pid
+                    )
+                    .into());
+                };
+                running
+                    .connect(&project)
+                    .map_err(|e| format!("Failed to reach the running TUI (pid {}): {}", running.pid, e))?;
+                println!("Sent {} to the TUI already running (pid {})", project, running.pid);
+                return Ok(());
+            }
+            Err(e) => eprintln!("Warning: Failed to check for a running TUI: {}", e),
+        }
+    }
+
     // Setup terminal
     let mut terminal = setup_terminal()?;
 
     // Create app
     let mut app = App::new(state_dir);
+    app.instance = instance;
+    if let Some(client) = remote {
+        app.use_remote(client);
+    }
 
     // Initialize tokio runtime for async operations (agents feature)
     #[cfg(feature = "agents")]
     let _runtime = init_agents_runtime(&mut app);
 
+    // Apply config.toml now and whenever it changes
+    app.config_changes = Some(commander_core::subscribe_config_changes());
+    app.check_config_changes();
+
+    // Offer to relaunch sessions lost to a reboot
+    app.check_lost_sessions();
+
+    // Clean up after deleted projects (remote sessions are the daemon's business)
+    if local {
+        app.check_orphans();
+    }
+
     // Auto-connect if project specified
     if let Some(project) = connect_to {
         if let Err(e) = app.connect(&project) {
-            app.messages.push(super::app::Message::system(format!("Failed to connect: {}", e)));
+            app.messages.push(super::app::Message::error(&e));
         }
     }
 
@@ -153,18 +228,19 @@ fn run_loop(
 
     loop {
         // Draw UI
+        view::clear_closed_image(terminal, app)?;
         terminal.draw(|f| ui::draw(f, app))?;
+        view::draw_open_image(terminal, app)?;
 
         // Poll for events with timeout
         if event::poll(tick_rate)? {
             match event::read()? {
                 Event::Mouse(mouse) => {
-                    // Handle left mouse button clicks
-                    if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
-                        app.handle_click(mouse.column, mouse.row);
-                    }
+                    app.handle_mouse(mouse);
                     continue;
                 }
+                Event::FocusGained => app.terminal_focused = true,
+                Event::FocusLost => app.terminal_focused = false,
                 Event::Key(key) => {
                     // Only handle key press events (not release)
                     if key.kind != KeyEventKind::Press {
@@ -176,24 +252,74 @@ fn run_loop(
                         app.should_quit = true;
                     }
 
-                    // Handle F2 to toggle inspect mode
-                    if key.code == KeyCode::F(2) {
-                        app.toggle_inspect_mode();
+                    // The escalation modal takes keys until it is closed
+                    if app.escalation_modal.is_some() {
+                        match key.code {
+                            KeyCode::Enter | KeyCode::Char('a') => app.acknowledge_modal(),
+                            KeyCode::Esc | KeyCode::Char('d') => app.dismiss_modal(),
+                            _ => {}
+                        }
                         continue;
                     }
 
-                    // Handle F3 to show sessions view
-                    if key.code == KeyCode::F(3) {
-                        if app.view_mode == ViewMode::Sessions {
-                            app.view_mode = ViewMode::Normal;
-                        } else if app.tmux.is_some() {
-                            app.show_sessions();
-                        } else {
-                            app.messages.push(super::app::Message::system("Tmux not available"));
-                        }
+                    // An open image closes on any key
+                    if app.image_view.is_some() {
+                        app.close_image_view();
+                        continue;
+                    }
+
+                    // So does the /connect wizard
+                    if app.connect_wizard.is_some() {
+                        app.handle_wizard_key(key.code);
                         continue;
                     }
 
+                    // Global keys from [tui.keys] (F2 inspect, F3 sessions, Ctrl+L clear by default)
+                    match app.keybindings.action(&key) {
+                        Some(KeyAction::Inspect) => {
+                            app.toggle_inspect_mode();
+                            continue;
+                        }
+                        Some(KeyAction::Sessions) => {
+                            app.toggle_sessions_view();
+                            continue;
+                        }
+                        Some(KeyAction::Clear) => {
+                            app.messages.clear();
+                            app.messages.push(super::app::Message::system("Output cleared"));
+                            continue;
+                        }
+                        Some(KeyAction::Help) => {
+                            app.handle_command("help");
+                            continue;
+                        }
+                        Some(KeyAction::Keys) => {
+                            app.handle_command("keys");
+                            continue;
+                        }
+                        Some(KeyAction::Logs) => {
+                            app.show_logs(None);
+                            continue;
+                        }
+                        Some(KeyAction::Approvals) => {
+                            app.show_approvals();
+                            continue;
+                        }
+                        Some(KeyAction::Diff) => {
+                            app.show_diff(None);
+                            continue;
+                        }
+                        Some(KeyAction::Inbox) => {
+                            app.show_inbox();
+                            continue;
+                        }
+                        Some(KeyAction::Yank) => {
+                            app.yank_message();
+                            continue;
+                        }
+                        None => {}
+                    }
+
                     // Handle keys based on view mode
                     match app.view_mode {
                         ViewMode::Sessions => {
@@ -220,6 +346,71 @@ fn run_loop(
                                 _ => {}
                             }
                         }
+                        ViewMode::Diff => {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.diff_scroll_up(1),
+                                KeyCode::Down | KeyCode::Char('j') => app.diff_scroll_down(1),
+                                KeyCode::PageUp => app.diff_scroll_up(10),
+                                KeyCode::PageDown => app.diff_scroll_down(10),
+                                KeyCode::Home => app.diff_scroll = 0,
+                                KeyCode::Esc | KeyCode::Char('q') => app.close_diff(),
+                                _ => {}
+                            }
+                        }
+                        ViewMode::Logs => {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.log_scroll_up(1),
+                                KeyCode::Down | KeyCode::Char('j') => app.log_scroll_down(1),
+                                KeyCode::PageUp => app.log_scroll_up(10),
+                                KeyCode::PageDown => app.log_scroll_down(10),
+                                KeyCode::End => app.log_scroll = 0,
+                                KeyCode::Esc | KeyCode::Char('q') => app.close_logs(),
+                                _ => {}
+                            }
+                        }
+                        ViewMode::History => {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.history_scroll_up(1),
+                                KeyCode::Down | KeyCode::Char('j') => app.history_scroll_down(1),
+                                KeyCode::PageUp => app.history_scroll_up(10),
+                                KeyCode::PageDown => app.history_scroll_down(10),
+                                KeyCode::Home => app.history_scroll = 0,
+                                KeyCode::End => app.history_scroll = app.history_lines.len(),
+                                KeyCode::Esc | KeyCode::Char('q') => app.close_history(),
+                                _ => {}
+                            }
+                        }
+                        ViewMode::Approvals => {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.approval_select_up(),
+                                KeyCode::Down | KeyCode::Char('j') => app.approval_select_down(),
+                                KeyCode::Char('a') | KeyCode::Enter => app.decide_selected_approval(true),
+                                KeyCode::Char('d') => app.decide_selected_approval(false),
+                                KeyCode::Esc | KeyCode::Char('q') => app.close_approvals(),
+                                _ => {}
+                            }
+                        }
+                        ViewMode::Improvements => {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.improvement_select_up(),
+                                KeyCode::Down | KeyCode::Char('j') => app.improvement_select_down(),
+                                KeyCode::Char('a') | KeyCode::Enter => app.decide_selected_improvement(true),
+                                KeyCode::Char('r') => app.decide_selected_improvement(false),
+                                KeyCode::Esc | KeyCode::Char('q') => app.close_improvements(),
+                                _ => {}
+                            }
+                        }
+                        ViewMode::Inbox => {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.inbox_select_up(),
+                                KeyCode::Down | KeyCode::Char('j') => app.inbox_select_down(),
+                                KeyCode::Enter => app.inbox_open_selected(),
+                                KeyCode::Char('a') => app.inbox_acknowledge_selected(),
+                                KeyCode::Char('s') => app.inbox_snooze_selected(),
+                                KeyCode::Esc | KeyCode::Char('q') => app.close_inbox(),
+                                _ => {}
+                            }
+                        }
                         ViewMode::Normal => {
                             // Check if in option selection mode first
                             if app.option_mode {
@@ -294,12 +485,6 @@ fn run_loop(
                             }
                         }
                     }
-
-                    // Handle Ctrl+L to clear
-                    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('l') {
-                        app.messages.clear();
-                        app.messages.push(super::app::Message::system("Output cleared"));
-                    }
                 }
                 _ => {}
             }
@@ -315,9 +500,61 @@ fn run_loop(
             app.refresh_inspect_content();
         }
 
+        // Keep the shared session registry in sync with the connection
+        app.sync_session_attachment();
+
+        // Show the "Since you left" digest once summarized
+        app.check_recap();
+
+        // Connect to projects handed over by other invocations
+        app.check_instance_requests();
+
+        // Track file changes in the connected project
+        app.poll_file_changes();
+
+        // Send template, read-only and restore prompts once adapters are ready
+        app.deliver_pending_prompts();
+
+        // Reload sessions paused here or in another frontend
+        app.check_paused_sessions();
+
+        // Refresh the Telegram bot's heartbeat health
+        app.check_bot_health();
+
         // Check session status for "waiting for input" notifications
         app.check_session_status();
 
+        // Alert on stuck sessions and error loops
+        app.check_watchdog();
+
+        // Show blocking events the daemon escalated to the TUI
+        app.check_escalations();
+
+        // Show desktop notifications held during do-not-disturb once it ends
+        app.check_dnd_digest();
+
+        // Announce queued approval requests and run approved ones
+        app.check_approvals();
+
+        // Keep the open inbox current
+        app.check_inbox();
+
+        // Apply config.toml changes (keybindings, notifications, agent models)
+        app.check_config_changes();
+
+        // Follow the structured log while the log viewer is open
+        app.check_log_tail();
+
+        // Print per-task status of a running plan
+        #[cfg(feature = "agents")]
+        app.check_plan_progress();
+
+        // Snapshot live sessions so they can be restored after a reboot
+        app.check_snapshots();
+
+        // Append frames to an active /record recording
+        app.record_tick();
+
         // Full scan of all sessions every 5 minutes
         app.scan_all_sessions();
 
@@ -0,0 +1,93 @@
+//! File change tracking for the connected project.
+//!
+//! Watches the connected project's directory and keeps the set of files
+//! changed since connecting, shown as "N files changed" in the header.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use commander_runtime::ProjectWatcher;
+
+use super::app::App;
+
+/// Quiet period before a burst of file changes is reported.
+const FILE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+impl App {
+    /// Keep the file watcher in sync with the connected project and collect changes.
+    ///
+    /// Called on every event loop tick; (re)starts the watcher when the
+    /// connected project path changes and stops it on disconnect.
+    pub fn poll_file_changes(&mut self) {
+        let target = match (&self.project, &self.project_path) {
+            (Some(_), Some(path)) => Some(PathBuf::from(path)),
+            _ => None,
+        };
+
+        let watched = self.file_watcher.as_ref().map(|w| w.root().to_path_buf());
+        if watched != target {
+            self.restart_file_watcher(target.as_deref());
+        }
+
+        let Some(rx) = &self.file_changes_rx else {
+            return;
+        };
+        let batches: Vec<Vec<PathBuf>> = rx.try_iter().collect();
+        if batches.is_empty() {
+            return;
+        }
+
+        let paths: Vec<PathBuf> = batches.into_iter().flatten().collect();
+        self.forward_file_changes(&paths);
+        self.changed_files.extend(paths);
+    }
+
+    /// Number of files changed in the connected project since connecting.
+    pub fn changed_file_count(&self) -> usize {
+        self.changed_files.len()
+    }
+
+    /// Replace the current watcher with one on `path` (or none).
+    fn restart_file_watcher(&mut self, path: Option<&Path>) {
+        self.file_watcher = None;
+        self.file_changes_rx = None;
+        self.changed_files.clear();
+
+        let Some(path) = path else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        match ProjectWatcher::start(path, FILE_DEBOUNCE, move |paths| {
+            let _ = tx.send(paths);
+        }) {
+            Ok(watcher) => {
+                self.file_watcher = Some(watcher);
+                self.file_changes_rx = Some(rx);
+            }
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "file watcher unavailable");
+            }
+        }
+    }
+
+    /// Feed watcher results to the session agent so it stops guessing from output.
+    #[cfg(feature = "agents")]
+    fn forward_file_changes(&mut self, paths: &[PathBuf]) {
+        let Some(session_name) = self.project.as_ref().and_then(|p| self.sessions.get(p)).cloned() else {
+            return;
+        };
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            return;
+        };
+
+        let files = paths.iter().map(|p| p.display().to_string());
+        if let Err(e) = orchestrator.record_files_changed(&session_name, "claude_code", files) {
+            tracing::debug!(session = %session_name, error = %e, "failed to record file changes");
+        }
+    }
+
+    #[cfg(not(feature = "agents"))]
+    fn forward_file_changes(&mut self, _paths: &[PathBuf]) {}
+}
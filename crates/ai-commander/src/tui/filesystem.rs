@@ -0,0 +1,86 @@
+//! Filesystem commands typed while connected to a project.
+//!
+//! Commands run through the [`Sandbox`]: paths outside the project and
+//! oversized reads are refused, and commands touching a protected path
+//! (`[filesystem] protected`) wait for `/approve` or `/deny`.
+
+use std::path::Path;
+
+use super::app::{App, Message};
+use crate::filesystem::{self, FsCommand};
+use crate::sandbox::{Sandbox, SandboxError};
+
+impl App {
+    /// Run a filesystem command typed as `input` in `working_dir`.
+    ///
+    /// `confirmed` skips the protected-path check, once the user approved.
+    pub(super) fn run_fs_command(&mut self, input: String, fs_cmd: &FsCommand, working_dir: &Path, confirmed: bool) {
+        let project = self.project.clone().unwrap_or_default();
+        let sandbox = Sandbox::new(working_dir, self.filesystem_settings.clone());
+
+        let fs_cmd = match sandbox.confine(fs_cmd, confirmed) {
+            Ok(cmd) => cmd,
+            Err(SandboxError::Protected(path)) => {
+                self.messages.push(Message::sent(project, input.clone()));
+                self.messages.push(Message::system(format!(
+                    "[!] {} is protected. Use /approve to run it or /deny to drop it",
+                    path
+                )));
+                self.pending_fs_command = Some((input, working_dir.to_path_buf()));
+                self.scroll_to_bottom();
+                return;
+            }
+            Err(e) => {
+                self.messages.push(Message::sent(project, input));
+                self.messages.push(Message::system(format!("Error: {}", e)));
+                self.scroll_to_bottom();
+                return;
+            }
+        };
+
+        let read_only = self.is_read_only(&project);
+        if fs_cmd.is_mutating() && !read_only && self.queue_fs_command_if_required(&input, working_dir) {
+            return;
+        }
+
+        // Execute filesystem command locally
+        let result = if read_only {
+            filesystem::execute_read_only(&fs_cmd, sandbox.root())
+        } else {
+            filesystem::execute(&fs_cmd, sandbox.root())
+        };
+
+        self.messages.push(Message::sent(project.clone(), input));
+
+        if result.success {
+            self.messages.push(Message::received(project.clone(), result.message));
+            if let Some(details) = result.details {
+                for line in details.lines() {
+                    self.messages.push(Message::received(project.clone(), line.to_string()));
+                }
+            }
+        } else {
+            self.messages.push(Message::system(format!("Error: {}", result.message)));
+        }
+        self.scroll_to_bottom();
+    }
+
+    /// Run or drop the command awaiting confirmation for a protected path.
+    pub(super) fn resolve_pending_fs_command(&mut self, approve: bool) {
+        let Some((input, working_dir)) = self.pending_fs_command.take() else {
+            self.messages.push(Message::system("No filesystem command awaiting confirmation"));
+            return;
+        };
+
+        if !approve {
+            self.messages.push(Message::system(format!("Dropped: {}", input)));
+            return;
+        }
+        match filesystem::parse_command(&input, &working_dir) {
+            Some(fs_cmd) => self.run_fs_command(input, &fs_cmd, &working_dir, true),
+            None => self
+                .messages
+                .push(Message::system(format!("Not a filesystem command: {}", input))),
+        }
+    }
+}
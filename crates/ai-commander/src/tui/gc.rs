@@ -0,0 +1,53 @@
+//! Orphan cleanup at startup.
+//!
+//! Tmux sessions of deleted projects, stale PID files and dead worktrees are
+//! looked for once when the TUI starts, then listed or removed according to
+//! `[gc] on_startup` (`report` by default; `auto` removes them, `off` skips
+//! the check). `commander gc` removes them after confirmation.
+
+use commander_core::{GcPolicy, Settings};
+
+use crate::gc;
+
+use super::app::{App, Message};
+
+impl App {
+    /// Look for orphans and report or collect them per `[gc] on_startup`.
+    ///
+    /// Called once at startup, with local tmux only.
+    pub fn check_orphans(&mut self) {
+        let policy = Settings::load(&commander_core::config_file())
+            .unwrap_or_default()
+            .gc
+            .on_startup;
+        if policy == GcPolicy::Off {
+            return;
+        }
+
+        let tmux = self.tmux.as_deref();
+        let orphans = gc::find_orphans(&self.store, tmux, &commander_core::config::runtime_state_dir());
+        if orphans.is_empty() {
+            return;
+        }
+
+        if policy == GcPolicy::Report {
+            self.messages.push(Message::system(format!(
+                "[!] Found orphans of deleted projects: {}. `commander gc` lists and removes them",
+                orphans.summary()
+            )));
+            return;
+        }
+
+        let report = gc::collect(&orphans, tmux);
+        self.messages.push(Message::system(format!(
+            "Cleaned up {} of {} orphan(s): {}",
+            report.removed,
+            orphans.describe().len(),
+            orphans.summary()
+        )));
+        for error in report.errors {
+            self.messages.push(Message::system(format!("  Failed: {}", error)));
+        }
+        self.cached_sessions = None;
+    }
+}
@@ -13,10 +13,10 @@ impl App {
             .unwrap_or(false)
     }
 
-    /// Commit any uncommitted git changes in the project directory.
+    /// Commit any uncommitted git changes in the project directory with `message`.
     /// Returns Ok(None) if not a git repository, Ok(Some(true)) if committed,
     /// Ok(Some(false)) if no changes, or Err on failure.
-    pub fn git_commit_changes(&self, path: &str, project_name: &str) -> Result<Option<bool>, String> {
+    pub fn git_commit_changes(&self, path: &str, message: &str) -> Result<Option<bool>, String> {
         use std::process::Command;
 
         // Skip git operations if not in a git worktree
@@ -44,9 +44,8 @@ impl App {
             .map_err(|e| format!("Failed to stage changes: {}", e))?;
 
         // Commit with message (may fail if pre-commit hooks modify files)
-        let message = format!("WIP: Auto-commit from Commander session '{}'", project_name);
         let commit = Command::new("git")
-            .args(["commit", "-m", &message])
+            .args(["commit", "-m", message])
             .current_dir(path)
             .output()
             .map_err(|e| format!("Failed to commit: {}", e))?;
@@ -66,7 +65,7 @@ impl App {
                 .map_err(|e| format!("Failed to re-stage changes: {}", e))?;
 
             let retry = Command::new("git")
-                .args(["commit", "-m", &message])
+                .args(["commit", "-m", message])
                 .current_dir(path)
                 .output()
                 .map_err(|e| format!("Failed to commit after hooks: {}", e))?;
@@ -0,0 +1,97 @@
+//! Session handoff between frontends.
+//!
+//! Keeps the shared session registry in sync with the TUI's connection so a
+//! conversation started here can be picked up from the REPL or Telegram
+//! (and vice versa).
+
+use commander_core::{update_session_registry, AttachOutcome, Frontend, HandoffDirection};
+
+use super::app::{App, Message};
+
+impl App {
+    /// The tmux session for the connected project, if any.
+    pub(super) fn current_session_name(&self) -> Option<String> {
+        self.project.as_ref().and_then(|p| self.sessions.get(p)).cloned()
+    }
+
+    /// Attach to or detach from the shared registry when the connection changes.
+    ///
+    /// Called on every event loop tick so every connect/disconnect path is covered.
+    pub fn sync_session_attachment(&mut self) {
+        let current = self.current_session_name();
+        if current == self.attached_session {
+            return;
+        }
+
+        if let Some(previous) = self.attached_session.take() {
+            let _ = update_session_registry(|r| r.detach(&previous, Frontend::Tui));
+        }
+
+        let Some(session) = current else { return };
+        self.attached_session = Some(session.clone());
+        self.start_recap(&session);
+
+        let result = update_session_registry(|r| {
+            let outcome = r.attach(&session, Frontend::Tui, None);
+            let context = r.get(&session).and_then(|rec| rec.resume_context(Frontend::Tui));
+            (outcome, context)
+        });
+
+        match result {
+            Ok((outcome, context)) => {
+                if let Some(context) = context {
+                    self.messages.push(Message::system("Recent activity from other frontends:"));
+                    for line in context.lines() {
+                        self.messages.push(Message::system(format!("  {}", line)));
+                    }
+                }
+                if let AttachOutcome::HeldBy(holder) = outcome {
+                    self.messages.push(Message::system(format!(
+                        "'{}' is attached in {}. Use /takeover to claim it.",
+                        session, holder.frontend
+                    )));
+                }
+            }
+            Err(e) => {
+                tracing::debug!(session = %session, error = %e, "failed to update session registry");
+            }
+        }
+    }
+
+    /// Claim the connected session from whichever frontend holds it.
+    pub fn takeover_session(&mut self) {
+        let Some(session) = self.current_session_name() else {
+            self.messages.push(Message::system("Not connected to any project"));
+            return;
+        };
+
+        match update_session_registry(|r| r.takeover(&session, Frontend::Tui, None)) {
+            Ok(Some(previous)) => {
+                self.attached_session = Some(session.clone());
+                self.messages.push(Message::system(format!(
+                    "Took over '{}' from {}", session, previous.frontend
+                )));
+            }
+            Ok(None) => {
+                self.attached_session = Some(session.clone());
+                self.messages.push(Message::system(format!("'{}' is attached here", session)));
+            }
+            Err(e) => {
+                self.messages.push(Message::system(format!("Error: failed to claim session: {}", e)));
+            }
+        }
+    }
+
+    /// Record a query sent from the TUI in the shared registry.
+    pub(super) fn record_handoff_query(&self, session: &str, query: &str) {
+        let _ = update_session_registry(|r| r.record_query(session, Frontend::Tui, query));
+    }
+
+    /// Record a response shown in the TUI in the shared registry.
+    pub(super) fn record_handoff_response(&self, response: &str) {
+        let Some(session) = self.current_session_name() else { return };
+        let _ = update_session_registry(|r| {
+            r.record_message(&session, Frontend::Tui, HandoffDirection::Received, response)
+        });
+    }
+}
@@ -0,0 +1,161 @@
+//! Conversation search and the transcript history view.
+//!
+//! `/search <query>` lists numbered hits from the session transcripts and
+//! the memory store (see `crate::history_search`). `/history <n>` opens the
+//! transcript of hit `n`'s session scrolled to the match, and `/history`
+//! alone opens the connected session's transcript at its end.
+
+use chrono::{Local, TimeZone};
+use commander_core::{read_all_log_entries, search_log_entries, LogEntry};
+
+use crate::history_search::{self, HitSource, SEARCH_LIMIT};
+
+use super::app::{App, Message, ViewMode};
+
+/// Lines shown above the matching entry when jumping to a hit.
+const CONTEXT_LINES: usize = 2;
+
+impl App {
+    /// Handle `/search <query>`.
+    pub fn handle_search_command(&mut self, query: Option<&str>) {
+        let Some(query) = query.filter(|q| !q.is_empty()) else {
+            self.messages.push(Message::system("Usage: /search <query>"));
+            return;
+        };
+
+        let transcripts = search_log_entries(query, SEARCH_LIMIT);
+        let memories = self.search_memories(query);
+        let hits = history_search::merge(transcripts, memories, SEARCH_LIMIT);
+        if hits.is_empty() {
+            self.messages.push(Message::system(format!("No results for '{}'", query)));
+        } else {
+            self.messages.push(Message::system(format!("{} results for '{}':", hits.len(), query)));
+            for (i, hit) in hits.iter().enumerate() {
+                self.messages.push(Message::system(format!("  {}", hit.format_line(i + 1))));
+            }
+            self.messages.push(Message::system("Open a result in the history view with /history <n>"));
+        }
+        self.search_results = hits;
+    }
+
+    /// Semantic search over the memory store, when the orchestrator is available.
+    #[cfg(feature = "agents")]
+    fn search_memories(&mut self, query: &str) -> Vec<commander_memory::SearchResult> {
+        let (Some(handle), Some(orchestrator)) = (self.runtime_handle.clone(), self.orchestrator.as_ref()) else {
+            return Vec::new();
+        };
+        let store = orchestrator.memory_store().clone();
+        match handle.block_on(history_search::search_memories(store.as_ref(), query, SEARCH_LIMIT)) {
+            Ok(results) => results,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Memory search unavailable: {}", e)));
+                Vec::new()
+            }
+        }
+    }
+
+    #[cfg(not(feature = "agents"))]
+    fn search_memories(&mut self, _query: &str) -> Vec<commander_memory::SearchResult> {
+        Vec::new()
+    }
+
+    /// Handle `/history [n]`.
+    pub fn show_history(&mut self, arg: Option<&str>) {
+        let Some(arg) = arg else {
+            let Some(session) = self.current_session_name() else {
+                self.messages.push(Message::system("Connect to a project first, or open a result with /history <n>"));
+                return;
+            };
+            self.open_history(&session, None);
+            return;
+        };
+
+        let hit = arg
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| self.search_results.get(i).cloned());
+        let Some(hit) = hit else {
+            self.messages.push(Message::system(format!(
+                "No search result '{}'. Usage: /history [n] after /search <query>",
+                arg
+            )));
+            return;
+        };
+        match hit.source {
+            HitSource::Transcript { index } => self.open_history(&hit.session, Some(index)),
+            HitSource::Memory { .. } => {
+                let entries = read_all_log_entries(&hit.session).unwrap_or_default();
+                match history_search::nearest_entry(&entries, hit.at) {
+                    Some(index) => self.open_history(&hit.session, Some(index)),
+                    None => self.messages.push(Message::system(format!(
+                        "No transcript for {}. Memory: {}",
+                        hit.session, hit.text
+                    ))),
+                }
+            }
+        }
+    }
+
+    /// Show `session`'s transcript, scrolled to entry `focus` (or the end).
+    fn open_history(&mut self, session: &str, focus: Option<usize>) {
+        let entries = match read_all_log_entries(session) {
+            Ok(entries) if !entries.is_empty() => entries,
+            Ok(_) => {
+                self.messages.push(Message::system(format!("No transcript for {}", session)));
+                return;
+            }
+            Err(e) => {
+                self.messages.push(Message::system(format!("Failed to read transcript of {}: {}", session, e)));
+                return;
+            }
+        };
+
+        self.history_lines = Vec::new();
+        let mut focus_line = None;
+        for (index, entry) in entries.iter().enumerate() {
+            if focus == Some(index) {
+                focus_line = Some(self.history_lines.len());
+            }
+            self.history_lines
+                .extend(entry_lines(entry).into_iter().map(|line| (index, line)));
+        }
+        self.history_title = session.to_string();
+        self.history_focus = focus;
+        self.history_scroll = match focus_line {
+            Some(line) => line.saturating_sub(CONTEXT_LINES),
+            None => self.history_lines.len(),
+        };
+        self.view_mode = ViewMode::History;
+    }
+
+    /// Leave the history view.
+    pub fn close_history(&mut self) {
+        if self.view_mode == ViewMode::History {
+            self.view_mode = ViewMode::Normal;
+        }
+    }
+
+    /// Scroll the history view up.
+    pub fn history_scroll_up(&mut self, lines: usize) {
+        self.history_scroll = self.history_scroll.saturating_sub(lines);
+    }
+
+    /// Scroll the history view down.
+    pub fn history_scroll_down(&mut self, lines: usize) {
+        self.history_scroll = (self.history_scroll + lines).min(self.history_lines.len());
+    }
+}
+
+/// Display lines of a transcript entry: a dated header, then its text.
+fn entry_lines(entry: &LogEntry) -> Vec<String> {
+    let at = Local
+        .timestamp_opt(entry.ts, 0)
+        .single()
+        .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let who = if entry.kind.as_deref() == Some("user") { "you" } else { "session" };
+    let mut lines = vec![format!("{} {}", at, who)];
+    lines.extend(entry.text.lines().map(|line| format!("  {}", line)));
+    lines
+}
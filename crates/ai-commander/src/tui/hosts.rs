@@ -0,0 +1,102 @@
+//! Projects whose sessions run on a remote host over SSH.
+//!
+//! A project with a `host` in its configuration (`[user@]host[:port]`)
+//! starts its session on that host. The TUI registers the host with the
+//! session backend at startup, which then runs every tmux command for the
+//! session over a shared SSH connection; output of remote sessions is polled
+//! less often the slower the host answers. `/host` shows or changes the host
+//! of the connected project.
+
+use commander_tmux::SshHost;
+
+use super::app::{App, Message};
+
+impl App {
+    /// Register the SSH hosts of the stored projects with the session backend.
+    pub(super) fn register_project_hosts(&mut self) {
+        let Some(tmux) = &self.tmux else { return };
+        let Ok(projects) = self.store.load_all_projects() else { return };
+        for project in projects.values() {
+            let Some(host) = crate::project_host(project) else { continue };
+            let session = project.name.replace([' ', '.', '/', ':'], "-");
+            if let Err(e) = tmux.set_session_host(&session, Some(host)) {
+                self.messages.push(Message::system(format!("Remote hosts ignored: {}", e)));
+                return;
+            }
+        }
+    }
+
+    /// `/host [<[user@]host[:port]> | local]`: show or set where the
+    /// connected project's session runs.
+    pub(super) fn handle_host_command(&mut self, arg: Option<&str>) {
+        let (Some(project_name), Some(session)) = (self.project.clone(), self.current_session_name()) else {
+            self.messages.push(Message::system("Not connected to a project. Use /connect first."));
+            return;
+        };
+        let Some(tmux) = &self.tmux else {
+            self.messages.push(Message::system("Error: tmux not available"));
+            return;
+        };
+
+        let arg = arg.map(str::trim).unwrap_or_default();
+        if arg.is_empty() {
+            let text = match tmux.session_host(&session) {
+                Some((host, Some(latency))) => format!(
+                    "'{}' runs on {} ({} ms round trip)",
+                    project_name,
+                    host,
+                    latency.as_millis()
+                ),
+                Some((host, None)) => format!("'{}' runs on {}", project_name, host),
+                None => format!("'{}' runs locally. Move it with /host <user@host[:port]>", project_name),
+            };
+            self.messages.push(Message::system(text));
+            return;
+        }
+
+        let host = if arg == "local" {
+            None
+        } else {
+            match arg.parse::<SshHost>() {
+                Ok(host) => Some(host),
+                Err(e) => {
+                    self.messages.push(Message::system(format!("Error: {}", e)));
+                    return;
+                }
+            }
+        };
+
+        let projects = match self.store.load_all_projects() {
+            Ok(projects) => projects,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Error: failed to load projects: {}", e)));
+                return;
+            }
+        };
+        let Some(mut project) = projects.into_values().find(|p| p.name == project_name) else {
+            self.messages.push(Message::system(format!(
+                "'{}' is not a registered project; only projects can have a host",
+                project_name
+            )));
+            return;
+        };
+        match &host {
+            Some(host) => project.config.insert("host".to_string(), serde_json::json!(host.to_string())),
+            None => project.config.remove("host"),
+        };
+        if let Err(e) = self.store.save_project(&project) {
+            self.messages.push(Message::system(format!("Error: failed to save project: {}", e)));
+            return;
+        }
+        if let Err(e) = tmux.set_session_host(&session, host.clone()) {
+            self.messages.push(Message::system(format!("Error: {}", e)));
+            return;
+        }
+
+        let place = host.map_or_else(|| "locally".to_string(), |host| format!("on {}", host));
+        self.messages.push(Message::system(format!(
+            "'{}' now runs {}. Its current session is left where it was; /stop and /connect to start it there",
+            project_name, place
+        )));
+    }
+}
@@ -0,0 +1,119 @@
+//! Review pane for prompt patches proposed from feedback.
+//!
+//! `commander agent improvements generate` turns feedback into proposed
+//! patches to agent prompts. `/improvements` lists the ones awaiting review;
+//! applying one creates a new prompt version that agents use from their next
+//! start (`commander agent improvements rollback` undoes it).
+
+use commander_agent::eval::{ImprovementStore, PatchStatus};
+
+use super::app::{App, Message, ViewMode};
+
+impl App {
+    /// Open the improvements pane.
+    pub fn show_improvements(&mut self) {
+        self.refresh_improvements();
+        self.view_mode = ViewMode::Improvements;
+    }
+
+    /// Leave the improvements pane.
+    pub fn close_improvements(&mut self) {
+        if self.view_mode == ViewMode::Improvements {
+            self.view_mode = ViewMode::Normal;
+        }
+    }
+
+    /// Reload proposed patches from the improvement store.
+    pub(super) fn refresh_improvements(&mut self) {
+        match ImprovementStore::open_default().patches(None) {
+            Ok(patches) => {
+                self.improvement_list = patches
+                    .into_iter()
+                    .filter(|p| p.status == PatchStatus::Proposed)
+                    .collect();
+            }
+            Err(e) => {
+                self.improvement_list.clear();
+                self.messages
+                    .push(Message::system(format!("Failed to load prompt patches: {}", e)));
+            }
+        }
+        self.improvement_selected = self
+            .improvement_selected
+            .min(self.improvement_list.len().saturating_sub(1));
+    }
+
+    /// Select the previous patch in the improvements pane.
+    pub fn improvement_select_up(&mut self) {
+        self.improvement_selected = self.improvement_selected.saturating_sub(1);
+    }
+
+    /// Select the next patch in the improvements pane.
+    pub fn improvement_select_down(&mut self) {
+        if self.improvement_selected + 1 < self.improvement_list.len() {
+            self.improvement_selected += 1;
+        }
+    }
+
+    /// Apply or reject the patch selected in the improvements pane.
+    pub fn decide_selected_improvement(&mut self, apply: bool) {
+        let Some(id) = self
+            .improvement_list
+            .get(self.improvement_selected)
+            .map(|p| p.id.clone())
+        else {
+            return;
+        };
+
+        let store = ImprovementStore::open_default();
+        let result = if apply { store.apply(&id) } else { store.reject(&id) };
+        match result {
+            Ok(patch) if apply => self.messages.push(Message::system(format!(
+                "Applied {} to {} as prompt v{}: {}",
+                patch.id,
+                patch.template,
+                patch.version.unwrap_or_default(),
+                patch.instruction
+            ))),
+            Ok(patch) => self
+                .messages
+                .push(Message::system(format!("Rejected {}: {}", patch.id, patch.instruction))),
+            Err(e) => self.messages.push(Message::system(format!("Error: {}", e))),
+        }
+        self.refresh_improvements();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_agent::eval::{EvalTarget, Improvement, PromptPatch};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_improvement_selection_bounds() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.improvement_list = ["Be brief", "Cite files"]
+            .into_iter()
+            .map(|change| {
+                PromptPatch::new(
+                    &EvalTarget::User,
+                    Improvement {
+                        category: "style".into(),
+                        current_behavior: String::new(),
+                        suggested_change: change.into(),
+                        supporting_feedback: Vec::new(),
+                        confidence: 0.8,
+                    },
+                )
+            })
+            .collect();
+
+        app.improvement_select_up();
+        assert_eq!(app.improvement_selected, 0);
+        app.improvement_select_down();
+        app.improvement_select_down();
+        assert_eq!(app.improvement_selected, 1);
+    }
+}
@@ -0,0 +1,365 @@
+//! Priority inbox across all projects.
+//!
+//! The inbox (F5 or `/inbox`) lists what needs the user, whichever project
+//! it is in: sessions waiting for input, unresolved error events,
+//! notifications the TUI has not read, and work items past their deadline.
+//! Items are sorted by significance, then age, oldest first.
+//!
+//! Enter connects to the item's project, `a` acknowledges the item and `s`
+//! snoozes it for an hour. Acknowledging an error acknowledges its event and
+//! acknowledging a notification marks it read; a waiting session or an
+//! overdue work item is only hidden from this TUI, until it stops waiting or
+//! its deadline changes.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use commander_core::{get_unread_notifications, mark_notifications_read, Significance};
+use commander_models::{EventPriority, EventStatus, EventType};
+use commander_persistence::WorkStore;
+use commander_work::WorkQueue;
+
+use super::app::{App, Message, ViewMode};
+use super::escalation::shared_events;
+
+/// Minimum time between inbox refreshes while it is open.
+const INBOX_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long `s` hides an item.
+const SNOOZE_HOURS: i64 = 1;
+
+/// Channel the TUI marks notifications read as.
+const TUI_CHANNEL: &str = "tui";
+
+/// What an inbox item is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboxKind {
+    /// A session waiting for input.
+    Waiting,
+    /// An error event nobody acknowledged.
+    Error,
+    /// A notification the TUI has not read.
+    Notification,
+    /// A work item past its deadline.
+    Overdue,
+}
+
+impl InboxKind {
+    /// Short label for the inbox list.
+    pub fn label(self) -> &'static str {
+        match self {
+            InboxKind::Waiting => "waiting",
+            InboxKind::Error => "error",
+            InboxKind::Notification => "notice",
+            InboxKind::Overdue => "overdue",
+        }
+    }
+}
+
+/// One entry of the inbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InboxItem {
+    /// Identifies the item across refreshes, for acknowledging and snoozing.
+    pub key: String,
+    pub kind: InboxKind,
+    pub significance: Significance,
+    /// Project name, ID or session to connect to, if the item has one.
+    pub target: Option<String>,
+    /// Project name shown.
+    pub project: String,
+    pub title: String,
+    /// When the item started needing attention.
+    pub since: DateTime<Utc>,
+    /// Event or notification the item was made from.
+    source_id: Option<String>,
+}
+
+/// Sort `items` by significance, then age, oldest first.
+fn sort_items(items: &mut [InboxItem]) {
+    items.sort_by(|a, b| b.significance.cmp(&a.significance).then_with(|| a.since.cmp(&b.since)));
+}
+
+/// Age of an item, e.g. `5m`, `3h` or `2d`.
+pub fn format_age(since: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - since).num_minutes().max(0);
+    match minutes {
+        0..=59 => format!("{}m", minutes),
+        60..=1439 => format!("{}h", minutes / 60),
+        _ => format!("{}d", minutes / 1440),
+    }
+}
+
+impl App {
+    /// Open the inbox.
+    pub fn show_inbox(&mut self) {
+        self.refresh_inbox();
+        self.view_mode = ViewMode::Inbox;
+    }
+
+    /// Leave the inbox.
+    pub fn close_inbox(&mut self) {
+        if self.view_mode == ViewMode::Inbox {
+            self.view_mode = ViewMode::Normal;
+        }
+    }
+
+    /// Refresh the open inbox.
+    ///
+    /// Called on every event loop tick; rate limited to
+    /// `INBOX_REFRESH_INTERVAL`.
+    pub fn check_inbox(&mut self) {
+        if self.view_mode != ViewMode::Inbox {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .last_inbox_refresh
+            .is_some_and(|last| now.duration_since(last) < INBOX_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.refresh_inbox();
+    }
+
+    /// Collect the inbox items of every project.
+    pub(super) fn refresh_inbox(&mut self) {
+        self.last_inbox_refresh = Some(Instant::now());
+        let now = Utc::now();
+        let names: HashMap<String, String> = self
+            .store
+            .load_all_projects()
+            .map(|projects| projects.into_iter().map(|(id, p)| (id.to_string(), p.name)).collect())
+            .unwrap_or_default();
+        let project_name = |id: &str| {
+            names
+                .get(id)
+                .cloned()
+                .unwrap_or_else(|| id.strip_prefix("commander-").unwrap_or(id).to_string())
+        };
+
+        let mut items = Vec::new();
+
+        // Sessions waiting for input, other than the connected one
+        let waiting: BTreeSet<String> = self
+            .session_ready_state
+            .iter()
+            .filter(|(_, ready)| **ready)
+            .map(|(name, _)| name.clone())
+            .chain(
+                self.last_scan_waiting
+                    .iter()
+                    .map(|session| session.strip_prefix("commander-").unwrap_or(session).to_string()),
+            )
+            .filter(|name| self.project.as_ref() != Some(name))
+            .collect();
+        self.inbox_waiting_since.retain(|name, _| waiting.contains(name));
+        self.inbox_hidden
+            .retain(|key| key.strip_prefix("waiting:").is_none_or(|name| waiting.contains(name)));
+        for name in waiting {
+            let since = *self.inbox_waiting_since.entry(name.clone()).or_insert(now);
+            items.push(InboxItem {
+                key: format!("waiting:{}", name),
+                kind: InboxKind::Waiting,
+                significance: Significance::High,
+                target: Some(name.clone()),
+                project: name,
+                title: "Waiting for input".to_string(),
+                since,
+                source_id: None,
+            });
+        }
+
+        // Error events nobody acknowledged
+        match shared_events() {
+            Ok(manager) => {
+                let errors = manager.list(None).into_iter().filter(|e| {
+                    e.status == EventStatus::Pending && matches!(e.event_type, EventType::Error | EventType::InstanceError)
+                });
+                for event in errors {
+                    items.push(InboxItem {
+                        key: format!("event:{}", event.id),
+                        kind: InboxKind::Error,
+                        significance: if event.priority == EventPriority::Critical {
+                            Significance::Critical
+                        } else {
+                            Significance::High
+                        },
+                        target: Some(event.project_id.to_string()),
+                        project: project_name(event.project_id.as_str()),
+                        title: event.title,
+                        since: event.created_at,
+                        source_id: Some(event.id.to_string()),
+                    });
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to load events for the inbox"),
+        }
+
+        // Notifications the TUI has not read
+        for notification in get_unread_notifications(TUI_CHANNEL) {
+            let since = DateTime::from_timestamp(notification.created_at as i64, 0).unwrap_or(now);
+            items.push(InboxItem {
+                key: format!("notification:{}", notification.id),
+                kind: InboxKind::Notification,
+                significance: Significance::Medium,
+                project: notification.session.as_deref().map_or_else(|| "all".to_string(), project_name),
+                target: notification.session,
+                title: notification.message.lines().next().unwrap_or_default().to_string(),
+                since,
+                source_id: Some(notification.id),
+            });
+        }
+
+        // Work items past their deadline
+        let queue = WorkQueue::new(WorkStore::new(&self.state_dir));
+        match queue.load_all() {
+            Ok(()) => {
+                for item in queue.overdue(now) {
+                    let Some(deadline) = item.deadline else { continue };
+                    items.push(InboxItem {
+                        key: format!("overdue:{}:{}", item.id, deadline.timestamp()),
+                        kind: InboxKind::Overdue,
+                        significance: Significance::High,
+                        target: Some(item.project_id.to_string()),
+                        project: project_name(item.project_id.as_str()),
+                        title: item.content.lines().next().unwrap_or_default().to_string(),
+                        since: deadline,
+                        source_id: None,
+                    });
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to load work items for the inbox"),
+        }
+
+        self.inbox_snoozed.retain(|_, until| *until > now);
+        items.retain(|item| !self.inbox_hidden.contains(&item.key) && !self.inbox_snoozed.contains_key(&item.key));
+        sort_items(&mut items);
+        self.inbox_items = items;
+        self.inbox_selected = self.inbox_selected.min(self.inbox_items.len().saturating_sub(1));
+    }
+
+    /// Select the previous item in the inbox.
+    pub fn inbox_select_up(&mut self) {
+        self.inbox_selected = self.inbox_selected.saturating_sub(1);
+    }
+
+    /// Select the next item in the inbox.
+    pub fn inbox_select_down(&mut self) {
+        if self.inbox_selected + 1 < self.inbox_items.len() {
+            self.inbox_selected += 1;
+        }
+    }
+
+    /// Connect to the project of the selected item.
+    pub fn inbox_open_selected(&mut self) {
+        let Some(item) = self.inbox_items.get(self.inbox_selected).cloned() else {
+            return;
+        };
+        let Some(target) = item.target else {
+            self.messages
+                .push(Message::system(format!("Not tied to a project: {}", item.title)));
+            self.view_mode = ViewMode::Normal;
+            return;
+        };
+        self.view_mode = ViewMode::Normal;
+        if let Err(e) = self.connect(&target) {
+            self.messages.push(Message::error(&e));
+        }
+    }
+
+    /// Acknowledge the selected item.
+    pub fn inbox_acknowledge_selected(&mut self) {
+        let Some(item) = self.inbox_items.get(self.inbox_selected).cloned() else {
+            return;
+        };
+        match (item.kind, item.source_id.as_deref()) {
+            (InboxKind::Error, Some(id)) => self.acknowledge_event(Some(id)),
+            (InboxKind::Notification, Some(id)) => {
+                if let Err(e) = mark_notifications_read(TUI_CHANNEL, &[id.to_string()]) {
+                    self.messages
+                        .push(Message::system(format!("Failed to mark notification read: {}", e)));
+                }
+            }
+            _ => {
+                self.inbox_hidden.insert(item.key);
+            }
+        }
+        self.refresh_inbox();
+    }
+
+    /// Hide the selected item for `SNOOZE_HOURS`.
+    pub fn inbox_snooze_selected(&mut self) {
+        let Some(item) = self.inbox_items.get(self.inbox_selected) else {
+            return;
+        };
+        let until = Utc::now() + chrono::Duration::hours(SNOOZE_HOURS);
+        self.inbox_snoozed.insert(item.key.clone(), until);
+        self.refresh_inbox();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn item(key: &str, significance: Significance, minutes_ago: i64) -> InboxItem {
+        InboxItem {
+            key: key.to_string(),
+            kind: InboxKind::Waiting,
+            significance,
+            target: Some(key.to_string()),
+            project: key.to_string(),
+            title: String::new(),
+            since: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            source_id: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_items_by_significance_then_age() {
+        let mut items = vec![
+            item("medium", Significance::Medium, 90),
+            item("high-new", Significance::High, 5),
+            item("critical", Significance::Critical, 1),
+            item("high-old", Significance::High, 60),
+        ];
+        sort_items(&mut items);
+        let keys: Vec<&str> = items.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(keys, vec!["critical", "high-old", "high-new", "medium"]);
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = Utc::now();
+        assert_eq!(format_age(now - chrono::Duration::minutes(5), now), "5m");
+        assert_eq!(format_age(now - chrono::Duration::hours(3), now), "3h");
+        assert_eq!(format_age(now - chrono::Duration::days(2), now), "2d");
+    }
+
+    #[test]
+    fn test_acknowledged_waiting_session_returns_when_waiting_again() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.session_ready_state.insert("api".to_string(), true);
+
+        app.refresh_inbox();
+        let waiting: Vec<_> = app.inbox_items.iter().filter(|i| i.kind == InboxKind::Waiting).collect();
+        assert_eq!(waiting.len(), 1);
+        app.inbox_selected = app.inbox_items.iter().position(|i| i.key == "waiting:api").unwrap();
+        app.inbox_acknowledge_selected();
+        assert!(!app.inbox_items.iter().any(|i| i.key == "waiting:api"));
+
+        // Once the session works again, the next wait shows up
+        app.session_ready_state.insert("api".to_string(), false);
+        app.refresh_inbox();
+        app.session_ready_state.insert("api".to_string(), true);
+        app.refresh_inbox();
+        assert!(app.inbox_items.iter().any(|i| i.key == "waiting:api"));
+
+        app.inbox_selected = app.inbox_items.iter().position(|i| i.key == "waiting:api").unwrap();
+        app.inbox_snooze_selected();
+        assert!(!app.inbox_items.iter().any(|i| i.key == "waiting:api"));
+    }
+}
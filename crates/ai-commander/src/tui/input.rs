@@ -59,8 +59,10 @@ impl App {
             self.command_history.push(input.clone());
         }
 
-        // Handle commands
-        if let Some(cmd) = input.strip_prefix('/') {
+        // Handle snippets and commands
+        if let Some((name, args)) = commander_core::snippets::parse_invocation(&input) {
+            self.run_snippet(name, args);
+        } else if let Some(cmd) = input.strip_prefix('/') {
             self.handle_command(cmd);
         } else if input.starts_with('@') {
             // @ routing syntax
@@ -72,27 +74,11 @@ impl App {
                 .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
             if let Some(fs_cmd) = filesystem::parse_command(&input, &working_dir) {
-                // Execute filesystem command locally
-                let result = filesystem::execute(&fs_cmd, &working_dir);
-                let project = self.project.clone().unwrap_or_default();
-
-                self.messages.push(Message::sent(project.clone(), input.clone()));
-
-                if result.success {
-                    self.messages.push(Message::received(project.clone(), result.message));
-                    if let Some(details) = result.details {
-                        for line in details.lines() {
-                            self.messages.push(Message::received(project.clone(), line.to_string()));
-                        }
-                    }
-                } else {
-                    self.messages.push(Message::system(format!("Error: {}", result.message)));
-                }
-                self.scroll_to_bottom();
+                self.run_fs_command(input, &fs_cmd, &working_dir, false);
             } else {
                 // Send to connected project
                 if let Err(e) = self.send_message(&input) {
-                    self.messages.push(Message::system(format!("Error: {}", e)));
+                    self.messages.push(Message::error(&e));
                 }
             }
         } else {
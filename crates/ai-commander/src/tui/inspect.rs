@@ -1,12 +1,21 @@
 //! Inspect mode for live tmux session viewing.
 
+use std::time::{Duration, Instant};
+
 use super::app::{App, Message, ViewMode};
 
 impl App {
     /// Toggle inspect mode (live tmux view).
     pub fn toggle_inspect_mode(&mut self) {
         match self.view_mode {
-            ViewMode::Normal | ViewMode::Sessions => {
+            ViewMode::Normal
+            | ViewMode::Sessions
+            | ViewMode::Diff
+            | ViewMode::Approvals
+            | ViewMode::Logs
+            | ViewMode::History
+            | ViewMode::Inbox
+            | ViewMode::Improvements => {
                 if self.project.is_some() {
                     self.view_mode = ViewMode::Inspect;
                     self.inspect_scroll = 0;
@@ -27,6 +36,11 @@ impl App {
     pub fn refresh_inspect_content(&mut self) {
         if let (Some(project), Some(tmux)) = (&self.project, &self.tmux) {
             if let Some(session) = self.sessions.get(project) {
+                let interval = tmux.poll_interval(session, Duration::ZERO);
+                if self.last_output_poll.is_some_and(|polled| polled.elapsed() < interval) {
+                    return;
+                }
+                self.last_output_poll = Some(Instant::now());
                 // Capture more lines for full view
                 if let Ok(output) = tmux.capture_output(session, None, Some(200)) {
                     self.inspect_content = output;
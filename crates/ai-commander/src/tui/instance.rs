@@ -0,0 +1,207 @@
+//! Single-instance coordination of local TUIs.
+//!
+//! The first TUI binds `tui.sock` in the runtime state directory and answers
+//! requests on it from a background thread. A second `commander tui` finds
+//! the socket answering and, instead of starting its own pollers, hands its
+//! `--project` over to the running TUI (which connects to it) or refuses
+//! with a message naming the running instance. A socket nobody answers on
+//! was left by a TUI that crashed or re-executed itself, and is taken over.
+//!
+//! `--force-standalone` skips the check; remote TUIs never take part.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::app::{App, Message};
+
+/// Socket of the running TUI, in the runtime state directory.
+pub const SOCKET_FILE: &str = "tui.sock";
+
+/// How long a client waits on the running TUI.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A request to the running TUI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum Request {
+    /// Check that it is alive.
+    Ping,
+    /// Connect to a project.
+    Connect { project: String },
+}
+
+/// Reply of the running TUI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reply {
+    /// Process ID of the running TUI.
+    pub pid: u32,
+}
+
+/// Outcome of [`acquire`].
+pub enum Acquired {
+    /// This process is the running TUI.
+    Primary(InstanceLock),
+    /// Another TUI is running.
+    Running(RunningInstance),
+}
+
+/// Held by the running TUI; removes the socket when dropped.
+pub struct InstanceLock {
+    path: PathBuf,
+    requests: mpsc::Receiver<String>,
+}
+
+impl InstanceLock {
+    /// Next project another invocation asked to connect to, if any.
+    pub fn next_request(&self) -> Option<String> {
+        match self.requests.try_recv() {
+            Ok(project) => Some(project),
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => None,
+        }
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Another TUI, reached through its socket.
+pub struct RunningInstance {
+    path: PathBuf,
+    /// Process ID of the running TUI.
+    pub pid: u32,
+}
+
+impl RunningInstance {
+    /// Ask the running TUI to connect to `project`.
+    pub fn connect(&self, project: &str) -> io::Result<()> {
+        send(&self.path, &Request::Connect { project: project.to_string() }).map(|_| ())
+    }
+}
+
+/// Become the running TUI of `dir`, or find the one already running.
+pub fn acquire(dir: &Path) -> io::Result<Acquired> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(SOCKET_FILE);
+    for _ in 0..2 {
+        match UnixListener::bind(&path) {
+            Ok(listener) => {
+                let (tx, requests) = mpsc::channel();
+                std::thread::spawn(move || serve(listener, tx));
+                return Ok(Acquired::Primary(InstanceLock { path, requests }));
+            }
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse => match send(&path, &Request::Ping) {
+                Ok(reply) => return Ok(Acquired::Running(RunningInstance { path, pid: reply.pid })),
+                // Left behind by a TUI that is gone.
+                Err(_) => std::fs::remove_file(&path)?,
+            },
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AddrInUse,
+        format!("{} is in use", path.display()),
+    ))
+}
+
+/// Answer requests until the TUI stops listening for them.
+fn serve(listener: UnixListener, requests: mpsc::Sender<String>) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        if let Err(e) = handle(stream, &requests) {
+            if e.kind() == io::ErrorKind::BrokenPipe {
+                return;
+            }
+        }
+    }
+}
+
+/// Answer one request; fails when the TUI no longer takes requests.
+fn handle(mut stream: UnixStream, requests: &mpsc::Sender<String>) -> io::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let request: Request = serde_json::from_str(&line)?;
+    if let Request::Connect { project } = request {
+        requests
+            .send(project)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "TUI has stopped"))?;
+    }
+    let reply = serde_json::to_string(&Reply { pid: std::process::id() })?;
+    writeln!(stream, "{}", reply)
+}
+
+/// Send `request` to the TUI listening on `path`.
+fn send(path: &Path, request: &Request) -> io::Result<Reply> {
+    let mut stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+impl App {
+    /// Connect to projects other invocations handed over.
+    pub fn check_instance_requests(&mut self) {
+        let Some(lock) = &self.instance else { return };
+        let Some(project) = lock.next_request() else { return };
+        self.messages.push(Message::system(format!(
+            "Connecting to {} (requested by another commander tui)",
+            project
+        )));
+        if let Err(e) = self.connect(&project) {
+            self.messages.push(Message::error(&e));
+        }
+        self.scroll_to_bottom();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_instance_hands_over_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let Acquired::Primary(lock) = acquire(dir.path()).unwrap() else {
+            panic!("first instance should be primary");
+        };
+        let Acquired::Running(running) = acquire(dir.path()).unwrap() else {
+            panic!("second instance should find the first");
+        };
+        assert_eq!(running.pid, std::process::id());
+
+        running.connect("web").unwrap();
+        assert_eq!(lock.next_request().as_deref(), Some("web"));
+        assert_eq!(lock.next_request(), None);
+
+        drop(lock);
+        assert!(!dir.path().join(SOCKET_FILE).exists());
+    }
+
+    #[test]
+    fn test_stale_socket_is_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        drop(UnixListener::bind(dir.path().join(SOCKET_FILE)).unwrap());
+        assert!(dir.path().join(SOCKET_FILE).exists());
+
+        assert!(matches!(acquire(dir.path()).unwrap(), Acquired::Primary(_)));
+    }
+
+    #[test]
+    fn test_request_wire_format() {
+        let request = Request::Connect { project: "web".to_string() };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"request":"connect","project":"web"}"#);
+        assert_eq!(serde_json::from_str::<Request>(r#"{"request":"ping"}"#).unwrap(), Request::Ping);
+    }
+}
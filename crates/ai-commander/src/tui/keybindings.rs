@@ -0,0 +1,361 @@
+//! Configurable keybindings.
+//!
+//! Every global action can be remapped in `config.toml` (`[tui.keys]`, or
+//! its older name `[tui.keybindings]`); `"none"` unbinds an action:
+//!
+//! ```toml
+//! [tui.keys]
+//! inspect = "f4"
+//! sessions = "ctrl+s"
+//! logs = "f6"
+//! yank = "alt+y"
+//! clear = "none"
+//! ```
+//!
+//! Invalid bindings stop the TUI from starting; later changes are picked up
+//! while it runs (see `App::check_config_changes`). Ctrl+C always quits and
+//! cannot be rebound. `/keys` lists the bindings in effect.
+
+use std::collections::BTreeMap;
+
+use commander_core::TuiSettings;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A key with modifiers, e.g. `ctrl+l` or `f2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Parse `[ctrl+][alt+][shift+]<key>`, where key is a character, `f1`-`f12`,
+    /// `esc`, `tab`, `home`, `end`, `pageup`, `pagedown` or `insert`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let mut parts: Vec<&str> = text.split('+').collect();
+        let key = parts.pop()?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in parts {
+            modifiers |= match part {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+
+        let code = match key {
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "insert" => KeyCode::Insert,
+            f if f.len() > 1 && f.starts_with('f') => match f[1..].parse::<u8>() {
+                Ok(n @ 1..=12) => KeyCode::F(n),
+                _ => return None,
+            },
+            c if c.chars().count() == 1 => KeyCode::Char(c.chars().next()?),
+            _ => return None,
+        };
+
+        // Plain characters are typed into the input line
+        if matches!(code, KeyCode::Char(_)) && !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return None;
+        }
+        // Ctrl+C is reserved for quitting
+        if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+            return None;
+        }
+
+        Some(Self::new(code, modifiers))
+    }
+
+    /// Whether `key` is this binding.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code_matches = match (key.code, self.code) {
+            (KeyCode::Char(a), KeyCode::Char(b)) => a.eq_ignore_ascii_case(&b),
+            (a, b) => a == b,
+        };
+        code_matches && modifiers == self.modifiers
+    }
+
+    /// Short label for footers, e.g. `F2` or `Ctrl+L`.
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                label.push_str(name);
+            }
+        }
+        match self.code {
+            KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+            KeyCode::Char(c) => label.push(c.to_ascii_uppercase()),
+            KeyCode::Esc => label.push_str("Esc"),
+            KeyCode::Tab => label.push_str("Tab"),
+            KeyCode::Home => label.push_str("Home"),
+            KeyCode::End => label.push_str("End"),
+            KeyCode::PageUp => label.push_str("PgUp"),
+            KeyCode::PageDown => label.push_str("PgDn"),
+            KeyCode::Insert => label.push_str("Ins"),
+            _ => label.push('?'),
+        }
+        label
+    }
+}
+
+/// Value that leaves an action without a key.
+const UNBOUND: &str = "none";
+
+/// Actions bound to global keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyAction {
+    /// Toggle inspect mode.
+    Inspect,
+    /// Toggle the sessions view.
+    Sessions,
+    /// Clear the output.
+    Clear,
+    /// Show the command help.
+    Help,
+    /// List the keybindings.
+    Keys,
+    /// Open the log viewer.
+    Logs,
+    /// Open the approvals pane.
+    Approvals,
+    /// Show uncommitted changes.
+    Diff,
+    /// Open the inbox.
+    Inbox,
+    /// Copy the message at the bottom of the output to the clipboard.
+    Yank,
+}
+
+impl KeyAction {
+    /// Every action, in the order `/keys` lists them.
+    pub const ALL: [KeyAction; 10] = [
+        KeyAction::Inspect,
+        KeyAction::Sessions,
+        KeyAction::Clear,
+        KeyAction::Help,
+        KeyAction::Keys,
+        KeyAction::Logs,
+        KeyAction::Approvals,
+        KeyAction::Diff,
+        KeyAction::Inbox,
+        KeyAction::Yank,
+    ];
+
+    /// Name of the action in `[tui.keys]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyAction::Inspect => "inspect",
+            KeyAction::Sessions => "sessions",
+            KeyAction::Clear => "clear",
+            KeyAction::Help => "help",
+            KeyAction::Keys => "keys",
+            KeyAction::Logs => "logs",
+            KeyAction::Approvals => "approvals",
+            KeyAction::Diff => "diff",
+            KeyAction::Inbox => "inbox",
+            KeyAction::Yank => "yank",
+        }
+    }
+
+    /// What the action does, for `/keys`.
+    pub fn description(self) -> &'static str {
+        match self {
+            KeyAction::Inspect => "Inspect mode (live tmux)",
+            KeyAction::Sessions => "Session picker",
+            KeyAction::Clear => "Clear output",
+            KeyAction::Help => "Command help",
+            KeyAction::Keys => "List keybindings",
+            KeyAction::Logs => "Log viewer",
+            KeyAction::Approvals => "Approvals pane",
+            KeyAction::Diff => "Uncommitted changes",
+            KeyAction::Inbox => "Inbox across projects",
+            KeyAction::Yank => "Copy message to clipboard",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    fn default_binding(self) -> Option<KeyBinding> {
+        match self {
+            KeyAction::Inspect => Some(KeyBinding::new(KeyCode::F(2), KeyModifiers::NONE)),
+            KeyAction::Sessions => Some(KeyBinding::new(KeyCode::F(3), KeyModifiers::NONE)),
+            KeyAction::Clear => Some(KeyBinding::new(KeyCode::Char('l'), KeyModifiers::CONTROL)),
+            KeyAction::Inbox => Some(KeyBinding::new(KeyCode::F(5), KeyModifiers::NONE)),
+            KeyAction::Yank => Some(KeyBinding::new(KeyCode::Char('y'), KeyModifiers::CONTROL)),
+            _ => None,
+        }
+    }
+}
+
+/// Key per action; actions without a key are unbound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keybindings {
+    keys: BTreeMap<KeyAction, KeyBinding>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            keys: KeyAction::ALL
+                .into_iter()
+                .filter_map(|action| Some((action, action.default_binding()?)))
+                .collect(),
+        }
+    }
+}
+
+impl Keybindings {
+    /// Build keybindings from `[tui.keys]`, starting from the defaults.
+    ///
+    /// Returns the problems found; invalid entries keep the default key, and
+    /// of two actions sharing a key the first listed by `/keys` gets it.
+    pub fn from_settings(settings: &TuiSettings) -> (Self, Vec<String>) {
+        let mut bindings = Self::default();
+        let mut problems = Vec::new();
+
+        for (name, key) in &settings.keybindings {
+            let Some(action) = KeyAction::from_name(name) else {
+                let names: Vec<&str> = KeyAction::ALL.iter().map(|a| a.name()).collect();
+                problems.push(format!(
+                    "unknown keybinding action '{}' (expected one of: {})",
+                    name,
+                    names.join(", ")
+                ));
+                continue;
+            };
+            if key.trim().eq_ignore_ascii_case(UNBOUND) {
+                bindings.keys.remove(&action);
+                continue;
+            }
+            match KeyBinding::parse(key) {
+                Some(binding) => {
+                    bindings.keys.insert(action, binding);
+                }
+                None => problems.push(format!(
+                    "invalid key '{}' for {} (expected e.g. \"f4\", \"ctrl+k\" or \"{}\")",
+                    key, name, UNBOUND
+                )),
+            }
+        }
+
+        for (i, (first, key)) in bindings.keys.iter().enumerate() {
+            for (second, other) in bindings.keys.iter().skip(i + 1) {
+                if key == other {
+                    problems.push(format!(
+                        "{} is bound to both {} and {}",
+                        key.label(),
+                        first.name(),
+                        second.name()
+                    ));
+                }
+            }
+        }
+
+        (bindings, problems)
+    }
+
+    /// The key bound to `action`, if any.
+    pub fn get(&self, action: KeyAction) -> Option<KeyBinding> {
+        self.keys.get(&action).copied()
+    }
+
+    /// Footer label of the key bound to `action`, if any.
+    pub fn label(&self, action: KeyAction) -> Option<String> {
+        self.get(action).map(|key| key.label())
+    }
+
+    /// The action bound to `key`, if any.
+    pub fn action(&self, key: &KeyEvent) -> Option<KeyAction> {
+        self.keys
+            .iter()
+            .find(|(_, binding)| binding.matches(key))
+            .map(|(action, _)| *action)
+    }
+
+    /// Chords for the footer, e.g. `Ctrl+L clear`, skipping `except`.
+    pub fn chords(&self, except: &[KeyAction]) -> Vec<String> {
+        self.keys
+            .iter()
+            .filter(|(action, _)| !except.contains(action))
+            .map(|(action, key)| format!("{} {}", key.label(), action.name()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(KeyBinding::parse("F4").unwrap().label(), "F4");
+        assert_eq!(KeyBinding::parse("ctrl+s").unwrap().label(), "Ctrl+S");
+        assert_eq!(KeyBinding::parse("alt+pagedown").unwrap().label(), "Alt+PgDn");
+        assert!(KeyBinding::parse("f13").is_none());
+        assert!(KeyBinding::parse("x").is_none());
+        assert!(KeyBinding::parse("ctrl+c").is_none());
+        assert!(KeyBinding::parse("hyper+x").is_none());
+    }
+
+    #[test]
+    fn test_keybindings_from_settings() {
+        let mut settings = TuiSettings::default();
+        settings.keybindings.insert("inspect".into(), "f4".into());
+        settings.keybindings.insert("sessions".into(), "q".into());
+        settings.keybindings.insert("quit".into(), "f10".into());
+
+        let (bindings, problems) = Keybindings::from_settings(&settings);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(bindings.get(KeyAction::Sessions), Keybindings::default().get(KeyAction::Sessions));
+
+        let f4 = KeyEvent::new(KeyCode::F(4), KeyModifiers::NONE);
+        let f2 = KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE);
+        assert_eq!(bindings.action(&f4), Some(KeyAction::Inspect));
+        assert_eq!(bindings.action(&f2), None);
+    }
+
+    #[test]
+    fn test_keybindings_remap_and_unbind() {
+        let mut settings = TuiSettings::default();
+        settings.keybindings.insert("logs".into(), "f6".into());
+        settings.keybindings.insert("clear".into(), "none".into());
+
+        let (bindings, problems) = Keybindings::from_settings(&settings);
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(bindings.label(KeyAction::Logs).as_deref(), Some("F6"));
+        assert_eq!(bindings.get(KeyAction::Clear), None);
+        assert_eq!(bindings.get(KeyAction::Diff), None);
+        assert_eq!(bindings.chords(&[KeyAction::Inspect]), vec!["F3 sessions", "F6 logs", "F5 inbox", "Ctrl+Y yank"]);
+    }
+
+    #[test]
+    fn test_keybindings_conflict() {
+        let mut settings = TuiSettings::default();
+        settings.keybindings.insert("diff".into(), "f2".into());
+
+        let (bindings, problems) = Keybindings::from_settings(&settings);
+        assert_eq!(problems, vec!["F2 is bound to both inspect and diff"]);
+        let f2 = KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE);
+        assert_eq!(bindings.action(&f2), Some(KeyAction::Inspect));
+    }
+}
@@ -0,0 +1,93 @@
+//! Review list of shared project knowledge (feature-gated).
+//!
+//! Session Agents promote facts that hold for the whole project with their
+//! `promote_memory` tool. `/knowledge` lists what was promoted for the
+//! connected session's project, newest unreviewed first, with provenance;
+//! `/knowledge keep <id>` and `/knowledge drop <id>` review an entry.
+
+use super::app::{App, Message};
+
+/// Characters of an entry ID shown in the review list.
+const SHORT_ID_LEN: usize = 8;
+
+impl App {
+    /// Handle `/knowledge [keep|drop <id>]`.
+    pub fn handle_knowledge_command(&mut self, arg: Option<&str>) {
+        let mut parts = arg.unwrap_or("").split_whitespace();
+        match (parts.next(), parts.next()) {
+            (None, _) => self.show_knowledge(),
+            (Some("keep"), Some(id)) => self.review_knowledge(id, true),
+            (Some("drop"), Some(id)) => self.review_knowledge(id, false),
+            _ => self
+                .messages
+                .push(Message::system("Usage: /knowledge [keep|drop <id>]")),
+        }
+    }
+
+    /// List the connected project's knowledge.
+    fn show_knowledge(&mut self) {
+        let Some(project) = self.current_session_name() else {
+            self.messages.push(Message::system("Connect to a project first"));
+            return;
+        };
+        let (Some(handle), Some(orchestrator)) = (self.runtime_handle.clone(), self.orchestrator.as_ref()) else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        let entries = match handle.block_on(orchestrator.project_knowledge(&project)) {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Failed to read project knowledge: {}", e)));
+                return;
+            }
+        };
+        if entries.is_empty() {
+            self.messages.push(Message::system(format!("No shared knowledge for {} yet", project)));
+            return;
+        }
+
+        let unreviewed = entries.iter().filter(|e| !e.reviewed).count();
+        self.messages.push(Message::system(format!(
+            "Project knowledge for {} ({} awaiting review):",
+            project, unreviewed
+        )));
+        for entry in &entries {
+            let mark = if entry.reviewed { "[x]" } else { "[ ]" };
+            let short_id: String = entry.id.chars().take(SHORT_ID_LEN).collect();
+            self.messages.push(Message::system(format!("  {} {} {}", mark, short_id, entry.content)));
+            let mut provenance = format!(
+                "      from {} on {}",
+                entry.source_session,
+                entry.promoted_at.format("%Y-%m-%d %H:%M")
+            );
+            if let Some(reason) = &entry.reason {
+                provenance.push_str(&format!(": {}", reason));
+            }
+            self.messages.push(Message::system(provenance));
+        }
+        if unreviewed > 0 {
+            self.messages.push(Message::system("Review with /knowledge keep <id> or /knowledge drop <id>"));
+        }
+    }
+
+    /// Keep or drop a knowledge entry.
+    fn review_knowledge(&mut self, id: &str, keep: bool) {
+        let Some(project) = self.current_session_name() else {
+            self.messages.push(Message::system("Connect to a project first"));
+            return;
+        };
+        let (Some(handle), Some(orchestrator)) = (self.runtime_handle.clone(), self.orchestrator.as_ref()) else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        let message = match handle.block_on(orchestrator.review_project_knowledge(&project, id, keep)) {
+            Ok(Some(entry)) if keep => format!("Kept: {}", entry.content),
+            Ok(Some(entry)) => format!("Dropped: {}", entry.content),
+            Ok(None) => format!("No single knowledge entry matches '{}'", id),
+            Err(e) => format!("Failed to review knowledge: {}", e),
+        };
+        self.messages.push(Message::system(message));
+    }
+}
@@ -0,0 +1,94 @@
+//! Log viewer for the structured session logs.
+//!
+//! `/logs [level]` shows the connected project's session log (or the
+//! combined log of every component when not connected) and keeps
+//! appending new lines while open.
+
+use commander_core::{combined_log_path, config, read_log, session_log_path, LogTail};
+use tracing::Level;
+
+use super::app::{App, Message, ViewMode};
+
+/// Most recent log lines kept in the viewer.
+const MAX_LOG_LINES: usize = 1000;
+
+impl App {
+    /// Open the log viewer for `/logs [level]`.
+    pub fn show_logs(&mut self, level: Option<&str>) {
+        let level = match level {
+            None => Level::INFO,
+            Some(level) => match level.parse::<Level>() {
+                Ok(level) => level,
+                Err(_) => {
+                    self.messages.push(Message::system(format!(
+                        "Unknown level '{}'. Usage: /logs [error|warn|info|debug|trace]",
+                        level
+                    )));
+                    return;
+                }
+            },
+        };
+
+        let logs_dir = config::logs_dir();
+        let (name, path) = match &self.project {
+            Some(project) => (project.clone(), session_log_path(&logs_dir, project)),
+            None => ("all components".to_string(), combined_log_path(&logs_dir)),
+        };
+
+        let tail = LogTail::new(&path, level);
+        match read_log(&path, level) {
+            Ok(records) => {
+                let skip = records.len().saturating_sub(MAX_LOG_LINES);
+                self.log_lines = records.iter().skip(skip).map(|r| r.format_line()).collect();
+                self.log_title = format!("{} ({} and above)", name, level);
+                self.log_tail = Some(tail);
+                self.log_scroll = 0;
+                self.view_mode = ViewMode::Logs;
+            }
+            Err(e) => self
+                .messages
+                .push(Message::system(format!("Failed to read {}: {}", path.display(), e))),
+        }
+    }
+
+    /// Leave the log viewer.
+    pub fn close_logs(&mut self) {
+        if self.view_mode == ViewMode::Logs {
+            self.view_mode = ViewMode::Normal;
+            self.log_tail = None;
+        }
+    }
+
+    /// Append lines written since the last tick while the viewer is open.
+    ///
+    /// Called on every event loop tick.
+    pub fn check_log_tail(&mut self) {
+        if self.view_mode != ViewMode::Logs {
+            return;
+        }
+        let Some(tail) = self.log_tail.as_mut() else { return };
+        let Ok(records) = tail.poll() else { return };
+        if records.is_empty() {
+            return;
+        }
+
+        // Keep the lines in view steady when scrolled back
+        if self.log_scroll > 0 {
+            self.log_scroll += records.len();
+        }
+        self.log_lines.extend(records.iter().map(|r| r.format_line()));
+        let excess = self.log_lines.len().saturating_sub(MAX_LOG_LINES);
+        self.log_lines.drain(..excess);
+        self.log_scroll = self.log_scroll.min(self.log_lines.len());
+    }
+
+    /// Scroll back (up) in the log viewer.
+    pub fn log_scroll_up(&mut self, lines: usize) {
+        self.log_scroll = self.log_scroll.saturating_add(lines).min(self.log_lines.len());
+    }
+
+    /// Scroll forward (down) in the log viewer; at the bottom new lines follow.
+    pub fn log_scroll_down(&mut self, lines: usize) {
+        self.log_scroll = self.log_scroll.saturating_sub(lines);
+    }
+}
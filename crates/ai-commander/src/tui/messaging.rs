@@ -2,39 +2,68 @@
 //!
 //! Contains methods for sending messages, polling output,
 //! and handling summarization of responses.
+//!
+//! While a long response streams in, its output is folded into a running
+//! summary every `FOLD_LINES` lines, so the final summary only sends that
+//! summary and the output after it.
 
-use std::sync::mpsc;
-use std::time::Instant;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::{Duration, Instant};
 
-use commander_core::{find_new_lines, is_claude_ready, summarize_blocking_with_fallback};
+use commander_core::{
+    acquire_send_lock, find_new_lines, is_claude_ready, summarize_blocking_with_fallback, summarize_differential_blocking,
+    CommanderError,
+};
 
 use super::app::{App, Message};
+#[cfg(feature = "agents")]
+use super::diff::CHANGED_FILE_PREFIX;
 use super::options::OptionDetector;
 
+/// New response lines that trigger a background update of the running summary.
+const FOLD_LINES: usize = 80;
+
 impl App {
     /// Send a message to the connected project.
-    pub fn send_message(&mut self, message: &str) -> Result<(), String> {
+    pub fn send_message(&mut self, message: &str) -> Result<(), CommanderError> {
+        self.checkpoint_before_task(message);
+
         let project = self.project.as_ref()
-            .ok_or_else(|| "Not connected to any project".to_string())?;
+            .ok_or_else(|| CommanderError::Runtime("Not connected to any project".to_string()))?;
 
         let session = self.sessions.get(project)
-            .ok_or_else(|| "Session not found".to_string())?;
+            .ok_or_else(|| CommanderError::Runtime("Session not found".to_string()))?;
 
         let tmux = self.tmux.as_ref()
-            .ok_or_else(|| "Tmux not available".to_string())?;
+            .ok_or_else(|| CommanderError::Tmux("Tmux not available".to_string()))?;
 
         // Capture initial output for comparison
         self.last_output = tmux.capture_output(session, None, Some(200))
             .unwrap_or_default();
 
-        // Send the message
+        // Send the message, after any other frontend typing into the session
+        let lock = acquire_send_lock(session, "TUI")
+            .map_err(|e| CommanderError::Runtime(e.to_string()))?;
         tmux.send_line(session, None, message)
-            .map_err(|e| format!("Failed to send: {}", e))?;
+            .map_err(|e| CommanderError::Tmux(format!("Failed to send: {}", e)))?;
+        let waited_for = lock.waited_for().map(String::from);
+        drop(lock);
+
+        self.record_handoff_query(session, message);
+        self.mark_session_seen();
 
         // Add sent message to output and reset response collection
         self.messages.push(Message::sent(project.clone(), message));
+        if let Some(frontend) = waited_for {
+            self.messages.push(Message::system(format!(
+                "{} was sending to this session; your message went after it",
+                frontend
+            )));
+        }
         self.pending_query = Some(message.to_string());
         self.response_buffer.clear();
+        self.diff_summary.reset();
+        self.fold_rx = None;
         self.last_activity = Some(Instant::now());
         self.is_working = true;
         self.is_summarizing = false;
@@ -47,9 +76,17 @@ impl App {
     /// Poll for new output from tmux and trigger summarization when idle.
     pub fn poll_output(&mut self) {
         // Check for summarization results first
+        self.check_summary_fold();
         if let Some(rx) = &self.summarizer_rx {
-            if let Ok(summary) = rx.try_recv() {
+            if let Ok((request, summary)) = rx.try_recv() {
                 // Got summary result
+                if let Some(request) = request {
+                    self.diff_summary.apply(&request, summary.clone());
+                }
+                self.diff_summary.reset();
+                self.record_handoff_response(&summary);
+                let image_text = format!("{}\n{}", self.response_buffer.join("\n"), summary);
+                self.mark_session_seen();
                 if let Some(project) = &self.project {
                     self.messages.push(Message::received(project.clone(), summary.clone()));
 
@@ -58,6 +95,9 @@ impl App {
                         self.enter_option_mode(detected_options);
                     }
                 }
+                self.show_image_links(&image_text);
+                #[cfg(feature = "agents")]
+                self.show_diagnosis_card();
                 self.summarizer_rx = None;
                 self.is_summarizing = false;
                 self.is_working = false;
@@ -79,6 +119,15 @@ impl App {
         let Some(project) = &self.project else { return };
         let Some(session) = self.sessions.get(project) else { return };
         let Some(tmux) = &self.tmux else { return };
+        if self.paused_sessions.contains(session) {
+            return;
+        }
+        // Sessions on slow SSH hosts are polled less often than every tick
+        let interval = tmux.poll_interval(session, Duration::ZERO);
+        if self.last_output_poll.is_some_and(|polled| polled.elapsed() < interval) {
+            return;
+        }
+        self.last_output_poll = Some(Instant::now());
 
         // Capture current output
         let current_output = match tmux.capture_output(session, None, Some(200)) {
@@ -97,6 +146,7 @@ impl App {
             }
             self.last_output = current_output.clone();
             self.last_activity = Some(Instant::now());
+            self.fold_summary_if_due();
 
             // Check for options in the raw output (immediate detection)
             // This allows detecting options without waiting for summarization
@@ -145,18 +195,74 @@ impl App {
         {
             if let Some(summary) = self.try_orchestrator_analysis(&raw_response) {
                 // Got synchronous result from orchestrator
-                let _ = tx.send(summary);
+                let _ = tx.send((None, summary));
                 return;
             }
         }
 
-        // Fallback: Spawn thread for blocking HTTP call
+        // Fallback: Spawn thread for blocking HTTP call, sending only the output
+        // the running summary does not cover yet
+        self.fold_rx = None;
+        let request = self.diff_summary.request(&self.response_buffer);
+        std::thread::spawn(move || {
+            let differential = request
+                .and_then(|request| summarize_differential_blocking(&query, &request).ok().map(|s| (request, s)));
+            let result = match differential {
+                Some((request, summary)) => (Some(request), summary),
+                None => (None, summarize_blocking_with_fallback(&query, &raw_response)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Whether responses go to the summarizer directly rather than to the
+    /// agent orchestrator, which keeps its own context.
+    fn summarizes_directly(&self) -> bool {
+        #[cfg(feature = "agents")]
+        if self.orchestrator.is_some() {
+            return false;
+        }
+        true
+    }
+
+    /// Fold the output not yet covered into the running summary in the
+    /// background, once `FOLD_LINES` new lines have come in.
+    fn fold_summary_if_due(&mut self) {
+        if self.fold_rx.is_some()
+            || !self.summarizes_directly()
+            || self.diff_summary.pending_lines(&self.response_buffer) < FOLD_LINES
+        {
+            return;
+        }
+        let Some(request) = self.diff_summary.request(&self.response_buffer) else {
+            return;
+        };
+        let query = self.pending_query.clone().unwrap_or_default();
+
+        let (tx, rx) = mpsc::channel();
+        self.fold_rx = Some(rx);
         std::thread::spawn(move || {
-            let summary = summarize_blocking_with_fallback(&query, &raw_response);
-            let _ = tx.send(summary);
+            let summary = summarize_differential_blocking(&query, &request)
+                .map_err(|e| tracing::debug!(error = %e, "Running summary update failed"))
+                .ok();
+            let _ = tx.send((request, summary));
         });
     }
 
+    /// Record a finished background update of the running summary.
+    fn check_summary_fold(&mut self) {
+        let Some(rx) = &self.fold_rx else { return };
+        match rx.try_recv() {
+            Ok((request, Some(summary))) => {
+                self.diff_summary.apply(&request, summary);
+                self.fold_rx = None;
+            }
+            // Failed updates are retried with the next batch of output
+            Ok((_, None)) | Err(TryRecvError::Disconnected) => self.fold_rx = None,
+            Err(TryRecvError::Empty) => {}
+        }
+    }
+
     /// Try to analyze output using the agent orchestrator.
     ///
     /// Returns Some(summary) if orchestrator analysis succeeded, None to fall back.
@@ -166,22 +272,28 @@ impl App {
         // Need runtime handle for async operation
         let handle = self.runtime_handle.as_ref()?.clone();
 
-        // Need orchestrator
-        let orchestrator = self.orchestrator.as_mut()?;
-
         // Get session info for the orchestrator
-        let session_name = self.project.as_ref()
-            .and_then(|p| self.sessions.get(p))?
-            .clone();
+        let project = self.project.clone()?;
+        let session_name = self.sessions.get(&project)?.clone();
 
-        // Determine adapter type from project config (default to claude_code)
-        let adapter_type = "claude_code";
+        // Determine adapter type from project config (default to claude-code)
+        let adapter_type = self.project_tool_id(&project);
+
+        // Need orchestrator
+        let orchestrator = self.orchestrator.as_mut()?;
 
         // Run async analysis synchronously
         // This blocks briefly but provides LLM-based semantic understanding
         let output = output.to_string();
-        match handle.block_on(orchestrator.process_session_output(&session_name, adapter_type, &output)) {
-            Ok(analysis) => {
+        match handle.block_on(orchestrator.process_session_output(&session_name, &adapter_type, &output)) {
+            Ok(mut analysis) => {
+                self.notify_budget_exhausted(&session_name);
+
+                // Show the suggested fix for an error after the summary
+                if let Some(diagnosis) = analysis.diagnosis.take() {
+                    self.diagnosis_card = Some(diagnosis);
+                }
+
                 // Build summary from OutputAnalysis
                 let mut summary = analysis.summary.clone();
 
@@ -211,7 +323,12 @@ impl App {
                     if !summary.is_empty() {
                         summary.push_str("\n\n");
                     }
-                    summary.push_str(&format!("Files changed: {}", analysis.files_changed.join(", ")));
+                    summary.push_str("Files changed:");
+                    for file in &analysis.files_changed {
+                        summary.push('\n');
+                        summary.push_str(CHANGED_FILE_PREFIX);
+                        summary.push_str(file);
+                    }
                 }
 
                 Some(summary)
@@ -229,6 +346,8 @@ impl App {
         self.is_summarizing = false;
         self.progress = 0.0;
         self.response_buffer.clear();
+        self.diff_summary.reset();
+        self.fold_rx = None;
         self.pending_query = None;
     }
 
@@ -1,27 +1,98 @@
 //! Terminal User Interface for Commander.
 //!
 //! Provides a multi-pane TUI with:
-//! - Header showing project name and connection status
+//! - Header showing project name, connection status and changed-file count
 //! - Scrollable output area for messages
 //! - Status bar with working indicator
 //! - Input area for commands/messages
-//! - Footer with keybindings
+//! - Footer with the bound keys and clickable inspect/sessions buttons
+//! - Mouse support: click sessions and message headers, scroll wheel
+//! - Diff view of the connected project's uncommitted changes (`/diff`)
+//! - Session recording to asciicast files (`/record start|stop`)
+//! - Modal for escalated blocking events, acknowledged with `/ack`
+//! - Approvals pane for tool calls queued by approval mode (`/approvals`)
+//! - Inbox of what needs attention across all projects (F5, `/inbox`)
+//! - Review pane for prompt patches proposed from feedback (`/improvements`)
+//! - Log viewer following the connected session's structured log (`/logs`)
+//! - Search over transcripts and memories, opened in a history view (`/search`, `/history`)
+//! - Task plans from the User Agent, approved and run with `/plan`
+//! - Suggested fixes for errors diagnosed by Session Agents, applied with `/fix`
+//! - Review list of knowledge promoted by Session Agents (`/knowledge`)
+//! - Read-only sessions for asking about a project (`/connect <name> --read-only`)
+//! - Step-by-step wizard for creating a project (`/connect` without arguments)
+//! - Session snapshots, relaunched after a reboot with `/restore`
+//! - "Since you left" digests when connecting to a session after a while
+//! - One local TUI at a time; `commander tui --project` hands the project to the running one
+//! - Orphaned tmux sessions, PID files and worktrees reported or removed at startup
+//! - Filesystem commands confined to the project, protected paths confirmed
+//! - Workspaces grouping projects, scoping list/status/`@all` (`/workspace`)
+//! - Keybindings from `[tui.keys]` in `config.toml`, reloaded while running (`/keys`)
+//! - Color themes from `[tui.theme]` or `~/.ai-commander/themes/`, switched with `/theme`
+//! - Inline images via the kitty or iTerm2 graphics protocols (`/view`), with a text fallback
+//! - Copying responses and diffs to the clipboard, over SSH through OSC 52 (`/copy`, Ctrl+Y)
+//! - Switching a running project to another adapter, keeping its context (`/adapter use`)
+//! - Scratchpad notes on a session, read by its agent on the next analysis (`/note`, `/notes`)
+//! - Named prompt snippets with project variables (`/run`, `!name`, `/snippets`)
+//! - Re-running failing cargo test, pytest, Jest and go test tests (`/retest`)
+//! - Sessions on remote hosts over SSH, configured per project (`/host`)
 
+mod adapter;
 mod agents;
 mod app;
+mod approvals;
+mod bot_health;
+mod budget;
+mod clipboard;
 mod commands;
 mod completion;
 mod connection;
+mod desktop;
+#[cfg(feature = "agents")]
+mod diagnosis;
+mod diff;
+mod escalation;
 mod events;
+mod files;
+mod filesystem;
+mod gc;
 mod git;
+mod handoff;
 mod helpers;
+mod history;
+mod hosts;
+mod improvements;
+mod inbox;
 mod input;
 mod inspect;
+mod instance;
+mod keybindings;
+#[cfg(feature = "agents")]
+mod knowledge;
+mod logs;
 mod messaging;
+mod mouse;
+mod notes;
 mod options;
+mod pause;
+#[cfg(feature = "agents")]
+mod plan;
+mod read_only;
+mod recap;
+mod recording;
+mod restore;
+mod retest;
 mod scroll;
 mod sessions;
+mod settings;
+mod snippets;
+mod theme;
 mod ui;
+mod undo;
+mod view;
+mod watchdog;
+mod wizard;
+mod work;
+mod workspace;
 
 pub use app::{App, ClickAction, ClickableItem, Message, MessageDirection, SessionInfo, ViewMode};
 pub use events::run;
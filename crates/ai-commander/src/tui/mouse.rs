@@ -0,0 +1,210 @@
+//! Mouse handling for the TUI.
+//!
+//! Clickable regions are registered by the renderer each frame (see
+//! `ClickableItem`); clicks are resolved against them here. The scroll
+//! wheel scrolls whichever pane the current view shows.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use super::app::{App, ClickAction, Message, ViewMode};
+
+/// Lines scrolled per wheel notch.
+const WHEEL_LINES: usize = 3;
+
+impl App {
+    /// Handle a mouse event from the terminal.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.handle_click(mouse.column, mouse.row),
+            MouseEventKind::ScrollUp => self.handle_wheel(true, mouse.column, mouse.row),
+            MouseEventKind::ScrollDown => self.handle_wheel(false, mouse.column, mouse.row),
+            _ => {}
+        }
+    }
+
+    /// Handle a mouse click at the given screen coordinates.
+    ///
+    /// Iterates through registered clickable items and executes the
+    /// corresponding action if the click falls within a clickable region.
+    pub fn handle_click(&mut self, x: u16, y: u16) {
+        // Find the first clickable item containing this point
+        let action = self.clickable_items.iter()
+            .find(|item| item.contains(x, y))
+            .map(|item| item.action.clone());
+
+        // Any click closes an open image
+        if self.image_view.is_some() {
+            self.close_image_view();
+            return;
+        }
+
+        let Some(action) = action else {
+            return;
+        };
+
+        match action {
+            ClickAction::Connect(session_name) => {
+                if let Err(e) = self.connect(&session_name) {
+                    self.messages.push(Message::error(&e));
+                }
+            }
+            ClickAction::SelectSession(index) => {
+                if index == self.session_selected {
+                    self.connect_selected_session();
+                } else if index < self.session_list.len() {
+                    self.session_selected = index;
+                }
+            }
+            ClickAction::ToggleMessage(index) => self.toggle_message_collapsed(index),
+            ClickAction::ToggleInspect => self.toggle_inspect_mode(),
+            ClickAction::ToggleSessions => self.toggle_sessions_view(),
+            ClickAction::ShowDiff(file) => self.show_diff(file.as_deref()),
+            ClickAction::CloseDiff => self.close_diff(),
+            ClickAction::ViewImage(path) => self.handle_view(Some(&path)),
+            ClickAction::CloseLogs => self.close_logs(),
+            ClickAction::CloseHistory => self.close_history(),
+            ClickAction::CloseApprovals => self.close_approvals(),
+            ClickAction::CloseInbox => self.close_inbox(),
+            ClickAction::CloseImprovements => self.close_improvements(),
+            #[cfg(feature = "agents")]
+            ClickAction::ApplyDiagnosis => self.apply_diagnosis(),
+            #[cfg(feature = "agents")]
+            ClickAction::IgnoreDiagnosis => self.ignore_diagnosis(),
+            #[cfg(not(feature = "agents"))]
+            ClickAction::ApplyDiagnosis | ClickAction::IgnoreDiagnosis => {}
+        }
+    }
+
+    /// Collapse or expand a message.
+    pub fn toggle_message_collapsed(&mut self, index: usize) {
+        if let Some(msg) = self.messages.get_mut(index) {
+            msg.collapsed = !msg.collapsed;
+        }
+    }
+
+    /// Scroll the pane for the current view.
+    ///
+    /// In chat mode only wheel events over the output area scroll it.
+    fn handle_wheel(&mut self, up: bool, x: u16, y: u16) {
+        match self.view_mode {
+            ViewMode::Normal => {
+                let over_output = self.output_area.is_none_or(|area| {
+                    area.x <= x && x < area.x + area.width && area.y <= y && y < area.y + area.height
+                });
+                if !over_output {
+                    return;
+                }
+                for _ in 0..WHEEL_LINES {
+                    if up {
+                        self.scroll_up();
+                    } else {
+                        self.scroll_down();
+                    }
+                }
+            }
+            ViewMode::Inspect => {
+                if up {
+                    self.inspect_scroll_page_up(WHEEL_LINES);
+                } else {
+                    self.inspect_scroll_page_down(WHEEL_LINES);
+                }
+            }
+            ViewMode::Sessions => {
+                if up {
+                    self.session_select_up();
+                } else {
+                    self.session_select_down();
+                }
+            }
+            ViewMode::Approvals => {
+                if up {
+                    self.approval_select_up();
+                } else {
+                    self.approval_select_down();
+                }
+            }
+            ViewMode::Improvements => {
+                if up {
+                    self.improvement_select_up();
+                } else {
+                    self.improvement_select_down();
+                }
+            }
+            ViewMode::Inbox => {
+                if up {
+                    self.inbox_select_up();
+                } else {
+                    self.inbox_select_down();
+                }
+            }
+            ViewMode::Diff => {
+                if up {
+                    self.diff_scroll_up(WHEEL_LINES);
+                } else {
+                    self.diff_scroll_down(WHEEL_LINES);
+                }
+            }
+            ViewMode::Logs => {
+                if up {
+                    self.log_scroll_up(WHEEL_LINES);
+                } else {
+                    self.log_scroll_down(WHEEL_LINES);
+                }
+            }
+            ViewMode::History => {
+                if up {
+                    self.history_scroll_up(WHEEL_LINES);
+                } else {
+                    self.history_scroll_down(WHEEL_LINES);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::layout::Rect;
+    use tempfile::tempdir;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+    }
+
+    #[test]
+    fn test_click_toggles_message() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.messages.push(Message::received("proj", "line\n".repeat(20)));
+        let index = app.messages.len() - 1;
+        app.add_clickable_item(Rect { x: 1, y: 3, width: 40, height: 1 }, ClickAction::ToggleMessage(index));
+
+        app.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3));
+        assert!(app.messages[index].collapsed);
+        app.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3));
+        assert!(!app.messages[index].collapsed);
+
+        // Clicks outside any region are ignored
+        app.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 10));
+        assert!(!app.messages[index].collapsed);
+    }
+
+    #[test]
+    fn test_wheel_scrolls_output_area_only() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        for i in 0..20 {
+            app.messages.push(Message::system(format!("message {}", i)));
+        }
+        app.output_area = Some(Rect { x: 0, y: 1, width: 80, height: 20 });
+
+        app.handle_mouse(mouse(MouseEventKind::ScrollUp, 10, 5));
+        assert_eq!(app.scroll_offset, WHEEL_LINES);
+        app.handle_mouse(mouse(MouseEventKind::ScrollUp, 10, 30));
+        assert_eq!(app.scroll_offset, WHEEL_LINES);
+        app.handle_mouse(mouse(MouseEventKind::ScrollDown, 10, 5));
+        assert_eq!(app.scroll_offset, 0);
+    }
+}
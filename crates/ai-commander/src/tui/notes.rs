@@ -0,0 +1,60 @@
+//! `/note` and `/notes`: scratchpad notes on the connected session.
+//!
+//! Notes are stored with `commander_core::notes`, so the session's agent
+//! reads them on its next analysis and they are exported with the transcript.
+
+use super::app::{App, Message};
+
+impl App {
+    /// Tmux session of the connected project, or a message saying why there is none.
+    fn notes_session(&mut self) -> Option<String> {
+        let session = self
+            .project
+            .as_ref()
+            .and_then(|project| self.sessions.get(project))
+            .cloned();
+        if session.is_none() {
+            self.messages.push(Message::system("Not connected to a session. Use /connect first."));
+        }
+        session
+    }
+
+    /// `/note <text>`: leave a note on the connected session.
+    pub(super) fn add_session_note(&mut self, text: Option<&str>) {
+        let Some(text) = text.filter(|t| !t.is_empty()) else {
+            self.messages.push(Message::system("Usage: /note <text>"));
+            return;
+        };
+        let Some(session) = self.notes_session() else {
+            return;
+        };
+        match commander_core::add_note(&session, text, "tui") {
+            Ok(_) => self.messages.push(Message::system(format!(
+                "📝 Note added to '{}'; the agent sees it on its next analysis",
+                session
+            ))),
+            Err(e) => self.messages.push(Message::system(format!("Failed to save note: {}", e))),
+        }
+    }
+
+    /// `/notes`: list the notes of the connected session.
+    pub(super) fn show_session_notes(&mut self) {
+        let Some(session) = self.notes_session() else {
+            return;
+        };
+        let notes = commander_core::session_notes(&session);
+        if notes.is_empty() {
+            self.messages.push(Message::system(format!("No notes on '{}'. Add one with /note <text>", session)));
+            return;
+        }
+        self.messages.push(Message::system(format!("=== Notes on {} ===", session)));
+        for note in notes {
+            self.messages.push(Message::system(format!(
+                "  {} ({}) {}",
+                note.at.with_timezone(&chrono::Local).format("%m-%d %H:%M"),
+                note.by,
+                note.text
+            )));
+        }
+    }
+}
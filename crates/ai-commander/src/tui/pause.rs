@@ -0,0 +1,115 @@
+//! `/pause` and `/resume` in the TUI.
+//!
+//! Paused sessions are skipped by the status checks, the full session scan
+//! and the watchdog, and show a pause badge in the header and session list.
+//! The paused set is shared with the REPL and Telegram through
+//! `commander_core::pause`, so it is reloaded periodically.
+
+use std::time::{Duration, Instant};
+
+use commander_core::pause;
+
+use super::app::{App, Message};
+
+/// Minimum time between reloads of the paused sessions.
+const PAUSE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+impl App {
+    /// Tmux session of `project`.
+    fn project_session(&self, project: &str) -> String {
+        self.sessions
+            .get(project)
+            .cloned()
+            .unwrap_or_else(|| project.replace([' ', '.', '/', ':'], "-"))
+    }
+
+    /// Whether the session of `project` is paused.
+    pub(super) fn is_project_paused(&self, project: &str) -> bool {
+        self.sessions
+            .get(project)
+            .is_some_and(|session| self.paused_sessions.contains(session))
+    }
+
+    /// Reload paused sessions, which other frontends may have changed.
+    ///
+    /// Called on every event loop tick; rate limited to `PAUSE_CHECK_INTERVAL`.
+    pub(super) fn check_paused_sessions(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_pause_check
+            .is_some_and(|last| now.duration_since(last) < PAUSE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_pause_check = Some(now);
+        self.paused_sessions = pause::paused_session_names();
+    }
+
+    /// `/pause [project]`: pause a project's session, the connected one by default.
+    pub(super) fn pause_project(&mut self, arg: Option<&str>) {
+        let Some(project) = arg.map(str::to_string).or_else(|| self.project.clone()) else {
+            self.messages.push(Message::system("Usage: /pause [project] or connect to a project first"));
+            return;
+        };
+        let Some(tmux) = &self.tmux else {
+            self.messages.push(Message::system("Tmux not available"));
+            return;
+        };
+        let session = self.project_session(&project);
+        if !tmux.session_exists(&session) {
+            self.messages.push(Message::system(format!("Session '{}' not found", session)));
+            return;
+        }
+
+        let adapter = self.registry.get(&self.project_tool_id(&project));
+        let result = crate::pause::pause_session(tmux.as_ref(), adapter.as_deref(), &session, &project, "tui");
+        match result {
+            Ok(description) => {
+                self.paused_sessions.insert(session);
+                if self.project.as_deref() == Some(project.as_str()) && self.is_working {
+                    self.stop_working();
+                }
+                self.messages.push(Message::system(format!("⏸ {} (/resume {} to continue)", description, project)));
+            }
+            Err(e) => self.messages.push(Message::system(e)),
+        }
+    }
+
+    /// `/resume [project]`: resume a paused project's session.
+    pub(super) fn resume_project(&mut self, arg: Option<&str>) {
+        let Some(project) = arg.map(str::to_string).or_else(|| self.project.clone()) else {
+            self.messages.push(Message::system("Usage: /resume [project] or connect to a project first"));
+            return;
+        };
+        let Some(tmux) = &self.tmux else {
+            self.messages.push(Message::system("Tmux not available"));
+            return;
+        };
+        let session = self.project_session(&project);
+        match crate::pause::resume_session(tmux.as_ref(), &session) {
+            Ok(description) => {
+                self.paused_sessions.remove(&session);
+                self.messages.push(Message::system(format!("▶ {}", description)));
+            }
+            Err(e) => self.messages.push(Message::system(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_is_project_paused() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.sessions.insert("api".into(), "api-main".into());
+        assert!(!app.is_project_paused("api"));
+
+        app.paused_sessions.insert("api-main".into());
+        assert!(app.is_project_paused("api"));
+        assert!(!app.is_project_paused("web"));
+    }
+}
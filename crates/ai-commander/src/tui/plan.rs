@@ -0,0 +1,165 @@
+//! Task plans from the User Agent (feature-gated).
+//!
+//! `/plan <goal>` asks the User Agent to break a large goal into a tree of
+//! dependent tasks and shows it for approval, with the reviewer's critique
+//! when peer review is enabled. `/plan approve` runs the plan
+//! in the connected project's session on a background thread; per-task
+//! status updates are printed as they arrive.
+
+use std::sync::mpsc;
+
+use commander_orchestrator::{PlanEvent, TaskSpec};
+
+use super::app::{App, Message};
+
+impl App {
+    /// Handle `/plan [<goal>|approve|cancel]`.
+    pub fn handle_plan_command(&mut self, arg: Option<&str>) {
+        match arg.map(str::trim).filter(|a| !a.is_empty()) {
+            None => self.show_plan(),
+            Some("approve") => self.approve_plan(),
+            Some("cancel") => self.discard_plan(),
+            Some(goal) => self.propose_plan(goal),
+        }
+    }
+
+    /// Generate a plan for `goal` and show it for approval.
+    fn propose_plan(&mut self, goal: &str) {
+        let (Some(handle), Some(orchestrator)) = (self.runtime_handle.clone(), self.orchestrator.as_mut()) else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        self.messages.push(Message::system(format!("Planning: {}", goal)));
+        match handle.block_on(orchestrator.propose_plan(goal, None)) {
+            Ok(_) => self.show_plan(),
+            Err(e) => self.messages.push(Message::system(format!("Planning failed: {}", e))),
+        }
+        self.scroll_to_bottom();
+    }
+
+    /// Show the plan awaiting approval, or the state of a running plan.
+    fn show_plan(&mut self) {
+        if self.plan_rx.is_some() {
+            self.messages.push(Message::system("A plan is running; progress is shown as tasks finish"));
+            return;
+        }
+        let Some(plan) = self.orchestrator.as_ref().and_then(|o| o.pending_plan()) else {
+            self.messages.push(Message::system("No plan awaiting approval. Usage: /plan <goal>"));
+            return;
+        };
+
+        let mut lines = vec![format!("Plan for \"{}\" ({} tasks):", plan.goal, plan.tasks.len())];
+        lines.extend(plan.format_tree().into_iter().map(|line| format!("  {}", line)));
+        if let Some(review) = self.orchestrator.as_ref().and_then(|o| o.plan_review()) {
+            if review.is_approved() {
+                lines.push(format!("Reviewed by {}: no issues", review.model));
+            } else {
+                lines.push(format!("Reviewer ({}) flagged:", review.model));
+                lines.extend(review.issue_lines().into_iter().map(|line| format!("  {}", line)));
+            }
+        }
+        lines.push("Run it with /plan approve, or discard it with /plan cancel".to_string());
+        for line in lines {
+            self.messages.push(Message::system(line));
+        }
+    }
+
+    /// Approve the pending plan and run it in the connected project's session.
+    fn approve_plan(&mut self) {
+        if self.plan_rx.is_some() {
+            self.messages.push(Message::system("A plan is already running"));
+            return;
+        }
+        let Some(session) = self.current_session_name() else {
+            self.messages.push(Message::system("Connect to a project first"));
+            return;
+        };
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        let mut template = TaskSpec::new(session.as_str(), "");
+        if let Some(path) = &self.project_path {
+            template = template.in_dir(path.as_str());
+        }
+
+        let run = match orchestrator.approve_plan(template) {
+            Ok(Some(run)) => run,
+            Ok(None) => {
+                self.messages.push(Message::system("No plan awaiting approval. Usage: /plan <goal>"));
+                return;
+            }
+            Err(e) => {
+                self.messages.push(Message::system(format!("Failed to start plan: {}", e)));
+                return;
+            }
+        };
+
+        self.messages.push(Message::system(format!(
+            "Running {} tasks in {}",
+            run.plan().tasks.len(),
+            session
+        )));
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            run.run(|event| {
+                let _ = tx.send(event);
+            });
+        });
+        self.plan_rx = Some(rx);
+        self.scroll_to_bottom();
+    }
+
+    /// Discard the plan awaiting approval.
+    fn discard_plan(&mut self) {
+        match self.orchestrator.as_mut().and_then(|o| o.discard_plan()) {
+            Some(plan) => self
+                .messages
+                .push(Message::system(format!("Discarded plan for \"{}\"", plan.goal))),
+            None => self.messages.push(Message::system("No plan awaiting approval")),
+        }
+    }
+
+    /// Print status updates from a running plan.
+    ///
+    /// Called on every event loop tick.
+    pub fn check_plan_progress(&mut self) {
+        let Some(rx) = &self.plan_rx else { return };
+
+        let mut finished = false;
+        let mut lines = Vec::new();
+        loop {
+            match rx.try_recv() {
+                Ok(PlanEvent::TaskStarted { task }) => lines.push(format!("[plan] [~] {}", task)),
+                Ok(PlanEvent::TaskCompleted { task }) => lines.push(format!("[plan] [x] {}", task)),
+                Ok(PlanEvent::TaskFailed { task, error }) => {
+                    lines.push(format!("[plan] [!] {}: {}", task, error));
+                }
+                Ok(PlanEvent::Finished(report)) => {
+                    lines.push(format!(
+                        "[plan] Finished: {} completed, {} failed, {} skipped",
+                        report.completed, report.failed, report.skipped
+                    ));
+                    finished = true;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        if finished {
+            self.plan_rx = None;
+        }
+        if !lines.is_empty() {
+            for line in lines {
+                self.messages.push(Message::system(line));
+            }
+            self.scroll_to_bottom();
+        }
+    }
+}
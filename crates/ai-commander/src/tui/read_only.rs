@@ -0,0 +1,76 @@
+//! Read-only ("ask the project") sessions.
+//!
+//! `/connect <name> --read-only` connects to a project for questions only.
+//! The adapter is launched in its read-only mode where it has one and told not
+//! to change anything, mutating filesystem commands are refused, the Session
+//! Agent loses its write-capable tools and the header shows a lock. The mode
+//! lasts until the session is stopped.
+
+use super::app::{App, Message};
+use super::connection::PendingPrompt;
+
+/// Instructions sent to the adapter of a read-only session.
+const READ_ONLY_PROMPT: &str = "This session is read-only: answer questions about the project, \
+but do not create, edit, move or delete files, and do not run commands that change anything \
+(installs, builds that write artifacts, git commits). If a question needs a change, describe it instead.";
+
+impl App {
+    /// Whether `project` is connected in read-only mode.
+    pub(super) fn is_read_only(&self, project: &str) -> bool {
+        self.read_only_projects.contains(project)
+    }
+
+    /// Put `project` (tmux session `session`) in read-only mode and queue the
+    /// read-only instructions for its adapter.
+    pub(super) fn enable_read_only(&mut self, project: &str, session: &str) {
+        if !self.read_only_projects.insert(project.to_string()) {
+            return;
+        }
+
+        #[cfg(feature = "agents")]
+        if let Some(orchestrator) = self.orchestrator.as_mut() {
+            orchestrator.set_session_read_only(session, true);
+        }
+        #[cfg(not(feature = "agents"))]
+        let _ = session;
+
+        self.pending_prompts.push(PendingPrompt::new(project, READ_ONLY_PROMPT));
+        self.messages.push(Message::system(format!(
+            "🔒 '{}' is read-only: file changes are blocked until the session is stopped (/stop {})",
+            project, project
+        )));
+    }
+
+    /// Leave read-only mode when `project`'s session is stopped.
+    pub(super) fn disable_read_only(&mut self, project: &str, session: &str) {
+        if !self.read_only_projects.remove(project) {
+            return;
+        }
+
+        #[cfg(feature = "agents")]
+        if let Some(orchestrator) = self.orchestrator.as_mut() {
+            orchestrator.set_session_read_only(session, false);
+        }
+        #[cfg(not(feature = "agents"))]
+        let _ = session;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_until_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new(dir.path());
+
+        app.enable_read_only("docs", "docs");
+        assert!(app.is_read_only("docs"));
+        assert_eq!(app.pending_prompts.len(), 1);
+        assert!(!app.is_read_only("api"));
+
+        app.disable_read_only("docs", "docs");
+        assert!(!app.is_read_only("docs"));
+    }
+}
@@ -0,0 +1,68 @@
+//! "Since you left" digests when connecting to a session.
+//!
+//! The screen is recorded whenever the user sends a message or reads a
+//! response. On connect, what happened since is summarized on a background
+//! thread and shown once ready.
+
+use std::sync::mpsc::{self, TryRecvError};
+
+use commander_core::{mark_seen, recap_blocking};
+
+use super::app::{App, Message};
+
+/// Screen lines compared, as captured when sending a message.
+const SCREEN_LINES: u32 = 200;
+
+impl App {
+    /// Summarize what happened in `session` since the user last saw it.
+    pub(super) fn start_recap(&mut self, session: &str) {
+        let Some(tmux) = &self.tmux else { return };
+        let Ok(screen) = tmux.capture_output(session, None, Some(SCREEN_LINES)) else {
+            return;
+        };
+        let session = session.to_string();
+        let project = self.project.clone();
+        let (tx, rx) = mpsc::channel();
+        self.recap_rx = Some(rx);
+        std::thread::spawn(move || {
+            let names: Vec<&str> = project.iter().map(String::as_str).collect();
+            if let Some(digest) = recap_blocking(&session, &names, &screen) {
+                let _ = tx.send((session, digest));
+            }
+        });
+    }
+
+    /// Show the digest once it is ready, if still connected to its session.
+    pub fn check_recap(&mut self) {
+        let Some(rx) = &self.recap_rx else { return };
+        let (session, digest) = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.recap_rx = None;
+                return;
+            }
+        };
+        self.recap_rx = None;
+        if self.current_session_name().as_deref() != Some(session.as_str()) {
+            return;
+        }
+
+        let mut lines = digest.lines();
+        if let Some(heading) = lines.next() {
+            self.messages.push(Message::system(heading));
+        }
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            self.messages.push(Message::system(format!("  {}", line)));
+        }
+        self.scroll_to_bottom();
+    }
+
+    /// Record that the user saw the connected session's last captured screen.
+    pub(super) fn mark_session_seen(&self) {
+        let Some(session) = self.current_session_name() else { return };
+        if let Err(e) = mark_seen(&session, &self.last_output) {
+            tracing::debug!(session = %session, error = %e, "Failed to record recap mark");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Session recording for the TUI (`/record start|stop`).
+//!
+//! Captures the recorded session's pane on a fixed interval and appends
+//! changed screens to an asciicast file in `~/.ai-commander/recordings/`.
+
+use std::time::{Duration, Instant};
+
+use commander_core::Recorder;
+
+use super::app::{App, Message};
+
+/// Minimum time between recording captures.
+const RECORD_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Lines captured per recording frame.
+const RECORD_LINES: u32 = 50;
+
+impl App {
+    /// Handle `/record [start|stop]`.
+    pub(super) fn handle_record_command(&mut self, arg: Option<&str>) {
+        match arg.unwrap_or("") {
+            "start" => self.start_recording(),
+            "stop" => self.stop_recording(),
+            "" => match &self.recording {
+                Some((session, recorder)) => self.messages.push(Message::system(format!(
+                    "Recording '{}' to {}",
+                    session,
+                    recorder.path().display()
+                ))),
+                None => self.messages.push(Message::system("Not recording. Use /record start")),
+            },
+            other => self.messages.push(Message::system(format!(
+                "Unknown /record option '{}'. Use /record start|stop",
+                other
+            ))),
+        }
+    }
+
+    /// Start recording the connected project's session.
+    pub fn start_recording(&mut self) {
+        if let Some((session, _)) = &self.recording {
+            self.messages.push(Message::system(format!("Already recording '{}'", session)));
+            return;
+        }
+        let Some(session) = self.current_session_name() else {
+            self.messages.push(Message::system("Connect to a project first"));
+            return;
+        };
+
+        match Recorder::start(&session) {
+            Ok(recorder) => {
+                self.messages.push(Message::system(format!(
+                    "Recording '{}' to {}",
+                    session,
+                    recorder.path().display()
+                )));
+                self.recording = Some((session, recorder));
+                self.last_record_capture = None;
+            }
+            Err(e) => self.messages.push(Message::system(format!("Failed to start recording: {}", e))),
+        }
+    }
+
+    /// Stop the active recording.
+    pub fn stop_recording(&mut self) {
+        match self.recording.take() {
+            Some((session, recorder)) => self.messages.push(Message::system(format!(
+                "Stopped recording '{}'. Replay with: ai-commander play {}",
+                session,
+                recorder.path().display()
+            ))),
+            None => self.messages.push(Message::system("Not recording")),
+        }
+    }
+
+    /// Append the recorded session's screen if it changed.
+    ///
+    /// Called on every event loop tick; rate limited to `RECORD_INTERVAL`.
+    /// The recording ends when its session goes away.
+    pub fn record_tick(&mut self) {
+        let Some((session, recorder)) = &mut self.recording else { return };
+        let now = Instant::now();
+        if self
+            .last_record_capture
+            .is_some_and(|last| now.duration_since(last) < RECORD_INTERVAL)
+        {
+            return;
+        }
+        self.last_record_capture = Some(now);
+
+        let Some(tmux) = &self.tmux else { return };
+        let result = match tmux.capture_output(session, None, Some(RECORD_LINES)) {
+            Ok(screen) => recorder.record_screen(&screen).map(|_| ()).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        if let Err(e) = result {
+            let session = session.clone();
+            self.recording = None;
+            self.messages.push(Message::system(format!(
+                "Recording of '{}' stopped: {}",
+                session, e
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_record_requires_connection() {
+        let dir = tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.handle_record_command(Some("start"));
+        assert!(app.recording.is_none());
+        assert!(app.messages.last().unwrap().content.contains("Connect to a project"));
+
+        app.handle_record_command(Some("stop"));
+        assert_eq!(app.messages.last().unwrap().content, "Not recording");
+    }
+}
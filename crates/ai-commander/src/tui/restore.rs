@@ -0,0 +1,135 @@
+//! Session snapshots and restore after a reboot.
+//!
+//! Live sessions of registered projects are snapshotted every
+//! `SNAPSHOT_INTERVAL`. Sessions whose tmux session is gone on startup are
+//! listed, and `/restore` relaunches them and sends each adapter a
+//! context-priming message once it is ready.
+
+use std::time::{Duration, Instant};
+
+use commander_core::CommanderError;
+
+use crate::restore;
+
+use super::app::{App, Message};
+use super::connection::PendingPrompt;
+
+/// How often live sessions are snapshotted.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+
+impl App {
+    /// Snapshot the live sessions of registered projects.
+    ///
+    /// Called on every event loop tick; rate limited to `SNAPSHOT_INTERVAL`.
+    pub fn check_snapshots(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_snapshot
+            .is_some_and(|last| now.duration_since(last) < SNAPSHOT_INTERVAL)
+        {
+            return;
+        }
+        self.last_snapshot = Some(now);
+
+        let (Some(tmux), Ok(projects)) = (self.tmux.as_deref(), self.store.load_all_projects()) else {
+            return;
+        };
+        for project in projects.values() {
+            #[cfg(feature = "agents")]
+            let agent_state = {
+                let session = project.name.replace([' ', '.', '/', ':'], "-");
+                self.orchestrator.as_ref().and_then(|o| o.session_state(&session))
+            };
+            #[cfg(not(feature = "agents"))]
+            let agent_state = None;
+
+            if let Some(snapshot) = restore::take_snapshot(tmux, project, agent_state) {
+                if let Err(e) = self.snapshots.save(&snapshot) {
+                    tracing::warn!(project = %project.name, error = %e, "Failed to save session snapshot");
+                }
+            }
+        }
+    }
+
+    /// Report sessions that did not survive a restart.
+    ///
+    /// Called once at startup, before the first snapshot.
+    pub fn check_lost_sessions(&mut self) {
+        let Some(tmux) = self.tmux.as_deref() else {
+            return;
+        };
+        let lost = restore::detect_lost_sessions(&self.store, &self.snapshots, tmux);
+        if lost.is_empty() {
+            return;
+        }
+
+        let names: Vec<&str> = lost.iter().map(|s| s.project.as_str()).collect();
+        self.messages.push(Message::system(format!(
+            "[!] {} session(s) did not survive a restart: {}. /restore relaunches them (or /restore <project>)",
+            lost.len(),
+            names.join(", ")
+        )));
+    }
+
+    /// Relaunch lost sessions, all of them or only `project`'s.
+    pub(super) fn restore_sessions(&mut self, project: Option<&str>) {
+        let Some(tmux) = self.tmux.as_deref() else {
+            self.messages.push(Message::system("Tmux not available"));
+            return;
+        };
+        let lost: Vec<_> = restore::detect_lost_sessions(&self.store, &self.snapshots, tmux)
+            .into_iter()
+            .filter(|s| project.is_none_or(|p| p == s.project))
+            .collect();
+        if lost.is_empty() {
+            self.messages.push(Message::system("No lost sessions to restore"));
+            return;
+        }
+
+        for snapshot in lost {
+            let relaunched = match self.tmux.as_deref() {
+                Some(tmux) => restore::relaunch(&snapshot, &self.store, &self.registry, tmux),
+                None => Err(CommanderError::Tmux("Tmux not available".to_string())),
+            };
+            if let Err(e) = relaunched {
+                self.messages.push(Message::system(format!(
+                    "Failed to restore '{}': {}",
+                    snapshot.project, e
+                )));
+                continue;
+            }
+
+            #[cfg(feature = "agents")]
+            if let (Some(orchestrator), Some(state)) =
+                (self.orchestrator.as_mut(), restore::agent_state(&snapshot))
+            {
+                if let Err(e) = orchestrator.restore_session_state(&snapshot.session, &snapshot.adapter, state) {
+                    tracing::warn!(session = %snapshot.session, error = %e, "Failed to restore agent state");
+                }
+            }
+
+            self.sessions.insert(snapshot.project.clone(), snapshot.session.clone());
+            self.pending_prompts.push(PendingPrompt::new(
+                snapshot.project.clone(),
+                restore::priming_message(&snapshot),
+            ));
+            self.messages.push(Message::system(format!(
+                "Relaunched '{}'; its last context will be sent when the adapter is ready",
+                snapshot.project
+            )));
+        }
+    }
+
+    /// Drop `project`'s snapshot so a deliberately stopped session is not
+    /// offered for restore.
+    pub(super) fn forget_snapshot(&mut self, project: &str) {
+        let Ok(projects) = self.store.load_all_projects() else {
+            return;
+        };
+        if let Some(project) = projects.values().find(|p| p.name == project) {
+            if let Err(e) = self.snapshots.delete(&project.id) {
+                tracing::warn!(project = %project.name, error = %e, "Failed to delete session snapshot");
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! `/retest`: re-run the failing tests of the connected session.
+//!
+//! Test results are parsed from the session's screen (cargo test, pytest,
+//! Jest, go test), falling back to the last run its Session Agent saw, and
+//! the session is asked to run just the failing tests.
+
+use commander_adapters::{parse_test_output, TestRun};
+
+use super::app::{App, Message};
+
+/// Screen lines searched for test results.
+const RETEST_LINES: u32 = 300;
+
+impl App {
+    /// `/retest`: send the connected session a prompt re-running its failing tests.
+    pub(super) fn retest_failing(&mut self) {
+        let Some(session) = self.current_session_name() else {
+            self.messages.push(Message::system("Not connected to a session. Use /connect first."));
+            return;
+        };
+        let Some(run) = self.last_test_run(&session) else {
+            self.messages.push(Message::system("No test results found in the session's output"));
+            return;
+        };
+        if run.succeeded() {
+            self.messages.push(Message::system(format!("{}; nothing to re-run", run.summary())));
+            return;
+        }
+        let (Some(command), Some(prompt)) = (run.rerun_command(), run.rerun_prompt()) else {
+            self.messages.push(Message::system(format!(
+                "{}; the failing tests are not named in the output",
+                run.summary()
+            )));
+            return;
+        };
+        self.messages.push(Message::system(format!("{}; re-running: {}", run.summary(), command)));
+        if let Err(e) = self.send_message(&prompt) {
+            self.messages.push(Message::error(&e));
+        }
+    }
+
+    /// Latest test results of `session`: from its screen, else from its
+    /// Session Agent's state.
+    fn last_test_run(&self, session: &str) -> Option<TestRun> {
+        let from_screen = self
+            .tmux
+            .as_ref()
+            .and_then(|tmux| tmux.capture_output(session, None, Some(RETEST_LINES)).ok())
+            .and_then(|screen| parse_test_output(&screen));
+        #[cfg(feature = "agents")]
+        let from_screen = from_screen.or_else(|| {
+            self.orchestrator
+                .as_ref()
+                .and_then(|o| o.session_state(session))
+                .and_then(|state| state.tests.clone())
+        });
+        from_screen
+    }
+}
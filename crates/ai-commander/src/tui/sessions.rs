@@ -12,7 +12,7 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
-use commander_core::is_claude_ready;
+use commander_core::{is_claude_ready, Significance};
 use commander_core::notification_parser::parse_session_preview;
 
 use super::app::{App, Message, SessionInfo, ViewMode};
@@ -43,11 +43,24 @@ impl App {
         let connected_project = self.project.clone();
 
         // Check each session and collect notifications
-        let mut notifications: Vec<(String, bool, String)> = Vec::new();
+        let mut notifications: Vec<(String, bool, String, String)> = Vec::new();
         let mut state_updates: Vec<(String, bool)> = Vec::new();
+        let mut ended: Vec<String> = Vec::new();
+        let mut captured: Vec<(String, String)> = Vec::new();
 
         if let Some(tmux) = &self.tmux {
             for (name, session) in sessions_to_check {
+                // The session was killed or exited outside Commander
+                if !tmux.session_exists(&session) {
+                    ended.push(name);
+                    continue;
+                }
+
+                // Paused sessions are not analyzed until /resume
+                if self.paused_sessions.contains(&session) {
+                    continue;
+                }
+
                 if let Ok(output) = tmux.capture_output(&session, None, Some(50)) {
                     let is_ready = is_claude_ready(&output);
 
@@ -61,17 +74,31 @@ impl App {
                     if has_prior_state && is_ready && !was_ready {
                         let preview = extract_ready_preview(&output);
                         let is_connected = connected_project.as_ref() == Some(&name);
-                        notifications.push((name.clone(), is_connected, preview));
+                        notifications.push((name.clone(), is_connected, preview, output.clone()));
                     }
 
-                    state_updates.push((name, is_ready));
+                    state_updates.push((name.clone(), is_ready));
+                    captured.push((name, output));
                 }
             }
         }
 
         // Apply notifications with conversational output
-        let mut should_scroll = false;
-        for (name, is_connected, preview) in notifications {
+        let mut should_scroll = !ended.is_empty();
+        for name in ended {
+            self.sessions.remove(&name);
+            self.session_ready_state.remove(&name);
+            self.change_detectors.remove(&name);
+            self.messages.push(Message::system(format!(
+                "Session \"{}\" is no longer running (ended outside Commander)",
+                name
+            )));
+            if connected_project.as_ref() == Some(&name) {
+                self.disconnect();
+            }
+        }
+
+        for (name, is_connected, preview, output) in notifications {
             // Parse preview to get structured status, then convert to conversational
             let status = parse_session_preview(&name, &preview);
             let brief = status.to_brief();
@@ -90,20 +117,29 @@ impl App {
                 }
             };
 
+            self.notify_desktop(&name, Significance::High, &msg);
             self.messages.push(Message::system(msg));
             should_scroll = true;
 
-            // Broadcast to all channels (Telegram, etc.)
+            // Broadcast to all channels (Telegram, etc.), off the UI thread
+            // while the headline is written.
             // Use the actual session name (might be commander-prefixed or not)
             let session_name = self.sessions.get(&name)
                 .cloned()
                 .unwrap_or_else(|| name.replace([' ', '.', '/', ':'], "-"));
-            if let Err(e) = commander_telegram::notify_session_ready(
-                &session_name,
-                if preview.is_empty() { None } else { Some(&preview) }
-            ) {
-                tracing::warn!(error = %e, "Failed to broadcast notification");
-            }
+            std::thread::spawn(move || {
+                if let Err(e) = commander_telegram::notify_session_ready(
+                    &session_name,
+                    if preview.is_empty() { None } else { Some(&preview) },
+                    Some(&output),
+                ) {
+                    tracing::warn!(error = %e, "Failed to broadcast notification");
+                }
+            });
+        }
+
+        for (name, output) in captured {
+            self.detect_desktop_changes(&name, &output);
         }
 
         // Apply state updates
@@ -142,13 +178,18 @@ impl App {
         // Check each session for ready state
         let mut current_waiting: std::collections::HashSet<String> = std::collections::HashSet::new();
         let mut waiting_previews: HashMap<String, String> = HashMap::new();
+        let mut waiting_screens: HashMap<String, String> = HashMap::new();
 
         for session_info in &all_sessions {
+            if self.paused_sessions.contains(&session_info.name) {
+                continue;
+            }
             if let Ok(output) = tmux.capture_output(&session_info.name, None, Some(50)) {
                 if is_claude_ready(&output) {
                     let preview = extract_ready_preview(&output);
                     current_waiting.insert(session_info.name.clone());
                     waiting_previews.insert(session_info.name.clone(), preview);
+                    waiting_screens.insert(session_info.name.clone(), output);
                 }
             }
         }
@@ -199,16 +240,20 @@ impl App {
             }
             self.scroll_to_bottom();
 
-            // Broadcast to all channels (Telegram, etc.)
+            // Broadcast to all channels (Telegram, etc.), off the UI thread
+            // while headlines are written
             let sessions_for_broadcast: Vec<_> = newly_waiting.iter()
                 .map(|name| {
                     let preview = waiting_previews.get(name).cloned().unwrap_or_default();
-                    (name.clone(), preview)
+                    let screen = waiting_screens.remove(name).unwrap_or_default();
+                    (name.clone(), preview, screen)
                 })
                 .collect();
-            if let Err(e) = commander_telegram::notify_sessions_waiting(&sessions_for_broadcast) {
-                tracing::warn!(error = %e, "Failed to broadcast notification");
-            }
+            std::thread::spawn(move || {
+                if let Err(e) = commander_telegram::notify_sessions_waiting(&sessions_for_broadcast) {
+                    tracing::warn!(error = %e, "Failed to broadcast notification");
+                }
+            });
         }
 
         // Report sessions that resumed work (conversational)
@@ -241,22 +286,40 @@ impl App {
         self.session_selected = 0;
     }
 
+    /// Toggle the sessions list view (F3).
+    pub fn toggle_sessions_view(&mut self) {
+        if self.view_mode == ViewMode::Sessions {
+            self.view_mode = ViewMode::Normal;
+        } else if self.tmux.is_some() {
+            self.show_sessions();
+        } else {
+            self.messages.push(Message::system("Tmux not available"));
+        }
+    }
+
     /// Refresh the list of tmux sessions.
     pub fn refresh_session_list(&mut self) {
         if let Some(tmux) = &self.tmux {
             if let Ok(sessions) = tmux.list_sessions() {
-                self.session_list = sessions.iter().map(|s| {
+                let grouped = self.group_sessions(sessions, |s| s.name.as_str());
+                self.session_list = grouped.into_iter().flat_map(|(workspace, sessions)| {
+                    sessions.into_iter().map(move |s| (workspace.clone(), s))
+                }).map(|(workspace, s)| {
                     let is_connected = self.sessions.values().any(|n| n == &s.name);
                     // Detect adapter type from screen content
                     let adapter = tmux.capture_output(&s.name, None, Some(50))
                         .map(|output| commander_core::detect_adapter(&output))
                         .unwrap_or(commander_core::Adapter::Unknown);
+                    let is_paused = self.paused_sessions.contains(&s.name);
                     SessionInfo {
-                        name: s.name.clone(),
+                        name: s.name,
                         adapter,
                         is_connected,
+                        is_paused,
+                        workspace,
                     }
                 }).collect();
+                self.session_selected = self.session_selected.min(self.session_list.len().saturating_sub(1));
             }
         }
     }
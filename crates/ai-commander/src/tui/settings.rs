@@ -0,0 +1,59 @@
+//! Live `config.toml` changes.
+//!
+//! Keybindings are rebuilt from `[tui.keys]` and the theme from
+//! `[tui.theme]`, desktop notification rules replaced from
+//! `[notifications]`, filesystem limits from `[filesystem]` and, with agents
+//! enabled, `[agents]` models are applied to the orchestrator, without
+//! restarting.
+
+use commander_core::ConfigChange;
+
+use super::app::{App, Message};
+use super::keybindings::Keybindings;
+use super::theme::Theme;
+
+impl App {
+    /// Apply `config.toml` changes published since the last tick.
+    ///
+    /// Called on every event loop tick.
+    pub fn check_config_changes(&mut self) {
+        let Some(rx) = &self.config_changes else { return };
+        let changes: Vec<ConfigChange> = rx.try_iter().collect();
+
+        for change in changes {
+            match change {
+                ConfigChange::Tui(settings) => {
+                    let (keybindings, problems) = Keybindings::from_settings(&settings);
+                    for problem in problems {
+                        self.messages.push(Message::system(format!("config.toml: {}", problem)));
+                    }
+                    if keybindings != self.keybindings {
+                        self.keybindings = keybindings;
+                        self.messages.push(Message::system("Keybindings reloaded from config.toml"));
+                    }
+                    if settings.theme != self.theme_settings {
+                        let (theme, problems) = Theme::from_settings(&settings.theme);
+                        for problem in problems {
+                            self.messages.push(Message::system(format!("config.toml: {}", problem)));
+                        }
+                        self.theme = theme;
+                        self.theme_settings = settings.theme;
+                    }
+                }
+                ConfigChange::Notifications(settings) => {
+                    self.notification_settings = settings;
+                }
+                ConfigChange::Filesystem(settings) => {
+                    self.filesystem_settings = settings;
+                }
+                #[cfg(feature = "agents")]
+                ConfigChange::Agents(settings) => {
+                    if let Some(orchestrator) = self.orchestrator.as_mut() {
+                        orchestrator.apply_agent_settings(&settings);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
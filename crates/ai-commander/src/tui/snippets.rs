@@ -0,0 +1,77 @@
+//! `/run`, `!name` and `/snippets`: named prompts from `snippets.toml`.
+//!
+//! Snippets are expanded with `commander_core::snippets` against the
+//! connected project (name, path and git branch) and sent like typed input.
+
+use commander_core::snippets::{self, SnippetContext};
+
+use super::app::{App, Message};
+
+impl App {
+    /// `/run <name> [args]` or `!name [args]`: send a snippet to the connected session.
+    pub(super) fn run_snippet(&mut self, name: &str, args: &str) {
+        let Some(project) = self.project.clone() else {
+            self.messages.push(Message::system("Not connected to a session. Use /connect first."));
+            return;
+        };
+        let context = SnippetContext::for_project(&project, self.project_path.as_deref());
+        let text = match snippets::load_snippets().and_then(|s| snippets::expand(&s, name, args, &context)) {
+            Ok(text) => text,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Error: {}", e)));
+                return;
+            }
+        };
+        if let Err(e) = self.send_message(&text) {
+            self.messages.push(Message::error(&e));
+        }
+    }
+
+    /// `/snippets`: list the snippets.
+    pub(super) fn show_snippets(&mut self) {
+        let snippets = match snippets::load_snippets() {
+            Ok(snippets) => snippets,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Error: {}", e)));
+                return;
+            }
+        };
+        if snippets.is_empty() {
+            self.messages.push(Message::system(
+                "No snippets. Add one with /snippet add <name> <text>",
+            ));
+            return;
+        }
+        self.messages.push(Message::system("=== Snippets (/run <name> or !name) ==="));
+        for line in snippets::format_list(&snippets) {
+            self.messages.push(Message::system(format!("  {}", line)));
+        }
+    }
+
+    /// `/snippet add|edit <name> <text>` or `/snippet rm <name>`.
+    pub(super) fn handle_snippet_command(&mut self, arg: Option<&str>) {
+        let mut parts = arg.unwrap_or_default().splitn(3, ' ');
+        let (action, name, text) = (parts.next(), parts.next(), parts.next().map(str::trim));
+        let result = match (action, name, text) {
+            (Some("add"), Some(name), Some(text)) if !text.is_empty() => {
+                snippets::add_snippet(name, text, None).map(|_| format!("Added snippet '{}'", name))
+            }
+            (Some("edit"), Some(name), Some(text)) if !text.is_empty() => {
+                snippets::edit_snippet(name, text, None).map(|_| format!("Updated snippet '{}'", name))
+            }
+            (Some("rm" | "remove"), Some(name), None) => {
+                snippets::remove_snippet(name).map(|_| format!("Removed snippet '{}'", name))
+            }
+            _ => {
+                self.messages.push(Message::system(
+                    "Usage: /snippet add <name> <text> | /snippet edit <name> <text> | /snippet rm <name>",
+                ));
+                return;
+            }
+        };
+        match result {
+            Ok(done) => self.messages.push(Message::system(done)),
+            Err(e) => self.messages.push(Message::system(format!("Error: {}", e))),
+        }
+    }
+}
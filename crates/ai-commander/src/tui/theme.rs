@@ -0,0 +1,334 @@
+//! Color themes.
+//!
+//! The colors of message directions, the status bar, borders, diff
+//! rendering and work item deadlines come from a theme: one of the built-ins (`dark`, `light`,
+//! `high-contrast`) or a TOML file in `~/.ai-commander/themes/`, with
+//! per-color overrides from `config.toml`:
+//!
+//! ```toml
+//! [tui.theme]
+//! name = "light"
+//! sent = "#005fd7"
+//! border = "gray"
+//! ```
+//!
+//! A theme file uses the same keys, with `base` naming the theme it starts
+//! from. Colors are names (`cyan`, `lightred`), `#rrggbb` or a 256-color
+//! index. `/theme <name>` switches themes while the TUI runs, and changes to
+//! `[tui.theme]` are applied live (see `App::check_config_changes`).
+
+use std::path::PathBuf;
+
+use commander_core::ThemeSettings;
+use ratatui::style::Color;
+
+use super::app::{App, Message};
+
+/// Built-in theme names, in the order `/theme` lists them.
+pub const BUILTIN_THEMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+/// Value of `border` that keeps each view's own border color.
+const VIEW_BORDER: &str = "auto";
+
+/// Colors used by the TUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Name shown by `/theme`.
+    pub name: String,
+    /// Messages sent to a session.
+    pub sent: Color,
+    /// Responses from a session.
+    pub received: Color,
+    /// Commander's own messages.
+    pub system: Color,
+    /// Status bar background when idle.
+    pub status_bg: Color,
+    /// Status bar text when idle.
+    pub status_fg: Color,
+    /// Status bar background while a response is received.
+    pub working_bg: Color,
+    /// Status bar text while a response is received.
+    pub working_fg: Color,
+    /// Border of every panel; `None` keeps each view's own color.
+    pub border: Option<Color>,
+    /// Hints, empty states and other secondary text.
+    pub muted: Color,
+    /// Selected rows.
+    pub highlight: Color,
+    /// Added diff lines.
+    pub diff_added: Color,
+    /// Removed diff lines.
+    pub diff_removed: Color,
+    /// Diff hunk headers (`@@ ... @@`).
+    pub diff_hunk: Color,
+    /// Diff file headers.
+    pub diff_file: Color,
+    /// Other diff metadata (`index`, `---`, `+++`).
+    pub diff_meta: Color,
+    /// Work items close to their deadline.
+    pub due_soon: Color,
+    /// Work items past their deadline.
+    pub overdue: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "dark".to_string(),
+            sent: Color::Cyan,
+            received: Color::Green,
+            system: Color::Yellow,
+            status_bg: Color::DarkGray,
+            status_fg: Color::White,
+            working_bg: Color::Yellow,
+            working_fg: Color::Black,
+            border: None,
+            muted: Color::DarkGray,
+            highlight: Color::Yellow,
+            diff_added: Color::Green,
+            diff_removed: Color::Red,
+            diff_hunk: Color::Cyan,
+            diff_file: Color::Yellow,
+            diff_meta: Color::DarkGray,
+            due_soon: Color::LightYellow,
+            overdue: Color::LightRed,
+        }
+    }
+}
+
+impl Theme {
+    /// A built-in theme by name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let theme = match name {
+            "dark" => Self::default(),
+            "light" => Self {
+                name: name.to_string(),
+                sent: Color::Blue,
+                received: Color::Green,
+                system: Color::Magenta,
+                status_bg: Color::Gray,
+                status_fg: Color::Black,
+                working_bg: Color::LightYellow,
+                working_fg: Color::Black,
+                border: None,
+                muted: Color::DarkGray,
+                highlight: Color::Blue,
+                diff_added: Color::Green,
+                diff_removed: Color::Red,
+                diff_hunk: Color::Blue,
+                diff_file: Color::Magenta,
+                diff_meta: Color::DarkGray,
+                due_soon: Color::Rgb(0xaf, 0x5f, 0x00),
+                overdue: Color::Red,
+            },
+            "high-contrast" => Self {
+                name: name.to_string(),
+                sent: Color::LightCyan,
+                received: Color::LightGreen,
+                system: Color::LightYellow,
+                status_bg: Color::White,
+                status_fg: Color::Black,
+                working_bg: Color::LightYellow,
+                working_fg: Color::Black,
+                border: Some(Color::White),
+                muted: Color::Gray,
+                highlight: Color::LightYellow,
+                diff_added: Color::LightGreen,
+                diff_removed: Color::LightRed,
+                diff_hunk: Color::LightCyan,
+                diff_file: Color::White,
+                diff_meta: Color::Gray,
+                due_soon: Color::LightYellow,
+                overdue: Color::LightRed,
+            },
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// Load a theme by name: a built-in, or `<name>.toml` in the themes
+    /// directory. Returns the problems found in the theme file.
+    pub fn load(name: &str) -> Result<(Self, Vec<String>), String> {
+        if let Some(theme) = Self::builtin(name) {
+            return Ok((theme, Vec::new()));
+        }
+        let path = theme_file(name);
+        if !path.exists() {
+            return Err(format!(
+                "unknown theme '{}' (built-in: {}; or add {})",
+                name,
+                BUILTIN_THEMES.join(", "),
+                path.display()
+            ));
+        }
+        let settings = ThemeSettings::load(&path).map_err(|e| e.to_string())?;
+        let base = match settings.name.as_deref() {
+            Some(base) if base != name => {
+                Self::builtin(base).ok_or_else(|| format!("{}: unknown base theme '{}'", path.display(), base))?
+            }
+            _ => Self::default(),
+        };
+        let mut theme = base;
+        theme.name = name.to_string();
+        let problems = theme.apply(&settings.colors);
+        Ok((theme, problems))
+    }
+
+    /// Build the theme from `[tui.theme]`: the named theme with its colors
+    /// overridden. Problems are reported and the offending entries skipped.
+    pub fn from_settings(settings: &ThemeSettings) -> (Self, Vec<String>) {
+        let (mut theme, mut problems) = match settings.name.as_deref() {
+            Some(name) => Self::load(name).unwrap_or_else(|e| (Self::default(), vec![e])),
+            None => (Self::default(), Vec::new()),
+        };
+        problems.extend(theme.apply(&settings.colors));
+        (theme, problems)
+    }
+
+    /// Override colors by element name, returning the problems found.
+    fn apply<'a>(&mut self, colors: impl IntoIterator<Item = (&'a String, &'a String)>) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (key, value) in colors {
+            if key == "border" && value.trim().eq_ignore_ascii_case(VIEW_BORDER) {
+                self.border = None;
+                continue;
+            }
+            let Some(color) = parse_color(value) else {
+                problems.push(format!(
+                    "invalid color '{}' for {} (expected e.g. \"cyan\", \"#5fafff\" or \"244\")",
+                    value, key
+                ));
+                continue;
+            };
+            let slot = match key.as_str() {
+                "sent" => &mut self.sent,
+                "received" => &mut self.received,
+                "system" => &mut self.system,
+                "status_bg" => &mut self.status_bg,
+                "status_fg" => &mut self.status_fg,
+                "working_bg" => &mut self.working_bg,
+                "working_fg" => &mut self.working_fg,
+                "muted" => &mut self.muted,
+                "highlight" => &mut self.highlight,
+                "diff_added" => &mut self.diff_added,
+                "diff_removed" => &mut self.diff_removed,
+                "diff_hunk" => &mut self.diff_hunk,
+                "diff_file" => &mut self.diff_file,
+                "diff_meta" => &mut self.diff_meta,
+                "due_soon" => &mut self.due_soon,
+                "overdue" => &mut self.overdue,
+                "border" => {
+                    self.border = Some(color);
+                    continue;
+                }
+                _ => {
+                    problems.push(format!("unknown theme color '{}'", key));
+                    continue;
+                }
+            };
+            *slot = color;
+        }
+        problems
+    }
+
+    /// Border color of a view whose own color is `view`.
+    pub fn border_or(&self, view: Color) -> Color {
+        self.border.unwrap_or(view)
+    }
+}
+
+/// Parse a color name, `#rrggbb` or 256-color index.
+fn parse_color(value: &str) -> Option<Color> {
+    value.trim().parse().ok()
+}
+
+/// Path of a user theme file.
+fn theme_file(name: &str) -> PathBuf {
+    commander_core::themes_dir().join(format!("{}.toml", name))
+}
+
+/// Names of the user theme files.
+fn user_themes() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(commander_core::themes_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .filter(|name| !BUILTIN_THEMES.contains(&name.as_str()))
+        .collect();
+    names.sort();
+    names
+}
+
+impl App {
+    /// `/theme [name]`: list the themes, or switch to one.
+    pub fn handle_theme_command(&mut self, arg: Option<&str>) {
+        let Some(name) = arg.map(str::trim).filter(|name| !name.is_empty()) else {
+            let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|name| name.to_string()).collect();
+            names.extend(user_themes());
+            self.messages.push(Message::system(format!("Theme: {}", self.theme.name)));
+            for name in names {
+                let marker = if name == self.theme.name { ">" } else { " " };
+                self.messages.push(Message::system(format!("  {} {}", marker, name)));
+            }
+            self.messages.push(Message::system("Switch with /theme <name>"));
+            return;
+        };
+
+        match Theme::load(name) {
+            Ok((theme, problems)) => {
+                for problem in problems {
+                    self.messages.push(Message::system(format!("Theme {}: {}", name, problem)));
+                }
+                self.theme = theme;
+                self.messages.push(Message::system(format!("Switched to theme {}", name)));
+            }
+            Err(e) => self.messages.push(Message::system(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes() {
+        for name in BUILTIN_THEMES {
+            assert_eq!(Theme::builtin(name).unwrap().name, name);
+        }
+        assert!(Theme::builtin("solarized").is_none());
+        assert_eq!(Theme::builtin("high-contrast").unwrap().border_or(Color::Cyan), Color::White);
+        assert_eq!(Theme::default().border_or(Color::Cyan), Color::Cyan);
+    }
+
+    #[test]
+    fn test_theme_from_settings() {
+        let mut settings = ThemeSettings {
+            name: Some("light".to_string()),
+            ..Default::default()
+        };
+        settings.colors.insert("sent".into(), "#005fd7".into());
+        settings.colors.insert("border".into(), "gray".into());
+        settings.colors.insert("diff_added".into(), "not-a-color".into());
+        settings.colors.insert("sparkle".into(), "red".into());
+
+        let (theme, problems) = Theme::from_settings(&settings);
+        assert_eq!(theme.name, "light");
+        assert_eq!(theme.sent, Color::Rgb(0x00, 0x5f, 0xd7));
+        assert_eq!(theme.border, Some(Color::Gray));
+        assert_eq!(theme.diff_added, Theme::builtin("light").unwrap().diff_added);
+        assert_eq!(problems.len(), 2);
+
+        settings.colors.clear();
+        settings.colors.insert("border".into(), "auto".into());
+        settings.name = Some("high-contrast".into());
+        assert_eq!(Theme::from_settings(&settings).0.border, None);
+    }
+}
@@ -4,11 +4,23 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
+use commander_core::{classify_diff_line, DiffLineKind};
+use commander_models::SlaStatus;
+use commander_work::sla::tag_status;
+
 use super::app::{App, ClickAction, InputMode, MessageDirection, SessionInfo, ViewMode};
+use super::diff::{changed_file_in, syntax_for};
+use super::inbox::{format_age, InboxKind};
+use super::keybindings::KeyAction;
+use super::theme::Theme;
+use super::wizard::{WizardOption, WizardStep};
+
+/// Messages with more lines than this get a clickable collapse header.
+const COLLAPSIBLE_LINES: usize = 8;
 
 /// Draw the TUI.
 pub fn draw(frame: &mut Frame, app: &mut App) {
@@ -19,7 +31,179 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         ViewMode::Normal => draw_normal(frame, app),
         ViewMode::Inspect => draw_inspect(frame, app),
         ViewMode::Sessions => draw_sessions(frame, app),
+        ViewMode::Diff => draw_diff(frame, app),
+        ViewMode::Approvals => draw_approvals(frame, app),
+        ViewMode::Logs => draw_logs(frame, app),
+        ViewMode::History => draw_history(frame, app),
+        ViewMode::Inbox => draw_inbox(frame, app),
+        ViewMode::Improvements => draw_improvements(frame, app),
+    }
+
+    if app.connect_wizard.is_some() {
+        draw_connect_wizard(frame, app);
+    }
+    if app.escalation_modal.is_some() {
+        draw_escalation_modal(frame, app);
+    }
+    if app.image_view.is_some() {
+        draw_image_view(frame, app);
+    }
+}
+
+/// Draw the frame of the image opened with `/view`; the image itself is
+/// written into the reserved area after the frame is drawn.
+fn draw_image_view(frame: &mut Frame, app: &mut App) {
+    let area = frame.area();
+    let rect = Rect::new(
+        area.x + area.width / 10,
+        area.y + area.height / 10,
+        area.width - area.width / 5,
+        area.height - area.height / 5,
+    );
+    let Some(view) = app.image_view.as_mut() else { return };
+
+    let block = Block::default()
+        .title(format!(" {} ", view.name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_or(Color::Cyan)));
+    let inner = block.inner(rect);
+    let hint = Paragraph::new(Line::from(Span::styled(
+        format!("{} | Esc close", view.description()),
+        Style::default().fg(app.theme.muted),
+    )));
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+    let hint_row = Rect::new(inner.x, inner.y + inner.height.saturating_sub(1), inner.width, inner.height.min(1));
+    frame.render_widget(hint, hint_row);
+    view.area = Some(Rect::new(inner.x, inner.y, inner.width, inner.height.saturating_sub(1)));
+}
+
+/// Draw the `/connect` wizard over the current view.
+fn draw_connect_wizard(frame: &mut Frame, app: &App) {
+    let Some(wizard) = &app.connect_wizard else { return };
+    let area = frame.area();
+    let width = area.width.min(76);
+    let height = area.height.min(20);
+    let rect = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let selected = Style::default().fg(app.theme.highlight).add_modifier(Modifier::BOLD);
+    let row = |text: String, is_selected: bool| {
+        let marker = if is_selected { ">" } else { " " };
+        Line::from(Span::styled(
+            format!(" {} {}", marker, text),
+            if is_selected { selected } else { Style::default() },
+        ))
+    };
+
+    let mut text = vec![Line::from(Span::styled(
+        wizard.dir.display().to_string(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    let hint = match wizard.step {
+        WizardStep::Directory => {
+            // Keep the selected row in view; borders, path, error and hint take 6 lines
+            let visible = (height as usize).saturating_sub(6).max(1);
+            let rows = wizard.dir_rows();
+            let first = wizard.dir_selected.saturating_sub(visible - 1);
+            for (i, name) in rows.into_iter().enumerate().skip(first).take(visible) {
+                text.push(row(name, i == wizard.dir_selected));
+            }
+            "Up/Down select | Enter open/use | Left parent | Esc cancel"
+        }
+        WizardStep::Adapter => {
+            for (i, id) in wizard.adapters.iter().enumerate() {
+                let label = match app.registry.get(id) {
+                    Some(adapter) => format!("{:<14} {}", id, adapter.info().name),
+                    None => id.clone(),
+                };
+                text.push(row(label, i == wizard.adapter_selected));
+            }
+            "Up/Down select | Enter next | Esc back"
+        }
+        WizardStep::Name => {
+            text.push(Line::from(format!("Adapter: {}", wizard.adapters[wizard.adapter_selected])));
+            text.push(Line::from(""));
+            text.push(Line::from(vec![
+                Span::raw("Name: "),
+                Span::styled(format!("{}_", wizard.name), selected),
+            ]));
+            "Type to edit | Enter next | Esc back"
+        }
+        WizardStep::Options => {
+            text.push(Line::from(format!(
+                "Adapter: {}   Name: {}",
+                wizard.adapters[wizard.adapter_selected], wizard.name
+            )));
+            text.push(Line::from(""));
+            for (i, option) in WizardOption::ALL.iter().enumerate() {
+                let check = if wizard.is_set(*option) { "x" } else { " " };
+                text.push(row(format!("[{}] {}", check, option.label()), i == wizard.option_selected));
+            }
+            "Up/Down select | Space toggle | Enter create and connect | Esc back"
+        }
+    };
+    text.push(Line::from(""));
+    if let Some(error) = &wizard.error {
+        text.push(Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))));
     }
+    text.push(Line::from(Span::styled(hint, Style::default().fg(app.theme.muted))));
+
+    let modal = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .block(Block::default()
+            .title(format!(" New project - {} ", wizard.step.title()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Cyan))));
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(modal, rect);
+}
+
+/// Draw the escalated blocking event over the current view.
+fn draw_escalation_modal(frame: &mut Frame, app: &App) {
+    let Some(escalation) = &app.escalation_modal else { return };
+    let area = frame.area();
+    let width = area.width.min(70);
+    let height = area.height.min(8);
+    let rect = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let text = vec![
+        Line::from(Span::styled(
+            escalation.title.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("Project: {}", escalation.project_id)),
+        Line::from(format!(
+            "Escalated at {} (attempt {})",
+            escalation.escalated_at.with_timezone(&chrono::Local).format("%H:%M"),
+            escalation.attempt
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("[Enter/a] acknowledge   [Esc/d] dismiss   /ack {}", escalation.short_id()),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+    let modal = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .block(Block::default()
+            .title(format!(" {} ", escalation.label()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red)));
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(modal, rect);
 }
 
 /// Draw normal chat mode.
@@ -36,10 +220,15 @@ fn draw_normal(frame: &mut Frame, app: &mut App) {
         0
     };
 
+    // Watchdog banner when a session is stuck or looping on errors
+    let banner = app.watchdog_banner();
+    let banner_height = if banner.is_some() { 1 } else { 0 };
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),                                      // Header
+            Constraint::Length(banner_height),                          // Watchdog banner (if active)
             Constraint::Min(5),                                         // Output area
             Constraint::Length(1),                                      // Status/Progress bar
             Constraint::Length(option_height),                          // Option selector (if active)
@@ -49,25 +238,28 @@ fn draw_normal(frame: &mut Frame, app: &mut App) {
         .split(frame.area());
 
     draw_header(frame, app, chunks[0]);
-    draw_output(frame, app, chunks[1]);
-    draw_status(frame, app, chunks[2]);
+    if let Some(text) = banner {
+        draw_banner(frame, &text, chunks[1]);
+    }
+    draw_output(frame, app, chunks[2]);
+    draw_status(frame, app, chunks[3]);
 
     // Draw option selector if active
     if app.option_mode && option_height > 0 {
-        draw_option_selector(frame, app, chunks[3]);
-        draw_input(frame, app, chunks[4]);
-        draw_footer(frame, app, chunks[5]);
+        draw_option_selector(frame, app, chunks[4]);
+        draw_input(frame, app, chunks[5]);
+        draw_footer(frame, app, chunks[6]);
     } else {
-        draw_input(frame, app, chunks[4]);
-        draw_footer(frame, app, chunks[5]);
+        draw_input(frame, app, chunks[5]);
+        draw_footer(frame, app, chunks[6]);
     }
 
     // Store output area rect for click detection
-    app.output_area = Some(chunks[1]);
+    app.output_area = Some(chunks[2]);
 }
 
 /// Draw inspect mode (live tmux view).
-fn draw_inspect(frame: &mut Frame, app: &App) {
+fn draw_inspect(frame: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -79,7 +271,11 @@ fn draw_inspect(frame: &mut Frame, app: &App) {
 
     // Header with magenta background to indicate inspect mode
     let project_name = app.project.as_deref().unwrap_or("none");
-    let header_text = format!(" Commander - [{}] INSPECT MODE                    F2 to exit ", project_name);
+    let inspect_key = view_key(app, KeyAction::Inspect);
+    let header_text = format!(
+        " Commander - [{}] INSPECT MODE                    {} to exit ",
+        project_name, inspect_key
+    );
     let header = Paragraph::new(header_text)
         .style(Style::default().bg(Color::Magenta).fg(Color::White).add_modifier(Modifier::BOLD));
     frame.render_widget(header, chunks[0]);
@@ -109,7 +305,7 @@ fn draw_inspect(frame: &mut Frame, app: &App) {
 
     let tmux_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta))
+        .border_style(Style::default().fg(app.theme.border_or(Color::Magenta)))
         .title(title);
 
     let content = Paragraph::new(visible_content)
@@ -118,13 +314,20 @@ fn draw_inspect(frame: &mut Frame, app: &App) {
     frame.render_widget(content, chunks[1]);
 
     // Footer
-    let footer = Paragraph::new(" Live tmux view | Auto-refresh 100ms | Up/Down scroll | F2/Esc/q return to chat ")
-        .style(Style::default().bg(Color::DarkGray).fg(Color::White));
-    frame.render_widget(footer, chunks[2]);
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(&format!(" {} Back ", inspect_key), ClickAction::ToggleInspect)],
+        &format!(
+            "Live tmux view | Auto-refresh 100ms | Up/Down/wheel scroll | {} return to chat",
+            back_keys(&inspect_key, "q")
+        ),
+    );
 }
 
 /// Draw sessions list view.
-fn draw_sessions(frame: &mut Frame, app: &App) {
+fn draw_sessions(frame: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -135,45 +338,511 @@ fn draw_sessions(frame: &mut Frame, app: &App) {
         .split(frame.area());
 
     // Header with cyan background for sessions mode
-    let header = Paragraph::new(" Commander - Sessions                                     F3 to exit ")
+    let sessions_key = view_key(app, KeyAction::Sessions);
+    let header = Paragraph::new(format!(
+        " Commander - Sessions                                     {} to exit ",
+        sessions_key
+    ))
         .style(Style::default().bg(Color::Cyan).fg(Color::Black).add_modifier(Modifier::BOLD));
     frame.render_widget(header, chunks[0]);
 
-    // Session list
-    let items: Vec<ListItem> = app.session_list.iter().enumerate().map(|(i, s)| {
-        format_session_item(i, s, app.session_selected)
-    }).collect();
+    // Session list, under a heading per workspace once any session has one
+    let grouped = app.session_list.iter().any(|s| s.workspace.is_some());
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut session_rows: Vec<(usize, usize)> = Vec::new();
+    let mut current_group: Option<Option<&str>> = None;
+    for (i, s) in app.session_list.iter().enumerate() {
+        let group = s.workspace.as_deref();
+        if grouped && current_group != Some(group) {
+            current_group = Some(group);
+            items.push(ListItem::new(format!(" {}", group.unwrap_or("(no workspace)")))
+                .style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+        }
+        session_rows.push((items.len(), i));
+        items.push(format_session_item(i, s, app.session_selected, &app.theme));
+    }
 
+    let title = match &app.workspace {
+        Some(workspace) => format!(" Available Sessions - {} ", workspace),
+        None => " Available Sessions ".to_string(),
+    };
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
-            .title(" Available Sessions "));
+            .border_style(Style::default().fg(app.theme.border_or(Color::Cyan)))
+            .title(title));
     frame.render_widget(list, chunks[1]);
 
+    // One clickable row per visible session
+    let visible_rows = chunks[1].height.saturating_sub(2) as usize;
+    for (row_index, index) in session_rows.into_iter().filter(|(row, _)| *row < visible_rows) {
+        let row = Rect {
+            x: chunks[1].x + 1,
+            y: chunks[1].y + 1 + row_index as u16,
+            width: chunks[1].width.saturating_sub(2),
+            height: 1,
+        };
+        app.add_clickable_item(row, ClickAction::SelectSession(index));
+    }
+
     // Footer
-    let footer = Paragraph::new(" Up/Down select | Enter connect | d delete | F3/Esc back ")
-        .style(Style::default().bg(Color::DarkGray).fg(Color::White));
-    frame.render_widget(footer, chunks[2]);
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(&format!(" {} Back ", sessions_key), ClickAction::ToggleSessions)],
+        &format!("Up/Down select | Enter/click connect | d delete | {} back", back_keys(&sessions_key, "")),
+    );
+}
+
+/// Draw the diff view (`git diff` of the connected project).
+fn draw_diff(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Diff content
+            Constraint::Length(1),  // Footer
+        ])
+        .split(frame.area());
+
+    let project_name = app.project.as_deref().unwrap_or("none");
+    let stats = commander_core::diff_stats(&app.diff_content);
+    let added: usize = stats.iter().map(|s| s.added).sum();
+    let removed: usize = stats.iter().map(|s| s.removed).sum();
+    let header_text = format!(
+        " Commander - [{}] DIFF | {} file{} +{} -{} ",
+        project_name,
+        stats.len(),
+        if stats.len() == 1 { "" } else { "s" },
+        added,
+        removed
+    );
+    let header = Paragraph::new(header_text)
+        .style(Style::default().bg(Color::Green).fg(Color::Black).add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut current_file = String::new();
+    for line in app.diff_content.lines() {
+        let kind = classify_diff_line(line);
+        if kind == DiffLineKind::FileHeader {
+            current_file = commander_core::diff::header_path(line).unwrap_or_default().to_string();
+        }
+        lines.push(diff_line(line, kind, &current_file, &app.theme));
+    }
+
+    let title = match (&app.diff_file, app.diff_scroll) {
+        (Some(file), 0) => format!(" git diff: {} ", file),
+        (Some(file), n) => format!(" git diff: {} [line {}] ", file, n + 1),
+        (None, 0) => " git diff ".to_string(),
+        (None, n) => format!(" git diff [line {}] ", n + 1),
+    };
+
+    let content = Paragraph::new(Text::from(lines))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Green)))
+            .title(title))
+        .scroll((app.diff_scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(content, chunks[1]);
+
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(" Esc Back ", ClickAction::CloseDiff)],
+        "Up/Down/PgUp/PgDn/wheel scroll | Esc/q return to chat",
+    );
+}
+
+/// Draw the log viewer, newest lines at the bottom.
+fn draw_logs(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Log lines
+            Constraint::Length(1),  // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(format!(" Commander - Logs | {} ", app.log_title))
+        .style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let height = chunks[1].height.saturating_sub(2) as usize;
+    let end = app.log_lines.len().saturating_sub(app.log_scroll);
+    let start = end.saturating_sub(height);
+    let lines: Vec<Line> = if app.log_lines.is_empty() {
+        vec![Line::from(Span::styled("  No log lines yet", Style::default().fg(app.theme.muted)))]
+    } else {
+        app.log_lines[start..end].iter().map(|line| log_line(line)).collect()
+    };
+
+    let title = if app.log_scroll == 0 {
+        " following ".to_string()
+    } else {
+        format!(" {} lines back ", app.log_scroll)
+    };
+    let content = Paragraph::new(Text::from(lines))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Blue)))
+            .title(title));
+    frame.render_widget(content, chunks[1]);
+
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(" Esc Back ", ClickAction::CloseLogs)],
+        "Up/Down/PgUp/PgDn/wheel scroll | End follow | Esc/q return to chat",
+    );
+}
+
+/// Draw a session transcript, highlighting the opened search result.
+fn draw_history(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Transcript
+            Constraint::Length(1),  // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(format!(" Commander - History | {} ", app.history_title))
+        .style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let height = chunks[1].height.saturating_sub(2) as usize;
+    let start = app.history_scroll.min(app.history_lines.len().saturating_sub(height));
+    let end = (start + height).min(app.history_lines.len());
+    let lines: Vec<Line> = app.history_lines[start..end]
+        .iter()
+        .map(|(entry, line)| {
+            let style = if app.history_focus == Some(*entry) {
+                Style::default().fg(app.theme.highlight).add_modifier(Modifier::BOLD)
+            } else if line.starts_with(' ') {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(app.theme.muted)
+            };
+            Line::from(Span::styled(line.as_str(), style))
+        })
+        .collect();
+
+    let title = format!(" lines {}-{} of {} ", start + 1, end, app.history_lines.len());
+    let content = Paragraph::new(Text::from(lines))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Blue)))
+            .title(title));
+    frame.render_widget(content, chunks[1]);
+
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(" Esc Back ", ClickAction::CloseHistory)],
+        "Up/Down/PgUp/PgDn/wheel scroll | Home/End jump | Esc/q return to chat",
+    );
+}
+
+/// Color a formatted log line by its level.
+fn log_line(line: &str) -> Line<'_> {
+    let color = match line.split_whitespace().nth(2) {
+        Some("ERROR") => Color::Red,
+        Some("WARN") => Color::Yellow,
+        Some("DEBUG") | Some("TRACE") => Color::DarkGray,
+        _ => Color::White,
+    };
+    Line::from(Span::styled(line, Style::default().fg(color)))
+}
+
+/// Draw the approvals pane (tool calls waiting for a decision).
+fn draw_approvals(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Request list
+            Constraint::Length(1),  // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(format!(
+        " Commander - Approvals | {} pending ",
+        app.approval_list.len()
+    ))
+    .style(Style::default().bg(Color::Magenta).fg(Color::Black).add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = if app.approval_list.is_empty() {
+        vec![ListItem::new("  Nothing awaiting approval").style(Style::default().fg(app.theme.muted))]
+    } else {
+        app.approval_list
+            .iter()
+            .enumerate()
+            .map(|(i, request)| {
+                let selected = i == app.approval_selected;
+                let marker = if selected { ">" } else { " " };
+                let style = if selected {
+                    Style::default().fg(app.theme.highlight).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(format!(
+                    "  {} {}  {}  [{}] {:<10} {}",
+                    marker,
+                    request.id,
+                    request.created_at.with_timezone(&chrono::Local).format("%H:%M"),
+                    request.project,
+                    request.tool,
+                    request.summary
+                ))
+                .style(style)
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Magenta)))
+            .title(" Pending Approvals "),
+    );
+    frame.render_widget(list, chunks[1]);
+
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(" Esc Back ", ClickAction::CloseApprovals)],
+        "Up/Down select | a/Enter approve | d deny | Esc/q return to chat",
+    );
+}
+
+/// Draw the improvements pane (prompt patches awaiting review).
+fn draw_improvements(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Patch list
+            Constraint::Length(1),  // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(format!(
+        " Commander - Prompt Improvements | {} proposed ",
+        app.improvement_list.len()
+    ))
+    .style(Style::default().bg(Color::Magenta).fg(Color::Black).add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = if app.improvement_list.is_empty() {
+        vec![ListItem::new(
+            "  No proposed patches (commander agent improvements generate)",
+        )
+        .style(Style::default().fg(app.theme.muted))]
+    } else {
+        app.improvement_list
+            .iter()
+            .enumerate()
+            .map(|(i, patch)| {
+                let selected = i == app.improvement_selected;
+                let marker = if selected { ">" } else { " " };
+                let style = if selected {
+                    Style::default().fg(app.theme.highlight).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(format!(
+                    "  {} {}  {}  [{}] {:.0}%  {}",
+                    marker,
+                    patch.id,
+                    patch.created_at.with_timezone(&chrono::Local).format("%m-%d %H:%M"),
+                    patch.template,
+                    patch.improvement.confidence * 100.0,
+                    patch.instruction
+                ))
+                .style(style)
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Magenta)))
+            .title(" Proposed Prompt Patches "),
+    );
+    frame.render_widget(list, chunks[1]);
+
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(" Esc Back ", ClickAction::CloseImprovements)],
+        "Up/Down select | a/Enter apply | r reject | Esc/q return to chat",
+    );
+}
+
+/// Draw the inbox (what needs attention across all projects).
+fn draw_inbox(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Item list
+            Constraint::Length(1),  // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(format!(" Commander - Inbox | {} items ", app.inbox_items.len()))
+        .style(Style::default().bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let now = chrono::Utc::now();
+    let items: Vec<ListItem> = if app.inbox_items.is_empty() {
+        vec![ListItem::new("  Nothing needs your attention").style(Style::default().fg(app.theme.muted))]
+    } else {
+        app.inbox_items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let selected = i == app.inbox_selected;
+                let marker = if selected { ">" } else { " " };
+                let color = match item.kind {
+                    InboxKind::Error => Color::Red,
+                    InboxKind::Overdue => Color::Yellow,
+                    InboxKind::Waiting => Color::Green,
+                    InboxKind::Notification => app.theme.muted,
+                };
+                let style = if selected {
+                    Style::default().fg(app.theme.highlight).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("  {} ", marker), style),
+                    Span::styled(format!("{:<8}", item.kind.label()), Style::default().fg(color)),
+                    Span::styled(
+                        format!("{:>4}  [{}] {}", format_age(item.since, now), item.project, item.title),
+                        style,
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Yellow)))
+            .title(" Needs Attention "),
+    );
+    frame.render_widget(list, chunks[1]);
+
+    draw_footer_bar(
+        frame,
+        app,
+        chunks[2],
+        &[(" Esc Back ", ClickAction::CloseInbox)],
+        "Up/Down select | Enter go to project | a acknowledge | s snooze 1h | Esc/q return to chat",
+    );
+}
+
+/// Style one line of diff output.
+///
+/// Added and removed lines take the theme's diff colors; context lines get
+/// comment and keyword highlighting for the file's language.
+fn diff_line(line: &str, kind: DiffLineKind, path: &str, theme: &Theme) -> Line<'static> {
+    match kind {
+        DiffLineKind::FileHeader => Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(theme.diff_file).add_modifier(Modifier::BOLD),
+        )),
+        DiffLineKind::HunkHeader => Line::from(Span::styled(line.to_string(), Style::default().fg(theme.diff_hunk))),
+        DiffLineKind::Meta => Line::from(Span::styled(line.to_string(), Style::default().fg(theme.diff_meta))),
+        DiffLineKind::Added | DiffLineKind::Removed | DiffLineKind::Context => {
+            let base = match kind {
+                DiffLineKind::Added => Style::default().fg(theme.diff_added),
+                DiffLineKind::Removed => Style::default().fg(theme.diff_removed),
+                _ => Style::default(),
+            };
+            let (marker, code) = line.split_at(line.chars().next().map_or(0, char::len_utf8));
+            let mut spans = vec![Span::styled(marker.to_string(), base)];
+            spans.extend(highlight_code(code, path, base));
+            Line::from(spans)
+        }
+    }
+}
+
+/// Split a code line into spans with comments and keywords highlighted.
+fn highlight_code(code: &str, path: &str, base: Style) -> Vec<Span<'static>> {
+    let (comment, keywords) = syntax_for(path);
+    let (code, trailing_comment) = match code.find(comment) {
+        Some(pos) if !comment.is_empty() => (&code[..pos], Some(&code[pos..])),
+        _ => (code, None),
+    };
+
+    let mut spans = Vec::new();
+    let mut word = String::new();
+    let mut other = String::new();
+    let flush_word = |word: &mut String, spans: &mut Vec<Span<'static>>| {
+        if word.is_empty() {
+            return;
+        }
+        let style = if keywords.contains(&word.as_str()) {
+            base.add_modifier(Modifier::BOLD).fg(base.fg.unwrap_or(Color::Magenta))
+        } else {
+            base
+        };
+        spans.push(Span::styled(std::mem::take(word), style));
+    };
+
+    for c in code.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            if !other.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut other), base));
+            }
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut spans);
+            other.push(c);
+        }
+    }
+    flush_word(&mut word, &mut spans);
+    if !other.is_empty() {
+        spans.push(Span::styled(other, base));
+    }
+    if let Some(comment) = trailing_comment {
+        spans.push(Span::styled(comment.to_string(), base.add_modifier(Modifier::DIM)));
+    }
+    spans
 }
 
 /// Format a session list item.
 /// Uses [Claude], [Shell], or [?] based on detected adapter type.
-fn format_session_item(index: usize, session: &SessionInfo, selected: usize) -> ListItem<'static> {
+fn format_session_item(index: usize, session: &SessionInfo, selected: usize, theme: &Theme) -> ListItem<'static> {
     let marker = if index == selected { ">" } else { " " };
 
     // Type indicator based on detected adapter
     let type_indicator = session.adapter.indicator();
 
     // Status indicator
-    let status = if session.is_connected {
-        "connected"
-    } else {
-        ""
+    let status = match (session.is_connected, session.is_paused) {
+        (true, true) => "connected, paused",
+        (true, false) => "connected",
+        (false, true) => "paused",
+        (false, false) => "",
     };
 
     let style = if index == selected {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)
     } else if session.is_connected {
         Style::default().fg(Color::Green)
     } else {
@@ -181,7 +850,7 @@ fn format_session_item(index: usize, session: &SessionInfo, selected: usize) ->
         match session.adapter {
             commander_core::Adapter::Claude => Style::default().fg(Color::Cyan),
             commander_core::Adapter::Shell => Style::default(),
-            commander_core::Adapter::Unknown => Style::default().fg(Color::DarkGray),
+            commander_core::Adapter::Unknown => Style::default().fg(theme.muted),
         }
     };
 
@@ -195,17 +864,74 @@ fn format_session_item(index: usize, session: &SessionInfo, selected: usize) ->
 }
 
 /// Draw the header bar.
-fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
+fn draw_header(frame: &mut Frame, app: &mut App, area: Rect) {
+    let lock = match &app.project {
+        Some(name) if app.is_read_only(name) => "🔒 ",
+        _ => "",
+    };
+    let lock = match &app.project {
+        Some(name) if app.is_project_paused(name) => format!("{}⏸ ", lock),
+        _ => lock.to_string(),
+    };
     let header_text = match (&app.project, &app.project_path) {
-        (Some(name), Some(path)) => format!(" Commander - [{}] {} ", name, path),
-        (Some(name), None) => format!(" Commander - [{}] connected ", name),
+        (Some(name), Some(path)) => match app.changed_file_count() {
+            0 => format!(" Commander - {}[{}] {} ", lock, name, path),
+            1 => format!(" Commander - {}[{}] {} | 1 file changed ", lock, name, path),
+            n => format!(" Commander - {}[{}] {} | {} files changed ", lock, name, path, n),
+        },
+        (Some(name), None) => format!(" Commander - {}[{}] connected ", lock, name),
         (None, _) => " Commander - disconnected ".to_string(),
     };
+    let header_text = match &app.workspace {
+        Some(workspace) => format!("{}| workspace: {} ", header_text, workspace),
+        None => header_text,
+    };
 
     let header = Paragraph::new(header_text)
         .style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD));
 
     frame.render_widget(header, area);
+
+    // Clicking the header opens the diff of the changed files
+    if app.project_path.is_some() && app.changed_file_count() > 0 {
+        app.add_clickable_item(area, ClickAction::ShowDiff(None));
+    }
+}
+
+/// Link changed-file lines in a message body to the diff view.
+fn push_changed_file_actions(body: &[&str], first_line: usize, line_actions: &mut Vec<(usize, ClickAction)>) {
+    for (offset, line) in body.iter().enumerate().skip(1) {
+        if let Some(path) = changed_file_in(line) {
+            line_actions.push((first_line + offset, ClickAction::ShowDiff(Some(path.to_string()))));
+        }
+    }
+}
+
+/// Link image lines listed after a response to the image view.
+fn push_image_actions(body: &[&str], first_line: usize, line_actions: &mut Vec<(usize, ClickAction)>) {
+    for (offset, line) in body.iter().enumerate().skip(1) {
+        if let Some(path) = super::view::image_in(line) {
+            line_actions.push((first_line + offset, ClickAction::ViewImage(path.to_string())));
+        }
+    }
+}
+
+/// `style` colored by the SLA countdown tag in `line`, if any.
+fn sla_style(line: &str, style: Style, theme: &Theme) -> Style {
+    match tag_status(line) {
+        Some(SlaStatus::DueSoon) => style.fg(theme.due_soon),
+        Some(SlaStatus::Overdue) => style.fg(theme.overdue),
+        Some(SlaStatus::Breached) => style.fg(theme.overdue).add_modifier(Modifier::BOLD),
+        _ => style,
+    }
+}
+
+/// Draw the watchdog alert banner.
+fn draw_banner(frame: &mut Frame, text: &str, area: Rect) {
+    let banner = Paragraph::new(format!(" ! {} ", text))
+        .style(Style::default().bg(Color::Red).fg(Color::White).add_modifier(Modifier::BOLD));
+
+    frame.render_widget(banner, area);
 }
 
 /// Draw the scrollable output area.
@@ -216,38 +942,75 @@ fn draw_output(frame: &mut Frame, app: &mut App, area: Rect) {
         " Output ".to_string()
     };
 
-    // Build lines from messages and track session names for clickable regions
-    let mut session_line_info: Vec<(usize, String)> = Vec::new(); // (line_index, session_name)
+    // Build lines from messages, tracking the click action for each message's first line
+    let mut lines: Vec<Line> = Vec::new();
+    let mut line_actions: Vec<(usize, ClickAction)> = Vec::new(); // (line_index, action)
+    let mut message_starts: Vec<(usize, usize)> = Vec::new(); // (line_index, message index)
 
-    let lines: Vec<Line> = app
-        .messages
-        .iter()
-        .enumerate()
-        .map(|(idx, msg)| {
-            let style = match msg.direction {
-                MessageDirection::Sent => Style::default().fg(Color::Cyan),
-                MessageDirection::Received => Style::default().fg(Color::Green),
-                MessageDirection::System => Style::default().fg(Color::Yellow),
-            };
+    for (idx, msg) in app.messages.iter().enumerate() {
+        let style = match msg.direction {
+            MessageDirection::Sent => Style::default().fg(app.theme.sent),
+            MessageDirection::Received => Style::default().fg(app.theme.received),
+            MessageDirection::System => Style::default().fg(app.theme.system),
+        };
 
-            let prefix = match msg.direction {
-                MessageDirection::Sent => format!("[{}] > ", msg.project),
-                MessageDirection::Received => format!("[{}] ", msg.project),
-                MessageDirection::System => String::new(),
-            };
+        let prefix = match msg.direction {
+            MessageDirection::Sent => format!("[{}] > ", msg.project),
+            MessageDirection::Received => format!("[{}] ", msg.project),
+            MessageDirection::System => String::new(),
+        };
 
-            let content = format!("{}{}", prefix, msg.content);
+        // Work items close to or past their deadline stand out
+        let line_style = |line: &str| match msg.direction {
+            MessageDirection::System => sla_style(line, style, &app.theme),
+            _ => style,
+        };
 
-            // Detect session names in /list output (format: "  [Claude|Shell|?] session-name ...")
-            if msg.direction == MessageDirection::System {
-                if let Some(session_name) = extract_clickable_session(&msg.content) {
-                    session_line_info.push((idx, session_name));
-                }
+        let body: Vec<&str> = if msg.content.is_empty() {
+            vec![""]
+        } else {
+            msg.content.lines().collect()
+        };
+        let first_line = lines.len();
+        message_starts.push((first_line, idx));
+
+        if body.len() > COLLAPSIBLE_LINES {
+            // Long message: clickable header with a collapse marker
+            let header = if msg.collapsed {
+                format!("{}▸ {} … [+{} lines]", prefix, body[0], body.len() - 1)
+            } else {
+                format!("{}▾ {}", prefix, body[0])
+            };
+            lines.push(Line::from(Span::styled(header, style)));
+            if !msg.collapsed {
+                lines.extend(body[1..].iter().map(|l| Line::from(Span::styled(l.to_string(), line_style(l)))));
+            }
+            line_actions.push((first_line, ClickAction::ToggleMessage(idx)));
+            if !msg.collapsed {
+                push_changed_file_actions(&body, first_line, &mut line_actions);
+                push_image_actions(&body, first_line, &mut line_actions);
             }
+            continue;
+        }
 
-            Line::from(vec![Span::styled(content, style)])
-        })
-        .collect();
+        lines.push(Line::from(Span::styled(format!("{}{}", prefix, body[0]), line_style(body[0]))));
+        lines.extend(body[1..].iter().map(|l| Line::from(Span::styled(l.to_string(), line_style(l)))));
+        push_changed_file_actions(&body, first_line, &mut line_actions);
+        push_image_actions(&body, first_line, &mut line_actions);
+
+        // Detect session names in /list output (format: "  [Claude|Shell|?] session-name ...")
+        if msg.direction == MessageDirection::System {
+            if let Some(session_name) = extract_clickable_session(&msg.content) {
+                line_actions.push((first_line, ClickAction::Connect(session_name)));
+            }
+            #[cfg(feature = "agents")]
+            match msg.content.as_str() {
+                super::diagnosis::APPLY_LINE => line_actions.push((first_line, ClickAction::ApplyDiagnosis)),
+                super::diagnosis::IGNORE_LINE => line_actions.push((first_line, ClickAction::IgnoreDiagnosis)),
+                _ => {}
+            }
+        }
+    }
 
     // Calculate scroll - estimate wrapped line count
     let inner_height = area.height.saturating_sub(2) as usize;
@@ -258,9 +1021,9 @@ fn draw_output(frame: &mut Frame, app: &mut App, area: Rect) {
     cumulative_lines.push(0);
     let mut total = 0usize;
     for line in &lines {
-        let line_len: usize = line.spans.iter().map(|s| s.content.len()).sum();
+        let line_len = line.width();
         let wrapped_count = if inner_width > 0 {
-            ((line_len + inner_width - 1) / inner_width).max(1)
+            line_len.div_ceil(inner_width).max(1)
         } else {
             1
         };
@@ -276,7 +1039,15 @@ fn draw_output(frame: &mut Frame, app: &mut App, area: Rect) {
         0
     };
 
-    // Register clickable items for visible session lines
+    // The yank key copies the last message starting above the bottom edge
+    let bottom = scroll_offset + inner_height;
+    app.message_in_view = message_starts
+        .iter()
+        .rev()
+        .find(|(line_idx, _)| cumulative_lines.get(*line_idx).copied().unwrap_or(0) < bottom)
+        .map(|(_, idx)| *idx);
+
+    // Register clickable items for visible session lines and message headers
     let inner_area = Rect {
         x: area.x + 1,
         y: area.y + 1,
@@ -284,7 +1055,7 @@ fn draw_output(frame: &mut Frame, app: &mut App, area: Rect) {
         height: area.height.saturating_sub(2),
     };
 
-    for (line_idx, session_name) in session_line_info {
+    for (line_idx, action) in line_actions {
         // Get the screen row for this line (after wrapping and scroll)
         let line_start = cumulative_lines.get(line_idx).copied().unwrap_or(0);
 
@@ -292,14 +1063,13 @@ fn draw_output(frame: &mut Frame, app: &mut App, area: Rect) {
         if line_start >= scroll_offset {
             let visible_row = line_start - scroll_offset;
             if visible_row < inner_height {
-                // Create clickable region for this session line
                 let click_rect = Rect {
                     x: inner_area.x,
                     y: inner_area.y + visible_row as u16,
                     width: inner_area.width,
                     height: 1,
                 };
-                app.add_clickable_item(click_rect, ClickAction::Connect(session_name));
+                app.add_clickable_item(click_rect, action);
             }
         }
     }
@@ -307,7 +1077,10 @@ fn draw_output(frame: &mut Frame, app: &mut App, area: Rect) {
     let text = Text::from(lines);
 
     let output = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Reset)))
+            .title(title))
         .wrap(Wrap { trim: false })
         .scroll((scroll_offset as u16, 0));
 
@@ -361,17 +1134,31 @@ fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
         let line_count = app.response_buffer_len();
         let label = format!(" Receiving... ({} lines captured) ", line_count);
         let status = Paragraph::new(label)
-            .style(Style::default().bg(Color::Yellow).fg(Color::Black));
+            .style(Style::default().bg(app.theme.working_bg).fg(app.theme.working_fg));
         frame.render_widget(status, area);
     } else {
         // Show connection status
-        let status_text = if let Some(project) = &app.project {
-            format!(" Ready - {} ", project)
+        let mut status_text = if let Some(project) = &app.project {
+            match app.context_usage_label() {
+                Some(usage) => format!(" Ready - {} | ctx {} ", project, usage),
+                None => format!(" Ready - {} ", project),
+            }
         } else {
             " No project connected ".to_string()
         };
-        let status = Paragraph::new(status_text)
-            .style(Style::default().bg(Color::DarkGray).fg(Color::White));
+        if let Some(bot) = app.bot_health_label() {
+            status_text.push_str(&format!("| {} ", bot));
+        }
+        let mut status = vec![Span::raw(status_text)];
+        if commander_core::connectivity::is_offline() {
+            // LLM summaries and analysis are off until the network is back
+            status.push(Span::styled(
+                " OFFLINE ",
+                Style::default().bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD),
+            ));
+        }
+        let status = Paragraph::new(Line::from(status))
+            .style(Style::default().bg(app.theme.status_bg).fg(app.theme.status_fg));
         frame.render_widget(status, area);
     }
 }
@@ -380,7 +1167,7 @@ fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
 fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
     let input_style = match app.input_mode {
         InputMode::Normal => Style::default(),
-        InputMode::Scrolling => Style::default().fg(Color::DarkGray),
+        InputMode::Scrolling => Style::default().fg(app.theme.muted),
         InputMode::SelectingOption => Style::default().fg(Color::Cyan),
     };
 
@@ -404,7 +1191,7 @@ fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
         // Render hint
         if let Some(hint_text) = hint {
             let hint_widget = Paragraph::new(format!("  {}", hint_text))
-                .style(Style::default().fg(Color::DarkGray));
+                .style(Style::default().fg(app.theme.muted));
             frame.render_widget(hint_widget, chunks[1]);
         }
 
@@ -417,7 +1204,10 @@ fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
 
     let input = Paragraph::new(input_text.clone())
         .style(input_style)
-        .block(Block::default().borders(Borders::ALL).title(" Input "))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_or(Color::Reset)))
+            .title(" Input "))
         .wrap(Wrap { trim: false });
 
     frame.render_widget(input, input_area);
@@ -444,7 +1234,7 @@ fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Draw the footer with keybindings.
-fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+fn draw_footer(frame: &mut Frame, app: &mut App, area: Rect) {
     let project_indicator = if let Some(p) = &app.project {
         p.as_str()
     } else {
@@ -452,16 +1242,66 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
     };
 
     let keys = if app.option_mode {
-        "↑/↓: navigate | Enter: confirm | Esc: cancel | A/B/1/2: quick select"
+        "↑/↓: navigate | Enter: confirm | Esc: cancel | A/B/1/2: quick select".to_string()
     } else if app.input_mode == InputMode::Scrolling {
-        "j/k scroll | Enter: back to input | q: quit"
+        "j/k scroll | Enter: back to input | q: quit".to_string()
     } else {
-        "↑/↓: history | PgUp/PgDn: scroll | /help | Ctrl+C: quit"
+        // Inspect and sessions have buttons; the other bound actions are listed
+        let mut keys = vec!["↑/↓: history".to_string(), "PgUp/PgDn: scroll".to_string()];
+        keys.extend(app.keybindings.chords(&[KeyAction::Inspect, KeyAction::Sessions]));
+        keys.push("/help".to_string());
+        keys.push("Ctrl+C: quit".to_string());
+        keys.join(" | ")
     };
 
-    let footer_text = format!(" {} | {} ", project_indicator, keys);
-    let footer = Paragraph::new(footer_text)
-        .style(Style::default().bg(Color::DarkGray).fg(Color::White));
+    let footer_text = format!("{} | {}", project_indicator, keys);
+    let mut buttons = Vec::new();
+    for (action, name, click) in [
+        (KeyAction::Inspect, "Inspect", ClickAction::ToggleInspect),
+        (KeyAction::Sessions, "Sessions", ClickAction::ToggleSessions),
+    ] {
+        if let Some(key) = app.keybindings.label(action) {
+            buttons.push((format!(" {} {} ", key, name), click));
+        }
+    }
+    let buttons: Vec<(&str, ClickAction)> = buttons.iter().map(|(label, click)| (label.as_str(), click.clone())).collect();
+    draw_footer_bar(frame, app, area, &buttons, &footer_text);
+}
+
+/// Label of the key toggling the view of `action`; `Esc` when unbound.
+fn view_key(app: &App, action: KeyAction) -> String {
+    app.keybindings.label(action).unwrap_or_else(|| "Esc".to_string())
+}
+
+/// Keys leaving a view: its toggle `key`, Esc and `extra` (if any).
+fn back_keys(key: &str, extra: &str) -> String {
+    let mut keys = vec![key];
+    if key != "Esc" {
+        keys.push("Esc");
+    }
+    if !extra.is_empty() {
+        keys.push(extra);
+    }
+    keys.join("/")
+}
+
+/// Draw a footer with clickable buttons followed by help text.
+fn draw_footer_bar(frame: &mut Frame, app: &mut App, area: Rect, buttons: &[(&str, ClickAction)], text: &str) {
+    let button_style = Style::default().bg(Color::Gray).fg(Color::Black);
+    let mut spans = vec![Span::raw(" ")];
+    let mut x = area.x + 1;
+
+    for (label, action) in buttons {
+        let width = Span::raw(*label).width() as u16;
+        app.add_clickable_item(Rect { x, y: area.y, width, height: 1 }, action.clone());
+        spans.push(Span::styled(*label, button_style));
+        spans.push(Span::raw(" "));
+        x += width + 1;
+    }
+    spans.push(Span::raw(format!("{} ", text)));
+
+    let footer = Paragraph::new(Line::from(spans))
+        .style(Style::default().bg(app.theme.status_bg).fg(app.theme.status_fg));
 
     frame.render_widget(footer, area);
 }
@@ -478,7 +1318,7 @@ fn draw_option_selector(frame: &mut Frame, app: &App, area: Rect) {
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(app.theme.border_or(Color::Cyan)))
             .title(title);
 
         // Build option items
@@ -523,6 +1363,16 @@ fn draw_option_selector(frame: &mut Frame, app: &App, area: Rect) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sla_style() {
+        let theme = Theme::default();
+        let base = Style::default().fg(theme.system);
+        assert_eq!(sla_style("[ ]   3f2a91bc Fix login [due in 3h 05m]", base, &theme), base);
+        assert_eq!(sla_style("[ ]   3f2a91bc Fix login [due soon: 45m left]", base, &theme).fg, Some(theme.due_soon));
+        assert_eq!(sla_style("[>]   3f2a91bc Fix login [overdue 2h 10m]", base, &theme).fg, Some(theme.overdue));
+        assert_eq!(sla_style("Work graph for api:", base, &theme), base);
+    }
+
     #[test]
     fn test_extract_clickable_session_claude() {
         let result = extract_clickable_session("  [Claude] myproject (connected) - Waiting for input");
@@ -553,6 +1403,30 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_draw_registers_click_targets() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new(dir.path());
+        app.messages.push(super::super::app::Message::received("proj", "line\n".repeat(20)));
+        let long_index = app.messages.len() - 1;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|f| draw(f, &mut app)).unwrap();
+
+        let actions: Vec<ClickAction> = app.clickable_items.iter().map(|i| i.action.clone()).collect();
+        assert!(actions.contains(&ClickAction::ToggleMessage(long_index)));
+        assert!(actions.contains(&ClickAction::ToggleInspect));
+        assert!(actions.contains(&ClickAction::ToggleSessions));
+
+        // Collapsing hides the body lines
+        app.toggle_message_collapsed(long_index);
+        terminal.draw(|f| draw(f, &mut app)).unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(screen.contains("[+19 lines]"));
+    }
+
     #[test]
     fn test_clickable_item_contains() {
         let item = super::super::app::ClickableItem {
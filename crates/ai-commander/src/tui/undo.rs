@@ -0,0 +1,114 @@
+//! Conversation-level undo for delegated tasks (feature-gated).
+//!
+//! Every message sent to a session checkpoints the project first, so
+//! `/undo` can roll back the file changes made by the last task. The undo
+//! is a two-step flow: `/undo` previews the affected files and
+//! `/undo confirm` reverts them.
+
+#[cfg(feature = "agents")]
+use std::path::Path;
+
+use super::app::App;
+#[cfg(feature = "agents")]
+use super::app::Message;
+
+/// Maximum number of files listed in the undo preview.
+#[cfg(feature = "agents")]
+const MAX_PREVIEW_FILES: usize = 15;
+
+#[cfg(feature = "agents")]
+impl App {
+    /// Checkpoint the connected project before sending it a task.
+    pub(super) fn checkpoint_before_task(&mut self, task: &str) {
+        let Some(session) = self.current_session_name() else { return };
+        let Some(path) = self.project_path.clone() else { return };
+        let Some(orchestrator) = self.orchestrator.as_mut() else { return };
+
+        if let Err(e) = orchestrator.checkpoint_task(&session, "claude_code", Path::new(&path), task) {
+            tracing::warn!(session = %session, error = %e, "failed to checkpoint before task");
+        }
+        // A new task invalidates any undo awaiting confirmation
+        self.pending_undo = None;
+        self.budget_notified = None;
+    }
+
+    /// Handle `/undo` (preview) and `/undo confirm` (revert).
+    pub fn handle_undo(&mut self, arg: Option<&str>) {
+        let Some(session) = self.current_session_name() else {
+            self.messages.push(Message::system("Not connected to any project"));
+            return;
+        };
+        let Some(orchestrator) = self.orchestrator.as_mut() else {
+            self.messages.push(Message::system("Agent orchestrator not available"));
+            return;
+        };
+
+        match arg {
+            Some("confirm") | Some("yes") | Some("y") => {
+                if self.pending_undo.as_deref() != Some(session.as_str()) {
+                    self.messages.push(Message::system("Nothing to confirm. Run /undo first."));
+                    return;
+                }
+                self.pending_undo = None;
+
+                match orchestrator.undo_last_task(&session) {
+                    Ok(Some((checkpoint, files))) => {
+                        self.messages.push(Message::system(format!(
+                            "Reverted \"{}\" ({} file{} restored)",
+                            checkpoint.task,
+                            files.len(),
+                            if files.len() == 1 { "" } else { "s" }
+                        )));
+                        self.changed_files.clear();
+                    }
+                    Ok(None) => self.messages.push(Message::system("Nothing to undo")),
+                    Err(e) => self.messages.push(Message::system(format!("Undo failed: {}", e))),
+                }
+            }
+            Some("cancel") | Some("no") | Some("n") => {
+                self.pending_undo = None;
+                self.messages.push(Message::system("Undo cancelled"));
+            }
+            Some(other) => {
+                self.messages.push(Message::system(format!(
+                    "Unknown /undo option '{}'. Usage: /undo [confirm|cancel]",
+                    other
+                )));
+            }
+            None => match orchestrator.preview_undo(&session) {
+                Ok(Some((checkpoint, files))) => {
+                    let age = chrono::Utc::now().signed_duration_since(checkpoint.created_at);
+                    self.messages.push(Message::system(format!(
+                        "Undo last task \"{}\" (sent {} min ago)?",
+                        checkpoint.task,
+                        age.num_minutes()
+                    )));
+                    if files.is_empty() {
+                        self.messages.push(Message::system("  No file changes since the task was sent."));
+                    } else {
+                        for file in files.iter().take(MAX_PREVIEW_FILES) {
+                            self.messages.push(Message::system(format!("  {}", file)));
+                        }
+                        if files.len() > MAX_PREVIEW_FILES {
+                            self.messages.push(Message::system(format!(
+                                "  ... and {} more",
+                                files.len() - MAX_PREVIEW_FILES
+                            )));
+                        }
+                    }
+                    self.messages.push(Message::system(
+                        "This resets commits made since then and restores your prior uncommitted work. Use /undo confirm to proceed or /undo cancel.",
+                    ));
+                    self.pending_undo = Some(session);
+                }
+                Ok(None) => self.messages.push(Message::system("Nothing to undo for this session")),
+                Err(e) => self.messages.push(Message::system(format!("Error: {}", e))),
+            },
+        }
+    }
+}
+
+#[cfg(not(feature = "agents"))]
+impl App {
+    pub(super) fn checkpoint_before_task(&mut self, _task: &str) {}
+}
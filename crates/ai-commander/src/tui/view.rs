@@ -0,0 +1,442 @@
+//! Inline images in the TUI.
+//!
+//! `/view <image>` opens a PNG, JPEG, GIF or WebP file (relative paths are
+//! resolved in the connected project) in a modal, drawn with the kitty
+//! graphics protocol or iTerm2 inline images. Terminals without either, or
+//! TUIs running inside tmux, get a textual description of the image instead.
+//!
+//! Image paths in a session's output, such as screenshots written by a
+//! browser-automation session, are listed after the response summary and
+//! open the image when clicked.
+
+use std::io::{self, Stdout, Write};
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use crossterm::{cursor::MoveTo, queue};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
+
+use super::app::{App, Message};
+
+/// Prefix of image lines listed after a response; such lines open the image.
+pub(super) const IMAGE_PREFIX: &str = "  🖼 ";
+
+/// Extensions treated as images in session output.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Largest image sent to the terminal.
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Base64 bytes per kitty graphics escape sequence.
+const KITTY_CHUNK: usize = 4096;
+
+/// Remove every image placed with the kitty graphics protocol.
+const KITTY_DELETE_ALL: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// How the terminal draws images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    /// Kitty graphics protocol (kitty, Ghostty).
+    Kitty,
+    /// iTerm2 inline images (iTerm2, WezTerm).
+    Iterm2,
+    /// No inline graphics: describe the image in text.
+    Text,
+}
+
+impl GraphicsProtocol {
+    /// Protocol of the terminal the TUI runs in.
+    pub fn detect() -> Self {
+        Self::detect_from(|name| std::env::var(name).ok())
+    }
+
+    /// Protocol for the environment read through `var`.
+    pub fn detect_from(var: impl Fn(&str) -> Option<String>) -> Self {
+        // tmux swallows graphics escapes unless passthrough is configured
+        if var("TMUX").is_some() {
+            return Self::Text;
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || term == "xterm-ghostty" || program == "ghostty" {
+            Self::Kitty
+        } else if program == "iTerm.app" || program == "WezTerm" {
+            Self::Iterm2
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Whether this protocol can draw `format`.
+    pub fn supports(self, format: ImageFormat) -> bool {
+        match self {
+            // Other formats would have to be decoded to pixels first
+            Self::Kitty => format == ImageFormat::Png,
+            Self::Iterm2 => true,
+            Self::Text => false,
+        }
+    }
+}
+
+/// Image file format, from the file's magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Format of the image in `data`, if it is one.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if data.starts_with(&[0xff, 0xd8]) {
+            Some(Self::Jpeg)
+        } else if data.starts_with(b"GIF8") {
+            Some(Self::Gif)
+        } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Gif => "GIF",
+            Self::Webp => "WebP",
+        }
+    }
+}
+
+/// Width and height in pixels of the image in `data`, read from its header.
+pub fn image_size(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| Some(u16::from_be_bytes([*data.get(i)?, *data.get(i + 1)?]) as u32);
+    let le16 = |i: usize| Some(u16::from_le_bytes([*data.get(i)?, *data.get(i + 1)?]) as u32);
+    let le24 = |i: usize| Some(u32::from_le_bytes([*data.get(i)?, *data.get(i + 1)?, *data.get(i + 2)?, 0]));
+
+    match ImageFormat::detect(data)? {
+        ImageFormat::Png => {
+            let be32 = |i: usize| Some(u32::from_be_bytes(data.get(i..i + 4)?.try_into().ok()?));
+            Some((be32(16)?, be32(20)?))
+        }
+        ImageFormat::Gif => Some((le16(6)?, le16(8)?)),
+        ImageFormat::Jpeg => {
+            // Walk the segments up to the start-of-frame marker
+            let mut i = 2;
+            while *data.get(i)? == 0xff {
+                let marker = *data.get(i + 1)?;
+                match marker {
+                    0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                        return Some((be16(i + 7)?, be16(i + 5)?));
+                    }
+                    0xd0..=0xd9 | 0x01 | 0xff => i += if marker == 0xff { 1 } else { 2 },
+                    _ => i += 2 + be16(i + 2)? as usize,
+                }
+            }
+            None
+        }
+        ImageFormat::Webp => match data.get(12..16)? {
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Image paths mentioned in `text`, in order and without duplicates.
+pub fn image_paths_in(text: &str) -> Vec<&str> {
+    let mut paths: Vec<&str> = Vec::new();
+    for word in text.split_whitespace() {
+        let word = word.trim_start_matches("file://");
+        let word = word.trim_matches(|c: char| "'\"`()[]<>,;".contains(c)).trim_end_matches(['.', ':']);
+        let is_image = Path::new(word)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+        if is_image && !paths.contains(&word) {
+            paths.push(word);
+        }
+    }
+    paths
+}
+
+/// Cells `(columns, rows)` an image of `size` pixels takes when fit into
+/// `area` cells, keeping its aspect ratio. Cells are taken to be twice as
+/// tall as they are wide.
+pub fn fit_cells(size: Option<(u32, u32)>, area: (u16, u16)) -> (u16, u16) {
+    let (max_cols, max_rows) = (area.0.max(1) as u64, area.1.max(1) as u64);
+    let Some((width, height)) = size.filter(|(w, h)| *w > 0 && *h > 0) else {
+        return (max_cols as u16, max_rows as u16);
+    };
+    let (width, height) = (width as u64, height as u64);
+    let rows = (max_cols * height).div_ceil(width * 2);
+    if rows <= max_rows {
+        (max_cols as u16, rows.max(1) as u16)
+    } else {
+        let cols = max_rows * width * 2 / height;
+        (cols.clamp(1, max_cols) as u16, max_rows as u16)
+    }
+}
+
+/// "1280×720 PNG, 84 KB"
+fn describe(format: ImageFormat, size: Option<(u32, u32)>, bytes: usize) -> String {
+    let kb = bytes.div_ceil(1024);
+    match size {
+        Some((width, height)) => format!("{}×{} {}, {} KB", width, height, format.name(), kb),
+        None => format!("{}, {} KB", format.name(), kb),
+    }
+}
+
+/// An image opened with `/view`.
+#[derive(Debug, Clone)]
+pub struct ImageView {
+    /// Path as given by the user.
+    pub name: String,
+    data: Vec<u8>,
+    format: ImageFormat,
+    size: Option<(u32, u32)>,
+    protocol: GraphicsProtocol,
+    /// Cells reserved for the image, set when the modal is drawn.
+    pub(super) area: Option<Rect>,
+    /// Cells the image was last written to.
+    drawn: Option<Rect>,
+}
+
+impl ImageView {
+    /// "1280×720 PNG, 84 KB"
+    pub fn description(&self) -> String {
+        describe(self.format, self.size, self.data.len())
+    }
+
+    /// Escape sequence drawing the image into `cols` × `rows` cells at the
+    /// cursor.
+    fn escape_sequence(&self, cols: u16, rows: u16) -> String {
+        let encoded = STANDARD.encode(&self.data);
+        match self.protocol {
+            GraphicsProtocol::Kitty => {
+                let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK).collect();
+                let mut out = String::from(KITTY_DELETE_ALL);
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = u8::from(i + 1 < chunks.len());
+                    let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+                    if i == 0 {
+                        out.push_str(&format!("\x1b_Ga=T,f=100,q=2,c={},r={},m={};{}\x1b\\", cols, rows, more, chunk));
+                    } else {
+                        out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+                    }
+                }
+                out
+            }
+            GraphicsProtocol::Iterm2 => format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                self.data.len(),
+                cols,
+                rows,
+                encoded
+            ),
+            GraphicsProtocol::Text => String::new(),
+        }
+    }
+}
+
+impl App {
+    /// Handle `/view <image>`.
+    pub fn handle_view(&mut self, arg: Option<&str>) {
+        let Some(name) = arg.map(str::trim).filter(|a| !a.is_empty()) else {
+            self.messages.push(Message::system("Usage: /view <image>"));
+            return;
+        };
+        let path = self.resolve_image_path(name);
+        let data = match std::fs::metadata(&path) {
+            Ok(meta) if meta.len() > MAX_IMAGE_BYTES => {
+                self.messages.push(Message::system(format!(
+                    "{} is too large to view ({} MB)",
+                    name,
+                    meta.len() / (1024 * 1024)
+                )));
+                return;
+            }
+            Ok(_) => std::fs::read(&path),
+            Err(e) => Err(e),
+        };
+        let data = match data {
+            Ok(data) => data,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Cannot open {}: {}", path.display(), e)));
+                return;
+            }
+        };
+        let Some(format) = ImageFormat::detect(&data) else {
+            self.messages.push(Message::system(format!("{} is not a PNG, JPEG, GIF or WebP image", name)));
+            return;
+        };
+
+        let size = image_size(&data);
+        let protocol = GraphicsProtocol::detect();
+        if !protocol.supports(format) {
+            let reason = match protocol {
+                GraphicsProtocol::Text => "this terminal has no inline graphics",
+                _ => "this terminal only draws PNG inline",
+            };
+            self.messages.push(Message::system(format!(
+                "🖼 {}: {} ({}; open {})",
+                name,
+                describe(format, size, data.len()),
+                reason,
+                path.display()
+            )));
+            return;
+        }
+
+        self.close_image_view();
+        self.image_view = Some(ImageView {
+            name: name.to_string(),
+            data,
+            format,
+            size,
+            protocol,
+            area: None,
+            drawn: None,
+        });
+    }
+
+    /// Close the image modal.
+    pub fn close_image_view(&mut self) {
+        if self.image_view.take().is_some() {
+            self.image_closed = true;
+        }
+    }
+
+    /// List the existing images mentioned in a response, as lines that open
+    /// them when clicked.
+    pub(super) fn show_image_links(&mut self, text: &str) {
+        let found: Vec<String> = image_paths_in(text)
+            .into_iter()
+            .filter(|name| self.resolve_image_path(name).is_file())
+            .map(str::to_string)
+            .collect();
+        if found.is_empty() {
+            return;
+        }
+        let mut message = String::from("Images in the output (click or /view <path>):");
+        for name in found {
+            message.push('\n');
+            message.push_str(IMAGE_PREFIX);
+            message.push_str(&name);
+        }
+        self.messages.push(Message::system(message));
+    }
+
+    /// `name` with `~` expanded and, when relative, inside the connected
+    /// project.
+    fn resolve_image_path(&self, name: &str) -> PathBuf {
+        let path = PathBuf::from(shellexpand::tilde(name).as_ref());
+        match &self.project_path {
+            Some(root) if path.is_relative() => Path::new(root).join(path),
+            _ => path,
+        }
+    }
+}
+
+/// The image named by an image line listed after a response, if `line` is one.
+pub(super) fn image_in(line: &str) -> Option<&str> {
+    line.strip_prefix(IMAGE_PREFIX).map(str::trim).filter(|path| !path.is_empty())
+}
+
+/// Remove a closed image from the screen and repaint. Called before drawing.
+pub(super) fn clear_closed_image(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) -> io::Result<()> {
+    if !std::mem::take(&mut app.image_closed) {
+        return Ok(());
+    }
+    let backend = terminal.backend_mut();
+    backend.write_all(KITTY_DELETE_ALL.as_bytes())?;
+    backend.flush()?;
+    // Inline images live in the cells; only a full repaint removes them
+    terminal.clear()
+}
+
+/// Write the open image into its modal, once per position. Called after
+/// drawing, since the frame's cells would paint over it.
+pub(super) fn draw_open_image(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) -> io::Result<()> {
+    let Some(view) = app.image_view.as_mut() else { return Ok(()) };
+    let Some(area) = view.area.filter(|area| area.width > 0 && area.height > 0) else {
+        return Ok(());
+    };
+    if view.drawn == Some(area) {
+        return Ok(());
+    }
+
+    let (cols, rows) = fit_cells(view.size, (area.width, area.height));
+    let x = area.x + (area.width - cols) / 2;
+    let backend = terminal.backend_mut();
+    queue!(backend, MoveTo(x, area.y))?;
+    backend.write_all(view.escape_sequence(cols, rows).as_bytes())?;
+    backend.flush()?;
+    view.drawn = Some(area);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_protocol() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        };
+        assert_eq!(GraphicsProtocol::detect_from(env(&[("TERM", "xterm-kitty")])), GraphicsProtocol::Kitty);
+        assert_eq!(GraphicsProtocol::detect_from(env(&[("TERM_PROGRAM", "iTerm.app")])), GraphicsProtocol::Iterm2);
+        assert_eq!(
+            GraphicsProtocol::detect_from(env(&[("TERM_PROGRAM", "iTerm.app"), ("TMUX", "/tmp/tmux-1000/default,1,0")])),
+            GraphicsProtocol::Text
+        );
+        assert_eq!(GraphicsProtocol::detect_from(env(&[("TERM", "xterm-256color")])), GraphicsProtocol::Text);
+        assert!(!GraphicsProtocol::Kitty.supports(ImageFormat::Jpeg));
+    }
+
+    #[test]
+    fn test_image_size_from_headers() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&1280u32.to_be_bytes());
+        png.extend_from_slice(&720u32.to_be_bytes());
+        assert_eq!(ImageFormat::detect(&png), Some(ImageFormat::Png));
+        assert_eq!(image_size(&png), Some((1280, 720)));
+
+        let gif = b"GIF89a\x40\x01\xc8\x00";
+        assert_eq!(image_size(gif), Some((320, 200)));
+
+        // SOI, an APP0 segment, then SOF0 with height 480 and width 640
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80,
+        ];
+        assert_eq!(image_size(&jpeg), Some((640, 480)));
+
+        assert_eq!(ImageFormat::detect(b"not an image"), None);
+        assert_eq!(image_size(b"\x89PNG\r\n\x1a\n"), None);
+    }
+
+    #[test]
+    fn test_image_paths_and_fit() {
+        let output = "Saved screenshot to shots/home.png. Also see (shots/Login.JPG) and shots/home.png; done.txt";
+        assert_eq!(image_paths_in(output), ["shots/home.png", "shots/Login.JPG"]);
+        assert_eq!(image_in("  🖼 shots/home.png"), Some("shots/home.png"));
+        assert_eq!(image_in("shots/home.png"), None);
+
+        // A 16:9 image in 80×40 cells fills the width
+        assert_eq!(fit_cells(Some((1600, 900)), (80, 40)), (80, 23));
+        // A tall image is limited by the rows
+        assert_eq!(fit_cells(Some((500, 2000)), (80, 20)), (10, 20));
+        assert_eq!(fit_cells(None, (80, 20)), (80, 20));
+    }
+}
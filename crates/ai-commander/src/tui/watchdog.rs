@@ -0,0 +1,130 @@
+//! Stuck-session and error-loop alerting for the TUI.
+//!
+//! Feeds every tracked session's output to a `Watchdog` and escalates its
+//! alerts: a banner above the output, then a push notification to Telegram,
+//! then an optional webhook POST.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use commander_core::is_claude_ready;
+use commander_runtime::{AlertLevel, WatchdogAlert};
+
+use super::app::{App, Message};
+
+/// Minimum time between watchdog checks.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Timeout for webhook deliveries.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl App {
+    /// Observe all tracked sessions and surface watchdog alerts.
+    ///
+    /// Called on every event loop tick; rate limited to `WATCHDOG_INTERVAL`.
+    pub fn check_watchdog(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_watchdog_check
+            .is_some_and(|last| now.duration_since(last) < WATCHDOG_INTERVAL)
+        {
+            return;
+        }
+        self.last_watchdog_check = Some(now);
+
+        let Some(tmux) = &self.tmux else { return };
+        let adapter = self.registry.get("claude-code");
+
+        let mut alerts = Vec::new();
+        for (project, session) in &self.sessions {
+            if self.paused_sessions.contains(session) {
+                continue;
+            }
+            let Ok(output) = tmux.capture_output(session, None, Some(50)) else {
+                continue;
+            };
+            let errors = adapter
+                .as_ref()
+                .map(|a| a.analyze_output(&output).errors)
+                .unwrap_or_default();
+            let idle = is_claude_ready(&output);
+
+            alerts.extend(self.watchdog.observe(session, &output, &errors, idle, now));
+            if !self.watchdog.is_alerting(session) {
+                self.watchdog_banners.remove(project);
+            }
+        }
+
+        // Stop tracking sessions that were disconnected
+        let tracked: HashSet<String> = self.sessions.values().cloned().collect();
+        for session in self.watchdog_sessions.difference(&tracked) {
+            self.watchdog.forget(session);
+        }
+        self.watchdog_sessions = tracked;
+        let sessions = &self.sessions;
+        self.watchdog_banners.retain(|project, _| sessions.contains_key(project));
+
+        for alert in alerts {
+            self.raise_watchdog_alert(alert);
+        }
+    }
+
+    /// Current banner text, if any session needs attention.
+    pub fn watchdog_banner(&self) -> Option<String> {
+        match self.watchdog_banners.len() {
+            0 => None,
+            1 => self.watchdog_banners.values().next().cloned(),
+            n => Some(format!("{} sessions need attention (see messages)", n)),
+        }
+    }
+
+    fn raise_watchdog_alert(&mut self, alert: WatchdogAlert) {
+        let message = alert.message();
+        tracing::warn!(project = %alert.project, level = %alert.level, "{}", message);
+
+        if let Some(project) = project_for_session(&self.sessions, &alert.project) {
+            self.watchdog_banners.insert(project, message.clone());
+        }
+        self.messages.push(Message::system(format!("Watchdog ({}): {}", alert.level, message)));
+        self.scroll_to_bottom();
+
+        if alert.level >= AlertLevel::Push {
+            let text = format!("{}\n\n/connect {}", message, display_name(&alert.project));
+            if let Err(e) = commander_telegram::push_notification(text, Some(alert.project.clone())) {
+                tracing::warn!(error = %e, "Failed to broadcast watchdog alert");
+            }
+        }
+
+        if alert.level == AlertLevel::Webhook {
+            send_webhook(alert);
+        }
+    }
+}
+
+/// Project name for a tmux session.
+fn project_for_session(sessions: &HashMap<String, String>, session: &str) -> Option<String> {
+    sessions
+        .iter()
+        .find(|(_, s)| s.as_str() == session)
+        .map(|(project, _)| project.clone())
+}
+
+fn display_name(session: &str) -> &str {
+    session.strip_prefix("commander-").unwrap_or(session)
+}
+
+/// Deliver a webhook alert on a background thread so the UI never blocks.
+fn send_webhook(alert: WatchdogAlert) {
+    let Some(url) = alert.webhook_url.clone() else { return };
+
+    std::thread::spawn(move || {
+        let result = reqwest::blocking::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .and_then(|client| client.post(&url).json(&alert.to_payload()).send())
+            .and_then(|response| response.error_for_status());
+        if let Err(e) = result {
+            tracing::warn!(url = %url, error = %e, "Failed to deliver watchdog webhook");
+        }
+    });
+}
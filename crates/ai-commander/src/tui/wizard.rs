@@ -0,0 +1,470 @@
+//! Step-by-step `/connect` wizard.
+//!
+//! `/connect` without arguments opens a modal that walks through creating a
+//! project: browse to its directory, pick an adapter from the registry,
+//! accept or edit the name suggested from the directory, and choose whether
+//! the session is read-only, runs in its own git worktree, and waits for
+//! approval before delegations and file writes. Esc goes back a step and
+//! closes the wizard from the first one.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use commander_core::{approvals, CommanderError};
+use crossterm::event::KeyCode;
+
+use super::app::{App, Message};
+
+/// Directory inside a repository holding per-session git worktrees.
+const WORKTREES_DIR: &str = ".worktrees";
+
+/// Step the wizard is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Directory,
+    Adapter,
+    Name,
+    Options,
+}
+
+impl WizardStep {
+    /// Title of the step, shown in the modal's border.
+    pub fn title(self) -> &'static str {
+        match self {
+            WizardStep::Directory => "1/4 Project directory",
+            WizardStep::Adapter => "2/4 Adapter",
+            WizardStep::Name => "3/4 Project name",
+            WizardStep::Options => "4/4 Options",
+        }
+    }
+}
+
+/// A toggle on the options step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardOption {
+    /// Start the session without permission to modify files.
+    ReadOnly,
+    /// Run the session in a new worktree of the directory's repository.
+    Worktree,
+    /// Queue agent delegations and file writes for `/approve`.
+    ApprovalMode,
+}
+
+impl WizardOption {
+    pub const ALL: [WizardOption; 3] = [WizardOption::ReadOnly, WizardOption::Worktree, WizardOption::ApprovalMode];
+
+    /// Label on the options step.
+    pub fn label(self) -> &'static str {
+        match self {
+            WizardOption::ReadOnly => "Read-only (ask about the project, no file changes)",
+            WizardOption::Worktree => "Git worktree (work on a new session/<name> branch)",
+            WizardOption::ApprovalMode => "Approval mode (delegations and file writes wait for /approve)",
+        }
+    }
+}
+
+/// State of the `/connect` wizard.
+#[derive(Debug, Clone)]
+pub struct ConnectWizard {
+    pub step: WizardStep,
+    /// Directory being browsed, and chosen once past the first step.
+    pub dir: PathBuf,
+    /// Subdirectories of `dir`, sorted, without hidden ones.
+    pub entries: Vec<String>,
+    /// Selected row on the directory step: 0 uses `dir`, 1 is the parent,
+    /// then `entries`.
+    pub dir_selected: usize,
+    /// Adapter IDs from the registry, sorted.
+    pub adapters: Vec<String>,
+    pub adapter_selected: usize,
+    pub name: String,
+    pub option_selected: usize,
+    pub read_only: bool,
+    pub worktree: bool,
+    pub approval_mode: bool,
+    /// Problem with the current step, shown until the next key.
+    pub error: Option<String>,
+}
+
+impl ConnectWizard {
+    /// Start browsing at `dir` with the adapters in `adapters`.
+    pub fn new(dir: PathBuf, mut adapters: Vec<String>) -> Self {
+        adapters.sort();
+        let adapter_selected = adapters.iter().position(|a| a == "claude-code").unwrap_or(0);
+        let mut wizard = Self {
+            step: WizardStep::Directory,
+            dir,
+            entries: Vec::new(),
+            dir_selected: 0,
+            adapters,
+            adapter_selected,
+            name: String::new(),
+            option_selected: 0,
+            read_only: false,
+            worktree: false,
+            approval_mode: false,
+            error: None,
+        };
+        wizard.read_dir();
+        wizard
+    }
+
+    /// Rows of the directory step.
+    pub fn dir_rows(&self) -> Vec<String> {
+        let mut rows = vec!["./  (use this directory)".to_string(), "../".to_string()];
+        rows.extend(self.entries.iter().map(|name| format!("{}/", name)));
+        rows
+    }
+
+    /// Whether the option is on.
+    pub fn is_set(&self, option: WizardOption) -> bool {
+        match option {
+            WizardOption::ReadOnly => self.read_only,
+            WizardOption::Worktree => self.worktree,
+            WizardOption::ApprovalMode => self.approval_mode,
+        }
+    }
+
+    fn read_dir(&mut self) {
+        self.entries = list_subdirectories(&self.dir);
+        self.dir_selected = 0;
+    }
+
+    /// Browse into the selected directory.
+    fn open_selected(&mut self) {
+        let target = match self.dir_selected {
+            0 => return,
+            1 => match self.dir.parent() {
+                Some(parent) => parent.to_path_buf(),
+                None => return,
+            },
+            i => self.dir.join(&self.entries[i - 2]),
+        };
+        match fs::read_dir(&target) {
+            Ok(_) => {
+                self.dir = target;
+                self.read_dir();
+            }
+            Err(e) => self.error = Some(format!("Cannot open {}: {}", target.display(), e)),
+        }
+    }
+
+    /// Browse to the parent directory.
+    fn open_parent(&mut self) {
+        if let Some(parent) = self.dir.parent() {
+            self.dir = parent.to_path_buf();
+            self.read_dir();
+        }
+    }
+
+    /// Toggle the selected option. Read-only and worktree exclude each
+    /// other: a read-only session has no use for its own branch.
+    fn toggle_selected(&mut self) {
+        match WizardOption::ALL[self.option_selected] {
+            WizardOption::ReadOnly => {
+                self.read_only = !self.read_only;
+                if self.read_only {
+                    self.worktree = false;
+                }
+            }
+            WizardOption::Worktree => {
+                if !self.worktree && !is_git_repo(&self.dir) {
+                    self.error = Some(format!("{} is not a git repository", self.dir.display()));
+                    return;
+                }
+                self.worktree = !self.worktree;
+                if self.worktree {
+                    self.read_only = false;
+                }
+            }
+            WizardOption::ApprovalMode => self.approval_mode = !self.approval_mode,
+        }
+    }
+}
+
+/// Names of the non-hidden subdirectories of `dir`, sorted case-insensitively.
+fn list_subdirectories(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Project name suggested for `dir`: its name in lowercase with runs of
+/// other characters turned into `-`, numbered when `taken`.
+pub fn suggest_name(dir: &Path, taken: &[String]) -> String {
+    let raw = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut base = String::new();
+    for c in raw.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            base.push(c.to_ascii_lowercase());
+        } else if !base.is_empty() && !base.ends_with('-') {
+            base.push('-');
+        }
+    }
+    let base = match base.trim_end_matches('-') {
+        "" => "project".to_string(),
+        trimmed => trimmed.to_string(),
+    };
+    if !taken.contains(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|name| !taken.contains(name))
+        .unwrap_or(base)
+}
+
+/// Whether `dir` is inside a git repository.
+fn is_git_repo(dir: &Path) -> bool {
+    App::is_git_worktree(&dir.to_string_lossy())
+}
+
+/// Add a worktree of the repository at `repo` in `.worktrees/<session>` on a
+/// new `session/<session>` branch, where orphan collection finds it once the
+/// session ends.
+fn create_worktree(repo: &Path, session: &str) -> Result<PathBuf, String> {
+    let path = repo.join(WORKTREES_DIR).join(session);
+    if path.exists() {
+        return Err(format!("Worktree already exists at {}", path.display()));
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["worktree", "add"])
+        .arg(&path)
+        .args(["-b", &format!("session/{}", session)])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git worktree add failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(path)
+}
+
+impl App {
+    /// Open the `/connect` wizard in the current directory.
+    pub fn open_connect_wizard(&mut self) {
+        let dir = std::env::current_dir()
+            .ok()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let adapters = self.registry.list().into_iter().map(str::to_string).collect();
+        self.connect_wizard = Some(ConnectWizard::new(dir, adapters));
+    }
+
+    /// Handle a key while the wizard is open.
+    pub fn handle_wizard_key(&mut self, code: KeyCode) {
+        let Some(wizard) = self.connect_wizard.as_mut() else { return };
+        wizard.error = None;
+
+        match (wizard.step, code) {
+            (WizardStep::Directory, KeyCode::Esc) => {
+                self.connect_wizard = None;
+            }
+            (WizardStep::Directory, KeyCode::Up) => {
+                wizard.dir_selected = wizard.dir_selected.saturating_sub(1);
+            }
+            (WizardStep::Directory, KeyCode::Down) if wizard.dir_selected + 1 < wizard.entries.len() + 2 => {
+                wizard.dir_selected += 1;
+            }
+            (WizardStep::Directory, KeyCode::Right) => wizard.open_selected(),
+            (WizardStep::Directory, KeyCode::Left | KeyCode::Backspace) => wizard.open_parent(),
+            (WizardStep::Directory, KeyCode::Enter) if wizard.dir_selected > 0 => wizard.open_selected(),
+            (WizardStep::Directory, KeyCode::Enter) => {
+                let taken = self.project_names();
+                let Some(wizard) = self.connect_wizard.as_mut() else { return };
+                if wizard.name.is_empty() {
+                    wizard.name = suggest_name(&wizard.dir, &taken);
+                }
+                wizard.step = WizardStep::Adapter;
+            }
+
+            (WizardStep::Adapter, KeyCode::Esc) => wizard.step = WizardStep::Directory,
+            (WizardStep::Adapter, KeyCode::Up) => {
+                wizard.adapter_selected = wizard.adapter_selected.saturating_sub(1);
+            }
+            (WizardStep::Adapter, KeyCode::Down) if wizard.adapter_selected + 1 < wizard.adapters.len() => {
+                wizard.adapter_selected += 1;
+            }
+            (WizardStep::Adapter, KeyCode::Enter) => {
+                if wizard.adapters.is_empty() {
+                    wizard.error = Some("No adapters registered".to_string());
+                } else {
+                    wizard.step = WizardStep::Name;
+                }
+            }
+
+            (WizardStep::Name, KeyCode::Esc) => wizard.step = WizardStep::Adapter,
+            (WizardStep::Name, KeyCode::Char(c)) if !c.is_whitespace() => wizard.name.push(c),
+            (WizardStep::Name, KeyCode::Backspace) => {
+                wizard.name.pop();
+            }
+            (WizardStep::Name, KeyCode::Enter) => {
+                let name = wizard.name.trim().to_string();
+                if name.is_empty() {
+                    wizard.error = Some("Enter a project name".to_string());
+                } else if self.project_names().contains(&name) {
+                    let Some(wizard) = self.connect_wizard.as_mut() else { return };
+                    wizard.error = Some(format!("Project '{}' already exists", name));
+                } else {
+                    let Some(wizard) = self.connect_wizard.as_mut() else { return };
+                    wizard.step = WizardStep::Options;
+                }
+            }
+
+            (WizardStep::Options, KeyCode::Esc) => wizard.step = WizardStep::Name,
+            (WizardStep::Options, KeyCode::Up) => {
+                wizard.option_selected = wizard.option_selected.saturating_sub(1);
+            }
+            (WizardStep::Options, KeyCode::Down) if wizard.option_selected + 1 < WizardOption::ALL.len() => {
+                wizard.option_selected += 1;
+            }
+            (WizardStep::Options, KeyCode::Char(' ')) => wizard.toggle_selected(),
+            (WizardStep::Options, KeyCode::Enter) => {
+                if let Some(wizard) = self.connect_wizard.take() {
+                    if let Err(e) = self.finish_connect_wizard(&wizard) {
+                        self.messages.push(Message::error(&e));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Names of the registered projects.
+    fn project_names(&self) -> Vec<String> {
+        self.store
+            .load_all_projects()
+            .map(|projects| projects.into_values().map(|p| p.name).collect())
+            .unwrap_or_default()
+    }
+
+    /// Create the project the wizard describes and connect to it.
+    fn finish_connect_wizard(&mut self, wizard: &ConnectWizard) -> Result<(), CommanderError> {
+        let adapter = &wizard.adapters[wizard.adapter_selected];
+        let name = wizard.name.trim();
+        let path = if wizard.worktree {
+            let session = name.replace([' ', '.', '/', ':'], "-");
+            let path = create_worktree(&wizard.dir, &session).map_err(CommanderError::Runtime)?;
+            self.messages.push(Message::system(format!(
+                "Created worktree {} on branch session/{}",
+                path.display(),
+                session
+            )));
+            path
+        } else {
+            wizard.dir.clone()
+        };
+
+        if wizard.approval_mode {
+            approvals::set_approval_mode(name, true)
+                .map_err(|e| CommanderError::Runtime(format!("Failed to save approval mode: {}", e)))?;
+        }
+
+        let path = path.to_string_lossy();
+        if wizard.read_only {
+            self.create_project(&path, adapter, name)?;
+            self.connect_read_only(name)
+        } else {
+            self.connect_new(&path, adapter, name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_suggest_name() {
+        assert_eq!(suggest_name(Path::new("/src/My App.v2"), &[]), "my-app-v2");
+        assert_eq!(suggest_name(Path::new("/src/api"), &["api".to_string()]), "api-2");
+        assert_eq!(
+            suggest_name(Path::new("/src/api"), &["api".to_string(), "api-2".to_string()]),
+            "api-3"
+        );
+        assert_eq!(suggest_name(Path::new("/"), &[]), "project");
+    }
+
+    #[test]
+    fn test_browse_directories() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("beta")).unwrap();
+        fs::create_dir(dir.path().join("Alpha")).unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join("file.txt"), "").unwrap();
+
+        let mut wizard = ConnectWizard::new(dir.path().to_path_buf(), vec!["shell".to_string()]);
+        assert_eq!(wizard.entries, vec!["Alpha", "beta"]);
+
+        wizard.dir_selected = 3;
+        wizard.open_selected();
+        assert_eq!(wizard.dir, dir.path().join("beta"));
+        assert!(wizard.entries.is_empty());
+
+        wizard.open_parent();
+        assert_eq!(wizard.dir, dir.path());
+    }
+
+    #[test]
+    fn test_wizard_steps_and_back() {
+        let state = tempdir().unwrap();
+        let project = tempdir().unwrap();
+        fs::create_dir(project.path().join("my-service")).unwrap();
+        let mut app = App::new(state.path());
+        app.connect_wizard = Some(ConnectWizard::new(
+            project.path().join("my-service"),
+            vec!["shell".to_string(), "claude-code".to_string()],
+        ));
+
+        app.handle_wizard_key(KeyCode::Enter);
+        let wizard = app.connect_wizard.as_ref().unwrap();
+        assert_eq!(wizard.step, WizardStep::Adapter);
+        assert_eq!(wizard.name, "my-service");
+        assert_eq!(wizard.adapters[wizard.adapter_selected], "claude-code");
+
+        app.handle_wizard_key(KeyCode::Enter);
+        app.handle_wizard_key(KeyCode::Backspace);
+        app.handle_wizard_key(KeyCode::Char('x'));
+        assert_eq!(app.connect_wizard.as_ref().unwrap().name, "my-servicx");
+
+        app.handle_wizard_key(KeyCode::Enter);
+        assert_eq!(app.connect_wizard.as_ref().unwrap().step, WizardStep::Options);
+
+        // Not a git repository, so no worktree
+        app.handle_wizard_key(KeyCode::Down);
+        app.handle_wizard_key(KeyCode::Char(' '));
+        let wizard = app.connect_wizard.as_ref().unwrap();
+        assert!(!wizard.worktree);
+        assert!(wizard.error.is_some());
+
+        for _ in 0..4 {
+            app.handle_wizard_key(KeyCode::Esc);
+        }
+        assert!(app.connect_wizard.is_none());
+    }
+
+    #[test]
+    fn test_read_only_excludes_worktree() {
+        let dir = tempdir().unwrap();
+        let mut wizard = ConnectWizard::new(dir.path().to_path_buf(), Vec::new());
+        wizard.worktree = true;
+        wizard.toggle_selected();
+        assert!(wizard.read_only);
+        assert!(!wizard.worktree);
+    }
+}
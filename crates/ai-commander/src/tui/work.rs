@@ -0,0 +1,40 @@
+//! Work item dependency graph.
+//!
+//! `/work [session]` shows the dependency graph of a session's work items
+//! (by default the connected one), such as the tasks of an approved plan:
+//! blocked items are marked `[#]` and the critical path is starred. Items
+//! with a deadline show a countdown, colored once due soon or overdue.
+//! With agents enabled, the current task's budget usage is shown above the
+//! graph.
+
+use super::app::{App, Message};
+use crate::work_commands::load_graph;
+
+impl App {
+    /// Handle `/work [session]`.
+    pub fn show_work_graph(&mut self, session: Option<&str>) {
+        let Some(session) = session.map(String::from).or_else(|| self.current_session_name()) else {
+            self.messages.push(Message::system("Connect to a project first, or use /work <session>"));
+            return;
+        };
+
+        let graph = match load_graph(&self.state_dir, &session) {
+            Ok(graph) => graph,
+            Err(e) => {
+                self.messages.push(Message::system(e.to_string()));
+                return;
+            }
+        };
+
+        self.messages.push(Message::system(format!("Work graph for {}:", session)));
+        if let Some(line) = self.budget_line(&session) {
+            self.messages.push(Message::system(format!("  {}", line)));
+        }
+        for line in graph.render_ascii() {
+            self.messages.push(Message::system(format!("  {}", line)));
+        }
+        self.messages.push(Message::system(
+            "Export with: commander work graph <session> --format dot",
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+//! Workspaces: named groups of projects (for example one per client).
+//!
+//! `/workspace` lists workspaces and `/workspace assign <project> <name>`
+//! moves a project into one. `/workspace switch <name>` scopes `/list`, the
+//! sessions view, `/status` and `@all` broadcasts to that workspace's
+//! projects, and limits the User Agent's memory searches to their agents.
+
+use std::collections::{BTreeMap, HashMap};
+
+use commander_models::Project;
+
+use super::app::{App, Message, ViewMode};
+
+/// `/workspace switch` arguments that clear the scope.
+const CLEAR_SCOPE: &[&str] = &["all", "none", "-"];
+
+impl App {
+    /// Handle `/workspace [list|switch|assign|unassign] ...`.
+    pub fn handle_workspace_command(&mut self, arg: Option<&str>) {
+        let parts: Vec<&str> = arg.unwrap_or("").split_whitespace().collect();
+        match parts.as_slice() {
+            [] | ["list"] => self.list_workspaces(),
+            ["switch", name] => self.switch_workspace(name),
+            ["assign", project, workspace] => self.assign_workspace(project, Some(workspace)),
+            ["unassign", project] => self.assign_workspace(project, None),
+            _ => self.messages.push(Message::system(
+                "Usage: /workspace [list] | switch <name|all> | assign <project> <workspace> | unassign <project>",
+            )),
+        }
+    }
+
+    fn list_workspaces(&mut self) {
+        let workspaces = match self.store.list_workspaces() {
+            Ok(workspaces) => workspaces,
+            Err(e) => {
+                self.messages.push(Message::system(format!("Failed to load workspaces: {}", e)));
+                return;
+            }
+        };
+        if workspaces.is_empty() {
+            self.messages.push(Message::system(
+                "No workspaces. Add a project with /workspace assign <project> <workspace>",
+            ));
+            return;
+        }
+
+        self.messages.push(Message::system("Workspaces:"));
+        for (name, projects) in &workspaces {
+            let marker = if self.workspace.as_ref() == Some(name) { "*" } else { " " };
+            self.messages.push(Message::system(format!(
+                "  {} {} ({}): {}",
+                marker,
+                name,
+                projects.len(),
+                projects.join(", ")
+            )));
+        }
+        if self.workspace.is_none() {
+            self.messages.push(Message::system("Showing all projects. Scope with /workspace switch <name>"));
+        }
+    }
+
+    fn switch_workspace(&mut self, name: &str) {
+        if CLEAR_SCOPE.contains(&name) {
+            self.set_workspace_scope(None);
+            self.messages.push(Message::system("Showing all workspaces"));
+            return;
+        }
+        match self.store.projects_in_workspace(name) {
+            Ok(projects) if !projects.is_empty() => {
+                self.set_workspace_scope(Some(name.to_string()));
+                self.messages.push(Message::system(format!(
+                    "Switched to workspace {} ({} projects)",
+                    name,
+                    projects.len()
+                )));
+            }
+            Ok(_) => self.messages.push(Message::system(format!(
+                "No workspace named {}. Add projects with /workspace assign <project> {}",
+                name, name
+            ))),
+            Err(e) => self.messages.push(Message::system(format!("Failed to load workspaces: {}", e))),
+        }
+    }
+
+    fn assign_workspace(&mut self, project: &str, workspace: Option<&str>) {
+        let mut found = match self.store.find_project_by_name_or_alias(project) {
+            Ok(Some(p)) => p,
+            Ok(None) => {
+                self.messages.push(Message::system(format!("Project not found: {}", project)));
+                return;
+            }
+            Err(e) => {
+                self.messages.push(Message::system(format!("Failed to load project: {}", e)));
+                return;
+            }
+        };
+        if let Err(e) = found.set_workspace(workspace.map(String::from)) {
+            self.messages.push(Message::system(format!("Error: {}", e)));
+            return;
+        }
+        if let Err(e) = self.store.save_project(&found) {
+            self.messages.push(Message::system(format!("Failed to save project: {}", e)));
+            return;
+        }
+
+        self.messages.push(Message::system(match workspace {
+            Some(w) => format!("Moved {} to workspace {}", found.name, w),
+            None => format!("Removed {} from its workspace", found.name),
+        }));
+        // Re-apply the scope so the memory filter includes the change
+        self.set_workspace_scope(self.workspace.clone());
+    }
+
+    /// Scope the TUI to `workspace`, or to every project with `None`.
+    pub(super) fn set_workspace_scope(&mut self, workspace: Option<String>) {
+        self.workspace = workspace;
+        self.apply_memory_scope();
+        if self.view_mode == ViewMode::Sessions {
+            self.refresh_session_list();
+        }
+    }
+
+    /// Limit the User Agent's memory searches to the workspace's projects.
+    #[cfg(feature = "agents")]
+    fn apply_memory_scope(&mut self) {
+        let scope = self.workspace_projects().map(|projects| {
+            commander_memory::MemoryFilter::agents(
+                projects.iter().flat_map(|p| commander_agent::project_agent_ids(&p.name)),
+            )
+        });
+        if let Some(orchestrator) = self.orchestrator.as_mut() {
+            orchestrator.user_agent_mut().set_memory_scope(scope);
+        }
+    }
+
+    #[cfg(not(feature = "agents"))]
+    fn apply_memory_scope(&mut self) {}
+
+    /// Projects of the active workspace, or `None` when not scoped.
+    pub(super) fn workspace_projects(&self) -> Option<Vec<Project>> {
+        let workspace = self.workspace.as_ref()?;
+        Some(self.store.projects_in_workspace(workspace).unwrap_or_default())
+    }
+
+    /// Workspace of each tmux session that belongs to a project in one.
+    pub(super) fn session_workspaces(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        for project in self.store.load_all_projects().unwrap_or_default().into_values() {
+            if let Some(workspace) = project.workspace.clone() {
+                for session in project_sessions(&project) {
+                    map.insert(session, workspace.clone());
+                }
+            }
+        }
+        map
+    }
+
+    /// Group session names by workspace, keeping only the active workspace
+    /// when scoped. Sessions outside any workspace come last, under `None`.
+    pub(super) fn group_sessions<T>(&self, sessions: Vec<T>, name: impl Fn(&T) -> &str) -> Vec<(Option<String>, Vec<T>)> {
+        let workspaces = self.session_workspaces();
+        let mut grouped: BTreeMap<(bool, String), Vec<T>> = BTreeMap::new();
+        for session in sessions {
+            let workspace = workspaces.get(name(&session)).cloned();
+            if self.workspace.is_some() && workspace != self.workspace {
+                continue;
+            }
+            let key = (workspace.is_none(), workspace.unwrap_or_default());
+            grouped.entry(key).or_default().push(session);
+        }
+        grouped
+            .into_iter()
+            .map(|((unassigned, workspace), sessions)| ((!unassigned).then_some(workspace), sessions))
+            .collect()
+    }
+
+    /// Running sessions an `@all` broadcast goes to, in the active workspace
+    /// when scoped.
+    pub(super) fn broadcast_targets(&self) -> Vec<String> {
+        let Some(tmux) = &self.tmux else {
+            return Vec::new();
+        };
+        match self.workspace_projects() {
+            Some(projects) => projects
+                .iter()
+                .filter_map(|p| project_sessions(p).into_iter().find(|s| tmux.session_exists(s)))
+                .collect(),
+            None => tmux
+                .list_sessions()
+                .map(|sessions| sessions.into_iter().map(|s| s.name).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Tmux session names a project's session may run under.
+fn project_sessions(project: &Project) -> Vec<String> {
+    vec![
+        project.session_name(),
+        format!("commander-{}", project.session_name()),
+    ]
+}
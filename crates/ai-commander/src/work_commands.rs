@@ -0,0 +1,90 @@
+//! Work queue commands for the CLI.
+//!
+//! `work graph` renders the dependency graph of a session's work items
+//! (such as the tasks of an approved plan) as ASCII or Graphviz DOT,
+//! marking blocked items and the critical path. `work deadline` gives an
+//! item an SLA, which the daemon escalates as the deadline nears and passes.
+
+use std::path::Path;
+
+use chrono::Utc;
+use commander_models::ProjectId;
+use commander_persistence::WorkStore;
+use commander_work::{parse_sla, SlaPolicy, WorkGraph, WorkQueue};
+
+use crate::cli::{GraphFormat, WorkCommands};
+use crate::commands::Result;
+
+/// Execute work commands.
+pub fn execute(state_dir: &Path, command: WorkCommands) -> Result<()> {
+    match command {
+        WorkCommands::Graph {
+            session,
+            format,
+            output,
+        } => graph(state_dir, &session, format, output.as_deref()),
+        WorkCommands::Deadline { session, item, due } => deadline(state_dir, &session, &item, &due),
+    }
+}
+
+/// Load the work queue of `session`, also trying its `commander-` tmux name.
+fn load_queue(state_dir: &Path, session: &str) -> Result<WorkQueue> {
+    for candidate in [session.to_string(), format!("commander-{}", session)] {
+        let queue = WorkQueue::new(WorkStore::new(state_dir));
+        queue.load_project(&ProjectId::from(candidate.as_str()))?;
+        if !queue.is_empty() {
+            return Ok(queue);
+        }
+    }
+    Err(format!("No work items for session: {}", session).into())
+}
+
+/// Load the work graph of `session`, also trying its `commander-` tmux name.
+pub fn load_graph(state_dir: &Path, session: &str) -> Result<WorkGraph> {
+    Ok(load_queue(state_dir, session)?.graph()?)
+}
+
+fn deadline(state_dir: &Path, session: &str, item: &str, due: &str) -> Result<()> {
+    let queue = load_queue(state_dir, session)?;
+    let prefix = item.trim_start_matches("work-");
+    let matches: Vec<_> = queue
+        .list(None)
+        .into_iter()
+        .filter(|work| work.id.as_str().trim_start_matches("work-").starts_with(prefix))
+        .collect();
+    let work = match matches.as_slice() {
+        [work] => work,
+        [] => return Err(format!("No work item {} in {}", item, session).into()),
+        _ => return Err(format!("{} matches {} work items; give more of the ID", item, matches.len()).into()),
+    };
+
+    if due.eq_ignore_ascii_case("none") {
+        queue.set_deadline(&work.id, None)?;
+        println!("Cleared the deadline of {}", work.id);
+        return Ok(());
+    }
+    let sla = parse_sla(due).ok_or_else(|| format!("Invalid deadline: {} (expected e.g. 45m, 4h, 2d or none)", due))?;
+    let now = Utc::now();
+    queue.set_deadline(&work.id, Some(now + sla))?;
+    let updated = queue.get(&work.id).ok_or("Work item disappeared")?;
+    let tag = SlaPolicy::default().tag(&updated, now).unwrap_or_default();
+    println!("{} is due {} {}", work.id, (now + sla).format("%Y-%m-%d %H:%M UTC"), tag);
+    Ok(())
+}
+
+fn graph(state_dir: &Path, session: &str, format: GraphFormat, output: Option<&Path>) -> Result<()> {
+    let graph = load_graph(state_dir, session)?;
+    let rendered = match format {
+        GraphFormat::Ascii => graph.render_ascii().join("\n") + "\n",
+        GraphFormat::Dot => graph.to_dot(),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            println!("Wrote work graph for {} ({} items) to {}", session, graph.len(), path.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
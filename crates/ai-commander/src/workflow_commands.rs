@@ -0,0 +1,145 @@
+//! Workflow commands for the CLI.
+//!
+//! `workflow run` executes a YAML workflow in a project's session through the
+//! orchestrator's workflow engine (requires the `agents` feature). Step
+//! progress is printed and recorded as events in the shared event store, so
+//! a failed step shows up as a blocking error in the TUI and chat bots.
+
+use std::path::Path;
+
+use commander_persistence::StateStore;
+
+use crate::cli::WorkflowCommands;
+use crate::commands::Result;
+
+/// Execute workflow commands.
+#[cfg(feature = "agents")]
+pub fn execute(store: &StateStore, state_dir: &Path, command: WorkflowCommands) -> Result<()> {
+    match command {
+        WorkflowCommands::List => list(),
+        WorkflowCommands::Run {
+            name,
+            project,
+            restart,
+        } => run(store, state_dir, &name, &project, restart),
+    }
+}
+
+#[cfg(not(feature = "agents"))]
+pub fn execute(_store: &StateStore, _state_dir: &Path, _command: WorkflowCommands) -> Result<()> {
+    Err("Workflows require agent features. Rebuild with: cargo build --features agents".into())
+}
+
+#[cfg(feature = "agents")]
+fn list() -> Result<()> {
+    let dir = commander_core::workflows_dir();
+    let workflows = commander_orchestrator::list_workflows(&dir);
+    if workflows.is_empty() {
+        println!("No workflows in {}", dir.display());
+        return Ok(());
+    }
+
+    println!("Workflows:");
+    for workflow in workflows {
+        let description = workflow.description.as_deref().unwrap_or("");
+        println!("  {} ({} steps) {}", workflow.name, workflow.steps.len(), description);
+    }
+    println!("\nWorkflows dir: {}", dir.display());
+    Ok(())
+}
+
+#[cfg(feature = "agents")]
+fn run(store: &StateStore, state_dir: &Path, name: &str, project_id: &str, restart: bool) -> Result<()> {
+    use std::sync::Arc;
+
+    use commander_events::EventManager;
+    use commander_models::{Event, EventType};
+    use commander_orchestrator::fanout::{FanOutConfig, TmuxTaskRunner};
+    use commander_orchestrator::{TaskSpec, Workflow, WorkflowEvent, WorkflowExecution};
+    use commander_persistence::EventStore;
+    use tracing::warn;
+
+    let projects = store.load_all_projects()?;
+    let project = projects
+        .values()
+        .find(|p| p.id.as_str() == project_id || p.name == project_id)
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let workflow = Workflow::find(&commander_core::workflows_dir(), name)?;
+    let adapter = project
+        .config
+        .get("tool")
+        .and_then(|v| v.as_str())
+        .unwrap_or("claude-code");
+    let session = project.name.replace([' ', '.', '/', ':'], "-");
+    let template = TaskSpec::new(session, "")
+        .with_adapter(adapter)
+        .in_dir(project.path.clone());
+
+    let runner = TmuxTaskRunner::new(FanOutConfig::load_or_default(&FanOutConfig::default_path()))?;
+    let execution = WorkflowExecution::new(
+        workflow,
+        template,
+        Arc::new(runner),
+        commander_core::workflow_runs_dir(),
+        restart,
+    );
+    let events = EventManager::new(EventStore::new(state_dir));
+    let total = execution.workflow().steps.len();
+    println!("Running workflow '{}' on {} ({} steps)", name, project.name, total);
+
+    let report = execution.run(|event| {
+        let (event_type, title, content) = match &event {
+            WorkflowEvent::Resumed { completed } => {
+                println!("  Resuming after {} completed step(s)", completed);
+                return;
+            }
+            WorkflowEvent::StepStarted { step, attempt } => {
+                if *attempt == 1 {
+                    println!("  {} ...", step);
+                } else {
+                    println!("  {} (attempt {}) ...", step, attempt);
+                }
+                return;
+            }
+            WorkflowEvent::StepRetrying { step, error, .. } => {
+                println!("  {} failed, retrying: {}", step, error);
+                return;
+            }
+            WorkflowEvent::StepCompleted { step, .. } => {
+                println!("  {} done", step);
+                (EventType::Status, format!("Workflow {}: {} done", name, step), None)
+            }
+            WorkflowEvent::StepFailed { step, error } => {
+                println!("  {} failed: {}", step, error);
+                (
+                    EventType::Error,
+                    format!("Workflow {}: {} failed", name, step),
+                    Some(error.clone()),
+                )
+            }
+            WorkflowEvent::Finished(report) => {
+                if !report.succeeded() {
+                    return;
+                }
+                (EventType::TaskComplete, format!("Workflow {} completed", name), None)
+            }
+        };
+
+        let mut recorded = Event::new(project.id.clone(), event_type, title);
+        recorded.content = content;
+        if let Err(e) = events.emit(recorded) {
+            warn!(error = %e, "Failed to record workflow event");
+        }
+    })?;
+
+    println!("\n{}/{} steps completed", report.completed, report.total);
+    match report.failed_step {
+        Some(step) => Err(format!(
+            "Step '{}' failed; run the workflow again to resume from it (or --restart)",
+            step
+        )
+        .into()),
+        None => Ok(()),
+    }
+}
@@ -95,6 +95,21 @@ impl RuntimeAdapter for ClaudeCodeAdapter {
         (self.info.command.clone(), args)
     }
 
+    fn read_only_launch_command(&self, project_path: &str) -> (String, Vec<String>) {
+        // Plan mode lets Claude read and explore but not edit or run commands
+        let args = vec![
+            "--permission-mode".to_string(),
+            "plan".to_string(),
+            "--project".to_string(),
+            project_path.to_string(),
+        ];
+        (self.info.command.clone(), args)
+    }
+
+    fn exit_command(&self) -> Option<&str> {
+        Some("/exit")
+    }
+
     fn analyze_output(&self, output: &str) -> OutputAnalysis {
         let state = self.analyze_recent_output(output, 10);
         let errors = if state == RuntimeState::Error {
@@ -154,6 +169,16 @@ mod tests {
         assert!(args.contains(&"/path/to/project".to_string()));
     }
 
+    #[test]
+    fn test_read_only_launch_command() {
+        let adapter = ClaudeCodeAdapter::new();
+        let (cmd, args) = adapter.read_only_launch_command("/path/to/project");
+
+        assert_eq!(cmd, "claude");
+        assert!(!args.contains(&"--dangerously-skip-permissions".to_string()));
+        assert!(args.windows(2).any(|w| w == ["--permission-mode", "plan"]));
+    }
+
     #[test]
     fn test_analyze_idle_output() {
         let adapter = ClaudeCodeAdapter::new();
@@ -95,6 +95,12 @@ impl RuntimeAdapter for CodexAdapter {
         (self.info.command.clone(), args)
     }
 
+    fn read_only_launch_command(&self, project_path: &str) -> (String, Vec<String>) {
+        let (command, mut args) = self.launch_command(project_path);
+        args.extend(["--sandbox".to_string(), "read-only".to_string()]);
+        (command, args)
+    }
+
     fn analyze_output(&self, output: &str) -> OutputAnalysis {
         let state = self.analyze_recent_output(output, 10);
         let errors = if state == RuntimeState::Error {
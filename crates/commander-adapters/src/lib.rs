@@ -9,6 +9,8 @@
 //! - **RuntimeAdapter**: Trait that all adapters implement
 //! - **AdapterRegistry**: Discovers and manages available adapters
 //! - **Pattern matching**: Detects idle/error/working states from output
+//! - **Manifest adapters**: TOML-defined adapters for tools without built-in support
+//! - **Test results**: pass/fail counts and failing tests from test runner output
 //!
 //! # Example
 //!
@@ -34,20 +36,24 @@ pub mod auggie;
 pub mod claude_code;
 pub mod codex;
 pub mod event_driven;
+pub mod manifest;
 pub mod mpm;
 pub mod mpm_sdk;
 pub mod patterns;
 pub mod registry;
 pub mod shell;
+pub mod test_results;
 pub mod traits;
 
 pub use auggie::AuggieAdapter;
 pub use claude_code::ClaudeCodeAdapter;
 pub use codex::CodexAdapter;
 pub use event_driven::{EventDrivenAdapter, EventStream, RuntimeEvent, SessionHandle};
+pub use manifest::{AdapterManifest, GenericManifestAdapter, ManifestError};
 pub use mpm::MpmAdapter;
 pub use mpm_sdk::MpmSdkAdapter;
 pub use patterns::Pattern;
 pub use registry::AdapterRegistry;
 pub use shell::ShellAdapter;
+pub use test_results::{parse_test_output, TestFramework, TestRun};
 pub use traits::{AdapterInfo, OutputAnalysis, RuntimeAdapter, RuntimeState};
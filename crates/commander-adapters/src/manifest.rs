@@ -0,0 +1,398 @@
+//! Manifest adapters defined by TOML files.
+//!
+//! Each `*.toml` file in `~/.ai-commander/adapters/` describes one CLI tool:
+//! how to launch it and which output patterns mean it is ready, idle,
+//! working or failing. [`AdapterRegistry::new`](crate::AdapterRegistry::new)
+//! loads them as [`GenericManifestAdapter`]s, so new tools can be supported
+//! without recompiling.
+//!
+//! ```toml
+//! id = "aider"
+//! name = "Aider"
+//! description = "Aider pair programmer"
+//! command = "aider"
+//! args = ["--no-auto-commits"]
+//! aliases = ["ai"]
+//!
+//! [patterns]
+//! ready = ['(?i)^aider v\d']
+//! idle = ['(?m)^>\s*$']
+//! working = ['(?i)thinking|editing']
+//! error = ['(?im)^error:']
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use regex::Regex;
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::traits::{AdapterInfo, OutputAnalysis, RuntimeAdapter, RuntimeState};
+
+/// Number of trailing output lines checked for idle, working and error state.
+const RECENT_LINES: usize = 10;
+
+/// Errors from loading an adapter manifest.
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("invalid manifest {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+
+    #[error("invalid {kind} pattern '{pattern}': {source}")]
+    Pattern {
+        kind: &'static str,
+        pattern: String,
+        source: regex::Error,
+    },
+
+    #[error("manifest field '{0}' must not be empty")]
+    Empty(&'static str),
+}
+
+/// Output patterns of a manifest adapter.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestPatterns {
+    /// Output showing the tool finished starting up. When set, the runtime
+    /// stays `Starting` until one of these has appeared.
+    #[serde(default)]
+    pub ready: Vec<String>,
+    /// Output showing the tool waits for input.
+    #[serde(default)]
+    pub idle: Vec<String>,
+    /// Output showing the tool is busy.
+    #[serde(default)]
+    pub working: Vec<String>,
+    /// Output showing the tool failed.
+    #[serde(default)]
+    pub error: Vec<String>,
+}
+
+/// Contents of an adapter manifest file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdapterManifest {
+    /// Adapter ID used with `-a <id>`.
+    pub id: String,
+    /// Human-readable name; defaults to the ID.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// Command that launches the tool.
+    pub command: String,
+    /// Arguments passed on every launch.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Flag followed by the project path on launch (e.g. `--project`); the
+    /// path is not passed when unset.
+    #[serde(default)]
+    pub project_arg: Option<String>,
+    /// Extra names accepted by `AdapterRegistry::resolve`.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub patterns: ManifestPatterns,
+}
+
+impl AdapterManifest {
+    /// Parse a manifest from TOML.
+    pub fn from_toml(content: &str, path: &Path) -> Result<Self, ManifestError> {
+        toml::from_str(content).map_err(|source| ManifestError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// A compiled output pattern set.
+struct PatternSet {
+    /// Source patterns, interned for the `&[&str]` trait accessors.
+    sources: Vec<&'static str>,
+    regexes: Vec<Regex>,
+}
+
+impl PatternSet {
+    fn compile(kind: &'static str, patterns: &[String]) -> Result<Self, ManifestError> {
+        let regexes = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|source| ManifestError::Pattern {
+                    kind,
+                    pattern: pattern.clone(),
+                    source,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            sources: patterns.iter().map(|p| intern(p)).collect(),
+            regexes,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+
+    fn any_match(&self, text: &str) -> bool {
+        self.regexes.iter().any(|r| r.is_match(text))
+    }
+}
+
+/// Intern a pattern string for the `&[&str]` pattern accessors of
+/// [`RuntimeAdapter`].
+///
+/// Each distinct pattern is leaked once, so reloading manifests does not
+/// grow memory.
+fn intern(pattern: &str) -> &'static str {
+    static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut interned = INTERNED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = interned.get(pattern) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(pattern.to_string().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+/// Runtime adapter driven entirely by an [`AdapterManifest`].
+pub struct GenericManifestAdapter {
+    info: AdapterInfo,
+    project_arg: Option<String>,
+    aliases: Vec<String>,
+    ready: PatternSet,
+    idle: PatternSet,
+    working: PatternSet,
+    error: PatternSet,
+}
+
+impl GenericManifestAdapter {
+    /// Build an adapter from a parsed manifest, compiling its patterns.
+    pub fn from_manifest(manifest: AdapterManifest) -> Result<Self, ManifestError> {
+        if manifest.id.trim().is_empty() {
+            return Err(ManifestError::Empty("id"));
+        }
+        if manifest.command.trim().is_empty() {
+            return Err(ManifestError::Empty("command"));
+        }
+
+        Ok(Self {
+            ready: PatternSet::compile("ready", &manifest.patterns.ready)?,
+            idle: PatternSet::compile("idle", &manifest.patterns.idle)?,
+            working: PatternSet::compile("working", &manifest.patterns.working)?,
+            error: PatternSet::compile("error", &manifest.patterns.error)?,
+            info: AdapterInfo {
+                name: manifest.name.unwrap_or_else(|| manifest.id.clone()),
+                id: manifest.id,
+                description: manifest.description,
+                command: manifest.command,
+                default_args: manifest.args,
+            },
+            project_arg: manifest.project_arg,
+            aliases: manifest.aliases,
+        })
+    }
+
+    /// Load an adapter from a manifest file.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let content = fs::read_to_string(path).map_err(|source| ManifestError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_manifest(AdapterManifest::from_toml(&content, path)?)
+    }
+
+    /// Extra names this adapter can be selected by.
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn recent_state(&self, output: &str) -> RuntimeState {
+        let lines: Vec<&str> = output.lines().collect();
+        let recent = lines[lines.len().saturating_sub(RECENT_LINES)..].join("\n");
+
+        if self.error.any_match(&recent) {
+            return RuntimeState::Error;
+        }
+        if !self.ready.is_empty() && !self.ready.any_match(output) {
+            return RuntimeState::Starting;
+        }
+        if self.idle.any_match(&recent) {
+            return RuntimeState::Idle;
+        }
+        if self.working.any_match(&recent) || !recent.trim().is_empty() {
+            return RuntimeState::Working;
+        }
+        RuntimeState::Starting
+    }
+}
+
+impl RuntimeAdapter for GenericManifestAdapter {
+    fn info(&self) -> &AdapterInfo {
+        &self.info
+    }
+
+    fn launch_command(&self, project_path: &str) -> (String, Vec<String>) {
+        let mut args = self.info.default_args.clone();
+        if let Some(flag) = &self.project_arg {
+            args.push(flag.clone());
+            args.push(project_path.to_string());
+        }
+        (self.info.command.clone(), args)
+    }
+
+    fn analyze_output(&self, output: &str) -> OutputAnalysis {
+        let state = self.recent_state(output);
+        let errors = if state == RuntimeState::Error {
+            output
+                .lines()
+                .filter(|line| self.error.any_match(line))
+                .map(|line| line.trim().to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let confidence = match state {
+            RuntimeState::Error => 0.9,
+            RuntimeState::Idle => 0.85,
+            RuntimeState::Working if self.working.any_match(output) => 0.8,
+            RuntimeState::Working => 0.6,
+            RuntimeState::Starting => 0.5,
+            RuntimeState::Stopped => 1.0,
+        };
+
+        OutputAnalysis {
+            state,
+            confidence,
+            errors,
+            data: HashMap::new(),
+        }
+    }
+
+    fn idle_patterns(&self) -> &[&str] {
+        &self.idle.sources
+    }
+
+    fn error_patterns(&self) -> &[&str] {
+        &self.error.sources
+    }
+}
+
+/// Load every `*.toml` manifest in `dir`, sorted by file name.
+///
+/// Invalid manifests are skipped with a warning; a missing directory yields
+/// no adapters.
+pub fn load_manifests(dir: &Path) -> Vec<GenericManifestAdapter> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| match GenericManifestAdapter::load(path) {
+            Ok(adapter) => {
+                debug!(id = %adapter.info.id, path = %path.display(), "Loaded manifest adapter");
+                Some(adapter)
+            }
+            Err(e) => {
+                warn!(error = %e, "Skipping adapter manifest");
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AIDER: &str = r#"
+id = "aider"
+name = "Aider"
+command = "aider"
+args = ["--no-auto-commits"]
+project_arg = "--project"
+aliases = ["ai"]
+
+[patterns]
+ready = ['(?i)^aider v\d']
+idle = ['(?m)^>\s*$']
+working = ['(?i)thinking']
+error = ['(?im)^error:']
+"#;
+
+    fn aider() -> GenericManifestAdapter {
+        let manifest = AdapterManifest::from_toml(AIDER, Path::new("aider.toml")).unwrap();
+        GenericManifestAdapter::from_manifest(manifest).unwrap()
+    }
+
+    #[test]
+    fn test_manifest_launch_command() {
+        let adapter = aider();
+        assert_eq!(adapter.info().name, "Aider");
+        assert_eq!(adapter.aliases(), ["ai".to_string()]);
+        let (cmd, args) = adapter.launch_command("/src/app");
+        assert_eq!(cmd, "aider");
+        assert_eq!(args, vec!["--no-auto-commits", "--project", "/src/app"]);
+        assert_eq!(adapter.idle_patterns(), [r"(?m)^>\s*$"]);
+    }
+
+    #[test]
+    fn test_manifest_state_detection() {
+        let adapter = aider();
+        assert_eq!(adapter.analyze_output("loading models").state, RuntimeState::Starting);
+        assert_eq!(adapter.analyze_output("Aider v0.50\n> ").state, RuntimeState::Idle);
+        assert_eq!(adapter.analyze_output("Aider v0.50\nThinking...").state, RuntimeState::Working);
+
+        let analysis = adapter.analyze_output("Aider v0.50\nError: no API key\n");
+        assert_eq!(analysis.state, RuntimeState::Error);
+        assert_eq!(analysis.errors, vec!["Error: no API key"]);
+    }
+
+    #[test]
+    fn test_invalid_manifests_are_rejected() {
+        let bad_regex = AIDER.replace(r"(?i)thinking", "(unclosed");
+        let manifest = AdapterManifest::from_toml(&bad_regex, Path::new("bad.toml")).unwrap();
+        assert!(matches!(
+            GenericManifestAdapter::from_manifest(manifest),
+            Err(ManifestError::Pattern { kind: "working", .. })
+        ));
+
+        assert!(AdapterManifest::from_toml("id = \"x\"", Path::new("x.toml")).is_err());
+    }
+
+    #[test]
+    fn test_load_manifests_skips_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("aider.toml"), AIDER).unwrap();
+        fs::write(dir.path().join("broken.toml"), "id = ").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a manifest").unwrap();
+
+        let adapters = load_manifests(dir.path());
+        assert_eq!(adapters.len(), 1);
+        assert_eq!(adapters[0].info().id, "aider");
+        assert!(load_manifests(&dir.path().join("missing")).is_empty());
+    }
+}
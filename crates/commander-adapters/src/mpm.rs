@@ -5,6 +5,13 @@ use std::collections::HashMap;
 use crate::patterns::{self, mpm as mpm_patterns};
 use crate::traits::{AdapterInfo, OutputAnalysis, RuntimeAdapter, RuntimeState};
 
+/// Slash command that saves and pauses an MPM session; the MPM agent
+/// template's context strategy uses the same pair.
+pub const PAUSE_COMMAND: &str = "/mpm-session-pause";
+
+/// Slash command that resumes a paused MPM session.
+pub const RESUME_COMMAND: &str = "/mpm-session-resume";
+
 /// Adapter for MPM CLI.
 pub struct MpmAdapter {
     info: AdapterInfo,
@@ -95,6 +102,14 @@ impl RuntimeAdapter for MpmAdapter {
         (self.info.command.clone(), args)
     }
 
+    fn pause_commands(&self) -> Option<(&str, &str)> {
+        Some((PAUSE_COMMAND, RESUME_COMMAND))
+    }
+
+    fn exit_command(&self) -> Option<&str> {
+        Some("/exit")
+    }
+
     fn analyze_output(&self, output: &str) -> OutputAnalysis {
         let state = self.analyze_recent_output(output, 10);
         let errors = if state == RuntimeState::Error {
@@ -154,6 +169,13 @@ mod tests {
         assert!(args.contains(&"/path/to/project".to_string()));
     }
 
+    #[test]
+    fn test_pause_commands() {
+        let adapter = MpmAdapter::new();
+        assert_eq!(adapter.pause_commands(), Some((PAUSE_COMMAND, RESUME_COMMAND)));
+        assert_eq!(adapter.exit_command(), Some("/exit"));
+    }
+
     #[test]
     fn test_analyze_idle_output() {
         let adapter = MpmAdapter::new();
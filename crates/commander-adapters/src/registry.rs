@@ -1,12 +1,16 @@
 //! Adapter registry for discovering and instantiating adapters.
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
+use tracing::warn;
+
 use crate::auggie::AuggieAdapter;
 use crate::claude_code::ClaudeCodeAdapter;
 use crate::codex::CodexAdapter;
 use crate::event_driven::EventDrivenAdapter;
+use crate::manifest;
 use crate::mpm::MpmAdapter;
 use crate::mpm_sdk::MpmSdkAdapter;
 use crate::shell::ShellAdapter;
@@ -18,6 +22,9 @@ use crate::traits::RuntimeAdapter;
 /// instances by name. All adapters are stored as `Arc<dyn RuntimeAdapter>`
 /// to allow sharing across threads.
 ///
+/// Besides the built-in adapters, [`new`](Self::new) loads manifest adapters
+/// from `~/.ai-commander/adapters/*.toml` (see [`crate::manifest`]).
+///
 /// # Example
 ///
 /// ```
@@ -39,11 +46,21 @@ use crate::traits::RuntimeAdapter;
 pub struct AdapterRegistry {
     adapters: HashMap<String, Arc<dyn RuntimeAdapter>>,
     event_driven: HashMap<String, Arc<dyn EventDrivenAdapter>>,
+    /// Aliases declared by manifest adapters, mapped to adapter IDs.
+    aliases: HashMap<String, String>,
 }
 
 impl AdapterRegistry {
-    /// Creates a new registry with all built-in adapters.
+    /// Creates a new registry with all built-in adapters and the manifest
+    /// adapters in the user's adapters directory.
     pub fn new() -> Self {
+        let mut registry = Self::builtin();
+        registry.load_manifests(&commander_core::config::adapters_dir());
+        registry
+    }
+
+    /// Creates a registry with only the built-in adapters.
+    pub fn builtin() -> Self {
         let mut adapters: HashMap<String, Arc<dyn RuntimeAdapter>> = HashMap::new();
 
         // Register built-in adapters
@@ -69,6 +86,7 @@ impl AdapterRegistry {
         Self {
             adapters,
             event_driven,
+            aliases: HashMap::new(),
         }
     }
 
@@ -77,7 +95,30 @@ impl AdapterRegistry {
         Self {
             adapters: HashMap::new(),
             event_driven: HashMap::new(),
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// Registers the manifest adapters found in `dir`.
+    ///
+    /// Manifests may not replace an already registered adapter, and their
+    /// aliases never shadow built-in ones. Returns the number of adapters
+    /// registered.
+    pub fn load_manifests(&mut self, dir: &Path) -> usize {
+        let mut loaded = 0;
+        for adapter in manifest::load_manifests(dir) {
+            let id = adapter.info().id.clone();
+            if self.adapters.contains_key(&id) || self.event_driven.contains_key(&id) {
+                warn!(id = %id, "Manifest adapter ID already registered; skipping");
+                continue;
+            }
+            for alias in adapter.aliases() {
+                self.aliases.entry(alias.clone()).or_insert_with(|| id.clone());
+            }
+            self.adapters.insert(id, Arc::new(adapter));
+            loaded += 1;
         }
+        loaded
     }
 
     /// Registers a terminal-output `RuntimeAdapter`.
@@ -139,8 +180,10 @@ impl AdapterRegistry {
     /// - `claude-code` -> `claude-code` (already canonical)
     /// - `shell`, `sh`, `bash`, `zsh` -> `shell`
     ///
+    /// Manifest adapters resolve by their ID and declared aliases.
+    ///
     /// Returns `None` if the alias is unknown.
-    pub fn resolve(&self, alias: &str) -> Option<&'static str> {
+    pub fn resolve(&self, alias: &str) -> Option<&str> {
         match alias {
             "cc" | "claude-code" => Some("claude-code"),
             "claude-mpm" | "mpm" => Some("mpm"),
@@ -148,16 +191,11 @@ impl AdapterRegistry {
             "shell" | "sh" | "bash" | "zsh" => Some("shell"),
             "auggie" | "augment" => Some("auggie"),
             "codex" => Some("codex"),
-            _ => {
-                // Check if it's a registered adapter ID
-                if self.adapters.contains_key(alias) {
-                    // For dynamic adapters, we return the static mapping if known
-                    // Since we can't return a reference to the input, we just use known IDs
-                    None
-                } else {
-                    None
-                }
-            }
+            _ => self
+                .adapters
+                .get_key_value(alias)
+                .map(|(id, _)| id.as_str())
+                .or_else(|| self.aliases.get(alias).map(String::as_str)),
         }
     }
 }
@@ -262,6 +300,26 @@ mod tests {
         assert_eq!(registry.resolve("unknown"), None);
     }
 
+    #[test]
+    fn test_manifest_adapters_registered() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("aider.toml"),
+            "id = \"aider\"\ncommand = \"aider\"\naliases = [\"ai\", \"cc\"]\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("shell.toml"), "id = \"shell\"\ncommand = \"sh\"\n").unwrap();
+
+        let mut registry = AdapterRegistry::builtin();
+        assert_eq!(registry.load_manifests(dir.path()), 1);
+        assert_eq!(registry.get("aider").unwrap().info().command, "aider");
+        assert_eq!(registry.resolve("aider"), Some("aider"));
+        assert_eq!(registry.resolve("ai"), Some("aider"));
+        // Built-ins keep their IDs and aliases
+        assert_eq!(registry.resolve("cc"), Some("claude-code"));
+        assert_eq!(registry.get("shell").unwrap().info().name, "Shell");
+    }
+
     #[test]
     fn test_auggie_adapter() {
         let registry = AdapterRegistry::new();
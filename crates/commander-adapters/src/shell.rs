@@ -114,6 +114,10 @@ impl RuntimeAdapter for ShellAdapter {
         (self.info.command.clone(), self.info.default_args.clone())
     }
 
+    fn exit_command(&self) -> Option<&str> {
+        Some("exit")
+    }
+
     fn analyze_output(&self, output: &str) -> OutputAnalysis {
         let state = self.analyze_recent_output(output, 10);
         let errors = if state == RuntimeState::Error {
@@ -0,0 +1,367 @@
+//! Test runner output parsing.
+//!
+//! Recognizes the result output of `cargo test`, pytest, Jest and
+//! `go test` in session output and extracts pass/fail counts and the names
+//! of failing tests, so failing tests can be re-run with
+//! [`TestRun::rerun_command`]. When output holds results of several
+//! runners, the one reported last wins.
+
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Test runner that produced a [`TestRun`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestFramework {
+    Cargo,
+    Pytest,
+    Jest,
+    Go,
+}
+
+impl TestFramework {
+    /// Display name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo test",
+            Self::Pytest => "pytest",
+            Self::Jest => "jest",
+            Self::Go => "go test",
+        }
+    }
+}
+
+/// Results of one test run found in output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestRun {
+    pub framework: TestFramework,
+    pub passed: u32,
+    pub failed: u32,
+    pub skipped: u32,
+    /// Names of failing tests, as the runner prints them.
+    #[serde(default)]
+    pub failing: Vec<String>,
+    /// Files (Jest) or packages (`go test`) with failing tests.
+    #[serde(default)]
+    pub failing_files: Vec<String>,
+}
+
+impl TestRun {
+    fn new(framework: TestFramework) -> Self {
+        Self {
+            framework,
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            failing: Vec::new(),
+            failing_files: Vec::new(),
+        }
+    }
+
+    /// Whether no test failed.
+    pub fn succeeded(&self) -> bool {
+        self.failed == 0 && self.failing.is_empty()
+    }
+
+    /// One-line summary, e.g. `cargo test: 41 passed, 2 failed, 1 skipped`.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{}: {} passed, {} failed", self.framework.as_str(), self.passed, self.failed);
+        if self.skipped > 0 {
+            summary.push_str(&format!(", {} skipped", self.skipped));
+        }
+        summary
+    }
+
+    /// Shell command running only the failing tests, when they are known.
+    pub fn rerun_command(&self) -> Option<String> {
+        if self.failing.is_empty() && self.failing_files.is_empty() {
+            return None;
+        }
+        let command = match self.framework {
+            TestFramework::Cargo if !self.failing.is_empty() => {
+                format!("cargo test -- --exact {}", self.failing.join(" "))
+            }
+            TestFramework::Pytest if !self.failing.is_empty() => {
+                let ids: Vec<String> = self.failing.iter().map(|t| shell_quote(t)).collect();
+                format!("pytest {}", ids.join(" "))
+            }
+            TestFramework::Jest if !self.failing_files.is_empty() => {
+                format!("npx jest {}", self.failing_files.join(" "))
+            }
+            TestFramework::Go if !self.failing.is_empty() => {
+                // -run matches each level of a subtest name separately
+                let top: BTreeSet<&str> = self
+                    .failing
+                    .iter()
+                    .map(|t| t.split('/').next().unwrap_or(t))
+                    .collect();
+                let packages = if self.failing_files.is_empty() {
+                    "./...".to_string()
+                } else {
+                    self.failing_files.join(" ")
+                };
+                format!(
+                    "go test {} -run '^({})$'",
+                    packages,
+                    top.into_iter().collect::<Vec<_>>().join("|")
+                )
+            }
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    /// Prompt asking a session to re-run the failing tests.
+    pub fn rerun_prompt(&self) -> Option<String> {
+        let command = self.rerun_command()?;
+        Some(format!(
+            "Re-run the failing tests with `{}` and fix any that still fail.",
+            command
+        ))
+    }
+}
+
+/// Test results reported last in `output`, if any runner's are there.
+pub fn parse_test_output(output: &str) -> Option<TestRun> {
+    let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    [parse_cargo(&lines), parse_pytest(&lines), parse_jest(&lines), parse_go(&lines)]
+        .into_iter()
+        .flatten()
+        .max_by_key(|(last_line, _)| *last_line)
+        .map(|(_, run)| run)
+}
+
+static CARGO_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"test result: (?:ok|FAILED)\. (\d+) passed; (\d+) failed; (\d+) ignored").expect("CARGO_RESULT")
+});
+static CARGO_FAILED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^test (\S+) \.\.\. FAILED$").expect("CARGO_FAILED"));
+
+static PYTEST_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^=*\s*((?:\d+ [a-z]+(?:, )?)+) in [\d.]+s\b").expect("PYTEST_RESULT")
+});
+static PYTEST_FAILED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:FAILED|ERROR) (\S+::\S+)").expect("PYTEST_FAILED"));
+
+static JEST_RESULT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^Tests:\s+(.*?)\d+ total").expect("JEST_RESULT"));
+static JEST_FAILED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*● (.+ › .+)$").expect("JEST_FAILED"));
+static JEST_FAILED_FILE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*FAIL (\S+)").expect("JEST_FAILED_FILE"));
+
+static GO_TEST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*--- (PASS|FAIL|SKIP): (\S+)").expect("GO_TEST"));
+static GO_PACKAGE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(ok|FAIL)\s+(\S+)\s+(?:[\d.]+s|\(cached\))").expect("GO_PACKAGE"));
+
+/// Counts named in a comma-separated list such as `2 failed, 10 passed`.
+static COUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+) ([a-z]+)").expect("COUNT"));
+
+fn counts(list: &str) -> impl Iterator<Item = (u32, &str)> {
+    COUNT
+        .captures_iter(list)
+        .filter_map(|c| Some((c[1].parse().ok()?, c.get(2)?.as_str())))
+}
+
+fn push_unique(list: &mut Vec<String>, item: &str) {
+    if !list.iter().any(|i| i == item) {
+        list.push(item.to_string());
+    }
+}
+
+/// `cargo test` prints one result line per test target; they are summed.
+fn parse_cargo(lines: &[&str]) -> Option<(usize, TestRun)> {
+    let mut run = TestRun::new(TestFramework::Cargo);
+    let mut last = None;
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(caps) = CARGO_RESULT.captures(line) {
+            run.passed += caps[1].parse::<u32>().unwrap_or(0);
+            run.failed += caps[2].parse::<u32>().unwrap_or(0);
+            run.skipped += caps[3].parse::<u32>().unwrap_or(0);
+            last = Some(i);
+        } else if let Some(caps) = CARGO_FAILED.captures(line) {
+            push_unique(&mut run.failing, &caps[1]);
+        }
+    }
+    last.map(|i| (i, run))
+}
+
+fn parse_pytest(lines: &[&str]) -> Option<(usize, TestRun)> {
+    let mut run = TestRun::new(TestFramework::Pytest);
+    let mut last = None;
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(caps) = PYTEST_FAILED.captures(line) {
+            push_unique(&mut run.failing, &caps[1]);
+        } else if let Some(caps) = PYTEST_RESULT.captures(line) {
+            let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+            for (n, kind) in counts(&caps[1]) {
+                match kind {
+                    "passed" | "xpassed" => passed += n,
+                    "failed" | "error" | "errors" => failed += n,
+                    "skipped" | "xfailed" | "deselected" => skipped += n,
+                    _ => {}
+                }
+            }
+            (run.passed, run.failed, run.skipped) = (passed, failed, skipped);
+            last = Some(i);
+        }
+    }
+    last.map(|i| (i, run))
+}
+
+fn parse_jest(lines: &[&str]) -> Option<(usize, TestRun)> {
+    let mut run = TestRun::new(TestFramework::Jest);
+    let mut last = None;
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(caps) = JEST_RESULT.captures(line) {
+            let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+            for (n, kind) in counts(&caps[1]) {
+                match kind {
+                    "passed" => passed += n,
+                    "failed" => failed += n,
+                    "skipped" | "todo" => skipped += n,
+                    _ => {}
+                }
+            }
+            (run.passed, run.failed, run.skipped) = (passed, failed, skipped);
+            last = Some(i);
+        } else if let Some(caps) = JEST_FAILED_FILE.captures(line) {
+            push_unique(&mut run.failing_files, &caps[1]);
+        } else if let Some(caps) = JEST_FAILED.captures(line) {
+            push_unique(&mut run.failing, caps[1].trim());
+        }
+    }
+    last.map(|i| (i, run))
+}
+
+/// Counts come from `go test -v`'s per-test lines; without `-v` only
+/// failing tests and packages are known.
+fn parse_go(lines: &[&str]) -> Option<(usize, TestRun)> {
+    let mut run = TestRun::new(TestFramework::Go);
+    let mut last = None;
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(caps) = GO_TEST.captures(line) {
+            match &caps[1] {
+                "PASS" => run.passed += 1,
+                "FAIL" => {
+                    run.failed += 1;
+                    push_unique(&mut run.failing, &caps[2]);
+                }
+                _ => run.skipped += 1,
+            }
+            last = Some(i);
+        } else if let Some(caps) = GO_PACKAGE.captures(line) {
+            if &caps[1] == "FAIL" {
+                push_unique(&mut run.failing_files, &caps[2]);
+            }
+            last = Some(i);
+        }
+    }
+    last.map(|i| (i, run))
+}
+
+/// Single-quote `s` for a POSIX shell when it contains special characters.
+fn shell_quote(s: &str) -> String {
+    if s.chars().all(|c| c.is_ascii_alphanumeric() || "/._-:".contains(c)) {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo() {
+        let output = "\
+running 3 tests
+test config::tests::test_default ... ok
+test parser::tests::test_empty ... FAILED
+test parser::tests::test_nested ... FAILED
+
+failures:
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+running 2 tests
+test result: ok. 2 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        let run = parse_test_output(output).unwrap();
+        assert_eq!(run.framework, TestFramework::Cargo);
+        assert_eq!((run.passed, run.failed, run.skipped), (3, 2, 1));
+        assert_eq!(run.failing, ["parser::tests::test_empty", "parser::tests::test_nested"]);
+        assert_eq!(
+            run.rerun_command().unwrap(),
+            "cargo test -- --exact parser::tests::test_empty parser::tests::test_nested"
+        );
+    }
+
+    #[test]
+    fn test_parse_pytest() {
+        let output = "\
+tests/test_api.py ..F.                                                   [100%]
+=========================== short test summary info ============================
+FAILED tests/test_api.py::test_login[admin] - AssertionError: 401 != 200
+ERROR tests/test_db.py::test_connect - ConnectionRefusedError
+============= 1 failed, 3 passed, 1 skipped, 1 error in 0.42s =============
+";
+        let run = parse_test_output(output).unwrap();
+        assert_eq!(run.framework, TestFramework::Pytest);
+        assert_eq!((run.passed, run.failed, run.skipped), (3, 2, 1));
+        assert_eq!(run.failing, ["tests/test_api.py::test_login[admin]", "tests/test_db.py::test_connect"]);
+        assert_eq!(
+            run.rerun_command().unwrap(),
+            "pytest 'tests/test_api.py::test_login[admin]' tests/test_db.py::test_connect"
+        );
+    }
+
+    #[test]
+    fn test_parse_jest() {
+        let output = "\
+PASS src/utils.test.js
+FAIL src/api.test.js
+  ● Login › rejects a bad password
+
+Test Suites: 1 failed, 1 passed, 2 total
+Tests:       1 failed, 1 skipped, 12 passed, 14 total
+";
+        let run = parse_test_output(output).unwrap();
+        assert_eq!(run.framework, TestFramework::Jest);
+        assert_eq!((run.passed, run.failed, run.skipped), (12, 1, 1));
+        assert_eq!(run.failing, ["Login › rejects a bad password"]);
+        assert_eq!(run.rerun_command().unwrap(), "npx jest src/api.test.js");
+    }
+
+    #[test]
+    fn test_parse_go_and_last_runner_wins() {
+        let output = "\
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
+=== RUN   TestParse
+--- PASS: TestParse (0.00s)
+=== RUN   TestServe
+    --- FAIL: TestServe/timeout (0.01s)
+--- FAIL: TestServe (0.01s)
+--- SKIP: TestSlow (0.00s)
+FAIL
+FAIL\texample.com/app/server\t0.015s
+ok  \texample.com/app/parser\t(cached)
+";
+        let run = parse_test_output(output).unwrap();
+        assert_eq!(run.framework, TestFramework::Go);
+        assert_eq!((run.passed, run.failed, run.skipped), (1, 2, 1));
+        assert_eq!(run.failing, ["TestServe/timeout", "TestServe"]);
+        assert_eq!(
+            run.rerun_command().unwrap(),
+            "go test example.com/app/server -run '^(TestServe)$'"
+        );
+        assert!(!run.succeeded());
+
+        assert_eq!(parse_test_output("Compiling commander v0.1.0\nFinished"), None);
+    }
+}
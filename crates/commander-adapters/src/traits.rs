@@ -78,6 +78,32 @@ pub trait RuntimeAdapter: Send + Sync {
     /// Returns the command to launch this runtime.
     fn launch_command(&self, project_path: &str) -> (String, Vec<String>);
 
+    /// Returns the command to launch this runtime without permission to
+    /// modify files, for read-only sessions.
+    ///
+    /// Defaults to [`launch_command`](Self::launch_command) for runtimes
+    /// without a read-only mode; those are only instructed not to write.
+    fn read_only_launch_command(&self, project_path: &str) -> (String, Vec<String>) {
+        self.launch_command(project_path)
+    }
+
+    /// Returns the commands that pause and resume this runtime natively,
+    /// such as MPM's session pause.
+    ///
+    /// `None` for runtimes without one; their sessions are paused by
+    /// suspending their processes.
+    fn pause_commands(&self) -> Option<(&str, &str)> {
+        None
+    }
+
+    /// Returns the input that makes this runtime exit back to the shell,
+    /// such as Claude Code's `/exit`.
+    ///
+    /// `None` for runtimes without one; they are interrupted with Ctrl+C.
+    fn exit_command(&self) -> Option<&str> {
+        None
+    }
+
     /// Analyzes output to determine runtime state.
     fn analyze_output(&self, output: &str) -> OutputAnalysis;
 
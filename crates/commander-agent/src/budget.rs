@@ -0,0 +1,268 @@
+//! Per-task budgets for delegated work.
+//!
+//! Autonomous delegation can loop expensively. A [`TaskBudget`] caps the LLM
+//! turns, tokens and wall-clock time one task may use. The
+//! [`CompletionDriver`](crate::CompletionDriver) and Session Agents track a
+//! task's usage with a [`BudgetTracker`]; once a limit is reached the task is
+//! blocked until the user approves continuing, which grants another
+//! allowance of every limit. Limits come from `[agents.budget]`.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use commander_core::BudgetSettings;
+
+use crate::client::ChatResponse;
+
+/// Limits on one task. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskBudget {
+    /// LLM requests.
+    pub max_turns: Option<u32>,
+    /// Prompt and completion tokens.
+    pub max_tokens: Option<u64>,
+    /// Wall-clock time since the task started.
+    pub max_duration: Option<Duration>,
+}
+
+impl TaskBudget {
+    /// Budget from `[agents.budget]`, ignoring zero limits.
+    pub fn from_settings(settings: &BudgetSettings) -> Self {
+        Self {
+            max_turns: settings.max_turns.filter(|n| *n > 0),
+            max_tokens: settings.max_tokens.filter(|n| *n > 0),
+            max_duration: settings
+                .max_minutes
+                .filter(|n| *n > 0)
+                .map(|mins| Duration::from_secs(mins * 60)),
+        }
+    }
+
+    /// Whether no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_turns.is_none() && self.max_tokens.is_none() && self.max_duration.is_none()
+    }
+}
+
+/// The limit a task ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLimit {
+    Turns,
+    Tokens,
+    Time,
+}
+
+impl fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BudgetLimit::Turns => "turns",
+            BudgetLimit::Tokens => "tokens",
+            BudgetLimit::Time => "time",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// LLM requests made and tokens used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Spend {
+    pub turns: u32,
+    pub tokens: u64,
+}
+
+impl Spend {
+    /// Count one request and the tokens of its `response`.
+    pub fn record(&mut self, response: &ChatResponse) {
+        self.turns += 1;
+        self.tokens += response.usage.as_ref().map_or(0, |u| u64::from(u.total_tokens));
+    }
+
+    /// What was spent since `earlier`.
+    pub fn since(self, earlier: Spend) -> Spend {
+        Spend {
+            turns: self.turns.saturating_sub(earlier.turns),
+            tokens: self.tokens.saturating_sub(earlier.tokens),
+        }
+    }
+}
+
+/// Usage of a task's budget.
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetTracker {
+    budget: TaskBudget,
+    spend: Spend,
+    started: Instant,
+    /// Allowances granted: one, plus one per approval to continue.
+    allowances: u32,
+}
+
+impl BudgetTracker {
+    /// Start tracking a task under `budget`.
+    pub fn new(budget: TaskBudget) -> Self {
+        Self {
+            budget,
+            spend: Spend::default(),
+            started: Instant::now(),
+            allowances: 1,
+        }
+    }
+
+    /// Count one LLM request of the task.
+    pub fn record(&mut self, response: &ChatResponse) {
+        self.spend.record(response);
+    }
+
+    /// Add `spend` to the task.
+    pub fn add(&mut self, spend: Spend) {
+        self.spend.turns += spend.turns;
+        self.spend.tokens += spend.tokens;
+    }
+
+    /// The limit the task has reached, if any.
+    pub fn exhausted(&self) -> Option<BudgetLimit> {
+        self.usage().exhausted
+    }
+
+    /// Allow the task another allowance of every limit.
+    pub fn approve_more(&mut self) {
+        self.allowances += 1;
+    }
+
+    /// Current usage against the limits in effect.
+    pub fn usage(&self) -> BudgetUsage {
+        let n = self.allowances;
+        let usage = BudgetUsage {
+            turns: self.spend.turns,
+            max_turns: self.budget.max_turns.map(|max| max.saturating_mul(n)),
+            tokens: self.spend.tokens,
+            max_tokens: self.budget.max_tokens.map(|max| max.saturating_mul(u64::from(n))),
+            elapsed: self.started.elapsed(),
+            max_duration: self.budget.max_duration.map(|max| max.saturating_mul(n)),
+            exhausted: None,
+        };
+        let exhausted = if usage.max_turns.is_some_and(|max| usage.turns >= max) {
+            Some(BudgetLimit::Turns)
+        } else if usage.max_tokens.is_some_and(|max| usage.tokens >= max) {
+            Some(BudgetLimit::Tokens)
+        } else if usage.max_duration.is_some_and(|max| usage.elapsed >= max) {
+            Some(BudgetLimit::Time)
+        } else {
+            None
+        };
+        BudgetUsage { exhausted, ..usage }
+    }
+}
+
+/// A snapshot of a task's budget usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetUsage {
+    pub turns: u32,
+    pub max_turns: Option<u32>,
+    pub tokens: u64,
+    pub max_tokens: Option<u64>,
+    pub elapsed: Duration,
+    pub max_duration: Option<Duration>,
+    /// The limit reached, if any.
+    pub exhausted: Option<BudgetLimit>,
+}
+
+impl fmt::Display for BudgetUsage {
+    /// "turns 12/40 · tokens 48.2k/500k · time 4m/30m"; unlimited values
+    /// show the usage alone.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let turns = limited(self.turns.to_string(), self.max_turns.map(|m| m.to_string()));
+        let tokens = limited(format_tokens(self.tokens), self.max_tokens.map(format_tokens));
+        let time = limited(format_duration(self.elapsed), self.max_duration.map(format_duration));
+        write!(f, "turns {} · tokens {} · time {}", turns, tokens, time)
+    }
+}
+
+fn limited(used: String, max: Option<String>) -> String {
+    match max {
+        Some(max) => format!("{}/{}", used, max),
+        None => used,
+    }
+}
+
+/// "950", "48.2k" or "1.5M".
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => trim_decimal(format!("{:.1}k", tokens as f64 / 1_000.0)),
+        _ => trim_decimal(format!("{:.1}M", tokens as f64 / 1_000_000.0)),
+    }
+}
+
+fn trim_decimal(s: String) -> String {
+    s.replace(".0", "")
+}
+
+/// "40s", "4m" or "1h 5m".
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget() -> TaskBudget {
+        TaskBudget {
+            max_turns: Some(3),
+            max_tokens: Some(10_000),
+            max_duration: Some(Duration::from_secs(30 * 60)),
+        }
+    }
+
+    #[test]
+    fn test_budget_from_settings() {
+        let settings = BudgetSettings {
+            max_turns: Some(40),
+            max_tokens: Some(0),
+            max_minutes: Some(30),
+        };
+        let budget = TaskBudget::from_settings(&settings);
+        assert_eq!(budget.max_turns, Some(40));
+        assert_eq!(budget.max_tokens, None);
+        assert_eq!(budget.max_duration, Some(Duration::from_secs(1800)));
+        assert!(TaskBudget::from_settings(&BudgetSettings::default()).is_unlimited());
+    }
+
+    #[test]
+    fn test_tracker_exhausts_and_extends() {
+        let mut tracker = BudgetTracker::new(budget());
+        tracker.add(Spend { turns: 2, tokens: 4_000 });
+        assert_eq!(tracker.exhausted(), None);
+
+        tracker.add(Spend { turns: 1, tokens: 1_200 });
+        assert_eq!(tracker.exhausted(), Some(BudgetLimit::Turns));
+        assert_eq!(tracker.usage().to_string(), "turns 3/3 · tokens 5.2k/10k · time 0s/30m");
+
+        tracker.approve_more();
+        assert_eq!(tracker.exhausted(), None);
+        assert_eq!(tracker.usage().max_turns, Some(6));
+
+        tracker.add(Spend { turns: 0, tokens: 15_000 });
+        assert_eq!(tracker.exhausted(), Some(BudgetLimit::Tokens));
+
+        let mut timed = BudgetTracker::new(budget());
+        timed.started = Instant::now() - Duration::from_secs(31 * 60);
+        assert_eq!(timed.exhausted(), Some(BudgetLimit::Time));
+    }
+
+    #[test]
+    fn test_spend_since() {
+        let earlier = Spend { turns: 2, tokens: 500 };
+        let now = Spend { turns: 5, tokens: 2_000 };
+        assert_eq!(now.since(earlier), Spend { turns: 3, tokens: 1_500 });
+        assert_eq!(format_tokens(1_500_000), "1.5M");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1h 5m");
+    }
+}
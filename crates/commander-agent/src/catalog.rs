@@ -0,0 +1,381 @@
+//! OpenRouter model catalog.
+//!
+//! The catalog is fetched from OpenRouter's `/models` endpoint and cached in
+//! the runtime state directory, so model IDs can be checked without a request
+//! per agent. It backs three things:
+//!
+//! - [`validate_model`], called when an agent is built with a custom
+//!   [`ModelConfig`], which rejects unknown model IDs with close matches as
+//!   suggestions
+//! - `commander models list` and `commander models search`
+//! - per-token pricing, used by [`OpenRouterClient`](crate::OpenRouterClient)
+//!   to estimate spend
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::client::ChatUsage;
+use crate::config::{ModelConfig, Provider};
+use crate::error::{AgentError, Result};
+
+/// File in the runtime state directory holding the cached catalog.
+pub const CATALOG_FILE: &str = "model_catalog.json";
+
+/// OpenRouter model list endpoint.
+const OPENROUTER_MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
+
+/// Age after which the cached catalog is refreshed.
+pub fn catalog_max_age() -> Duration {
+    Duration::hours(24)
+}
+
+/// Suggestions offered for an unknown model ID.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Price of a model in USD per token.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// USD per prompt token.
+    pub prompt: f64,
+    /// USD per completion token.
+    pub completion: f64,
+}
+
+impl ModelPricing {
+    /// Cost in USD of a request with the given token counts.
+    pub fn cost(&self, prompt_tokens: u32, completion_tokens: u32) -> f64 {
+        self.prompt * f64::from(prompt_tokens) + self.completion * f64::from(completion_tokens)
+    }
+}
+
+/// A model offered by OpenRouter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogModel {
+    /// Model ID as used in [`ModelConfig::model`].
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Context window in tokens, if published.
+    pub context_length: Option<u64>,
+    /// Per-token pricing.
+    pub pricing: ModelPricing,
+}
+
+/// OpenRouter's `/models` response.
+#[derive(Deserialize)]
+struct ModelsResponse {
+    data: Vec<ApiModel>,
+}
+
+/// One model in the `/models` response. Prices are decimal strings.
+#[derive(Deserialize)]
+struct ApiModel {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    context_length: Option<u64>,
+    #[serde(default)]
+    pricing: Option<ApiPricing>,
+}
+
+#[derive(Deserialize)]
+struct ApiPricing {
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    completion: Option<String>,
+}
+
+impl From<ApiModel> for CatalogModel {
+    fn from(model: ApiModel) -> Self {
+        let price = |p: Option<&String>| p.and_then(|p| p.parse::<f64>().ok()).unwrap_or(0.0).max(0.0);
+        let pricing = model
+            .pricing
+            .map(|p| ModelPricing {
+                prompt: price(p.prompt.as_ref()),
+                completion: price(p.completion.as_ref()),
+            })
+            .unwrap_or_default();
+        Self {
+            name: model.name.unwrap_or_else(|| model.id.clone()),
+            id: model.id,
+            context_length: model.context_length,
+            pricing,
+        }
+    }
+}
+
+/// Models available on OpenRouter, as of `fetched_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    /// Models sorted by ID.
+    pub models: Vec<CatalogModel>,
+    /// When the catalog was fetched.
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl ModelCatalog {
+    /// Build a catalog from models, sorting them by ID.
+    pub fn new(mut models: Vec<CatalogModel>) -> Self {
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+        Self {
+            models,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    /// Parse a `/models` response body.
+    pub fn from_response(body: &str) -> Result<Self> {
+        let response: ModelsResponse = serde_json::from_str(body)?;
+        Ok(Self::new(response.data.into_iter().map(CatalogModel::from).collect()))
+    }
+
+    /// Fetch the catalog from OpenRouter. The endpoint needs no API key.
+    pub async fn fetch() -> Result<Self> {
+        let response = reqwest::get(OPENROUTER_MODELS_URL)
+            .await
+            .map_err(|e| AgentError::ProviderUnavailable(format!("failed to fetch model catalog: {}", e)))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AgentError::ProviderUnavailable(format!("failed to read model catalog: {}", e)))?;
+        if !status.is_success() {
+            return Err(AgentError::ProviderUnavailable(format!(
+                "model catalog request failed with {}: {}",
+                status, body
+            )));
+        }
+        Self::from_response(&body)
+    }
+
+    /// Fetch the catalog and cache it in `dir`.
+    pub async fn sync(dir: &Path) -> Result<Self> {
+        let catalog = Self::fetch().await?;
+        catalog.save(dir)?;
+        debug!(models = catalog.models.len(), "Model catalog synced");
+        Ok(catalog)
+    }
+
+    /// The catalog cached in `dir`, fetched again when missing or older than
+    /// `max_age`. A stale cache is kept when fetching fails.
+    pub async fn load_or_sync(dir: &Path, max_age: Duration) -> Result<Self> {
+        match Self::load(dir) {
+            Some(catalog) if !catalog.is_stale(max_age) => Ok(catalog),
+            Some(catalog) => Ok(Self::sync(dir).await.unwrap_or(catalog)),
+            None => Self::sync(dir).await,
+        }
+    }
+
+    /// Path of the cache file in `dir`.
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join(CATALOG_FILE)
+    }
+
+    /// The catalog cached in `dir`, if any.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::path(dir)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// The catalog cached in the runtime state directory, if any.
+    pub fn cached() -> Option<Self> {
+        Self::load(&commander_core::runtime_state_dir())
+    }
+
+    /// Cache the catalog in `dir`.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(Self::path(dir), serde_json::to_string(self)?))
+            .map_err(|e| AgentError::Internal(format!("failed to cache model catalog: {}", e)))
+    }
+
+    /// Whether the catalog is older than `max_age`.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        Utc::now() - self.fetched_at > max_age
+    }
+
+    /// The model with ID `id`.
+    pub fn get(&self, id: &str) -> Option<&CatalogModel> {
+        self.models
+            .binary_search_by(|m| m.id.as_str().cmp(id))
+            .ok()
+            .map(|i| &self.models[i])
+    }
+
+    /// Models whose ID or name contains every word of `query`, ignoring case.
+    pub fn search(&self, query: &str) -> Vec<&CatalogModel> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.models
+            .iter()
+            .filter(|m| {
+                let haystack = format!("{} {}", m.id, m.name).to_lowercase();
+                words.iter().all(|w| haystack.contains(w.as_str()))
+            })
+            .collect()
+    }
+
+    /// IDs closest to `id`, closest first.
+    pub fn suggest(&self, id: &str) -> Vec<&str> {
+        let id = id.to_lowercase();
+        let threshold = (id.chars().count() / 3).max(2);
+        let mut scored: Vec<(usize, &str)> = self
+            .models
+            .iter()
+            .filter_map(|m| {
+                let candidate = m.id.to_lowercase();
+                let distance = edit_distance(&id, &candidate);
+                // A bare model name ("claude-sonnet-4") matches its ID suffix
+                let suffix = candidate.rsplit_once('/').map(|(_, name)| edit_distance(&id, name));
+                let distance = suffix.map_or(distance, |s| s.min(distance));
+                (distance <= threshold).then_some((distance, m.id.as_str()))
+            })
+            .collect();
+        scored.sort();
+        scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, id)| id).collect()
+    }
+
+    /// Check that `id` is in the catalog.
+    pub fn validate(&self, id: &str) -> Result<()> {
+        if self.get(id).is_some() {
+            return Ok(());
+        }
+        let suggestions = self.suggest(id);
+        let hint = if suggestions.is_empty() {
+            "see `commander models search`".to_string()
+        } else {
+            format!("did you mean {}?", suggestions.join(", "))
+        };
+        Err(AgentError::Configuration(format!(
+            "unknown OpenRouter model '{}' ({})",
+            id, hint
+        )))
+    }
+
+    /// Estimated cost in USD of a response from `model`, if it is priced.
+    pub fn cost(&self, model: &str, usage: &ChatUsage) -> Option<f64> {
+        self.get(model)
+            .map(|m| m.pricing.cost(usage.prompt_tokens, usage.completion_tokens))
+    }
+}
+
+/// Check the model of `config` against the cached catalog.
+///
+/// Only OpenRouter models are checked. Without a cached catalog every model is
+/// accepted, so agents never wait on the network to start.
+pub fn validate_model(config: &ModelConfig) -> Result<()> {
+    if config.provider != Provider::OpenRouter {
+        return Ok(());
+    }
+    match ModelCatalog::cached() {
+        Some(catalog) => catalog.validate(&config.model),
+        None => Ok(()),
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + usize::from(ca != *cb));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> ModelCatalog {
+        ModelCatalog::from_response(
+            r#"{"data": [
+                {"id": "anthropic/claude-sonnet-4", "name": "Anthropic: Claude Sonnet 4",
+                 "context_length": 200000, "pricing": {"prompt": "0.000003", "completion": "0.000015"}},
+                {"id": "anthropic/claude-opus-4", "name": "Anthropic: Claude Opus 4",
+                 "context_length": 200000, "pricing": {"prompt": "0.000015", "completion": "0.000075"}},
+                {"id": "openai/gpt-4o", "name": "OpenAI: GPT-4o", "pricing": {"prompt": "-1"}}
+            ]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_response() {
+        let catalog = catalog();
+        assert_eq!(catalog.models.len(), 3);
+        assert_eq!(catalog.models[0].id, "anthropic/claude-opus-4");
+
+        let sonnet = catalog.get("anthropic/claude-sonnet-4").unwrap();
+        assert_eq!(sonnet.context_length, Some(200_000));
+        assert_eq!(sonnet.pricing.prompt, 0.000003);
+
+        // Negative (variable) and missing prices count as free
+        let gpt = catalog.get("openai/gpt-4o").unwrap();
+        assert_eq!(gpt.pricing, ModelPricing::default());
+        assert_eq!(gpt.context_length, None);
+    }
+
+    #[test]
+    fn test_search() {
+        let catalog = catalog();
+        assert_eq!(catalog.search("claude").len(), 2);
+        assert_eq!(catalog.search("CLAUDE opus").len(), 1);
+        assert_eq!(catalog.search("gpt-4o")[0].id, "openai/gpt-4o");
+        assert!(catalog.search("llama").is_empty());
+    }
+
+    #[test]
+    fn test_validate_suggests_close_ids() {
+        let catalog = catalog();
+        assert!(catalog.validate("anthropic/claude-sonnet-4").is_ok());
+
+        assert_eq!(catalog.suggest("anthropic/claude-sonet-4")[0], "anthropic/claude-sonnet-4");
+        assert_eq!(catalog.suggest("claude-opus-4")[0], "anthropic/claude-opus-4");
+        assert!(catalog.suggest("mistral/mixtral").is_empty());
+
+        let err = catalog.validate("anthropic/claude-sonet-4").unwrap_err();
+        assert!(err.to_string().contains("did you mean anthropic/claude-sonnet-4"));
+        let err = catalog.validate("mistral/mixtral").unwrap_err();
+        assert!(err.to_string().contains("commander models search"));
+    }
+
+    #[test]
+    fn test_cost() {
+        let catalog = catalog();
+        let usage = ChatUsage {
+            prompt_tokens: 1000,
+            completion_tokens: 100,
+            total_tokens: 1100,
+        };
+        let cost = catalog.cost("anthropic/claude-sonnet-4", &usage).unwrap();
+        assert!((cost - 0.0045).abs() < 1e-9);
+        assert_eq!(catalog.cost("unknown/model", &usage), None);
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ModelCatalog::load(dir.path()), None);
+
+        let catalog = catalog();
+        catalog.save(dir.path()).unwrap();
+        let loaded = ModelCatalog::load(dir.path()).unwrap();
+        assert_eq!(loaded, catalog);
+        assert!(!loaded.is_stale(catalog_max_age()));
+
+        let mut old = loaded;
+        old.fetched_at = Utc::now() - Duration::days(2);
+        assert!(old.is_stale(catalog_max_age()));
+    }
+}
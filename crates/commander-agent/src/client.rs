@@ -3,11 +3,25 @@
 //! This module provides a client for the OpenRouter API, supporting:
 //! - Chat completions with multiple message roles
 //! - Tool/function calling
+//! - Per-model rate limiting (requests per minute)
+//! - Automatic retry with exponential backoff and jitter for 429/5xx responses
+//! - Several API keys, rotated round-robin, on 429 or pinned per agent
+//! - Spend estimates from the cached [`ModelCatalog`] pricing, in total and
+//!   per API key
 //! - Streaming (future)
 
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasher, DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
+use crate::catalog::ModelCatalog;
 use crate::config::ModelConfig;
 use crate::context::{Message, MessageRole};
 use crate::error::{AgentError, Result};
@@ -16,45 +30,537 @@ use crate::tool::{ToolCall, ToolDefinition};
 /// Environment variable for OpenRouter API key.
 pub const OPENROUTER_API_KEY_ENV: &str = "OPENROUTER_API_KEY";
 
+/// Environment variable with further OpenRouter API keys, comma separated.
+pub const OPENROUTER_API_KEYS_ENV: &str = "OPENROUTER_API_KEYS";
+
+/// Environment variable choosing how requests are spread over the API keys:
+/// `failover` (default), `round-robin` or `per-agent`.
+pub const OPENROUTER_KEY_ROTATION_ENV: &str = "OPENROUTER_KEY_ROTATION";
+
+/// Environment variable with request-per-minute limits.
+///
+/// A bare number limits every model (e.g. `60`); `model=N` entries override
+/// it for one model (e.g. `60,anthropic/claude-opus-4=20`).
+pub const OPENROUTER_RATE_LIMIT_ENV: &str = "OPENROUTER_REQUESTS_PER_MINUTE";
+
 /// OpenRouter chat completions endpoint.
 const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 
+/// Window over which request-per-minute limits are enforced.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// How long a rate-limited key is skipped when the response has no
+/// `Retry-After` header.
+const KEY_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Requests-per-minute limits, each enforced separately per model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimits {
+    /// Limit for models without their own entry; `None` means unlimited.
+    pub default_rpm: Option<u32>,
+    /// Limits for specific models.
+    pub per_model: HashMap<String, u32>,
+}
+
+impl RateLimits {
+    /// Parse limits in the [`OPENROUTER_RATE_LIMIT_ENV`] format.
+    ///
+    /// Malformed entries are skipped with a warning.
+    pub fn parse(spec: &str) -> Self {
+        let mut limits = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (model, rpm) = match entry.rsplit_once('=') {
+                Some((model, rpm)) => (Some(model.trim()), rpm.trim()),
+                None => (None, entry),
+            };
+            let Ok(rpm) = rpm.parse::<u32>() else {
+                warn!(entry = %entry, "Ignoring invalid rate limit");
+                continue;
+            };
+            match model {
+                Some(model) => {
+                    limits.per_model.insert(model.to_string(), rpm);
+                }
+                None => limits.default_rpm = Some(rpm),
+            }
+        }
+        limits
+    }
+
+    /// Read limits from [`OPENROUTER_RATE_LIMIT_ENV`]; unlimited when unset.
+    pub fn from_env() -> Self {
+        std::env::var(OPENROUTER_RATE_LIMIT_ENV)
+            .map(|spec| Self::parse(&spec))
+            .unwrap_or_default()
+    }
+
+    /// Requests per minute allowed for `model`, if limited.
+    pub fn limit_for(&self, model: &str) -> Option<u32> {
+        self.per_model
+            .get(model)
+            .copied()
+            .or(self.default_rpm)
+            .filter(|&rpm| rpm > 0)
+    }
+}
+
+/// How requests are spread over several API keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyRotation {
+    /// Use the first key, moving on to the next while a key is rate limited.
+    #[default]
+    Failover,
+    /// Take turns, skipping keys that are rate limited.
+    RoundRobin,
+    /// Each agent always uses the same key, chosen from its ID, so agents
+    /// draw on separate quotas. Clients without an agent fail over.
+    PerAgent,
+}
+
+impl KeyRotation {
+    /// Read the rotation from [`OPENROUTER_KEY_ROTATION_ENV`]; failover when
+    /// unset or invalid.
+    pub fn from_env() -> Self {
+        let Ok(value) = std::env::var(OPENROUTER_KEY_ROTATION_ENV) else {
+            return Self::default();
+        };
+        value.parse().unwrap_or_else(|e| {
+            warn!("{}; using failover", e);
+            Self::default()
+        })
+    }
+}
+
+impl std::str::FromStr for KeyRotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "failover" => Ok(Self::Failover),
+            "round-robin" | "roundrobin" => Ok(Self::RoundRobin),
+            "per-agent" | "pinned" => Ok(Self::PerAgent),
+            other => Err(format!(
+                "unknown key rotation '{}' (expected failover, round-robin or per-agent)",
+                other
+            )),
+        }
+    }
+}
+
+/// Split a comma-separated list of API keys, dropping blanks and duplicates.
+pub fn parse_api_keys(spec: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for key in spec.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        if !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+    }
+    keys
+}
+
+/// Retry behaviour for rate-limited (429) and server error (5xx) responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub base_delay: Duration,
+    /// Upper bound for a single delay, including `Retry-After` hints.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry `attempt` (0-based).
+    ///
+    /// Exponential backoff capped at `max_delay`, scaled by `jitter` (0.0 to
+    /// 1.0) to between half and all of the delay.
+    pub fn backoff(&self, attempt: u32, jitter: f64) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        delay.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+/// Snapshot of a client's request metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientMetrics {
+    /// Requests waiting for a rate limit slot.
+    pub queue_depth: usize,
+    /// Requests currently sent and awaiting a response.
+    pub in_flight: usize,
+    /// HTTP requests sent, including retries.
+    pub requests: u64,
+    /// Retries after 429, 5xx or connection failures.
+    pub retries: u64,
+    /// Responses with status 429.
+    pub rate_limited: u64,
+    /// Chat calls that failed after all retries.
+    pub failures: u64,
+    /// Estimated spend in millionths of a USD, for models with catalog pricing.
+    pub cost_micro_usd: u64,
+}
+
+impl ClientMetrics {
+    /// Estimated spend in USD.
+    pub fn cost_usd(&self) -> f64 {
+        self.cost_micro_usd as f64 / 1_000_000.0
+    }
+}
+
+/// Snapshot of one API key's usage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsage {
+    /// The key's last characters, safe to display.
+    pub label: String,
+    /// HTTP requests sent with the key, including retries.
+    pub requests: u64,
+    /// Responses with status 429.
+    pub rate_limited: u64,
+    /// Estimated spend in millionths of a USD.
+    pub cost_micro_usd: u64,
+    /// Whether the key is skipped after a 429.
+    pub cooling_down: bool,
+}
+
+impl KeyUsage {
+    /// Estimated spend in USD.
+    pub fn cost_usd(&self) -> f64 {
+        self.cost_micro_usd as f64 / 1_000_000.0
+    }
+}
+
+/// Label of `key` for display: its last four characters.
+fn key_label(key: &str) -> String {
+    let tail: String = key.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+    format!("…{}", tail)
+}
+
+/// Usage and cooldown of one API key.
+#[derive(Debug, Clone, Default)]
+struct KeyState {
+    requests: u64,
+    rate_limited: u64,
+    cost_micro_usd: u64,
+    /// Skipped by rotation until then.
+    cooldown_until: Option<Instant>,
+}
+
+/// Rate limiter windows and metrics shared by clones of a client.
+#[derive(Debug, Default)]
+struct ClientState {
+    /// Send times within the last minute, per model.
+    windows: Mutex<HashMap<String, VecDeque<Instant>>>,
+    queue_depth: AtomicUsize,
+    in_flight: AtomicUsize,
+    requests: AtomicU64,
+    retries: AtomicU64,
+    rate_limited: AtomicU64,
+    failures: AtomicU64,
+    cost_micro_usd: AtomicU64,
+    /// Round-robin position over the API keys.
+    next_key: AtomicUsize,
+    /// Usage and cooldown per API key.
+    keys: Mutex<HashMap<String, KeyState>>,
+}
+
+impl ClientState {
+    /// Take a rate limit slot for `model` at `now`, or return how long until
+    /// one frees up.
+    fn try_acquire(&self, model: &str, limit: u32, now: Instant) -> std::result::Result<(), Duration> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let window = windows.entry(model.to_string()).or_default();
+        while window
+            .front()
+            .is_some_and(|&sent| now.duration_since(sent) >= RATE_LIMIT_WINDOW)
+        {
+            window.pop_front();
+        }
+        if window.len() < limit as usize {
+            window.push_back(now);
+            return Ok(());
+        }
+        Err(RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(window[0])))
+    }
+
+    /// Index of the key in `keys` to send the next request with.
+    ///
+    /// Keys cooling down after a 429 are passed over; when every key is, the
+    /// one whose cooldown ends first is used.
+    fn select_key(&self, keys: &[String], rotation: KeyRotation, agent: Option<&str>, now: Instant) -> usize {
+        if keys.len() <= 1 {
+            return 0;
+        }
+        if let (KeyRotation::PerAgent, Some(agent)) = (rotation, agent) {
+            let mut hasher = DefaultHasher::new();
+            agent.hash(&mut hasher);
+            return (hasher.finish() % keys.len() as u64) as usize;
+        }
+        let start = match rotation {
+            KeyRotation::RoundRobin => self.next_key.fetch_add(1, Ordering::Relaxed) % keys.len(),
+            KeyRotation::Failover | KeyRotation::PerAgent => 0,
+        };
+        let states = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        let cooldown = |i: usize| {
+            states
+                .get(&keys[i])
+                .and_then(|s| s.cooldown_until)
+                .filter(|&until| until > now)
+        };
+        // `None` (ready) sorts first; ties keep rotation order
+        (0..keys.len())
+            .map(|offset| (start + offset) % keys.len())
+            .min_by_key(|&i| cooldown(i))
+            .unwrap_or(0)
+    }
+
+    /// Whether any of `keys` is not cooling down at `now`.
+    fn has_ready_key(&self, keys: &[String], now: Instant) -> bool {
+        let states = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        keys.iter().any(|key| {
+            states
+                .get(key)
+                .and_then(|s| s.cooldown_until)
+                .is_none_or(|until| until <= now)
+        })
+    }
+
+    /// Update the usage of `key`.
+    fn update_key(&self, key: &str, update: impl FnOnce(&mut KeyState)) {
+        let mut states = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        update(states.entry(key.to_string()).or_default());
+    }
+}
+
+/// Increments a gauge for as long as it is alive.
+struct GaugeGuard<'a>(&'a AtomicUsize);
+
+impl<'a> GaugeGuard<'a> {
+    fn new(gauge: &'a AtomicUsize) -> Self {
+        gauge.fetch_add(1, Ordering::Relaxed);
+        Self(gauge)
+    }
+}
+
+impl Drop for GaugeGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Why a single request attempt failed.
+enum AttemptError {
+    /// Worth retrying: 429, 5xx or a connection failure.
+    Transient {
+        message: String,
+        retry_after: Option<Duration>,
+        /// The response was a 429, so another key may succeed.
+        rate_limited: bool,
+    },
+    /// Retrying would not help.
+    Fatal(AgentError),
+}
+
+/// Whether a response status is worth retrying.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Parse a `Retry-After` header given in seconds.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Random jitter between 0.0 and 1.0.
+fn jitter() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+/// State shared by every client created with [`OpenRouterClient::from_env`].
+fn shared_state() -> Arc<ClientState> {
+    static SHARED: OnceLock<Arc<ClientState>> = OnceLock::new();
+    Arc::clone(SHARED.get_or_init(Default::default))
+}
+
 /// OpenRouter API client for chat completions.
+///
+/// Clones share rate limiter windows, metrics and key usage.
 #[derive(Clone)]
 pub struct OpenRouterClient {
     client: reqwest::Client,
-    api_key: String,
+    /// At least one key.
+    api_keys: Vec<String>,
+    rotation: KeyRotation,
+    /// ID of the agent using the client, for [`KeyRotation::PerAgent`].
+    agent: Option<String>,
+    rate_limits: RateLimits,
+    retry: RetryPolicy,
+    state: Arc<ClientState>,
+    catalog: Option<Arc<ModelCatalog>>,
 }
 
 impl OpenRouterClient {
     /// Create a new client with the given API key.
+    ///
+    /// The client is not rate limited and retries with the default policy.
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             client: reqwest::Client::new(),
-            api_key: api_key.into(),
+            api_keys: vec![api_key.into()],
+            rotation: KeyRotation::default(),
+            agent: None,
+            rate_limits: RateLimits::default(),
+            retry: RetryPolicy::default(),
+            state: Arc::default(),
+            catalog: None,
         }
     }
 
-    /// Create a client from environment variables.
+    /// Create a client from the secret store or environment.
     ///
-    /// Uses `OPENROUTER_API_KEY` environment variable.
+    /// Reads `OPENROUTER_API_KEY` and `OPENROUTER_API_KEYS` from the secret
+    /// store, falling back to the environment variables, the key rotation
+    /// from `OPENROUTER_KEY_ROTATION`, rate limits from
+    /// `OPENROUTER_REQUESTS_PER_MINUTE` and pricing from the cached model
+    /// catalog. All clients created this way share one rate limiter and one
+    /// set of metrics.
     pub fn from_env() -> Result<Self> {
-        let api_key = std::env::var(OPENROUTER_API_KEY_ENV).map_err(|_| {
-            AgentError::Configuration(format!(
-                "Missing {} environment variable",
-                OPENROUTER_API_KEY_ENV
-            ))
-        })?;
-        Ok(Self::new(api_key))
+        let mut api_keys = commander_core::get_secret(OPENROUTER_API_KEYS_ENV)
+            .map(|spec| parse_api_keys(&spec))
+            .unwrap_or_default();
+        if let Some(key) = commander_core::get_secret(OPENROUTER_API_KEY_ENV) {
+            if !api_keys.contains(&key) {
+                api_keys.insert(0, key);
+            }
+        }
+        if api_keys.is_empty() {
+            return Err(AgentError::Configuration(format!(
+                "Missing {} (set it with `commander secrets set {}` or in the environment)",
+                OPENROUTER_API_KEY_ENV, OPENROUTER_API_KEY_ENV
+            )));
+        }
+        Ok(Self {
+            api_keys,
+            rotation: KeyRotation::from_env(),
+            rate_limits: RateLimits::from_env(),
+            state: shared_state(),
+            catalog: ModelCatalog::cached().map(Arc::new),
+            ..Self::new(String::new())
+        })
+    }
+
+    /// Use `api_keys` instead of the current keys; ignored when empty.
+    pub fn with_api_keys(mut self, api_keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let api_keys: Vec<String> = api_keys.into_iter().map(Into::into).collect();
+        if !api_keys.is_empty() {
+            self.api_keys = api_keys;
+        }
+        self
+    }
+
+    /// Set how requests are spread over the API keys.
+    pub fn with_key_rotation(mut self, rotation: KeyRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Mark the client as used by agent `agent_id`, which pins it to one key
+    /// under [`KeyRotation::PerAgent`].
+    pub fn for_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent = Some(agent_id.into());
+        self
+    }
+
+    /// Set the requests-per-minute limits.
+    pub fn with_rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Set the retry policy.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set the catalog whose pricing estimates spend.
+    pub fn with_catalog(mut self, catalog: ModelCatalog) -> Self {
+        self.catalog = Some(Arc::new(catalog));
+        self
+    }
+
+    /// Current request metrics.
+    pub fn metrics(&self) -> ClientMetrics {
+        let state = &self.state;
+        ClientMetrics {
+            queue_depth: state.queue_depth.load(Ordering::Relaxed),
+            in_flight: state.in_flight.load(Ordering::Relaxed),
+            requests: state.requests.load(Ordering::Relaxed),
+            retries: state.retries.load(Ordering::Relaxed),
+            rate_limited: state.rate_limited.load(Ordering::Relaxed),
+            failures: state.failures.load(Ordering::Relaxed),
+            cost_micro_usd: state.cost_micro_usd.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Usage of each API key, in configuration order.
+    pub fn key_usage(&self) -> Vec<KeyUsage> {
+        let now = Instant::now();
+        let states = self.state.keys.lock().unwrap_or_else(|e| e.into_inner());
+        self.api_keys
+            .iter()
+            .map(|key| {
+                let state = states.get(key).cloned().unwrap_or_default();
+                KeyUsage {
+                    label: key_label(key),
+                    requests: state.requests,
+                    rate_limited: state.rate_limited,
+                    cost_micro_usd: state.cost_micro_usd,
+                    cooling_down: state.cooldown_until.is_some_and(|until| until > now),
+                }
+            })
+            .collect()
     }
 
     /// Send a chat completion request.
+    ///
+    /// Waits for a rate limit slot before each attempt, then picks an API key
+    /// per the [`KeyRotation`]. 429, 5xx and connection failures are retried
+    /// per the [`RetryPolicy`], without waiting when a 429 can be retried on
+    /// another key; when retries run out the error is
+    /// [`AgentError::ProviderUnavailable`]. Secrets in
+    /// message content are redacted before the request leaves the process.
     pub async fn chat(
         &self,
         config: &ModelConfig,
-        messages: Vec<ChatMessage>,
+        mut messages: Vec<ChatMessage>,
         tools: Option<Vec<ChatTool>>,
     ) -> Result<ChatResponse> {
+        for message in &mut messages {
+            if let Some(content) = &message.content {
+                message.content = Some(commander_core::redact(content));
+            }
+        }
         let request = ChatRequest {
             model: config.model.clone(),
             messages,
@@ -65,31 +571,162 @@ impl OpenRouterClient {
 
         trace!("Sending chat request: {:?}", request);
 
+        let mut attempt = 0;
+        loop {
+            self.wait_for_slot(&request.model).await;
+            let key = &self.api_keys[self.state.select_key(
+                &self.api_keys,
+                self.rotation,
+                self.agent.as_deref(),
+                Instant::now(),
+            )];
+            let error = match self.send(&request, key).await {
+                Ok(response) => {
+                    self.record_cost(key, &request.model, &response);
+                    return Ok(response);
+                }
+                Err(error) => error,
+            };
+
+            match error {
+                AttemptError::Transient {
+                    message,
+                    retry_after,
+                    rate_limited,
+                } if attempt < self.retry.max_retries => {
+                    let delay = if rate_limited && self.can_switch_key() {
+                        Duration::ZERO
+                    } else {
+                        retry_after
+                            .map(|d| d.min(self.retry.max_delay))
+                            .unwrap_or_else(|| self.retry.backoff(attempt, jitter()))
+                    };
+                    warn!(
+                        model = %request.model,
+                        attempt = attempt + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        "{}; retrying",
+                        message
+                    );
+                    self.state.retries.fetch_add(1, Ordering::Relaxed);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                AttemptError::Transient { message, .. } => {
+                    self.state.failures.fetch_add(1, Ordering::Relaxed);
+                    return Err(AgentError::ProviderUnavailable(format!(
+                        "{} (after {} attempts)",
+                        message,
+                        attempt + 1
+                    )));
+                }
+                AttemptError::Fatal(e) => {
+                    self.state.failures.fetch_add(1, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Add the priced cost of `response` to the spend metric and to the
+    /// usage of `key`.
+    fn record_cost(&self, key: &str, model: &str, response: &ChatResponse) {
+        let (Some(catalog), Some(usage)) = (&self.catalog, &response.usage) else {
+            return;
+        };
+        if let Some(cost) = catalog.cost(model, usage) {
+            let micros = (cost * 1_000_000.0).round() as u64;
+            self.state.cost_micro_usd.fetch_add(micros, Ordering::Relaxed);
+            self.state.update_key(key, |state| state.cost_micro_usd += micros);
+        }
+    }
+
+    /// Whether a rate-limited request can move to another key right away.
+    fn can_switch_key(&self) -> bool {
+        let pinned = self.rotation == KeyRotation::PerAgent && self.agent.is_some();
+        self.api_keys.len() > 1 && !pinned && self.state.has_ready_key(&self.api_keys, Instant::now())
+    }
+
+    /// Wait until the rate limit for `model` allows another request.
+    async fn wait_for_slot(&self, model: &str) {
+        let Some(limit) = self.rate_limits.limit_for(model) else {
+            return;
+        };
+        let _queued = GaugeGuard::new(&self.state.queue_depth);
+        loop {
+            match self.state.try_acquire(model, limit, Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => {
+                    debug!(model = %model, wait_ms = wait.as_millis() as u64, "Rate limit reached, waiting");
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// Send one request attempt with `key`.
+    async fn send(&self, request: &ChatRequest, key: &str) -> std::result::Result<ChatResponse, AttemptError> {
+        let _in_flight = GaugeGuard::new(&self.state.in_flight);
+        self.state.requests.fetch_add(1, Ordering::Relaxed);
+        self.state.update_key(key, |state| state.requests += 1);
+
         let response = self
             .client
             .post(OPENROUTER_API_URL)
-            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Authorization", format!("Bearer {}", key))
             .header("Content-Type", "application/json")
             .header("HTTP-Referer", "https://github.com/ezykeys/ai-commander")
             .header("X-Title", "AI Commander")
-            .json(&request)
+            .json(request)
             .send()
             .await
-            .map_err(|e| AgentError::ModelInvocation(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| {
+                let message = format!("HTTP request failed: {}", e);
+                if e.is_connect() || e.is_timeout() {
+                    AttemptError::Transient {
+                        message,
+                        retry_after: None,
+                        rate_limited: false,
+                    }
+                } else {
+                    AttemptError::Fatal(AgentError::ModelInvocation(message))
+                }
+            })?;
 
         let status = response.status();
         if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
             let text = response.text().await.unwrap_or_default();
-            return Err(AgentError::ModelInvocation(format!(
-                "OpenRouter API error {}: {}",
-                status, text
-            )));
+            let message = format!("OpenRouter API error {}: {}", status, text);
+            if !is_retryable_status(status) {
+                return Err(AttemptError::Fatal(AgentError::ModelInvocation(message)));
+            }
+            let rate_limited = status == StatusCode::TOO_MANY_REQUESTS;
+            if rate_limited {
+                self.state.rate_limited.fetch_add(1, Ordering::Relaxed);
+                let until = Instant::now() + retry_after.unwrap_or(KEY_COOLDOWN);
+                self.state.update_key(key, |state| {
+                    state.rate_limited += 1;
+                    state.cooldown_until = Some(until);
+                });
+            }
+            return Err(AttemptError::Transient {
+                message,
+                retry_after,
+                rate_limited,
+            });
         }
 
-        let response: ChatResponse = response
-            .json()
-            .await
-            .map_err(|e| AgentError::ResponseParse(format!("Failed to parse response: {}", e)))?;
+        let response: ChatResponse = response.json().await.map_err(|e| {
+            AttemptError::Fatal(AgentError::ResponseParse(format!(
+                "Failed to parse response: {}",
+                e
+            )))
+        })?;
 
         debug!(
             "Chat response received: {} tokens used",
@@ -519,4 +1156,110 @@ mod tests {
         assert_eq!(tool_calls.len(), 1);
         assert_eq!(tool_calls[0].name, "search_memories");
     }
+
+    #[test]
+    fn test_rate_limits_parse() {
+        let limits = RateLimits::parse("60, anthropic/claude-opus-4=20, bogus=x");
+        assert_eq!(limits.default_rpm, Some(60));
+        assert_eq!(limits.limit_for("anthropic/claude-opus-4"), Some(20));
+        assert_eq!(limits.limit_for("openai/gpt-4o"), Some(60));
+        assert_eq!(RateLimits::parse("").limit_for("any"), None);
+        assert_eq!(RateLimits::parse("0").limit_for("any"), None);
+    }
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let state = ClientState::default();
+        let start = Instant::now();
+        assert!(state.try_acquire("m", 2, start).is_ok());
+        assert!(state.try_acquire("m", 2, start + Duration::from_secs(10)).is_ok());
+
+        let wait = state.try_acquire("m", 2, start + Duration::from_secs(20)).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(40));
+        assert!(state.try_acquire("other", 2, start).is_ok());
+        assert!(state.try_acquire("m", 2, start + RATE_LIMIT_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0, 1.0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2, 1.0), Duration::from_secs(2));
+        assert_eq!(policy.backoff(2, 0.0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(20, 1.0), policy.max_delay);
+
+        let jittered = policy.backoff(1, jitter());
+        assert!(jittered >= Duration::from_millis(500) && jittered <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert_eq!(parse_retry_after(" 7 "), Some(Duration::from_secs(7)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2026 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_record_cost() {
+        let catalog = ModelCatalog::from_response(
+            r#"{"data": [{"id": "m", "pricing": {"prompt": "0.000002", "completion": "0.00001"}}]}"#,
+        )
+        .unwrap();
+        let client = OpenRouterClient::new("key").with_catalog(catalog);
+        let response: ChatResponse = serde_json::from_value(json!({
+            "id": "gen-1",
+            "choices": [],
+            "usage": {"prompt_tokens": 1000, "completion_tokens": 100, "total_tokens": 1100}
+        }))
+        .unwrap();
+
+        client.record_cost("key", "m", &response);
+        client.record_cost("key", "unpriced", &response);
+        assert_eq!(client.metrics().cost_micro_usd, 3000);
+        assert!((client.metrics().cost_usd() - 0.003).abs() < 1e-9);
+        assert_eq!(client.key_usage()[0].cost_micro_usd, 3000);
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let keys = parse_api_keys("sk-a1111, sk-b2222,,sk-a1111, sk-c3333");
+        assert_eq!(keys, ["sk-a1111", "sk-b2222", "sk-c3333"]);
+        assert_eq!("round_robin".parse(), Ok(KeyRotation::RoundRobin));
+        assert!("random".parse::<KeyRotation>().is_err());
+
+        let state = ClientState::default();
+        let now = Instant::now();
+        let select = |rotation, agent| state.select_key(&keys, rotation, agent, now);
+
+        // Round-robin takes turns
+        let turns: Vec<usize> = (0..4).map(|_| select(KeyRotation::RoundRobin, None)).collect();
+        assert_eq!(turns, [0, 1, 2, 0]);
+
+        // Failover stays on the first key until it is rate limited
+        assert_eq!(select(KeyRotation::Failover, None), 0);
+        state.update_key("sk-a1111", |s| s.cooldown_until = Some(now + Duration::from_secs(30)));
+        assert_eq!(select(KeyRotation::Failover, None), 1);
+        assert_eq!(select(KeyRotation::RoundRobin, None), 1);
+        assert!(state.has_ready_key(&keys, now));
+
+        // With every key cooling down, the one free soonest is used
+        state.update_key("sk-b2222", |s| s.cooldown_until = Some(now + Duration::from_secs(10)));
+        state.update_key("sk-c3333", |s| s.cooldown_until = Some(now + Duration::from_secs(20)));
+        assert!(!state.has_ready_key(&keys, now));
+        assert_eq!(select(KeyRotation::Failover, None), 1);
+        assert_eq!(state.select_key(&keys, KeyRotation::Failover, None, now + Duration::from_secs(60)), 0);
+
+        // Pinned agents keep their key; unpinned clients fail over
+        let pinned = select(KeyRotation::PerAgent, Some("session-agent-api"));
+        assert_eq!(select(KeyRotation::PerAgent, Some("session-agent-api")), pinned);
+        assert_eq!(select(KeyRotation::PerAgent, None), 1);
+
+        let client = OpenRouterClient::new("unused").with_api_keys(keys.clone());
+        let usage = client.key_usage();
+        assert_eq!(usage.len(), 3);
+        assert_eq!(usage[1].label, "…2222");
+        assert_eq!(usage[1].requests, 0);
+    }
 }
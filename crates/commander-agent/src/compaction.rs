@@ -36,6 +36,7 @@ use crate::client::OpenRouterClient;
 use crate::config::ModelConfig;
 use crate::context::{AgentContext, Message};
 use crate::error::{AgentError, Result};
+use crate::tokenizer::Tokenizer;
 use commander_memory::Memory;
 
 /// Default number of recent messages to keep in full.
@@ -97,6 +98,9 @@ pub struct ContextWindow {
 
     /// Threshold for triggering compaction (number of pending messages).
     compaction_threshold: usize,
+
+    /// Model tokenizer; falls back to the summarizer's estimate when unset.
+    tokenizer: Option<Tokenizer>,
 }
 
 impl ContextWindow {
@@ -116,6 +120,20 @@ impl ContextWindow {
             summarizer,
             pending_compaction: Vec::new(),
             compaction_threshold: max_recent, // Compact when we have max_recent pending
+            tokenizer: None,
+        }
+    }
+
+    /// Count tokens with a model tokenizer instead of the summarizer's estimate.
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        match &self.tokenizer {
+            Some(tokenizer) => tokenizer.count(text),
+            None => self.summarizer.estimate_tokens(text),
         }
     }
 
@@ -221,14 +239,14 @@ impl ContextWindow {
         let recent_tokens: usize = self
             .recent_messages
             .iter()
-            .map(|m| self.summarizer.estimate_tokens(&m.content))
+            .map(|m| self.count_tokens(&m.content))
             .sum();
 
-        let history_tokens = self.summarizer.estimate_tokens(&self.summarized_history);
+        let history_tokens = self.count_tokens(&self.summarized_history);
         let task_tokens = self
             .current_task
             .as_ref()
-            .map_or(0, |t| self.summarizer.estimate_tokens(t));
+            .map_or(0, |t| self.count_tokens(t));
 
         recent_tokens + history_tokens + task_tokens
     }
@@ -562,6 +580,20 @@ mod tests {
         assert!(!window.within_budget());
     }
 
+    #[tokio::test]
+    async fn test_estimated_tokens_with_tokenizer() {
+        let tokenizer = Tokenizer::for_model("openai/gpt-4o");
+        let mut window = ContextWindow::new(5, 8000, Arc::new(SimpleSummarizer)).with_tokenizer(tokenizer);
+
+        let text = "Refactor the session poller to use a bounded channel.";
+        window
+            .add_message(create_test_message(MessageRole::User, text))
+            .await
+            .unwrap();
+
+        assert_eq!(window.estimated_tokens(), tokenizer.count(text));
+    }
+
     #[tokio::test]
     async fn test_clear() {
         let summarizer = Arc::new(SimpleSummarizer);
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::budget::{BudgetTracker, BudgetUsage, Spend, TaskBudget};
+
 /// Default maximum iterations before forcing a user check-in.
 const DEFAULT_MAX_ITERATIONS: usize = 50;
 
@@ -19,6 +21,8 @@ pub struct CompletionDriver {
     goals: Vec<Goal>,
     /// Blockers requiring user input.
     blockers: Vec<Blocker>,
+    /// Turn, token and time usage, when the task has a budget.
+    budget: Option<BudgetTracker>,
 }
 
 impl Default for CompletionDriver {
@@ -35,6 +39,7 @@ impl CompletionDriver {
             iteration_count: 0,
             goals: Vec::new(),
             blockers: Vec::new(),
+            budget: None,
         }
     }
 
@@ -56,6 +61,15 @@ impl CompletionDriver {
             };
         }
 
+        // Stop if the task's budget is spent
+        if let Some(usage) = self.budget_usage().filter(|u| u.exhausted.is_some()) {
+            let blocker = Blocker::budget(format!("Task budget exhausted ({})", usage));
+            return ContinueDecision::StopForUser {
+                reason: Self::format_blockers_of(std::slice::from_ref(&blocker)),
+                blockers: vec![blocker],
+            };
+        }
+
         // Stop if max iterations reached
         if self.iteration_count >= self.max_iterations {
             return ContinueDecision::CheckIn {
@@ -132,6 +146,32 @@ impl CompletionDriver {
         !self.blockers.is_empty()
     }
 
+    /// Limit the task to `budget`, starting now. An unlimited budget
+    /// removes the limits.
+    pub fn set_budget(&mut self, budget: TaskBudget) {
+        self.budget = (!budget.is_unlimited()).then(|| BudgetTracker::new(budget));
+    }
+
+    /// Count LLM requests and tokens spent on the task.
+    pub fn record_spend(&mut self, spend: Spend) {
+        if let Some(budget) = &mut self.budget {
+            budget.add(spend);
+        }
+    }
+
+    /// Budget usage, when the task has a budget.
+    pub fn budget_usage(&self) -> Option<BudgetUsage> {
+        self.budget.as_ref().map(BudgetTracker::usage)
+    }
+
+    /// The user approved continuing: if the budget is spent, grant another
+    /// allowance of every limit.
+    pub fn approve_budget(&mut self) {
+        if let Some(budget) = self.budget.as_mut().filter(|b| b.exhausted().is_some()) {
+            budget.approve_more();
+        }
+    }
+
     /// Increment the iteration count.
     pub fn increment_iteration(&mut self) {
         self.iteration_count += 1;
@@ -164,7 +204,11 @@ impl CompletionDriver {
 
     /// Format blockers for display.
     fn format_blockers(&self) -> String {
-        self.blockers
+        Self::format_blockers_of(&self.blockers)
+    }
+
+    fn format_blockers_of(blockers: &[Blocker]) -> String {
+        blockers
             .iter()
             .map(|b| format!("- {}: {}", b.blocker_type, b.reason))
             .collect::<Vec<_>>()
@@ -340,6 +384,24 @@ impl Blocker {
     pub fn external(reason: impl Into<String>) -> Self {
         Self::new(reason, BlockerType::ExternalDependency)
     }
+
+    /// Create a blocker for a task whose budget is spent.
+    pub fn budget(reason: impl Into<String>) -> Self {
+        Self::with_options(
+            reason,
+            BlockerType::BudgetExhausted,
+            vec!["Continue".into(), "Stop".into()],
+        )
+    }
+
+    /// Create an approval blocker for a guarded command.
+    pub fn approval(reason: impl Into<String>) -> Self {
+        Self::with_options(
+            reason,
+            BlockerType::ApprovalRequired,
+            vec!["Approve".into(), "Deny".into()],
+        )
+    }
 }
 
 /// Type of blocker requiring user input.
@@ -355,6 +417,10 @@ pub enum BlockerType {
     AmbiguousRequirements,
     /// External dependency (API key, access, etc.).
     ExternalDependency,
+    /// A guardrail requires approval before a command can run.
+    ApprovalRequired,
+    /// The task used up its budget; continuing needs approval.
+    BudgetExhausted,
 }
 
 impl std::fmt::Display for BlockerType {
@@ -365,6 +431,8 @@ impl std::fmt::Display for BlockerType {
             Self::ErrorRequiresJudgment => write!(f, "Error Requires Judgment"),
             Self::AmbiguousRequirements => write!(f, "Ambiguous Requirements"),
             Self::ExternalDependency => write!(f, "External Dependency"),
+            Self::ApprovalRequired => write!(f, "Approval Required"),
+            Self::BudgetExhausted => write!(f, "Budget Exhausted"),
         }
     }
 }
@@ -549,6 +617,10 @@ mod tests {
 
         let error = Blocker::error_judgment("Error occurred", vec!["Retry".into()]);
         assert_eq!(error.blocker_type, BlockerType::ErrorRequiresJudgment);
+
+        let approval = Blocker::approval("git push --force");
+        assert_eq!(approval.blocker_type, BlockerType::ApprovalRequired);
+        assert_eq!(approval.options, vec!["Approve".to_string(), "Deny".to_string()]);
     }
 
     #[test]
@@ -565,6 +637,28 @@ mod tests {
         assert!(progress.contains("[x] Goal 2"));
     }
 
+    #[test]
+    fn test_should_continue_budget_exhausted() {
+        let mut driver = CompletionDriver::new();
+        driver.add_goal(Goal::new("Test goal"));
+        driver.set_budget(TaskBudget { max_turns: Some(4), ..TaskBudget::default() });
+        driver.record_spend(Spend { turns: 3, tokens: 900 });
+        assert!(driver.should_continue().should_continue());
+
+        driver.record_spend(Spend { turns: 1, tokens: 300 });
+        match driver.should_continue() {
+            ContinueDecision::StopForUser { blockers, reason } => {
+                assert_eq!(blockers[0].blocker_type, BlockerType::BudgetExhausted);
+                assert!(reason.contains("turns 4/4"));
+            }
+            other => panic!("Expected StopForUser, got {:?}", other),
+        }
+
+        driver.approve_budget();
+        assert!(driver.should_continue().should_continue());
+        assert_eq!(driver.budget_usage().unwrap().max_turns, Some(8));
+    }
+
     #[test]
     fn test_continue_decision_helpers() {
         let cont = ContinueDecision::Continue;
@@ -1,12 +1,15 @@
 //! Agent context and message types.
 //!
 //! This module defines the context passed to agents when processing messages,
-//! and the message types used for conversation history.
+//! and the message types used for conversation history. Contexts can be
+//! checkpointed under a label and later rewound to, or branched from, that
+//! point.
 
 use chrono::{DateTime, Utc};
 use commander_memory::Memory;
 use serde::{Deserialize, Serialize};
 
+use crate::tokenizer::Tokenizer;
 use crate::tool::{ToolCall, ToolResult};
 
 /// Role of a message in the conversation.
@@ -132,6 +135,30 @@ pub struct AgentContext {
     /// Relevant memories retrieved from the vector store.
     /// These provide long-term context and learned information.
     pub relevant_memories: Vec<Memory>,
+
+    /// Labelled snapshots of the conversation, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checkpoints: Vec<ContextCheckpoint>,
+}
+
+/// Snapshot of a conversation taken by [`AgentContext::checkpoint`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextCheckpoint {
+    /// Label the checkpoint was saved under.
+    pub label: String,
+
+    /// When the checkpoint was taken.
+    pub created_at: DateTime<Utc>,
+
+    /// Task being worked on at the checkpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_task: Option<String>,
+
+    /// Recent messages at the checkpoint.
+    pub recent_messages: Vec<Message>,
+
+    /// Summarized history at the checkpoint.
+    pub summarized_history: String,
 }
 
 impl Default for AgentContext {
@@ -148,6 +175,7 @@ impl AgentContext {
             recent_messages: Vec::new(),
             summarized_history: String::new(),
             relevant_memories: Vec::new(),
+            checkpoints: Vec::new(),
         }
     }
 
@@ -209,6 +237,69 @@ impl AgentContext {
 
         (message_chars + memory_chars + summary_chars + task_chars) / 4
     }
+
+    /// Count the tokens in the context with a model tokenizer.
+    pub fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        let messages: usize = self
+            .recent_messages
+            .iter()
+            .map(|m| tokenizer.count(&m.content))
+            .sum();
+        let memories: usize = self
+            .relevant_memories
+            .iter()
+            .map(|m| tokenizer.count(&m.content))
+            .sum();
+        let task = self.current_task.as_deref().map_or(0, |t| tokenizer.count(t));
+
+        messages + memories + tokenizer.count(&self.summarized_history) + task
+    }
+
+    /// Snapshot the messages and summarized history under `label`.
+    ///
+    /// Replaces an existing checkpoint with the same label.
+    pub fn checkpoint(&mut self, label: impl Into<String>) {
+        let label = label.into();
+        self.checkpoints.retain(|c| c.label != label);
+        self.checkpoints.push(ContextCheckpoint {
+            label,
+            created_at: Utc::now(),
+            current_task: self.current_task.clone(),
+            recent_messages: self.recent_messages.clone(),
+            summarized_history: self.summarized_history.clone(),
+        });
+    }
+
+    /// Find a checkpoint by label.
+    pub fn find_checkpoint(&self, label: &str) -> Option<&ContextCheckpoint> {
+        self.checkpoints.iter().find(|c| c.label == label)
+    }
+
+    /// Fork a new context from the checkpoint `label`.
+    ///
+    /// The fork keeps this context's memories and checkpoints; this context
+    /// is left unchanged. Returns `None` if no such checkpoint exists.
+    pub fn branch(&self, label: &str) -> Option<AgentContext> {
+        let mut forked = self.clone();
+        forked.restore(&self.find_checkpoint(label)?.clone());
+        Some(forked)
+    }
+
+    /// Restore this context to the checkpoint `label`, discarding messages
+    /// added since. Returns `false` if no such checkpoint exists.
+    pub fn rewind(&mut self, label: &str) -> bool {
+        let Some(checkpoint) = self.find_checkpoint(label).cloned() else {
+            return false;
+        };
+        self.restore(&checkpoint);
+        true
+    }
+
+    fn restore(&mut self, checkpoint: &ContextCheckpoint) {
+        self.current_task = checkpoint.current_task.clone();
+        self.recent_messages = checkpoint.recent_messages.clone();
+        self.summarized_history = checkpoint.summarized_history.clone();
+    }
 }
 
 #[cfg(test)]
@@ -331,4 +422,41 @@ mod tests {
 
         assert_eq!(ctx.current_task, parsed.current_task);
     }
+
+    #[test]
+    fn test_checkpoint_and_rewind() {
+        let mut ctx = AgentContext::new();
+        ctx.add_message(Message::user("Fix the build"));
+        ctx.set_summarized_history("Build broken on main");
+        ctx.checkpoint("good");
+
+        ctx.add_message(Message::assistant("Deleting target/"));
+        ctx.set_summarized_history("Went down a bad path");
+        assert!(!ctx.rewind("missing"));
+        assert!(ctx.rewind("good"));
+
+        assert_eq!(ctx.recent_messages.len(), 1);
+        assert_eq!(ctx.summarized_history, "Build broken on main");
+        assert_eq!(ctx.checkpoints.len(), 1);
+
+        ctx.add_message(Message::assistant("Retrying"));
+        ctx.checkpoint("good");
+        assert_eq!(ctx.checkpoints.len(), 1);
+        assert_eq!(ctx.find_checkpoint("good").unwrap().recent_messages.len(), 2);
+    }
+
+    #[test]
+    fn test_branch_leaves_original_untouched() {
+        let mut ctx = AgentContext::with_task("Refactor");
+        ctx.add_message(Message::user("Start"));
+        ctx.checkpoint("start");
+        ctx.add_message(Message::assistant("Step one"));
+
+        let fork = ctx.branch("start").unwrap();
+        assert_eq!(fork.recent_messages.len(), 1);
+        assert_eq!(fork.current_task.as_deref(), Some("Refactor"));
+        assert!(fork.find_checkpoint("start").is_some());
+        assert_eq!(ctx.recent_messages.len(), 2);
+        assert!(ctx.branch("missing").is_none());
+    }
 }
@@ -28,9 +28,16 @@
 //!     }
 //! }
 //! ```
+//!
+//! Use [`ContextManager::for_model`] to size the window and count tokens with
+//! the model's own tokenizer (see [`crate::tokenizer`]).
+
+use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+use crate::tokenizer::Tokenizer;
+
 /// Strategy for handling context limits.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -93,6 +100,53 @@ pub enum CriticalAction {
     },
 }
 
+/// Snapshot of context window usage, e.g. for a status bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextUsage {
+    /// Tokens currently in use.
+    pub used_tokens: usize,
+    /// Context window size in tokens.
+    pub max_tokens: usize,
+}
+
+impl ContextUsage {
+    /// Fraction of the window in use (0.0 to 1.0, may exceed 1.0 when over budget).
+    pub fn used_fraction(&self) -> f32 {
+        if self.max_tokens == 0 {
+            return 1.0;
+        }
+        self.used_tokens as f32 / self.max_tokens as f32
+    }
+
+    /// Percentage of the window in use, rounded down.
+    pub fn percent_used(&self) -> u8 {
+        (self.used_fraction() * 100.0).clamp(0.0, 100.0) as u8
+    }
+}
+
+impl fmt::Display for ContextUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} tokens ({}%)",
+            format_tokens(self.used_tokens),
+            format_tokens(self.max_tokens),
+            self.percent_used()
+        )
+    }
+}
+
+/// Format a token count compactly (`950`, `24.1k`, `1.2M`).
+fn format_tokens(tokens: usize) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{:.1}k", tokens as f64 / 1_000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
 /// Context manager for tracking and managing context window usage.
 ///
 /// Monitors token usage and triggers appropriate actions based on
@@ -113,6 +167,8 @@ pub struct ContextManager {
     state_summary: String,
     /// Number of messages to consider for compaction.
     compaction_target: usize,
+    /// Tokenizer matching the model.
+    tokenizer: Tokenizer,
 }
 
 impl ContextManager {
@@ -130,9 +186,21 @@ impl ContextManager {
             strategy,
             state_summary: String::new(),
             compaction_target: 10, // Default: summarize 10 oldest messages
+            tokenizer: Tokenizer::default(),
         }
     }
 
+    /// Create a context manager sized and tokenized for a model.
+    ///
+    /// # Arguments
+    /// * `strategy` - The context handling strategy
+    /// * `model` - Model ID, e.g. `anthropic/claude-haiku-4`
+    pub fn for_model(strategy: ContextStrategy, model: &str) -> Self {
+        let mut manager = Self::new(strategy, model_contexts::for_model(model));
+        manager.tokenizer = Tokenizer::for_model(model);
+        manager
+    }
+
     /// Create a context manager with custom thresholds.
     ///
     /// # Arguments
@@ -154,6 +222,7 @@ impl ContextManager {
             strategy,
             state_summary: String::new(),
             compaction_target: 10,
+            tokenizer: Tokenizer::default(),
         }
     }
 
@@ -206,6 +275,24 @@ impl ContextManager {
         &self.strategy
     }
 
+    /// Get the tokenizer used for counting.
+    pub fn tokenizer(&self) -> Tokenizer {
+        self.tokenizer
+    }
+
+    /// Count the tokens in `text` with the model's tokenizer.
+    pub fn count_tokens(&self, text: &str) -> usize {
+        self.tokenizer.count(text)
+    }
+
+    /// Current usage as of the last `update`.
+    pub fn context_usage(&self) -> ContextUsage {
+        ContextUsage {
+            used_tokens: self.current_tokens,
+            max_tokens: self.max_tokens,
+        }
+    }
+
     /// Set the state summary for pause/resume operations.
     pub fn set_state_summary(&mut self, summary: impl Into<String>) {
         self.state_summary = summary.into();
@@ -295,8 +382,28 @@ pub mod model_contexts {
     pub const CLAUDE_3_OPUS: usize = 200_000;
     /// GPT-4 Turbo context window (128K tokens).
     pub const GPT_4_TURBO: usize = 128_000;
+    /// GPT-4o context window (128K tokens).
+    pub const GPT_4O: usize = 128_000;
+    /// Original GPT-4 context window (8K tokens).
+    pub const GPT_4: usize = 8_192;
     /// Default context window for unknown models.
     pub const DEFAULT: usize = 100_000;
+
+    /// Context window for a model ID (provider prefixes are ignored).
+    pub fn for_model(model: &str) -> usize {
+        let name = crate::tokenizer::model_name(model);
+        if name.contains("claude") {
+            CLAUDE_3_5_SONNET
+        } else if name.starts_with("gpt-4o") || name.starts_with("o1") || name.starts_with("o3") {
+            GPT_4O
+        } else if name.starts_with("gpt-4-turbo") || name.starts_with("gpt-4-1106") {
+            GPT_4_TURBO
+        } else if name == "gpt-4" || name.starts_with("gpt-4-0") {
+            GPT_4
+        } else {
+            DEFAULT
+        }
+    }
 }
 
 #[cfg(test)]
@@ -540,6 +647,34 @@ mod tests {
         matches!(action, ContextAction::Critical { .. });
     }
 
+    #[test]
+    fn test_for_model() {
+        let manager = ContextManager::for_model(ContextStrategy::Compaction, "anthropic/claude-haiku-4");
+        assert_eq!(manager.max_tokens(), 200_000);
+        assert_eq!(manager.tokenizer().kind(), crate::tokenizer::TokenizerKind::ClaudeApprox);
+
+        let manager = ContextManager::for_model(ContextStrategy::Compaction, "openai/gpt-4o");
+        assert_eq!(manager.max_tokens(), 128_000);
+        assert_eq!(manager.count_tokens("hello world"), 2);
+
+        assert_eq!(model_contexts::for_model("gpt-4"), 8_192);
+        assert_eq!(model_contexts::for_model("mistral-large"), model_contexts::DEFAULT);
+    }
+
+    #[test]
+    fn test_context_usage() {
+        let mut manager = ContextManager::new(ContextStrategy::Compaction, 200_000);
+        manager.update(24_100);
+
+        let usage = manager.context_usage();
+        assert_eq!(usage.used_tokens, 24_100);
+        assert_eq!(usage.percent_used(), 12);
+        assert_eq!(usage.to_string(), "24.1k/200.0k tokens (12%)");
+
+        let empty = ContextUsage { used_tokens: 5, max_tokens: 0 };
+        assert_eq!(empty.percent_used(), 100);
+    }
+
     #[test]
     fn test_model_context_sizes() {
         assert_eq!(model_contexts::CLAUDE_3_5_SONNET, 200_000);
@@ -35,6 +35,10 @@ pub enum AgentError {
     #[error("model invocation failed: {0}")]
     ModelInvocation(String),
 
+    /// The model provider is rate limiting or failing, even after retries.
+    #[error("model provider unavailable: {0}")]
+    ProviderUnavailable(String),
+
     /// Response parsing failed.
     #[error("failed to parse response: {0}")]
     ResponseParse(String),
@@ -51,10 +55,40 @@ pub enum AgentError {
     #[error("maximum iterations ({0}) exceeded in tool execution loop")]
     MaxIterationsExceeded(u32),
 
+    /// A task reached its budget and waits for the user to approve continuing.
+    #[error("task budget exhausted: {0}")]
+    BudgetExhausted(String),
+
     /// Agent not initialized.
     #[error("agent not initialized: {0}")]
     NotInitialized(String),
 
+    /// A guardrail requires user approval before the command can run.
+    #[error("approval required for '{command}': {reason}")]
+    ApprovalRequired {
+        /// The command awaiting approval.
+        command: String,
+        /// Why approval is required.
+        reason: String,
+    },
+
+    /// The agent's permission profile does not allow the tool.
+    #[error("permission denied: {agent} may not use {tool} under the {profile} profile ({reason})")]
+    PermissionDenied {
+        /// Agent that called the tool.
+        agent: String,
+        /// Name of the tool.
+        tool: String,
+        /// Permission profile in effect.
+        profile: String,
+        /// Why the profile refuses the tool.
+        reason: String,
+    },
+
+    /// Git operation failed.
+    #[error("git operation failed: {0}")]
+    Git(String),
+
     /// Configuration error.
     #[error("configuration error: {0}")]
     Configuration(String),
@@ -67,6 +101,22 @@ pub enum AgentError {
 /// Result type for agent operations.
 pub type Result<T> = std::result::Result<T, AgentError>;
 
+impl From<AgentError> for commander_core::CommanderError {
+    fn from(err: AgentError) -> Self {
+        use commander_core::CommanderError;
+        match err {
+            AgentError::ModelInvocation(_)
+            | AgentError::ProviderUnavailable(_)
+            | AgentError::ResponseParse(_) => CommanderError::Provider(err.to_string()),
+            AgentError::ApprovalRequired { .. } | AgentError::PermissionDenied { .. } => {
+                CommanderError::Permission(err.to_string())
+            }
+            AgentError::Configuration(_) => CommanderError::Config(err.to_string()),
+            _ => CommanderError::Runtime(err.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +142,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_commander_error_category() {
+        let err: commander_core::CommanderError = AgentError::ProviderUnavailable("429".into()).into();
+        assert_eq!(err.category(), "provider");
+
+        let err: commander_core::CommanderError = AgentError::ToolNotFound("x".into()).into();
+        assert_eq!(err.category(), "runtime");
+    }
+
     #[test]
     fn test_error_from_json() {
         let json_err = serde_json::from_str::<serde_json::Value>("invalid").unwrap_err();
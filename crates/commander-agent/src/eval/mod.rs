@@ -12,6 +12,14 @@
 //! - Timeouts
 //! - Corrections provided by users
 //! - Positive feedback (for balance)
+//! - Change detector misclassifications, used to learn per-adapter
+//!   significance patterns
+//!
+//! Offline regression evals replay golden transcripts against the current
+//! prompts and models and score the answers with an LLM judge (see [`golden`]).
+//!
+//! Improvements suggested from feedback become prompt patches that are
+//! reviewed, applied as new prompt versions and rolled back (see [`patches`]).
 //!
 //! # Example
 //!
@@ -36,7 +44,10 @@
 //! ```
 
 mod detector;
+pub mod golden;
 mod improvement;
+pub mod patches;
+mod significance;
 mod store;
 mod types;
 
@@ -49,7 +60,13 @@ use crate::error::Result;
 
 // Re-export public types
 pub use detector::FeedbackDetector;
+pub use golden::{
+    load_datasets, run_evals, EvalDataset, EvalOptions, EvalReport, EvalTarget, GoldenCase, Judge,
+    LlmJudge, LlmResponder, Responder,
+};
 pub use improvement::{Improvement, ImprovementGenerator};
+pub use patches::{ImprovementStore, PatchStatus, PromptPatch, PromptVersion, TemplateHistory};
+pub use significance::change_detector_agent_id;
 pub use store::FeedbackStore;
 pub use types::{Feedback, FeedbackSummary, FeedbackType};
 
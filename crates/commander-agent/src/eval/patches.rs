@@ -0,0 +1,394 @@
+//! Prompt patches proposed from feedback, and versioned agent prompts.
+//!
+//! Suggestions from the [`ImprovementGenerator`] are stored as proposed
+//! patches for a template (`user` or an adapter type). Applying a patch writes
+//! a new version of that template's system prompt with the patch's guideline
+//! added under [`LEARNED_SECTION`]; agents use the active version. Every
+//! version is kept, so a bad change can be rolled back.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::error::{AgentError, Result};
+use crate::template::TemplateRegistry;
+use crate::user_agent::DEFAULT_SYSTEM_PROMPT;
+
+use super::golden::EvalTarget;
+use super::improvement::{Improvement, ImprovementGenerator};
+use super::store::FeedbackStore;
+use super::types::Feedback;
+
+/// Heading under which applied guidelines are added to a prompt.
+pub const LEARNED_SECTION: &str = "## Learned from feedback";
+
+/// Prefix of Session Agent IDs, whose feedback feeds adapter templates.
+const SESSION_AGENT_PREFIX: &str = "session-agent-";
+
+/// Review state of a patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchStatus {
+    Proposed,
+    Applied,
+    Rejected,
+    /// Applied, then undone by a rollback.
+    RolledBack,
+}
+
+impl std::fmt::Display for PatchStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Proposed => write!(f, "proposed"),
+            Self::Applied => write!(f, "applied"),
+            Self::Rejected => write!(f, "rejected"),
+            Self::RolledBack => write!(f, "rolled back"),
+        }
+    }
+}
+
+/// A proposed change to a template's system prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptPatch {
+    /// Short ID, unique among patches.
+    pub id: String,
+    /// Template the patch is for (`user` or an adapter type).
+    pub template: String,
+    /// The suggestion the patch was made from.
+    pub improvement: Improvement,
+    /// Guideline added to the prompt when applied.
+    pub instruction: String,
+    pub status: PatchStatus,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Prompt version the patch created when applied.
+    #[serde(default)]
+    pub version: Option<u32>,
+}
+
+impl PromptPatch {
+    /// Create a proposed patch from an improvement.
+    pub fn new(template: &EvalTarget, improvement: Improvement) -> Self {
+        let mut id = Uuid::new_v4().simple().to_string();
+        id.truncate(8);
+        Self {
+            id,
+            template: template.to_string(),
+            instruction: improvement.suggested_change.trim().to_string(),
+            improvement,
+            status: PatchStatus::Proposed,
+            created_at: Utc::now(),
+            decided_at: None,
+            version: None,
+        }
+    }
+}
+
+/// A version of a template's system prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptVersion {
+    /// 1 for the first applied patch; version 0 is the built-in prompt.
+    pub version: u32,
+    pub system_prompt: String,
+    /// Patch that produced this version.
+    pub patch_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Prompt versions of one template.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateHistory {
+    /// Version agents use; 0 means the built-in prompt.
+    pub active: u32,
+    pub versions: Vec<PromptVersion>,
+}
+
+impl TemplateHistory {
+    /// The active version, unless the built-in prompt is active.
+    pub fn active_version(&self) -> Option<&PromptVersion> {
+        self.versions.iter().find(|v| v.version == self.active)
+    }
+}
+
+/// Contents of `improvements.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ImprovementData {
+    #[serde(default)]
+    patches: Vec<PromptPatch>,
+    /// Keyed by template name.
+    #[serde(default)]
+    templates: BTreeMap<String, TemplateHistory>,
+}
+
+/// Proposed patches and prompt versions, stored in `improvements.json`.
+pub struct ImprovementStore {
+    path: PathBuf,
+}
+
+impl ImprovementStore {
+    /// Store in `dir`. Nothing is written until a patch is proposed.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { path: dir.into() }
+    }
+
+    /// Store in `~/.ai-commander/improvements`.
+    pub fn open_default() -> Self {
+        Self::new(commander_core::config::state_dir().join("improvements"))
+    }
+
+    /// Directory of the store.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Patches for `template` (all templates when `None`), oldest first.
+    pub fn patches(&self, template: Option<&EvalTarget>) -> Result<Vec<PromptPatch>> {
+        let template = template.map(|t| t.to_string());
+        Ok(self
+            .load()?
+            .patches
+            .into_iter()
+            .filter(|p| template.as_ref().is_none_or(|t| &p.template == t))
+            .collect())
+    }
+
+    /// Prompt versions of `template`.
+    pub fn history(&self, template: &EvalTarget) -> Result<TemplateHistory> {
+        Ok(self.load()?.templates.remove(&template.to_string()).unwrap_or_default())
+    }
+
+    /// System prompt of the active version of `template`, unless the built-in
+    /// prompt is active.
+    pub fn active_prompt(&self, template: &EvalTarget) -> Option<String> {
+        let history = self.history(template).ok()?;
+        history.active_version().map(|v| v.system_prompt.clone())
+    }
+
+    /// Store `improvements` as proposed patches for `template`, skipping ones
+    /// already proposed or applied. Returns the new patches.
+    pub fn propose(&self, template: &EvalTarget, improvements: Vec<Improvement>) -> Result<Vec<PromptPatch>> {
+        let mut data = self.load()?;
+        let name = template.to_string();
+        let mut added = Vec::new();
+        for improvement in improvements {
+            let duplicate = data.patches.iter().any(|p| {
+                p.template == name
+                    && matches!(p.status, PatchStatus::Proposed | PatchStatus::Applied)
+                    && p.instruction == improvement.suggested_change.trim()
+            });
+            if duplicate || improvement.suggested_change.trim().is_empty() {
+                continue;
+            }
+            let patch = PromptPatch::new(template, improvement);
+            info!(patch = %patch.id, template = %name, category = %patch.improvement.category, "Proposed prompt patch");
+            added.push(patch.clone());
+            data.patches.push(patch);
+        }
+        if !added.is_empty() {
+            self.save(&data)?;
+        }
+        Ok(added)
+    }
+
+    /// Analyze `template`'s feedback and propose patches from the suggestions.
+    pub async fn propose_from_feedback(
+        &self,
+        generator: &ImprovementGenerator,
+        feedback: &FeedbackStore,
+        template: &EvalTarget,
+    ) -> Result<Vec<PromptPatch>> {
+        let feedback = template_feedback(feedback, template);
+        let improvements = generator.analyze(&feedback).await?;
+        self.propose(template, improvements)
+    }
+
+    /// Apply a proposed (or rolled back) patch by ID or ID prefix: add its
+    /// guideline to the active prompt as a new version and make it active.
+    pub fn apply(&self, id: &str) -> Result<PromptPatch> {
+        let mut data = self.load()?;
+        let index = find_patch(&data.patches, id)?;
+        let patch = &data.patches[index];
+        if !matches!(patch.status, PatchStatus::Proposed | PatchStatus::RolledBack) {
+            return Err(AgentError::Configuration(format!(
+                "patch {} is {}, not proposed",
+                patch.id, patch.status
+            )));
+        }
+        let target: EvalTarget = patch.template.parse()?;
+
+        let history = data.templates.entry(patch.template.clone()).or_default();
+        let current = match history.active_version() {
+            Some(version) => version.system_prompt.clone(),
+            None => builtin_prompt(&target)?,
+        };
+        let version = history.versions.iter().map(|v| v.version).max().unwrap_or(0) + 1;
+        history.versions.push(PromptVersion {
+            version,
+            system_prompt: add_guideline(&current, &patch.instruction),
+            patch_id: Some(patch.id.clone()),
+            created_at: Utc::now(),
+        });
+        history.active = version;
+
+        let patch = &mut data.patches[index];
+        patch.status = PatchStatus::Applied;
+        patch.decided_at = Some(Utc::now());
+        patch.version = Some(version);
+        let patch = patch.clone();
+        self.save(&data)?;
+        info!(patch = %patch.id, template = %patch.template, version, "Applied prompt patch");
+        Ok(patch)
+    }
+
+    /// Reject a proposed patch by ID or ID prefix.
+    pub fn reject(&self, id: &str) -> Result<PromptPatch> {
+        let mut data = self.load()?;
+        let index = find_patch(&data.patches, id)?;
+        let patch = &mut data.patches[index];
+        if patch.status != PatchStatus::Proposed {
+            return Err(AgentError::Configuration(format!(
+                "patch {} is {}, not proposed",
+                patch.id, patch.status
+            )));
+        }
+        patch.status = PatchStatus::Rejected;
+        patch.decided_at = Some(Utc::now());
+        let patch = patch.clone();
+        self.save(&data)?;
+        info!(patch = %patch.id, template = %patch.template, "Rejected prompt patch");
+        Ok(patch)
+    }
+
+    /// Make version `to` of `template` active (the one before the active
+    /// version when `None`; 0 is the built-in prompt). Patches applied after
+    /// it are marked rolled back. Returns the now active version.
+    pub fn rollback(&self, template: &EvalTarget, to: Option<u32>) -> Result<u32> {
+        let mut data = self.load()?;
+        let name = template.to_string();
+        let history = data.templates.entry(name.clone()).or_default();
+        let to = match to {
+            Some(to) => to,
+            None if history.active == 0 => {
+                return Err(AgentError::Configuration(format!("{} already uses the built-in prompt", name)));
+            }
+            None => history
+                .versions
+                .iter()
+                .map(|v| v.version)
+                .filter(|&v| v < history.active)
+                .max()
+                .unwrap_or(0),
+        };
+        if to != 0 && !history.versions.iter().any(|v| v.version == to) {
+            return Err(AgentError::Configuration(format!("{} has no prompt version {}", name, to)));
+        }
+        history.active = to;
+
+        for patch in data.patches.iter_mut().filter(|p| p.template == name) {
+            if patch.status == PatchStatus::Applied && patch.version.is_some_and(|v| v > to) {
+                patch.status = PatchStatus::RolledBack;
+            }
+        }
+        self.save(&data)?;
+        info!(template = %name, version = to, "Rolled back prompt");
+        Ok(to)
+    }
+
+    fn data_file(&self) -> PathBuf {
+        self.path.join("improvements.json")
+    }
+
+    fn load(&self) -> Result<ImprovementData> {
+        let file = self.data_file();
+        if !file.exists() {
+            return Ok(ImprovementData::default());
+        }
+        let data = std::fs::read_to_string(&file).map_err(|e| {
+            AgentError::Configuration(format!("Failed to read improvements: {}", e))
+        })?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    fn save(&self, data: &ImprovementData) -> Result<()> {
+        std::fs::create_dir_all(&self.path).map_err(|e| {
+            AgentError::Configuration(format!(
+                "Failed to create improvements directory {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+        let file = self.data_file();
+        let json = serde_json::to_string_pretty(data)?;
+
+        // Atomic write via temp file
+        let temp_file = file.with_extension("json.tmp");
+        std::fs::write(&temp_file, &json).map_err(|e| {
+            AgentError::Configuration(format!("Failed to write improvements: {}", e))
+        })?;
+        std::fs::rename(&temp_file, &file).map_err(|e| {
+            AgentError::Configuration(format!("Failed to save improvements: {}", e))
+        })?;
+        debug!(patches = data.patches.len(), "Saved improvements to disk");
+        Ok(())
+    }
+}
+
+/// Feedback about agents using `template`: the User Agent's for `user`,
+/// Session Agents' for adapter templates.
+pub fn template_feedback(store: &FeedbackStore, template: &EvalTarget) -> Vec<Feedback> {
+    store
+        .entries()
+        .iter()
+        .filter(|f| match template {
+            EvalTarget::User => f.agent_id == "user-agent",
+            EvalTarget::Session(_) => f.agent_id.starts_with(SESSION_AGENT_PREFIX),
+        })
+        .cloned()
+        .collect()
+}
+
+/// Built-in system prompt of `template`.
+pub fn builtin_prompt(template: &EvalTarget) -> Result<String> {
+    match template {
+        EvalTarget::User => Ok(DEFAULT_SYSTEM_PROMPT.to_string()),
+        EvalTarget::Session(adapter) => TemplateRegistry::new()
+            .get(adapter)
+            .map(|t| t.system_prompt.clone())
+            .ok_or_else(|| AgentError::Configuration(format!("no template for {}", adapter))),
+    }
+}
+
+/// `prompt` with `guideline` added as a bullet under [`LEARNED_SECTION`].
+fn add_guideline(prompt: &str, guideline: &str) -> String {
+    let mut prompt = prompt.trim_end().to_string();
+    if !prompt.contains(LEARNED_SECTION) {
+        prompt.push_str("\n\n");
+        prompt.push_str(LEARNED_SECTION);
+    }
+    prompt.push_str("\n- ");
+    prompt.push_str(guideline);
+    prompt
+}
+
+/// Index of the patch with ID `id`, or the only one starting with it.
+fn find_patch(patches: &[PromptPatch], id: &str) -> Result<usize> {
+    if let Some(index) = patches.iter().position(|p| p.id == id) {
+        return Ok(index);
+    }
+    let matches: Vec<usize> = patches
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !id.is_empty() && p.id.starts_with(id))
+        .map(|(i, _)| i)
+        .collect();
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(AgentError::Configuration(format!("no patch with id {}", id))),
+        _ => Err(AgentError::Configuration(format!("patch id {} is ambiguous", id))),
+    }
+}
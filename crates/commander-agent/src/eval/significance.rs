@@ -0,0 +1,79 @@
+//! Change detector feedback for adaptive significance learning.
+//!
+//! Verdicts on escalated changes and user-reported missed events are stored
+//! as feedback under a per-adapter agent ID, then turned into
+//! `LearnedPatterns` for that adapter's `ChangeDetector`.
+
+use commander_core::{LearnedPatterns, SignificanceObservation};
+
+use crate::error::Result;
+
+use super::types::{Feedback, FeedbackType};
+use super::AutoEval;
+
+/// Agent ID under which an adapter's change detector feedback is stored.
+pub fn change_detector_agent_id(adapter: &str) -> String {
+    format!("change-detector-{}", adapter)
+}
+
+impl AutoEval {
+    /// Record whether LLM analysis agreed a change matched by `pattern` was significant.
+    pub async fn record_change_verdict(
+        &mut self,
+        adapter: &str,
+        pattern: &str,
+        significant: bool,
+        summary: &str,
+    ) -> Result<()> {
+        let feedback_type = if significant {
+            FeedbackType::SignificantChange
+        } else {
+            FeedbackType::TrivialChange
+        };
+        let feedback = Feedback::new(
+            change_detector_agent_id(adapter),
+            feedback_type,
+            pattern,
+            "",
+            summary,
+        );
+        self.store.add(feedback).await
+    }
+
+    /// Record a significant output line the change detector missed.
+    pub async fn record_missed_change(&mut self, adapter: &str, line: &str) -> Result<()> {
+        let feedback = Feedback::new(
+            change_detector_agent_id(adapter),
+            FeedbackType::MissedChange,
+            "Missed significant event",
+            line,
+            "",
+        );
+        self.store.add(feedback).await
+    }
+
+    /// All change detector observations recorded for an adapter.
+    pub fn significance_observations(&self, adapter: &str) -> Vec<SignificanceObservation> {
+        self.store
+            .get_all(&change_detector_agent_id(adapter))
+            .into_iter()
+            .filter_map(|f| match f.feedback_type {
+                FeedbackType::SignificantChange => Some(SignificanceObservation::Confirmed {
+                    pattern: f.context.clone(),
+                }),
+                FeedbackType::TrivialChange => Some(SignificanceObservation::Trivial {
+                    pattern: f.context.clone(),
+                }),
+                FeedbackType::MissedChange => Some(SignificanceObservation::Missed {
+                    line: f.user_input.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Regenerate learned significance patterns for an adapter.
+    pub fn learn_patterns(&self, adapter: &str) -> LearnedPatterns {
+        LearnedPatterns::learn(adapter, &self.significance_observations(adapter))
+    }
+}
@@ -77,6 +77,11 @@ impl FeedbackStore {
             .collect()
     }
 
+    /// All feedback, oldest first.
+    pub fn entries(&self) -> &[Feedback] {
+        &self.entries
+    }
+
     /// Count feedback by type for an agent.
     pub fn count_by_type(&self, agent_id: &str) -> HashMap<FeedbackType, usize> {
         let mut counts = HashMap::new();
@@ -88,6 +93,29 @@ impl FeedbackStore {
         counts
     }
 
+    /// Count feedback recorded by any of `agent_ids`.
+    pub fn count_for_agents(&self, agent_ids: &[String]) -> usize {
+        self.entries
+            .iter()
+            .filter(|f| agent_ids.contains(&f.agent_id))
+            .count()
+    }
+
+    /// Remove all feedback recorded by any of `agent_ids`.
+    ///
+    /// Returns the number of entries removed; the file is rewritten once.
+    pub fn remove_agents(&mut self, agent_ids: &[String]) -> Result<usize> {
+        let before = self.entries.len();
+        self.entries.retain(|f| !agent_ids.contains(&f.agent_id));
+        let removed = before - self.entries.len();
+
+        if removed > 0 {
+            info!(removed, "Removing feedback");
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
     /// Save feedback to disk.
     pub fn save(&self) -> Result<()> {
         let file = self.data_file();
@@ -224,6 +224,29 @@ async fn test_feedback_store_persistence() {
     }
 }
 
+#[tokio::test]
+async fn test_feedback_store_remove_agents() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().to_path_buf();
+    let purged = vec!["agent-1".to_string(), "agent-2".to_string()];
+
+    {
+        let mut store = FeedbackStore::new(path.clone()).unwrap();
+        for agent in ["agent-1", "agent-2", "agent-3"] {
+            store
+                .add(Feedback::new(agent, FeedbackType::Error, "Context", "Input", "Output"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(store.count_for_agents(&purged), 2);
+        assert_eq!(store.remove_agents(&purged).unwrap(), 2);
+    }
+
+    let store = FeedbackStore::new(path).unwrap();
+    assert_eq!(store.count_for_agents(&purged), 0);
+    assert_eq!(store.get_all("agent-3").len(), 1);
+}
+
 #[tokio::test]
 async fn test_auto_eval_process_turn() {
     let temp_dir = TempDir::new().unwrap();
@@ -345,3 +368,99 @@ async fn test_improvement_generator() {
     assert!(!improvements.is_empty());
     assert!(improvements.iter().any(|i| i.category == "clarity"));
 }
+
+#[tokio::test]
+async fn test_improvements_become_proposed_patches() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut feedback, _feedback_dir) = create_test_store();
+    for i in 0..6 {
+        let f = Feedback::new("user-agent", FeedbackType::ImplicitRetry, "Context", format!("Input {}", i), "Output");
+        feedback.add(f).await.unwrap();
+    }
+
+    let store = ImprovementStore::new(temp_dir.path());
+    let generator = ImprovementGenerator::new();
+    let added = store.propose_from_feedback(&generator, &feedback, &EvalTarget::User).await.unwrap();
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0].status, PatchStatus::Proposed);
+
+    // The same suggestion is not proposed twice
+    let again = store.propose_from_feedback(&generator, &feedback, &EvalTarget::User).await.unwrap();
+    assert!(again.is_empty());
+
+    // Session Agent feedback goes to adapter templates
+    let session = EvalTarget::Session(crate::template::AdapterType::ClaudeCode);
+    assert!(store.propose_from_feedback(&generator, &feedback, &session).await.unwrap().is_empty());
+}
+
+#[test]
+fn test_apply_reject_and_rollback_patches() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ImprovementStore::new(temp_dir.path());
+    let target = EvalTarget::Session(crate::template::AdapterType::Generic);
+    let improvement = |change: &str| Improvement {
+        category: "clarity".into(),
+        current_behavior: "Users retry".into(),
+        suggested_change: change.into(),
+        supporting_feedback: vec![],
+        confidence: 0.6,
+    };
+    let patches = store
+        .propose(&target, vec![improvement("Ask before guessing"), improvement("Quote the error"), improvement("Be vague")])
+        .unwrap();
+    assert!(store.active_prompt(&target).is_none());
+
+    let first = store.apply(&patches[0].id[..4]).unwrap();
+    assert_eq!(first.version, Some(1));
+    let prompt = store.active_prompt(&target).unwrap();
+    assert!(prompt.starts_with(&patches::builtin_prompt(&target).unwrap()));
+    assert!(prompt.ends_with(&format!("{}\n- Ask before guessing", patches::LEARNED_SECTION)));
+
+    store.apply(&patches[1].id).unwrap();
+    let prompt = store.active_prompt(&target).unwrap();
+    assert!(prompt.ends_with("- Ask before guessing\n- Quote the error"));
+    assert!(store.apply(&patches[1].id).is_err());
+
+    assert_eq!(store.reject(&patches[2].id).unwrap().status, PatchStatus::Rejected);
+    assert!(store.apply(&patches[2].id).is_err());
+
+    // Roll back the second patch, then back to the built-in prompt
+    assert_eq!(store.rollback(&target, None).unwrap(), 1);
+    assert!(!store.active_prompt(&target).unwrap().contains("Quote the error"));
+    let statuses: Vec<_> = store.patches(Some(&target)).unwrap().iter().map(|p| p.status).collect();
+    assert_eq!(statuses, [PatchStatus::Applied, PatchStatus::RolledBack, PatchStatus::Rejected]);
+    assert_eq!(store.rollback(&target, Some(0)).unwrap(), 0);
+    assert!(store.active_prompt(&target).is_none());
+    assert!(store.rollback(&target, None).is_err());
+    assert_eq!(store.history(&target).unwrap().versions.len(), 2);
+
+    // The registry picks up the active version
+    store.rollback(&target, Some(2)).unwrap();
+    let registry = crate::template::TemplateRegistry::new().with_applied_prompts(&store);
+    let template = registry.get(&crate::template::AdapterType::Generic).unwrap();
+    assert!(template.system_prompt.contains("Quote the error"));
+}
+
+#[tokio::test]
+async fn test_significance_feedback_learns_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut eval = AutoEval::new(temp_dir.path().to_path_buf()).unwrap();
+
+    for _ in 0..4 {
+        eval.record_change_verdict("mpm", "noisy", false, "spinner text").await.unwrap();
+    }
+    eval.record_change_verdict("claude_code", "noisy", true, "real error").await.unwrap();
+    eval.record_missed_change("mpm", "Agent handoff 3 complete").await.unwrap();
+    eval.record_missed_change("mpm", "Agent handoff 4 complete").await.unwrap();
+
+    assert_eq!(eval.significance_observations("mpm").len(), 6);
+    assert_eq!(eval.significance_observations("claude_code").len(), 1);
+
+    let learned = eval.learn_patterns("mpm");
+    assert!(learned.weights["noisy"] < 0.5);
+    assert_eq!(learned.significant.len(), 1);
+
+    // Change detector feedback stays out of agent summaries
+    assert_eq!(eval.summary("user-agent").total, 0);
+    assert_eq!(FeedbackType::MissedChange.to_string(), "missed_change");
+}
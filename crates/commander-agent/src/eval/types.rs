@@ -70,6 +70,12 @@ pub enum FeedbackType {
     Correction,
     /// Positive feedback (for balance).
     Positive,
+    /// LLM analysis confirmed a change the detector flagged as significant.
+    SignificantChange,
+    /// LLM analysis found a change the detector flagged as significant trivial.
+    TrivialChange,
+    /// User reported a significant event the detector missed.
+    MissedChange,
 }
 
 impl std::fmt::Display for FeedbackType {
@@ -81,6 +87,9 @@ impl std::fmt::Display for FeedbackType {
             Self::Timeout => write!(f, "timeout"),
             Self::Correction => write!(f, "correction"),
             Self::Positive => write!(f, "positive"),
+            Self::SignificantChange => write!(f, "significant_change"),
+            Self::TrivialChange => write!(f, "trivial_change"),
+            Self::MissedChange => write!(f, "missed_change"),
         }
     }
 }
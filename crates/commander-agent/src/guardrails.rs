@@ -0,0 +1,415 @@
+//! Command guardrails for autonomous execution.
+//!
+//! Guardrails check commands that an agent proposes to run (either directly
+//! via tool calls or by delegating a task to a session) against configurable
+//! allow/deny regex lists. A matching rule can:
+//!
+//! - **Allow** the command outright (overrides approval rules)
+//! - **Require approval** before the command is sent
+//! - **Deny** the command entirely
+//!
+//! Deny rules always take precedence, followed by allow rules, then
+//! approval rules. Commands that match no rule are allowed.
+//!
+//! # Configuration
+//!
+//! Rules are loaded from `~/.ai-commander/config/guardrails.yaml` when present,
+//! otherwise the built-in defaults are used:
+//!
+//! ```yaml
+//! rules:
+//!   - pattern: 'rm\s+-rf\s+/'
+//!     action: deny
+//!     reason: Recursive delete of root paths
+//!   - pattern: 'git\s+push\s+.*--force'
+//!     action: require_approval
+//!     reason: Force push rewrites remote history
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AgentError, Result};
+use crate::tool::ToolCall;
+
+/// Guardrails configuration file name (under the config directory).
+pub const GUARDRAILS_FILE: &str = "guardrails.yaml";
+
+/// Tool call arguments that may carry a command to be executed.
+const COMMAND_ARGS: &[&str] = &["command", "task", "message", "input"];
+
+/// Action taken when a guardrail rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardrailAction {
+    /// Allow the command without asking.
+    Allow,
+    /// Pause and ask the user before running the command.
+    RequireApproval,
+    /// Never run the command.
+    Deny,
+}
+
+/// A single guardrail rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuardrailRule {
+    /// Regex matched against the command text.
+    pub pattern: String,
+    /// Action to take when the pattern matches.
+    pub action: GuardrailAction,
+    /// Human-readable explanation shown to the user.
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl GuardrailRule {
+    /// Create a new rule.
+    pub fn new(pattern: impl Into<String>, action: GuardrailAction, reason: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            action,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// On-disk guardrails configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuardrailsConfig {
+    /// Rules to evaluate.
+    #[serde(default)]
+    pub rules: Vec<GuardrailRule>,
+}
+
+/// Outcome of checking a command against the guardrails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardrailDecision {
+    /// The command may run.
+    Allowed,
+    /// The command needs explicit user approval.
+    NeedsApproval {
+        /// The command that triggered the rule.
+        command: String,
+        /// Why approval is required.
+        reason: String,
+    },
+    /// The command must not run.
+    Denied {
+        /// The command that triggered the rule.
+        command: String,
+        /// Why the command was denied.
+        reason: String,
+    },
+}
+
+impl GuardrailDecision {
+    /// Check if the command may run without user involvement.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allowed)
+    }
+}
+
+/// A command awaiting user approval.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// Tool that proposed the command.
+    pub tool_name: String,
+    /// The command awaiting approval.
+    pub command: String,
+    /// Why approval is required.
+    pub reason: String,
+}
+
+/// Compiled guardrail rule set.
+#[derive(Debug, Clone)]
+pub struct Guardrails {
+    rules: Vec<(Regex, GuardrailRule)>,
+}
+
+impl Default for Guardrails {
+    fn default() -> Self {
+        Self::from_rules(default_rules()).expect("built-in guardrail patterns are valid")
+    }
+}
+
+impl Guardrails {
+    /// Create guardrails with no rules (everything is allowed).
+    pub fn permissive() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Compile guardrails from a list of rules.
+    pub fn from_rules(rules: Vec<GuardrailRule>) -> Result<Self> {
+        let compiled = rules
+            .into_iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|re| (re, rule.clone()))
+                    .map_err(|e| {
+                        AgentError::Configuration(format!(
+                            "invalid guardrail pattern '{}': {}",
+                            rule.pattern, e
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { rules: compiled })
+    }
+
+    /// Load guardrails from a YAML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            AgentError::Configuration(format!("failed to read {}: {}", path.display(), e))
+        })?;
+
+        let config: GuardrailsConfig = serde_yaml::from_str(&content).map_err(|e| {
+            AgentError::Configuration(format!("failed to parse YAML {}: {}", path.display(), e))
+        })?;
+
+        Self::from_rules(config.rules)
+    }
+
+    /// Load guardrails from the config directory, falling back to defaults.
+    pub fn load_or_default() -> Self {
+        let path = default_path();
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::load(&path) {
+            Ok(guardrails) => guardrails,
+            Err(e) => {
+                tracing::warn!(error = %e, "Invalid guardrails config, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Get the configured rules.
+    pub fn rules(&self) -> impl Iterator<Item = &GuardrailRule> {
+        self.rules.iter().map(|(_, rule)| rule)
+    }
+
+    /// Check a command against the rules.
+    pub fn check(&self, command: &str) -> GuardrailDecision {
+        let first_match = |action: GuardrailAction| {
+            self.rules
+                .iter()
+                .find(|(re, rule)| rule.action == action && re.is_match(command))
+                .map(|(_, rule)| rule)
+        };
+
+        if let Some(rule) = first_match(GuardrailAction::Deny) {
+            return GuardrailDecision::Denied {
+                command: command.to_string(),
+                reason: rule.reason.clone(),
+            };
+        }
+
+        if first_match(GuardrailAction::Allow).is_some() {
+            return GuardrailDecision::Allowed;
+        }
+
+        if let Some(rule) = first_match(GuardrailAction::RequireApproval) {
+            return GuardrailDecision::NeedsApproval {
+                command: command.to_string(),
+                reason: rule.reason.clone(),
+            };
+        }
+
+        GuardrailDecision::Allowed
+    }
+
+    /// Check every command-bearing argument of a tool call.
+    ///
+    /// Returns the most restrictive decision across all arguments.
+    pub fn check_tool_call(&self, call: &ToolCall) -> GuardrailDecision {
+        let mut decision = GuardrailDecision::Allowed;
+
+        for arg in COMMAND_ARGS {
+            let Some(command) = call.get_optional_string_arg(arg) else {
+                continue;
+            };
+
+            match self.check(command) {
+                denied @ GuardrailDecision::Denied { .. } => return denied,
+                approval @ GuardrailDecision::NeedsApproval { .. } => {
+                    if decision.is_allowed() {
+                        decision = approval;
+                    }
+                }
+                GuardrailDecision::Allowed => {}
+            }
+        }
+
+        decision
+    }
+}
+
+/// Default guardrails config file path.
+pub fn default_path() -> PathBuf {
+    commander_core::config::config_dir().join(GUARDRAILS_FILE)
+}
+
+/// Built-in rules applied when no config file exists.
+pub fn default_rules() -> Vec<GuardrailRule> {
+    vec![
+        GuardrailRule::new(
+            r"rm\s+(-[a-zA-Z]*r[a-zA-Z]*f|-[a-zA-Z]*f[a-zA-Z]*r)[a-zA-Z]*\s+(/|~|\*|\$HOME)(\s|$)",
+            GuardrailAction::Deny,
+            "Recursive delete of root, home, or wildcard paths",
+        ),
+        GuardrailRule::new(r"\bmkfs(\.\w+)?\b", GuardrailAction::Deny, "Filesystem formatting"),
+        GuardrailRule::new(
+            r"\bdd\s+.*of=/dev/",
+            GuardrailAction::Deny,
+            "Raw writes to block devices",
+        ),
+        GuardrailRule::new(
+            r":\(\)\s*\{\s*:\|:&\s*\};:",
+            GuardrailAction::Deny,
+            "Fork bomb",
+        ),
+        GuardrailRule::new(
+            r"rm\s+-[a-zA-Z]*[rR]",
+            GuardrailAction::RequireApproval,
+            "Recursive delete",
+        ),
+        GuardrailRule::new(
+            r"git\s+push\s+.*(--force\b|-f\b)",
+            GuardrailAction::RequireApproval,
+            "Force push rewrites remote history",
+        ),
+        GuardrailRule::new(
+            r"git\s+reset\s+--hard",
+            GuardrailAction::RequireApproval,
+            "Hard reset discards uncommitted work",
+        ),
+        GuardrailRule::new(
+            r"git\s+clean\s+-[a-zA-Z]*f",
+            GuardrailAction::RequireApproval,
+            "git clean deletes untracked files",
+        ),
+        GuardrailRule::new(
+            r"(?i)\bdrop\s+(table|database)\b",
+            GuardrailAction::RequireApproval,
+            "Destructive database operation",
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_denies_rm_rf_root() {
+        let guardrails = Guardrails::default();
+        assert!(matches!(
+            guardrails.check("rm -rf /"),
+            GuardrailDecision::Denied { .. }
+        ));
+        assert!(matches!(
+            guardrails.check("sudo rm -fr ~"),
+            GuardrailDecision::Denied { .. }
+        ));
+    }
+
+    #[test]
+    fn test_default_requires_approval_for_force_push() {
+        let guardrails = Guardrails::default();
+        match guardrails.check("git push origin main --force") {
+            GuardrailDecision::NeedsApproval { command, reason } => {
+                assert_eq!(command, "git push origin main --force");
+                assert!(reason.contains("Force push"));
+            }
+            other => panic!("Expected NeedsApproval, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_allows_safe_commands() {
+        let guardrails = Guardrails::default();
+        assert!(guardrails.check("cargo test").is_allowed());
+        assert!(guardrails.check("git push origin main").is_allowed());
+        assert!(guardrails.check("rm build.log").is_allowed());
+    }
+
+    #[test]
+    fn test_deny_beats_allow() {
+        let guardrails = Guardrails::from_rules(vec![
+            GuardrailRule::new("rm", GuardrailAction::Allow, ""),
+            GuardrailRule::new(r"rm\s+-rf", GuardrailAction::Deny, "no"),
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            guardrails.check("rm -rf target"),
+            GuardrailDecision::Denied { .. }
+        ));
+    }
+
+    #[test]
+    fn test_allow_overrides_approval() {
+        let guardrails = Guardrails::from_rules(vec![
+            GuardrailRule::new(r"rm\s+-r", GuardrailAction::RequireApproval, "recursive"),
+            GuardrailRule::new(r"rm\s+-rf\s+target\b", GuardrailAction::Allow, ""),
+        ])
+        .unwrap();
+
+        assert!(guardrails.check("rm -rf target").is_allowed());
+        assert!(!guardrails.check("rm -rf src").is_allowed());
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_configuration_error() {
+        let result = Guardrails::from_rules(vec![GuardrailRule::new(
+            "(unclosed",
+            GuardrailAction::Deny,
+            "",
+        )]);
+        assert!(matches!(result, Err(AgentError::Configuration(_))));
+    }
+
+    #[test]
+    fn test_check_tool_call_inspects_task_argument() {
+        let guardrails = Guardrails::default();
+        let call = ToolCall::new(
+            "delegate_to_session",
+            json!({"session_id": "proj", "task": "git reset --hard HEAD~3"}),
+        );
+
+        assert!(matches!(
+            guardrails.check_tool_call(&call),
+            GuardrailDecision::NeedsApproval { .. }
+        ));
+
+        let call = ToolCall::new("search_memories", json!({"query": "rm -rf /"}));
+        assert!(guardrails.check_tool_call(&call).is_allowed());
+    }
+
+    #[test]
+    fn test_load_yaml_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(GUARDRAILS_FILE);
+        fs::write(
+            &path,
+            "rules:\n  - pattern: 'npm publish'\n    action: require_approval\n    reason: Publishing\n",
+        )
+        .unwrap();
+
+        let guardrails = Guardrails::load(&path).unwrap();
+        assert_eq!(guardrails.rules().count(), 1);
+        assert!(matches!(
+            guardrails.check("npm publish --access public"),
+            GuardrailDecision::NeedsApproval { .. }
+        ));
+        // Built-in rules are not merged into a custom config
+        assert!(guardrails.check("git push --force").is_allowed());
+    }
+}
@@ -0,0 +1,97 @@
+//! Conversation history imported from other tools.
+//!
+//! `ai-commander import-history` converts a project's Claude Code
+//! transcripts into memories of the project's Session Agent. Imported
+//! memories are tagged with their source, session and message, so a re-run
+//! skips what is already there, and keep the time of the original exchange.
+//! A new Session Agent reads the most recent ones into its context before
+//! its first message, instead of starting cold.
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use commander_memory::Memory;
+
+/// Source of memories imported from Claude Code.
+pub const CLAUDE_CODE_SOURCE: &str = "claude-code";
+
+/// Imported memories put into a Session Agent's context.
+pub const PRIOR_CONTEXT_MEMORIES: usize = 10;
+
+// Provenance metadata keys
+const SOURCE_KEY: &str = "source";
+const SOURCE_SESSION_KEY: &str = "source_session";
+const SOURCE_MESSAGE_KEY: &str = "source_message";
+
+/// Memory of `agent_id` imported from `source`, written at `at`.
+pub fn imported_memory(
+    agent_id: &str,
+    content: &str,
+    embedding: Vec<f32>,
+    source: &str,
+    session: &str,
+    message: &str,
+    at: DateTime<Utc>,
+) -> Memory {
+    let mut memory = Memory::new(agent_id, content, embedding)
+        .with_metadata(SOURCE_KEY, json!(source))
+        .with_metadata(SOURCE_SESSION_KEY, json!(session))
+        .with_metadata(SOURCE_MESSAGE_KEY, json!(message));
+    memory.created_at = at;
+    memory
+}
+
+/// Message an imported memory was made from, if it was imported.
+pub fn imported_message(memory: &Memory) -> Option<&str> {
+    memory.get_metadata(SOURCE_KEY)?;
+    memory.get_metadata(SOURCE_MESSAGE_KEY)?.as_str()
+}
+
+/// Context note listing the most recent imported memories, oldest first.
+pub fn prior_context(memories: &[Memory]) -> Option<String> {
+    let mut imported: Vec<&Memory> = memories
+        .iter()
+        .filter(|m| imported_message(m).is_some())
+        .collect();
+    if imported.is_empty() {
+        return None;
+    }
+    imported.sort_by_key(|m| m.created_at);
+    let recent = &imported[imported.len().saturating_sub(PRIOR_CONTEXT_MEMORIES)..];
+    let lines: Vec<String> = recent
+        .iter()
+        .map(|m| format!("- [{}] {}", m.created_at.format("%Y-%m-%d"), m.content))
+        .collect();
+    Some(format!(
+        "Earlier work on this project (imported history):\n{}",
+        lines.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_prior_context_lists_recent_imports_in_order() {
+        let at = |day| Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        let mut memories = vec![Memory::new("a", "not imported", vec![0.0])];
+        for day in (1..=12).rev() {
+            let content = format!("exchange {}", day);
+            let message = format!("m{}", day);
+            memories.push(imported_memory("a", &content, vec![0.0], CLAUDE_CODE_SOURCE, "s", &message, at(day)));
+        }
+
+        let context = prior_context(&memories).unwrap();
+        let lines: Vec<&str> = context.lines().skip(1).collect();
+        assert_eq!(lines.len(), PRIOR_CONTEXT_MEMORIES);
+        assert_eq!(lines[0], "- [2026-03-03] exchange 3");
+        assert_eq!(lines[9], "- [2026-03-12] exchange 12");
+        assert!(!context.contains("not imported"));
+
+        assert_eq!(imported_message(&memories[1]), Some("m12"));
+        assert_eq!(imported_message(&memories[0]), None);
+        assert!(prior_context(&memories[..1]).is_none());
+    }
+}
@@ -0,0 +1,206 @@
+//! Project knowledge shared across sessions.
+//!
+//! Session Agent memories are isolated. When a session learns something that
+//! holds for the whole project ("this repo uses pnpm, not npm"), the agent
+//! promotes it with the `promote_memory` tool: the memory is copied into the
+//! project's knowledge namespace (`project-knowledge-<project>`), where the
+//! User Agent and every later session on the project can find it.
+//!
+//! Promoted entries carry provenance metadata (source agent, session and
+//! memory, reason, time) and stay unreviewed until the user keeps or drops
+//! them from the review list.
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use commander_memory::{Memory, MemoryStore};
+
+use crate::error::{AgentError, Result};
+
+/// Name of the tool Session Agents use to promote a memory.
+pub const PROMOTE_MEMORY_TOOL: &str = "promote_memory";
+
+/// Prefix of project knowledge agent namespaces.
+pub const KNOWLEDGE_AGENT_PREFIX: &str = "project-knowledge-";
+
+/// Maximum entries read when listing a project's knowledge.
+const MAX_KNOWLEDGE_ENTRIES: usize = 500;
+
+// Provenance metadata keys
+const PROMOTED_FROM_KEY: &str = "promoted_from";
+const SOURCE_SESSION_KEY: &str = "source_session";
+const SOURCE_MEMORY_KEY: &str = "source_memory_id";
+const REASON_KEY: &str = "reason";
+const PROMOTED_AT_KEY: &str = "promoted_at";
+const REVIEWED_KEY: &str = "reviewed";
+
+/// Agent namespace holding the shared knowledge of `project`.
+pub fn knowledge_agent_id(project: &str) -> String {
+    format!("{}{}", KNOWLEDGE_AGENT_PREFIX, project)
+}
+
+/// Agents whose memories belong to `project`: the session agents of its
+/// tmux sessions (`commander-<project>` and `<project>`) and its knowledge
+/// namespace.
+pub fn project_agent_ids(project: &str) -> Vec<String> {
+    vec![
+        format!("session-agent-commander-{}", project),
+        format!("session-agent-{}", project),
+        knowledge_agent_id(project),
+    ]
+}
+
+/// Copy `memory` into the knowledge namespace of `project`, recording where
+/// it came from. The copy gets a new ID and starts unreviewed.
+pub fn promote(memory: &Memory, project: &str, session_id: &str, reason: Option<&str>) -> Memory {
+    let mut promoted = Memory::new(
+        knowledge_agent_id(project),
+        memory.content.clone(),
+        memory.embedding.clone(),
+    )
+    .with_metadata(PROMOTED_FROM_KEY, json!(memory.agent_id))
+    .with_metadata(SOURCE_SESSION_KEY, json!(session_id))
+    .with_metadata(SOURCE_MEMORY_KEY, json!(memory.id))
+    .with_metadata(PROMOTED_AT_KEY, json!(Utc::now().to_rfc3339()))
+    .with_metadata(REVIEWED_KEY, json!(false));
+    if let Some(reason) = reason.filter(|r| !r.trim().is_empty()) {
+        promoted = promoted.with_metadata(REASON_KEY, json!(reason));
+    }
+    promoted
+}
+
+/// Whether `memory` is promoted project knowledge.
+pub fn is_knowledge(memory: &Memory) -> bool {
+    memory.agent_id.starts_with(KNOWLEDGE_AGENT_PREFIX)
+}
+
+/// A promoted memory with its provenance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnowledgeEntry {
+    /// Memory ID in the knowledge namespace.
+    pub id: String,
+    /// Project the knowledge belongs to.
+    pub project: String,
+    /// The knowledge itself.
+    pub content: String,
+    /// Agent that promoted it.
+    pub source_agent: String,
+    /// Session it was learned in.
+    pub source_session: String,
+    /// ID of the original session memory.
+    pub source_memory_id: String,
+    /// Why the agent promoted it.
+    pub reason: Option<String>,
+    /// When it was promoted.
+    pub promoted_at: DateTime<Utc>,
+    /// Whether the user kept it from the review list.
+    pub reviewed: bool,
+}
+
+impl KnowledgeEntry {
+    /// Read a knowledge entry from a memory in a knowledge namespace.
+    pub fn from_memory(memory: &Memory) -> Option<Self> {
+        let project = memory.agent_id.strip_prefix(KNOWLEDGE_AGENT_PREFIX)?;
+        let text = |key: &str| {
+            memory
+                .get_metadata(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+
+        Some(Self {
+            id: memory.id.clone(),
+            project: project.to_string(),
+            content: memory.content.clone(),
+            source_agent: text(PROMOTED_FROM_KEY).unwrap_or_default(),
+            source_session: text(SOURCE_SESSION_KEY).unwrap_or_default(),
+            source_memory_id: text(SOURCE_MEMORY_KEY).unwrap_or_default(),
+            reason: text(REASON_KEY),
+            promoted_at: text(PROMOTED_AT_KEY)
+                .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or(memory.created_at),
+            reviewed: memory
+                .get_metadata(REVIEWED_KEY)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        })
+    }
+}
+
+/// List the knowledge of `project`, unreviewed entries first, newest first.
+pub async fn list_knowledge(store: &dyn MemoryStore, project: &str) -> Result<Vec<KnowledgeEntry>> {
+    let memories = store
+        .list(&knowledge_agent_id(project), MAX_KNOWLEDGE_ENTRIES)
+        .await
+        .map_err(AgentError::Memory)?;
+    let mut entries: Vec<KnowledgeEntry> = memories.iter().filter_map(KnowledgeEntry::from_memory).collect();
+    entries.sort_by(|a, b| a.reviewed.cmp(&b.reviewed).then(b.promoted_at.cmp(&a.promoted_at)));
+    Ok(entries)
+}
+
+/// Keep (`true`) or drop (`false`) the knowledge entry `id` after review.
+///
+/// Returns `false` if `id` is not project knowledge.
+pub async fn review_knowledge(store: &dyn MemoryStore, id: &str, keep: bool) -> Result<bool> {
+    let Some(memory) = store.get(id).await.map_err(AgentError::Memory)? else {
+        return Ok(false);
+    };
+    if !is_knowledge(&memory) {
+        return Ok(false);
+    }
+
+    if keep {
+        let memory = memory.with_metadata(REVIEWED_KEY, json!(true));
+        store.store(memory).await.map_err(AgentError::Memory)?;
+    } else {
+        store.delete(id).await.map_err(AgentError::Memory)?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_memory::LocalStore;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_promote_records_provenance() {
+        let memory = Memory::new("session-agent-web", "This repo uses pnpm, not npm", vec![0.1; 8]);
+        let promoted = promote(&memory, "web", "web", Some("npm install failed"));
+
+        assert_eq!(promoted.agent_id, "project-knowledge-web");
+        assert_ne!(promoted.id, memory.id);
+        assert!(is_knowledge(&promoted));
+
+        let entry = KnowledgeEntry::from_memory(&promoted).unwrap();
+        assert_eq!(entry.project, "web");
+        assert_eq!(entry.source_agent, "session-agent-web");
+        assert_eq!(entry.source_memory_id, memory.id);
+        assert_eq!(entry.reason.as_deref(), Some("npm install failed"));
+        assert!(!entry.reviewed);
+
+        assert!(KnowledgeEntry::from_memory(&memory).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_review_knowledge() {
+        let dir = tempdir().unwrap();
+        let store = LocalStore::new(dir.path().to_path_buf()).await.unwrap();
+        let source = Memory::new("session-agent-web", "Run tests with `just test`", vec![0.1; 8]);
+        let kept = promote(&source, "web", "web", None);
+        let dropped = promote(&source, "web", "web", None);
+        store.store(kept.clone()).await.unwrap();
+        store.store(dropped.clone()).await.unwrap();
+
+        assert!(review_knowledge(&store, &kept.id, true).await.unwrap());
+        assert!(review_knowledge(&store, &dropped.id, false).await.unwrap());
+        assert!(!review_knowledge(&store, "missing", true).await.unwrap());
+
+        let entries = list_knowledge(&store, "web").await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, kept.id);
+        assert!(entries[0].reviewed);
+    }
+}
@@ -25,6 +25,15 @@
 //! - [`ToolCall`]: Request to execute a tool
 //! - [`ToolResult`]: Result of tool execution
 //! - [`ModelConfig`]: LLM configuration (model, temperature, etc.)
+//! - [`Tokenizer`]: Model-specific token counting for context accounting
+//! - [`Planner`]: Breaks large goals into a [`Plan`] of dependent tasks
+//! - [`KnowledgeEntry`]: Session memory promoted to shared project knowledge
+//! - [`ModelRouter`]: Picks a model tier per request by task complexity
+//! - [`ToolClass`]: What a tool does, checked against an agent's permission profile
+//! - [`ModelCatalog`]: OpenRouter models with pricing, used to validate model IDs
+//! - [`TaskBudget`]: Turn, token and time limits on a delegated task
+//! - [`Reviewer`]: Second agent that critiques drafts before they reach a session
+//! - [`AgentMessage`]: Typed message between agents, carried by the orchestrator's bus
 //!
 //! # Example
 //!
@@ -59,6 +68,8 @@
 //! ```
 
 pub mod agent;
+pub mod budget;
+pub mod catalog;
 pub mod client;
 pub mod compaction;
 pub mod completion_driver;
@@ -67,26 +78,56 @@ pub mod context;
 pub mod context_manager;
 pub mod error;
 pub mod eval;
+pub mod guardrails;
+pub mod history;
+pub mod knowledge;
+pub mod messaging;
+pub mod permissions;
+pub mod planner;
 pub mod response;
+pub mod review;
+pub mod router;
 pub mod session_agent;
+pub mod structured;
 pub mod template;
+pub mod tokenizer;
 pub mod tool;
 pub mod user_agent;
 
 // Re-export commonly used items
 pub use agent::{Agent, AgentType};
-pub use client::OpenRouterClient;
+pub use budget::{BudgetLimit, BudgetTracker, BudgetUsage, Spend, TaskBudget};
+pub use catalog::{validate_model, CatalogModel, ModelCatalog, ModelPricing};
+pub use client::{ClientMetrics, KeyRotation, KeyUsage, OpenRouterClient, RateLimits, RetryPolicy};
 pub use compaction::{ContextWindow, LlmSummarizer, SimpleSummarizer, Summarizer};
 pub use completion_driver::{
     AutonomousResult, Blocker, BlockerType, CompletionDriver, ContinueDecision, Goal, GoalStatus,
 };
 pub use config::{ModelConfig, Provider};
-pub use context::{AgentContext, Message, MessageRole};
-pub use context_manager::{ContextAction, ContextManager, ContextStrategy, CriticalAction};
+pub use context::{AgentContext, ContextCheckpoint, Message, MessageRole};
+pub use context_manager::{
+    ContextAction, ContextManager, ContextStrategy, ContextUsage, CriticalAction,
+};
 pub use error::{AgentError, Result};
 pub use eval::{AutoEval, Feedback, FeedbackDetector, FeedbackStore, FeedbackSummary, FeedbackType};
+pub use guardrails::{
+    GuardrailAction, GuardrailDecision, GuardrailRule, Guardrails, GuardrailsConfig,
+    PendingApproval,
+};
+pub use history::CLAUDE_CODE_SOURCE;
+pub use knowledge::{
+    knowledge_agent_id, list_knowledge, project_agent_ids, review_knowledge, KnowledgeEntry,
+    KNOWLEDGE_AGENT_PREFIX, PROMOTE_MEMORY_TOOL,
+};
+pub use messaging::{AgentMessage, MessageKind, SEND_AGENT_MESSAGE_TOOL};
+pub use permissions::{PermissionDecision, ToolClass};
+pub use planner::{Plan, PlanTask, Planner, PLAN_TOOL};
 pub use response::AgentResponse;
-pub use session_agent::{OutputAnalysis, SessionAgent, SessionState};
+pub use review::{Critique, IssueKind, ReviewIssue, Reviewer};
+pub use router::{classify, Complexity, ModelRouter, RoutingDecision, RoutingMethod};
+pub use session_agent::{ChangeVerdict, OutputAnalysis, SessionAgent, SessionState, TaskCheckpoint};
+pub use structured::{result_schema, AgentResult, ResultStatus};
+pub use tokenizer::{count_tokens, Tokenizer, TokenizerKind};
 pub use tool::{ToolCall, ToolDefinition, ToolResult};
 pub use user_agent::UserAgent;
 
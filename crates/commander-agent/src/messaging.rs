@@ -0,0 +1,143 @@
+//! Typed messages agents exchange over the orchestrator's message bus.
+//!
+//! A Session Agent sends one with the [`SEND_AGENT_MESSAGE_TOOL`] tool, or the
+//! orchestrator on its behalf; messages delivered to an agent are shown to it
+//! on its next analysis or request, then cleared.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::tool::ToolDefinition;
+
+/// Tool Session Agents use to message other agents.
+pub const SEND_AGENT_MESSAGE_TOOL: &str = "send_agent_message";
+
+/// What a message is about; agents subscribe by kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    /// Progress or state of the sender's session.
+    Status,
+    /// The sender is stuck and asks for help.
+    HelpRequest,
+    /// Something other sessions should know, e.g. a changed API contract.
+    KnowledgeShare,
+}
+
+impl MessageKind {
+    /// Every kind, for subscribing to all of them.
+    pub const ALL: [MessageKind; 3] = [Self::Status, Self::HelpRequest, Self::KnowledgeShare];
+
+    /// Wire name, as used by the tool.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::HelpRequest => "help_request",
+            Self::KnowledgeShare => "knowledge_share",
+        }
+    }
+}
+
+impl std::str::FromStr for MessageKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "status" => Ok(Self::Status),
+            "help_request" | "help" => Ok(Self::HelpRequest),
+            "knowledge_share" | "knowledge" => Ok(Self::KnowledgeShare),
+            other => Err(format!(
+                "unknown message kind '{}' (expected status, help_request or knowledge_share)",
+                other
+            )),
+        }
+    }
+}
+
+/// A message from one agent to another, or to every subscriber of its kind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentMessage {
+    pub id: String,
+    /// Session of the sending agent.
+    pub from: String,
+    /// Recipient session; `None` for every subscriber of the kind.
+    pub to: Option<String>,
+    pub kind: MessageKind,
+    pub body: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl AgentMessage {
+    /// A message from `from` to every subscriber of `kind`.
+    pub fn broadcast(from: impl Into<String>, kind: MessageKind, body: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            from: from.into(),
+            to: None,
+            kind,
+            body: body.into(),
+            sent_at: Utc::now(),
+        }
+    }
+
+    /// A message from `from` to the agent of session `to`.
+    pub fn direct(
+        from: impl Into<String>,
+        to: impl Into<String>,
+        kind: MessageKind,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            to: Some(to.into()),
+            ..Self::broadcast(from, kind, body)
+        }
+    }
+}
+
+/// Definition of [`SEND_AGENT_MESSAGE_TOOL`].
+pub fn send_agent_message_tool() -> ToolDefinition {
+    ToolDefinition::new(
+        SEND_AGENT_MESSAGE_TOOL,
+        "Message the agents of other sessions: share knowledge they need (e.g. an API contract \
+         you changed), ask for help, or report status. Omit `to` to reach every session \
+         subscribed to the kind",
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "kind": {
+                    "type": "string",
+                    "enum": ["status", "help_request", "knowledge_share"],
+                    "description": "What the message is about"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "The message"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Session to send it to (default: all subscribers)"
+                }
+            },
+            "required": ["kind", "body"]
+        }),
+    )
+}
+
+/// Messages for an agent's prompt, oldest first.
+pub fn format_inbox(messages: &[AgentMessage]) -> Option<String> {
+    if messages.is_empty() {
+        return None;
+    }
+    let mut context = String::from("Messages from other sessions' agents (act on them if relevant):\n");
+    for message in messages {
+        context.push_str(&format!(
+            "- [{}] from {} at {}: {}\n",
+            message.kind.as_str(),
+            message.from,
+            message.sent_at.format("%H:%M"),
+            message.body
+        ));
+    }
+    Some(context)
+}
@@ -0,0 +1,231 @@
+//! Tool permission profiles for agents.
+//!
+//! Every tool falls in one of three classes:
+//!
+//! - **Read**: searching memories, session status, analysis
+//! - **Record**: updating the agent's own session state and reports
+//! - **Act**: changing a session, e.g. delegating a task to it (and any tool
+//!   not listed here)
+//!
+//! A [`PermissionProfile`] decides what each class may do:
+//!
+//! | Profile      | Read  | Record | Act      |
+//! |--------------|-------|--------|----------|
+//! | `observer`   | allow | deny   | deny     |
+//! | `operator`   | allow | allow  | approval |
+//! | `autonomous` | allow | allow  | allow    |
+//!
+//! Profiles are set per project under `[agents.permissions]` in
+//! `config.toml` and enforced in each agent's `execute_tool`, which fails
+//! with [`AgentError::PermissionDenied`]. Denied tools are not offered to
+//! the model at all. Every refused call is appended to the audit log in
+//! `~/.ai-commander/logs/permissions.jsonl`; delegation held for approval is
+//! queued, and audited, like approval mode's (see [`commander_core::approvals`]).
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use commander_core::PermissionProfile;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::{AgentError, Result};
+use crate::knowledge::PROMOTE_MEMORY_TOOL;
+use crate::messaging::SEND_AGENT_MESSAGE_TOOL;
+use crate::planner::PLAN_TOOL;
+use crate::tool::ToolDefinition;
+
+/// Tools that only read.
+const READ_TOOLS: &[&str] = &[
+    "search_memories",
+    "search_all_memories",
+    "get_session_status",
+    "analyze_output",
+    "parse_output",
+    "detect_completion",
+    "detect_ready",
+    "aggregate_status",
+    "list_agents",
+    PLAN_TOOL,
+];
+
+/// Tools that record the agent's own state or report it.
+const RECORD_TOOLS: &[&str] = &[
+    "update_session_state",
+    "report_to_user",
+    "report_status",
+    "report_output",
+    "track_files",
+    "track_delegation",
+    PROMOTE_MEMORY_TOOL,
+    SEND_AGENT_MESSAGE_TOOL,
+];
+
+/// What a tool does, for permission purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolClass {
+    Read,
+    Record,
+    Act,
+}
+
+impl ToolClass {
+    /// Class of the tool named `tool`; unknown tools act.
+    pub fn of(tool: &str) -> Self {
+        if READ_TOOLS.contains(&tool) {
+            Self::Read
+        } else if RECORD_TOOLS.contains(&tool) {
+            Self::Record
+        } else {
+            Self::Act
+        }
+    }
+}
+
+/// Whether a profile lets an agent use a tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionDecision {
+    Allowed,
+    NeedsApproval,
+    Denied,
+}
+
+/// Decide whether `profile` lets an agent use `tool`.
+pub fn check(profile: PermissionProfile, tool: &str) -> PermissionDecision {
+    match (profile, ToolClass::of(tool)) {
+        (_, ToolClass::Read) | (PermissionProfile::Autonomous, _) => PermissionDecision::Allowed,
+        (PermissionProfile::Operator, ToolClass::Record) => PermissionDecision::Allowed,
+        (PermissionProfile::Operator, ToolClass::Act) => PermissionDecision::NeedsApproval,
+        (PermissionProfile::Observer, _) => PermissionDecision::Denied,
+    }
+}
+
+/// Whether `profile` lets an agent use `tool` at all, with or without approval.
+pub fn is_permitted(profile: PermissionProfile, tool: &str) -> bool {
+    check(profile, tool) != PermissionDecision::Denied
+}
+
+/// Drop the tools `profile` denies.
+pub fn filter_tools(profile: PermissionProfile, tools: &mut Vec<ToolDefinition>) {
+    tools.retain(|tool| is_permitted(profile, &tool.name));
+}
+
+/// Fail with [`AgentError::PermissionDenied`] unless `profile` lets `agent`
+/// run `tool` right away, recording the refusal in the audit log.
+pub(crate) fn enforce(
+    agent: &str,
+    project: Option<&str>,
+    profile: PermissionProfile,
+    tool: &str,
+) -> Result<()> {
+    let decision = check(profile, tool);
+    let reason = match decision {
+        PermissionDecision::Allowed => return Ok(()),
+        PermissionDecision::NeedsApproval => "it requires user approval",
+        PermissionDecision::Denied => match ToolClass::of(tool) {
+            ToolClass::Record => "it records state",
+            _ => "it acts on a session",
+        },
+    };
+
+    warn!(agent = %agent, tool = %tool, profile = %profile, ?decision, "Tool call refused by permission profile");
+    let entry = AuditEntry {
+        at: Utc::now(),
+        agent: agent.to_string(),
+        project: project.map(str::to_string),
+        tool: tool.to_string(),
+        profile,
+        decision,
+    };
+    if let Err(e) = append_audit(&commander_core::config::permissions_audit_file(), &entry) {
+        warn!(error = %e, "Failed to write permission audit log");
+    }
+
+    Err(AgentError::PermissionDenied {
+        agent: agent.to_string(),
+        tool: tool.to_string(),
+        profile: profile.to_string(),
+        reason: reason.to_string(),
+    })
+}
+
+/// One line of the permission audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    pub agent: String,
+    /// Project whose profile applied, if the call was project-scoped.
+    #[serde(default)]
+    pub project: Option<String>,
+    pub tool: String,
+    pub profile: PermissionProfile,
+    pub decision: PermissionDecision,
+}
+
+/// Append `entry` to the audit log at `path`.
+pub fn append_audit(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        use PermissionDecision::*;
+        use PermissionProfile::*;
+
+        for (profile, read, record, act) in [
+            (Observer, Allowed, Denied, Denied),
+            (Operator, Allowed, Allowed, NeedsApproval),
+            (Autonomous, Allowed, Allowed, Allowed),
+        ] {
+            assert_eq!(check(profile, "search_memories"), read, "{}", profile);
+            assert_eq!(check(profile, "track_files"), record, "{}", profile);
+            assert_eq!(check(profile, "delegate_to_session"), act, "{}", profile);
+        }
+        assert_eq!(check(Operator, "unknown_tool"), NeedsApproval);
+    }
+
+    #[test]
+    fn test_filter_tools() {
+        let mut tools: Vec<ToolDefinition> = ["search_memories", "report_to_user", "delegate_to_session"]
+            .into_iter()
+            .map(|name| ToolDefinition::new(name, "", serde_json::json!({})))
+            .collect();
+        filter_tools(PermissionProfile::Operator, &mut tools);
+        assert_eq!(tools.len(), 3);
+        filter_tools(PermissionProfile::Observer, &mut tools);
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["search_memories"]);
+    }
+
+    #[test]
+    fn test_append_audit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("permissions.jsonl");
+        let entry = AuditEntry {
+            at: Utc::now(),
+            agent: "session-agent-web".to_string(),
+            project: Some("web".to_string()),
+            tool: "track_files".to_string(),
+            profile: PermissionProfile::Observer,
+            decision: PermissionDecision::Denied,
+        };
+        append_audit(&path, &entry).unwrap();
+        append_audit(&path, &entry).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<AuditEntry> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines, vec![entry.clone(), entry]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Task decomposition planning.
+//!
+//! The [`Planner`] asks the model to break a large goal ("add OAuth to this
+//! app") into a [`Plan`]: a tree of small tasks connected by dependencies.
+//! Plans are validated (unique IDs, known dependencies, no cycles) before
+//! they are shown to the user, and [`Plan::to_goals`] turns an approved plan
+//! into goals for the [`CompletionDriver`](crate::CompletionDriver).
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::client::{ChatMessage, OpenRouterClient};
+use crate::completion_driver::Goal;
+use crate::config::ModelConfig;
+use crate::error::{AgentError, Result};
+
+/// Name of the User Agent tool that produces a plan.
+pub const PLAN_TOOL: &str = "plan";
+
+/// Maximum number of tasks accepted in a single plan.
+pub const MAX_PLAN_TASKS: usize = 20;
+
+/// System prompt for plan generation.
+const PLANNER_SYSTEM_PROMPT: &str = r#"You are a software project planner. Break the user's goal into small, concrete tasks that a coding agent can complete one at a time.
+
+Respond with JSON only, in this shape:
+{"tasks": [{"id": "1", "title": "Short title", "description": "What to do and how to verify it", "depends_on": []}]}
+
+Rules:
+- Each task must be completable in a single coding session
+- "depends_on" lists the ids of tasks that must finish first
+- Order tasks so that dependencies come before the tasks that need them
+- Use at most 20 tasks"#;
+
+/// A single task in a [`Plan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanTask {
+    /// Plan-local task ID.
+    pub id: String,
+    /// Short title.
+    pub title: String,
+    /// Instructions sent to the coding agent.
+    #[serde(default)]
+    pub description: String,
+    /// IDs of tasks that must complete before this one.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+impl PlanTask {
+    /// Create a task with no dependencies.
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            description: String::new(),
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Set the task instructions.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Add a dependency on another task.
+    pub fn after(mut self, id: impl Into<String>) -> Self {
+        self.depends_on.push(id.into());
+        self
+    }
+
+    /// Goal description used for this task in the completion driver.
+    pub fn goal_description(&self) -> String {
+        format!("{}. {}", self.id, self.title)
+    }
+
+    /// Instructions sent to the coding agent for this task.
+    pub fn instructions(&self) -> String {
+        if self.description.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}\n\n{}", self.title, self.description)
+        }
+    }
+}
+
+/// A goal broken down into dependent tasks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Plan {
+    /// The goal this plan achieves.
+    pub goal: String,
+    /// Tasks in the order the planner listed them.
+    pub tasks: Vec<PlanTask>,
+}
+
+/// Shape of the model's JSON response.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PlanResponse {
+    Object { tasks: Vec<PlanTask> },
+    List(Vec<PlanTask>),
+}
+
+impl Plan {
+    /// Create a validated plan.
+    pub fn new(goal: impl Into<String>, tasks: Vec<PlanTask>) -> Result<Self> {
+        let plan = Self {
+            goal: goal.into(),
+            tasks,
+        };
+        plan.validate()?;
+        Ok(plan)
+    }
+
+    /// Parse a plan from the model's response.
+    ///
+    /// Accepts a `{"tasks": [...]}` object or a bare task array, optionally
+    /// wrapped in a Markdown code fence or surrounding prose.
+    pub fn parse(goal: impl Into<String>, response: &str) -> Result<Self> {
+        let json = extract_json(response)
+            .ok_or_else(|| AgentError::ResponseParse("plan response contains no JSON".into()))?;
+        let tasks = match serde_json::from_str(json)
+            .map_err(|e| AgentError::ResponseParse(format!("invalid plan: {}", e)))?
+        {
+            PlanResponse::Object { tasks } | PlanResponse::List(tasks) => tasks,
+        };
+        Self::new(goal, tasks)
+    }
+
+    /// Check that the plan is non-empty, IDs are unique, dependencies exist
+    /// and there are no dependency cycles.
+    pub fn validate(&self) -> Result<()> {
+        if self.tasks.is_empty() {
+            return Err(AgentError::ResponseParse("plan has no tasks".into()));
+        }
+        if self.tasks.len() > MAX_PLAN_TASKS {
+            return Err(AgentError::ResponseParse(format!(
+                "plan has {} tasks (maximum {})",
+                self.tasks.len(),
+                MAX_PLAN_TASKS
+            )));
+        }
+
+        let mut ids = HashSet::new();
+        for task in &self.tasks {
+            if task.id.trim().is_empty() || task.title.trim().is_empty() {
+                return Err(AgentError::ResponseParse("plan task is missing an id or title".into()));
+            }
+            if !ids.insert(task.id.as_str()) {
+                return Err(AgentError::ResponseParse(format!("duplicate plan task id '{}'", task.id)));
+            }
+        }
+        for task in &self.tasks {
+            if let Some(unknown) = task.depends_on.iter().find(|d| !ids.contains(d.as_str())) {
+                return Err(AgentError::ResponseParse(format!(
+                    "task '{}' depends on unknown task '{}'",
+                    task.id, unknown
+                )));
+            }
+        }
+
+        self.execution_order().map(|_| ())
+    }
+
+    /// Look up a task by ID.
+    pub fn task(&self, id: &str) -> Option<&PlanTask> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+
+    /// Tasks ordered so that every task comes after its dependencies.
+    ///
+    /// Ties keep the planner's listing order.
+    pub fn execution_order(&self) -> Result<Vec<&PlanTask>> {
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut order = Vec::with_capacity(self.tasks.len());
+
+        while order.len() < self.tasks.len() {
+            let next = self.tasks.iter().find(|t| {
+                !done.contains(t.id.as_str()) && t.depends_on.iter().all(|d| done.contains(d.as_str()))
+            });
+            match next {
+                Some(task) => {
+                    done.insert(task.id.as_str());
+                    order.push(task);
+                }
+                None => {
+                    return Err(AgentError::ResponseParse(
+                        "plan has a dependency cycle".into(),
+                    ))
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// One pending goal per task, in execution order.
+    pub fn to_goals(&self) -> Vec<Goal> {
+        self.execution_order()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| Goal::new(t.goal_description()))
+            .collect()
+    }
+
+    /// Render the plan as an indented task tree, one line per task.
+    ///
+    /// Each task is indented by the length of its longest dependency chain.
+    pub fn format_tree(&self) -> Vec<String> {
+        let mut depth: HashMap<&str, usize> = HashMap::new();
+        let mut lines = Vec::with_capacity(self.tasks.len());
+
+        for task in self.execution_order().unwrap_or_default() {
+            let level = task
+                .depends_on
+                .iter()
+                .filter_map(|d| depth.get(d.as_str()))
+                .map(|d| d + 1)
+                .max()
+                .unwrap_or(0);
+            depth.insert(task.id.as_str(), level);
+
+            let after = if task.depends_on.is_empty() {
+                String::new()
+            } else {
+                format!("  (after {})", task.depends_on.join(", "))
+            };
+            lines.push(format!("{}{}{}", "  ".repeat(level), task.goal_description(), after));
+        }
+
+        lines
+    }
+}
+
+/// Slice out the JSON object or array in a model response.
+fn extract_json(response: &str) -> Option<&str> {
+    let start = response.find(['{', '['])?;
+    let close = if response[start..].starts_with('{') { '}' } else { ']' };
+    let end = response.rfind(close)?;
+    (end > start).then(|| &response[start..=end])
+}
+
+/// Generates task plans with the model.
+pub struct Planner {
+    client: OpenRouterClient,
+    config: ModelConfig,
+}
+
+impl Planner {
+    /// Create a planner using the given client and model configuration.
+    pub fn new(client: OpenRouterClient, config: ModelConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// Break `goal` into a plan, with optional project context.
+    pub async fn plan(&self, goal: &str, context: Option<&str>) -> Result<Plan> {
+        let mut prompt = format!("Goal: {}", goal);
+        if let Some(context) = context.filter(|c| !c.trim().is_empty()) {
+            prompt.push_str(&format!("\n\nProject context:\n{}", context));
+        }
+
+        let messages = vec![
+            ChatMessage::system(PLANNER_SYSTEM_PROMPT),
+            ChatMessage::user(prompt),
+        ];
+        let response = self.client.chat(&self.config, messages, None).await?;
+        let content = response
+            .message()
+            .and_then(|m| m.content.clone())
+            .unwrap_or_default();
+
+        let plan = Plan::parse(goal, &content)?;
+        info!(tasks = plan.tasks.len(), "Generated plan");
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_orders_and_renders_tree() {
+        let response = r#"Here is the plan:
+```json
+{"tasks": [
+  {"id": "3", "title": "Add login route", "depends_on": ["1", "2"]},
+  {"id": "1", "title": "Add OAuth dependency"},
+  {"id": "2", "title": "Add provider config", "description": "Read client id from env", "depends_on": ["1"]}
+]}
+```"#;
+        let plan = Plan::parse("Add OAuth", response).unwrap();
+
+        let order: Vec<&str> = plan.execution_order().unwrap().iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(order, ["1", "2", "3"]);
+
+        let goals = plan.to_goals();
+        assert_eq!(goals[0].description, "1. Add OAuth dependency");
+
+        let tree = plan.format_tree();
+        assert_eq!(tree[1], "  2. Add provider config  (after 1)");
+        assert_eq!(tree[2], "    3. Add login route  (after 1, 2)");
+
+        assert_eq!(
+            plan.task("2").unwrap().instructions(),
+            "Add provider config\n\nRead client id from env"
+        );
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_plans() {
+        assert!(Plan::parse("goal", "no json here").is_err());
+        assert!(Plan::new("goal", vec![]).is_err());
+        assert!(Plan::new("goal", vec![PlanTask::new("1", "a"), PlanTask::new("1", "b")]).is_err());
+        assert!(Plan::new("goal", vec![PlanTask::new("1", "a").after("9")]).is_err());
+
+        let cycle = vec![PlanTask::new("1", "a").after("2"), PlanTask::new("2", "b").after("1")];
+        assert!(Plan::new("goal", cycle).is_err());
+
+        let list = r#"[{"id": "a", "title": "Only task"}]"#;
+        assert_eq!(Plan::parse("goal", list).unwrap().tasks.len(), 1);
+    }
+}
@@ -0,0 +1,260 @@
+//! Peer review of User Agent drafts.
+//!
+//! With `[agents.review]` enabled, a second, cheaper agent critiques what the
+//! User Agent is about to send to a session (a plan, or a delegated task)
+//! and flags ambiguity and risky commands. Each critique that flags issues
+//! is fed back for a revision, up to `max_rounds` critiques per draft; the
+//! issues of the last critique are shown to the user with the draft.
+//!
+//! Review is advisory: a reviewer that fails or returns no usable critique
+//! lets the draft through unchanged.
+
+use std::fmt;
+
+use commander_core::ReviewSettings;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::client::{ChatMessage, OpenRouterClient};
+use crate::config::ModelConfig;
+use crate::error::{AgentError, Result};
+use crate::planner::Plan;
+use crate::router::DEFAULT_SIMPLE_MODEL;
+
+/// System prompt of the reviewer.
+const REVIEW_SYSTEM_PROMPT: &str = r#"You review instructions that a coordinating agent is about to send to an autonomous coding agent. Flag only real problems:
+- ambiguity: the coding agent could reasonably do the wrong thing (unclear scope, missing target, undefined terms)
+- risk: destructive or irreversible commands, force pushes, deleting data, touching production, leaking secrets
+- other: anything else that would make the instructions fail
+
+Respond with JSON only:
+{"issues": [{"kind": "ambiguity" | "risk" | "other", "message": "<what is wrong and how to fix it>"}]}
+Use an empty list when the instructions are fine."#;
+
+/// What a review issue is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    Ambiguity,
+    Risk,
+    #[serde(other)]
+    Other,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            IssueKind::Ambiguity => "ambiguity",
+            IssueKind::Risk => "risk",
+            IssueKind::Other => "other",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A problem the reviewer found in a draft.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewIssue {
+    pub kind: IssueKind,
+    pub message: String,
+}
+
+/// The reviewer's verdict on a draft.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Critique {
+    /// Model that wrote the critique.
+    #[serde(default)]
+    pub model: String,
+    pub issues: Vec<ReviewIssue>,
+}
+
+impl Critique {
+    /// Parse the reviewer's response, optionally wrapped in a code fence or
+    /// prose.
+    pub fn parse(response: &str) -> Result<Self> {
+        let json = response
+            .find('{')
+            .zip(response.rfind('}'))
+            .filter(|(start, end)| end > start)
+            .map(|(start, end)| &response[start..=end])
+            .ok_or_else(|| AgentError::ResponseParse("review contains no JSON".into()))?;
+        serde_json::from_str(json).map_err(|e| AgentError::ResponseParse(format!("invalid review: {}", e)))
+    }
+
+    /// Whether the draft may be sent as is.
+    pub fn is_approved(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Whether any issue is a risky command.
+    pub fn has_risk(&self) -> bool {
+        self.issues.iter().any(|issue| issue.kind == IssueKind::Risk)
+    }
+
+    /// One "- [kind] message" line per issue.
+    pub fn issue_lines(&self) -> Vec<String> {
+        self.issues
+            .iter()
+            .map(|issue| format!("- [{}] {}", issue.kind, issue.message))
+            .collect()
+    }
+}
+
+/// Critiques drafts with a second model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reviewer {
+    model: String,
+    max_rounds: u32,
+}
+
+impl Reviewer {
+    /// Create a reviewer using `model` for one critique per draft.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            max_rounds: 1,
+        }
+    }
+
+    /// Create a reviewer from `[agents.review]`, or `None` when review is off.
+    pub fn from_settings(settings: &ReviewSettings) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let model = settings.model.clone().unwrap_or_else(|| DEFAULT_SIMPLE_MODEL.to_string());
+        Some(Self::new(model).with_max_rounds(settings.max_rounds.unwrap_or(1)))
+    }
+
+    /// Critique each draft at most `rounds` times (at least once).
+    pub fn with_max_rounds(mut self, rounds: u32) -> Self {
+        self.max_rounds = rounds.max(1);
+        self
+    }
+
+    /// Reviewer model.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Critiques per draft.
+    pub fn max_rounds(&self) -> u32 {
+        self.max_rounds
+    }
+
+    /// Critique `draft`, described by `kind` ("plan", "task for session api").
+    pub async fn critique(
+        &self,
+        client: &OpenRouterClient,
+        config: &ModelConfig,
+        kind: &str,
+        draft: &str,
+    ) -> Result<Critique> {
+        let config = ModelConfig {
+            model: self.model.clone(),
+            system_prompt: None,
+            ..config.clone()
+        };
+        let messages = vec![
+            ChatMessage::system(REVIEW_SYSTEM_PROMPT),
+            ChatMessage::user(format!("Review this {}:\n\n{}", kind, draft)),
+        ];
+        let response = client.chat(&config, messages, None).await?;
+        let content = response
+            .message()
+            .and_then(|m| m.content.clone())
+            .unwrap_or_default();
+
+        let critique = Critique {
+            model: self.model.clone(),
+            ..Critique::parse(&content)?
+        };
+        info!(kind = %kind, model = %self.model, issues = critique.issues.len(), "Reviewed draft");
+        Ok(critique)
+    }
+}
+
+/// A plan as the reviewer reads it: the goal and every task's instructions.
+pub fn plan_draft(plan: &Plan) -> String {
+    let mut draft = format!("Goal: {}\n", plan.goal);
+    for task in &plan.tasks {
+        draft.push_str(&format!("\nTask {}: {}", task.id, task.instructions()));
+        if !task.depends_on.is_empty() {
+            draft.push_str(&format!(" (after {})", task.depends_on.join(", ")));
+        }
+        draft.push('\n');
+    }
+    draft
+}
+
+/// Context for revising a draft after `critique`.
+pub fn revision_context(context: Option<&str>, draft: &str, critique: &Critique) -> String {
+    let mut revision = String::new();
+    if let Some(context) = context.filter(|c| !c.trim().is_empty()) {
+        revision.push_str(context);
+        revision.push_str("\n\n");
+    }
+    revision.push_str(&format!(
+        "A reviewer flagged these issues in the previous draft; address them:\n{}\n\nPrevious draft:\n{}",
+        critique.issue_lines().join("\n"),
+        draft
+    ));
+    revision
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::PlanTask;
+
+    #[test]
+    fn test_parse_critique() {
+        let response = "```json\n{\"issues\": [\
+            {\"kind\": \"risk\", \"message\": \"Task 2 force-pushes to main\"},\
+            {\"kind\": \"scope\", \"message\": \"Unclear which service\"}]}\n```";
+        let critique = Critique::parse(response).unwrap();
+        assert!(!critique.is_approved());
+        assert!(critique.has_risk());
+        assert_eq!(critique.issues[1].kind, IssueKind::Other);
+        assert_eq!(critique.issue_lines()[0], "- [risk] Task 2 force-pushes to main");
+
+        assert!(Critique::parse("{\"issues\": []}").unwrap().is_approved());
+        assert!(Critique::parse("Looks good to me").is_err());
+    }
+
+    #[test]
+    fn test_reviewer_from_settings() {
+        assert_eq!(Reviewer::from_settings(&ReviewSettings::default()), None);
+
+        let settings = ReviewSettings {
+            enabled: true,
+            model: None,
+            max_rounds: Some(0),
+        };
+        let reviewer = Reviewer::from_settings(&settings).unwrap();
+        assert_eq!(reviewer.model(), DEFAULT_SIMPLE_MODEL);
+        assert_eq!(reviewer.max_rounds(), 1);
+    }
+
+    #[test]
+    fn test_plan_draft_and_revision_context() {
+        let plan = Plan::new(
+            "Rotate API keys",
+            vec![
+                PlanTask::new("1", "Generate new keys"),
+                PlanTask::new("2", "Deploy").with_description("Push to prod").after("1"),
+            ],
+        )
+        .unwrap();
+        let draft = plan_draft(&plan);
+        assert!(draft.starts_with("Goal: Rotate API keys\n"));
+        assert!(draft.contains("Task 2: Deploy\n\nPush to prod (after 1)"));
+
+        let critique = Critique {
+            model: "m".into(),
+            issues: vec![ReviewIssue { kind: IssueKind::Ambiguity, message: "Which keys?".into() }],
+        };
+        let context = revision_context(Some("Rust service"), &draft, &critique);
+        assert!(context.starts_with("Rust service\n\nA reviewer flagged"));
+        assert!(context.contains("- [ambiguity] Which keys?"));
+    }
+}
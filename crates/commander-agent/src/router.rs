@@ -0,0 +1,299 @@
+//! Per-request model routing by task complexity.
+//!
+//! The [`ModelRouter`] classifies each incoming request as
+//! [`Complexity::Simple`] (status summaries, short lookups),
+//! [`Complexity::Standard`] (analysis, ordinary questions) or
+//! [`Complexity::Complex`] (planning, multi-step work) and picks the model of
+//! the matching tier, overriding the agent's `ModelConfig.model` for that
+//! request. Classification uses a keyword heuristic, or a small model when
+//! one is configured (falling back to the heuristic if the call fails).
+//! Tiers come from `[agents.routing]` in `config.toml`.
+
+use std::fmt;
+
+use commander_core::RoutingSettings;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::client::{ChatMessage, OpenRouterClient};
+use crate::config::ModelConfig;
+
+/// Default model for simple requests.
+pub const DEFAULT_SIMPLE_MODEL: &str = "anthropic/claude-haiku-4";
+
+/// Default model for standard requests.
+pub const DEFAULT_STANDARD_MODEL: &str = "anthropic/claude-sonnet-4";
+
+/// Default model for complex requests.
+pub const DEFAULT_COMPLEX_MODEL: &str = "anthropic/claude-opus-4";
+
+/// Requests longer than this (in characters) are treated as complex.
+const COMPLEX_LENGTH: usize = 600;
+
+/// Requests shorter than this (in characters) without analysis or planning
+/// words are treated as simple.
+const SIMPLE_LENGTH: usize = 60;
+
+/// Words that mark planning and multi-step work.
+const COMPLEX_KEYWORDS: &[&str] = &[
+    "plan", "design", "architect", "refactor", "migrate", "implement", "break down", "roadmap",
+    "strategy", "step by step", "multi-step", "end to end", "overhaul",
+];
+
+/// Words that mark status checks and summaries.
+const SIMPLE_KEYWORDS: &[&str] = &[
+    "status", "summary", "summarize", "progress", "list", "show", "what's running", "is it done",
+    "ping",
+];
+
+/// Words that mark analysis, keeping short requests off the simple tier.
+const STANDARD_KEYWORDS: &[&str] = &[
+    "why", "how", "explain", "explanation", "debug", "review", "compare", "fix", "error", "fail",
+];
+
+/// System prompt for the classifier model.
+const CLASSIFIER_PROMPT: &str = "Classify the complexity of the user's request to a coding assistant. \
+Reply with exactly one word: simple (status checks, summaries, short lookups), standard (analysis, \
+debugging, ordinary questions) or complex (planning, design, multi-step implementation).";
+
+/// Complexity tier of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Complexity {
+    /// Status summaries and short lookups.
+    Simple,
+    /// Analysis and ordinary questions.
+    Standard,
+    /// Planning and multi-step work.
+    Complex,
+}
+
+impl Complexity {
+    /// Parse a classifier reply, which should contain one tier name.
+    fn from_reply(reply: &str) -> Option<Self> {
+        let reply = reply.to_lowercase();
+        [Self::Complex, Self::Standard, Self::Simple]
+            .into_iter()
+            .find(|tier| reply.contains(&tier.to_string()))
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Simple => write!(f, "simple"),
+            Self::Standard => write!(f, "standard"),
+            Self::Complex => write!(f, "complex"),
+        }
+    }
+}
+
+/// How a request was classified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingMethod {
+    /// Keyword and length heuristic.
+    Heuristic,
+    /// Small-model classification.
+    Classifier,
+}
+
+impl fmt::Display for RoutingMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Heuristic => write!(f, "heuristic"),
+            Self::Classifier => write!(f, "classifier"),
+        }
+    }
+}
+
+/// Model chosen for a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutingDecision {
+    /// Classified complexity.
+    pub complexity: Complexity,
+    /// Model of the complexity's tier.
+    pub model: String,
+    /// How the complexity was determined.
+    pub method: RoutingMethod,
+}
+
+/// Classify a request with the keyword and length heuristic.
+pub fn classify(message: &str) -> Complexity {
+    let text = message.to_lowercase();
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .collect();
+    // Single keywords match word prefixes ("plan" matches "planning" but not "explanation")
+    let mentions = |keywords: &[&str]| {
+        keywords.iter().any(|keyword| {
+            if keyword.contains([' ', '-']) {
+                text.contains(keyword)
+            } else {
+                words.iter().any(|word| word.starts_with(keyword))
+            }
+        })
+    };
+
+    if text.len() > COMPLEX_LENGTH || mentions(COMPLEX_KEYWORDS) {
+        Complexity::Complex
+    } else if mentions(STANDARD_KEYWORDS) {
+        Complexity::Standard
+    } else if mentions(SIMPLE_KEYWORDS) || text.len() < SIMPLE_LENGTH {
+        Complexity::Simple
+    } else {
+        Complexity::Standard
+    }
+}
+
+/// Picks a model per request from complexity tiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelRouter {
+    simple: String,
+    standard: String,
+    complex: String,
+    classifier_model: Option<String>,
+}
+
+impl Default for ModelRouter {
+    fn default() -> Self {
+        Self {
+            simple: DEFAULT_SIMPLE_MODEL.to_string(),
+            standard: DEFAULT_STANDARD_MODEL.to_string(),
+            complex: DEFAULT_COMPLEX_MODEL.to_string(),
+            classifier_model: None,
+        }
+    }
+}
+
+impl ModelRouter {
+    /// Create a router from `[agents.routing]`, or `None` when routing is off.
+    pub fn from_settings(settings: &RoutingSettings) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let defaults = Self::default();
+        Some(Self {
+            simple: settings.simple.clone().unwrap_or(defaults.simple),
+            standard: settings.standard.clone().unwrap_or(defaults.standard),
+            complex: settings.complex.clone().unwrap_or(defaults.complex),
+            classifier_model: settings.classifier_model.clone(),
+        })
+    }
+
+    /// Classify requests with `model` instead of the heuristic.
+    pub fn with_classifier_model(mut self, model: impl Into<String>) -> Self {
+        self.classifier_model = Some(model.into());
+        self
+    }
+
+    /// Model of a complexity tier.
+    pub fn model_for(&self, complexity: Complexity) -> &str {
+        match complexity {
+            Complexity::Simple => &self.simple,
+            Complexity::Standard => &self.standard,
+            Complexity::Complex => &self.complex,
+        }
+    }
+
+    /// Choose the model for `message` and log the decision.
+    pub async fn route(&self, client: &OpenRouterClient, message: &str) -> RoutingDecision {
+        let (complexity, method) = match &self.classifier_model {
+            Some(model) => match self.classify_with_model(client, model, message).await {
+                Some(complexity) => (complexity, RoutingMethod::Classifier),
+                None => (classify(message), RoutingMethod::Heuristic),
+            },
+            None => (classify(message), RoutingMethod::Heuristic),
+        };
+
+        let decision = RoutingDecision {
+            complexity,
+            model: self.model_for(complexity).to_string(),
+            method,
+        };
+        info!(
+            complexity = %decision.complexity,
+            model = %decision.model,
+            method = %decision.method,
+            "Routed request"
+        );
+        decision
+    }
+
+    /// `config` with the model chosen for `message`.
+    pub async fn route_config(
+        &self,
+        client: &OpenRouterClient,
+        config: &ModelConfig,
+        message: &str,
+    ) -> ModelConfig {
+        let decision = self.route(client, message).await;
+        ModelConfig {
+            model: decision.model,
+            ..config.clone()
+        }
+    }
+
+    async fn classify_with_model(
+        &self,
+        client: &OpenRouterClient,
+        model: &str,
+        message: &str,
+    ) -> Option<Complexity> {
+        let config = ModelConfig {
+            model: model.to_string(),
+            max_tokens: 8,
+            temperature: 0.0,
+            ..ModelConfig::default()
+        };
+        let preview: String = message.chars().take(2000).collect();
+        let messages = vec![ChatMessage::system(CLASSIFIER_PROMPT), ChatMessage::user(preview)];
+
+        match client.chat(&config, messages, None).await {
+            Ok(response) => {
+                let reply = response.message().and_then(|m| m.content.clone()).unwrap_or_default();
+                let complexity = Complexity::from_reply(&reply);
+                if complexity.is_none() {
+                    warn!(reply = %reply, "Unrecognized complexity from classifier, using heuristic");
+                }
+                complexity
+            }
+            Err(e) => {
+                warn!(error = %e, "Complexity classifier failed, using heuristic");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_heuristic() {
+        assert_eq!(classify("status?"), Complexity::Simple);
+        assert_eq!(classify("Summarize what the api session did this morning"), Complexity::Simple);
+        assert_eq!(classify("why does the build fail?"), Complexity::Standard);
+        assert_eq!(classify("I need an explanation of this error"), Complexity::Standard);
+        assert_eq!(classify("Plan the migration from REST to gRPC"), Complexity::Complex);
+        assert_eq!(classify(&"details ".repeat(100)), Complexity::Complex);
+    }
+
+    #[test]
+    fn test_router_from_settings() {
+        assert!(ModelRouter::from_settings(&RoutingSettings::default()).is_none());
+
+        let settings = RoutingSettings {
+            enabled: true,
+            complex: Some("big".to_string()),
+            ..Default::default()
+        };
+        let router = ModelRouter::from_settings(&settings).unwrap();
+        assert_eq!(router.model_for(Complexity::Complex), "big");
+        assert_eq!(router.model_for(Complexity::Simple), DEFAULT_SIMPLE_MODEL);
+        assert_eq!(Complexity::from_reply("Complex."), Some(Complexity::Complex));
+        assert_eq!(Complexity::from_reply("no idea"), None);
+    }
+}
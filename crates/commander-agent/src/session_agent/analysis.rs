@@ -1,16 +1,23 @@
 //! Output analysis logic for SessionAgent.
 
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
-use commander_core::{ChangeNotification, ChangeType, Significance};
+use commander_core::{ChangeNotification, ChangeType, ProgressMilestones, Significance};
 
 use crate::client::ChatMessage;
-use crate::error::Result;
+use crate::error::{AgentError, Result};
+use crate::messaging::format_inbox;
 
-use super::state::OutputAnalysis;
+use super::state::{ChangeVerdict, OutputAnalysis};
 use super::SessionAgent;
 use super::DEFAULT_SYSTEM_PROMPT;
 
+/// Trailing output lines examined by offline analysis.
+const OFFLINE_ANALYSIS_LINES: usize = 20;
+
+/// Trailing lines of each auxiliary pane included in LLM analysis.
+const PANE_ANALYSIS_LINES: usize = 20;
+
 impl SessionAgent {
     /// Process session output with smart change detection.
     ///
@@ -22,7 +29,8 @@ impl SessionAgent {
     ///
     /// - `Ok(Some(notification))` if user should be notified
     /// - `Ok(None)` if change was not significant enough for notification
-    /// - `Err(_)` if LLM analysis failed
+    /// - `Err(_)` if LLM analysis failed; when the provider is unavailable the
+    ///   pattern-based summary is used instead (see [`Self::take_llm_outcome`])
     ///
     /// # Example
     ///
@@ -37,6 +45,23 @@ impl SessionAgent {
     pub async fn process_output_change(
         &mut self,
         output: &str,
+    ) -> Result<Option<ChangeNotification>> {
+        self.process_change(output, true).await
+    }
+
+    /// Process session output with change detection only, never calling the
+    /// LLM. Used while the model provider is unavailable.
+    pub async fn process_output_change_offline(
+        &mut self,
+        output: &str,
+    ) -> Result<Option<ChangeNotification>> {
+        self.process_change(output, false).await
+    }
+
+    async fn process_change(
+        &mut self,
+        output: &str,
+        use_llm: bool,
     ) -> Result<Option<ChangeNotification>> {
         // Stage 1: Deterministic change detection (no LLM call)
         let change = self.change_detector.detect(output);
@@ -60,10 +85,27 @@ impl SessionAgent {
 
         // Stage 3: For significant changes, optionally do LLM analysis
         // Only invoke LLM for high-significance changes to get better summary
-        let (summary, requires_action) = if change.significance >= Significance::High {
-            // Do LLM analysis for high-significance changes
-            let analysis = self.analyze_output(output).await?;
+        let mut analysis = if use_llm && change.significance >= Significance::High {
+            match self.analyze_output(output).await {
+                Ok(analysis) => {
+                    self.llm_outcome = Some(Ok(()));
+                    Some(analysis)
+                }
+                Err(e @ AgentError::ProviderUnavailable(_)) => {
+                    // Degrade to the pattern-based summary rather than lose the change
+                    warn!(session_id = %self.session_id, error = %e, "LLM analysis unavailable");
+                    self.llm_outcome = Some(Err(e));
+                    None
+                }
+                Err(AgentError::BudgetExhausted(_)) => None,
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
 
+        let diagnosis = analysis.as_mut().and_then(|a| a.diagnosis.take());
+        let (summary, requires_action) = if let Some(analysis) = analysis {
             let requires_action = analysis.waiting_for_input || analysis.error_detected.is_some();
             let summary = if analysis.summary.is_empty() {
                 change.summary.clone()
@@ -71,9 +113,18 @@ impl SessionAgent {
                 analysis.summary
             };
 
+            // Record whether the analysis agreed, for pattern re-weighting
+            if let Some(pattern) = &change.matched_pattern {
+                self.change_verdict = Some(ChangeVerdict {
+                    pattern: pattern.clone(),
+                    significant: requires_action || analysis.detected_completion,
+                    summary: summary.clone(),
+                });
+            }
+
             (summary, requires_action)
         } else {
-            // For medium significance, use the pattern-based summary
+            // For medium significance, or without the LLM, use the pattern-based summary
             (change.summary.clone(), false)
         };
 
@@ -89,6 +140,7 @@ impl SessionAgent {
                 requires_action,
                 change_type: change.change_type,
                 significance: change.significance,
+                diagnosis,
             }))
         } else {
             Ok(None)
@@ -99,6 +151,7 @@ impl SessionAgent {
     ///
     /// This method uses the LLM to analyze session output and extract
     /// progress indicators, completion status, errors, and file changes.
+    /// A detected error is diagnosed (see [`Self::diagnose_error`]).
     pub async fn analyze_output(&mut self, output: &str) -> Result<OutputAnalysis> {
         // Store the output
         self.session_state.set_last_output(output);
@@ -114,27 +167,35 @@ Output to analyze:
 ```
 {}
 ```
-
+{}
 Provide a brief summary and structured analysis."#,
-            output.chars().take(4000).collect::<String>() // Limit output size
+            output.chars().take(4000).collect::<String>(), // Limit output size
+            self.pane_context()
         );
 
         // Build messages for analysis
-        let messages = vec![
-            ChatMessage::system(
-                self.config
-                    .system_prompt
-                    .as_deref()
-                    .unwrap_or(DEFAULT_SYSTEM_PROMPT),
-            ),
-            ChatMessage::user(analysis_prompt),
-        ];
+        let mut messages = vec![ChatMessage::system(
+            self.config
+                .system_prompt
+                .as_deref()
+                .unwrap_or(DEFAULT_SYSTEM_PROMPT),
+        )];
+        if let Some(notes) = self.session_notes_context() {
+            messages.push(ChatMessage::system(notes));
+        }
+        if let Some(inbox) = format_inbox(&self.inbox) {
+            messages.push(ChatMessage::system(inbox));
+        }
+        messages.push(ChatMessage::user(analysis_prompt));
 
         // Send request without tools for direct analysis
+        self.check_budget()?;
         let response = self
             .client
             .chat(&self.config, messages, None)
             .await?;
+        self.charge_budget(&response);
+        self.inbox.clear();
 
         let content = response
             .message()
@@ -142,14 +203,80 @@ Provide a brief summary and structured analysis."#,
             .unwrap_or_default();
 
         // Parse the response to extract structured analysis
-        let analysis = self.parse_analysis_response(&content, output);
+        let mut analysis = self.parse_analysis_response(&content, output);
+        analysis.error_pane = self.error_pane(&analysis);
 
         // Update state based on analysis
         self.update_state(&analysis);
 
+        // Suggest a fix for the error
+        if analysis.error_detected.is_some() {
+            match self.diagnose_error(output).await {
+                Ok(diagnosis) => analysis.diagnosis = diagnosis,
+                Err(e) => warn!(session_id = %self.session_id, error = %e, "Error diagnosis failed"),
+            }
+        }
+
         Ok(analysis)
     }
 
+    /// Analyze raw output with keyword heuristics instead of the LLM.
+    ///
+    /// Used while the model provider is unavailable; the summary is the last
+    /// non-empty output line.
+    pub fn analyze_output_offline(&mut self, output: &str) -> OutputAnalysis {
+        self.session_state.set_last_output(output);
+
+        let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+        let recent = lines[lines.len().saturating_sub(OFFLINE_ANALYSIS_LINES)..].join("\n");
+        let mut analysis = self.parse_analysis_response(&recent, output);
+        analysis.summary = lines
+            .last()
+            .map(|l| l.trim().chars().take(200).collect())
+            .unwrap_or_default();
+        analysis.error_pane = self.error_pane(&analysis);
+
+        self.update_state(&analysis);
+        analysis
+    }
+
+    /// Record output captured from an auxiliary pane (dev server, test
+    /// runner), so analysis can tell which pane a line came from.
+    pub fn record_pane_output(&mut self, pane: &str, output: &str) {
+        self.session_state.set_pane_output(pane, output);
+    }
+
+    /// Name of the pane an output line came from, if it is still on screen.
+    pub fn pane_for_line(&self, line: &str) -> Option<&str> {
+        self.session_state.pane_of_line(line)
+    }
+
+    /// Pane the analysis' error came from.
+    fn error_pane(&self, analysis: &OutputAnalysis) -> Option<String> {
+        let error = analysis.error_detected.as_deref()?;
+        self.pane_for_line(error).map(str::to_string)
+    }
+
+    /// Recent auxiliary pane output for the analysis prompt, each line
+    /// prefixed with `[pane]`.
+    fn pane_context(&self) -> String {
+        if self.session_state.pane_outputs.is_empty() {
+            return String::new();
+        }
+
+        let mut context = String::from(
+            "\nAuxiliary pane output (each line is prefixed with its pane; keep the prefix when quoting an error):\n```\n",
+        );
+        for (pane, output) in &self.session_state.pane_outputs {
+            let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+            for line in &lines[lines.len().saturating_sub(PANE_ANALYSIS_LINES)..] {
+                context.push_str(&format!("[{}] {}\n", pane, line));
+            }
+        }
+        context.push_str("```\n");
+        context
+    }
+
     /// Parse the LLM's analysis response into structured data.
     pub(super) fn parse_analysis_response(&self, response: &str, _output: &str) -> OutputAnalysis {
         let response_lower = response.to_lowercase();
@@ -199,11 +326,40 @@ Provide a brief summary and structured analysis."#,
         analysis
     }
 
+    /// Record file changes reported by the project's filesystem watcher.
+    ///
+    /// Watcher events replace the heuristic file extraction from output.
+    pub fn record_files_changed<I, S>(&mut self, files: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.session_state.record_watched_files(files);
+    }
+
+    /// Milestones of the task in progress, from the output collected so far.
+    ///
+    /// Files reported by the filesystem watcher take precedence over paths
+    /// found in output, and the tracked task stands in for the current step
+    /// when no tool call is visible.
+    pub fn progress_milestones(&self, lines: &[String]) -> ProgressMilestones {
+        let mut milestones = ProgressMilestones::from_output(lines);
+        if self.session_state.files_watched {
+            milestones.files_changed = self.session_state.files_modified.clone();
+        }
+        if milestones.current_step.is_none() {
+            milestones.current_step = self.session_state.current_task.clone();
+        }
+        milestones
+    }
+
     /// Update session state based on output analysis.
     pub fn update_state(&mut self, analysis: &OutputAnalysis) {
-        // Add detected files
-        for file in &analysis.files_changed {
-            self.session_state.add_modified_file(file);
+        // Add detected files, unless a filesystem watcher is authoritative
+        if !self.session_state.files_watched {
+            for file in &analysis.files_changed {
+                self.session_state.add_modified_file(file);
+            }
         }
 
         // Update progress based on completion
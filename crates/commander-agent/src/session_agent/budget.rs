@@ -0,0 +1,87 @@
+//! Per-task budgets for SessionAgent.
+//!
+//! Each task delegated to the session starts a fresh [`BudgetTracker`] under
+//! the agent's [`TaskBudget`]. Every LLM request of the agent counts towards
+//! it; once a limit is reached the session is marked blocked and further
+//! requests fail with [`AgentError::BudgetExhausted`] until the user approves
+//! continuing.
+
+use tracing::{info, warn};
+
+use crate::budget::{BudgetTracker, BudgetUsage, TaskBudget};
+use crate::client::ChatResponse;
+use crate::error::{AgentError, Result};
+
+use super::SessionAgent;
+
+/// Start of the blocker recorded while a task's budget is spent.
+const BUDGET_BLOCKER: &str = "Task budget exhausted";
+
+impl SessionAgent {
+    /// Limit each task delegated to the session to `budget`, from the next
+    /// task on.
+    pub fn set_task_budget(&mut self, budget: TaskBudget) {
+        self.task_budget = budget;
+    }
+
+    /// Start the budget of a newly delegated task.
+    pub fn start_task_budget(&mut self) {
+        self.budget = (!self.task_budget.is_unlimited()).then(|| BudgetTracker::new(self.task_budget));
+        self.clear_budget_blocker();
+    }
+
+    /// Budget usage of the current task, if it has a budget.
+    pub fn budget_usage(&self) -> Option<BudgetUsage> {
+        self.budget.as_ref().map(BudgetTracker::usage)
+    }
+
+    /// Whether the current task has spent its budget.
+    pub fn is_budget_exhausted(&self) -> bool {
+        self.budget.as_ref().is_some_and(|b| b.exhausted().is_some())
+    }
+
+    /// The user approved continuing a task whose budget is spent: grant
+    /// another allowance and lift the blocker. Returns false when the
+    /// budget was not spent.
+    pub fn approve_budget(&mut self) -> bool {
+        let Some(budget) = self.budget.as_mut().filter(|b| b.exhausted().is_some()) else {
+            return false;
+        };
+        budget.approve_more();
+        info!(session_id = %self.session_id, usage = %budget.usage(), "Task budget extended");
+        self.clear_budget_blocker();
+        true
+    }
+
+    /// Refuse an LLM request once the task's budget is spent, marking the
+    /// session blocked the first time.
+    pub(crate) fn check_budget(&mut self) -> Result<()> {
+        let Some(usage) = self.budget_usage().filter(|u| u.exhausted.is_some()) else {
+            return Ok(());
+        };
+        let blocked = self
+            .session_state
+            .blockers
+            .iter()
+            .any(|b| b.starts_with(BUDGET_BLOCKER));
+        if !blocked {
+            warn!(session_id = %self.session_id, usage = %usage, "Task budget exhausted");
+            self.session_state
+                .add_blocker(format!("{} ({}); approve to continue", BUDGET_BLOCKER, usage));
+        }
+        Err(AgentError::BudgetExhausted(usage.to_string()))
+    }
+
+    /// Count an LLM request towards the task's budget.
+    pub(crate) fn charge_budget(&mut self, response: &ChatResponse) {
+        if let Some(budget) = &mut self.budget {
+            budget.record(response);
+        }
+    }
+
+    fn clear_budget_blocker(&mut self) {
+        self.session_state
+            .blockers
+            .retain(|b| !b.starts_with(BUDGET_BLOCKER));
+    }
+}
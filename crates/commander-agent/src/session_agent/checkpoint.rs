@@ -0,0 +1,257 @@
+//! Git checkpoints taken before delegated tasks, used for `/undo`.
+//!
+//! A checkpoint records the project's `HEAD` together with a snapshot commit
+//! of the full working tree (tracked, modified and untracked files). The
+//! snapshot is written through a temporary index, so taking a checkpoint never
+//! touches the user's index, working tree or stash. Each snapshot is pinned
+//! under `refs/commander/undo/` so it survives garbage collection until the
+//! checkpoint is undone or discarded.
+//!
+//! Rolling back resets `HEAD` to the recorded commit (dropping any commits
+//! made during the task, such as auto-commits) and restores the working tree
+//! to the snapshot, including uncommitted changes that predate the task.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AgentError, Result};
+
+/// Ref namespace that keeps checkpoint snapshots reachable.
+const CHECKPOINT_REF_PREFIX: &str = "refs/commander/undo";
+
+/// A restorable point captured before a delegated task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskCheckpoint {
+    /// Root of the git worktree the checkpoint belongs to.
+    pub project_path: PathBuf,
+    /// The task that was about to run.
+    pub task: String,
+    /// `HEAD` commit before the task (None for a repository without commits).
+    pub head: Option<String>,
+    /// Commit capturing the full working tree before the task.
+    pub snapshot: String,
+    /// When the checkpoint was taken.
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskCheckpoint {
+    /// Capture a checkpoint of `project_path` before running `task`.
+    ///
+    /// The whole worktree containing `project_path` is captured. Returns
+    /// `Ok(None)` when the directory is not inside a git worktree.
+    pub fn create(project_path: &Path, task: &str) -> Result<Option<Self>> {
+        if !is_git_worktree(project_path) {
+            return Ok(None);
+        }
+        let root = PathBuf::from(git(project_path, &["rev-parse", "--show-toplevel"])?);
+        let project_path = root.as_path();
+
+        let head = git(project_path, &["rev-parse", "--verify", "-q", "HEAD"]).ok();
+        let tree = snapshot_tree(project_path)?;
+
+        let message = format!("commander checkpoint: {}", task);
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", message.as_str()];
+        if let Some(head) = &head {
+            args.extend(["-p", head.as_str()]);
+        }
+        let snapshot = git(project_path, &args)?;
+
+        let checkpoint = Self {
+            project_path: project_path.to_path_buf(),
+            task: task.to_string(),
+            head,
+            snapshot,
+            created_at: Utc::now(),
+        };
+        git(project_path, &["update-ref", &checkpoint.ref_name(), &checkpoint.snapshot])?;
+
+        Ok(Some(checkpoint))
+    }
+
+    /// Files that differ between the checkpoint and the current working tree.
+    pub fn changed_files(&self) -> Result<Vec<String>> {
+        let current = snapshot_tree(&self.project_path)?;
+        let output = git(
+            &self.project_path,
+            &["diff", "--name-only", "--no-renames", &self.snapshot, &current],
+        )?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    /// Revert the project to the state captured by this checkpoint.
+    ///
+    /// Returns the files that were changed back.
+    pub fn restore(&self) -> Result<Vec<String>> {
+        let path = self.project_path.as_path();
+        let changed = self.changed_files()?;
+
+        match &self.head {
+            Some(head) => {
+                git(path, &["reset", "-q", "--hard", head])?;
+            }
+            None => {
+                git(path, &["read-tree", "--empty"])?;
+            }
+        }
+
+        let snapshot_files: HashSet<String> =
+            git(path, &["ls-tree", "-r", "--name-only", &self.snapshot])?
+                .lines()
+                .map(str::to_string)
+                .collect();
+
+        if !snapshot_files.is_empty() {
+            git(path, &["checkout", &self.snapshot, "--", "."])?;
+        }
+        // Unstage the restored files so the index matches HEAD again
+        if self.head.is_some() {
+            git(path, &["reset", "-q"])?;
+        } else {
+            git(path, &["read-tree", "--empty"])?;
+        }
+
+        // Remove files that did not exist before the task
+        let present = git(path, &["ls-files", "--cached", "--others", "--exclude-standard"])?;
+        for file in present.lines().filter(|f| !snapshot_files.contains(*f)) {
+            let full = path.join(file);
+            if full.is_file() {
+                std::fs::remove_file(&full).map_err(|e| {
+                    AgentError::Git(format!("failed to remove {}: {}", full.display(), e))
+                })?;
+            }
+        }
+
+        self.discard();
+        Ok(changed)
+    }
+
+    /// Drop the ref that keeps this checkpoint's snapshot alive.
+    pub fn discard(&self) {
+        let _ = git(&self.project_path, &["update-ref", "-d", &self.ref_name()]);
+    }
+
+    fn ref_name(&self) -> String {
+        format!("{}/{}", CHECKPOINT_REF_PREFIX, self.snapshot)
+    }
+}
+
+/// Check if `path` is inside a git worktree.
+fn is_git_worktree(path: &Path) -> bool {
+    git(path, &["rev-parse", "--is-inside-work-tree"])
+        .map(|out| out == "true")
+        .unwrap_or(false)
+}
+
+/// Write the current working tree (respecting .gitignore) as a tree object.
+fn snapshot_tree(path: &Path) -> Result<String> {
+    let index = PathBuf::from(git(path, &["rev-parse", "--git-path", "commander-undo-index"])?);
+    let index = if index.is_absolute() { index } else { path.join(index) };
+
+    let result = (|| {
+        git_with_index(path, &index, &["add", "-A", "--", "."])?;
+        git_with_index(path, &index, &["write-tree"])
+    })();
+    let _ = std::fs::remove_file(&index);
+    result
+}
+
+fn git(path: &Path, args: &[&str]) -> Result<String> {
+    run_git(Command::new("git").args(args).current_dir(path), args)
+}
+
+fn git_with_index(path: &Path, index: &Path, args: &[&str]) -> Result<String> {
+    run_git(
+        Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .env("GIT_INDEX_FILE", index),
+        args,
+    )
+}
+
+fn run_git(command: &mut Command, args: &[&str]) -> Result<String> {
+    let output = command
+        .output()
+        .map_err(|e| AgentError::Git(format!("failed to run git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AgentError::Git(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn init_repo() -> tempfile::TempDir {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path();
+        git(path, &["init", "-q"]).unwrap();
+        git(path, &["config", "user.email", "test@example.com"]).unwrap();
+        git(path, &["config", "user.name", "Test"]).unwrap();
+        fs::write(path.join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(path.join("lib.rs"), "pub fn lib() {}\n").unwrap();
+        git(path, &["add", "-A"]).unwrap();
+        git(path, &["commit", "-q", "-m", "initial"]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_not_a_repository() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(TaskCheckpoint::create(dir.path(), "task").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_restore_reverts_task_changes_and_keeps_prior_work() {
+        let repo = init_repo();
+        let path = repo.path();
+        let head = git(path, &["rev-parse", "HEAD"]).unwrap();
+
+        // Uncommitted work that predates the task must survive the undo
+        fs::write(path.join("main.rs"), "fn main() { wip(); }\n").unwrap();
+        fs::write(path.join("notes.md"), "todo\n").unwrap();
+
+        let checkpoint = TaskCheckpoint::create(path, "refactor").unwrap().unwrap();
+        assert_eq!(checkpoint.head.as_deref(), Some(head.as_str()));
+        // Taking a checkpoint leaves the working tree and index alone
+        assert!(git(path, &["status", "--porcelain"]).unwrap().contains("notes.md"));
+        assert!(checkpoint.changed_files().unwrap().is_empty());
+
+        // The task edits, creates and deletes files, then auto-commits
+        fs::write(path.join("main.rs"), "fn main() { broken }\n").unwrap();
+        fs::write(path.join("new.rs"), "mod new;\n").unwrap();
+        fs::remove_file(path.join("lib.rs")).unwrap();
+        git(path, &["add", "-A"]).unwrap();
+        git(path, &["commit", "-q", "-m", "WIP: Auto-commit"]).unwrap();
+
+        let mut changed = checkpoint.changed_files().unwrap();
+        changed.sort();
+        assert_eq!(changed, vec!["lib.rs", "main.rs", "new.rs"]);
+
+        checkpoint.restore().unwrap();
+
+        assert_eq!(git(path, &["rev-parse", "HEAD"]).unwrap(), head);
+        assert_eq!(fs::read_to_string(path.join("main.rs")).unwrap(), "fn main() { wip(); }\n");
+        assert_eq!(fs::read_to_string(path.join("notes.md")).unwrap(), "todo\n");
+        assert!(path.join("lib.rs").exists());
+        assert!(!path.join("new.rs").exists());
+
+        // Prior work is uncommitted again, and the checkpoint ref is gone
+        let status = git(path, &["status", "--porcelain"]).unwrap();
+        assert!(status.contains("M main.rs"));
+        assert!(status.contains("?? notes.md"));
+        let refs = git(path, &["for-each-ref", CHECKPOINT_REF_PREFIX]).unwrap();
+        assert!(refs.is_empty());
+    }
+}
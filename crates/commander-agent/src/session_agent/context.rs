@@ -94,16 +94,13 @@ impl SessionAgent {
         Ok(action)
     }
 
-    /// Estimate the current context token usage.
+    /// Count the current context token usage with the model's tokenizer.
     pub(super) fn estimate_context_tokens(&self) -> usize {
-        // Rough estimate: 4 chars per token
-        let context_chars = self.context.estimated_tokens() * 4;
-        let window_tokens = self.context_window.estimated_tokens();
+        let tokenizer = self.context_manager.tokenizer();
+        let context_tokens = self.context.token_count(&tokenizer);
+        let state_tokens = tokenizer.count(&format!("{:?}", self.session_state));
 
-        // Add state information
-        let state_chars = format!("{:?}", self.session_state).len();
-
-        (context_chars + state_chars) / 4 + window_tokens
+        context_tokens + state_tokens + self.context_window.estimated_tokens()
     }
 
     /// Generate a state summary for pause/resume operations.
@@ -160,4 +157,12 @@ impl SessionAgent {
 
         summary
     }
+
+    /// Notes the user left on this session with `/note`, as a context block.
+    ///
+    /// Read on every build so a note added between analyses is seen on the
+    /// next one.
+    pub(super) fn session_notes_context(&self) -> Option<String> {
+        commander_core::notes_context(&commander_core::session_notes(&self.session_id))
+    }
 }
@@ -0,0 +1,89 @@
+//! Error diagnosis for SessionAgent.
+
+use tracing::{debug, warn};
+
+use commander_core::diagnosis::{
+    build_prompt, error_context, find_error_line, parse_reply, DIAGNOSIS_MEMORY_PREFIX,
+    DIAGNOSIS_SYSTEM_PROMPT,
+};
+use commander_core::ErrorDiagnosis;
+
+use crate::client::ChatMessage;
+use crate::error::Result;
+
+use super::SessionAgent;
+
+/// Memories searched for prior occurrences of an error.
+const PRIOR_SEARCH_LIMIT: usize = 5;
+
+/// Minimum similarity for a memory to count as a prior occurrence.
+const PRIOR_MIN_SCORE: f32 = 0.75;
+
+impl SessionAgent {
+    /// Diagnose the last error in `output` and suggest a fix.
+    ///
+    /// Extracts the lines around the error, looks up earlier diagnoses of
+    /// similar errors in this agent's memories and asks the LLM for the
+    /// cause and a fix prompt. The diagnosis is remembered for next time.
+    ///
+    /// Returns `Ok(None)` when the output shows no error, the error is the
+    /// one diagnosed last, or the reply could not be parsed.
+    pub async fn diagnose_error(&mut self, output: &str) -> Result<Option<ErrorDiagnosis>> {
+        let Some((index, line)) = find_error_line(output) else {
+            return Ok(None);
+        };
+        let error = line.trim().to_string();
+        if self.last_diagnosed_error.as_deref() == Some(error.as_str()) {
+            return Ok(None);
+        }
+        self.check_budget()?;
+        self.last_diagnosed_error = Some(error.clone());
+
+        let prior = self.prior_occurrences(&error).await;
+        let messages = vec![
+            ChatMessage::system(DIAGNOSIS_SYSTEM_PROMPT),
+            ChatMessage::user(build_prompt(&error, &error_context(output, index), &prior)),
+        ];
+        let response = self.client.chat(&self.config, messages, None).await?;
+        self.charge_budget(&response);
+        let content = response
+            .message()
+            .and_then(|m| m.content.clone())
+            .unwrap_or_default();
+
+        let Some(reply) = parse_reply(&content) else {
+            warn!(session_id = %self.session_id, "Unparseable error diagnosis reply");
+            return Ok(None);
+        };
+        let diagnosis = ErrorDiagnosis::new(&self.session_id, &error, reply, prior);
+
+        if let Err(e) = self.store_memory(&diagnosis.memory_text()).await {
+            debug!(session_id = %self.session_id, error = %e, "Failed to remember error diagnosis");
+        }
+        Ok(Some(diagnosis))
+    }
+
+    /// Earlier diagnoses of errors similar to `error`.
+    async fn prior_occurrences(&self, error: &str) -> Vec<String> {
+        let Ok(embedding) = self.embedder.embed(error).await else {
+            return Vec::new();
+        };
+        let results = match self.memory.search(&embedding, &self.id, PRIOR_SEARCH_LIMIT).await {
+            Ok(results) => results,
+            Err(e) => {
+                debug!(session_id = %self.session_id, error = %e, "Prior occurrence search failed");
+                return Vec::new();
+            }
+        };
+        results
+            .into_iter()
+            .filter(|r| r.score >= PRIOR_MIN_SCORE)
+            .filter_map(|r| {
+                r.memory
+                    .content
+                    .strip_prefix(DIAGNOSIS_MEMORY_PREFIX)
+                    .map(|rest| rest.trim().to_string())
+            })
+            .collect()
+    }
+}
@@ -11,9 +11,34 @@
 //! - Pattern matching classifies changes without LLM calls
 //! - Only significant changes (errors, completion, input needed) trigger LLM analysis
 //! - Adaptive polling speeds up during activity, slows down when idle
+//!
+//! ## Error Diagnosis
+//!
+//! When analysis finds an error, the agent diagnoses it: the surrounding
+//! output and prior occurrences from its memories go to the LLM, which
+//! suggests a fix and a prompt that applies it.
+//!
+//! ## Imported History
+//!
+//! Before its first message, the agent reads the most recent memories
+//! imported from other tools (see [`crate::history`]) into its context.
+//!
+//! ## Permissions
+//!
+//! The project's [`PermissionProfile`] limits the tools the agent is offered
+//! and may run; see [`crate::permissions`].
+//!
+//! ## Task Budgets
+//!
+//! A delegated task may be limited in LLM turns, tokens and time; see
+//! [`crate::budget`]. A spent budget blocks the session's analysis until the
+//! user approves continuing.
 
 mod analysis;
+mod budget;
+mod checkpoint;
 mod context;
+mod diagnosis;
 mod state;
 mod tools;
 
@@ -21,28 +46,44 @@ mod tools;
 mod tests;
 
 use async_trait::async_trait;
-use std::sync::Arc;
-use tracing::{debug, info, trace};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, trace, warn};
 
-use commander_core::ChangeDetector;
-use commander_memory::{EmbeddingGenerator, Memory, MemoryStore};
+use commander_core::{ChangeDetector, PermissionProfile};
+use commander_memory::{EmbeddingGenerator, Memory, MemoryStore, EMBEDDING_MODEL_KEY};
 
 use crate::agent::{Agent, AgentType};
+use crate::budget::{BudgetTracker, TaskBudget};
+use crate::catalog::validate_model;
 use crate::client::{ChatMessage, ChatTool, OpenRouterClient};
 use crate::compaction::{ContextWindow, SimpleSummarizer, Summarizer};
 use crate::config::ModelConfig;
 use crate::context::{AgentContext, Message};
-use crate::context_manager::{model_contexts, ContextManager, ContextStrategy};
+use crate::context_manager::{ContextManager, ContextStrategy, ContextUsage};
 use crate::error::{AgentError, Result};
+use crate::history;
+use crate::knowledge::PROMOTE_MEMORY_TOOL;
+use crate::messaging::{format_inbox, AgentMessage, SEND_AGENT_MESSAGE_TOOL};
+use crate::permissions;
 use crate::response::AgentResponse;
+use crate::router::ModelRouter;
+use crate::eval::ImprovementStore;
 use crate::template::{AdapterType, AgentTemplate, TemplateRegistry};
 use crate::tool::{ToolCall, ToolDefinition, ToolResult};
 
-pub use state::{OutputAnalysis, SessionState};
+pub use checkpoint::TaskCheckpoint;
+pub use state::{ChangeVerdict, OutputAnalysis, SessionState, MAIN_PANE};
 
 /// Maximum iterations in the tool calling loop.
 const MAX_TOOL_ITERATIONS: u32 = 5;
 
+/// Maximum number of task checkpoints kept for undo.
+const MAX_CHECKPOINTS: usize = 10;
+
+/// Tools that record file changes or write shared state, stripped from
+/// read-only sessions.
+const WRITE_TOOLS: &[&str] = &["track_files", "track_delegation", PROMOTE_MEMORY_TOOL, SEND_AGENT_MESSAGE_TOOL];
+
 /// Default system prompt for Session Agents.
 const DEFAULT_SYSTEM_PROMPT: &str = r#"You are a Session Agent in the AI Commander system.
 Your role is to monitor and analyze a specific coding session.
@@ -53,6 +94,7 @@ Key responsibilities:
 3. Detect when the session needs user input or has completed tasks
 4. Maintain state about goals, progress, and blockers
 5. Report status summaries to the User Agent
+6. Promote facts that hold for the whole project with promote_memory
 
 When analyzing output:
 - Look for completion indicators (success messages, test results)
@@ -65,7 +107,9 @@ Be concise in your analysis and focus on actionable information."#;
 /// Session Agent that manages a specific coding session.
 ///
 /// Uses Claude Haiku 4.5 via OpenRouter for cost-optimized analysis.
-/// Maintains isolated memory (can only access own memories).
+/// Maintains isolated memory (can only access own memories), plus read
+/// access to its project's shared knowledge, which it can add to with the
+/// `promote_memory` tool.
 ///
 /// ## Change Detection
 ///
@@ -87,6 +131,10 @@ pub struct SessionAgent {
     /// Session ID this agent is managing.
     pub(crate) session_id: String,
 
+    /// Project whose shared knowledge this agent reads and promotes into
+    /// (defaults to the session ID).
+    pub(crate) project: String,
+
     /// Type of adapter (e.g., claude_code, mpm, generic).
     adapter_type: AdapterType,
 
@@ -117,11 +165,47 @@ pub struct SessionAgent {
     /// Change detector for smart output monitoring.
     change_detector: ChangeDetector,
 
+    /// Verdict on the last change escalated to LLM analysis, not yet collected.
+    pub(crate) change_verdict: Option<ChangeVerdict>,
+
+    /// Outcome of the last LLM analysis of a change, not yet collected.
+    pub(crate) llm_outcome: Option<Result<()>>,
+
+    /// Error line diagnosed last, so a lingering error isn't diagnosed twice.
+    pub(crate) last_diagnosed_error: Option<String>,
+
     /// Context manager for tracking token usage and triggering actions.
     pub(crate) context_manager: ContextManager,
 
     /// Context window for message compaction.
     pub(crate) context_window: ContextWindow,
+
+    /// Checkpoints taken before delegated tasks, most recent last.
+    checkpoints: Vec<TaskCheckpoint>,
+
+    /// Whether the session is read-only ("ask the project" mode).
+    read_only: bool,
+
+    /// Tool permission profile of the project.
+    permission_profile: PermissionProfile,
+
+    /// Imported history put into the context, once loaded.
+    prior_context: Option<Option<String>>,
+
+    /// Picks the model per request when routing is enabled.
+    router: Option<ModelRouter>,
+
+    /// Limits on each delegated task.
+    task_budget: TaskBudget,
+
+    /// Usage of the current task's budget, when it has one.
+    budget: Option<BudgetTracker>,
+
+    /// Messages from other agents, shown on the next analysis or request.
+    inbox: Vec<AgentMessage>,
+
+    /// Messages this agent sent, until the orchestrator collects them.
+    outbox: Mutex<Vec<AgentMessage>>,
 }
 
 impl SessionAgent {
@@ -132,11 +216,11 @@ impl SessionAgent {
         memory: Arc<dyn MemoryStore>,
     ) -> Result<Self> {
         let session_id = session_id.into();
-        let client = OpenRouterClient::from_env()?;
+        let client = OpenRouterClient::from_env()?.for_agent(format!("session-agent-{}", session_id));
         let embedder = EmbeddingGenerator::from_env();
 
-        // Get template for this adapter type
-        let registry = TemplateRegistry::new();
+        // Get template for this adapter type, with any applied prompt patches
+        let registry = TemplateRegistry::new().with_applied_prompts(&ImprovementStore::open_default());
         let template = registry
             .get(&adapter_type)
             .cloned()
@@ -148,22 +232,28 @@ impl SessionAgent {
 
         let id = format!("session-agent-{}", session_id);
 
+        // Detector tuned by patterns learned from this adapter's feedback
+        let change_detector = ChangeDetector::new_for_adapter(&adapter_type.to_string());
+
         // Initialize context manager with strategy from template
         let context_strategy = template
             .context_strategy
             .clone()
             .unwrap_or(ContextStrategy::WarnAndContinue);
-        let context_manager = ContextManager::new(context_strategy, model_contexts::CLAUDE_3_HAIKU);
+        let config = Self::default_config(&template);
+        let context_manager = ContextManager::for_model(context_strategy, &config.model);
 
         // Initialize context window for message compaction
         let summarizer: Arc<dyn Summarizer> = Arc::new(SimpleSummarizer);
-        let context_window = ContextWindow::with_defaults(summarizer);
+        let context_window =
+            ContextWindow::with_defaults(summarizer).with_tokenizer(context_manager.tokenizer());
 
         Ok(Self {
             id,
+            project: session_id.clone(),
             session_id,
             adapter_type,
-            config: Self::default_config(&template),
+            config,
             memory,
             embedder,
             tools,
@@ -171,9 +261,21 @@ impl SessionAgent {
             context: AgentContext::new(),
             session_state: SessionState::new(),
             template,
-            change_detector: ChangeDetector::new(),
+            change_detector,
+            change_verdict: None,
+            llm_outcome: None,
+            last_diagnosed_error: None,
             context_manager,
             context_window,
+            checkpoints: Vec::new(),
+            read_only: false,
+            permission_profile: PermissionProfile::default(),
+            prior_context: None,
+            router: None,
+            task_budget: TaskBudget::default(),
+            budget: None,
+            inbox: Vec::new(),
+            outbox: Mutex::new(Vec::new()),
         })
     }
 
@@ -188,7 +290,7 @@ impl SessionAgent {
         let client = OpenRouterClient::new(api_key);
         let embedder = EmbeddingGenerator::from_env();
 
-        let registry = TemplateRegistry::new();
+        let registry = TemplateRegistry::new().with_applied_prompts(&ImprovementStore::open_default());
         let template = registry
             .get(&adapter_type)
             .cloned()
@@ -199,22 +301,28 @@ impl SessionAgent {
 
         let id = format!("session-agent-{}", session_id);
 
+        // Detector tuned by patterns learned from this adapter's feedback
+        let change_detector = ChangeDetector::new_for_adapter(&adapter_type.to_string());
+
         // Initialize context manager with strategy from template
         let context_strategy = template
             .context_strategy
             .clone()
             .unwrap_or(ContextStrategy::WarnAndContinue);
-        let context_manager = ContextManager::new(context_strategy, model_contexts::CLAUDE_3_HAIKU);
+        let config = Self::default_config(&template);
+        let context_manager = ContextManager::for_model(context_strategy, &config.model);
 
         // Initialize context window for message compaction
         let summarizer: Arc<dyn Summarizer> = Arc::new(SimpleSummarizer);
-        let context_window = ContextWindow::with_defaults(summarizer);
+        let context_window =
+            ContextWindow::with_defaults(summarizer).with_tokenizer(context_manager.tokenizer());
 
         Self {
             id,
+            project: session_id.clone(),
             session_id,
             adapter_type,
-            config: Self::default_config(&template),
+            config,
             memory,
             embedder,
             tools,
@@ -222,9 +330,21 @@ impl SessionAgent {
             context: AgentContext::new(),
             session_state: SessionState::new(),
             template,
-            change_detector: ChangeDetector::new(),
+            change_detector,
+            change_verdict: None,
+            llm_outcome: None,
+            last_diagnosed_error: None,
             context_manager,
             context_window,
+            checkpoints: Vec::new(),
+            read_only: false,
+            permission_profile: PermissionProfile::default(),
+            prior_context: None,
+            router: None,
+            task_budget: TaskBudget::default(),
+            budget: None,
+            inbox: Vec::new(),
+            outbox: Mutex::new(Vec::new()),
         }
     }
 
@@ -259,6 +379,16 @@ impl SessionAgent {
         &self.session_id
     }
 
+    /// Get the project whose shared knowledge this agent uses.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    /// Share knowledge with other sessions of `project`.
+    pub fn set_project(&mut self, project: impl Into<String>) {
+        self.project = project.into();
+    }
+
     /// Get the adapter type.
     pub fn adapter_type(&self) -> &AdapterType {
         &self.adapter_type
@@ -289,6 +419,136 @@ impl SessionAgent {
         &self.template
     }
 
+    /// Switch the model used for later LLM calls.
+    ///
+    /// `None` restores the template's default model. A model missing from
+    /// the cached OpenRouter catalog is still used, with a warning.
+    pub fn set_model(&mut self, model: Option<&str>) {
+        self.config.model = match model {
+            Some(model) => model.to_string(),
+            None => Self::default_config(&self.template).model,
+        };
+        if let Err(e) = validate_model(&self.config) {
+            warn!(session_id = %self.session_id, error = %e, "Session Agent model not in catalog");
+        }
+    }
+
+    /// Switch to the template of another adapter after the session's
+    /// runtime was relaunched with it.
+    ///
+    /// The conversation context and session state are kept. The model
+    /// follows the new template unless it was switched with
+    /// [`set_model`](Self::set_model).
+    pub fn switch_adapter(&mut self, adapter_type: AdapterType) {
+        if adapter_type == self.adapter_type {
+            return;
+        }
+        let custom_model = (self.config.model != Self::default_config(&self.template).model)
+            .then(|| self.config.model.clone());
+
+        let registry = TemplateRegistry::new().with_applied_prompts(&ImprovementStore::open_default());
+        self.template = registry
+            .get(&adapter_type)
+            .cloned()
+            .unwrap_or_else(AgentTemplate::generic);
+        self.config = Self::default_config(&self.template);
+        if let Some(model) = custom_model {
+            self.config.model = model;
+        }
+        let context_strategy = self
+            .template
+            .context_strategy
+            .clone()
+            .unwrap_or(ContextStrategy::WarnAndContinue);
+        self.context_manager = ContextManager::for_model(context_strategy, &self.config.model);
+        self.change_detector = ChangeDetector::new_for_adapter(&adapter_type.to_string());
+
+        info!(session_id = %self.session_id, from = %self.adapter_type, to = %adapter_type, "Switched adapter");
+        self.adapter_type = adapter_type;
+        self.rebuild_tools();
+    }
+
+    /// What the session has been doing (goals, task, blockers, changed files
+    /// and the summarized conversation), for carrying it over to a
+    /// relaunched runtime. `None` when nothing is known yet.
+    pub fn context_summary(&self) -> Option<String> {
+        let state = &self.session_state;
+        let mut lines = Vec::new();
+        if !state.goals.is_empty() {
+            lines.push(format!("Goals: {}", state.goals.join("; ")));
+        }
+        if let Some(task) = &state.current_task {
+            lines.push(format!("Current task: {}", task));
+        }
+        if !state.blockers.is_empty() {
+            lines.push(format!("Blockers: {}", state.blockers.join("; ")));
+        }
+        if !state.files_modified.is_empty() {
+            lines.push(format!("Files modified: {}", state.files_modified.join(", ")));
+        }
+        if !self.context.summarized_history.is_empty() {
+            lines.push(format!("Conversation so far:\n{}", self.context.summarized_history));
+        }
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Hand this agent messages from other agents; they are shown to it on
+    /// its next LLM analysis or request.
+    pub fn deliver_messages(&mut self, messages: impl IntoIterator<Item = AgentMessage>) {
+        self.inbox.extend(messages);
+    }
+
+    /// Messages delivered and not yet shown to the agent.
+    pub fn inbox(&self) -> &[AgentMessage] {
+        &self.inbox
+    }
+
+    /// Take the messages this agent sent since the last call.
+    pub fn take_sent_messages(&self) -> Vec<AgentMessage> {
+        std::mem::take(&mut *self.outbox.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Whether the session is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Switch read-only mode, which strips the tools that record changes or
+    /// write shared state.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+        self.rebuild_tools();
+    }
+
+    /// Get the tool permission profile.
+    pub fn permission_profile(&self) -> PermissionProfile {
+        self.permission_profile
+    }
+
+    /// Switch the tool permission profile, which strips the tools it denies.
+    pub fn set_permission_profile(&mut self, profile: PermissionProfile) {
+        self.permission_profile = profile;
+        self.rebuild_tools();
+    }
+
+    /// Offer the template's tools minus those read-only mode and the
+    /// permission profile take away.
+    fn rebuild_tools(&mut self) {
+        let mut tools = Self::builtin_tools();
+        tools.extend(self.template.tools.clone());
+        if self.read_only {
+            tools.retain(|tool| !WRITE_TOOLS.contains(&tool.name.as_str()));
+        }
+        permissions::filter_tools(self.permission_profile, &mut tools);
+        self.tools = tools;
+    }
+
+    /// Route each request to a model tier by complexity, or `None` to always
+    /// use the configured model.
+    pub fn set_router(&mut self, router: Option<ModelRouter>) {
+        self.router = router;
+    }
+
     /// Get a reference to the change detector.
     pub fn change_detector(&self) -> &ChangeDetector {
         &self.change_detector
@@ -299,6 +559,19 @@ impl SessionAgent {
         &mut self.change_detector
     }
 
+    /// Take the verdict on the last escalated change, if any.
+    pub fn take_change_verdict(&mut self) -> Option<ChangeVerdict> {
+        self.change_verdict.take()
+    }
+
+    /// Take the outcome of the last LLM analysis of a change, if one ran.
+    ///
+    /// An error means the provider was unavailable and the change was
+    /// summarized from the detector's patterns instead.
+    pub fn take_llm_outcome(&mut self) -> Option<Result<()>> {
+        self.llm_outcome.take()
+    }
+
     /// Get a reference to the context manager.
     pub fn context_manager(&self) -> &ContextManager {
         &self.context_manager
@@ -309,6 +582,14 @@ impl SessionAgent {
         &mut self.context_manager
     }
 
+    /// Live context usage, counted with the model's tokenizer.
+    pub fn context_usage(&self) -> ContextUsage {
+        ContextUsage {
+            used_tokens: self.estimate_context_tokens(),
+            max_tokens: self.context_manager.max_tokens(),
+        }
+    }
+
     /// Get a reference to the context window.
     pub fn context_window(&self) -> &ContextWindow {
         &self.context_window
@@ -327,24 +608,92 @@ impl SessionAgent {
         self.change_detector.reset();
     }
 
+    /// Snapshot the project before a delegated task so it can be undone.
+    ///
+    /// Returns `Ok(None)` when the project is not a git repository.
+    pub fn checkpoint_task(
+        &mut self,
+        project_path: &std::path::Path,
+        task: &str,
+    ) -> Result<Option<&TaskCheckpoint>> {
+        let Some(checkpoint) = TaskCheckpoint::create(project_path, task)? else {
+            return Ok(None);
+        };
+
+        if self.checkpoints.len() >= MAX_CHECKPOINTS {
+            self.checkpoints.remove(0).discard();
+        }
+        debug!(session = %self.session_id, snapshot = %checkpoint.snapshot, "task checkpoint created");
+        self.checkpoints.push(checkpoint);
+        Ok(self.checkpoints.last())
+    }
+
+    /// The checkpoint taken before the most recent task, if any.
+    pub fn last_checkpoint(&self) -> Option<&TaskCheckpoint> {
+        self.checkpoints.last()
+    }
+
+    /// Revert the project to the state before the most recent task.
+    ///
+    /// Returns the checkpoint that was restored and the files reverted.
+    pub fn undo_last_task(&mut self) -> Result<Option<(TaskCheckpoint, Vec<String>)>> {
+        let Some(checkpoint) = self.checkpoints.pop() else {
+            return Ok(None);
+        };
+
+        match checkpoint.restore() {
+            Ok(files) => {
+                info!(session = %self.session_id, files = files.len(), "reverted last task");
+                self.reset_change_detector();
+                Ok(Some((checkpoint, files)))
+            }
+            Err(e) => {
+                // Keep the checkpoint so the undo can be retried
+                self.checkpoints.push(checkpoint);
+                Err(e)
+            }
+        }
+    }
+
     /// Store a memory from the session.
+    ///
+    /// Offline, the memory is stored without an embedding and embedded once
+    /// the network is back (see [`commander_memory::Backfill::with_pending_only`]).
     pub async fn store_memory(&self, content: &str) -> Result<()> {
-        let embedding = self
-            .embedder
-            .embed(content)
-            .await
-            .map_err(|e| AgentError::ToolExecution {
-                tool_name: "store_memory".to_string(),
-                message: format!("Failed to generate embedding: {}", e),
-            })?;
-
-        let memory = Memory::new(&self.id, content, embedding);
+        let memory = match self.embedder.embed(content).await {
+            Ok(embedding) => Memory::new(&self.id, content, embedding)
+                .with_metadata(EMBEDDING_MODEL_KEY, self.embedder.model_id().into()),
+            Err(e) if commander_core::connectivity::is_offline() => {
+                debug!(error = %e, "Offline, storing memory to embed later");
+                Memory::new(&self.id, content, Vec::new())
+            }
+            Err(e) => {
+                return Err(AgentError::ToolExecution {
+                    tool_name: "store_memory".to_string(),
+                    message: format!("Failed to generate embedding: {}", e),
+                })
+            }
+        };
         self.memory.store(memory).await.map_err(AgentError::Memory)?;
 
         debug!("Stored memory for session {}: {}", self.session_id, content.chars().take(50).collect::<String>());
         Ok(())
     }
 
+    /// Read the most recent imported history into the context, once.
+    pub async fn load_prior_context(&mut self) -> Result<()> {
+        if self.prior_context.is_some() {
+            return Ok(());
+        }
+        let memories = self.memory.list(&self.id, usize::MAX).await?;
+        let context = history::prior_context(&memories);
+        if context.is_some() {
+            debug!(session = %self.session_id, "Loaded imported history into context");
+        }
+        self.prior_context = Some(context);
+        Ok(())
+    }
+
     /// Build chat messages from context.
     fn build_messages(&self, user_message: &str) -> Vec<ChatMessage> {
         let mut messages = Vec::new();
@@ -369,6 +718,21 @@ impl SessionAgent {
         );
         messages.push(ChatMessage::system(state_context));
 
+        // Add notes the user left on the session
+        if let Some(notes) = self.session_notes_context() {
+            messages.push(ChatMessage::system(notes));
+        }
+
+        // Add messages from other agents
+        if let Some(inbox) = format_inbox(&self.inbox) {
+            messages.push(ChatMessage::system(inbox));
+        }
+
+        // Add history imported from other tools
+        if let Some(Some(prior)) = &self.prior_context {
+            messages.push(ChatMessage::system(prior));
+        }
+
         // Add summarized history if available
         if !self.context.summarized_history.is_empty() {
             messages.push(ChatMessage::system(format!(
@@ -413,6 +777,9 @@ impl Agent for SessionAgent {
         }
         self.context.relevant_memories = context.relevant_memories.clone();
 
+        // Start from imported history rather than cold
+        self.load_prior_context().await?;
+
         // Build chat messages
         let mut messages = self.build_messages(message);
 
@@ -423,6 +790,11 @@ impl Agent for SessionAgent {
             .map(ChatTool::from_definition)
             .collect();
 
+        let config = match &self.router {
+            Some(router) => router.route_config(&self.client, &self.config, message).await,
+            None => self.config.clone(),
+        };
+
         // Tool calling loop
         let mut iteration = 0;
         loop {
@@ -432,16 +804,18 @@ impl Agent for SessionAgent {
             }
 
             trace!("Session {} tool loop iteration {}", self.session_id, iteration);
+            self.check_budget()?;
 
             // Send request to OpenRouter
             let response = self
                 .client
                 .chat(
-                    &self.config,
+                    &config,
                     messages.clone(),
                     Some(chat_tools.clone()),
                 )
                 .await?;
+            self.charge_budget(&response);
 
             // Check for tool calls
             if response.has_tool_calls() {
@@ -465,7 +839,12 @@ impl Agent for SessionAgent {
 
                 // Execute each tool call
                 for call in &tool_calls {
-                    let result = self.execute_tool(call).await?;
+                    let result = match self.execute_tool(call).await {
+                        Err(e @ AgentError::PermissionDenied { .. }) => {
+                            ToolResult::error(&call.id, e.to_string())
+                        }
+                        result => result?,
+                    };
                     messages.push(ChatMessage::tool(&call.id, &result.content));
                 }
 
@@ -485,6 +864,7 @@ impl Agent for SessionAgent {
 
             // Trim context if needed (smaller for session agents)
             self.context.trim_recent(5);
+            self.inbox.clear();
 
             return Ok(AgentResponse::text(content));
         }
@@ -498,6 +878,14 @@ impl Agent for SessionAgent {
         debug!("Session {} executing tool: {}", self.session_id, call.name);
         trace!("Tool arguments: {:?}", call.arguments);
 
+        if self.read_only && WRITE_TOOLS.contains(&call.name.as_str()) {
+            return Ok(ToolResult::error(
+                &call.id,
+                format!("Tool '{}' is disabled in read-only sessions", call.name),
+            ));
+        }
+        permissions::enforce(&self.id, Some(&self.project), self.permission_profile, &call.name)?;
+
         match call.name.as_str() {
             "search_memories" => self.execute_search_memories(call).await,
             "update_session_state" => {
@@ -509,6 +897,8 @@ impl Agent for SessionAgent {
                 ))
             }
             "report_to_user" => self.execute_report_to_user(call).await,
+            PROMOTE_MEMORY_TOOL => self.execute_promote_memory(call).await,
+            SEND_AGENT_MESSAGE_TOOL => self.execute_send_agent_message(call),
             "analyze_output" => {
                 // For non-mutable context, we return a placeholder
                 // The full analysis should be done via analyze_output method
@@ -1,7 +1,15 @@
 //! Session state and output analysis structures.
 
+use std::collections::BTreeMap;
+
+use commander_adapters::{parse_test_output, TestRun};
+use commander_core::ErrorDiagnosis;
 use serde::{Deserialize, Serialize};
 
+/// Name of the pane running the AI tool, as opposed to auxiliary panes
+/// (dev server, test runner) declared in `.commander.toml`.
+pub const MAIN_PANE: &str = "main";
+
 /// State of the session being monitored.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SessionState {
@@ -20,8 +28,22 @@ pub struct SessionState {
     /// Files that have been modified in this session.
     pub files_modified: Vec<String>,
 
+    /// Whether `files_modified` is fed by a filesystem watcher.
+    ///
+    /// Once set, file paths guessed from output analysis are ignored.
+    #[serde(default)]
+    pub files_watched: bool,
+
     /// Last output received from the session.
     pub last_output: Option<String>,
+
+    /// Last output of each auxiliary pane, keyed by pane name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pane_outputs: BTreeMap<String, String>,
+
+    /// Results of the last test run seen in the session's output or panes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestRun>,
 }
 
 impl SessionState {
@@ -68,9 +90,64 @@ impl SessionState {
         }
     }
 
-    /// Set the last output.
+    /// Record files reported by a filesystem watcher.
+    pub fn record_watched_files<I, S>(&mut self, files: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.files_watched = true;
+        for file in files {
+            self.add_modified_file(file);
+        }
+    }
+
+    /// Set the last output, updating `tests` when it holds test results.
     pub fn set_last_output(&mut self, output: impl Into<String>) {
-        self.last_output = Some(output.into());
+        let output = output.into();
+        self.record_tests(&output);
+        self.last_output = Some(output);
+    }
+
+    /// Set the last output of an auxiliary pane, updating `tests` when it
+    /// holds test results.
+    pub fn set_pane_output(&mut self, pane: impl Into<String>, output: impl Into<String>) {
+        let output = output.into();
+        self.record_tests(&output);
+        self.pane_outputs.insert(pane.into(), output);
+    }
+
+    fn record_tests(&mut self, output: &str) {
+        if let Some(run) = parse_test_output(output) {
+            self.tests = Some(run);
+        }
+    }
+
+    /// Name of the pane whose last output contains `line`.
+    ///
+    /// A `[pane] ` prefix, as used in analysis prompts, is honored first.
+    /// Auxiliary panes are searched before the main pane.
+    pub fn pane_of_line(&self, line: &str) -> Option<&str> {
+        let line = line.trim();
+        if let Some((pane, _)) = line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            if let Some((name, _)) = self.pane_outputs.get_key_value(pane) {
+                return Some(name.as_str());
+            }
+        }
+        if line.is_empty() {
+            return None;
+        }
+
+        self.pane_outputs
+            .iter()
+            .find(|(_, output)| output.contains(line))
+            .map(|(name, _)| name.as_str())
+            .or_else(|| {
+                self.last_output
+                    .as_deref()
+                    .filter(|output| output.contains(line))
+                    .map(|_| MAIN_PANE)
+            })
     }
 }
 
@@ -86,9 +163,17 @@ pub struct OutputAnalysis {
     /// Error message if an error was detected.
     pub error_detected: Option<String>,
 
+    /// Pane the detected error came from, when known.
+    #[serde(default)]
+    pub error_pane: Option<String>,
+
     /// Files that were changed in this output.
     pub files_changed: Vec<String>,
 
+    /// Diagnosis and suggested fix for the detected error.
+    #[serde(default)]
+    pub diagnosis: Option<ErrorDiagnosis>,
+
     /// Summary of the output.
     pub summary: String,
 }
@@ -107,3 +192,17 @@ impl OutputAnalysis {
         }
     }
 }
+
+/// Whether LLM analysis agreed with the change detector about a change.
+///
+/// Recorded for high-significance changes so the detector's patterns can be
+/// re-weighted per adapter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeVerdict {
+    /// Significant pattern that classified the change.
+    pub pattern: String,
+    /// Whether the analysis found the change worth attention.
+    pub significant: bool,
+    /// Summary of the change.
+    pub summary: String,
+}
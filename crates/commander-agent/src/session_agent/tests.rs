@@ -8,6 +8,7 @@ use tokio::sync::RwLock;
 use commander_memory::{Memory, MemoryStore, SearchResult};
 
 use crate::context_manager::{ContextAction, ContextManager, ContextStrategy, CriticalAction};
+use crate::messaging::MessageKind;
 use crate::template::AgentTemplate;
 use super::tools::format_search_results;
 
@@ -132,6 +133,50 @@ fn test_session_state_updates() {
     assert_eq!(state.files_modified.len(), 1);
 }
 
+#[test]
+fn test_session_state_watched_files() {
+    let mut state = SessionState::new();
+    assert!(!state.files_watched);
+
+    state.add_modified_file("src/lib.rs");
+    state.record_watched_files(vec!["src/lib.rs", "Cargo.toml"]);
+
+    assert!(state.files_watched);
+    assert_eq!(state.files_modified, vec!["src/lib.rs", "Cargo.toml"]);
+}
+
+#[test]
+fn test_session_state_pane_of_line() {
+    let mut state = SessionState::new();
+    state.set_last_output("Editing src/auth.rs");
+    state.set_pane_output("server", "Listening on :3000\nError: EADDRINUSE");
+    state.set_pane_output("tests", "test auth::login ... FAILED");
+
+    assert_eq!(state.pane_of_line("Error: EADDRINUSE"), Some("server"));
+    assert_eq!(state.pane_of_line("[tests] something else"), Some("tests"));
+    assert_eq!(state.pane_of_line("Editing src/auth.rs"), Some(MAIN_PANE));
+    assert_eq!(state.pane_of_line("not on screen"), None);
+}
+
+#[test]
+fn test_session_state_records_test_results() {
+    let mut state = SessionState::new();
+    state.set_last_output("Editing src/auth.rs");
+    assert!(state.tests.is_none());
+
+    state.set_pane_output(
+        "tests",
+        "test auth::login ... FAILED\ntest result: FAILED. 4 passed; 1 failed; 0 ignored; 0 measured",
+    );
+    let tests = state.tests.as_ref().unwrap();
+    assert_eq!((tests.passed, tests.failed), (4, 1));
+    assert_eq!(tests.failing, ["auth::login"]);
+
+    // Output without results keeps the last run
+    state.set_last_output("Fixing the login check");
+    assert!(state.tests.is_some());
+}
+
 #[test]
 fn test_output_analysis_default() {
     let analysis = OutputAnalysis::new();
@@ -164,13 +209,126 @@ fn test_default_config() {
 fn test_builtin_tools() {
     let tools = SessionAgent::builtin_tools();
 
-    assert_eq!(tools.len(), 4);
+    assert_eq!(tools.len(), 6);
 
     let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
     assert!(tool_names.contains(&"search_memories"));
     assert!(tool_names.contains(&"update_session_state"));
     assert!(tool_names.contains(&"report_to_user"));
     assert!(tool_names.contains(&"analyze_output"));
+    assert!(tool_names.contains(&"promote_memory"));
+    assert!(tool_names.contains(&"send_agent_message"));
+}
+
+#[tokio::test]
+async fn test_send_agent_message_queues_for_bus() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("frontend", AdapterType::ClaudeCode, memory, "test-key");
+
+    let call = ToolCall::new(
+        "send_agent_message",
+        serde_json::json!({"kind": "knowledge_share", "to": "backend", "body": "GET /users now returns a page object"}),
+    );
+    agent.execute_tool(&call).await.unwrap();
+    let bad = ToolCall::new("send_agent_message", serde_json::json!({"kind": "gossip", "body": "hi"}));
+    assert!(agent.execute_tool(&bad).await.is_err());
+
+    let sent = agent.take_sent_messages();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].from, "frontend");
+    assert_eq!(sent[0].to.as_deref(), Some("backend"));
+    assert_eq!(sent[0].kind, MessageKind::KnowledgeShare);
+    assert!(agent.take_sent_messages().is_empty());
+
+    agent.deliver_messages(sent);
+    assert_eq!(agent.inbox().len(), 1);
+}
+
+#[test]
+fn test_read_only_strips_write_tools() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("s1", AdapterType::ClaudeCode, memory, "test-key");
+    let names = |agent: &SessionAgent| -> Vec<String> { agent.tools().iter().map(|t| t.name.clone()).collect() };
+    assert!(names(&agent).contains(&"track_files".to_string()));
+
+    agent.set_read_only(true);
+    assert!(agent.is_read_only());
+    let read_only = names(&agent);
+    assert!(!read_only.contains(&"track_files".to_string()));
+    assert!(!read_only.contains(&"promote_memory".to_string()));
+    assert!(read_only.contains(&"search_memories".to_string()));
+
+    agent.set_read_only(false);
+    assert!(names(&agent).contains(&"promote_memory".to_string()));
+}
+
+#[test]
+fn test_switch_adapter_keeps_context() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("s1", AdapterType::ClaudeCode, memory, "test-key");
+    assert_eq!(agent.context_summary(), None);
+    agent.set_read_only(true);
+    agent.state_mut().goals.push("Add OAuth login".into());
+    agent.state_mut().files_modified.push("src/auth.rs".into());
+    agent.context_mut().summarized_history = "User asked for OAuth; callback route added.".into();
+
+    agent.switch_adapter(AdapterType::Mpm);
+    assert_eq!(agent.adapter_type(), &AdapterType::Mpm);
+    assert_eq!(agent.template().adapter_type, AdapterType::Mpm);
+    assert!(agent.is_read_only());
+    assert!(!agent.tools().iter().any(|t| t.name == "track_files"));
+
+    let summary = agent.context_summary().unwrap();
+    assert!(summary.starts_with("Goals: Add OAuth login\nFiles modified: src/auth.rs"));
+    assert!(summary.ends_with("callback route added."));
+}
+
+#[test]
+fn test_observer_profile_strips_record_tools() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("s1", AdapterType::ClaudeCode, memory, "test-key");
+    let names = |agent: &SessionAgent| -> Vec<String> { agent.tools().iter().map(|t| t.name.clone()).collect() };
+    assert_eq!(agent.permission_profile(), PermissionProfile::Autonomous);
+
+    agent.set_permission_profile(PermissionProfile::Observer);
+    let observer = names(&agent);
+    assert!(!observer.contains(&"track_files".to_string()));
+    assert!(!observer.contains(&"report_to_user".to_string()));
+    assert!(observer.contains(&"parse_output".to_string()));
+
+    // Read-only mode survives a profile change and vice versa
+    agent.set_read_only(true);
+    agent.set_permission_profile(PermissionProfile::Operator);
+    let operator = names(&agent);
+    assert!(operator.contains(&"report_to_user".to_string()));
+    assert!(!operator.contains(&"track_files".to_string()));
+}
+
+#[test]
+fn test_progress_milestones_prefer_tracked_state() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("s1", AdapterType::ClaudeCode, memory, "test-key");
+    let output = vec!["Updated src/guess.rs".to_string()];
+
+    let milestones = agent.progress_milestones(&output);
+    assert_eq!(milestones.files_changed, vec!["src/guess.rs"]);
+    assert!(milestones.current_step.is_none());
+
+    agent.record_files_changed(vec!["src/lib.rs"]);
+    agent.state_mut().set_current_task("Add rate limiting");
+    let milestones = agent.progress_milestones(&output);
+    assert_eq!(milestones.files_changed, vec!["src/lib.rs"]);
+    assert_eq!(milestones.current_step.as_deref(), Some("Add rate limiting"));
+}
+
+#[tokio::test]
+async fn test_diagnose_error_without_error() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("s1", AdapterType::ClaudeCode, memory, "test-key");
+
+    let diagnosis = agent.diagnose_error("Compiling api\nFinished in 2.1s").await.unwrap();
+    assert!(diagnosis.is_none());
+    assert!(agent.last_diagnosed_error.is_none());
 }
 
 #[test]
@@ -212,6 +370,51 @@ async fn test_mock_memory_isolation() {
     assert_eq!(results2[0].memory.agent_id, "session-agent-2");
 }
 
+#[tokio::test]
+async fn test_prior_context_from_imported_history() {
+    let store = Arc::new(MockMemoryStore::new());
+    let imported = crate::history::imported_memory(
+        "session-agent-commander-web",
+        "Asked Claude Code: add rate limiting\nClaude Code: Added a token bucket",
+        vec![0.1; 64],
+        crate::history::CLAUDE_CODE_SOURCE,
+        "abc",
+        "m1",
+        chrono::Utc::now(),
+    );
+    store.store(imported).await.unwrap();
+
+    let mut agent = SessionAgent::with_api_key("commander-web", AdapterType::ClaudeCode, store, "test-key");
+    agent.load_prior_context().await.unwrap();
+    let messages = agent.build_messages("status?");
+    assert!(messages
+        .iter()
+        .any(|m| m.content.as_deref().is_some_and(|c| c.contains("token bucket"))));
+}
+
+#[test]
+fn test_task_budget_blocks_until_approved() {
+    let memory = Arc::new(MockMemoryStore::new());
+    let mut agent = SessionAgent::with_api_key("s1", AdapterType::ClaudeCode, memory, "test-key");
+    agent.start_task_budget();
+    assert!(agent.budget_usage().is_none());
+
+    agent.set_task_budget(TaskBudget { max_turns: Some(2), ..TaskBudget::default() });
+    agent.start_task_budget();
+    agent.budget.as_mut().unwrap().add(crate::budget::Spend { turns: 2, tokens: 800 });
+    assert!(agent.is_budget_exhausted());
+
+    assert!(matches!(agent.check_budget(), Err(AgentError::BudgetExhausted(_))));
+    assert!(agent.check_budget().is_err());
+    assert_eq!(agent.state().blockers.len(), 1);
+    assert!(agent.state().blockers[0].contains("turns 2/2"));
+
+    assert!(agent.approve_budget());
+    assert!(agent.check_budget().is_ok());
+    assert!(agent.state().blockers.is_empty());
+    assert!(!agent.approve_budget());
+}
+
 // ==========================================================================
 // Context Manager Tests
 // ==========================================================================
@@ -3,9 +3,11 @@
 use serde_json::json;
 use tracing::debug;
 
-use commander_memory::SearchResult;
+use commander_memory::{Memory, SearchResult, EMBEDDING_MODEL_KEY};
 
 use crate::error::{AgentError, Result};
+use crate::knowledge::{self, knowledge_agent_id, PROMOTE_MEMORY_TOOL};
+use crate::messaging::{send_agent_message_tool, AgentMessage, MessageKind};
 use crate::tool::{ToolCall, ToolDefinition, ToolResult};
 
 use super::SessionAgent;
@@ -16,7 +18,7 @@ impl SessionAgent {
         vec![
             ToolDefinition::new(
                 "search_memories",
-                "Search your own memories for relevant information (agent-isolated)",
+                "Search your own memories and your project's shared knowledge for relevant information",
                 json!({
                     "type": "object",
                     "properties": {
@@ -98,6 +100,29 @@ impl SessionAgent {
                     "required": ["summary"]
                 }),
             ),
+            ToolDefinition::new(
+                PROMOTE_MEMORY_TOOL,
+                "Share a fact that holds for the whole project (e.g. \"this repo uses pnpm, not npm\") \
+                 with the User Agent and future sessions. Pass the ID of one of your memories, or the fact itself",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "memory_id": {
+                            "type": "string",
+                            "description": "ID of your memory to promote (from search_memories)"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "The fact to promote, if it is not in your memories yet"
+                        },
+                        "reason": {
+                            "type": "string",
+                            "description": "Why this is useful beyond this session"
+                        }
+                    }
+                }),
+            ),
+            send_agent_message_tool(),
             ToolDefinition::new(
                 "analyze_output",
                 "Parse session output for progress indicators",
@@ -144,17 +169,102 @@ impl SessionAgent {
                 message: format!("Failed to generate embedding: {}", e),
             })?;
 
-        // Search memories - IMPORTANT: filtered by own agent_id for isolation
-        let results = self
+        // Search memories - IMPORTANT: filtered by own agent_id for isolation,
+        // plus the knowledge promoted for this project
+        let mut results = self
             .memory
             .search(&embedding, &self.id, limit)
             .await
             .map_err(AgentError::Memory)?;
+        results.extend(
+            self.memory
+                .search(&embedding, &knowledge_agent_id(&self.project), limit)
+                .await
+                .map_err(AgentError::Memory)?,
+        );
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit);
 
         let output = format_search_results(&results);
         Ok(ToolResult::success(&call.id, output))
     }
 
+    /// Execute the promote_memory tool: copy one of this agent's memories
+    /// (or a new fact) into the project's shared knowledge.
+    pub(super) async fn execute_promote_memory(&self, call: &ToolCall) -> Result<ToolResult> {
+        let invalid = |message: &str| AgentError::InvalidArguments {
+            tool_name: call.name.clone(),
+            message: message.to_string(),
+        };
+
+        let source = match (
+            call.get_optional_string_arg("memory_id"),
+            call.get_optional_string_arg("content"),
+        ) {
+            (Some(id), _) => {
+                let memory = self.memory.get(id).await.map_err(AgentError::Memory)?;
+                // Only this agent's own memories can be promoted
+                match memory.filter(|m| m.agent_id == self.id) {
+                    Some(memory) => memory,
+                    None => return Err(invalid(&format!("no memory '{}' in this session", id))),
+                }
+            }
+            (None, Some(content)) if !content.trim().is_empty() => {
+                let embedding = self.embedder.embed(content).await.map_err(|e| {
+                    AgentError::ToolExecution {
+                        tool_name: call.name.clone(),
+                        message: format!("Failed to generate embedding: {}", e),
+                    }
+                })?;
+                let memory = Memory::new(&self.id, content, embedding)
+                    .with_metadata(EMBEDDING_MODEL_KEY, self.embedder.model_id().into());
+                self.memory.store(memory.clone()).await.map_err(AgentError::Memory)?;
+                memory
+            }
+            _ => return Err(invalid("memory_id or content is required")),
+        };
+
+        let promoted = knowledge::promote(
+            &source,
+            &self.project,
+            &self.session_id,
+            call.get_optional_string_arg("reason"),
+        );
+        let id = promoted.id.clone();
+        self.memory.store(promoted).await.map_err(AgentError::Memory)?;
+
+        tracing::info!(
+            session = %self.session_id,
+            project = %self.project,
+            knowledge_id = %id,
+            "Promoted memory to project knowledge"
+        );
+        Ok(ToolResult::success(
+            &call.id,
+            format!("Promoted to {} project knowledge (id {}); awaiting user review.", self.project, id),
+        ))
+    }
+
+    /// Execute the send_agent_message tool: queue a message for the
+    /// orchestrator's bus.
+    pub(super) fn execute_send_agent_message(&self, call: &ToolCall) -> Result<ToolResult> {
+        let invalid = |message: String| AgentError::InvalidArguments {
+            tool_name: call.name.clone(),
+            message,
+        };
+        let kind: MessageKind = call.get_string_arg("kind").map_err(invalid)?.parse().map_err(invalid)?;
+        let body = call.get_string_arg("body").map_err(invalid)?;
+        let message = match call.get_optional_string_arg("to").filter(|to| !to.trim().is_empty()) {
+            Some(to) => AgentMessage::direct(&self.session_id, to.trim(), kind, body),
+            None => AgentMessage::broadcast(&self.session_id, kind, body),
+        };
+
+        let recipient = message.to.clone().unwrap_or_else(|| format!("{} subscribers", kind.as_str()));
+        self.outbox.lock().unwrap_or_else(|e| e.into_inner()).push(message);
+        debug!(session = %self.session_id, kind = kind.as_str(), %recipient, "Queued agent message");
+        Ok(ToolResult::success(&call.id, format!("Message queued for {}.", recipient)))
+    }
+
     /// Execute the update_session_state tool.
     /// Call this method directly when you have mutable access to the SessionAgent.
     pub fn execute_update_session_state(&mut self, call: &ToolCall) -> Result<ToolResult> {
@@ -274,11 +384,18 @@ pub(super) fn format_search_results(results: &[SearchResult]) -> String {
     let mut output = format!("Found {} relevant memories:\n\n", results.len());
 
     for (i, result) in results.iter().enumerate() {
+        let shared = if knowledge::is_knowledge(&result.memory) {
+            " [project knowledge]"
+        } else {
+            ""
+        };
         output.push_str(&format!(
-            "{}. [Score: {:.2}] {}\n   Created: {}\n\n",
+            "{}. [Score: {:.2}]{} {}\n   ID: {} | Created: {}\n\n",
             i + 1,
             result.score,
+            shared,
             result.memory.content,
+            result.memory.id,
             result.memory.created_at.format("%Y-%m-%d %H:%M:%S")
         ));
     }
@@ -0,0 +1,171 @@
+//! Structured agent output for machine consumption.
+//!
+//! With `[agents] structured_output = true`, the User Agent ends each final
+//! reply with a JSON result: its status, the actions it took, next steps,
+//! blockers and the files involved. The result is validated against
+//! [`result_schema`] and returned as the response's `structured_output`, next
+//! to the prose, so frontends can render it and automation can act on it.
+//! Replies whose JSON is missing or invalid stay plain text.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::{AgentError, Result};
+
+/// Instructions appended to the system prompt in structured output mode.
+pub const STRUCTURED_OUTPUT_PROMPT: &str = r#"End every final reply with a JSON result in a ```json code fence, after your answer:
+{"status": "completed" | "in_progress" | "blocked" | "failed", "summary": "<one sentence>", "actions_taken": ["..."], "next_steps": ["..."], "blockers": ["..."], "files": ["path/to/file"]}
+Use empty lists when there is nothing to report. Do not add other fields."#;
+
+/// Outcome of the request an agent answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultStatus {
+    Completed,
+    InProgress,
+    Blocked,
+    Failed,
+}
+
+impl ResultStatus {
+    /// Icon shown on result cards.
+    pub fn icon(self) -> &'static str {
+        match self {
+            ResultStatus::Completed => "✓",
+            ResultStatus::InProgress => "…",
+            ResultStatus::Blocked => "⏸",
+            ResultStatus::Failed => "✗",
+        }
+    }
+}
+
+impl std::fmt::Display for ResultStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ResultStatus::Completed => "completed",
+            ResultStatus::InProgress => "in progress",
+            ResultStatus::Blocked => "blocked",
+            ResultStatus::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A validated structured result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentResult {
+    pub status: ResultStatus,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub actions_taken: Vec<String>,
+    #[serde(default)]
+    pub next_steps: Vec<String>,
+    #[serde(default)]
+    pub blockers: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+impl AgentResult {
+    /// Validate `value` against [`result_schema`].
+    pub fn from_value(value: &Value) -> Result<Self> {
+        if !value.is_object() {
+            return Err(AgentError::ResponseParse("structured result is not an object".into()));
+        }
+        serde_json::from_value(value.clone())
+            .map_err(|e| AgentError::ResponseParse(format!("invalid structured result: {}", e)))
+    }
+
+    /// Lines of a result card, without the heading.
+    pub fn card_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} {}", self.status.icon(), self.status)];
+        if !self.summary.is_empty() {
+            lines[0].push_str(&format!(": {}", self.summary));
+        }
+        for (label, items) in [
+            ("Done", &self.actions_taken),
+            ("Next", &self.next_steps),
+            ("Blocked by", &self.blockers),
+            ("Files", &self.files),
+        ] {
+            if !items.is_empty() {
+                lines.push(format!("{}:", label));
+                lines.extend(items.iter().map(|item| format!("  - {}", item)));
+            }
+        }
+        lines
+    }
+}
+
+/// JSON Schema of structured results.
+pub fn result_schema() -> Value {
+    let list = json!({ "type": "array", "items": { "type": "string" } });
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "AgentResult",
+        "type": "object",
+        "required": ["status"],
+        "additionalProperties": false,
+        "properties": {
+            "status": { "enum": ["completed", "in_progress", "blocked", "failed"] },
+            "summary": { "type": "string" },
+            "actions_taken": list,
+            "next_steps": list,
+            "blockers": list,
+            "files": list,
+        },
+    })
+}
+
+/// Split a reply into its prose and its structured result.
+///
+/// The result is the last ```json fence of the reply. Returns `None` when
+/// there is none or it is not valid JSON; the result still needs validating.
+pub fn split_structured(content: &str) -> Option<(String, Value)> {
+    let start = content.rfind("```json")?;
+    let body_start = start + "```json".len();
+    let body_end = body_start + content[body_start..].find("```")?;
+    let value: Value = serde_json::from_str(content[body_start..body_end].trim()).ok()?;
+
+    let after = &content[body_end + 3..];
+    let prose = format!("{}{}", content[..start].trim_end(), after.trim_end());
+    Some((prose.trim().to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_validate_result() {
+        let reply = "Tests pass now.\n\n```json\n{\"status\": \"completed\", \"summary\": \"Fixed login\", \
+                     \"actions_taken\": [\"Patched auth.rs\"], \"files\": [\"src/auth.rs\"]}\n```\n";
+        let (prose, value) = split_structured(reply).unwrap();
+        assert_eq!(prose, "Tests pass now.");
+
+        let result = AgentResult::from_value(&value).unwrap();
+        assert_eq!(result.status, ResultStatus::Completed);
+        assert_eq!(result.files, ["src/auth.rs"]);
+        assert!(result.next_steps.is_empty());
+        assert_eq!(result.card_lines()[0], "✓ completed: Fixed login");
+
+        assert!(split_structured("No result here.").is_none());
+        assert!(split_structured("```json\nnot json\n```").is_none());
+    }
+
+    #[test]
+    fn test_invalid_results_rejected() {
+        for value in [
+            json!("completed"),
+            json!({ "summary": "no status" }),
+            json!({ "status": "done" }),
+            json!({ "status": "completed", "extra": 1 }),
+            json!({ "status": "completed", "files": "src/main.rs" }),
+        ] {
+            assert!(AgentResult::from_value(&value).is_err(), "accepted {}", value);
+        }
+        assert_eq!(result_schema()["required"], json!(["status"]));
+    }
+}
@@ -38,6 +38,7 @@ use std::path::Path;
 
 use crate::context_manager::ContextStrategy;
 use crate::error::{AgentError, Result};
+use crate::eval::{EvalTarget, ImprovementStore};
 use crate::tool::ToolDefinition;
 
 use prompts::{CLAUDE_CODE_SYSTEM_PROMPT, GENERIC_SYSTEM_PROMPT, MPM_SYSTEM_PROMPT};
@@ -205,6 +206,18 @@ impl TemplateRegistry {
         self
     }
 
+    /// Use the active prompt versions from `store` in place of the built-in
+    /// system prompts (see [`crate::eval::patches`]).
+    pub fn with_applied_prompts(mut self, store: &ImprovementStore) -> Self {
+        for template in self.templates.values_mut() {
+            let target = EvalTarget::Session(template.adapter_type.clone());
+            if let Some(prompt) = store.active_prompt(&target) {
+                template.system_prompt = prompt;
+            }
+        }
+        self
+    }
+
     /// Get a template by adapter type.
     pub fn get(&self, adapter_type: &AdapterType) -> Option<&AgentTemplate> {
         self.templates.get(adapter_type)
@@ -0,0 +1,160 @@
+//! Model-specific token counting.
+//!
+//! Token counts drive context usage percentages and compaction triggers, so
+//! they should match what the model actually sees. OpenAI-family models use
+//! their exact BPE encodings via `tiktoken-rs`. Anthropic does not publish
+//! Claude's tokenizer; Claude models use `cl100k_base` counts scaled by
+//! [`CLAUDE_TOKEN_RATIO`], which tracks Claude's counts closely and errs on
+//! the high side so compaction triggers early rather than late.
+//!
+//! # Example
+//!
+//! ```
+//! use commander_agent::tokenizer::{Tokenizer, TokenizerKind};
+//!
+//! let tokenizer = Tokenizer::for_model("openai/gpt-4o");
+//! assert_eq!(tokenizer.kind(), TokenizerKind::O200k);
+//! assert!(tokenizer.count("Hello, world!") > 0);
+//! ```
+
+use std::fmt;
+
+use tiktoken_rs::{cl100k_base_singleton, o200k_base_singleton};
+
+/// Ratio of Claude tokens to `cl100k_base` tokens for typical code and prose.
+pub const CLAUDE_TOKEN_RATIO: f64 = 1.1;
+
+/// Token encoding used for a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizerKind {
+    /// `cl100k_base` (GPT-4, GPT-3.5, and the fallback for unknown models).
+    Cl100k,
+    /// `o200k_base` (GPT-4o, GPT-4.1, o-series).
+    O200k,
+    /// Approximation of Claude's tokenizer from `cl100k_base` counts.
+    ClaudeApprox,
+}
+
+impl fmt::Display for TokenizerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenizerKind::Cl100k => write!(f, "cl100k_base"),
+            TokenizerKind::O200k => write!(f, "o200k_base"),
+            TokenizerKind::ClaudeApprox => write!(f, "claude (approx)"),
+        }
+    }
+}
+
+/// Counts tokens the way a specific model does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tokenizer {
+    kind: TokenizerKind,
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new(TokenizerKind::Cl100k)
+    }
+}
+
+impl Tokenizer {
+    /// Create a tokenizer for an encoding.
+    pub fn new(kind: TokenizerKind) -> Self {
+        Self { kind }
+    }
+
+    /// Pick the tokenizer for a model ID such as `anthropic/claude-haiku-4`.
+    ///
+    /// Provider prefixes (OpenRouter style) are ignored.
+    pub fn for_model(model: &str) -> Self {
+        let name = model_name(model);
+        let kind = if name.contains("claude") {
+            TokenizerKind::ClaudeApprox
+        } else if name.starts_with("gpt-4o")
+            || name.starts_with("gpt-4.1")
+            || name.starts_with("gpt-5")
+            || is_o_series(&name)
+        {
+            TokenizerKind::O200k
+        } else {
+            TokenizerKind::Cl100k
+        };
+        Self::new(kind)
+    }
+
+    /// The encoding in use.
+    pub fn kind(&self) -> TokenizerKind {
+        self.kind
+    }
+
+    /// Count the tokens in `text`.
+    pub fn count(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        match self.kind {
+            TokenizerKind::Cl100k => cl100k_base_singleton().encode_ordinary(text).len(),
+            TokenizerKind::O200k => o200k_base_singleton().encode_ordinary(text).len(),
+            TokenizerKind::ClaudeApprox => {
+                let base = cl100k_base_singleton().encode_ordinary(text).len();
+                (base as f64 * CLAUDE_TOKEN_RATIO).ceil() as usize
+            }
+        }
+    }
+}
+
+/// Count the tokens in `text` for `model`.
+pub fn count_tokens(model: &str, text: &str) -> usize {
+    Tokenizer::for_model(model).count(text)
+}
+
+/// Lowercased model name without a provider prefix.
+pub(crate) fn model_name(model: &str) -> String {
+    model
+        .rsplit('/')
+        .next()
+        .unwrap_or(model)
+        .to_lowercase()
+}
+
+/// OpenAI reasoning models (`o1`, `o3-mini`, `o4-mini`, ...).
+fn is_o_series(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next() == Some('o') && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_model() {
+        assert_eq!(Tokenizer::for_model("anthropic/claude-haiku-4").kind(), TokenizerKind::ClaudeApprox);
+        assert_eq!(Tokenizer::for_model("claude-3-5-sonnet-20241022").kind(), TokenizerKind::ClaudeApprox);
+        assert_eq!(Tokenizer::for_model("openai/gpt-4o-mini").kind(), TokenizerKind::O200k);
+        assert_eq!(Tokenizer::for_model("o3-mini").kind(), TokenizerKind::O200k);
+        assert_eq!(Tokenizer::for_model("gpt-4-turbo").kind(), TokenizerKind::Cl100k);
+        assert_eq!(Tokenizer::for_model("meta-llama/llama-3.1-70b").kind(), TokenizerKind::Cl100k);
+        assert_eq!(Tokenizer::for_model("ollama").kind(), TokenizerKind::Cl100k);
+    }
+
+    #[test]
+    fn test_exact_counts() {
+        let cl100k = Tokenizer::new(TokenizerKind::Cl100k);
+        assert_eq!(cl100k.count(""), 0);
+        assert_eq!(cl100k.count("hello world"), 2);
+
+        let o200k = Tokenizer::new(TokenizerKind::O200k);
+        assert_eq!(o200k.count("hello world"), 2);
+    }
+
+    #[test]
+    fn test_claude_approximation_is_conservative() {
+        let text = "fn main() {\n    println!(\"Context accounting for Commander sessions\");\n}\n";
+        let base = Tokenizer::new(TokenizerKind::Cl100k).count(text);
+        let claude = Tokenizer::new(TokenizerKind::ClaudeApprox).count(text);
+        assert!(claude > base);
+        assert_eq!(claude, (base as f64 * CLAUDE_TOKEN_RATIO).ceil() as usize);
+        assert_eq!(count_tokens("anthropic/claude-sonnet-4", text), claude);
+    }
+}
@@ -21,6 +21,7 @@ impl UserAgent {
     /// drives work forward, only stopping when:
     /// - All goals are complete
     /// - A blocker requires user input
+    /// - The task's budget (turns, tokens, time) is spent
     /// - Maximum iterations reached (safety limit)
     ///
     /// # Example
@@ -44,10 +45,14 @@ impl UserAgent {
 
         // Initialize completion driver
         let mut driver = CompletionDriver::new();
+        driver.set_budget(self.task_budget);
+        let mut spent = self.spend;
 
         // Parse initial request into goals
         let goals = self.parse_goals(initial_request).await?;
         driver.set_goals(goals);
+        driver.record_spend(self.spend.since(spent));
+        spent = self.spend;
 
         info!("Parsed {} goals from request", driver.goals().len());
 
@@ -78,6 +83,8 @@ impl UserAgent {
                         }
                     }
 
+                    driver.record_spend(self.spend.since(spent));
+                    spent = self.spend;
                     driver.increment_iteration();
                 }
                 ContinueDecision::StopForUser { reason, blockers } => {
@@ -105,7 +112,8 @@ impl UserAgent {
 
     /// Resume autonomous processing after user provides input.
     ///
-    /// Call this after receiving user input that resolves blockers.
+    /// Call this after receiving user input that resolves blockers. Resuming
+    /// also approves continuing a task whose budget is spent.
     pub async fn resume_autonomous(
         &mut self,
         user_input: &str,
@@ -116,19 +124,30 @@ impl UserAgent {
         // Clear blockers since user provided input
         driver.clear_blockers();
         driver.reset_iterations();
+        driver.approve_budget();
 
         // Process the user input to update context
+        let mut spent = self.spend;
         let context = self.context.clone();
         let _ = self.process(user_input, &context).await?;
+        driver.record_spend(self.spend.since(spent));
+        spent = self.spend;
 
         // Continue autonomous processing
         loop {
             match driver.should_continue() {
                 ContinueDecision::Continue => {
-                    let action_result = self.execute_next_action(driver).await;
-                    if let Ok(Some(blocker)) = action_result {
-                        driver.add_blocker(blocker);
+                    match self.execute_next_action(driver).await {
+                        Ok(Some(blocker)) => driver.add_blocker(blocker),
+                        Ok(None) => {}
+                        Err(e) => {
+                            if let Some(blocker) = self.classify_error_as_blocker(&e) {
+                                driver.add_blocker(blocker);
+                            }
+                        }
                     }
+                    driver.record_spend(self.spend.since(spent));
+                    spent = self.spend;
                     driver.increment_iteration();
                 }
                 ContinueDecision::StopForUser { reason, blockers } => {
@@ -173,6 +192,7 @@ Goals:"#,
         ];
 
         let response = self.client.chat(&self.config, messages, None).await?;
+        self.spend.record(&response);
 
         let content = response
             .message()
@@ -221,9 +241,15 @@ Goals:"#,
 
         debug!("Working on goal: {}", next_goal);
 
-        // Generate action for this goal
+        // Generate action for this goal, with overdue work taking precedence
+        let overdue = if self.overdue_work.is_empty() {
+            ""
+        } else {
+            "Some work is overdue (listed in your context). Address it before this goal \
+             unless the goal is part of it.\n\n"
+        };
         let action_prompt = format!(
-            r#"You are working on this goal: {}
+            r#"{}You are working on this goal: {}
 
 Current progress:
 {}
@@ -233,6 +259,7 @@ If this goal is complete, say "[GOAL COMPLETE]".
 If you're blocked and need user input, say "[BLOCKED]" followed by what you need.
 
 What is your next action?"#,
+            overdue,
             next_goal,
             driver.format_progress()
         );
@@ -15,6 +15,9 @@ impl UserAgent {
             AgentError::Configuration(msg) => {
                 Some(Blocker::external(format!("Configuration error: {}", msg)))
             }
+            AgentError::ApprovalRequired { command, reason } => Some(Blocker::approval(
+                format!("Approval required for `{}`: {}", command, reason),
+            )),
             AgentError::MaxIterationsExceeded(_) => Some(Blocker::new(
                 "Maximum iterations reached - may need guidance",
                 BlockerType::DecisionNeeded,
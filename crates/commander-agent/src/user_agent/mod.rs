@@ -11,6 +11,44 @@
 //! - All goals are complete
 //! - A blocker requires user input
 //! - Maximum iterations reached (safety limit)
+//!
+//! ## Guardrails
+//!
+//! Every tool call is checked against [`Guardrails`] before execution.
+//! Denied commands are reported back to the model as tool errors; commands
+//! requiring approval pause processing with [`AgentError::ApprovalRequired`]
+//! until the user calls [`UserAgent::approve_pending`] or
+//! [`UserAgent::deny_pending`].
+//!
+//! Delegation to a project in approval mode is not run at all: the call is
+//! added to the shared approval queue and runs once the user approves it.
+//!
+//! ## Permissions
+//!
+//! [`PermissionSettings`] pick a profile per project (see
+//! [`crate::permissions`]). Delegation is checked against the target
+//! project's profile, other tools against the default one. Delegation an
+//! `operator` profile holds for approval goes to the approval queue too.
+//!
+//! ## Planning
+//!
+//! The `plan` tool breaks a large goal into a [`Plan`] of dependent tasks.
+//! The plan is not run; it is kept until the caller collects it with
+//! [`UserAgent::take_proposed_plan`] and shows it to the user for approval.
+//!
+//! ## Peer Review
+//!
+//! With a [`Reviewer`] set, each task the User Agent delegates is critiqued
+//! by a second model first. A task with flagged issues is returned to the
+//! model with the critique to revise, up to the reviewer's round limit per
+//! turn; after that it is delegated with the critique attached.
+//!
+//! ## Overdue Work
+//!
+//! Work items past their SLA deadline are passed in with
+//! [`UserAgent::set_overdue_work`]. They are listed ahead of everything else
+//! in the context, and autonomous execution is told to address them before
+//! its own goals.
 
 mod autonomous;
 mod blockers;
@@ -19,18 +57,30 @@ mod tools;
 mod tests;
 
 use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
 
-use commander_memory::{EmbeddingGenerator, Memory, MemoryStore};
+use commander_core::{approvals, PermissionProfile, PermissionSettings};
+use commander_memory::{EmbeddingGenerator, Memory, MemoryFilter, MemoryStore, EMBEDDING_MODEL_KEY};
 
 use crate::agent::{Agent, AgentType};
-use crate::client::{ChatMessage, ChatTool, OpenRouterClient};
+use crate::budget::{Spend, TaskBudget};
+use crate::catalog::validate_model;
+use crate::client::{ChatMessage, ChatTool, ClientMetrics, KeyUsage, OpenRouterClient};
 use crate::completion_driver::CompletionDriver;
 use crate::config::ModelConfig;
 use crate::context::{AgentContext, Message};
 use crate::error::{AgentError, Result};
+use crate::eval::{EvalTarget, ImprovementStore};
+use crate::guardrails::{GuardrailDecision, Guardrails, PendingApproval};
+use crate::permissions::{self, PermissionDecision};
+use crate::planner::{Plan, Planner, PLAN_TOOL};
 use crate::response::AgentResponse;
+use crate::review::{Critique, Reviewer};
+use crate::router::{Complexity, ModelRouter};
+use crate::structured::{split_structured, AgentResult, STRUCTURED_OUTPUT_PROMPT};
 use crate::tool::{ToolCall, ToolDefinition, ToolResult};
 
 /// Maximum iterations in the tool calling loop.
@@ -83,6 +133,7 @@ You have access to tools for:
 - Searching memories for relevant context
 - Delegating tasks to session agents
 - Querying session status
+- Planning large goals as dependent tasks (the plan is shown to the user for approval)
 
 Use these proactively to drive work forward."#;
 
@@ -121,12 +172,51 @@ pub struct UserAgent {
 
     /// Completion driver for autonomous execution.
     pub(crate) completion_driver: Option<CompletionDriver>,
+
+    /// Command guardrails checked before each tool call.
+    pub(crate) guardrails: Guardrails,
+
+    /// Command currently awaiting user approval.
+    pub(crate) pending_approval: Option<PendingApproval>,
+
+    /// Commands approved by the user (consumed on next use).
+    pub(crate) approved_commands: HashSet<String>,
+
+    /// Plan produced by the `plan` tool, awaiting user approval.
+    pub(crate) proposed_plan: Option<Plan>,
+
+    /// Picks the model per request when routing is enabled.
+    pub(crate) router: Option<ModelRouter>,
+
+    /// Agents whose memories searches are limited to (the active workspace).
+    pub(crate) memory_scope: Option<MemoryFilter>,
+
+    /// Overdue work items, most overdue first, one line each.
+    pub(crate) overdue_work: Vec<String>,
+
+    /// Tool permission profiles, per project.
+    pub(crate) permissions: PermissionSettings,
+
+    /// Whether final replies end with a validated JSON result.
+    pub(crate) structured_output: bool,
+
+    /// Limits on each autonomous task.
+    pub(crate) task_budget: TaskBudget,
+
+    /// LLM requests and tokens used so far.
+    pub(crate) spend: Spend,
+
+    /// Critiques delegated tasks, when peer review is enabled.
+    pub(crate) reviewer: Option<Reviewer>,
+
+    /// Delegated tasks sent back for revision in the current turn.
+    pub(crate) review_rounds: AtomicU32,
 }
 
 impl UserAgent {
     /// Create a new User Agent with the given memory store.
     pub fn new(memory: Arc<dyn MemoryStore>) -> Result<Self> {
-        let client = OpenRouterClient::from_env()?;
+        let client = OpenRouterClient::from_env()?.for_agent("user-agent");
         let embedder = EmbeddingGenerator::from_env();
 
         Ok(Self {
@@ -138,12 +228,29 @@ impl UserAgent {
             client,
             context: AgentContext::new(),
             completion_driver: None,
+            guardrails: Guardrails::load_or_default(),
+            pending_approval: None,
+            approved_commands: HashSet::new(),
+            proposed_plan: None,
+            router: None,
+            memory_scope: None,
+            overdue_work: Vec::new(),
+            permissions: PermissionSettings::default(),
+            structured_output: false,
+            task_budget: TaskBudget::default(),
+            spend: Spend::default(),
+            reviewer: None,
+            review_rounds: AtomicU32::new(0),
         })
     }
 
     /// Create a User Agent with custom configuration.
+    ///
+    /// Fails when the model is missing from the cached OpenRouter catalog,
+    /// suggesting close model IDs.
     pub fn with_config(memory: Arc<dyn MemoryStore>, config: ModelConfig) -> Result<Self> {
-        let client = OpenRouterClient::from_env()?;
+        validate_model(&config)?;
+        let client = OpenRouterClient::from_env()?.for_agent("user-agent");
         let embedder = EmbeddingGenerator::from_env();
 
         Ok(Self {
@@ -155,6 +262,19 @@ impl UserAgent {
             client,
             context: AgentContext::new(),
             completion_driver: None,
+            guardrails: Guardrails::load_or_default(),
+            pending_approval: None,
+            approved_commands: HashSet::new(),
+            proposed_plan: None,
+            router: None,
+            memory_scope: None,
+            overdue_work: Vec::new(),
+            permissions: PermissionSettings::default(),
+            structured_output: false,
+            task_budget: TaskBudget::default(),
+            spend: Spend::default(),
+            reviewer: None,
+            review_rounds: AtomicU32::new(0),
         })
     }
 
@@ -172,17 +292,34 @@ impl UserAgent {
             client,
             context: AgentContext::new(),
             completion_driver: None,
+            guardrails: Guardrails::load_or_default(),
+            pending_approval: None,
+            approved_commands: HashSet::new(),
+            proposed_plan: None,
+            router: None,
+            memory_scope: None,
+            overdue_work: Vec::new(),
+            permissions: PermissionSettings::default(),
+            structured_output: false,
+            task_budget: TaskBudget::default(),
+            spend: Spend::default(),
+            reviewer: None,
+            review_rounds: AtomicU32::new(0),
         }
     }
 
-    /// Get the default model configuration for User Agent.
+    /// Get the default model configuration for User Agent, with the active
+    /// version of its prompt.
     pub(crate) fn default_config() -> ModelConfig {
+        let system_prompt = ImprovementStore::open_default()
+            .active_prompt(&EvalTarget::User)
+            .unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.to_string());
         ModelConfig {
             model: "anthropic/claude-opus-4".to_string(),
             max_tokens: 4096,
             temperature: 0.7,
             provider: crate::config::Provider::OpenRouter,
-            system_prompt: Some(DEFAULT_SYSTEM_PROMPT.to_string()),
+            system_prompt: Some(system_prompt),
             api_key: None,
         }
     }
@@ -198,6 +335,14 @@ impl UserAgent {
             .as_deref()
             .unwrap_or(DEFAULT_SYSTEM_PROMPT);
         messages.push(ChatMessage::system(system_prompt));
+        if self.structured_output {
+            messages.push(ChatMessage::system(STRUCTURED_OUTPUT_PROMPT));
+        }
+
+        // Overdue work comes first so it is weighed before anything else
+        if let Some(overdue) = self.overdue_work_prompt() {
+            messages.push(ChatMessage::system(overdue));
+        }
 
         // Add summarized history if available
         if !self.context.summarized_history.is_empty() {
@@ -247,18 +392,241 @@ impl UserAgent {
         self.completion_driver = None;
     }
 
-    /// Store a memory from the conversation.
-    pub async fn store_memory(&self, content: &str) -> Result<()> {
-        let embedding = self
-            .embedder
-            .embed(content)
+    /// Get the active guardrails.
+    pub fn guardrails(&self) -> &Guardrails {
+        &self.guardrails
+    }
+
+    /// Replace the active guardrails.
+    pub fn set_guardrails(&mut self, guardrails: Guardrails) {
+        self.guardrails = guardrails;
+    }
+
+    /// Switch the model used for later LLM calls.
+    ///
+    /// `None` restores the default model. A model missing from the cached
+    /// OpenRouter catalog is still used, with a warning.
+    pub fn set_model(&mut self, model: Option<&str>) {
+        self.config.model = match model {
+            Some(model) => model.to_string(),
+            None => Self::default_config().model,
+        };
+        if let Err(e) = validate_model(&self.config) {
+            warn!(error = %e, "User Agent model not in catalog");
+        }
+    }
+
+    /// Route each request to a model tier by complexity, or `None` to always
+    /// use the configured model.
+    pub fn set_router(&mut self, router: Option<ModelRouter>) {
+        self.router = router;
+    }
+
+    /// Ask for a JSON result at the end of each final reply, returned as the
+    /// response's structured output (see [`crate::structured`]).
+    pub fn set_structured_output(&mut self, enabled: bool) {
+        self.structured_output = enabled;
+    }
+
+    /// Limit each autonomous task to `budget` (see [`crate::budget`]).
+    pub fn set_task_budget(&mut self, budget: TaskBudget) {
+        self.task_budget = budget;
+    }
+
+    /// Have `reviewer` critique drafts before they are sent, or `None` to
+    /// send them unreviewed (see [`crate::review`]).
+    pub fn set_reviewer(&mut self, reviewer: Option<Reviewer>) {
+        self.reviewer = reviewer;
+    }
+
+    /// Reviewer of drafts, when peer review is enabled.
+    pub fn reviewer(&self) -> Option<&Reviewer> {
+        self.reviewer.as_ref()
+    }
+
+    /// Critique `draft` with the reviewer. Returns `None` when peer review
+    /// is disabled.
+    pub async fn review_draft(&self, kind: &str, draft: &str) -> Result<Option<Critique>> {
+        let Some(reviewer) = &self.reviewer else {
+            return Ok(None);
+        };
+        reviewer
+            .critique(&self.client, &self.config, kind, draft)
             .await
-            .map_err(|e| AgentError::ToolExecution {
-                tool_name: "store_memory".to_string(),
-                message: format!("Failed to generate embedding: {}", e),
-            })?;
+            .map(Some)
+    }
+
+    /// Limit memory searches to the agents in `scope` (for example the
+    /// projects of a workspace), or `None` to search every agent.
+    pub fn set_memory_scope(&mut self, scope: Option<MemoryFilter>) {
+        self.memory_scope = scope;
+    }
+
+    /// The current memory search scope.
+    pub fn memory_scope(&self) -> Option<&MemoryFilter> {
+        self.memory_scope.as_ref()
+    }
+
+    /// Get the tool permission profiles.
+    pub fn permissions(&self) -> &PermissionSettings {
+        &self.permissions
+    }
+
+    /// Replace the tool permission profiles, which strips the tools no
+    /// profile allows.
+    pub fn set_permissions(&mut self, settings: PermissionSettings) {
+        let mut tools = tools::default_tools();
+        tools.retain(|tool| {
+            if tool.name == approvals::DELEGATE_TOOL {
+                std::iter::once(&settings.default)
+                    .chain(settings.projects.values())
+                    .any(|profile| permissions::is_permitted(*profile, &tool.name))
+            } else {
+                permissions::is_permitted(settings.default, &tool.name)
+            }
+        });
+        self.tools = tools;
+        self.permissions = settings;
+    }
 
-        let memory = Memory::new(&self.id, content, embedding);
+    /// Project whose profile applies to `call` (the delegation target), if any.
+    pub(crate) fn permission_project<'a>(&self, call: &'a ToolCall) -> Option<&'a str> {
+        if call.name != approvals::DELEGATE_TOOL {
+            return None;
+        }
+        call.get_string_arg("session_id").ok()
+    }
+
+    /// Profile that applies to `call`.
+    pub(crate) fn permission_profile(&self, call: &ToolCall) -> PermissionProfile {
+        match self.permission_project(call) {
+            Some(project) => self.permissions.profile_for(project),
+            None => self.permissions.default,
+        }
+    }
+
+    /// Get the command currently awaiting approval, if any.
+    pub fn pending_approval(&self) -> Option<&PendingApproval> {
+        self.pending_approval.as_ref()
+    }
+
+    /// Approve the pending command so it runs on the next attempt.
+    ///
+    /// Returns the approved approval request, if one was pending.
+    pub fn approve_pending(&mut self) -> Option<PendingApproval> {
+        let approval = self.pending_approval.take()?;
+        info!(command = %approval.command, "Guarded command approved");
+        self.approved_commands.insert(approval.command.clone());
+        Some(approval)
+    }
+
+    /// Deny the pending command.
+    ///
+    /// Returns the denied approval request, if one was pending.
+    pub fn deny_pending(&mut self) -> Option<PendingApproval> {
+        let approval = self.pending_approval.take()?;
+        info!(command = %approval.command, "Guarded command denied");
+        Some(approval)
+    }
+
+    /// Check a tool call against the guardrails.
+    ///
+    /// Returns `Ok(Some(result))` when the call is denied (the result should be
+    /// reported to the model instead of executing), `Ok(None)` when the call may
+    /// run, and [`AgentError::ApprovalRequired`] when the user must approve it.
+    pub(crate) fn enforce_guardrails(&mut self, call: &ToolCall) -> Result<Option<ToolResult>> {
+        match self.guardrails.check_tool_call(call) {
+            GuardrailDecision::Allowed => Ok(None),
+            GuardrailDecision::Denied { command, reason } => {
+                warn!(command = %command, reason = %reason, "Guardrail denied command");
+                Ok(Some(ToolResult::error(
+                    &call.id,
+                    format!(
+                        "Command blocked by guardrails: {} ({}). Choose a different approach.",
+                        command, reason
+                    ),
+                )))
+            }
+            GuardrailDecision::NeedsApproval { command, reason } => {
+                if self.approved_commands.remove(&command) {
+                    debug!(command = %command, "Running previously approved command");
+                    return Ok(None);
+                }
+
+                self.pending_approval = Some(PendingApproval {
+                    tool_name: call.name.clone(),
+                    command: command.clone(),
+                    reason: reason.clone(),
+                });
+                Err(AgentError::ApprovalRequired { command, reason })
+            }
+        }
+    }
+
+    /// Queue the call for user approval when its project is in approval mode
+    /// or its permission profile requires approval.
+    ///
+    /// Returns the result reported to the model in place of running the call,
+    /// or `None` when the call may run now. Approved calls are run later by
+    /// the frontend (see [`commander_core::approvals`]).
+    pub(crate) fn queue_if_approval_mode(&self, call: &ToolCall) -> Option<ToolResult> {
+        let project = self.permission_project(call)?;
+        let profile = self.permissions.profile_for(project);
+        let why = match permissions::check(profile, &call.name) {
+            // Refused with an error by execute_tool
+            PermissionDecision::Denied => return None,
+            PermissionDecision::NeedsApproval => {
+                format!("project '{}' uses the {} permission profile", project, profile)
+            }
+            PermissionDecision::Allowed if approvals::approval_mode_enabled(project) => {
+                format!("project '{}' is in approval mode", project)
+            }
+            PermissionDecision::Allowed => return None,
+        };
+
+        let task = call.get_optional_string_arg("task").unwrap_or_default();
+        let summary = format!("Delegate to {}: {}", project, task);
+        match approvals::queue_approval(project, &call.name, call.arguments.clone(), summary) {
+            Ok(request) => {
+                info!(request = %request.id, project = %project, "Tool call queued for approval");
+                Some(ToolResult::success(
+                    &call.id,
+                    format!(
+                        "Queued for user approval as {} ({}). \
+                         It will run once approved; do not retry it.",
+                        request.id, why
+                    ),
+                ))
+            }
+            Err(e) => Some(ToolResult::error(
+                &call.id,
+                format!(
+                    "Project '{}' requires approval but the request could not be queued: {}",
+                    project, e
+                ),
+            )),
+        }
+    }
+
+    /// Store a memory from the conversation.
+    ///
+    /// Offline, the memory is stored without an embedding and embedded once
+    /// the network is back (see [`commander_memory::Backfill::with_pending_only`]).
+    pub async fn store_memory(&self, content: &str) -> Result<()> {
+        let memory = match self.embedder.embed(content).await {
+            Ok(embedding) => Memory::new(&self.id, content, embedding)
+                .with_metadata(EMBEDDING_MODEL_KEY, self.embedder.model_id().into()),
+            Err(e) if commander_core::connectivity::is_offline() => {
+                debug!(error = %e, "Offline, storing memory to embed later");
+                Memory::new(&self.id, content, Vec::new())
+            }
+            Err(e) => {
+                return Err(AgentError::ToolExecution {
+                    tool_name: "store_memory".to_string(),
+                    message: format!("Failed to generate embedding: {}", e),
+                })
+            }
+        };
         self.memory.store(memory).await.map_err(AgentError::Memory)?;
 
         debug!("Stored memory: {}", content.chars().take(50).collect::<String>());
@@ -275,6 +643,28 @@ impl UserAgent {
         self.context.clear_task();
     }
 
+    /// Set the overdue work items, most overdue first, one line each.
+    pub fn set_overdue_work(&mut self, items: Vec<String>) {
+        self.overdue_work = items;
+    }
+
+    /// Overdue work items set with [`set_overdue_work`](Self::set_overdue_work).
+    pub fn overdue_work(&self) -> &[String] {
+        &self.overdue_work
+    }
+
+    /// Context note listing the overdue work, if any.
+    pub(crate) fn overdue_work_prompt(&self) -> Option<String> {
+        if self.overdue_work.is_empty() {
+            return None;
+        }
+        let items: Vec<String> = self.overdue_work.iter().map(|item| format!("- {}", item)).collect();
+        Some(format!(
+            "Overdue work (past its deadline; address these before anything else):\n{}",
+            items.join("\n")
+        ))
+    }
+
     /// Get the conversation context.
     pub fn context(&self) -> &AgentContext {
         &self.context
@@ -284,6 +674,33 @@ impl UserAgent {
     pub fn context_mut(&mut self) -> &mut AgentContext {
         &mut self.context
     }
+
+    /// Break `goal` into a plan of dependent tasks.
+    pub async fn plan(&self, goal: &str, context: Option<&str>) -> Result<Plan> {
+        // Planning always gets the complex tier when routing is enabled
+        let mut config = self.config.clone();
+        if let Some(router) = &self.router {
+            config.model = router.model_for(Complexity::Complex).to_string();
+        }
+        Planner::new(self.client.clone(), config)
+            .plan(goal, context)
+            .await
+    }
+
+    /// Take the plan proposed by the `plan` tool, if any.
+    pub fn take_proposed_plan(&mut self) -> Option<Plan> {
+        self.proposed_plan.take()
+    }
+
+    /// Request metrics of the model client.
+    pub fn client_metrics(&self) -> ClientMetrics {
+        self.client.metrics()
+    }
+
+    /// Usage of each API key of the model client.
+    pub fn client_key_usage(&self) -> Vec<KeyUsage> {
+        self.client.key_usage()
+    }
 }
 
 #[async_trait]
@@ -300,6 +717,8 @@ impl Agent for UserAgent {
         let preview: String = message.chars().take(50).collect();
         info!("Processing message: {}...", preview);
 
+        self.review_rounds.store(0, Ordering::Relaxed);
+
         // Update internal context with provided context
         self.context.current_task = context.current_task.clone();
         self.context.summarized_history = context.summarized_history.clone();
@@ -315,6 +734,11 @@ impl Agent for UserAgent {
             .map(ChatTool::from_definition)
             .collect();
 
+        let config = match &self.router {
+            Some(router) => router.route_config(&self.client, &self.config, message).await,
+            None => self.config.clone(),
+        };
+
         // Tool calling loop
         let mut iteration = 0;
         loop {
@@ -328,8 +752,9 @@ impl Agent for UserAgent {
             // Send request to OpenRouter
             let response = self
                 .client
-                .chat(&self.config, messages.clone(), Some(chat_tools.clone()))
+                .chat(&config, messages.clone(), Some(chat_tools.clone()))
                 .await?;
+            self.spend.record(&response);
 
             // Check for tool calls
             if response.has_tool_calls() {
@@ -349,7 +774,23 @@ impl Agent for UserAgent {
 
                 // Execute each tool call
                 for call in &tool_calls {
-                    let result = self.execute_tool(call).await?;
+                    let result = match self.enforce_guardrails(call)? {
+                        Some(blocked) => blocked,
+                        None => match self.queue_if_approval_mode(call) {
+                            Some(queued) => queued,
+                            None if call.name == PLAN_TOOL => {
+                                let (plan, result) = tools::execute_plan(self, call).await?;
+                                self.proposed_plan = Some(plan);
+                                result
+                            }
+                            None => match self.execute_tool(call).await {
+                                Err(e @ AgentError::PermissionDenied { .. }) => {
+                                    ToolResult::error(&call.id, e.to_string())
+                                }
+                                result => result?,
+                            },
+                        },
+                    };
                     messages.push(ChatMessage::tool(&call.id, &result.content));
                 }
 
@@ -370,6 +811,9 @@ impl Agent for UserAgent {
             // Trim context if needed
             self.context.trim_recent(10);
 
+            if self.structured_output {
+                return Ok(structured_response(content));
+            }
             return Ok(AgentResponse::text(content));
         }
     }
@@ -382,11 +826,19 @@ impl Agent for UserAgent {
         debug!("Executing tool: {}", call.name);
         trace!("Tool arguments: {:?}", call.arguments);
 
+        permissions::enforce(
+            &self.id,
+            self.permission_project(call),
+            self.permission_profile(call),
+            &call.name,
+        )?;
+
         match call.name.as_str() {
             "search_all_memories" => tools::execute_search_all_memories(self, call).await,
             "search_memories" => tools::execute_search_memories(self, call).await,
             "delegate_to_session" => tools::execute_delegate_to_session(self, call).await,
             "get_session_status" => tools::execute_get_session_status(self, call).await,
+            PLAN_TOOL => tools::execute_plan(self, call).await.map(|(_, result)| result),
             _ => Err(AgentError::ToolNotFound(call.name.clone())),
         }
     }
@@ -399,3 +851,21 @@ impl Agent for UserAgent {
         &self.config
     }
 }
+
+/// Split a final reply into prose and a validated result. Replies without a
+/// valid result are returned as plain text.
+fn structured_response(content: String) -> AgentResponse {
+    match split_structured(&content) {
+        Some((prose, value)) => match AgentResult::from_value(&value) {
+            Ok(_) => AgentResponse::structured(prose, value),
+            Err(e) => {
+                warn!(error = %e, "Discarding invalid structured result");
+                AgentResponse::text(content)
+            }
+        },
+        None => {
+            warn!("Final reply has no structured result");
+            AgentResponse::text(content)
+        }
+    }
+}
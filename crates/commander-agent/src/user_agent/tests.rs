@@ -1,15 +1,22 @@
 //! Tests for the User Agent module.
 
 use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use commander_core::{PermissionProfile, PermissionSettings};
 use commander_memory::{Memory, MemoryStore, SearchResult};
 
+use crate::budget::{Spend, TaskBudget};
 use crate::client::OpenRouterClient;
 use crate::completion_driver::{BlockerType, CompletionDriver};
 use crate::context::AgentContext;
 use crate::error::AgentError;
+use crate::guardrails::Guardrails;
+use crate::tool::ToolCall;
 use commander_memory::EmbeddingGenerator;
 
 use super::tools::{default_tools, format_search_results};
@@ -109,6 +116,19 @@ fn create_test_agent_struct() -> UserAgent {
         client: OpenRouterClient::new("fake-key-for-testing"),
         context: AgentContext::new(),
         completion_driver: None,
+        guardrails: Guardrails::default(),
+        pending_approval: None,
+        approved_commands: HashSet::new(),
+        proposed_plan: None,
+        router: None,
+        memory_scope: None,
+        overdue_work: Vec::new(),
+        permissions: PermissionSettings::default(),
+        structured_output: false,
+        task_budget: TaskBudget::default(),
+        spend: Spend::default(),
+        reviewer: None,
+        review_rounds: AtomicU32::new(0),
     }
 }
 
@@ -123,13 +143,14 @@ fn test_default_config() {
 #[test]
 fn test_default_tools() {
     let tools = default_tools();
-    assert_eq!(tools.len(), 4);
+    assert_eq!(tools.len(), 5);
 
     let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
     assert!(tool_names.contains(&"search_all_memories"));
     assert!(tool_names.contains(&"search_memories"));
     assert!(tool_names.contains(&"delegate_to_session"));
     assert!(tool_names.contains(&"get_session_status"));
+    assert!(tool_names.contains(&"plan"));
 }
 
 #[test]
@@ -289,3 +310,121 @@ fn test_completion_driver_accessors() {
     agent.clear_completion_driver();
     assert!(agent.completion_driver().is_none());
 }
+
+#[test]
+fn test_overdue_work_listed_first() {
+    let mut agent = create_test_agent_struct();
+    assert!(agent.overdue_work_prompt().is_none());
+    assert_eq!(agent.build_messages("hi").len(), 2);
+
+    agent.set_overdue_work(vec!["[api] Fix login (overdue 2h 10m)".to_string()]);
+    let messages = agent.build_messages("hi");
+    assert_eq!(messages.len(), 3);
+    let overdue = messages[1].content.as_deref().unwrap();
+    assert!(overdue.starts_with("Overdue work"));
+    assert!(overdue.contains("- [api] Fix login (overdue 2h 10m)"));
+}
+
+#[test]
+fn test_structured_output_mode() {
+    let mut agent = create_test_agent_struct();
+    agent.set_structured_output(true);
+    let messages = agent.build_messages("hi");
+    assert_eq!(messages.len(), 3);
+    assert!(messages[1].content.as_deref().unwrap().contains("```json"));
+
+    let response = super::structured_response(
+        "Done.\n```json\n{\"status\": \"blocked\", \"blockers\": [\"Needs API key\"]}\n```".to_string(),
+    );
+    assert_eq!(response.content, "Done.");
+    assert_eq!(response.structured_output.unwrap()["blockers"], json!(["Needs API key"]));
+
+    let invalid = "Done.\n```json\n{\"status\": \"maybe\"}\n```".to_string();
+    let response = super::structured_response(invalid.clone());
+    assert_eq!(response.content, invalid);
+    assert!(response.structured_output.is_none());
+}
+
+// ==================== Guardrail Tests ====================
+
+#[test]
+fn test_guardrails_deny_returns_tool_error() {
+    let mut agent = create_test_agent_struct();
+    let call = ToolCall::new(
+        "delegate_to_session",
+        json!({"session_id": "proj", "task": "rm -rf /"}),
+    );
+
+    let result = agent.enforce_guardrails(&call).unwrap();
+    let result = result.expect("denied call should produce a tool result");
+    assert!(result.is_error);
+    assert!(result.content.contains("blocked by guardrails"));
+    assert!(agent.pending_approval().is_none());
+}
+
+#[test]
+fn test_guardrails_approval_flow() {
+    let mut agent = create_test_agent_struct();
+    let call = ToolCall::new(
+        "delegate_to_session",
+        json!({"session_id": "proj", "task": "git push --force"}),
+    );
+
+    // First attempt pauses for approval
+    let err = agent.enforce_guardrails(&call).unwrap_err();
+    assert!(matches!(err, AgentError::ApprovalRequired { .. }));
+    assert_eq!(
+        agent.pending_approval().map(|p| p.command.as_str()),
+        Some("git push --force")
+    );
+
+    // The error becomes an approval blocker in autonomous mode
+    let blocker = agent.classify_error_as_blocker(&err).unwrap();
+    assert_eq!(blocker.blocker_type, BlockerType::ApprovalRequired);
+
+    // After approval the command runs exactly once
+    assert!(agent.approve_pending().is_some());
+    assert!(agent.enforce_guardrails(&call).unwrap().is_none());
+    assert!(agent.enforce_guardrails(&call).is_err());
+}
+
+#[test]
+fn test_guardrails_deny_pending_clears_request() {
+    let mut agent = create_test_agent_struct();
+    let call = ToolCall::new(
+        "delegate_to_session",
+        json!({"session_id": "proj", "task": "git reset --hard"}),
+    );
+
+    assert!(agent.enforce_guardrails(&call).is_err());
+    let denied = agent.deny_pending().unwrap();
+    assert_eq!(denied.tool_name, "delegate_to_session");
+    assert!(agent.pending_approval().is_none());
+    assert!(agent.enforce_guardrails(&call).is_err());
+}
+
+#[test]
+fn test_permission_profiles_follow_delegation_target() {
+    let mut agent = create_test_agent_struct();
+    let names = |agent: &UserAgent| -> Vec<String> { agent.tools.iter().map(|t| t.name.clone()).collect() };
+
+    let mut settings = PermissionSettings {
+        default: PermissionProfile::Observer,
+        ..Default::default()
+    };
+    agent.set_permissions(settings.clone());
+    assert!(!names(&agent).contains(&"delegate_to_session".to_string()));
+    assert!(names(&agent).contains(&"search_memories".to_string()));
+
+    // One project that may be delegated to keeps the tool on offer
+    settings.projects.insert("web".to_string(), PermissionProfile::Operator);
+    agent.set_permissions(settings);
+    assert!(names(&agent).contains(&"delegate_to_session".to_string()));
+
+    let delegate = |project: &str| {
+        ToolCall::new("delegate_to_session", json!({"session_id": project, "task": "run tests"}))
+    };
+    assert_eq!(agent.permission_profile(&delegate("web")), PermissionProfile::Operator);
+    assert_eq!(agent.permission_profile(&delegate("api")), PermissionProfile::Observer);
+    assert!(agent.queue_if_approval_mode(&delegate("api")).is_none());
+}
@@ -3,12 +3,16 @@
 //! Contains the default tools available to the User Agent and their
 //! execution implementations.
 
+use std::sync::atomic::Ordering;
+
 use serde_json::json;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use commander_memory::SearchResult;
 
 use crate::error::{AgentError, Result};
+use crate::planner::{Plan, PLAN_TOOL};
+use crate::review::Reviewer;
 use crate::tool::{ToolCall, ToolDefinition, ToolResult};
 
 use super::UserAgent;
@@ -47,7 +51,7 @@ pub(crate) fn default_tools() -> Vec<ToolDefinition> {
                     },
                     "agent_id": {
                         "type": "string",
-                        "description": "The agent ID to search within"
+                        "description": "The agent ID to search within (shared project knowledge is under project-knowledge-<project>)"
                     },
                     "limit": {
                         "type": "integer",
@@ -80,6 +84,24 @@ pub(crate) fn default_tools() -> Vec<ToolDefinition> {
                 "required": ["session_id", "task"]
             }),
         ),
+        ToolDefinition::new(
+            PLAN_TOOL,
+            "Break a large goal into a plan of dependent tasks for the user to approve",
+            json!({
+                "type": "object",
+                "properties": {
+                    "goal": {
+                        "type": "string",
+                        "description": "The goal to plan, e.g. 'add OAuth to this app'"
+                    },
+                    "context": {
+                        "type": "string",
+                        "description": "Relevant project details (stack, layout, constraints)"
+                    }
+                },
+                "required": ["goal"]
+            }),
+        ),
         ToolDefinition::new(
             "get_session_status",
             "Query the current status of a session agent",
@@ -126,12 +148,12 @@ pub(crate) async fn execute_search_all_memories(
             message: format!("Failed to generate embedding: {}", e),
         })?;
 
-    // Search memories
-    let results = agent
-        .memory
-        .search_all(&embedding, limit)
-        .await
-        .map_err(AgentError::Memory)?;
+    // Search memories, within the workspace when one is active
+    let results = match &agent.memory_scope {
+        Some(scope) => agent.memory.search_matching(&embedding, scope, limit).await,
+        None => agent.memory.search_all(&embedding, limit).await,
+    }
+    .map_err(AgentError::Memory)?;
 
     let output = format_search_results(&results);
     Ok(ToolResult::success(&call.id, output))
@@ -166,6 +188,13 @@ pub(crate) async fn execute_search_memories(
         agent_id, query, limit
     );
 
+    if agent.memory_scope.as_ref().is_some_and(|scope| !scope.agent_ids.iter().any(|a| a == agent_id)) {
+        return Ok(ToolResult::success(
+            &call.id,
+            format!("Agent '{}' is outside the current workspace.", agent_id),
+        ));
+    }
+
     // Generate embedding for the query
     let embedding = agent
         .embedder
@@ -188,8 +217,11 @@ pub(crate) async fn execute_search_memories(
 }
 
 /// Execute the delegate_to_session tool (placeholder).
+///
+/// With peer review enabled, the task is critiqued first; see
+/// [`review_delegation`].
 pub(crate) async fn execute_delegate_to_session(
-    _agent: &UserAgent,
+    agent: &UserAgent,
     call: &ToolCall,
 ) -> Result<ToolResult> {
     let session_id = call.get_string_arg("session_id").map_err(|e| {
@@ -208,19 +240,101 @@ pub(crate) async fn execute_delegate_to_session(
 
     let context = call.get_optional_string_arg("context");
 
+    let review_notes = match review_delegation(agent, session_id, task, context).await {
+        Ok(notes) => notes,
+        Err(critique) => {
+            return Ok(ToolResult::success(
+                &call.id,
+                format!(
+                    "Not delegated: a reviewer flagged issues with this task:\n{}\n\
+                     Revise the task and delegate it again, or ask the user to clarify.",
+                    critique
+                ),
+            ));
+        }
+    };
+
     info!("Delegating task to session '{}': {}", session_id, task);
 
     // Placeholder - will be implemented when session agent integration is complete
-    let output = format!(
+    let mut output = format!(
         "Task delegated to session '{}': {}\nContext: {}\n\nNote: Session agent integration is not yet implemented. This is a placeholder response.",
         session_id,
         task,
         context.unwrap_or("None")
     );
+    if let Some(notes) = review_notes {
+        output.push_str(&format!("\n\nReviewer notes:\n{}", notes));
+    }
 
     Ok(ToolResult::success(&call.id, output))
 }
 
+/// Critique a task before it is delegated.
+///
+/// Returns `Err(issues)` to send the task back for revision, which happens
+/// for every flagged critique but the last of the reviewer's rounds in this
+/// turn, and otherwise `Ok` with the issues to deliver alongside the task,
+/// if any. A failed review lets the task through.
+async fn review_delegation(
+    agent: &UserAgent,
+    session_id: &str,
+    task: &str,
+    context: Option<&str>,
+) -> std::result::Result<Option<String>, String> {
+    let Some(max_rounds) = agent.reviewer.as_ref().map(Reviewer::max_rounds) else {
+        return Ok(None);
+    };
+    let draft = match context {
+        Some(context) => format!("{}\n\nContext: {}", task, context),
+        None => task.to_string(),
+    };
+
+    let critique = match agent
+        .review_draft(&format!("task for session {}", session_id), &draft)
+        .await
+    {
+        Ok(Some(critique)) if !critique.is_approved() => critique,
+        Ok(_) => return Ok(None),
+        Err(e) => {
+            warn!(error = %e, "Review failed, delegating unreviewed");
+            return Ok(None);
+        }
+    };
+
+    let issues = critique.issue_lines().join("\n");
+    let round = agent.review_rounds.fetch_add(1, Ordering::Relaxed) + 1;
+    if round < max_rounds {
+        info!(session_id = %session_id, round, "Reviewer sent task back for revision");
+        Err(issues)
+    } else {
+        Ok(Some(issues))
+    }
+}
+
+/// Execute the plan tool, returning the generated plan with the tool result.
+///
+/// The plan is not run: the caller keeps it for the user to approve.
+pub(crate) async fn execute_plan(agent: &UserAgent, call: &ToolCall) -> Result<(Plan, ToolResult)> {
+    let goal = call.get_string_arg("goal").map_err(|e| {
+        AgentError::InvalidArguments {
+            tool_name: call.name.clone(),
+            message: e,
+        }
+    })?;
+    let context = call.get_optional_string_arg("context");
+
+    info!("Planning goal: {}", goal);
+    let plan = agent.plan(goal, context).await?;
+
+    let output = format!(
+        "Plan with {} tasks is awaiting user approval:\n{}",
+        plan.tasks.len(),
+        plan.format_tree().join("\n")
+    );
+    Ok((plan, ToolResult::success(&call.id, output)))
+}
+
 /// Execute the get_session_status tool (placeholder).
 pub(crate) async fn execute_get_session_status(
     _agent: &UserAgent,
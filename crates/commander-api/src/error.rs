@@ -18,6 +18,10 @@ pub enum ApiError {
     #[error("not found: {0}")]
     NotFound(String),
 
+    /// Missing or invalid credentials.
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
     /// Bad request - invalid input.
     #[error("bad request: {0}")]
     BadRequest(String),
@@ -40,6 +44,7 @@ impl ApiError {
     pub fn status_code(&self) -> StatusCode {
         match self {
             ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
             ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ApiError::Conflict(_) => StatusCode::CONFLICT,
@@ -103,6 +108,12 @@ impl From<commander_work::WorkError> for ApiError {
     }
 }
 
+impl From<commander_memory::MemoryError> for ApiError {
+    fn from(err: commander_memory::MemoryError) -> Self {
+        ApiError::Internal(err.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +128,10 @@ mod tests {
             ApiError::BadRequest("test".into()).status_code(),
             StatusCode::BAD_REQUEST
         );
+        assert_eq!(
+            ApiError::Unauthorized("test".into()).status_code(),
+            StatusCode::UNAUTHORIZED
+        );
         assert_eq!(
             ApiError::Internal("test".into()).status_code(),
             StatusCode::INTERNAL_SERVER_ERROR
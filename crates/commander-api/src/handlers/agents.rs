@@ -0,0 +1,195 @@
+//! Agent inspection handlers.
+//!
+//! Read-only views of what the agents know (their memories) and believe
+//! (the state session agents track). Memory access follows
+//! [`AccessControlledStore`]: the User Agent searches every agent's
+//! memories, other agents only their own.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+
+use commander_memory::{AccessControlledStore, AccessLevel, MemoryFilter};
+
+use crate::error::{ApiError, Result};
+use crate::state::AppState;
+use crate::types::{
+    AgentListResponse, AgentSummary, MemoryListQuery, MemoryListResponse, MemorySummary,
+    SessionStateResponse,
+};
+
+/// ID of the User Agent, the only agent allowed to search all memories.
+const USER_AGENT_ID: &str = "user-agent";
+
+/// Prefix of session agent IDs, followed by the session ID.
+const SESSION_AGENT_PREFIX: &str = "session-agent-";
+
+/// Prefix of the agents holding shared knowledge of a project.
+const PROJECT_KNOWLEDGE_PREFIX: &str = "project-knowledge-";
+
+/// Memories returned when no limit is given.
+const DEFAULT_MEMORY_LIMIT: usize = 20;
+
+/// Largest page of memories returned.
+const MAX_MEMORY_LIMIT: usize = 200;
+
+/// GET /api/agents - List the User Agent, session agents and agents with memories.
+pub async fn list_agents(State(state): State<AppState>) -> Result<Json<AgentListResponse>> {
+    let mut ids: Vec<String> = Vec::new();
+
+    #[cfg(feature = "agents")]
+    if let Some(orchestrator) = &state.orchestrator {
+        let orchestrator = orchestrator.read().await;
+        ids.push(orchestrator.user_agent_id().to_string());
+        let mut sessions = orchestrator.session_ids();
+        sessions.sort_unstable();
+        ids.extend(sessions.into_iter().map(|s| format!("{}{}", SESSION_AGENT_PREFIX, s)));
+    }
+
+    let mut agents = Vec::new();
+    if let Some(memory) = &state.memory {
+        let mut stored = memory.list_agents().await?;
+        stored.sort_unstable();
+        for id in stored {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        for id in ids {
+            let memory_count = memory.count(&id).await?;
+            agents.push(agent_summary(id, memory_count));
+        }
+    } else {
+        agents.extend(ids.into_iter().map(|id| agent_summary(id, 0)));
+    }
+
+    let total = agents.len();
+    Ok(Json(AgentListResponse { agents, total }))
+}
+
+/// GET /api/agents/{id}/memories - List or search the memories an agent can see.
+///
+/// Without `query`, lists the agent's own memories, newest first. With
+/// `query`, ranks by similarity the memories the agent may search.
+pub async fn list_agent_memories(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<MemoryListQuery>,
+) -> Result<Json<MemoryListResponse>> {
+    let memory = state
+        .memory
+        .clone()
+        .ok_or_else(|| ApiError::ServiceUnavailable("agent memory is not available".to_string()))?;
+    let access = if id == USER_AGENT_ID {
+        AccessLevel::All
+    } else {
+        AccessLevel::Own
+    };
+    let store = AccessControlledStore::new(memory, id, access);
+
+    let limit = query.limit.unwrap_or(DEFAULT_MEMORY_LIMIT).min(MAX_MEMORY_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let search = query.query.as_deref().map(str::trim).filter(|q| !q.is_empty());
+
+    let (items, total) = match search {
+        Some(text) => {
+            let embedding = state
+                .embedder
+                .embed(text)
+                .await
+                .map_err(|e| ApiError::ServiceUnavailable(format!("failed to embed query: {}", e)))?;
+            let results = store.search(&embedding, offset.saturating_add(limit)).await?;
+            let total = match access {
+                AccessLevel::Own => store.count().await?,
+                AccessLevel::All => {
+                    let agents = MemoryFilter::agents(store.inner().list_agents().await?);
+                    store.inner().count_matching(&agents).await?
+                }
+            };
+            let items = results.iter().skip(offset).map(MemorySummary::from).collect();
+            (items, total)
+        }
+        None => {
+            let mut memories = store.list(usize::MAX).await?;
+            memories.sort_by_key(|m| std::cmp::Reverse(m.created_at));
+            let total = memories.len();
+            let items = memories.iter().skip(offset).take(limit).map(MemorySummary::from).collect();
+            (items, total)
+        }
+    };
+
+    Ok(Json(MemoryListResponse {
+        items,
+        total,
+        offset,
+        limit,
+    }))
+}
+
+/// GET /api/sessions/{name}/state - State tracked by a session's agent.
+#[cfg_attr(not(feature = "agents"), allow(unused_variables))]
+pub async fn get_session_state(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<SessionStateResponse>> {
+    #[cfg(feature = "agents")]
+    if let Some(orchestrator) = &state.orchestrator {
+        let orchestrator = orchestrator.read().await;
+        let session_state = orchestrator
+            .session_state(&name)
+            .ok_or_else(|| ApiError::NotFound(format!("session agent not found: {}", name)))?;
+        let session_state =
+            serde_json::to_value(session_state).map_err(|e| ApiError::Internal(e.to_string()))?;
+        return Ok(Json(SessionStateResponse {
+            agent_id: format!("{}{}", SESSION_AGENT_PREFIX, name),
+            session: name,
+            state: session_state,
+        }));
+    }
+
+    Err(ApiError::ServiceUnavailable(
+        "agent orchestrator is not available".to_string(),
+    ))
+}
+
+/// GET /api/agents/{id}/result - An agent's last structured result, verbatim.
+///
+/// Results are only produced with `[agents] structured_output` enabled.
+#[cfg_attr(not(feature = "agents"), allow(unused_variables))]
+pub async fn get_agent_result(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>> {
+    #[cfg(feature = "agents")]
+    if let Some(orchestrator) = &state.orchestrator {
+        let orchestrator = orchestrator.read().await;
+        let result = orchestrator
+            .last_result(&id)
+            .ok_or_else(|| ApiError::NotFound(format!("no structured result for agent: {}", id)))?;
+        return Ok(Json(result.clone()));
+    }
+
+    Err(ApiError::ServiceUnavailable(
+        "agent orchestrator is not available".to_string(),
+    ))
+}
+
+/// Summary of agent `id`, classified by its ID.
+fn agent_summary(id: String, memory_count: usize) -> AgentSummary {
+    let (kind, session) = if id == USER_AGENT_ID {
+        ("user", None)
+    } else if let Some(session) = id.strip_prefix(SESSION_AGENT_PREFIX) {
+        ("session", Some(session.to_string()))
+    } else if id.starts_with(PROJECT_KNOWLEDGE_PREFIX) {
+        ("project", None)
+    } else {
+        ("other", None)
+    };
+    AgentSummary {
+        id,
+        kind: kind.to_string(),
+        session,
+        memory_count,
+    }
+}
@@ -104,7 +104,7 @@ pub async fn generate_code(State(_state): State<AppState>) -> Result<Json<Genera
 }
 
 /// Extract the bearer token from `Authorization: Bearer <token>`.
-fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
+pub(crate) fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
     headers
         .get("authorization")
         .and_then(|v| v.to_str().ok())
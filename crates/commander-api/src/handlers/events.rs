@@ -16,10 +16,12 @@ use crate::types::{
 };
 
 /// GET /api/events - List events with optional filters.
+///
+/// Reads through the event store's index, newest first, one page at a time.
 pub async fn list_events(
     State(state): State<AppState>,
     Query(query): Query<EventListQuery>,
-) -> Json<EventListResponse> {
+) -> Result<Json<EventListResponse>> {
     let mut filter = EventFilter::new();
 
     if let Some(project_id) = query.project_id {
@@ -38,20 +40,23 @@ pub async fn list_events(
         }
     }
 
-    let events = state.event_manager.list(Some(filter));
-    let mut summaries: Vec<EventSummary> = events.iter().map(EventSummary::from).collect();
+    if let Some(since) = query.since {
+        filter = filter.with_since(since);
+    }
 
-    // Apply limit if specified
-    if let Some(limit) = query.limit {
-        summaries.truncate(limit);
+    if let Some(until) = query.until {
+        filter = filter.with_until(until);
     }
 
-    let total = summaries.len();
+    let offset = query.offset.unwrap_or(0);
+    let page = state.event_manager.query(&filter, offset, query.limit)?;
+    let next_offset = page.next_offset(&filter.to_query(offset, query.limit));
 
-    Json(EventListResponse {
-        events: summaries,
-        total,
-    })
+    Ok(Json(EventListResponse {
+        events: page.events.iter().map(EventSummary::from).collect(),
+        total: page.total,
+        next_offset,
+    }))
 }
 
 /// GET /api/events/:id - Get an event by ID.
@@ -147,7 +152,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_events_empty() {
         let state = make_test_state();
-        let response = list_events(State(state), Query(EventListQuery::default())).await;
+        let response = list_events(State(state), Query(EventListQuery::default())).await.unwrap();
 
         assert_eq!(response.total, 0);
         assert!(response.events.is_empty());
@@ -164,7 +169,7 @@ mod tests {
         state.event_manager.emit(event2).unwrap();
 
         // List all
-        let response = list_events(State(state.clone()), Query(EventListQuery::default())).await;
+        let response = list_events(State(state.clone()), Query(EventListQuery::default())).await.unwrap();
         assert_eq!(response.total, 2);
 
         // Filter by project
@@ -172,7 +177,7 @@ mod tests {
             project_id: Some("proj-1".to_string()),
             ..Default::default()
         };
-        let response = list_events(State(state), Query(query)).await;
+        let response = list_events(State(state), Query(query)).await.unwrap();
         assert_eq!(response.total, 1);
         assert_eq!(response.events[0].project_id, "proj-1");
     }
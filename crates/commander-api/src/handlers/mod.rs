@@ -1,14 +1,18 @@
 //! API request handlers.
 
 pub mod adapters;
+pub mod agents;
 pub mod auth;
 pub mod events;
 pub mod health;
 pub mod projects;
+#[cfg(feature = "ui")]
+pub mod ui;
 pub mod web;
 pub mod work;
 
 pub use adapters::*;
+pub use agents::*;
 pub use auth::*;
 pub use events::*;
 pub use health::*;
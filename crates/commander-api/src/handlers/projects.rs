@@ -2,17 +2,21 @@
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 
+use commander_core::secrets::{get_secret, INBOUND_WEBHOOK_TOKEN};
 use commander_models::Project;
+use commander_runtime::Admission;
 
 use crate::error::{ApiError, Result};
+use crate::handlers::auth::extract_bearer_token;
 use crate::state::AppState;
 use crate::types::{
-    CreateProjectRequest, CreatedResponse, ProjectDetailResponse, ProjectListResponse,
-    ProjectSummary, SendMessageRequest, SuccessResponse,
+    CreateProjectRequest, CreatedResponse, InboundMessageRequest, InboundMessageResponse,
+    ProjectDetailResponse, ProjectListResponse, ProjectSummary, SendMessageRequest,
+    SuccessResponse,
 };
 
 /// GET /api/projects - List all projects.
@@ -113,11 +117,14 @@ pub async fn start_project(
         .ok_or_else(|| ApiError::ServiceUnavailable("no adapter available".to_string()))?;
 
     let runtime = runtime.read().await;
-    runtime.executor().start(&project, adapter).await?;
+    let message = match runtime.executor().start(&project, adapter).await? {
+        Admission::Started => "project started".to_string(),
+        Admission::Queued { position, .. } => {
+            format!("project queued at position {} until a session slot frees", position)
+        }
+    };
 
-    Ok(Json(SuccessResponse {
-        message: "project started".to_string(),
-    }))
+    Ok(Json(SuccessResponse { message }))
 }
 
 /// POST /api/projects/:id/stop - Stop a project instance.
@@ -181,6 +188,123 @@ pub async fn send_message(
     }))
 }
 
+/// POST /api/projects/:id/messages - Deliver a message from another service
+/// (e.g. "build failed, logs attached" from CI) to a project's session.
+///
+/// Requires `Authorization: Bearer <token>` with the `INBOUND_WEBHOOK_TOKEN`
+/// secret or a paired web client's token. With `triage`, the project's
+/// Session Agent diagnoses the message first and its suggested fix is sent
+/// along with it.
+pub async fn inbound_message(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<InboundMessageRequest>,
+) -> Result<Json<InboundMessageResponse>> {
+    authorize_inbound(&state, &headers)?;
+    let text = req.text.trim();
+    if text.is_empty() {
+        return Err(ApiError::BadRequest("text must not be empty".to_string()));
+    }
+
+    let runtime = state
+        .runtime
+        .as_ref()
+        .ok_or_else(|| ApiError::ServiceUnavailable("runtime not available".to_string()))?;
+    let project = state
+        .get_project(&id)
+        .await
+        .ok_or_else(|| ApiError::NotFound(format!("project not found: {}", id)))?;
+    let project_id = commander_models::ProjectId::from_string(&id);
+    let runtime = runtime.read().await;
+    let executor = runtime.executor();
+    if !executor.has_instance(&project_id).await {
+        return Err(ApiError::NotFound(format!(
+            "no running instance for project: {}",
+            id
+        )));
+    }
+    let session_name = format!("cmd-{}", project.name.replace([' ', '.', '/'], "-"));
+
+    let mut message = match req.source.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(source) => format!("Message from {}: {}", source, text),
+        None => text.to_string(),
+    };
+    let triage = if req.triage {
+        triage_message(&state, &session_name, &project, text).await
+    } else {
+        None
+    };
+    let triaged = triage.is_some();
+    if let Some(triage) = triage {
+        message.push_str("\n\n");
+        message.push_str(&triage);
+    }
+
+    // Wait for any other frontend typing into the session to finish.
+    let session = session_name.clone();
+    let lock = tokio::task::spawn_blocking(move || commander_core::acquire_send_lock(&session, "API"))
+        .await
+        .map_err(|e| ApiError::Internal(format!("send lock task failed: {}", e)))?
+        .map_err(|e| ApiError::Conflict(e.to_string()))?;
+    executor.send_prompt(&project_id, &message).await?;
+    drop(lock);
+
+    if let Err(e) = commander_core::append_user_message(&session_name, &message) {
+        tracing::warn!("failed to append inbound message to log: {}", e);
+    }
+
+    Ok(Json(InboundMessageResponse {
+        session: session_name,
+        sent: message,
+        triaged,
+    }))
+}
+
+/// Accept the inbound webhook token or a paired web client's token.
+fn authorize_inbound(state: &AppState, headers: &HeaderMap) -> Result<()> {
+    let token = extract_bearer_token(headers)
+        .ok_or_else(|| ApiError::Unauthorized("missing bearer token".to_string()))?;
+    let webhook_token = get_secret(INBOUND_WEBHOOK_TOKEN).filter(|t| !t.is_empty());
+    if webhook_token.is_some_and(|expected| constant_time_eq(expected.as_bytes(), token.as_bytes())) {
+        return Ok(());
+    }
+    if state.web_clients.validate_token(token).is_some() {
+        state.web_clients.update_last_seen(token);
+        return Ok(());
+    }
+    Err(ApiError::Unauthorized("invalid token".to_string()))
+}
+
+/// Compare tokens without leaking where they differ through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// The Session Agent's diagnosis of `text` as a note for the session, or
+/// `None` when it finds no error or agents are not hosted.
+#[cfg(feature = "agents")]
+async fn triage_message(state: &AppState, session: &str, project: &Project, text: &str) -> Option<String> {
+    let orchestrator = state.orchestrator.as_ref()?;
+    let adapter = project.effective_adapter_type().to_string();
+    let diagnosis = orchestrator
+        .write()
+        .await
+        .diagnose_session_error(session, &adapter, text)
+        .await
+        .map_err(|e| tracing::warn!(session = %session, error = %e, "Inbound message triage failed"))
+        .ok()??;
+    Some(format!(
+        "Likely cause: {}\nSuggested fix: {}",
+        diagnosis.cause, diagnosis.fix_prompt
+    ))
+}
+
+#[cfg(not(feature = "agents"))]
+async fn triage_message(_state: &AppState, _session: &str, _project: &Project, _text: &str) -> Option<String> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,4 +434,33 @@ mod tests {
 
         assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
     }
+
+    #[tokio::test]
+    async fn test_inbound_message_requires_token() {
+        let state = make_test_state();
+        let req = || InboundMessageRequest {
+            text: "build failed, logs attached".to_string(),
+            source: Some("CI".to_string()),
+            triage: false,
+        };
+
+        let result = inbound_message(State(state.clone()), Path("p1".to_string()), HeaderMap::new(), Json(req())).await;
+        assert!(matches!(result, Err(ApiError::Unauthorized(_))));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer not-a-token".parse().unwrap());
+        let result = inbound_message(State(state.clone()), Path("p1".to_string()), headers, Json(req())).await;
+        assert!(matches!(result, Err(ApiError::Unauthorized(_))));
+
+        // A paired client gets through to the (missing) runtime.
+        let client = state.web_clients.create_client(None).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", format!("Bearer {}", client.token).parse().unwrap());
+        let result = inbound_message(State(state), Path("p1".to_string()), headers, Json(req())).await;
+        assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
+
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
 }
@@ -0,0 +1,19 @@
+//! Embedded browser frontend.
+//!
+//! `GET /ui` serves a single self-contained page (no build step, no external
+//! assets) for teammates without the commander binary. It lists the tmux
+//! sessions with their project nicknames, follows the selected session
+//! through `GET /api/sessions/{name}/events`, shows its screen on demand and
+//! sends messages through `POST /api/sessions/message`. Compiled in with the
+//! default `ui` feature.
+
+use axum::http::header;
+use axum::response::{Html, IntoResponse};
+
+/// The page, with its styles and script inline.
+const UI_PAGE: &str = include_str!("ui.html");
+
+/// GET /ui - The embedded web frontend.
+pub async fn web_ui() -> impl IntoResponse {
+    ([(header::CACHE_CONTROL, "no-cache")], Html(UI_PAGE))
+}
@@ -14,6 +14,7 @@ use axum::{
     response::{sse::{Event, KeepAlive, Sse}, IntoResponse, Response},
     Json,
 };
+use commander_core::command_audit::{self, CommandSender, InputKind};
 use futures::stream::Stream;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, RwLock};
@@ -587,6 +588,7 @@ fn try_auto_create_registered_session(input: &str) -> Option<String> {
             .output()
         {
             Ok(out) if out.status.success() => {
+                command_audit::record(CommandSender::Api, &session_name, None, InputKind::Keys, cmd);
                 tracing::info!(
                     session = %session_name,
                     command = %cmd,
@@ -1042,8 +1044,15 @@ pub async fn send_message(
         )));
     }
 
+    // Wait for any other frontend typing into the session to finish.
+    let session = req.session.clone();
+    let lock = tokio::task::spawn_blocking(move || commander_core::acquire_send_lock(&session, "API"))
+        .await
+        .map_err(|e| ApiError::Internal(format!("send lock task failed: {}", e)))?
+        .map_err(|e| ApiError::Conflict(e.to_string()))?;
     tmux.send_line(&req.session, req.pane.as_deref(), &req.message)
         .map_err(|e| ApiError::Internal(format!("failed to send message: {}", e)))?;
+    drop(lock);
 
     // Persist user message to the session log so it survives session
     // switches / app restarts (LLM interpretations were the only thing
@@ -5,6 +5,7 @@
 //! - Event management (list, acknowledge, resolve)
 //! - Work queue management (list, create, complete)
 //! - Adapter listing
+//! - An embedded browser frontend at `/ui` (the default `ui` feature)
 //!
 //! # Example
 //!
@@ -33,5 +34,5 @@ pub mod web_clients;
 
 pub use config::ApiConfig;
 pub use error::{ApiError, Result};
-pub use router::{create_router, serve};
+pub use router::{create_router, serve, serve_with_shutdown};
 pub use state::{AppState, GitHubStats, SessionEvent};
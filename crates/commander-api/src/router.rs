@@ -36,6 +36,7 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/projects/{id}/start", post(handlers::start_project))
         .route("/api/projects/{id}/stop", post(handlers::stop_project))
         .route("/api/projects/{id}/send", post(handlers::send_message))
+        .route("/api/projects/{id}/messages", post(handlers::inbound_message))
         // Events
         .route("/api/events", get(handlers::list_events))
         .route("/api/events/{id}", get(handlers::get_event))
@@ -51,6 +52,10 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/work/{id}/complete", post(handlers::complete_work))
         // Adapters
         .route("/api/adapters", get(handlers::list_adapters))
+        // Agents
+        .route("/api/agents", get(handlers::list_agents))
+        .route("/api/agents/{id}/memories", get(handlers::list_agent_memories))
+        .route("/api/agents/{id}/result", get(handlers::get_agent_result))
         // Web UI — Session management
         .route("/api/sessions", get(handlers::web::list_sessions))
         .route("/api/sessions", post(handlers::web::create_session))
@@ -66,6 +71,7 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/sessions/{name}/summary", post(handlers::web::get_session_summary))
         .route("/api/sessions/{name}/capture", post(handlers::web::capture_session_output))
         .route("/api/sessions/{name}/events", get(handlers::web::session_event_stream))
+        .route("/api/sessions/{name}/state", get(handlers::get_session_state))
         // Session summary logs
         .route("/api/sessions/{name}/logs", get(handlers::web::list_session_logs))
         .route("/api/sessions/{name}/logs/archive", post(handlers::web::archive_session_logs))
@@ -84,6 +90,10 @@ pub fn create_router(state: AppState) -> Router {
         .layer(cors)
         .with_state(state);
 
+    // Embedded browser frontend, independent of the web-dist build below.
+    #[cfg(feature = "ui")]
+    let router = router.route("/ui", get(handlers::ui::web_ui));
+
     // Attach static file serving when the web-dist directory is present.
     // The directory is configurable via the AIC_WEB_DIR environment variable.
     let web_dir = std::env::var("AIC_WEB_DIR").unwrap_or_else(|_| {
@@ -112,6 +122,18 @@ pub fn create_router(state: AppState) -> Router {
 
 /// Starts the API server and the background session poller.
 pub async fn serve(config: ApiConfig, state: AppState) -> Result<(), std::io::Error> {
+    serve_with_shutdown(config, state, std::future::pending()).await
+}
+
+/// Starts the API server, stopping gracefully once `shutdown` resolves.
+pub async fn serve_with_shutdown<F>(
+    config: ApiConfig,
+    state: AppState,
+    shutdown: F,
+) -> Result<(), std::io::Error>
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
     let addr = config.bind_address();
 
     // Start the SSE session poller that broadcasts interpreted output.
@@ -122,12 +144,17 @@ pub async fn serve(config: ApiConfig, state: AppState) -> Result<(), std::io::Er
     // polled and broadcast.
     handlers::web::spawn_connected_sessions_poller(state.clone());
 
+    // Watch connectivity so summaries fall back while offline.
+    commander_core::connectivity::start_monitor();
+
     // Start the GitHub stats poller (hourly).
     handlers::web::spawn_github_stats_poller(state.github_stats.clone());
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     info!("API server listening on {}", addr);
-    axum::serve(listener, create_router(state)).await
+    axum::serve(listener, create_router(state))
+        .with_graceful_shutdown(shutdown)
+        .await
 }
 
 #[cfg(test)]
@@ -140,6 +167,7 @@ mod tests {
     use commander_persistence::{EventStore, WorkStore};
     use commander_work::WorkQueue;
     use serde_json::json;
+    use std::sync::Arc;
     use tempfile::tempdir;
 
     fn make_test_state() -> AppState {
@@ -174,6 +202,19 @@ mod tests {
         assert!(!body["version"].as_str().unwrap().is_empty());
     }
 
+    #[cfg(feature = "ui")]
+    #[tokio::test]
+    async fn test_web_ui_page() {
+        let server = TestServer::new(create_router(make_test_state()));
+
+        let response = server.get("/ui").await;
+        response.assert_status_ok();
+        assert!(response.header("content-type").to_str().unwrap().starts_with("text/html"));
+        let page = response.text();
+        assert!(page.contains("/api/sessions/message"));
+        assert!(page.contains("EventSource"));
+    }
+
     #[tokio::test]
     async fn test_list_projects_empty() {
         let state = make_test_state();
@@ -316,6 +357,37 @@ mod tests {
         assert_eq!(body["total"], 1);
     }
 
+    #[tokio::test]
+    async fn test_list_events_paged() {
+        let state = make_test_state();
+
+        for i in 0..3 {
+            state
+                .event_manager
+                .emit(Event::new("proj-1", EventType::Status, format!("Event {}", i)))
+                .unwrap();
+        }
+
+        let app = create_router(state);
+        let server = TestServer::new(app);
+
+        let response = server.get("/api/events?limit=2").await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["events"].as_array().unwrap().len(), 2);
+        assert_eq!(body["next_offset"], 2);
+
+        let response = server.get("/api/events?offset=2&limit=2").await;
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["events"].as_array().unwrap().len(), 1);
+        assert!(body.get("next_offset").is_none());
+
+        let response = server.get("/api/events?since=2100-01-01T00:00:00Z").await;
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 0);
+    }
+
     #[tokio::test]
     async fn test_list_work() {
         let state = make_test_state();
@@ -355,6 +427,112 @@ mod tests {
         assert!(!body["id"].as_str().unwrap().is_empty());
     }
 
+    async fn make_memory_state() -> AppState {
+        use commander_memory::{EmbeddingGenerator, EmbeddingProvider, LocalStore, Memory, MemoryStore};
+
+        let dir = tempdir().unwrap();
+        let store = LocalStore::new(dir.path().join("memory")).await.unwrap();
+        std::mem::forget(dir);
+        let embedder = EmbeddingGenerator::new(EmbeddingProvider::HashBased {
+            dimension: commander_memory::DEFAULT_EMBEDDING_DIM,
+        });
+        for (agent, content) in [
+            ("user-agent", "User prefers small commits"),
+            ("session-agent-s1", "Login fails on expired tokens"),
+            ("session-agent-s1", "Token refresh lives in auth.rs"),
+            ("session-agent-s2", "Dashboard charts are slow"),
+            ("project-knowledge-web", "Web uses Tailwind"),
+        ] {
+            let embedding = embedder.embed(content).await.unwrap();
+            store.store(Memory::new(agent, content, embedding)).await.unwrap();
+        }
+        make_test_state().with_memory(Arc::new(store), embedder)
+    }
+
+    #[tokio::test]
+    async fn test_list_agents() {
+        let server = TestServer::new(create_router(make_memory_state().await));
+
+        let response = server.get("/api/agents").await;
+        response.assert_status_ok();
+
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 4);
+        let agents = body["agents"].as_array().unwrap();
+        let s1 = agents.iter().find(|a| a["id"] == "session-agent-s1").unwrap();
+        assert_eq!(s1["kind"], "session");
+        assert_eq!(s1["session"], "s1");
+        assert_eq!(s1["memory_count"], 2);
+        let user = agents.iter().find(|a| a["id"] == "user-agent").unwrap();
+        assert_eq!(user["kind"], "user");
+        let web = agents.iter().find(|a| a["id"] == "project-knowledge-web").unwrap();
+        assert_eq!(web["kind"], "project");
+    }
+
+    #[tokio::test]
+    async fn test_list_agent_memories_paginated() {
+        let server = TestServer::new(create_router(make_memory_state().await));
+
+        let response = server.get("/api/agents/session-agent-s1/memories?limit=1").await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["items"].as_array().unwrap().len(), 1);
+        assert!(body["items"][0].get("embedding").is_none());
+
+        let response = server.get("/api/agents/session-agent-s1/memories?limit=1&offset=1").await;
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["offset"], 1);
+        assert_eq!(body["items"].as_array().unwrap().len(), 1);
+
+        let response = server.get("/api/agents/unknown/memories").await;
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_agent_memories_respects_access() {
+        let server = TestServer::new(create_router(make_memory_state().await));
+
+        // Session agents only search their own memories.
+        let response = server
+            .get("/api/agents/session-agent-s2/memories?query=login%20tokens&limit=10")
+            .await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        let items = body["items"].as_array().unwrap();
+        assert!(!items.is_empty());
+        assert!(items.iter().all(|m| m["agent_id"] == "session-agent-s2"));
+        assert!(items[0]["score"].is_number());
+
+        // The User Agent searches everyone's.
+        let response = server
+            .get("/api/agents/user-agent/memories?query=login%20tokens&limit=10")
+            .await;
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 5);
+        assert_eq!(body["items"].as_array().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_agent_endpoints_unavailable_without_agents() {
+        let server = TestServer::new(create_router(make_test_state()));
+
+        let response = server.get("/api/agents").await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["total"], 0);
+
+        let response = server.get("/api/agents/user-agent/memories").await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = server.get("/api/agents/user-agent/result").await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = server.get("/api/sessions/s1/state").await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[tokio::test]
     async fn test_cors_headers() {
         let state = make_test_state();
@@ -8,9 +8,12 @@ use serde::Serialize;
 use tokio::sync::{broadcast, RwLock};
 
 use commander_adapters::AdapterRegistry;
-use commander_core::config;
+use commander_core::{config, CommandSender};
 use commander_events::EventManager;
+use commander_memory::{EmbeddingGenerator, MemoryStore};
 use commander_models::Project;
+#[cfg(feature = "agents")]
+use commander_orchestrator::AgentOrchestrator;
 use commander_runtime::Runtime;
 use commander_tmux::TmuxOrchestrator;
 use commander_work::WorkQueue;
@@ -84,6 +87,13 @@ pub struct AppState {
     /// session is "connected" only after the client POSTs `/api/sessions/:name
     /// /connect`, regardless of whether the tmux session exists.
     pub connected_sessions: Arc<std::sync::RwLock<HashSet<String>>>,
+    /// Agent memory store (optional - unavailable when agents are not hosted).
+    pub memory: Option<Arc<dyn MemoryStore>>,
+    /// Embeds memory search queries.
+    pub embedder: Arc<EmbeddingGenerator>,
+    /// Agent orchestrator (optional - unavailable when agents are not hosted).
+    #[cfg(feature = "agents")]
+    pub orchestrator: Option<Arc<RwLock<AgentOrchestrator>>>,
 }
 
 impl AppState {
@@ -109,7 +119,9 @@ impl AppState {
         adapter_registry: AdapterRegistry,
         storage_dir: PathBuf,
     ) -> Self {
-        let tmux = TmuxOrchestrator::new().ok().map(Arc::new);
+        let tmux = TmuxOrchestrator::new()
+            .ok()
+            .map(|tmux| Arc::new(tmux.with_sender(CommandSender::Api)));
         let (event_tx, _rx) = broadcast::channel(64);
 
         Self {
@@ -125,9 +137,33 @@ impl AppState {
             session_adapters: Arc::new(RwLock::new(HashMap::new())),
             github_stats: Arc::new(RwLock::new(HashMap::new())),
             connected_sessions: Arc::new(std::sync::RwLock::new(HashSet::new())),
+            memory: None,
+            embedder: Arc::new(EmbeddingGenerator::from_env()),
+            #[cfg(feature = "agents")]
+            orchestrator: None,
         }
     }
 
+    /// Uses a runtime shared with other components (e.g. the daemon's orchestrator).
+    pub fn with_shared_runtime(mut self, runtime: Arc<RwLock<Runtime>>) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Exposes an agent memory store, searched with `embedder`.
+    pub fn with_memory(mut self, memory: Arc<dyn MemoryStore>, embedder: EmbeddingGenerator) -> Self {
+        self.memory = Some(memory);
+        self.embedder = Arc::new(embedder);
+        self
+    }
+
+    /// Exposes an agent orchestrator shared with other components.
+    #[cfg(feature = "agents")]
+    pub fn with_orchestrator(mut self, orchestrator: Arc<RwLock<AgentOrchestrator>>) -> Self {
+        self.orchestrator = Some(orchestrator);
+        self
+    }
+
     /// Gets a project by ID.
     pub async fn get_project(&self, id: &str) -> Option<Project> {
         let projects = self.projects.read().await;
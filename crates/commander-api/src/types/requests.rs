@@ -1,5 +1,6 @@
 //! Request DTOs for the API.
 
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 /// Create project request.
@@ -20,6 +21,20 @@ pub struct SendMessageRequest {
     pub message: String,
 }
 
+/// Inbound message for a project's session, e.g. from CI.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InboundMessageRequest {
+    /// Message text.
+    pub text: String,
+    /// Where it comes from (e.g. "CI"); shown to the session.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Have the project's Session Agent diagnose the message first and send
+    /// its suggested fix along with it.
+    #[serde(default)]
+    pub triage: bool,
+}
+
 /// Event list query parameters.
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct EventListQuery {
@@ -29,6 +44,12 @@ pub struct EventListQuery {
     pub status: Option<String>,
     /// Filter by priority.
     pub priority: Option<String>,
+    /// Only events created at or after this RFC 3339 time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only events created before this RFC 3339 time.
+    pub until: Option<DateTime<Utc>>,
+    /// Number of matching events to skip.
+    pub offset: Option<usize>,
     /// Maximum number of events to return.
     pub limit: Option<usize>,
 }
@@ -82,6 +103,17 @@ pub struct WorkListQuery {
     pub limit: Option<usize>,
 }
 
+/// Agent memory list query parameters.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MemoryListQuery {
+    /// Semantic search query; memories are listed unranked when absent.
+    pub query: Option<String>,
+    /// Maximum number of memories to return.
+    pub limit: Option<usize>,
+    /// Number of memories to skip.
+    pub offset: Option<usize>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,12 @@
 //! Response DTOs for the API.
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use commander_adapters::AdapterInfo;
+use commander_memory::{Memory, SearchResult};
 use commander_models::{Event, Project, WorkItem};
 
 /// Health check response.
@@ -103,8 +106,11 @@ impl From<&Project> for ProjectDetailResponse {
 pub struct EventListResponse {
     /// List of events.
     pub events: Vec<EventSummary>,
-    /// Total count.
+    /// Number of matching events across all pages.
     pub total: usize,
+    /// Offset of the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
 }
 
 /// Event summary for list responses.
@@ -269,6 +275,92 @@ impl From<&WorkItem> for WorkDetailResponse {
     }
 }
 
+/// Agent list response.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentListResponse {
+    /// List of agents.
+    pub agents: Vec<AgentSummary>,
+    /// Total count.
+    pub total: usize,
+}
+
+/// Agent summary for list responses.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentSummary {
+    /// Agent ID, as used for its memories.
+    pub id: String,
+    /// Agent kind: "user", "session", "project" (shared project knowledge) or "other".
+    pub kind: String,
+    /// Session the agent monitors (session agents only).
+    pub session: Option<String>,
+    /// Number of memories the agent owns.
+    pub memory_count: usize,
+}
+
+/// Agent memory list response.
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryListResponse {
+    /// Page of memories.
+    pub items: Vec<MemorySummary>,
+    /// Number of memories the page is taken from.
+    pub total: usize,
+    /// Number of memories skipped.
+    pub offset: usize,
+    /// Maximum number of memories in the page.
+    pub limit: usize,
+}
+
+/// Memory summary for list responses (without its embedding).
+#[derive(Debug, Clone, Serialize)]
+pub struct MemorySummary {
+    /// Memory ID.
+    pub id: String,
+    /// Agent owning the memory.
+    pub agent_id: String,
+    /// Memory content.
+    pub content: String,
+    /// Additional metadata.
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// When the memory was created.
+    pub created_at: DateTime<Utc>,
+    /// Similarity to the search query (searches only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+impl From<&Memory> for MemorySummary {
+    fn from(memory: &Memory) -> Self {
+        Self {
+            id: memory.id.clone(),
+            agent_id: memory.agent_id.clone(),
+            content: memory.content.clone(),
+            metadata: memory.metadata.clone(),
+            created_at: memory.created_at,
+            score: None,
+        }
+    }
+}
+
+impl From<&SearchResult> for MemorySummary {
+    fn from(result: &SearchResult) -> Self {
+        Self {
+            score: Some(result.score),
+            ..Self::from(&result.memory)
+        }
+    }
+}
+
+/// Session agent state response.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStateResponse {
+    /// Session ID.
+    pub session: String,
+    /// ID of the session's agent.
+    pub agent_id: String,
+    /// Goals, task, progress, blockers and files tracked by the agent.
+    pub state: serde_json::Value,
+}
+
 /// Adapter list response.
 #[derive(Debug, Clone, Serialize)]
 pub struct AdapterListResponse {
@@ -340,6 +432,17 @@ pub struct SuccessResponse {
     pub message: String,
 }
 
+/// Result of an inbound message.
+#[derive(Debug, Clone, Serialize)]
+pub struct InboundMessageResponse {
+    /// Tmux session the message was sent to.
+    pub session: String,
+    /// Text typed into the session.
+    pub sent: String,
+    /// Whether the Session Agent's diagnosis was added.
+    pub triaged: bool,
+}
+
 /// Created response with ID.
 #[derive(Debug, Clone, Serialize)]
 pub struct CreatedResponse {
@@ -0,0 +1,526 @@
+//! Approval queue for agent tool calls and file writes.
+//!
+//! Projects in approval mode queue selected actions (agent delegation to a
+//! session, file-writing filesystem commands) instead of running them.
+//! Requests are stored in `~/.ai-commander/state/approvals.json` so every
+//! frontend can list and decide them:
+//! - The TUI shows them in the approvals pane and runs approved requests
+//! - The Telegram bot announces new requests with Approve/Deny buttons
+//!
+//! Every queued request, decision and execution is appended to the audit log
+//! in `~/.ai-commander/logs/approvals.jsonl`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::config;
+
+/// Tool name of agent delegation to a session.
+pub const DELEGATE_TOOL: &str = "delegate_to_session";
+
+/// Tool name of file-writing filesystem commands run from the TUI.
+pub const FILESYSTEM_TOOL: &str = "filesystem";
+
+/// Tools queued for approval when their project is in approval mode.
+pub const GATED_TOOLS: &[&str] = &[DELEGATE_TOOL, FILESYSTEM_TOOL];
+
+/// Number of decided requests kept in the queue file (the audit log keeps
+/// everything).
+const MAX_DECIDED: usize = 50;
+
+/// Errors from deciding approval requests.
+#[derive(Debug, Error)]
+pub enum ApprovalError {
+    #[error("no approval request matches '{0}'")]
+    NotFound(String),
+
+    #[error("'{0}' matches more than one approval request")]
+    Ambiguous(String),
+
+    #[error("approval request {id} is already {status}")]
+    AlreadyDecided { id: String, status: ApprovalStatus },
+
+    #[error("failed to save approval queue: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Lifecycle of an approval request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Waiting for a decision.
+    Pending,
+    /// Approved, waiting to be run.
+    Approved,
+    /// Denied; will not run.
+    Denied,
+    /// Approved and run successfully.
+    Executed,
+    /// Approved but running it failed.
+    Failed,
+}
+
+impl std::fmt::Display for ApprovalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Denied => "denied",
+            ApprovalStatus::Executed => "executed",
+            ApprovalStatus::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A tool call waiting for (or past) a decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    /// Short unique ID (`apr-` + 8 hex digits).
+    pub id: String,
+    /// Project the call acts on.
+    pub project: String,
+    /// Tool name, e.g. [`DELEGATE_TOOL`] or [`FILESYSTEM_TOOL`].
+    pub tool: String,
+    /// Tool arguments needed to run the call once approved.
+    pub arguments: serde_json::Value,
+    /// One-line description shown to the approver.
+    pub summary: String,
+    /// Current status.
+    pub status: ApprovalStatus,
+    /// When the request was queued.
+    pub created_at: DateTime<Utc>,
+    /// Who approved or denied the request (e.g. "tui", "telegram:<chat>").
+    #[serde(default)]
+    pub decided_by: Option<String>,
+    /// When the request was approved or denied.
+    #[serde(default)]
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Channels that have already shown this request to the user.
+    #[serde(default)]
+    pub announced_to: BTreeSet<String>,
+}
+
+impl ApprovalRequest {
+    /// Whether the request is still waiting for a decision.
+    pub fn is_pending(&self) -> bool {
+        self.status == ApprovalStatus::Pending
+    }
+
+    /// Get a string argument.
+    pub fn argument(&self, key: &str) -> Option<&str> {
+        self.arguments.get(key).and_then(|v| v.as_str())
+    }
+}
+
+/// One line of the approval audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    pub request_id: String,
+    pub project: String,
+    pub tool: String,
+    pub summary: String,
+    /// What happened: the request's new status.
+    pub action: ApprovalStatus,
+    /// Who decided, for approvals and denials.
+    #[serde(default)]
+    pub by: Option<String>,
+    /// Execution output or error.
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+impl AuditEntry {
+    fn new(request: &ApprovalRequest, detail: Option<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            request_id: request.id.clone(),
+            project: request.project.clone(),
+            tool: request.tool.clone(),
+            summary: request.summary.clone(),
+            action: request.status,
+            by: request.decided_by.clone(),
+            detail,
+        }
+    }
+}
+
+/// Approval mode settings and queued requests stored in the shared file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalQueue {
+    /// Projects in approval mode.
+    #[serde(default)]
+    pub approval_mode: BTreeSet<String>,
+    /// Requests, oldest first.
+    #[serde(default)]
+    pub requests: Vec<ApprovalRequest>,
+}
+
+impl ApprovalQueue {
+    /// Load the queue from `path`; missing or unreadable files yield an
+    /// empty queue.
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to parse approvals file");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the queue to `path`.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        debug!(path = %path.display(), "Saved approvals file");
+        Ok(())
+    }
+
+    /// Whether `project` is in approval mode.
+    pub fn is_enabled(&self, project: &str) -> bool {
+        self.approval_mode.contains(project)
+    }
+
+    /// Turn approval mode on or off for `project`.
+    pub fn set_enabled(&mut self, project: &str, enabled: bool) {
+        if enabled {
+            self.approval_mode.insert(project.to_string());
+        } else {
+            self.approval_mode.remove(project);
+        }
+    }
+
+    /// Queue a new pending request and return it.
+    pub fn push(
+        &mut self,
+        project: &str,
+        tool: &str,
+        arguments: serde_json::Value,
+        summary: impl Into<String>,
+    ) -> ApprovalRequest {
+        let summary = summary.into();
+        let mut id = new_id(project, tool, &summary);
+        while self.requests.iter().any(|r| r.id == id) {
+            id = new_id(project, tool, &id);
+        }
+        let request = ApprovalRequest {
+            id,
+            project: project.to_string(),
+            tool: tool.to_string(),
+            arguments,
+            summary,
+            status: ApprovalStatus::Pending,
+            created_at: Utc::now(),
+            decided_by: None,
+            decided_at: None,
+            announced_to: BTreeSet::new(),
+        };
+        self.requests.push(request.clone());
+        request
+    }
+
+    /// Pending requests, oldest first.
+    pub fn pending(&self) -> impl Iterator<Item = &ApprovalRequest> {
+        self.requests.iter().filter(|r| r.is_pending())
+    }
+
+    fn find_mut(&mut self, id: &str) -> Result<&mut ApprovalRequest, ApprovalError> {
+        let id = id.trim();
+        let mut matches = self
+            .requests
+            .iter_mut()
+            .filter(|r| !id.is_empty() && (r.id == id || r.id.starts_with(id) || r.id[4..].starts_with(id)));
+        let first = matches.next().ok_or_else(|| ApprovalError::NotFound(id.to_string()))?;
+        if matches.next().is_some() {
+            return Err(ApprovalError::Ambiguous(id.to_string()));
+        }
+        Ok(first)
+    }
+
+    /// Approve or deny a pending request by ID or unique ID prefix.
+    pub fn decide(&mut self, id: &str, approve: bool, by: &str) -> Result<ApprovalRequest, ApprovalError> {
+        let request = self.find_mut(id)?;
+        if !request.is_pending() {
+            return Err(ApprovalError::AlreadyDecided {
+                id: request.id.clone(),
+                status: request.status,
+            });
+        }
+        request.status = if approve {
+            ApprovalStatus::Approved
+        } else {
+            ApprovalStatus::Denied
+        };
+        request.decided_by = Some(by.to_string());
+        request.decided_at = Some(Utc::now());
+        let decided = request.clone();
+        self.prune();
+        Ok(decided)
+    }
+
+    /// Claim approved requests for the given tools so they are run once.
+    ///
+    /// Claimed requests are marked executed; report failures with
+    /// [`finish`](Self::finish).
+    pub fn take_approved(&mut self, tools: &[&str]) -> Vec<ApprovalRequest> {
+        self.requests
+            .iter_mut()
+            .filter(|r| r.status == ApprovalStatus::Approved && tools.contains(&r.tool.as_str()))
+            .map(|r| {
+                r.status = ApprovalStatus::Executed;
+                r.clone()
+            })
+            .collect()
+    }
+
+    /// Record the outcome of running a claimed request.
+    pub fn finish(&mut self, id: &str, success: bool) -> Option<ApprovalRequest> {
+        let request = self.requests.iter_mut().find(|r| r.id == id)?;
+        request.status = if success {
+            ApprovalStatus::Executed
+        } else {
+            ApprovalStatus::Failed
+        };
+        Some(request.clone())
+    }
+
+    /// Pending requests not yet shown on `channel`.
+    pub fn unannounced(&self, channel: &str) -> Vec<ApprovalRequest> {
+        self.pending()
+            .filter(|r| !r.announced_to.contains(channel))
+            .cloned()
+            .collect()
+    }
+
+    /// Mark requests as shown on `channel`.
+    pub fn mark_announced(&mut self, channel: &str, ids: &[String]) {
+        for request in self.requests.iter_mut().filter(|r| ids.contains(&r.id)) {
+            request.announced_to.insert(channel.to_string());
+        }
+    }
+
+    /// Drop the oldest decided requests beyond [`MAX_DECIDED`].
+    fn prune(&mut self) {
+        let decided = self.requests.iter().filter(|r| !r.is_pending()).count();
+        let mut excess = decided.saturating_sub(MAX_DECIDED);
+        self.requests.retain(|r| {
+            if excess > 0 && !r.is_pending() && r.status != ApprovalStatus::Approved {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+}
+
+/// Generate a short request ID.
+fn new_id(project: &str, tool: &str, salt: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    Utc::now().timestamp_nanos_opt().hash(&mut hasher);
+    std::process::id().hash(&mut hasher);
+    project.hash(&mut hasher);
+    tool.hash(&mut hasher);
+    salt.hash(&mut hasher);
+    format!("apr-{:08x}", hasher.finish() as u32)
+}
+
+/// Append an entry to the audit log at `path`.
+pub fn append_audit(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+/// Read the last `limit` entries of the audit log at `path`.
+pub fn read_audit(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let entries: Vec<AuditEntry> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.into_iter().skip(skip).collect()
+}
+
+fn audit(request: &ApprovalRequest, detail: Option<String>) {
+    if let Err(e) = append_audit(&config::approvals_audit_file(), &AuditEntry::new(request, detail)) {
+        warn!(error = %e, request = %request.id, "Failed to write approval audit log");
+    }
+}
+
+/// Apply `f` to the shared queue and save it.
+fn update<T>(f: impl FnOnce(&mut ApprovalQueue) -> T) -> std::io::Result<T> {
+    let path = config::approvals_file();
+    let mut queue = ApprovalQueue::load(&path);
+    let result = f(&mut queue);
+    queue.save(&path)?;
+    Ok(result)
+}
+
+/// Load the shared approval queue.
+pub fn load_approvals() -> ApprovalQueue {
+    ApprovalQueue::load(&config::approvals_file())
+}
+
+/// Whether `project` is in approval mode.
+pub fn approval_mode_enabled(project: &str) -> bool {
+    load_approvals().is_enabled(project)
+}
+
+/// Turn approval mode on or off for `project`.
+pub fn set_approval_mode(project: &str, enabled: bool) -> std::io::Result<()> {
+    update(|queue| queue.set_enabled(project, enabled))
+}
+
+/// Queue a tool call for approval and record it in the audit log.
+pub fn queue_approval(
+    project: &str,
+    tool: &str,
+    arguments: serde_json::Value,
+    summary: impl Into<String>,
+) -> std::io::Result<ApprovalRequest> {
+    let request = update(|queue| queue.push(project, tool, arguments, summary))?;
+    audit(&request, None);
+    Ok(request)
+}
+
+/// Pending requests, oldest first.
+pub fn pending_approvals() -> Vec<ApprovalRequest> {
+    load_approvals().pending().cloned().collect()
+}
+
+/// Approve or deny a pending request and record the decision.
+pub fn decide_approval(id: &str, approve: bool, by: &str) -> Result<ApprovalRequest, ApprovalError> {
+    let request = update(|queue| queue.decide(id, approve, by))??;
+    audit(&request, None);
+    Ok(request)
+}
+
+/// Claim approved requests for `tools`; the caller runs them and reports
+/// back with [`finish_approval`].
+pub fn take_approved(tools: &[&str]) -> Vec<ApprovalRequest> {
+    update(|queue| queue.take_approved(tools)).unwrap_or_else(|e| {
+        warn!(error = %e, "Failed to claim approved requests");
+        Vec::new()
+    })
+}
+
+/// Record the outcome of running an approved request.
+pub fn finish_approval(id: &str, success: bool, detail: impl Into<String>) {
+    match update(|queue| queue.finish(id, success)) {
+        Ok(Some(request)) => audit(&request, Some(detail.into())),
+        Ok(None) => warn!(request = %id, "Finished approval request no longer queued"),
+        Err(e) => warn!(error = %e, request = %id, "Failed to record approval outcome"),
+    }
+}
+
+/// Pending requests not yet shown on `channel`.
+pub fn unannounced_approvals(channel: &str) -> Vec<ApprovalRequest> {
+    load_approvals().unannounced(channel)
+}
+
+/// Mark requests as shown on `channel`.
+pub fn mark_approvals_announced(channel: &str, ids: &[String]) -> std::io::Result<()> {
+    update(|queue| queue.mark_announced(channel, ids))
+}
+
+/// Read the most recent audit log entries.
+pub fn recent_audit_entries(limit: usize) -> Vec<AuditEntry> {
+    read_audit(&config::approvals_audit_file(), limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decide_by_prefix() {
+        let mut queue = ApprovalQueue::default();
+        let request = queue.push("proj", DELEGATE_TOOL, json!({"task": "ls"}), "Delegate: ls");
+        assert_eq!(queue.pending().count(), 1);
+
+        let prefix = &request.id[4..8];
+        let decided = queue.decide(prefix, true, "tui").unwrap();
+        assert_eq!(decided.status, ApprovalStatus::Approved);
+        assert_eq!(decided.decided_by.as_deref(), Some("tui"));
+        assert_eq!(queue.pending().count(), 0);
+
+        assert!(matches!(
+            queue.decide(&request.id, false, "tui"),
+            Err(ApprovalError::AlreadyDecided { .. })
+        ));
+        assert!(matches!(queue.decide("zzz", true, "tui"), Err(ApprovalError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_approved_requests_run_once() {
+        let mut queue = ApprovalQueue::default();
+        let write = queue.push("proj", FILESYSTEM_TOOL, json!({"command": "write a.txt x"}), "write a.txt");
+        let delegate = queue.push("proj", DELEGATE_TOOL, json!({}), "delegate");
+        queue.decide(&write.id, true, "telegram:1").unwrap();
+        queue.decide(&delegate.id, false, "tui").unwrap();
+
+        assert!(queue.take_approved(&[DELEGATE_TOOL]).is_empty());
+        let taken = queue.take_approved(GATED_TOOLS);
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].id, write.id);
+        assert!(queue.take_approved(GATED_TOOLS).is_empty());
+
+        let failed = queue.finish(&write.id, false).unwrap();
+        assert_eq!(failed.status, ApprovalStatus::Failed);
+    }
+
+    #[test]
+    fn test_approval_mode_and_announcements() {
+        let mut queue = ApprovalQueue::default();
+        queue.set_enabled("proj", true);
+        assert!(queue.is_enabled("proj"));
+        assert!(!queue.is_enabled("other"));
+
+        let request = queue.push("proj", DELEGATE_TOOL, json!({}), "delegate");
+        assert_eq!(queue.unannounced("telegram").len(), 1);
+        queue.mark_announced("telegram", std::slice::from_ref(&request.id));
+        assert!(queue.unannounced("telegram").is_empty());
+        assert_eq!(queue.unannounced("matrix").len(), 1);
+
+        queue.set_enabled("proj", false);
+        assert!(!queue.is_enabled("proj"));
+    }
+
+    #[test]
+    fn test_queue_and_audit_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("approvals.json");
+        let mut queue = ApprovalQueue::default();
+        queue.set_enabled("proj", true);
+        let request = queue.push("proj", DELEGATE_TOOL, json!({"task": "ls"}), "delegate");
+        queue.save(&path).unwrap();
+        assert_eq!(ApprovalQueue::load(&path), queue);
+
+        let log = dir.path().join("logs").join("approvals.jsonl");
+        append_audit(&log, &AuditEntry::new(&request, None)).unwrap();
+        append_audit(&log, &AuditEntry::new(&request, Some("ok".into()))).unwrap();
+        let entries = read_audit(&log, 1);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].detail.as_deref(), Some("ok"));
+        assert_eq!(entries[0].action, ApprovalStatus::Pending);
+    }
+}
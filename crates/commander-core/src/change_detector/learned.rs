@@ -0,0 +1,281 @@
+//! Per-adapter significance patterns learned from feedback.
+//!
+//! The default patterns misclassify output for some tools. Feedback on
+//! detected changes is turned into a `LearnedPatterns` file per adapter:
+//! - significant patterns that keep producing trivial changes get a low
+//!   weight and are demoted
+//! - lines the user reports as missed events become new significant patterns
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeType, Significance};
+
+/// Observations needed before a pattern's weight is adjusted.
+pub const MIN_PATTERN_SAMPLES: usize = 3;
+
+/// Reports of the same missed line needed before it becomes a pattern.
+pub const MIN_MISSED_REPORTS: usize = 2;
+
+/// Weight below which a pattern is demoted one level.
+const DEMOTE_WEIGHT: f32 = 0.5;
+
+/// Weight below which a pattern is demoted to `Low` (no longer meaningful).
+const SUPPRESS_WEIGHT: f32 = 0.2;
+
+/// Leading words of a missed line used to build its pattern.
+const MISSED_PATTERN_WORDS: usize = 4;
+
+/// One piece of feedback about a detected (or undetected) change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignificanceObservation {
+    /// LLM analysis agreed the change matched by `pattern` was significant.
+    Confirmed { pattern: String },
+    /// LLM analysis found the change matched by `pattern` trivial.
+    Trivial { pattern: String },
+    /// The user reported a significant event the detector missed.
+    Missed { line: String },
+}
+
+/// A significant pattern learned from missed events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedPattern {
+    /// Regex source.
+    pub pattern: String,
+    pub change_type: ChangeType,
+    pub significance: Significance,
+    /// Number of reports this pattern was learned from.
+    pub reports: usize,
+}
+
+/// Learned pattern adjustments for one adapter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LearnedPatterns {
+    pub adapter: String,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Confirmation rate per significant pattern, keyed by regex source.
+    #[serde(default)]
+    pub weights: BTreeMap<String, f32>,
+    /// Additional significant patterns.
+    #[serde(default)]
+    pub significant: Vec<LearnedPattern>,
+}
+
+impl LearnedPatterns {
+    /// Derive weights and new patterns from feedback observations.
+    pub fn learn(adapter: &str, observations: &[SignificanceObservation]) -> Self {
+        let mut outcomes: HashMap<&str, (usize, usize)> = HashMap::new();
+        let mut missed: HashMap<String, usize> = HashMap::new();
+
+        for observation in observations {
+            match observation {
+                SignificanceObservation::Confirmed { pattern } => {
+                    outcomes.entry(pattern).or_default().0 += 1;
+                }
+                SignificanceObservation::Trivial { pattern } => {
+                    outcomes.entry(pattern).or_default().1 += 1;
+                }
+                SignificanceObservation::Missed { line } => {
+                    if let Some(pattern) = pattern_for_line(line) {
+                        *missed.entry(pattern).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        let weights = outcomes
+            .into_iter()
+            .filter(|(_, (confirmed, trivial))| confirmed + trivial >= MIN_PATTERN_SAMPLES)
+            .map(|(pattern, (confirmed, trivial))| {
+                // Laplace smoothing keeps a single outlier from swinging the weight
+                let weight = (confirmed + 1) as f32 / (confirmed + trivial + 2) as f32;
+                (pattern.to_string(), weight)
+            })
+            .collect();
+
+        let mut significant: Vec<LearnedPattern> = missed
+            .into_iter()
+            .filter(|(_, reports)| *reports >= MIN_MISSED_REPORTS)
+            .map(|(pattern, reports)| LearnedPattern {
+                pattern,
+                change_type: ChangeType::Progress,
+                significance: Significance::Medium,
+                reports,
+            })
+            .collect();
+        significant.sort_by(|a, b| b.reports.cmp(&a.reports).then(a.pattern.cmp(&b.pattern)));
+
+        Self {
+            adapter: adapter.to_string(),
+            updated_at: Some(Utc::now()),
+            weights,
+            significant,
+        }
+    }
+
+    /// Whether there is nothing to apply.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty() && self.significant.is_empty()
+    }
+
+    /// Adjusted significance for a pattern, given its learned weight.
+    pub fn adjusted_significance(&self, pattern: &str, significance: Significance) -> Significance {
+        match self.weights.get(pattern) {
+            Some(&w) if w < SUPPRESS_WEIGHT => significance.min(Significance::Low),
+            Some(&w) if w < DEMOTE_WEIGHT => significance.demoted(),
+            _ => significance,
+        }
+    }
+
+    /// Apply the adjustments to a detector's significant patterns.
+    ///
+    /// Learned patterns are appended after the existing ones so built-in
+    /// patterns keep precedence; invalid regexes are skipped.
+    pub fn apply(&self, patterns: &mut Vec<(Regex, ChangeType, Significance)>) {
+        for (regex, _, significance) in patterns.iter_mut() {
+            *significance = self.adjusted_significance(regex.as_str(), *significance);
+        }
+        for learned in &self.significant {
+            if patterns.iter().any(|(r, _, _)| r.as_str() == learned.pattern) {
+                continue;
+            }
+            match Regex::new(&learned.pattern) {
+                Ok(regex) => patterns.push((regex, learned.change_type.clone(), learned.significance)),
+                Err(e) => {
+                    tracing::warn!(pattern = %learned.pattern, error = %e, "Skipping invalid learned pattern");
+                }
+            }
+        }
+    }
+
+    /// Load learned patterns from a JSON file, or an empty set if missing or invalid.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save learned patterns as JSON, creating the parent directory.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Build a pattern matching lines that start like `line`.
+///
+/// Uses the first few words, with digit runs generalized, anchored at the
+/// start of the line.
+fn pattern_for_line(line: &str) -> Option<String> {
+    let words: Vec<String> = line
+        .split_whitespace()
+        .take(MISSED_PATTERN_WORDS)
+        .map(generalize_word)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    Some(format!("^{}", words.join(r"\s+")))
+}
+
+/// Escape a word for regex use, replacing each digit run with `\d+`.
+fn generalize_word(word: &str) -> String {
+    let mut out = String::new();
+    let mut in_digits = false;
+    for c in word.chars() {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                out.push_str(r"\d+");
+            }
+            in_digits = true;
+        } else {
+            in_digits = false;
+            out.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmed(pattern: &str) -> SignificanceObservation {
+        SignificanceObservation::Confirmed { pattern: pattern.to_string() }
+    }
+
+    fn trivial(pattern: &str) -> SignificanceObservation {
+        SignificanceObservation::Trivial { pattern: pattern.to_string() }
+    }
+
+    #[test]
+    fn test_learn_weights_need_samples() {
+        let learned = LearnedPatterns::learn("claude_code", &[trivial("a"), trivial("a")]);
+        assert!(learned.weights.is_empty());
+
+        let learned = LearnedPatterns::learn(
+            "claude_code",
+            &[trivial("a"), trivial("a"), trivial("a"), trivial("a"), confirmed("b"), confirmed("b"), confirmed("b")],
+        );
+        assert!(learned.weights["a"] < SUPPRESS_WEIGHT);
+        assert!(learned.weights["b"] > DEMOTE_WEIGHT);
+    }
+
+    #[test]
+    fn test_adjusted_significance() {
+        let mut learned = LearnedPatterns::default();
+        learned.weights.insert("noisy".to_string(), 0.1);
+        learned.weights.insert("mixed".to_string(), 0.4);
+        learned.weights.insert("good".to_string(), 0.9);
+
+        assert_eq!(learned.adjusted_significance("noisy", Significance::High), Significance::Low);
+        assert_eq!(learned.adjusted_significance("mixed", Significance::High), Significance::Medium);
+        assert_eq!(learned.adjusted_significance("good", Significance::High), Significance::High);
+        assert_eq!(learned.adjusted_significance("unknown", Significance::Critical), Significance::Critical);
+    }
+
+    #[test]
+    fn test_missed_lines_become_patterns() {
+        let observations = [
+            SignificanceObservation::Missed { line: "Deployed build 42 to staging".to_string() },
+            SignificanceObservation::Missed { line: "Deployed build 43 to production".to_string() },
+            SignificanceObservation::Missed { line: "one-off event".to_string() },
+        ];
+        let learned = LearnedPatterns::learn("mpm", &observations);
+        assert_eq!(learned.significant.len(), 1);
+
+        let regex = Regex::new(&learned.significant[0].pattern).unwrap();
+        assert!(regex.is_match("Deployed build 7 to qa"));
+        assert!(!regex.is_match("Built 7 artifacts"));
+    }
+
+    #[test]
+    fn test_generalize_word() {
+        assert_eq!(generalize_word("v1.2"), r"v\d+\.\d+");
+        assert_eq!(generalize_word("build1234"), r"build\d+");
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learned").join("claude_code.json");
+
+        let learned = LearnedPatterns {
+            adapter: "claude_code".to_string(),
+            weights: BTreeMap::from([("x".to_string(), 0.25)]),
+            ..Default::default()
+        };
+        learned.save(&path).unwrap();
+
+        assert_eq!(LearnedPatterns::load(&path), learned);
+        assert!(LearnedPatterns::load(&dir.path().join("missing.json")).is_empty());
+    }
+}
@@ -12,6 +12,7 @@
 //! 4. **Pattern classification** - Match against significant/ignore patterns
 //! 5. **Significance scoring** - Determine if LLM analysis is needed
 
+mod learned;
 mod patterns;
 #[cfg(test)]
 mod tests;
@@ -24,7 +25,9 @@ use std::time::Duration;
 
 use regex::Regex;
 
+pub use self::learned::{LearnedPattern, LearnedPatterns, SignificanceObservation};
 pub use self::patterns::{classify_change, default_ignore_patterns, default_significant_patterns, summarize_change};
+use self::patterns::classify_with_pattern;
 pub use self::types::{ChangeEvent, ChangeNotification, ChangeType, Significance};
 
 /// Pattern-based change detector for session output.
@@ -59,6 +62,26 @@ impl ChangeDetector {
         }
     }
 
+    /// Create a detector for an adapter, applying its learned patterns.
+    ///
+    /// Learned patterns are read from `learned_patterns_file(adapter)`; a
+    /// missing file leaves the defaults unchanged.
+    pub fn new_for_adapter(adapter: &str) -> Self {
+        let mut detector = Self::new();
+        detector.apply_learned(&LearnedPatterns::load(&crate::config::learned_patterns_file(adapter)));
+        detector
+    }
+
+    /// Apply learned pattern weights and additional patterns.
+    ///
+    /// Always starts from the default patterns, so re-applying updated
+    /// weights does not compound earlier demotions.
+    pub fn apply_learned(&mut self, learned: &LearnedPatterns) {
+        let mut patterns = default_significant_patterns();
+        learned.apply(&mut patterns);
+        self.significant_patterns = patterns;
+    }
+
     /// Add a custom significant pattern.
     pub fn add_significant_pattern(
         &mut self,
@@ -110,7 +133,10 @@ impl ChangeDetector {
         let new_lines = self.find_new_lines(&prev_cleaned, &cleaned);
 
         // Stage 4: Classify change type and significance
-        let (change_type, significance) = classify_change(&new_lines, &self.significant_patterns);
+        let (change_type, significance, pattern_index) =
+            classify_with_pattern(&new_lines, &self.significant_patterns);
+        let matched_pattern =
+            pattern_index.map(|i| self.significant_patterns[i].0.as_str().to_string());
 
         // Stage 5: Generate summary
         let summary = summarize_change(&new_lines, &change_type, &self.significant_patterns);
@@ -124,6 +150,7 @@ impl ChangeDetector {
             summary,
             diff_lines: new_lines,
             significance,
+            matched_pattern,
         }
     }
 
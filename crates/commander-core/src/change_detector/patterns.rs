@@ -137,22 +137,33 @@ pub fn classify_change(
     new_lines: &[String],
     significant_patterns: &[(Regex, ChangeType, Significance)],
 ) -> (ChangeType, Significance) {
+    let (change_type, significance, _) = classify_with_pattern(new_lines, significant_patterns);
+    (change_type, significance)
+}
+
+/// Classify the change and report the index of the pattern that set the significance.
+pub(super) fn classify_with_pattern(
+    new_lines: &[String],
+    significant_patterns: &[(Regex, ChangeType, Significance)],
+) -> (ChangeType, Significance, Option<usize>) {
     if new_lines.is_empty() {
-        return (ChangeType::None, Significance::Ignore);
+        return (ChangeType::None, Significance::Ignore, None);
     }
 
     let mut best_type = ChangeType::Addition;
     let mut best_significance = Significance::Low;
+    let mut best_pattern = None;
 
     for line in new_lines {
         // Find the FIRST pattern that matches this line (order matters)
-        for (pattern, change_type, significance) in significant_patterns {
+        for (index, (pattern, change_type, significance)) in significant_patterns.iter().enumerate() {
             if pattern.is_match(line) {
                 // First match for this line wins
                 // Update best if this significance is higher
                 if *significance > best_significance {
                     best_significance = *significance;
                     best_type = change_type.clone();
+                    best_pattern = Some(index);
                 }
                 // Stop checking patterns for this line (first match wins)
                 break;
@@ -160,7 +171,7 @@ pub fn classify_change(
         }
     }
 
-    (best_type, best_significance)
+    (best_type, best_significance, best_pattern)
 }
 
 /// Generate a human-readable summary of the change.
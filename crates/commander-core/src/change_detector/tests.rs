@@ -195,6 +195,7 @@ fn test_change_notification_fields() {
         requires_action: false,
         change_type: ChangeType::Completion,
         significance: Significance::High,
+        diagnosis: None,
     };
 
     assert_eq!(notification.session_id, "test-session");
@@ -267,3 +268,31 @@ fn test_notification_threshold() {
     };
     assert!(high_event.requires_notification());
 }
+
+#[test]
+fn test_detect_reports_matched_pattern() {
+    let mut detector = ChangeDetector::new();
+    detector.detect("Starting...");
+    let event = detector.detect("Starting...\nError: build failed");
+    assert!(event.matched_pattern.is_some());
+
+    let event = detector.detect("Starting...\nError: build failed\nplain line");
+    assert_eq!(event.matched_pattern, None);
+}
+
+#[test]
+fn test_apply_learned_demotes_noisy_pattern() {
+    let mut detector = ChangeDetector::new();
+    detector.detect("Starting...");
+    let event = detector.detect("Starting...\nError: build failed");
+    let pattern = event.matched_pattern.clone().unwrap();
+    assert!(event.requires_notification());
+
+    let mut learned = LearnedPatterns::default();
+    learned.weights.insert(pattern, 0.05);
+    detector.apply_learned(&learned);
+    detector.reset();
+    detector.detect("Starting...");
+    let event = detector.detect("Starting...\nError: build failed");
+    assert!(!event.is_meaningful());
+}
@@ -1,9 +1,14 @@
 //! Type definitions for change detection.
 
+use serde::{Deserialize, Serialize};
+
+use crate::diagnosis::ErrorDiagnosis;
+
 /// Significance level of a detected change.
 ///
 /// Used to determine polling rate and whether to invoke LLM analysis.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Significance {
     /// Ignore - UI noise, spinners, no actual content change
     Ignore,
@@ -23,8 +28,21 @@ impl Default for Significance {
     }
 }
 
+impl Significance {
+    /// The next level down (`Ignore` stays `Ignore`).
+    pub fn demoted(self) -> Self {
+        match self {
+            Self::Critical => Self::High,
+            Self::High => Self::Medium,
+            Self::Medium => Self::Low,
+            Self::Low | Self::Ignore => Self::Ignore,
+        }
+    }
+}
+
 /// Type of change detected in session output.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ChangeType {
     /// No meaningful change detected
     None,
@@ -59,6 +77,8 @@ pub struct ChangeEvent {
     pub diff_lines: Vec<String>,
     /// Significance level for polling/notification decisions
     pub significance: Significance,
+    /// Source of the pattern that set the significance, if any matched
+    pub matched_pattern: Option<String>,
 }
 
 impl ChangeEvent {
@@ -69,6 +89,7 @@ impl ChangeEvent {
             summary: String::new(),
             diff_lines: Vec::new(),
             significance: Significance::Ignore,
+            matched_pattern: None,
         }
     }
 
@@ -84,7 +105,7 @@ impl ChangeEvent {
 }
 
 /// Notification to send when significant changes are detected.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ChangeNotification {
     /// Session that generated this notification
     pub session_id: String,
@@ -96,4 +117,6 @@ pub struct ChangeNotification {
     pub change_type: ChangeType,
     /// Significance level
     pub significance: Significance,
+    /// Diagnosis of the error, when the change is one and it was diagnosed
+    pub diagnosis: Option<ErrorDiagnosis>,
 }
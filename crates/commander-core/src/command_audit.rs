@@ -0,0 +1,285 @@
+//! Audit log of input sent to sessions.
+//!
+//! Every line and key sequence Commander types into a tmux session is
+//! appended to `commands.jsonl` in the logs directory: when, who sent it
+//! (the CLI user, the TUI, a chat bot, an agent...), the session and
+//! project, and a SHA-256 hash of the content. The content itself is not
+//! stored, so the log can be kept without leaking what was typed while still
+//! proving what was sent.
+//!
+//! The log is append-only and rotates by size: `commands.jsonl` becomes
+//! `commands.jsonl.1`, older files shift up to [`AUDIT_KEEP`], and the
+//! oldest is dropped. `commander audit show` queries all of them.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::config;
+
+/// Rotate the audit log once it reaches this size.
+pub const AUDIT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated audit logs kept alongside the active one.
+pub const AUDIT_KEEP: usize = 5;
+
+/// Prefix of the tmux sessions Commander creates for projects.
+const SESSION_PREFIX: &str = "commander-";
+
+/// Who sent input to a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSender {
+    /// The CLI or REPL.
+    User,
+    Tui,
+    Gui,
+    Telegram,
+    Matrix,
+    /// The HTTP API and web UI.
+    Api,
+    /// An agent or workflow acting on its own.
+    Agent,
+    /// The daemon (idle nudges, queued messages).
+    Daemon,
+}
+
+impl fmt::Display for CommandSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CommandSender::User => "user",
+            CommandSender::Tui => "tui",
+            CommandSender::Gui => "gui",
+            CommandSender::Telegram => "telegram",
+            CommandSender::Matrix => "matrix",
+            CommandSender::Api => "api",
+            CommandSender::Agent => "agent",
+            CommandSender::Daemon => "daemon",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for CommandSender {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_lowercase()))
+            .map_err(|_| format!("Unknown sender: {}", s))
+    }
+}
+
+/// How the input was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    /// Literal text followed by Enter.
+    Line,
+    /// Key names such as `C-c` or `Escape`.
+    Keys,
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputKind::Line => write!(f, "line"),
+            InputKind::Keys => write!(f, "keys"),
+        }
+    }
+}
+
+/// One line of the command audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandAuditEntry {
+    pub at: DateTime<Utc>,
+    pub sender: CommandSender,
+    /// Tmux session the input went to.
+    pub session: String,
+    /// Project of the session (its name without the `commander-` prefix).
+    pub project: String,
+    #[serde(default)]
+    pub pane: Option<String>,
+    pub kind: InputKind,
+    /// `sha256:<hex>` of the content.
+    pub content_hash: String,
+    /// Content length in bytes.
+    pub len: usize,
+}
+
+impl CommandAuditEntry {
+    /// Entry for `content` sent to `session` now.
+    pub fn new(
+        sender: CommandSender,
+        session: &str,
+        pane: Option<&str>,
+        kind: InputKind,
+        content: &str,
+    ) -> Self {
+        Self {
+            at: Utc::now(),
+            sender,
+            session: session.to_string(),
+            project: session.strip_prefix(SESSION_PREFIX).unwrap_or(session).to_string(),
+            pane: pane.map(str::to_string),
+            kind,
+            content_hash: content_hash(content),
+            len: content.len(),
+        }
+    }
+}
+
+/// `sha256:<hex>` of `content`, as recorded in the audit log.
+pub fn content_hash(content: &str) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(content.as_bytes())))
+}
+
+/// Record input sent to a session in the audit log. Failures are logged,
+/// never returned: auditing must not block sending.
+pub fn record(sender: CommandSender, session: &str, pane: Option<&str>, kind: InputKind, content: &str) {
+    let entry = CommandAuditEntry::new(sender, session, pane, kind, content);
+    if let Err(e) = append_entry(&config::command_audit_file(), &entry, AUDIT_MAX_BYTES, AUDIT_KEEP) {
+        warn!(error = %e, session = %session, "Failed to write command audit log");
+    }
+}
+
+/// Append `entry` to the log at `path`, first rotating it if the entry
+/// would take it past `max_bytes`.
+pub fn append_entry(path: &Path, entry: &CommandAuditEntry, max_bytes: u64, keep: usize) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = format!("{}\n", serde_json::to_string(entry)?);
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size > 0 && size + line.len() as u64 > max_bytes {
+        rotate(path, keep)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Shift rotated logs up by one, dropping the oldest, and move the active
+/// log to `.1`.
+fn rotate(path: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 {
+        return fs::remove_file(path);
+    }
+    fs::remove_file(rotated_path(path, keep)).ok();
+    for index in (1..keep).rev() {
+        let from = rotated_path(path, index);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, index + 1))?;
+        }
+    }
+    fs::rename(path, rotated_path(path, 1))
+}
+
+/// Filter for [`read_entries`].
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Only this project (or session).
+    pub project: Option<String>,
+    /// Only entries at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    pub sender: Option<CommandSender>,
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &CommandAuditEntry) -> bool {
+        self.project
+            .as_deref()
+            .is_none_or(|p| entry.project == p || entry.session == p)
+            && self.since.is_none_or(|since| entry.at >= since)
+            && self.sender.is_none_or(|sender| entry.sender == sender)
+    }
+}
+
+/// Entries of the log at `path` and its rotated files matching `query`,
+/// oldest first.
+pub fn read_entries(path: &Path, keep: usize, query: &AuditQuery) -> Vec<CommandAuditEntry> {
+    let files = (1..=keep).rev().map(|i| rotated_path(path, i)).chain([path.to_path_buf()]);
+    files
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<CommandAuditEntry>(line).ok())
+                .collect::<Vec<_>>()
+        })
+        .filter(|entry| query.matches(entry))
+        .collect()
+}
+
+/// Entries of the command audit log matching `query`, oldest first.
+pub fn query(query: &AuditQuery) -> Vec<CommandAuditEntry> {
+    read_entries(&config::command_audit_file(), AUDIT_KEEP, query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(sender: CommandSender, session: &str, content: &str) -> CommandAuditEntry {
+        CommandAuditEntry::new(sender, session, None, InputKind::Line, content)
+    }
+
+    #[test]
+    fn test_entry_hashes_content() {
+        let entry = entry(CommandSender::Telegram, "commander-api", "cargo test");
+        assert_eq!(entry.project, "api");
+        assert_eq!(entry.len, 10);
+        assert_eq!(entry.content_hash, content_hash("cargo test"));
+        assert!(entry.content_hash.starts_with("sha256:"));
+        assert_eq!(entry.content_hash.len(), "sha256:".len() + 64);
+
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(!json.contains("cargo test"));
+        assert!(json.contains("\"sender\":\"telegram\""));
+        assert_eq!("TUI".parse::<CommandSender>(), Ok(CommandSender::Tui));
+        assert!("bot".parse::<CommandSender>().is_err());
+    }
+
+    #[test]
+    fn test_rotates_and_queries_all_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("logs/commands.jsonl");
+        let line_len = serde_json::to_string(&entry(CommandSender::User, "commander-api", "a")).unwrap().len() + 1;
+
+        for (sender, session) in [
+            (CommandSender::User, "commander-api"),
+            (CommandSender::Tui, "commander-web"),
+            (CommandSender::Agent, "commander-api"),
+            (CommandSender::Tui, "commander-api"),
+        ] {
+            let entry = entry(sender, session, "a");
+            append_entry(&path, &entry, line_len as u64 * 2, 1).unwrap();
+        }
+        // Two entries per file and one rotated file kept
+        assert!(rotated_path(&path, 1).exists());
+        assert!(!rotated_path(&path, 2).exists());
+
+        let all = read_entries(&path, 1, &AuditQuery::default());
+        let senders: Vec<CommandSender> = all.iter().map(|e| e.sender).collect();
+        assert_eq!(senders, [CommandSender::User, CommandSender::Tui, CommandSender::Agent, CommandSender::Tui]);
+
+        let api = AuditQuery { project: Some("api".into()), ..Default::default() };
+        assert_eq!(read_entries(&path, 1, &api).len(), 3);
+        let tui = AuditQuery { sender: Some(CommandSender::Tui), ..api };
+        assert_eq!(read_entries(&path, 1, &tui).len(), 1);
+        let future = AuditQuery { since: Some(Utc::now() + chrono::Duration::hours(1)), ..Default::default() };
+        assert!(read_entries(&path, 1, &future).is_empty());
+    }
+}
@@ -14,6 +14,9 @@
 //! ├── logs/         # Application logs
 //! ├── config/       # User configuration files
 //! ├── cache/        # Temporary cache files
+//! ├── recordings/   # Session recordings (asciicast v2)
+//! ├── adapters/     # Manifest adapters (TOML)
+//! ├── themes/       # TUI color themes (TOML)
 //! └── state/        # Runtime state files
 //! ```
 //!
@@ -55,6 +58,12 @@ const LOGS_SUBDIR: &str = "logs";
 const CONFIG_SUBDIR: &str = "config";
 const CACHE_SUBDIR: &str = "cache";
 const STATE_SUBDIR: &str = "state";
+const TEMPLATES_SUBDIR: &str = "templates";
+const RECORDINGS_SUBDIR: &str = "recordings";
+const ADAPTERS_SUBDIR: &str = "adapters";
+const WORKFLOWS_SUBDIR: &str = "workflows";
+const EVALS_SUBDIR: &str = "evals";
+const THEMES_SUBDIR: &str = "themes";
 
 // Static caches for lazy initialization
 static STATE_DIR_CACHE: OnceLock<PathBuf> = OnceLock::new();
@@ -146,6 +155,21 @@ pub fn telegram_pid_file() -> PathBuf {
     runtime_state_dir().join("telegram.pid")
 }
 
+/// Get the Telegram bot heartbeat file path.
+///
+/// Rewritten by a running bot so other processes can tell it is healthy.
+pub fn telegram_heartbeat_file() -> PathBuf {
+    runtime_state_dir().join("telegram-heartbeat.json")
+}
+
+/// Get the Telegram bot identity file path.
+///
+/// The bot records its username here at startup so the CLI and TUI can
+/// build `t.me` pairing links.
+pub fn telegram_bot_file() -> PathBuf {
+    runtime_state_dir().join("telegram_bot.json")
+}
+
 /// Get the projects database file path.
 ///
 /// Stores project definitions and metadata.
@@ -167,6 +191,100 @@ pub fn notifications_file() -> PathBuf {
     runtime_state_dir().join("notifications.json")
 }
 
+/// Get the escalation state file path.
+///
+/// Records which blocking events are currently escalated, and to which
+/// channel, so the TUI can show escalation modals raised by the daemon.
+pub fn escalations_file() -> PathBuf {
+    runtime_state_dir().join("escalations.json")
+}
+
+/// Get the recap state file path.
+///
+/// Records, per session, the screen when the user last interacted with it
+/// and the last "Since you left" digest, shared by the TUI and the bots.
+pub fn recaps_file() -> PathBuf {
+    runtime_state_dir().join("recaps.json")
+}
+
+/// Get the paused sessions file path.
+///
+/// Records sessions stopped with `/pause` and how to resume them.
+pub fn paused_file() -> PathBuf {
+    runtime_state_dir().join("paused.json")
+}
+
+/// Get the session notes directory.
+///
+/// Holds one JSONL file of `/note` scratchpad notes per session.
+pub fn notes_dir() -> PathBuf {
+    runtime_state_dir().join("notes")
+}
+
+/// Get the send locks directory.
+///
+/// Holds one directory of queued senders per session, so frontends in
+/// different processes take turns typing into it.
+pub fn send_locks_dir() -> PathBuf {
+    runtime_state_dir().join("send-locks")
+}
+
+/// Get the do-not-disturb state file path.
+///
+/// Records the running `/snooze` and the notifications held back during
+/// quiet time, shared by the TUI and the bots.
+pub fn dnd_file() -> PathBuf {
+    runtime_state_dir().join("dnd.json")
+}
+
+/// Get the manifest adapters directory.
+///
+/// Holds one TOML manifest per user-defined adapter, loaded by the adapter
+/// registry at startup.
+pub fn adapters_dir() -> PathBuf {
+    state_dir().join(ADAPTERS_SUBDIR)
+}
+
+/// Get the approval queue file path.
+///
+/// Stores per-project approval mode and the queue of tool calls waiting for
+/// a decision, shared by the TUI and the chat bots.
+pub fn approvals_file() -> PathBuf {
+    runtime_state_dir().join("approvals.json")
+}
+
+/// Get the approval audit log path.
+///
+/// Append-only JSON lines recording every queued request, decision and
+/// execution.
+pub fn approvals_audit_file() -> PathBuf {
+    logs_dir().join("approvals.jsonl")
+}
+
+/// Get the command audit log path.
+///
+/// Append-only JSON lines recording every input sent to a session, rotated
+/// by size (see [`crate::command_audit`]).
+pub fn command_audit_file() -> PathBuf {
+    logs_dir().join("commands.jsonl")
+}
+
+/// Get the tool permission audit log path.
+///
+/// Append-only JSON lines recording every tool call an agent's permission
+/// profile denied or held for approval.
+pub fn permissions_audit_file() -> PathBuf {
+    logs_dir().join("permissions.jsonl")
+}
+
+/// Get the session registry file path.
+///
+/// Records which frontend is attached to each session for handoff between
+/// the TUI, REPL and Telegram.
+pub fn session_registry_file() -> PathBuf {
+    runtime_state_dir().join("session_registry.json")
+}
+
 /// Get the authorized chats file path.
 ///
 /// Stores authorized Telegram chat IDs that persist across bot restarts.
@@ -174,6 +292,80 @@ pub fn authorized_chats_file() -> PathBuf {
     runtime_state_dir().join("authorized_chats.json")
 }
 
+/// Get the authorized Matrix rooms file path.
+///
+/// Stores Matrix room IDs paired with the Matrix bot.
+pub fn matrix_rooms_file() -> PathBuf {
+    runtime_state_dir().join("matrix_rooms.json")
+}
+
+/// Get the project templates directory.
+///
+/// User-defined templates for `commander new` and `/connect --template`.
+pub fn project_templates_dir() -> PathBuf {
+    state_dir().join(TEMPLATES_SUBDIR).join("projects")
+}
+
+/// Get the learned significance patterns file for an adapter.
+///
+/// Written by the feedback loop that tunes `ChangeDetector` per adapter.
+pub fn learned_patterns_file(adapter: &str) -> PathBuf {
+    config_dir()
+        .join("learned_patterns")
+        .join(format!("{}.json", adapter))
+}
+
+/// Get the session recordings directory.
+///
+/// Holds asciicast v2 recordings of session pane output.
+pub fn recordings_dir() -> PathBuf {
+    state_dir().join(RECORDINGS_SUBDIR)
+}
+
+/// Get the workflows directory.
+///
+/// Holds the YAML pipelines run by `commander workflow run`.
+pub fn workflows_dir() -> PathBuf {
+    state_dir().join(WORKFLOWS_SUBDIR)
+}
+
+/// Get the workflow runs directory.
+///
+/// Stores the progress of each workflow run so it can resume after a crash.
+pub fn workflow_runs_dir() -> PathBuf {
+    runtime_state_dir().join("workflow_runs")
+}
+
+/// Get the evals directory.
+///
+/// Holds golden transcript datasets (`golden/`) and the baseline report
+/// used by `commander eval run`.
+pub fn evals_dir() -> PathBuf {
+    state_dir().join(EVALS_SUBDIR)
+}
+
+/// Get the TUI themes directory.
+///
+/// Holds one TOML file per user theme, selected with `/theme <name>` or
+/// `[tui.theme] name`.
+pub fn themes_dir() -> PathBuf {
+    state_dir().join(THEMES_SUBDIR)
+}
+
+/// Get the snippets file.
+///
+/// Named prompts sent with `/run <name>` or `!name` (see `snippets`).
+pub fn snippets_file() -> PathBuf {
+    state_dir().join("snippets.toml")
+}
+
+/// Get the secrets directory.
+///
+/// Holds the encrypted secrets file and its key (see `commander-secrets`).
+pub fn secrets_dir() -> PathBuf {
+    config_dir().join("secrets")
+}
+
 /// Get the main config file path.
 ///
 /// The config.toml file for user settings.
@@ -339,12 +531,42 @@ mod tests {
         assert!(file.ends_with("notifications.json"));
     }
 
+    #[test]
+    fn test_session_registry_file_name() {
+        let file = session_registry_file();
+        assert!(file.ends_with("session_registry.json"));
+    }
+
     #[test]
     fn test_authorized_chats_file_name() {
         let file = authorized_chats_file();
         assert!(file.ends_with("authorized_chats.json"));
     }
 
+    #[test]
+    fn test_project_templates_dir_name() {
+        let dir = project_templates_dir();
+        assert!(dir.ends_with("templates/projects"));
+    }
+
+    #[test]
+    fn test_learned_patterns_file_name() {
+        let file = learned_patterns_file("claude_code");
+        assert!(file.ends_with("learned_patterns/claude_code.json"));
+    }
+
+    #[test]
+    fn test_workflow_and_eval_dir_names() {
+        assert!(workflows_dir().ends_with("workflows"));
+        assert!(workflow_runs_dir().ends_with("state/workflow_runs"));
+        assert!(evals_dir().ends_with("evals"));
+    }
+
+    #[test]
+    fn test_secrets_dir_name() {
+        assert!(secrets_dir().ends_with("secrets"));
+    }
+
     #[test]
     fn test_config_file_name() {
         let file = config_file();
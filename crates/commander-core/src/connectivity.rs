@@ -0,0 +1,147 @@
+//! Offline mode, detected with periodic connectivity checks.
+//!
+//! Frontends call [`start_monitor`] once at startup; from then on a
+//! background thread opens a TCP connection to the model provider every
+//! [`CHECK_INTERVAL`] and [`is_offline`] reports whether the last attempt
+//! failed. While offline, summaries fall back to the cleaned raw response,
+//! agents analyze output with their deterministic patterns only, and work
+//! that needs the network waits for it instead of failing: memories are
+//! embedded again once [`reconnects`] moves, and evals block in
+//! [`wait_until_online`].
+//!
+//! Setting `COMMANDER_OFFLINE=1` forces offline mode without any checks.
+//! Until the first check completes, or without a monitor, the network is
+//! assumed to be up.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, warn};
+
+/// Environment variable forcing offline mode.
+pub const OFFLINE_ENV: &str = "COMMANDER_OFFLINE";
+
+/// Time between connectivity checks.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Host checked for connectivity: the model provider's API.
+const PROBE_ADDR: &str = "openrouter.ai:443";
+
+/// Timeout of a single connection attempt.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How often [`wait_until_online`] looks at the monitor's result.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Incremented every time connectivity comes back.
+static RECONNECTS: AtomicU64 = AtomicU64::new(0);
+
+static MONITOR: Once = Once::new();
+
+/// Whether offline mode is forced with [`OFFLINE_ENV`].
+pub fn forced_offline() -> bool {
+    std::env::var(OFFLINE_ENV).is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes"))
+}
+
+/// Whether the network was unreachable at the last check.
+pub fn is_offline() -> bool {
+    forced_offline() || OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Number of times connectivity has come back since startup.
+///
+/// Callers holding work for the network compare it with the value they last
+/// saw to learn that it is time to retry.
+pub fn reconnects() -> u64 {
+    RECONNECTS.load(Ordering::Relaxed)
+}
+
+/// Try to reach the model provider once, blocking for up to a few seconds.
+pub fn probe() -> bool {
+    let Ok(addrs) = PROBE_ADDR.to_socket_addrs() else {
+        return false;
+    };
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).is_ok())
+}
+
+/// Start checking connectivity in the background, once per process.
+pub fn start_monitor() {
+    MONITOR.call_once(|| {
+        if forced_offline() {
+            info!("Offline mode forced by {}", OFFLINE_ENV);
+            return;
+        }
+        let spawned = std::thread::Builder::new()
+            .name("connectivity".to_string())
+            .spawn(|| loop {
+                record(probe());
+                std::thread::sleep(CHECK_INTERVAL);
+            });
+        if let Err(e) = spawned {
+            warn!(error = %e, "Failed to start connectivity monitor");
+        }
+    });
+}
+
+/// Record the outcome of a check, logging changes.
+fn record(reachable: bool) {
+    let was_offline = OFFLINE.swap(!reachable, Ordering::Relaxed);
+    match (was_offline, reachable) {
+        (false, false) => warn!("Network unreachable; switching to offline mode"),
+        (true, true) => {
+            RECONNECTS.fetch_add(1, Ordering::Relaxed);
+            info!("Network reachable again; leaving offline mode");
+        }
+        _ => debug!(reachable, "Connectivity checked"),
+    }
+}
+
+/// Block until the network is reachable, for up to `timeout`. Returns
+/// whether it is online.
+///
+/// Without a running monitor the network is probed directly.
+pub fn wait_until_online(timeout: Duration) -> bool {
+    if forced_offline() {
+        return false;
+    }
+    let started = Instant::now();
+    loop {
+        let online = if MONITOR.is_completed() {
+            !is_offline()
+        } else {
+            probe()
+        };
+        if online {
+            return true;
+        }
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(WAIT_POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_tracks_transitions() {
+        let before = reconnects();
+        record(false);
+        assert!(OFFLINE.load(Ordering::Relaxed));
+        record(false);
+        assert_eq!(reconnects(), before);
+        record(true);
+        assert!(!OFFLINE.load(Ordering::Relaxed));
+        assert_eq!(reconnects(), before + 1);
+        record(true);
+        assert_eq!(reconnects(), before + 1);
+    }
+}
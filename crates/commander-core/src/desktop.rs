@@ -0,0 +1,131 @@
+//! Native desktop notifications.
+//!
+//! On macOS notifications are posted through `osascript` (Notification
+//! Center); elsewhere through `notify-send` (libnotify). Neither supports
+//! acting on a click, so callers put instructions for getting back to the
+//! right place in the notification itself.
+
+use std::process::Command;
+
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Errors posting a desktop notification.
+#[derive(Debug, Error)]
+pub enum DesktopNotifyError {
+    #[error("failed to run {program}: {source}")]
+    Spawn {
+        program: &'static str,
+        source: std::io::Error,
+    },
+
+    #[error("{program} failed: {stderr}")]
+    Failed {
+        program: &'static str,
+        stderr: String,
+    },
+}
+
+/// A notification to show on the desktop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopNotification {
+    pub title: String,
+    /// Second line (shown under the title on macOS, prepended to the body elsewhere).
+    pub subtitle: Option<String>,
+    pub body: String,
+}
+
+impl DesktopNotification {
+    /// Create a notification.
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            body: body.into(),
+        }
+    }
+
+    /// Set the subtitle.
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Program and arguments that post this notification on this platform.
+    pub fn command(&self) -> (&'static str, Vec<String>) {
+        if cfg!(target_os = "macos") {
+            let mut script = format!(
+                "display notification {} with title {}",
+                applescript_string(&self.body),
+                applescript_string(&self.title)
+            );
+            if let Some(subtitle) = &self.subtitle {
+                script.push_str(&format!(" subtitle {}", applescript_string(subtitle)));
+            }
+            script.push_str(" sound name \"Glass\"");
+            ("osascript", vec!["-e".to_string(), script])
+        } else {
+            let body = match &self.subtitle {
+                Some(subtitle) => format!("{}\n{}", subtitle, self.body),
+                None => self.body.clone(),
+            };
+            (
+                "notify-send",
+                vec!["--app-name=Commander".to_string(), self.title.clone(), body],
+            )
+        }
+    }
+
+    /// Post the notification, waiting for the helper program to finish.
+    pub fn send(&self) -> Result<(), DesktopNotifyError> {
+        let (program, args) = self.command();
+        let output = Command::new(program)
+            .args(&args)
+            .output()
+            .map_err(|source| DesktopNotifyError::Spawn { program, source })?;
+        if !output.status.success() {
+            return Err(DesktopNotifyError::Failed {
+                program,
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        debug!(title = %self.title, "Desktop notification sent");
+        Ok(())
+    }
+
+    /// Post the notification from a background thread, logging failures.
+    pub fn send_in_background(self) {
+        std::thread::spawn(move || {
+            if let Err(e) = self.send() {
+                warn!(error = %e, "Failed to send desktop notification");
+            }
+        });
+    }
+}
+
+/// Quote `text` as an AppleScript string literal.
+fn applescript_string(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applescript_string_escapes_quotes() {
+        assert_eq!(applescript_string(r#"say "hi" \o/"#), r#""say \"hi\" \\o/""#);
+    }
+
+    #[test]
+    fn test_command_includes_all_parts() {
+        let notification = DesktopNotification::new("api needs input", "Proceed? (y/n)")
+            .with_subtitle("Return to Commander: /connect api");
+        let (_, args) = notification.command();
+        let joined = args.join(" ");
+        assert!(joined.contains("api needs input"));
+        assert!(joined.contains("Proceed? (y/n)"));
+        assert!(joined.contains("/connect api"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Error diagnosis with a suggested fix.
+//!
+//! When a session's output shows an error, its Session Agent extracts the
+//! lines around it, looks up prior occurrences in its memories and asks the
+//! LLM for the likely cause and a fix. The resulting [`ErrorDiagnosis`] is
+//! shown as a card in the TUI and Telegram, where the fix prompt can be sent
+//! to the session or the suggestion ignored.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::change_detector::{default_significant_patterns, ChangeType};
+
+/// Output lines kept on each side of the error line.
+pub const CONTEXT_LINES: usize = 8;
+
+/// Prefix of the memory recorded for each diagnosis, so later diagnoses can
+/// find it as a prior occurrence.
+pub const DIAGNOSIS_MEMORY_PREFIX: &str = "Error diagnosis:";
+
+/// System prompt asking for the diagnosis as JSON.
+pub const DIAGNOSIS_SYSTEM_PROMPT: &str = r#"You diagnose errors in an AI coding session's terminal output.
+Reply with only a JSON object:
+{"cause": "...", "suggestion": "...", "fix_prompt": "..."}
+- cause: the likely cause in one sentence.
+- suggestion: the fix in one or two sentences, for the developer.
+- fix_prompt: an instruction to send to the coding assistant that applies the fix, written as a request to it.
+Base the diagnosis on the output shown and on prior occurrences when they match."#;
+
+/// A diagnosed error and its suggested fix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorDiagnosis {
+    /// Short ID used by "apply" and "ignore" buttons.
+    pub id: String,
+    /// Session whose output showed the error.
+    pub session_id: String,
+    /// The error line.
+    pub error: String,
+    /// Likely cause.
+    pub cause: String,
+    /// Suggested fix, for the developer.
+    pub suggestion: String,
+    /// Prompt that applies the fix when sent to the session.
+    pub fix_prompt: String,
+    /// Memories of earlier occurrences of similar errors.
+    #[serde(default)]
+    pub prior_occurrences: Vec<String>,
+}
+
+impl ErrorDiagnosis {
+    /// Build a diagnosis from the model's reply.
+    pub fn new(session_id: &str, error: &str, reply: DiagnosisReply, prior_occurrences: Vec<String>) -> Self {
+        Self {
+            id: new_id(session_id, error),
+            session_id: session_id.to_string(),
+            error: error.to_string(),
+            cause: reply.cause,
+            suggestion: reply.suggestion,
+            fix_prompt: reply.fix_prompt,
+            prior_occurrences,
+        }
+    }
+
+    /// Card text: error, cause, suggestion and how often it was seen before.
+    pub fn card_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Error: {}", self.error),
+            format!("Cause: {}", self.cause),
+            format!("Suggested fix: {}", self.suggestion),
+        ];
+        match self.prior_occurrences.len() {
+            0 => {}
+            1 => lines.push("Seen once before".to_string()),
+            n => lines.push(format!("Seen {} times before", n)),
+        }
+        lines
+    }
+
+    /// Memory recorded so the next occurrence is recognized.
+    pub fn memory_text(&self) -> String {
+        format!(
+            "{} {} | cause: {} | fix: {}",
+            DIAGNOSIS_MEMORY_PREFIX, self.error, self.cause, self.suggestion
+        )
+    }
+}
+
+/// The model's diagnosis reply.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DiagnosisReply {
+    pub cause: String,
+    pub suggestion: String,
+    pub fix_prompt: String,
+}
+
+/// Index and text of the last output line the change detector classifies
+/// as an error.
+pub fn find_error_line(output: &str) -> Option<(usize, &str)> {
+    let patterns = default_significant_patterns();
+    output
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            patterns
+                .iter()
+                .find(|(pattern, _, _)| pattern.is_match(line))
+                .is_some_and(|(_, change_type, _)| *change_type == ChangeType::Error)
+        })
+        .last()
+}
+
+/// Up to [`CONTEXT_LINES`] lines on each side of line `index`.
+pub fn error_context(output: &str, index: usize) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    let start = index.saturating_sub(CONTEXT_LINES);
+    let end = (index + CONTEXT_LINES + 1).min(lines.len());
+    lines[start.min(end)..end].join("\n")
+}
+
+/// The user prompt for an error, its surrounding output and prior occurrences.
+pub fn build_prompt(error: &str, context: &str, prior_occurrences: &[String]) -> String {
+    let prior = if prior_occurrences.is_empty() {
+        "(none)".to_string()
+    } else {
+        prior_occurrences
+            .iter()
+            .map(|p| format!("- {}", p))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!(
+        "Error:\n{}\n\nSurrounding output:\n```\n{}\n```\n\nPrior occurrences:\n{}",
+        error, context, prior
+    )
+}
+
+/// Parse the model's reply, tolerating a Markdown code fence around the JSON.
+pub fn parse_reply(reply: &str) -> Option<DiagnosisReply> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    let reply: DiagnosisReply = serde_json::from_str(reply.get(start..=end)?).ok()?;
+    let reply = DiagnosisReply {
+        cause: reply.cause.trim().to_string(),
+        suggestion: reply.suggestion.trim().to_string(),
+        fix_prompt: reply.fix_prompt.trim().to_string(),
+    };
+    if reply.suggestion.is_empty() || reply.fix_prompt.is_empty() {
+        return None;
+    }
+    Some(reply)
+}
+
+/// Generate a short diagnosis ID.
+fn new_id(session_id: &str, error: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    Utc::now().timestamp_nanos_opt().hash(&mut hasher);
+    session_id.hash(&mut hasher);
+    error.hash(&mut hasher);
+    format!("dx-{:08x}", hasher.finish() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "Compiling api v0.1.0\nerror[E0425]: cannot find value `x` in this scope\n --> src/main.rs:3:5\n\nTests finished";
+
+    #[test]
+    fn test_find_error_line() {
+        assert_eq!(
+            find_error_line(OUTPUT),
+            Some((1, "error[E0425]: cannot find value `x` in this scope"))
+        );
+        assert_eq!(find_error_line("3 tests failed\nall good"), None);
+        assert_eq!(find_error_line(""), None);
+    }
+
+    #[test]
+    fn test_error_context() {
+        assert_eq!(error_context(OUTPUT, 1).lines().count(), 5);
+        let long: String = (0..40).map(|i| format!("line {}\n", i)).collect();
+        let context = error_context(&long, 20);
+        assert!(context.starts_with("line 12"));
+        assert!(context.ends_with("line 28"));
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = parse_reply(
+            "```json\n{\"cause\": \"x is undefined\", \"suggestion\": \" Declare x. \", \"fix_prompt\": \"Declare x before use\"}\n```",
+        )
+        .unwrap();
+        assert_eq!(reply.suggestion, "Declare x.");
+        assert!(parse_reply("{\"cause\": \"?\", \"suggestion\": \"\", \"fix_prompt\": \"\"}").is_none());
+        assert!(parse_reply("no idea").is_none());
+    }
+
+    #[test]
+    fn test_card_lines() {
+        let reply = DiagnosisReply {
+            cause: "x is undefined".into(),
+            suggestion: "Declare x".into(),
+            fix_prompt: "Declare x before use".into(),
+        };
+        let diagnosis = ErrorDiagnosis::new("api", "error: x", reply, vec!["a".into(), "b".into()]);
+        assert!(diagnosis.id.starts_with("dx-"));
+        assert_eq!(diagnosis.card_lines().last().unwrap(), "Seen 2 times before");
+        assert!(diagnosis.memory_text().starts_with(DIAGNOSIS_MEMORY_PREFIX));
+    }
+}
@@ -0,0 +1,265 @@
+//! Git diff capture, classification and summarization.
+//!
+//! Shared by the TUI `/diff` view, which colors the full diff, and the chat
+//! bots, which send a trimmed summary.
+
+use std::path::Path;
+use std::process::Command;
+
+use thiserror::Error;
+
+/// Errors from capturing a diff.
+#[derive(Debug, Error)]
+pub enum DiffError {
+    #[error("{0} is not a git repository")]
+    NotARepository(String),
+
+    #[error("git diff failed: {0}")]
+    Git(String),
+
+    #[error("failed to run git: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Kind of a line in unified diff output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// `diff --git a/... b/...`
+    FileHeader,
+    /// `@@ -1,3 +1,4 @@`
+    HunkHeader,
+    /// Added line (`+`).
+    Added,
+    /// Removed line (`-`).
+    Removed,
+    /// Unchanged context line.
+    Context,
+    /// `index`, `---`/`+++`, mode and rename lines.
+    Meta,
+}
+
+/// Lines added and removed in one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffFileStat {
+    pub path: String,
+    pub added: usize,
+    pub removed: usize,
+}
+
+const META_PREFIXES: &[&str] = &[
+    "--- ", "+++ ", "index ", "new file", "deleted file", "old mode", "new mode",
+    "similarity", "rename ", "copy ", "Binary files", "\\ No newline",
+];
+
+/// Classify a line of unified diff output.
+pub fn classify_line(line: &str) -> DiffLineKind {
+    if line.starts_with("diff --git ") {
+        DiffLineKind::FileHeader
+    } else if line.starts_with("@@") {
+        DiffLineKind::HunkHeader
+    } else if META_PREFIXES.iter().any(|p| line.starts_with(p)) {
+        DiffLineKind::Meta
+    } else if line.starts_with('+') {
+        DiffLineKind::Added
+    } else if line.starts_with('-') {
+        DiffLineKind::Removed
+    } else {
+        DiffLineKind::Context
+    }
+}
+
+/// Extract the file path from a `diff --git a/x b/y` header.
+pub fn header_path(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("diff --git ")?;
+    rest.split_once(" b/").map(|(_, path)| path)
+}
+
+/// Run `git diff` against `HEAD` in `project_path`, optionally for one file.
+///
+/// Falls back to a plain `git diff` in repositories without commits.
+pub fn git_diff(project_path: &Path, file: Option<&str>) -> Result<String, DiffError> {
+    let inside = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(project_path)
+        .output()?;
+    if !inside.status.success() {
+        return Err(DiffError::NotARepository(project_path.display().to_string()));
+    }
+
+    let run = |base: Option<&str>| -> Result<std::process::Output, DiffError> {
+        let mut cmd = Command::new("git");
+        cmd.args(["diff", "--no-color", "--no-ext-diff"]);
+        if let Some(base) = base {
+            cmd.arg(base);
+        }
+        if let Some(file) = file {
+            cmd.args(["--", file]);
+        }
+        Ok(cmd.current_dir(project_path).output()?)
+    };
+
+    let mut output = run(Some("HEAD"))?;
+    if !output.status.success() {
+        output = run(None)?;
+    }
+    if !output.status.success() {
+        return Err(DiffError::Git(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Per-file added/removed line counts, in diff order.
+pub fn diff_stats(diff: &str) -> Vec<DiffFileStat> {
+    let mut stats: Vec<DiffFileStat> = Vec::new();
+    for line in diff.lines() {
+        match classify_line(line) {
+            DiffLineKind::FileHeader => stats.push(DiffFileStat {
+                path: header_path(line).unwrap_or(line).to_string(),
+                added: 0,
+                removed: 0,
+            }),
+            DiffLineKind::Added => {
+                if let Some(stat) = stats.last_mut() {
+                    stat.added += 1;
+                }
+            }
+            DiffLineKind::Removed => {
+                if let Some(stat) = stats.last_mut() {
+                    stat.removed += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// Summarize a diff for chat: per-file stats followed by the first
+/// `max_lines` changed lines with their file and hunk headers.
+pub fn summarize_diff(diff: &str, max_lines: usize) -> String {
+    let stats = diff_stats(diff);
+    if stats.is_empty() {
+        return "No changes".to_string();
+    }
+
+    let added: usize = stats.iter().map(|s| s.added).sum();
+    let removed: usize = stats.iter().map(|s| s.removed).sum();
+    let mut out = format!(
+        "{} file{} changed, +{} -{}\n",
+        stats.len(),
+        if stats.len() == 1 { "" } else { "s" },
+        added,
+        removed
+    );
+    for stat in &stats {
+        out.push_str(&format!("  {} +{} -{}\n", stat.path, stat.added, stat.removed));
+    }
+
+    let body: Vec<String> = diff
+        .lines()
+        .filter_map(|line| match classify_line(line) {
+            DiffLineKind::FileHeader => header_path(line).map(|p| format!("── {}", p)),
+            DiffLineKind::HunkHeader | DiffLineKind::Added | DiffLineKind::Removed => {
+                Some(line.to_string())
+            }
+            DiffLineKind::Context | DiffLineKind::Meta => None,
+        })
+        .collect();
+
+    out.push('\n');
+    for line in body.iter().take(max_lines) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if body.len() > max_lines {
+        out.push_str(&format!("… {} more lines\n", body.len() - max_lines));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 123..456 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    old();
++    new();
++    more();
+ }
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1 @@
+-Hello
++Hi
+";
+
+    #[test]
+    fn test_classify_line() {
+        assert_eq!(classify_line("diff --git a/x b/x"), DiffLineKind::FileHeader);
+        assert_eq!(classify_line("@@ -1 +1 @@"), DiffLineKind::HunkHeader);
+        assert_eq!(classify_line("+++ b/x"), DiffLineKind::Meta);
+        assert_eq!(classify_line("--- a/x"), DiffLineKind::Meta);
+        assert_eq!(classify_line("+added"), DiffLineKind::Added);
+        assert_eq!(classify_line("-removed"), DiffLineKind::Removed);
+        assert_eq!(classify_line(" context"), DiffLineKind::Context);
+        assert_eq!(header_path("diff --git a/src/a.rs b/src/a.rs"), Some("src/a.rs"));
+    }
+
+    #[test]
+    fn test_diff_stats() {
+        let stats = diff_stats(SAMPLE);
+        assert_eq!(
+            stats,
+            vec![
+                DiffFileStat { path: "src/lib.rs".into(), added: 2, removed: 1 },
+                DiffFileStat { path: "README.md".into(), added: 1, removed: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_diff_trims() {
+        let summary = summarize_diff(SAMPLE, 3);
+        assert!(summary.starts_with("2 files changed, +3 -2"));
+        assert!(summary.contains("  src/lib.rs +2 -1"));
+        assert!(summary.contains("── src/lib.rs"));
+        assert!(!summary.contains("Hi"));
+        assert!(summary.contains("more lines"));
+        assert_eq!(summarize_diff("", 10), "No changes");
+    }
+
+    #[test]
+    fn test_git_diff_in_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(dir.path()).output().unwrap()
+        };
+        if !git(&["init", "-q"]).status.success() {
+            return;
+        }
+        std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        git(&["add", "a.txt"]);
+
+        // No commits yet: falls back to the working tree diff
+        assert!(git_diff(dir.path(), None).is_ok());
+
+        git(&["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-qm", "init"]);
+        std::fs::write(dir.path().join("a.txt"), "two\n").unwrap();
+        let diff = git_diff(dir.path(), Some("a.txt")).unwrap();
+        assert!(diff.contains("+two"));
+        assert!(diff.contains("-one"));
+    }
+
+    #[test]
+    fn test_git_diff_outside_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(git_diff(dir.path(), None), Err(DiffError::NotARepository(_))));
+    }
+}
@@ -0,0 +1,352 @@
+//! Do-not-disturb windows and snoozing for notifications.
+//!
+//! `[notifications.dnd]` in `config.toml` lists quiet windows by weekday and
+//! local time, each for some or all channels, and `/snooze 2h` quiets every
+//! channel until a given time. While a channel is quiet its notifications are
+//! held in `dnd.json`; the channel delivers them as one digest once it is no
+//! longer quiet. Critical notifications break through unless
+//! `critical_breaks_through = false`.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config;
+
+/// Channel name of Telegram pushes.
+pub const TELEGRAM_CHANNEL: &str = "telegram";
+
+/// Channel name of desktop notifications.
+pub const DESKTOP_CHANNEL: &str = "desktop";
+
+/// Longest snooze accepted.
+const MAX_SNOOZE: Duration = Duration::days(7);
+
+/// Notification lines shown in a digest before the rest are counted.
+const MAX_DIGEST_LINES: usize = 20;
+
+/// Quiet windows, under `[notifications.dnd]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DndSettings {
+    pub windows: Vec<DndWindow>,
+    /// Whether critical notifications are delivered during quiet time.
+    pub critical_breaks_through: bool,
+}
+
+impl Default for DndSettings {
+    fn default() -> Self {
+        Self {
+            windows: Vec::new(),
+            critical_breaks_through: true,
+        }
+    }
+}
+
+impl DndSettings {
+    /// Whether `channel` is in a quiet window at local time `at`.
+    pub fn in_window(&self, channel: &str, at: NaiveDateTime) -> bool {
+        self.windows
+            .iter()
+            .any(|window| window.applies_to(channel) && window.contains(at))
+    }
+}
+
+/// A recurring quiet window.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DndWindow {
+    /// Days the window starts on (`"mon"`, `"tuesday"`, ...); every day when empty.
+    pub days: Vec<String>,
+    /// Local start time, `HH:MM`.
+    pub start: String,
+    /// Local end time, `HH:MM`. Before `start` for windows past midnight.
+    pub end: String,
+    /// Channels it quiets (`"telegram"`, `"desktop"`); all when empty.
+    pub channels: Vec<String>,
+}
+
+impl DndWindow {
+    /// Whether the window quiets `channel`.
+    pub fn applies_to(&self, channel: &str) -> bool {
+        self.channels.is_empty() || self.channels.iter().any(|c| c.eq_ignore_ascii_case(channel))
+    }
+
+    /// Whether local time `at` falls in the window. A window past midnight
+    /// belongs to the day it starts on.
+    pub fn contains(&self, at: NaiveDateTime) -> bool {
+        let (Some(start), Some(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+        let (day, time) = (at.weekday(), at.time());
+        if start <= end {
+            self.starts_on(day) && time >= start && time < end
+        } else {
+            (self.starts_on(day) && time >= start) || (self.starts_on(day.pred()) && time < end)
+        }
+    }
+
+    fn starts_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.iter().any(|d| d.trim().parse::<Weekday>().ok() == Some(day))
+    }
+}
+
+/// Parse an `HH:MM` time.
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+/// A notification held back during quiet time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeldNotification {
+    pub message: String,
+    pub session: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// Contents of `dnd.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DndState {
+    /// End of the current `/snooze`, if any.
+    #[serde(default)]
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// Held notifications, keyed by channel.
+    #[serde(default)]
+    pub held: BTreeMap<String, Vec<HeldNotification>>,
+}
+
+impl DndState {
+    /// Whether a snooze is running at `now`.
+    pub fn snoozed(&self, now: DateTime<Utc>) -> bool {
+        self.snoozed_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Load `dnd.json`.
+pub fn load_state() -> DndState {
+    let path = config::dnd_file();
+    if !path.exists() {
+        return DndState::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to parse DND state file");
+            DndState::default()
+        }),
+        Err(e) => {
+            warn!(error = %e, "Failed to read DND state file");
+            DndState::default()
+        }
+    }
+}
+
+fn save_state(state: &DndState) -> std::io::Result<()> {
+    config::ensure_runtime_state_dir()?;
+    let path = config::dnd_file();
+    fs::write(&path, serde_json::to_string_pretty(state)?)?;
+    debug!(path = %path.display(), "Saved DND state file");
+    Ok(())
+}
+
+/// Quiet every channel for `duration` (at most a week). Returns the end of
+/// the snooze.
+pub fn snooze(duration: Duration) -> std::io::Result<DateTime<Utc>> {
+    let until = Utc::now() + duration.min(MAX_SNOOZE);
+    let mut state = load_state();
+    state.snoozed_until = Some(until);
+    save_state(&state)?;
+    Ok(until)
+}
+
+/// End the snooze. Returns whether one was running.
+pub fn end_snooze() -> std::io::Result<bool> {
+    let mut state = load_state();
+    let was_snoozed = state.snoozed(Utc::now());
+    if state.snoozed_until.take().is_some() {
+        save_state(&state)?;
+    }
+    Ok(was_snoozed)
+}
+
+/// End of the running snooze, if any.
+pub fn snoozed_until() -> Option<DateTime<Utc>> {
+    let state = load_state();
+    state.snoozed(Utc::now()).then_some(state.snoozed_until).flatten()
+}
+
+/// Whether `channel` is quiet now: snoozed or in one of its windows.
+pub fn is_quiet(settings: &DndSettings, channel: &str) -> bool {
+    let now = Utc::now();
+    load_state().snoozed(now) || settings.in_window(channel, now.with_timezone(&Local).naive_local())
+}
+
+/// Whether a notification should go out on `channel` now; otherwise hold it.
+pub fn should_deliver(settings: &DndSettings, channel: &str, critical: bool) -> bool {
+    (critical && settings.critical_breaks_through) || !is_quiet(settings, channel)
+}
+
+/// Hold a notification for `channel` until it is no longer quiet.
+pub fn hold(channel: &str, message: impl Into<String>, session: Option<String>) -> std::io::Result<()> {
+    let mut state = load_state();
+    state.held.entry(channel.to_string()).or_default().push(HeldNotification {
+        message: message.into(),
+        session,
+        at: Utc::now(),
+    });
+    save_state(&state)
+}
+
+/// Remove and return the notifications held for `channel`.
+pub fn take_held(channel: &str) -> std::io::Result<Vec<HeldNotification>> {
+    let mut state = load_state();
+    let held = state.held.remove(channel).unwrap_or_default();
+    if !held.is_empty() {
+        save_state(&state)?;
+    }
+    Ok(held)
+}
+
+/// One message listing held notifications, oldest first, by the first line
+/// of each.
+pub fn format_digest(held: &[HeldNotification]) -> String {
+    let mut text = match held.len() {
+        1 => "🔕 1 notification while you were in do-not-disturb:".to_string(),
+        n => format!("🔕 {} notifications while you were in do-not-disturb:", n),
+    };
+    for notification in held.iter().take(MAX_DIGEST_LINES) {
+        let first_line = notification.message.lines().next().unwrap_or_default();
+        let time = notification.at.with_timezone(&Local).format("%H:%M");
+        match &notification.session {
+            Some(session) => {
+                let name = session.strip_prefix("commander-").unwrap_or(session);
+                text.push_str(&format!("\n• {} [{}] {}", time, name, first_line));
+            }
+            None => text.push_str(&format!("\n• {} {}", time, first_line)),
+        }
+    }
+    if held.len() > MAX_DIGEST_LINES {
+        text.push_str(&format!("\n… and {} more", held.len() - MAX_DIGEST_LINES));
+    }
+    text
+}
+
+/// Parse a snooze duration such as `2h`, `30m`, `1h30m` or `1d`. A bare
+/// number is minutes.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return (minutes > 0).then(|| Duration::minutes(minutes));
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+        total += match c {
+            'd' => Duration::days(value),
+            'h' => Duration::hours(value),
+            'm' => Duration::minutes(value),
+            _ => return None,
+        };
+    }
+    (number.is_empty() && total > Duration::zero()).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2026-10-12 is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn window(days: &[&str], start: &str, end: &str) -> DndWindow {
+        DndWindow {
+            days: days.iter().map(|d| d.to_string()).collect(),
+            start: start.into(),
+            end: end.into(),
+            channels: vec![],
+        }
+    }
+
+    #[test]
+    fn test_window_same_day() {
+        let focus = window(&["mon", "tue"], "09:00", "12:00");
+        assert!(focus.contains(at(12, 9, 0)));
+        assert!(focus.contains(at(13, 11, 59)));
+        assert!(!focus.contains(at(12, 12, 0)));
+        assert!(!focus.contains(at(14, 10, 0)));
+    }
+
+    #[test]
+    fn test_window_past_midnight() {
+        let nights = window(&["fri"], "22:00", "07:00");
+        assert!(nights.contains(at(16, 23, 30)));
+        assert!(nights.contains(at(17, 6, 59)));
+        assert!(!nights.contains(at(17, 7, 0)));
+        assert!(!nights.contains(at(16, 6, 0)));
+
+        let every_night = window(&[], "22:00", "07:00");
+        assert!(every_night.contains(at(14, 3, 0)));
+        assert!(!every_night.contains(at(14, 12, 0)));
+    }
+
+    #[test]
+    fn test_in_window_by_channel() {
+        let mut nights = window(&[], "22:00", "07:00");
+        nights.channels = vec!["telegram".into()];
+        let settings = DndSettings {
+            windows: vec![nights],
+            ..Default::default()
+        };
+        assert!(settings.in_window(TELEGRAM_CHANNEL, at(12, 23, 0)));
+        assert!(!settings.in_window(DESKTOP_CHANNEL, at(12, 23, 0)));
+        assert!(!DndSettings::default().in_window(TELEGRAM_CHANNEL, at(12, 23, 0)));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("2h"), Some(Duration::hours(2)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("45"), Some(Duration::minutes(45)));
+        assert_eq!(parse_duration("1D"), Some(Duration::days(1)));
+        assert_eq!(parse_duration("2"), Some(Duration::minutes(2)));
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("2x"), None);
+        assert_eq!(parse_duration("0"), None);
+    }
+
+    #[test]
+    fn test_format_digest() {
+        let held = vec![
+            HeldNotification {
+                message: "Tests failed\n\n/connect api".into(),
+                session: Some("commander-api".into()),
+                at: Utc::now(),
+            },
+            HeldNotification {
+                message: "Deploy finished".into(),
+                session: None,
+                at: Utc::now(),
+            },
+        ];
+        let text = format_digest(&held);
+        assert!(text.starts_with("🔕 2 notifications"));
+        assert!(text.contains("[api] Tests failed"));
+        assert!(!text.contains("/connect"));
+        assert!(text.contains("Deploy finished"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Typed errors shared by the CLI, TUI and chat bots.
+//!
+//! Each crate keeps its own detailed error type. Where an error reaches the
+//! user it is mapped to a [`CommanderError`], whose category decides the hint
+//! shown with it and the exit code of the CLI.
+
+use thiserror::Error;
+
+use crate::approvals::ApprovalError;
+use crate::ollama::OllamaError;
+use crate::settings::ConfigError;
+use crate::summarizer::SummarizerError;
+
+/// An error as shown to the user, by category.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CommanderError {
+    /// `config.toml`, a secret or another setting is missing or invalid.
+    #[error("{0}")]
+    Config(String),
+
+    /// A project, session or the daemon failed or was not found.
+    #[error("{0}")]
+    Runtime(String),
+
+    /// An LLM or another remote provider failed.
+    #[error("{0}")]
+    Provider(String),
+
+    /// tmux is missing or one of its commands failed.
+    #[error("{0}")]
+    Tmux(String),
+
+    /// Access to a path, tool or chat was refused.
+    #[error("{0}")]
+    Permission(String),
+}
+
+/// Result type for operations whose errors reach the user.
+pub type Result<T> = std::result::Result<T, CommanderError>;
+
+impl CommanderError {
+    /// Lowercase name of the category, for logs.
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Config(_) => "config",
+            Self::Runtime(_) => "runtime",
+            Self::Provider(_) => "provider",
+            Self::Tmux(_) => "tmux",
+            Self::Permission(_) => "permission",
+        }
+    }
+
+    /// Process exit code, following `sysexits.h`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(_) => 78,     // EX_CONFIG
+            Self::Runtime(_) => 70,    // EX_SOFTWARE
+            Self::Provider(_) => 69,   // EX_UNAVAILABLE
+            Self::Tmux(_) => 71,       // EX_OSERR
+            Self::Permission(_) => 77, // EX_NOPERM
+        }
+    }
+
+    /// What the user can do about this kind of error.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Self::Config(_) => {
+                "Check ~/.ai-commander/config/config.toml; `commander secrets list` shows which secrets are set."
+            }
+            Self::Runtime(_) => "`commander list` shows the projects and their sessions.",
+            Self::Provider(_) => {
+                "Check OPENROUTER_API_KEY and the network; the provider may also be rate limiting, so try again shortly."
+            }
+            Self::Tmux(_) => "Make sure tmux is installed and on PATH (`tmux -V`), and that the session still exists.",
+            Self::Permission(_) => "Check the path's permissions, or the project's approval mode and permission profile.",
+        }
+    }
+
+    /// The error followed by its hint, as printed by the CLI and sent by the
+    /// chat bots.
+    pub fn user_message(&self) -> String {
+        format!("{}\nHint: {}", self, self.hint())
+    }
+}
+
+impl From<std::io::Error> for CommanderError {
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::PermissionDenied => Self::Permission(err.to_string()),
+            _ => Self::Runtime(err.to_string()),
+        }
+    }
+}
+
+impl From<ConfigError> for CommanderError {
+    fn from(err: ConfigError) -> Self {
+        Self::Config(err.to_string())
+    }
+}
+
+impl From<ApprovalError> for CommanderError {
+    fn from(err: ApprovalError) -> Self {
+        match err {
+            ApprovalError::Io(e) => e.into(),
+            _ => Self::Runtime(err.to_string()),
+        }
+    }
+}
+
+impl From<OllamaError> for CommanderError {
+    fn from(err: OllamaError) -> Self {
+        Self::Provider(err.to_string())
+    }
+}
+
+impl From<SummarizerError> for CommanderError {
+    fn from(err: SummarizerError) -> Self {
+        match err {
+            SummarizerError::NoApiKey => Self::Config(err.to_string()),
+            _ => Self::Provider(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_message_and_exit_code() {
+        let err = CommanderError::Tmux("session 'api' not found".into());
+        assert_eq!(err.to_string(), "session 'api' not found");
+        assert!(err.user_message().starts_with("session 'api' not found\nHint: "));
+        assert_eq!(err.exit_code(), 71);
+        assert_eq!(err.category(), "tmux");
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let denied = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert!(matches!(CommanderError::from(denied), CommanderError::Permission(_)));
+
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert!(matches!(CommanderError::from(missing), CommanderError::Runtime(_)));
+    }
+}
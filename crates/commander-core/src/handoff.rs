@@ -0,0 +1,496 @@
+//! Shared session registry for handing sessions off between frontends.
+//!
+//! The registry lives in `~/.ai-commander/state/session_registry.json` so that
+//! the TUI, REPL and Telegram bot see the same picture of each session:
+//! - which frontend is currently attached
+//! - the last few messages exchanged, regardless of frontend
+//! - a query that is still waiting for a response
+//!
+//! When a user switches frontends, the new frontend calls [`SessionRegistry::attach`]
+//! (or [`SessionRegistry::takeover`] to claim a session held elsewhere) and shows
+//! [`SessionRecord::resume_context`] so the conversation carries on.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config;
+
+/// Maximum number of recent messages kept per session.
+const MAX_RECENT_MESSAGES: usize = 20;
+
+/// Number of recent messages shown when resuming on another frontend.
+const RESUME_MESSAGES: usize = 5;
+
+/// Maximum characters of a message shown in the resume context.
+const RESUME_PREVIEW_CHARS: usize = 200;
+
+/// A Commander frontend that can attach to a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Frontend {
+    /// The ratatui terminal interface.
+    Tui,
+    /// The line-based REPL.
+    Repl,
+    /// The Telegram bot.
+    Telegram,
+    /// The Matrix bot.
+    Matrix,
+}
+
+impl fmt::Display for Frontend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Frontend::Tui => write!(f, "TUI"),
+            Frontend::Repl => write!(f, "REPL"),
+            Frontend::Telegram => write!(f, "Telegram"),
+            Frontend::Matrix => write!(f, "Matrix"),
+        }
+    }
+}
+
+/// Which frontend holds a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// The attached frontend.
+    pub frontend: Frontend,
+    /// Frontend-specific client identifier (e.g. Telegram chat ID).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// When the frontend attached.
+    pub attached_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    fn new(frontend: Frontend, client_id: Option<String>) -> Self {
+        Self {
+            frontend,
+            client_id,
+            attached_at: Utc::now(),
+        }
+    }
+
+    /// Returns true if this attachment belongs to the given frontend and client.
+    pub fn is_held_by(&self, frontend: Frontend, client_id: Option<&str>) -> bool {
+        self.frontend == frontend && self.client_id.as_deref() == client_id
+    }
+}
+
+/// Direction of a recorded message, from the user's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HandoffDirection {
+    /// Sent by the user to the session.
+    Sent,
+    /// Received from the session.
+    Received,
+}
+
+/// A message exchanged with a session through some frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffMessage {
+    /// When the message was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// Frontend the message went through.
+    pub frontend: Frontend,
+    /// Whether the user sent or received it.
+    pub direction: HandoffDirection,
+    /// Message text.
+    pub text: String,
+}
+
+/// A query that has been sent but not yet answered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingResponse {
+    /// The query text.
+    pub query: String,
+    /// Frontend that sent the query.
+    pub frontend: Frontend,
+    /// When the query was sent.
+    pub sent_at: DateTime<Utc>,
+}
+
+/// Shared state for one session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Frontend currently attached, if any.
+    #[serde(default)]
+    pub attached: Option<Attachment>,
+    /// Most recent messages, oldest first.
+    #[serde(default)]
+    pub recent: VecDeque<HandoffMessage>,
+    /// Query still waiting for a response.
+    #[serde(default)]
+    pub pending: Option<PendingResponse>,
+    /// Last time anything changed.
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl SessionRecord {
+    /// Build a summary of recent activity for a frontend taking over the session.
+    ///
+    /// Returns `None` if nothing happened on another frontend since the last
+    /// message seen through `frontend`, so reconnecting to the same frontend
+    /// stays quiet.
+    pub fn resume_context(&self, frontend: Frontend) -> Option<String> {
+        let last_own = self.recent.iter().rposition(|m| m.frontend == frontend);
+        let foreign_since_own = self
+            .recent
+            .iter()
+            .skip(last_own.map_or(0, |i| i + 1))
+            .any(|m| m.frontend != frontend);
+        let foreign_pending = self.pending.as_ref().is_some_and(|p| p.frontend != frontend);
+
+        if !foreign_since_own && !foreign_pending {
+            return None;
+        }
+
+        let mut lines = Vec::new();
+        let skip = self.recent.len().saturating_sub(RESUME_MESSAGES);
+        for msg in self.recent.iter().skip(skip) {
+            let arrow = match msg.direction {
+                HandoffDirection::Sent => ">",
+                HandoffDirection::Received => "<",
+            };
+            lines.push(format!("[{}] {} {}", msg.frontend, arrow, preview(&msg.text)));
+        }
+
+        if let Some(pending) = &self.pending {
+            lines.push(format!(
+                "Awaiting response to \"{}\" (sent from {})",
+                preview(&pending.query),
+                pending.frontend
+            ));
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    /// The last few messages exchanged through any frontend, as
+    /// "User: ..." and "Response: ..." lines, for briefing a runtime that
+    /// replaces the one the conversation was held with. `None` without
+    /// messages.
+    pub fn conversation_summary(&self) -> Option<String> {
+        let skip = self.recent.len().saturating_sub(RESUME_MESSAGES);
+        let lines: Vec<String> = self
+            .recent
+            .iter()
+            .skip(skip)
+            .map(|msg| {
+                let speaker = match msg.direction {
+                    HandoffDirection::Sent => "User",
+                    HandoffDirection::Received => "Response",
+                };
+                format!("{}: {}", speaker, preview(&msg.text))
+            })
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+}
+
+/// Result of trying to attach to a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttachOutcome {
+    /// The frontend is now attached.
+    Attached,
+    /// Another frontend holds the session; use takeover to claim it.
+    HeldBy(Attachment),
+}
+
+/// Registry of session records keyed by session name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionRegistry {
+    /// Records keyed by tmux session name.
+    #[serde(default)]
+    pub sessions: HashMap<String, SessionRecord>,
+}
+
+impl SessionRegistry {
+    /// Get the record for a session.
+    pub fn get(&self, session: &str) -> Option<&SessionRecord> {
+        self.sessions.get(session)
+    }
+
+    fn record_mut(&mut self, session: &str) -> &mut SessionRecord {
+        let record = self.sessions.entry(session.to_string()).or_default();
+        record.updated_at = Some(Utc::now());
+        record
+    }
+
+    /// Attach a frontend to a session unless another frontend holds it.
+    pub fn attach(
+        &mut self,
+        session: &str,
+        frontend: Frontend,
+        client_id: Option<String>,
+    ) -> AttachOutcome {
+        let record = self.record_mut(session);
+        match &record.attached {
+            Some(holder) if holder.frontend != frontend => AttachOutcome::HeldBy(holder.clone()),
+            _ => {
+                record.attached = Some(Attachment::new(frontend, client_id));
+                AttachOutcome::Attached
+            }
+        }
+    }
+
+    /// Claim a session for a frontend, returning the previous holder if it differs.
+    pub fn takeover(
+        &mut self,
+        session: &str,
+        frontend: Frontend,
+        client_id: Option<String>,
+    ) -> Option<Attachment> {
+        let record = self.record_mut(session);
+        let previous = record.attached.replace(Attachment::new(frontend, client_id.clone()));
+        previous.filter(|p| !p.is_held_by(frontend, client_id.as_deref()))
+    }
+
+    /// Release a session if it is held by the given frontend.
+    pub fn detach(&mut self, session: &str, frontend: Frontend) {
+        if let Some(record) = self.sessions.get_mut(session) {
+            if record.attached.as_ref().is_some_and(|a| a.frontend == frontend) {
+                record.attached = None;
+                record.updated_at = Some(Utc::now());
+            }
+        }
+    }
+
+    /// Record a message exchanged through a frontend.
+    ///
+    /// A received message also clears the pending response.
+    pub fn record_message(
+        &mut self,
+        session: &str,
+        frontend: Frontend,
+        direction: HandoffDirection,
+        text: impl Into<String>,
+    ) {
+        let record = self.record_mut(session);
+        while record.recent.len() >= MAX_RECENT_MESSAGES {
+            record.recent.pop_front();
+        }
+        record.recent.push_back(HandoffMessage {
+            timestamp: Utc::now(),
+            frontend,
+            direction,
+            text: text.into(),
+        });
+
+        match direction {
+            HandoffDirection::Sent => {}
+            HandoffDirection::Received => record.pending = None,
+        }
+    }
+
+    /// Record a query sent through a frontend and mark it as awaiting a response.
+    pub fn record_query(&mut self, session: &str, frontend: Frontend, query: impl Into<String>) {
+        let query = query.into();
+        self.record_message(session, frontend, HandoffDirection::Sent, query.clone());
+        self.record_mut(session).pending = Some(PendingResponse {
+            query,
+            frontend,
+            sent_at: Utc::now(),
+        });
+    }
+
+    /// Remove a session's record entirely (e.g. when the session is stopped).
+    pub fn remove(&mut self, session: &str) -> Option<SessionRecord> {
+        self.sessions.remove(session)
+    }
+}
+
+/// Truncate text to a single-line preview.
+fn preview(text: &str) -> String {
+    let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+    if line.chars().count() > RESUME_PREVIEW_CHARS {
+        let truncated: String = line.chars().take(RESUME_PREVIEW_CHARS).collect();
+        format!("{}...", truncated)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Load the session registry from the shared file.
+pub fn load_session_registry() -> SessionRegistry {
+    let path = config::session_registry_file();
+
+    if !path.exists() {
+        return SessionRegistry::default();
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to parse session registry");
+            SessionRegistry::default()
+        }),
+        Err(e) => {
+            warn!(error = %e, "Failed to read session registry");
+            SessionRegistry::default()
+        }
+    }
+}
+
+/// Save the session registry to the shared file.
+pub fn save_session_registry(registry: &SessionRegistry) -> std::io::Result<()> {
+    let path = config::session_registry_file();
+    config::ensure_runtime_state_dir()?;
+
+    // Write to a temp file and rename so concurrent readers never see a partial file
+    let tmp = path.with_extension("json.tmp");
+    let content = serde_json::to_string_pretty(registry)?;
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, &path)?;
+    debug!(path = %path.display(), "Saved session registry");
+    Ok(())
+}
+
+/// Load the registry, apply `f`, and save it back.
+pub fn update_session_registry<T>(f: impl FnOnce(&mut SessionRegistry) -> T) -> std::io::Result<T> {
+    let mut registry = load_session_registry();
+    let result = f(&mut registry);
+    save_session_registry(&registry)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach_free_session() {
+        let mut registry = SessionRegistry::default();
+        assert_eq!(registry.attach("s1", Frontend::Tui, None), AttachOutcome::Attached);
+
+        let holder = registry.get("s1").unwrap().attached.as_ref().unwrap();
+        assert_eq!(holder.frontend, Frontend::Tui);
+    }
+
+    #[test]
+    fn test_attach_held_elsewhere() {
+        let mut registry = SessionRegistry::default();
+        registry.attach("s1", Frontend::Tui, None);
+
+        match registry.attach("s1", Frontend::Telegram, Some("42".into())) {
+            AttachOutcome::HeldBy(holder) => assert_eq!(holder.frontend, Frontend::Tui),
+            other => panic!("expected HeldBy, got {:?}", other),
+        }
+
+        // Re-attaching from the same frontend is fine
+        assert_eq!(registry.attach("s1", Frontend::Tui, None), AttachOutcome::Attached);
+    }
+
+    #[test]
+    fn test_takeover_returns_previous_holder() {
+        let mut registry = SessionRegistry::default();
+        registry.attach("s1", Frontend::Tui, None);
+
+        let previous = registry.takeover("s1", Frontend::Telegram, Some("42".into()));
+        assert_eq!(previous.unwrap().frontend, Frontend::Tui);
+
+        let holder = registry.get("s1").unwrap().attached.clone().unwrap();
+        assert!(holder.is_held_by(Frontend::Telegram, Some("42")));
+
+        // Taking over again from the same client reports no previous holder
+        assert!(registry.takeover("s1", Frontend::Telegram, Some("42".into())).is_none());
+    }
+
+    #[test]
+    fn test_detach_only_by_holder() {
+        let mut registry = SessionRegistry::default();
+        registry.attach("s1", Frontend::Tui, None);
+
+        registry.detach("s1", Frontend::Repl);
+        assert!(registry.get("s1").unwrap().attached.is_some());
+
+        registry.detach("s1", Frontend::Tui);
+        assert!(registry.get("s1").unwrap().attached.is_none());
+    }
+
+    #[test]
+    fn test_query_and_response_track_pending() {
+        let mut registry = SessionRegistry::default();
+        registry.record_query("s1", Frontend::Tui, "run the tests");
+        assert!(registry.get("s1").unwrap().pending.is_some());
+
+        registry.record_message("s1", Frontend::Tui, HandoffDirection::Received, "all green");
+        let record = registry.get("s1").unwrap();
+        assert!(record.pending.is_none());
+        assert_eq!(record.recent.len(), 2);
+    }
+
+    #[test]
+    fn test_recent_messages_capped() {
+        let mut registry = SessionRegistry::default();
+        for i in 0..(MAX_RECENT_MESSAGES + 5) {
+            registry.record_message("s1", Frontend::Repl, HandoffDirection::Sent, format!("m{}", i));
+        }
+
+        let record = registry.get("s1").unwrap();
+        assert_eq!(record.recent.len(), MAX_RECENT_MESSAGES);
+        assert_eq!(record.recent.front().unwrap().text, "m5");
+    }
+
+    #[test]
+    fn test_resume_context_for_other_frontend() {
+        let mut registry = SessionRegistry::default();
+        registry.record_query("s1", Frontend::Tui, "fix the build");
+        let record = registry.get("s1").unwrap();
+
+        // The frontend that sent the query sees nothing new
+        assert!(record.resume_context(Frontend::Tui).is_none());
+
+        let context = record.resume_context(Frontend::Telegram).unwrap();
+        assert!(context.contains("[TUI] > fix the build"));
+        assert!(context.contains("Awaiting response to \"fix the build\" (sent from TUI)"));
+    }
+
+    #[test]
+    fn test_resume_context_after_own_message() {
+        let mut registry = SessionRegistry::default();
+        registry.record_message("s1", Frontend::Tui, HandoffDirection::Sent, "a");
+        registry.record_message("s1", Frontend::Telegram, HandoffDirection::Sent, "b");
+        registry.record_message("s1", Frontend::Tui, HandoffDirection::Sent, "c");
+
+        let record = registry.get("s1").unwrap();
+        // Telegram has not seen "c" from the TUI
+        assert!(record.resume_context(Frontend::Telegram).is_some());
+        // TUI's last message is the newest, nothing foreign since then
+        assert!(record.resume_context(Frontend::Tui).is_none());
+    }
+
+    #[test]
+    fn test_conversation_summary_from_any_frontend() {
+        let mut registry = SessionRegistry::default();
+        registry.record_message("s1", Frontend::Tui, HandoffDirection::Sent, "add a login page");
+        registry.record_message("s1", Frontend::Telegram, HandoffDirection::Received, "Added\nsrc/login.tsx");
+
+        let record = registry.get("s1").unwrap();
+        assert_eq!(
+            record.conversation_summary().unwrap(),
+            "User: add a login page\nResponse: Added"
+        );
+        assert!(SessionRecord::default().conversation_summary().is_none());
+    }
+
+    #[test]
+    fn test_registry_roundtrip() {
+        let mut registry = SessionRegistry::default();
+        registry.attach("s1", Frontend::Telegram, Some("42".into()));
+        registry.record_query("s1", Frontend::Telegram, "status?");
+
+        let json = serde_json::to_string(&registry).unwrap();
+        assert!(json.contains("\"telegram\""));
+
+        let restored: SessionRegistry = serde_json::from_str(&json).unwrap();
+        let record = restored.get("s1").unwrap();
+        assert_eq!(record.recent.len(), 1);
+        assert_eq!(record.pending.as_ref().unwrap().query, "status?");
+    }
+}
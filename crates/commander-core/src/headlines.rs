@@ -0,0 +1,222 @@
+//! One-line, project-specific text for "waiting for input" notifications.
+//!
+//! [`notify_session_ready`](crate::notify_session_ready) and
+//! [`notify_sessions_waiting`](crate::notify_sessions_waiting) describe each
+//! session with a headline such as "duetto: tests failing in auth module,
+//! needs decision on mocking strategy", written by the cheap tier-2 model from
+//! the session's status line and the end of its screen. Headlines are cached
+//! under a key of those inputs, so a session still showing the same screen is
+//! not summarized again.
+//!
+//! Without an LLM (no API key, offline, a failed request, or `[notifications]
+//! llm_headlines = false`) the notifications keep their template text built
+//! by the notification parser. After a failed request the LLM is left alone
+//! for [`FAILURE_BACKOFF`], so a dead endpoint doesn't slow every
+//! notification down.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::config::config_file;
+use crate::notification_parser::{parse_session_preview, strip_ansi};
+use crate::output_filter::clean_screen_preview;
+use crate::redaction::redact;
+use crate::settings::Settings;
+use crate::summarizer::{get_api_key, get_tier2_model, require_online, SummarizerError, OPENROUTER_API_URL};
+
+/// Screen lines sent to the model, most recent kept.
+const MAX_SCREEN_LINES: usize = 40;
+
+/// Longest headline kept; longer replies are cut at a word boundary.
+const MAX_HEADLINE_CHARS: usize = 140;
+
+/// Headlines kept in the cache before it is cleared.
+const MAX_CACHED: usize = 256;
+
+/// How long the LLM is skipped after a failed request.
+pub const FAILURE_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Timeout of a headline request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// System prompt asking for a single-line headline.
+const SYSTEM_PROMPT: &str = r#"You write one-line notifications about AI coding sessions that stopped and are waiting for their user.
+Reply with a single line under 120 characters: the project name, a colon, what the session did or ran into, and what it needs from the user.
+Be specific: name the module, test, file or decision involved. No preamble, quotes or Markdown.
+Example: duetto: tests failing in auth module, needs decision on mocking strategy"#;
+
+static CACHE: LazyLock<Mutex<HashMap<u64, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static LAST_FAILURE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Template description of `project` from its status line `preview`, e.g.
+/// "on main, 70% context used". `None` when it says nothing beyond the name.
+pub fn template_brief(project: &str, preview: &str) -> Option<String> {
+    let clean = strip_ansi(preview);
+    if clean.is_empty() {
+        return None;
+    }
+    let brief = parse_session_preview(project, &clean).to_brief();
+    (!brief.is_empty() && brief != project).then_some(brief)
+}
+
+/// The headline for `project`, waiting on `screen` with the status line
+/// `preview`, or `None` to fall back on the template text.
+///
+/// Blocks on the LLM request when the headline is not cached.
+pub fn session_headline(project: &str, preview: &str, screen: &str) -> Option<String> {
+    if !Settings::load(&config_file()).unwrap_or_default().notifications.llm_headlines {
+        return None;
+    }
+    let prompt = build_prompt(project, preview, screen)?;
+    let key = cache_key(&prompt);
+    if let Some(headline) = CACHE.lock().ok()?.get(&key) {
+        return Some(headline.clone());
+    }
+    if LAST_FAILURE
+        .lock()
+        .ok()?
+        .is_some_and(|at| at.elapsed() < FAILURE_BACKOFF)
+    {
+        return None;
+    }
+
+    let headline = match request_headline(&prompt) {
+        Ok(reply) => parse_reply(project, &reply),
+        Err(SummarizerError::NoApiKey | SummarizerError::Offline) => return None,
+        Err(e) => {
+            warn!(project = %project, error = %e, "Headline request failed, using template text");
+            None
+        }
+    };
+    let Some(headline) = headline else {
+        if let Ok(mut last) = LAST_FAILURE.lock() {
+            *last = Some(Instant::now());
+        }
+        return None;
+    };
+    debug!(project = %project, headline = %headline, "Generated notification headline");
+    if let Ok(mut cache) = CACHE.lock() {
+        if cache.len() >= MAX_CACHED {
+            cache.clear();
+        }
+        cache.insert(key, headline.clone());
+    }
+    Some(headline)
+}
+
+/// The user prompt for `project`, or `None` when there is nothing to go on.
+pub fn build_prompt(project: &str, preview: &str, screen: &str) -> Option<String> {
+    let screen = clean_screen_preview(&strip_ansi(screen), MAX_SCREEN_LINES);
+    let status = template_brief(project, preview);
+    if screen.trim().is_empty() && status.is_none() {
+        return None;
+    }
+    let mut prompt = format!("Project: {}\n", project);
+    if let Some(status) = status {
+        prompt.push_str(&format!("Status: {}\n", status));
+    }
+    if !screen.trim().is_empty() {
+        prompt.push_str(&format!("\nEnd of the session's screen:\n{}\n", screen));
+    }
+    Some(redact(&prompt))
+}
+
+/// The headline in the model's `reply`: its first line, stripped of quotes
+/// and list markers, prefixed with `project` when the model left it out.
+pub fn parse_reply(project: &str, reply: &str) -> Option<String> {
+    let line = reply
+        .lines()
+        .map(|l| l.trim().trim_start_matches(['-', '*', '#', '>']).trim())
+        .map(|l| l.trim_matches(['"', '\'', '`']).trim())
+        .find(|l| !l.is_empty())?;
+
+    let named = line
+        .get(..project.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(project));
+    let headline = if named {
+        line.to_string()
+    } else {
+        format!("{}: {}", project, line)
+    };
+
+    if headline.chars().count() <= MAX_HEADLINE_CHARS {
+        return Some(headline);
+    }
+    let cut: String = headline.chars().take(MAX_HEADLINE_CHARS).collect();
+    let end = cut.rfind(char::is_whitespace).unwrap_or(cut.len());
+    Some(format!("{}…", cut[..end].trim_end_matches([',', ';', ':'])))
+}
+
+fn cache_key(prompt: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    prompt.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn request_headline(prompt: &str) -> Result<String, SummarizerError> {
+    require_online()?;
+    let api_key = get_api_key().ok_or(SummarizerError::NoApiKey)?;
+    let body = serde_json::json!({
+        "model": get_tier2_model(),
+        "messages": [
+            {"role": "system", "content": SYSTEM_PROMPT},
+            {"role": "user", "content": prompt}
+        ],
+        "max_tokens": 60
+    });
+    let response = reqwest::blocking::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| SummarizerError::RequestFailed(e.to_string()))?
+        .post(OPENROUTER_API_URL)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&body)
+        .send()
+        .map_err(|e| SummarizerError::RequestFailed(e.to_string()))?;
+    let json: serde_json::Value = response
+        .json()
+        .map_err(|e| SummarizerError::ParseError(e.to_string()))?;
+    json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| SummarizerError::ParseError("No content in response".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_and_reply_parsing() {
+        assert!(build_prompt("duetto", "", "  \n").is_none());
+        let prompt = build_prompt(
+            "duetto",
+            "",
+            "\x1b[31mFAILED\x1b[0m tests/auth_test.rs::login\nShould I mock the OAuth client?",
+        )
+        .unwrap();
+        assert!(prompt.starts_with("Project: duetto\n"));
+        assert!(prompt.contains("FAILED tests/auth_test.rs::login"));
+
+        assert_eq!(
+            parse_reply("duetto", "\"duetto: tests failing in auth, needs a mocking decision\"\n").as_deref(),
+            Some("duetto: tests failing in auth, needs a mocking decision")
+        );
+        assert_eq!(
+            parse_reply("duetto", "\n- Tests failing in auth module").as_deref(),
+            Some("duetto: Tests failing in auth module")
+        );
+        assert!(parse_reply("duetto", " \n ").is_none());
+
+        let long = parse_reply("api", &"word ".repeat(60)).unwrap();
+        assert!(long.chars().count() <= MAX_HEADLINE_CHARS + 1);
+        assert!(long.ends_with("word…"));
+    }
+}
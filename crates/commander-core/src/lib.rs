@@ -1,27 +1,71 @@
 //! Commander Core - shared business logic for all Commander interfaces.
 //!
 //! This crate provides core functionality used by both the TUI (ai-commander)
-//! and Telegram (commander-telegram) interfaces:
+//! and the chat bot (commander-telegram, commander-matrix) interfaces:
 //!
+//! - **approvals**: Approval mode and the queue of tool calls awaiting a decision
 //! - **change_detector**: Smart change detection to reduce inference costs
+//! - **command_audit**: Append-only audit log of input sent to sessions
 //! - **config**: Shared configuration paths and utilities
+//! - **connectivity**: Offline mode detected with periodic connectivity checks
+//! - **desktop**: Native desktop notifications (Notification Center / libnotify)
+//! - **diagnosis**: Error diagnoses with a suggested fix for a session's errors
+//! - **dnd**: Do-not-disturb windows, `/snooze` and digests of held notifications
+//! - **diff**: Capture, classify and summarize `git diff` output
+//! - **error**: Typed user-facing errors with hints and exit codes
+//! - **handoff**: Shared session registry for switching between frontends
+//! - **headlines**: LLM-written one-line headlines for "waiting for input" notifications
+//! - **logging**: Structured JSON logs, combined and per session
 //! - **migration**: Storage migration from legacy paths
+//! - **notifications**: Cross-channel notification queue shared by the chat bots
 //! - **notification_parser**: Parse timer notifications into structured data
 //! - **onboarding**: First-run setup wizard
 //! - **output_filter**: Filter UI noise from Claude Code terminal output
+//! - **pairing**: Pairing codes that link chat bots to projects
+//! - **pause**: Sessions paused with `/pause`, skipped by pollers until `/resume`
+//! - **pr_summary**: LLM-written commit messages and pull requests for stopped sessions
+//! - **project_templates**: Built-in and user templates for scaffolding new projects
+//! - **redaction**: Replace secrets in session output before it is stored or sent
+//! - **send_lock**: Per-session send locks so frontends take turns typing into a session
+//! - **snippets**: Named prompt snippets with project variables, sent with `/run` or `!name`
+//! - **settings**: Typed `config.toml` settings and a watcher that publishes changes
 //! - **structured_summarizer**: Extract structured facts and template-based summaries
 //! - **summarizer**: Summarize long responses using OpenRouter API
 
+pub mod approvals;
 pub mod change_detector;
+pub mod command_audit;
 pub mod client_adapter;
 pub mod config;
+pub mod connectivity;
+pub mod desktop;
+pub mod diagnosis;
+pub mod diff;
+pub mod dnd;
+pub mod error;
+pub mod handoff;
+pub mod headlines;
 pub mod log;
+pub mod logging;
 pub mod migration;
+pub mod notes;
 pub mod notification_parser;
+pub mod notifications;
 pub mod ollama;
 pub mod onboarding;
 pub mod options;
 pub mod output_filter;
+pub mod pairing;
+pub mod pause;
+pub mod pr_summary;
+pub mod project_templates;
+pub mod recap;
+pub mod recording;
+pub mod redaction;
+pub mod secrets;
+pub mod send_lock;
+pub mod settings;
+pub mod snippets;
 pub mod structured_summarizer;
 pub mod summarizer;
 pub mod usage;
@@ -32,31 +76,87 @@ pub use ollama::{OllamaClient, OllamaError};
 // Re-export session log helpers
 pub use log::{
     append_log_entry, append_user_message, archive_session_logs,
+    import_entries as import_log_entries,
     list_dates as list_log_dates, log_dir_for,
     read_all_entries as read_all_log_entries,
-    read_entries as read_log_entries, LogEntry,
+    read_entries as read_log_entries, search_entries as search_log_entries, LogEntry,
+    TranscriptMatch,
+};
+
+// Re-export structured logging
+pub use logging::{
+    combined_log_path, read_log, session_log_path, LogRecord, LogTail, SessionLogLayer,
 };
 
 // Re-export commonly used items for convenience
+pub use command_audit::{AuditQuery, CommandAuditEntry, CommandSender, InputKind};
+pub use approvals::{
+    approval_mode_enabled, decide_approval, pending_approvals, queue_approval,
+    set_approval_mode, ApprovalError, ApprovalQueue, ApprovalRequest, ApprovalStatus,
+};
 pub use config::{
-    cache_dir, chroma_dir, config_dir, config_file, db_dir, ensure_all_dirs, ensure_config_dir,
-    ensure_runtime_state_dir, ensure_sessions_dir, ensure_state_dir, env_file, legacy_state_dir,
-    logs_dir, notifications_file, pairing_file, projects_file, runtime_state_dir, sessions_dir,
-    state_dir, telegram_pid_file,
+    adapters_dir, approvals_audit_file, approvals_file, cache_dir, chroma_dir, config_dir,
+    config_file, db_dir, ensure_all_dirs, ensure_config_dir, ensure_runtime_state_dir,
+    dnd_file, ensure_sessions_dir, ensure_state_dir, env_file, escalations_file, evals_dir, learned_patterns_file,
+    legacy_state_dir, logs_dir, matrix_rooms_file, notes_dir, notifications_file, pairing_file, paused_file,
+    project_templates_dir, projects_file, recaps_file, recordings_dir, runtime_state_dir, secrets_dir,
+    session_registry_file, sessions_dir, state_dir, telegram_bot_file, telegram_heartbeat_file, telegram_pid_file, themes_dir,
+    workflow_runs_dir, workflows_dir,
+};
+pub use desktop::{DesktopNotification, DesktopNotifyError};
+pub use diagnosis::ErrorDiagnosis;
+pub use dnd::{DndSettings, DndWindow, HeldNotification};
+pub use pause::{PauseMethod, PausedSession};
+pub use error::CommanderError;
+pub use diff::{
+    classify_line as classify_diff_line, diff_stats, git_diff, summarize_diff, DiffError,
+    DiffFileStat, DiffLineKind,
+};
+pub use handoff::{
+    load_session_registry, save_session_registry, update_session_registry, AttachOutcome,
+    Attachment, Frontend, HandoffDirection, HandoffMessage, PendingResponse, SessionRecord,
+    SessionRegistry,
 };
 pub use migration::migrate_if_needed;
+pub use notifications::{
+    get_unread_notifications, mark_notifications_read, notify_session_ready,
+    notify_session_resumed, notify_sessions_waiting, push_critical_notification, push_notification,
+    Notification,
+};
+pub use notes::{add_note, notes_context, notes_path, session_notes, SessionNote};
+pub use pairing::{consume_pairing, create_pairing, generate_code};
+pub use pr_summary::{generate_pr_summary, generate_pr_summary_blocking, PrSummary};
+pub use project_templates::{
+    find_template, list_templates, ProjectTemplate, TemplateError, TemplateSource,
+};
+pub use recording::{
+    list_recordings, recording_path, recordings_for, resolve_recording, Recorder, Recording, RecordingError,
+};
+pub use send_lock::{acquire_send_lock, SendLock, SendLockError};
+pub use snippets::{load_snippets, Snippet, SnippetContext, SnippetError, Snippets};
+pub use recap::{mark_seen, recap_async, recap_blocking, RecapInput};
+pub use redaction::{redact, Redactor, REDACTED};
+pub use secrets::{get_secret, secret_store};
+pub use settings::{
+    subscribe_config_changes, AgentSettings, BudgetSettings, ConfigChange, ConfigError, ConfigWatcher,
+    EventSettings, FilesystemSettings, GcPolicy, GcSettings, NotificationSettings, PermissionProfile,
+    PermissionSettings, ProjectNotificationSettings, ReviewSettings, RoutingSettings, RuntimeSettings, Settings, StopSettings, TelegramSettings, ThemeSettings,
+    RecapSettings, RedactionSettings, TuiSettings, TunnelBackend, TunnelSettings, VoiceSettings, WebhookSettings,
+};
 pub use onboarding::{load_config, needs_onboarding, run_onboarding};
 pub use output_filter::{clean_response, clean_screen_preview, detect_adapter, detect_selector, find_new_lines, is_claude_ready, is_mpm_ready, is_ui_noise, Adapter, SelectorPrompt, SessionEvent};
 pub use summarizer::{
     interpret_screen_context, is_actively_working, is_available as is_summarization_available,
     llm_available, summarize_async, summarize_blocking, summarize_blocking_with_fallback,
-    summarize_incremental, summarize_incremental_tiered, summarize_tiered, summarize_with_fallback,
-    SummarizerError,
+    set_model_override as set_summarizer_model, summarize_differential_blocking, summarize_incremental,
+    summarize_incremental_tiered, summarize_tiered, summarize_with_fallback, DifferentialRequest,
+    DifferentialStats, DifferentialSummary, SummarizerError,
 };
 
 // Re-export change detection types
 pub use change_detector::{
-    ChangeDetector, ChangeEvent, ChangeNotification, ChangeType, Significance, SmartPoller,
+    ChangeDetector, ChangeEvent, ChangeNotification, ChangeType, LearnedPattern, LearnedPatterns,
+    Significance, SignificanceObservation, SmartPoller,
 };
 
 // Re-export notification parsing
@@ -72,4 +172,6 @@ pub use client_adapter::{
 };
 
 // Re-export structured summarizer
-pub use structured_summarizer::{extract as extract_structured, StructuredSummary, TestResult};
+pub use structured_summarizer::{
+    extract as extract_structured, ProgressMilestones, StructuredSummary, TestResult,
+};
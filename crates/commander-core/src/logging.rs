@@ -0,0 +1,388 @@
+//! Structured per-session logs.
+//!
+//! [`SessionLogLayer`] is a `tracing` layer that writes every event as a
+//! JSON line to `logs/combined.jsonl` and, when the event or one of its
+//! spans has a `session`, `session_id` or `project` field, also to
+//! `logs/sessions/<session>.jsonl`. tmux session names lose their
+//! `commander-` prefix, so a session's log is found by its project name.
+//!
+//! Each binary installs the layer next to its usual stderr output, tagged
+//! with its component (`cli`, `daemon`, `telegram`, ...), so logs from the
+//! runtime, agents and bots can be told apart and read per session.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Log of every component, in the logs directory.
+pub const COMBINED_LOG_FILE: &str = "combined.jsonl";
+
+/// Subdirectory of the logs directory holding one file per session.
+pub const SESSION_LOGS_SUBDIR: &str = "sessions";
+
+/// Fields naming the session an event belongs to, most specific first.
+const SESSION_FIELDS: &[&str] = &["session", "session_id", "project"];
+
+/// One structured log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Level name, e.g. `INFO`.
+    pub level: String,
+    /// Module that logged the event.
+    pub target: String,
+    /// Binary that wrote the line (`cli`, `daemon`, `telegram`, ...).
+    pub component: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    pub message: String,
+    /// Remaining event fields.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogRecord {
+    /// Parsed level; unknown names count as `INFO`.
+    pub fn level(&self) -> Level {
+        self.level.parse().unwrap_or(Level::INFO)
+    }
+
+    /// Whether the record is at least as severe as `min`.
+    pub fn is_at_least(&self, min: Level) -> bool {
+        // tracing orders levels by verbosity: ERROR < WARN < ... < TRACE
+        self.level() <= min
+    }
+
+    /// One-line rendering for terminals.
+    pub fn format_line(&self) -> String {
+        let mut line = format!(
+            "{} {:<5} [{}] {}",
+            self.timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"),
+            self.level,
+            self.component,
+            self.message
+        );
+        for (key, value) in &self.fields {
+            line.push_str(&format!(" {}={}", key, value));
+        }
+        line
+    }
+}
+
+/// File name key for `session`: tmux prefix removed, path separators replaced.
+pub fn session_key(session: &str) -> String {
+    let session = session.strip_prefix("commander-").unwrap_or(session);
+    session.replace(['/', '\\'], "_").replace("..", "_")
+}
+
+/// Path of the structured log for `session` under `logs_dir`.
+pub fn session_log_path(logs_dir: &Path, session: &str) -> PathBuf {
+    logs_dir
+        .join(SESSION_LOGS_SUBDIR)
+        .join(format!("{}.jsonl", session_key(session)))
+}
+
+/// Path of the combined structured log under `logs_dir`.
+pub fn combined_log_path(logs_dir: &Path) -> PathBuf {
+    logs_dir.join(COMBINED_LOG_FILE)
+}
+
+/// Read the records of a log file at `min` level or above.
+///
+/// Lines that are not valid records are skipped; a missing file is empty.
+pub fn read_log(path: &Path, min: Level) -> io::Result<Vec<LogRecord>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(record) = serde_json::from_str::<LogRecord>(&line?) {
+            if record.is_at_least(min) {
+                records.push(record);
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Follows a log file, returning records appended since the last poll.
+#[derive(Debug)]
+pub struct LogTail {
+    path: PathBuf,
+    min: Level,
+    offset: u64,
+    partial: String,
+}
+
+impl LogTail {
+    /// Follow `path` from its current end.
+    pub fn new(path: impl Into<PathBuf>, min: Level) -> Self {
+        let path = path.into();
+        let offset = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        Self {
+            path,
+            min,
+            offset,
+            partial: String::new(),
+        }
+    }
+
+    /// Records at the minimum level or above appended since the last poll.
+    pub fn poll(&mut self) -> io::Result<Vec<LogRecord>> {
+        let len = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if len < self.offset {
+            // Truncated or purged: start over
+            self.offset = 0;
+            self.partial.clear();
+        }
+        if len == self.offset {
+            return Ok(Vec::new());
+        }
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut chunk = Vec::new();
+        file.read_to_end(&mut chunk)?;
+        self.offset += chunk.len() as u64;
+        self.partial.push_str(&String::from_utf8_lossy(&chunk));
+
+        let mut records = Vec::new();
+        while let Some(end) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=end).collect();
+            if let Ok(record) = serde_json::from_str::<LogRecord>(line.trim_end()) {
+                if record.is_at_least(self.min) {
+                    records.push(record);
+                }
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// `tracing` layer writing JSON lines to the combined and per-session logs.
+#[derive(Debug)]
+pub struct SessionLogLayer {
+    dir: PathBuf,
+    component: String,
+    files: Mutex<HashMap<PathBuf, File>>,
+}
+
+impl SessionLogLayer {
+    /// Layer for `component` writing under `logs_dir`.
+    pub fn new(logs_dir: impl Into<PathBuf>, component: impl Into<String>) -> Self {
+        Self {
+            dir: logs_dir.into(),
+            component: component.into(),
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Append `record` to the combined log and its session's log.
+    pub fn write(&self, record: &LogRecord) -> io::Result<()> {
+        let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+        line.push('\n');
+
+        let mut files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        append(&mut files, combined_log_path(&self.dir), &line)?;
+        if let Some(session) = &record.session {
+            append(&mut files, session_log_path(&self.dir, session), &line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Append `line` to `path`, keeping the file open for later lines.
+fn append(files: &mut HashMap<PathBuf, File>, path: PathBuf, line: &str) -> io::Result<()> {
+    let file = match files.entry(path) {
+        std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+        std::collections::hash_map::Entry::Vacant(entry) => {
+            if let Some(parent) = entry.key().parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new().create(true).append(true).open(entry.key())?;
+            entry.insert(file)
+        }
+    };
+    file.write_all(line.as_bytes())
+}
+
+/// Session named by a span's fields, kept in the span's extensions.
+struct SpanSession(String);
+
+/// Collects the message, session and other fields of an event or span.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    /// Session and the index of its field in `SESSION_FIELDS`.
+    session: Option<(usize, String)>,
+    fields: BTreeMap<String, String>,
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        let name = field.name();
+        if name == "message" {
+            self.message = value;
+            return;
+        }
+        if let Some(rank) = SESSION_FIELDS.iter().position(|f| *f == name) {
+            if self.session.as_ref().is_none_or(|(current, _)| rank < *current) {
+                self.session = Some((rank, value.clone()));
+            }
+        }
+        self.fields.insert(name.to_string(), value);
+    }
+
+    fn session(&self) -> Option<String> {
+        self.session.as_ref().map(|(_, session)| session.clone())
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl<S> Layer<S> for SessionLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(session), Some(span)) = (visitor.session(), ctx.span(id)) {
+            span.extensions_mut().insert(SpanSession(session));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        // Fall back to the innermost span naming a session
+        let session = visitor.session().or_else(|| {
+            ctx.event_scope(event)?
+                .find_map(|span| span.extensions().get::<SpanSession>().map(|s| s.0.clone()))
+        });
+
+        let metadata = event.metadata();
+        let record = LogRecord {
+            timestamp: Utc::now(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            component: self.component.clone(),
+            session,
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+        // A logger has nowhere to report its own failures
+        let _ = self.write(&record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_writes_combined_and_session_logs() {
+        let dir = tempdir().unwrap();
+        let subscriber = tracing_subscriber::registry().with(SessionLogLayer::new(dir.path(), "test"));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(session = "commander-web", "polled");
+            let span = tracing::info_span!("task", project = "api");
+            span.in_scope(|| tracing::warn!(attempt = 2, "retrying"));
+            tracing::debug!("no session");
+        });
+
+        let combined = read_log(&combined_log_path(dir.path()), Level::TRACE).unwrap();
+        assert_eq!(combined.len(), 3);
+        assert!(combined.iter().all(|r| r.component == "test"));
+
+        let web = read_log(&session_log_path(dir.path(), "web"), Level::TRACE).unwrap();
+        assert_eq!(web.len(), 1);
+        assert_eq!(web[0].message, "polled");
+        assert_eq!(web[0].session.as_deref(), Some("commander-web"));
+
+        let api = read_log(&session_log_path(dir.path(), "commander-api"), Level::TRACE).unwrap();
+        assert_eq!(api.len(), 1);
+        assert_eq!(api[0].level(), Level::WARN);
+        assert_eq!(api[0].fields.get("attempt").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn test_read_log_filters_level() {
+        let dir = tempdir().unwrap();
+        let layer = SessionLogLayer::new(dir.path(), "test");
+        for level in ["DEBUG", "INFO", "ERROR"] {
+            layer
+                .write(&LogRecord {
+                    timestamp: Utc::now(),
+                    level: level.to_string(),
+                    target: "t".to_string(),
+                    component: "test".to_string(),
+                    session: Some("web".to_string()),
+                    message: level.to_lowercase(),
+                    fields: BTreeMap::new(),
+                })
+                .unwrap();
+        }
+        fs::write(dir.path().join("garbage.jsonl"), "not json\n").unwrap();
+
+        let path = session_log_path(dir.path(), "web");
+        let messages: Vec<String> = read_log(&path, Level::INFO).unwrap().into_iter().map(|r| r.message).collect();
+        assert_eq!(messages, vec!["info", "error"]);
+        assert!(read_log(&dir.path().join("garbage.jsonl"), Level::TRACE).unwrap().is_empty());
+        assert!(read_log(&dir.path().join("missing.jsonl"), Level::TRACE).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tail_returns_new_records() {
+        let dir = tempdir().unwrap();
+        let layer = SessionLogLayer::new(dir.path(), "test");
+        let record = |message: &str| LogRecord {
+            timestamp: Utc::now(),
+            level: "INFO".to_string(),
+            target: "t".to_string(),
+            component: "test".to_string(),
+            session: None,
+            message: message.to_string(),
+            fields: BTreeMap::new(),
+        };
+        layer.write(&record("before")).unwrap();
+
+        let mut tail = LogTail::new(combined_log_path(dir.path()), Level::INFO);
+        assert!(tail.poll().unwrap().is_empty());
+        layer.write(&record("after")).unwrap();
+        let records = tail.poll().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].message, "after");
+    }
+
+    #[test]
+    fn test_session_key() {
+        assert_eq!(session_key("commander-web"), "web");
+        assert_eq!(session_key("../etc"), "__etc");
+    }
+}
@@ -0,0 +1,122 @@
+//! Scratchpad notes attached to a session.
+//!
+//! `/note <text>` leaves a timestamped note on a session, for the user to
+//! read back with `/notes` and for the session's agent, which sees every note
+//! in the context of its next analysis. Notes are appended to
+//! `state/notes/<session>.jsonl`, are exported with the session's transcript
+//! and are deleted with it by `commander purge`.
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config;
+use crate::redaction::redact;
+
+/// A note left on a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionNote {
+    pub at: DateTime<Utc>,
+    pub text: String,
+    /// Who left it (`tui`, `repl`, `telegram`, or an agent id).
+    pub by: String,
+}
+
+impl SessionNote {
+    /// Create a note written now, with its secrets redacted.
+    pub fn new(text: &str, by: impl Into<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            text: redact(text.trim()),
+            by: by.into(),
+        }
+    }
+}
+
+/// File holding the notes of `session`.
+pub fn notes_path(session: &str) -> PathBuf {
+    config::notes_dir().join(format!("{}.jsonl", session.replace(['/', '\\'], "_").replace("..", "_")))
+}
+
+/// Add a note to `session`, returning it.
+pub fn add_note(session: &str, text: &str, by: &str) -> std::io::Result<SessionNote> {
+    let note = SessionNote::new(text, by);
+    append(&notes_path(session), &note)?;
+    Ok(note)
+}
+
+/// Notes of `session`, oldest first.
+pub fn session_notes(session: &str) -> Vec<SessionNote> {
+    read(&notes_path(session))
+}
+
+/// The notes of a session as a block for an agent's context, or `None`
+/// without notes.
+pub fn notes_context(notes: &[SessionNote]) -> Option<String> {
+    if notes.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = notes
+        .iter()
+        .map(|n| format!("- [{}] {}", n.at.with_timezone(&Local).format("%Y-%m-%d %H:%M"), n.text))
+        .collect();
+    Some(format!(
+        "Notes the user left on this session (follow their instructions):\n{}",
+        lines.join("\n")
+    ))
+}
+
+fn append(path: &Path, note: &SessionNote) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(note)?)
+}
+
+fn read(path: &Path) -> Vec<SessionNote> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(&line) {
+            Ok(note) => Some(note),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Skipping malformed session note");
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_round_trip_and_context() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("notes").join("api.jsonl");
+        assert!(read(&path).is_empty());
+        assert!(notes_context(&[]).is_none());
+
+        append(&path, &SessionNote::new("  use pnpm, not npm ", "tui")).unwrap();
+        append(&path, &SessionNote::new("key sk-ant-REDACTED", "repl")).unwrap();
+
+        let notes = read(&path);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].text, "use pnpm, not npm");
+        assert!(!notes[1].text.contains("abcdefghijklmnop"));
+
+        let context = notes_context(&notes).unwrap();
+        assert!(context.starts_with("Notes the user left"));
+        assert!(context.contains("] use pnpm, not npm"));
+    }
+}
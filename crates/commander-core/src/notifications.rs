@@ -2,13 +2,14 @@
 //!
 //! Notifications are stored in `~/.ai-commander/state/notifications.json` so that:
 //! - The TUI/REPL can write notifications when sessions need attention
-//! - The Telegram bot can poll and broadcast to all authorized users
+//! - The Telegram and Matrix bots can poll and broadcast to all authorized users
 
 use std::collections::VecDeque;
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use commander_core::config;
+use crate::config;
+use crate::headlines::{session_headline, template_brief};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
@@ -32,6 +33,9 @@ pub struct Notification {
     /// Whether this has been read by each channel (channel_name -> read)
     #[serde(default)]
     pub read_by: std::collections::HashSet<String>,
+    /// Whether this breaks through do-not-disturb
+    #[serde(default)]
+    pub critical: bool,
 }
 
 impl Notification {
@@ -51,6 +55,7 @@ impl Notification {
             session,
             created_at: now,
             read_by: std::collections::HashSet::new(),
+            critical: false,
         }
     }
 
@@ -117,8 +122,18 @@ fn save_notifications(queue: &NotificationQueue) -> Result<(), std::io::Error> {
 
 /// Push a new notification to the queue.
 pub fn push_notification(message: impl Into<String>, session: Option<String>) -> Result<(), std::io::Error> {
-    let notification = Notification::new(message, session);
+    enqueue(Notification::new(message, session))
+}
+
+/// Push a notification that is delivered even during do-not-disturb (unless
+/// `[notifications.dnd] critical_breaks_through = false`).
+pub fn push_critical_notification(message: impl Into<String>, session: Option<String>) -> Result<(), std::io::Error> {
+    let mut notification = Notification::new(message, session);
+    notification.critical = true;
+    enqueue(notification)
+}
 
+fn enqueue(notification: Notification) -> Result<(), std::io::Error> {
     let mut queue = load_notifications();
 
     // Clean up expired notifications
@@ -168,28 +183,23 @@ pub fn mark_notifications_read(channel: &str, notification_ids: &[String]) -> Re
 
 /// Convenience function to broadcast a session ready notification.
 ///
-/// Uses conversational language instead of raw session output.
-pub fn notify_session_ready(session_name: &str, preview: Option<&str>) -> Result<(), std::io::Error> {
-    use commander_core::notification_parser::{parse_session_preview, strip_ansi};
-
+/// Describes the session with a headline written from its `screen` (see
+/// [`crate::headlines`]), or with the status line `preview` when no LLM is
+/// available. Blocks while the headline is generated.
+pub fn notify_session_ready(
+    session_name: &str,
+    preview: Option<&str>,
+    screen: Option<&str>,
+) -> Result<(), std::io::Error> {
     let display_name = session_name.strip_prefix("commander-").unwrap_or(session_name);
-
-    let mut message = if let Some(prev) = preview {
-        let clean_preview = strip_ansi(prev);
-        if clean_preview.is_empty() {
-            format!("Session \"{}\" is ready for input", display_name)
-        } else {
-            // Parse and convert to conversational format
-            let status = parse_session_preview(display_name, &clean_preview);
-            let brief = status.to_brief();
-            if brief.is_empty() || brief == display_name {
-                format!("Session \"{}\" is ready for input", display_name)
-            } else {
-                format!("Session \"{}\" is ready: {}", display_name, brief)
-            }
-        }
-    } else {
-        format!("Session \"{}\" is ready for input", display_name)
+    let preview = preview.unwrap_or("");
+
+    let mut message = match session_headline(display_name, preview, screen.unwrap_or("")) {
+        Some(headline) => headline,
+        None => match template_brief(display_name, preview) {
+            Some(brief) => format!("Session \"{}\" is ready: {}", display_name, brief),
+            None => format!("Session \"{}\" is ready for input", display_name),
+        },
     };
 
     // Add clickable connect link
@@ -210,10 +220,10 @@ pub fn notify_session_resumed(session_name: &str) -> Result<(), std::io::Error>
 
 /// Convenience function to broadcast multiple new sessions waiting.
 ///
-/// Uses conversational language with clean, human-readable summaries.
-pub fn notify_sessions_waiting(sessions: &[(String, String)]) -> Result<(), std::io::Error> {
-    use commander_core::notification_parser::{parse_session_preview, strip_ansi};
-
+/// `sessions` holds each session's name, status line preview and screen.
+/// Each gets a line with its headline or, without an LLM, its template text.
+/// Blocks while headlines are generated.
+pub fn notify_sessions_waiting(sessions: &[(String, String, String)]) -> Result<(), std::io::Error> {
     if sessions.is_empty() {
         return Ok(());
     }
@@ -225,29 +235,23 @@ pub fn notify_sessions_waiting(sessions: &[(String, String)]) -> Result<(), std:
         format!("{} sessions are waiting for your input:", sessions.len())
     };
 
-    for (name, preview) in sessions.iter() {
+    for (name, preview, screen) in sessions.iter() {
         let display_name = name.strip_prefix("commander-").unwrap_or(name);
-        let clean_preview = strip_ansi(preview);
-
-        if clean_preview.is_empty() {
-            message.push_str(&format!("\n  - \"{}\"", display_name));
-        } else {
-            // Parse and convert to conversational format
-            let status = parse_session_preview(display_name, &clean_preview);
-            let brief = status.to_brief();
-            if brief.is_empty() || brief == display_name {
-                message.push_str(&format!("\n  - \"{}\"", display_name));
-            } else {
-                message.push_str(&format!("\n  - \"{}\": {}", display_name, brief));
-            }
-        }
+        let line = match session_headline(display_name, preview, screen) {
+            Some(headline) => headline,
+            None => match template_brief(display_name, preview) {
+                Some(brief) => format!("\"{}\": {}", display_name, brief),
+                None => format!("\"{}\"", display_name),
+            },
+        };
+        message.push_str(&format!("\n  - {}", line));
     }
 
     // Add clickable connect links
     message.push_str("\n\nChat with: ");
     let connect_commands: Vec<String> = sessions
         .iter()
-        .map(|(name, _)| {
+        .map(|(name, _, _)| {
             let display_name = name.strip_prefix("commander-").unwrap_or(name);
             format!("/connect {}", display_name)
         })
@@ -294,6 +298,7 @@ mod tests {
             session: None,
             created_at: now,
             read_by: std::collections::HashSet::new(),
+            critical: false,
         };
         assert!(!fresh.is_expired());
 
@@ -304,6 +309,7 @@ mod tests {
             session: None,
             created_at: now - 7200,
             read_by: std::collections::HashSet::new(),
+            critical: false,
         };
         assert!(expired.is_expired());
     }
@@ -7,6 +7,7 @@ use std::fs;
 use std::io::{self, Write};
 
 use crate::config;
+use crate::secrets::{secret_store, SecretStore, OPENROUTER_API_KEY, TELEGRAM_BOT_TOKEN};
 
 /// Check if onboarding has been completed.
 ///
@@ -68,13 +69,13 @@ pub fn run_onboarding() -> io::Result<()> {
     if !openrouter_key.is_empty() {
         println!("[ok] OpenRouter API key saved");
     } else {
-        println!("[ ] OpenRouter: skipped (add later to ~/.ai-commander/config/.env.local)");
+        println!("[ ] OpenRouter: skipped (add later with `commander secrets set OPENROUTER_API_KEY`)");
     }
 
     if !telegram_token.is_empty() {
         println!("[ok] Telegram bot token saved");
     } else {
-        println!("[ ] Telegram: skipped (add later to ~/.ai-commander/config/.env.local)");
+        println!("[ ] Telegram: skipped (add later with `commander secrets set TELEGRAM_BOT_TOKEN`)");
     }
 
     println!();
@@ -88,43 +89,53 @@ pub fn run_onboarding() -> io::Result<()> {
 }
 
 /// Save configuration to files.
+///
+/// Credentials go to the secret store; `config.toml` only marks onboarding
+/// as done.
 fn save_config(openrouter_key: &str, telegram_token: &str) -> io::Result<()> {
     // Ensure config directory exists
     config::ensure_config_dir()?;
 
-    let config_path = config::config_file();
-
-    // Build config.toml content
-    let mut content = String::from("# AI Commander Configuration\n\n");
-
-    if !openrouter_key.is_empty() {
-        content.push_str(&format!("openrouter_api_key = \"{}\"\n", openrouter_key));
+    // Write config.toml (creates it even if empty to mark onboarding as done)
+    let content = "# AI Commander Configuration\n\n\
+                   # API keys and tokens are stored with `commander secrets`\n";
+    fs::write(config::config_file(), content)?;
+
+    let store = secret_store();
+    let mut env_fallback = Vec::new();
+    for (name, value) in [(OPENROUTER_API_KEY, openrouter_key), (TELEGRAM_BOT_TOKEN, telegram_token)] {
+        if !store_secret(&store, name, value) {
+            env_fallback.push((name, value));
+        }
     }
 
-    if !telegram_token.is_empty() {
-        content.push_str(&format!("telegram_bot_token = \"{}\"\n", telegram_token));
+    // Secrets the store rejected go to .env.local so setup still works
+    if !env_fallback.is_empty() {
+        let env_content: String = env_fallback
+            .iter()
+            .map(|(name, value)| format!("{}={}\n", name, value))
+            .collect();
+        fs::write(config::env_file(), env_content)?;
     }
 
-    // Write config.toml (creates it even if empty to mark onboarding as done)
-    fs::write(&config_path, content)?;
-
-    // Also write to .env.local for compatibility with existing code
-    let env_path = config::env_file();
-    let mut env_content = String::new();
-
-    if !openrouter_key.is_empty() {
-        env_content.push_str(&format!("OPENROUTER_API_KEY={}\n", openrouter_key));
-    }
+    Ok(())
+}
 
-    if !telegram_token.is_empty() {
-        env_content.push_str(&format!("TELEGRAM_BOT_TOKEN={}\n", telegram_token));
+/// Store a non-empty secret. Returns `false` if it could not be stored.
+fn store_secret(store: &SecretStore, name: &str, value: &str) -> bool {
+    if value.is_empty() {
+        return true;
     }
-
-    if !env_content.is_empty() {
-        fs::write(&env_path, env_content)?;
+    match store.set(name, value) {
+        Ok(backend) => {
+            tracing::debug!(name = %name, backend = %backend, "Saved secret");
+            true
+        }
+        Err(e) => {
+            tracing::warn!(name = %name, error = %e, "Failed to store secret, writing .env.local");
+            false
+        }
     }
-
-    Ok(())
 }
 
 /// Load saved config into environment variables.
@@ -192,6 +203,17 @@ mod tests {
         assert!(read_env.contains("TELEGRAM_BOT_TOKEN=test-token"));
     }
 
+    #[test]
+    fn test_store_secret() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::file_only(temp_dir.path());
+
+        assert!(store_secret(&store, OPENROUTER_API_KEY, "sk-or-test"));
+        assert!(store_secret(&store, TELEGRAM_BOT_TOKEN, ""));
+        assert_eq!(store.get(OPENROUTER_API_KEY).unwrap().as_deref(), Some("sk-or-test"));
+        assert_eq!(store.get(TELEGRAM_BOT_TOKEN).unwrap(), None);
+    }
+
     #[test]
     fn test_config_toml_format_with_empty() {
         // Test that empty values don't produce config lines
@@ -1,14 +1,18 @@
-//! Shared pairing file for CLI-Telegram communication.
+//! Shared pairing file for CLI-to-chat-bot communication.
 //!
 //! Pairings are stored in `~/.ai-commander/state/pairings.json` so that:
 //! - The CLI can generate pairing codes and write them
-//! - The Telegram bot can read and consume them
+//! - The Telegram and Matrix bots can read and consume them
+//!
+//! The Telegram bot also records its username in `telegram_bot.json`, so a
+//! code can be offered as a `t.me/<bot>?start=<CODE>` link that pairs in one
+//! tap.
 
 use std::collections::HashMap;
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use commander_core::config;
+use crate::config;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
@@ -155,6 +159,40 @@ pub fn pairing_exists(code: &str) -> bool {
     pairings.get(&code).map(|p| !p.is_expired()).unwrap_or(false)
 }
 
+/// Check whether `s` looks like a pairing code (case-insensitive).
+pub fn is_pairing_code(s: &str) -> bool {
+    s.len() == 6 && s.bytes().all(|b| CHARSET.contains(&b.to_ascii_uppercase()))
+}
+
+/// Identity of the running Telegram bot, written at startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BotIdentity {
+    username: String,
+}
+
+/// Record the Telegram bot's username for pairing links.
+pub fn save_bot_username(username: &str) -> Result<(), std::io::Error> {
+    config::ensure_runtime_state_dir()?;
+    let identity = BotIdentity {
+        username: username.to_string(),
+    };
+    fs::write(config::telegram_bot_file(), serde_json::to_string_pretty(&identity)?)
+}
+
+/// Username of the Telegram bot, once it has been started.
+pub fn bot_username() -> Option<String> {
+    let content = fs::read_to_string(config::telegram_bot_file()).ok()?;
+    serde_json::from_str::<BotIdentity>(&content)
+        .map(|identity| identity.username)
+        .ok()
+        .filter(|username| !username.is_empty())
+}
+
+/// Deep link that opens the bot and pairs with `code` in one tap.
+pub fn pairing_link(bot_username: &str, code: &str) -> String {
+    format!("https://t.me/{}?start={}", bot_username, code)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +212,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_pairing_code() {
+        assert!(is_pairing_code(&generate_code()));
+        assert!(is_pairing_code("abcdef"));
+        assert!(!is_pairing_code("ABCDE"));
+        assert!(!is_pairing_code("ABCDE0"));
+        assert!(!is_pairing_code("connect_api"));
+        assert_eq!(pairing_link("commander_bot", "ABC234"), "https://t.me/commander_bot?start=ABC234");
+    }
+
     #[test]
     fn test_file_pairing_expiry() {
         let now = SystemTime::now()
@@ -0,0 +1,120 @@
+//! Paused sessions, shared by the TUI, REPL and bots.
+//!
+//! `/pause` stops a session without ending it: adapters with a native pause
+//! command (MPM's session pause) are sent that command, others have their
+//! processes suspended at the tmux level. Paused sessions are recorded in
+//! `paused.json` so every poller skips them and every frontend shows them as
+//! paused until `/resume`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config;
+
+/// How a session was paused, and so how to resume it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum PauseMethod {
+    /// The adapter's pause command was sent; resuming sends `resume_command`.
+    Command { resume_command: String },
+    /// The session's foreground processes were stopped with SIGSTOP.
+    Suspended { process_groups: Vec<u32> },
+}
+
+/// A paused session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PausedSession {
+    /// Project the session belongs to.
+    pub project: String,
+    pub method: PauseMethod,
+    pub paused_at: DateTime<Utc>,
+    /// Frontend that paused it (`tui`, `repl`, `telegram`).
+    pub by: String,
+}
+
+/// Paused sessions by tmux session name.
+fn load() -> BTreeMap<String, PausedSession> {
+    let path = config::paused_file();
+    if !path.exists() {
+        return BTreeMap::new();
+    }
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to parse paused sessions file");
+            BTreeMap::new()
+        }),
+        Err(e) => {
+            warn!(error = %e, "Failed to read paused sessions file");
+            BTreeMap::new()
+        }
+    }
+}
+
+fn save(paused: &BTreeMap<String, PausedSession>) -> std::io::Result<()> {
+    config::ensure_runtime_state_dir()?;
+    let path = config::paused_file();
+    fs::write(&path, serde_json::to_string_pretty(paused)?)?;
+    debug!(path = %path.display(), "Saved paused sessions file");
+    Ok(())
+}
+
+/// Record `session` as paused.
+pub fn mark_paused(session: &str, paused: PausedSession) -> std::io::Result<()> {
+    let mut all = load();
+    all.insert(session.to_string(), paused);
+    save(&all)
+}
+
+/// Clear the paused record of `session`, returning it.
+pub fn mark_resumed(session: &str) -> std::io::Result<Option<PausedSession>> {
+    let mut all = load();
+    let removed = all.remove(session);
+    if removed.is_some() {
+        save(&all)?;
+    }
+    Ok(removed)
+}
+
+/// The paused record of `session`, if it is paused.
+pub fn paused_session(session: &str) -> Option<PausedSession> {
+    load().remove(session)
+}
+
+/// Whether `session` is paused.
+pub fn is_paused(session: &str) -> bool {
+    load().contains_key(session)
+}
+
+/// Names of all paused sessions, for pollers checking many sessions at once.
+pub fn paused_session_names() -> HashSet<String> {
+    load().into_keys().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_method_serialization() {
+        let paused = PausedSession {
+            project: "api".into(),
+            method: PauseMethod::Suspended { process_groups: vec![4242] },
+            paused_at: Utc::now(),
+            by: "tui".into(),
+        };
+        let json = serde_json::to_value(&paused).unwrap();
+        assert_eq!(json["method"]["method"], "suspended");
+        assert_eq!(json["method"]["process_groups"][0], 4242);
+
+        let back: PausedSession = serde_json::from_value(json).unwrap();
+        assert_eq!(back.method, paused.method);
+
+        let command = PauseMethod::Command { resume_command: "/mpm-session-resume".into() };
+        let json = serde_json::to_string(&command).unwrap();
+        assert_eq!(serde_json::from_str::<PauseMethod>(&json).unwrap(), command);
+    }
+}
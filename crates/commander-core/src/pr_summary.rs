@@ -0,0 +1,197 @@
+//! Commit messages and pull request descriptions written from a session.
+//!
+//! When a session is stopped with `[stop] ai_commit = true`, its uncommitted
+//! changes are committed with a message written by the LLM from the staged
+//! diff and the session's transcript (the persisted log), instead of
+//! "WIP: Auto-commit". The same reply carries a pull request title and body
+//! for the pushed branch.
+
+use serde::Deserialize;
+
+use crate::log::LogEntry;
+use crate::redaction::redact;
+use crate::summarizer::{get_api_key, get_model, require_online, SummarizerError, OPENROUTER_API_URL};
+
+/// Characters of the diff sent to the model.
+const MAX_DIFF_CHARS: usize = 12_000;
+
+/// Characters of the transcript sent to the model, most recent kept.
+const MAX_TRANSCRIPT_CHARS: usize = 6_000;
+
+/// System prompt asking for the commit message and pull request as JSON.
+const SYSTEM_PROMPT: &str = r###"You write git commit messages and pull request descriptions for changes made in an AI coding session.
+Reply with only a JSON object:
+{"commit_message": "...", "title": "...", "body": "..."}
+- commit_message: an imperative subject line under 72 characters, a blank line, then a short body explaining what changed and why.
+- title: the pull request title, usually the commit subject.
+- body: Markdown with a short summary paragraph, a "## Changes" bullet list, and a "## Testing" section naming tests the transcript shows were run (or "Not run").
+Describe only what the diff and transcript show. Do not mention the AI session or Commander."###;
+
+/// A generated commit message and pull request description.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PrSummary {
+    /// Commit message: subject line, blank line, body.
+    pub commit_message: String,
+    /// Pull request title.
+    pub title: String,
+    /// Pull request body (Markdown).
+    pub body: String,
+}
+
+/// The user prompt for a project's staged `diff` and session `transcript`.
+///
+/// Both are truncated: the diff keeps its start, the transcript its end.
+pub fn build_prompt(project: &str, transcript: &[LogEntry], diff: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut chars = 0;
+    for entry in transcript.iter().rev() {
+        let who = if entry.kind.as_deref() == Some("user") { "User" } else { "Session" };
+        let line = format!("{}: {}", who, entry.text.trim());
+        chars += line.len();
+        if chars > MAX_TRANSCRIPT_CHARS {
+            break;
+        }
+        lines.push(line);
+    }
+    lines.reverse();
+    let transcript = if lines.is_empty() { "(no transcript)".to_string() } else { lines.join("\n") };
+
+    let diff = match diff.char_indices().nth(MAX_DIFF_CHARS) {
+        Some((end, _)) => format!("{}\n[diff truncated]", &diff[..end]),
+        None => diff.to_string(),
+    };
+
+    format!(
+        "Project: {}\n\nSession transcript:\n{}\n\nStaged diff:\n{}",
+        project, transcript, diff
+    )
+}
+
+/// Parse the model's reply, tolerating a Markdown code fence around the JSON.
+pub fn parse_reply(reply: &str) -> Option<PrSummary> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    let summary: PrSummary = serde_json::from_str(reply.get(start..=end)?).ok()?;
+    let summary = PrSummary {
+        commit_message: summary.commit_message.trim().to_string(),
+        title: summary.title.trim().to_string(),
+        body: summary.body.trim().to_string(),
+    };
+    if summary.commit_message.is_empty() || summary.title.is_empty() {
+        return None;
+    }
+    Some(summary)
+}
+
+/// Generate the commit message and pull request description (blocking).
+///
+/// `model` overrides the summarizer model.
+pub fn generate_pr_summary_blocking(
+    project: &str,
+    transcript: &[LogEntry],
+    diff: &str,
+    model: Option<&str>,
+) -> Result<PrSummary, SummarizerError> {
+    require_online()?;
+    let api_key = get_api_key().ok_or(SummarizerError::NoApiKey)?;
+    let response = reqwest::blocking::Client::new()
+        .post(OPENROUTER_API_URL)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&request_body(project, transcript, diff, model))
+        .send()
+        .map_err(|e| SummarizerError::RequestFailed(e.to_string()))?;
+    let json: serde_json::Value = response
+        .json()
+        .map_err(|e| SummarizerError::ParseError(e.to_string()))?;
+    summary_from_response(&json)
+}
+
+/// Generate the commit message and pull request description.
+///
+/// `model` overrides the summarizer model.
+pub async fn generate_pr_summary(
+    project: &str,
+    transcript: &[LogEntry],
+    diff: &str,
+    model: Option<&str>,
+) -> Result<PrSummary, SummarizerError> {
+    require_online()?;
+    let api_key = get_api_key().ok_or(SummarizerError::NoApiKey)?;
+    let response = reqwest::Client::new()
+        .post(OPENROUTER_API_URL)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&request_body(project, transcript, diff, model))
+        .send()
+        .await
+        .map_err(|e| SummarizerError::RequestFailed(e.to_string()))?;
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| SummarizerError::ParseError(e.to_string()))?;
+    summary_from_response(&json)
+}
+
+fn request_body(project: &str, transcript: &[LogEntry], diff: &str, model: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "model": model.map(str::to_string).unwrap_or_else(get_model),
+        "messages": [
+            {"role": "system", "content": SYSTEM_PROMPT},
+            {"role": "user", "content": redact(&build_prompt(project, transcript, diff))}
+        ],
+        "max_tokens": 1000
+    })
+}
+
+fn summary_from_response(json: &serde_json::Value) -> Result<PrSummary, SummarizerError> {
+    let content = json["choices"][0]["message"]["content"]
+        .as_str()
+        .ok_or_else(|| SummarizerError::ParseError("No content in response".to_string()))?;
+    parse_reply(content).ok_or_else(|| SummarizerError::ParseError("Reply is not a commit summary".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, kind: Option<&str>) -> LogEntry {
+        LogEntry {
+            ts: 0,
+            text: text.to_string(),
+            hash: String::new(),
+            kind: kind.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = "```json\n{\"commit_message\": \"Add rate limiting\\n\\nLimits login attempts.\", \
+            \"title\": \"Add rate limiting \", \"body\": \"## Changes\\n- limiter\"}\n```";
+        let summary = parse_reply(reply).unwrap();
+        assert_eq!(summary.commit_message, "Add rate limiting\n\nLimits login attempts.");
+        assert_eq!(summary.title, "Add rate limiting");
+        assert_eq!(summary.body, "## Changes\n- limiter");
+
+        assert!(parse_reply("Sure! Here is a commit message.").is_none());
+        assert!(parse_reply(r#"{"commit_message": "", "title": "x", "body": ""}"#).is_none());
+    }
+
+    #[test]
+    fn test_build_prompt_keeps_recent_transcript() {
+        let old = "x".repeat(MAX_TRANSCRIPT_CHARS);
+        let transcript = vec![
+            entry(&old, None),
+            entry("add a rate limiter", Some("user")),
+            entry("Added the limiter; cargo test passes", None),
+        ];
+        let prompt = build_prompt("api", &transcript, "diff --git a/x b/x\n+new");
+        assert!(prompt.starts_with("Project: api"));
+        assert!(prompt.contains("User: add a rate limiter\nSession: Added the limiter"));
+        assert!(!prompt.contains(&old));
+        assert!(prompt.ends_with("+new"));
+
+        let long_diff = "+".repeat(MAX_DIFF_CHARS + 10);
+        assert!(build_prompt("api", &[], &long_diff).ends_with("[diff truncated]"));
+    }
+}
@@ -0,0 +1,412 @@
+//! Project templates for scaffolding new projects.
+//!
+//! A template is a directory tree plus an optional initial prompt that is
+//! sent to the adapter once the new project's session is ready. Commander
+//! ships a few built-in templates; user templates live in
+//! `~/.ai-commander/templates/projects/<name>/` and override built-ins of
+//! the same name.
+//!
+//! A user template directory is copied verbatim, except for an optional
+//! `template.json` manifest:
+//!
+//! ```json
+//! {
+//!   "description": "Axum web service",
+//!   "adapter": "cc",
+//!   "prompt": "Add a /health endpoint with a test."
+//! }
+//! ```
+//!
+//! The placeholder `{{name}}` is replaced with the project name in file
+//! contents, file names, and the prompt.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::config::project_templates_dir;
+
+/// Manifest file name inside a user template directory.
+pub const MANIFEST_FILE: &str = "template.json";
+
+/// Placeholder replaced with the project name.
+const NAME_PLACEHOLDER: &str = "{{name}}";
+
+/// Errors from loading or applying project templates.
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    /// No template with this name.
+    #[error("Unknown template '{name}'. Available: {available}")]
+    NotFound { name: String, available: String },
+
+    /// The destination already contains files.
+    #[error("Destination is not empty: {0}")]
+    DestinationNotEmpty(PathBuf),
+
+    /// The template manifest could not be parsed.
+    #[error("Invalid template manifest {path}: {source}")]
+    InvalidManifest {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// A filesystem operation failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type for template operations.
+pub type Result<T> = std::result::Result<T, TemplateError>;
+
+/// Where a template was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Shipped with Commander.
+    BuiltIn,
+    /// Loaded from the user templates directory.
+    User,
+}
+
+/// A file created by a template, relative to the project root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateFile {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// A project template.
+#[derive(Debug, Clone)]
+pub struct ProjectTemplate {
+    pub name: String,
+    pub description: String,
+    /// Preferred adapter (alias or ID), if the template specifies one.
+    pub adapter: Option<String>,
+    /// Prompt sent to the adapter once the session is ready.
+    pub prompt: Option<String>,
+    pub files: Vec<TemplateFile>,
+    pub source: TemplateSource,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    adapter: Option<String>,
+    #[serde(default)]
+    prompt: Option<String>,
+}
+
+impl ProjectTemplate {
+    /// Load a user template from a directory.
+    pub fn from_dir(dir: &Path) -> Result<Self> {
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest = if manifest_path.exists() {
+            let content = fs::read_to_string(&manifest_path)?;
+            serde_json::from_str(&content).map_err(|source| TemplateError::InvalidManifest {
+                path: manifest_path.clone(),
+                source,
+            })?
+        } else {
+            Manifest::default()
+        };
+
+        let mut files = Vec::new();
+        collect_files(dir, dir, &mut files)?;
+        files.retain(|f| f.path != Path::new(MANIFEST_FILE));
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Self {
+            description: manifest
+                .description
+                .unwrap_or_else(|| format!("User template from {}", dir.display())),
+            adapter: manifest.adapter,
+            prompt: manifest.prompt,
+            files,
+            source: TemplateSource::User,
+            name,
+        })
+    }
+
+    /// The initial prompt with placeholders filled in.
+    pub fn prompt_for(&self, project_name: &str) -> Option<String> {
+        self.prompt
+            .as_ref()
+            .map(|p| p.replace(NAME_PLACEHOLDER, project_name))
+    }
+
+    /// Write the template's files into `dest`, creating it if needed.
+    ///
+    /// Refuses to write into a non-empty directory. Returns the created paths.
+    pub fn scaffold(&self, dest: &Path, project_name: &str) -> Result<Vec<PathBuf>> {
+        if dest.exists() && fs::read_dir(dest)?.next().is_some() {
+            return Err(TemplateError::DestinationNotEmpty(dest.to_path_buf()));
+        }
+        fs::create_dir_all(dest)?;
+
+        let mut created = Vec::with_capacity(self.files.len());
+        for file in &self.files {
+            let relative = file.path.to_string_lossy().replace(NAME_PLACEHOLDER, project_name);
+            let path = dest.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, file.contents.replace(NAME_PLACEHOLDER, project_name))?;
+            created.push(path);
+        }
+        Ok(created)
+    }
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<TemplateFile>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            // Binary files cannot hold placeholders; skip anything that isn't UTF-8
+            let Ok(contents) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            files.push(TemplateFile { path: relative, contents });
+        }
+    }
+    Ok(())
+}
+
+/// Templates shipped with Commander.
+pub fn builtin_templates() -> Vec<ProjectTemplate> {
+    fn builtin(
+        name: &str,
+        description: &str,
+        prompt: &str,
+        files: &[(&str, &str)],
+    ) -> ProjectTemplate {
+        ProjectTemplate {
+            name: name.to_string(),
+            description: description.to_string(),
+            adapter: None,
+            prompt: Some(prompt.to_string()),
+            files: files
+                .iter()
+                .map(|(path, contents)| TemplateFile {
+                    path: PathBuf::from(path),
+                    contents: contents.to_string(),
+                })
+                .collect(),
+            source: TemplateSource::BuiltIn,
+        }
+    }
+
+    vec![
+        builtin(
+            "rust-cli",
+            "Rust command-line tool with clap",
+            "This is a new Rust CLI project named {{name}}. Review the scaffold, \
+             run `cargo build`, and fix anything that doesn't compile. Then wait \
+             for further instructions.",
+            &[
+                (
+                    "Cargo.toml",
+                    "[package]\nname = \"{{name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+                     [dependencies]\nclap = { version = \"4\", features = [\"derive\"] }\n",
+                ),
+                (
+                    "src/main.rs",
+                    "use clap::Parser;\n\n/// {{name}}\n#[derive(Parser, Debug)]\n\
+                     #[command(version, about)]\nstruct Cli {\n    /// Name to greet\n    \
+                     #[arg(default_value = \"world\")]\n    name: String,\n}\n\n\
+                     fn main() {\n    let cli = Cli::parse();\n    println!(\"Hello, {}!\", cli.name);\n}\n",
+                ),
+                (".gitignore", "/target\n"),
+                ("README.md", "# {{name}}\n"),
+            ],
+        ),
+        builtin(
+            "python-cli",
+            "Python command-line tool with pyproject.toml",
+            "This is a new Python CLI project named {{name}}. Review the scaffold, \
+             set up a virtual environment, and make sure `python -m {{name}}` runs. \
+             Then wait for further instructions.",
+            &[
+                (
+                    "pyproject.toml",
+                    "[project]\nname = \"{{name}}\"\nversion = \"0.1.0\"\nrequires-python = \">=3.10\"\n\n\
+                     [project.scripts]\n{{name}} = \"{{name}}.__main__:main\"\n",
+                ),
+                ("{{name}}/__init__.py", ""),
+                (
+                    "{{name}}/__main__.py",
+                    "import argparse\n\n\ndef main() -> None:\n    parser = argparse.ArgumentParser(prog=\"{{name}}\")\n    \
+                     parser.add_argument(\"name\", nargs=\"?\", default=\"world\")\n    \
+                     args = parser.parse_args()\n    print(f\"Hello, {args.name}!\")\n\n\n\
+                     if __name__ == \"__main__\":\n    main()\n",
+                ),
+                (".gitignore", "__pycache__/\n.venv/\n*.egg-info/\n"),
+                ("README.md", "# {{name}}\n"),
+            ],
+        ),
+        builtin(
+            "node-ts",
+            "TypeScript Node.js project",
+            "This is a new TypeScript project named {{name}}. Review the scaffold, \
+             run `npm install` and `npm run build`, and fix any errors. Then wait \
+             for further instructions.",
+            &[
+                (
+                    "package.json",
+                    "{\n  \"name\": \"{{name}}\",\n  \"version\": \"0.1.0\",\n  \"private\": true,\n  \
+                     \"scripts\": {\n    \"build\": \"tsc\",\n    \"start\": \"node dist/index.js\"\n  },\n  \
+                     \"devDependencies\": {\n    \"typescript\": \"^5.0.0\"\n  }\n}\n",
+                ),
+                (
+                    "tsconfig.json",
+                    "{\n  \"compilerOptions\": {\n    \"target\": \"ES2022\",\n    \"module\": \"commonjs\",\n    \
+                     \"outDir\": \"dist\",\n    \"strict\": true\n  },\n  \"include\": [\"src\"]\n}\n",
+                ),
+                ("src/index.ts", "console.log(\"Hello from {{name}}\");\n"),
+                (".gitignore", "node_modules/\ndist/\n"),
+                ("README.md", "# {{name}}\n"),
+            ],
+        ),
+    ]
+}
+
+/// All available templates, sorted by name. User templates override built-ins.
+pub fn list_templates() -> Vec<ProjectTemplate> {
+    list_templates_in(&project_templates_dir())
+}
+
+/// All templates, reading user templates from `dir`.
+pub fn list_templates_in(dir: &Path) -> Vec<ProjectTemplate> {
+    let mut templates: BTreeMap<String, ProjectTemplate> = builtin_templates()
+        .into_iter()
+        .map(|t| (t.name.clone(), t))
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            match ProjectTemplate::from_dir(&path) {
+                Ok(template) => {
+                    templates.insert(template.name.clone(), template);
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Skipping invalid project template");
+                }
+            }
+        }
+    }
+
+    templates.into_values().collect()
+}
+
+/// Find a template by name.
+pub fn find_template(name: &str) -> Result<ProjectTemplate> {
+    find_template_in(&project_templates_dir(), name)
+}
+
+/// Find a template by name, reading user templates from `dir`.
+pub fn find_template_in(dir: &Path, name: &str) -> Result<ProjectTemplate> {
+    let templates = list_templates_in(dir);
+    let available = templates
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    templates
+        .into_iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| TemplateError::NotFound {
+            name: name.to_string(),
+            available,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_scaffold() {
+        let dir = TempDir::new().unwrap();
+        let dest = dir.path().join("hello");
+        let template = find_template_in(dir.path(), "rust-cli").unwrap();
+
+        let created = template.scaffold(&dest, "hello").unwrap();
+        assert!(created.contains(&dest.join("src/main.rs")));
+
+        let manifest = fs::read_to_string(dest.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"hello\""));
+        assert!(template.prompt_for("hello").unwrap().contains("named hello"));
+    }
+
+    #[test]
+    fn test_scaffold_substitutes_paths() {
+        let dir = TempDir::new().unwrap();
+        let dest = dir.path().join("tool");
+        let template = find_template_in(dir.path(), "python-cli").unwrap();
+
+        template.scaffold(&dest, "tool").unwrap();
+        assert!(dest.join("tool/__main__.py").exists());
+    }
+
+    #[test]
+    fn test_scaffold_refuses_non_empty_destination() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("existing.txt"), "keep me").unwrap();
+        let template = find_template_in(dir.path(), "node-ts").unwrap();
+
+        let err = template.scaffold(dir.path(), "app").unwrap_err();
+        assert!(matches!(err, TemplateError::DestinationNotEmpty(_)));
+    }
+
+    #[test]
+    fn test_user_template_overrides_builtin() {
+        let dir = TempDir::new().unwrap();
+        let template_dir = dir.path().join("rust-cli");
+        fs::create_dir_all(template_dir.join("src")).unwrap();
+        fs::write(template_dir.join("src/lib.rs"), "// {{name}}\n").unwrap();
+        fs::write(
+            template_dir.join(MANIFEST_FILE),
+            r#"{"description": "Team Rust layout", "adapter": "mpm", "prompt": "Set up {{name}}"}"#,
+        )
+        .unwrap();
+
+        let template = find_template_in(dir.path(), "rust-cli").unwrap();
+        assert_eq!(template.source, TemplateSource::User);
+        assert_eq!(template.description, "Team Rust layout");
+        assert_eq!(template.adapter.as_deref(), Some("mpm"));
+        assert_eq!(template.prompt_for("demo").as_deref(), Some("Set up demo"));
+        assert_eq!(template.files.len(), 1);
+
+        let dest = dir.path().join("out");
+        template.scaffold(&dest, "demo").unwrap();
+        assert_eq!(fs::read_to_string(dest.join("src/lib.rs")).unwrap(), "// demo\n");
+        assert!(!dest.join(MANIFEST_FILE).exists());
+    }
+
+    #[test]
+    fn test_unknown_template_lists_available() {
+        let dir = TempDir::new().unwrap();
+        let err = find_template_in(dir.path(), "cobol").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("cobol"));
+        assert!(message.contains("rust-cli"));
+    }
+}
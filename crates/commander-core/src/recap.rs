@@ -0,0 +1,283 @@
+//! "Since you left" digests for sessions the user comes back to.
+//!
+//! Whenever the user sends a message to a session or reads its response,
+//! [`mark_seen`] records the session's screen in `recaps.json`. When the user
+//! connects again after `[recap] min_away_mins`, the output that appeared
+//! since then and the notifications queued for the session are run through
+//! the summarizer. The digest is cached under a key of its inputs, so
+//! connecting again from any frontend without new activity reuses it.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::{self, config_file};
+use crate::notifications::{load_notifications, Notification};
+use crate::output_filter::find_new_lines;
+use crate::settings::Settings;
+use crate::summarizer::{summarize_blocking_with_fallback, summarize_with_fallback};
+
+/// Most recent new output lines passed to the summarizer.
+const MAX_RECAP_LINES: usize = 300;
+
+/// Query the digest answers.
+const RECAP_QUERY: &str = "What happened in this session while I was away? Recap what was done, \
+the results, and anything waiting on me.";
+
+/// A session's screen when the user last interacted with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMark {
+    pub seen_at: DateTime<Utc>,
+    pub screen: String,
+    /// Last digest computed since then.
+    #[serde(default)]
+    pub digest: Option<CachedDigest>,
+}
+
+/// A digest and the key of the inputs it summarized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedDigest {
+    pub key: String,
+    pub text: String,
+}
+
+/// Contents of `recaps.json`, keyed by tmux session name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecapStore {
+    #[serde(default)]
+    pub sessions: HashMap<String, SessionMark>,
+}
+
+/// What happened in a session since the user last saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecapInput {
+    /// Time since the last interaction.
+    pub away: Duration,
+    /// Output lines that appeared since, oldest first.
+    pub new_lines: Vec<String>,
+    /// Notifications queued for the session since.
+    pub notifications: Vec<String>,
+    /// Key of the above, for the digest cache.
+    pub key: String,
+}
+
+impl RecapInput {
+    /// Compare `screen` with `mark` and collect the notifications for any of
+    /// `names` created since. `None` when nothing happened.
+    pub fn gather(
+        mark: &SessionMark,
+        screen: &str,
+        notifications: &[Notification],
+        names: &[&str],
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let mut new_lines = find_new_lines(&mark.screen, screen);
+        if new_lines.len() > MAX_RECAP_LINES {
+            new_lines.drain(..new_lines.len() - MAX_RECAP_LINES);
+        }
+        let seen_at = u64::try_from(mark.seen_at.timestamp()).unwrap_or(0);
+        let notifications: Vec<String> = notifications
+            .iter()
+            .filter(|n| n.created_at >= seen_at)
+            .filter(|n| n.session.as_deref().is_some_and(|s| names.contains(&s)))
+            .map(|n| n.message.clone())
+            .collect();
+        if new_lines.is_empty() && notifications.is_empty() {
+            return None;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        new_lines.hash(&mut hasher);
+        notifications.hash(&mut hasher);
+        Some(Self {
+            away: now - mark.seen_at,
+            new_lines,
+            notifications,
+            key: format!("{:016x}", hasher.finish()),
+        })
+    }
+
+    /// Text handed to the summarizer.
+    pub fn raw_text(&self) -> String {
+        let mut text = String::new();
+        if !self.notifications.is_empty() {
+            text.push_str("Notifications:\n");
+            for notification in &self.notifications {
+                text.push_str(&format!("- {}\n", notification));
+            }
+            text.push('\n');
+        }
+        if !self.new_lines.is_empty() {
+            text.push_str("New output:\n");
+            text.push_str(&self.new_lines.join("\n"));
+        }
+        text
+    }
+
+    /// First line of the digest, e.g. "Since you left (3h 12m ago):".
+    pub fn heading(&self) -> String {
+        format!("Since you left ({} ago):", format_away(self.away))
+    }
+}
+
+/// Record that the user saw `session` showing `screen`.
+pub fn mark_seen(session: &str, screen: &str) -> std::io::Result<()> {
+    let mut store = load_store();
+    store.sessions.insert(
+        session.to_string(),
+        SessionMark {
+            seen_at: Utc::now(),
+            screen: screen.to_string(),
+            digest: None,
+        },
+    );
+    save_store(&store)
+}
+
+/// Digest for connecting to `session`, which now shows `screen`.
+///
+/// `names` are the other names notifications may use for the session, such
+/// as its project's name. `None` when recaps are disabled, the user was not
+/// away long enough, or nothing happened.
+pub async fn recap_async(session: &str, names: &[&str], screen: &str) -> Option<String> {
+    let (input, cached) = prepare(session, names, screen)?;
+    if let Some(text) = cached {
+        return Some(format!("{}\n{}", input.heading(), text));
+    }
+    let summary = summarize_with_fallback(RECAP_QUERY, &input.raw_text()).await;
+    Some(finish(session, &input, summary))
+}
+
+/// Blocking version of [`recap_async`], for threads outside a Tokio runtime.
+pub fn recap_blocking(session: &str, names: &[&str], screen: &str) -> Option<String> {
+    let (input, cached) = prepare(session, names, screen)?;
+    if let Some(text) = cached {
+        return Some(format!("{}\n{}", input.heading(), text));
+    }
+    let summary = summarize_blocking_with_fallback(RECAP_QUERY, &input.raw_text());
+    Some(finish(session, &input, summary))
+}
+
+/// The recap's input and its cached digest, if still current.
+fn prepare(session: &str, names: &[&str], screen: &str) -> Option<(RecapInput, Option<String>)> {
+    let settings = Settings::load(&config_file()).unwrap_or_default().recap;
+    if !settings.enabled {
+        return None;
+    }
+    let store = load_store();
+    let mark = store.sessions.get(session)?;
+    let now = Utc::now();
+    let min_away = Duration::minutes(i64::try_from(settings.min_away_mins).unwrap_or(i64::MAX));
+    if now - mark.seen_at < min_away {
+        return None;
+    }
+
+    let mut all_names = vec![session];
+    all_names.extend_from_slice(names);
+    let notifications: Vec<Notification> = load_notifications().notifications.into();
+    let input = RecapInput::gather(mark, screen, &notifications, &all_names, now)?;
+    let cached = mark
+        .digest
+        .as_ref()
+        .filter(|digest| digest.key == input.key)
+        .map(|digest| digest.text.clone());
+    debug!(session = %session, cached = cached.is_some(), "Preparing recap");
+    Some((input, cached))
+}
+
+/// Cache `summary` for `session` and return the digest.
+fn finish(session: &str, input: &RecapInput, summary: String) -> String {
+    let mut store = load_store();
+    if let Some(mark) = store.sessions.get_mut(session) {
+        mark.digest = Some(CachedDigest {
+            key: input.key.clone(),
+            text: summary.clone(),
+        });
+        if let Err(e) = save_store(&store) {
+            warn!(error = %e, "Failed to cache recap");
+        }
+    }
+    format!("{}\n{}", input.heading(), summary)
+}
+
+/// Load `recaps.json`, empty if missing or unreadable.
+fn load_store() -> RecapStore {
+    let path = config::recaps_file();
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to parse recaps file");
+            RecapStore::default()
+        }),
+        Err(_) => RecapStore::default(),
+    }
+}
+
+fn save_store(store: &RecapStore) -> std::io::Result<()> {
+    config::ensure_runtime_state_dir()?;
+    let content = serde_json::to_string_pretty(store)?;
+    fs::write(config::recaps_file(), content)
+}
+
+/// Short form of `away`, e.g. "45m", "3h 12m" or "2d 4h".
+fn format_away(away: Duration) -> String {
+    let minutes = away.num_minutes().max(1);
+    match (minutes / (24 * 60), (minutes / 60) % 24, minutes % 60) {
+        (0, 0, m) => format!("{}m", m),
+        (0, h, m) => format!("{}h {}m", h, m),
+        (d, h, _) => format!("{}d {}h", d, h),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mark(screen: &str, hours_ago: i64) -> SessionMark {
+        SessionMark {
+            seen_at: Utc::now() - Duration::hours(hours_ago),
+            screen: screen.to_string(),
+            digest: None,
+        }
+    }
+
+    #[test]
+    fn test_gather() {
+        let mark = mark("$ cargo test\nrunning 3 tests", 3);
+        let mut old = Notification::new("Old news", Some("api".into()));
+        old.created_at = 0;
+        let notifications = vec![
+            Notification::new("Tests failed in api", Some("api".into())),
+            Notification::new("Deploy finished", Some("web".into())),
+            old,
+        ];
+        let screen = "$ cargo test\nrunning 3 tests\ntest result: FAILED. 2 passed; 1 failed";
+
+        let input = RecapInput::gather(&mark, screen, &notifications, &["api"], Utc::now()).unwrap();
+        assert_eq!(input.new_lines, vec!["test result: FAILED. 2 passed; 1 failed"]);
+        assert_eq!(input.notifications, vec!["Tests failed in api"]);
+        assert!(input.heading().starts_with("Since you left (3h"));
+        assert!(input.raw_text().contains("- Tests failed in api"));
+
+        // Same inputs give the same cache key
+        let again = RecapInput::gather(&mark, screen, &notifications, &["api"], Utc::now()).unwrap();
+        assert_eq!(again.key, input.key);
+    }
+
+    #[test]
+    fn test_gather_nothing_new() {
+        let mark = mark("$ cargo test\nrunning 3 tests", 3);
+        assert!(RecapInput::gather(&mark, "$ cargo test\nrunning 3 tests", &[], &["api"], Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_format_away() {
+        assert_eq!(format_away(Duration::seconds(20)), "1m");
+        assert_eq!(format_away(Duration::minutes(192)), "3h 12m");
+        assert_eq!(format_away(Duration::hours(52)), "2d 4h");
+    }
+}
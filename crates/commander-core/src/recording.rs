@@ -0,0 +1,343 @@
+//! Session recording and playback as asciicast v2 files.
+//!
+//! Recordings are built from pane captures: every time the captured screen
+//! changes, a frame that clears the terminal and redraws the screen is
+//! appended. Files live in `~/.ai-commander/recordings/` and play back with
+//! `commander play` or any asciinema player.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::recordings_dir;
+
+/// Environment variable that turns on recording in the runtime poller.
+pub const RECORD_SESSIONS_ENV: &str = "COMMANDER_RECORD_SESSIONS";
+
+/// File extension for recordings.
+pub const RECORDING_EXTENSION: &str = "cast";
+
+/// Terminal size written to headers when the pane size is unknown.
+pub const DEFAULT_WIDTH: u32 = 200;
+pub const DEFAULT_HEIGHT: u32 = 50;
+
+/// Clear screen and move the cursor home; prefixes every frame.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Errors from reading or writing recordings.
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    #[error("recording not found: {0}")]
+    NotFound(String),
+
+    #[error("invalid recording: {0}")]
+    Format(String),
+
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// asciicast v2 header line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastHeader {
+    pub version: u8,
+    pub width: u32,
+    pub height: u32,
+    /// Unix timestamp of the start of the recording.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Appends screen frames for one session to an asciicast file.
+#[derive(Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    file: File,
+    started: Instant,
+    last_screen: Option<String>,
+}
+
+impl Recorder {
+    /// Start a recording for `session` in the recordings directory.
+    pub fn start(session: &str) -> Result<Self, RecordingError> {
+        Self::create(recording_path(session), DEFAULT_WIDTH, DEFAULT_HEIGHT, Some(session))
+    }
+
+    /// Create a recording at `path` and write its header.
+    pub fn create(
+        path: impl Into<PathBuf>,
+        width: u32,
+        height: u32,
+        title: Option<&str>,
+    ) -> Result<Self, RecordingError> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let header = CastHeader {
+            version: 2,
+            width,
+            height,
+            timestamp: Some(Utc::now().timestamp()),
+            title: title.map(str::to_string),
+        };
+        let mut file = File::create(&path)?;
+        let line = serde_json::to_string(&header).map_err(|e| RecordingError::Format(e.to_string()))?;
+        writeln!(file, "{}", line)?;
+
+        Ok(Self {
+            path,
+            file,
+            started: Instant::now(),
+            last_screen: None,
+        })
+    }
+
+    /// Path of the recording file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record `screen` if it differs from the last recorded frame.
+    ///
+    /// Returns whether a frame was written.
+    pub fn record_screen(&mut self, screen: &str) -> Result<bool, RecordingError> {
+        if self.last_screen.as_deref() == Some(screen) {
+            return Ok(false);
+        }
+
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let data = format!("{}{}", CLEAR_SCREEN, screen.replace('\n', "\r\n"));
+        let event = serde_json::json!([(elapsed * 1000.0).round() / 1000.0, "o", data]);
+        writeln!(self.file, "{}", event)?;
+        self.file.flush()?;
+
+        self.last_screen = Some(screen.to_string());
+        Ok(true)
+    }
+}
+
+/// A loaded recording: header and output events as `(seconds, data)`.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub header: CastHeader,
+    pub events: Vec<(f64, String)>,
+}
+
+impl Recording {
+    /// Load an asciicast v2 file. Input and marker events are skipped.
+    pub fn load(path: &Path) -> Result<Self, RecordingError> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut lines = reader.lines();
+
+        let header_line = lines
+            .next()
+            .ok_or_else(|| RecordingError::Format("empty file".to_string()))??;
+        let header: CastHeader = serde_json::from_str(&header_line)
+            .map_err(|e| RecordingError::Format(format!("bad header: {}", e)))?;
+        if header.version != 2 {
+            return Err(RecordingError::Format(format!("unsupported version {}", header.version)));
+        }
+
+        let mut events = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (time, kind, data): (f64, String, String) = serde_json::from_str(&line)
+                .map_err(|e| RecordingError::Format(format!("bad event: {}", e)))?;
+            if kind == "o" {
+                events.push((time, data));
+            }
+        }
+        Ok(Self { header, events })
+    }
+
+    /// Total length in seconds.
+    pub fn duration(&self) -> f64 {
+        self.events.last().map(|(t, _)| *t).unwrap_or(0.0)
+    }
+
+    /// Write the events to `out` in real time, divided by `speed`, with
+    /// pauses capped at `max_idle` seconds.
+    pub fn play(&self, out: &mut impl Write, speed: f64, max_idle: Option<f64>) -> io::Result<()> {
+        let speed = if speed > 0.0 { speed } else { 1.0 };
+        let mut previous = 0.0;
+        for (time, data) in &self.events {
+            let mut pause = (time - previous).max(0.0);
+            if let Some(max_idle) = max_idle {
+                pause = pause.min(max_idle);
+            }
+            previous = *time;
+            std::thread::sleep(Duration::from_secs_f64(pause / speed));
+            out.write_all(data.as_bytes())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `COMMANDER_RECORD_SESSIONS` asks for sessions to be recorded.
+pub fn record_sessions_enabled() -> bool {
+    std::env::var(RECORD_SESSIONS_ENV)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+/// New recording path for `session`: `<recordings>/<session>-<timestamp>.cast`.
+pub fn recording_path(session: &str) -> PathBuf {
+    recordings_dir().join(format!(
+        "{}-{}.{}",
+        recording_name(session),
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        RECORDING_EXTENSION
+    ))
+}
+
+/// File name prefix of `session`'s recordings.
+fn recording_name(session: &str) -> String {
+    session
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// Whether `path` is a recording of `session` (named by [`recording_path`]).
+fn is_recording_of(path: &Path, session: &str) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let Some(timestamp) = stem
+        .strip_prefix(&recording_name(session))
+        .and_then(|rest| rest.strip_prefix('-'))
+    else {
+        return false;
+    };
+    // `YYYYmmdd-HHMMSS`, so `api` does not match `api-v2`'s recordings
+    timestamp.len() == 15
+        && timestamp
+            .char_indices()
+            .all(|(i, c)| if i == 8 { c == '-' } else { c.is_ascii_digit() })
+}
+
+/// Recordings of `session`, oldest first.
+pub fn recordings_for(session: &str) -> Vec<PathBuf> {
+    list_recordings()
+        .into_iter()
+        .filter(|p| is_recording_of(p, session))
+        .collect()
+}
+
+/// All recordings, oldest first.
+pub fn list_recordings() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(recordings_dir()) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == RECORDING_EXTENSION))
+        .map(|p| {
+            let modified = fs::metadata(&p)
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            (modified, p)
+        })
+        .collect();
+    paths.sort();
+    paths.into_iter().map(|(_, p)| p).collect()
+}
+
+/// Resolve a recording argument: an existing path, or a file name (with or
+/// without extension) in the recordings directory.
+pub fn resolve_recording(name: &str) -> Result<PathBuf, RecordingError> {
+    let direct = PathBuf::from(name);
+    if direct.is_file() {
+        return Ok(direct);
+    }
+    let in_dir = recordings_dir().join(name);
+    if in_dir.is_file() {
+        return Ok(in_dir);
+    }
+    let with_ext = in_dir.with_extension(RECORDING_EXTENSION);
+    if with_ext.is_file() {
+        return Ok(with_ext);
+    }
+    Err(RecordingError::NotFound(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_recording_of() {
+        let dir = Path::new("/rec");
+        assert!(is_recording_of(&dir.join("commander-api-20261017-120000.cast"), "commander-api"));
+        assert!(!is_recording_of(&dir.join("commander-api-v2-20261017-120000.cast"), "commander-api"));
+        assert!(!is_recording_of(&dir.join("commander-api.cast"), "commander-api"));
+        assert!(is_recording_of(&dir.join("a_b-20261017-120000.cast"), "a/b"));
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rec").join("s.cast");
+        let mut recorder = Recorder::create(&path, 80, 24, Some("s")).unwrap();
+
+        assert!(recorder.record_screen("one\ntwo").unwrap());
+        assert!(!recorder.record_screen("one\ntwo").unwrap());
+        assert!(recorder.record_screen("three").unwrap());
+
+        let recording = Recording::load(&path).unwrap();
+        assert_eq!(recording.header.version, 2);
+        assert_eq!(recording.header.width, 80);
+        assert_eq!(recording.header.title.as_deref(), Some("s"));
+        assert_eq!(recording.events.len(), 2);
+        assert_eq!(recording.events[0].1, "\x1b[2J\x1b[Hone\r\ntwo");
+        assert!(recording.events[1].0 >= recording.events[0].0);
+    }
+
+    #[test]
+    fn test_load_skips_input_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.cast");
+        fs::write(
+            &path,
+            "{\"version\":2,\"width\":10,\"height\":5}\n[0.5,\"o\",\"hi\"]\n[0.7,\"i\",\"x\"]\n[1.0,\"o\",\"!\"]\n",
+        )
+        .unwrap();
+
+        let recording = Recording::load(&path).unwrap();
+        assert_eq!(recording.events, vec![(0.5, "hi".to_string()), (1.0, "!".to_string())]);
+        assert_eq!(recording.duration(), 1.0);
+
+        let mut out = Vec::new();
+        recording.play(&mut out, 1000.0, Some(0.0)).unwrap();
+        assert_eq!(out, b"hi!");
+    }
+
+    #[test]
+    fn test_load_rejects_other_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("v1.cast");
+        fs::write(&path, "{\"version\":1,\"width\":10,\"height\":5}\n").unwrap();
+        assert!(matches!(Recording::load(&path), Err(RecordingError::Format(_))));
+    }
+
+    #[test]
+    fn test_recording_path_sanitizes() {
+        let path = recording_path("cmd-my app/1");
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("cmd-my_app_1-"));
+        assert!(name.ends_with(".cast"));
+    }
+}
@@ -0,0 +1,22 @@
+//! Access to Commander's secret store.
+//!
+//! API keys and bot tokens are read from the store in
+//! [`config::secrets_dir`](crate::config::secrets_dir) before falling back
+//! to environment variables (including those loaded from `.env.local`).
+
+pub use commander_secrets::{
+    Backend, SecretEntry, SecretStore, SecretsError, GITHUB_TOKEN, INBOUND_WEBHOOK_TOKEN,
+    KNOWN_SECRETS, OPENAI_API_KEY, OPENROUTER_API_KEY, TELEGRAM_BOT_TOKEN,
+};
+
+use crate::config;
+
+/// Open the secret store in the config directory.
+pub fn secret_store() -> SecretStore {
+    SecretStore::new(config::secrets_dir())
+}
+
+/// Read a secret from the store, falling back to the environment variable of the same name.
+pub fn get_secret(name: &str) -> Option<String> {
+    secret_store().get_or_env(name)
+}
@@ -0,0 +1,220 @@
+//! Per-session send locks shared by every frontend and process.
+//!
+//! A message reaches a session as tmux keystrokes followed by Enter, so two
+//! frontends sending to the same session at once (the TUI and the Telegram
+//! bot, say) interleave into garbage. Before sending, a frontend takes a
+//! ticket in `state/send-locks/<session>/` with [`acquire_send_lock`]; tickets
+//! are served in the order they were taken, and a ticket is removed when its
+//! [`SendLock`] is dropped.
+//!
+//! At most [`MAX_QUEUED`] senders wait behind the one sending, each for up to
+//! [`SEND_LOCK_TIMEOUT`]; past that the sender is told which frontend is
+//! busy with the session. A ticket older than [`STALE_AFTER`] was left by a
+//! frontend that died while sending and is cleared by the next sender.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::config;
+
+/// How long a sender waits for its turn.
+pub const SEND_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Senders allowed to wait behind the one sending.
+pub const MAX_QUEUED: usize = 4;
+
+/// Age after which a ticket is considered abandoned.
+pub const STALE_AFTER: Duration = Duration::from_secs(30);
+
+/// How often a waiting sender checks whether it is its turn.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Distinguishes tickets taken by one process in the same nanosecond.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Errors taking a send lock.
+#[derive(Debug, Error)]
+pub enum SendLockError {
+    /// Another frontend kept the session busy past the timeout.
+    #[error("{0} is sending to this session; try again in a moment")]
+    Busy(String),
+    /// Too many senders are already waiting.
+    #[error("{0} messages are already queued for this session; try again in a moment")]
+    QueueFull(usize),
+    #[error("send lock: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Who holds or waits for a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Ticket {
+    frontend: String,
+    pid: u32,
+    at: DateTime<Utc>,
+}
+
+/// The right to send to a session, released on drop.
+#[derive(Debug)]
+pub struct SendLock {
+    path: PathBuf,
+    waited_for: Option<String>,
+}
+
+impl SendLock {
+    /// The frontend this sender had to wait for, if any, for feedback such
+    /// as "Telegram was sending; your message went after it".
+    pub fn waited_for(&self) -> Option<&str> {
+        self.waited_for.as_deref()
+    }
+}
+
+impl Drop for SendLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(path = %self.path.display(), error = %e, "Failed to release send lock");
+            }
+        }
+    }
+}
+
+/// Directory holding the tickets of `session`.
+pub fn send_lock_dir(session: &str) -> PathBuf {
+    config::send_locks_dir().join(session.replace(['/', '\\'], "_").replace("..", "_"))
+}
+
+/// Wait for the turn of `frontend` (e.g. "TUI", "Telegram") to send to
+/// `session`, blocking for up to [`SEND_LOCK_TIMEOUT`].
+pub fn acquire_send_lock(session: &str, frontend: &str) -> Result<SendLock, SendLockError> {
+    acquire_in(&send_lock_dir(session), frontend, SEND_LOCK_TIMEOUT)
+}
+
+fn acquire_in(dir: &Path, frontend: &str, timeout: Duration) -> Result<SendLock, SendLockError> {
+    fs::create_dir_all(dir)?;
+    let queued = live_tickets(dir).len();
+    if queued > MAX_QUEUED {
+        return Err(SendLockError::QueueFull(queued));
+    }
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let name = format!(
+        "{:024}-{}-{}.json",
+        nanos,
+        std::process::id(),
+        SEQUENCE.fetch_add(1, Ordering::Relaxed)
+    );
+    let ticket = Ticket {
+        frontend: frontend.to_string(),
+        pid: std::process::id(),
+        at: Utc::now(),
+    };
+    let path = dir.join(name);
+    let mut file = OpenOptions::new().write(true).create_new(true).open(&path)?;
+    file.write_all(serde_json::to_string(&ticket).map_err(std::io::Error::from)?.as_bytes())?;
+    drop(file);
+
+    let mut lock = SendLock { path, waited_for: None };
+    let started = Instant::now();
+    loop {
+        let tickets = live_tickets(dir);
+        let holder = match tickets.first() {
+            // Our own ticket went missing (cleared by hand); send anyway.
+            None => return Ok(lock),
+            Some((first, _)) if *first == lock.path => {
+                if let Some(frontend) = &lock.waited_for {
+                    debug!(frontend = %frontend, waited_ms = started.elapsed().as_millis(), "Send lock acquired after waiting");
+                }
+                return Ok(lock);
+            }
+            Some((_, holder)) => holder.frontend.clone(),
+        };
+        if started.elapsed() >= timeout {
+            return Err(SendLockError::Busy(holder));
+        }
+        lock.waited_for = Some(holder);
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The tickets in `dir`, oldest first, after removing stale ones.
+fn live_tickets(dir: &Path) -> Vec<(PathBuf, Ticket)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let now = Utc::now();
+    let stale_after = chrono::Duration::from_std(STALE_AFTER).unwrap_or(chrono::Duration::MAX);
+    let mut tickets: Vec<(PathBuf, Ticket)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            // A ticket being written is empty for a moment; it is still live.
+            let ticket = match fs::read_to_string(&path) {
+                Ok(text) if text.is_empty() => Ticket {
+                    frontend: "another frontend".to_string(),
+                    pid: 0,
+                    at: now,
+                },
+                Ok(text) => serde_json::from_str(&text).ok()?,
+                Err(_) => return None,
+            };
+            if now - ticket.at > stale_after {
+                warn!(path = %path.display(), frontend = %ticket.frontend, "Removing stale send lock");
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+            Some((path, ticket))
+        })
+        .collect();
+    tickets.sort_by(|a, b| a.0.file_name().cmp(&b.0.file_name()));
+    tickets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_lock_serializes_and_reports_holder() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dir = dir.path().join("api");
+
+        let first = acquire_in(&dir, "Telegram", Duration::from_millis(100)).unwrap();
+        assert!(first.waited_for().is_none());
+        match acquire_in(&dir, "TUI", Duration::from_millis(60)) {
+            Err(SendLockError::Busy(holder)) => assert_eq!(holder, "Telegram"),
+            other => panic!("expected busy, got {:?}", other),
+        }
+        // The timed-out ticket was released with its lock.
+        assert_eq!(live_tickets(&dir).len(), 1);
+
+        let waiter = std::thread::spawn({
+            let dir = dir.clone();
+            move || acquire_in(&dir, "TUI", Duration::from_secs(5)).map(|l| l.waited_for().map(String::from))
+        });
+        std::thread::sleep(Duration::from_millis(80));
+        drop(first);
+        assert_eq!(waiter.join().unwrap().unwrap().as_deref(), Some("Telegram"));
+        assert!(live_tickets(&dir).is_empty());
+
+        // Abandoned tickets don't block anyone.
+        let stale = Ticket {
+            frontend: "REPL".into(),
+            pid: 1,
+            at: Utc::now() - chrono::Duration::minutes(5),
+        };
+        fs::write(dir.join("0-1-0.json"), serde_json::to_string(&stale).unwrap()).unwrap();
+        assert!(acquire_in(&dir, "TUI", Duration::from_millis(60)).unwrap().waited_for().is_none());
+    }
+}
@@ -0,0 +1,289 @@
+//! Named prompt snippets, expanded before they are sent to a session.
+//!
+//! Snippets live in `~/.ai-commander/snippets.toml`, one table per name:
+//!
+//! ```toml
+//! [review]
+//! description = "Review the uncommitted changes"
+//! text = "Review the uncommitted changes on {branch} for bugs and missing tests"
+//!
+//! [fix-issue]
+//! text = "Fix GitHub issue #{args} in {project}, then run the tests"
+//! ```
+//!
+//! `/run <name> [args]` or `!name [args]` in the TUI, REPL and chat bots sends
+//! a snippet's text with its variables resolved: `{project}` (project name),
+//! `{path}` (project directory), `{branch}` (its current git branch) and
+//! `{args}` (the text after the name, appended when the snippet doesn't use
+//! it). Unknown `{...}` placeholders are left as they are.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config;
+
+/// Errors loading, changing or expanding snippets.
+#[derive(Debug, Error)]
+pub enum SnippetError {
+    /// No snippet with this name.
+    #[error("Unknown snippet '{name}'. Available: {available}")]
+    NotFound { name: String, available: String },
+
+    /// A snippet with this name already exists.
+    #[error("Snippet '{0}' already exists; edit it instead")]
+    Exists(String),
+
+    /// Names are letters, digits, `-` and `_`.
+    #[error("Invalid snippet name '{0}': use letters, digits, '-' and '_'")]
+    InvalidName(String),
+
+    /// The snippets file could not be parsed.
+    #[error("Invalid snippets file {path}: {message}")]
+    Invalid { path: PathBuf, message: String },
+
+    /// A filesystem operation failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type for snippet operations.
+pub type Result<T> = std::result::Result<T, SnippetError>;
+
+/// A named prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Text sent to the session, with `{...}` variables.
+    pub text: String,
+    /// Shown when listing snippets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Snippets by name.
+pub type Snippets = BTreeMap<String, Snippet>;
+
+/// Values of the variables of a snippet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnippetContext {
+    pub project: Option<String>,
+    pub path: Option<String>,
+    pub branch: Option<String>,
+}
+
+impl SnippetContext {
+    /// Context of `project` in `path`, reading the branch from git.
+    pub fn for_project(project: &str, path: Option<&str>) -> Self {
+        Self {
+            project: Some(project.to_string()),
+            path: path.map(str::to_string),
+            branch: path.and_then(|p| current_branch(Path::new(p))),
+        }
+    }
+}
+
+/// Load the user's snippets; none when the file doesn't exist.
+pub fn load_snippets() -> Result<Snippets> {
+    load_from(&config::snippets_file())
+}
+
+/// Add a snippet, failing if the name is taken.
+pub fn add_snippet(name: &str, text: &str, description: Option<&str>) -> Result<()> {
+    let path = config::snippets_file();
+    let mut snippets = load_from(&path)?;
+    validate_name(name)?;
+    if snippets.contains_key(name) {
+        return Err(SnippetError::Exists(name.to_string()));
+    }
+    snippets.insert(
+        name.to_string(),
+        Snippet {
+            text: text.trim().to_string(),
+            description: description.map(str::to_string),
+        },
+    );
+    save_to(&path, &snippets)
+}
+
+/// Replace the text, and the description when given, of a snippet.
+pub fn edit_snippet(name: &str, text: &str, description: Option<&str>) -> Result<()> {
+    let path = config::snippets_file();
+    let mut snippets = load_from(&path)?;
+    let Some(snippet) = snippets.get_mut(name) else {
+        return Err(not_found(&snippets, name));
+    };
+    snippet.text = text.trim().to_string();
+    if let Some(description) = description {
+        snippet.description = Some(description.to_string());
+    }
+    save_to(&path, &snippets)
+}
+
+/// Remove a snippet, returning it.
+pub fn remove_snippet(name: &str) -> Result<Snippet> {
+    let path = config::snippets_file();
+    let mut snippets = load_from(&path)?;
+    let snippet = snippets.remove(name).ok_or_else(|| not_found(&snippets, name))?;
+    save_to(&path, &snippets)?;
+    Ok(snippet)
+}
+
+/// The snippet named in `input`, `/run <name> [args]` or `!name [args]`,
+/// as `(name, args)`. `None` when `input` is not a snippet invocation.
+pub fn parse_invocation(input: &str) -> Option<(&str, &str)> {
+    let input = input.trim();
+    let rest = input
+        .strip_prefix("/run ")
+        .or_else(|| input.strip_prefix('!'))?
+        .trim_start();
+    let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    (!name.is_empty() && valid_name(name)).then_some((name, args.trim()))
+}
+
+/// Text of snippet `name` from `snippets`, with its variables resolved.
+pub fn expand(snippets: &Snippets, name: &str, args: &str, context: &SnippetContext) -> Result<String> {
+    let snippet = snippets.get(name).ok_or_else(|| not_found(snippets, name))?;
+    Ok(render(&snippet.text, args, context))
+}
+
+/// Resolve the variables of `text`.
+pub fn render(text: &str, args: &str, context: &SnippetContext) -> String {
+    let mut rendered = text.to_string();
+    for (name, value) in [
+        ("project", &context.project),
+        ("path", &context.path),
+        ("branch", &context.branch),
+    ] {
+        if let Some(value) = value {
+            rendered = rendered.replace(&format!("{{{}}}", name), value);
+        }
+    }
+    if rendered.contains("{args}") {
+        rendered.replace("{args}", args)
+    } else if args.is_empty() {
+        rendered
+    } else {
+        format!("{} {}", rendered, args)
+    }
+}
+
+/// One line per snippet, for listings.
+pub fn format_list(snippets: &Snippets) -> Vec<String> {
+    snippets
+        .iter()
+        .map(|(name, snippet)| {
+            let about = snippet.description.as_deref().unwrap_or(&snippet.text);
+            let about: String = about.lines().next().unwrap_or_default().chars().take(70).collect();
+            format!("{:<16} {}", name, about)
+        })
+        .collect()
+}
+
+fn load_from(path: &Path) -> Result<Snippets> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Snippets::new()),
+        Err(e) => return Err(e.into()),
+    };
+    toml::from_str(&text).map_err(|e| SnippetError::Invalid {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
+
+fn save_to(path: &Path, snippets: &Snippets) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let text = toml::to_string_pretty(snippets).map_err(|e| SnippetError::Invalid {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    fs::write(path, text)?;
+    Ok(())
+}
+
+fn not_found(snippets: &Snippets, name: &str) -> SnippetError {
+    let available = if snippets.is_empty() {
+        "none".to_string()
+    } else {
+        snippets.keys().cloned().collect::<Vec<_>>().join(", ")
+    };
+    SnippetError::NotFound {
+        name: name.to_string(),
+        available,
+    }
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if valid_name(name) {
+        Ok(())
+    } else {
+        Err(SnippetError::InvalidName(name.to_string()))
+    }
+}
+
+fn current_branch(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["branch", "--show-current"])
+        .output()
+        .ok()?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !branch.is_empty()).then_some(branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippets_round_trip_and_expand() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("snippets.toml");
+        assert!(load_from(&path).unwrap().is_empty());
+
+        let mut snippets = Snippets::new();
+        snippets.insert(
+            "review".into(),
+            Snippet {
+                text: "Review {project} on {branch} in {path}".into(),
+                description: Some("Review changes".into()),
+            },
+        );
+        snippets.insert("issue".into(), Snippet { text: "Fix issue #{args}".into(), description: None });
+        save_to(&path, &snippets).unwrap();
+        let snippets = load_from(&path).unwrap();
+        assert_eq!(snippets.len(), 2);
+
+        let context = SnippetContext {
+            project: Some("api".into()),
+            path: Some("/src/api".into()),
+            branch: Some("main".into()),
+        };
+        assert_eq!(
+            expand(&snippets, "review", "then stop", &context).unwrap(),
+            "Review api on main in /src/api then stop"
+        );
+        assert_eq!(expand(&snippets, "issue", "42", &SnippetContext::default()).unwrap(), "Fix issue #42");
+        match expand(&snippets, "deploy", "", &context) {
+            Err(SnippetError::NotFound { available, .. }) => assert_eq!(available, "issue, review"),
+            other => panic!("expected not found, got {:?}", other),
+        }
+
+        assert_eq!(parse_invocation("/run review now"), Some(("review", "now")));
+        assert_eq!(parse_invocation("!issue 42"), Some(("issue", "42")));
+        assert_eq!(parse_invocation("!"), None);
+        assert_eq!(parse_invocation("hello !review"), None);
+        assert_eq!(parse_invocation("!= not a snippet"), None);
+    }
+}
@@ -473,6 +473,55 @@ impl StructuredSummary {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Progress milestones
+// ---------------------------------------------------------------------------
+
+/// Longest `current_step` kept, in characters.
+const MAX_STEP_CHARS: usize = 80;
+
+static RE_TOOL_CALL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[⏺●]\s*([A-Z][A-Za-z]*\(.*)$").expect("RE_TOOL_CALL")
+});
+
+/// Milestones of a task still in progress, for streaming to the user.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressMilestones {
+    /// Files changed so far.
+    pub files_changed: Vec<String>,
+    /// Latest test counts, if tests have run.
+    pub tests: Option<TestResult>,
+    /// What the session is doing now, e.g. `Bash(cargo test)`.
+    pub current_step: Option<String>,
+}
+
+impl ProgressMilestones {
+    /// Extract milestones from the output collected so far.
+    ///
+    /// The current step is the most recent tool call (`⏺ Bash(cargo test)`).
+    pub fn from_output(lines: &[String]) -> Self {
+        let facts = extract(lines);
+        let current_step = lines.iter().rev().find_map(|line| {
+            let caps = RE_TOOL_CALL.captures(line.trim())?;
+            let step = caps[1].trim();
+            Some(match step.char_indices().nth(MAX_STEP_CHARS) {
+                Some((end, _)) => format!("{}…", &step[..end]),
+                None => step.to_string(),
+            })
+        });
+        Self {
+            files_changed: facts.files_edited,
+            tests: facts.tests,
+            current_step,
+        }
+    }
+
+    /// Returns `true` if nothing has been extracted.
+    pub fn is_empty(&self) -> bool {
+        self.files_changed.is_empty() && self.tests.is_none() && self.current_step.is_none()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -762,4 +811,21 @@ mod tests {
             summary
         );
     }
+
+    #[test]
+    fn test_progress_milestones() {
+        let input = lines(
+            "⏺ Update(src/lib.rs)\n\
+             ⎿ Updated src/lib.rs with 3 additions\n\
+             ⏺ Bash(cargo test)\n\
+             test result: ok. 12 passed; 1 failed; 0 ignored",
+        );
+        let m = ProgressMilestones::from_output(&input);
+        assert_eq!(m.files_changed, vec!["src/lib.rs"]);
+        let tests = m.tests.unwrap();
+        assert_eq!((tests.passed, tests.failed), (12, 1));
+        assert_eq!(m.current_step.as_deref(), Some("Bash(cargo test)"));
+
+        assert!(ProgressMilestones::from_output(&lines("Thinking about it")).is_empty());
+    }
 }
@@ -9,12 +9,19 @@
 //! 2. **OpenRouter** — used as fallback when Ollama is unavailable or fails.
 //!    The API key is read from `OPENROUTER_API_KEY`; if that variable is unset a
 //!    hardcoded fallback key is used so the feature works out of the box.
+//!
+//! Long responses can be summarized differentially with
+//! [`DifferentialSummary`]: each update sends the previous summary plus only
+//! the output observed since, instead of the whole buffer.
+
+use std::sync::RwLock;
 
 use thiserror::Error;
 use tracing::{info, warn};
 
 use crate::ollama::OllamaClient;
 use crate::output_filter::clean_response;
+use crate::redaction::redact;
 
 /// Hardcoded OpenRouter fallback key used when `OPENROUTER_API_KEY` is not set.
 /// Reading from the env var takes precedence.
@@ -70,7 +77,7 @@ pub fn get_confidence_threshold() -> f32 {
 }
 
 /// OpenRouter API endpoint.
-const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+pub(crate) const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 
 /// System prompt for the summarizer.
 const SYSTEM_PROMPT: &str = r#"You are a response summarizer for Commander, an AI orchestration tool.
@@ -111,6 +118,19 @@ pub enum SummarizerError {
     /// Failed to parse API response.
     #[error("Failed to parse response: {0}")]
     ParseError(String),
+
+    /// The network is down (see [`crate::connectivity`]).
+    #[error("Offline: the model provider can't be reached")]
+    Offline,
+}
+
+/// Fail with [`SummarizerError::Offline`] before a request that can't
+/// reach OpenRouter.
+pub(crate) fn require_online() -> Result<(), SummarizerError> {
+    if crate::connectivity::is_offline() {
+        return Err(SummarizerError::Offline);
+    }
+    Ok(())
 }
 
 /// Check if summarization is available.
@@ -129,7 +149,7 @@ pub fn is_available() -> bool {
 /// Why: GUI launched from Finder/dock does not inherit the shell environment,
 /// so `OPENROUTER_API_KEY` is typically unset. We must read the key from the
 /// user's config files before falling back to the hardcoded key.
-/// What: Search order is (1) `OPENROUTER_API_KEY` env var, (2)
+/// What: Search order is (1) the secret store, then the `OPENROUTER_API_KEY` env var, (2)
 /// `~/.ai-commander/config/.env.local`, (3) `~/.ai-commander/config/config.toml`
 /// (field `openrouter_api_key`), (4) `~/.ai-commander/config.json` (either
 /// `{"openrouter_api_key": "..."}` or the GUI-written `{"key":"OPENROUTER_API_KEY","value":"..."}`
@@ -138,10 +158,8 @@ pub fn is_available() -> bool {
 /// returned key matches the TOML value; with a `config.json` `{key,value}` pair,
 /// assert it is parsed. Clear env + remove files → fallback constant is returned.
 pub fn get_api_key() -> Option<String> {
-    if let Ok(key) = std::env::var("OPENROUTER_API_KEY") {
-        if !key.is_empty() {
-            return Some(key);
-        }
+    if let Some(key) = crate::secrets::get_secret(crate::secrets::OPENROUTER_API_KEY) {
+        return Some(key);
     }
     if let Some(key) = read_api_key_from_config_files() {
         return Some(key);
@@ -218,8 +236,24 @@ fn read_api_key_from_config_files() -> Option<String> {
     None
 }
 
+/// Model set at runtime, taking precedence over `OPENROUTER_MODEL`.
+static MODEL_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Override the summarization model for this process (`None` clears it).
+///
+/// Used to apply `[telegram] summarizer_model` from `config.toml` without a
+/// restart.
+pub fn set_model_override(model: Option<String>) {
+    if let Ok(mut current) = MODEL_OVERRIDE.write() {
+        *current = model;
+    }
+}
+
 /// Get the configured model, or default.
 pub fn get_model() -> String {
+    if let Some(model) = MODEL_OVERRIDE.read().ok().and_then(|m| m.clone()) {
+        return model;
+    }
     std::env::var("OPENROUTER_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string())
 }
 
@@ -239,6 +273,7 @@ pub async fn summarize_async(
     api_key: &str,
     model: &str,
 ) -> Result<String, SummarizerError> {
+    require_online()?;
     let user_prompt = format!(
         "User asked: {}\n\nRaw response:\n{}\n\nProvide a conversational summary:",
         query, raw_response
@@ -248,7 +283,7 @@ pub async fn summarize_async(
         "model": model,
         "messages": [
             {"role": "system", "content": SYSTEM_PROMPT},
-            {"role": "user", "content": user_prompt}
+            {"role": "user", "content": redact(&user_prompt)}
         ],
         "max_tokens": 500
     });
@@ -290,6 +325,7 @@ pub fn summarize_blocking(
     api_key: &str,
     model: &str,
 ) -> Result<String, SummarizerError> {
+    require_online()?;
     let user_prompt = format!(
         "User asked: {}\n\nRaw response:\n{}\n\nProvide a conversational summary:",
         query, raw_response
@@ -299,7 +335,7 @@ pub fn summarize_blocking(
         "model": model,
         "messages": [
             {"role": "system", "content": SYSTEM_PROMPT},
-            {"role": "user", "content": user_prompt}
+            {"role": "user", "content": redact(&user_prompt)}
         ],
         "max_tokens": 500
     });
@@ -373,6 +409,12 @@ pub async fn summarize_tiered(query: &str, raw_response: &str) -> (String, u8) {
         info!("Ollama not available, skipping to OpenRouter fallback");
     }
 
+    // Without a network OpenRouter can't be reached; use the cleaned response
+    if crate::connectivity::is_offline() {
+        info!("Offline, summarizing via fallback truncation");
+        return (fallback_truncate(raw_response, FALLBACK_MAX_LINES, FALLBACK_MAX_CHARS), 4);
+    }
+
     // Tier 3: Cheap OpenRouter model with pre-digested context
     if confidence >= 0.4 {
         let context = extracted.to_context();
@@ -451,6 +493,9 @@ pub async fn summarize_with_fallback(query: &str, raw_response: &str) -> String
 /// (Ollama requires async; use `summarize_with_fallback` for the full provider
 /// chain including local Ollama inference).
 pub fn summarize_blocking_with_fallback(query: &str, raw_response: &str) -> String {
+    if crate::connectivity::is_offline() {
+        return fallback_truncate(raw_response, FALLBACK_MAX_LINES, FALLBACK_MAX_CHARS);
+    }
     // get_api_key() always returns Some (hardcoded fallback key), so unwrap is safe.
     let api_key = get_api_key().expect("get_api_key always returns Some");
     let model = get_model();
@@ -494,6 +539,7 @@ pub async fn summarize_incremental(content: &str, line_count: usize) -> Result<S
     }
 
     // Fall back to OpenRouter
+    require_online()?;
     let api_key = get_api_key().expect("get_api_key always returns Some");
     let model = get_model();
 
@@ -501,7 +547,7 @@ pub async fn summarize_incremental(content: &str, line_count: usize) -> Result<S
         "model": model,
         "messages": [
             {"role": "system", "content": INCREMENTAL_SYSTEM_PROMPT},
-            {"role": "user", "content": user_prompt}
+            {"role": "user", "content": redact(&user_prompt)}
         ],
         "max_tokens": 150
     });
@@ -529,6 +575,189 @@ pub async fn summarize_incremental(content: &str, line_count: usize) -> Result<S
     Ok(format!("📊 Incremental Summary ({} lines):\n{}", line_count, summary))
 }
 
+/// System prompt for differential summaries: earlier output arrives already
+/// summarized, only the output after it is raw.
+const DIFFERENTIAL_SYSTEM_PROMPT: &str = r#"You are a response summarizer for Commander, an AI orchestration tool.
+You keep a running summary of a long response. You are given the summary of the output so far and only the output that came after it.
+
+Rules:
+- Return one updated summary of the WHOLE response, merging the new output into the earlier summary
+- Keep earlier points that still matter; drop ones the new output supersedes
+- Be concise but informative (2-4 sentences for simple responses, more for complex ones)
+- Focus on what was DONE or LEARNED, not the process
+- Skip UI noise, file listings, and verbose tool output
+- Use natural language, not bullet points unless listing multiple items
+- Never say "Claude Code" or mention the underlying tool"#;
+
+/// Rough token count of `text` (about four characters per token).
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Tokens sent to the summarizer, against what sending the whole buffer
+/// every time would have cost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DifferentialStats {
+    /// Summaries produced.
+    pub updates: usize,
+    /// Estimated prompt tokens actually sent.
+    pub tokens_sent: usize,
+    /// Estimated prompt tokens had every update sent the whole buffer.
+    pub tokens_full: usize,
+}
+
+impl DifferentialStats {
+    /// Fraction of prompt tokens saved (0.0 to 1.0).
+    pub fn saved_ratio(&self) -> f64 {
+        if self.tokens_full == 0 {
+            return 0.0;
+        }
+        1.0 - self.tokens_sent as f64 / self.tokens_full as f64
+    }
+}
+
+/// One summarization step: the prior summary and the output after it.
+#[derive(Debug, Clone)]
+pub struct DifferentialRequest {
+    /// Summary of the lines already covered, if any.
+    pub prior_summary: Option<String>,
+    /// Output not yet covered by the prior summary.
+    pub new_output: String,
+    /// Buffer lines covered once this request is applied.
+    pub covered_lines: usize,
+    /// Estimated tokens of the whole buffer, for the stats.
+    full_tokens: usize,
+}
+
+impl DifferentialRequest {
+    /// User prompt for this step.
+    pub fn user_prompt(&self, query: &str) -> String {
+        match &self.prior_summary {
+            Some(prior) => format!(
+                "User asked: {}\n\nSummary of the output so far:\n{}\n\nNew output since then:\n{}\n\nProvide the updated summary:",
+                query, prior, self.new_output
+            ),
+            None => format!(
+                "User asked: {}\n\nRaw response:\n{}\n\nProvide a conversational summary:",
+                query, self.new_output
+            ),
+        }
+    }
+}
+
+/// Running summary of a growing response buffer.
+///
+/// Each update feeds only the lines observed since the previous one, together
+/// with the previous summary, so the prompt stays about the same size however
+/// long the response gets. [`stats`](Self::stats) measure the saving.
+#[derive(Debug, Clone, Default)]
+pub struct DifferentialSummary {
+    summary: Option<String>,
+    covered_lines: usize,
+    stats: DifferentialStats,
+}
+
+impl DifferentialSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The summary of the lines covered so far.
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Number of lines in `lines` not yet covered.
+    pub fn pending_lines(&self, lines: &[String]) -> usize {
+        lines.len().saturating_sub(self.covered_lines)
+    }
+
+    /// Tokens sent and saved since creation; kept across [`reset`](Self::reset).
+    pub fn stats(&self) -> DifferentialStats {
+        self.stats
+    }
+
+    /// Start over for a new response, keeping the stats.
+    pub fn reset(&mut self) {
+        self.summary = None;
+        self.covered_lines = 0;
+    }
+
+    /// Build the next step for `lines`, the whole buffer so far.
+    ///
+    /// Returns `None` when there is no new output. A buffer shorter than the
+    /// covered part means it was replaced, so it is summarized from scratch.
+    pub fn request(&self, lines: &[String]) -> Option<DifferentialRequest> {
+        let (prior_summary, start) = if lines.len() < self.covered_lines {
+            (None, 0)
+        } else {
+            (self.summary.clone(), self.covered_lines)
+        };
+        if start == lines.len() {
+            return None;
+        }
+        Some(DifferentialRequest {
+            prior_summary,
+            new_output: lines[start..].join("\n"),
+            covered_lines: lines.len(),
+            full_tokens: estimate_tokens(&lines.join("\n")),
+        })
+    }
+
+    /// Record the summary produced for `request`.
+    pub fn apply(&mut self, request: &DifferentialRequest, summary: String) {
+        self.stats.updates += 1;
+        self.stats.tokens_sent += estimate_tokens(&request.new_output)
+            + request.prior_summary.as_deref().map(estimate_tokens).unwrap_or(0);
+        self.stats.tokens_full += request.full_tokens;
+        self.summary = Some(summary);
+        self.covered_lines = request.covered_lines;
+        info!(
+            updates = self.stats.updates,
+            tokens_sent = self.stats.tokens_sent,
+            tokens_full = self.stats.tokens_full,
+            saved = format!("{:.0}%", self.stats.saved_ratio() * 100.0),
+            "Differential summary updated"
+        );
+    }
+}
+
+/// Summarize one differential step synchronously via OpenRouter.
+pub fn summarize_differential_blocking(
+    query: &str,
+    request: &DifferentialRequest,
+) -> Result<String, SummarizerError> {
+    require_online()?;
+    let api_key = get_api_key().ok_or(SummarizerError::NoApiKey)?;
+    let system_prompt = if request.prior_summary.is_some() { DIFFERENTIAL_SYSTEM_PROMPT } else { SYSTEM_PROMPT };
+    let request_body = serde_json::json!({
+        "model": get_model(),
+        "messages": [
+            {"role": "system", "content": system_prompt},
+            {"role": "user", "content": redact(&request.user_prompt(query))}
+        ],
+        "max_tokens": 500
+    });
+
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .post(OPENROUTER_API_URL)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&request_body)
+        .send()
+        .map_err(|e| SummarizerError::RequestFailed(e.to_string()))?;
+
+    let json: serde_json::Value = response
+        .json()
+        .map_err(|e| SummarizerError::ParseError(e.to_string()))?;
+
+    json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| SummarizerError::ParseError("No content in response".to_string()))
+}
+
 /// System prompt for screen context interpretation.
 ///
 /// Why: A tight, example-driven prompt prevents the model from echoing the raw
@@ -897,7 +1126,10 @@ pub fn llm_available() -> bool {
             return true;
         }
     }
-    // Check if we have a plausible OpenRouter key
+    // Check if we have a plausible OpenRouter key and can reach it
+    if crate::connectivity::is_offline() {
+        return false;
+    }
     if let Some(key) = get_api_key() {
         if key.starts_with("sk-or-") && key.len() > 20 {
             return true;
@@ -997,6 +1229,9 @@ pub fn interpret_screen_context(screen_content: &str, is_ready: bool) -> Option<
     }
 
     // Fall back to OpenRouter
+    if crate::connectivity::is_offline() {
+        return None;
+    }
     let api_key = match get_api_key() {
         Some(key) => key,
         None => {
@@ -1013,7 +1248,7 @@ pub fn interpret_screen_context(screen_content: &str, is_ready: bool) -> Option<
         "model": model,
         "messages": [
             {"role": "system", "content": SCREEN_INTERPRET_PROMPT},
-            {"role": "user", "content": user_prompt}
+            {"role": "user", "content": redact(&user_prompt)}
         ],
         "max_tokens": 100
     });
@@ -1241,6 +1476,59 @@ mod tests {
         let _ = is_available();
     }
 
+    fn lines(range: std::ops::Range<usize>) -> Vec<String> {
+        range.map(|i| format!("Edited src/module_{}.rs: added validation for input {}", i, i)).collect()
+    }
+
+    #[test]
+    fn test_differential_request_covers_only_new_lines() {
+        let mut diff = DifferentialSummary::new();
+        let buffer = lines(0..3);
+
+        let first = diff.request(&buffer).unwrap();
+        assert!(first.prior_summary.is_none());
+        assert_eq!(first.new_output.lines().count(), 3);
+        assert!(first.user_prompt("fix it").contains("Raw response:"));
+        diff.apply(&first, "Added validation to three modules.".into());
+        assert!(diff.request(&buffer).is_none());
+
+        let buffer = lines(0..5);
+        assert_eq!(diff.pending_lines(&buffer), 2);
+        let second = diff.request(&buffer).unwrap();
+        assert_eq!(second.prior_summary.as_deref(), Some("Added validation to three modules."));
+        assert_eq!(second.new_output, lines(3..5).join("\n"));
+        assert!(second.user_prompt("fix it").contains("New output since then:"));
+
+        // A replaced, shorter buffer starts over
+        let restart = diff.request(&lines(0..1)).unwrap();
+        assert!(restart.prior_summary.is_none());
+    }
+
+    #[test]
+    fn test_differential_stats_for_long_session() {
+        // 20 updates of 50 new lines each, as in a long-running task
+        let mut diff = DifferentialSummary::new();
+        let mut per_update = Vec::new();
+        for step in 1..=20 {
+            let buffer = lines(0..step * 50);
+            let request = diff.request(&buffer).unwrap();
+            let before = diff.stats().tokens_sent;
+            diff.apply(&request, "Added input validation across the service modules.".into());
+            per_update.push(diff.stats().tokens_sent - before);
+        }
+
+        let stats = diff.stats();
+        assert_eq!(stats.updates, 20);
+        // Tokens per update stay flat while the buffer grows twentyfold
+        assert!(per_update[19] < per_update[0] * 2);
+        assert!(stats.tokens_full > stats.tokens_sent * 8);
+        assert!(stats.saved_ratio() > 0.85);
+
+        diff.reset();
+        assert!(diff.summary().is_none());
+        assert_eq!(diff.stats(), stats);
+    }
+
     #[test]
     fn test_get_model_default() {
         // Clear env var for test
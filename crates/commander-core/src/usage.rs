@@ -115,6 +115,13 @@ impl UsageTracker {
         self.save_state()
     }
 
+    /// Records made at or after `since`, oldest first.
+    pub fn records_since(&self, since: DateTime<Utc>) -> Vec<&UsageRecord> {
+        let mut records: Vec<&UsageRecord> = self.records.iter().filter(|r| r.timestamp >= since).collect();
+        records.sort_by_key(|r| r.timestamp);
+        records
+    }
+
     /// Compute aggregated statistics.
     ///
     /// - `session_id`: when provided, restricts the session totals to records with
@@ -320,6 +327,19 @@ mod tests {
         assert_eq!(stats.week_run_count, 2);
     }
 
+    #[test]
+    fn test_records_since() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = UsageTracker::new(dir.path().to_path_buf());
+        tracker.record_usage(make_record("s1", 1.0, 1)).unwrap();
+        tracker.record_usage(make_record("s1", 2.0, 48)).unwrap();
+        tracker.record_usage(make_record("s1", 4.0, 200)).unwrap();
+
+        let records = tracker.records_since(Utc::now() - Duration::days(7));
+        let costs: Vec<f64> = records.iter().map(|r| r.cost_usd).collect();
+        assert_eq!(costs, vec![2.0, 1.0]);
+    }
+
     #[test]
     fn test_session_filter() {
         let dir = TempDir::new().unwrap();
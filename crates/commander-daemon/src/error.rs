@@ -47,3 +47,14 @@ pub enum DaemonError {
     #[error("Orchestrator error: {0}")]
     Orchestrator(#[from] commander_orchestrator::OrchestratorError),
 }
+
+impl From<DaemonError> for commander_core::CommanderError {
+    fn from(err: DaemonError) -> Self {
+        use commander_core::CommanderError;
+        match err {
+            DaemonError::Configuration(_) => CommanderError::Config(err.to_string()),
+            DaemonError::Io(e) => e.into(),
+            _ => CommanderError::Runtime(err.to_string()),
+        }
+    }
+}
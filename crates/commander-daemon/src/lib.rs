@@ -29,11 +29,13 @@
 pub mod error;
 pub mod health;
 pub mod idle_tracker;
+pub mod logs;
 pub mod message_poller;
 pub mod monitoring;
 pub mod pairing;
 pub mod service;
 pub mod sessions;
+pub mod supervisor;
 pub mod ipc;
 
 // Re-export main types
@@ -43,3 +45,5 @@ pub use service::DaemonService;
 pub use sessions::SessionManager;
 pub use monitoring::MemoryMonitor;
 pub use pairing::PairingManager;
+pub use logs::{daemon_log_path, RotatingLog};
+pub use supervisor::{Component, ComponentState, ComponentStatus, Supervisor, SupervisorConfig};
@@ -0,0 +1,131 @@
+//! Size-based log rotation for the daemon log.
+//!
+//! The daemon writes to `~/.ai-commander/logs/daemon.log`. When the file
+//! would grow past `max_bytes` it is renamed to `daemon.log.1` (shifting
+//! older files up to `daemon.log.<keep>`) and a fresh file is started.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Daemon log file name (in the logs directory).
+pub const DAEMON_LOG_FILE: &str = "daemon.log";
+
+/// Rotate once the log reaches this size.
+pub const DEFAULT_MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated files kept alongside the active log.
+pub const DEFAULT_KEEP_LOGS: usize = 5;
+
+/// Path of the daemon log.
+pub fn daemon_log_path() -> PathBuf {
+    commander_core::config::logs_dir().join(DAEMON_LOG_FILE)
+}
+
+/// Append-only log file that rotates by size.
+#[derive(Debug)]
+pub struct RotatingLog {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLog {
+    /// Open (or create) the log at `path`, creating parent directories.
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, max_bytes, keep, file, size })
+    }
+
+    /// Open the daemon log with the default limits.
+    pub fn daemon() -> io::Result<Self> {
+        Self::open(daemon_log_path(), DEFAULT_MAX_LOG_BYTES, DEFAULT_KEEP_LOGS)
+    }
+
+    /// Path of the active log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Shift rotated files up by one and start a new active file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.keep == 0 {
+            self.file = File::create(&self.path)?;
+            self.size = 0;
+            return Ok(());
+        }
+
+        fs::remove_file(self.rotated_path(self.keep)).ok();
+        for index in (1..self.keep).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(buf)?;
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_and_keeps_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join(DAEMON_LOG_FILE);
+        let mut log = RotatingLog::open(&path, 10, 2).unwrap();
+
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            log.write_all(line.as_bytes()).unwrap();
+        }
+        log.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(fs::read_to_string(dir.path().join("logs/daemon.log.1")).unwrap(), "third\n");
+        assert_eq!(fs::read_to_string(dir.path().join("logs/daemon.log.2")).unwrap(), "second\n");
+        assert!(!dir.path().join("logs/daemon.log.3").exists());
+    }
+
+    #[test]
+    fn test_reopen_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DAEMON_LOG_FILE);
+        RotatingLog::open(&path, 1024, 1).unwrap().write_all(b"a\n").unwrap();
+        RotatingLog::open(&path, 1024, 1).unwrap().write_all(b"b\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+    }
+}
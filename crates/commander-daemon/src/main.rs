@@ -7,7 +7,11 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
+use commander_core::SessionLogLayer;
 use tracing::info;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
 
 use commander_daemon::{DaemonService, Result};
 
@@ -58,8 +62,17 @@ async fn main() -> Result<()> {
 
     // Initialize logging
     let log_level = if cli.verbose { "debug" } else { "info" };
-    tracing_subscriber::fmt()
-        .with_env_filter(format!("commander_daemon={},commander={}", log_level, log_level))
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer().with_filter(EnvFilter::new(format!(
+                "commander_daemon={},commander={}",
+                log_level, log_level
+            ))),
+        )
+        .with(
+            SessionLogLayer::new(commander_core::config::logs_dir(), "daemon")
+                .with_filter(LevelFilter::INFO),
+        )
         .init();
 
     // Load environment configuration
@@ -2,9 +2,10 @@
 //!
 //! This module provides the main daemon service that coordinates all
 //! subsystems including session management, IPC server, memory monitoring,
-//! and pairing code management.
+//! and pairing code management. Long-running components registered with
+//! [`DaemonService::with_component`] are run by a [`Supervisor`].
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -20,6 +21,7 @@ use crate::ipc::{IpcConfig, IpcServer, protocol::{HealthStatusResponse, MemorySt
 use crate::monitoring::MemoryUsage;
 use crate::pairing::{PairingManager, PairingEntry};
 use crate::sessions::SessionManager;
+use crate::supervisor::{Component, ComponentStatus, Supervisor, SupervisorConfig, SupervisorSnapshot, STATUS_FILE};
 
 /// Daemon service configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +34,9 @@ pub struct DaemonConfig {
     pub idle_threshold_secs: u64,
     /// Log level
     pub log_level: String,
+    /// Restart policy for supervised components
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
 }
 
 impl Default for DaemonConfig {
@@ -41,6 +46,7 @@ impl Default for DaemonConfig {
             cleanup_interval_secs: 300, // 5 minutes
             idle_threshold_secs: 3600,  // 1 hour
             log_level: "info".to_string(),
+            supervisor: SupervisorConfig::default(),
         }
     }
 }
@@ -62,6 +68,9 @@ pub struct DaemonStatus {
     pub config: DaemonConfig,
     /// Version information
     pub version: String,
+    /// Supervised components (runtime, API, Telegram bot, orchestrator)
+    #[serde(default)]
+    pub components: Vec<ComponentStatus>,
 }
 
 /// Service handle for IPC operations (to avoid self-referencing).
@@ -224,6 +233,8 @@ pub struct DaemonService {
     idle_monitor_shutdown_tx: Option<tokio::sync::watch::Sender<bool>>,
     /// Shutdown signal
     shutdown_tx: Option<tokio::sync::broadcast::Sender<()>>,
+    /// Supervisor for hosted components
+    supervisor: Supervisor,
 }
 
 impl DaemonService {
@@ -250,6 +261,9 @@ impl DaemonService {
             PairingManager::new()?
         ));
 
+        let supervisor = Supervisor::new(config.supervisor.clone())
+            .with_status_file(daemon_status_file());
+
         Ok(Self {
             config,
             session_manager,
@@ -260,9 +274,21 @@ impl DaemonService {
             idle_monitor_handle: None,
             idle_monitor_shutdown_tx: None,
             shutdown_tx: None,
+            supervisor,
         })
     }
 
+    /// Host a long-running component, restarted on failure by the supervisor.
+    pub fn with_component(mut self, component: Component) -> Self {
+        self.supervisor.add(component);
+        self
+    }
+
+    /// Current status of the supervised components.
+    pub fn component_statuses(&self) -> Vec<ComponentStatus> {
+        self.supervisor.statuses()
+    }
+
     /// Run the daemon service (foreground mode).
     pub async fn run(mut self) -> Result<()> {
         info!("Starting daemon service in foreground mode");
@@ -295,6 +321,9 @@ impl DaemonService {
         // Start idle monitor for MPM sessions
         self.start_idle_monitor();
 
+        // Start supervised components
+        self.supervisor.start();
+
         // Setup signal handling
         let (shutdown_tx, mut shutdown_rx) = tokio::sync::broadcast::channel(1);
         self.shutdown_tx = Some(shutdown_tx);
@@ -349,15 +378,26 @@ impl DaemonService {
         let binary = find_daemon_binary()
             .ok_or_else(|| DaemonError::StartFailed("commander-daemon binary not found".to_string()))?;
 
+        Self::spawn_background(&binary, &["start", "--foreground"]).await
+    }
+
+    /// Spawn `program args...` detached as the background daemon and wait
+    /// for it to write its PID file.
+    ///
+    /// `args` must make the program run the daemon in foreground mode.
+    pub async fn spawn_background(program: &Path, args: &[&str]) -> Result<()> {
+        if let Some(pid) = Self::get_running_pid().await {
+            return Err(DaemonError::AlreadyRunning(pid));
+        }
+
         // Spawn the daemon process in foreground mode (which now creates PID file)
         let current_dir = std::env::current_dir()
             .map_err(|e| DaemonError::StartFailed(format!("Failed to get current directory: {}", e)))?;
 
         // Use nohup for reliable background process spawning (matching manual approach)
         let child = std::process::Command::new("nohup")
-            .arg(&binary)
-            .arg("start")
-            .arg("--foreground")
+            .arg(program)
+            .args(args)
             .current_dir(&current_dir)
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
@@ -488,6 +528,7 @@ impl DaemonService {
                 active_sessions: 0,
                 config,
                 version: env!("CARGO_PKG_VERSION").to_string(),
+                components: Vec::new(),
             });
         }
 
@@ -503,17 +544,21 @@ impl DaemonService {
             std::fs::remove_file(&pid_file).ok();
         }
 
-        // TODO: In real implementation, we could query the daemon via IPC
-        // to get accurate session counts and other runtime information
+        // Start time and components come from the supervisor's snapshot;
+        // session counts would need to be queried via IPC
+        let snapshot = SupervisorSnapshot::load(&daemon_status_file())
+            .filter(|snapshot| running && snapshot.pid == pid);
+        let started_at = snapshot.as_ref().map(|s| s.started_at);
 
         Ok(DaemonStatus {
             running,
             pid: if running { Some(pid) } else { None },
-            started_at: None, // Would need to be persisted or queried
-            uptime_seconds: None, // Would need to be calculated
+            started_at,
+            uptime_seconds: started_at.map(|t| (Utc::now() - t).num_seconds().max(0) as u64),
             active_sessions: 0, // Would need to be queried via IPC
             config,
             version: env!("CARGO_PKG_VERSION").to_string(),
+            components: snapshot.map(|s| s.components).unwrap_or_default(),
         })
     }
 
@@ -646,6 +691,9 @@ impl DaemonService {
     pub async fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down daemon service");
 
+        // Stop supervised components first so they can finish in-flight work
+        self.supervisor.shutdown().await;
+
         // Stop IPC server
         if let Some(server) = &mut self.ipc_server {
             server.stop().await?;
@@ -741,11 +789,12 @@ impl DaemonService {
     /// Start the idle monitor task for MPM sessions.
     fn start_idle_monitor(&mut self) {
         use commander_persistence::StateStore;
+        use commander_core::CommandSender;
         use commander_tmux::TmuxOrchestrator;
         use crate::idle_tracker::run_idle_monitor;
 
         let tmux = match TmuxOrchestrator::new() {
-            Ok(t) => Arc::new(t),
+            Ok(t) => Arc::new(t.with_sender(CommandSender::Daemon)),
             Err(e) => {
                 warn!(error = %e, "idle_monitor: tmux not available, skipping idle monitor");
                 return;
@@ -769,6 +818,11 @@ fn daemon_pid_file() -> PathBuf {
     commander_core::config::runtime_state_dir().join("daemon.pid")
 }
 
+/// Get the supervisor status snapshot path.
+fn daemon_status_file() -> PathBuf {
+    commander_core::config::runtime_state_dir().join(STATUS_FILE)
+}
+
 /// Check if a process is running.
 fn is_process_running(pid: u32) -> bool {
     #[cfg(unix)]
@@ -0,0 +1,453 @@
+//! Supervision of the long-running components hosted by the daemon.
+//!
+//! Each [`Component`] is a factory for a future that runs until the shared
+//! shutdown signal fires. The [`Supervisor`] runs every component in its own
+//! task, restarts it with exponential backoff when it fails, exits or
+//! panics, and gives up after `max_restarts` consecutive failures.
+//!
+//! Component status is written to a snapshot file so `daemon status` can
+//! report it from another process.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Supervisor status snapshot file name (in the runtime state directory).
+pub const STATUS_FILE: &str = "daemon-status.json";
+
+/// Restart and shutdown policy for supervised components.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorConfig {
+    /// Consecutive restarts before a component is marked failed.
+    pub max_restarts: u32,
+    /// Delay before the first restart (doubles on each retry).
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the restart delay.
+    pub max_backoff_ms: u64,
+    /// A component that ran this long before failing has its restart count reset.
+    pub healthy_after_secs: u64,
+    /// How long to wait for components to stop before aborting them.
+    pub shutdown_timeout_secs: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 60_000,
+            healthy_after_secs: 300,
+            shutdown_timeout_secs: 10,
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Delay before restart number `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        Duration::from_millis(self.initial_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+}
+
+/// Lifecycle state of a supervised component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentState {
+    /// Registered but not started yet.
+    Pending,
+    /// Running.
+    Running,
+    /// Waiting to be restarted after a failure.
+    Backoff,
+    /// Gave up after too many consecutive failures.
+    Failed,
+    /// Stopped by shutdown.
+    Stopped,
+}
+
+/// Status of one supervised component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentStatus {
+    /// Component name.
+    pub name: String,
+    /// Current state.
+    pub state: ComponentState,
+    /// Consecutive restarts since the component was last healthy.
+    pub restarts: u32,
+    /// Most recent failure, if any.
+    pub last_error: Option<String>,
+    /// When the component entered its current state.
+    pub since: DateTime<Utc>,
+}
+
+/// Snapshot of the supervisor written to [`STATUS_FILE`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorSnapshot {
+    /// Daemon process ID.
+    pub pid: u32,
+    /// When the supervisor started.
+    pub started_at: DateTime<Utc>,
+    /// Component statuses, in registration order.
+    pub components: Vec<ComponentStatus>,
+}
+
+impl SupervisorSnapshot {
+    /// Read a snapshot written by a running daemon.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+type ComponentFactory =
+    Arc<dyn Fn(watch::Receiver<bool>) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// A named, restartable unit of work run by the supervisor.
+#[derive(Clone)]
+pub struct Component {
+    name: String,
+    factory: ComponentFactory,
+}
+
+impl Component {
+    /// Create a component from a factory.
+    ///
+    /// The factory is called on every (re)start with the shutdown receiver;
+    /// the returned future should resolve once the receiver reports `true`.
+    /// Resolving earlier, with or without an error, triggers a restart.
+    pub fn new<F, Fut>(name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(watch::Receiver<bool>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            factory: Arc::new(move |shutdown| Box::pin(factory(shutdown))),
+        }
+    }
+
+    /// Component name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Debug for Component {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Component").field("name", &self.name).finish()
+    }
+}
+
+/// Shared status table plus the snapshot file it is mirrored to.
+#[derive(Clone)]
+struct StatusBoard {
+    statuses: Arc<RwLock<Vec<ComponentStatus>>>,
+    started_at: DateTime<Utc>,
+    status_file: Option<PathBuf>,
+}
+
+impl StatusBoard {
+    fn update(&self, index: usize, state: ComponentState, restarts: u32, last_error: Option<String>) {
+        let snapshot = {
+            let mut statuses = self.statuses.write().unwrap_or_else(|e| e.into_inner());
+            let Some(status) = statuses.get_mut(index) else {
+                return;
+            };
+            status.state = state;
+            status.restarts = restarts;
+            if last_error.is_some() {
+                status.last_error = last_error;
+            }
+            status.since = Utc::now();
+            SupervisorSnapshot {
+                pid: std::process::id(),
+                started_at: self.started_at,
+                components: statuses.clone(),
+            }
+        };
+        self.write(&snapshot);
+    }
+
+    fn write(&self, snapshot: &SupervisorSnapshot) {
+        let Some(path) = &self.status_file else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(snapshot)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(path, json));
+        if let Err(e) = result {
+            warn!(path = %path.display(), error = %e, "Failed to write supervisor status");
+        }
+    }
+}
+
+/// Aborts the wrapped task when dropped, so aborting a supervision loop
+/// also stops the component it is running.
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Runs components, restarting them on failure, until shutdown.
+pub struct Supervisor {
+    config: SupervisorConfig,
+    components: Vec<Component>,
+    board: StatusBoard,
+    shutdown_tx: watch::Sender<bool>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Supervisor {
+    /// Create a supervisor with no components.
+    pub fn new(config: SupervisorConfig) -> Self {
+        let (shutdown_tx, _) = watch::channel(false);
+        Self {
+            config,
+            components: Vec::new(),
+            board: StatusBoard {
+                statuses: Arc::new(RwLock::new(Vec::new())),
+                started_at: Utc::now(),
+                status_file: None,
+            },
+            shutdown_tx,
+            handles: Vec::new(),
+        }
+    }
+
+    /// Mirror component status to `path` while running.
+    pub fn with_status_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.board.status_file = Some(path.into());
+        self
+    }
+
+    /// Register a component. Components added after `start` are not run.
+    pub fn add(&mut self, component: Component) {
+        self.board
+            .statuses
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(ComponentStatus {
+                name: component.name.clone(),
+                state: ComponentState::Pending,
+                restarts: 0,
+                last_error: None,
+                since: Utc::now(),
+            });
+        self.components.push(component);
+    }
+
+    /// Whether any components are registered.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Start every registered component.
+    pub fn start(&mut self) {
+        self.board.started_at = Utc::now();
+        for (index, component) in self.components.iter().enumerate() {
+            info!(component = %component.name, "Starting component");
+            let handle = tokio::spawn(supervise(
+                component.clone(),
+                index,
+                self.config.clone(),
+                self.board.clone(),
+                self.shutdown_tx.subscribe(),
+            ));
+            self.handles.push(handle);
+        }
+    }
+
+    /// Current status of every component.
+    pub fn statuses(&self) -> Vec<ComponentStatus> {
+        self.board.statuses.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Signal shutdown and wait for components to stop.
+    ///
+    /// Components still running after `shutdown_timeout_secs` are aborted.
+    pub async fn shutdown(&mut self) {
+        let _ = self.shutdown_tx.send(true);
+        let timeout = Duration::from_secs(self.config.shutdown_timeout_secs);
+        let handles: Vec<JoinHandle<()>> = self.handles.drain(..).collect();
+        let aborts: Vec<_> = handles.iter().map(JoinHandle::abort_handle).collect();
+
+        if tokio::time::timeout(timeout, futures::future::join_all(handles)).await.is_err() {
+            warn!(timeout_secs = timeout.as_secs(), "Components did not stop in time, aborting");
+            for abort in aborts {
+                abort.abort();
+            }
+        }
+
+        if let Some(path) = &self.board.status_file {
+            std::fs::remove_file(path).ok();
+        }
+        info!("All components stopped");
+    }
+}
+
+/// Run one component until shutdown, restarting it on failure.
+async fn supervise(
+    component: Component,
+    index: usize,
+    config: SupervisorConfig,
+    board: StatusBoard,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let healthy_after = Duration::from_secs(config.healthy_after_secs);
+    let mut restarts = 0u32;
+
+    loop {
+        board.update(index, ComponentState::Running, restarts, None);
+        let started = Instant::now();
+
+        let mut task = AbortOnDrop(tokio::spawn((component.factory)(shutdown_rx.clone())));
+        let result = (&mut task.0).await;
+
+        if *shutdown_rx.borrow() {
+            board.update(index, ComponentState::Stopped, restarts, None);
+            info!(component = %component.name, "Component stopped");
+            return;
+        }
+
+        let failure = match result {
+            Ok(Ok(())) => "exited unexpectedly".to_string(),
+            Ok(Err(e)) => e,
+            Err(e) if e.is_panic() => "panicked".to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        if started.elapsed() >= healthy_after {
+            restarts = 0;
+        }
+        if restarts >= config.max_restarts {
+            error!(component = %component.name, error = %failure, restarts, "Component failed, giving up");
+            board.update(index, ComponentState::Failed, restarts, Some(failure));
+            return;
+        }
+
+        restarts += 1;
+        let delay = config.backoff(restarts);
+        warn!(
+            component = %component.name,
+            error = %failure,
+            attempt = restarts,
+            delay_ms = delay.as_millis() as u64,
+            "Component failed, restarting"
+        );
+        board.update(index, ComponentState::Backoff, restarts, Some(failure));
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown_rx.changed() => {
+                board.update(index, ComponentState::Stopped, restarts, None);
+                return;
+            }
+        }
+    }
+}
+
+/// Resolve once the shutdown receiver reports `true` (or its sender is gone).
+pub async fn wait_for_shutdown(mut shutdown: watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if shutdown.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_config() -> SupervisorConfig {
+        SupervisorConfig {
+            max_restarts: 2,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            healthy_after_secs: 300,
+            shutdown_timeout_secs: 1,
+        }
+    }
+
+    async fn wait_for_state(supervisor: &Supervisor, state: ComponentState) -> ComponentStatus {
+        for _ in 0..200 {
+            let status = supervisor.statuses().remove(0);
+            if status.state == state {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("component never reached {:?}", state);
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let config = SupervisorConfig::default();
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(20), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_restarts_failed_component_then_stops() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&runs);
+        let mut supervisor = Supervisor::new(fast_config());
+        supervisor.add(Component::new("flaky", move |shutdown| {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    return Err(format!("boom {}", attempt));
+                }
+                wait_for_shutdown(shutdown).await;
+                Ok(())
+            }
+        }));
+
+        supervisor.start();
+
+        // Wait until the third run is up
+        while runs.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let status = wait_for_state(&supervisor, ComponentState::Running).await;
+        assert_eq!(status.restarts, 2);
+
+        supervisor.shutdown().await;
+        let status = supervisor.statuses().remove(0);
+        assert_eq!(status.state, ComponentState::Stopped);
+        assert_eq!(status.last_error.as_deref(), Some("boom 1"));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let status_path = dir.path().join(STATUS_FILE);
+        let mut supervisor = Supervisor::new(fast_config()).with_status_file(&status_path);
+        supervisor.add(Component::new("broken", |_| async { Err("no token".to_string()) }));
+
+        supervisor.start();
+        let status = wait_for_state(&supervisor, ComponentState::Failed).await;
+        assert_eq!(status.restarts, 2);
+
+        let snapshot = SupervisorSnapshot::load(&status_path).unwrap();
+        assert_eq!(snapshot.components[0].state, ComponentState::Failed);
+
+        supervisor.shutdown().await;
+        assert!(!status_path.exists());
+    }
+}
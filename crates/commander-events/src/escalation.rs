@@ -0,0 +1,321 @@
+//! Escalation of unacknowledged blocking events.
+//!
+//! A blocking event (error, decision or approval needed) that is still
+//! pending `after` its creation is escalated to the first channel of the
+//! policy. While it stays pending it is escalated again every
+//! `repeat_every`, cycling through the channels (TUI modal, then Telegram
+//! ping, then the TUI again, ...). Acknowledging or resolving the event
+//! stops the cycle.
+//!
+//! The [`EscalationState`] is persisted so frontends in other processes can
+//! see which events are escalated to them.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use commander_models::{Event, EventType};
+
+/// Length of the short event ID shown to users (`evt-` + 8 hex digits).
+const SHORT_ID_LEN: usize = 12;
+
+/// Where an escalation is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationChannel {
+    /// Modal in the TUI.
+    Tui,
+    /// Ping through the Telegram bot.
+    Telegram,
+}
+
+impl std::fmt::Display for EscalationChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EscalationChannel::Tui => write!(f, "tui"),
+            EscalationChannel::Telegram => write!(f, "telegram"),
+        }
+    }
+}
+
+/// When and where blocking events are escalated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscalationPolicy {
+    /// Time a blocking event may stay pending before the first escalation.
+    pub after: Duration,
+    /// Time between further escalations.
+    pub repeat_every: Duration,
+    /// Channels escalated to, in order; cycled while the event is pending.
+    pub channels: Vec<EscalationChannel>,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            after: Duration::from_secs(5 * 60),
+            repeat_every: Duration::from_secs(5 * 60),
+            channels: vec![EscalationChannel::Tui, EscalationChannel::Telegram],
+        }
+    }
+}
+
+impl EscalationPolicy {
+    /// Sets the delay before the first escalation.
+    pub fn with_after(mut self, after: Duration) -> Self {
+        self.after = after;
+        self
+    }
+
+    /// Sets the delay between further escalations.
+    pub fn with_repeat_every(mut self, repeat_every: Duration) -> Self {
+        self.repeat_every = repeat_every;
+        self
+    }
+
+    /// Sets the channel cycle.
+    pub fn with_channels(mut self, channels: Vec<EscalationChannel>) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    fn channel_for(&self, attempt: u32) -> Option<EscalationChannel> {
+        if self.channels.is_empty() {
+            return None;
+        }
+        let index = (attempt.saturating_sub(1) as usize) % self.channels.len();
+        Some(self.channels[index])
+    }
+}
+
+/// One escalation of a blocking event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Escalation {
+    /// Full event ID.
+    pub event_id: String,
+    /// Project (or session) the event belongs to.
+    pub project_id: String,
+    /// Event title.
+    pub title: String,
+    /// Event type.
+    pub event_type: EventType,
+    /// Channel this escalation goes to.
+    pub channel: EscalationChannel,
+    /// 1 for the first escalation, incremented on every repeat.
+    pub attempt: u32,
+    /// When this escalation happened.
+    pub escalated_at: DateTime<Utc>,
+}
+
+impl Escalation {
+    /// Short event ID accepted by `/ack`.
+    pub fn short_id(&self) -> &str {
+        short_event_id(&self.event_id)
+    }
+
+    /// Human-readable kind of the blocking event.
+    pub fn label(&self) -> &'static str {
+        match self.event_type {
+            EventType::Error => "Error",
+            EventType::DecisionNeeded => "Decision needed",
+            EventType::Approval => "Approval needed",
+            _ => "Event",
+        }
+    }
+
+    /// One-line description for notifications.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} unacknowledged in {}: {} (/ack {})",
+            self.label(),
+            self.project_id,
+            self.title,
+            self.short_id()
+        )
+    }
+}
+
+/// Latest escalation of every escalated event, keyed by event ID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EscalationState {
+    pub escalations: BTreeMap<String, Escalation>,
+}
+
+impl EscalationState {
+    /// Load state from `path`; missing or unreadable files yield empty state.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save state to `path`, creating parent directories.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Current escalations delivered to `channel`.
+    pub fn for_channel(&self, channel: EscalationChannel) -> impl Iterator<Item = &Escalation> {
+        self.escalations.values().filter(move |e| e.channel == channel)
+    }
+}
+
+/// Decides which blocking events are due for escalation.
+#[derive(Debug, Clone, Default)]
+pub struct Escalator {
+    policy: EscalationPolicy,
+    state: EscalationState,
+}
+
+impl Escalator {
+    /// Create an escalator with no prior state.
+    pub fn new(policy: EscalationPolicy) -> Self {
+        Self::with_state(policy, EscalationState::default())
+    }
+
+    /// Create an escalator that continues from saved state.
+    pub fn with_state(policy: EscalationPolicy, state: EscalationState) -> Self {
+        Self { policy, state }
+    }
+
+    /// Current escalation state.
+    pub fn state(&self) -> &EscalationState {
+        &self.state
+    }
+
+    /// Escalate the blocking `events` that are due at `now`.
+    ///
+    /// Events that are no longer blocking are forgotten. Returns the new
+    /// escalations, which are also recorded in the state.
+    pub fn tick(&mut self, events: &[Event], now: DateTime<Utc>) -> Vec<Escalation> {
+        let blocking: Vec<&Event> = events.iter().filter(|e| e.is_blocking()).collect();
+        self.state
+            .escalations
+            .retain(|id, _| blocking.iter().any(|e| e.id.as_str() == id));
+
+        let mut due = Vec::new();
+        for event in blocking {
+            let previous = self.state.escalations.get(event.id.as_str());
+            let (due_at, attempt) = match previous {
+                Some(previous) => (
+                    previous.escalated_at + to_chrono(self.policy.repeat_every),
+                    previous.attempt + 1,
+                ),
+                None => (event.created_at + to_chrono(self.policy.after), 1),
+            };
+            if now < due_at {
+                continue;
+            }
+            let Some(channel) = self.policy.channel_for(attempt) else { continue };
+
+            let escalation = Escalation {
+                event_id: event.id.as_str().to_string(),
+                project_id: event.project_id.as_str().to_string(),
+                title: event.title.clone(),
+                event_type: event.event_type,
+                channel,
+                attempt,
+                escalated_at: now,
+            };
+            self.state
+                .escalations
+                .insert(escalation.event_id.clone(), escalation.clone());
+            due.push(escalation);
+        }
+        due
+    }
+}
+
+/// Short form of an event ID (`evt-` plus the first 8 hex digits).
+pub fn short_event_id(id: &str) -> &str {
+    id.get(..SHORT_ID_LEN).unwrap_or(id)
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_models::EventStatus;
+
+    fn minutes(n: i64) -> chrono::Duration {
+        chrono::Duration::minutes(n)
+    }
+
+    #[test]
+    fn test_escalates_through_channels_and_repeats() {
+        let mut escalator = Escalator::new(EscalationPolicy::default());
+        let event = Event::new("proj-1", EventType::DecisionNeeded, "Pick a database");
+        let start = event.created_at;
+        let events = vec![event];
+
+        assert!(escalator.tick(&events, start + minutes(4)).is_empty());
+
+        let first = escalator.tick(&events, start + minutes(5));
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].channel, EscalationChannel::Tui);
+        assert_eq!(first[0].attempt, 1);
+
+        assert!(escalator.tick(&events, start + minutes(7)).is_empty());
+
+        let second = escalator.tick(&events, start + minutes(10));
+        assert_eq!(second[0].channel, EscalationChannel::Telegram);
+
+        let third = escalator.tick(&events, start + minutes(15));
+        assert_eq!(third[0].channel, EscalationChannel::Tui);
+        assert_eq!(third[0].attempt, 3);
+    }
+
+    #[test]
+    fn test_acknowledged_and_non_blocking_events_are_ignored() {
+        let mut escalator = Escalator::new(EscalationPolicy::default());
+        let mut event = Event::new("proj-1", EventType::Error, "Build failed");
+        let status = Event::new("proj-1", EventType::Status, "Working");
+        let start = event.created_at;
+
+        let due = escalator.tick(&[event.clone(), status.clone()], start + minutes(6));
+        assert_eq!(due.len(), 1);
+        assert_eq!(escalator.state().escalations.len(), 1);
+
+        event.status = EventStatus::Acknowledged;
+        assert!(escalator.tick(&[event, status], start + minutes(20)).is_empty());
+        assert!(escalator.state().escalations.is_empty());
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("escalations.json");
+        let mut escalator = Escalator::new(EscalationPolicy::default());
+        let event = Event::new("proj-1", EventType::Approval, "Run migration?");
+        escalator.tick(std::slice::from_ref(&event), event.created_at + minutes(5));
+
+        escalator.state().save(&path).unwrap();
+        let loaded = EscalationState::load(&path);
+        assert_eq!(&loaded, escalator.state());
+        assert_eq!(loaded.for_channel(EscalationChannel::Tui).count(), 1);
+        assert_eq!(loaded.for_channel(EscalationChannel::Telegram).count(), 0);
+        assert_eq!(EscalationState::load(&dir.path().join("missing.json")), EscalationState::default());
+    }
+
+    #[test]
+    fn test_summary_uses_short_id() {
+        let event = Event::new("proj-1", EventType::Error, "Tests failing");
+        let mut escalator = Escalator::new(EscalationPolicy::default());
+        let due = escalator.tick(std::slice::from_ref(&event), event.created_at + minutes(5));
+        let summary = due[0].summary();
+        assert!(summary.starts_with("Error unacknowledged in proj-1: Tests failing"));
+        assert!(summary.ends_with(&format!("(/ack {})", &event.id.as_str()[..12])));
+    }
+}
@@ -1,6 +1,8 @@
 //! Event filtering for queries.
 
+use chrono::{DateTime, Utc};
 use commander_models::{Event, EventPriority, EventStatus, EventType, ProjectId};
+use commander_persistence::EventQuery;
 
 /// Filter criteria for querying events.
 #[derive(Debug, Clone, Default)]
@@ -13,6 +15,10 @@ pub struct EventFilter {
     pub status: Option<EventStatus>,
     /// Filter by minimum priority.
     pub priority_min: Option<EventPriority>,
+    /// Only events created at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only events created before this time.
+    pub until: Option<DateTime<Utc>>,
 }
 
 impl EventFilter {
@@ -45,6 +51,32 @@ impl EventFilter {
         self
     }
 
+    /// Sets the earliest creation time.
+    pub fn with_since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Sets the creation time events must precede.
+    pub fn with_until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// The store query for one page of events matching this filter.
+    pub fn to_query(&self, offset: usize, limit: Option<usize>) -> EventQuery {
+        EventQuery {
+            project_id: self.project_id.clone(),
+            event_type: self.event_type,
+            status: self.status,
+            priority_min: self.priority_min,
+            since: self.since,
+            until: self.until,
+            offset,
+            limit,
+        }
+    }
+
     /// Returns true if the event matches this filter.
     pub fn matches(&self, event: &Event) -> bool {
         if let Some(ref project_id) = self.project_id {
@@ -71,6 +103,14 @@ impl EventFilter {
             }
         }
 
+        if self.since.is_some_and(|since| event.created_at < since) {
+            return false;
+        }
+
+        if self.until.is_some_and(|until| event.created_at >= until) {
+            return false;
+        }
+
         true
     }
 }
@@ -156,4 +196,23 @@ mod tests {
         assert!(!filter.matches(&e2)); // wrong project
         assert!(!filter.matches(&e3)); // wrong type
     }
+
+    #[test]
+    fn test_filter_by_time_range() {
+        let now = Utc::now();
+        let filter = EventFilter::new()
+            .with_since(now - chrono::Duration::hours(2))
+            .with_until(now);
+
+        let mut inside = make_event("proj-1", EventType::Status);
+        inside.created_at = now - chrono::Duration::hours(1);
+        let mut before = make_event("proj-1", EventType::Status);
+        before.created_at = now - chrono::Duration::hours(3);
+        let mut at_until = make_event("proj-1", EventType::Status);
+        at_until.created_at = now;
+
+        assert!(filter.matches(&inside));
+        assert!(!filter.matches(&before));
+        assert!(!filter.matches(&at_until));
+    }
 }
@@ -4,6 +4,7 @@
 //! - Thread-safe storage using `Arc<RwLock<T>>`
 //! - Pub/sub notifications using `mpsc` channels
 //! - Persistence integration with `EventStore`
+//! - Escalation of unacknowledged blocking events (`Escalator`)
 //!
 //! # Example
 //!
@@ -29,9 +30,13 @@
 //! ```
 
 pub mod error;
+pub mod escalation;
 pub mod filter;
 pub mod manager;
 
 pub use error::{EventError, Result};
+pub use escalation::{
+    short_event_id, Escalation, EscalationChannel, EscalationPolicy, EscalationState, Escalator,
+};
 pub use filter::EventFilter;
 pub use manager::EventManager;
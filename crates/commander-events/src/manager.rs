@@ -10,7 +10,7 @@ use std::sync::{Arc, RwLock};
 
 use chrono::Utc;
 use commander_models::{Event, EventId, EventStatus, ProjectId};
-use commander_persistence::EventStore;
+use commander_persistence::{EventPage, EventStore};
 
 use crate::error::{EventError, Result};
 use crate::filter::EventFilter;
@@ -88,6 +88,22 @@ impl EventManager {
         Ok(())
     }
 
+    /// Loads events of every project into the in-memory cache.
+    ///
+    /// Used by frontends that act on events emitted by another process.
+    pub fn load_all(&self) -> Result<()> {
+        let events = self.store.list_all_events()?;
+        let mut cache = self
+            .events
+            .write()
+            .map_err(|e| EventError::LockPoisoned(e.to_string()))?;
+
+        for event in events {
+            cache.insert(event.id.clone(), event);
+        }
+        Ok(())
+    }
+
     /// Subscribes to event notifications.
     ///
     /// Returns a receiver that will receive clones of all emitted events.
@@ -220,6 +236,36 @@ impl EventManager {
         result
     }
 
+    /// Queries the store for one page of events matching `filter`, newest
+    /// first.
+    ///
+    /// Unlike [`list`](Self::list), this reads through the store's index
+    /// rather than the cache, so events that were never loaded are included.
+    pub fn query(&self, filter: &EventFilter, offset: usize, limit: Option<usize>) -> Result<EventPage> {
+        Ok(self.store.query(&filter.to_query(offset, limit))?)
+    }
+
+    /// Finds the cached event whose ID starts with `prefix`.
+    ///
+    /// Lets users type a short ID such as `evt-1a2b3c4d`. Errors if no
+    /// event or more than one event matches.
+    pub fn find_by_prefix(&self, prefix: &str) -> Result<Event> {
+        let events = self
+            .events
+            .read()
+            .map_err(|e| EventError::LockPoisoned(e.to_string()))?;
+
+        let mut matches = events.values().filter(|e| e.id.as_str().starts_with(prefix));
+        match (matches.next(), matches.next()) {
+            (Some(event), None) => Ok(event.clone()),
+            (Some(_), Some(_)) => Err(EventError::InvalidState(format!(
+                "event id '{}' is ambiguous",
+                prefix
+            ))),
+            (None, _) => Err(EventError::NotFound(prefix.to_string())),
+        }
+    }
+
     /// Acknowledges an event (marks it as seen but not resolved).
     ///
     /// # Arguments
@@ -419,6 +465,26 @@ mod tests {
         assert!(events.iter().all(|e| e.project_id.as_str() == "proj-1"));
     }
 
+    #[test]
+    fn test_query_pages_from_store() {
+        let manager = make_manager();
+
+        for i in 0..5 {
+            manager.emit(make_event("proj-1", &format!("E{}", i))).unwrap();
+        }
+        manager.emit(make_event("proj-2", "Other")).unwrap();
+        manager.clear_cache();
+
+        let filter = EventFilter::new().with_project_id("proj-1".into());
+        let page = manager.query(&filter, 0, Some(2)).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.events.len(), 2);
+
+        let rest = manager.query(&filter, 2, None).unwrap();
+        assert_eq!(rest.events.len(), 3);
+        assert!(rest.events.iter().all(|e| e.project_id.as_str() == "proj-1"));
+    }
+
     #[test]
     fn test_list_sorted_by_created_at() {
         let manager = make_manager();
@@ -451,6 +517,24 @@ mod tests {
         assert_eq!(retrieved.status, EventStatus::Acknowledged);
     }
 
+    #[test]
+    fn test_load_all_and_find_by_prefix() {
+        let dir = tempdir().unwrap();
+        let emitter = EventManager::new(EventStore::new(dir.path()));
+        let id = emitter.emit(Event::new("proj-1", EventType::Error, "Boom")).unwrap();
+        emitter.emit(make_event("proj-2", "Other")).unwrap();
+
+        // A second process sees both projects' events
+        let reader = EventManager::new(EventStore::new(dir.path()));
+        reader.load_all().unwrap();
+        assert_eq!(reader.len(), 2);
+
+        let found = reader.find_by_prefix(&id.as_str()[..12]).unwrap();
+        assert_eq!(found.id, id);
+        assert!(matches!(reader.find_by_prefix("evt-"), Err(EventError::InvalidState(_))));
+        assert!(matches!(reader.find_by_prefix("evt-zzz"), Err(EventError::NotFound(_))));
+    }
+
     #[test]
     fn test_acknowledge_resolved_fails() {
         let manager = make_manager();
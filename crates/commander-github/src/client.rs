@@ -0,0 +1,149 @@
+//! Minimal GitHub REST client.
+//!
+//! Covers what the work queue integration needs: listing labelled issues,
+//! commenting on them and opening pull requests.
+
+use reqwest::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::debug;
+
+use crate::error::{GitHubError, Result};
+use crate::issue::Issue;
+use crate::repo::RepoRef;
+
+/// Public GitHub API endpoint.
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Issues requested per page.
+const PAGE_SIZE: usize = 100;
+
+/// A created pull request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    /// Pull request number.
+    pub number: u64,
+    /// Web URL of the pull request.
+    pub html_url: String,
+}
+
+#[derive(Deserialize)]
+struct RepoInfo {
+    default_branch: String,
+}
+
+#[derive(Deserialize)]
+struct ApiMessage {
+    message: String,
+}
+
+/// Client for the GitHub REST API, authenticated with a token.
+#[derive(Debug, Clone)]
+pub struct GitHubClient {
+    http: Client,
+    token: String,
+    base_url: String,
+}
+
+impl GitHubClient {
+    /// Creates a client for api.github.com.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            token: token.into(),
+            base_url: DEFAULT_API_URL.to_string(),
+        }
+    }
+
+    /// Uses another API endpoint (GitHub Enterprise).
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "ai-commander")
+    }
+
+    fn url(&self, repo: &RepoRef, path: &str) -> String {
+        format!("{}/repos/{}/{}", self.base_url, repo, path)
+    }
+
+    async fn parse<T: DeserializeOwned>(response: Response) -> Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let message = response
+                .json::<ApiMessage>()
+                .await
+                .map(|m| m.message)
+                .unwrap_or_else(|_| status.to_string());
+            return Err(GitHubError::Api {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Open issues of `repo` carrying all of `labels`; pull requests are skipped.
+    pub async fn list_issues(&self, repo: &RepoRef, labels: &[String]) -> Result<Vec<Issue>> {
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let mut query = vec![
+                ("state", "open".to_string()),
+                ("per_page", PAGE_SIZE.to_string()),
+                ("page", page.to_string()),
+            ];
+            if !labels.is_empty() {
+                query.push(("labels", labels.join(",")));
+            }
+            let response = self
+                .request(self.http.get(self.url(repo, "issues")))
+                .query(&query)
+                .send()
+                .await?;
+            let batch: Vec<Issue> = Self::parse(response).await?;
+            let done = batch.len() < PAGE_SIZE;
+            debug!(repo = %repo, page, count = batch.len(), "Fetched issues");
+            issues.extend(batch.into_iter().filter(|i| !i.is_pull_request()));
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Posts a comment on issue `number`.
+    pub async fn comment(&self, repo: &RepoRef, number: u64, body: &str) -> Result<()> {
+        let response = self
+            .request(self.http.post(self.url(repo, &format!("issues/{}/comments", number))))
+            .json(&json!({ "body": body }))
+            .send()
+            .await?;
+        Self::parse::<serde_json::Value>(response).await?;
+        Ok(())
+    }
+
+    /// Default branch of `repo`, the base of new pull requests.
+    pub async fn default_branch(&self, repo: &RepoRef) -> Result<String> {
+        let response = self
+            .request(self.http.get(format!("{}/repos/{}", self.base_url, repo)))
+            .send()
+            .await?;
+        Ok(Self::parse::<RepoInfo>(response).await?.default_branch)
+    }
+
+    /// Opens a pull request from `head` into `base`.
+    pub async fn create_pull(&self, repo: &RepoRef, head: &str, base: &str, title: &str, body: &str) -> Result<PullRequest> {
+        let response = self
+            .request(self.http.post(self.url(repo, "pulls")))
+            .json(&json!({ "title": title, "head": head, "base": base, "body": body }))
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+}
@@ -0,0 +1,49 @@
+//! Error types for the GitHub integration.
+
+use commander_work::WorkError;
+use thiserror::Error;
+
+/// Errors that can occur while syncing with GitHub.
+#[derive(Error, Debug)]
+pub enum GitHubError {
+    /// `owner/name` could not be parsed.
+    #[error("invalid repository '{0}', expected owner/name")]
+    InvalidRepo(String),
+
+    /// The request could not be sent or its response read.
+    #[error("GitHub request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// GitHub answered with an error status.
+    #[error("GitHub API error {status}: {message}")]
+    Api {
+        /// HTTP status code.
+        status: u16,
+        /// Message from the response body.
+        message: String,
+    },
+
+    /// A git command in the project directory failed.
+    #[error("git {command} failed: {message}")]
+    Git {
+        /// The git subcommand.
+        command: String,
+        /// Its error output.
+        message: String,
+    },
+
+    /// The branch to publish is the repository's default branch.
+    #[error("{0} is the default branch; check out a feature branch to open a pull request")]
+    DefaultBranch(String),
+
+    /// The work item is not linked to an issue.
+    #[error("work item {0} is not linked to a GitHub issue")]
+    NotLinked(String),
+
+    /// Work queue error.
+    #[error(transparent)]
+    Work(#[from] WorkError),
+}
+
+/// Result type alias for GitHub operations.
+pub type Result<T> = std::result::Result<T, GitHubError>;
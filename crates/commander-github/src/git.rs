@@ -0,0 +1,85 @@
+//! Git operations in a project checkout.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{GitHubError, Result};
+use crate::repo::RepoRef;
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| GitHubError::Git {
+            command: args[0].to_string(),
+            message: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(GitHubError::Git {
+            command: args[0].to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Checks out `branch` in `dir`, creating it from HEAD if it does not exist.
+pub fn checkout_branch(dir: &Path, branch: &str) -> Result<()> {
+    let exists = git(dir, &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)]).is_ok();
+    if exists {
+        git(dir, &["checkout", branch])?;
+    } else {
+        git(dir, &["checkout", "-b", branch])?;
+    }
+    Ok(())
+}
+
+/// Pushes `branch` to `origin`, setting its upstream.
+pub fn push_branch(dir: &Path, branch: &str) -> Result<()> {
+    git(dir, &["push", "--set-upstream", "origin", branch])?;
+    Ok(())
+}
+
+/// The branch checked out in `dir`.
+pub fn current_branch(dir: &Path) -> Result<String> {
+    git(dir, &["branch", "--show-current"])
+}
+
+/// The GitHub repository of the `origin` remote.
+pub fn origin_repo(dir: &Path) -> Result<RepoRef> {
+    git(dir, &["remote", "get-url", "origin"])?.parse()
+}
+
+/// The default branch of `origin` as last fetched, if known locally.
+pub fn origin_default_branch(dir: &Path) -> Option<String> {
+    git(dir, &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .ok()
+        .and_then(|r| r.strip_prefix("origin/").map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_checkout_branch_creates_then_reuses() {
+        let dir = TempDir::new().unwrap();
+        if git(dir.path(), &["init", "--quiet"]).is_err() {
+            return; // git not installed
+        }
+        git(
+            dir.path(),
+            &["-c", "user.name=t", "-c", "user.email=t@t", "commit", "--allow-empty", "--quiet", "-m", "init"],
+        )
+        .unwrap();
+
+        checkout_branch(dir.path(), "issue-1-fix").unwrap();
+        git(dir.path(), &["checkout", "--quiet", "-"]).unwrap();
+        checkout_branch(dir.path(), "issue-1-fix").unwrap();
+        assert_eq!(current_branch(dir.path()).unwrap(), "issue-1-fix");
+        assert!(origin_repo(dir.path()).is_err());
+    }
+}
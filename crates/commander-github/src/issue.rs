@@ -0,0 +1,215 @@
+//! GitHub issues and their link to work items.
+
+use commander_models::{ProjectId, WorkItem, WorkPriority, WorkState};
+use serde::{Deserialize, Serialize};
+
+use crate::repo::RepoRef;
+
+/// Work item metadata key holding the [`IssueLink`].
+pub const METADATA_KEY: &str = "github";
+
+/// Longest title slug used in a branch name.
+const MAX_SLUG_LEN: usize = 40;
+
+/// A label on an issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Label {
+    /// Label name.
+    pub name: String,
+}
+
+/// An issue as returned by the GitHub API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    /// Issue number within the repository.
+    pub number: u64,
+    /// Issue title.
+    pub title: String,
+    /// Issue description.
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Web URL of the issue.
+    pub html_url: String,
+    /// Labels on the issue.
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// Set when the "issue" is a pull request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+impl Issue {
+    /// The issues endpoint also lists pull requests; they carry this field.
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    /// Work priority from labels such as `priority:high`, `urgent` or `P1`;
+    /// the highest wins.
+    pub fn priority(&self) -> WorkPriority {
+        self.labels
+            .iter()
+            .filter_map(|label| {
+                let name = label.name.to_lowercase();
+                match name.trim_start_matches("priority:").trim_start_matches("priority/") {
+                    "critical" | "urgent" | "p0" => Some(WorkPriority::Critical),
+                    "high" | "p1" => Some(WorkPriority::High),
+                    "medium" | "p2" => Some(WorkPriority::Medium),
+                    "low" | "p3" => Some(WorkPriority::Low),
+                    _ => None,
+                }
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Branch the issue's work is done on: `issue-<n>-<title slug>`.
+    pub fn branch_name(&self) -> String {
+        branch_name(self.number, &self.title)
+    }
+
+    /// A work item for the issue in `project_id`, linked back to it.
+    pub fn to_work_item(&self, repo: &RepoRef, project_id: impl Into<ProjectId>) -> WorkItem {
+        let mut content = format!("{} (#{})", self.title, self.number);
+        if let Some(body) = self.body.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+            content.push_str("\n\n");
+            content.push_str(body);
+        }
+        let mut item = WorkItem::with_priority(project_id, content, self.priority());
+        IssueLink::new(repo, self).store(&mut item);
+        item
+    }
+}
+
+/// The issue a work item came from, kept in the item's metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueLink {
+    /// Repository, `owner/name`.
+    pub repo: RepoRef,
+    /// Issue number.
+    pub number: u64,
+    /// Issue title.
+    pub title: String,
+    /// Web URL of the issue.
+    pub url: String,
+    /// Branch created when the issue was delegated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Session the issue was delegated to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    /// Last work state reported on the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported: Option<WorkState>,
+    /// Pull request opened for the work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<String>,
+}
+
+impl IssueLink {
+    /// A link to `issue` in `repo`.
+    pub fn new(repo: &RepoRef, issue: &Issue) -> Self {
+        Self {
+            repo: repo.clone(),
+            number: issue.number,
+            title: issue.title.clone(),
+            url: issue.html_url.clone(),
+            branch: None,
+            session: None,
+            reported: None,
+            pull_request: None,
+        }
+    }
+
+    /// The link stored on `item`, if any.
+    pub fn of(item: &WorkItem) -> Option<Self> {
+        item.metadata
+            .get(METADATA_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// The link as a metadata value.
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Stores the link on `item`.
+    pub fn store(&self, item: &mut WorkItem) {
+        item.metadata.insert(METADATA_KEY.to_string(), self.to_value());
+    }
+
+    /// The branch recorded on delegation, or the one it would get.
+    pub fn branch_name(&self) -> String {
+        self.branch.clone().unwrap_or_else(|| branch_name(self.number, &self.title))
+    }
+
+    /// `owner/name#n`.
+    pub fn reference(&self) -> String {
+        format!("{}#{}", self.repo, self.number)
+    }
+}
+
+/// `issue-<number>-<title slug>`, the slug cut to a readable length.
+fn branch_name(number: u64, title: &str) -> String {
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let mut slug = slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+        slug = slug.trim_end_matches('-').to_string();
+    }
+    if slug.is_empty() {
+        format!("issue-{}", number)
+    } else {
+        format!("issue-{}-{}", number, slug)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, title: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            body: Some("Steps to reproduce".to_string()),
+            html_url: format!("https://github.com/acme/api/issues/{}", number),
+            labels: labels.iter().map(|l| Label { name: l.to_string() }).collect(),
+            pull_request: None,
+        }
+    }
+
+    #[test]
+    fn test_branch_name() {
+        assert_eq!(issue(12, "Fix: login fails on Safari!", &[]).branch_name(), "issue-12-fix-login-fails-on-safari");
+        assert_eq!(issue(3, "???", &[]).branch_name(), "issue-3");
+        let long = issue(7, "a very long title that keeps going and going well past the limit", &[]);
+        assert!(long.branch_name().len() <= "issue-7-".len() + MAX_SLUG_LEN);
+        assert!(!long.branch_name().ends_with('-'));
+    }
+
+    #[test]
+    fn test_priority_from_labels() {
+        assert_eq!(issue(1, "x", &["ai"]).priority(), WorkPriority::Medium);
+        assert_eq!(issue(1, "x", &["ai", "priority:high"]).priority(), WorkPriority::High);
+        assert_eq!(issue(1, "x", &["low", "urgent"]).priority(), WorkPriority::Critical);
+        assert_eq!(issue(1, "x", &["P3"]).priority(), WorkPriority::Low);
+    }
+
+    #[test]
+    fn test_work_item_link_roundtrip() {
+        let repo = RepoRef::new("acme", "api");
+        let item = issue(42, "Add rate limiting", &["ai"]).to_work_item(&repo, "api");
+        assert!(item.content.starts_with("Add rate limiting (#42)\n\nSteps"));
+
+        let link = IssueLink::of(&item).unwrap();
+        assert_eq!(link.reference(), "acme/api#42");
+        assert_eq!(link.branch, None);
+        assert_eq!(link.branch_name(), "issue-42-add-rate-limiting");
+        assert!(IssueLink::of(&WorkItem::new("api", "unlinked")).is_none());
+    }
+}
@@ -0,0 +1,49 @@
+//! GitHub issues and pull requests as Commander work items.
+//!
+//! - `commander gh import --repo org/name --label ai` turns open issues into
+//!   work items of a project, each linked to its issue ([`IssueLink`]).
+//! - Delegating an issue to a session creates its branch
+//!   ([`git::checkout_branch`]) and starts the work item; progress is
+//!   reported back as issue comments ([`report_progress`]).
+//! - Completing the item pushes the branch and opens a pull request that
+//!   closes the issue ([`GitHubClient::create_pull`]).
+//! - Stopping a session can push its branch and open a pull request with a
+//!   generated description ([`publish_branch`]).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use commander_github::{import_issues, GitHubClient, RepoRef};
+//! use commander_models::ProjectId;
+//! use commander_persistence::WorkStore;
+//! use commander_work::WorkQueue;
+//!
+//! # async fn run() -> commander_github::Result<()> {
+//! let repo: RepoRef = "acme/api".parse()?;
+//! let client = GitHubClient::new("ghp_...");
+//! let issues = client.list_issues(&repo, &["ai".to_string()]).await?;
+//!
+//! let queue = WorkQueue::new(WorkStore::new("/tmp/commander"));
+//! let summary = import_issues(&queue, &ProjectId::from("api"), &repo, &issues)?;
+//! println!("Imported {} issues", summary.imported.len());
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod git;
+pub mod issue;
+pub mod publish;
+pub mod repo;
+pub mod sync;
+
+pub use client::{GitHubClient, PullRequest, DEFAULT_API_URL};
+pub use error::{GitHubError, Result};
+pub use issue::{Issue, IssueLink, Label, METADATA_KEY};
+pub use publish::{compare_url, publish_branch, Published};
+pub use repo::RepoRef;
+pub use sync::{
+    delegation_prompt, find_issue, import_issues, linked_items, progress_comment, pull_request_body,
+    report_progress, update_link, ImportSummary,
+};
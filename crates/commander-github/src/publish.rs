@@ -0,0 +1,85 @@
+//! Publishing a stopped session's branch as a pull request.
+//!
+//! After `/stop` commits a session's changes with a generated message, the
+//! checked-out branch is pushed to `origin` and a pull request is opened
+//! with the generated title and body. Without a token the pull request is
+//! left for the user to open from GitHub's compare page.
+
+use std::path::Path;
+
+use crate::client::GitHubClient;
+use crate::error::{GitHubError, Result};
+use crate::git;
+use crate::repo::RepoRef;
+
+/// A pushed branch and its pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Published {
+    /// The pushed branch.
+    pub branch: String,
+    /// The pull request, or the page to open one from.
+    pub url: String,
+    /// Whether the pull request was opened (not just linked to).
+    pub opened: bool,
+}
+
+/// GitHub's page for opening a pull request from `branch`.
+pub fn compare_url(repo: &RepoRef, branch: &str) -> String {
+    format!("https://github.com/{}/compare/{}?expand=1", repo, branch)
+}
+
+/// Pushes the branch checked out in `dir` and opens a pull request for it.
+///
+/// The default branch is never pushed. With a `client` the pull request is
+/// created through the API; without one the result links to the compare page.
+pub async fn publish_branch(
+    dir: &Path,
+    client: Option<&GitHubClient>,
+    title: &str,
+    body: &str,
+) -> Result<Published> {
+    let branch = git::current_branch(dir)?;
+    let repo = git::origin_repo(dir)?;
+    let base = match client {
+        Some(client) => Some(client.default_branch(&repo).await?),
+        None => git::origin_default_branch(dir),
+    };
+    let is_default = match &base {
+        Some(base) => *base == branch,
+        None => branch == "main" || branch == "master",
+    };
+    if branch.is_empty() || is_default {
+        return Err(GitHubError::DefaultBranch(branch));
+    }
+
+    git::push_branch(dir, &branch)?;
+
+    match (client, base) {
+        (Some(client), Some(base)) => {
+            let pull = client.create_pull(&repo, &branch, &base, title, body).await?;
+            Ok(Published {
+                branch,
+                url: pull.html_url,
+                opened: true,
+            })
+        }
+        _ => Ok(Published {
+            url: compare_url(&repo, &branch),
+            branch,
+            opened: false,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_url() {
+        assert_eq!(
+            compare_url(&RepoRef::new("acme", "api"), "fix-login"),
+            "https://github.com/acme/api/compare/fix-login?expand=1"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Repository references.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::GitHubError;
+
+/// A GitHub repository, written `owner/name`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RepoRef {
+    /// User or organization owning the repository.
+    pub owner: String,
+    /// Repository name.
+    pub name: String,
+}
+
+impl RepoRef {
+    /// Creates a repository reference.
+    pub fn new(owner: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl FromStr for RepoRef {
+    type Err = GitHubError;
+
+    /// Parses `owner/name`, also accepting a `https://github.com/` URL or an
+    /// SSH remote (`git@github.com:owner/name.git`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s
+            .trim()
+            .trim_start_matches("https://github.com/")
+            .trim_start_matches("ssh://git@github.com/")
+            .trim_start_matches("git@github.com:")
+            .trim_end_matches('/')
+            .trim_end_matches(".git");
+        match trimmed.split_once('/') {
+            Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+                Ok(Self::new(owner, name))
+            }
+            _ => Err(GitHubError::InvalidRepo(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RepoRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repo() {
+        let repo: RepoRef = "acme/api".parse().unwrap();
+        assert_eq!(repo, RepoRef::new("acme", "api"));
+        assert_eq!(repo.to_string(), "acme/api");
+
+        let url: RepoRef = "https://github.com/acme/api.git".parse().unwrap();
+        assert_eq!(url, repo);
+        let ssh: RepoRef = "git@github.com:acme/api.git".parse().unwrap();
+        assert_eq!(ssh, repo);
+
+        assert!("acme".parse::<RepoRef>().is_err());
+        assert!("acme/".parse::<RepoRef>().is_err());
+        assert!("a/b/c".parse::<RepoRef>().is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Syncing issues with the work queue.
+//!
+//! Imported issues become work items carrying an [`IssueLink`]. As a linked
+//! item moves through its states, [`report_progress`] comments on the issue
+//! once per state change.
+
+use commander_models::{ProjectId, WorkId, WorkItem, WorkState};
+use commander_work::{WorkFilter, WorkQueue};
+
+use crate::client::GitHubClient;
+use crate::error::{GitHubError, Result};
+use crate::issue::{Issue, IssueLink, METADATA_KEY};
+use crate::repo::RepoRef;
+
+/// Outcome of an import.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Work items created, one per new issue.
+    pub imported: Vec<WorkId>,
+    /// Issues that already had a work item.
+    pub skipped: usize,
+}
+
+/// Enqueues a work item in `project_id` for each issue not yet imported.
+pub fn import_issues(queue: &WorkQueue, project_id: &ProjectId, repo: &RepoRef, issues: &[Issue]) -> Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+    for issue in issues {
+        if find_issue(queue, project_id, repo, issue.number).is_some() {
+            summary.skipped += 1;
+            continue;
+        }
+        summary.imported.push(queue.enqueue(issue.to_work_item(repo, project_id.clone()))?);
+    }
+    Ok(summary)
+}
+
+/// Work items of `project_id` linked to an issue, with their links.
+pub fn linked_items(queue: &WorkQueue, project_id: &ProjectId) -> Vec<(WorkItem, IssueLink)> {
+    queue
+        .list(Some(WorkFilter::new().with_project_id(project_id.clone())))
+        .into_iter()
+        .filter_map(|item| IssueLink::of(&item).map(|link| (item, link)))
+        .collect()
+}
+
+/// The work item of issue `number` of `repo`.
+pub fn find_issue(queue: &WorkQueue, project_id: &ProjectId, repo: &RepoRef, number: u64) -> Option<(WorkItem, IssueLink)> {
+    linked_items(queue, project_id)
+        .into_iter()
+        .find(|(_, link)| &link.repo == repo && link.number == number)
+}
+
+/// Saves `link` on the work item `id`.
+pub fn update_link(queue: &WorkQueue, id: &WorkId, link: &IssueLink) -> Result<()> {
+    Ok(queue.set_metadata(id, METADATA_KEY, link.to_value())?)
+}
+
+/// Prompt sent to the session an issue is delegated to.
+pub fn delegation_prompt(item: &WorkItem, link: &IssueLink) -> String {
+    let mut prompt = format!("Work on GitHub issue {} ({}).", link.reference(), link.url);
+    if let Some(branch) = &link.branch {
+        prompt.push_str(&format!(" Commit your changes on the branch `{}`.", branch));
+    }
+    prompt.push_str("\n\n");
+    prompt.push_str(&item.content);
+    prompt
+}
+
+/// Comment describing the item's state, when it changed since the last report.
+pub fn progress_comment(item: &WorkItem, link: &IssueLink) -> Option<String> {
+    if link.reported == Some(item.state) {
+        return None;
+    }
+    let comment = match item.state {
+        WorkState::InProgress => {
+            let mut text = "Work on this issue has started".to_string();
+            if let Some(session) = &link.session {
+                text.push_str(&format!(" in session `{}`", session));
+            }
+            if let Some(branch) = &link.branch {
+                text.push_str(&format!(" on branch `{}`", branch));
+            }
+            text.push('.');
+            text
+        }
+        WorkState::Completed => {
+            let mut text = "Work on this issue is complete.".to_string();
+            if let Some(result) = item.result.as_deref().filter(|r| !r.is_empty()) {
+                text.push_str(&format!("\n\n{}", result));
+            }
+            if let Some(pr) = &link.pull_request {
+                text.push_str(&format!("\n\nPull request: {}", pr));
+            }
+            text
+        }
+        WorkState::Failed => format!(
+            "Work on this issue failed: {}",
+            item.error.as_deref().unwrap_or("no details")
+        ),
+        WorkState::Blocked => "Work on this issue is blocked.".to_string(),
+        WorkState::Cancelled => "Work on this issue was cancelled.".to_string(),
+        WorkState::Pending | WorkState::Queued => return None,
+    };
+    Some(comment)
+}
+
+/// Body of the pull request opened for a completed item.
+pub fn pull_request_body(item: &WorkItem, link: &IssueLink) -> String {
+    let mut body = format!("Closes #{}.", link.number);
+    if let Some(result) = item.result.as_deref().filter(|r| !r.is_empty()) {
+        body.push_str(&format!("\n\n{}", result));
+    }
+    body
+}
+
+/// Comments on the issue of `item` if its state changed since the last
+/// report, and records the reported state. Returns whether it commented.
+pub async fn report_progress(client: &GitHubClient, queue: &WorkQueue, item: &WorkItem) -> Result<bool> {
+    let mut link = IssueLink::of(item).ok_or_else(|| GitHubError::NotLinked(item.id.to_string()))?;
+    let Some(comment) = progress_comment(item, &link) else {
+        return Ok(false);
+    };
+    client.comment(&link.repo, link.number, &comment).await?;
+    link.reported = Some(item.state);
+    update_link(queue, &item.id, &link)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issue::Label;
+    use commander_persistence::WorkStore;
+    use tempfile::TempDir;
+
+    fn issue(number: u64) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            body: None,
+            html_url: format!("https://github.com/acme/api/issues/{}", number),
+            labels: vec![Label { name: "ai".to_string() }],
+            pull_request: None,
+        }
+    }
+
+    #[test]
+    fn test_import_skips_known_issues() {
+        let dir = TempDir::new().unwrap();
+        let queue = WorkQueue::new(WorkStore::new(dir.path()));
+        let project = ProjectId::from("api");
+        let repo = RepoRef::new("acme", "api");
+
+        let first = import_issues(&queue, &project, &repo, &[issue(1), issue(2)]).unwrap();
+        assert_eq!(first.imported.len(), 2);
+        let second = import_issues(&queue, &project, &repo, &[issue(2), issue(3)]).unwrap();
+        assert_eq!((second.imported.len(), second.skipped), (1, 1));
+
+        // Persisted links survive a reload
+        let reloaded = WorkQueue::new(WorkStore::new(dir.path()));
+        reloaded.load_project(&project).unwrap();
+        assert_eq!(linked_items(&reloaded, &project).len(), 3);
+        assert!(find_issue(&reloaded, &project, &repo, 3).is_some());
+        assert!(find_issue(&reloaded, &project, &RepoRef::new("acme", "web"), 3).is_none());
+    }
+
+    #[test]
+    fn test_progress_comment_once_per_state() {
+        let dir = TempDir::new().unwrap();
+        let queue = WorkQueue::new(WorkStore::new(dir.path()));
+        let project = ProjectId::from("api");
+        let repo = RepoRef::new("acme", "api");
+        let id = import_issues(&queue, &project, &repo, &[issue(5)]).unwrap().imported[0].clone();
+
+        let item = queue.get(&id).unwrap();
+        let mut link = IssueLink::of(&item).unwrap();
+        assert_eq!(progress_comment(&item, &link), None);
+
+        link.session = Some("commander-api".to_string());
+        link.branch = Some("issue-5-issue-5".to_string());
+        update_link(&queue, &id, &link).unwrap();
+        let item = queue.start(&id).unwrap();
+        let comment = progress_comment(&item, &link).unwrap();
+        assert!(comment.contains("`commander-api`") && comment.contains("`issue-5-issue-5`"));
+        assert!(delegation_prompt(&item, &link).contains("acme/api#5"));
+
+        link.reported = Some(WorkState::InProgress);
+        assert_eq!(progress_comment(&item, &link), None);
+
+        queue.complete_with_result(&id, "Added the endpoint".to_string()).unwrap();
+        let item = queue.get(&id).unwrap();
+        assert!(progress_comment(&item, &link).unwrap().contains("Added the endpoint"));
+        assert_eq!(pull_request_body(&item, &link), "Closes #5.\n\nAdded the endpoint");
+    }
+}
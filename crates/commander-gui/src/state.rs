@@ -1,5 +1,6 @@
 use anyhow::Result;
 use commander_persistence::StateStore;
+use commander_core::CommandSender;
 use commander_tmux::TmuxOrchestrator;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
@@ -39,7 +40,7 @@ impl GuiState {
         // Use commander_core::config to get the state directory
         let state_dir = commander_core::config::state_dir();
         let store = StateStore::new(state_dir);
-        let tmux = TmuxOrchestrator::new().ok();
+        let tmux = TmuxOrchestrator::new().ok().map(|tmux| tmux.with_sender(CommandSender::Gui));
 
         Ok(Self {
             store: Arc::new(store),
@@ -0,0 +1,261 @@
+//! Matrix bot event loops.
+//!
+//! Three tasks share the bot state:
+//! - a `/sync` long-poll loop that joins invited rooms and handles commands
+//! - a response loop that delivers summarized session output
+//! - a notification loop that broadcasts cross-channel notifications
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use commander_core::{get_unread_notifications, mark_notifications_read};
+use tracing::{debug, error, info, warn};
+
+use crate::client::{IncomingMessage, MatrixClient, MatrixConfig, SYNC_TIMEOUT};
+use crate::command::{Command, HELP_TEXT};
+use crate::error::{MatrixError, Result};
+use crate::state::{create_shared_state, MatrixState};
+
+/// Channel name used to track read notifications.
+pub const NOTIFICATION_CHANNEL: &str = "matrix";
+
+/// Polling interval for session responses.
+const RESPONSE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polling interval for cross-channel notifications.
+const NOTIFICATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Delay before retrying a failed sync.
+const SYNC_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Commander Matrix bot.
+pub struct MatrixBot {
+    client: Arc<MatrixClient>,
+    state: Arc<MatrixState>,
+    user_id: Option<String>,
+}
+
+impl MatrixBot {
+    /// Create a bot configured from the environment.
+    pub fn new(state_dir: &Path) -> Result<Self> {
+        Self::with_config(MatrixConfig::from_env()?, state_dir)
+    }
+
+    /// Create a bot with an explicit account configuration.
+    pub fn with_config(config: MatrixConfig, state_dir: &Path) -> Result<Self> {
+        Ok(Self {
+            client: Arc::new(MatrixClient::new(config)?),
+            state: create_shared_state(state_dir),
+            user_id: None,
+        })
+    }
+
+    /// Resolve the bot's user ID.
+    pub async fn whoami(&mut self) -> Result<String> {
+        let user_id = self.client.whoami().await?;
+        self.user_id = Some(user_id.clone());
+        Ok(user_id)
+    }
+
+    /// Run the bot until the process is stopped.
+    pub async fn run(&mut self) -> Result<()> {
+        let user_id = match &self.user_id {
+            Some(id) => id.clone(),
+            None => self.whoami().await?,
+        };
+
+        commander_core::connectivity::start_monitor();
+
+        let restored = self.state.restore_connections().await;
+        if restored > 0 {
+            info!(count = restored, "Restored room connections");
+        }
+
+        let client = Arc::clone(&self.client);
+        let state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            poll_responses_loop(client, state).await;
+        });
+
+        let client = Arc::clone(&self.client);
+        let state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            poll_notifications_loop(client, state).await;
+        });
+
+        // Skip history: only react to events that arrive after startup
+        let initial = self.client.sync(None, Duration::ZERO).await?;
+        let mut since = initial.next_batch.clone();
+        for room in initial.invites() {
+            self.join(&room).await;
+        }
+        info!(user = %user_id, "Matrix bot listening");
+
+        loop {
+            let sync = match self.client.sync(Some(&since), SYNC_TIMEOUT).await {
+                Ok(sync) => sync,
+                Err(e) => {
+                    warn!(error = %e, "Sync failed, retrying");
+                    tokio::time::sleep(SYNC_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+            since = sync.next_batch.clone();
+
+            for room in sync.invites() {
+                self.join(&room).await;
+            }
+            for message in sync.messages(&user_id) {
+                if let Err(e) = self.handle_message(&message).await {
+                    warn!(room = %message.room_id, error = %e, "Failed to handle message");
+                    self.reply(&message.room_id, &format!("Error: {}", e)).await;
+                }
+            }
+        }
+    }
+
+    async fn join(&self, room_id: &str) {
+        match self.client.join_room(room_id).await {
+            Ok(()) => {
+                let text = if self.state.is_authorized(room_id).await {
+                    "Commander is back in this room. Send !help for commands.".to_string()
+                } else {
+                    format!("Hi! {}", MatrixError::NotAuthorized)
+                };
+                self.reply(room_id, &text).await;
+            }
+            Err(e) => warn!(room = %room_id, error = %e, "Failed to join room"),
+        }
+    }
+
+    async fn handle_message(&self, message: &IncomingMessage) -> Result<()> {
+        let room = message.room_id.as_str();
+        let command = Command::parse(&message.body);
+        debug!(room = %room, sender = %message.sender, command = ?command, "Received message");
+
+        match command {
+            Command::Help => self.reply(room, HELP_TEXT).await,
+            Command::Pair(code) => {
+                let project = self.state.pair(room, &code).await?;
+                self.reply(room, "Paired! This room can now control Commander.").await;
+                if let Some(project) = project {
+                    self.connect(room, &project).await?;
+                }
+            }
+            // Everything below requires a paired room
+            _ if !self.state.is_authorized(room).await => {
+                return Err(MatrixError::NotAuthorized);
+            }
+            Command::Usage(usage) => self.reply(room, &format!("Usage: {}", usage)).await,
+            Command::Unknown(name) => {
+                self.reply(room, &format!("Unknown command '{}'. Send !help for commands.", name))
+                    .await
+            }
+            Command::Connect(project) => self.connect(room, &project).await?,
+            Command::Disconnect => {
+                let text = match self.state.disconnect(room).await {
+                    Some(project) => format!("Disconnected from {}", project),
+                    None => "Not connected to any project".to_string(),
+                };
+                self.reply(room, &text).await;
+            }
+            Command::Status => {
+                let text = match self.state.session(room).await {
+                    Some(session) => {
+                        let waiting = if self.state.is_waiting(room).await {
+                            " (waiting for response)"
+                        } else {
+                            ""
+                        };
+                        format!(
+                            "Connected to {} [{}]{}",
+                            session.project_name, session.adapter_type, waiting
+                        )
+                    }
+                    None => "Not connected. Use !connect <project>".to_string(),
+                };
+                self.reply(room, &text).await;
+            }
+            Command::List => {
+                let mut projects = self.state.list_projects();
+                projects.sort();
+                let text = if projects.is_empty() {
+                    "No projects registered".to_string()
+                } else {
+                    let lines: Vec<String> = projects
+                        .iter()
+                        .map(|(name, path)| format!("- {} ({})", name, path))
+                        .collect();
+                    format!("Projects:\n{}", lines.join("\n"))
+                };
+                self.reply(room, &text).await;
+            }
+            Command::Message(text) => {
+                if text.is_empty() {
+                    return Ok(());
+                }
+                self.state.send_message(room, &text).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn connect(&self, room_id: &str, project: &str) -> Result<()> {
+        let (name, adapter) = self.state.connect(room_id, project).await?;
+        self.reply(room_id, &format!("Connected to {} [{}]", name, adapter)).await;
+        Ok(())
+    }
+
+    async fn reply(&self, room_id: &str, text: &str) {
+        send_or_log(&self.client, room_id, text).await;
+    }
+}
+
+async fn send_or_log(client: &MatrixClient, room_id: &str, text: &str) {
+    if let Err(e) = client.send_notice(room_id, text).await {
+        error!(room = %room_id, error = %e, "Failed to send message");
+    }
+}
+
+/// Deliver completed, summarized responses to their rooms.
+async fn poll_responses_loop(client: Arc<MatrixClient>, state: Arc<MatrixState>) {
+    let mut interval = tokio::time::interval(RESPONSE_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        for room in state.authorized_rooms().await {
+            if !state.is_waiting(&room).await {
+                continue;
+            }
+            match state.poll_response(&room).await {
+                Ok(Some(response)) => send_or_log(&client, &room, &response).await,
+                Ok(None) => {}
+                Err(e) => warn!(room = %room, error = %e, "Failed to poll session output"),
+            }
+        }
+    }
+}
+
+/// Broadcast cross-channel notifications to all paired rooms.
+async fn poll_notifications_loop(client: Arc<MatrixClient>, state: Arc<MatrixState>) {
+    let mut interval = tokio::time::interval(NOTIFICATION_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let notifications = get_unread_notifications(NOTIFICATION_CHANNEL);
+        if notifications.is_empty() {
+            continue;
+        }
+
+        let rooms = state.authorized_rooms().await;
+        for notification in &notifications {
+            for room in &rooms {
+                send_or_log(&client, room, &notification.message).await;
+            }
+        }
+
+        let ids: Vec<String> = notifications.into_iter().map(|n| n.id).collect();
+        if let Err(e) = mark_notifications_read(NOTIFICATION_CHANNEL, &ids) {
+            warn!(error = %e, "Failed to mark notifications read");
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Minimal Matrix Client-Server API client.
+//!
+//! Covers the handful of endpoints the bot needs: `whoami`, long-polling
+//! `/sync`, joining rooms and sending text messages. Authentication uses an
+//! access token for an existing bot account.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::error::{MatrixError, Result};
+
+/// Environment variable for the homeserver base URL.
+pub const HOMESERVER_ENV: &str = "MATRIX_HOMESERVER";
+
+/// Environment variable for the bot account's access token.
+pub const ACCESS_TOKEN_ENV: &str = "MATRIX_ACCESS_TOKEN";
+
+/// Server-side timeout for a `/sync` long poll.
+pub const SYNC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connection settings for a Matrix account.
+#[derive(Debug, Clone)]
+pub struct MatrixConfig {
+    /// Homeserver base URL, e.g. `https://matrix.example.org`.
+    pub homeserver: String,
+    /// Access token of the bot account.
+    pub access_token: String,
+}
+
+impl MatrixConfig {
+    /// Read the configuration from `MATRIX_HOMESERVER` and `MATRIX_ACCESS_TOKEN`.
+    pub fn from_env() -> Result<Self> {
+        let homeserver = std::env::var(HOMESERVER_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .ok_or(MatrixError::MissingConfig(HOMESERVER_ENV))?;
+        let access_token = std::env::var(ACCESS_TOKEN_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .ok_or(MatrixError::MissingConfig(ACCESS_TOKEN_ENV))?;
+
+        Ok(Self {
+            homeserver: homeserver.trim().trim_end_matches('/').to_string(),
+            access_token: access_token.trim().to_string(),
+        })
+    }
+}
+
+/// A text message received in a joined room.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingMessage {
+    /// Room the message was sent in.
+    pub room_id: String,
+    /// Fully qualified sender ID, e.g. `@alice:example.org`.
+    pub sender: String,
+    /// Message body.
+    pub body: String,
+}
+
+/// Response body of `GET /sync`, reduced to the fields the bot reads.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SyncResponse {
+    /// Token to pass as `since` in the next sync.
+    pub next_batch: String,
+    #[serde(default)]
+    pub rooms: SyncRooms,
+}
+
+/// Room updates in a sync response.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SyncRooms {
+    #[serde(default)]
+    pub join: HashMap<String, JoinedRoom>,
+    #[serde(default)]
+    pub invite: HashMap<String, Value>,
+}
+
+/// Updates for a joined room.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JoinedRoom {
+    #[serde(default)]
+    pub timeline: Timeline,
+}
+
+/// New timeline events for a room.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Timeline {
+    #[serde(default)]
+    pub events: Vec<RoomEvent>,
+}
+
+/// A timeline event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoomEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub sender: String,
+    #[serde(default)]
+    pub content: Value,
+}
+
+impl SyncResponse {
+    /// Text messages in joined rooms, excluding those sent by `own_user_id`.
+    pub fn messages(&self, own_user_id: &str) -> Vec<IncomingMessage> {
+        let mut messages = Vec::new();
+        for (room_id, room) in &self.rooms.join {
+            for event in &room.timeline.events {
+                if event.event_type != "m.room.message" || event.sender == own_user_id {
+                    continue;
+                }
+                if event.content.get("msgtype").and_then(Value::as_str) != Some("m.text") {
+                    continue;
+                }
+                let Some(body) = event.content.get("body").and_then(Value::as_str) else {
+                    continue;
+                };
+                messages.push(IncomingMessage {
+                    room_id: room_id.clone(),
+                    sender: event.sender.clone(),
+                    body: body.to_string(),
+                });
+            }
+        }
+        messages
+    }
+
+    /// Rooms the bot has been invited to.
+    pub fn invites(&self) -> Vec<String> {
+        self.rooms.invite.keys().cloned().collect()
+    }
+}
+
+/// Matrix Client-Server API client.
+pub struct MatrixClient {
+    http: reqwest::Client,
+    config: MatrixConfig,
+    txn_counter: AtomicU64,
+}
+
+impl MatrixClient {
+    /// Create a client for the given account.
+    pub fn new(config: MatrixConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            // Leave headroom over the sync long-poll timeout
+            .timeout(SYNC_TIMEOUT + Duration::from_secs(30))
+            .build()?;
+        Ok(Self {
+            http,
+            config,
+            txn_counter: AtomicU64::new(0),
+        })
+    }
+
+    /// The fully qualified user ID of the bot account.
+    pub async fn whoami(&self) -> Result<String> {
+        let body = self.get("/_matrix/client/v3/account/whoami", &[]).await?;
+        body.get("user_id")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| MatrixError::HttpError("whoami response missing user_id".to_string()))
+    }
+
+    /// Long-poll for new events since `since` (or an initial sync when None).
+    pub async fn sync(&self, since: Option<&str>, timeout: Duration) -> Result<SyncResponse> {
+        let timeout_ms = timeout.as_millis().to_string();
+        let mut query = vec![("timeout", timeout_ms.as_str())];
+        if let Some(since) = since {
+            query.push(("since", since));
+        }
+        let body = self.get("/_matrix/client/v3/sync", &query).await?;
+        Ok(serde_json::from_value(body)?)
+    }
+
+    /// Join a room the bot was invited to.
+    pub async fn join_room(&self, room_id: &str) -> Result<()> {
+        let path = format!("/_matrix/client/v3/rooms/{}/join", encode_path_segment(room_id));
+        self.send_json(reqwest::Method::POST, &path, json!({})).await?;
+        debug!(room = %room_id, "Joined room");
+        Ok(())
+    }
+
+    /// Send a plain-text notice to a room.
+    ///
+    /// Bot replies use `m.notice` so other bots do not respond to them.
+    pub async fn send_notice(&self, room_id: &str, body: &str) -> Result<()> {
+        let path = format!(
+            "/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
+            encode_path_segment(room_id),
+            self.next_txn_id()
+        );
+        let content = json!({ "msgtype": "m.notice", "body": body });
+        self.send_json(reqwest::Method::PUT, &path, content).await?;
+        Ok(())
+    }
+
+    fn next_txn_id(&self) -> String {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        format!("commander-{}-{}", millis, self.txn_counter.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let response = self
+            .http
+            .get(format!("{}{}", self.config.homeserver, path))
+            .bearer_auth(&self.config.access_token)
+            .query(query)
+            .send()
+            .await?;
+        parse_response(response).await
+    }
+
+    async fn send_json(&self, method: reqwest::Method, path: &str, body: Value) -> Result<Value> {
+        let response = self
+            .http
+            .request(method, format!("{}{}", self.config.homeserver, path))
+            .bearer_auth(&self.config.access_token)
+            .json(&body)
+            .send()
+            .await?;
+        parse_response(response).await
+    }
+}
+
+async fn parse_response(response: reqwest::Response) -> Result<Value> {
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if status.is_success() {
+        return Ok(body);
+    }
+
+    let message = match (
+        body.get("errcode").and_then(Value::as_str),
+        body.get("error").and_then(Value::as_str),
+    ) {
+        (Some(code), Some(error)) => format!("{}: {}", code, error),
+        (Some(code), None) => code.to_string(),
+        (None, Some(error)) => error.to_string(),
+        (None, None) => status.to_string(),
+    };
+    Err(MatrixError::ApiError {
+        status: status.as_u16(),
+        message,
+    })
+}
+
+/// Percent-encode an identifier (room ID, transaction ID) for a URL path.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("!abc:example.org"), "%21abc%3Aexample.org");
+        assert_eq!(encode_path_segment("commander-1-0"), "commander-1-0");
+    }
+
+    #[test]
+    fn test_sync_response_messages() {
+        let body = json!({
+            "next_batch": "s72595_4483_1934",
+            "rooms": {
+                "join": {
+                    "!room:example.org": {
+                        "timeline": {
+                            "events": [
+                                {
+                                    "type": "m.room.message",
+                                    "sender": "@alice:example.org",
+                                    "content": { "msgtype": "m.text", "body": "!status" }
+                                },
+                                {
+                                    "type": "m.room.message",
+                                    "sender": "@commander:example.org",
+                                    "content": { "msgtype": "m.notice", "body": "Connected" }
+                                },
+                                {
+                                    "type": "m.room.member",
+                                    "sender": "@bob:example.org",
+                                    "content": { "membership": "join" }
+                                },
+                                {
+                                    "type": "m.room.message",
+                                    "sender": "@alice:example.org",
+                                    "content": { "msgtype": "m.image", "body": "cat.png" }
+                                }
+                            ]
+                        }
+                    }
+                },
+                "invite": { "!new:example.org": {} }
+            }
+        });
+
+        let sync: SyncResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(sync.next_batch, "s72595_4483_1934");
+        assert_eq!(
+            sync.messages("@commander:example.org"),
+            vec![IncomingMessage {
+                room_id: "!room:example.org".to_string(),
+                sender: "@alice:example.org".to_string(),
+                body: "!status".to_string(),
+            }]
+        );
+        assert_eq!(sync.invites(), vec!["!new:example.org".to_string()]);
+    }
+
+    #[test]
+    fn test_sync_response_without_rooms() {
+        let sync: SyncResponse = serde_json::from_value(json!({ "next_batch": "s1" })).unwrap();
+        assert!(sync.messages("@commander:example.org").is_empty());
+        assert!(sync.invites().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Bot command parsing.
+//!
+//! Commands accept either a `!` or `/` prefix. Element and most other
+//! clients reserve `/` for client-side commands, so `!` is the documented
+//! form.
+
+/// A parsed room message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Show available commands.
+    Help,
+    /// Pair the room using a code generated in Commander.
+    Pair(String),
+    /// Connect the room to a project.
+    Connect(String),
+    /// Disconnect the room from its project.
+    Disconnect,
+    /// Show connection status.
+    Status,
+    /// List available projects.
+    List,
+    /// A command with a missing argument; holds the usage hint.
+    Usage(&'static str),
+    /// An unrecognised command.
+    Unknown(String),
+    /// Plain text to forward to the connected session.
+    Message(String),
+}
+
+impl Command {
+    /// Parse a message body.
+    pub fn parse(body: &str) -> Self {
+        let body = body.trim();
+        let Some(rest) = body.strip_prefix('!').or_else(|| body.strip_prefix('/')) else {
+            return Command::Message(body.to_string());
+        };
+
+        let (name, arg) = match rest.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (rest, ""),
+        };
+
+        match name.to_lowercase().as_str() {
+            "help" | "start" => Command::Help,
+            "pair" if arg.is_empty() => Command::Usage("!pair <code>"),
+            "pair" => Command::Pair(arg.to_string()),
+            "connect" if arg.is_empty() => Command::Usage("!connect <project>"),
+            "connect" => Command::Connect(arg.to_string()),
+            "disconnect" => Command::Disconnect,
+            "status" => Command::Status,
+            "list" => Command::List,
+            _ => Command::Unknown(name.to_string()),
+        }
+    }
+}
+
+/// Help text listing the bot's commands.
+pub const HELP_TEXT: &str = "\
+Commander Matrix bot
+
+!pair <code>        Pair this room (generate a code with /telegram in Commander)
+!connect <project>  Connect to a project
+!disconnect         Disconnect from the current project
+!status             Show connection status
+!list               List available projects
+!help               Show this help
+
+Any other message is sent to the connected session and answered with a summary.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(Command::parse("!help"), Command::Help);
+        assert_eq!(Command::parse("/start"), Command::Help);
+        assert_eq!(Command::parse("!pair ab3cde"), Command::Pair("ab3cde".to_string()));
+        assert_eq!(Command::parse("!connect  my app "), Command::Connect("my app".to_string()));
+        assert_eq!(Command::parse("!DISCONNECT"), Command::Disconnect);
+        assert_eq!(Command::parse("/status"), Command::Status);
+        assert_eq!(Command::parse("!list"), Command::List);
+        assert_eq!(Command::parse("!frobnicate"), Command::Unknown("frobnicate".to_string()));
+    }
+
+    #[test]
+    fn test_parse_missing_arguments() {
+        assert_eq!(Command::parse("!pair"), Command::Usage("!pair <code>"));
+        assert_eq!(Command::parse("!connect "), Command::Usage("!connect <project>"));
+    }
+
+    #[test]
+    fn test_parse_plain_message() {
+        assert_eq!(
+            Command::parse("  fix the failing test  "),
+            Command::Message("fix the failing test".to_string())
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Error types for the Matrix bot.
+
+use thiserror::Error;
+
+/// Errors that can occur in the Matrix bot.
+#[derive(Debug, Error)]
+pub enum MatrixError {
+    /// A required environment variable is not set.
+    #[error("{0} not set. Set it in ~/.ai-commander/config/.env or the environment.")]
+    MissingConfig(&'static str),
+
+    /// The homeserver rejected a request.
+    #[error("Matrix API error ({status}): {message}")]
+    ApiError {
+        /// HTTP status code.
+        status: u16,
+        /// Matrix `errcode`/`error` text.
+        message: String,
+    },
+
+    /// Session error.
+    #[error("Session error: {0}")]
+    SessionError(String),
+
+    /// Room not connected to any project.
+    #[error("Not connected to a project. Use !connect <project> first.")]
+    NotConnected,
+
+    /// Room has not been paired.
+    #[error("This room is not paired. Run /telegram in Commander and send !pair <code>.")]
+    NotAuthorized,
+
+    /// Project not found.
+    #[error("Project not found: {0}")]
+    ProjectNotFound(String),
+
+    /// Tmux error.
+    #[error("Tmux error: {0}")]
+    TmuxError(String),
+
+    /// Invalid or expired pairing code.
+    #[error("Invalid or expired pairing code")]
+    InvalidPairingCode,
+
+    /// HTTP request error.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// IO error.
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// JSON parsing error.
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// Result type for Matrix operations.
+pub type Result<T> = std::result::Result<T, MatrixError>;
+
+impl From<commander_tmux::TmuxError> for MatrixError {
+    fn from(e: commander_tmux::TmuxError) -> Self {
+        MatrixError::TmuxError(e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for MatrixError {
+    fn from(e: reqwest::Error) -> Self {
+        MatrixError::HttpError(e.to_string())
+    }
+}
@@ -0,0 +1,44 @@
+//! Matrix bot interface for Commander.
+//!
+//! A self-hostable alternative to the Telegram bot: pair a Matrix room with
+//! Commander, connect it to a project, and chat with the session from any
+//! Matrix client. It shares pairing codes, the notification queue and the
+//! session handoff registry with the other frontends via `commander-core`.
+//!
+//! The bot talks to the homeserver through the Matrix Client-Server HTTP API
+//! directly (long-polling `/sync`), so it only needs an access token for an
+//! existing bot account. End-to-end encrypted rooms are not supported; use an
+//! unencrypted room for the bot.
+//!
+//! # Environment Variables
+//!
+//! Required:
+//! - `MATRIX_HOMESERVER`: Homeserver base URL, e.g. `https://matrix.example.org`
+//! - `MATRIX_ACCESS_TOKEN`: Access token of the bot account
+//!
+//! Optional:
+//! - `OPENROUTER_API_KEY`: For response summarization
+//!
+//! # Commands
+//!
+//! - `!pair <code>` - Pair the room with a code from `/telegram` in Commander
+//! - `!connect <project>` - Connect to a project
+//! - `!disconnect` - Disconnect from current project
+//! - `!status` - Show connection status
+//! - `!list` - List available projects
+//! - `!help` - Show available commands
+//!
+//! Any other message is sent to the connected session and the response is
+//! summarized back into the room.
+
+pub mod bot;
+pub mod client;
+pub mod command;
+pub mod error;
+pub mod state;
+
+pub use bot::{MatrixBot, NOTIFICATION_CHANNEL};
+pub use client::{IncomingMessage, MatrixClient, MatrixConfig, SyncResponse};
+pub use command::Command;
+pub use error::{MatrixError, Result};
+pub use state::{create_shared_state, MatrixState, RoomSession};
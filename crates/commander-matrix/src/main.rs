@@ -0,0 +1,66 @@
+//! Commander Matrix Bot binary.
+//!
+//! Start the bot with:
+//! ```bash
+//! MATRIX_HOMESERVER=https://matrix.example.org MATRIX_ACCESS_TOKEN=xxx cargo run -p commander-matrix
+//! ```
+
+use clap::Parser;
+use commander_core::{config, SessionLogLayer};
+use commander_matrix::MatrixBot;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+/// Commander Matrix Bot - interact with Claude Code from Matrix
+#[derive(Parser, Debug)]
+#[command(name = "commander-matrix")]
+#[command(about = "Matrix bot for Commander - interact with Claude Code remotely")]
+struct Args {
+    /// Verbose logging (-v, -vv, -vvv)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    // Load environment variables from config directory first
+    let env_path = config::env_file();
+    if env_path.exists() {
+        let _ = dotenvy::from_path(&env_path);
+    }
+    let _ = dotenvy::from_filename(".env.local")
+        .or_else(|_| dotenvy::dotenv());
+
+    let filter = match args.verbose {
+        0 => "commander_matrix=info",
+        1 => "commander_matrix=debug",
+        2 => "commander_matrix=trace",
+        _ => "trace",
+    };
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_filter(EnvFilter::try_new(filter).unwrap_or_else(|_| EnvFilter::new("info"))),
+        )
+        .with(SessionLogLayer::new(config::logs_dir(), "matrix").with_filter(LevelFilter::INFO))
+        .init();
+
+    if let Err(e) = config::ensure_all_dirs() {
+        tracing::warn!(error = %e, "Failed to create all directories");
+    }
+
+    let mut bot = MatrixBot::new(&config::state_dir())?;
+
+    let user_id = bot.whoami().await?;
+    println!("\n[robot] Commander Matrix Bot");
+    println!("   Account: {}", user_id);
+    println!("\n[chat] Invite the bot to a room and send !pair <code>");
+    println!("   Press Ctrl+C to stop\n");
+
+    bot.run().await?;
+    Ok(())
+}
@@ -0,0 +1,387 @@
+//! Shared state for the Matrix bot.
+//!
+//! Tracks paired rooms, which project each room is connected to, and the
+//! query awaiting a response in each room. Paired rooms and their projects
+//! are persisted to `matrix_rooms.json` so they survive bot restarts.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use commander_adapters::AdapterRegistry;
+use commander_core::{acquire_send_lock, config, CommandSender};
+use commander_core::{
+    clean_response, consume_pairing, find_new_lines, is_claude_ready, is_mpm_ready,
+    summarize_with_fallback, update_session_registry, Frontend, HandoffDirection,
+};
+use commander_persistence::StateStore;
+use commander_tmux::TmuxOrchestrator;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::error::{MatrixError, Result};
+
+/// How long output must be unchanged before a response is considered complete.
+const IDLE_THRESHOLD: Duration = Duration::from_millis(1500);
+
+/// Give up on a response after this long without new output.
+const MAX_WAIT: Duration = Duration::from_secs(300);
+
+/// Lines of tmux scrollback captured per poll.
+const CAPTURE_LINES: u32 = 200;
+
+/// A room's connection to a project session.
+#[derive(Debug, Clone)]
+pub struct RoomSession {
+    /// Project display name.
+    pub project_name: String,
+    /// Tmux session backing the project.
+    pub tmux_session: String,
+    /// Adapter running in the session (e.g. `claude-code`, `mpm`).
+    pub adapter_type: String,
+    pending: Option<PendingQuery>,
+}
+
+/// A message sent to a session whose response has not been delivered yet.
+#[derive(Debug, Clone)]
+struct PendingQuery {
+    query: String,
+    baseline: String,
+    last_output: String,
+    last_change: Instant,
+}
+
+/// Shared state for the Matrix bot.
+pub struct MatrixState {
+    tmux: Option<TmuxOrchestrator>,
+    adapters: AdapterRegistry,
+    store: StateStore,
+    /// Paired rooms mapped to their connected project, if any.
+    rooms: RwLock<HashMap<String, Option<String>>>,
+    sessions: RwLock<HashMap<String, RoomSession>>,
+}
+
+impl MatrixState {
+    /// Create the state, loading paired rooms from disk.
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            tmux: TmuxOrchestrator::new().ok().map(|tmux| tmux.with_sender(CommandSender::Matrix)),
+            adapters: AdapterRegistry::new(),
+            store: StateStore::new(state_dir),
+            rooms: RwLock::new(load_rooms(&config::matrix_rooms_file())),
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Whether a room has been paired.
+    pub async fn is_authorized(&self, room_id: &str) -> bool {
+        self.rooms.read().await.contains_key(room_id)
+    }
+
+    /// All paired rooms.
+    pub async fn authorized_rooms(&self) -> Vec<String> {
+        self.rooms.read().await.keys().cloned().collect()
+    }
+
+    /// Pair a room with a code generated in Commander.
+    ///
+    /// Returns the project to auto-connect to, if the code carries one.
+    pub async fn pair(&self, room_id: &str, code: &str) -> Result<Option<String>> {
+        let (project_name, _session_name) =
+            consume_pairing(code).ok_or(MatrixError::InvalidPairingCode)?;
+
+        self.rooms.write().await.entry(room_id.to_string()).or_insert(None);
+        self.save_rooms().await;
+        info!(room = %room_id, "Paired Matrix room");
+
+        Ok((!project_name.is_empty()).then_some(project_name))
+    }
+
+    /// Reconnect rooms to the projects they were connected to before a restart.
+    pub async fn restore_connections(&self) -> usize {
+        let rooms: Vec<(String, String)> = self
+            .rooms
+            .read()
+            .await
+            .iter()
+            .filter_map(|(room, project)| project.clone().map(|p| (room.clone(), p)))
+            .collect();
+
+        let mut restored = 0;
+        for (room, project) in rooms {
+            match self.connect(&room, &project).await {
+                Ok(_) => restored += 1,
+                Err(e) => warn!(room = %room, project = %project, error = %e, "Failed to restore connection"),
+            }
+        }
+        restored
+    }
+
+    /// Connect a room to a project, starting its session if needed.
+    ///
+    /// Returns the project name and adapter.
+    pub async fn connect(&self, room_id: &str, project_name: &str) -> Result<(String, String)> {
+        let tmux = self
+            .tmux
+            .as_ref()
+            .ok_or_else(|| MatrixError::TmuxError("tmux not available".to_string()))?;
+        let base_name = project_name.strip_prefix("commander-").unwrap_or(project_name);
+
+        let projects = self
+            .store
+            .load_all_projects()
+            .map_err(|e| MatrixError::SessionError(format!("Failed to load projects: {}", e)))?;
+
+        let session = if let Some(project) = projects
+            .values()
+            .find(|p| p.name == base_name || p.id.as_str() == base_name)
+        {
+            let adapter_type = project
+                .config
+                .get("tool")
+                .and_then(|v| v.as_str())
+                .unwrap_or("claude-code")
+                .to_string();
+
+            if !tmux.session_exists(&project.name) {
+                let adapter = self
+                    .adapters
+                    .get(&adapter_type)
+                    .ok_or_else(|| MatrixError::SessionError(format!("Unknown adapter: {}", adapter_type)))?;
+                let (cmd, args) = adapter.launch_command(&project.path);
+                let full_cmd = if args.is_empty() {
+                    cmd
+                } else {
+                    format!("{} {}", cmd, args.join(" "))
+                };
+
+                tmux.create_session_in_dir(&project.name, Some(&project.path))?;
+                tmux.send_line(&project.name, None, &full_cmd)?;
+                info!(project = %project.name, "Started new session");
+            }
+
+            RoomSession {
+                project_name: project.name.clone(),
+                tmux_session: project.name.clone(),
+                adapter_type,
+                pending: None,
+            }
+        } else if let Some(name) = [base_name, project_name]
+            .into_iter()
+            .find(|name| tmux.session_exists(name))
+        {
+            // Unregistered tmux session
+            RoomSession {
+                project_name: name.to_string(),
+                tmux_session: name.to_string(),
+                adapter_type: "claude-code".to_string(),
+                pending: None,
+            }
+        } else {
+            return Err(MatrixError::ProjectNotFound(project_name.to_string()));
+        };
+
+        let result = (session.project_name.clone(), session.adapter_type.clone());
+        let tmux_session = session.tmux_session.clone();
+
+        let previous = self.sessions.write().await.insert(room_id.to_string(), session);
+        if let Some(previous) = previous.filter(|p| p.tmux_session != tmux_session) {
+            let _ = update_session_registry(|r| r.detach(&previous.tmux_session, Frontend::Matrix));
+        }
+        let _ = update_session_registry(|r| {
+            r.attach(&tmux_session, Frontend::Matrix, Some(room_id.to_string()))
+        });
+
+        self.rooms
+            .write()
+            .await
+            .insert(room_id.to_string(), Some(result.0.clone()));
+        self.save_rooms().await;
+
+        debug!(room = %room_id, project = %result.0, "Room connected");
+        Ok(result)
+    }
+
+    /// Disconnect a room from its project.
+    pub async fn disconnect(&self, room_id: &str) -> Option<String> {
+        let session = self.sessions.write().await.remove(room_id)?;
+        let _ = update_session_registry(|r| r.detach(&session.tmux_session, Frontend::Matrix));
+
+        if let Some(project) = self.rooms.write().await.get_mut(room_id) {
+            *project = None;
+        }
+        self.save_rooms().await;
+
+        Some(session.project_name)
+    }
+
+    /// The session a room is connected to.
+    pub async fn session(&self, room_id: &str) -> Option<RoomSession> {
+        self.sessions.read().await.get(room_id).cloned()
+    }
+
+    /// Whether a room is waiting for a response.
+    pub async fn is_waiting(&self, room_id: &str) -> bool {
+        self.sessions
+            .read()
+            .await
+            .get(room_id)
+            .is_some_and(|s| s.pending.is_some())
+    }
+
+    /// Registered projects as (name, path) pairs.
+    pub fn list_projects(&self) -> Vec<(String, String)> {
+        self.store
+            .load_all_projects()
+            .map(|projects| {
+                projects
+                    .values()
+                    .map(|p| (p.name.clone(), p.path.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Send a message to a room's connected session.
+    pub async fn send_message(&self, room_id: &str, message: &str) -> Result<()> {
+        let tmux = self
+            .tmux
+            .as_ref()
+            .ok_or_else(|| MatrixError::TmuxError("tmux not available".to_string()))?;
+
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(room_id).ok_or(MatrixError::NotConnected)?;
+
+        let baseline = tmux.capture_output(&session.tmux_session, None, Some(CAPTURE_LINES))?;
+        {
+            let _lock = acquire_send_lock(&session.tmux_session, "Matrix")
+                .map_err(|e| MatrixError::SessionError(e.to_string()))?;
+            tmux.send_line(&session.tmux_session, None, message)?;
+        }
+
+        session.pending = Some(PendingQuery {
+            query: message.to_string(),
+            last_output: baseline.clone(),
+            baseline,
+            last_change: Instant::now(),
+        });
+
+        let _ = update_session_registry(|r| {
+            r.record_query(&session.tmux_session, Frontend::Matrix, message)
+        });
+        Ok(())
+    }
+
+    /// Check a room's pending query and return the summarized response once complete.
+    pub async fn poll_response(&self, room_id: &str) -> Result<Option<String>> {
+        let tmux = self
+            .tmux
+            .as_ref()
+            .ok_or_else(|| MatrixError::TmuxError("tmux not available".to_string()))?;
+
+        let (query, raw, tmux_session) = {
+            let mut sessions = self.sessions.write().await;
+            let session = sessions.get_mut(room_id).ok_or(MatrixError::NotConnected)?;
+            let Some(pending) = session.pending.as_mut() else {
+                return Ok(None);
+            };
+
+            let output = tmux.capture_output(&session.tmux_session, None, Some(CAPTURE_LINES))?;
+            if output != pending.last_output {
+                pending.last_output = output.clone();
+                pending.last_change = Instant::now();
+                return Ok(None);
+            }
+
+            let idle_for = pending.last_change.elapsed();
+            if idle_for >= MAX_WAIT {
+                session.pending = None;
+                return Ok(Some(
+                    "No new output received for 5 minutes. The session may have stalled.".to_string(),
+                ));
+            }
+
+            let ready = if session.adapter_type == "mpm" {
+                is_mpm_ready(&output)
+            } else {
+                is_claude_ready(&output)
+            };
+            if idle_for < IDLE_THRESHOLD || !ready || output == pending.baseline {
+                return Ok(None);
+            }
+
+            let raw = clean_response(&find_new_lines(&pending.baseline, &output).join("\n"));
+            let query = pending.query.clone();
+            session.pending = None;
+            (query, raw, session.tmux_session.clone())
+        };
+
+        let response = if raw.trim().is_empty() {
+            "(no response)".to_string()
+        } else {
+            summarize_with_fallback(&query, &raw).await
+        };
+
+        let _ = update_session_registry(|r| {
+            r.record_message(&tmux_session, Frontend::Matrix, HandoffDirection::Received, &response)
+        });
+        Ok(Some(response))
+    }
+
+    async fn save_rooms(&self) {
+        let rooms = self.rooms.read().await;
+        if let Err(e) = save_rooms(&config::matrix_rooms_file(), &rooms) {
+            warn!(error = %e, "Failed to save Matrix rooms");
+        }
+    }
+}
+
+/// Create a shared Matrix state.
+pub fn create_shared_state(state_dir: &Path) -> Arc<MatrixState> {
+    Arc::new(MatrixState::new(state_dir))
+}
+
+fn load_rooms(path: &Path) -> HashMap<String, Option<String>> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_rooms(path: &Path, rooms: &HashMap<String, Option<String>>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(rooms)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rooms_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state").join("matrix_rooms.json");
+
+        let mut rooms = HashMap::new();
+        rooms.insert("!a:example.org".to_string(), Some("api".to_string()));
+        rooms.insert("!b:example.org".to_string(), None);
+        save_rooms(&path, &rooms).unwrap();
+
+        assert_eq!(load_rooms(&path), rooms);
+    }
+
+    #[test]
+    fn test_load_rooms_missing_or_corrupt() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("matrix_rooms.json");
+        assert!(load_rooms(&path).is_empty());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(load_rooms(&path).is_empty());
+    }
+}
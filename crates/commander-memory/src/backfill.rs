@@ -0,0 +1,304 @@
+//! Re-embedding memories after a change of embedding provider.
+//!
+//! Memories keep the vectors of the provider that was configured when they
+//! were stored, so switching from hash-based embeddings to OpenAI (or to a
+//! local model) leaves them unsearchable. A [`Backfill`] finds the memories
+//! whose vectors do not come from the configured [`EmbeddingGenerator`] and
+//! embeds their content again in batches, pausing between requests to stay
+//! under the provider's rate limit.
+//!
+//! A memory is stale when its embedding has the wrong dimension, or when its
+//! [`EMBEDDING_MODEL_KEY`] metadata names another model. Memories without
+//! that key and of the right dimension cannot be told apart from current
+//! ones (hash-based and OpenAI vectors are both 1536-dimensional); they are
+//! only re-embedded with [`Backfill::with_unstamped`]. Memories stored
+//! without an embedding, because the provider could not be reached, are
+//! pending; [`Backfill::with_pending_only`] embeds just those once the
+//! network is back.
+//!
+//! Every stale memory is embedded before the store is touched, and the new
+//! vectors are then written with a single [`MemoryStore::import_all`], so a
+//! failed request leaves the store as it was.
+
+use std::fmt;
+use std::time::Duration;
+
+use tracing::{debug, info};
+
+use crate::embedding::EmbeddingGenerator;
+use crate::error::Result;
+use crate::memory::Memory;
+use crate::store::MemoryStore;
+
+/// Metadata key naming the model that produced a memory's embedding
+/// (see [`EmbeddingProvider::model_id`](crate::EmbeddingProvider::model_id)).
+pub const EMBEDDING_MODEL_KEY: &str = "embedding_model";
+
+/// Default number of memories embedded per request.
+pub const DEFAULT_BACKFILL_BATCH_SIZE: usize = 50;
+
+/// Why a memory's embedding has to be replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Staleness {
+    /// The memory was stored without an embedding.
+    Pending,
+    /// The embedding has another dimension than the configured provider's.
+    Dimension,
+    /// The embedding was stamped with another model.
+    Model,
+    /// The embedding is unstamped; it may come from another provider.
+    Unstamped,
+}
+
+/// Progress of a running backfill, reported after each batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillProgress {
+    /// Memories embedded so far.
+    pub done: usize,
+    /// Memories to embed in total.
+    pub total: usize,
+}
+
+/// Outcome of a backfill run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    /// Memories examined.
+    pub scanned: usize,
+    /// Memories stored without an embedding.
+    pub pending: usize,
+    /// Memories whose embedding had the wrong dimension.
+    pub wrong_dimension: usize,
+    /// Memories stamped with another model.
+    pub other_model: usize,
+    /// Unstamped memories of the right dimension.
+    pub unstamped: usize,
+    /// Memories given new embeddings.
+    pub reembedded: usize,
+    /// Embedding requests made.
+    pub batches: usize,
+}
+
+impl BackfillReport {
+    /// Memories that were, or would be, embedded again.
+    pub fn stale(&self, include_unstamped: bool) -> usize {
+        self.pending + self.wrong_dimension + self.other_model + if include_unstamped { self.unstamped } else { 0 }
+    }
+}
+
+impl fmt::Display for BackfillReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} memories re-embedded in {} batch(es) ({} pending, {} wrong dimension, {} other model, {} unstamped)",
+            self.reembedded,
+            self.scanned,
+            self.batches,
+            self.pending,
+            self.wrong_dimension,
+            self.other_model,
+            self.unstamped
+        )
+    }
+}
+
+/// Re-embeds the memories of a store with the configured provider.
+pub struct Backfill {
+    embedder: EmbeddingGenerator,
+    model_id: String,
+    batch_size: usize,
+    delay: Duration,
+    unstamped: bool,
+    pending_only: bool,
+    dry_run: bool,
+    progress: Option<Box<dyn Fn(BackfillProgress) + Send + Sync>>,
+}
+
+impl Backfill {
+    /// Create a backfill to `embedder`'s provider, without rate limiting.
+    pub fn new(embedder: EmbeddingGenerator) -> Self {
+        Self {
+            model_id: embedder.model_id(),
+            embedder,
+            batch_size: DEFAULT_BACKFILL_BATCH_SIZE,
+            delay: Duration::ZERO,
+            unstamped: false,
+            pending_only: false,
+            dry_run: false,
+            progress: None,
+        }
+    }
+
+    /// Set the number of memories embedded per request.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Send at most `requests` embedding requests per minute.
+    pub fn with_requests_per_minute(mut self, requests: u32) -> Self {
+        self.delay = match requests {
+            0 => Duration::ZERO,
+            n => Duration::from_secs(60) / n,
+        };
+        self
+    }
+
+    /// Also re-embed unstamped memories of the right dimension.
+    pub fn with_unstamped(mut self, unstamped: bool) -> Self {
+        self.unstamped = unstamped;
+        self
+    }
+
+    /// Only embed the memories stored without an embedding.
+    pub fn with_pending_only(mut self, pending_only: bool) -> Self {
+        self.pending_only = pending_only;
+        self
+    }
+
+    /// Only report what is stale; leave the store untouched.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Call `progress` after each embedded batch.
+    pub fn with_progress(mut self, progress: impl Fn(BackfillProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Whether `memory` needs a new embedding, and why.
+    pub fn staleness(&self, memory: &Memory) -> Option<Staleness> {
+        if memory.embedding.is_empty() {
+            return Some(Staleness::Pending);
+        }
+        if memory.embedding.len() != self.embedder.dimension() {
+            return Some(Staleness::Dimension);
+        }
+        match memory.get_metadata(EMBEDDING_MODEL_KEY).and_then(|v| v.as_str()) {
+            Some(model) if model == self.model_id => None,
+            Some(_) => Some(Staleness::Model),
+            None => Some(Staleness::Unstamped),
+        }
+    }
+
+    /// Re-embed the stale memories of every agent in `store`.
+    pub async fn run(&self, store: &dyn MemoryStore) -> Result<BackfillReport> {
+        let memories = store.export_all().await?;
+        let mut report = BackfillReport {
+            scanned: memories.len(),
+            ..Default::default()
+        };
+
+        let mut stale = Vec::new();
+        for memory in memories {
+            let selected = match self.staleness(&memory) {
+                Some(Staleness::Pending) => {
+                    report.pending += 1;
+                    true
+                }
+                Some(Staleness::Dimension) => {
+                    report.wrong_dimension += 1;
+                    !self.pending_only
+                }
+                Some(Staleness::Model) => {
+                    report.other_model += 1;
+                    !self.pending_only
+                }
+                Some(Staleness::Unstamped) => {
+                    report.unstamped += 1;
+                    self.unstamped && !self.pending_only
+                }
+                None => false,
+            };
+            if selected {
+                stale.push(memory);
+            }
+        }
+        if self.dry_run || stale.is_empty() {
+            return Ok(report);
+        }
+
+        info!(count = stale.len(), model = %self.model_id, "Re-embedding memories");
+        let total = stale.len();
+        let mut updated = Vec::with_capacity(total);
+        for (i, chunk) in stale.chunks(self.batch_size).enumerate() {
+            if i > 0 && !self.delay.is_zero() {
+                tokio::time::sleep(self.delay).await;
+            }
+            let texts: Vec<&str> = chunk.iter().map(|m| m.content.as_str()).collect();
+            let embeddings = self.embedder.embed_batch(&texts).await?;
+            report.batches += 1;
+
+            for (memory, embedding) in chunk.iter().zip(embeddings) {
+                let mut memory = memory.clone();
+                memory.embedding = embedding;
+                memory
+                    .metadata
+                    .insert(EMBEDDING_MODEL_KEY.to_string(), self.model_id.clone().into());
+                updated.push(memory);
+            }
+            debug!(done = updated.len(), total, "Backfill batch embedded");
+            if let Some(progress) = &self.progress {
+                progress(BackfillProgress { done: updated.len(), total });
+            }
+        }
+
+        report.reembedded = store.import_all(updated).await?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::EmbeddingProvider;
+    use crate::local::LocalStore;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_backfill_reembeds_stale_memories() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = LocalStore::new(dir.path().to_path_buf()).await.unwrap();
+        let embedder = EmbeddingGenerator::new(EmbeddingProvider::HashBased { dimension: 8 });
+        let model = embedder.model_id();
+
+        let current = Memory::with_id("current", "agent-1", "a", embedder.embed("a").await.unwrap())
+            .with_metadata(EMBEDDING_MODEL_KEY, model.clone().into());
+        let short = Memory::with_id("short", "agent-1", "b", vec![0.5; 4]);
+        let other = Memory::with_id("other", "agent-1", "c", vec![0.5; 8])
+            .with_metadata(EMBEDDING_MODEL_KEY, "openai:text-embedding-3-small".into());
+        let unstamped = Memory::with_id("unstamped", "agent-1", "d", vec![0.5; 8]);
+        let pending = Memory::with_id("pending", "agent-1", "e", Vec::new());
+        store.import_all(vec![current, short, other, unstamped, pending]).await.unwrap();
+
+        let report = Backfill::new(embedder.clone()).with_dry_run(true).run(&store).await.unwrap();
+        assert_eq!((report.pending, report.wrong_dimension, report.other_model, report.unstamped), (1, 1, 1, 1));
+        assert_eq!(report.reembedded, 0);
+        assert_eq!(store.get("short").await.unwrap().unwrap().embedding.len(), 4);
+
+        let report = Backfill::new(embedder.clone()).with_pending_only(true).run(&store).await.unwrap();
+        assert_eq!(report.reembedded, 1);
+        assert_eq!(store.get("pending").await.unwrap().unwrap().embedding, embedder.embed("e").await.unwrap());
+        assert_eq!(store.get("short").await.unwrap().unwrap().embedding.len(), 4);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let report = Backfill::new(embedder.clone())
+            .with_batch_size(1)
+            .with_progress(move |p| log.lock().unwrap().push(p.done))
+            .run(&store)
+            .await
+            .unwrap();
+        assert_eq!((report.reembedded, report.batches), (2, 2));
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+
+        let short = store.get("short").await.unwrap().unwrap();
+        assert_eq!(short.embedding, embedder.embed("b").await.unwrap());
+        assert_eq!(short.get_metadata(EMBEDDING_MODEL_KEY), Some(&model.clone().into()));
+        assert_eq!(store.get("unstamped").await.unwrap().unwrap().embedding, vec![0.5; 8]);
+
+        let report = Backfill::new(embedder).with_unstamped(true).run(&store).await.unwrap();
+        assert_eq!(report.reembedded, 1);
+    }
+}
@@ -0,0 +1,430 @@
+//! Deduplication and consolidation of near-duplicate memories.
+//!
+//! Agents often store the same fact several times with slightly different
+//! wording. The [`Consolidator`] clusters an agent's memories by cosine
+//! similarity and collapses each cluster into one memory: either the newest
+//! memory of the cluster, or a summary written by a [`MemoryMerger`] (an LLM).
+//!
+//! Clustering is greedy from newest to oldest: a memory joins the first
+//! cluster whose newest memory is at least `threshold` similar, so clusters
+//! cannot drift through chains of slightly-similar memories.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, info, warn};
+
+use crate::embedding::{cosine_similarity, EmbeddingGenerator};
+use crate::error::{MemoryError, Result};
+use crate::memory::Memory;
+use crate::store::MemoryStore;
+
+/// Default similarity above which memories count as duplicates.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.92;
+
+/// Metadata key listing the IDs of the memories merged into a memory.
+pub const CONSOLIDATED_FROM_KEY: &str = "consolidated_from";
+
+/// OpenRouter chat completions endpoint used by [`LlmMerger`].
+const OPENROUTER_CHAT_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+
+const MERGE_PROMPT: &str = "You merge near-duplicate memories of an AI coding agent. \
+Combine the memories below into a single concise memory that keeps every distinct fact. \
+Prefer the most recent information when they conflict. Reply with the merged memory only.";
+
+/// Writes one memory that replaces a cluster of near-duplicates.
+#[async_trait]
+pub trait MemoryMerger: Send + Sync {
+    /// Merge `contents` (newest first) into a single memory text.
+    async fn merge(&self, contents: &[&str]) -> Result<String>;
+}
+
+/// [`MemoryMerger`] backed by an OpenRouter chat model.
+pub struct LlmMerger {
+    api_key: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl LlmMerger {
+    /// Create a merger using `model` with the given OpenRouter key.
+    pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model: model.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create a merger with the summarizer's OpenRouter key and model.
+    pub fn from_env() -> Self {
+        use commander_core::summarizer::{get_api_key, get_model};
+        Self::new(get_api_key().unwrap_or_default(), get_model())
+    }
+}
+
+#[async_trait]
+impl MemoryMerger for LlmMerger {
+    async fn merge(&self, contents: &[&str]) -> Result<String> {
+        let memories = contents
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{}. {}", i + 1, c))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let body = serde_json::json!({
+            "model": self.model,
+            "messages": [
+                {"role": "system", "content": MERGE_PROMPT},
+                {"role": "user", "content": memories}
+            ],
+            "max_tokens": 400
+        });
+
+        let json: serde_json::Value = self
+            .client
+            .post(OPENROUTER_CHAT_URL)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| MemoryError::DatabaseError(format!("merge request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| MemoryError::DatabaseError(format!("invalid merge response: {}", e)))?;
+
+        json["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| MemoryError::DatabaseError("merge response had no content".to_string()))
+    }
+}
+
+/// Outcome of a consolidation run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsolidationReport {
+    /// Agents whose memories were examined.
+    pub agents: usize,
+    /// Memories before consolidation.
+    pub memories_before: usize,
+    /// Memories after consolidation.
+    pub memories_after: usize,
+    /// Clusters of duplicates found.
+    pub clusters: usize,
+    /// Clusters merged by the merger rather than by keeping the newest memory.
+    pub merged_by_llm: usize,
+    /// Approximate storage (content plus embedding) before consolidation.
+    pub bytes_before: usize,
+    /// Approximate storage after consolidation.
+    pub bytes_after: usize,
+}
+
+impl ConsolidationReport {
+    /// Memories removed.
+    pub fn removed(&self) -> usize {
+        self.memories_before.saturating_sub(self.memories_after)
+    }
+
+    /// Bytes saved.
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+
+    fn add(&mut self, other: &ConsolidationReport) {
+        self.agents += other.agents;
+        self.memories_before += other.memories_before;
+        self.memories_after += other.memories_after;
+        self.clusters += other.clusters;
+        self.merged_by_llm += other.merged_by_llm;
+        self.bytes_before += other.bytes_before;
+        self.bytes_after += other.bytes_after;
+    }
+}
+
+impl fmt::Display for ConsolidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} memories across {} agent(s) ({} removed in {} cluster(s), {:.1} KB saved)",
+            self.memories_before,
+            self.memories_after,
+            self.agents,
+            self.removed(),
+            self.clusters,
+            self.bytes_saved() as f64 / 1024.0
+        )
+    }
+}
+
+/// Clusters and collapses near-duplicate memories.
+pub struct Consolidator {
+    threshold: f32,
+    merger: Option<Arc<dyn MemoryMerger>>,
+    embedder: Option<EmbeddingGenerator>,
+    dry_run: bool,
+}
+
+impl Default for Consolidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Consolidator {
+    /// Create a consolidator that keeps the newest memory of each cluster.
+    pub fn new() -> Self {
+        Self {
+            threshold: DEFAULT_SIMILARITY_THRESHOLD,
+            merger: None,
+            embedder: None,
+            dry_run: false,
+        }
+    }
+
+    /// Set the similarity threshold for duplicates.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Merge clusters with `merger`, embedding merged text with `embedder`.
+    ///
+    /// Clusters whose merge fails fall back to keeping the newest memory.
+    pub fn with_merger(mut self, merger: Arc<dyn MemoryMerger>, embedder: EmbeddingGenerator) -> Self {
+        self.merger = Some(merger);
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Only report what would change; leave the store untouched.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Group `memories` into clusters of near-duplicates.
+    ///
+    /// Returns indices into `memories`, newest first within each cluster.
+    /// Memories without duplicates are not returned.
+    pub fn cluster(&self, memories: &[Memory]) -> Vec<Vec<usize>> {
+        let mut order: Vec<usize> = (0..memories.len()).collect();
+        order.sort_by(|&a, &b| memories[b].created_at.cmp(&memories[a].created_at));
+
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        for i in order {
+            let embedding = &memories[i].embedding;
+            if embedding.is_empty() {
+                continue;
+            }
+            let home = clusters.iter_mut().find(|cluster| {
+                let leader = &memories[cluster[0]].embedding;
+                leader.len() == embedding.len() && cosine_similarity(leader, embedding) >= self.threshold
+            });
+            match home {
+                Some(cluster) => cluster.push(i),
+                None => clusters.push(vec![i]),
+            }
+        }
+        clusters.retain(|cluster| cluster.len() > 1);
+        clusters
+    }
+
+    /// Consolidate the memories of every agent in `store`.
+    pub async fn consolidate_all(&self, store: &dyn MemoryStore) -> Result<ConsolidationReport> {
+        let mut report = ConsolidationReport::default();
+        for agent_id in store.list_agents().await? {
+            report.add(&self.consolidate_agent(store, &agent_id).await?);
+        }
+        Ok(report)
+    }
+
+    /// Consolidate the memories of one agent.
+    pub async fn consolidate_agent(&self, store: &dyn MemoryStore, agent_id: &str) -> Result<ConsolidationReport> {
+        let count = store.count(agent_id).await?;
+        let memories = store.list(agent_id, count).await?;
+        let clusters = self.cluster(&memories);
+
+        let bytes_before: usize = memories.iter().map(memory_size).sum();
+        let mut report = ConsolidationReport {
+            agents: 1,
+            memories_before: memories.len(),
+            memories_after: memories.len(),
+            clusters: clusters.len(),
+            bytes_before,
+            bytes_after: bytes_before,
+            ..Default::default()
+        };
+
+        for cluster in clusters {
+            let members: Vec<&Memory> = cluster.iter().map(|&i| &memories[i]).collect();
+            let (survivor, by_llm) = self.collapse(&members).await;
+            debug!(
+                agent_id = %agent_id,
+                kept = %survivor.id,
+                merged = members.len(),
+                "Consolidating duplicate memories"
+            );
+
+            report.memories_after -= members.len() - 1;
+            report.bytes_after = report.bytes_after + memory_size(&survivor)
+                - members.iter().map(|m| memory_size(m)).sum::<usize>();
+            if by_llm {
+                report.merged_by_llm += 1;
+            }
+
+            if !self.dry_run {
+                for duplicate in &members[1..] {
+                    store.delete(&duplicate.id).await?;
+                }
+                if by_llm {
+                    store.store(survivor).await?;
+                }
+            }
+        }
+
+        if report.clusters > 0 {
+            info!(agent_id = %agent_id, dry_run = self.dry_run, "Memory consolidation: {}", report);
+        }
+        Ok(report)
+    }
+
+    /// Collapse a cluster (newest first) into the memory that replaces it.
+    ///
+    /// Returns the memory and whether it was written by the merger.
+    async fn collapse(&self, members: &[&Memory]) -> (Memory, bool) {
+        let newest = members[0].clone();
+        let (Some(merger), Some(embedder)) = (&self.merger, &self.embedder) else {
+            return (newest, false);
+        };
+        if self.dry_run {
+            return (newest, true);
+        }
+
+        let contents: Vec<&str> = members.iter().map(|m| m.content.as_str()).collect();
+        let merged = match merger.merge(&contents).await {
+            Ok(merged) => merged,
+            Err(e) => {
+                warn!(memory = %newest.id, error = %e, "Memory merge failed, keeping newest");
+                return (newest, false);
+            }
+        };
+        let embedding = match embedder.embed(&merged).await {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                warn!(memory = %newest.id, error = %e, "Embedding merged memory failed, keeping newest");
+                return (newest, false);
+            }
+        };
+
+        let mut metadata: HashMap<String, serde_json::Value> = HashMap::new();
+        for member in members.iter().rev() {
+            metadata.extend(member.metadata.clone());
+        }
+        let sources: Vec<&str> = members.iter().map(|m| m.id.as_str()).collect();
+        metadata.insert(CONSOLIDATED_FROM_KEY.to_string(), serde_json::json!(sources));
+
+        let memory = Memory {
+            content: merged,
+            embedding,
+            metadata,
+            ..newest
+        };
+        (memory, true)
+    }
+}
+
+/// Approximate stored size of a memory.
+fn memory_size(memory: &Memory) -> usize {
+    memory.content.len() + memory.embedding.len() * std::mem::size_of::<f32>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::EmbeddingProvider;
+    use crate::local::LocalStore;
+    use chrono::{Duration, Utc};
+
+    fn memory(id: &str, content: &str, embedding: Vec<f32>, age_minutes: i64) -> Memory {
+        let mut memory = Memory::with_id(id, "agent-1", content, embedding);
+        memory.created_at = Utc::now() - Duration::minutes(age_minutes);
+        memory
+    }
+
+    fn sample() -> Vec<Memory> {
+        vec![
+            memory("old", "Tests use pytest", vec![1.0, 0.0, 0.0], 30),
+            memory("new", "Tests run with pytest", vec![0.99, 0.05, 0.0], 5),
+            memory("other", "Deploys go through CI", vec![0.0, 1.0, 0.0], 10),
+        ]
+    }
+
+    struct JoinMerger;
+
+    #[async_trait]
+    impl MemoryMerger for JoinMerger {
+        async fn merge(&self, contents: &[&str]) -> Result<String> {
+            Ok(contents.join(" / "))
+        }
+    }
+
+    #[test]
+    fn test_cluster_groups_similar_newest_first() {
+        let memories = sample();
+        let clusters = Consolidator::new().cluster(&memories);
+        assert_eq!(clusters, vec![vec![1, 0]]);
+
+        assert!(Consolidator::new().with_threshold(0.9999).cluster(&memories).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_keep_newest_and_dry_run() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = LocalStore::new(dir.path().to_path_buf()).await.unwrap();
+        for memory in sample() {
+            store.store(memory).await.unwrap();
+        }
+
+        let report = Consolidator::new()
+            .with_dry_run(true)
+            .consolidate_all(&store)
+            .await
+            .unwrap();
+        assert_eq!(report.removed(), 1);
+        assert_eq!(store.count("agent-1").await.unwrap(), 3);
+
+        let report = Consolidator::new().consolidate_all(&store).await.unwrap();
+        assert_eq!((report.memories_before, report.memories_after, report.clusters), (3, 2, 1));
+        assert!(report.bytes_saved() > 0);
+        assert!(store.get("old").await.unwrap().is_none());
+        assert!(store.get("new").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_llm_merge_replaces_cluster() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = LocalStore::new(dir.path().to_path_buf()).await.unwrap();
+        for memory in sample() {
+            store.store(memory).await.unwrap();
+        }
+
+        let embedder = EmbeddingGenerator::new(EmbeddingProvider::HashBased { dimension: 3 });
+        let report = Consolidator::new()
+            .with_merger(Arc::new(JoinMerger), embedder)
+            .consolidate_agent(&store, "agent-1")
+            .await
+            .unwrap();
+        assert_eq!(report.merged_by_llm, 1);
+
+        let merged = store.get("new").await.unwrap().unwrap();
+        assert_eq!(merged.content, "Tests run with pytest / Tests use pytest");
+        assert_eq!(
+            merged.get_metadata(CONSOLIDATED_FROM_KEY),
+            Some(&serde_json::json!(["new", "old"]))
+        );
+        assert_eq!(store.count("agent-1").await.unwrap(), 2);
+    }
+}
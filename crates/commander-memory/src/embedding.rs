@@ -2,6 +2,14 @@
 //!
 //! Supports multiple embedding providers with fallback to hash-based embeddings
 //! when no API key is available (useful for testing).
+//!
+//! The provider can be chosen explicitly with `COMMANDER_EMBEDDING_PROVIDER`
+//! (`openai`, `openrouter`, `local`, `hash`). The `local` provider runs a small
+//! sentence-embedding model (MiniLM by default) on-device through an Ollama
+//! server, so memory search keeps real semantic quality without network access.
+//! Text sent to the OpenAI and OpenRouter APIs has its secrets redacted first.
+
+use commander_core::redact;
 
 use crate::error::{MemoryError, Result};
 use crate::memory::DEFAULT_EMBEDDING_DIM;
@@ -22,6 +30,24 @@ const OPENAI_API_URL: &str = "https://api.openai.com/v1/embeddings";
 /// OpenRouter embedding API endpoint.
 const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/embeddings";
 
+/// Environment variable selecting the embedding provider.
+pub const EMBEDDING_PROVIDER_ENV: &str = "COMMANDER_EMBEDDING_PROVIDER";
+
+/// Environment variable overriding the embedding model.
+pub const EMBEDDING_MODEL_ENV: &str = "COMMANDER_EMBEDDING_MODEL";
+
+/// Environment variable overriding the local embedding server URL.
+pub const LOCAL_EMBEDDING_URL_ENV: &str = "COMMANDER_LOCAL_EMBEDDING_URL";
+
+/// Default on-device embedding model (all-MiniLM-L6-v2).
+pub const DEFAULT_LOCAL_MODEL: &str = "all-minilm";
+
+/// Default local embedding server (Ollama).
+pub const DEFAULT_LOCAL_URL: &str = "http://localhost:11434";
+
+/// Maximum number of texts sent in a single embedding request.
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
 /// Embedding provider configuration.
 #[derive(Debug, Clone)]
 pub enum EmbeddingProvider {
@@ -29,6 +55,12 @@ pub enum EmbeddingProvider {
     OpenAI { api_key: String, model: String },
     /// Use OpenRouter API.
     OpenRouter { api_key: String, model: String },
+    /// Use an on-device model served by a local Ollama server.
+    Local {
+        base_url: String,
+        model: String,
+        dimension: usize,
+    },
     /// Use hash-based fake embeddings (for testing only).
     HashBased { dimension: usize },
 }
@@ -36,24 +68,92 @@ pub enum EmbeddingProvider {
 impl EmbeddingProvider {
     /// Create provider from environment variables.
     ///
-    /// Priority:
+    /// If `COMMANDER_EMBEDDING_PROVIDER` is set it selects the provider
+    /// (`COMMANDER_EMBEDDING_MODEL` overrides the model). Otherwise:
     /// 1. OPENAI_API_KEY -> OpenAI
     /// 2. OPENROUTER_API_KEY -> OpenRouter
     /// 3. None -> HashBased fallback
     pub fn from_env() -> Self {
-        if let Ok(api_key) = std::env::var(OPENAI_API_KEY_ENV) {
+        let provider = std::env::var(EMBEDDING_PROVIDER_ENV).ok();
+        let model = std::env::var(EMBEDDING_MODEL_ENV).ok();
+        let local_url = std::env::var(LOCAL_EMBEDDING_URL_ENV).ok();
+
+        Self::from_settings(
+            provider.as_deref(),
+            model.as_deref(),
+            local_url.as_deref(),
+            std::env::var(OPENAI_API_KEY_ENV).ok(),
+            std::env::var(OPENROUTER_API_KEY_ENV).ok(),
+        )
+    }
+
+    /// Resolve a provider from explicit settings (see [`Self::from_env`]).
+    pub fn from_settings(
+        provider: Option<&str>,
+        model: Option<&str>,
+        local_url: Option<&str>,
+        openai_key: Option<String>,
+        openrouter_key: Option<String>,
+    ) -> Self {
+        match provider.map(|p| p.trim().to_lowercase()).as_deref() {
+            Some("local") => {
+                let model = model.unwrap_or(DEFAULT_LOCAL_MODEL);
+                debug!(model = %model, "Using local embedding provider");
+                return Self::Local {
+                    base_url: local_url
+                        .unwrap_or(DEFAULT_LOCAL_URL)
+                        .trim_end_matches('/')
+                        .to_string(),
+                    model: model.to_string(),
+                    dimension: local_model_dimension(model),
+                };
+            }
+            Some("hash") => {
+                return Self::HashBased {
+                    dimension: DEFAULT_EMBEDDING_DIM,
+                };
+            }
+            Some("openai") if openai_key.is_none() => {
+                warn!(
+                    "OpenAI embeddings requested but {} is not set",
+                    OPENAI_API_KEY_ENV
+                );
+            }
+            Some("openrouter") if openrouter_key.is_none() => {
+                warn!(
+                    "OpenRouter embeddings requested but {} is not set",
+                    OPENROUTER_API_KEY_ENV
+                );
+            }
+            Some("openrouter") => {
+                return Self::OpenRouter {
+                    api_key: openrouter_key.unwrap_or_default(),
+                    model: model
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("openai/{}", DEFAULT_MODEL)),
+                };
+            }
+            Some("openai") | None => {}
+            Some(other) => {
+                warn!(provider = %other, "Unknown embedding provider, using default selection");
+            }
+        }
+
+        if let Some(api_key) = openai_key {
             debug!("Using OpenAI embedding provider");
             return Self::OpenAI {
                 api_key,
-                model: DEFAULT_MODEL.to_string(),
+                model: model.unwrap_or(DEFAULT_MODEL).to_string(),
             };
         }
 
-        if let Ok(api_key) = std::env::var(OPENROUTER_API_KEY_ENV) {
+        if let Some(api_key) = openrouter_key {
             debug!("Using OpenRouter embedding provider");
             return Self::OpenRouter {
                 api_key,
-                model: format!("openai/{}", DEFAULT_MODEL),
+                model: model
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("openai/{}", DEFAULT_MODEL)),
             };
         }
 
@@ -63,6 +163,15 @@ impl EmbeddingProvider {
         }
     }
 
+    /// Create a local provider using the default MiniLM model and Ollama URL.
+    pub fn local() -> Self {
+        Self::Local {
+            base_url: DEFAULT_LOCAL_URL.to_string(),
+            model: DEFAULT_LOCAL_MODEL.to_string(),
+            dimension: local_model_dimension(DEFAULT_LOCAL_MODEL),
+        }
+    }
+
     /// Check if this provider uses real embeddings (API-based).
     pub fn is_real(&self) -> bool {
         !matches!(self, Self::HashBased { .. })
@@ -72,9 +181,39 @@ impl EmbeddingProvider {
     pub fn dimension(&self) -> usize {
         match self {
             Self::OpenAI { .. } | Self::OpenRouter { .. } => DEFAULT_EMBEDDING_DIM,
-            Self::HashBased { dimension } => *dimension,
+            Self::Local { dimension, .. } | Self::HashBased { dimension } => *dimension,
         }
     }
+
+    /// Identifier of the model producing this provider's vectors, e.g.
+    /// `openai:text-embedding-3-small` or `hash:1536`.
+    ///
+    /// OpenAI models reached through OpenRouter share the OpenAI identifier,
+    /// since their vectors are interchangeable.
+    pub fn model_id(&self) -> String {
+        match self {
+            Self::OpenAI { model, .. } => format!("openai:{}", model),
+            Self::OpenRouter { model, .. } => match model.strip_prefix("openai/") {
+                Some(openai) => format!("openai:{}", openai),
+                None => format!("openrouter:{}", model),
+            },
+            Self::Local { model, .. } => format!("local:{}", model),
+            Self::HashBased { dimension } => format!("hash:{}", dimension),
+        }
+    }
+}
+
+/// Output dimension of well-known local embedding models.
+///
+/// Unknown models are assumed to be MiniLM-sized.
+pub fn local_model_dimension(model: &str) -> usize {
+    let name = model.split(':').next().unwrap_or(model);
+    match name {
+        "nomic-embed-text" => 768,
+        "mxbai-embed-large" | "bge-large" => 1024,
+        "snowflake-arctic-embed" => 1024,
+        _ => 384,
+    }
 }
 
 /// Generate embeddings for text content.
@@ -82,6 +221,7 @@ impl EmbeddingProvider {
 pub struct EmbeddingGenerator {
     provider: EmbeddingProvider,
     client: reqwest::Client,
+    batch_size: usize,
 }
 
 impl EmbeddingGenerator {
@@ -90,9 +230,21 @@ impl EmbeddingGenerator {
         Self {
             provider,
             client: reqwest::Client::new(),
+            batch_size: DEFAULT_BATCH_SIZE,
         }
     }
 
+    /// Set the maximum number of texts per embedding request.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Get the configured provider.
+    pub fn provider(&self) -> &EmbeddingProvider {
+        &self.provider
+    }
+
     /// Create a generator from environment variables.
     pub fn from_env() -> Self {
         Self::new(EmbeddingProvider::from_env())
@@ -108,28 +260,67 @@ impl EmbeddingGenerator {
         self.provider.dimension()
     }
 
+    /// Identifier of the model producing the embeddings.
+    pub fn model_id(&self) -> String {
+        self.provider.model_id()
+    }
+
     /// Generate an embedding for the given text.
     pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
         match &self.provider {
             EmbeddingProvider::OpenAI { api_key, model } => {
-                self.embed_openai(text, api_key, model).await
+                self.embed_openai(&redact(text), api_key, model).await
             }
             EmbeddingProvider::OpenRouter { api_key, model } => {
-                self.embed_openrouter(text, api_key, model).await
+                self.embed_openrouter(&redact(text), api_key, model).await
+            }
+            EmbeddingProvider::Local {
+                base_url, model, ..
+            } => self
+                .embed_batch_local(&[text], base_url, model)
+                .await?
+                .pop()
+                .ok_or_else(|| MemoryError::EmbeddingError("Empty embedding response".to_string())),
+            EmbeddingProvider::HashBased { dimension } => {
+                Ok(hash_based_embedding(text, *dimension))
             }
-            EmbeddingProvider::HashBased { dimension } => Ok(hash_based_embedding(text, *dimension)),
         }
     }
 
-    /// Generate embeddings for multiple texts in a batch.
+    /// Generate embeddings for multiple texts.
+    ///
+    /// Texts are sent in requests of at most `batch_size` inputs; results are
+    /// returned in the same order as `texts`.
     pub async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size) {
+            let batch = self.embed_chunk(chunk).await?;
+            if batch.len() != chunk.len() {
+                return Err(MemoryError::EmbeddingError(format!(
+                    "Expected {} embeddings, got {}",
+                    chunk.len(),
+                    batch.len()
+                )));
+            }
+            embeddings.extend(batch);
+        }
+        Ok(embeddings)
+    }
+
+    /// Embed a single request-sized chunk of texts.
+    async fn embed_chunk(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let redacted: Vec<String> = texts.iter().map(|t| redact(t)).collect();
+        let redacted: Vec<&str> = redacted.iter().map(String::as_str).collect();
         match &self.provider {
             EmbeddingProvider::OpenAI { api_key, model } => {
-                self.embed_batch_openai(texts, api_key, model).await
+                self.embed_batch_openai(&redacted, api_key, model).await
             }
             EmbeddingProvider::OpenRouter { api_key, model } => {
-                self.embed_batch_openrouter(texts, api_key, model).await
+                self.embed_batch_openrouter(&redacted, api_key, model).await
             }
+            EmbeddingProvider::Local {
+                base_url, model, ..
+            } => self.embed_batch_local(texts, base_url, model).await,
             EmbeddingProvider::HashBased { dimension } => Ok(texts
                 .iter()
                 .map(|t| hash_based_embedding(t, *dimension))
@@ -270,6 +461,46 @@ impl EmbeddingGenerator {
 
         parse_batch_embedding_response(&json)
     }
+
+    async fn embed_batch_local(
+        &self,
+        texts: &[&str],
+        base_url: &str,
+        model: &str,
+    ) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/api/embed", base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({
+                "model": model,
+                "input": texts
+            }))
+            .send()
+            .await
+            .map_err(|e| {
+                MemoryError::EmbeddingError(format!(
+                    "Local embedding server not reachable at {}: {}",
+                    base_url, e
+                ))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(MemoryError::EmbeddingError(format!(
+                "Local embedding error {} (is '{}' pulled?): {}",
+                status, model, text
+            )));
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| MemoryError::EmbeddingError(e.to_string()))?;
+
+        parse_local_embedding_response(&json)
+    }
 }
 
 fn parse_embedding_response(json: &serde_json::Value) -> Result<Vec<f32>> {
@@ -292,13 +523,41 @@ fn parse_batch_embedding_response(json: &serde_json::Value) -> Result<Vec<Vec<f3
         .as_array()
         .ok_or_else(|| MemoryError::EmbeddingError("Invalid response format".to_string()))?;
 
-    data.iter()
+    // The API may return items out of order; `index` refers to the input position.
+    let mut items: Vec<&serde_json::Value> = data.iter().collect();
+    items.sort_by_key(|item| item["index"].as_u64().unwrap_or(u64::MAX));
+
+    items
+        .into_iter()
         .map(|item| {
-            let embedding = item["embedding"]
-                .as_array()
-                .ok_or_else(|| MemoryError::EmbeddingError("Invalid embedding format".to_string()))?;
+            let embedding = item["embedding"].as_array().ok_or_else(|| {
+                MemoryError::EmbeddingError("Invalid embedding format".to_string())
+            })?;
+
+            embedding
+                .iter()
+                .map(|v| {
+                    v.as_f64().map(|f| f as f32).ok_or_else(|| {
+                        MemoryError::EmbeddingError("Invalid embedding value".to_string())
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Parse an Ollama `/api/embed` response (`{"embeddings": [[...], ...]}`).
+fn parse_local_embedding_response(json: &serde_json::Value) -> Result<Vec<Vec<f32>>> {
+    let embeddings = json["embeddings"]
+        .as_array()
+        .ok_or_else(|| MemoryError::EmbeddingError("Invalid response format".to_string()))?;
 
+    embeddings
+        .iter()
+        .map(|embedding| {
             embedding
+                .as_array()
+                .ok_or_else(|| MemoryError::EmbeddingError("Invalid embedding format".to_string()))?
                 .iter()
                 .map(|v| {
                     v.as_f64().map(|f| f as f32).ok_or_else(|| {
@@ -431,6 +690,117 @@ mod tests {
         assert_eq!(embedding.len(), 64);
     }
 
+    #[test]
+    fn test_provider_from_settings_local() {
+        let provider = EmbeddingProvider::from_settings(Some("local"), None, None, None, None);
+        match &provider {
+            EmbeddingProvider::Local {
+                base_url,
+                model,
+                dimension,
+            } => {
+                assert_eq!(base_url, DEFAULT_LOCAL_URL);
+                assert_eq!(model, DEFAULT_LOCAL_MODEL);
+                assert_eq!(*dimension, 384);
+            }
+            other => panic!("expected local provider, got {:?}", other),
+        }
+        assert!(provider.is_real());
+
+        let provider = EmbeddingProvider::from_settings(
+            Some("LOCAL"),
+            Some("nomic-embed-text:latest"),
+            Some("http://gpu-box:11434/"),
+            Some("sk-test".to_string()),
+            None,
+        );
+        match provider {
+            EmbeddingProvider::Local {
+                base_url,
+                dimension,
+                ..
+            } => {
+                assert_eq!(base_url, "http://gpu-box:11434");
+                assert_eq!(dimension, 768);
+            }
+            other => panic!("expected local provider, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_provider_from_settings_priority() {
+        let provider = EmbeddingProvider::from_settings(
+            None,
+            None,
+            None,
+            Some("sk-openai".to_string()),
+            Some("sk-or".to_string()),
+        );
+        assert!(matches!(provider, EmbeddingProvider::OpenAI { .. }));
+
+        let provider = EmbeddingProvider::from_settings(
+            Some("openrouter"),
+            None,
+            None,
+            Some("sk-openai".to_string()),
+            Some("sk-or".to_string()),
+        );
+        assert!(matches!(provider, EmbeddingProvider::OpenRouter { .. }));
+
+        let provider = EmbeddingProvider::from_settings(
+            Some("hash"),
+            None,
+            None,
+            Some("sk".to_string()),
+            None,
+        );
+        assert!(!provider.is_real());
+
+        // Requested provider without a key falls back to default selection
+        let provider = EmbeddingProvider::from_settings(Some("openai"), None, None, None, None);
+        assert!(!provider.is_real());
+    }
+
+    #[test]
+    fn test_parse_batch_response_orders_by_index() {
+        let json = serde_json::json!({
+            "data": [
+                {"index": 1, "embedding": [0.0, 1.0]},
+                {"index": 0, "embedding": [1.0, 0.0]}
+            ]
+        });
+        let embeddings = parse_batch_embedding_response(&json).unwrap();
+        assert_eq!(embeddings, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_parse_local_response() {
+        let json = serde_json::json!({
+            "model": "all-minilm",
+            "embeddings": [[0.5, 0.25], [0.1, 0.2]]
+        });
+        let embeddings = parse_local_embedding_response(&json).unwrap();
+        assert_eq!(embeddings.len(), 2);
+        assert_eq!(embeddings[0], vec![0.5, 0.25]);
+
+        assert!(parse_local_embedding_response(&serde_json::json!({"error": "x"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_embed_batch_chunks_preserve_order() {
+        let gen = EmbeddingGenerator::new(EmbeddingProvider::HashBased { dimension: 16 })
+            .with_batch_size(2);
+        let texts = ["a", "b", "c", "d", "e"];
+
+        let embeddings = gen.embed_batch(&texts).await.unwrap();
+        assert_eq!(embeddings.len(), texts.len());
+        for (text, embedding) in texts.iter().zip(&embeddings) {
+            assert_eq!(embedding, &gen.embed(text).await.unwrap());
+        }
+
+        assert!(gen.embed_batch(&[]).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_hash_based_embed_batch() {
         let gen = EmbeddingGenerator::new(EmbeddingProvider::HashBased { dimension: 32 });
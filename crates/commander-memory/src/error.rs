@@ -25,6 +25,10 @@ pub enum MemoryError {
     #[error("configuration error: {0}")]
     ConfigError(String),
 
+    /// A memory export file is malformed or from a newer version.
+    #[error("invalid memory export: {0}")]
+    InvalidExport(String),
+
     /// I/O error.
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -0,0 +1,167 @@
+//! Portable memory export format.
+//!
+//! Memories are exported as JSONL: a header line identifying the format and
+//! its version, then one [`Memory`] per line with its ID, agent ID, content,
+//! embedding, metadata and creation time. Because embeddings are included, an
+//! export can be imported into another machine's store, or from a
+//! [`LocalStore`](crate::LocalStore) into a [`QdrantStore`](crate::QdrantStore),
+//! without embedding anything again.
+//!
+//! ```text
+//! {"format":"ai-commander-memory","version":1,"exported_at":"2026-10-17T09:00:00Z","count":2,"embedding_dim":1536}
+//! {"id":"…","agent_id":"session-api","content":"…","embedding":[…],"metadata":{},"created_at":"…"}
+//! {"id":"…","agent_id":"user-agent","content":"…","embedding":[…],"metadata":{},"created_at":"…"}
+//! ```
+
+use std::io::{BufRead, Write};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemoryError, Result};
+use crate::memory::Memory;
+
+/// Value of the header's `format` field.
+pub const EXPORT_FORMAT: &str = "ai-commander-memory";
+
+/// Current export format version. Newer versions are rejected on import.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// First line of an export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportHeader {
+    /// Always [`EXPORT_FORMAT`].
+    pub format: String,
+    /// Format version the file was written with.
+    pub version: u32,
+    /// When the export was written.
+    pub exported_at: DateTime<Utc>,
+    /// Number of memories that follow.
+    pub count: usize,
+    /// Dimension shared by every embedding, `None` if empty or mixed.
+    pub embedding_dim: Option<usize>,
+}
+
+impl ExportHeader {
+    /// Header describing `memories`.
+    pub fn for_memories(memories: &[Memory]) -> Self {
+        let mut dims = memories.iter().map(|m| m.embedding.len());
+        let embedding_dim = dims.next().filter(|first| dims.all(|dim| dim == *first));
+        Self {
+            format: EXPORT_FORMAT.to_string(),
+            version: EXPORT_VERSION,
+            exported_at: Utc::now(),
+            count: memories.len(),
+            embedding_dim,
+        }
+    }
+}
+
+/// Write `memories` to `writer` in the export format.
+pub fn write_export<W: Write>(mut writer: W, memories: &[Memory]) -> Result<ExportHeader> {
+    let header = ExportHeader::for_memories(memories);
+    serde_json::to_writer(&mut writer, &header)?;
+    writeln!(writer)?;
+    for memory in memories {
+        serde_json::to_writer(&mut writer, memory)?;
+        writeln!(writer)?;
+    }
+    writer.flush()?;
+    Ok(header)
+}
+
+/// Read an export written by [`write_export`].
+///
+/// Fails on a missing or unknown header, a newer format version, or a line
+/// that is not a memory with an embedding.
+pub fn read_export<R: BufRead>(reader: R) -> Result<(ExportHeader, Vec<Memory>)> {
+    let mut lines = reader.lines().enumerate().filter(|(_, line)| {
+        line.as_ref().map(|l| !l.trim().is_empty()).unwrap_or(true)
+    });
+
+    let (_, first) = lines
+        .next()
+        .ok_or_else(|| MemoryError::InvalidExport("empty file".to_string()))?;
+    let header: ExportHeader = serde_json::from_str(&first?)
+        .map_err(|e| MemoryError::InvalidExport(format!("bad header: {}", e)))?;
+    if header.format != EXPORT_FORMAT {
+        return Err(MemoryError::InvalidExport(format!("unknown format '{}'", header.format)));
+    }
+    if header.version > EXPORT_VERSION {
+        return Err(MemoryError::InvalidExport(format!(
+            "version {} is newer than supported version {}",
+            header.version, EXPORT_VERSION
+        )));
+    }
+
+    let mut memories = Vec::with_capacity(header.count);
+    for (index, line) in lines {
+        let memory: Memory = serde_json::from_str(&line?)
+            .map_err(|e| MemoryError::InvalidExport(format!("line {}: {}", index + 1, e)))?;
+        if memory.embedding.is_empty() {
+            return Err(MemoryError::InvalidExport(format!(
+                "line {}: memory {} has no embedding",
+                index + 1,
+                memory.id
+            )));
+        }
+        memories.push(memory);
+    }
+    Ok((header, memories))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_round_trip() {
+        let memories = vec![
+            Memory::new("session-api", "Uses sqlx for queries", vec![0.1, 0.2, 0.3])
+                .with_metadata("kind", serde_json::json!("fact")),
+            Memory::new("user-agent", "Prefers small PRs", vec![0.4, 0.5, 0.6]),
+        ];
+
+        let mut buf = Vec::new();
+        let header = write_export(&mut buf, &memories).unwrap();
+        assert_eq!(header.count, 2);
+        assert_eq!(header.embedding_dim, Some(3));
+        assert_eq!(String::from_utf8_lossy(&buf).lines().count(), 3);
+
+        let (read_header, read) = read_export(buf.as_slice()).unwrap();
+        assert_eq!(read_header, header);
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].id, memories[0].id);
+        assert_eq!(read[0].agent_id, "session-api");
+        assert_eq!(read[0].embedding, vec![0.1, 0.2, 0.3]);
+        assert_eq!(read[0].get_metadata("kind"), Some(&serde_json::json!("fact")));
+        assert_eq!(read[1].created_at, memories[1].created_at);
+    }
+
+    #[test]
+    fn test_header_mixed_dimensions() {
+        let memories = vec![
+            Memory::new("a", "x", vec![0.1; 3]),
+            Memory::new("a", "y", vec![0.1; 4]),
+        ];
+        assert_eq!(ExportHeader::for_memories(&memories).embedding_dim, None);
+        assert_eq!(ExportHeader::for_memories(&[]).embedding_dim, None);
+    }
+
+    #[test]
+    fn test_read_export_rejects_bad_input() {
+        assert!(read_export("".as_bytes()).is_err());
+        assert!(read_export(r#"{"id":"x"}"#.as_bytes()).is_err());
+
+        let newer = format!(
+            r#"{{"format":"{}","version":{},"exported_at":"2026-01-01T00:00:00Z","count":0,"embedding_dim":null}}"#,
+            EXPORT_FORMAT,
+            EXPORT_VERSION + 1
+        );
+        assert!(read_export(newer.as_bytes()).is_err());
+
+        let mut buf = Vec::new();
+        write_export(&mut buf, &[Memory::new("a", "no vector", vec![])]).unwrap();
+        assert!(read_export(buf.as_slice()).is_err());
+    }
+}
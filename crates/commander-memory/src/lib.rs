@@ -49,6 +49,31 @@
 //! 2. **OpenRouter** (set `OPENROUTER_API_KEY`): Uses `openai/text-embedding-3-small`
 //! 3. **Hash-based** (no API key): Deterministic hash-based embeddings for testing
 //!
+//! Set `COMMANDER_EMBEDDING_PROVIDER=local` to embed on-device instead, using a
+//! MiniLM model (`all-minilm`, 384 dimensions) served by a local Ollama server.
+//! `COMMANDER_EMBEDDING_MODEL` and `COMMANDER_LOCAL_EMBEDDING_URL` override the
+//! model and server. Use [`EmbeddingGenerator::embed_batch`] to embed many texts
+//! with batched requests.
+//!
+//! # Consolidation
+//!
+//! Agents store near-duplicate memories constantly. A [`Consolidator`]
+//! clusters each agent's memories by cosine similarity and keeps only the
+//! newest of each cluster, or merges the cluster with an [`LlmMerger`].
+//!
+//! # Backfill
+//!
+//! Memories keep the vectors of the provider they were stored with. After
+//! switching providers, a [`Backfill`] re-embeds the memories whose vectors
+//! have another dimension or model, in rate-limited batches, and swaps the new
+//! vectors in with one write.
+//!
+//! # Export and Import
+//!
+//! [`MemoryStore::export_all`] and [`MemoryStore::import_all`] move every
+//! memory between stores with its embedding, and [`write_export`] and
+//! [`read_export`] save them in a versioned JSONL file (see [`export`]).
+//!
 //! # Agent Isolation and Access Control
 //!
 //! Memories are tagged with an `agent_id` for isolation. The crate provides
@@ -84,20 +109,33 @@
 //! # }
 //! ```
 
+pub mod backfill;
+pub mod consolidate;
 pub mod embedding;
 pub mod error;
+pub mod export;
 pub mod local;
 pub mod memory;
 pub mod qdrant;
 pub mod store;
 
 // Re-export commonly used items
-pub use embedding::{cosine_similarity, EmbeddingGenerator, EmbeddingProvider};
+pub use backfill::{
+    Backfill, BackfillProgress, BackfillReport, Staleness, DEFAULT_BACKFILL_BATCH_SIZE,
+    EMBEDDING_MODEL_KEY,
+};
+pub use consolidate::{
+    ConsolidationReport, Consolidator, LlmMerger, MemoryMerger, DEFAULT_SIMILARITY_THRESHOLD,
+};
+pub use embedding::{
+    cosine_similarity, local_model_dimension, EmbeddingGenerator, EmbeddingProvider,
+};
 pub use error::{MemoryError, Result};
+pub use export::{read_export, write_export, ExportHeader, EXPORT_FORMAT, EXPORT_VERSION};
 pub use local::LocalStore;
 pub use memory::{Memory, SearchResult, DEFAULT_EMBEDDING_DIM};
 pub use qdrant::QdrantStore;
-pub use store::{AccessControlledStore, AccessLevel, MemoryStore};
+pub use store::{AccessControlledStore, AccessLevel, MemoryFilter, MemoryStore};
 
 /// Create the default memory store.
 ///
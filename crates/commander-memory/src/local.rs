@@ -15,7 +15,7 @@ use tracing::{debug, info};
 use crate::embedding::cosine_similarity;
 use crate::error::{MemoryError, Result};
 use crate::memory::{Memory, SearchResult};
-use crate::store::MemoryStore;
+use crate::store::{MemoryFilter, MemoryStore};
 
 /// Local file-based memory store.
 ///
@@ -181,6 +181,52 @@ impl MemoryStore for LocalStore {
         }
         self.save().await
     }
+
+    async fn list_agents(&self) -> Result<Vec<String>> {
+        let memories = self.memories.read().await;
+        let agents: std::collections::BTreeSet<&str> =
+            memories.values().map(|m| m.agent_id.as_str()).collect();
+        Ok(agents.into_iter().map(str::to_string).collect())
+    }
+
+    async fn export_all(&self) -> Result<Vec<Memory>> {
+        let memories = self.memories.read().await;
+        let mut all: Vec<Memory> = memories.values().cloned().collect();
+        all.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(all)
+    }
+
+    async fn import_all(&self, memories: Vec<Memory>) -> Result<usize> {
+        let total = memories.len();
+        {
+            let mut store = self.memories.write().await;
+            for memory in memories {
+                store.insert(memory.id.clone(), memory);
+            }
+        }
+        // One save instead of one per memory
+        self.save().await?;
+        Ok(total)
+    }
+
+    async fn count_matching(&self, filter: &MemoryFilter) -> Result<usize> {
+        let memories = self.memories.read().await;
+        Ok(memories.values().filter(|m| filter.matches(m)).count())
+    }
+
+    async fn delete_matching(&self, filter: &MemoryFilter) -> Result<usize> {
+        let deleted = {
+            let mut memories = self.memories.write().await;
+            let before = memories.len();
+            memories.retain(|_, m| !filter.matches(m));
+            before - memories.len()
+        };
+        // One save, so the file never holds a partial deletion
+        if deleted > 0 {
+            self.save().await?;
+        }
+        Ok(deleted)
+    }
 }
 
 #[cfg(test)]
@@ -209,6 +255,25 @@ mod tests {
         assert_eq!(retrieved.unwrap().content, "test content");
     }
 
+    #[tokio::test]
+    async fn test_export_import_between_stores() {
+        let (source, _src_dir) = create_test_store().await;
+        source.store(Memory::new("agent-1", "first", vec![0.1; 10])).await.unwrap();
+        source.store(Memory::new("agent-2", "second", vec![0.2; 10])).await.unwrap();
+
+        let exported = source.export_all().await.unwrap();
+        assert_eq!(exported.len(), 2);
+
+        let (target, target_dir) = create_test_store().await;
+        assert_eq!(target.import_all(exported.clone()).await.unwrap(), 2);
+        assert_eq!(target.list_agents().await.unwrap(), vec!["agent-1", "agent-2"]);
+
+        // Persisted with IDs and embeddings intact
+        let reloaded = LocalStore::new(target_dir.path().to_path_buf()).await.unwrap();
+        let memory = reloaded.get(&exported[1].id).await.unwrap().unwrap();
+        assert_eq!(memory.embedding, exported[1].embedding);
+    }
+
     #[tokio::test]
     async fn test_delete() {
         let (store, _dir) = create_test_store().await;
@@ -359,6 +424,30 @@ mod tests {
         assert_eq!(store.count("agent-2").await.unwrap(), 1);
     }
 
+    #[tokio::test]
+    async fn test_delete_matching_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_path_buf();
+        let filter = MemoryFilter::agents(["agent-1", "agent-2"]);
+
+        {
+            let store = LocalStore::new(path.clone()).await.unwrap();
+            let embedding = vec![0.1; 10];
+            for agent in ["agent-1", "agent-2", "agent-3"] {
+                store
+                    .store(Memory::new(agent, "m", embedding.clone()))
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(store.count_matching(&filter).await.unwrap(), 2);
+            assert_eq!(store.delete_matching(&filter).await.unwrap(), 2);
+        }
+
+        let store = LocalStore::new(path).await.unwrap();
+        assert_eq!(store.count_matching(&filter).await.unwrap(), 0);
+        assert_eq!(store.count("agent-3").await.unwrap(), 1);
+    }
+
     #[tokio::test]
     async fn test_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -18,7 +18,7 @@ use tracing::{debug, info};
 
 use crate::error::{MemoryError, Result};
 use crate::memory::{Memory, SearchResult, DEFAULT_EMBEDDING_DIM};
-use crate::store::MemoryStore;
+use crate::store::{MemoryFilter, MemoryStore};
 
 /// Default collection name for memories.
 const COLLECTION_NAME: &str = "memories";
@@ -29,6 +29,9 @@ const FIELD_CONTENT: &str = "content";
 const FIELD_METADATA: &str = "metadata";
 const FIELD_CREATED_AT: &str = "created_at";
 
+/// Points fetched per page when scrolling the whole collection.
+const SCROLL_PAGE_SIZE: u32 = 256;
+
 /// Qdrant-based memory store.
 ///
 /// Requires a running Qdrant server. For configuration:
@@ -74,6 +77,17 @@ impl QdrantStore {
         Self::new(&url, api_key.as_deref()).await
     }
 
+    /// Create a store from environment variables whose collection, if it
+    /// has to be created, holds vectors of `dimension`.
+    ///
+    /// Used when importing memories embedded with another model.
+    pub async fn from_env_with_dimension(dimension: usize) -> Result<Self> {
+        let url =
+            std::env::var("QDRANT_URL").unwrap_or_else(|_| "http://localhost:6334".to_string());
+        let api_key = std::env::var("QDRANT_API_KEY").ok();
+        Self::with_config(&url, api_key.as_deref(), COLLECTION_NAME, dimension).await
+    }
+
     /// Create a store with custom collection name and dimension.
     pub async fn with_config(
         url: &str,
@@ -251,6 +265,16 @@ impl QdrantStore {
     fn make_agent_filter(&self, agent_id: &str) -> Filter {
         Filter::must([Condition::matches(FIELD_AGENT_ID, agent_id.to_string())])
     }
+
+    /// Create a filter matching any agent of a [`MemoryFilter`].
+    fn make_memory_filter(&self, filter: &MemoryFilter) -> Filter {
+        Filter::should(
+            filter
+                .agent_ids
+                .iter()
+                .map(|id| Condition::matches(FIELD_AGENT_ID, id.clone())),
+        )
+    }
 }
 
 #[async_trait]
@@ -396,6 +420,103 @@ impl MemoryStore for QdrantStore {
 
         Ok(())
     }
+
+    async fn list_agents(&self) -> Result<Vec<String>> {
+        let mut agents = std::collections::BTreeSet::new();
+        let mut offset = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(&self.collection)
+                .limit(SCROLL_PAGE_SIZE)
+                .with_payload(true)
+                .with_vectors(false);
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+            let page = self
+                .client
+                .scroll(request)
+                .await
+                .map_err(|e| MemoryError::DatabaseError(e.to_string()))?;
+
+            agents.extend(
+                page.result
+                    .iter()
+                    .filter_map(|point| point.payload.get(FIELD_AGENT_ID)?.as_str().cloned()),
+            );
+            match page.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+        Ok(agents.into_iter().collect())
+    }
+
+    async fn export_all(&self) -> Result<Vec<Memory>> {
+        let mut memories = Vec::new();
+        let mut offset = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(&self.collection)
+                .limit(SCROLL_PAGE_SIZE)
+                .with_payload(true)
+                .with_vectors(true);
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+            let page = self
+                .client
+                .scroll(request)
+                .await
+                .map_err(|e| MemoryError::DatabaseError(e.to_string()))?;
+
+            memories.extend(page.result.iter().filter_map(|point| self.retrieved_to_memory(point)));
+            match page.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+        Ok(memories)
+    }
+
+    async fn import_all(&self, memories: Vec<Memory>) -> Result<usize> {
+        for batch in memories.chunks(SCROLL_PAGE_SIZE as usize) {
+            let points: Vec<PointStruct> = batch.iter().map(|m| self.memory_to_point(m)).collect();
+            self.client
+                .upsert_points(UpsertPointsBuilder::new(&self.collection, points))
+                .await
+                .map_err(|e| MemoryError::DatabaseError(e.to_string()))?;
+        }
+        Ok(memories.len())
+    }
+
+    async fn count_matching(&self, filter: &MemoryFilter) -> Result<usize> {
+        use qdrant_client::qdrant::CountPointsBuilder;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+        let result = self
+            .client
+            .count(CountPointsBuilder::new(&self.collection).filter(self.make_memory_filter(filter)))
+            .await
+            .map_err(|e| MemoryError::DatabaseError(e.to_string()))?;
+
+        Ok(result.result.map(|r| r.count as usize).unwrap_or(0))
+    }
+
+    async fn delete_matching(&self, filter: &MemoryFilter) -> Result<usize> {
+        // An empty should-filter matches every point
+        if filter.is_empty() {
+            return Ok(0);
+        }
+        let total = self.count_matching(filter).await?;
+
+        self.client
+            .delete_points(DeletePointsBuilder::new(&self.collection).points(self.make_memory_filter(filter)))
+            .await
+            .map_err(|e| MemoryError::DatabaseError(e.to_string()))?;
+
+        Ok(total)
+    }
 }
 
 // Note: Integration tests require a running Qdrant server
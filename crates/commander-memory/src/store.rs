@@ -33,6 +33,39 @@ pub enum AccessLevel {
     All,
 }
 
+/// Selects memories for bulk operations such as a purge.
+///
+/// A filter matches the memories of any of its agents; an empty filter
+/// matches nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryFilter {
+    /// Agents whose memories match.
+    pub agent_ids: Vec<String>,
+}
+
+impl MemoryFilter {
+    /// Filter matching the memories of `agent_ids`.
+    pub fn agents<I, S>(agent_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            agent_ids: agent_ids.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Whether `memory` matches the filter.
+    pub fn matches(&self, memory: &Memory) -> bool {
+        self.agent_ids.contains(&memory.agent_id)
+    }
+
+    /// Whether the filter matches nothing.
+    pub fn is_empty(&self) -> bool {
+        self.agent_ids.is_empty()
+    }
+}
+
 /// Trait for memory storage backends.
 ///
 /// Implementations must support basic CRUD operations plus semantic search.
@@ -115,6 +148,79 @@ pub trait MemoryStore: Send + Sync {
     /// * `agent_id` - The agent whose memories to delete
     async fn clear_agent(&self, agent_id: &str) -> Result<()>;
 
+    /// List the IDs of all agents that have memories.
+    ///
+    /// Stores that cannot enumerate agents return an empty list.
+    async fn list_agents(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Every memory in the store, across all agents, with its embedding.
+    ///
+    /// Backends override this to page through the store; the default lists
+    /// one agent at a time.
+    async fn export_all(&self) -> Result<Vec<Memory>> {
+        let mut memories = Vec::new();
+        for agent_id in self.list_agents().await? {
+            memories.extend(self.list(&agent_id, usize::MAX).await?);
+        }
+        Ok(memories)
+    }
+
+    /// Store `memories` as they are, keeping their IDs, agents and
+    /// embeddings. Memories with an existing ID are replaced.
+    ///
+    /// Returns how many were imported. Backends override this to write in
+    /// batches; the default stores one memory at a time.
+    async fn import_all(&self, memories: Vec<Memory>) -> Result<usize> {
+        let total = memories.len();
+        for memory in memories {
+            self.store(memory).await?;
+        }
+        Ok(total)
+    }
+
+    /// Count the memories matching `filter`.
+    async fn count_matching(&self, filter: &MemoryFilter) -> Result<usize> {
+        let mut total = 0;
+        for agent_id in &filter.agent_ids {
+            total += self.count(agent_id).await?;
+        }
+        Ok(total)
+    }
+
+    /// Search for similar memories of the agents in `filter`.
+    ///
+    /// Used to scope a search to a group of projects. Backends may override
+    /// this with a single query; the default searches one agent at a time
+    /// and merges the results.
+    async fn search_matching(
+        &self,
+        query_embedding: &[f32],
+        filter: &MemoryFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let mut results = Vec::new();
+        for agent_id in &filter.agent_ids {
+            results.extend(self.search(query_embedding, agent_id, limit).await?);
+        }
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    /// Delete every memory matching `filter`, returning how many were deleted.
+    ///
+    /// Backends override this to delete in a single operation; the default
+    /// clears one agent at a time.
+    async fn delete_matching(&self, filter: &MemoryFilter) -> Result<usize> {
+        let total = self.count_matching(filter).await?;
+        for agent_id in &filter.agent_ids {
+            self.clear_agent(agent_id).await?;
+        }
+        Ok(total)
+    }
+
     /// Search with explicit access control.
     ///
     /// This method enforces access control based on the provided `AccessLevel`:
@@ -181,13 +287,13 @@ pub trait MemoryStore: Send + Sync {
 /// # Ok(())
 /// # }
 /// ```
-pub struct AccessControlledStore<S: MemoryStore> {
+pub struct AccessControlledStore<S: MemoryStore + ?Sized> {
     inner: Arc<S>,
     agent_id: String,
     access_level: AccessLevel,
 }
 
-impl<S: MemoryStore> AccessControlledStore<S> {
+impl<S: MemoryStore + ?Sized> AccessControlledStore<S> {
     /// Create a new access-controlled store wrapper.
     ///
     /// # Arguments
@@ -389,6 +495,21 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_search_matching_only_returns_filtered_agents() {
+        let store = MockStore::new();
+        let embedding = vec![0.1; DEFAULT_EMBEDDING_DIM];
+        for agent in ["agent-1", "agent-2", "agent-3"] {
+            store.store(Memory::new(agent, "note", embedding.clone())).await.unwrap();
+        }
+
+        let filter = MemoryFilter::agents(["agent-1", "agent-3"]);
+        let results = store.search_matching(&embedding, &filter, 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| filter.matches(&r.memory)));
+        assert_eq!(store.search_matching(&embedding, &filter, 1).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_mock_store_basic_operations() {
         let store: Arc<dyn MemoryStore> = Arc::new(MockStore::new());
@@ -589,6 +710,24 @@ mod tests {
         assert!(own_mem.is_none());
     }
 
+    #[tokio::test]
+    async fn test_delete_matching() {
+        let store = MockStore::new();
+        for agent in ["session-agent-api", "project-knowledge-api", "session-agent-web"] {
+            store
+                .store(Memory::new(agent, "fact", vec![0.0; DEFAULT_EMBEDDING_DIM]))
+                .await
+                .unwrap();
+        }
+
+        let filter = MemoryFilter::agents(["session-agent-api", "project-knowledge-api"]);
+        assert_eq!(store.count_matching(&filter).await.unwrap(), 2);
+        assert_eq!(store.delete_matching(&filter).await.unwrap(), 2);
+        assert_eq!(store.count_matching(&filter).await.unwrap(), 0);
+        assert_eq!(store.count("session-agent-web").await.unwrap(), 1);
+        assert_eq!(store.delete_matching(&MemoryFilter::default()).await.unwrap(), 0);
+    }
+
     #[test]
     fn test_access_level_equality() {
         assert_eq!(AccessLevel::Own, AccessLevel::Own);
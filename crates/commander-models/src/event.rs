@@ -35,6 +35,10 @@ pub enum EventType {
     InstanceReady,
     /// An instance encountered an error.
     InstanceError,
+    /// An instance was stopped.
+    InstanceStopped,
+    /// A work item is due soon or past its deadline.
+    WorkOverdue,
 }
 
 /// Priority levels for events.
@@ -119,6 +123,8 @@ pub fn default_priority(event_type: EventType) -> EventPriority {
         EventType::InstanceStarting => EventPriority::Info,
         EventType::InstanceReady => EventPriority::Info,
         EventType::InstanceError => EventPriority::Critical,
+        EventType::InstanceStopped => EventPriority::Info,
+        EventType::WorkOverdue => EventPriority::High,
     }
 }
 
@@ -136,6 +142,8 @@ pub fn get_default_priorities() -> HashMap<EventType, EventPriority> {
     m.insert(EventType::InstanceStarting, EventPriority::Info);
     m.insert(EventType::InstanceReady, EventPriority::Info);
     m.insert(EventType::InstanceError, EventPriority::Critical);
+    m.insert(EventType::InstanceStopped, EventPriority::Info);
+    m.insert(EventType::WorkOverdue, EventPriority::High);
     m
 }
 
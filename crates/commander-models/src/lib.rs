@@ -17,4 +17,4 @@ pub use event::{
 };
 pub use ids::{EventId, MessageId, ProjectId, SessionId, WorkId};
 pub use project::{Project, ProjectState, ThreadMessage, ToolSession};
-pub use work::{WorkItem, WorkPriority, WorkState};
+pub use work::{SlaStatus, WorkItem, WorkPriority, WorkState};
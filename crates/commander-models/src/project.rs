@@ -79,6 +79,8 @@ pub enum ProjectState {
     Paused,
     /// Project is in an error state.
     Error,
+    /// The project's runtime session exited outside Commander.
+    Crashed,
 }
 
 /// A tool session within a project.
@@ -250,6 +252,10 @@ pub struct Project {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub adapter_type: Option<AdapterType>,
 
+    /// Workspace the project belongs to (e.g., a client), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+
     /// When the project was created.
     pub created_at: DateTime<Utc>,
 
@@ -278,6 +284,7 @@ impl Project {
             thread: Vec::new(),
             aliases: Vec::new(),
             adapter_type: None,
+            workspace: None,
             created_at: Utc::now(),
             last_activity: None,
         }
@@ -351,6 +358,23 @@ impl Project {
         }
     }
 
+    /// Moves this project into `workspace`, or out of any with `None`.
+    ///
+    /// Workspace names follow the alias rules.
+    pub fn set_workspace(&mut self, workspace: Option<String>) -> Result<(), String> {
+        if let Some(name) = &workspace {
+            Self::validate_alias(name).map_err(|e| e.replace("Alias", "Workspace name"))?;
+        }
+        self.workspace = workspace;
+        self.touch();
+        Ok(())
+    }
+
+    /// Checks if this project belongs to `workspace`.
+    pub fn in_workspace(&self, workspace: &str) -> bool {
+        self.workspace.as_deref() == Some(workspace)
+    }
+
     /// Checks if this project matches a name or alias.
     ///
     /// Matches against:
@@ -953,4 +977,25 @@ mod tests {
         let json = serde_json::to_string(&project).unwrap();
         assert!(json.contains("\"adapter_type\":\"claude-mpm\""));
     }
+
+    // === Workspace Tests ===
+
+    #[test]
+    fn test_set_workspace() {
+        let mut project = Project::new("/path", "test");
+        assert!(!project.in_workspace("acme"));
+
+        project.set_workspace(Some("acme".to_string())).unwrap();
+        assert!(project.in_workspace("acme"));
+        let json = serde_json::to_string(&project).unwrap();
+        assert!(json.contains("\"workspace\":\"acme\""));
+
+        let err = project.set_workspace(Some("acme corp".to_string())).unwrap_err();
+        assert!(err.starts_with("Workspace name must be alphanumeric"));
+        assert!(project.in_workspace("acme"));
+
+        project.set_workspace(None).unwrap();
+        assert_eq!(project.workspace, None);
+        assert!(!serde_json::to_string(&project).unwrap().contains("workspace"));
+    }
 }
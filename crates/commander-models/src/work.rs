@@ -3,7 +3,7 @@
 //! Work items represent units of work that can be queued, processed,
 //! and tracked through their lifecycle.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
@@ -73,6 +73,22 @@ impl Ord for WorkPriority {
     }
 }
 
+/// Where a work item stands against its deadline.
+///
+/// Ordered by urgency: `OnTrack` < `DueSoon` < `Overdue` < `Breached`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaStatus {
+    /// The deadline is not near.
+    OnTrack,
+    /// The deadline is near.
+    DueSoon,
+    /// The deadline has passed.
+    Overdue,
+    /// The deadline passed long ago and nobody acted on it.
+    Breached,
+}
+
 /// A unit of work in the Commander system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkItem {
@@ -117,6 +133,15 @@ pub struct WorkItem {
     /// Additional metadata for the work item.
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
+
+    /// When the work must be finished by, if it has an SLA.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+
+    /// SLA status last recorded by the SLA checker, so each escalation is
+    /// only reported once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sla_status: Option<SlaStatus>,
 }
 
 impl WorkItem {
@@ -135,6 +160,8 @@ impl WorkItem {
             error: None,
             depends_on: Vec::new(),
             metadata: HashMap::new(),
+            deadline: None,
+            sla_status: None,
         }
     }
 
@@ -145,6 +172,32 @@ impl WorkItem {
         item
     }
 
+    /// Gives the work item an SLA of `sla` from its creation.
+    pub fn set_sla(&mut self, sla: Duration) {
+        self.deadline = Some(self.created_at + sla);
+    }
+
+    /// Returns true until the work item is completed, failed or cancelled.
+    pub fn is_open(&self) -> bool {
+        !matches!(
+            self.state,
+            WorkState::Completed | WorkState::Failed | WorkState::Cancelled
+        )
+    }
+
+    /// Time left until the deadline at `now`, negative once it has passed.
+    ///
+    /// `None` if the item has no deadline or is no longer open.
+    pub fn time_remaining(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let deadline = self.deadline?;
+        self.is_open().then(|| deadline - now)
+    }
+
+    /// Returns true if the item is open and past its deadline at `now`.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.time_remaining(now).is_some_and(|left| left < Duration::zero())
+    }
+
     /// Checks if this work item can start based on completed dependencies.
     ///
     /// Returns true if:
@@ -294,6 +347,23 @@ mod tests {
         assert!(item.can_start(&completed));
     }
 
+    #[test]
+    fn test_work_item_deadline() {
+        let mut item = WorkItem::new("p1", "Task");
+        let now = item.created_at;
+        assert_eq!(item.time_remaining(now), None);
+        assert!(!item.is_overdue(now));
+
+        item.set_sla(Duration::hours(2));
+        assert_eq!(item.time_remaining(now), Some(Duration::hours(2)));
+        assert!(item.is_overdue(now + Duration::hours(3)));
+
+        // Closed items have no countdown
+        item.complete(None);
+        assert_eq!(item.time_remaining(now), None);
+        assert!(!item.is_overdue(now + Duration::hours(3)));
+    }
+
     #[test]
     fn test_work_item_start() {
         let mut item = WorkItem::new("p1", "Task");
@@ -0,0 +1,247 @@
+//! Message bus between agents.
+//!
+//! Session Agents publish typed [`AgentMessage`]s (status, help requests,
+//! shared knowledge) with the `send_agent_message` tool; the orchestrator
+//! collects them after each agent call and publishes them here. A message
+//! addressed to a session goes to that session's agent; one without a
+//! recipient goes to every agent subscribed to its kind. Delivered messages
+//! wait in the recipient's inbox until the orchestrator hands them to the
+//! agent, which sees them on its next analysis.
+//!
+//! What happens to a message nobody can receive yet depends on its kind's
+//! [`DeliveryPolicy`]: status updates replace older undelivered ones
+//! ([`DeliveryPolicy::LatestOnly`]), while help requests and shared
+//! knowledge are held for agents that subscribe later
+//! ([`DeliveryPolicy::Durable`]).
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::debug;
+
+use commander_agent::{AgentMessage, MessageKind};
+
+/// Messages kept per inbox; the oldest are dropped beyond this.
+const MAX_INBOX: usize = 20;
+
+/// How a message is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryPolicy {
+    /// To current subscribers only; dropped when nobody listens.
+    BestEffort,
+    /// To current subscribers, replacing any undelivered message of the same
+    /// kind from the same sender.
+    LatestOnly,
+    /// To current subscribers, and held for `ttl` for agents that subscribe
+    /// later (or, for a direct message, until its recipient does).
+    Durable { ttl: Duration },
+}
+
+/// Default policy of each kind.
+fn default_policy(kind: MessageKind) -> DeliveryPolicy {
+    match kind {
+        MessageKind::Status => DeliveryPolicy::LatestOnly,
+        MessageKind::HelpRequest => DeliveryPolicy::Durable {
+            ttl: Duration::from_secs(60 * 60),
+        },
+        MessageKind::KnowledgeShare => DeliveryPolicy::Durable {
+            ttl: Duration::from_secs(24 * 60 * 60),
+        },
+    }
+}
+
+/// A durable message waiting for agents that have not received it.
+#[derive(Debug)]
+struct Held {
+    message: AgentMessage,
+    expires_at: DateTime<Utc>,
+    delivered: HashSet<String>,
+}
+
+/// Routes messages between agents by recipient and subscription.
+#[derive(Debug, Default)]
+pub struct MessageBus {
+    policies: HashMap<MessageKind, DeliveryPolicy>,
+    subscriptions: HashMap<String, BTreeSet<MessageKind>>,
+    inboxes: HashMap<String, Vec<AgentMessage>>,
+    held: Vec<Held>,
+}
+
+impl MessageBus {
+    /// An empty bus with the default policies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Policy applied to messages of `kind`.
+    pub fn policy(&self, kind: MessageKind) -> DeliveryPolicy {
+        self.policies.get(&kind).copied().unwrap_or_else(|| default_policy(kind))
+    }
+
+    /// Change the policy of `kind`.
+    pub fn set_policy(&mut self, kind: MessageKind, policy: DeliveryPolicy) {
+        self.policies.insert(kind, policy);
+    }
+
+    /// Subscribe `agent` (a session ID) to `kinds`, adding to its current
+    /// subscriptions, and deliver the held messages it should receive.
+    ///
+    /// A subscribed agent also receives messages addressed to it, whatever
+    /// their kind.
+    pub fn subscribe(&mut self, agent: &str, kinds: &[MessageKind]) {
+        self.subscriptions
+            .entry(agent.to_string())
+            .or_default()
+            .extend(kinds.iter().copied());
+
+        self.expire_held();
+        let mut deliveries = Vec::new();
+        for held in &mut self.held {
+            if !held.delivered.contains(agent) && receives(&self.subscriptions, agent, &held.message) {
+                held.delivered.insert(agent.to_string());
+                deliveries.push(held.message.clone());
+            }
+        }
+        // Direct messages are held only for their recipient
+        self.held
+            .retain(|h| h.message.to.is_none() || !h.delivered.contains(agent));
+        for message in deliveries {
+            self.deliver(agent, message);
+        }
+    }
+
+    /// Stop delivering to `agent` and drop its inbox.
+    pub fn unsubscribe(&mut self, agent: &str) {
+        self.subscriptions.remove(agent);
+        self.inboxes.remove(agent);
+    }
+
+    /// Whether `agent` is subscribed to anything.
+    pub fn is_subscribed(&self, agent: &str) -> bool {
+        self.subscriptions.contains_key(agent)
+    }
+
+    /// Publish `message` under its kind's policy. Returns the number of
+    /// inboxes it was delivered to.
+    pub fn publish(&mut self, message: AgentMessage) -> usize {
+        let policy = self.policy(message.kind);
+        let recipients: Vec<String> = self
+            .subscriptions
+            .keys()
+            .filter(|agent| **agent != message.from && receives(&self.subscriptions, agent, &message))
+            .cloned()
+            .collect();
+
+        for agent in &recipients {
+            if policy == DeliveryPolicy::LatestOnly {
+                if let Some(inbox) = self.inboxes.get_mut(agent) {
+                    inbox.retain(|m| !(m.kind == message.kind && m.from == message.from));
+                }
+            }
+            self.deliver(agent, message.clone());
+        }
+
+        let delivered_to_recipient = message.to.as_ref().is_some_and(|to| recipients.contains(to));
+        if let DeliveryPolicy::Durable { ttl } = policy {
+            if !delivered_to_recipient {
+                self.expire_held();
+                self.held.push(Held {
+                    expires_at: message.sent_at
+                        + chrono::Duration::from_std(ttl).unwrap_or_else(|_| chrono::Duration::days(1)),
+                    delivered: recipients.iter().cloned().collect(),
+                    message: message.clone(),
+                });
+            }
+        }
+
+        debug!(
+            from = %message.from,
+            to = ?message.to,
+            kind = message.kind.as_str(),
+            delivered = recipients.len(),
+            "Published agent message"
+        );
+        recipients.len()
+    }
+
+    /// Take the messages waiting in `agent`'s inbox, oldest first.
+    pub fn take(&mut self, agent: &str) -> Vec<AgentMessage> {
+        self.inboxes.remove(agent).unwrap_or_default()
+    }
+
+    /// Number of messages waiting in `agent`'s inbox.
+    pub fn pending(&self, agent: &str) -> usize {
+        self.inboxes.get(agent).map_or(0, Vec::len)
+    }
+
+    fn deliver(&mut self, agent: &str, message: AgentMessage) {
+        let inbox = self.inboxes.entry(agent.to_string()).or_default();
+        inbox.push(message);
+        if inbox.len() > MAX_INBOX {
+            inbox.remove(0);
+        }
+    }
+
+    fn expire_held(&mut self) {
+        let now = Utc::now();
+        self.held.retain(|h| h.expires_at > now);
+    }
+}
+
+/// Whether subscriber `agent` should receive `message`.
+fn receives(subscriptions: &HashMap<String, BTreeSet<MessageKind>>, agent: &str, message: &AgentMessage) -> bool {
+    match &message.to {
+        Some(to) => to == agent,
+        None => subscriptions.get(agent).is_some_and(|kinds| kinds.contains(&message.kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_and_policies() {
+        let mut bus = MessageBus::new();
+        bus.subscribe("frontend", &MessageKind::ALL);
+        bus.subscribe("backend", &[MessageKind::KnowledgeShare]);
+
+        // Direct messages reach their recipient whatever its subscriptions
+        let direct = AgentMessage::direct("frontend", "backend", MessageKind::HelpRequest, "Which port?");
+        assert_eq!(bus.publish(direct), 1);
+        // Broadcasts reach subscribers of the kind, never the sender
+        let status = AgentMessage::broadcast("backend", MessageKind::Status, "Migrating");
+        assert_eq!(bus.publish(status), 1);
+        assert_eq!(bus.publish(AgentMessage::broadcast("backend", MessageKind::Status, "Migrated")), 1);
+
+        // Latest status only
+        let frontend = bus.take("frontend");
+        assert_eq!(frontend.len(), 1);
+        assert_eq!(frontend[0].body, "Migrated");
+        assert_eq!(bus.take("backend")[0].body, "Which port?");
+        assert_eq!(bus.pending("backend"), 0);
+
+        // Durable messages wait for agents that subscribe later
+        let contract = AgentMessage::broadcast("backend", MessageKind::KnowledgeShare, "POST /users takes JSON");
+        assert_eq!(bus.publish(contract), 1);
+        assert_eq!(bus.publish(AgentMessage::direct("backend", "docs", MessageKind::HelpRequest, "Update the API docs")), 0);
+        assert_eq!(bus.publish(AgentMessage::broadcast("backend", MessageKind::Status, "Idle")), 1);
+        bus.subscribe("docs", &[MessageKind::KnowledgeShare]);
+        let docs: Vec<String> = bus.take("docs").into_iter().map(|m| m.body).collect();
+        assert_eq!(docs, ["POST /users takes JSON", "Update the API docs"]);
+
+        // Held messages are delivered once per agent
+        bus.subscribe("docs", &[MessageKind::Status]);
+        assert_eq!(bus.pending("docs"), 0);
+        assert_eq!(bus.pending("frontend"), 2);
+
+        // Best effort drops what nobody receives
+        bus.set_policy(MessageKind::HelpRequest, DeliveryPolicy::BestEffort);
+        bus.publish(AgentMessage::direct("docs", "qa", MessageKind::HelpRequest, "Test the docs"));
+        bus.subscribe("qa", &MessageKind::ALL);
+        assert_eq!(bus.pending("qa"), 1); // the held contract only
+        bus.unsubscribe("qa");
+        assert!(!bus.is_subscribed("qa"));
+    }
+}
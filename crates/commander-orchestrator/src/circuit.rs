@@ -0,0 +1,160 @@
+//! Circuit breaker for LLM calls.
+//!
+//! After `failure_threshold` consecutive provider failures the breaker opens
+//! and the orchestrator stops calling the model, degrading to pattern-based
+//! (non-LLM) summaries. Once `cooldown` has passed one call is let through;
+//! success closes the breaker, failure opens it for another cooldown.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+/// Consecutive failures that open the breaker by default.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Default time the breaker stays open before probing the provider again.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through.
+    Closed,
+    /// Calls are suspended.
+    Open,
+    /// The cooldown has passed; the next call probes the provider.
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => write!(f, "closed"),
+            Self::Open => write!(f, "open"),
+            Self::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+/// Tracks provider failures and suspends LLM calls while it is down.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_COOLDOWN)
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a breaker that opens after `failure_threshold` consecutive
+    /// failures and stays open for `cooldown`.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> CircuitState {
+        self.state_at(Instant::now())
+    }
+
+    fn state_at(&self, now: Instant) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened) if now.duration_since(opened) < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether an LLM call may be made now.
+    pub fn allows_call(&self) -> bool {
+        self.state() != CircuitState::Open
+    }
+
+    /// Time until the breaker lets a call through, while open.
+    pub fn retry_in(&self) -> Option<Duration> {
+        let opened = self.opened_at?;
+        self.cooldown.checked_sub(opened.elapsed()).filter(|d| !d.is_zero())
+    }
+
+    /// Record a successful call, closing the breaker.
+    pub fn record_success(&mut self) {
+        if self.opened_at.is_some() {
+            info!("Model provider recovered, resuming LLM calls");
+        }
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    /// Record a provider failure, opening the breaker at the threshold.
+    pub fn record_failure(&mut self) {
+        self.record_failure_at(Instant::now());
+    }
+
+    fn record_failure_at(&mut self, now: Instant) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.consecutive_failures >= self.failure_threshold {
+            if self.opened_at.is_none() {
+                warn!(
+                    failures = self.consecutive_failures,
+                    cooldown_secs = self.cooldown.as_secs(),
+                    "Model provider unavailable, using non-LLM summaries"
+                );
+            }
+            self.opened_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold_and_probes_after_cooldown() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+        let start = Instant::now();
+
+        breaker.record_failure_at(start);
+        assert_eq!(breaker.state_at(start), CircuitState::Closed);
+        breaker.record_failure_at(start);
+        assert_eq!(breaker.state_at(start), CircuitState::Open);
+        assert_eq!(
+            breaker.state_at(start + Duration::from_secs(30)),
+            CircuitState::HalfOpen
+        );
+
+        // A failed probe reopens for another cooldown
+        let probe = start + Duration::from_secs(31);
+        breaker.record_failure_at(probe);
+        assert_eq!(breaker.state_at(probe + Duration::from_secs(29)), CircuitState::Open);
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allows_call());
+        assert!(breaker.retry_in().is_none());
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert!(breaker.allows_call());
+        breaker.record_failure();
+        assert!(!breaker.allows_call());
+        assert!(breaker.retry_in().is_some());
+    }
+}
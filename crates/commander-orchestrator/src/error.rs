@@ -13,10 +13,26 @@ pub enum OrchestratorError {
     #[error("Memory error: {0}")]
     Memory(#[from] commander_memory::MemoryError),
 
+    /// Work queue error.
+    #[error("Work queue error: {0}")]
+    Work(#[from] commander_work::WorkError),
+
     /// Configuration error.
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    /// Invalid workflow or workflow run state.
+    #[error("Workflow error: {0}")]
+    Workflow(String),
+
+    /// LLM calls are suspended because the model provider is down.
+    #[error("Model provider unavailable, retrying in {0}s")]
+    ProviderUnavailable(u64),
+
+    /// LLM calls are suspended because the network is down.
+    #[error("Offline: LLM features are paused until the network is back")]
+    Offline,
+
     /// Session not found.
     #[error("Session not found: {0}")]
     SessionNotFound(String),
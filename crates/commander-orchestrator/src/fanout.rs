@@ -0,0 +1,529 @@
+//! Parallel task fan-out across sessions.
+//!
+//! [`AgentOrchestrator::delegate_parallel`](crate::AgentOrchestrator::delegate_parallel)
+//! runs several tasks at once, each in its own tmux session. Tasks are
+//! enqueued as work items and dispatched from the `WorkQueue` at most
+//! `FanOutConfig::max_concurrent` at a time; the queue records each task's
+//! result or failure. The User Agent then aggregates the results into a
+//! single report.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+use commander_adapters::{AdapterRegistry, RuntimeAdapter};
+use commander_core::{clean_response, find_new_lines, is_claude_ready, CommandSender};
+use commander_models::{WorkId, WorkItem, WorkPriority};
+use commander_tmux::TmuxOrchestrator;
+use commander_work::WorkQueue;
+
+use crate::error::{OrchestratorError, Result};
+
+/// Fan-out config file name within the config directory.
+pub const FANOUT_FILE: &str = "fanout.yaml";
+
+/// Lines of pane history captured while a task runs.
+const CAPTURE_LINES: u32 = 500;
+
+/// Settings for parallel task execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FanOutConfig {
+    /// Maximum number of tasks running at once.
+    pub max_concurrent: usize,
+    /// Seconds to wait for a newly launched adapter to become ready.
+    pub ready_timeout_secs: u64,
+    /// Seconds a single task may run before it is marked failed.
+    pub task_timeout_secs: u64,
+    /// Milliseconds between output polls.
+    pub poll_interval_ms: u64,
+    /// Consecutive unchanged, idle polls that mark a task finished.
+    pub idle_polls: u32,
+}
+
+impl Default for FanOutConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 3,
+            ready_timeout_secs: 120,
+            task_timeout_secs: 1800,
+            poll_interval_ms: 1000,
+            idle_polls: 3,
+        }
+    }
+}
+
+impl FanOutConfig {
+    /// Default config path (`~/.ai-commander/config/fanout.yaml`).
+    pub fn default_path() -> PathBuf {
+        commander_core::config_dir().join(FANOUT_FILE)
+    }
+
+    /// Load the configuration from a YAML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            OrchestratorError::Configuration(format!("failed to read {}: {}", path.display(), e))
+        })?;
+
+        serde_yaml::from_str(&content).map_err(|e| {
+            OrchestratorError::Configuration(format!("failed to parse YAML {}: {}", path.display(), e))
+        })
+    }
+
+    /// Load the configuration if `path` exists, falling back to defaults.
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!(error = %e, "Invalid fan-out config, using defaults");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// A task to run in a session as part of a parallel fan-out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskSpec {
+    /// Tmux session to run the task in; created if it does not exist.
+    pub session_id: String,
+    /// Adapter ID or alias (e.g. `cc`, `mpm`).
+    pub adapter: String,
+    /// Prompt sent to the session.
+    pub task: String,
+    /// Working directory for a newly created session.
+    pub project_path: Option<String>,
+    /// Dispatch priority within the batch.
+    pub priority: WorkPriority,
+}
+
+impl TaskSpec {
+    /// A task for `session_id` using the claude-code adapter.
+    pub fn new(session_id: impl Into<String>, task: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            adapter: "claude-code".to_string(),
+            task: task.into(),
+            project_path: None,
+            priority: WorkPriority::default(),
+        }
+    }
+
+    /// Use a different adapter.
+    pub fn with_adapter(mut self, adapter: impl Into<String>) -> Self {
+        self.adapter = adapter.into();
+        self
+    }
+
+    /// Start a new session in `path`.
+    pub fn in_dir(mut self, path: impl Into<String>) -> Self {
+        self.project_path = Some(path.into());
+        self
+    }
+
+    /// Set the dispatch priority.
+    pub fn with_priority(mut self, priority: WorkPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// How a task ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskOutcome {
+    /// The session went idle after the task; holds its new output.
+    Completed { output: String },
+    /// The task could not be run or did not finish.
+    Failed { error: String },
+}
+
+/// Result of one task in a fan-out.
+#[derive(Debug, Clone)]
+pub struct TaskResult {
+    /// The task as submitted.
+    pub spec: TaskSpec,
+    /// Work item tracking the task.
+    pub work_id: WorkId,
+    pub outcome: TaskOutcome,
+    /// Wall-clock time from dispatch to completion.
+    pub duration: Duration,
+    /// Session Agent summary of the output, when analysis succeeded.
+    pub summary: Option<String>,
+}
+
+impl TaskResult {
+    /// Whether the task completed.
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome, TaskOutcome::Completed { .. })
+    }
+}
+
+/// Aggregated outcome of a parallel fan-out.
+#[derive(Debug, Clone)]
+pub struct ParallelReport {
+    /// Per-task results, in submission order.
+    pub results: Vec<TaskResult>,
+    /// Combined report written by the User Agent.
+    pub report: String,
+}
+
+impl ParallelReport {
+    /// Number of tasks that completed.
+    pub fn succeeded(&self) -> usize {
+        self.results.iter().filter(|r| r.succeeded()).count()
+    }
+
+    /// Number of tasks that failed.
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.succeeded()
+    }
+}
+
+/// Runs a single task to completion.
+///
+/// Called from a blocking thread; implementations may block.
+pub trait TaskRunner: Send + Sync {
+    /// Run `spec` and return the session's output for it.
+    fn run(&self, spec: &TaskSpec) -> std::result::Result<String, String>;
+}
+
+/// Runs tasks in tmux sessions, launching the adapter when needed.
+pub struct TmuxTaskRunner {
+    tmux: TmuxOrchestrator,
+    registry: AdapterRegistry,
+    config: FanOutConfig,
+}
+
+impl TmuxTaskRunner {
+    /// Create a runner; fails if tmux is not available.
+    pub fn new(config: FanOutConfig) -> Result<Self> {
+        let tmux = TmuxOrchestrator::new()
+            .map_err(|e| OrchestratorError::Configuration(format!("tmux not available: {}", e)))?
+            .with_sender(CommandSender::Agent);
+        Ok(Self {
+            tmux,
+            registry: AdapterRegistry::new(),
+            config,
+        })
+    }
+
+    fn capture(&self, session: &str) -> String {
+        self.tmux
+            .capture_output(session, None, Some(CAPTURE_LINES))
+            .unwrap_or_default()
+    }
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.config.poll_interval_ms)
+    }
+
+    /// Create the session and launch the adapter, waiting until it is ready.
+    fn start_session(&self, spec: &TaskSpec, adapter: &dyn RuntimeAdapter) -> std::result::Result<(), String> {
+        let path = spec.project_path.as_deref().unwrap_or(".");
+        let (cmd, args) = adapter.launch_command(path);
+        let full_cmd = if args.is_empty() {
+            cmd
+        } else {
+            format!("{} {}", cmd, args.join(" "))
+        };
+
+        self.tmux
+            .create_session_in_dir(&spec.session_id, spec.project_path.as_deref())
+            .map_err(|e| format!("failed to create tmux session: {}", e))?;
+        self.tmux
+            .send_line(&spec.session_id, None, &full_cmd)
+            .map_err(|e| format!("failed to start adapter: {}", e))?;
+
+        let timeout = Duration::from_secs(self.config.ready_timeout_secs);
+        let started = Instant::now();
+        loop {
+            std::thread::sleep(self.poll_interval());
+            let output = self.capture(&spec.session_id);
+            if is_claude_ready(&output) || adapter.is_idle(&output) {
+                return Ok(());
+            }
+            if started.elapsed() > timeout {
+                return Err(format!("{} not ready after {}s", adapter.info().name, timeout.as_secs()));
+            }
+        }
+    }
+}
+
+impl TaskRunner for TmuxTaskRunner {
+    fn run(&self, spec: &TaskSpec) -> std::result::Result<String, String> {
+        let adapter = self
+            .registry
+            .resolve(&spec.adapter)
+            .and_then(|id| self.registry.get(id))
+            .ok_or_else(|| format!("unknown adapter: {}", spec.adapter))?;
+
+        if !self.tmux.session_exists(&spec.session_id) {
+            self.start_session(spec, adapter.as_ref())?;
+        }
+
+        let baseline = self.capture(&spec.session_id);
+        self.tmux
+            .send_line(&spec.session_id, None, &spec.task)
+            .map_err(|e| format!("failed to send task: {}", e))?;
+
+        let timeout = Duration::from_secs(self.config.task_timeout_secs);
+        let started = Instant::now();
+        let mut last = baseline.clone();
+        let mut idle_polls = 0;
+        loop {
+            std::thread::sleep(self.poll_interval());
+            let output = self.capture(&spec.session_id);
+
+            // Only count idle polls once the session has reacted to the task
+            if output != baseline && output == last && adapter.is_idle(&output) {
+                idle_polls += 1;
+                if idle_polls >= self.config.idle_polls {
+                    let new_lines = find_new_lines(&baseline, &output);
+                    return Ok(clean_response(&new_lines.join("\n")));
+                }
+            } else {
+                idle_polls = 0;
+            }
+            last = output;
+
+            if started.elapsed() > timeout {
+                return Err(format!("timed out after {}s", timeout.as_secs()));
+            }
+        }
+    }
+}
+
+/// Run `tasks` concurrently, dispatching from `queue` at most `max_concurrent` at a time.
+///
+/// Each task becomes a work item in the queue, marked completed with the
+/// task's output or failed with its error. Results are returned in
+/// submission order.
+pub async fn run_parallel(
+    queue: &WorkQueue,
+    runner: Arc<dyn TaskRunner>,
+    tasks: Vec<TaskSpec>,
+    max_concurrent: usize,
+) -> Result<Vec<TaskResult>> {
+    let max_concurrent = max_concurrent.max(1);
+    let mut order = Vec::with_capacity(tasks.len());
+    let mut pending: HashMap<WorkId, TaskSpec> = HashMap::new();
+
+    for spec in tasks {
+        let item = WorkItem::with_priority(spec.session_id.as_str(), spec.task.as_str(), spec.priority);
+        let work_id = queue.enqueue(item)?;
+        order.push(work_id.clone());
+        pending.insert(work_id, spec);
+    }
+
+    info!(tasks = order.len(), max_concurrent, "Fanning out tasks");
+
+    let mut running = JoinSet::new();
+    let mut finished: HashMap<WorkId, TaskResult> = HashMap::new();
+
+    loop {
+        while running.len() < max_concurrent {
+            let Some(item) = queue.dequeue() else {
+                break;
+            };
+            let Some(spec) = pending.remove(&item.id) else {
+                // Not part of this batch; leave it for its owner
+                warn!(work_id = %item.id, "Skipping work item outside the fan-out batch");
+                continue;
+            };
+
+            debug!(work_id = %item.id, session = %spec.session_id, "Dispatching task");
+            let runner = Arc::clone(&runner);
+            running.spawn_blocking(move || {
+                let started = Instant::now();
+                let outcome = match runner.run(&spec) {
+                    Ok(output) => TaskOutcome::Completed { output },
+                    Err(error) => TaskOutcome::Failed { error },
+                };
+                TaskResult {
+                    spec,
+                    work_id: item.id,
+                    outcome,
+                    duration: started.elapsed(),
+                    summary: None,
+                }
+            });
+        }
+
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let result = joined.map_err(|e| OrchestratorError::Configuration(format!("task panicked: {}", e)))?;
+
+        match &result.outcome {
+            TaskOutcome::Completed { output } => {
+                queue.complete_with_result(&result.work_id, output.clone())?;
+            }
+            TaskOutcome::Failed { error } => {
+                warn!(session = %result.spec.session_id, error = %error, "Parallel task failed");
+                queue.fail(&result.work_id, error.clone())?;
+            }
+        }
+        finished.insert(result.work_id.clone(), result);
+    }
+
+    Ok(order
+        .into_iter()
+        .filter_map(|id| finished.remove(&id))
+        .collect())
+}
+
+/// Prompt asking the User Agent to combine task results into one report.
+pub(crate) fn aggregation_prompt(results: &[TaskResult]) -> String {
+    let mut prompt = String::from(
+        "The following tasks ran in parallel sessions. Write a single concise report: \
+         what each session accomplished, any failures, and follow-up actions.\n",
+    );
+    prompt.push_str(&format_results(results));
+    prompt
+}
+
+/// Plain-text report of task results, used when the User Agent is unavailable.
+pub fn format_results(results: &[TaskResult]) -> String {
+    let succeeded = results.iter().filter(|r| r.succeeded()).count();
+    let mut report = format!("\n{}/{} tasks completed\n", succeeded, results.len());
+
+    for result in results {
+        let status = if result.succeeded() { "done" } else { "failed" };
+        report.push_str(&format!(
+            "\n## {} [{}] ({}s)\nTask: {}\n",
+            result.spec.session_id,
+            status,
+            result.duration.as_secs(),
+            result.spec.task
+        ));
+        match (&result.outcome, &result.summary) {
+            (TaskOutcome::Completed { .. }, Some(summary)) => {
+                report.push_str(&format!("Summary: {}\n", summary));
+            }
+            (TaskOutcome::Completed { output }, None) => {
+                report.push_str(&format!("Output:\n{}\n", tail(output, 20)));
+            }
+            (TaskOutcome::Failed { error }, _) => {
+                report.push_str(&format!("Error: {}\n", error));
+            }
+        }
+    }
+
+    report
+}
+
+/// Last `lines` lines of `text`.
+fn tail(text: &str, lines: usize) -> String {
+    let all: Vec<&str> = text.lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use commander_models::WorkState;
+    use commander_persistence::WorkStore;
+
+    /// Records peak concurrency; tasks containing "fail" fail.
+    #[derive(Default)]
+    struct MockRunner {
+        active: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl TaskRunner for MockRunner {
+        fn run(&self, spec: &TaskSpec) -> std::result::Result<String, String> {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(30));
+            self.active.fetch_sub(1, Ordering::SeqCst);
+
+            if spec.task.contains("fail") {
+                Err("boom".to_string())
+            } else {
+                Ok(format!("finished {}", spec.task))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_parallel_respects_limit_and_tracks_work() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = WorkQueue::new(WorkStore::new(dir.path()));
+        let runner = Arc::new(MockRunner::default());
+
+        let tasks: Vec<TaskSpec> = (0..5)
+            .map(|i| TaskSpec::new(format!("sess-{}", i), format!("task {}", i)))
+            .chain([TaskSpec::new("sess-x", "please fail")])
+            .collect();
+
+        let results = run_parallel(&queue, runner.clone(), tasks, 2).await.unwrap();
+
+        assert_eq!(results.len(), 6);
+        assert_eq!(results[0].spec.session_id, "sess-0");
+        assert!(runner.peak.load(Ordering::SeqCst) <= 2);
+        assert_eq!(results.iter().filter(|r| r.succeeded()).count(), 5);
+
+        let done = queue.get(&results[1].work_id).unwrap();
+        assert_eq!(done.state, WorkState::Completed);
+        assert_eq!(done.result.as_deref(), Some("finished task 1"));
+
+        let failed = queue.get(&results[5].work_id).unwrap();
+        assert_eq!(failed.state, WorkState::Failed);
+        assert_eq!(failed.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_format_results() {
+        let results = vec![
+            TaskResult {
+                spec: TaskSpec::new("api", "add endpoint"),
+                work_id: WorkId::new(),
+                outcome: TaskOutcome::Completed { output: "ok".to_string() },
+                duration: Duration::from_secs(4),
+                summary: Some("Added /health".to_string()),
+            },
+            TaskResult {
+                spec: TaskSpec::new("web", "fix build"),
+                work_id: WorkId::new(),
+                outcome: TaskOutcome::Failed { error: "timed out after 60s".to_string() },
+                duration: Duration::from_secs(60),
+                summary: None,
+            },
+        ];
+
+        let report = format_results(&results);
+        assert!(report.contains("1/2 tasks completed"));
+        assert!(report.contains("## api [done]"));
+        assert!(report.contains("Summary: Added /health"));
+        assert!(report.contains("Error: timed out after 60s"));
+        assert!(aggregation_prompt(&results).contains("## web [failed]"));
+    }
+
+    #[test]
+    fn test_config_load_or_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FANOUT_FILE);
+        assert_eq!(FanOutConfig::load_or_default(&path), FanOutConfig::default());
+
+        fs::write(&path, "max_concurrent: 5\n").unwrap();
+        let config = FanOutConfig::load_or_default(&path);
+        assert_eq!(config.max_concurrent, 5);
+        assert_eq!(config.idle_polls, FanOutConfig::default().idle_polls);
+
+        fs::write(&path, "max_concurrent: [").unwrap();
+        assert_eq!(FanOutConfig::load_or_default(&path), FanOutConfig::default());
+    }
+}
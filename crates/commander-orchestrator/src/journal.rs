@@ -0,0 +1,349 @@
+//! Append-only journal of orchestrator decisions, replayed after a crash.
+//!
+//! Every task the orchestrator hands to a session, and what became of it
+//! (completed, failed, blocked), is appended to `orchestrator-journal.jsonl`
+//! in the data directory as it happens. On startup [`Journal::replay`] folds
+//! the journal back into per-task status, so work that was in flight when the
+//! previous process died is known again; tasks whose session no longer
+//! exists are marked [`TaskStatus::Orphaned`]. The journal is then rewritten
+//! with only the tasks still in flight, which keeps it small.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// File name of the journal in the orchestrator's data directory.
+pub const JOURNAL_FILE: &str = "orchestrator-journal.jsonl";
+
+/// Distinguishes task IDs created in the same millisecond.
+static NEXT_TASK: AtomicU64 = AtomicU64::new(0);
+
+/// A decision recorded in the journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// A task was sent to a session.
+    Delegated {
+        task_id: String,
+        session_id: String,
+        task: String,
+    },
+    /// The task finished.
+    Completed { task_id: String },
+    /// The task could not be run or did not finish.
+    Failed { task_id: String, error: String },
+    /// The task's session hit a blocker.
+    Blocked { task_id: String, reason: String },
+}
+
+impl JournalEvent {
+    fn task_id(&self) -> &str {
+        match self {
+            Self::Delegated { task_id, .. }
+            | Self::Completed { task_id }
+            | Self::Failed { task_id, .. }
+            | Self::Blocked { task_id, .. } => task_id,
+        }
+    }
+}
+
+/// One line of the journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: JournalEvent,
+}
+
+/// Status of a task reconstructed from the journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskStatus {
+    /// Sent to its session and not finished.
+    InFlight,
+    /// In flight, but its session reported a blocker.
+    Blocked(String),
+    Completed,
+    Failed(String),
+    /// Was in flight when the orchestrator stopped, and its session is gone.
+    Orphaned,
+}
+
+impl TaskStatus {
+    /// Whether the task is still being worked on.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::InFlight | Self::Blocked(_))
+    }
+}
+
+/// A task known to the journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedTask {
+    pub task_id: String,
+    pub session_id: String,
+    /// Prompt sent to the session.
+    pub task: String,
+    pub status: TaskStatus,
+    pub delegated_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The journal file and the task status it describes.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    tasks: Vec<TrackedTask>,
+}
+
+impl Journal {
+    /// An empty journal at `path`, without reading what it holds.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Journal in `data_dir`.
+    pub fn path_in(data_dir: &Path) -> PathBuf {
+        data_dir.join(JOURNAL_FILE)
+    }
+
+    /// Replay the journal at `path`, marking active tasks whose session is
+    /// not alive as orphaned, then compact it to the tasks still active.
+    ///
+    /// Unreadable lines are skipped; a missing journal replays to no tasks.
+    pub fn replay(path: impl Into<PathBuf>, session_alive: impl Fn(&str) -> bool) -> io::Result<Self> {
+        let mut journal = Self::new(path);
+        let text = match fs::read_to_string(&journal.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(journal),
+            Err(e) => return Err(e),
+        };
+
+        let mut skipped = 0;
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<JournalEntry>(line) {
+                Ok(entry) => journal.apply(&entry),
+                Err(_) => skipped += 1,
+            }
+        }
+        if skipped > 0 {
+            warn!(path = %journal.path.display(), skipped, "Skipped unreadable journal entries");
+        }
+
+        let now = Utc::now();
+        for task in journal.tasks.iter_mut().filter(|t| t.status.is_active()) {
+            if !session_alive(&task.session_id) {
+                warn!(task_id = %task.task_id, session = %task.session_id, "Task orphaned: its session is gone");
+                task.status = TaskStatus::Orphaned;
+                task.updated_at = now;
+            }
+        }
+        let active = journal.tasks.iter().filter(|t| t.status.is_active()).count();
+        info!(tasks = journal.tasks.len(), active, "Replayed orchestrator journal");
+
+        journal.compact()?;
+        Ok(journal)
+    }
+
+    /// Every task replayed or recorded since, oldest first.
+    pub fn tasks(&self) -> &[TrackedTask] {
+        &self.tasks
+    }
+
+    /// Tasks still in flight or blocked.
+    pub fn active_tasks(&self) -> impl Iterator<Item = &TrackedTask> {
+        self.tasks.iter().filter(|t| t.status.is_active())
+    }
+
+    /// Tasks found orphaned on replay.
+    pub fn orphaned_tasks(&self) -> impl Iterator<Item = &TrackedTask> {
+        self.tasks.iter().filter(|t| t.status == TaskStatus::Orphaned)
+    }
+
+    /// Record that `task` was sent to `session_id`, returning its task ID.
+    pub fn delegate(&mut self, session_id: &str, task: &str) -> String {
+        let task_id = format!(
+            "task-{}-{}",
+            Utc::now().timestamp_millis(),
+            NEXT_TASK.fetch_add(1, Ordering::Relaxed)
+        );
+        self.record(JournalEvent::Delegated {
+            task_id: task_id.clone(),
+            session_id: session_id.to_string(),
+            task: task.to_string(),
+        });
+        task_id
+    }
+
+    /// Record that a task finished.
+    pub fn complete(&mut self, task_id: &str) {
+        self.record(JournalEvent::Completed {
+            task_id: task_id.to_string(),
+        });
+    }
+
+    /// Record that a task failed.
+    pub fn fail(&mut self, task_id: &str, error: &str) {
+        self.record(JournalEvent::Failed {
+            task_id: task_id.to_string(),
+            error: error.to_string(),
+        });
+    }
+
+    /// Record that every active task of `session_id` finished.
+    pub fn complete_session(&mut self, session_id: &str) {
+        for task_id in self.active_ids(session_id) {
+            self.complete(&task_id);
+        }
+    }
+
+    /// Record that every active task of `session_id` is blocked by `reason`.
+    pub fn block_session(&mut self, session_id: &str, reason: &str) {
+        let blocked: Vec<String> = self
+            .active_tasks()
+            .filter(|t| t.session_id == session_id && t.status != TaskStatus::Blocked(reason.to_string()))
+            .map(|t| t.task_id.clone())
+            .collect();
+        for task_id in blocked {
+            self.record(JournalEvent::Blocked {
+                task_id,
+                reason: reason.to_string(),
+            });
+        }
+    }
+
+    fn active_ids(&self, session_id: &str) -> Vec<String> {
+        self.active_tasks()
+            .filter(|t| t.session_id == session_id)
+            .map(|t| t.task_id.clone())
+            .collect()
+    }
+
+    /// Append `event` to the journal and apply it. Write failures are
+    /// logged, never returned: journaling must not stop orchestration.
+    fn record(&mut self, event: JournalEvent) {
+        let entry = JournalEntry { at: Utc::now(), event };
+        if let Err(e) = append(&self.path, &[&entry]) {
+            warn!(path = %self.path.display(), error = %e, "Failed to write orchestrator journal");
+        }
+        self.apply(&entry);
+    }
+
+    fn apply(&mut self, entry: &JournalEntry) {
+        if let JournalEvent::Delegated {
+            task_id,
+            session_id,
+            task,
+        } = &entry.event
+        {
+            self.tasks.push(TrackedTask {
+                task_id: task_id.clone(),
+                session_id: session_id.clone(),
+                task: task.clone(),
+                status: TaskStatus::InFlight,
+                delegated_at: entry.at,
+                updated_at: entry.at,
+            });
+            return;
+        }
+
+        let Some(task) = self.tasks.iter_mut().find(|t| t.task_id == entry.event.task_id()) else {
+            return;
+        };
+        task.status = match &entry.event {
+            JournalEvent::Completed { .. } => TaskStatus::Completed,
+            JournalEvent::Failed { error, .. } => TaskStatus::Failed(error.clone()),
+            JournalEvent::Blocked { reason, .. } => TaskStatus::Blocked(reason.clone()),
+            JournalEvent::Delegated { .. } => unreachable!("handled above"),
+        };
+        task.updated_at = entry.at;
+    }
+
+    /// Rewrite the journal with the events of the active tasks only.
+    fn compact(&self) -> io::Result<()> {
+        let mut entries = Vec::new();
+        for task in self.active_tasks() {
+            entries.push(JournalEntry {
+                at: task.delegated_at,
+                event: JournalEvent::Delegated {
+                    task_id: task.task_id.clone(),
+                    session_id: task.session_id.clone(),
+                    task: task.task.clone(),
+                },
+            });
+            if let TaskStatus::Blocked(reason) = &task.status {
+                entries.push(JournalEntry {
+                    at: task.updated_at,
+                    event: JournalEvent::Blocked {
+                        task_id: task.task_id.clone(),
+                        reason: reason.clone(),
+                    },
+                });
+            }
+        }
+
+        let tmp = self.path.with_extension("jsonl.tmp");
+        fs::remove_file(&tmp).ok();
+        append(&tmp, &entries.iter().collect::<Vec<_>>())?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+fn append(path: &Path, entries: &[&JournalEntry]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&serde_json::to_string(entry)?);
+        text.push('\n');
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_replay_reconstructs_status_and_orphans_dead_sessions() {
+        let dir = tempdir().unwrap();
+        let path = Journal::path_in(dir.path());
+
+        let mut journal = Journal::replay(&path, |_| true).unwrap();
+        assert!(journal.tasks().is_empty());
+        let done = journal.delegate("api", "Add the login route");
+        let blocked = journal.delegate("api", "Write the tests");
+        let failed = journal.delegate("web", "Fix the build");
+        let lost = journal.delegate("docs", "Update the README");
+        journal.complete(&done);
+        journal.block_session("api", "Needs a database URL");
+        journal.fail(&failed, "tests failed");
+        drop(journal);
+
+        let journal = Journal::replay(&path, |session| session != "docs").unwrap();
+        let status = |id: &str| journal.tasks().iter().find(|t| t.task_id == id).unwrap().status.clone();
+        assert_eq!(status(&done), TaskStatus::Completed);
+        assert_eq!(status(&blocked), TaskStatus::Blocked("Needs a database URL".to_string()));
+        assert_eq!(status(&failed), TaskStatus::Failed("tests failed".to_string()));
+        assert_eq!(status(&lost), TaskStatus::Orphaned);
+        assert_eq!(journal.orphaned_tasks().count(), 1);
+
+        // Compacted to the blocked task, which survives another restart
+        let mut journal = Journal::replay(&path, |_| true).unwrap();
+        assert_eq!(journal.tasks().len(), 1);
+        assert_eq!(journal.tasks()[0].task_id, blocked);
+        assert_eq!(journal.tasks()[0].status, TaskStatus::Blocked("Needs a database URL".to_string()));
+        journal.complete_session("api");
+        assert_eq!(journal.active_tasks().count(), 0);
+        assert_eq!(journal.tasks()[0].status, TaskStatus::Completed);
+    }
+}
@@ -10,6 +10,17 @@
 //! - Multiple Session Agents (one per tmux/VS Code session)
 //! - Shared memory store for agent memories
 //! - Auto-eval for feedback tracking
+//! - Parallel task fan-out across sessions (see [`fanout`])
+//! - A circuit breaker that degrades to non-LLM summaries while the model
+//!   provider is down (see [`circuit`])
+//! - Approval and execution of task plans from the User Agent (see [`planning`])
+//! - Optional peer review of plans by a second, cheaper agent before approval
+//! - Declarative YAML workflows with per-step checks, retries and resume
+//!   (see [`workflow`])
+//! - A message bus carrying status, help requests and shared knowledge
+//!   between Session Agents (see [`bus`])
+//! - A journal of delegated tasks, replayed on startup to recover in-flight
+//!   work after a crash (see [`journal`])
 //!
 //! # Example
 //!
@@ -32,14 +43,30 @@
 //! # }
 //! ```
 
+pub mod bus;
+pub mod circuit;
 mod error;
+pub mod fanout;
+pub mod journal;
 mod orchestrator;
+pub mod planning;
+pub mod workflow;
 
+pub use bus::{DeliveryPolicy, MessageBus};
+pub use circuit::{CircuitBreaker, CircuitState};
 pub use error::{OrchestratorError, Result};
+pub use fanout::{FanOutConfig, ParallelReport, TaskOutcome, TaskResult, TaskRunner, TaskSpec};
+pub use journal::{Journal, JournalEvent, TaskStatus, TrackedTask};
 pub use orchestrator::AgentOrchestrator;
+pub use planning::{PlanEvent, PlanReport, PlanRun};
+pub use workflow::{
+    list_workflows, RunStatus, StepRecord, Workflow, WorkflowEvent, WorkflowExecution, WorkflowReport,
+    WorkflowRun, WorkflowStep,
+};
 
 // Re-export commonly used types from commander-agent
 pub use commander_agent::{
-    AgentContext, AgentResponse, FeedbackSummary, OutputAnalysis, SessionAgent, SessionState,
+    AgentContext, AgentMessage, AgentResponse, ClientMetrics, ContextUsage, FeedbackSummary, KeyUsage, KnowledgeEntry,
+    MessageKind, OutputAnalysis, PendingApproval, Plan, PlanTask, SessionAgent, SessionState, TaskCheckpoint,
     UserAgent,
 };
@@ -1,18 +1,39 @@
 //! Agent orchestrator for coordinating the multi-agent system.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use commander_agent::{
-    template::AdapterType, AutoEval, FeedbackSummary, OutputAnalysis, SessionAgent,
-    UserAgent,
+    list_knowledge, review_knowledge, template::AdapterType, AgentError, AutoEval, BudgetUsage,
+    ClientMetrics, ContextUsage, KeyUsage, Critique, Reviewer, FeedbackSummary, KnowledgeEntry, ModelRouter, OutputAnalysis, PendingApproval, Plan,
+    AgentMessage, AgentResponse, MessageKind, SessionAgent, SessionState, TaskBudget, TaskCheckpoint, UserAgent,
 };
-use commander_memory::{LocalStore, MemoryStore};
+use commander_core::{
+    connectivity, learned_patterns_file, AgentSettings, ChangeNotification, ErrorDiagnosis, LearnedPatterns,
+    PermissionSettings, ProgressMilestones,
+};
+use commander_memory::{
+    Backfill, ConsolidationReport, Consolidator, EmbeddingGenerator, LlmMerger, LocalStore, MemoryStore,
+};
+use commander_persistence::WorkStore;
+use commander_tmux::TmuxOrchestrator;
+use commander_work::{SlaPolicy, WorkQueue};
 
+use crate::bus::MessageBus;
+use crate::circuit::{CircuitBreaker, CircuitState};
 use crate::error::{OrchestratorError, Result};
+use crate::fanout::{
+    aggregation_prompt, format_results, run_parallel, FanOutConfig, ParallelReport, TaskOutcome,
+    TaskRunner, TaskSpec, TmuxTaskRunner,
+};
+use crate::journal::{Journal, TrackedTask};
+use crate::planning::PlanRun;
+
+/// Change detector feedback entries per adapter between pattern regenerations.
+const RELEARN_INTERVAL: usize = 10;
 
 /// Agent orchestrator that coordinates the User Agent and Session Agents.
 ///
@@ -29,6 +50,54 @@ pub struct AgentOrchestrator {
 
     /// Auto-eval for feedback tracking.
     auto_eval: AutoEval,
+
+    /// Change detector feedback recorded per adapter since patterns were last learned.
+    pending_significance_feedback: HashMap<String, usize>,
+
+    /// Work queue tracking parallel fan-out tasks.
+    work_queue: WorkQueue,
+
+    /// Parallel fan-out settings.
+    fanout_config: FanOutConfig,
+
+    /// Suspends LLM calls while the model provider is down.
+    llm_circuit: CircuitBreaker,
+
+    /// Connectivity reconnects seen, to embed memories stored offline.
+    reconnects_seen: u64,
+
+    /// Directory holding work items of fan-outs and plan runs.
+    data_dir: PathBuf,
+
+    /// Plan proposed by the User Agent, awaiting user approval.
+    pending_plan: Option<Plan>,
+
+    /// Model for Session Agents from `config.toml`, if set.
+    session_model: Option<String>,
+
+    /// Sessions connected in read-only mode.
+    read_only_sessions: HashSet<String>,
+
+    /// Model router from `config.toml`, if routing is enabled.
+    router: Option<ModelRouter>,
+
+    /// Tool permission profiles from `config.toml`.
+    permissions: PermissionSettings,
+
+    /// Last structured result of each agent, by agent ID.
+    last_results: HashMap<String, serde_json::Value>,
+
+    /// Per-task budget from `config.toml`.
+    task_budget: TaskBudget,
+
+    /// Last peer review of the plan awaiting approval.
+    plan_review: Option<Critique>,
+
+    /// Journal of delegated tasks, replayed on startup.
+    journal: Arc<Mutex<Journal>>,
+
+    /// Messages between agents.
+    bus: MessageBus,
 }
 
 impl AgentOrchestrator {
@@ -62,26 +131,61 @@ impl AgentOrchestrator {
         let auto_eval =
             AutoEval::new(feedback_path).map_err(OrchestratorError::Agent)?;
 
+        let journal = replay_journal(&data_dir);
+
         Ok(Self {
             user_agent,
             session_agents: HashMap::new(),
             memory_store,
             auto_eval,
+            pending_significance_feedback: HashMap::new(),
+            work_queue: WorkQueue::new(WorkStore::new(&data_dir)),
+            fanout_config: FanOutConfig::load_or_default(&FanOutConfig::default_path()),
+            llm_circuit: CircuitBreaker::default(),
+            reconnects_seen: connectivity::reconnects(),
+            data_dir,
+            pending_plan: None,
+            session_model: None,
+            read_only_sessions: HashSet::new(),
+            router: None,
+            permissions: PermissionSettings::default(),
+            last_results: HashMap::new(),
+            task_budget: TaskBudget::default(),
+            plan_review: None,
+            journal: Arc::new(Mutex::new(journal)),
+            bus: MessageBus::new(),
         })
     }
 
     /// Process user input through the User Agent.
     ///
-    /// Returns the agent's response text.
+    /// Returns the agent's response text. Fails fast with
+    /// [`OrchestratorError::ProviderUnavailable`] while the LLM circuit
+    /// breaker is open, and with [`OrchestratorError::Offline`] offline.
     pub async fn process_user_input(&mut self, input: &str) -> Result<String> {
+        self.process_user_input_structured(input).await.map(|response| response.content)
+    }
+
+    /// Process user input through the User Agent, returning the whole
+    /// response, with its structured result in structured output mode.
+    pub async fn process_user_input_structured(&mut self, input: &str) -> Result<AgentResponse> {
         debug!(input_len = input.len(), "Processing user input");
 
+        if !self.llm_allowed() {
+            return Err(self.provider_unavailable());
+        }
+
+        self.refresh_overdue_work();
         let context = self.user_agent.context().clone();
-        let response = self
-            .user_agent
-            .process(input, &context)
-            .await
-            .map_err(OrchestratorError::Agent)?;
+        let result = self.user_agent.process(input, &context).await;
+        self.record_llm_result(result.as_ref().map(|_| ()));
+        let response = result.map_err(OrchestratorError::Agent)?;
+
+        if let Some(plan) = self.user_agent.take_proposed_plan() {
+            info!(tasks = plan.tasks.len(), "User Agent proposed a plan");
+            let plan = self.review_plan(plan, None).await;
+            self.pending_plan = Some(plan);
+        }
 
         // Track feedback
         let _ = self
@@ -95,7 +199,41 @@ impl AgentOrchestrator {
             )
             .await;
 
-        Ok(response.content)
+        if let Some(result) = &response.structured_output {
+            self.last_results
+                .insert(self.user_agent.id().to_string(), result.clone());
+        }
+
+        Ok(response)
+    }
+
+    /// Last structured result of an agent, as it returned it.
+    pub fn last_result(&self, agent_id: &str) -> Option<&serde_json::Value> {
+        self.last_results.get(agent_id)
+    }
+
+    /// Hand the work items past their deadline to the User Agent, so it
+    /// weighs them first.
+    pub fn refresh_overdue_work(&mut self) {
+        let queue = WorkQueue::new(WorkStore::new(&self.data_dir));
+        if let Err(e) = queue.load_all() {
+            warn!(error = %e, "Failed to load work items for SLA check");
+        }
+        let policy = SlaPolicy::default();
+        let now = chrono::Utc::now();
+        let overdue: Vec<String> = queue
+            .overdue(now)
+            .iter()
+            .map(|item| {
+                let content = item.content.lines().next().unwrap_or_default();
+                let tag = policy.tag(item, now).unwrap_or_default();
+                format!("[{}] {} {}", item.project_id, content, tag)
+            })
+            .collect();
+        if !overdue.is_empty() {
+            debug!(count = overdue.len(), "Overdue work handed to User Agent");
+        }
+        self.user_agent.set_overdue_work(overdue);
     }
 
     /// Get or create a session agent for the given session.
@@ -119,9 +257,19 @@ impl AgentOrchestrator {
                 "Creating new session agent"
             );
 
-            let agent = SessionAgent::new(session_id, adapter, Arc::clone(&self.memory_store))
+            let mut agent = SessionAgent::new(session_id, adapter, Arc::clone(&self.memory_store))
                 .map_err(OrchestratorError::Agent)?;
+            if let Some(model) = &self.session_model {
+                agent.set_model(Some(model));
+            }
+            if self.read_only_sessions.contains(session_id) {
+                agent.set_read_only(true);
+            }
+            agent.set_router(self.router.clone());
+            agent.set_permission_profile(self.permissions.profile_for(agent.project()));
+            agent.set_task_budget(self.task_budget);
 
+            self.bus.subscribe(session_id, &MessageKind::ALL);
             self.session_agents.insert(session_id.to_string(), agent);
         }
 
@@ -146,15 +294,551 @@ impl AgentOrchestrator {
             "Processing session output"
         );
 
+        self.retry_pending_embeddings();
+        let llm_allowed = self.llm_allowed();
+        // Created first, so messages waiting for it are handed over
+        self.get_session_agent(session_id, adapter_type)?;
+        self.route_agent_messages();
         let agent = self.get_session_agent(session_id, adapter_type)?;
-        let analysis = agent
-            .analyze_output(output)
-            .await
-            .map_err(OrchestratorError::Agent)?;
+        if !llm_allowed {
+            return Ok(agent.analyze_output_offline(output));
+        }
 
+        let analysis = match agent.analyze_output(output).await {
+            Ok(analysis) => {
+                self.llm_circuit.record_success();
+                analysis
+            }
+            Err(e @ AgentError::ProviderUnavailable(_)) => {
+                warn!(session_id = %session_id, error = %e, "Output analysis unavailable, using heuristics");
+                let analysis = agent.analyze_output_offline(output);
+                self.llm_circuit.record_failure();
+                analysis
+            }
+            Err(AgentError::BudgetExhausted(_)) => agent.analyze_output_offline(output),
+            Err(e) => return Err(OrchestratorError::Agent(e)),
+        };
+        self.journal_analysis(session_id, &analysis);
         Ok(analysis)
     }
 
+    /// Record completions and blockers detected in a session's output
+    /// against the tasks delegated to it.
+    fn journal_analysis(&self, session_id: &str, analysis: &OutputAnalysis) {
+        let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        if analysis.detected_completion {
+            journal.complete_session(session_id);
+        } else if let Some(error) = &analysis.error_detected {
+            journal.block_session(session_id, error);
+        }
+    }
+
+    /// Diagnose the error in a session's output and suggest a fix.
+    ///
+    /// Returns `Ok(None)` when the output shows no error, the error was
+    /// already diagnosed, the LLM is unavailable, or the task's budget is
+    /// spent.
+    pub async fn diagnose_session_error(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        output: &str,
+    ) -> Result<Option<ErrorDiagnosis>> {
+        if !self.llm_allowed() {
+            return Ok(None);
+        }
+        let agent = self.get_session_agent(session_id, adapter_type)?;
+        let result = agent.diagnose_error(output).await;
+        self.record_llm_result(result.as_ref().map(|_| ()));
+        match result {
+            Ok(diagnosis) => Ok(diagnosis),
+            Err(e @ AgentError::ProviderUnavailable(_)) => {
+                warn!(session_id = %session_id, error = %e, "Error diagnosis unavailable");
+                Ok(None)
+            }
+            Err(AgentError::BudgetExhausted(_)) => Ok(None),
+            Err(e) => Err(OrchestratorError::Agent(e)),
+        }
+    }
+
+    /// Run several tasks concurrently, each in its own session.
+    ///
+    /// Sessions that don't exist are created and their adapter launched. At
+    /// most `max_concurrent` tasks (from `fanout.yaml`) run at once. Each
+    /// completed task's output is analyzed by its Session Agent, and the User
+    /// Agent aggregates everything into a single report.
+    pub async fn delegate_parallel(&mut self, tasks: Vec<TaskSpec>) -> Result<ParallelReport> {
+        let runner = TmuxTaskRunner::new(self.fanout_config.clone())?;
+        self.delegate_parallel_with(tasks, Arc::new(runner)).await
+    }
+
+    /// [`Self::delegate_parallel`] with a custom task runner.
+    pub async fn delegate_parallel_with(
+        &mut self,
+        tasks: Vec<TaskSpec>,
+        runner: Arc<dyn TaskRunner>,
+    ) -> Result<ParallelReport> {
+        let mut delegated: Vec<(String, String, String)> = {
+            let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+            tasks
+                .iter()
+                .map(|t| (journal.delegate(&t.session_id, &t.task), t.session_id.clone(), t.task.clone()))
+                .collect()
+        };
+        let mut results = run_parallel(
+            &self.work_queue,
+            runner,
+            tasks,
+            self.fanout_config.max_concurrent,
+        )
+        .await?;
+
+        {
+            let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+            for result in &results {
+                let Some(index) = delegated
+                    .iter()
+                    .position(|(_, session, task)| *session == result.spec.session_id && *task == result.spec.task)
+                else {
+                    continue;
+                };
+                let (task_id, _, _) = delegated.remove(index);
+                match &result.outcome {
+                    TaskOutcome::Completed { .. } => journal.complete(&task_id),
+                    TaskOutcome::Failed { error } => journal.fail(&task_id, error),
+                }
+            }
+        }
+
+        for result in &mut results {
+            let TaskOutcome::Completed { output } = &result.outcome else {
+                continue;
+            };
+            match self
+                .process_session_output(&result.spec.session_id, &result.spec.adapter, output)
+                .await
+            {
+                Ok(analysis) => result.summary = Some(analysis.summary),
+                Err(e) => {
+                    warn!(session = %result.spec.session_id, error = %e, "Failed to analyze task output");
+                }
+            }
+        }
+
+        let report = match self.process_user_input(&aggregation_prompt(&results)).await {
+            Ok(report) => report,
+            Err(e) => {
+                warn!(error = %e, "User Agent aggregation failed, using plain report");
+                format_results(&results)
+            }
+        };
+
+        Ok(ParallelReport { results, report })
+    }
+
+    /// Break `goal` into a plan and hold it for approval.
+    ///
+    /// With peer review enabled the plan is critiqued, and revised, first;
+    /// see [`Self::review_plan`]. Replaces any plan already awaiting approval.
+    pub async fn propose_plan(&mut self, goal: &str, context: Option<&str>) -> Result<&Plan> {
+        if !self.llm_allowed() {
+            return Err(self.provider_unavailable());
+        }
+
+        let result = self.user_agent.plan(goal, context).await;
+        self.record_llm_result(result.as_ref().map(|_| ()));
+        let plan = result.map_err(OrchestratorError::Agent)?;
+        let plan = self.review_plan(plan, context).await;
+        Ok(self.pending_plan.insert(plan))
+    }
+
+    /// Have the reviewer critique `plan`, re-planning with each critique
+    /// that flags issues until the reviewer approves or its rounds run out.
+    ///
+    /// The last critique is kept for [`Self::plan_review`]. Without a
+    /// reviewer, or when review or revision fails, the latest plan is
+    /// returned as is.
+    async fn review_plan(&mut self, mut plan: Plan, context: Option<&str>) -> Plan {
+        self.plan_review = None;
+        let Some(rounds) = self.user_agent.reviewer().map(Reviewer::max_rounds) else {
+            return plan;
+        };
+
+        for round in 1..=rounds {
+            let draft = commander_agent::review::plan_draft(&plan);
+            let critique = match self.user_agent.review_draft("plan", &draft).await {
+                Ok(Some(critique)) => critique,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!(error = %e, "Plan review failed, keeping the plan unreviewed");
+                    break;
+                }
+            };
+            if critique.is_approved() || round == rounds {
+                self.plan_review = Some(critique);
+                break;
+            }
+
+            info!(round, issues = critique.issues.len(), "Revising plan after review");
+            let revision = commander_agent::review::revision_context(context, &draft, &critique);
+            let result = self.user_agent.plan(&plan.goal, Some(&revision)).await;
+            self.record_llm_result(result.as_ref().map(|_| ()));
+            match result {
+                Ok(revised) => plan = revised,
+                Err(e) => {
+                    warn!(error = %e, "Plan revision failed, keeping the reviewed plan");
+                    self.plan_review = Some(critique);
+                    break;
+                }
+            }
+        }
+        plan
+    }
+
+    /// Plan awaiting user approval, if any.
+    pub fn pending_plan(&self) -> Option<&Plan> {
+        self.pending_plan.as_ref()
+    }
+
+    /// Last peer review of the plan awaiting approval, if it was reviewed.
+    pub fn plan_review(&self) -> Option<&Critique> {
+        self.pending_plan.as_ref().and(self.plan_review.as_ref())
+    }
+
+    /// Discard the plan awaiting approval.
+    pub fn discard_plan(&mut self) -> Option<Plan> {
+        self.plan_review = None;
+        self.pending_plan.take()
+    }
+
+    /// Approve the pending plan, preparing it to run in `template`'s session.
+    ///
+    /// Each task is sent to the session with the template's adapter and
+    /// directory. Returns `None` when no plan is awaiting approval. The
+    /// returned run blocks while executing; see [`PlanRun::run`].
+    pub fn approve_plan(&mut self, template: TaskSpec) -> Result<Option<PlanRun>> {
+        let runner = TmuxTaskRunner::new(self.fanout_config.clone())?;
+        self.approve_plan_with(template, Arc::new(runner))
+    }
+
+    /// [`Self::approve_plan`] with a custom task runner.
+    pub fn approve_plan_with(
+        &mut self,
+        template: TaskSpec,
+        runner: Arc<dyn TaskRunner>,
+    ) -> Result<Option<PlanRun>> {
+        let Some(plan) = self.pending_plan.take() else {
+            return Ok(None);
+        };
+        self.plan_review = None;
+        // A dedicated queue, so fan-outs and the run never dequeue each other's items
+        let queue = WorkQueue::new(WorkStore::new(&self.data_dir));
+        PlanRun::new(plan, template, queue, runner).map(|run| Some(run.with_journal(Arc::clone(&self.journal))))
+    }
+
+    /// Apply `[agents]` settings from `config.toml` to the User Agent and
+    /// every Session Agent, including ones created later.
+    ///
+    /// Unset models restore the agents' defaults; with `[agents.routing]`
+    /// enabled, each request is routed to a model tier instead. Permission
+    /// profiles from `[agents.permissions]` take effect on the next tool
+    /// call, and `[agents.budget]` limits from the next delegated task.
+    /// `[agents.review]` enables peer review of plans and delegated tasks.
+    /// Conversations are kept.
+    pub fn apply_agent_settings(&mut self, settings: &AgentSettings) {
+        info!(
+            user_model = ?settings.user_model,
+            session_model = ?settings.session_model,
+            routing = settings.routing.enabled,
+            "Applying agent settings"
+        );
+        self.router = ModelRouter::from_settings(&settings.routing);
+        self.user_agent.set_model(settings.user_model.as_deref());
+        self.user_agent.set_router(self.router.clone());
+        self.user_agent.set_permissions(settings.permissions.clone());
+        self.user_agent.set_structured_output(settings.structured_output);
+        self.task_budget = TaskBudget::from_settings(&settings.budget);
+        self.user_agent.set_task_budget(self.task_budget);
+        self.user_agent.set_reviewer(Reviewer::from_settings(&settings.review));
+        self.session_model = settings.session_model.clone();
+        self.permissions = settings.permissions.clone();
+        for agent in self.session_agents.values_mut() {
+            agent.set_model(settings.session_model.as_deref());
+            agent.set_router(self.router.clone());
+            agent.set_permission_profile(self.permissions.profile_for(agent.project()));
+            agent.set_task_budget(self.task_budget);
+        }
+    }
+
+    /// Switch a session's read-only mode, which strips write-capable tools
+    /// from its Session Agent, including one created later.
+    pub fn set_session_read_only(&mut self, session_id: &str, read_only: bool) {
+        if read_only {
+            self.read_only_sessions.insert(session_id.to_string());
+        } else {
+            self.read_only_sessions.remove(session_id);
+        }
+        if let Some(agent) = self.session_agents.get_mut(session_id) {
+            agent.set_read_only(read_only);
+        }
+    }
+
+    /// Move a session's agent to the template of `adapter_type` after its
+    /// runtime was relaunched with that adapter (`claude-code`, `mpm`...).
+    ///
+    /// Returns the agent's summary of the session so far, to hand to the new
+    /// runtime. Sessions without an agent get one for the new adapter when
+    /// their output is next analyzed.
+    pub fn switch_session_adapter(&mut self, session_id: &str, adapter_type: &str) -> Option<String> {
+        let agent = self.session_agents.get_mut(session_id)?;
+        let adapter = adapter_type
+            .parse::<AdapterType>()
+            .unwrap_or(AdapterType::Generic);
+        agent.switch_adapter(adapter);
+        agent.context_summary()
+    }
+
+    /// Parallel fan-out settings.
+    pub fn fanout_config(&self) -> &FanOutConfig {
+        &self.fanout_config
+    }
+
+    /// Run a session's output through its change detector.
+    ///
+    /// Only significant changes reach LLM analysis. When the analysis
+    /// disagrees with the detector, the verdict is recorded so the adapter's
+    /// significance patterns can be re-learned.
+    pub async fn process_output_change(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        output: &str,
+    ) -> Result<Option<ChangeNotification>> {
+        self.retry_pending_embeddings();
+        let llm_allowed = self.llm_allowed();
+        let agent = self.get_session_agent(session_id, adapter_type)?;
+        let adapter = agent.adapter_type().to_string();
+        let notification = if llm_allowed {
+            agent.process_output_change(output).await
+        } else {
+            agent.process_output_change_offline(output).await
+        }
+        .map_err(OrchestratorError::Agent)?;
+
+        let llm_outcome = agent.take_llm_outcome();
+        let verdict = agent.take_change_verdict();
+        if let Some(outcome) = llm_outcome {
+            self.record_llm_result(outcome.as_ref().map(|_| ()));
+        }
+
+        if let Some(verdict) = verdict {
+            self.auto_eval
+                .record_change_verdict(&adapter, &verdict.pattern, verdict.significant, &verdict.summary)
+                .await
+                .map_err(OrchestratorError::Agent)?;
+            self.note_significance_feedback(&adapter, 1);
+        }
+
+        Ok(notification)
+    }
+
+    /// Report output the change detector should have flagged as significant.
+    ///
+    /// Each non-empty line of `text` is recorded as a missed event. Returns
+    /// the number of lines recorded.
+    pub async fn report_missed_change(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        text: &str,
+    ) -> Result<usize> {
+        let adapter = self
+            .get_session_agent(session_id, adapter_type)?
+            .adapter_type()
+            .to_string();
+
+        let mut recorded = 0;
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            self.auto_eval
+                .record_missed_change(&adapter, line)
+                .await
+                .map_err(OrchestratorError::Agent)?;
+            recorded += 1;
+        }
+        self.note_significance_feedback(&adapter, recorded);
+        Ok(recorded)
+    }
+
+    /// Regenerate an adapter's learned significance patterns from feedback.
+    ///
+    /// Persists them under the config dir and applies them to the adapter's
+    /// active session agents.
+    pub fn relearn_significance(&mut self, adapter: &str) -> Result<LearnedPatterns> {
+        let learned = self.auto_eval.learn_patterns(adapter);
+        let path = learned_patterns_file(adapter);
+        learned.save(&path).map_err(|e| {
+            OrchestratorError::Configuration(format!(
+                "Failed to save learned patterns to {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        for agent in self.session_agents.values_mut() {
+            if agent.adapter_type().to_string() == adapter {
+                agent.change_detector_mut().apply_learned(&learned);
+            }
+        }
+
+        info!(
+            adapter = %adapter,
+            weights = learned.weights.len(),
+            patterns = learned.significant.len(),
+            "Learned significance patterns"
+        );
+        self.pending_significance_feedback.remove(adapter);
+        Ok(learned)
+    }
+
+    fn note_significance_feedback(&mut self, adapter: &str, count: usize) {
+        let pending = self
+            .pending_significance_feedback
+            .entry(adapter.to_string())
+            .or_insert(0);
+        *pending += count;
+        if *pending >= RELEARN_INTERVAL {
+            if let Err(e) = self.relearn_significance(adapter) {
+                warn!(adapter = %adapter, error = %e, "Failed to relearn significance patterns");
+            }
+        }
+    }
+
+    /// Record file changes reported by a filesystem watcher for a session.
+    ///
+    /// The session agent uses these instead of guessing changed files from output.
+    pub fn record_files_changed<I, S>(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        files: I,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let agent = self.get_session_agent(session_id, adapter_type)?;
+        agent.record_files_changed(files);
+        Ok(())
+    }
+
+    /// Record output from one of a session's auxiliary panes (dev server,
+    /// test runner), so its Session Agent can attribute lines to panes.
+    pub fn record_pane_output(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        pane: &str,
+        output: &str,
+    ) -> Result<()> {
+        let agent = self.get_session_agent(session_id, adapter_type)?;
+        agent.record_pane_output(pane, output);
+        Ok(())
+    }
+
+    /// Checkpoint the project before a task is delegated to a session, and
+    /// start the task's budget.
+    ///
+    /// Returns `Ok(None)` when the project is not a git repository.
+    pub fn checkpoint_task(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        project_path: &Path,
+        task: &str,
+    ) -> Result<Option<TaskCheckpoint>> {
+        let agent = self.get_session_agent(session_id, adapter_type)?;
+        agent.start_task_budget();
+        let checkpoint = agent
+            .checkpoint_task(project_path, task)
+            .map_err(OrchestratorError::Agent)?;
+        Ok(checkpoint.cloned())
+    }
+
+    /// Budget usage of the session's current task, if it has a budget.
+    pub fn task_budget_usage(&self, session_id: &str) -> Option<BudgetUsage> {
+        self.session_agents.get(session_id)?.budget_usage()
+    }
+
+    /// Let the session's current task continue past its spent budget.
+    ///
+    /// Returns false when the session has no spent budget.
+    pub fn approve_task_budget(&mut self, session_id: &str) -> bool {
+        self.session_agents
+            .get_mut(session_id)
+            .is_some_and(|agent| agent.approve_budget())
+    }
+
+    /// Preview what undoing the last task would revert.
+    ///
+    /// Returns the checkpoint and the files changed since it was taken, or
+    /// `None` if the session has nothing to undo.
+    pub fn preview_undo(&self, session_id: &str) -> Result<Option<(TaskCheckpoint, Vec<String>)>> {
+        let Some(checkpoint) = self
+            .session_agents
+            .get(session_id)
+            .and_then(|agent| agent.last_checkpoint())
+        else {
+            return Ok(None);
+        };
+
+        let files = checkpoint.changed_files().map_err(OrchestratorError::Agent)?;
+        Ok(Some((checkpoint.clone(), files)))
+    }
+
+    /// Roll the session's project back to before its last delegated task.
+    ///
+    /// Callers should confirm with the user first (see [`Self::preview_undo`]).
+    pub fn undo_last_task(&mut self, session_id: &str) -> Result<Option<(TaskCheckpoint, Vec<String>)>> {
+        let Some(agent) = self.session_agents.get_mut(session_id) else {
+            return Ok(None);
+        };
+        agent.undo_last_task().map_err(OrchestratorError::Agent)
+    }
+
+    /// State tracked by a session's agent, if one exists.
+    pub fn session_state(&self, session_id: &str) -> Option<&SessionState> {
+        self.session_agents.get(session_id).map(|agent| agent.state())
+    }
+
+    /// Milestones of a session's task in progress, from its collected output.
+    ///
+    /// Uses the session's agent when one exists, so watcher-reported files
+    /// and the tracked task are included.
+    pub fn session_progress(&self, session_id: &str, lines: &[String]) -> ProgressMilestones {
+        match self.session_agents.get(session_id) {
+            Some(agent) => agent.progress_milestones(lines),
+            None => ProgressMilestones::from_output(lines),
+        }
+    }
+
+    /// Seed a session's agent with state saved before a restart.
+    pub fn restore_session_state(
+        &mut self,
+        session_id: &str,
+        adapter_type: &str,
+        state: SessionState,
+    ) -> Result<()> {
+        *self.get_session_agent(session_id, adapter_type)?.state_mut() = state;
+        Ok(())
+    }
+
+    /// Context window usage of a session's agent, if one exists.
+    pub fn context_usage(&self, session_id: &str) -> Option<ContextUsage> {
+        self.session_agents
+            .get(session_id)
+            .map(|agent| agent.context_usage())
+    }
+
     /// Get reference to the User Agent.
     pub fn user_agent(&self) -> &UserAgent {
         &self.user_agent
@@ -165,6 +849,21 @@ impl AgentOrchestrator {
         &mut self.user_agent
     }
 
+    /// Get the command awaiting guardrail approval, if any.
+    pub fn pending_approval(&self) -> Option<&PendingApproval> {
+        self.user_agent.pending_approval()
+    }
+
+    /// Approve the command awaiting guardrail approval.
+    pub fn approve_pending(&mut self) -> Option<PendingApproval> {
+        self.user_agent.approve_pending()
+    }
+
+    /// Deny the command awaiting guardrail approval.
+    pub fn deny_pending(&mut self) -> Option<PendingApproval> {
+        self.user_agent.deny_pending()
+    }
+
     /// Get feedback summary for the User Agent.
     pub fn feedback_summary(&self) -> FeedbackSummary {
         self.auto_eval.summary(self.user_agent.id())
@@ -182,13 +881,164 @@ impl AgentOrchestrator {
 
     /// Remove a session agent.
     pub fn remove_session(&mut self, session_id: &str) -> Option<SessionAgent> {
+        self.bus.unsubscribe(session_id);
         self.session_agents.remove(session_id)
     }
 
+    /// Publish a message to other agents on the bus, as the session agents'
+    /// `send_agent_message` tool does. Returns the number of agents it was
+    /// delivered to; they see it on their next analysis.
+    pub fn publish_agent_message(&mut self, message: AgentMessage) -> usize {
+        let delivered = self.bus.publish(message);
+        self.route_agent_messages();
+        delivered
+    }
+
+    /// The message bus.
+    pub fn message_bus(&self) -> &MessageBus {
+        &self.bus
+    }
+
+    /// The message bus, to subscribe agents or change delivery policies.
+    pub fn message_bus_mut(&mut self) -> &mut MessageBus {
+        &mut self.bus
+    }
+
+    /// Publish the messages session agents sent, then hand each agent the
+    /// messages waiting for it.
+    fn route_agent_messages(&mut self) {
+        for agent in self.session_agents.values() {
+            for message in agent.take_sent_messages() {
+                self.bus.publish(message);
+            }
+        }
+        for (session_id, agent) in &mut self.session_agents {
+            let messages = self.bus.take(session_id);
+            if !messages.is_empty() {
+                agent.deliver_messages(messages);
+            }
+        }
+    }
+
+    /// Tasks delegated to sessions, including ones in flight when the
+    /// previous orchestrator stopped (replayed from the journal; those whose
+    /// session was gone are [`TaskStatus::Orphaned`](crate::TaskStatus::Orphaned)).
+    pub fn tracked_tasks(&self) -> Vec<TrackedTask> {
+        self.journal.lock().unwrap_or_else(|e| e.into_inner()).tasks().to_vec()
+    }
+
     /// Get the memory store.
     pub fn memory_store(&self) -> &Arc<dyn MemoryStore> {
         &self.memory_store
     }
+
+    /// State of the LLM circuit breaker.
+    pub fn llm_circuit_state(&self) -> CircuitState {
+        self.llm_circuit.state()
+    }
+
+    /// Request metrics of the model provider client (queue depth, retries,
+    /// rate-limited responses).
+    pub fn provider_metrics(&self) -> ClientMetrics {
+        self.user_agent.client_metrics()
+    }
+
+    /// Usage of each OpenRouter API key, including spend, shared by every
+    /// agent's client.
+    pub fn provider_key_usage(&self) -> Vec<KeyUsage> {
+        self.user_agent.client_key_usage()
+    }
+
+    /// Feed the outcome of an LLM call into the circuit breaker.
+    ///
+    /// Only provider outages count as failures.
+    fn record_llm_result(&mut self, result: std::result::Result<(), &AgentError>) {
+        match result {
+            Ok(()) => self.llm_circuit.record_success(),
+            Err(AgentError::ProviderUnavailable(_)) => self.llm_circuit.record_failure(),
+            Err(_) => {}
+        }
+    }
+
+    /// Whether LLM calls may be made: the network is up and the provider's
+    /// circuit is closed.
+    fn llm_allowed(&self) -> bool {
+        !connectivity::is_offline() && self.llm_circuit.allows_call()
+    }
+
+    /// Once connectivity is back, embed the memories agents stored without an
+    /// embedding while offline.
+    fn retry_pending_embeddings(&mut self) {
+        let reconnects = connectivity::reconnects();
+        if reconnects == self.reconnects_seen {
+            return;
+        }
+        self.reconnects_seen = reconnects;
+        let store = Arc::clone(&self.memory_store);
+        tokio::spawn(async move {
+            let backfill = Backfill::new(EmbeddingGenerator::from_env()).with_pending_only(true);
+            match backfill.run(store.as_ref()).await {
+                Ok(report) if report.reembedded > 0 => {
+                    info!(count = report.reembedded, "Embedded memories stored while offline");
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Failed to embed memories stored while offline"),
+            }
+        });
+    }
+
+    fn provider_unavailable(&self) -> OrchestratorError {
+        if connectivity::is_offline() {
+            return OrchestratorError::Offline;
+        }
+        let retry_in = self.llm_circuit.retry_in().unwrap_or_default();
+        OrchestratorError::ProviderUnavailable(retry_in.as_secs().max(1))
+    }
+
+    /// Knowledge promoted by the Session Agents of `project`, unreviewed first.
+    pub async fn project_knowledge(&self, project: &str) -> Result<Vec<KnowledgeEntry>> {
+        list_knowledge(self.memory_store.as_ref(), project)
+            .await
+            .map_err(OrchestratorError::Agent)
+    }
+
+    /// Keep or drop the knowledge entry of `project` whose ID starts with
+    /// `id_prefix`.
+    ///
+    /// Returns the entry, or `None` if the prefix does not match exactly one.
+    pub async fn review_project_knowledge(
+        &self,
+        project: &str,
+        id_prefix: &str,
+        keep: bool,
+    ) -> Result<Option<KnowledgeEntry>> {
+        let entries = self.project_knowledge(project).await?;
+        let mut matches = entries.into_iter().filter(|e| e.id.starts_with(id_prefix));
+        let (Some(entry), None) = (matches.next(), matches.next()) else {
+            return Ok(None);
+        };
+
+        review_knowledge(self.memory_store.as_ref(), &entry.id, keep)
+            .await
+            .map_err(OrchestratorError::Agent)?;
+        info!(project = %project, id = %entry.id, keep, "Reviewed project knowledge");
+        Ok(Some(entry))
+    }
+
+    /// Periodic maintenance: consolidate near-duplicate memories of all agents.
+    ///
+    /// Clusters are merged by the summarization model; clusters it cannot
+    /// merge keep their newest memory.
+    pub async fn run_maintenance(&self) -> Result<ConsolidationReport> {
+        let consolidator = Consolidator::new()
+            .with_merger(Arc::new(LlmMerger::from_env()), EmbeddingGenerator::from_env());
+        let report = consolidator
+            .consolidate_all(self.memory_store.as_ref())
+            .await
+            .map_err(OrchestratorError::Memory)?;
+        info!(%report, "Memory maintenance finished");
+        Ok(report)
+    }
 }
 
 // Implement traits that might be needed for the User Agent
@@ -201,6 +1051,21 @@ impl AgentOrchestrator {
     }
 }
 
+/// Replay the journal in `data_dir`, orphaning tasks whose tmux session is
+/// gone. A journal that cannot be read is logged and started afresh.
+fn replay_journal(data_dir: &Path) -> Journal {
+    let path = Journal::path_in(data_dir);
+    let tmux = TmuxOrchestrator::new().ok();
+    let alive = |session: &str| tmux.as_ref().is_some_and(|t| t.session_exists(session));
+    match Journal::replay(&path, alive) {
+        Ok(journal) => journal,
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "Failed to replay orchestrator journal");
+            Journal::new(path)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
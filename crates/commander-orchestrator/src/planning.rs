@@ -0,0 +1,282 @@
+//! Plan approval and execution.
+//!
+//! The User Agent's `plan` tool (or [`AgentOrchestrator::propose_plan`](crate::AgentOrchestrator::propose_plan))
+//! produces a [`Plan`], which the orchestrator holds until the user approves
+//! it. An approved plan becomes a [`PlanRun`]: each task is enqueued as a work
+//! item whose dependencies mirror the plan, and [`PlanRun::run`] dispatches
+//! ready items one at a time into the project's session while a
+//! [`CompletionDriver`] tracks per-task status. A failed task stops the run;
+//! tasks that were not reached are reported as skipped.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tracing::{info, warn};
+
+use commander_agent::{Blocker, CompletionDriver, ContinueDecision, GoalStatus, Plan, PlanTask};
+use commander_models::{WorkId, WorkItem};
+use commander_work::WorkQueue;
+
+use crate::error::Result;
+use crate::fanout::{TaskRunner, TaskSpec};
+use crate::journal::Journal;
+
+/// Work item metadata key holding the goal of the plan a task belongs to.
+pub const PLAN_GOAL_KEY: &str = "plan_goal";
+
+/// Work item metadata key holding the plan-local task ID.
+pub const PLAN_TASK_KEY: &str = "plan_task";
+
+/// Progress of a running plan, reported per task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanEvent {
+    /// A task was sent to the session.
+    TaskStarted {
+        /// Goal description of the task (`"<id>. <title>"`).
+        task: String,
+    },
+    /// A task finished.
+    TaskCompleted {
+        /// Goal description of the task.
+        task: String,
+    },
+    /// A task failed; the run stops.
+    TaskFailed {
+        /// Goal description of the task.
+        task: String,
+        /// Why it failed.
+        error: String,
+    },
+    /// The run ended.
+    Finished(PlanReport),
+}
+
+/// Outcome of a plan run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanReport {
+    /// Tasks that completed.
+    pub completed: usize,
+    /// Tasks that failed.
+    pub failed: usize,
+    /// Tasks never started because an earlier task failed.
+    pub skipped: usize,
+    /// Per-task status from the completion driver.
+    pub progress: String,
+}
+
+impl PlanReport {
+    /// Whether every task completed.
+    pub fn succeeded(&self) -> bool {
+        self.failed == 0 && self.skipped == 0
+    }
+}
+
+/// An approved plan ready to execute.
+pub struct PlanRun {
+    plan: Plan,
+    template: TaskSpec,
+    queue: WorkQueue,
+    runner: Arc<dyn TaskRunner>,
+    tasks: HashMap<WorkId, PlanTask>,
+    journal: Option<Arc<Mutex<Journal>>>,
+}
+
+impl PlanRun {
+    /// Enqueue the plan's tasks into `queue`.
+    ///
+    /// Tasks are sent to `template`'s session, adapter and directory; only
+    /// the prompt differs per task. `queue` should be dedicated to this run,
+    /// since the run dequeues whatever becomes ready.
+    pub fn new(
+        plan: Plan,
+        template: TaskSpec,
+        queue: WorkQueue,
+        runner: Arc<dyn TaskRunner>,
+    ) -> Result<Self> {
+        let mut ids: HashMap<String, WorkId> = HashMap::new();
+        let mut tasks = HashMap::new();
+
+        for task in plan.execution_order()? {
+            let mut item = WorkItem::new(template.session_id.as_str(), task.instructions());
+            item.depends_on = task.depends_on.iter().filter_map(|d| ids.get(d).cloned()).collect();
+            item.metadata.insert(PLAN_GOAL_KEY.to_string(), plan.goal.clone().into());
+            item.metadata.insert(PLAN_TASK_KEY.to_string(), task.id.clone().into());
+
+            let work_id = queue.enqueue(item)?;
+            ids.insert(task.id.clone(), work_id.clone());
+            tasks.insert(work_id, task.clone());
+        }
+
+        Ok(Self {
+            plan,
+            template,
+            queue,
+            runner,
+            tasks,
+            journal: None,
+        })
+    }
+
+    /// Record each task's delegation and outcome in `journal`.
+    pub fn with_journal(mut self, journal: Arc<Mutex<Journal>>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    fn journal(&self, record: impl FnOnce(&mut Journal)) {
+        if let Some(journal) = &self.journal {
+            record(&mut journal.lock().unwrap_or_else(|e| e.into_inner()));
+        }
+    }
+
+    /// The plan being executed.
+    pub fn plan(&self) -> &Plan {
+        &self.plan
+    }
+
+    /// Execute the plan, calling `on_event` as each task starts and ends.
+    ///
+    /// Blocks until the run ends; call it from a background thread.
+    pub fn run(mut self, mut on_event: impl FnMut(PlanEvent)) -> PlanReport {
+        info!(goal = %self.plan.goal, tasks = self.tasks.len(), "Executing plan");
+
+        // One iteration per task, plus one to observe completion
+        let mut driver = CompletionDriver::with_max_iterations(self.tasks.len() + 1);
+        driver.set_goals(self.plan.to_goals());
+
+        while matches!(driver.should_continue(), ContinueDecision::Continue) {
+            let Some(item) = self.queue.dequeue() else {
+                break;
+            };
+            let Some(task) = self.tasks.remove(&item.id) else {
+                warn!(work_id = %item.id, "Skipping work item outside the plan");
+                continue;
+            };
+
+            let goal = task.goal_description();
+            driver.update_goal_status(&goal, GoalStatus::InProgress);
+            on_event(PlanEvent::TaskStarted { task: goal.clone() });
+
+            let spec = TaskSpec {
+                task: task.instructions(),
+                ..self.template.clone()
+            };
+            let mut task_id = String::new();
+            self.journal(|j| task_id = j.delegate(&spec.session_id, &spec.task));
+            match self.runner.run(&spec) {
+                Ok(output) => {
+                    self.journal(|j| j.complete(&task_id));
+                    if let Err(e) = self.queue.complete_with_result(&item.id, output) {
+                        warn!(error = %e, "Failed to record plan task result");
+                    }
+                    driver.complete_goal(&goal);
+                    on_event(PlanEvent::TaskCompleted { task: goal });
+                }
+                Err(error) => {
+                    warn!(task = %goal, error = %error, "Plan task failed");
+                    self.journal(|j| j.fail(&task_id, &error));
+                    if let Err(e) = self.queue.fail(&item.id, error.clone()) {
+                        warn!(error = %e, "Failed to record plan task failure");
+                    }
+                    driver.block_goal(&goal, &error);
+                    driver.add_blocker(Blocker::error_judgment(
+                        format!("Task {} failed: {}", goal, error),
+                        vec!["Fix the problem and plan the remaining work again".to_string()],
+                    ));
+                    on_event(PlanEvent::TaskFailed { task: goal, error });
+                }
+            }
+
+            driver.increment_iteration();
+        }
+
+        let count = |f: fn(&GoalStatus) -> bool| driver.goals().iter().filter(|g| f(&g.status)).count();
+        let report = PlanReport {
+            completed: count(|s| *s == GoalStatus::Completed),
+            failed: count(|s| matches!(s, GoalStatus::Blocked(_))),
+            skipped: count(|s| *s == GoalStatus::Pending),
+            progress: driver.format_progress(),
+        };
+        info!(completed = report.completed, failed = report.failed, skipped = report.skipped, "Plan finished");
+
+        on_event(PlanEvent::Finished(report.clone()));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_persistence::WorkStore;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    /// Records prompts and fails those containing "fail".
+    struct RecordingRunner {
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl TaskRunner for RecordingRunner {
+        fn run(&self, spec: &TaskSpec) -> std::result::Result<String, String> {
+            self.prompts.lock().unwrap().push(spec.task.clone());
+            if spec.task.contains("fail") {
+                Err("tests failed".to_string())
+            } else {
+                Ok(format!("done: {}", spec.task))
+            }
+        }
+    }
+
+    fn run_plan(tasks: Vec<PlanTask>) -> (PlanReport, Vec<String>, Vec<PlanEvent>) {
+        let dir = tempdir().unwrap();
+        let runner = Arc::new(RecordingRunner {
+            prompts: Mutex::new(Vec::new()),
+        });
+        let plan = Plan::new("Add OAuth", tasks).unwrap();
+        let run = PlanRun::new(
+            plan,
+            TaskSpec::new("commander-app", ""),
+            WorkQueue::new(WorkStore::new(dir.path())),
+            runner.clone(),
+        )
+        .unwrap();
+
+        let mut events = Vec::new();
+        let report = run.run(|e| events.push(e));
+        let prompts = runner.prompts.lock().unwrap().clone();
+        (report, prompts, events)
+    }
+
+    #[test]
+    fn test_runs_tasks_in_dependency_order() {
+        let (report, prompts, events) = run_plan(vec![
+            PlanTask::new("2", "Add login route").after("1"),
+            PlanTask::new("1", "Add OAuth dependency"),
+        ]);
+
+        assert!(report.succeeded());
+        assert_eq!(report.completed, 2);
+        assert_eq!(prompts, ["Add OAuth dependency", "Add login route"]);
+        assert_eq!(
+            events[0],
+            PlanEvent::TaskStarted {
+                task: "1. Add OAuth dependency".to_string()
+            }
+        );
+        assert!(matches!(events.last(), Some(PlanEvent::Finished(_))));
+    }
+
+    #[test]
+    fn test_failure_stops_run_and_skips_remaining() {
+        let (report, prompts, events) = run_plan(vec![
+            PlanTask::new("1", "Add dependency and fail"),
+            PlanTask::new("2", "Add login route").after("1"),
+            PlanTask::new("3", "Write docs"),
+        ]);
+
+        assert_eq!(prompts.len(), 1);
+        assert_eq!((report.completed, report.failed, report.skipped), (0, 1, 2));
+        assert!(events.iter().any(|e| matches!(e, PlanEvent::TaskFailed { .. })));
+        assert!(report.progress.contains("[!] 1. Add dependency and fail"));
+    }
+}
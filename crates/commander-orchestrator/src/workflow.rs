@@ -0,0 +1,605 @@
+//! Declarative workflows.
+//!
+//! A workflow is a YAML pipeline in `~/.ai-commander/workflows/<name>.yaml`:
+//!
+//! ```yaml
+//! name: release
+//! description: Test, update the changelog and tag
+//! adapter: claude-code          # default for every step
+//! steps:
+//!   - name: test
+//!     prompt: Run the full test suite and fix any failures
+//!     success: ['(?i)all tests pass', 'test result: ok']
+//!     failure: ['(?i)could not compile']
+//!     retries: 2
+//!   - name: changelog
+//!     prompt: Add the unreleased changes to CHANGELOG.md
+//!     adapter: mpm
+//! ```
+//!
+//! Steps run in order in the project's session. Once the session goes idle
+//! after a step's prompt, the step's output is checked: it fails if a
+//! `failure` pattern matches or, when `success` patterns are given, if none
+//! matches. A failed step is sent again up to `retries` times before the run
+//! stops.
+//!
+//! Progress is saved after every step in `state/workflow_runs/`, so running
+//! the same workflow on the same project after a crash or a failure resumes
+//! at the first step that did not complete.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::error::{OrchestratorError, Result};
+use crate::fanout::{TaskRunner, TaskSpec};
+
+/// Output characters kept per step in the run state.
+const MAX_SAVED_OUTPUT: usize = 2000;
+
+/// A step of a workflow.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowStep {
+    /// Step name, unique within the workflow.
+    pub name: String,
+    /// Prompt sent to the session.
+    pub prompt: String,
+    /// Adapter for this step; defaults to the workflow's adapter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adapter: Option<String>,
+    /// Output patterns of which at least one must match for the step to succeed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub success: Vec<String>,
+    /// Output patterns that fail the step.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failure: Vec<String>,
+    /// Times a failed step is sent again before the run stops.
+    #[serde(default)]
+    pub retries: u32,
+}
+
+impl WorkflowStep {
+    /// Check the step's output against its success and failure patterns.
+    pub fn check_output(&self, output: &str) -> std::result::Result<(), String> {
+        for pattern in &self.failure {
+            if compile(pattern)?.is_match(output) {
+                return Err(format!("output matched failure pattern '{}'", pattern));
+            }
+        }
+        if self.success.is_empty() {
+            return Ok(());
+        }
+        for pattern in &self.success {
+            if compile(pattern)?.is_match(output) {
+                return Ok(());
+            }
+        }
+        Err("output matched no success pattern".to_string())
+    }
+}
+
+fn compile(pattern: &str) -> std::result::Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("invalid pattern '{}': {}", pattern, e))
+}
+
+/// A workflow definition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workflow {
+    /// Workflow name.
+    pub name: String,
+    /// What the workflow does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Default adapter for the steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adapter: Option<String>,
+    /// Steps, run in order.
+    pub steps: Vec<WorkflowStep>,
+}
+
+impl Workflow {
+    /// Parse and validate a workflow from YAML.
+    pub fn parse(content: &str) -> Result<Self> {
+        let workflow: Self = serde_yaml::from_str(content)
+            .map_err(|e| OrchestratorError::Workflow(format!("invalid workflow YAML: {}", e)))?;
+        workflow.validate()?;
+        Ok(workflow)
+    }
+
+    /// Load a workflow file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            OrchestratorError::Workflow(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&content)
+            .map_err(|e| OrchestratorError::Workflow(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Find the workflow `name` in `dir` (`<name>.yaml` or `<name>.yml`).
+    pub fn find(dir: &Path, name: &str) -> Result<Self> {
+        ["yaml", "yml"]
+            .iter()
+            .map(|ext| dir.join(format!("{}.{}", name, ext)))
+            .find(|path| path.exists())
+            .ok_or_else(|| {
+                OrchestratorError::Workflow(format!("no workflow '{}' in {}", name, dir.display()))
+            })
+            .and_then(|path| Self::load(&path))
+    }
+
+    /// Check that the workflow has steps, unique step names and valid patterns.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: String| Err(OrchestratorError::Workflow(msg));
+        if self.steps.is_empty() {
+            return invalid(format!("workflow '{}' has no steps", self.name));
+        }
+        for (i, step) in self.steps.iter().enumerate() {
+            if step.name.trim().is_empty() || step.prompt.trim().is_empty() {
+                return invalid(format!("step {} needs a name and a prompt", i + 1));
+            }
+            if self.steps[..i].iter().any(|s| s.name == step.name) {
+                return invalid(format!("duplicate step name '{}'", step.name));
+            }
+            for pattern in step.success.iter().chain(&step.failure) {
+                if let Err(e) = compile(pattern) {
+                    return invalid(format!("step '{}': {}", step.name, e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adapter used for `step`.
+    pub fn adapter_for<'a>(&'a self, step: &'a WorkflowStep) -> Option<&'a str> {
+        step.adapter.as_deref().or(self.adapter.as_deref())
+    }
+}
+
+/// Workflows in `dir`, sorted by name. Invalid files are skipped with a warning.
+pub fn list_workflows(dir: &Path) -> Vec<Workflow> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut workflows: Vec<Workflow> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "yaml" || ext == "yml"))
+        .filter_map(|p| match Workflow::load(&p) {
+            Ok(workflow) => Some(workflow),
+            Err(e) => {
+                warn!(error = %e, "Skipping invalid workflow");
+                None
+            }
+        })
+        .collect();
+    workflows.sort_by(|a, b| a.name.cmp(&b.name));
+    workflows
+}
+
+/// Where a workflow run stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// Steps are running (or the process died while they were).
+    Running,
+    /// Every step completed.
+    Completed,
+    /// A step failed after its retries.
+    Failed,
+}
+
+/// Result of a finished step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepRecord {
+    /// Step name.
+    pub name: String,
+    /// Attempts made.
+    pub attempts: u32,
+    /// Output of the last attempt, truncated.
+    pub output: String,
+    /// When the step completed.
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Saved progress of a workflow on a project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    /// Workflow name.
+    pub workflow: String,
+    /// Project (session) the workflow runs in.
+    pub project: String,
+    pub status: RunStatus,
+    /// Completed steps, in order.
+    pub completed: Vec<StepRecord>,
+    /// Error of the step that failed the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkflowRun {
+    fn new(workflow: &str, project: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            workflow: workflow.to_string(),
+            project: project.to_string(),
+            status: RunStatus::Running,
+            completed: Vec::new(),
+            error: None,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// State file of `workflow` on `project` in `dir`.
+    pub fn path(dir: &Path, workflow: &str, project: &str) -> PathBuf {
+        let safe = |s: &str| s.replace(['/', '\\', ' ', ':'], "-");
+        dir.join(format!("{}--{}.json", safe(workflow), safe(project)))
+    }
+
+    /// Load the saved run of `workflow` on `project`, if any.
+    pub fn load(dir: &Path, workflow: &str, project: &str) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(dir, workflow, project)).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(run) => Some(run),
+            Err(e) => {
+                warn!(error = %e, workflow, project, "Ignoring unreadable workflow run state");
+                None
+            }
+        }
+    }
+
+    /// Save the run, replacing the state file atomically.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let io_err = |e: std::io::Error| {
+            OrchestratorError::Workflow(format!("failed to save run state: {}", e))
+        };
+        fs::create_dir_all(dir).map_err(io_err)?;
+        let path = Self::path(dir, &self.workflow, &self.project);
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| OrchestratorError::Workflow(e.to_string()))?;
+        fs::write(&tmp, json).map_err(io_err)?;
+        fs::rename(&tmp, &path).map_err(io_err)
+    }
+
+    /// Index of the next step to run, or `None` if the saved progress does
+    /// not belong to `workflow`'s current steps.
+    fn resume_index(&self, workflow: &Workflow) -> Option<usize> {
+        let matches = self.completed.len() <= workflow.steps.len()
+            && self
+                .completed
+                .iter()
+                .zip(&workflow.steps)
+                .all(|(record, step)| record.name == step.name);
+        matches.then_some(self.completed.len())
+    }
+}
+
+/// Progress of a running workflow, reported per step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkflowEvent {
+    /// A saved run is being resumed.
+    Resumed {
+        /// Steps already completed.
+        completed: usize,
+    },
+    /// A step's prompt was sent.
+    StepStarted { step: String, attempt: u32 },
+    /// An attempt failed; the step will be sent again.
+    StepRetrying { step: String, attempt: u32, error: String },
+    /// A step completed.
+    StepCompleted { step: String, attempts: u32 },
+    /// A step failed after its retries; the run stops.
+    StepFailed { step: String, error: String },
+    /// The run ended.
+    Finished(WorkflowReport),
+}
+
+/// Outcome of a workflow run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowReport {
+    pub workflow: String,
+    pub project: String,
+    /// Steps completed, including those from earlier attempts of the run.
+    pub completed: usize,
+    /// Steps in the workflow.
+    pub total: usize,
+    /// Step that failed the run.
+    pub failed_step: Option<String>,
+}
+
+impl WorkflowReport {
+    /// Whether every step completed.
+    pub fn succeeded(&self) -> bool {
+        self.failed_step.is_none() && self.completed == self.total
+    }
+}
+
+/// A workflow ready to execute on a project.
+pub struct WorkflowExecution {
+    workflow: Workflow,
+    run: WorkflowRun,
+    resumed: bool,
+    template: TaskSpec,
+    runner: Arc<dyn TaskRunner>,
+    runs_dir: PathBuf,
+}
+
+impl WorkflowExecution {
+    /// Prepare `workflow` to run in `template`'s session.
+    ///
+    /// Steps are sent to `template`'s session and directory; `template`'s
+    /// adapter is used for steps without one. An unfinished run saved in
+    /// `runs_dir` is resumed unless `restart` is set or the workflow's steps
+    /// changed since.
+    pub fn new(
+        workflow: Workflow,
+        template: TaskSpec,
+        runner: Arc<dyn TaskRunner>,
+        runs_dir: impl Into<PathBuf>,
+        restart: bool,
+    ) -> Self {
+        let runs_dir = runs_dir.into();
+        let project = template.session_id.clone();
+        let saved = WorkflowRun::load(&runs_dir, &workflow.name, &project)
+            .filter(|run| !restart && run.status != RunStatus::Completed)
+            .filter(|run| run.resume_index(&workflow).is_some());
+
+        let resumed = saved.is_some();
+        let run = saved.unwrap_or_else(|| WorkflowRun::new(&workflow.name, &project));
+        Self {
+            workflow,
+            run,
+            resumed,
+            template,
+            runner,
+            runs_dir,
+        }
+    }
+
+    /// The workflow being executed.
+    pub fn workflow(&self) -> &Workflow {
+        &self.workflow
+    }
+
+    /// Execute the remaining steps, calling `on_event` as each starts and ends.
+    ///
+    /// Blocks until the run ends; call it from a blocking context. Fails only
+    /// if the run state cannot be saved.
+    pub fn run(mut self, mut on_event: impl FnMut(WorkflowEvent)) -> Result<WorkflowReport> {
+        let start = self.run.completed.len();
+        info!(workflow = %self.workflow.name, project = %self.run.project, start, "Running workflow");
+        if self.resumed && start > 0 {
+            on_event(WorkflowEvent::Resumed { completed: start });
+        }
+
+        self.run.status = RunStatus::Running;
+        self.run.error = None;
+        self.save()?;
+
+        let mut failed_step = None;
+        let steps = self.workflow.steps.clone();
+        for step in steps.into_iter().skip(start) {
+            match self.run_step(&step, &mut on_event) {
+                Ok((attempts, output)) => {
+                    self.run.completed.push(StepRecord {
+                        name: step.name.clone(),
+                        attempts,
+                        output: truncate(&output, MAX_SAVED_OUTPUT),
+                        finished_at: Utc::now(),
+                    });
+                    self.save()?;
+                    on_event(WorkflowEvent::StepCompleted {
+                        step: step.name,
+                        attempts,
+                    });
+                }
+                Err(error) => {
+                    warn!(workflow = %self.workflow.name, step = %step.name, error = %error, "Workflow step failed");
+                    self.run.status = RunStatus::Failed;
+                    self.run.error = Some(format!("{}: {}", step.name, error));
+                    self.save()?;
+                    on_event(WorkflowEvent::StepFailed {
+                        step: step.name.clone(),
+                        error,
+                    });
+                    failed_step = Some(step.name);
+                    break;
+                }
+            }
+        }
+
+        if failed_step.is_none() {
+            self.run.status = RunStatus::Completed;
+            self.save()?;
+        }
+
+        let report = WorkflowReport {
+            workflow: self.workflow.name.clone(),
+            project: self.run.project.clone(),
+            completed: self.run.completed.len(),
+            total: self.workflow.steps.len(),
+            failed_step,
+        };
+        on_event(WorkflowEvent::Finished(report.clone()));
+        Ok(report)
+    }
+
+    /// Send `step` until its output passes or its retries run out.
+    fn run_step(
+        &self,
+        step: &WorkflowStep,
+        on_event: &mut impl FnMut(WorkflowEvent),
+    ) -> std::result::Result<(u32, String), String> {
+        let spec = TaskSpec {
+            task: step.prompt.clone(),
+            adapter: self
+                .workflow
+                .adapter_for(step)
+                .unwrap_or(&self.template.adapter)
+                .to_string(),
+            ..self.template.clone()
+        };
+
+        let attempts = step.retries + 1;
+        let mut attempt = 1;
+        loop {
+            on_event(WorkflowEvent::StepStarted {
+                step: step.name.clone(),
+                attempt,
+            });
+            let result = self
+                .runner
+                .run(&spec)
+                .and_then(|output| step.check_output(&output).map(|()| output));
+            match result {
+                Ok(output) => return Ok((attempt, output)),
+                Err(error) if attempt < attempts => {
+                    on_event(WorkflowEvent::StepRetrying {
+                        step: step.name.clone(),
+                        attempt,
+                        error,
+                    });
+                    attempt += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn save(&mut self) -> Result<()> {
+        self.run.updated_at = Utc::now();
+        self.run.save(&self.runs_dir)
+    }
+}
+
+/// Last `max` characters of `text`.
+fn truncate(text: &str, max: usize) -> String {
+    let count = text.chars().count();
+    text.chars().skip(count.saturating_sub(max)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    const WORKFLOW: &str = r#"
+name: release
+adapter: mpm
+steps:
+  - name: test
+    prompt: run tests
+    success: ['test result: ok']
+    retries: 1
+  - name: tag
+    prompt: tag release
+    adapter: claude-code
+"#;
+
+    /// Replies with queued outputs and records the prompts and adapters it got.
+    struct ScriptedRunner {
+        replies: Mutex<Vec<std::result::Result<String, String>>>,
+        sent: Mutex<Vec<(String, String)>>,
+    }
+
+    impl ScriptedRunner {
+        fn new(replies: Vec<std::result::Result<&str, &str>>) -> Arc<Self> {
+            Arc::new(Self {
+                replies: Mutex::new(
+                    replies
+                        .into_iter()
+                        .rev()
+                        .map(|r| r.map(str::to_string).map_err(str::to_string))
+                        .collect(),
+                ),
+                sent: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    impl TaskRunner for ScriptedRunner {
+        fn run(&self, spec: &TaskSpec) -> std::result::Result<String, String> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push((spec.task.clone(), spec.adapter.clone()));
+            self.replies
+                .lock()
+                .unwrap()
+                .pop()
+                .unwrap_or_else(|| Err("no reply".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_parse_and_validate() {
+        let workflow = Workflow::parse(WORKFLOW).unwrap();
+        assert_eq!(workflow.steps.len(), 2);
+        assert_eq!(workflow.adapter_for(&workflow.steps[0]), Some("mpm"));
+        assert_eq!(workflow.adapter_for(&workflow.steps[1]), Some("claude-code"));
+
+        assert!(Workflow::parse("name: empty\nsteps: []\n").is_err());
+        assert!(Workflow::parse(
+            "name: bad\nsteps:\n  - name: a\n    prompt: x\n    success: ['(']\n"
+        )
+        .is_err());
+
+        let step = &workflow.steps[0];
+        assert!(step.check_output("test result: ok. 3 passed").is_ok());
+        assert!(step.check_output("test result: FAILED").is_err());
+    }
+
+    #[test]
+    fn test_retries_then_resumes_after_failure() {
+        let dir = tempdir().unwrap();
+        let workflow = Workflow::parse(WORKFLOW).unwrap();
+
+        // "test" fails its check, passes on retry; "tag" errors
+        let runner = ScriptedRunner::new(vec![
+            Ok("test result: FAILED"),
+            Ok("test result: ok"),
+            Err("timed out after 60s"),
+        ]);
+        let execution = WorkflowExecution::new(
+            workflow.clone(),
+            TaskSpec::new("api", ""),
+            runner.clone(),
+            dir.path(),
+            false,
+        );
+        let mut events = Vec::new();
+        let report = execution.run(|e| events.push(e)).unwrap();
+
+        assert_eq!(report.completed, 1);
+        assert_eq!(report.failed_step.as_deref(), Some("tag"));
+        assert!(events.iter().any(|e| matches!(e, WorkflowEvent::StepRetrying { attempt: 1, .. })));
+        assert_eq!(runner.sent.lock().unwrap()[0], ("run tests".to_string(), "mpm".to_string()));
+
+        let saved = WorkflowRun::load(dir.path(), "release", "api").unwrap();
+        assert_eq!(saved.status, RunStatus::Failed);
+        assert_eq!(saved.completed[0].attempts, 2);
+
+        // Running again resumes at "tag"
+        let runner = ScriptedRunner::new(vec![Ok("tagged v1.0")]);
+        let execution = WorkflowExecution::new(workflow, TaskSpec::new("api", ""), runner.clone(), dir.path(), false);
+        let mut events = Vec::new();
+        let report = execution.run(|e| events.push(e)).unwrap();
+
+        assert!(report.succeeded());
+        assert_eq!(events[0], WorkflowEvent::Resumed { completed: 1 });
+        assert_eq!(runner.sent.lock().unwrap().len(), 1);
+        assert_eq!(
+            WorkflowRun::load(dir.path(), "release", "api").unwrap().status,
+            RunStatus::Completed
+        );
+    }
+}
@@ -1,27 +1,166 @@
 //! Event store for event persistence.
 
-use std::fs;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 
-use commander_models::{Event, EventId, ProjectId};
+use chrono::{DateTime, Duration, Utc};
+use commander_models::{Event, EventId, EventPriority, EventStatus, EventType, ProjectId};
+use serde::{Deserialize, Serialize};
 
-use crate::atomic::{atomic_write_json, read_json};
+use crate::atomic::{atomic_write, atomic_write_json, read_json};
 use crate::error::{PersistenceError, Result};
 
+/// Index file inside a project's events directory.
+const INDEX_FILE: &str = "index.json";
+
+/// Directory inside a project's events directory holding the segments.
+const SEGMENTS_DIR: &str = "segments";
+
 /// Manages persistence of events.
 ///
-/// Events are stored as individual JSON files organized by project:
+/// Events are appended as JSON lines to one segment per project and day
+/// (UTC, by the event's creation time). Updating an event appends its new
+/// version; deleting it appends a tombstone. The latest line for an ID wins.
 /// ```text
 /// base_path/
 /// └── events/
 ///     └── {project_id}/
-///         ├── evt-abc123.json
-///         └── evt-def456.json
+///         ├── index.json
+///         └── segments/
+///             ├── 2026-10-16.jsonl
+///             └── 2026-10-17.jsonl
 /// ```
+///
+/// `index.json` records the segment, time, type, status and priority of
+/// every event, so [`query`](Self::query) reads only the segments holding the
+/// requested page. The index is a cache: it records how many bytes of each
+/// segment it covers and catches up on lines appended by other processes
+/// before every read.
+///
+/// [`compact`](Self::compact) rewrites segments down to the latest version
+/// of each event and applies a [`RetentionPolicy`]. Projects still stored as
+/// one JSON file per event are migrated to segments on first access.
 pub struct EventStore {
     base_path: PathBuf,
 }
 
+/// Which events [`EventStore::compact`] removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetentionPolicy {
+    /// Events created longer ago than this are removed. `None` keeps them.
+    pub max_age: Option<Duration>,
+    /// Keep events that are not resolved, whatever their age.
+    pub keep_unresolved: bool,
+}
+
+impl RetentionPolicy {
+    /// Whether `event` should be removed at `now`.
+    fn expires(&self, event: &Event, now: DateTime<Utc>) -> bool {
+        let Some(max_age) = self.max_age else {
+            return false;
+        };
+        if self.keep_unresolved && event.status != EventStatus::Resolved {
+            return false;
+        }
+        event.created_at < now - max_age
+    }
+}
+
+/// What a compaction removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompactionReport {
+    /// Segments rewritten with fewer lines.
+    pub segments_rewritten: usize,
+    /// Segments deleted because nothing in them was kept.
+    pub segments_removed: usize,
+    /// Events removed by the retention policy.
+    pub events_removed: usize,
+    /// Superseded versions and tombstones dropped.
+    pub lines_dropped: usize,
+}
+
+/// Criteria and page for [`EventStore::query`].
+#[derive(Debug, Clone, Default)]
+pub struct EventQuery {
+    pub project_id: Option<ProjectId>,
+    pub event_type: Option<EventType>,
+    pub status: Option<EventStatus>,
+    pub priority_min: Option<EventPriority>,
+    /// Only events created at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only events created before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Matching events to skip, newest first.
+    pub offset: usize,
+    /// Maximum number of events returned.
+    pub limit: Option<usize>,
+}
+
+/// One page of [`EventStore::query`] results.
+#[derive(Debug, Clone, Default)]
+pub struct EventPage {
+    /// Events on the page, newest first.
+    pub events: Vec<Event>,
+    /// Number of matching events across all pages.
+    pub total: usize,
+}
+
+impl EventPage {
+    /// Offset of the next page, if there is one.
+    pub fn next_offset(&self, query: &EventQuery) -> Option<usize> {
+        let next = query.offset + self.events.len();
+        (next < self.total && !self.events.is_empty()).then_some(next)
+    }
+}
+
+/// A line of a segment.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SegmentLine {
+    Deleted { deleted: EventId },
+    Event(Box<Event>),
+}
+
+/// Where an event is stored and the fields queries filter on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    segment: String,
+    created_at: DateTime<Utc>,
+    event_type: EventType,
+    status: EventStatus,
+    priority: EventPriority,
+}
+
+impl IndexEntry {
+    fn new(segment: &str, event: &Event) -> Self {
+        Self {
+            segment: segment.to_string(),
+            created_at: event.created_at,
+            event_type: event.event_type,
+            status: event.status,
+            priority: event.priority,
+        }
+    }
+
+    fn matches(&self, query: &EventQuery) -> bool {
+        query.event_type.is_none_or(|t| self.event_type == t)
+            && query.status.is_none_or(|s| self.status == s)
+            && query.priority_min.is_none_or(|p| self.priority >= p)
+            && query.since.is_none_or(|since| self.created_at >= since)
+            && query.until.is_none_or(|until| self.created_at < until)
+    }
+}
+
+/// Index of one project's events.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EventIndex {
+    /// Bytes of each segment folded into `events`.
+    segments: BTreeMap<String, u64>,
+    events: HashMap<String, IndexEntry>,
+}
+
 impl EventStore {
     /// Creates a new EventStore with the given base path.
     pub fn new(base_path: impl Into<PathBuf>) -> Self {
@@ -30,91 +169,368 @@ impl EventStore {
         }
     }
 
+    /// Returns the directory holding every project's events.
+    fn root_dir(&self) -> PathBuf {
+        self.base_path.join("events")
+    }
+
     /// Returns the path to a project's events directory.
     fn events_dir(&self, project_id: &ProjectId) -> PathBuf {
-        self.base_path.join("events").join(project_id.as_str())
+        self.root_dir().join(project_id.as_str())
     }
 
-    /// Returns the path to a specific event file.
-    fn event_path(&self, project_id: &ProjectId, event_id: &EventId) -> PathBuf {
-        self.events_dir(project_id)
-            .join(format!("{}.json", event_id))
+    fn segments_dir(&self, project_id: &ProjectId) -> PathBuf {
+        self.events_dir(project_id).join(SEGMENTS_DIR)
     }
 
-    /// Ensures the events directory for a project exists.
-    fn ensure_dirs(&self, project_id: &ProjectId) -> Result<()> {
-        let dir = self.events_dir(project_id);
+    fn segment_path(&self, project_id: &ProjectId, segment: &str) -> PathBuf {
+        self.segments_dir(project_id).join(format!("{}.jsonl", segment))
+    }
+
+    /// Projects with an events directory.
+    fn project_ids(&self) -> Result<Vec<ProjectId>> {
+        let dir = self.root_dir();
         if !dir.exists() {
-            fs::create_dir_all(&dir).map_err(|source| PersistenceError::DirectoryError {
-                path: dir,
-                source,
-            })?;
+            return Ok(Vec::new());
         }
-        Ok(())
+        let entries = fs::read_dir(&dir).map_err(|source| PersistenceError::ReadError {
+            path: dir.clone(),
+            source,
+        })?;
+        Ok(entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| ProjectId::from_string(entry.file_name().to_string_lossy()))
+            .collect())
     }
 
-    /// Saves an event.
+    /// Saves an event, or a new version of it.
     pub fn save_event(&self, event: &Event) -> Result<()> {
-        self.ensure_dirs(&event.project_id)?;
-        let path = self.event_path(&event.project_id, &event.id);
-        atomic_write_json(&path, event)
+        let segment = segment_name(event.created_at);
+        self.append(&event.project_id, &segment, &SegmentLine::Event(Box::new(event.clone())))?;
+        self.refresh_index(&event.project_id).map(|_| ())
     }
 
     /// Loads an event by ID.
     pub fn load_event(&self, project_id: &ProjectId, event_id: &EventId) -> Result<Event> {
-        let path = self.event_path(project_id, event_id);
-        if !path.exists() {
-            return Err(PersistenceError::NotFound {
-                kind: "event".to_string(),
-                id: event_id.to_string(),
-            });
-        }
-        read_json(&path)
+        let not_found = || PersistenceError::NotFound {
+            kind: "event".to_string(),
+            id: event_id.to_string(),
+        };
+        let index = self.refresh_index(project_id)?;
+        let entry = index.events.get(event_id.as_str()).ok_or_else(not_found)?;
+        let mut events = self.read_segment(project_id, &entry.segment)?.0;
+        events.remove(event_id.as_str()).ok_or_else(not_found)
     }
 
-    /// Lists all events for a project.
+    /// Lists all events for a project, newest first.
     pub fn list_events(&self, project_id: &ProjectId) -> Result<Vec<Event>> {
+        self.query(&EventQuery {
+            project_id: Some(project_id.clone()),
+            ..EventQuery::default()
+        })
+        .map(|page| page.events)
+    }
+
+    /// Lists events across all projects, newest first.
+    pub fn list_all_events(&self) -> Result<Vec<Event>> {
+        self.query(&EventQuery::default()).map(|page| page.events)
+    }
+
+    /// Events matching `query`, newest first, one page at a time.
+    ///
+    /// Matching uses the index; only the segments holding events on the
+    /// requested page are read.
+    pub fn query(&self, query: &EventQuery) -> Result<EventPage> {
+        let projects = match &query.project_id {
+            Some(project_id) => vec![project_id.clone()],
+            None => self.project_ids()?,
+        };
+
+        let mut matches = Vec::new();
+        for project_id in projects {
+            let index = self.refresh_index(&project_id)?;
+            for (id, entry) in index.events {
+                if entry.matches(query) {
+                    matches.push((project_id.clone(), id, entry));
+                }
+            }
+        }
+        matches.sort_by(|a, b| b.2.created_at.cmp(&a.2.created_at).then_with(|| a.1.cmp(&b.1)));
+
+        let total = matches.len();
+        let page = matches
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX));
+
+        let mut segments: HashMap<(ProjectId, String), HashMap<String, Event>> = HashMap::new();
+        let mut events = Vec::new();
+        for (project_id, id, entry) in page {
+            let key = (project_id, entry.segment);
+            if !segments.contains_key(&key) {
+                let loaded = self.read_segment(&key.0, &key.1)?.0;
+                segments.insert(key.clone(), loaded);
+            }
+            if let Some(event) = segments.get_mut(&key).and_then(|events| events.remove(&id)) {
+                events.push(event);
+            }
+        }
+        Ok(EventPage { events, total })
+    }
+
+    /// Deletes an event.
+    pub fn delete_event(&self, project_id: &ProjectId, event_id: &EventId) -> Result<()> {
+        let index = self.refresh_index(project_id)?;
+        let Some(entry) = index.events.get(event_id.as_str()) else {
+            return Ok(());
+        };
+        let tombstone = SegmentLine::Deleted {
+            deleted: event_id.clone(),
+        };
+        self.append(project_id, &entry.segment, &tombstone)?;
+        self.refresh_index(project_id).map(|_| ())
+    }
+
+    /// Rewrites every segment but today's down to the latest version of each
+    /// event, removing deleted events and those `policy` expires.
+    ///
+    /// Today's segments are left alone, since other processes append to
+    /// them; their superseded lines are dropped once the day is over.
+    pub fn compact(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<CompactionReport> {
+        let mut report = CompactionReport::default();
+        let today = segment_name(now);
+        for project_id in self.project_ids()? {
+            self.migrate_project(&project_id)?;
+            for segment in self.segment_names(&project_id)? {
+                if segment == today {
+                    continue;
+                }
+                let (events, lines) = self.read_segment(&project_id, &segment)?;
+                let mut kept: Vec<&Event> = events.values().filter(|e| !policy.expires(e, now)).collect();
+                kept.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.as_str().cmp(b.id.as_str())));
+                if kept.len() == lines {
+                    continue;
+                }
+
+                report.events_removed += events.len() - kept.len();
+                report.lines_dropped += lines - events.len();
+                let path = self.segment_path(&project_id, &segment);
+                if kept.is_empty() {
+                    fs::remove_file(&path).map_err(|source| PersistenceError::WriteError { path, source })?;
+                    report.segments_removed += 1;
+                } else {
+                    let mut data = String::new();
+                    for event in kept {
+                        data.push_str(&serde_json::to_string(event)?);
+                        data.push('\n');
+                    }
+                    atomic_write(&path, data.as_bytes())?;
+                    report.segments_rewritten += 1;
+                }
+            }
+            self.rebuild_index(&project_id)?;
+        }
+        Ok(report)
+    }
+
+    /// Moves every project still stored as one JSON file per event to
+    /// segments. Returns the number of events migrated.
+    pub fn migrate(&self) -> Result<usize> {
+        let mut migrated = 0;
+        for project_id in self.project_ids()? {
+            migrated += self.migrate_project(&project_id)?;
+        }
+        Ok(migrated)
+    }
+
+    /// Moves a project's `{event_id}.json` files to segments.
+    fn migrate_project(&self, project_id: &ProjectId) -> Result<usize> {
         let dir = self.events_dir(project_id);
-        if !dir.exists() {
-            return Ok(Vec::new());
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(0);
+        };
+        let files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path.extension().is_some_and(|ext| ext == "json")
+                    && path.file_name().is_some_and(|name| name != INDEX_FILE)
+            })
+            .collect();
+        if files.is_empty() {
+            return Ok(0);
         }
 
         let mut events = Vec::new();
-        let entries = fs::read_dir(&dir).map_err(|source| PersistenceError::ReadError {
+        for path in &files {
+            match read_json::<Event>(path) {
+                Ok(event) => events.push(event),
+                Err(e) => eprintln!("Warning: failed to migrate event {:?}: {}", path, e),
+            }
+        }
+        events.sort_by_key(|e| e.created_at);
+        for event in &events {
+            self.append(project_id, &segment_name(event.created_at), &SegmentLine::Event(Box::new(event.clone())))?;
+        }
+        for path in files {
+            fs::remove_file(&path).map_err(|source| PersistenceError::WriteError { path, source })?;
+        }
+        self.rebuild_index(project_id)?;
+        Ok(events.len())
+    }
+
+    /// Appends `line` to a segment.
+    fn append(&self, project_id: &ProjectId, segment: &str, line: &SegmentLine) -> Result<()> {
+        let dir = self.segments_dir(project_id);
+        fs::create_dir_all(&dir).map_err(|source| PersistenceError::DirectoryError {
             path: dir.clone(),
             source,
         })?;
+        let path = self.segment_path(project_id, segment);
+        let mut data = serde_json::to_string(line)?;
+        data.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(data.as_bytes()))
+            .map_err(|source| PersistenceError::WriteError { path, source })
+    }
 
-        for entry in entries {
-            let entry = entry.map_err(|source| PersistenceError::ReadError {
-                path: dir.clone(),
-                source,
-            })?;
-
-            let path = entry.path();
-            if path.extension().is_some_and(|ext| ext == "json") {
-                match read_json::<Event>(&path) {
-                    Ok(event) => events.push(event),
-                    Err(e) => {
-                        eprintln!("Warning: failed to load event {:?}: {}", path, e);
-                    }
+    /// Names of a project's segments, oldest first.
+    fn segment_names(&self, project_id: &ProjectId) -> Result<Vec<String>> {
+        let dir = self.segments_dir(project_id);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(Vec::new());
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+            .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Latest version of each event in a segment, and the number of lines.
+    fn read_segment(&self, project_id: &ProjectId, segment: &str) -> Result<(HashMap<String, Event>, usize)> {
+        let path = self.segment_path(project_id, segment);
+        let data = fs::read_to_string(&path).map_err(|source| PersistenceError::ReadError {
+            path: path.clone(),
+            source,
+        })?;
+        let mut events = HashMap::new();
+        let mut lines = 0;
+        for line in complete_lines(&data).0 {
+            lines += 1;
+            match parse_line(line, &path) {
+                Some(SegmentLine::Event(event)) => {
+                    events.insert(event.id.to_string(), *event);
+                }
+                Some(SegmentLine::Deleted { deleted }) => {
+                    events.remove(deleted.as_str());
                 }
+                None => {}
             }
         }
+        Ok((events, lines))
+    }
+
+    /// Bring a project's index up to date with its segments, saving it if
+    /// anything changed.
+    fn refresh_index(&self, project_id: &ProjectId) -> Result<EventIndex> {
+        self.migrate_project(project_id)?;
+        let index_path = self.events_dir(project_id).join(INDEX_FILE);
+        let mut index: EventIndex = if index_path.exists() {
+            read_json(&index_path).unwrap_or_default()
+        } else {
+            EventIndex::default()
+        };
+
+        let segments = self.segment_names(project_id)?;
+        let mut changed = false;
+
+        // Segments removed by a compaction
+        let gone: Vec<String> = index
+            .segments
+            .keys()
+            .filter(|s| !segments.contains(s))
+            .cloned()
+            .collect();
+        for segment in gone {
+            index.segments.remove(&segment);
+            index.events.retain(|_, entry| entry.segment != segment);
+            changed = true;
+        }
+
+        for segment in segments {
+            let path = self.segment_path(project_id, &segment);
+            let len = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            let mut indexed = index.segments.get(&segment).copied().unwrap_or(0);
+            if len == indexed {
+                continue;
+            }
+            if len < indexed {
+                // Rewritten by a compaction: index it again
+                index.events.retain(|_, entry| entry.segment != segment);
+                indexed = 0;
+            }
 
-        // Sort by created_at descending (newest first)
-        events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            let mut data = String::new();
+            fs::File::open(&path)
+                .and_then(|mut file| {
+                    file.seek(SeekFrom::Start(indexed))?;
+                    file.read_to_string(&mut data)
+                })
+                .map_err(|source| PersistenceError::ReadError {
+                    path: path.clone(),
+                    source,
+                })?;
+            // A line another process is still writing is picked up next time
+            let (lines, consumed) = complete_lines(&data);
+            for line in lines {
+                match parse_line(line, &path) {
+                    Some(SegmentLine::Event(event)) => {
+                        index.events.insert(event.id.to_string(), IndexEntry::new(&segment, &event));
+                    }
+                    Some(SegmentLine::Deleted { deleted }) => {
+                        index.events.remove(deleted.as_str());
+                    }
+                    None => {}
+                }
+            }
+            index.segments.insert(segment, indexed + consumed as u64);
+            changed = true;
+        }
 
-        Ok(events)
+        if changed {
+            atomic_write_json(&index_path, &index)?;
+        }
+        Ok(index)
     }
 
-    /// Deletes an event.
-    pub fn delete_event(&self, project_id: &ProjectId, event_id: &EventId) -> Result<()> {
-        let path = self.event_path(project_id, event_id);
+    /// Index a project's segments from scratch.
+    fn rebuild_index(&self, project_id: &ProjectId) -> Result<()> {
+        let path = self.events_dir(project_id).join(INDEX_FILE);
         if path.exists() {
             fs::remove_file(&path).map_err(|source| PersistenceError::WriteError { path, source })?;
         }
-        Ok(())
+        self.refresh_index(project_id).map(|_| ())
+    }
+
+    /// Returns the paths holding a project's events, if any exist.
+    ///
+    /// Used to stage a purge so it can be rolled back.
+    pub fn project_paths(&self, project_id: &ProjectId) -> Vec<PathBuf> {
+        let dir = self.events_dir(project_id);
+        if dir.exists() {
+            vec![dir]
+        } else {
+            Vec::new()
+        }
     }
 
     /// Deletes all events for a project.
@@ -128,16 +544,44 @@ impl EventStore {
     }
 }
 
+/// Segment holding events created at `time`.
+fn segment_name(time: DateTime<Utc>) -> String {
+    time.date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// The newline-terminated lines of `data`, and the bytes they span.
+fn complete_lines(data: &str) -> (impl Iterator<Item = &str>, usize) {
+    let consumed = data.rfind('\n').map_or(0, |i| i + 1);
+    (data[..consumed].lines().filter(|l| !l.trim().is_empty()), consumed)
+}
+
+fn parse_line(line: &str, path: &Path) -> Option<SegmentLine> {
+    match serde_json::from_str(line) {
+        Ok(line) => Some(line),
+        Err(e) => {
+            eprintln!("Warning: skipping bad line in {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use commander_models::EventType;
+    use std::collections::HashSet;
     use tempfile::tempdir;
 
     fn create_test_event(project_id: &ProjectId) -> Event {
         Event::new(project_id.clone(), EventType::Status, "Test event".to_string())
     }
 
+    fn event_at(project_id: &ProjectId, event_type: EventType, created_at: DateTime<Utc>) -> Event {
+        let mut event = Event::new(project_id.clone(), event_type, "Test event".to_string());
+        event.created_at = created_at;
+        event
+    }
+
     #[test]
     fn test_save_and_load_event() {
         let dir = tempdir().unwrap();
@@ -166,6 +610,40 @@ mod tests {
         assert!(matches!(result, Err(PersistenceError::NotFound { .. })));
     }
 
+    #[test]
+    fn test_latest_version_wins() {
+        let dir = tempdir().unwrap();
+        let store = EventStore::new(dir.path());
+
+        let project_id = ProjectId::new();
+        let mut event = create_test_event(&project_id);
+        store.save_event(&event).unwrap();
+        event.status = EventStatus::Resolved;
+        store.save_event(&event).unwrap();
+
+        let events = store.list_events(&project_id).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, EventStatus::Resolved);
+        assert_eq!(store.load_event(&project_id, &event.id).unwrap().status, EventStatus::Resolved);
+    }
+
+    #[test]
+    fn test_list_all_events() {
+        let dir = tempdir().unwrap();
+        let store = EventStore::new(dir.path());
+        assert!(store.list_all_events().unwrap().is_empty());
+
+        let first = ProjectId::new();
+        let second = ProjectId::new();
+        store.save_event(&create_test_event(&first)).unwrap();
+        store.save_event(&create_test_event(&second)).unwrap();
+        store.save_event(&create_test_event(&second)).unwrap();
+
+        let events = store.list_all_events().unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(events.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+    }
+
     #[test]
     fn test_list_events() {
         let dir = tempdir().unwrap();
@@ -193,6 +671,61 @@ mod tests {
         assert!(events.is_empty());
     }
 
+    #[test]
+    fn test_query_time_range_type_and_pages() {
+        let dir = tempdir().unwrap();
+        let store = EventStore::new(dir.path());
+        let project_id = ProjectId::new();
+        let now = Utc::now();
+        for days in 0..5 {
+            store
+                .save_event(&event_at(&project_id, EventType::Status, now - Duration::days(days)))
+                .unwrap();
+        }
+        store
+            .save_event(&event_at(&project_id, EventType::Error, now - Duration::days(1)))
+            .unwrap();
+
+        let query = EventQuery {
+            project_id: Some(project_id.clone()),
+            event_type: Some(EventType::Status),
+            since: Some(now - Duration::days(3) - Duration::hours(1)),
+            until: Some(now - Duration::hours(1)),
+            limit: Some(2),
+            ..EventQuery::default()
+        };
+        let page = store.query(&query).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.events.len(), 2);
+        assert_eq!(page.events[0].created_at, now - Duration::days(1));
+        assert_eq!(page.next_offset(&query), Some(2));
+
+        let query = EventQuery { offset: 2, ..query };
+        let page = store.query(&query).unwrap();
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].created_at, now - Duration::days(3));
+        assert_eq!(page.next_offset(&query), None);
+    }
+
+    #[test]
+    fn test_index_catches_up_with_other_writers() {
+        let dir = tempdir().unwrap();
+        let reader = EventStore::new(dir.path());
+        let writer = EventStore::new(dir.path());
+        let project_id = ProjectId::new();
+
+        reader.save_event(&create_test_event(&project_id)).unwrap();
+        assert_eq!(reader.list_events(&project_id).unwrap().len(), 1);
+
+        // Append behind the index's back
+        let event = create_test_event(&project_id);
+        writer
+            .append(&project_id, &segment_name(event.created_at), &SegmentLine::Event(Box::new(event.clone())))
+            .unwrap();
+        assert_eq!(reader.list_events(&project_id).unwrap().len(), 2);
+        assert!(reader.load_event(&project_id, &event.id).is_ok());
+    }
+
     #[test]
     fn test_delete_event() {
         let dir = tempdir().unwrap();
@@ -206,6 +739,73 @@ mod tests {
         store.delete_event(&project_id, &event_id).unwrap();
 
         assert!(store.load_event(&project_id, &event_id).is_err());
+        assert!(store.list_events(&project_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compact_drops_old_versions_and_expired_events() {
+        let dir = tempdir().unwrap();
+        let store = EventStore::new(dir.path());
+        let project_id = ProjectId::new();
+        let now = Utc::now();
+
+        // Resolved and old: expires
+        let mut old = event_at(&project_id, EventType::Status, now - Duration::days(40));
+        store.save_event(&old).unwrap();
+        old.status = EventStatus::Resolved;
+        store.save_event(&old).unwrap();
+        // Old but still pending: kept
+        let pending = event_at(&project_id, EventType::Error, now - Duration::days(40));
+        store.save_event(&pending).unwrap();
+        // Recent, updated twice: one line left
+        let mut recent = event_at(&project_id, EventType::Status, now - Duration::days(2));
+        store.save_event(&recent).unwrap();
+        recent.status = EventStatus::Acknowledged;
+        store.save_event(&recent).unwrap();
+        // Deleted
+        let deleted = event_at(&project_id, EventType::Status, now - Duration::days(2));
+        store.save_event(&deleted).unwrap();
+        store.delete_event(&project_id, &deleted.id).unwrap();
+
+        let policy = RetentionPolicy {
+            max_age: Some(Duration::days(30)),
+            keep_unresolved: true,
+        };
+        let report = store.compact(&policy, now).unwrap();
+        assert_eq!(report.events_removed, 1);
+        assert_eq!(report.segments_rewritten, 2);
+        assert_eq!(report.lines_dropped, 4);
+
+        let events = store.list_events(&project_id).unwrap();
+        let ids: HashSet<&str> = events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, HashSet::from([pending.id.as_str(), recent.id.as_str()]));
+        assert_eq!(store.load_event(&project_id, &recent.id).unwrap().status, EventStatus::Acknowledged);
+
+        // Nothing left to do
+        assert_eq!(store.compact(&policy, now).unwrap(), CompactionReport::default());
+    }
+
+    #[test]
+    fn test_migrates_flat_files() {
+        let dir = tempdir().unwrap();
+        let store = EventStore::new(dir.path());
+        let project_id = ProjectId::new();
+        let legacy_dir = store.events_dir(&project_id);
+        fs::create_dir_all(&legacy_dir).unwrap();
+        let events: Vec<Event> = (0..3).map(|_| create_test_event(&project_id)).collect();
+        for event in &events {
+            atomic_write_json(&legacy_dir.join(format!("{}.json", event.id)), event).unwrap();
+        }
+
+        assert_eq!(store.list_events(&project_id).unwrap().len(), 3);
+        assert!(store.load_event(&project_id, &events[0].id).is_ok());
+        let leftovers = fs::read_dir(&legacy_dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json") && e.file_name() != INDEX_FILE)
+            .count();
+        assert_eq!(leftovers, 0);
+        assert_eq!(store.migrate().unwrap(), 0);
     }
 
     #[test]
@@ -22,10 +22,12 @@
 pub mod atomic;
 pub mod error;
 pub mod event_store;
+pub mod snapshot_store;
 pub mod state_store;
 pub mod work_store;
 
 pub use error::{PersistenceError, Result};
-pub use event_store::EventStore;
+pub use event_store::{CompactionReport, EventPage, EventQuery, EventStore, RetentionPolicy};
+pub use snapshot_store::{SessionSnapshot, SnapshotStore};
 pub use state_store::StateStore;
 pub use work_store::WorkStore;
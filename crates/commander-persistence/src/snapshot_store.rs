@@ -0,0 +1,171 @@
+//! Snapshot store for relaunching sessions after a reboot.
+
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use commander_models::ProjectId;
+
+use crate::atomic::{atomic_write_json, read_json};
+use crate::error::{PersistenceError, Result};
+
+/// Last known state of a project's running session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// Project the session belongs to.
+    pub project_id: ProjectId,
+    /// Project name.
+    pub project: String,
+    /// Tmux session name.
+    pub session: String,
+    /// Working directory of the session.
+    pub working_dir: String,
+    /// Adapter (tool ID) running in the session.
+    pub adapter: String,
+    /// Tail of the session's scrollback.
+    pub scrollback: String,
+    /// Serialized Session Agent state, if an agent was tracking the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_state: Option<serde_json::Value>,
+    /// When the snapshot was taken.
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Manages persistence of session snapshots.
+///
+/// Each project keeps only its latest snapshot:
+/// ```text
+/// base_path/
+/// └── snapshots/
+///     ├── proj-abc123.json
+///     └── proj-def456.json
+/// ```
+pub struct SnapshotStore {
+    base_path: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Creates a new SnapshotStore with the given base path.
+    pub fn new(base_path: impl Into<PathBuf>) -> Self {
+        Self {
+            base_path: base_path.into(),
+        }
+    }
+
+    /// Returns the path to the snapshots directory.
+    fn snapshots_dir(&self) -> PathBuf {
+        self.base_path.join("snapshots")
+    }
+
+    /// Returns the path to a project's snapshot file.
+    fn snapshot_path(&self, project_id: &ProjectId) -> PathBuf {
+        self.snapshots_dir().join(format!("{}.json", project_id))
+    }
+
+    /// Saves a snapshot, replacing the project's previous one.
+    pub fn save(&self, snapshot: &SessionSnapshot) -> Result<()> {
+        atomic_write_json(&self.snapshot_path(&snapshot.project_id), snapshot)
+    }
+
+    /// Loads a project's snapshot, if one exists.
+    pub fn load(&self, project_id: &ProjectId) -> Result<Option<SessionSnapshot>> {
+        let path = self.snapshot_path(project_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_json(&path).map(Some)
+    }
+
+    /// Lists all snapshots, oldest first.
+    pub fn list(&self) -> Result<Vec<SessionSnapshot>> {
+        let dir = self.snapshots_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&dir).map_err(|source| PersistenceError::ReadError {
+            path: dir.clone(),
+            source,
+        })?;
+
+        let mut snapshots = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|source| PersistenceError::ReadError {
+                path: dir.clone(),
+                source,
+            })?;
+
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match read_json::<SessionSnapshot>(&path) {
+                    Ok(snapshot) => snapshots.push(snapshot),
+                    Err(e) => {
+                        eprintln!("Warning: failed to load snapshot {:?}: {}", path, e);
+                    }
+                }
+            }
+        }
+
+        snapshots.sort_by_key(|s| s.captured_at);
+        Ok(snapshots)
+    }
+
+    /// Returns the path of a project's snapshot, if one exists.
+    pub fn project_paths(&self, project_id: &ProjectId) -> Vec<PathBuf> {
+        let path = self.snapshot_path(project_id);
+        if path.exists() {
+            vec![path]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Deletes a project's snapshot.
+    pub fn delete(&self, project_id: &ProjectId) -> Result<()> {
+        let path = self.snapshot_path(project_id);
+        if path.exists() {
+            fs::remove_file(&path).map_err(|source| PersistenceError::WriteError { path, source })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn snapshot(project_id: &ProjectId, scrollback: &str) -> SessionSnapshot {
+        SessionSnapshot {
+            project_id: project_id.clone(),
+            project: "api".to_string(),
+            session: "api".to_string(),
+            working_dir: "/code/api".to_string(),
+            adapter: "claude-code".to_string(),
+            scrollback: scrollback.to_string(),
+            agent_state: None,
+            captured_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_save_replaces_and_delete() {
+        let dir = tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        let project_id = ProjectId::new();
+
+        store.save(&snapshot(&project_id, "first")).unwrap();
+        store.save(&snapshot(&project_id, "second")).unwrap();
+
+        let all = store.list().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].scrollback, "second");
+        assert_eq!(store.load(&project_id).unwrap().unwrap().working_dir, "/code/api");
+
+        store.delete(&project_id).unwrap();
+        assert!(store.load(&project_id).unwrap().is_none());
+        assert!(store.list().unwrap().is_empty());
+    }
+}
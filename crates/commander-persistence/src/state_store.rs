@@ -1,6 +1,6 @@
 //! State store for project persistence.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -136,6 +136,16 @@ impl StateStore {
         Ok(())
     }
 
+    /// Returns the path of a project's record, if one exists.
+    pub fn project_paths(&self, id: &ProjectId) -> Vec<PathBuf> {
+        let path = self.project_path(id);
+        if path.exists() {
+            vec![path]
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Finds a project by name or alias.
     ///
     /// Returns the first project that matches the given name or alias.
@@ -145,6 +155,33 @@ impl StateStore {
         Ok(projects.into_values().find(|p| p.matches(name_or_alias)))
     }
 
+    /// Lists workspaces with the names of their projects, both sorted.
+    ///
+    /// Projects outside any workspace are not included.
+    pub fn list_workspaces(&self) -> Result<BTreeMap<String, Vec<String>>> {
+        let mut workspaces: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for project in self.load_all_projects()?.into_values() {
+            if let Some(workspace) = project.workspace {
+                workspaces.entry(workspace).or_default().push(project.name);
+            }
+        }
+        for names in workspaces.values_mut() {
+            names.sort();
+        }
+        Ok(workspaces)
+    }
+
+    /// Loads the projects of `workspace`, sorted by name.
+    pub fn projects_in_workspace(&self, workspace: &str) -> Result<Vec<Project>> {
+        let mut projects: Vec<Project> = self
+            .load_all_projects()?
+            .into_values()
+            .filter(|p| p.in_workspace(workspace))
+            .collect();
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(projects)
+    }
+
     /// Checks if an alias is already in use by any project.
     ///
     /// Returns true if the alias matches:
@@ -354,4 +391,24 @@ mod tests {
 
         assert_eq!(loaded.aliases, vec!["prod", "staging"]);
     }
+
+    #[test]
+    fn test_workspaces() {
+        let dir = tempdir().unwrap();
+        let store = StateStore::new(dir.path());
+
+        for (name, workspace) in [("web", Some("acme")), ("api", Some("acme")), ("site", Some("globex")), ("misc", None)] {
+            let mut project = Project::new(format!("/path/{}", name), name);
+            project.set_workspace(workspace.map(String::from)).unwrap();
+            store.save_project(&project).unwrap();
+        }
+
+        let workspaces = store.list_workspaces().unwrap();
+        assert_eq!(workspaces.keys().collect::<Vec<_>>(), vec!["acme", "globex"]);
+        assert_eq!(workspaces["acme"], vec!["api", "web"]);
+
+        let names: Vec<String> = store.projects_in_workspace("acme").unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["api", "web"]);
+        assert!(store.projects_in_workspace("initech").unwrap().is_empty());
+    }
 }
@@ -113,6 +113,27 @@ impl WorkStore {
         Ok(items)
     }
 
+    /// Lists the IDs of projects that have a work directory.
+    pub fn list_projects(&self) -> Result<Vec<ProjectId>> {
+        let dir = self.base_path.join("work");
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&dir).map_err(|source| PersistenceError::ReadError {
+            path: dir.clone(),
+            source,
+        })?;
+
+        let mut projects: Vec<ProjectId> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| ProjectId::from_string(entry.file_name().to_string_lossy()))
+            .collect();
+        projects.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        Ok(projects)
+    }
+
     /// Deletes a work item.
     pub fn delete_work(&self, project_id: &ProjectId, work_id: &WorkId) -> Result<()> {
         let path = self.work_path(project_id, work_id);
@@ -122,6 +143,18 @@ impl WorkStore {
         Ok(())
     }
 
+    /// Returns the paths holding a project's work items, if any exist.
+    ///
+    /// Used to stage a purge so it can be rolled back.
+    pub fn project_paths(&self, project_id: &ProjectId) -> Vec<PathBuf> {
+        let dir = self.work_dir(project_id);
+        if dir.exists() {
+            vec![dir]
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Deletes all work items for a project.
     pub fn delete_project_work(&self, project_id: &ProjectId) -> Result<()> {
         let dir = self.work_dir(project_id);
@@ -198,6 +231,19 @@ mod tests {
         assert!(items.is_empty());
     }
 
+    #[test]
+    fn test_list_projects() {
+        let dir = tempdir().unwrap();
+        let store = WorkStore::new(dir.path());
+        assert!(store.list_projects().unwrap().is_empty());
+
+        store.save_work(&create_test_work(&ProjectId::from_string("web"))).unwrap();
+        store.save_work(&create_test_work(&ProjectId::from_string("api"))).unwrap();
+
+        let projects = store.list_projects().unwrap();
+        assert_eq!(projects, vec![ProjectId::from_string("api"), ProjectId::from_string("web")]);
+    }
+
     #[test]
     fn test_list_work_sorted_by_priority() {
         let dir = tempdir().unwrap();
@@ -254,6 +300,19 @@ mod tests {
         assert_eq!(store.list_work(&project_id).unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_project_paths() {
+        let dir = tempdir().unwrap();
+        let store = WorkStore::new(dir.path());
+
+        let project_id = ProjectId::new();
+        assert!(store.project_paths(&project_id).is_empty());
+
+        store.save_work(&create_test_work(&project_id)).unwrap();
+        let paths = store.project_paths(&project_id);
+        assert_eq!(paths, vec![dir.path().join("work").join(project_id.as_str())]);
+    }
+
     #[test]
     fn test_work_state_preserved() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,101 @@
+//! Poller workload with many sessions: a global tick capturing every session
+//! versus the per-session `PollSchedule`.
+//!
+//! Twelve sessions are simulated for one minute at a 500ms base interval; two
+//! of them print a new line on every poll, the rest sit at a prompt. A capture
+//! renders a 50-line screen (standing in for `tmux capture-pane`), compares it
+//! with the last one, and classifies it when it changed.
+//!
+//! Run with `cargo bench -p commander-runtime --bench poller`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use commander_core::ChangeDetector;
+use commander_runtime::PollSchedule;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SESSIONS: usize = 12;
+const ACTIVE: usize = 2;
+const BASE: Duration = Duration::from_millis(500);
+const SIMULATED: Duration = Duration::from_secs(60);
+
+/// Work done over one simulated run.
+#[derive(Debug, Default)]
+struct Load {
+    captures: usize,
+    events: usize,
+}
+
+/// The screen of session `index` at poll `tick`.
+fn screen(index: usize, tick: usize) -> String {
+    let progress = if index < ACTIVE { tick } else { 0 };
+    (0..50)
+        .map(|line| format!("session {} line {} step {}", index, line, progress.saturating_sub(line)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Capture every session on every tick (the previous poller design).
+fn global_tick() -> Load {
+    let mut load = Load::default();
+    let mut last: HashMap<usize, String> = HashMap::new();
+    let mut detectors: Vec<ChangeDetector> = (0..SESSIONS).map(|_| ChangeDetector::new()).collect();
+    let ticks = (SIMULATED.as_millis() / BASE.as_millis()) as usize;
+
+    for tick in 0..ticks {
+        for (index, detector) in detectors.iter_mut().enumerate() {
+            let output = screen(index, tick);
+            load.captures += 1;
+            if last.get(&index) != Some(&output) {
+                detector.detect(&output);
+                load.events += 1;
+                last.insert(index, output);
+            }
+        }
+    }
+    load
+}
+
+/// Capture only the sessions `PollSchedule` reports due, coalescing events.
+fn adaptive() -> Load {
+    let mut load = Load::default();
+    let mut last: HashMap<usize, String> = HashMap::new();
+    let mut schedule = PollSchedule::new(BASE, Duration::from_secs(5), Duration::from_secs(1));
+    let names: Vec<String> = (0..SESSIONS).map(|i| format!("session-{}", i)).collect();
+    let start = Instant::now();
+    let ticks = (SIMULATED.as_millis() / BASE.as_millis()) as usize;
+
+    for tick in 0..ticks {
+        let now = start + BASE * tick as u32;
+        for (index, name) in names.iter().enumerate() {
+            if !schedule.is_due(name, 0, now) {
+                continue;
+            }
+            let output = screen(index, tick);
+            load.captures += 1;
+            let changed = last.get(&index) != Some(&output);
+            if schedule.observe(name, &output, changed, now).is_some() {
+                load.events += 1;
+            }
+            if changed {
+                last.insert(index, output);
+            }
+        }
+        load.events += schedule.flush(now).len();
+    }
+    load
+}
+
+fn bench_poller(c: &mut Criterion) {
+    eprintln!("global tick: {:?}", global_tick());
+    eprintln!("adaptive:    {:?}", adaptive());
+
+    let mut group = c.benchmark_group("poll_12_sessions_60s");
+    group.bench_function("global_tick", |b| b.iter(global_tick));
+    group.bench_function("adaptive", |b| b.iter(adaptive));
+    group.finish();
+}
+
+criterion_group!(benches, bench_poller);
+criterion_main!(benches);
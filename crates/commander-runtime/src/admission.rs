@@ -0,0 +1,202 @@
+//! Admission control for instance starts.
+//!
+//! `ConcurrencyLimits` caps how many instances run at once, overall and per
+//! workspace. A start beyond a limit is queued instead of refused: it is
+//! recorded as a work item of its project, `RuntimeEvent::StartQueued` is
+//! emitted, and the instance starts as soon as a stop frees a slot.
+//!
+//! The executor writes the current `SlotUsage` to the state directory, so
+//! `commander status --runtime` can show it from another process.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use commander_adapters::RuntimeAdapter;
+use commander_core::RuntimeSettings;
+use commander_models::{Project, ProjectId, WorkId};
+
+/// File in the state directory holding the latest `SlotUsage`.
+pub const SLOTS_FILE: &str = "runtime-slots.json";
+
+/// Instances allowed to run at once. `None` leaves a scope unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConcurrencyLimits {
+    /// Limit across all instances.
+    pub global: Option<usize>,
+    /// Limit per workspace, keyed by workspace name.
+    pub workspaces: HashMap<String, usize>,
+}
+
+impl ConcurrencyLimits {
+    /// Limits from `[runtime]` in `config.toml`.
+    pub fn from_settings(settings: &RuntimeSettings) -> Self {
+        Self {
+            global: settings.max_sessions,
+            workspaces: settings
+                .workspace_max_sessions
+                .iter()
+                .map(|(name, limit)| (name.clone(), *limit))
+                .collect(),
+        }
+    }
+
+    /// These limits, with scopes they leave unset taken from `fallback`.
+    pub fn or(mut self, fallback: &ConcurrencyLimits) -> Self {
+        self.global = self.global.or(fallback.global);
+        if self.workspaces.is_empty() {
+            self.workspaces = fallback.workspaces.clone();
+        }
+        self
+    }
+
+    /// Whether an instance in `workspace` may start next to `running`, the
+    /// workspaces of the instances already running.
+    pub fn has_slot(&self, running: &[Option<String>], workspace: Option<&str>) -> bool {
+        if self.global.is_some_and(|limit| running.len() >= limit) {
+            return false;
+        }
+        let Some((name, limit)) = workspace.and_then(|name| Some((name, *self.workspaces.get(name)?))) else {
+            return true;
+        };
+        running.iter().filter(|w| w.as_deref() == Some(name)).count() < limit
+    }
+}
+
+/// Outcome of asking the executor to start an instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    /// The instance is running.
+    Started,
+    /// The instance waits for a free slot.
+    Queued {
+        /// 1-based place in the admission queue.
+        position: usize,
+        /// Work item recording the queued start, when a state directory is set.
+        work_id: Option<WorkId>,
+    },
+}
+
+/// A start waiting for a free slot.
+pub(crate) struct QueuedStart {
+    pub project: Project,
+    pub adapter: Arc<dyn RuntimeAdapter>,
+    pub work_id: Option<WorkId>,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Slots in use in one workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceSlots {
+    pub running: usize,
+    pub limit: Option<usize>,
+}
+
+/// A start waiting in the admission queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedSlot {
+    pub project_id: ProjectId,
+    pub project: String,
+    pub workspace: Option<String>,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Slot utilization at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotUsage {
+    /// Instances running.
+    pub running: usize,
+    /// Global limit, if any.
+    pub limit: Option<usize>,
+    /// Per workspace with running instances or a limit.
+    pub workspaces: BTreeMap<String, WorkspaceSlots>,
+    /// Starts waiting for a slot, first in line first.
+    pub queued: Vec<QueuedSlot>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SlotUsage {
+    /// Usage of `limits` by instances running in `running` workspaces, with
+    /// `queued` waiting.
+    pub(crate) fn new(limits: &ConcurrencyLimits, running: &[Option<String>], queued: Vec<QueuedSlot>) -> Self {
+        let mut workspaces: BTreeMap<String, WorkspaceSlots> = limits
+            .workspaces
+            .iter()
+            .map(|(name, limit)| (name.clone(), WorkspaceSlots { running: 0, limit: Some(*limit) }))
+            .collect();
+        for name in running.iter().flatten() {
+            workspaces
+                .entry(name.clone())
+                .or_insert(WorkspaceSlots { running: 0, limit: None })
+                .running += 1;
+        }
+        Self {
+            running: running.len(),
+            limit: limits.global,
+            workspaces,
+            queued,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Path of the usage file in `state_dir`.
+    pub fn path(state_dir: &Path) -> PathBuf {
+        state_dir.join(SLOTS_FILE)
+    }
+
+    /// Usage last written to `state_dir`, if any.
+    pub fn load(state_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(state_dir)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Write this usage to `state_dir`.
+    pub fn save(&self, state_dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(state_dir)?;
+        fs::write(Self::path(state_dir), serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(workspaces: &[Option<&str>]) -> Vec<Option<String>> {
+        workspaces.iter().map(|w| w.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn test_has_slot() {
+        let limits = ConcurrencyLimits {
+            global: Some(3),
+            workspaces: HashMap::from([("acme".to_string(), 1)]),
+        };
+        assert!(limits.has_slot(&running(&[None]), Some("acme")));
+        assert!(!limits.has_slot(&running(&[Some("acme")]), Some("acme")));
+        assert!(limits.has_slot(&running(&[Some("acme")]), Some("other")));
+        assert!(limits.has_slot(&running(&[Some("acme")]), None));
+        assert!(!limits.has_slot(&running(&[None, None, None]), None));
+        assert!(ConcurrencyLimits::default().has_slot(&running(&[None; 50]), Some("acme")));
+    }
+
+    #[test]
+    fn test_slot_usage_round_trip() {
+        let limits = ConcurrencyLimits {
+            global: Some(4),
+            workspaces: HashMap::from([("acme".to_string(), 2)]),
+        };
+        let usage = SlotUsage::new(&limits, &running(&[Some("acme"), Some("beta"), None]), Vec::new());
+        assert_eq!(usage.running, 3);
+        assert_eq!(usage.workspaces["acme"], WorkspaceSlots { running: 1, limit: Some(2) });
+        assert_eq!(usage.workspaces["beta"], WorkspaceSlots { running: 1, limit: None });
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(SlotUsage::load(dir.path()), None);
+        usage.save(dir.path()).unwrap();
+        assert_eq!(SlotUsage::load(dir.path()), Some(usage));
+    }
+}
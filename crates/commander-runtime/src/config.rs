@@ -1,24 +1,75 @@
 //! Runtime configuration.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
+use commander_models::ProjectId;
+
+use crate::admission::ConcurrencyLimits;
+use crate::health::RestartPolicy;
+use crate::watchdog::WatchdogConfig;
+
 /// Configuration for the runtime.
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
-    /// How often to poll for output changes.
+    /// How often to poll for output changes (the fastest per-session rate).
     pub poll_interval: Duration,
+    /// Slowest per-session poll interval once a session's output goes quiet.
+    pub max_poll_interval: Duration,
+    /// Maximum tmux captures running at the same time.
+    pub capture_workers: usize,
+    /// Window within which a session's output changes are coalesced into one event.
+    pub output_coalesce: Duration,
     /// How long before marking an instance as idle.
     pub idle_timeout: Duration,
     /// Maximum concurrent instances allowed.
     pub max_instances: usize,
+    /// Limits beyond which starts are queued rather than refused.
+    pub concurrency: ConcurrencyLimits,
+    /// Whether to watch project directories for file changes.
+    pub watch_files: bool,
+    /// Quiet period before a batch of file changes is reported.
+    pub file_debounce: Duration,
+    /// Stuck-session and error-loop alert thresholds.
+    pub watchdog: WatchdogConfig,
+    /// How often to check that instance tmux sessions still exist.
+    pub health_check_interval: Duration,
+    /// Restart policy for projects without an override.
+    pub restart_policy: RestartPolicy,
+    /// Per-project restart policy overrides, keyed by project ID.
+    pub project_restart_policies: HashMap<String, RestartPolicy>,
+    /// Whether to record instance pane output as asciicast files.
+    pub record_sessions: bool,
+    /// Whether the poller applies `[runtime]` changes from `config.toml`.
+    pub reload_config: bool,
+    /// Whether sessions are watched by tmux control mode clients, capturing
+    /// on output notifications instead of polling (falls back to polling).
+    pub control_mode: bool,
+    /// Where queued starts and slot usage are persisted, if anywhere.
+    pub state_dir: Option<PathBuf>,
 }
 
 impl Default for RuntimeConfig {
     fn default() -> Self {
         Self {
             poll_interval: Duration::from_millis(500),
+            max_poll_interval: Duration::from_secs(5),
+            capture_workers: 4,
+            output_coalesce: Duration::from_secs(1),
             idle_timeout: Duration::from_secs(30),
             max_instances: 10,
+            concurrency: ConcurrencyLimits::default(),
+            watch_files: true,
+            file_debounce: Duration::from_millis(500),
+            watchdog: WatchdogConfig::default(),
+            health_check_interval: Duration::from_secs(5),
+            restart_policy: RestartPolicy::Never,
+            project_restart_policies: HashMap::new(),
+            record_sessions: false,
+            reload_config: false,
+            control_mode: false,
+            state_dir: None,
         }
     }
 }
@@ -35,6 +86,24 @@ impl RuntimeConfig {
         self
     }
 
+    /// Sets the slowest interval quiet sessions back off to.
+    pub fn with_max_poll_interval(mut self, interval: Duration) -> Self {
+        self.max_poll_interval = interval;
+        self
+    }
+
+    /// Sets how many tmux captures may run at the same time (at least one).
+    pub fn with_capture_workers(mut self, workers: usize) -> Self {
+        self.capture_workers = workers.max(1);
+        self
+    }
+
+    /// Sets the window within which a session's output changes are coalesced.
+    pub fn with_output_coalesce(mut self, window: Duration) -> Self {
+        self.output_coalesce = window;
+        self
+    }
+
     /// Sets the idle timeout.
     pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
         self.idle_timeout = timeout;
@@ -46,6 +115,81 @@ impl RuntimeConfig {
         self.max_instances = max;
         self
     }
+
+    /// Sets the global and per-workspace concurrency limits.
+    pub fn with_concurrency_limits(mut self, limits: ConcurrencyLimits) -> Self {
+        self.concurrency = limits;
+        self
+    }
+
+    /// Sets the state directory for queued starts and slot usage.
+    pub fn with_state_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.state_dir = Some(dir.into());
+        self
+    }
+
+    /// Enables or disables file watching for started instances.
+    pub fn with_watch_files(mut self, watch: bool) -> Self {
+        self.watch_files = watch;
+        self
+    }
+
+    /// Sets the debounce period for file change events.
+    pub fn with_file_debounce(mut self, debounce: Duration) -> Self {
+        self.file_debounce = debounce;
+        self
+    }
+
+    /// Sets the watchdog configuration.
+    pub fn with_watchdog(mut self, watchdog: WatchdogConfig) -> Self {
+        self.watchdog = watchdog;
+        self
+    }
+
+    /// Sets how often instance sessions are health-checked.
+    pub fn with_health_check_interval(mut self, interval: Duration) -> Self {
+        self.health_check_interval = interval;
+        self
+    }
+
+    /// Sets the default restart policy for crashed sessions.
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
+        self
+    }
+
+    /// Overrides the restart policy for a single project.
+    pub fn with_project_restart_policy(mut self, project_id: &ProjectId, policy: RestartPolicy) -> Self {
+        self.project_restart_policies
+            .insert(project_id.as_str().to_string(), policy);
+        self
+    }
+
+    /// Enables or disables recording of instance pane output.
+    pub fn with_record_sessions(mut self, record: bool) -> Self {
+        self.record_sessions = record;
+        self
+    }
+
+    /// Enables or disables applying `config.toml` changes while running.
+    pub fn with_reload_config(mut self, reload: bool) -> Self {
+        self.reload_config = reload;
+        self
+    }
+
+    /// Enables or disables tmux control mode clients for output notifications.
+    pub fn with_control_mode(mut self, control_mode: bool) -> Self {
+        self.control_mode = control_mode;
+        self
+    }
+
+    /// Returns the restart policy that applies to a project.
+    pub fn restart_policy_for(&self, project_id: &ProjectId) -> RestartPolicy {
+        self.project_restart_policies
+            .get(project_id.as_str())
+            .copied()
+            .unwrap_or(self.restart_policy)
+    }
 }
 
 #[cfg(test)]
@@ -59,6 +203,15 @@ mod tests {
         assert_eq!(config.poll_interval, Duration::from_millis(500));
         assert_eq!(config.idle_timeout, Duration::from_secs(30));
         assert_eq!(config.max_instances, 10);
+        assert!(config.watch_files);
+        assert_eq!(config.file_debounce, Duration::from_millis(500));
+        assert!(!config.record_sessions);
+        assert_eq!(config.max_poll_interval, Duration::from_secs(5));
+        assert_eq!(config.capture_workers, 4);
+        assert_eq!(config.output_coalesce, Duration::from_secs(1));
+        assert_eq!(config.concurrency, ConcurrencyLimits::default());
+        assert!(config.state_dir.is_none());
+        assert!(!config.control_mode);
     }
 
     #[test]
@@ -66,10 +219,42 @@ mod tests {
         let config = RuntimeConfig::new()
             .with_poll_interval(Duration::from_millis(100))
             .with_idle_timeout(Duration::from_secs(60))
-            .with_max_instances(5);
+            .with_max_instances(5)
+            .with_watch_files(false)
+            .with_file_debounce(Duration::from_millis(200))
+            .with_max_poll_interval(Duration::from_secs(2))
+            .with_capture_workers(0)
+            .with_output_coalesce(Duration::from_millis(250))
+            .with_concurrency_limits(ConcurrencyLimits {
+                global: Some(2),
+                workspaces: HashMap::new(),
+            })
+            .with_state_dir("/tmp/commander")
+            .with_control_mode(true);
 
         assert_eq!(config.poll_interval, Duration::from_millis(100));
         assert_eq!(config.idle_timeout, Duration::from_secs(60));
         assert_eq!(config.max_instances, 5);
+        assert!(!config.watch_files);
+        assert_eq!(config.file_debounce, Duration::from_millis(200));
+        assert_eq!(config.max_poll_interval, Duration::from_secs(2));
+        assert_eq!(config.capture_workers, 1);
+        assert_eq!(config.output_coalesce, Duration::from_millis(250));
+        assert_eq!(config.concurrency.global, Some(2));
+        assert_eq!(config.state_dir, Some(PathBuf::from("/tmp/commander")));
+        assert!(config.control_mode);
+    }
+
+    #[test]
+    fn test_restart_policy_override() {
+        let api = ProjectId::from_string("api");
+        let web = ProjectId::from_string("web");
+        let config = RuntimeConfig::new()
+            .with_restart_policy(RestartPolicy::on_crash(1))
+            .with_project_restart_policy(&web, RestartPolicy::Never);
+
+        assert_eq!(config.restart_policy_for(&api), RestartPolicy::on_crash(1));
+        assert_eq!(config.restart_policy_for(&web), RestartPolicy::Never);
+        assert_eq!(RuntimeConfig::default().restart_policy_for(&api), RestartPolicy::Never);
     }
 }
@@ -0,0 +1,100 @@
+//! Environment variables declared in a project's `.commander.toml`.
+//!
+//! Adapters often need variables such as API keys or `NODE_ENV`:
+//!
+//! ```toml
+//! [env]
+//! NODE_ENV = "development"
+//! OPENAI_API_KEY = { secret = "OPENAI_API_KEY" }
+//! ```
+//!
+//! Plain values are used as written. `secret` values name an entry in the
+//! secret store (`commander secrets set`), falling back to Commander's own
+//! environment, so keys never sit in the project file. The variables are set
+//! on the tmux session when it is created, before the adapter launches.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::panes::ProjectConfig;
+
+/// Value of a variable in `[env]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnvValue {
+    /// Used as written.
+    Plain(String),
+    /// Read from the secret store entry of this name.
+    Secret { secret: String },
+}
+
+/// Variables of `[env]` with their values resolved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedEnv {
+    /// Variables to set, sorted by name.
+    pub vars: Vec<(String, String)>,
+    /// Variables whose secret was not found, and were left out.
+    pub missing: Vec<String>,
+}
+
+/// Resolve `env`, looking secrets up with `secret`.
+pub fn resolve_env(env: &BTreeMap<String, EnvValue>, secret: impl Fn(&str) -> Option<String>) -> ResolvedEnv {
+    let mut resolved = ResolvedEnv::default();
+    for (name, value) in env {
+        let value = match value {
+            EnvValue::Plain(value) => Some(value.clone()),
+            EnvValue::Secret { secret: key } => secret(key),
+        };
+        match value {
+            Some(value) => resolved.vars.push((name.clone(), value)),
+            None => resolved.missing.push(name.clone()),
+        }
+    }
+    resolved
+}
+
+/// Environment for a session of the project at `project_path`, with secrets
+/// read from Commander's secret store.
+pub fn project_env(project_path: &Path) -> Result<ResolvedEnv> {
+    let config = ProjectConfig::load(project_path)?;
+    Ok(resolve_env(&config.env, commander_core::get_secret))
+}
+
+/// Whether `name` can be used as an environment variable name.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_env() {
+        let config = ProjectConfig::parse(
+            "[env]\nNODE_ENV = \"development\"\nAPI_KEY = { secret = \"TEST_API_KEY\" }\nTOKEN = { secret = \"MISSING\" }\n",
+        )
+        .unwrap();
+        let resolved = resolve_env(&config.env, |key| (key == "TEST_API_KEY").then(|| "sk-123".to_string()));
+        assert_eq!(
+            resolved.vars,
+            vec![
+                ("API_KEY".to_string(), "sk-123".to_string()),
+                ("NODE_ENV".to_string(), "development".to_string()),
+            ]
+        );
+        assert_eq!(resolved.missing, vec!["TOKEN".to_string()]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_env() {
+        assert!(ProjectConfig::parse("[env]\n\"1BAD\" = \"x\"\n").is_err());
+        assert!(ProjectConfig::parse("[env]\nKEY = { secret = \"\" }\n").is_err());
+        assert!(ProjectConfig::parse("[env]\nKEY = 3\n").is_err());
+    }
+}
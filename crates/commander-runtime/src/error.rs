@@ -17,6 +17,10 @@ pub enum RuntimeError {
     #[error("instance already exists: {0}")]
     InstanceExists(String),
 
+    /// Auxiliary pane not found.
+    #[error("pane not found: {0}")]
+    PaneNotFound(String),
+
     /// Maximum instances reached.
     #[error("maximum instances reached: {0}")]
     MaxInstancesReached(usize),
@@ -33,6 +37,22 @@ pub enum RuntimeError {
     #[error("shutdown error: {0}")]
     Shutdown(String),
 
+    /// File watcher error.
+    #[error("file watcher error: {0}")]
+    Watcher(String),
+
+    /// Watchdog configuration or webhook error.
+    #[error("watchdog error: {0}")]
+    Watchdog(String),
+
+    /// Outgoing webhook delivery error.
+    #[error("webhook error: {0}")]
+    Webhook(String),
+
+    /// Invalid project configuration (`.commander.toml`).
+    #[error("project config error: {0}")]
+    ProjectConfig(String),
+
     /// Channel error.
     #[error("channel error: {0}")]
     Channel(String),
@@ -40,3 +60,14 @@ pub enum RuntimeError {
 
 /// Result type for runtime operations.
 pub type Result<T> = std::result::Result<T, RuntimeError>;
+
+impl From<RuntimeError> for commander_core::CommanderError {
+    fn from(err: RuntimeError) -> Self {
+        use commander_core::CommanderError;
+        match err {
+            RuntimeError::Tmux(_) => CommanderError::Tmux(err.to_string()),
+            RuntimeError::ProjectConfig(_) => CommanderError::Config(err.to_string()),
+            _ => CommanderError::Runtime(err.to_string()),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 //! Runtime events.
 
+use std::path::PathBuf;
+
 use commander_models::{ProjectId, ProjectState};
 
+use crate::watchdog::WatchdogAlert;
+
 /// Events emitted by the runtime.
 #[derive(Debug, Clone)]
 pub enum RuntimeEvent {
@@ -24,6 +28,15 @@ pub enum RuntimeEvent {
         /// The output text.
         output: String,
     },
+    /// Output changed in one of an instance's auxiliary panes.
+    PaneOutput {
+        /// Project ID.
+        project_id: ProjectId,
+        /// Pane name from `.commander.toml`.
+        pane: String,
+        /// The output text.
+        output: String,
+    },
     /// The state of an instance changed.
     StateChanged {
         /// Project ID.
@@ -31,6 +44,45 @@ pub enum RuntimeEvent {
         /// New state.
         state: ProjectState,
     },
+    /// Files in the project directory changed (debounced).
+    FilesChanged {
+        /// Project ID.
+        project_id: ProjectId,
+        /// Changed paths, relative to the project root.
+        paths: Vec<PathBuf>,
+    },
+    /// The watchdog detected a stuck session or an error loop.
+    WatchdogAlert {
+        /// Project ID.
+        project_id: ProjectId,
+        /// The alert raised.
+        alert: WatchdogAlert,
+    },
+    /// An instance's tmux session disappeared outside Commander.
+    SessionCrashed {
+        /// Project ID.
+        project_id: ProjectId,
+        /// Tmux session name.
+        session: String,
+    },
+    /// A crashed instance was relaunched by its restart policy.
+    InstanceRestarted {
+        /// Project ID.
+        project_id: ProjectId,
+        /// Tmux session name.
+        session: String,
+        /// Number of restarts so far, including this one.
+        attempt: u32,
+        /// Whether the last prompt will be replayed once the adapter is idle.
+        replay_prompt: bool,
+    },
+    /// A start was queued because a concurrency limit is reached.
+    StartQueued {
+        /// Project ID.
+        project_id: ProjectId,
+        /// 1-based place in the admission queue.
+        position: usize,
+    },
     /// An error occurred.
     Error {
         /// Project ID.
@@ -47,11 +99,34 @@ impl RuntimeEvent {
             RuntimeEvent::InstanceStarted { project_id, .. } => project_id,
             RuntimeEvent::InstanceStopped { project_id } => project_id,
             RuntimeEvent::OutputReceived { project_id, .. } => project_id,
+            RuntimeEvent::PaneOutput { project_id, .. } => project_id,
             RuntimeEvent::StateChanged { project_id, .. } => project_id,
+            RuntimeEvent::FilesChanged { project_id, .. } => project_id,
+            RuntimeEvent::WatchdogAlert { project_id, .. } => project_id,
+            RuntimeEvent::SessionCrashed { project_id, .. } => project_id,
+            RuntimeEvent::InstanceRestarted { project_id, .. } => project_id,
+            RuntimeEvent::StartQueued { project_id, .. } => project_id,
             RuntimeEvent::Error { project_id, .. } => project_id,
         }
     }
 
+    /// Event type name, as used in webhook payloads and filters.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RuntimeEvent::InstanceStarted { .. } => "instance_started",
+            RuntimeEvent::InstanceStopped { .. } => "instance_stopped",
+            RuntimeEvent::OutputReceived { .. } => "output_received",
+            RuntimeEvent::PaneOutput { .. } => "pane_output",
+            RuntimeEvent::StateChanged { .. } => "state_changed",
+            RuntimeEvent::FilesChanged { .. } => "files_changed",
+            RuntimeEvent::WatchdogAlert { .. } => "watchdog_alert",
+            RuntimeEvent::SessionCrashed { .. } => "session_crashed",
+            RuntimeEvent::InstanceRestarted { .. } => "instance_restarted",
+            RuntimeEvent::StartQueued { .. } => "start_queued",
+            RuntimeEvent::Error { .. } => "error",
+        }
+    }
+
     /// Returns true if this is an error event.
     pub fn is_error(&self) -> bool {
         matches!(self, RuntimeEvent::Error { .. })
@@ -89,6 +164,33 @@ mod tests {
         };
         assert_eq!(event.project_id(), &project_id);
 
+        let event = RuntimeEvent::FilesChanged {
+            project_id: project_id.clone(),
+            paths: vec![PathBuf::from("src/main.rs")],
+        };
+        assert_eq!(event.project_id(), &project_id);
+
+        let event = RuntimeEvent::SessionCrashed {
+            project_id: project_id.clone(),
+            session: "test-session".to_string(),
+        };
+        assert_eq!(event.project_id(), &project_id);
+
+        let event = RuntimeEvent::InstanceRestarted {
+            project_id: project_id.clone(),
+            session: "test-session".to_string(),
+            attempt: 1,
+            replay_prompt: true,
+        };
+        assert_eq!(event.project_id(), &project_id);
+
+        let event = RuntimeEvent::StartQueued {
+            project_id: project_id.clone(),
+            position: 2,
+        };
+        assert_eq!(event.project_id(), &project_id);
+        assert_eq!(event.kind(), "start_queued");
+
         let event = RuntimeEvent::Error {
             project_id: project_id.clone(),
             error: "test error".to_string(),
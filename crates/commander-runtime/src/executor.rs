@@ -1,20 +1,28 @@
 //! Runtime executor for managing running instances.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use chrono::{DateTime, Utc};
-use tokio::sync::{broadcast, RwLock};
-use tracing::{debug, info};
+use tokio::sync::{broadcast, Mutex as AsyncMutex, RwLock};
+use tracing::{debug, info, warn};
 
 use commander_adapters::RuntimeAdapter;
-use commander_models::{Project, ProjectId, ProjectState};
+use commander_models::{Project, ProjectId, ProjectState, WorkId, WorkItem};
+use commander_persistence::WorkStore;
+use commander_core::CommandSender;
 use commander_tmux::TmuxOrchestrator;
+use commander_work::WorkQueue;
 
+use crate::admission::{Admission, ConcurrencyLimits, QueuedSlot, QueuedStart, SlotUsage};
 use crate::config::RuntimeConfig;
+use crate::env::{project_env, resolve_env};
 use crate::error::{Result, RuntimeError};
 use crate::event::RuntimeEvent;
+use crate::panes::{AuxPane, PaneSpec, ProjectConfig};
+use crate::watchdog::Watchdog;
+use crate::watcher::ProjectWatcher;
 
 /// A running instance of an AI tool.
 pub struct RunningInstance {
@@ -30,6 +38,24 @@ pub struct RunningInstance {
     pub last_output: Option<String>,
     /// Current state.
     pub state: ProjectState,
+    /// File watcher for the project directory, if enabled.
+    pub watcher: Option<ProjectWatcher>,
+    /// Command used to launch the adapter (replayed on restart).
+    pub launch_command: String,
+    /// Last prompt sent through `RuntimeExecutor::send_prompt`.
+    pub last_prompt: Option<String>,
+    /// Number of prompts sent, so the poller captures right after each one.
+    pub prompts_sent: u64,
+    /// Prompt to resend once a restarted adapter is idle.
+    pub pending_replay: Option<String>,
+    /// Number of automatic restarts after crashes.
+    pub restarts: u32,
+    /// Project directory, where auxiliary pane commands run.
+    pub project_path: Option<String>,
+    /// Auxiliary panes from the project's `.commander.toml`.
+    pub panes: Vec<AuxPane>,
+    /// Workspace of the project, counted against its concurrency limit.
+    pub workspace: Option<String>,
 }
 
 impl fmt::Debug for RunningInstance {
@@ -41,6 +67,9 @@ impl fmt::Debug for RunningInstance {
             .field("started_at", &self.started_at)
             .field("last_output", &self.last_output.is_some())
             .field("state", &self.state)
+            .field("watching", &self.watcher.is_some())
+            .field("restarts", &self.restarts)
+            .field("panes", &self.panes.iter().map(|p| &p.name).collect::<Vec<_>>())
             .finish()
     }
 }
@@ -59,6 +88,15 @@ impl RunningInstance {
             started_at: Utc::now(),
             last_output: None,
             state: ProjectState::Idle,
+            watcher: None,
+            launch_command: String::new(),
+            last_prompt: None,
+            prompts_sent: 0,
+            pending_replay: None,
+            restarts: 0,
+            project_path: None,
+            panes: Vec::new(),
+            workspace: None,
         }
     }
 }
@@ -73,15 +111,27 @@ pub struct RuntimeExecutor {
     instances: Arc<RwLock<HashMap<String, RunningInstance>>>,
     /// Event broadcast channel.
     event_tx: broadcast::Sender<RuntimeEvent>,
+    /// Stuck-session and error-loop detection.
+    watchdog: Mutex<Watchdog>,
+    /// Concurrency limits, replaced on config reload.
+    limits: Mutex<ConcurrencyLimits>,
+    /// Starts waiting for a free slot, first in line first.
+    admission: AsyncMutex<VecDeque<QueuedStart>>,
+    /// Work queue recording queued starts, when a state directory is set.
+    work: Option<WorkQueue>,
 }
 
 impl RuntimeExecutor {
     /// Creates a new runtime executor.
     pub fn new(config: RuntimeConfig) -> Result<Self> {
-        let tmux = TmuxOrchestrator::new()?;
+        let tmux = TmuxOrchestrator::new()?.with_sender(CommandSender::Daemon);
         let (event_tx, _) = broadcast::channel(256);
 
         Ok(Self {
+            watchdog: Mutex::new(Watchdog::new(config.watchdog.clone())),
+            limits: Mutex::new(config.concurrency.clone()),
+            admission: AsyncMutex::new(VecDeque::new()),
+            work: config.state_dir.as_ref().map(|dir| WorkQueue::new(WorkStore::new(dir))),
             config,
             tmux,
             instances: Arc::new(RwLock::new(HashMap::new())),
@@ -94,6 +144,10 @@ impl RuntimeExecutor {
         let (event_tx, _) = broadcast::channel(256);
 
         Ok(Self {
+            watchdog: Mutex::new(Watchdog::new(config.watchdog.clone())),
+            limits: Mutex::new(config.concurrency.clone()),
+            admission: AsyncMutex::new(VecDeque::new()),
+            work: config.state_dir.as_ref().map(|dir| WorkQueue::new(WorkStore::new(dir))),
             config,
             tmux,
             instances: Arc::new(RwLock::new(HashMap::new())),
@@ -111,27 +165,70 @@ impl RuntimeExecutor {
         &self.tmux
     }
 
+    /// Returns the watchdog for internal use.
+    pub(crate) fn watchdog(&self) -> &Mutex<Watchdog> {
+        &self.watchdog
+    }
+
     /// Returns the instances map for internal use.
     pub(crate) fn instances(&self) -> Arc<RwLock<HashMap<String, RunningInstance>>> {
         Arc::clone(&self.instances)
     }
 
     /// Start an instance for a project.
+    ///
+    /// When a concurrency limit is reached the start is queued instead, recorded
+    /// as a work item and announced with `RuntimeEvent::StartQueued`; it runs as
+    /// soon as a stop frees a slot. Starting a project that is already queued
+    /// returns its place in the queue.
     pub async fn start(
         &self,
         project: &Project,
         adapter: Arc<dyn RuntimeAdapter>,
-    ) -> Result<()> {
-        let project_id_str = project.id.as_str().to_string();
+    ) -> Result<Admission> {
+        let mut queue = self.admission.lock().await;
+        if let Some(index) = queue.iter().position(|q| q.project.id == project.id) {
+            return Ok(Admission::Queued {
+                position: index + 1,
+                work_id: queue[index].work_id.clone(),
+            });
+        }
+        if self.has_instance(&project.id).await {
+            return Err(RuntimeError::InstanceExists(project.id.as_str().to_string()));
+        }
 
-        // Check if instance already exists
-        {
-            let instances = self.instances.read().await;
-            if instances.contains_key(&project_id_str) {
-                return Err(RuntimeError::InstanceExists(project_id_str));
-            }
+        let running = self.running_workspaces().await;
+        if self.limits().has_slot(&running, project.workspace.as_deref()) {
+            self.launch(project, adapter).await?;
+            drop(queue);
+            self.publish_slots().await;
+            return Ok(Admission::Started);
         }
 
+        let work_id = self.record_queued_start(project);
+        queue.push_back(QueuedStart {
+            project: project.clone(),
+            adapter,
+            work_id: work_id.clone(),
+            queued_at: Utc::now(),
+        });
+        let position = queue.len();
+        drop(queue);
+
+        info!(project_id = %project.id, position, "no free slot, start queued");
+        self.emit_event(RuntimeEvent::StartQueued {
+            project_id: project.id.clone(),
+            position,
+        });
+        self.publish_slots().await;
+
+        Ok(Admission::Queued { position, work_id })
+    }
+
+    /// Launch an instance in a new tmux session.
+    async fn launch(&self, project: &Project, adapter: Arc<dyn RuntimeAdapter>) -> Result<()> {
+        let project_id_str = project.id.as_str().to_string();
+
         // Check max instances
         {
             let instances = self.instances.read().await;
@@ -153,8 +250,23 @@ impl RuntimeExecutor {
             "starting instance"
         );
 
-        // Create tmux session
-        self.tmux.create_session(&session_name)?;
+        let config = match ProjectConfig::load(std::path::Path::new(&project.path)) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!(project_id = %project.id, error = %e, "ignoring invalid project config");
+                ProjectConfig::default()
+            }
+        };
+        let env = resolve_env(&config.env, commander_core::get_secret);
+        if !env.missing.is_empty() {
+            warn!(project_id = %project.id, missing = ?env.missing, "secrets not found, variables not set");
+        }
+
+        // Create tmux session with the project's environment
+        self.tmux.create_session_with_env(&session_name, None, &env.vars)?;
+
+        // Auxiliary panes open in the background, leaving the AI pane active
+        let panes = self.open_panes(&session_name, &project.path, &config.panes);
 
         // Send launch command to the session
         let full_command = if args.is_empty() {
@@ -165,11 +277,19 @@ impl RuntimeExecutor {
         self.tmux.send_line(&session_name, None, &full_command)?;
 
         // Create running instance
-        let instance = RunningInstance::new(
+        let mut instance = RunningInstance::new(
             project.id.clone(),
             session_name.clone(),
             adapter,
         );
+        instance.launch_command = full_command;
+        instance.project_path = Some(project.path.clone());
+        instance.panes = panes;
+        instance.workspace = project.workspace.clone();
+
+        if self.config.watch_files {
+            instance.watcher = self.watch_project(project);
+        }
 
         // Add to instances map
         {
@@ -192,6 +312,203 @@ impl RuntimeExecutor {
         Ok(())
     }
 
+    /// Record a queued start as a work item of its project.
+    fn record_queued_start(&self, project: &Project) -> Option<WorkId> {
+        let work = self.work.as_ref()?;
+        let mut item = WorkItem::new(project.id.clone(), format!("Start session for {}", project.name));
+        item.metadata
+            .insert("kind".to_string(), serde_json::json!("session_start"));
+        if let Some(workspace) = &project.workspace {
+            item.metadata
+                .insert("workspace".to_string(), serde_json::json!(workspace));
+        }
+        match work.enqueue(item) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                warn!(project_id = %project.id, error = %e, "failed to record queued start");
+                None
+            }
+        }
+    }
+
+    /// Close the work item of a queued start, completed or failed with a reason.
+    fn finish_queued_start(&self, work_id: Option<&WorkId>, outcome: std::result::Result<(), String>) {
+        let (Some(work), Some(id)) = (&self.work, work_id) else {
+            return;
+        };
+        let result = work.start(id).and_then(|_| match outcome {
+            Ok(()) => work.complete(id),
+            Err(reason) => work.fail(id, reason),
+        });
+        if let Err(e) = result {
+            warn!(work_id = %id, error = %e, "failed to update queued start");
+        }
+    }
+
+    /// Start queued instances that fit in the free slots, first in line first.
+    async fn start_queued(&self) {
+        let mut queue = self.admission.lock().await;
+        let mut index = 0;
+        while index < queue.len() {
+            let running = self.running_workspaces().await;
+            if !self.limits().has_slot(&running, queue[index].project.workspace.as_deref()) {
+                index += 1;
+                continue;
+            }
+            let Some(next) = queue.remove(index) else {
+                break;
+            };
+            debug!(project_id = %next.project.id, queued_at = %next.queued_at, "slot freed, starting queued instance");
+            match self.launch(&next.project, Arc::clone(&next.adapter)).await {
+                Ok(()) => self.finish_queued_start(next.work_id.as_ref(), Ok(())),
+                Err(e) => {
+                    warn!(project_id = %next.project.id, error = %e, "failed to start queued instance");
+                    self.finish_queued_start(next.work_id.as_ref(), Err(e.to_string()));
+                    self.emit_event(RuntimeEvent::Error {
+                        project_id: next.project.id.clone(),
+                        error: format!("queued start failed: {}", e),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Drop every queued start, failing its work item with `reason`.
+    pub async fn cancel_queued(&self, reason: &str) {
+        let cancelled: Vec<QueuedStart> = self.admission.lock().await.drain(..).collect();
+        for start in &cancelled {
+            self.finish_queued_start(start.work_id.as_ref(), Err(reason.to_string()));
+        }
+        if !cancelled.is_empty() {
+            self.publish_slots().await;
+        }
+    }
+
+    /// Current concurrency limits.
+    pub fn limits(&self) -> ConcurrencyLimits {
+        self.limits.lock().map(|l| l.clone()).unwrap_or_default()
+    }
+
+    /// Replace the concurrency limits, starting queued instances that now fit.
+    pub async fn set_concurrency_limits(&self, limits: ConcurrencyLimits) {
+        if let Ok(mut current) = self.limits.lock() {
+            *current = limits;
+        }
+        self.start_queued().await;
+        self.publish_slots().await;
+    }
+
+    /// Workspaces of the running instances, one entry per instance.
+    async fn running_workspaces(&self) -> Vec<Option<String>> {
+        let instances = self.instances.read().await;
+        instances.values().map(|i| i.workspace.clone()).collect()
+    }
+
+    /// Slots in use against the concurrency limits, and the starts waiting.
+    pub async fn slot_usage(&self) -> SlotUsage {
+        let running = self.running_workspaces().await;
+        let queued = self
+            .admission
+            .lock()
+            .await
+            .iter()
+            .map(|q| QueuedSlot {
+                project_id: q.project.id.clone(),
+                project: q.project.name.clone(),
+                workspace: q.project.workspace.clone(),
+                queued_at: q.queued_at,
+            })
+            .collect();
+        SlotUsage::new(&self.limits(), &running, queued)
+    }
+
+    /// Write the slot usage to the state directory for `commander status --runtime`.
+    async fn publish_slots(&self) {
+        let Some(dir) = &self.config.state_dir else {
+            return;
+        };
+        if let Err(e) = self.slot_usage().await.save(dir) {
+            warn!(error = %e, "failed to write slot usage");
+        }
+    }
+
+    /// Open auxiliary panes in `session` and start their commands.
+    ///
+    /// Panes that fail to open are logged and skipped.
+    fn open_panes(&self, session: &str, project_path: &str, specs: &[PaneSpec]) -> Vec<AuxPane> {
+        let mut panes = Vec::with_capacity(specs.len());
+        for spec in specs {
+            let opened = self
+                .tmux
+                .create_background_pane(session, Some(project_path))
+                .and_then(|pane| {
+                    self.tmux.send_line(session, Some(&pane.id), &spec.command)?;
+                    Ok(pane)
+                });
+            match opened {
+                Ok(pane) => {
+                    debug!(session = %session, pane = %spec.name, pane_id = %pane.id, "opened auxiliary pane");
+                    panes.push(AuxPane {
+                        name: spec.name.clone(),
+                        command: spec.command.clone(),
+                        pane_id: pane.id,
+                        last_output: None,
+                    });
+                }
+                Err(e) => warn!(session = %session, pane = %spec.name, error = %e, "failed to open auxiliary pane"),
+            }
+        }
+        panes
+    }
+
+    /// Names of an instance's auxiliary panes.
+    pub async fn pane_names(&self, project_id: &ProjectId) -> Vec<String> {
+        let instances = self.instances.read().await;
+        instances
+            .get(project_id.as_str())
+            .map(|i| i.panes.iter().map(|p| p.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Capture output from one of an instance's auxiliary panes by name.
+    pub async fn capture_pane_output(&self, project_id: &ProjectId, pane: &str) -> Result<String> {
+        let instances = self.instances.read().await;
+        let instance = instances
+            .get(project_id.as_str())
+            .ok_or_else(|| RuntimeError::InstanceNotFound(project_id.as_str().to_string()))?;
+        let aux = instance
+            .panes
+            .iter()
+            .find(|p| p.name == pane)
+            .ok_or_else(|| RuntimeError::PaneNotFound(pane.to_string()))?;
+
+        Ok(self.tmux.capture_output(&instance.session_name, Some(&aux.pane_id), Some(50))?)
+    }
+
+    /// Start a file watcher that emits `FilesChanged` events for a project.
+    ///
+    /// Watcher failures are logged and do not prevent the instance from running.
+    fn watch_project(&self, project: &Project) -> Option<ProjectWatcher> {
+        let event_tx = self.event_tx.clone();
+        let project_id = project.id.clone();
+
+        let result = ProjectWatcher::start(&project.path, self.config.file_debounce, move |paths| {
+            debug!(project_id = %project_id, count = paths.len(), "files changed");
+            let _ = event_tx.send(RuntimeEvent::FilesChanged {
+                project_id: project_id.clone(),
+                paths,
+            });
+        });
+
+        match result {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                warn!(project_id = %project.id, error = %e, "failed to watch project directory");
+                None
+            }
+        }
+    }
+
     /// Stop an instance.
     pub async fn stop(&self, project_id: &ProjectId, force: bool) -> Result<()> {
         let project_id_str = project_id.as_str().to_string();
@@ -204,7 +521,7 @@ impl RuntimeExecutor {
 
         let instance = match instance {
             Some(i) => i,
-            None => return Err(RuntimeError::InstanceNotFound(project_id_str)),
+            None => return self.cancel_queued_start(project_id).await,
         };
 
         debug!(
@@ -221,6 +538,10 @@ impl RuntimeExecutor {
             tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         }
 
+        if let Ok(mut watchdog) = self.watchdog.lock() {
+            watchdog.forget(&instance.session_name);
+        }
+
         // Destroy the tmux session
         if self.tmux.session_exists(&instance.session_name) {
             self.tmux.destroy_session(&instance.session_name)?;
@@ -237,6 +558,27 @@ impl RuntimeExecutor {
             project_id: project_id.clone(),
         });
 
+        self.start_queued().await;
+        self.publish_slots().await;
+
+        Ok(())
+    }
+
+    /// Remove a project's start from the admission queue.
+    async fn cancel_queued_start(&self, project_id: &ProjectId) -> Result<()> {
+        let cancelled = {
+            let mut queue = self.admission.lock().await;
+            let index = queue
+                .iter()
+                .position(|q| &q.project.id == project_id)
+                .ok_or_else(|| RuntimeError::InstanceNotFound(project_id.as_str().to_string()))?;
+            queue.remove(index)
+        };
+        if let Some(start) = cancelled {
+            info!(project_id = %project_id, "queued start cancelled");
+            self.finish_queued_start(start.work_id.as_ref(), Err("cancelled".to_string()));
+        }
+        self.publish_slots().await;
         Ok(())
     }
 
@@ -328,6 +670,104 @@ impl RuntimeExecutor {
     pub async fn instance_count(&self) -> usize {
         self.instances.read().await.len()
     }
+
+    /// Send a prompt to an instance, remembering it for replay after a crash.
+    pub async fn send_prompt(&self, project_id: &ProjectId, prompt: &str) -> Result<()> {
+        let mut instances = self.instances.write().await;
+        let instance = instances
+            .get_mut(project_id.as_str())
+            .ok_or_else(|| RuntimeError::InstanceNotFound(project_id.as_str().to_string()))?;
+
+        self.tmux.send_line(&instance.session_name, None, prompt)?;
+        instance.last_prompt = Some(prompt.to_string());
+        instance.prompts_sent += 1;
+        Ok(())
+    }
+
+    /// Relaunch a crashed instance's adapter in a fresh tmux session.
+    ///
+    /// When `replay_prompt` is set, the last prompt is queued and resent by the
+    /// poller once the adapter reports idle. Returns the restart count.
+    pub async fn restart(&self, project_id: &ProjectId, replay_prompt: bool) -> Result<u32> {
+        let (session_name, attempt, replay) = {
+            let mut instances = self.instances.write().await;
+            let instance = instances
+                .get_mut(project_id.as_str())
+                .ok_or_else(|| RuntimeError::InstanceNotFound(project_id.as_str().to_string()))?;
+
+            if !self.tmux.session_exists(&instance.session_name) {
+                let env = match instance.project_path.as_deref().map(|path| project_env(std::path::Path::new(path))) {
+                    Some(Ok(env)) => env.vars,
+                    Some(Err(e)) => {
+                        warn!(project_id = %project_id, error = %e, "ignoring invalid project config");
+                        Vec::new()
+                    }
+                    None => Vec::new(),
+                };
+                self.tmux.create_session_with_env(&instance.session_name, None, &env)?;
+                if let Some(path) = instance.project_path.clone() {
+                    let specs: Vec<PaneSpec> = instance
+                        .panes
+                        .iter()
+                        .map(|p| PaneSpec {
+                            name: p.name.clone(),
+                            command: p.command.clone(),
+                        })
+                        .collect();
+                    instance.panes = self.open_panes(&instance.session_name, &path, &specs);
+                }
+            }
+            self.tmux
+                .send_line(&instance.session_name, None, &instance.launch_command)?;
+
+            instance.restarts += 1;
+            instance.last_output = None;
+            instance.pending_replay = if replay_prompt {
+                instance.last_prompt.clone()
+            } else {
+                None
+            };
+            (
+                instance.session_name.clone(),
+                instance.restarts,
+                instance.pending_replay.is_some(),
+            )
+        };
+
+        info!(
+            project_id = %project_id,
+            session = %session_name,
+            attempt = attempt,
+            "instance restarted"
+        );
+
+        self.update_state(project_id, ProjectState::Working).await;
+        self.emit_event(RuntimeEvent::InstanceRestarted {
+            project_id: project_id.clone(),
+            session: session_name,
+            attempt,
+            replay_prompt: replay,
+        });
+
+        Ok(attempt)
+    }
+
+    /// Resend the prompt queued by `restart`, if any.
+    ///
+    /// Returns true if a prompt was replayed.
+    pub(crate) async fn replay_pending_prompt(&self, project_id: &ProjectId) -> Result<bool> {
+        let mut instances = self.instances.write().await;
+        let Some(instance) = instances.get_mut(project_id.as_str()) else {
+            return Ok(false);
+        };
+        let Some(prompt) = instance.pending_replay.take() else {
+            return Ok(false);
+        };
+
+        self.tmux.send_line(&instance.session_name, None, &prompt)?;
+        debug!(project_id = %project_id, "replayed last prompt after restart");
+        Ok(true)
+    }
 }
 
 #[cfg(test)]
@@ -399,6 +839,10 @@ mod tests {
         assert_eq!(instance.session_name, "test-session");
         assert_eq!(instance.state, ProjectState::Idle);
         assert!(instance.last_output.is_none());
+        assert!(instance.watcher.is_none());
+        assert!(instance.last_prompt.is_none());
+        assert_eq!(instance.restarts, 0);
+        assert!(instance.panes.is_empty());
     }
 
     #[tokio::test]
@@ -551,6 +995,50 @@ mod tests {
         assert_eq!(executor.config().poll_interval, std::time::Duration::from_millis(100));
     }
 
+    #[tokio::test]
+    async fn test_executor_queues_start_without_free_slot() {
+        // Skip if tmux not available
+        if !commander_tmux::TmuxOrchestrator::is_available() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = RuntimeConfig::new()
+            .with_concurrency_limits(ConcurrencyLimits {
+                global: Some(0),
+                workspaces: HashMap::new(),
+            })
+            .with_state_dir(dir.path());
+        let executor = RuntimeExecutor::new(config).unwrap();
+        let mut rx = executor.subscribe();
+        let project = Project::new("/tmp", "queued-start-test");
+
+        let admission = executor.start(&project, Arc::new(MockAdapter::new())).await.unwrap();
+        let Admission::Queued { position, work_id } = admission else {
+            panic!("start should be queued");
+        };
+        assert_eq!(position, 1);
+        let event = rx.recv().await.unwrap();
+        assert!(matches!(event, RuntimeEvent::StartQueued { position: 1, .. }));
+
+        // Starting again keeps the same place
+        let again = executor.start(&project, Arc::new(MockAdapter::new())).await.unwrap();
+        assert_eq!(again, Admission::Queued { position: 1, work_id: work_id.clone() });
+
+        let usage = SlotUsage::load(dir.path()).unwrap();
+        assert_eq!(usage.running, 0);
+        assert_eq!(usage.limit, Some(0));
+        assert_eq!(usage.queued.len(), 1);
+
+        // Stopping a queued project cancels its start
+        executor.stop(&project.id, false).await.unwrap();
+        assert!(executor.slot_usage().await.queued.is_empty());
+        assert!(!executor.has_instance(&project.id).await);
+        let store = WorkStore::new(dir.path());
+        let item = store.load_work(&project.id, &work_id.unwrap()).unwrap();
+        assert_eq!(item.state, commander_models::WorkState::Failed);
+    }
+
     #[tokio::test]
     async fn test_broadcast_multiple_subscribers() {
         // Skip if tmux not available
@@ -0,0 +1,89 @@
+//! Session health checks and restart policies.
+//!
+//! The poller periodically verifies that each instance's tmux session still
+//! exists. A session that disappears outside Commander (killed, crashed,
+//! closed by the user) marks the project `ProjectState::Crashed`, and the
+//! project's `RestartPolicy` decides whether the adapter is relaunched.
+
+use serde::{Deserialize, Serialize};
+
+/// What to do when a project's tmux session disappears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum RestartPolicy {
+    /// Leave the project crashed until it is restarted manually.
+    #[default]
+    Never,
+    /// Relaunch the adapter in a new session.
+    OnCrash {
+        /// Maximum automatic restarts over the instance's lifetime.
+        max_restarts: u32,
+        /// Resend the last prompt once the relaunched adapter is idle.
+        #[serde(default = "default_replay_prompt")]
+        replay_prompt: bool,
+    },
+}
+
+fn default_replay_prompt() -> bool {
+    true
+}
+
+impl RestartPolicy {
+    /// Restart on crash up to `max_restarts` times, replaying the last prompt.
+    pub fn on_crash(max_restarts: u32) -> Self {
+        RestartPolicy::OnCrash {
+            max_restarts,
+            replay_prompt: true,
+        }
+    }
+
+    /// Whether another restart is allowed after `restarts` previous ones.
+    pub fn allows_restart(&self, restarts: u32) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnCrash { max_restarts, .. } => restarts < *max_restarts,
+        }
+    }
+
+    /// Whether the last prompt is replayed after a restart.
+    pub fn replays_prompt(&self) -> bool {
+        matches!(self, RestartPolicy::OnCrash { replay_prompt: true, .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_never_restarts() {
+        let policy = RestartPolicy::default();
+        assert_eq!(policy, RestartPolicy::Never);
+        assert!(!policy.allows_restart(0));
+        assert!(!policy.replays_prompt());
+    }
+
+    #[test]
+    fn test_on_crash_limits_restarts() {
+        let policy = RestartPolicy::on_crash(2);
+        assert!(policy.allows_restart(0));
+        assert!(policy.allows_restart(1));
+        assert!(!policy.allows_restart(2));
+        assert!(policy.replays_prompt());
+    }
+
+    #[test]
+    fn test_policy_deserialization() {
+        let policy: RestartPolicy =
+            serde_yaml::from_str("mode: on_crash\nmax_restarts: 3\n").unwrap();
+        assert_eq!(policy, RestartPolicy::on_crash(3));
+
+        let policy: RestartPolicy =
+            serde_yaml::from_str("mode: on_crash\nmax_restarts: 1\nreplay_prompt: false\n").unwrap();
+        assert!(policy.allows_restart(0));
+        assert!(!policy.replays_prompt());
+
+        let policy: RestartPolicy = serde_yaml::from_str("mode: never\n").unwrap();
+        assert_eq!(policy, RestartPolicy::Never);
+    }
+}
@@ -3,7 +3,12 @@
 //! This crate provides the async execution infrastructure for Commander:
 //! - `RuntimeExecutor` - spawns and manages AI tool instances
 //! - `OutputPoller` - polls tmux output for changes
+//! - `PollSchedule` - per-session adaptive poll intervals and output coalescing
 //! - `Runtime` - main entry point combining executor and poller
+//! - `ProjectWatcher` - reports debounced file changes in project directories
+//! - `Watchdog` - raises escalating alerts for stuck sessions and error loops
+//! - `RestartPolicy` - decides whether crashed sessions are relaunched
+//! - `ConcurrencyLimits` - global and per-workspace caps on running instances
 //!
 //! # Example
 //!
@@ -27,7 +32,8 @@
 //!     // Start an instance
 //!     let project = Project::new("/path/to/project", "my-project");
 //!     let adapter = Arc::new(ClaudeCodeAdapter::new());
-//!     runtime.executor().start(&project, adapter).await?;
+//!     let admission = runtime.executor().start(&project, adapter).await?;
+//!     println!("{:?}", admission);
 //!
 //!     // Handle events
 //!     tokio::spawn(async move {
@@ -63,23 +69,122 @@
 //! - Updates instance state
 //! - Emits events
 //!
+//! Sessions are not polled on a global tick. A `PollSchedule` gives each one
+//! its own `SmartPoller` interval, from `RuntimeConfig::poll_interval` while
+//! output keeps changing up to `max_poll_interval` once it goes quiet; sending
+//! a prompt resets it. Due sessions are captured on blocking threads, at most
+//! `capture_workers` at a time, and a session emits at most one
+//! `RuntimeEvent::OutputReceived` per `output_coalesce` window, carrying its
+//! latest screen.
+//!
+//! ## Control mode
+//!
+//! With `RuntimeConfig::with_control_mode`, the poller attaches a read-only
+//! tmux control mode client (`commander_tmux::ControlClient`) to each session.
+//! Its output notifications make the session due at once, so bursts of output
+//! are captured as they happen, and quiet sessions are only captured once per
+//! `max_poll_interval`. Where control mode is unavailable, or a client exits,
+//! the session falls back to polling.
+//!
 //! ## Runtime
 //!
 //! The main entry point that combines the executor and poller:
 //! - Creates and manages the executor
 //! - Spawns the poller task
 //! - Handles graceful shutdown
+//!
+//! ## ProjectWatcher
+//!
+//! Each started instance gets a recursive filesystem watcher on its project
+//! directory (unless disabled via `RuntimeConfig::with_watch_files`). Changes
+//! are debounced and emitted as `RuntimeEvent::FilesChanged`, with build and
+//! VCS directories (`.git`, `target`, `node_modules`, ...) filtered out.
+//!
+//! ## Watchdog
+//!
+//! The poller feeds every instance's output to a `Watchdog`. When a busy
+//! session stops producing meaningful output, or keeps hitting the same error,
+//! for longer than its thresholds (configurable per project via
+//! `RuntimeConfig::with_watchdog`), a `RuntimeEvent::WatchdogAlert` is emitted.
+//! Alerts escalate from banner to push notification to an optional webhook POST.
+//!
+//! ## Health checks
+//!
+//! Every `RuntimeConfig::health_check_interval` the poller checks that each
+//! instance's tmux session still exists. A missing session marks the project
+//! `ProjectState::Crashed` and emits `RuntimeEvent::SessionCrashed`. If the
+//! project's `RestartPolicy` allows it, the adapter is relaunched
+//! (`RuntimeEvent::InstanceRestarted`) and the last prompt sent through
+//! `RuntimeExecutor::send_prompt` is replayed once the adapter is idle again.
+//!
+//! ## Auxiliary panes
+//!
+//! A project's `.commander.toml` can declare named panes (a dev server, a
+//! test runner) that are opened next to the AI pane when the instance starts.
+//! The poller captures each one separately and emits
+//! `RuntimeEvent::PaneOutput` with the pane's name, so output can be traced
+//! back to the pane it came from.
+//!
+//! ## Environment
+//!
+//! Variables under `[env]` in `.commander.toml` are set on the instance's
+//! tmux session when it is created, with `{ secret = "NAME" }` values read
+//! from the secret store.
+//!
+//! ## Webhooks
+//!
+//! `WebhookDispatcher` POSTs runtime events and change notifications to the
+//! `[[webhooks]]` of `config.toml` that match them, signed with HMAC-SHA256
+//! when a secret is configured and retried on transient failures.
+//!
+//! ## Admission control
+//!
+//! `RuntimeConfig::with_concurrency_limits` caps how many instances run at
+//! once, overall and per workspace. `RuntimeExecutor::start` queues a start
+//! beyond a limit instead of refusing it: it returns `Admission::Queued`,
+//! emits `RuntimeEvent::StartQueued` and, with `RuntimeConfig::with_state_dir`,
+//! records the start as a work item of the project. Queued starts run in order
+//! as stops free slots. The executor keeps a `SlotUsage` snapshot in the state
+//! directory for `commander status --runtime`.
+//!
+//! ## Config reload
+//!
+//! With `RuntimeConfig::with_reload_config`, the poller subscribes to
+//! `config.toml` changes and applies `[runtime] poll_interval_ms`,
+//! `health_check_interval_secs` and the session limits without a restart.
+//! Removing a key restores the value from `RuntimeConfig`.
 
+pub mod admission;
 pub mod config;
+pub mod env;
 pub mod error;
 pub mod event;
 pub mod executor;
+pub mod health;
+pub mod panes;
 pub mod poller;
 pub mod runtime;
+pub mod schedule;
+pub mod watchdog;
+pub mod watcher;
+pub mod webhooks;
 
+pub use admission::{
+    Admission, ConcurrencyLimits, QueuedSlot, SlotUsage, WorkspaceSlots, SLOTS_FILE,
+};
 pub use config::RuntimeConfig;
+pub use env::{project_env, resolve_env, EnvValue, ResolvedEnv};
 pub use error::{Result, RuntimeError};
 pub use event::RuntimeEvent;
 pub use executor::{RunningInstance, RuntimeExecutor};
+pub use health::RestartPolicy;
+pub use panes::{AuxPane, PaneSpec, ProjectConfig, MAIN_PANE, PROJECT_CONFIG_FILE};
 pub use poller::OutputPoller;
 pub use runtime::Runtime;
+pub use schedule::PollSchedule;
+pub use watchdog::{
+    AlertKind, AlertLevel, Watchdog, WatchdogAlert, WatchdogConfig, WatchdogThresholds,
+    WATCHDOG_FILE,
+};
+pub use watcher::{ChangeBatcher, ProjectWatcher};
+pub use webhooks::WebhookDispatcher;
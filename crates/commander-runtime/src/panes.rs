@@ -0,0 +1,160 @@
+//! Auxiliary panes declared in a project's `.commander.toml`.
+//!
+//! Besides the AI tool's pane, a project can ask for named panes running
+//! long-lived commands such as a dev server or a test watcher:
+//!
+//! ```toml
+//! [[panes]]
+//! name = "server"
+//! command = "npm run dev"
+//!
+//! [[panes]]
+//! name = "tests"
+//! command = "cargo watch -x test"
+//! ```
+//!
+//! The executor opens these panes next to the AI pane when an instance
+//! starts, and the poller captures each one separately, emitting
+//! `RuntimeEvent::PaneOutput` tagged with the pane's name.
+//!
+//! The same file declares environment variables for the session under
+//! `[env]` (see [`crate::env`]).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::env::{is_valid_name, EnvValue};
+use crate::error::{Result, RuntimeError};
+
+/// Project configuration file, at the project root.
+pub const PROJECT_CONFIG_FILE: &str = ".commander.toml";
+
+/// Name reserved for the pane running the AI tool.
+pub const MAIN_PANE: &str = "main";
+
+/// An auxiliary pane declared in `.commander.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneSpec {
+    /// Name used to refer to the pane (e.g. `server`).
+    pub name: String,
+    /// Command started in the pane, from the project directory.
+    pub command: String,
+}
+
+/// Contents of a project's `.commander.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Auxiliary panes opened next to the AI pane.
+    #[serde(default)]
+    pub panes: Vec<PaneSpec>,
+    /// Environment variables set on the session.
+    #[serde(default)]
+    pub env: BTreeMap<String, EnvValue>,
+}
+
+impl ProjectConfig {
+    /// Load the configuration of the project at `project_path`.
+    ///
+    /// A missing file yields the default (no auxiliary panes).
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let path = project_path.join(PROJECT_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| RuntimeError::ProjectConfig(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&content)
+            .map_err(|e| RuntimeError::ProjectConfig(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parse and validate configuration text.
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self =
+            toml::from_str(content).map_err(|e| RuntimeError::ProjectConfig(e.to_string()))?;
+
+        for (i, pane) in config.panes.iter().enumerate() {
+            if pane.name.trim().is_empty() || pane.command.trim().is_empty() {
+                return Err(RuntimeError::ProjectConfig(format!(
+                    "pane {} needs a name and a command",
+                    i + 1
+                )));
+            }
+            if pane.name == MAIN_PANE {
+                return Err(RuntimeError::ProjectConfig(format!(
+                    "pane name '{}' is reserved for the AI pane",
+                    MAIN_PANE
+                )));
+            }
+            if config.panes[..i].iter().any(|p| p.name == pane.name) {
+                return Err(RuntimeError::ProjectConfig(format!(
+                    "duplicate pane name '{}'",
+                    pane.name
+                )));
+            }
+        }
+
+        for (name, value) in &config.env {
+            if !is_valid_name(name) {
+                return Err(RuntimeError::ProjectConfig(format!(
+                    "invalid environment variable name '{}'",
+                    name
+                )));
+            }
+            if matches!(value, EnvValue::Secret { secret } if secret.trim().is_empty()) {
+                return Err(RuntimeError::ProjectConfig(format!("env {}: empty secret name", name)));
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// An auxiliary pane opened for a running instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuxPane {
+    /// Name from `.commander.toml`.
+    pub name: String,
+    /// Command running in the pane.
+    pub command: String,
+    /// Tmux pane ID (e.g. `%3`).
+    pub pane_id: String,
+    /// Last captured output.
+    pub last_output: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_panes() {
+        let dir = tempdir().unwrap();
+        assert!(ProjectConfig::load(dir.path()).unwrap().panes.is_empty());
+
+        fs::write(
+            dir.path().join(PROJECT_CONFIG_FILE),
+            "[[panes]]\nname = \"server\"\ncommand = \"npm run dev\"\n\n[[panes]]\nname = \"tests\"\ncommand = \"npm test -- --watch\"\n",
+        )
+        .unwrap();
+        let config = ProjectConfig::load(dir.path()).unwrap();
+        assert_eq!(config.panes.len(), 2);
+        assert_eq!(config.panes[0].name, "server");
+        assert_eq!(config.panes[1].command, "npm test -- --watch");
+    }
+
+    #[test]
+    fn test_rejects_invalid_panes() {
+        let reserved = "[[panes]]\nname = \"main\"\ncommand = \"ls\"\n";
+        assert!(ProjectConfig::parse(reserved).is_err());
+
+        let duplicate = "[[panes]]\nname = \"a\"\ncommand = \"ls\"\n[[panes]]\nname = \"a\"\ncommand = \"pwd\"\n";
+        assert!(ProjectConfig::parse(duplicate).is_err());
+
+        let empty = "[[panes]]\nname = \"a\"\ncommand = \"\"\n";
+        assert!(ProjectConfig::parse(empty).is_err());
+    }
+}
@@ -1,16 +1,29 @@
 //! Output poller for monitoring tmux sessions.
 
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use tokio::sync::watch;
-use tokio::time::interval;
-use tracing::{debug, trace, warn};
+use tokio::sync::{mpsc, watch, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::{interval, Interval};
+use tracing::{debug, info, trace, warn};
 
 use commander_adapters::RuntimeState;
+use commander_core::{ConfigChange, Recorder};
 use commander_models::{ProjectId, ProjectState};
+use commander_tmux::{ControlClient, ControlEvent};
 
+use crate::admission::ConcurrencyLimits;
 use crate::event::RuntimeEvent;
-use crate::executor::RuntimeExecutor;
+use crate::executor::{RunningInstance, RuntimeExecutor};
+use crate::schedule::PollSchedule;
+use crate::watchdog::{self, AlertLevel, WatchdogAlert};
+
+/// Lines of scrollback captured per pane.
+const CAPTURE_LINES: u32 = 50;
 
 /// Polls tmux sessions for output changes.
 pub struct OutputPoller {
@@ -18,29 +31,132 @@ pub struct OutputPoller {
     executor: Arc<RuntimeExecutor>,
     /// Shutdown signal receiver.
     shutdown: watch::Receiver<bool>,
+    /// HTTP client for watchdog webhooks.
+    http: reqwest::Client,
+    /// Active recordings keyed by session name (when `record_sessions` is on).
+    recorders: Mutex<HashMap<String, Recorder>>,
+    /// `config.toml` changes to apply (see `with_config_changes`).
+    config_changes: Option<Mutex<Receiver<ConfigChange>>>,
+    /// Per-session poll intervals and output coalescing.
+    schedule: Mutex<PollSchedule>,
+    /// Control mode clients by session, with their ids (when `control_mode` is on).
+    control: Mutex<HashMap<String, (u64, ControlClient)>>,
+    /// Sessions whose control mode client failed; they are polled instead.
+    polling_only: Mutex<HashSet<String>>,
+    /// Id of the next control mode client.
+    next_client_id: AtomicU64,
+    /// Notifications from control mode clients, drained by `run`.
+    notices_tx: mpsc::UnboundedSender<ControlNotice>,
+    notices_rx: Option<mpsc::UnboundedReceiver<ControlNotice>>,
+}
+
+/// A control mode notification for the poll loop.
+struct ControlNotice {
+    session: String,
+    client_id: u64,
+    kind: NoticeKind,
+}
+
+enum NoticeKind {
+    Connected,
+    Output,
+    Exit(Option<String>),
 }
 
 impl OutputPoller {
     /// Creates a new output poller.
     pub fn new(executor: Arc<RuntimeExecutor>, shutdown: watch::Receiver<bool>) -> Self {
-        Self { executor, shutdown }
+        let config = executor.config();
+        let schedule = PollSchedule::new(
+            config.poll_interval,
+            config.max_poll_interval,
+            config.output_coalesce,
+        );
+        let (notices_tx, notices_rx) = mpsc::unbounded_channel();
+        Self {
+            schedule: Mutex::new(schedule),
+            executor,
+            shutdown,
+            http: reqwest::Client::new(),
+            recorders: Mutex::new(HashMap::new()),
+            config_changes: None,
+            control: Mutex::new(HashMap::new()),
+            polling_only: Mutex::new(HashSet::new()),
+            next_client_id: AtomicU64::new(0),
+            notices_tx,
+            notices_rx: Some(notices_rx),
+        }
+    }
+
+    /// Apply `[runtime]` settings received on `changes` while running.
+    pub fn with_config_changes(mut self, changes: Receiver<ConfigChange>) -> Self {
+        self.config_changes = Some(Mutex::new(changes));
+        self
     }
 
     /// Run the polling loop until shutdown signal.
+    ///
+    /// Instead of capturing every session on a global tick, the loop sleeps
+    /// until the next session is due (see `PollSchedule`), waking at least
+    /// once per poll interval to pick up newly started instances. With
+    /// control mode, output notifications wake it early.
     pub async fn run(&mut self) {
-        let poll_interval = self.executor.config().poll_interval;
-        let mut ticker = interval(poll_interval);
+        let config_changes = self
+            .config_changes
+            .take()
+            .and_then(|changes| changes.into_inner().ok());
+        let Some(mut notices) = self.notices_rx.take() else { return };
+        let mut poll_interval = self.executor.config().poll_interval;
+        let mut health_interval = self.executor.config().health_check_interval;
+        let mut health_ticker = interval(health_interval);
 
         debug!(
             poll_interval_ms = poll_interval.as_millis(),
+            capture_workers = self.executor.config().capture_workers,
+            control_mode = self.executor.config().control_mode,
             "starting output poller"
         );
 
         loop {
+            let wake = self.next_wake(poll_interval);
             tokio::select! {
-                _ = ticker.tick() => {
+                _ = tokio::time::sleep_until(wake.into()) => {
+                    let mut new_limits = None;
+                    for change in config_changes.iter().flat_map(|rx| rx.try_iter()) {
+                        let ConfigChange::Runtime(settings) = change else { continue };
+                        let config = self.executor.config();
+                        let poll = settings.poll_interval().unwrap_or(config.poll_interval);
+                        let health = settings
+                            .health_check_interval()
+                            .unwrap_or(config.health_check_interval);
+                        if poll != poll_interval {
+                            info!(interval_ms = poll.as_millis(), "poll interval changed");
+                            poll_interval = poll;
+                            if let Ok(mut schedule) = self.schedule.lock() {
+                                schedule.set_base_interval(poll);
+                            }
+                        }
+                        reset_interval(&mut health_ticker, &mut health_interval, health, "health check");
+                        let limits = ConcurrencyLimits::from_settings(&settings).or(&config.concurrency);
+                        if limits != self.executor.limits() {
+                            new_limits = Some(limits);
+                        }
+                    }
+                    if let Some(limits) = new_limits {
+                        info!(max_sessions = ?limits.global, "concurrency limits changed");
+                        self.executor.set_concurrency_limits(limits).await;
+                    }
                     self.poll_all().await;
                 }
+                Some(notice) = notices.recv() => {
+                    self.handle_notice(notice);
+                    while let Ok(notice) = notices.try_recv() {
+                        self.handle_notice(notice);
+                    }
+                }
+                _ = health_ticker.tick() => {
+                    self.check_health().await;
+                }
                 _ = self.shutdown.changed() => {
                     if *self.shutdown.borrow() {
                         debug!("poller received shutdown signal");
@@ -50,35 +166,177 @@ impl OutputPoller {
             }
         }
 
+        if let Ok(mut control) = self.control.lock() {
+            control.clear();
+        }
         debug!("output poller stopped");
     }
 
-    /// Poll all instances for output changes.
+    /// Apply a control mode notification to the schedule.
+    fn handle_notice(&self, notice: ControlNotice) {
+        let Ok(mut schedule) = self.schedule.lock() else { return };
+        let Ok(mut control) = self.control.lock() else { return };
+        // Notices of a client that was since dropped are stale
+        if control.get(&notice.session).is_none_or(|(id, _)| *id != notice.client_id) {
+            return;
+        }
+        match notice.kind {
+            NoticeKind::Connected => {
+                debug!(session = %notice.session, "control mode client attached");
+                schedule.set_pushed(&notice.session, true);
+            }
+            NoticeKind::Output => schedule.mark_dirty(&notice.session, Instant::now()),
+            NoticeKind::Exit(reason) => {
+                info!(
+                    session = %notice.session,
+                    reason = reason.as_deref().unwrap_or("none"),
+                    "control mode client exited, polling session instead"
+                );
+                schedule.set_pushed(&notice.session, false);
+                schedule.mark_dirty(&notice.session, Instant::now());
+                control.remove(&notice.session);
+                if let Ok(mut polling_only) = self.polling_only.lock() {
+                    polling_only.insert(notice.session);
+                }
+            }
+        }
+    }
+
+    /// Attach control mode clients to `live` sessions that have none, and
+    /// drop the clients of sessions that stopped. Sessions whose client
+    /// failed before stay polled.
+    fn sync_control_clients(&self, live: &HashSet<&str>) {
+        if !self.executor.config().control_mode {
+            return;
+        }
+        let (Ok(mut control), Ok(mut polling_only)) = (self.control.lock(), self.polling_only.lock()) else {
+            return;
+        };
+        control.retain(|session, _| live.contains(session.as_str()));
+        polling_only.retain(|session| live.contains(session.as_str()));
+
+        for session in live {
+            if control.contains_key(*session) || polling_only.contains(*session) {
+                continue;
+            }
+            let client_id = self.next_client_id.fetch_add(1, Ordering::Relaxed);
+            let notices = self.notices_tx.clone();
+            let name = session.to_string();
+            let result = self.executor.tmux().control_client(session, move |event| {
+                let kind = match event {
+                    ControlEvent::Connected => NoticeKind::Connected,
+                    ControlEvent::Output { .. } => NoticeKind::Output,
+                    ControlEvent::Exit(reason) => NoticeKind::Exit(reason),
+                };
+                let _ = notices.send(ControlNotice { session: name.clone(), client_id, kind });
+            });
+            match result {
+                Ok(client) => {
+                    control.insert(session.to_string(), (client_id, client));
+                }
+                Err(e) => {
+                    warn!(session = %session, error = %e, "control mode unavailable, polling session instead");
+                    polling_only.insert(session.to_string());
+                }
+            }
+        }
+    }
+
+    /// When the next poll should run: the earliest scheduled capture or
+    /// flush, but no later than one poll interval from now.
+    fn next_wake(&self, poll_interval: Duration) -> Instant {
+        let latest = Instant::now() + poll_interval;
+        self.schedule
+            .lock()
+            .ok()
+            .and_then(|schedule| schedule.next_wake())
+            .map_or(latest, |due| due.min(latest))
+    }
+
+    /// Capture the sessions that are due and process their output.
     async fn poll_all(&self) {
+        let now = Instant::now();
+        let jobs = self.due_jobs(now).await;
+        let captures = self.capture_batch(jobs).await;
+        self.process_captures(captures, now).await;
+    }
+
+    /// Collect capture jobs for due sessions, forgetting stopped ones and
+    /// skipping paused ones.
+    async fn due_jobs(&self, now: Instant) -> Vec<CaptureJob> {
+        let paused = commander_core::pause::paused_session_names();
+        let instances = self.executor.instances();
+        let instances = instances.read().await;
+        let Ok(mut schedule) = self.schedule.lock() else { return Vec::new() };
+
+        // Crashed sessions are handled by the health check
+        let live: HashMap<&str, &RunningInstance> = instances
+            .values()
+            .filter(|i| i.state != ProjectState::Crashed)
+            .map(|i| (i.session_name.as_str(), i))
+            .collect();
+        schedule.retain(|session| live.contains_key(session));
+        self.sync_control_clients(&live.keys().copied().collect());
+
+        live.into_values()
+            .filter(|i| !paused.contains(&i.session_name))
+            .filter(|i| schedule.is_due(&i.session_name, i.prompts_sent, now))
+            .map(|i| CaptureJob {
+                project_id: i.project_id.as_str().to_string(),
+                session: i.session_name.clone(),
+                panes: i.panes.iter().map(|p| (p.name.clone(), p.pane_id.clone())).collect(),
+            })
+            .collect()
+    }
+
+    /// Run `jobs` on blocking threads, at most `capture_workers` at a time.
+    async fn capture_batch(&self, jobs: Vec<CaptureJob>) -> Vec<Capture> {
+        let workers = Arc::new(Semaphore::new(self.executor.config().capture_workers.max(1)));
+        let mut tasks = JoinSet::new();
+        for job in jobs {
+            let Ok(permit) = Arc::clone(&workers).acquire_owned().await else { break };
+            let executor = Arc::clone(&self.executor);
+            tasks.spawn_blocking(move || {
+                let _permit = permit;
+                capture(&executor, job)
+            });
+        }
+
+        let mut captures = Vec::new();
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok(capture) => captures.push(capture),
+                Err(e) => warn!(error = %e, "capture task failed"),
+            }
+        }
+        captures
+    }
+
+    /// Analyze captured output, emitting events and updating instance state.
+    async fn process_captures(&self, captures: Vec<Capture>, now: Instant) {
         // Collect state changes to process after releasing the lock
         let mut state_changes: Vec<(ProjectId, ProjectState)> = Vec::new();
-
-        {
-            let instances = self.executor.instances();
+        let mut alerts: Vec<(ProjectId, WatchdogAlert)> = Vec::new();
+        let mut replays: Vec<ProjectId> = Vec::new();
+        let mut frames: Vec<(String, String)> = Vec::new();
+        let mut outputs: Vec<(String, String)> = Vec::new();
+        let mut pane_outputs: Vec<(String, String, String)> = Vec::new();
+        let mut emitted: Vec<(ProjectId, String)> = Vec::new();
+        let record = self.executor.config().record_sessions;
+
+        let instances = self.executor.instances();
+        let live_sessions: Vec<String> = {
             let instances = instances.read().await;
+            let Ok(mut schedule) = self.schedule.lock() else { return };
 
-            for (project_id_str, instance) in instances.iter() {
-                trace!(
-                    project_id = %project_id_str,
-                    session = %instance.session_name,
-                    "polling instance"
-                );
+            for capture in captures {
+                let Some(instance) = instances.get(&capture.project_id) else { continue };
 
-                // Capture current output
-                let output = match self.executor.tmux().capture_output(
-                    &instance.session_name,
-                    None,
-                    Some(50),
-                ) {
+                let output = match capture.output {
                     Ok(o) => o,
                     Err(e) => {
                         warn!(
-                            project_id = %project_id_str,
+                            project_id = %capture.project_id,
                             error = %e,
                             "failed to capture output"
                         );
@@ -92,18 +350,19 @@ impl OutputPoller {
                     None => true,
                 };
 
+                if let Some(output) = schedule.observe(&instance.session_name, &output, changed, now) {
+                    emitted.push((instance.project_id.clone(), output));
+                }
+
+                let mut errors = Vec::new();
+                let mut idle = instance.state == ProjectState::Idle;
+
                 if changed {
                     trace!(
-                        project_id = %project_id_str,
+                        project_id = %capture.project_id,
                         "output changed"
                     );
 
-                    // Emit output received event
-                    self.executor.emit_event(RuntimeEvent::OutputReceived {
-                        project_id: instance.project_id.clone(),
-                        output: output.clone(),
-                    });
-
                     // Analyze output for state changes
                     let analysis = instance.adapter.analyze_output(&output);
                     let new_state = match analysis.state {
@@ -117,14 +376,228 @@ impl OutputPoller {
                     if new_state != instance.state {
                         state_changes.push((instance.project_id.clone(), new_state));
                     }
+                    if new_state == ProjectState::Idle && instance.pending_replay.is_some() {
+                        replays.push(instance.project_id.clone());
+                    }
+                    idle = new_state == ProjectState::Idle;
+                    errors = analysis.errors;
+
+                    if record {
+                        frames.push((instance.session_name.clone(), output.clone()));
+                    }
+                }
+
+                // Auxiliary panes are reported separately and don't drive state
+                for (name, output) in capture.panes {
+                    let Some(pane) = instance.panes.iter().find(|p| p.name == name) else { continue };
+                    if pane.last_output.as_ref() != Some(&output) {
+                        self.executor.emit_event(RuntimeEvent::PaneOutput {
+                            project_id: instance.project_id.clone(),
+                            pane: name.clone(),
+                            output: output.clone(),
+                        });
+                        pane_outputs.push((capture.project_id.clone(), name, output));
+                    }
+                }
+
+                if let Ok(mut watchdog) = self.executor.watchdog().lock() {
+                    let raised = watchdog.observe(&instance.session_name, &output, &errors, idle, now);
+                    alerts.extend(raised.into_iter().map(|a| (instance.project_id.clone(), a)));
+                }
+
+                if changed {
+                    outputs.push((capture.project_id, output));
                 }
             }
-        } // Release read lock here
+
+            // Output held back by the coalesce window, including sessions not captured this time
+            for (session, output) in schedule.flush(now) {
+                if let Some(instance) = instances.values().find(|i| i.session_name == session) {
+                    emitted.push((instance.project_id.clone(), output));
+                }
+            }
+
+            instances
+                .values()
+                .filter(|i| i.state != ProjectState::Crashed)
+                .map(|i| i.session_name.clone())
+                .collect()
+        }; // Release read lock here
+
+        for (project_id, output) in emitted {
+            self.executor
+                .emit_event(RuntimeEvent::OutputReceived { project_id, output });
+        }
+
+        if !outputs.is_empty() || !pane_outputs.is_empty() {
+            let mut instances = instances.write().await;
+            for (project_id, output) in outputs {
+                if let Some(instance) = instances.get_mut(&project_id) {
+                    instance.last_output = Some(output);
+                }
+            }
+            for (project_id, name, output) in pane_outputs {
+                let pane = instances
+                    .get_mut(&project_id)
+                    .and_then(|i| i.panes.iter_mut().find(|p| p.name == name));
+                if let Some(pane) = pane {
+                    pane.last_output = Some(output);
+                }
+            }
+        }
+
+        if record {
+            self.record_frames(frames, &live_sessions);
+        }
+
+        for (project_id, alert) in alerts {
+            warn!(project_id = %project_id, level = %alert.level, "{}", alert.message());
+            if alert.level == AlertLevel::Webhook {
+                let http = self.http.clone();
+                let webhook_alert = alert.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = watchdog::send_webhook(&http, &webhook_alert).await {
+                        warn!(error = %e, "failed to deliver watchdog webhook");
+                    }
+                });
+            }
+            self.executor
+                .emit_event(RuntimeEvent::WatchdogAlert { project_id, alert });
+        }
 
         // Process state changes outside the lock
         for (project_id, new_state) in state_changes {
             self.executor.update_state(&project_id, new_state).await;
         }
+
+        for project_id in replays {
+            if let Err(e) = self.executor.replay_pending_prompt(&project_id).await {
+                warn!(project_id = %project_id, error = %e, "failed to replay prompt");
+            }
+        }
+    }
+
+    /// Append changed screens to each session's recording, starting new
+    /// recordings as needed and closing those of stopped instances.
+    fn record_frames(&self, frames: Vec<(String, String)>, live_sessions: &[String]) {
+        let Ok(mut recorders) = self.recorders.lock() else { return };
+        recorders.retain(|session, _| live_sessions.contains(session));
+
+        for (session, screen) in frames {
+            if !recorders.contains_key(&session) {
+                match Recorder::start(&session) {
+                    Ok(recorder) => {
+                        debug!(session = %session, path = %recorder.path().display(), "recording session");
+                        recorders.insert(session.clone(), recorder);
+                    }
+                    Err(e) => {
+                        warn!(session = %session, error = %e, "failed to start recording");
+                        continue;
+                    }
+                }
+            }
+            if let Some(recorder) = recorders.get_mut(&session) {
+                if let Err(e) = recorder.record_screen(&screen) {
+                    warn!(session = %session, error = %e, "failed to write recording frame");
+                    recorders.remove(&session);
+                }
+            }
+        }
+    }
+
+    /// Detect instances whose tmux session disappeared and apply restart policies.
+    async fn check_health(&self) {
+        let crashed: Vec<(ProjectId, String, u32)> = {
+            let instances = self.executor.instances();
+            let instances = instances.read().await;
+            instances
+                .values()
+                .filter(|i| i.state != ProjectState::Crashed)
+                .filter(|i| !self.executor.tmux().session_exists(&i.session_name))
+                .map(|i| (i.project_id.clone(), i.session_name.clone(), i.restarts))
+                .collect()
+        };
+
+        for (project_id, session, restarts) in crashed {
+            warn!(project_id = %project_id, session = %session, "tmux session disappeared");
+
+            if let Ok(mut watchdog) = self.executor.watchdog().lock() {
+                watchdog.forget(&session);
+            }
+            self.executor
+                .update_state(&project_id, ProjectState::Crashed)
+                .await;
+            self.executor.emit_event(RuntimeEvent::SessionCrashed {
+                project_id: project_id.clone(),
+                session,
+            });
+
+            let policy = self.executor.config().restart_policy_for(&project_id);
+            if !policy.allows_restart(restarts) {
+                continue;
+            }
+            if let Err(e) = self
+                .executor
+                .restart(&project_id, policy.replays_prompt())
+                .await
+            {
+                warn!(project_id = %project_id, error = %e, "failed to restart crashed instance");
+                self.executor.emit_event(RuntimeEvent::Error {
+                    project_id,
+                    error: format!("restart failed: {}", e),
+                });
+            }
+        }
+    }
+}
+
+/// Restart `ticker` at `new` if it differs from `current`.
+fn reset_interval(ticker: &mut Interval, current: &mut Duration, new: Duration, name: &str) {
+    if new != *current {
+        info!(interval_ms = new.as_millis(), "{} interval changed", name);
+        *current = new;
+        *ticker = interval(new);
+    }
+}
+
+/// A session to capture, with its auxiliary panes.
+struct CaptureJob {
+    project_id: String,
+    session: String,
+    /// `(name, tmux pane id)` of each auxiliary pane.
+    panes: Vec<(String, String)>,
+}
+
+/// The result of a `CaptureJob`.
+struct Capture {
+    project_id: String,
+    output: commander_tmux::Result<String>,
+    /// `(name, output)` of each auxiliary pane that could be captured.
+    panes: Vec<(String, String)>,
+}
+
+/// Capture a session's main pane and auxiliary panes (blocking).
+fn capture(executor: &RuntimeExecutor, job: CaptureJob) -> Capture {
+    trace!(project_id = %job.project_id, session = %job.session, "polling instance");
+    let tmux = executor.tmux();
+    let output = tmux.capture_output(&job.session, None, Some(CAPTURE_LINES));
+    let panes = job
+        .panes
+        .into_iter()
+        .filter_map(|(name, pane_id)| {
+            match tmux.capture_output(&job.session, Some(&pane_id), Some(CAPTURE_LINES)) {
+                Ok(output) => Some((name, output)),
+                Err(e) => {
+                    trace!(pane = %name, error = %e, "failed to capture pane output");
+                    None
+                }
+            }
+        })
+        .collect();
+    Capture {
+        project_id: job.project_id,
+        output,
+        panes,
     }
 }
 
@@ -202,4 +675,36 @@ mod tests {
         let result = tokio::time::timeout(Duration::from_millis(100), handle).await;
         assert!(result.is_ok(), "poller should stop after shutdown signal");
     }
+
+    #[tokio::test]
+    async fn test_health_check_detects_missing_session() {
+        // Skip if tmux not available
+        if !commander_tmux::TmuxOrchestrator::is_available() {
+            return;
+        }
+
+        let config = RuntimeConfig::new().with_watch_files(false);
+        let executor = Arc::new(RuntimeExecutor::new(config).unwrap());
+        let project = commander_models::Project::new("/tmp", "health-check-test");
+        if executor.start(&project, Arc::new(MockAdapter)).await.is_err() {
+            return;
+        }
+
+        let mut rx = executor.subscribe();
+        executor.tmux().destroy_session("health-check-test").unwrap();
+
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+        let poller = OutputPoller::new(Arc::clone(&executor), shutdown_rx);
+        poller.check_health().await;
+
+        assert_eq!(executor.get_state(&project.id).await, Some(ProjectState::Crashed));
+        let mut crashed = false;
+        while let Ok(event) = rx.try_recv() {
+            crashed |= matches!(event, RuntimeEvent::SessionCrashed { .. });
+            assert!(!matches!(event, RuntimeEvent::InstanceRestarted { .. }));
+        }
+        assert!(crashed);
+
+        let _ = executor.stop(&project.id, true).await;
+    }
 }
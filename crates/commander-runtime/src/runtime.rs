@@ -66,7 +66,11 @@ impl Runtime {
         let shutdown_rx = self.shutdown_rx.clone();
 
         let handle = tokio::spawn(async move {
+            let reload_config = executor.config().reload_config;
             let mut poller = OutputPoller::new(executor, shutdown_rx);
+            if reload_config {
+                poller = poller.with_config_changes(commander_core::subscribe_config_changes());
+            }
             poller.run().await;
         });
 
@@ -99,6 +103,9 @@ impl Runtime {
             })?;
         }
 
+        // Drop queued starts so stopping instances does not launch them
+        self.executor.cancel_queued("runtime shut down").await;
+
         // Stop all instances
         let instances = self.executor.list_instances().await;
         for project_id in instances {
@@ -0,0 +1,304 @@
+//! Per-session poll scheduling for the output poller.
+//!
+//! Each session gets its own `SmartPoller`: sessions producing meaningful
+//! output are captured at the base poll interval, quiet ones back off towards
+//! the maximum interval. Changed output is coalesced so that a session emits
+//! at most one `OutputReceived` per coalesce window, always with its latest
+//! screen.
+//!
+//! Sessions watched by a tmux control mode client are "pushed": their output
+//! notifications mark them due (`mark_dirty`), so while quiet they are only
+//! captured once per maximum interval as a safety net.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use commander_core::{ChangeDetector, ChangeEvent, SmartPoller};
+
+/// Minimum time between captures of a pushed session, so a burst of output
+/// notifications results in a few captures rather than one per write.
+pub const PUSH_CAPTURE_GAP: Duration = Duration::from_millis(50);
+
+/// Scheduling state for one session.
+struct SessionSchedule {
+    /// Classifies changed output to drive the adaptive interval.
+    detector: ChangeDetector,
+    /// Adaptive interval for this session.
+    poller: SmartPoller,
+    /// When the session should be captured next.
+    next_due: Instant,
+    /// When output was last emitted.
+    last_emitted: Option<Instant>,
+    /// Latest changed output held back by the coalesce window.
+    pending: Option<String>,
+    /// Prompts sent as of the last capture (a new prompt polls at once).
+    prompts_sent: u64,
+    /// When the session was last captured.
+    last_captured: Option<Instant>,
+    /// Whether output notifications drive captures (see `set_pushed`).
+    pushed: bool,
+}
+
+/// Decides which sessions are captured on a poll and which output is emitted.
+pub struct PollSchedule {
+    base_interval: Duration,
+    max_interval: Duration,
+    coalesce: Duration,
+    sessions: HashMap<String, SessionSchedule>,
+}
+
+impl PollSchedule {
+    /// Creates a schedule polling between `base_interval` and `max_interval`,
+    /// emitting output at most once per `coalesce` window per session.
+    pub fn new(base_interval: Duration, max_interval: Duration, coalesce: Duration) -> Self {
+        Self {
+            base_interval,
+            max_interval: max_interval.max(base_interval),
+            coalesce,
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// The fastest interval any session is polled at.
+    pub fn base_interval(&self) -> Duration {
+        self.base_interval
+    }
+
+    /// Changes the base interval, restarting every session at the new rate.
+    pub fn set_base_interval(&mut self, base_interval: Duration) {
+        self.base_interval = base_interval;
+        self.max_interval = self.max_interval.max(base_interval);
+        for session in self.sessions.values_mut() {
+            session.poller = SmartPoller::new(self.base_interval, self.max_interval);
+        }
+    }
+
+    /// Whether `session` should be captured at `now`.
+    ///
+    /// Unknown sessions are registered and due at once, as is any session
+    /// whose `prompts_sent` count moved since its last capture.
+    pub fn is_due(&mut self, session: &str, prompts_sent: u64, now: Instant) -> bool {
+        let Some(schedule) = self.sessions.get_mut(session) else {
+            self.sessions.insert(
+                session.to_string(),
+                SessionSchedule {
+                    detector: ChangeDetector::new(),
+                    poller: SmartPoller::new(self.base_interval, self.max_interval),
+                    next_due: now,
+                    last_emitted: None,
+                    pending: None,
+                    prompts_sent,
+                    last_captured: None,
+                    pushed: false,
+                },
+            );
+            return true;
+        };
+        if schedule.prompts_sent != prompts_sent {
+            schedule.prompts_sent = prompts_sent;
+            schedule.poller.reset();
+            return true;
+        }
+        schedule.next_due <= now
+    }
+
+    /// Mark whether `session` is watched by a control mode client. A pushed
+    /// session is captured when `mark_dirty` says it wrote output, and
+    /// otherwise only once per maximum interval.
+    pub fn set_pushed(&mut self, session: &str, pushed: bool) {
+        if let Some(schedule) = self.sessions.get_mut(session) {
+            schedule.pushed = pushed;
+        }
+    }
+
+    /// Whether `session` is watched by a control mode client.
+    pub fn is_pushed(&self, session: &str) -> bool {
+        self.sessions.get(session).is_some_and(|s| s.pushed)
+    }
+
+    /// `session` wrote output: make it due now, or `PUSH_CAPTURE_GAP` after
+    /// its last capture if that is later.
+    pub fn mark_dirty(&mut self, session: &str, now: Instant) {
+        let Some(schedule) = self.sessions.get_mut(session) else { return };
+        let earliest = schedule
+            .last_captured
+            .map_or(now, |at| (at + PUSH_CAPTURE_GAP).max(now));
+        schedule.next_due = schedule.next_due.min(earliest);
+    }
+
+    /// Forget sessions for which `live` returns false.
+    pub fn retain(&mut self, live: impl Fn(&str) -> bool) {
+        self.sessions.retain(|session, _| live(session));
+    }
+
+    /// Record a capture of `session` and schedule its next one.
+    ///
+    /// Returns the output to emit now, or `None` when it is unchanged or held
+    /// back until the session's coalesce window ends (see `flush`).
+    pub fn observe(&mut self, session: &str, output: &str, changed: bool, now: Instant) -> Option<String> {
+        let schedule = self.sessions.get_mut(session)?;
+        let change = if changed {
+            schedule.detector.detect(output)
+        } else {
+            ChangeEvent::none()
+        };
+        let interval = schedule.poller.next_interval(&change);
+        schedule.next_due = now + if schedule.pushed { self.max_interval } else { interval };
+        schedule.last_captured = Some(now);
+
+        if !changed {
+            return None;
+        }
+        let window_open = schedule
+            .last_emitted
+            .is_some_and(|at| now.duration_since(at) < self.coalesce);
+        if window_open {
+            schedule.pending = Some(output.to_string());
+            return None;
+        }
+        schedule.last_emitted = Some(now);
+        schedule.pending = None;
+        Some(output.to_string())
+    }
+
+    /// Take held-back output whose coalesce window has ended.
+    pub fn flush(&mut self, now: Instant) -> Vec<(String, String)> {
+        let mut flushed = Vec::new();
+        for (session, schedule) in &mut self.sessions {
+            let window_over = schedule
+                .last_emitted
+                .is_none_or(|at| now.duration_since(at) >= self.coalesce);
+            if !window_over {
+                continue;
+            }
+            if let Some(output) = schedule.pending.take() {
+                schedule.last_emitted = Some(now);
+                flushed.push((session.clone(), output));
+            }
+        }
+        flushed
+    }
+
+    /// The earliest time a capture or flush is due, if any session is known.
+    pub fn next_wake(&self) -> Option<Instant> {
+        self.sessions
+            .values()
+            .map(|s| match (s.pending.as_ref(), s.last_emitted) {
+                (Some(_), Some(at)) => s.next_due.min(at + self.coalesce),
+                _ => s.next_due,
+            })
+            .min()
+    }
+
+    /// Current interval of `session`, if it is scheduled.
+    pub fn interval(&self, session: &str) -> Option<Duration> {
+        self.sessions.get(session).map(|s| s.poller.interval())
+    }
+
+    /// Number of scheduled sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Whether no session is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Duration = Duration::from_millis(500);
+    const MAX: Duration = Duration::from_secs(4);
+
+    fn schedule() -> PollSchedule {
+        PollSchedule::new(BASE, MAX, Duration::from_secs(1))
+    }
+
+    #[test]
+    fn test_idle_session_backs_off() {
+        let mut schedule = schedule();
+        let mut now = Instant::now();
+        assert!(schedule.is_due("api", 0, now));
+        schedule.observe("api", "$ ", true, now);
+
+        for _ in 0..10 {
+            now += schedule.interval("api").unwrap();
+            assert!(schedule.is_due("api", 0, now));
+            schedule.observe("api", "$ ", false, now);
+        }
+        assert_eq!(schedule.interval("api"), Some(MAX));
+        assert!(!schedule.is_due("api", 0, now + BASE));
+    }
+
+    #[test]
+    fn test_new_prompt_polls_immediately() {
+        let mut schedule = schedule();
+        let mut now = Instant::now();
+        schedule.is_due("api", 0, now);
+        for _ in 0..10 {
+            schedule.observe("api", "$ ", false, now);
+            now += BASE;
+        }
+        assert!(!schedule.is_due("api", 0, now));
+        assert!(schedule.is_due("api", 1, now));
+        assert_eq!(schedule.interval("api"), Some(BASE));
+    }
+
+    #[test]
+    fn test_changes_coalesce_to_latest_output() {
+        let mut schedule = schedule();
+        let start = Instant::now();
+        schedule.is_due("api", 0, start);
+
+        assert_eq!(schedule.observe("api", "one", true, start).as_deref(), Some("one"));
+        let soon = start + Duration::from_millis(300);
+        assert_eq!(schedule.observe("api", "two", true, soon), None);
+        assert_eq!(schedule.observe("api", "three", true, soon), None);
+        assert!(schedule.flush(soon).is_empty());
+        assert_eq!(schedule.next_wake(), Some(start + Duration::from_secs(1)));
+
+        let later = start + Duration::from_secs(1);
+        assert_eq!(schedule.flush(later), vec![("api".to_string(), "three".to_string())]);
+        assert!(schedule.flush(later + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn test_pushed_session_captured_on_output() {
+        let mut schedule = schedule();
+        let start = Instant::now();
+        schedule.is_due("api", 0, start);
+        schedule.set_pushed("api", true);
+        assert!(schedule.is_pushed("api"));
+        schedule.observe("api", "$ ", true, start);
+
+        // Quiet pushed sessions wait for the safety capture
+        assert!(!schedule.is_due("api", 0, start + BASE));
+        assert!(schedule.is_due("api", 0, start + MAX));
+
+        // Output makes them due, but not sooner than the capture gap
+        schedule.mark_dirty("api", start);
+        assert_eq!(schedule.next_wake(), Some(start + PUSH_CAPTURE_GAP));
+        assert!(schedule.is_due("api", 0, start + PUSH_CAPTURE_GAP));
+
+        schedule.set_pushed("api", false);
+        let later = start + Duration::from_secs(1);
+        schedule.observe("api", "$ ls", true, later);
+        let interval = schedule.interval("api").unwrap();
+        assert!(interval < MAX);
+        assert!(schedule.is_due("api", 0, later + interval));
+    }
+
+    #[test]
+    fn test_retain_forgets_stopped_sessions() {
+        let mut schedule = schedule();
+        let now = Instant::now();
+        schedule.is_due("api", 0, now);
+        schedule.is_due("web", 0, now);
+        schedule.retain(|s| s == "web");
+        assert_eq!(schedule.len(), 1);
+        assert!(schedule.interval("api").is_none());
+    }
+}
@@ -0,0 +1,600 @@
+//! Inactivity and error-loop watchdog for running sessions.
+//!
+//! The `Watchdog` is fed periodic snapshots of each session's output and
+//! tracks two failure modes:
+//!
+//! - **Stuck**: the session is not idle, but its output has not meaningfully
+//!   changed for longer than `stuck_after_secs` (e.g. frozen on a prompt).
+//! - **Error loop**: the same error keeps reappearing as the output changes
+//!   for longer than `error_loop_after_secs`.
+//!
+//! Alerts escalate while the condition persists: a banner at the threshold,
+//! a push notification at twice the threshold, and a webhook POST at three
+//! times the threshold (only when a webhook URL is configured). Each level
+//! fires once; any meaningful activity resets the escalation.
+//!
+//! # Configuration
+//!
+//! Thresholds are loaded from `~/.ai-commander/config/watchdog.yaml` and can be
+//! overridden per project:
+//!
+//! ```yaml
+//! stuck_after_secs: 600
+//! error_loop_after_secs: 300
+//! error_repeats: 3
+//! webhook_url: https://hooks.example.com/commander
+//! projects:
+//!   my-api:
+//!     stuck_after_secs: 120
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::error::{Result, RuntimeError};
+
+/// Watchdog configuration file name (under the config directory).
+pub const WATCHDOG_FILE: &str = "watchdog.yaml";
+
+/// Session name prefix used by Commander-managed tmux sessions.
+const SESSION_PREFIX: &str = "commander-";
+
+/// Thresholds applied to a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchdogThresholds {
+    /// Seconds without meaningful output before a busy session counts as stuck.
+    pub stuck_after_secs: u64,
+    /// Seconds an error must keep recurring before it counts as a loop.
+    pub error_loop_after_secs: u64,
+    /// Output changes the same error must survive before it counts as a loop.
+    pub error_repeats: u32,
+    /// Endpoint that receives a JSON POST for the highest alert level.
+    pub webhook_url: Option<String>,
+}
+
+impl Default for WatchdogThresholds {
+    fn default() -> Self {
+        Self {
+            stuck_after_secs: 600,
+            error_loop_after_secs: 300,
+            error_repeats: 3,
+            webhook_url: None,
+        }
+    }
+}
+
+impl WatchdogThresholds {
+    fn stuck_after(&self) -> Duration {
+        Duration::from_secs(self.stuck_after_secs)
+    }
+
+    fn error_loop_after(&self) -> Duration {
+        Duration::from_secs(self.error_loop_after_secs)
+    }
+
+    /// Highest level reached after `elapsed` for a condition with `threshold`.
+    fn level_for(&self, elapsed: Duration, threshold: Duration) -> Option<AlertLevel> {
+        if elapsed >= threshold * 3 && self.webhook_url.is_some() {
+            Some(AlertLevel::Webhook)
+        } else if elapsed >= threshold * 2 {
+            Some(AlertLevel::Push)
+        } else if elapsed >= threshold {
+            Some(AlertLevel::Banner)
+        } else {
+            None
+        }
+    }
+}
+
+/// Per-project overrides; unset fields fall back to the global thresholds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectThresholds {
+    /// Override for `stuck_after_secs`.
+    pub stuck_after_secs: Option<u64>,
+    /// Override for `error_loop_after_secs`.
+    pub error_loop_after_secs: Option<u64>,
+    /// Override for `error_repeats`.
+    pub error_repeats: Option<u32>,
+    /// Override for `webhook_url`.
+    pub webhook_url: Option<String>,
+}
+
+/// On-disk watchdog configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchdogConfig {
+    /// Global thresholds.
+    #[serde(flatten)]
+    pub defaults: WatchdogThresholds,
+    /// Overrides keyed by project name.
+    #[serde(default)]
+    pub projects: HashMap<String, ProjectThresholds>,
+}
+
+impl WatchdogConfig {
+    /// Load the configuration from a YAML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| RuntimeError::Watchdog(format!("failed to read {}: {}", path.display(), e)))?;
+
+        serde_yaml::from_str(&content)
+            .map_err(|e| RuntimeError::Watchdog(format!("failed to parse YAML {}: {}", path.display(), e)))
+    }
+
+    /// Load the configuration if `path` exists, falling back to defaults.
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!(error = %e, "Invalid watchdog config, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Resolve the thresholds for a project or tmux session name.
+    ///
+    /// A `commander-` session prefix is ignored when looking up overrides.
+    pub fn thresholds_for(&self, project: &str) -> WatchdogThresholds {
+        let overrides = self
+            .projects
+            .get(project)
+            .or_else(|| project.strip_prefix(SESSION_PREFIX).and_then(|p| self.projects.get(p)));
+
+        let mut thresholds = self.defaults.clone();
+        if let Some(o) = overrides {
+            if let Some(v) = o.stuck_after_secs {
+                thresholds.stuck_after_secs = v;
+            }
+            if let Some(v) = o.error_loop_after_secs {
+                thresholds.error_loop_after_secs = v;
+            }
+            if let Some(v) = o.error_repeats {
+                thresholds.error_repeats = v;
+            }
+            if o.webhook_url.is_some() {
+                thresholds.webhook_url = o.webhook_url.clone();
+            }
+        }
+        thresholds
+    }
+}
+
+/// How loudly an alert should be surfaced. Levels are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertLevel {
+    /// Show a banner in the TUI.
+    Banner,
+    /// Push a notification to Telegram and other channels.
+    Push,
+    /// POST to the configured webhook.
+    Webhook,
+}
+
+impl fmt::Display for AlertLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertLevel::Banner => write!(f, "banner"),
+            AlertLevel::Push => write!(f, "push"),
+            AlertLevel::Webhook => write!(f, "webhook"),
+        }
+    }
+}
+
+/// What the watchdog detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertKind {
+    /// No meaningful output while the session is busy.
+    Stuck,
+    /// The same error keeps recurring.
+    ErrorLoop {
+        /// The recurring error line.
+        error: String,
+    },
+}
+
+/// An escalating alert for a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchdogAlert {
+    /// Project or session name the alert is about.
+    pub project: String,
+    /// What was detected.
+    pub kind: AlertKind,
+    /// Escalation level reached.
+    pub level: AlertLevel,
+    /// How long the condition has persisted.
+    pub elapsed: Duration,
+    /// Webhook to notify, set for `AlertLevel::Webhook`.
+    pub webhook_url: Option<String>,
+}
+
+impl WatchdogAlert {
+    /// Human-readable description of the alert.
+    pub fn message(&self) -> String {
+        let name = self.project.strip_prefix(SESSION_PREFIX).unwrap_or(&self.project);
+        let minutes = (self.elapsed.as_secs() / 60).max(1);
+        match &self.kind {
+            AlertKind::Stuck => format!(
+                "Session \"{}\" has made no progress for {} min",
+                name, minutes
+            ),
+            AlertKind::ErrorLoop { error } => format!(
+                "Session \"{}\" has been repeating an error for {} min: {}",
+                name, minutes, error
+            ),
+        }
+    }
+
+    /// JSON body sent to the webhook.
+    pub fn to_payload(&self) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "project": self.project,
+            "level": self.level,
+            "elapsed_secs": self.elapsed.as_secs(),
+            "message": self.message(),
+        });
+        if let (Some(fields), Ok(serde_json::Value::Object(kind))) =
+            (payload.as_object_mut(), serde_json::to_value(&self.kind))
+        {
+            fields.extend(kind);
+        }
+        payload
+    }
+}
+
+/// POST an alert to its webhook URL.
+pub async fn send_webhook(client: &reqwest::Client, alert: &WatchdogAlert) -> Result<()> {
+    let Some(url) = &alert.webhook_url else {
+        return Ok(());
+    };
+
+    let response = client
+        .post(url)
+        .json(&alert.to_payload())
+        .send()
+        .await
+        .map_err(|e| RuntimeError::Watchdog(format!("webhook request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(RuntimeError::Watchdog(format!(
+            "webhook returned {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+/// Recurring error being tracked for a session.
+#[derive(Debug)]
+struct ErrorTrack {
+    signature: String,
+    first_seen: Instant,
+    repeats: u32,
+    level: Option<AlertLevel>,
+}
+
+/// Per-session watchdog state.
+#[derive(Debug)]
+struct SessionWatch {
+    fingerprint: u64,
+    last_activity: Instant,
+    stuck_level: Option<AlertLevel>,
+    error: Option<ErrorTrack>,
+}
+
+/// Tracks session activity and raises escalating alerts.
+#[derive(Debug, Default)]
+pub struct Watchdog {
+    config: WatchdogConfig,
+    sessions: HashMap<String, SessionWatch>,
+}
+
+impl Watchdog {
+    /// Create a watchdog with the given configuration.
+    pub fn new(config: WatchdogConfig) -> Self {
+        Self {
+            config,
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &WatchdogConfig {
+        &self.config
+    }
+
+    /// Record a snapshot of a session's output and return any new alerts.
+    ///
+    /// `errors` are the error lines detected in `output`, and `idle` is true
+    /// when the session is waiting at its input prompt (idle sessions are
+    /// never considered stuck).
+    pub fn observe(
+        &mut self,
+        project: &str,
+        output: &str,
+        errors: &[String],
+        idle: bool,
+        now: Instant,
+    ) -> Vec<WatchdogAlert> {
+        let thresholds = self.config.thresholds_for(project);
+        let fingerprint = fingerprint(output);
+
+        let is_new = !self.sessions.contains_key(project);
+        let watch = self.sessions.entry(project.to_string()).or_insert_with(|| SessionWatch {
+            fingerprint,
+            last_activity: now,
+            stuck_level: None,
+            error: None,
+        });
+
+        let changed = is_new || watch.fingerprint != fingerprint;
+        if changed || idle {
+            watch.fingerprint = fingerprint;
+            watch.last_activity = now;
+            watch.stuck_level = None;
+        }
+
+        if changed {
+            match errors.last().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+                Some(error) => match &mut watch.error {
+                    Some(track) if track.signature == error => track.repeats += 1,
+                    _ => {
+                        watch.error = Some(ErrorTrack {
+                            signature: error.to_string(),
+                            first_seen: now,
+                            repeats: 1,
+                            level: None,
+                        });
+                    }
+                },
+                None => watch.error = None,
+            }
+        }
+
+        let mut alerts = Vec::new();
+
+        if !idle {
+            let elapsed = now.saturating_duration_since(watch.last_activity);
+            if let Some(level) = thresholds.level_for(elapsed, thresholds.stuck_after()) {
+                if watch.stuck_level < Some(level) {
+                    watch.stuck_level = Some(level);
+                    alerts.push(alert(project, AlertKind::Stuck, level, elapsed, &thresholds));
+                }
+            }
+        }
+
+        if let Some(track) = &mut watch.error {
+            if track.repeats >= thresholds.error_repeats {
+                let elapsed = now.saturating_duration_since(track.first_seen);
+                if let Some(level) = thresholds.level_for(elapsed, thresholds.error_loop_after()) {
+                    if track.level < Some(level) {
+                        track.level = Some(level);
+                        let kind = AlertKind::ErrorLoop {
+                            error: track.signature.clone(),
+                        };
+                        alerts.push(alert(project, kind, level, elapsed, &thresholds));
+                    }
+                }
+            }
+        }
+
+        for a in &alerts {
+            debug!(project = %project, level = %a.level, kind = ?a.kind, "watchdog alert");
+        }
+        alerts
+    }
+
+    /// Check if a session currently has an unresolved alert.
+    pub fn is_alerting(&self, project: &str) -> bool {
+        self.sessions.get(project).is_some_and(|w| {
+            w.stuck_level.is_some() || w.error.as_ref().is_some_and(|e| e.level.is_some())
+        })
+    }
+
+    /// Stop tracking a session.
+    pub fn forget(&mut self, project: &str) {
+        self.sessions.remove(project);
+    }
+}
+
+fn alert(
+    project: &str,
+    kind: AlertKind,
+    level: AlertLevel,
+    elapsed: Duration,
+    thresholds: &WatchdogThresholds,
+) -> WatchdogAlert {
+    WatchdogAlert {
+        project: project.to_string(),
+        kind,
+        level,
+        elapsed,
+        webhook_url: if level == AlertLevel::Webhook {
+            thresholds.webhook_url.clone()
+        } else {
+            None
+        },
+    }
+}
+
+/// Hash of the output's meaningful content.
+///
+/// Words containing digits are dropped and only letters are kept, so spinner
+/// glyphs and elapsed-time counters do not count as activity.
+fn fingerprint(output: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for line in output.lines() {
+        let words: Vec<String> = line
+            .split_whitespace()
+            .filter(|word| !word.chars().any(|c| c.is_ascii_digit()))
+            .map(|word| word.chars().filter(|c| c.is_alphabetic()).collect::<String>())
+            .filter(|word| !word.is_empty())
+            .collect();
+        if !words.is_empty() {
+            words.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(stuck: u64, error_loop: u64, webhook: Option<&str>) -> WatchdogConfig {
+        WatchdogConfig {
+            defaults: WatchdogThresholds {
+                stuck_after_secs: stuck,
+                error_loop_after_secs: error_loop,
+                error_repeats: 3,
+                webhook_url: webhook.map(str::to_string),
+            },
+            projects: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_stuck_alert_escalates_once_per_level() {
+        let mut watchdog = Watchdog::new(config(60, 60, Some("http://hook")));
+        let t0 = Instant::now();
+        let output = "Allow edit to src/main.rs? (y/n)";
+
+        assert!(watchdog.observe("proj", output, &[], false, t0).is_empty());
+        assert!(watchdog
+            .observe("proj", output, &[], false, t0 + Duration::from_secs(30))
+            .is_empty());
+
+        let alerts = watchdog.observe("proj", output, &[], false, t0 + Duration::from_secs(60));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, AlertKind::Stuck);
+        assert_eq!(alerts[0].level, AlertLevel::Banner);
+        assert!(alerts[0].webhook_url.is_none());
+
+        // Same level does not fire again
+        assert!(watchdog
+            .observe("proj", output, &[], false, t0 + Duration::from_secs(90))
+            .is_empty());
+
+        let alerts = watchdog.observe("proj", output, &[], false, t0 + Duration::from_secs(120));
+        assert_eq!(alerts[0].level, AlertLevel::Push);
+
+        let alerts = watchdog.observe("proj", output, &[], false, t0 + Duration::from_secs(180));
+        assert_eq!(alerts[0].level, AlertLevel::Webhook);
+        assert_eq!(alerts[0].webhook_url.as_deref(), Some("http://hook"));
+    }
+
+    #[test]
+    fn test_webhook_level_requires_url() {
+        let mut watchdog = Watchdog::new(config(60, 60, None));
+        let t0 = Instant::now();
+
+        watchdog.observe("proj", "working", &[], false, t0);
+        let alerts = watchdog.observe("proj", "working", &[], false, t0 + Duration::from_secs(600));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, AlertLevel::Push);
+    }
+
+    #[test]
+    fn test_activity_and_idle_reset_stuck_timer() {
+        let mut watchdog = Watchdog::new(config(60, 60, None));
+        let t0 = Instant::now();
+
+        watchdog.observe("proj", "Reading files", &[], false, t0);
+        // Spinner and timer changes are not meaningful activity
+        watchdog.observe("proj", "✻ Reading files (12s)", &[], false, t0 + Duration::from_secs(30));
+        let alerts = watchdog.observe("proj", "✽ Reading files (59s)", &[], false, t0 + Duration::from_secs(60));
+        assert_eq!(alerts.len(), 1);
+
+        assert!(watchdog.is_alerting("proj"));
+
+        // Real output resets the escalation
+        watchdog.observe("proj", "Writing tests", &[], false, t0 + Duration::from_secs(70));
+        assert!(!watchdog.is_alerting("proj"));
+        assert!(watchdog
+            .observe("proj", "Writing tests", &[], false, t0 + Duration::from_secs(100))
+            .is_empty());
+
+        // Idle sessions are never stuck
+        assert!(watchdog
+            .observe("proj", "Writing tests", &[], true, t0 + Duration::from_secs(1000))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_error_loop_detection() {
+        let mut watchdog = Watchdog::new(config(10_000, 60, None));
+        let t0 = Instant::now();
+        let errors = vec!["error[E0308]: mismatched types".to_string()];
+
+        for (i, secs) in [0u64, 20, 40].iter().enumerate() {
+            let output = format!("attempt {}\nerror[E0308]: mismatched types", "x".repeat(i + 1));
+            let alerts = watchdog.observe("proj", &output, &errors, false, t0 + Duration::from_secs(*secs));
+            assert!(alerts.is_empty());
+        }
+
+        let output = "attempt xxxx\nerror[E0308]: mismatched types";
+        let alerts = watchdog.observe("proj", output, &errors, false, t0 + Duration::from_secs(60));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(
+            alerts[0].kind,
+            AlertKind::ErrorLoop {
+                error: "error[E0308]: mismatched types".to_string()
+            }
+        );
+        assert!(alerts[0].message().contains("repeating an error"));
+
+        // A different error restarts tracking
+        let other = vec!["error: linker failed".to_string()];
+        assert!(watchdog
+            .observe("proj", "linker", &other, false, t0 + Duration::from_secs(200))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_project_overrides() {
+        let yaml = "stuck_after_secs: 600\nwebhook_url: http://global\nprojects:\n  my-api:\n    stuck_after_secs: 120\n";
+        let config: WatchdogConfig = serde_yaml::from_str(yaml).unwrap();
+
+        let api = config.thresholds_for("commander-my-api");
+        assert_eq!(api.stuck_after_secs, 120);
+        assert_eq!(api.error_loop_after_secs, 300);
+        assert_eq!(api.webhook_url.as_deref(), Some("http://global"));
+
+        assert_eq!(config.thresholds_for("other").stuck_after_secs, 600);
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let alert = WatchdogAlert {
+            project: "commander-my-api".to_string(),
+            kind: AlertKind::ErrorLoop {
+                error: "boom".to_string(),
+            },
+            level: AlertLevel::Webhook,
+            elapsed: Duration::from_secs(900),
+            webhook_url: Some("http://hook".to_string()),
+        };
+
+        let payload = alert.to_payload();
+        assert_eq!(payload["project"], "commander-my-api");
+        assert_eq!(payload["level"], "webhook");
+        assert_eq!(payload["kind"], "error_loop");
+        assert_eq!(payload["error"], "boom");
+        assert_eq!(payload["elapsed_secs"], 900);
+        assert!(payload["message"].as_str().unwrap().contains("\"my-api\""));
+    }
+}
@@ -0,0 +1,322 @@
+//! Filesystem watching for running projects.
+//!
+//! A `ProjectWatcher` watches a project directory recursively and reports
+//! debounced batches of changed paths. Raw filesystem notifications are
+//! coalesced by a `ChangeBatcher` so that a burst of writes (e.g. a formatter
+//! touching dozens of files) produces a single batch.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, warn};
+
+use crate::error::{Result, RuntimeError};
+
+/// Directory names whose contents never produce change events.
+const IGNORED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    ".venv",
+    "__pycache__",
+    ".idea",
+    ".vscode",
+];
+
+/// File suffixes written by editors as temporary/swap files.
+const IGNORED_SUFFIXES: &[&str] = &["~", ".swp", ".swx", ".tmp"];
+
+/// How long the watcher thread sleeps when nothing is pending.
+const IDLE_WAIT: Duration = Duration::from_secs(1);
+
+/// Returns true if changes to this path should be ignored.
+pub fn is_ignored(path: &Path) -> bool {
+    let in_ignored_dir = path.components().any(|c| {
+        c.as_os_str()
+            .to_str()
+            .is_some_and(|name| IGNORED_DIRS.contains(&name))
+    });
+    if in_ignored_dir {
+        return true;
+    }
+
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| IGNORED_SUFFIXES.iter().any(|s| name.ends_with(s)))
+}
+
+/// Coalesces individual path changes into debounced batches.
+///
+/// A batch is ready once no new change has arrived for `debounce`, or once
+/// the oldest pending change is `max_delay` old (so a constant stream of
+/// writes still gets reported).
+#[derive(Debug)]
+pub struct ChangeBatcher {
+    debounce: Duration,
+    max_delay: Duration,
+    pending: BTreeSet<PathBuf>,
+    first_at: Option<Instant>,
+    last_at: Option<Instant>,
+}
+
+impl ChangeBatcher {
+    /// Creates a batcher with the given quiet period.
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            max_delay: debounce * 10,
+            pending: BTreeSet::new(),
+            first_at: None,
+            last_at: None,
+        }
+    }
+
+    /// Records a changed path observed at `now`.
+    pub fn push(&mut self, path: PathBuf, now: Instant) {
+        if self.pending.is_empty() {
+            self.first_at = Some(now);
+        }
+        self.pending.insert(path);
+        self.last_at = Some(now);
+    }
+
+    /// Returns true if no changes are pending.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns how long until the pending batch becomes ready, if any.
+    pub fn time_until_ready(&self, now: Instant) -> Option<Duration> {
+        let (first, last) = (self.first_at?, self.last_at?);
+        let quiet_deadline = last + self.debounce;
+        let max_deadline = first + self.max_delay;
+        Some(quiet_deadline.min(max_deadline).saturating_duration_since(now))
+    }
+
+    /// Takes the pending batch if it is ready at `now`.
+    pub fn flush_if_ready(&mut self, now: Instant) -> Option<Vec<PathBuf>> {
+        match self.time_until_ready(now) {
+            Some(wait) if wait.is_zero() => Some(self.flush()),
+            _ => None,
+        }
+    }
+
+    /// Takes the pending batch unconditionally.
+    pub fn flush(&mut self) -> Vec<PathBuf> {
+        self.first_at = None;
+        self.last_at = None;
+        std::mem::take(&mut self.pending).into_iter().collect()
+    }
+}
+
+/// Watches a project directory and reports debounced change batches.
+///
+/// Paths are reported relative to the project root when possible. The
+/// watcher stops when dropped.
+pub struct ProjectWatcher {
+    root: PathBuf,
+    // Dropping the notify watcher closes the channel, which ends the
+    // debounce thread.
+    _watcher: RecommendedWatcher,
+}
+
+impl std::fmt::Debug for ProjectWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectWatcher")
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+impl ProjectWatcher {
+    /// Starts watching `root`, calling `on_change` with each debounced batch.
+    pub fn start<F>(root: impl Into<PathBuf>, debounce: Duration, on_change: F) -> Result<Self>
+    where
+        F: FnMut(Vec<PathBuf>) + Send + 'static,
+    {
+        let root = root.into();
+        // notify may report canonical paths (e.g. /private/var on macOS)
+        let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+
+        let (tx, rx) = mpsc::channel::<PathBuf>();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            match res {
+                Ok(event) => {
+                    if event.kind.is_access() {
+                        return;
+                    }
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Err(e) => warn!(error = %e, "file watcher error"),
+            }
+        })
+        .map_err(|e| RuntimeError::Watcher(e.to_string()))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| RuntimeError::Watcher(format!("{}: {}", root.display(), e)))?;
+
+        let thread_root = root.clone();
+        thread::Builder::new()
+            .name("project-watcher".to_string())
+            .spawn(move || {
+                run_debounce_loop(rx, debounce, &thread_root, &canonical_root, on_change)
+            })
+            .map_err(|e| RuntimeError::Watcher(e.to_string()))?;
+
+        debug!(root = %root.display(), "file watcher started");
+
+        Ok(Self {
+            root,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the watched directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+/// Receives raw paths, filters and batches them, and hands batches to `on_change`.
+fn run_debounce_loop<F>(
+    rx: mpsc::Receiver<PathBuf>,
+    debounce: Duration,
+    root: &Path,
+    canonical_root: &Path,
+    mut on_change: F,
+) where
+    F: FnMut(Vec<PathBuf>),
+{
+    let mut batcher = ChangeBatcher::new(debounce);
+
+    loop {
+        let wait = batcher
+            .time_until_ready(Instant::now())
+            .unwrap_or(IDLE_WAIT);
+
+        match rx.recv_timeout(wait) {
+            Ok(path) => {
+                let relative = relativize(&path, root, canonical_root);
+                if !is_ignored(&relative) {
+                    batcher.push(relative, Instant::now());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                if !batcher.is_empty() {
+                    on_change(batcher.flush());
+                }
+                debug!(root = %root.display(), "file watcher stopped");
+                return;
+            }
+        }
+
+        if let Some(batch) = batcher.flush_if_ready(Instant::now()) {
+            on_change(batch);
+        }
+    }
+}
+
+/// Strips the project root from a reported path.
+fn relativize(path: &Path, root: &Path, canonical_root: &Path) -> PathBuf {
+    path.strip_prefix(canonical_root)
+        .or_else(|_| path.strip_prefix(root))
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ignored() {
+        assert!(is_ignored(Path::new(".git/index")));
+        assert!(is_ignored(Path::new("target/debug/build")));
+        assert!(is_ignored(Path::new("web/node_modules/x/index.js")));
+        assert!(is_ignored(Path::new("src/main.rs~")));
+        assert!(is_ignored(Path::new("src/.main.rs.swp")));
+        assert!(!is_ignored(Path::new("src/main.rs")));
+        assert!(!is_ignored(Path::new("targets.md")));
+    }
+
+    #[test]
+    fn test_batcher_waits_for_quiet_period() {
+        let debounce = Duration::from_millis(100);
+        let mut batcher = ChangeBatcher::new(debounce);
+        let t0 = Instant::now();
+
+        batcher.push(PathBuf::from("a.rs"), t0);
+        batcher.push(PathBuf::from("b.rs"), t0 + Duration::from_millis(50));
+        assert!(batcher.flush_if_ready(t0 + Duration::from_millis(100)).is_none());
+
+        let batch = batcher
+            .flush_if_ready(t0 + Duration::from_millis(150))
+            .unwrap();
+        assert_eq!(batch, vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")]);
+        assert!(batcher.is_empty());
+        assert!(batcher.time_until_ready(t0).is_none());
+    }
+
+    #[test]
+    fn test_batcher_deduplicates_paths() {
+        let mut batcher = ChangeBatcher::new(Duration::from_millis(10));
+        let t0 = Instant::now();
+
+        batcher.push(PathBuf::from("a.rs"), t0);
+        batcher.push(PathBuf::from("a.rs"), t0);
+
+        let batch = batcher.flush_if_ready(t0 + Duration::from_secs(1)).unwrap();
+        assert_eq!(batch.len(), 1);
+    }
+
+    #[test]
+    fn test_batcher_max_delay_under_constant_writes() {
+        let debounce = Duration::from_millis(100);
+        let mut batcher = ChangeBatcher::new(debounce);
+        let t0 = Instant::now();
+
+        // A write every 50ms never leaves a quiet period, but the batch
+        // must still be released once it is max_delay (1s) old.
+        let mut now = t0;
+        let mut released = None;
+        for i in 0..40 {
+            batcher.push(PathBuf::from(format!("f{}.rs", i)), now);
+            if let Some(batch) = batcher.flush_if_ready(now) {
+                released = Some((now - t0, batch));
+                break;
+            }
+            now += Duration::from_millis(50);
+        }
+
+        let (elapsed, batch) = released.expect("batch should be released");
+        assert_eq!(elapsed, Duration::from_secs(1));
+        assert_eq!(batch.len(), 21);
+    }
+
+    #[test]
+    fn test_project_watcher_reports_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let watcher = ProjectWatcher::start(dir.path(), Duration::from_millis(50), move |paths| {
+            let _ = tx.send(paths);
+        })
+        .unwrap();
+        assert_eq!(watcher.root(), dir.path());
+
+        std::fs::write(dir.path().join(".git").join("HEAD"), "ref").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+
+        let batch = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(batch.contains(&PathBuf::from("main.rs")));
+        assert!(batch.iter().all(|p| !p.starts_with(".git")));
+    }
+}
@@ -0,0 +1,321 @@
+//! Outgoing webhooks for runtime events and change notifications.
+//!
+//! Each `[[webhooks]]` entry in `config.toml` receives a JSON POST for every
+//! event matching its filter:
+//!
+//! ```json
+//! {"event": "session_crashed", "project": "…", "timestamp": "…", "data": {"session": "commander-api"}}
+//! ```
+//!
+//! `event` is a [`RuntimeEvent::kind`] or [`CHANGE_EVENT`] for a session
+//! agent's [`ChangeNotification`]. When the hook names a `secret`, the body is
+//! signed with HMAC-SHA256 keyed by that secret store entry and the signature
+//! sent as `X-Commander-Signature: sha256=<hex>`. Network errors, 5xx and 429
+//! responses are retried with exponential backoff.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tracing::{debug, warn};
+
+use commander_core::{ChangeNotification, Significance, WebhookSettings};
+
+use crate::error::{Result, RuntimeError};
+use crate::event::RuntimeEvent;
+
+/// Event type of change notifications.
+pub const CHANGE_EVENT: &str = "change";
+
+/// Header carrying the body's HMAC-SHA256 signature.
+pub const SIGNATURE_HEADER: &str = "X-Commander-Signature";
+
+/// Header carrying the event type.
+pub const EVENT_HEADER: &str = "X-Commander-Event";
+
+/// Event types only sent to hooks that list them, as they fire on every
+/// screen update.
+const OUTPUT_EVENTS: &[&str] = &["output_received", "pane_output"];
+
+/// Delay before the first retry; doubled for each one after.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Time allowed for one delivery attempt.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether `hook` wants an `event` about `project`.
+///
+/// `significance` is set for change notifications only; runtime events are
+/// not filtered on it.
+pub fn accepts(hook: &WebhookSettings, event: &str, project: &str, significance: Option<Significance>) -> bool {
+    let event_ok = if hook.events.is_empty() {
+        !OUTPUT_EVENTS.contains(&event)
+    } else {
+        hook.events.iter().any(|e| e == event)
+    };
+    let project_ok = hook.projects.is_empty() || hook.projects.iter().any(|p| p == project);
+    let significance_ok = match significance {
+        Some(significance) => {
+            significance != Significance::Ignore && hook.min_significance.is_none_or(|min| significance >= min)
+        }
+        None => true,
+    };
+    event_ok && project_ok && significance_ok
+}
+
+/// The `data` of a runtime event's payload.
+pub fn event_data(event: &RuntimeEvent) -> Value {
+    match event {
+        RuntimeEvent::InstanceStarted { session, .. } | RuntimeEvent::SessionCrashed { session, .. } => {
+            json!({ "session": session })
+        }
+        RuntimeEvent::InstanceStopped { .. } => json!({}),
+        RuntimeEvent::OutputReceived { output, .. } => json!({ "output": output }),
+        RuntimeEvent::PaneOutput { pane, output, .. } => json!({ "pane": pane, "output": output }),
+        RuntimeEvent::StateChanged { state, .. } => json!({ "state": state }),
+        RuntimeEvent::FilesChanged { paths, .. } => {
+            let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+            json!({ "paths": paths })
+        }
+        RuntimeEvent::WatchdogAlert { alert, .. } => alert.to_payload(),
+        RuntimeEvent::InstanceRestarted { session, attempt, replay_prompt, .. } => json!({
+            "session": session,
+            "attempt": attempt,
+            "replay_prompt": replay_prompt,
+        }),
+        RuntimeEvent::StartQueued { position, .. } => json!({ "position": position }),
+        RuntimeEvent::Error { error, .. } => json!({ "error": error }),
+    }
+}
+
+/// The JSON body POSTed for an event.
+pub fn payload(event: &str, project: &str, data: Value) -> Value {
+    json!({
+        "event": event,
+        "project": project,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "data": data,
+    })
+}
+
+/// `sha256=<hex>` HMAC-SHA256 signature of `body` keyed by `key`.
+pub fn sign(key: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Sends events to the configured webhooks.
+///
+/// Cloning is cheap and clones share the hook list, so one component can
+/// reload it while others dispatch.
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    hooks: Arc<RwLock<Vec<WebhookSettings>>>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(hooks: Vec<WebhookSettings>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            hooks: Arc::new(RwLock::new(valid_hooks(hooks))),
+        }
+    }
+
+    /// Replace the hooks, e.g. after `config.toml` changed.
+    pub fn set_hooks(&self, hooks: Vec<WebhookSettings>) {
+        let hooks = valid_hooks(hooks);
+        if let Ok(mut current) = self.hooks.write() {
+            *current = hooks;
+        }
+    }
+
+    /// Whether any hook is configured.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.read().map(|hooks| hooks.is_empty()).unwrap_or(true)
+    }
+
+    /// Send a runtime event to the hooks that want it.
+    pub fn send_event(&self, event: &RuntimeEvent) {
+        self.dispatch(event.kind(), event.project_id().as_str(), None, || event_data(event));
+    }
+
+    /// Send a change notification to the hooks that want it.
+    pub fn send_change(&self, notification: &ChangeNotification) {
+        self.dispatch(
+            CHANGE_EVENT,
+            &notification.session_id,
+            Some(notification.significance),
+            || serde_json::to_value(notification).unwrap_or_default(),
+        );
+    }
+
+    /// Spawn a delivery to each matching hook. Must be called within a
+    /// Tokio runtime.
+    fn dispatch(&self, event: &str, project: &str, significance: Option<Significance>, data: impl FnOnce() -> Value) {
+        let hooks: Vec<WebhookSettings> = match self.hooks.read() {
+            Ok(hooks) => hooks
+                .iter()
+                .filter(|hook| accepts(hook, event, project, significance))
+                .cloned()
+                .collect(),
+            Err(_) => return,
+        };
+        if hooks.is_empty() {
+            return;
+        }
+
+        let body: Arc<str> = payload(event, project, data()).to_string().into();
+        for hook in hooks {
+            let client = self.client.clone();
+            let body = Arc::clone(&body);
+            let event = event.to_string();
+            tokio::spawn(async move {
+                if let Err(e) = deliver(&client, &hook, &event, &body).await {
+                    warn!(url = %hook.url, event = %event, error = %e, "Webhook delivery failed");
+                }
+            });
+        }
+    }
+}
+
+/// POST `body` to `hook`, retrying transient failures.
+async fn deliver(client: &reqwest::Client, hook: &WebhookSettings, event: &str, body: &str) -> Result<()> {
+    let signature = match &hook.secret {
+        Some(name) => match commander_core::get_secret(name) {
+            Some(key) => Some(sign(key.as_bytes(), body.as_bytes())),
+            None => return Err(RuntimeError::Webhook(format!("signing secret {} is not set", name))),
+        },
+        None => None,
+    };
+
+    let mut attempt = 0;
+    loop {
+        let mut request = client
+            .post(&hook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event)
+            .body(body.to_string());
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        let error = match request.send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if !is_retryable(response.status()) => {
+                return Err(RuntimeError::Webhook(format!("{} returned {}", hook.url, response.status())));
+            }
+            Ok(response) => format!("{} returned {}", hook.url, response.status()),
+            Err(e) => format!("request to {} failed: {}", hook.url, e),
+        };
+        if attempt >= hook.retries {
+            return Err(RuntimeError::Webhook(error));
+        }
+        let delay = RETRY_BASE_DELAY * 2u32.saturating_pow(attempt);
+        debug!(error = %error, attempt, delay_secs = delay.as_secs(), "Retrying webhook");
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Whether a response with `status` may succeed when retried.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Drop hooks without a URL.
+fn valid_hooks(hooks: Vec<WebhookSettings>) -> Vec<WebhookSettings> {
+    hooks
+        .into_iter()
+        .filter(|hook| {
+            let valid = !hook.url.trim().is_empty();
+            if !valid {
+                warn!("Ignoring [[webhooks]] entry without a url");
+            }
+            valid
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_core::ChangeType;
+    use commander_models::ProjectId;
+
+    fn hook() -> WebhookSettings {
+        WebhookSettings {
+            url: "https://example.com/hook".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_accepts() {
+        let all = hook();
+        assert!(accepts(&all, "session_crashed", "api", None));
+        assert!(!accepts(&all, "output_received", "api", None));
+        assert!(!accepts(&all, CHANGE_EVENT, "api", Some(Significance::Ignore)));
+
+        let filtered = WebhookSettings {
+            events: vec![CHANGE_EVENT.into(), "output_received".into()],
+            projects: vec!["api".into()],
+            min_significance: Some(Significance::High),
+            ..hook()
+        };
+        assert!(accepts(&filtered, "output_received", "api", None));
+        assert!(accepts(&filtered, CHANGE_EVENT, "api", Some(Significance::Critical)));
+        assert!(!accepts(&filtered, CHANGE_EVENT, "api", Some(Significance::Medium)));
+        assert!(!accepts(&filtered, CHANGE_EVENT, "web", Some(Significance::Critical)));
+        assert!(!accepts(&filtered, "session_crashed", "api", None));
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign(b"key", b"The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_payloads() {
+        let event = RuntimeEvent::InstanceRestarted {
+            project_id: ProjectId::from_string("api"),
+            session: "commander-api".into(),
+            attempt: 2,
+            replay_prompt: true,
+        };
+        let body = payload(event.kind(), event.project_id().as_str(), event_data(&event));
+        assert_eq!(body["event"], "instance_restarted");
+        assert_eq!(body["project"], "api");
+        assert_eq!(body["data"]["attempt"], 2);
+
+        let notification = ChangeNotification {
+            session_id: "api".into(),
+            summary: "Tests failed".into(),
+            requires_action: true,
+            change_type: ChangeType::Error,
+            significance: Significance::High,
+            diagnosis: None,
+        };
+        let data = serde_json::to_value(&notification).unwrap();
+        assert_eq!(data["significance"], "high");
+        assert_eq!(data["change_type"], "error");
+    }
+
+    #[test]
+    fn test_set_hooks_drops_entries_without_url() {
+        let dispatcher = WebhookDispatcher::new(vec![WebhookSettings::default()]);
+        assert!(dispatcher.is_empty());
+        dispatcher.set_hooks(vec![hook()]);
+        assert!(!dispatcher.is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+//! Error types for secret storage.
+
+use thiserror::Error;
+
+/// Errors that can occur while storing or reading secrets.
+#[derive(Error, Debug)]
+pub enum SecretsError {
+    /// Secret names must be non-empty and free of whitespace.
+    #[error("invalid secret name: {0:?}")]
+    InvalidName(String),
+
+    /// The OS keychain rejected an operation.
+    #[error("keychain error: {0}")]
+    Keychain(String),
+
+    /// Encryption or decryption failed (wrong key or corrupted file).
+    #[error("crypto error: {0}")]
+    Crypto(String),
+
+    /// The secrets file could not be parsed.
+    #[error("invalid secrets file: {0}")]
+    InvalidFile(#[from] serde_json::Error),
+
+    /// I/O error.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type alias for secret operations.
+pub type Result<T> = std::result::Result<T, SecretsError>;
@@ -0,0 +1,163 @@
+//! Encrypted-file secret storage.
+//!
+//! Secrets are kept in `secrets.json`, each value encrypted with
+//! ChaCha20-Poly1305 under a random 256-bit key stored in `secrets.key`
+//! (mode 0600 on Unix). The secret name is bound as associated data so
+//! ciphertexts cannot be swapped between names. The file also records which
+//! names live in the OS keychain, since keychains cannot be enumerated.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, SecretsError};
+
+/// Encrypted secrets file name.
+pub const SECRETS_FILE: &str = "secrets.json";
+
+/// Encryption key file name.
+pub const KEY_FILE: &str = "secrets.key";
+
+/// Nonce length for ChaCha20-Poly1305.
+const NONCE_LEN: usize = 12;
+
+/// On-disk layout of the secrets file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct SecretsFile {
+    /// Names stored in the OS keychain.
+    #[serde(default)]
+    pub keychain: Vec<String>,
+    /// Base64 of nonce followed by ciphertext, keyed by name.
+    #[serde(default)]
+    pub encrypted: BTreeMap<String, String>,
+}
+
+/// Encrypted secrets file in a directory.
+#[derive(Debug, Clone)]
+pub(crate) struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn secrets_path(&self) -> PathBuf {
+        self.dir.join(SECRETS_FILE)
+    }
+
+    fn key_path(&self) -> PathBuf {
+        self.dir.join(KEY_FILE)
+    }
+
+    /// Load the secrets file, or an empty one if it does not exist.
+    pub fn load(&self) -> Result<SecretsFile> {
+        let path = self.secrets_path();
+        if !path.exists() {
+            return Ok(SecretsFile::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, file: &SecretsFile) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        write_private(&self.secrets_path(), serde_json::to_string_pretty(file)?.as_bytes())
+    }
+
+    pub fn encrypt(&self, name: &str, value: &str) -> Result<String> {
+        let cipher = self.cipher(true)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: value.as_bytes(), aad: name.as_bytes() })
+            .map_err(|e| SecretsError::Crypto(e.to_string()))?;
+
+        let mut blob = nonce.to_vec();
+        blob.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(blob))
+    }
+
+    pub fn decrypt(&self, name: &str, encoded: &str) -> Result<String> {
+        let blob = STANDARD
+            .decode(encoded)
+            .map_err(|e| SecretsError::Crypto(e.to_string()))?;
+        if blob.len() < NONCE_LEN {
+            return Err(SecretsError::Crypto(format!("ciphertext for {} is truncated", name)));
+        }
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+
+        let plaintext = self
+            .cipher(false)?
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: name.as_bytes() })
+            .map_err(|_| SecretsError::Crypto(format!("failed to decrypt {}", name)))?;
+        String::from_utf8(plaintext).map_err(|e| SecretsError::Crypto(e.to_string()))
+    }
+
+    /// Load the encryption key, generating it on first write.
+    fn cipher(&self, create: bool) -> Result<ChaCha20Poly1305> {
+        let path = self.key_path();
+        if path.exists() {
+            let key = fs::read(&path)?;
+            if key.len() != 32 {
+                return Err(SecretsError::Crypto(format!("{} is not a 256-bit key", path.display())));
+            }
+            return Ok(ChaCha20Poly1305::new(Key::from_slice(&key)));
+        }
+        if !create {
+            return Err(SecretsError::Crypto(format!("missing key file {}", path.display())));
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        write_private(&path, &key)?;
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+}
+
+/// Write a file readable only by the current user.
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    fs::write(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(dir.path());
+
+        let encrypted = store.encrypt("TOKEN", "s3cret").unwrap();
+        assert!(!encrypted.contains("s3cret"));
+        assert_eq!(store.decrypt("TOKEN", &encrypted).unwrap(), "s3cret");
+
+        // Name is bound to the ciphertext
+        assert!(store.decrypt("OTHER", &encrypted).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(dir.path());
+        store.encrypt("TOKEN", "value").unwrap();
+
+        let mode = fs::metadata(dir.path().join(KEY_FILE)).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
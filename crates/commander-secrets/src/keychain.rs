@@ -0,0 +1,69 @@
+//! OS keychain access.
+//!
+//! Uses the macOS Keychain and Windows Credential Manager via `keyring`.
+//! Linux keyrings are either session-scoped (keyutils) or need a running
+//! Secret Service, so Linux uses the encrypted file instead.
+
+use crate::error::{Result, SecretsError};
+
+/// Keychain service name for Commander secrets.
+pub const SERVICE: &str = "ai-commander";
+
+/// Whether an OS keychain backend is compiled in.
+pub(crate) fn available() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod native {
+    use super::*;
+
+    fn entry(name: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(SERVICE, name).map_err(|e| SecretsError::Keychain(e.to_string()))
+    }
+
+    pub fn set(name: &str, value: &str) -> Result<()> {
+        entry(name)?
+            .set_password(value)
+            .map_err(|e| SecretsError::Keychain(e.to_string()))
+    }
+
+    pub fn get(name: &str) -> Result<Option<String>> {
+        match entry(name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(SecretsError::Keychain(e.to_string())),
+        }
+    }
+
+    pub fn delete(name: &str) -> Result<bool> {
+        match entry(name)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(SecretsError::Keychain(e.to_string())),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod native {
+    use super::*;
+
+    fn unsupported() -> SecretsError {
+        SecretsError::Keychain("no OS keychain on this platform".to_string())
+    }
+
+    pub fn set(_name: &str, _value: &str) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn get(_name: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn delete(_name: &str) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+pub(crate) use native::{delete, get, set};
@@ -0,0 +1,55 @@
+//! Encrypted storage for Commander API keys and bot tokens.
+//!
+//! This crate provides the `SecretStore`, which keeps secrets out of
+//! plaintext `.env.local` files:
+//! - OS keychain on macOS and Windows (via `keyring`)
+//! - ChaCha20-Poly1305 encrypted file everywhere else, or when the keychain
+//!   is unavailable
+//!
+//! Consumers read secrets with [`SecretStore::get_or_env`], which falls back
+//! to the environment variable of the same name.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use commander_secrets::SecretStore;
+//!
+//! let store = SecretStore::new("/tmp/commander/secrets");
+//! store.set("OPENROUTER_API_KEY", "sk-or-...").unwrap();
+//!
+//! let key = store.get_or_env("OPENROUTER_API_KEY");
+//! ```
+
+pub mod error;
+mod file;
+mod keychain;
+mod store;
+
+pub use error::{Result, SecretsError};
+pub use file::{KEY_FILE, SECRETS_FILE};
+pub use keychain::SERVICE;
+pub use store::{Backend, SecretEntry, SecretStore, BACKEND_ENV};
+
+/// OpenRouter API key.
+pub const OPENROUTER_API_KEY: &str = "OPENROUTER_API_KEY";
+
+/// Telegram bot token.
+pub const TELEGRAM_BOT_TOKEN: &str = "TELEGRAM_BOT_TOKEN";
+
+/// OpenAI API key.
+pub const OPENAI_API_KEY: &str = "OPENAI_API_KEY";
+
+/// GitHub token.
+pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+/// Bearer token accepted by the API's inbound message endpoint.
+pub const INBOUND_WEBHOOK_TOKEN: &str = "INBOUND_WEBHOOK_TOKEN";
+
+/// Secrets Commander knows about, with descriptions.
+pub const KNOWN_SECRETS: &[(&str, &str)] = &[
+    (OPENROUTER_API_KEY, "OpenRouter API key for agents and summarization"),
+    (TELEGRAM_BOT_TOKEN, "Telegram bot token for mobile access"),
+    (OPENAI_API_KEY, "OpenAI API key for Telegram voice transcription"),
+    (GITHUB_TOKEN, "GitHub token for importing issues and opening pull requests"),
+    (INBOUND_WEBHOOK_TOKEN, "Token CI and other services send to POST /api/projects/{id}/messages"),
+];
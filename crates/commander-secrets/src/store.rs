@@ -0,0 +1,233 @@
+//! Secret store combining the OS keychain and the encrypted file.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use tracing::{debug, warn};
+
+use crate::error::{Result, SecretsError};
+use crate::file::FileStore;
+use crate::keychain;
+
+/// Environment variable that forces the encrypted-file backend (`file`).
+pub const BACKEND_ENV: &str = "COMMANDER_SECRETS_BACKEND";
+
+/// Where a secret is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// OS keychain (macOS Keychain, Windows Credential Manager).
+    Keychain,
+    /// Encrypted file in the secrets directory.
+    File,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Keychain => write!(f, "keychain"),
+            Backend::File => write!(f, "encrypted file"),
+        }
+    }
+}
+
+/// A stored secret's name and location (never its value).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretEntry {
+    pub name: String,
+    pub backend: Backend,
+}
+
+/// Stores secrets in the OS keychain, falling back to an encrypted file.
+#[derive(Debug, Clone)]
+pub struct SecretStore {
+    file: FileStore,
+    use_keychain: bool,
+}
+
+impl SecretStore {
+    /// Open the store in `dir`, using the OS keychain when available.
+    ///
+    /// Set `COMMANDER_SECRETS_BACKEND=file` to keep everything in the
+    /// encrypted file.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let forced_file = std::env::var(BACKEND_ENV).is_ok_and(|v| v.eq_ignore_ascii_case("file"));
+        Self {
+            file: FileStore::new(dir),
+            use_keychain: keychain::available() && !forced_file,
+        }
+    }
+
+    /// Open the store in `dir` using only the encrypted file.
+    pub fn file_only(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            file: FileStore::new(dir),
+            use_keychain: false,
+        }
+    }
+
+    /// Store a secret, replacing any previous value.
+    ///
+    /// Returns the backend it was written to.
+    pub fn set(&self, name: &str, value: &str) -> Result<Backend> {
+        validate_name(name)?;
+        let mut file = self.file.load()?;
+
+        let backend = if self.use_keychain {
+            match keychain::set(name, value) {
+                Ok(()) => Backend::Keychain,
+                Err(e) => {
+                    warn!(name = %name, error = %e, "Keychain unavailable, using encrypted file");
+                    Backend::File
+                }
+            }
+        } else {
+            Backend::File
+        };
+
+        match backend {
+            Backend::Keychain => {
+                file.encrypted.remove(name);
+                if !file.keychain.iter().any(|n| n == name) {
+                    file.keychain.push(name.to_string());
+                    file.keychain.sort();
+                }
+            }
+            Backend::File => {
+                file.keychain.retain(|n| n != name);
+                file.encrypted.insert(name.to_string(), self.file.encrypt(name, value)?);
+            }
+        }
+
+        self.file.save(&file)?;
+        debug!(name = %name, backend = %backend, "Stored secret");
+        Ok(backend)
+    }
+
+    /// Read a secret, if stored.
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        validate_name(name)?;
+        let file = self.file.load()?;
+
+        if self.use_keychain && file.keychain.iter().any(|n| n == name) {
+            if let Some(value) = keychain::get(name)? {
+                return Ok(Some(value));
+            }
+        }
+
+        file.encrypted
+            .get(name)
+            .map(|encoded| self.file.decrypt(name, encoded))
+            .transpose()
+    }
+
+    /// Remove a secret. Returns whether anything was removed.
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        validate_name(name)?;
+        let mut file = self.file.load()?;
+
+        let mut removed = file.encrypted.remove(name).is_some();
+        if file.keychain.iter().any(|n| n == name) {
+            file.keychain.retain(|n| n != name);
+            if self.use_keychain {
+                keychain::delete(name)?;
+            }
+            removed = true;
+        }
+
+        if removed {
+            self.file.save(&file)?;
+        }
+        Ok(removed)
+    }
+
+    /// List stored secrets, sorted by name.
+    pub fn list(&self) -> Result<Vec<SecretEntry>> {
+        let file = self.file.load()?;
+        let mut entries: Vec<SecretEntry> = file
+            .keychain
+            .into_iter()
+            .map(|name| SecretEntry { name, backend: Backend::Keychain })
+            .chain(
+                file.encrypted
+                    .into_keys()
+                    .map(|name| SecretEntry { name, backend: Backend::File }),
+            )
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Read a secret, falling back to the environment variable of the same name.
+    ///
+    /// Store errors are logged and treated as missing.
+    pub fn get_or_env(&self, name: &str) -> Option<String> {
+        match self.get(name) {
+            Ok(Some(value)) => return Some(value),
+            Ok(None) => {}
+            Err(e) => warn!(name = %name, error = %e, "Failed to read secret, falling back to env"),
+        }
+        std::env::var(name).ok().filter(|v| !v.is_empty())
+    }
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        return Err(SecretsError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::file_only(dir.path());
+
+        assert_eq!(store.get("OPENROUTER_API_KEY").unwrap(), None);
+        assert_eq!(store.set("OPENROUTER_API_KEY", "sk-or-1").unwrap(), Backend::File);
+        store.set("OPENROUTER_API_KEY", "sk-or-2").unwrap();
+        assert_eq!(store.get("OPENROUTER_API_KEY").unwrap().as_deref(), Some("sk-or-2"));
+
+        let raw = std::fs::read_to_string(dir.path().join(crate::SECRETS_FILE)).unwrap();
+        assert!(!raw.contains("sk-or-2"));
+
+        assert!(store.delete("OPENROUTER_API_KEY").unwrap());
+        assert!(!store.delete("OPENROUTER_API_KEY").unwrap());
+        assert_eq!(store.get("OPENROUTER_API_KEY").unwrap(), None);
+    }
+
+    #[test]
+    fn test_list_is_sorted_and_hides_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::file_only(dir.path());
+        store.set("TELEGRAM_BOT_TOKEN", "123:abc").unwrap();
+        store.set("OPENROUTER_API_KEY", "sk").unwrap();
+
+        let names: Vec<String> = store.list().unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["OPENROUTER_API_KEY", "TELEGRAM_BOT_TOKEN"]);
+    }
+
+    #[test]
+    fn test_get_or_env_prefers_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::file_only(dir.path());
+        let name = "COMMANDER_SECRETS_TEST_VALUE";
+
+        std::env::set_var(name, "from-env");
+        assert_eq!(store.get_or_env(name).as_deref(), Some("from-env"));
+
+        store.set(name, "from-store").unwrap();
+        assert_eq!(store.get_or_env(name).as_deref(), Some("from-store"));
+        std::env::remove_var(name);
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::file_only(dir.path());
+        assert!(matches!(store.set("bad name", "x"), Err(SecretsError::InvalidName(_))));
+    }
+}
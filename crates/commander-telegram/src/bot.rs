@@ -1,20 +1,24 @@
 //! Main Telegram bot implementation.
 
+use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
 
 use commander_core::options::{DetectedOptions, OptionDetector, OptionFormat};
 use teloxide::dispatching::UpdateFilterExt;
 use teloxide::prelude::*;
-use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, InputFile};
+use teloxide::update_listeners::webhooks;
 use tokio::sync::mpsc;
 use tokio::time::interval;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::error::{Result, TelegramError};
 use crate::features::{apply_expandable_blockquotes, split_message, FeatureSet, EFFECT_ID_CONFETTI};
-use crate::handlers::{handle_callback, handle_command, handle_message, Command};
-use crate::ngrok::NgrokTunnel;
+use crate::heartbeat::{HeartbeatStats, HeartbeatTask};
+use crate::handlers::{handle_callback, handle_command, handle_message, handle_voice, menu_commands, Command};
+use crate::tunnel::{start_tunnel, Tunnel};
+use crate::progress::WorkingMessage;
 use crate::state::{create_shared_state, PollResult, TelegramState};
 
 
@@ -33,8 +37,8 @@ pub struct TelegramBot {
     bot: Bot,
     /// Shared state across handlers.
     state: Arc<TelegramState>,
-    /// ngrok tunnel for webhook.
-    ngrok: Option<NgrokTunnel>,
+    /// Tunnel exposing the webhook.
+    tunnel: Option<Box<dyn Tunnel>>,
     /// Webhook port.
     webhook_port: u16,
     /// Shutdown signal sender.
@@ -44,10 +48,10 @@ pub struct TelegramBot {
 impl TelegramBot {
     /// Create a new TelegramBot instance.
     ///
-    /// Requires `TELEGRAM_BOT_TOKEN` environment variable to be set.
+    /// Requires `TELEGRAM_BOT_TOKEN` in the secret store or environment.
     pub fn new(state_dir: &std::path::Path) -> Result<Self> {
-        let token = std::env::var("TELEGRAM_BOT_TOKEN")
-            .map_err(|_| TelegramError::NoToken)?;
+        let token = commander_core::get_secret(commander_core::secrets::TELEGRAM_BOT_TOKEN)
+            .ok_or(TelegramError::NoToken)?;
 
         let webhook_port = std::env::var("TELEGRAM_WEBHOOK_PORT")
             .ok()
@@ -70,7 +74,7 @@ impl TelegramBot {
         Ok(Self {
             bot,
             state,
-            ngrok: None,
+            tunnel: None,
             webhook_port,
             shutdown_tx: None,
         })
@@ -78,8 +82,8 @@ impl TelegramBot {
 
     /// Create a TelegramBot with custom state (for testing).
     pub fn with_state(state: Arc<TelegramState>) -> Result<Self> {
-        let token = std::env::var("TELEGRAM_BOT_TOKEN")
-            .map_err(|_| TelegramError::NoToken)?;
+        let token = commander_core::get_secret(commander_core::secrets::TELEGRAM_BOT_TOKEN)
+            .ok_or(TelegramError::NoToken)?;
 
         let webhook_port = std::env::var("TELEGRAM_WEBHOOK_PORT")
             .ok()
@@ -91,7 +95,7 @@ impl TelegramBot {
         Ok(Self {
             bot,
             state,
-            ngrok: None,
+            tunnel: None,
             webhook_port,
             shutdown_tx: None,
         })
@@ -104,27 +108,59 @@ impl TelegramBot {
         Ok(me.username().to_string())
     }
 
-    /// Start the bot with ngrok webhook.
+    /// Start the bot in webhook mode, behind the tunnel configured in
+    /// `[telegram.tunnel]`.
     pub async fn start(&mut self) -> Result<()> {
         info!("Starting Telegram bot with webhook...");
 
-        // Start ngrok tunnel
-        info!(port = self.webhook_port, "Starting ngrok tunnel");
-        let ngrok = NgrokTunnel::start(self.webhook_port).await?;
-        let webhook_url = format!("{}/webhook", ngrok.public_url());
-        info!(url = %webhook_url, "Webhook URL ready");
-
-        self.ngrok = Some(ngrok);
-
-        // For webhook mode, we'd use axum with teloxide's webhook adapter
-        // For now, fall back to polling mode with a note
-        warn!("Webhook mode requires additional setup. Starting in polling mode instead.");
-        self.start_polling().await
+        let settings = commander_core::Settings::load(&commander_core::config_file())
+            .map_err(|e| TelegramError::BotStartFailed(e.to_string()))?
+            .telegram
+            .tunnel;
+        let tunnel = start_tunnel(&settings, self.webhook_port).await?;
+        let webhook_url = format!("{}/webhook", tunnel.public_url());
+        info!(tunnel = tunnel.name(), url = %webhook_url, "Webhook URL ready");
+        let url = webhook_url
+            .parse()
+            .map_err(|e| TelegramError::WebhookFailed(format!("Invalid webhook URL {}: {}", webhook_url, e)))?;
+        self.tunnel = Some(tunnel);
+
+        // A reverse proxy may run on another host; tunnels connect locally.
+        let ip = if settings.backend == commander_core::TunnelBackend::Proxy {
+            Ipv4Addr::UNSPECIFIED
+        } else {
+            Ipv4Addr::LOCALHOST
+        };
+        let mut options = webhooks::Options::new(SocketAddr::from((ip, self.webhook_port)), url)
+            .path("/webhook".to_string());
+        if let Some(certificate) = &settings.certificate {
+            let path = shellexpand::tilde(&certificate.to_string_lossy()).into_owned();
+            options = options.certificate(InputFile::file(path));
+        }
+        self.run(Some(options)).await
     }
 
-    /// Start the bot in polling mode (simpler, no ngrok needed).
+    /// Start the bot in polling mode (simpler, no tunnel needed).
     pub async fn start_polling(&self) -> Result<()> {
         info!("Starting Telegram bot in polling mode...");
+        self.run(None).await
+    }
+
+    /// Run the bot, receiving updates through `webhook` or by polling.
+    async fn run(&self, webhook: Option<webhooks::Options>) -> Result<()> {
+        // Report liveness from the start, so a bot stuck starting up goes stale
+        let stats = Arc::new(HeartbeatStats::default());
+        let _heartbeat = HeartbeatTask::spawn(Arc::clone(&stats));
+        commander_core::connectivity::start_monitor();
+
+        let listener = match webhook {
+            Some(options) => Some(
+                webhooks::axum(self.bot.clone(), options)
+                    .await
+                    .map_err(|e| TelegramError::WebhookFailed(e.to_string()))?,
+            ),
+            None => None,
+        };
 
         // Check for rebuild and restore sessions
         let (is_rebuild, is_first_start, start_count) = crate::version::check_rebuild();
@@ -166,6 +202,10 @@ impl TelegramBot {
         match self.bot.get_me().await {
             Ok(me) => {
                 info!(username = %me.username(), "Bot identity cached");
+                // Lets the CLI and TUI offer one-tap pairing links
+                if let Err(e) = commander_core::pairing::save_bot_username(me.username()) {
+                    warn!(error = %e, "Failed to record bot username");
+                }
                 self.state.set_bot_info(me).await;
             }
             Err(e) => {
@@ -173,6 +213,11 @@ impl TelegramBot {
             }
         }
 
+        // Register the command menu shown by Telegram clients
+        if let Err(e) = self.bot.set_my_commands(menu_commands()).await {
+            warn!(error = %e, "Failed to register bot commands");
+        }
+
         let bot = self.bot.clone();
         let state = Arc::clone(&self.state);
 
@@ -190,6 +235,19 @@ impl TelegramBot {
             poll_notifications_loop(notify_bot, notify_state).await;
         });
 
+        // Start the approval queue polling task
+        let approvals_state = Arc::clone(&self.state);
+        let approvals_bot = bot.clone();
+        tokio::spawn(async move {
+            poll_approvals_loop(approvals_bot, approvals_state).await;
+        });
+
+        // Start the config reload task
+        let config_state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            poll_config_loop(config_state).await;
+        });
+
         // Start the API health polling task (detects server rebuilds/restarts)
         let health_state = Arc::clone(&self.state);
         let health_bot = bot.clone();
@@ -201,8 +259,11 @@ impl TelegramBot {
         let state_for_commands = Arc::clone(&state);
         let state_for_messages = Arc::clone(&state);
         let state_for_callbacks = Arc::clone(&state);
+        let state_for_voice = Arc::clone(&state);
 
+        let update_stats = Arc::clone(&stats);
         let handler = dptree::entry()
+            .inspect(move |_: Update| update_stats.record_update())
             .branch(
                 Update::filter_callback_query()
                     .endpoint(move |bot: Bot, q: teloxide::types::CallbackQuery| {
@@ -257,22 +318,47 @@ impl TelegramBot {
                         info!(chat_id = %msg.chat.id, text = ?msg.text(), "Regular message received");
                         async move { handle_message(bot, msg, state).await }
                     }),
+            )
+            .branch(
+                Update::filter_message()
+                    .filter(|msg: Message| msg.voice().is_some())
+                    .endpoint(move |bot: Bot, msg: Message| {
+                        let state = Arc::clone(&state_for_voice);
+                        info!(chat_id = %msg.chat.id, "Voice message received");
+                        async move { handle_voice(bot, msg, state).await }
+                    }),
             );
 
         info!("Bot is running! Send /start to begin.");
 
         // Build dispatcher with error handler
-        Dispatcher::builder(bot, handler)
+        let dispatch_stats = Arc::clone(&stats);
+        let mut dispatcher = Dispatcher::builder(bot, handler)
             .default_handler(|upd| async move {
                 warn!("Unhandled update: {:?}", upd);
             })
-            .error_handler(teloxide::error_handlers::LoggingErrorHandler::with_custom_text(
-                "An error occurred in the dispatcher"
-            ))
+            .error_handler(Arc::new(move |e: teloxide::RequestError| {
+                error!(error = %e, "An error occurred in the dispatcher");
+                dispatch_stats.record_error(e);
+                async {}
+            }))
             .enable_ctrlc_handler()
-            .build()
-            .dispatch()
-            .await;
+            .build();
+        match listener {
+            Some(listener) => {
+                let listener_stats = Arc::clone(&stats);
+                dispatcher
+                    .dispatch_with_listener(
+                        listener,
+                        Arc::new(move |e: std::convert::Infallible| {
+                            listener_stats.record_error(e);
+                            async {}
+                        }),
+                    )
+                    .await
+            }
+            None => dispatcher.dispatch().await,
+        }
 
         Ok(())
     }
@@ -286,18 +372,18 @@ impl TelegramBot {
             let _ = tx.send(()).await;
         }
 
-        // Stop ngrok
-        if let Some(mut ngrok) = self.ngrok.take() {
-            ngrok.stop()?;
+        // Stop the tunnel
+        if let Some(mut tunnel) = self.tunnel.take() {
+            tunnel.stop()?;
         }
 
         info!("Bot stopped");
         Ok(())
     }
 
-    /// Get the current webhook URL (if running with ngrok).
+    /// Get the current webhook URL (if running in webhook mode).
     pub fn webhook_url(&self) -> Option<String> {
-        self.ngrok.as_ref().map(|n| format!("{}/webhook", n.public_url()))
+        self.tunnel.as_ref().map(|t| format!("{}/webhook", t.public_url()))
     }
 }
 
@@ -427,6 +513,28 @@ async fn add_reaction(bot: &Bot, chat_id: teloxide::types::ChatId, message_id: t
 }
 
 /// Background task to poll for output from connected sessions and send responses.
+/// Send an error diagnosis card with Apply/Ignore buttons.
+#[cfg(feature = "agents")]
+async fn send_diagnosis_card(
+    bot: &Bot,
+    chat_id: ChatId,
+    thread_id: Option<teloxide::types::ThreadId>,
+    diagnosis: &commander_core::ErrorDiagnosis,
+) {
+    use crate::handlers::{diagnosis_keyboard, format_diagnosis};
+
+    let mut req = bot
+        .send_message(chat_id, format_diagnosis(diagnosis))
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .reply_markup(diagnosis_keyboard(&diagnosis.id));
+    if let Some(tid) = thread_id {
+        req = req.message_thread_id(tid);
+    }
+    if let Err(e) = req.await {
+        warn!(chat_id = %chat_id.0, error = %e, "Failed to send diagnosis card");
+    }
+}
+
 async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
     use teloxide::types::{LinkPreviewOptions, MessageId, ReplyParameters};
     use std::collections::HashMap;
@@ -434,10 +542,8 @@ async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
     let mut poll_interval = interval(Duration::from_millis(POLL_INTERVAL_MS));
     let typing_throttle = state.typing_throttle.clone();
 
-    // Track progress message IDs per session key
-    let mut progress_messages: HashMap<i64, MessageId> = HashMap::new();
-    // Track summary message IDs per session key
-    let mut summary_messages: HashMap<i64, MessageId> = HashMap::new();
+    // Track the working message (progress milestones + running summary) per session key
+    let mut working_messages: HashMap<i64, WorkingMessage> = HashMap::new();
     // Track last selector hash per session to avoid re-sending the same prompt every poll
     let mut last_selector_hashes: HashMap<i64, u64> = HashMap::new();
     // Track selector message IDs so we can delete them when the selector disappears
@@ -472,100 +578,22 @@ async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
 
             match poll_result {
                 Ok(PollResult::Progress(progress_msg)) => {
-                    if let Some(&msg_id) = progress_messages.get(&session_key) {
-                        // Update existing progress message silently (ignore errors — may have been deleted).
-                        let _ = bot.edit_message_text(chat_id, msg_id, &progress_msg).await;
-                    } else {
-                        // Send new progress message: silent + no link preview.
-                        let mut req = bot.send_message(chat_id, &progress_msg)
-                            .disable_notification(true)
-                            .link_preview_options(no_preview.clone());
-                        if let Some(tid) = thread_id {
-                            req = req.message_thread_id(tid);
-                        }
-                        match req.await {
-                            Ok(sent) => {
-                                progress_messages.insert(session_key, sent.id);
-                            }
-                            Err(e) => {
-                                warn!(chat_id = %chat_id.0, error = %e, "Failed to send progress message");
-                            }
-                        }
-                    }
+                    working_messages.entry(session_key).or_default().set_progress(progress_msg);
                 }
-                Ok(PollResult::IncrementalSummary(summary)) => {
-                    if let Some(&msg_id) = summary_messages.get(&session_key) {
-                        if let Err(e) = bot.edit_message_text(chat_id, msg_id, &summary).await {
-                            warn!(chat_id = %chat_id.0, error = %e, "Failed to update incremental summary");
-                        } else {
-                            info!(chat_id = %chat_id.0, "Incremental summary updated");
-                        }
-                    } else {
-                        let mut req = bot.send_message(chat_id, &summary)
-                            .disable_notification(true)
-                            .link_preview_options(no_preview.clone());
-                        if let Some(tid) = thread_id {
-                            req = req.message_thread_id(tid);
-                        }
-                        match req.await {
-                            Ok(sent) => {
-                                summary_messages.insert(session_key, sent.id);
-                                info!(chat_id = %chat_id.0, "Incremental summary sent");
-                            }
-                            Err(e) => {
-                                warn!(chat_id = %chat_id.0, error = %e, "Failed to send incremental summary");
-                            }
-                        }
-                    }
-                }
-                Ok(PollResult::ProgressiveSummary(summary)) => {
-                    if let Some(&msg_id) = summary_messages.get(&session_key) {
-                        let _ = bot.edit_message_text(chat_id, msg_id, &summary).await;
-                    } else {
-                        let mut req = bot.send_message(chat_id, &summary)
-                            .disable_notification(true)
-                            .link_preview_options(no_preview.clone());
-                        if let Some(tid) = thread_id {
-                            req = req.message_thread_id(tid);
-                        }
-                        match req.await {
-                            Ok(sent) => {
-                                summary_messages.insert(session_key, sent.id);
-                            }
-                            Err(e) => {
-                                warn!(chat_id = %chat_id.0, error = %e, "Failed to send progressive summary");
-                            }
-                        }
-                    }
+                Ok(PollResult::IncrementalSummary(summary)) | Ok(PollResult::ProgressiveSummary(summary)) => {
+                    working_messages.entry(session_key).or_default().set_summary(summary);
                 }
                 Ok(PollResult::Summarizing) => {
-                    let summarizing_msg = "🤖 Summarizing output...";
-                    if let Some(&msg_id) = progress_messages.get(&session_key) {
-                        let _ = bot.edit_message_text(chat_id, msg_id, summarizing_msg).await;
-                    } else {
-                        let mut req = bot.send_message(chat_id, summarizing_msg)
-                            .disable_notification(true)
-                            .link_preview_options(no_preview.clone());
-                        if let Some(tid) = thread_id {
-                            req = req.message_thread_id(tid);
-                        }
-                        match req.await {
-                            Ok(sent) => {
-                                progress_messages.insert(session_key, sent.id);
-                            }
-                            Err(e) => {
-                                warn!(chat_id = %chat_id.0, error = %e, "Failed to send summarizing message");
-                            }
-                        }
-                    }
+                    working_messages.entry(session_key).or_default().set_progress("🤖 Summarizing output...");
                 }
                 Ok(PollResult::Complete(mut response, message_id, response_thread_id)) => {
-                    // Delete progress and summary messages.
-                    if let Some(prog_msg_id) = progress_messages.remove(&session_key) {
-                        let _ = bot.delete_message(chat_id, prog_msg_id).await;
-                    }
-                    // Keep incremental summary messages in chat — they're useful history.
-                    summary_messages.remove(&session_key);
+                    state.record_handoff_response(session_key, &response).await;
+                    state.mark_seen(session_key).await;
+
+                    // The working message is finalized with the response below.
+                    let working_msg_id = working_messages
+                        .remove(&session_key)
+                        .and_then(|working| working.message_id);
                     // Clear selector state when session completes
                     last_selector_hashes.remove(&session_key);
                     if let Some(sel_msg_id) = selector_messages.remove(&session_key) {
@@ -605,19 +633,41 @@ async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
                     let reply_params = message_id.map(ReplyParameters::new);
                     let effect_id = if features.use_message_effects { Some(EFFECT_ID_CONFETTI) } else { None };
 
-                    // Send (possibly split) final response.
-                    let send_result = send_long_message(
-                        &bot,
-                        chat_id,
-                        &response,
-                        teloxide::types::ParseMode::Html,
-                        target_thread_id,
-                        reply_params,
-                        keyboard,
-                        false, // final response is not silent
-                        effect_id,
-                        features.max_message_length,
-                    ).await;
+                    // Finalize the working message in place when the response fits in it;
+                    // otherwise replace it with the (possibly split) final response.
+                    let fits = split_message(&response, features.max_message_length).len() == 1;
+                    let finalized = match working_msg_id {
+                        Some(msg_id) if fits && keyboard.is_none() => bot
+                            .edit_message_text(chat_id, msg_id, &response)
+                            .parse_mode(teloxide::types::ParseMode::Html)
+                            .link_preview_options(no_preview.clone())
+                            .await
+                            .map(|_| msg_id)
+                            .map_err(|e| warn!(chat_id = %chat_id.0, error = %e, "Failed to finalize working message"))
+                            .ok(),
+                        _ => None,
+                    };
+                    if finalized.is_none() {
+                        if let Some(msg_id) = working_msg_id {
+                            let _ = bot.delete_message(chat_id, msg_id).await;
+                        }
+                    }
+
+                    let send_result = match finalized {
+                        Some(msg_id) => Ok(vec![msg_id]),
+                        None => send_long_message(
+                            &bot,
+                            chat_id,
+                            &response,
+                            teloxide::types::ParseMode::Html,
+                            target_thread_id,
+                            reply_params,
+                            keyboard,
+                            false, // final response is not silent
+                            effect_id,
+                            features.max_message_length,
+                        ).await,
+                    };
 
                     match send_result {
                         Ok(sent_ids) => {
@@ -632,19 +682,28 @@ async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
                                 }
                             }
 
-                            // If @-addressed, record ALL sent message IDs so the user can
-                            // reply to any visible chunk and still be routed to the same session.
+                            // Record ALL sent message IDs so the user can reply to any
+                            // visible chunk and be routed to the session that wrote it,
+                            // even after connecting elsewhere. Topics route by thread.
+                            let reply_session = match (&at_session_name, target_thread_id) {
+                                (Some(name), _) => Some(name.clone()),
+                                (None, None) => state.get_session_info(chat_id).await.map(|(name, _)| name),
+                                (None, Some(_)) => None,
+                            };
                             debug!(
                                 chat_id = %chat_id.0,
                                 sent_ids = ?sent_ids,
-                                at_session = ?at_session_name,
+                                reply_session = ?reply_session,
                                 "Attempted record_at_reply"
                             );
-                            if let Some(session_name) = &at_session_name {
+                            if let Some(session_name) = &reply_session {
                                 for msg_id in &sent_ids {
                                     state.record_at_reply(chat_id.0, *msg_id, session_name.clone()).await;
                                 }
                             }
+                            if at_session_name.is_some() && thread_id.is_none() {
+                                state.return_from_at_session(chat_id).await;
+                            }
 
                             if detected_options.is_some() {
                                 info!(chat_id = %chat_id.0, thread_id = ?target_thread_id, "Response with options sent to user");
@@ -663,6 +722,17 @@ async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
                             warn!(chat_id = %chat_id.0, error = %e, "Failed to send response");
                         }
                     }
+
+                    // Diagnose an error in the response and offer its fix, off the poll loop.
+                    #[cfg(feature = "agents")]
+                    {
+                        let (bot, state) = (bot.clone(), Arc::clone(&state));
+                        tokio::spawn(async move {
+                            if let Some(diagnosis) = state.diagnose_response(session_key).await {
+                                send_diagnosis_card(&bot, chat_id, target_thread_id, &diagnosis).await;
+                            }
+                        });
+                    }
                 }
                 Ok(PollResult::SelectorDetected(selector)) => {
                     // Deduplicate: only send when question/options change — not on every poll.
@@ -738,12 +808,17 @@ async fn poll_output_loop(bot: Bot, state: Arc<TelegramState>) {
                 Err(e) => {
                     warn!(chat_id = %chat_id.0, error = %e, "Error polling output");
 
-                    // Clean up progress message on error.
-                    if let Some(prog_msg_id) = progress_messages.remove(&session_key) {
-                        let _ = bot.delete_message(chat_id, prog_msg_id).await;
+                    // Clean up the working message on error.
+                    if let Some(msg_id) = working_messages.remove(&session_key).and_then(|w| w.message_id) {
+                        let _ = bot.delete_message(chat_id, msg_id).await;
                     }
                 }
             }
+
+            // Send or edit the working message, at most once per MIN_EDIT_INTERVAL.
+            if let Some(working) = working_messages.get_mut(&session_key) {
+                working.flush(&bot, chat_id, thread_id).await;
+            }
         }
     }
 }
@@ -820,8 +895,12 @@ async fn poll_api_health_loop(bot: Bot, state: Arc<TelegramState>) {
 }
 
 /// Background task to poll for cross-channel notifications and broadcast to authorized users.
+///
+/// During do-not-disturb, notifications other than critical ones are held and
+/// sent as one digest once it ends.
 async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
     use teloxide::types::ChatId;
+    use commander_core::dnd::{self, TELEGRAM_CHANNEL};
     use crate::notifications::{get_unread_notifications, mark_notifications_read};
 
     let mut poll_interval = interval(Duration::from_millis(NOTIFICATION_POLL_INTERVAL_MS));
@@ -829,6 +908,15 @@ async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
     loop {
         poll_interval.tick().await;
 
+        let dnd_settings = commander_core::Settings::load(&commander_core::config_file())
+            .unwrap_or_default()
+            .notifications
+            .dnd;
+        let quiet = dnd::is_quiet(&dnd_settings, TELEGRAM_CHANNEL);
+        if !quiet {
+            send_dnd_digest(&bot, &state).await;
+        }
+
         // Get unread notifications for the telegram channel
         let notifications = get_unread_notifications("telegram");
         if notifications.is_empty() {
@@ -852,6 +940,16 @@ async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
         // No LLM summarization needed - it only introduces preamble bleeding.
         let mut sent_ids = Vec::new();
         for notification in &notifications {
+            if quiet && !(notification.critical && dnd_settings.critical_breaks_through) {
+                if let Err(e) = dnd::hold(TELEGRAM_CHANNEL, &notification.message, notification.session.clone()) {
+                    warn!(error = %e, "Failed to hold notification");
+                    continue;
+                }
+                debug!(notification_id = %notification.id, "Notification held for do-not-disturb");
+                sent_ids.push(notification.id.clone());
+                continue;
+            }
+
             // Build notification message with deep link if session is specified
             let mut message = notification.message.clone();
             if let Some(session) = &notification.session {
@@ -876,8 +974,14 @@ async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
             }
 
             for &chat_id in &authorized_chats {
+                // Post into the session's forum topic when the chat has one
+                let thread_id = match &notification.session {
+                    Some(session) => state.topic_thread_for_session(chat_id, session).await,
+                    None => None,
+                };
+
                 // Skip notification if it's for the session the user is currently connected to
-                if let Some(ref notification_session) = notification.session {
+                if let (Some(notification_session), None) = (&notification.session, thread_id) {
                     if let Some(current_session) = state.get_current_tmux_session(chat_id).await {
                         if &current_session == notification_session {
                             debug!(
@@ -890,7 +994,7 @@ async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
                     }
                 }
 
-                let req = bot.send_message(ChatId(chat_id), &message)
+                let mut req = bot.send_message(ChatId(chat_id), &message)
                     .parse_mode(teloxide::types::ParseMode::Html)
                     .link_preview_options(teloxide::types::LinkPreviewOptions {
                         is_disabled: true,
@@ -899,10 +1003,19 @@ async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
                         prefer_large_media: false,
                         show_above_text: false,
                     });
-                if let Err(e) = req.await {
-                    warn!(chat_id = %chat_id, error = %e, "Failed to send notification");
-                } else {
-                    info!(chat_id = %chat_id, notification_id = %notification.id, "Notification sent");
+                if let Some(tid) = thread_id {
+                    req = req.message_thread_id(tid);
+                }
+                match req.await {
+                    Ok(sent) => {
+                        info!(chat_id = %chat_id, thread_id = ?thread_id, notification_id = %notification.id, "Notification sent");
+                        // Replying to the notification talks to its session
+                        if let (Some(session), None) = (&notification.session, thread_id) {
+                            let name = session.strip_prefix("commander-").unwrap_or(session);
+                            state.record_at_reply(chat_id, sent.id, name.to_string()).await;
+                        }
+                    }
+                    Err(e) => warn!(chat_id = %chat_id, error = %e, "Failed to send notification"),
                 }
             }
             sent_ids.push(notification.id.clone());
@@ -915,6 +1028,109 @@ async fn poll_notifications_loop(bot: Bot, state: Arc<TelegramState>) {
     }
 }
 
+/// Send the notifications held during do-not-disturb to authorized users.
+async fn send_dnd_digest(bot: &Bot, state: &TelegramState) {
+    use teloxide::types::ChatId;
+    use commander_core::dnd::{self, TELEGRAM_CHANNEL};
+
+    let authorized_chats = state.get_authorized_chat_ids().await;
+    if authorized_chats.is_empty() {
+        return;
+    }
+    let held = match dnd::take_held(TELEGRAM_CHANNEL) {
+        Ok(held) if !held.is_empty() => held,
+        Ok(_) => return,
+        Err(e) => {
+            warn!(error = %e, "Failed to take held notifications");
+            return;
+        }
+    };
+
+    let digest = dnd::format_digest(&held);
+    for chat_id in authorized_chats {
+        if let Err(e) = bot.send_message(ChatId(chat_id), &digest).await {
+            warn!(chat_id = %chat_id, error = %e, "Failed to send do-not-disturb digest");
+        }
+    }
+    info!(count = held.len(), "Sent do-not-disturb digest");
+}
+
+/// Background task announcing queued approval requests to authorized users,
+/// with Approve/Deny buttons.
+async fn poll_approvals_loop(bot: Bot, state: Arc<TelegramState>) {
+    use teloxide::types::ChatId;
+    use commander_core::approvals::{mark_approvals_announced, unannounced_approvals};
+    use crate::handlers::{approval_keyboard, format_approval_request};
+
+    let mut poll_interval = interval(Duration::from_millis(NOTIFICATION_POLL_INTERVAL_MS));
+
+    loop {
+        poll_interval.tick().await;
+
+        let requests = unannounced_approvals("telegram");
+        if requests.is_empty() {
+            continue;
+        }
+
+        // Without authorized users the requests stay unannounced until
+        // someone pairs; /approvals lists them too.
+        let authorized_chats = state.get_authorized_chat_ids().await;
+        if authorized_chats.is_empty() {
+            continue;
+        }
+
+        for request in &requests {
+            for &chat_id in &authorized_chats {
+                let mut req = bot
+                    .send_message(ChatId(chat_id), format_approval_request(request))
+                    .parse_mode(teloxide::types::ParseMode::Html)
+                    .reply_markup(approval_keyboard(&request.id));
+                // Post into the project's forum topic when the chat has one
+                if let Some(tid) = state.topic_thread_for_session(chat_id, &request.project).await {
+                    req = req.message_thread_id(tid);
+                }
+                if let Err(e) = req.await {
+                    warn!(chat_id = %chat_id, error = %e, "Failed to send approval request");
+                } else {
+                    info!(chat_id = %chat_id, request = %request.id, "Approval request sent");
+                }
+            }
+        }
+
+        let ids: Vec<String> = requests.into_iter().map(|r| r.id).collect();
+        if let Err(e) = mark_approvals_announced("telegram", &ids) {
+            warn!(error = %e, "Failed to mark approval requests announced");
+        }
+    }
+}
+
+/// Background task applying `config.toml` changes: the summarizer model, voice
+/// transcription and, with agents enabled, the orchestrator's models.
+async fn poll_config_loop(state: Arc<TelegramState>) {
+    use commander_core::{set_summarizer_model, subscribe_config_changes, ConfigChange};
+
+    let changes = subscribe_config_changes();
+    let mut poll_interval = interval(commander_core::settings::CONFIG_POLL_INTERVAL);
+
+    loop {
+        poll_interval.tick().await;
+
+        let pending: Vec<ConfigChange> = changes.try_iter().collect();
+        for change in pending {
+            match change {
+                ConfigChange::Telegram(settings) => {
+                    info!(model = ?settings.summarizer_model, "Summarizer model changed");
+                    set_summarizer_model(settings.summarizer_model);
+                    state.set_voice_settings(settings.voice).await;
+                }
+                #[cfg(feature = "agents")]
+                ConfigChange::Agents(settings) => state.apply_agent_settings(&settings).await,
+                _ => {}
+            }
+        }
+    }
+}
+
 /// Send per-session restart notification to each restored session's user.
 async fn send_restart_notification(bot: Bot, state: Arc<TelegramState>) {
     use teloxide::types::{ChatId, ParseMode, ThreadId};
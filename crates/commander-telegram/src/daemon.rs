@@ -15,6 +15,8 @@ use commander_core::config;
 pub enum DaemonError {
     #[error("Failed to start daemon: {0}")]
     StartFailed(String),
+    #[error("{0}")]
+    NoToken(String),
     #[error("Failed to stop daemon: {0}")]
     StopFailed(String),
     #[error("Daemon not running")]
@@ -23,6 +25,17 @@ pub enum DaemonError {
     Io(#[from] std::io::Error),
 }
 
+impl From<DaemonError> for commander_core::CommanderError {
+    fn from(err: DaemonError) -> Self {
+        use commander_core::CommanderError;
+        match err {
+            DaemonError::NoToken(_) => CommanderError::Config(err.to_string()),
+            DaemonError::Io(e) => e.into(),
+            _ => CommanderError::Runtime(err.to_string()),
+        }
+    }
+}
+
 /// Result of starting the Telegram bot.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StartResult {
@@ -52,7 +65,7 @@ pub fn is_running() -> bool {
 }
 
 /// Check if a process with the given PID is running (cross-platform).
-fn is_process_running(pid: u32) -> bool {
+pub(crate) fn is_process_running(pid: u32) -> bool {
     #[cfg(unix)]
     {
         // kill -0 checks if process exists without sending signal
@@ -97,9 +110,9 @@ pub fn start() -> Result<u32, DaemonError> {
     let _ = dotenvy::from_filename(".env.local");
 
     // Check for TELEGRAM_BOT_TOKEN
-    if std::env::var("TELEGRAM_BOT_TOKEN").is_err() {
-        return Err(DaemonError::StartFailed(format!(
-            "TELEGRAM_BOT_TOKEN not set. Add it to {} or set in environment.",
+    if commander_core::get_secret(commander_core::secrets::TELEGRAM_BOT_TOKEN).is_none() {
+        return Err(DaemonError::NoToken(format!(
+            "TELEGRAM_BOT_TOKEN not set. Run `commander secrets set TELEGRAM_BOT_TOKEN`, add it to {}, or set in environment.",
             env_path.display()
         )));
     }
@@ -163,6 +176,7 @@ pub fn stop() -> Result<(), DaemonError> {
         std::thread::sleep(std::time::Duration::from_millis(100));
         if !is_process_running(pid) {
             let _ = fs::remove_file(&pid_file);
+            crate::heartbeat::clear();
             return Ok(());
         }
     }
@@ -174,8 +188,9 @@ pub fn stop() -> Result<(), DaemonError> {
     // Wait a moment for force kill to complete
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    // Clean up PID file
+    // Clean up PID and heartbeat files
     let _ = fs::remove_file(&pid_file);
+    crate::heartbeat::clear();
 
     Ok(())
 }
@@ -6,7 +6,7 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum TelegramError {
     /// Bot token not provided or invalid.
-    #[error("Telegram bot token not set. Set TELEGRAM_BOT_TOKEN environment variable.")]
+    #[error("Telegram bot token not set. Run `commander secrets set TELEGRAM_BOT_TOKEN` or set the TELEGRAM_BOT_TOKEN environment variable.")]
     NoToken,
 
     /// Failed to start the bot.
@@ -29,6 +29,10 @@ pub enum TelegramError {
     #[error("NGROK_AUTHTOKEN not set. Get a token from https://dashboard.ngrok.com/")]
     NgrokNoAuthToken,
 
+    /// Webhook tunnel error (cloudflared, tailscale, reverse proxy).
+    #[error("Tunnel error: {0}")]
+    TunnelError(String),
+
     /// Session error.
     #[error("Session error: {0}")]
     SessionError(String),
@@ -49,6 +53,10 @@ pub enum TelegramError {
     #[error("Summarization error: {0}")]
     SummarizationError(String),
 
+    /// Voice transcription error.
+    #[error("Transcription error: {0}")]
+    TranscriptionError(String),
+
     /// Invalid pairing code.
     #[error("Invalid pairing code")]
     InvalidPairingCode,
@@ -77,6 +85,34 @@ pub enum TelegramError {
 /// Result type for Telegram operations.
 pub type Result<T> = std::result::Result<T, TelegramError>;
 
+impl From<&TelegramError> for commander_core::CommanderError {
+    fn from(err: &TelegramError) -> Self {
+        use commander_core::CommanderError;
+        match err {
+            TelegramError::NoToken | TelegramError::NgrokNotFound | TelegramError::NgrokNoAuthToken => {
+                CommanderError::Config(err.to_string())
+            }
+            TelegramError::TmuxError(_) => CommanderError::Tmux(err.to_string()),
+            TelegramError::SummarizationError(_)
+            | TelegramError::TranscriptionError(_)
+            | TelegramError::HttpError(_) => CommanderError::Provider(err.to_string()),
+            TelegramError::InvalidPairingCode | TelegramError::PairingExpired | TelegramError::NotAuthorized => {
+                CommanderError::Permission(err.to_string())
+            }
+            TelegramError::IoError(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                CommanderError::Permission(err.to_string())
+            }
+            _ => CommanderError::Runtime(err.to_string()),
+        }
+    }
+}
+
+impl From<TelegramError> for commander_core::CommanderError {
+    fn from(err: TelegramError) -> Self {
+        Self::from(&err)
+    }
+}
+
 impl From<commander_tmux::TmuxError> for TelegramError {
     fn from(e: commander_tmux::TmuxError) -> Self {
         TelegramError::TmuxError(e.to_string())
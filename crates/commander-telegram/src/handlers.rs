@@ -2,8 +2,11 @@
 
 use std::sync::Arc;
 
+use commander_core::{AttachOutcome, CommanderError};
 use teloxide::prelude::*;
-use teloxide::types::{CallbackQuery, ThreadId};
+use teloxide::types::{
+    BotCommand, CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup, ReplyParameters, ThreadId,
+};
 use teloxide::utils::command::BotCommands;
 use tracing::{debug, error, info, warn};
 
@@ -39,11 +42,26 @@ pub enum Command {
     #[command(description = "Disconnect from current project")]
     Disconnect,
 
-    #[command(description = "Stop session (commits changes, ends tmux): /stop [session]")]
+    #[command(description = "Switch to another project without disconnecting this one: /use <project>")]
+    Use(String),
+
+    #[command(description = "Show which sessions this chat follows")]
+    Active,
+
+    #[command(description = "Claim the connected session from the TUI or REPL")]
+    Takeover,
+
+    #[command(description = "Stop session (commits changes, ends tmux): /stop [session] [--no-ai-commit]")]
     Stop(String),
     #[command(description = "Stop session (alias for /stop)")]
     S(String),
 
+    #[command(description = "Pause a session until /resume: /pause [project]")]
+    Pause(String),
+
+    #[command(description = "Resume a paused session: /resume [project]")]
+    Resume(String),
+
     #[command(description = "Connect with git worktree: /connect-tree <name>")]
     ConnectTree(String),
     #[command(description = "Connect with git worktree (alias for /connect-tree)")]
@@ -81,6 +99,62 @@ pub enum Command {
     Mpm,
     #[command(description = "Ask MPM a question: /ask <question>")]
     Ask(String),
+
+    #[command(description = "Approve a queued request or the command held by guardrails: /approve [id]")]
+    Approve(String),
+    #[command(description = "Deny a queued request or the command held by guardrails: /deny [id]")]
+    Deny(String),
+    #[command(description = "List tool calls waiting for approval")]
+    Approvals,
+    #[command(description = "Queue delegations and file writes for approval: /approvalmode [on|off]")]
+    ApprovalMode(String),
+
+    #[command(description = "Show summarized uncommitted changes: /diff [file]")]
+    Diff(String),
+
+    #[command(description = "Acknowledge a blocking event: /ack <event-id> (lists them without id)")]
+    Ack(String),
+
+    #[command(description = "Hold notifications for a while: /snooze 2h, /snooze off")]
+    Snooze(String),
+
+    #[command(description = "Send a snippet to the session: /run <name> [args] (or !name)")]
+    Run(String),
+
+    #[command(description = "List snippets from snippets.toml")]
+    Snippets,
+
+    #[command(description = "Re-run the failing tests in the session's output")]
+    Retest,
+}
+
+/// Reply for a failed action: what failed, the error and what to do about it.
+fn error_reply(action: &str, err: impl Into<CommanderError>) -> String {
+    format!("❌ {}: {}", action, err.into().user_message())
+}
+
+/// Commands registered with Telegram for the client's command menu.
+///
+/// Aliases are left out; they still work when typed.
+pub fn menu_commands() -> Vec<BotCommand> {
+    Command::bot_commands()
+        .into_iter()
+        .filter(|c| !c.description.contains("(alias for"))
+        .map(|c| BotCommand::new(c.command.trim_start_matches('/'), c.description))
+        .collect()
+}
+
+/// Longest name that fits a `connect:<name>` callback (Telegram allows 64 bytes).
+const MAX_CONNECT_CALLBACK_NAME: usize = 56;
+
+/// Inline keyboard connecting to one of `names`, two buttons per row.
+fn connect_menu_keyboard(names: &[String]) -> InlineKeyboardMarkup {
+    let buttons: Vec<InlineKeyboardButton> = names
+        .iter()
+        .filter(|name| name.len() <= MAX_CONNECT_CALLBACK_NAME)
+        .map(|name| InlineKeyboardButton::callback(name.clone(), format!("connect:{}", name)))
+        .collect();
+    InlineKeyboardMarkup::new(buttons.chunks(2).map(|row| row.to_vec()))
 }
 
 /// Handle the /start command with optional deep link parameter.
@@ -133,6 +207,12 @@ pub async fn handle_start(
         return handle_deep_link_stop(bot, msg, state, session_name).await;
     }
 
+    // Pairing link from the CLI or TUI: t.me/<bot>?start=<CODE>
+    if commander_core::pairing::is_pairing_code(payload) {
+        info!(chat_id = %msg.chat.id.0, "Deep link pairing attempt");
+        return handle_pair(bot, msg, state, payload.to_string()).await;
+    }
+
     // Show status if authorized
     if state.is_authorized(msg.chat.id.0).await {
         if state.get_session_info(msg.chat.id).await.is_some() {
@@ -244,7 +324,7 @@ async fn handle_deep_link_connect(
         Err(e) => {
             bot.send_message(
                 msg.chat.id,
-                format!("❌ Failed to connect to '{}': {}", session_name, e)
+                error_reply(&format!("Failed to connect to '{}'", session_name), &e)
             ).await?;
 
             error!(
@@ -342,7 +422,7 @@ async fn handle_deep_link_stop(
         Err(e) => {
             bot.send_message(
                 msg.chat.id,
-                format!("❌ Failed to stop '{}': {}", session_name, e)
+                error_reply(&format!("Failed to stop '{}'", session_name), CommanderError::Tmux(e.to_string()))
             )
             .await?;
 
@@ -547,6 +627,40 @@ fn parse_connect_args(arg: &str) -> Result<ConnectArgs, String> {
     }
 }
 
+/// Reply to `/connect` without a target: the registered projects and
+/// running sessions as buttons, with how to connect to anything else.
+async fn send_connect_menu(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+) -> ResponseResult<()> {
+    let mut names: Vec<String> = state.list_projects().into_iter().map(|(name, _)| name).collect();
+    names.sort();
+    for (session, _) in state.list_tmux_sessions() {
+        let project = session.strip_prefix("commander-").unwrap_or(&session);
+        if !names.iter().any(|name| name == project) {
+            names.push(session);
+        }
+    }
+
+    let usage = "<b>Connect to registered project or tmux session:</b>\n<code>/connect &lt;name&gt;</code>\n\n\
+        <b>Create new project:</b>\n<code>/connect &lt;path&gt; -a &lt;adapter&gt; --name &lt;name&gt;</code>\n\n\
+        Adapters: <code>cc</code> (Claude Code), <code>mpm</code>";
+
+    if names.is_empty() {
+        bot.send_message(msg.chat.id, format!("No projects or sessions yet.\n\n{}", usage))
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
+    }
+
+    bot.send_message(msg.chat.id, format!("Choose a project to connect to:\n\n{}", usage))
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .reply_markup(connect_menu_keyboard(&names))
+        .await?;
+    Ok(())
+}
+
 /// Handle the /connect command.
 pub async fn handle_connect(
     bot: Bot,
@@ -573,19 +687,7 @@ pub async fn handle_connect(
     let args = args.trim();
 
     if args.is_empty() {
-        bot.send_message(
-            msg.chat.id,
-            "Please specify a target.\n\n\
-            <b>Connect to registered project:</b>\n<code>/connect &lt;name&gt;</code>\n\n\
-            <b>Connect to tmux session:</b>\n<code>/connect &lt;session-name&gt;</code>\n\n\
-            <b>Create new project:</b>\n<code>/connect &lt;path&gt; -a &lt;adapter&gt; --name &lt;name&gt;</code>\n\n\
-            The command automatically detects whether the name refers to a registered project or an existing tmux session.\n\n\
-            Adapters: <code>cc</code> (Claude Code), <code>mpm</code>\n\n\
-            Use /list for projects and /sessions for tmux sessions.",
-        )
-        .parse_mode(teloxide::types::ParseMode::Html)
-        .await?;
-        return Ok(());
+        return send_connect_menu(bot, msg, state).await;
     }
 
     // Show typing early — connection + LLM status summary take noticeable time
@@ -646,10 +748,11 @@ pub async fn handle_connect(
                     .parse_mode(teloxide::types::ParseMode::Html)
                     .await?;
                     info!(chat_id = %msg.chat.id, project = %connected_name, "User connected to project");
+                    send_recap(&bot, &state, msg.chat.id);
                 }
                 Err(e) => {
                     if let Err(send_err) = bot
-                        .send_message(msg.chat.id, format!("❌ Failed to connect: {}", e))
+                        .send_message(msg.chat.id, error_reply("Failed to connect", &e))
                         .await
                     {
                         error!(chat_id = %msg.chat.id, send_error = %send_err, "Failed to send connection error message");
@@ -693,7 +796,7 @@ pub async fn handle_connect(
                     }
                     Err(e) => {
                         if let Err(send_err) = bot
-                            .send_message(msg.chat.id, format!("❌ Failed to connect: {}", e))
+                            .send_message(msg.chat.id, error_reply("Failed to connect", &e))
                             .await
                         {
                             error!(chat_id = %msg.chat.id, send_error = %send_err, "Failed to send connection error message");
@@ -730,7 +833,7 @@ pub async fn handle_connect(
                     }
                     Err(e) => {
                         if let Err(send_err) = bot
-                            .send_message(msg.chat.id, format!("❌ Failed to create project: {}", e))
+                            .send_message(msg.chat.id, error_reply("Failed to create project", &e))
                             .await
                         {
                             error!(chat_id = %msg.chat.id, send_error = %send_err, "Failed to send project creation error message");
@@ -742,6 +845,133 @@ pub async fn handle_connect(
         }
     }
 
+    send_handoff_note(&bot, msg.chat.id, &state).await;
+
+    Ok(())
+}
+
+/// Attach the chat's session in the shared registry and report activity from other frontends.
+async fn send_handoff_note(bot: &Bot, chat_id: ChatId, state: &TelegramState) {
+    let Some((session, outcome, context)) = state.attach_handoff(chat_id).await else {
+        return;
+    };
+    let Some(note) = format_handoff_note(&session, &outcome, context.as_deref()) else {
+        return;
+    };
+
+    if let Err(e) = bot
+        .send_message(chat_id, note)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await
+    {
+        warn!(chat_id = %chat_id, error = %e, "Failed to send handoff note");
+    }
+}
+
+/// Format the handoff note shown after connecting, if there is anything to say.
+fn format_handoff_note(
+    session: &str,
+    outcome: &AttachOutcome,
+    context: Option<&str>,
+) -> Option<String> {
+    let mut parts = Vec::new();
+
+    if let Some(context) = context {
+        parts.push(format!(
+            "🔄 <b>Recent activity from other frontends:</b>\n<pre>{}</pre>",
+            html_escape(context)
+        ));
+    }
+    if let AttachOutcome::HeldBy(holder) = outcome {
+        parts.push(format!(
+            "ℹ️ <b>{}</b> is attached in {}. Use /takeover to claim it.",
+            html_escape(session),
+            holder.frontend
+        ));
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("\n\n"))
+    }
+}
+
+/// Handle the /takeover command - claim the connected session for this chat.
+pub async fn handle_takeover(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let text = match state.takeover_session(msg.chat.id).await {
+        Ok((session, Some(previous))) => format!(
+            "✅ Took over <b>{}</b> from {}",
+            html_escape(&session),
+            previous.frontend
+        ),
+        Ok((session, None)) => format!("<b>{}</b> is attached here.", html_escape(&session)),
+        Err(TelegramError::NotConnected) => {
+            "Not connected to any project. Use /connect first.".to_string()
+        }
+        Err(e) => format!("❌ Error: {}", html_escape(&e.to_string())),
+    };
+
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// Handle the /pause and /resume commands.
+pub async fn handle_pause(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    project: String,
+    pause: bool,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let result = if pause {
+        state.pause_session(msg.chat.id, &project).await
+    } else {
+        state.resume_session(msg.chat.id, &project).await
+    };
+    let text = match result {
+        Ok(description) if pause => format!("⏸️ {}
+
+Use /resume to continue.", html_escape(&description)),
+        Ok(description) => format!("▶️ {}", html_escape(&description)),
+        Err(TelegramError::NotConnected) => format!(
+            "Not connected to any project.
+
+<b>Usage:</b> <code>/{} [project]</code>",
+            if pause { "pause" } else { "resume" }
+        ),
+        Err(e) => format!("❌ Error: {}", html_escape(&e.to_string())),
+    };
+
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
     Ok(())
 }
 
@@ -778,7 +1008,62 @@ pub async fn handle_disconnect(
                 .await?;
         }
         Err(e) => {
-            bot.send_message(msg.chat.id, format!("❌ Error: {}", e))
+            bot.send_message(msg.chat.id, error_reply("Error", &e))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the /use command: switch the active project, keeping the previous
+/// one followed.
+pub async fn handle_use(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    project: String,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "⛔ Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    if let Some(thread_id) = msg.thread_id {
+        bot.send_message(msg.chat.id, "Each topic follows its own session. Use /topic to add one.")
+            .message_thread_id(thread_id)
+            .await?;
+        return Ok(());
+    }
+
+    let project = project.trim();
+    if project.is_empty() {
+        return handle_active(bot, msg, state).await;
+    }
+
+    match state.use_session(msg.chat.id, project).await {
+        Ok((name, resumed)) => {
+            let (_, followed) = state.followed_sessions(msg.chat.id).await;
+            let mut text = if resumed {
+                format!("🎯 Now using <b>{}</b>", html_escape(&name))
+            } else {
+                format!("🎯 Connected to <b>{}</b>", html_escape(&name))
+            };
+            if !followed.is_empty() {
+                text.push_str(&format!("\nStill following: {}", format_followed(&followed)));
+            }
+            bot.send_message(msg.chat.id, text)
+                .parse_mode(teloxide::types::ParseMode::Html)
+                .await?;
+            info!(chat_id = %msg.chat.id, project = %name, resumed, "Switched active session");
+        }
+        Err(e) => {
+            bot.send_message(msg.chat.id, error_reply(&format!("Could not use {}", project), &e))
                 .await?;
         }
     }
@@ -786,6 +1071,55 @@ pub async fn handle_disconnect(
     Ok(())
 }
 
+/// Handle the /active command: list the sessions this chat follows.
+pub async fn handle_active(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "⛔ Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let (active, followed) = state.followed_sessions(msg.chat.id).await;
+    bot.send_message(msg.chat.id, format_active_sessions(active.as_deref(), &followed))
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// Comma-separated, escaped project names.
+fn format_followed(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|n| format!("<code>{}</code>", html_escape(n)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The /active reply: the active project, the other followed ones, and how
+/// to reach them.
+fn format_active_sessions(active: Option<&str>, followed: &[String]) -> String {
+    if active.is_none() && followed.is_empty() {
+        return "Not following any session.\n\nUse /connect &lt;project&gt; to connect.".to_string();
+    }
+    let mut text = match active {
+        Some(name) => format!("🎯 Active: <b>{}</b>", html_escape(name)),
+        None => "🎯 No active session".to_string(),
+    };
+    if !followed.is_empty() {
+        text.push_str(&format!("\n👀 Following: {}", format_followed(followed)));
+        text.push_str("\n\nSwitch with <code>/use &lt;project&gt;</code>, or send to one with <code>@project message</code>.");
+    }
+    text
+}
+
 /// Map tool_id to display name.
 fn adapter_display_name(tool_id: &str) -> &str {
     match tool_id {
@@ -822,6 +1156,18 @@ async fn get_connection_status(state: &Arc<TelegramState>, chat_id: ChatId, _con
     format!("{}\n• State: {}", branch_info, state_emoji)
 }
 
+/// Send the "Since you left" digest of the chat's session once summarized.
+fn send_recap(bot: &Bot, state: &Arc<TelegramState>, chat_id: ChatId) {
+    let (bot, state) = (bot.clone(), Arc::clone(state));
+    tokio::spawn(async move {
+        if let Some(digest) = state.recap(chat_id).await {
+            if let Err(e) = bot.send_message(chat_id, format!("🕒 {}", digest)).await {
+                warn!(chat_id = %chat_id, error = %e, "Failed to send recap");
+            }
+        }
+    });
+}
+
 /// Extract git branch from screen preview if visible.
 fn extract_git_branch(screen: &str) -> Option<String> {
     // Look for common git branch indicators in terminal prompts
@@ -991,16 +1337,22 @@ pub async fn handle_status(
         state.get_session_status(msg.chat.id).await
     {
         let adapter_name = adapter_display_name(&tool_id);
+        let paused = state
+            .get_current_tmux_session(msg.chat.id.0)
+            .await
+            .is_some_and(|session| commander_core::pause::is_paused(&session));
 
         // Build activity section with LLM interpretation
         // For idle sessions, get LLM interpretation via spawn_blocking before building activity
-        let llm_interpretation = if !is_waiting {
+        let llm_interpretation = if !is_waiting && !paused {
             get_session_summary(screen_preview.clone(), false).await
         } else {
             None
         };
 
-        let activity = if is_waiting {
+        let activity = if paused {
+            "⏸️ Activity: Paused (/resume to continue)".to_string()
+        } else if is_waiting {
             if let Some(query) = pending_query {
                 // Truncate long queries
                 let truncated = if query.chars().count() > 50 {
@@ -1151,6 +1503,7 @@ pub async fn handle_list(
         .map(|(name, _)| name);
 
     let mut text = String::from("🤖 <b>Available Sessions</b>\n\nTap a link to connect or stop:\n\n");
+    let paused = commander_core::pause::paused_session_names();
 
     for (name, is_commander, created_at, preview) in &sessions {
         let is_current = current_session.as_ref().map(|s| s == name).unwrap_or(false);
@@ -1167,8 +1520,11 @@ pub async fn handle_list(
             !p.is_empty() && !p.contains("Waiting")
         }).unwrap_or(false);
 
-        // Use checksum-based cache to avoid redundant LLM calls
-        let summary = if let Some(output) = preview.as_ref() {
+        // Use checksum-based cache to avoid redundant LLM calls; paused
+        // sessions are not analyzed
+        let summary = if paused.contains(name) {
+            None
+        } else if let Some(output) = preview.as_ref() {
             if let Some(cached) = state.get_cached_summary(name, output).await {
                 Some(cached)
             } else {
@@ -1185,6 +1541,7 @@ pub async fn handle_list(
         };
 
         let status_display = match &summary {
+            None if paused.contains(name) => "⏸️ paused".to_string(),
             Some(s) => format!("<i>{}</i>", html_escape(s)),
             None if is_active => "🔄 running".to_string(),
             None => "💤 idle".to_string(),
@@ -1351,11 +1708,31 @@ pub async fn handle_message(
         return Ok(());
     }
 
-    // Extract text and thread_id early to avoid borrow issues
+    // Extract text early to avoid borrow issues
     let text = match msg.text() {
         Some(t) => t.to_string(),
         None => return Ok(()),
     };
+
+    // "!name args" runs a snippet, like /run
+    if let Some(rest) = text.strip_prefix('!') {
+        if commander_core::snippets::parse_invocation(&text).is_some() {
+            let rest = rest.to_string();
+            return handle_run(bot, msg, state, rest).await;
+        }
+    }
+
+    route_text(bot, msg, state, text).await
+}
+
+/// Route the text of an authorized message (typed or transcribed) to its
+/// topic, @-addressed session or connected project.
+async fn route_text(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    text: String,
+) -> ResponseResult<()> {
     let thread_id = msg.thread_id;
 
     // Check if this is a message in a forum topic (group mode)
@@ -1438,10 +1815,10 @@ pub async fn handle_message(
                     let cmd_str = message.split_whitespace().next().unwrap_or("");
                     // Connect to target session to provide context for the command
                     typing_throttled(&bot, msg.chat.id, None, &state).await;
-                    if let Err(e) = state.connect(msg.chat.id, alias).await {
+                    if let Err(e) = state.use_session(msg.chat.id, alias).await {
                         bot.send_message(
                             msg.chat.id,
-                            format!("❌ Could not connect to @{}: {}", alias, e),
+                            error_reply(&format!("Could not connect to @{}", alias), &e),
                         ).await?;
                         return Ok(());
                     }
@@ -1452,6 +1829,8 @@ pub async fn handle_message(
                         "/stop" | "/s"             => {
                             handle_stop(bot, msg, state, alias.to_string()).await
                         }
+                        "/pause"                   => handle_pause(bot, msg, state, alias.to_string(), true).await,
+                        "/resume"                  => handle_pause(bot, msg, state, alias.to_string(), false).await,
                         _ => {
                             bot.send_message(
                                 msg.chat.id,
@@ -1486,7 +1865,7 @@ Use /help to see all commands.",
                     Err(e) => {
                         bot.send_message(
                             msg.chat.id,
-                            format!("❌ Could not route to @{}: {}", alias, e),
+                            error_reply(&format!("Could not route to @{}", alias), &e),
                         )
                         .await?;
                         return Ok(());
@@ -1523,7 +1902,7 @@ Use /help to see all commands.",
                 warn!(chat_id = %msg.chat.id, "Event-driven dispatch returned false");
             }
             Err(e) => {
-                bot.send_message(msg.chat.id, format!("❌ Error: {}", e))
+                bot.send_message(msg.chat.id, error_reply("Error", &e))
                     .await?;
                 error!(chat_id = %msg.chat.id, error = %e, "Event-driven dispatch failed");
             }
@@ -1541,7 +1920,7 @@ Use /help to see all commands.",
             // Response will be polled and sent back by the polling task
         }
         Err(e) => {
-            bot.send_message(msg.chat.id, format!("❌ Error: {}", e))
+            bot.send_message(msg.chat.id, error_reply("Error", &e))
                 .await?;
             error!(chat_id = %msg.chat.id, error = %e, "Failed to send message");
         }
@@ -1550,68 +1929,207 @@ Use /help to see all commands.",
     Ok(())
 }
 
-/// Handle messages sent in forum topics (group mode).
-async fn handle_topic_message(
+/// Handle voice messages: transcribe them and route the text like a typed
+/// message, asking for confirmation first when the transcription is unsure.
+pub async fn handle_voice(
     bot: Bot,
     msg: Message,
     state: Arc<TelegramState>,
-    text: &str,
-    thread_id: ThreadId,
 ) -> ResponseResult<()> {
-    // Check if group mode is enabled for this chat
-    if !state.is_group_mode(msg.chat.id.0).await {
-        // Not in group mode - ignore topic messages or send help
-        debug!(
-            chat_id = %msg.chat.id,
-            thread_id = ?thread_id,
-            "Topic message received but group mode not enabled"
-        );
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "⛔ Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
         return Ok(());
     }
 
-    // Check if this topic has a session mapping
-    if !state.has_topic_session(msg.chat.id, thread_id).await {
-        // Topic not configured - could be General topic or unlinked topic
-        // Check if there's a topic config (for topics created outside of /topic command)
-        if let Some(topic_config) = state.get_topic_session(msg.chat.id.0, thread_id.0.0).await {
-            // Topic exists in config but session not active - reconnect
-            match state.connect_topic(msg.chat.id, thread_id, &topic_config.session_name).await {
-                Ok(_) => {
-                    debug!(
-                        chat_id = %msg.chat.id,
-                        thread_id = ?thread_id,
-                        session = %topic_config.session_name,
-                        "Reconnected topic session"
-                    );
-                }
-                Err(e) => {
-                    warn!(
-                        chat_id = %msg.chat.id,
-                        thread_id = ?thread_id,
-                        error = %e,
-                        "Failed to reconnect topic session"
-                    );
-                    bot.send_message(
-                        msg.chat.id,
-                        format!("Failed to connect to session: {}", e),
-                    )
-                    .message_thread_id(thread_id)
-                    .await?;
-                    return Ok(());
-                }
-            }
-        } else {
-            // Topic has no session mapping - ignore or send help
-            bot.send_message(
-                msg.chat.id,
-                "This topic is not linked to a session.\n\n\
-                Use <code>/topic &lt;session&gt;</code> in the main chat to create a linked topic.",
-            )
-            .message_thread_id(thread_id)
-            .parse_mode(teloxide::types::ParseMode::Html)
+    let Some(voice) = msg.voice() else {
+        return Ok(());
+    };
+    let settings = state.voice_settings().await;
+    if !settings.enabled {
+        bot.send_message(msg.chat.id, "🎙 Voice messages are disabled. Send text instead.")
+            .reply_parameters(ReplyParameters::new(msg.id))
             .await?;
-            return Ok(());
-        }
+        return Ok(());
+    }
+
+    typing_throttled(&bot, msg.chat.id, msg.thread_id, &state).await;
+
+    let file = bot.get_file(voice.file.id.clone()).await?;
+    let mut audio = Vec::new();
+    {
+        use teloxide::net::Download;
+        if let Err(e) = bot.download_file(&file.path, &mut audio).await {
+            error!(chat_id = %msg.chat.id, error = %e, "Failed to download voice message");
+            bot.send_message(msg.chat.id, format!("❌ Could not download voice message: {}", e))
+                .reply_parameters(ReplyParameters::new(msg.id))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let transcription = match crate::voice::transcribe(&settings, audio, "voice.ogg").await {
+        Ok(t) => t,
+        Err(e) => {
+            error!(chat_id = %msg.chat.id, error = %e, "Voice transcription failed");
+            bot.send_message(msg.chat.id, format!("❌ {}", e))
+                .reply_parameters(ReplyParameters::new(msg.id))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if transcription.text.is_empty() {
+        bot.send_message(msg.chat.id, "🎙 Couldn't make out any speech.")
+            .reply_parameters(ReplyParameters::new(msg.id))
+            .await?;
+        return Ok(());
+    }
+
+    let escaped = teloxide::utils::html::escape(&transcription.text);
+    if transcription.needs_confirmation(settings.min_confidence) {
+        let confidence = transcription
+            .confidence
+            .map(|c| format!(" ({:.0}% confident)", c * 100.0))
+            .unwrap_or_default();
+        let keyboard = InlineKeyboardMarkup::new(vec![vec![
+            InlineKeyboardButton::callback("✅ Send", "voice:send"),
+            InlineKeyboardButton::callback("❌ Discard", "voice:discard"),
+        ]]);
+        // The confirmation replies to the voice note so the callback can
+        // route the text with the original message's chat and topic
+        let sent = bot
+            .send_message(
+                msg.chat.id,
+                format!("🎙 I heard{}:\n\n<i>{}</i>\n\nSend it?", confidence, escaped),
+            )
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .reply_parameters(ReplyParameters::new(msg.id))
+            .reply_markup(keyboard)
+            .await?;
+        state
+            .hold_transcription(msg.chat.id.0, sent.id, transcription.text)
+            .await;
+        info!(chat_id = %msg.chat.id, confidence = ?transcription.confidence, "Voice transcription awaiting confirmation");
+        return Ok(());
+    }
+
+    bot.send_message(msg.chat.id, format!("🎙 <i>{}</i>", escaped))
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .reply_parameters(ReplyParameters::new(msg.id))
+        .await?;
+    route_text(bot, msg, state, transcription.text).await
+}
+
+/// Handle the Send/Discard buttons under a low-confidence transcription.
+async fn handle_voice_confirmation(
+    bot: Bot,
+    q: CallbackQuery,
+    state: Arc<TelegramState>,
+    action: &str,
+) -> ResponseResult<()> {
+    let Some(confirmation) = q.message.as_ref().and_then(|m| m.regular_message()) else {
+        return Ok(());
+    };
+    let chat_id = confirmation.chat.id;
+
+    if !state.is_authorized(chat_id.0).await {
+        bot.send_message(chat_id, "Not authorized. Use <code>/pair &lt;code&gt;</code> first.")
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
+    }
+
+    // Taking the text also keeps a double tap from sending it twice
+    let Some(text) = state.take_transcription(chat_id.0, confirmation.id).await else {
+        return Ok(());
+    };
+    let escaped = teloxide::utils::html::escape(&text);
+
+    if action != "send" {
+        bot.edit_message_text(chat_id, confirmation.id, format!("🎙 Discarded: <s>{}</s>", escaped))
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
+    }
+
+    bot.edit_message_text(chat_id, confirmation.id, format!("🎙 <i>{}</i>", escaped))
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    let Some(voice_msg) = confirmation.reply_to_message() else {
+        warn!(chat_id = %chat_id, "Voice confirmation lost its original message");
+        return Ok(());
+    };
+    info!(chat_id = %chat_id, "Voice transcription confirmed");
+    route_text(bot, voice_msg.clone(), state, text).await
+}
+
+/// Handle messages sent in forum topics (group mode).
+async fn handle_topic_message(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    text: &str,
+    thread_id: ThreadId,
+) -> ResponseResult<()> {
+    // Check if group mode is enabled for this chat
+    if !state.is_group_mode(msg.chat.id.0).await {
+        // Not in group mode - ignore topic messages or send help
+        debug!(
+            chat_id = %msg.chat.id,
+            thread_id = ?thread_id,
+            "Topic message received but group mode not enabled"
+        );
+        return Ok(());
+    }
+
+    // Check if this topic has a session mapping
+    if !state.has_topic_session(msg.chat.id, thread_id).await {
+        // Topic not configured - could be General topic or unlinked topic
+        // Check if there's a topic config (for topics created outside of /topic command)
+        if let Some(topic_config) = state.get_topic_session(msg.chat.id.0, thread_id.0.0).await {
+            // Topic exists in config but session not active - reconnect
+            match state.connect_topic(msg.chat.id, thread_id, &topic_config.session_name).await {
+                Ok(_) => {
+                    debug!(
+                        chat_id = %msg.chat.id,
+                        thread_id = ?thread_id,
+                        session = %topic_config.session_name,
+                        "Reconnected topic session"
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        chat_id = %msg.chat.id,
+                        thread_id = ?thread_id,
+                        error = %e,
+                        "Failed to reconnect topic session"
+                    );
+                    bot.send_message(
+                        msg.chat.id,
+                        format!("Failed to connect to session: {}", e),
+                    )
+                    .message_thread_id(thread_id)
+                    .await?;
+                    return Ok(());
+                }
+            }
+        } else {
+            // Topic has no session mapping - ignore or send help
+            bot.send_message(
+                msg.chat.id,
+                "This topic is not linked to a session.\n\n\
+                Use <code>/topic &lt;session&gt;</code> in the main chat to create a linked topic.",
+            )
+            .message_thread_id(thread_id)
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+            return Ok(());
+        }
     }
 
     // Send typing indicator to the topic (throttled)
@@ -1646,7 +2164,7 @@ async fn handle_topic_message(
                 );
             }
             Err(e) => {
-                bot.send_message(msg.chat.id, format!("❌ Error: {}", e))
+                bot.send_message(msg.chat.id, error_reply("Error", &e))
                     .message_thread_id(thread_id)
                     .await?;
                 error!(
@@ -1671,9 +2189,18 @@ async fn handle_topic_message(
             );
             // Store reaction metadata (topics are group chats — not private).
             state.set_session_reaction_meta(session_key, Some(msg.id), false).await;
+
+            // Surface any command the agent is holding for guardrail approval.
+            #[cfg(feature = "agents")]
+            if let Some(approval) = state.pending_approval().await {
+                bot.send_message(msg.chat.id, format_approval_prompt(&approval))
+                    .message_thread_id(thread_id)
+                    .parse_mode(teloxide::types::ParseMode::Html)
+                    .await?;
+            }
         }
         Err(e) => {
-            bot.send_message(msg.chat.id, format!("❌ Error: {}", e))
+            bot.send_message(msg.chat.id, error_reply("Error", &e))
                 .message_thread_id(thread_id)
                 .await?;
             error!(
@@ -1759,7 +2286,7 @@ pub async fn handle_connect_tree(
         Err(e) => {
             bot.send_message(
                 msg.chat.id,
-                format!("❌ Failed to create worktree session: {}", e),
+                error_reply("Failed to create worktree session", &e),
             )
             .await?;
             error!(chat_id = %msg.chat.id, error = %e, "Worktree creation failed");
@@ -1788,7 +2315,15 @@ pub async fn handle_stop(
         return Ok(());
     }
 
-    let session_arg = session_arg.trim();
+    // --no-ai-commit skips the [stop] ai_commit message and pull request
+    let mut stop_settings = commander_core::Settings::load(&commander_core::config_file())
+        .unwrap_or_default()
+        .stop;
+    let words: Vec<&str> = session_arg.split_whitespace().collect();
+    if words.contains(&"--no-ai-commit") {
+        stop_settings.ai_commit = false;
+    }
+    let session_arg = words.into_iter().find(|w| *w != "--no-ai-commit").unwrap_or("");
 
     // Determine which session to stop
     let (session_name, project_path, is_connected_session) = if session_arg.is_empty() {
@@ -1895,7 +2430,7 @@ pub async fn handle_stop(
     let worktree_result = state.get_worktree_info(msg.chat.id).await;
 
     // Check for git changes and commit if needed
-    let mut commit_message = None;
+    let mut commit = None;
     let mut worktree_cleanup_message = None;
 
     if let Some(worktree_info) = worktree_result {
@@ -1910,8 +2445,12 @@ pub async fn handle_stop(
         }
     } else if project_path != "unknown" && std::path::Path::new(&project_path).exists() {
         // Handle regular session
-        match check_and_commit_changes(&project_path, &session_name).await {
-            Ok(Some(msg)) => commit_message = Some(msg),
+        if stop_settings.ai_commit {
+            bot.send_message(msg.chat.id, "Writing commit message and pull request...")
+                .await?;
+        }
+        match check_and_commit_changes(&project_path, &session_name, &stop_settings).await {
+            Ok(Some(made)) => commit = Some(made),
             Ok(None) => {} // No changes to commit
             Err(e) => {
                 info!(error = %e, "Git commit check failed (non-fatal)");
@@ -1925,6 +2464,9 @@ pub async fn handle_stop(
             .await?;
         return Ok(());
     }
+    if let Err(e) = commander_core::pause::mark_resumed(&session_name) {
+        warn!(session = %session_name, error = %e, "Failed to clear paused state");
+    }
 
     // Disconnect if this was the connected session
     if is_connected_session {
@@ -1939,12 +2481,17 @@ pub async fn handle_stop(
             html_escape(&session_name),
             html_escape(&worktree_msg)
         )
-    } else if let Some(commit_msg) = commit_message {
+    } else if let Some(commit) = commit {
+        let pull_request = commit
+            .pull_request
+            .map(|line| format!("\n\n{}", html_escape(&line)))
+            .unwrap_or_default();
         format!(
             "Session <code>{}</code> stopped.\n\n\
-            Git changes committed:\n<pre>{}</pre>",
+            Git changes committed:\n<pre>{}</pre>{}",
             html_escape(&session_name),
-            html_escape(&commit_msg)
+            html_escape(&commit.message),
+            pull_request
         )
     } else {
         format!(
@@ -2091,12 +2638,25 @@ async fn cleanup_worktree(
     ))
 }
 
+/// A commit made by `/stop`.
+struct StopCommit {
+    /// The commit message.
+    message: String,
+    /// The pull request opened for the branch, or why it was not.
+    pull_request: Option<String>,
+}
+
 /// Check for git changes and commit them if present.
-/// Returns the commit message if a commit was made, None if no changes.
+/// Returns the commit if one was made, None if no changes.
+///
+/// With `[stop] ai_commit`, the message is written from the diff and the
+/// session's transcript, and with `push` the branch is pushed and a pull
+/// request opened.
 async fn check_and_commit_changes(
     project_path: &str,
     session_name: &str,
-) -> std::result::Result<Option<String>, String> {
+    settings: &commander_core::StopSettings,
+) -> std::result::Result<Option<StopCommit>, String> {
     use std::process::Command;
 
     // Check for uncommitted changes
@@ -2131,7 +2691,23 @@ async fn check_and_commit_changes(
     let friendly_name = session_name
         .strip_prefix("commander-")
         .unwrap_or(session_name);
-    let commit_msg = format!("WIP: Auto-commit from Commander session '{}'", friendly_name);
+    let summary = if settings.ai_commit {
+        let diff = commander_core::git_diff(std::path::Path::new(project_path), None).unwrap_or_default();
+        let transcript = commander_core::read_all_log_entries(session_name).unwrap_or_default();
+        match commander_core::generate_pr_summary(friendly_name, &transcript, &diff, settings.model.as_deref()).await {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                warn!(error = %e, "Could not write commit message, using a WIP commit");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let commit_msg = summary
+        .as_ref()
+        .map(|s| s.commit_message.clone())
+        .unwrap_or_else(|| format!("WIP: Auto-commit from Commander session '{}'", friendly_name));
 
     // Commit
     let commit_output = Command::new("git")
@@ -2148,7 +2724,30 @@ async fn check_and_commit_changes(
         return Err(format!("git commit failed: {}", stderr));
     }
 
-    Ok(Some(commit_msg))
+    let pull_request = match summary {
+        Some(summary) if settings.push => {
+            let client = commander_core::secrets::get_secret(commander_core::secrets::GITHUB_TOKEN)
+                .map(commander_github::GitHubClient::new);
+            let published = commander_github::publish_branch(
+                std::path::Path::new(project_path),
+                client.as_ref(),
+                &summary.title,
+                &summary.body,
+            )
+            .await;
+            Some(match published {
+                Ok(p) if p.opened => format!("Pull request: {}", p.url),
+                Ok(p) => format!("Pushed {}. Open a pull request: {}", p.branch, p.url),
+                Err(e) => format!("Not pushed: {}", e),
+            })
+        }
+        _ => None,
+    };
+
+    Ok(Some(StopCommit {
+        message: commit_msg,
+        pull_request,
+    }))
 }
 
 /// Handle the /send command - send a message directly to the session without LLM interpretation.
@@ -2300,6 +2899,48 @@ async fn handle_option_selection(
     Ok(())
 }
 
+/// Handle Approve/Deny buttons on a queued approval request.
+///
+/// `rest` is the callback data after "approval:" — format: "<approve|deny>:<id>".
+async fn handle_approval_decision(
+    bot: Bot,
+    q: CallbackQuery,
+    state: Arc<TelegramState>,
+    rest: &str,
+) -> ResponseResult<()> {
+    let Some(msg) = q.message.as_ref() else {
+        return Ok(());
+    };
+    let chat_id = msg.chat().id;
+
+    if !state.is_authorized(chat_id.0).await {
+        bot.send_message(chat_id, "Not authorized. Use <code>/pair &lt;code&gt;</code> first.")
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
+    }
+
+    let Some((action, id)) = rest.split_once(':') else {
+        warn!(data = %rest, "Malformed approval callback");
+        return Ok(());
+    };
+    let text = decide_queued_approval(id, action == "approve", chat_id).await;
+
+    // Replace the buttons with the outcome so the request can't be decided twice
+    if bot
+        .edit_message_text(chat_id, msg.id(), &text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await
+        .is_err()
+    {
+        bot.send_message(chat_id, text)
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+    }
+    info!(chat_id = %chat_id.0, action = %action, request = %id, "Approval decided via button");
+    Ok(())
+}
+
 /// Handle callback queries from inline keyboard buttons.
 pub async fn handle_callback(
     bot: Bot,
@@ -2331,6 +2972,22 @@ pub async fn handle_callback(
         return handle_option_selection(bot, q, state, option_key).await;
     }
 
+    // Handle approval queue decisions (format: "approval:<approve|deny>:<id>")
+    if let Some(rest) = data.strip_prefix("approval:") {
+        return handle_approval_decision(bot, q, state, rest).await;
+    }
+
+    // Handle error diagnosis cards (format: "diagnosis:<apply|ignore>:<id>")
+    #[cfg(feature = "agents")]
+    if let Some(rest) = data.strip_prefix("diagnosis:") {
+        return handle_diagnosis_decision(bot, q, state, rest).await;
+    }
+
+    // Handle low-confidence voice transcriptions (format: "voice:<send|discard>")
+    if let Some(action) = data.strip_prefix("voice:") {
+        return handle_voice_confirmation(bot, q, state, action).await;
+    }
+
     // Handle interactive selector selection (format: "select:<n>:<current_selected>")
     if let Some(rest) = data.strip_prefix("select:") {
         return handle_selector_selection(bot, q, state, rest).await;
@@ -2385,7 +3042,7 @@ pub async fn handle_callback(
                 info!(chat_id = %chat_id, project = %name, "User connected via inline button");
             }
             Err(e) => {
-                bot.send_message(chat_id, format!("❌ Failed to connect: {}", e))
+                bot.send_message(chat_id, error_reply("Failed to connect", &e))
                     .await?;
                 error!(chat_id = %chat_id, error = %e, "Connection via button failed");
             }
@@ -2938,51 +3595,800 @@ async fn spawn_agent_with_streaming(
     Ok(())
 }
 
-/// Truncate a string to fit within Telegram's message length limit.
-fn truncate_for_telegram(text: &str, max_chars: usize) -> String {
-    if text.chars().count() <= max_chars {
-        text.to_string()
-    } else {
-        let truncated: String = text.chars().take(max_chars).collect();
-        format!("{}...\n<i>[truncated]</i>", truncated)
-    }
-}
-
-pub async fn handle_command(
+/// Handle /approve and /deny — decide a queued request by ID, or resolve a
+/// command held by guardrails.
+pub async fn handle_approval(
     bot: Bot,
     msg: Message,
-    cmd: Command,
     state: Arc<TelegramState>,
+    approve: bool,
+    id: String,
 ) -> ResponseResult<()> {
-    match cmd {
-        Command::Start(args) => handle_start(bot, msg, state, args).await,
-        Command::Help => handle_help(bot, msg).await,
-        Command::Pair(code) => handle_pair(bot, msg, state, code).await,
-        Command::Connect(project) => handle_connect(bot, msg, state, project).await,
-        Command::C(project) => handle_connect(bot, msg, state, project).await,
-        Command::Disconnect => handle_disconnect(bot, msg, state).await,
-        Command::Stop(session) => handle_stop(bot, msg, state, session).await,
-        Command::S(session) => handle_stop(bot, msg, state, session).await,
-        Command::ConnectTree(session) => handle_connect_tree(bot, msg, state, session).await,
-        Command::Ct(session) => handle_connect_tree(bot, msg, state, session).await,
-        Command::Send(message) => handle_send(bot, msg, state, message).await,
-        Command::Status => handle_status(bot, msg, state).await,
-        Command::List => handle_list(bot, msg, state).await,
-        Command::Ls => handle_list(bot, msg, state).await,
-        Command::Link(session) => handle_link(bot, msg, state, session).await,
-        Command::GroupMode => handle_groupmode(bot, msg, state).await,
-        Command::Topic(session) => handle_topic(bot, msg, state, session).await,
-        Command::Topics => handle_topics(bot, msg, state).await,
-        Command::Agents => handle_agents(bot, msg, state).await,
-        Command::Spawn(args) => handle_spawn(bot, msg, state, args).await,
-        Command::Mpm => handle_mpm_status(bot, msg, state).await,
-        Command::Ask(question) => handle_ask(bot, msg, state, question).await,
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let id = id.trim();
+    if !id.is_empty() {
+        let text = decide_queued_approval(id, approve, msg.chat.id).await;
+        bot.send_message(msg.chat.id, text)
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
     }
+
+    #[cfg(feature = "agents")]
+    let text = match state.resolve_pending_approval(approve).await {
+        Some(approval) if approve => format!(
+            "✅ Approved: <code>{}</code>\nIt will run on the next attempt.",
+            html_escape(&approval.command)
+        ),
+        Some(approval) => format!("🚫 Denied: <code>{}</code>", html_escape(&approval.command)),
+        None => "No command awaiting approval.".to_string(),
+    };
+
+    #[cfg(not(feature = "agents"))]
+    let text = {
+        let _ = approve;
+        "Guardrail approvals require the agents feature.".to_string()
+    };
+
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Approve or deny a queued request and describe the outcome.
+async fn decide_queued_approval(id: &str, approve: bool, chat_id: ChatId) -> String {
+    let id = id.to_string();
+    let by = format!("telegram:{}", chat_id.0);
+    let result = tokio::task::spawn_blocking(move || {
+        commander_core::decide_approval(&id, approve, &by)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(request)) if approve => format!(
+            "✅ Approved <code>{}</code>: {}\nIt runs from the TUI.",
+            request.id,
+            html_escape(&request.summary)
+        ),
+        Ok(Ok(request)) => format!(
+            "🚫 Denied <code>{}</code>: {}",
+            request.id,
+            html_escape(&request.summary)
+        ),
+        Ok(Err(e)) => format!("❌ {}", html_escape(&e.to_string())),
+        Err(e) => format!("❌ Approval failed: {}", html_escape(&e.to_string())),
+    }
+}
+
+/// Format a queued approval request for Telegram.
+pub(crate) fn format_approval_request(request: &commander_core::ApprovalRequest) -> String {
+    format!(
+        "🛂 <b>Approval needed</b> [{}] <code>{}</code>\n{}\n\n<code>{}</code>",
+        html_escape(&request.project),
+        request.id,
+        html_escape(&request.tool),
+        html_escape(&request.summary)
+    )
+}
+
+/// Approve/Deny buttons for a queued approval request.
+pub(crate) fn approval_keyboard(id: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("✅ Approve", format!("approval:approve:{}", id)),
+        InlineKeyboardButton::callback("🚫 Deny", format!("approval:deny:{}", id)),
+    ]])
+}
+
+/// Handle the /approvals command: list pending requests with decision buttons.
+pub async fn handle_approvals(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let pending = tokio::task::spawn_blocking(commander_core::pending_approvals)
+        .await
+        .unwrap_or_default();
+    if pending.is_empty() {
+        bot.send_message(msg.chat.id, "✨ Nothing awaiting approval").await?;
+        return Ok(());
+    }
+
+    for request in &pending {
+        bot.send_message(msg.chat.id, format_approval_request(request))
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .reply_markup(approval_keyboard(&request.id))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Handle the /approvalmode command for the connected project.
+pub async fn handle_approval_mode(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    arg: String,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let Some((project, _)) = state.get_session_info(msg.chat.id).await else {
+        bot.send_message(msg.chat.id, "Not connected. Use /connect <project> first.")
+            .await?;
+        return Ok(());
+    };
+
+    let enabled = match arg.trim() {
+        "" => {
+            let name = project.clone();
+            let enabled = tokio::task::spawn_blocking(move || commander_core::approval_mode_enabled(&name))
+                .await
+                .unwrap_or(false);
+            let text = format!(
+                "Approval mode for <b>{}</b> is {}",
+                html_escape(&project),
+                if enabled { "on" } else { "off" }
+            );
+            bot.send_message(msg.chat.id, text)
+                .parse_mode(teloxide::types::ParseMode::Html)
+                .await?;
+            return Ok(());
+        }
+        "on" => true,
+        "off" => false,
+        _ => {
+            bot.send_message(msg.chat.id, "Usage: /approvalmode [on|off]").await?;
+            return Ok(());
+        }
+    };
+
+    let name = project.clone();
+    let result = tokio::task::spawn_blocking(move || commander_core::set_approval_mode(&name, enabled)).await;
+    let text = match result {
+        Ok(Ok(())) if enabled => format!(
+            "🛂 Approval mode on for <b>{}</b>: agent delegations and file writes wait for approval.",
+            html_escape(&project)
+        ),
+        Ok(Ok(())) => format!("Approval mode off for <b>{}</b>", html_escape(&project)),
+        Ok(Err(e)) => format!("❌ Failed to save approval mode: {}", html_escape(&e.to_string())),
+        Err(e) => format!("❌ /approvalmode failed: {}", html_escape(&e.to_string())),
+    };
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// Changed lines shown by /diff before the summary is trimmed.
+const DIFF_SUMMARY_LINES: usize = 40;
+
+/// Handle the /diff command: summarized `git diff` of the connected project.
+pub async fn handle_diff(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    file: String,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let Some((project_name, project_path, ..)) = state.get_session_status(msg.chat.id).await else {
+        bot.send_message(msg.chat.id, "Not connected. Use /connect <project> first.")
+            .await?;
+        return Ok(());
+    };
+
+    let file = file.trim().to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        let file = (!file.is_empty()).then_some(file);
+        commander_core::git_diff(std::path::Path::new(&project_path), file.as_deref())
+    })
+    .await;
+
+    let text = match result {
+        Ok(Ok(diff)) => format_diff_summary(&project_name, &diff),
+        Ok(Err(e)) => format!("❌ {}", html_escape(&e.to_string())),
+        Err(e) => format!("❌ git diff failed: {}", html_escape(&e.to_string())),
+    };
+
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// Handle the /run command and `!name`: expand a snippet for the connected
+/// project and send it like a typed message.
+/// `/retest`: ask the connected session to re-run the failing tests found
+/// in its output (cargo test, pytest, Jest, go test).
+pub async fn handle_retest(bot: Bot, msg: Message, state: Arc<TelegramState>) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+    if !state.has_session(msg.chat.id).await {
+        bot.send_message(msg.chat.id, "Not connected. Use /connect <project> first.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(run) = state.last_test_run(msg.chat.id).await else {
+        bot.send_message(msg.chat.id, "No test results found in the session's output.").await?;
+        return Ok(());
+    };
+    if run.succeeded() {
+        bot.send_message(msg.chat.id, format!("✅ {}; nothing to re-run.", run.summary())).await?;
+        return Ok(());
+    }
+    let (Some(command), Some(prompt)) = (run.rerun_command(), run.rerun_prompt()) else {
+        bot.send_message(
+            msg.chat.id,
+            format!("{}; the failing tests are not named in the output.", run.summary()),
+        )
+        .await?;
+        return Ok(());
+    };
+    bot.send_message(msg.chat.id, format!("🔁 {}\nRe-running: {}", run.summary(), command)).await?;
+    route_text(bot, msg, state, prompt).await
+}
+
+pub async fn handle_run(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    args: String,
+) -> ResponseResult<()> {
+    use commander_core::snippets::{self, SnippetContext};
+
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let args = args.trim();
+    let (name, args) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    if name.is_empty() {
+        bot.send_message(msg.chat.id, "Usage: /run <name> [args] (see /snippets)").await?;
+        return Ok(());
+    }
+    let Some((project_name, project_path, ..)) = state.get_session_status(msg.chat.id).await else {
+        bot.send_message(msg.chat.id, "Not connected. Use /connect <project> first.")
+            .await?;
+        return Ok(());
+    };
+
+    let (name, args) = (name.to_string(), args.trim().to_string());
+    let expanded = tokio::task::spawn_blocking(move || {
+        let context = SnippetContext::for_project(&project_name, Some(&project_path));
+        snippets::load_snippets().and_then(|s| snippets::expand(&s, &name, &args, &context))
+    })
+    .await;
+    match expanded {
+        Ok(Ok(text)) => route_text(bot, msg, state, text).await,
+        Ok(Err(e)) => {
+            bot.send_message(msg.chat.id, format!("❌ {}", e)).await?;
+            Ok(())
+        }
+        Err(e) => {
+            bot.send_message(msg.chat.id, format!("❌ /run failed: {}", e)).await?;
+            Ok(())
+        }
+    }
+}
+
+/// Handle the /snippets command: list the snippets.
+pub async fn handle_snippets(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+) -> ResponseResult<()> {
+    use commander_core::snippets;
+
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let text = match snippets::load_snippets() {
+        Ok(list) if list.is_empty() => {
+            "No snippets. Add one with <code>commander snippets add &lt;name&gt; &lt;text&gt;</code>.".to_string()
+        }
+        Ok(list) => format!(
+            "📎 <b>Snippets</b> (<code>/run &lt;name&gt;</code> or <code>!name</code>)\n<pre>{}</pre>",
+            html_escape(&snippets::format_list(&list).join("\n"))
+        ),
+        Err(e) => format!("❌ {}", html_escape(&e.to_string())),
+    };
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// Format a trimmed diff summary for Telegram.
+fn format_diff_summary(project_name: &str, diff: &str) -> String {
+    if diff.trim().is_empty() {
+        return format!("✨ <b>{}</b>: no uncommitted changes", html_escape(project_name));
+    }
+
+    let summary = commander_core::summarize_diff(diff, DIFF_SUMMARY_LINES);
+    let trimmed: String = summary.chars().take(3500).collect();
+    let note = if trimmed.len() < summary.len() {
+        "\n<i>[truncated]</i>"
+    } else {
+        ""
+    };
+    format!(
+        "📝 <b>{}</b> diff\n<pre>{}</pre>{}",
+        html_escape(project_name),
+        html_escape(trimmed.trim_end()),
+        note
+    )
+}
+
+/// Handle the /ack command: acknowledge an escalated blocking event, or list
+/// pending blocking events when no ID is given.
+pub async fn handle_ack(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    id: String,
+) -> ResponseResult<()> {
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let id = id.trim().to_string();
+    let result = tokio::task::spawn_blocking(move || -> commander_events::Result<String> {
+        let manager = commander_events::EventManager::new(
+            commander_persistence::EventStore::new(commander_core::config::state_dir()),
+        );
+        manager.load_all()?;
+        if id.is_empty() {
+            let blocking: Vec<_> = manager.list(None).into_iter().filter(|e| e.is_blocking()).collect();
+            return Ok(format_blocking_events(&blocking));
+        }
+        let event = manager.find_by_prefix(&id)?;
+        manager.acknowledge(&event.id)?;
+        Ok(format!(
+            "✅ Acknowledged <code>{}</code>: {}",
+            commander_events::short_event_id(event.id.as_str()),
+            html_escape(&event.title)
+        ))
+    })
+    .await;
+
+    let text = match result {
+        Ok(Ok(text)) => text,
+        Ok(Err(e)) => format!("❌ {}", html_escape(&e.to_string())),
+        Err(e) => format!("❌ /ack failed: {}", html_escape(&e.to_string())),
+    };
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+    Ok(())
+}
+
+/// Handle the /snooze command: hold notifications for a while, end the
+/// snooze, or show whether one is running.
+pub async fn handle_snooze(
+    bot: Bot,
+    msg: Message,
+    state: Arc<TelegramState>,
+    arg: String,
+) -> ResponseResult<()> {
+    use commander_core::dnd;
+
+    if !state.is_authorized(msg.chat.id.0).await {
+        bot.send_message(
+            msg.chat.id,
+            "Not authorized. Use <code>/pair &lt;code&gt;</code> first.",
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    let breakthrough = commander_core::Settings::load(&commander_core::config_file())
+        .unwrap_or_default()
+        .notifications
+        .dnd
+        .critical_breaks_through;
+    let arg = arg.trim();
+    let text = match arg {
+        "" => format_snooze_status(dnd::snoozed_until(), breakthrough),
+        "off" | "end" | "stop" => match dnd::end_snooze() {
+            Ok(true) => "🔔 Snooze ended. Notifications held meanwhile follow as a digest.".to_string(),
+            Ok(false) => "🔔 Notifications were not snoozed.".to_string(),
+            Err(e) => error_reply("Could not end the snooze", e),
+        },
+        _ => match dnd::parse_duration(arg) {
+            Some(duration) => match dnd::snooze(duration) {
+                Ok(until) => format_snooze_status(Some(until), breakthrough),
+                Err(e) => error_reply("Could not snooze", e),
+            },
+            None => "Usage: /snooze 2h (or 30m, 1h30m, 1d), /snooze off".to_string(),
+        },
+    };
+    bot.send_message(msg.chat.id, text).await?;
+    Ok(())
+}
+
+/// Describe the running snooze, or say there is none.
+fn format_snooze_status(until: Option<chrono::DateTime<chrono::Utc>>, breakthrough: bool) -> String {
+    let Some(until) = until else {
+        return "🔔 Notifications are not snoozed. /snooze 2h holds them for two hours.".to_string();
+    };
+    let mut text = format!(
+        "🔕 Notifications snoozed until {}. You'll get a digest when it ends; /snooze off ends it now.",
+        until.with_timezone(&chrono::Local).format("%a %H:%M")
+    );
+    if breakthrough {
+        text.push_str("\nCritical alerts still come through.");
+    }
+    text
+}
+
+/// Format pending blocking events with their short IDs for /ack.
+fn format_blocking_events(events: &[commander_models::Event]) -> String {
+    if events.is_empty() {
+        return "✨ No pending blocking events".to_string();
+    }
+    let mut text = String::from("⏰ <b>Pending blocking events</b>\n");
+    for event in events {
+        text.push_str(&format!(
+            "<code>{}</code> [{}] {}\n",
+            commander_events::short_event_id(event.id.as_str()),
+            html_escape(event.project_id.as_str()),
+            html_escape(&event.title)
+        ));
+    }
+    text.push_str("\nReply /ack &lt;event-id&gt; to acknowledge.");
+    text
+}
+
+/// Format a guardrail approval prompt for Telegram.
+#[cfg(feature = "agents")]
+pub(crate) fn format_approval_prompt(approval: &commander_orchestrator::PendingApproval) -> String {
+    format!(
+        "⚠️ <b>Approval required</b> ({})\n{}\n\n<code>{}</code>\n\nReply /approve to run it or /deny to block it.",
+        html_escape(&approval.tool_name),
+        html_escape(&approval.reason),
+        html_escape(&approval.command)
+    )
+}
+
+/// Format an error diagnosis card for Telegram.
+#[cfg(feature = "agents")]
+pub(crate) fn format_diagnosis(diagnosis: &commander_core::ErrorDiagnosis) -> String {
+    let mut text = format!(
+        "🩺 <b>Error in {}</b>\n<code>{}</code>\n\n<b>Cause:</b> {}\n<b>Suggested fix:</b> {}",
+        html_escape(&diagnosis.session_id),
+        html_escape(&diagnosis.error),
+        html_escape(&diagnosis.cause),
+        html_escape(&diagnosis.suggestion)
+    );
+    match diagnosis.prior_occurrences.len() {
+        0 => {}
+        1 => text.push_str("\n<i>Seen once before</i>"),
+        n => text.push_str(&format!("\n<i>Seen {} times before</i>", n)),
+    }
+    text
+}
+
+/// Apply/Ignore buttons for an error diagnosis.
+#[cfg(feature = "agents")]
+pub(crate) fn diagnosis_keyboard(id: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("🔧 Apply suggestion", format!("diagnosis:apply:{}", id)),
+        InlineKeyboardButton::callback("🙈 Ignore", format!("diagnosis:ignore:{}", id)),
+    ]])
+}
+
+/// Handle a diagnosis card's buttons (format: "<apply|ignore>:<id>").
+///
+/// "apply" sends the fix prompt to the session like a typed message.
+#[cfg(feature = "agents")]
+async fn handle_diagnosis_decision(
+    bot: Bot,
+    q: CallbackQuery,
+    state: Arc<TelegramState>,
+    rest: &str,
+) -> ResponseResult<()> {
+    let Some(card) = q.message.as_ref().and_then(|m| m.regular_message()) else {
+        return Ok(());
+    };
+    let chat_id = card.chat.id;
+
+    if !state.is_authorized(chat_id.0).await {
+        bot.send_message(chat_id, "Not authorized. Use <code>/pair &lt;code&gt;</code> first.")
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
+    }
+
+    let Some((action, id)) = rest.split_once(':') else {
+        warn!(data = %rest, "Malformed diagnosis callback");
+        return Ok(());
+    };
+    // Taking the diagnosis also keeps a double tap from applying it twice
+    let Some(diagnosis) = state.take_diagnosis(id).await else {
+        bot.edit_message_text(chat_id, card.id, "🩺 This suggestion was already handled.")
+            .await?;
+        return Ok(());
+    };
+    let card_text = format_diagnosis(&diagnosis);
+
+    if action != "apply" {
+        bot.edit_message_text(chat_id, card.id, format!("{}\n\n🙈 Ignored", card_text))
+            .parse_mode(teloxide::types::ParseMode::Html)
+            .await?;
+        return Ok(());
+    }
+
+    // Outside topics, only apply to the session the error came from
+    if card.thread_id.is_none()
+        && state.get_current_tmux_session(chat_id.0).await.as_deref() != Some(diagnosis.session_id.as_str())
+    {
+        bot.edit_message_text(
+            chat_id,
+            card.id,
+            format!("{}\n\n⚠️ Not connected to {} anymore", card_text, html_escape(&diagnosis.session_id)),
+        )
+        .parse_mode(teloxide::types::ParseMode::Html)
+        .await?;
+        return Ok(());
+    }
+
+    bot.edit_message_text(
+        chat_id,
+        card.id,
+        format!("{}\n\n🔧 Sent: <i>{}</i>", card_text, html_escape(&diagnosis.fix_prompt)),
+    )
+    .parse_mode(teloxide::types::ParseMode::Html)
+    .await?;
+    info!(chat_id = %chat_id.0, diagnosis = %diagnosis.id, "Diagnosis fix applied via button");
+    route_text(bot, card.clone(), state, diagnosis.fix_prompt).await
+}
+
+/// Truncate a string to fit within Telegram's message length limit.
+fn truncate_for_telegram(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let truncated: String = text.chars().take(max_chars).collect();
+        format!("{}...\n<i>[truncated]</i>", truncated)
+    }
+}
+
+pub async fn handle_command(
+    bot: Bot,
+    msg: Message,
+    cmd: Command,
+    state: Arc<TelegramState>,
+) -> ResponseResult<()> {
+    match cmd {
+        Command::Start(args) => handle_start(bot, msg, state, args).await,
+        Command::Help => handle_help(bot, msg).await,
+        Command::Pair(code) => handle_pair(bot, msg, state, code).await,
+        Command::Connect(project) => handle_connect(bot, msg, state, project).await,
+        Command::C(project) => handle_connect(bot, msg, state, project).await,
+        Command::Disconnect => handle_disconnect(bot, msg, state).await,
+        Command::Use(project) => handle_use(bot, msg, state, project).await,
+        Command::Active => handle_active(bot, msg, state).await,
+        Command::Takeover => handle_takeover(bot, msg, state).await,
+        Command::Stop(session) => handle_stop(bot, msg, state, session).await,
+        Command::S(session) => handle_stop(bot, msg, state, session).await,
+        Command::Pause(project) => handle_pause(bot, msg, state, project, true).await,
+        Command::Resume(project) => handle_pause(bot, msg, state, project, false).await,
+        Command::ConnectTree(session) => handle_connect_tree(bot, msg, state, session).await,
+        Command::Ct(session) => handle_connect_tree(bot, msg, state, session).await,
+        Command::Send(message) => handle_send(bot, msg, state, message).await,
+        Command::Status => handle_status(bot, msg, state).await,
+        Command::List => handle_list(bot, msg, state).await,
+        Command::Ls => handle_list(bot, msg, state).await,
+        Command::Link(session) => handle_link(bot, msg, state, session).await,
+        Command::GroupMode => handle_groupmode(bot, msg, state).await,
+        Command::Topic(session) => handle_topic(bot, msg, state, session).await,
+        Command::Topics => handle_topics(bot, msg, state).await,
+        Command::Agents => handle_agents(bot, msg, state).await,
+        Command::Spawn(args) => handle_spawn(bot, msg, state, args).await,
+        Command::Mpm => handle_mpm_status(bot, msg, state).await,
+        Command::Ask(question) => handle_ask(bot, msg, state, question).await,
+        Command::Approve(id) => handle_approval(bot, msg, state, true, id).await,
+        Command::Deny(id) => handle_approval(bot, msg, state, false, id).await,
+        Command::Approvals => handle_approvals(bot, msg, state).await,
+        Command::ApprovalMode(arg) => handle_approval_mode(bot, msg, state, arg).await,
+        Command::Diff(file) => handle_diff(bot, msg, state, file).await,
+        Command::Ack(id) => handle_ack(bot, msg, state, id).await,
+        Command::Snooze(arg) => handle_snooze(bot, msg, state, arg).await,
+        Command::Run(args) => handle_run(bot, msg, state, args).await,
+        Command::Snippets => handle_snippets(bot, msg, state).await,
+        Command::Retest => handle_retest(bot, msg, state).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approval_request_message() {
+        let mut queue = commander_core::ApprovalQueue::default();
+        let request = queue.push(
+            "proj",
+            commander_core::approvals::FILESYSTEM_TOOL,
+            serde_json::json!({}),
+            "rm <old>.txt",
+        );
+        let text = format_approval_request(&request);
+        assert!(text.contains(&request.id));
+        assert!(text.contains("rm &lt;old&gt;.txt"));
+
+        let keyboard = approval_keyboard(&request.id);
+        assert_eq!(keyboard.inline_keyboard[0].len(), 2);
+    }
+
+    #[cfg(feature = "agents")]
+    #[test]
+    fn test_format_diagnosis() {
+        let reply = commander_core::diagnosis::DiagnosisReply {
+            cause: "x is undefined".into(),
+            suggestion: "Declare x".into(),
+            fix_prompt: "Declare x before use".into(),
+        };
+        let diagnosis = commander_core::ErrorDiagnosis::new("api", "error: x < y", reply, vec!["x".into()]);
+        let text = format_diagnosis(&diagnosis);
+        assert!(text.contains("error: x &lt; y"));
+        assert!(text.contains("Seen once before"));
+
+        let keyboard = diagnosis_keyboard(&diagnosis.id);
+        assert_eq!(keyboard.inline_keyboard[0].len(), 2);
+    }
+
+    #[test]
+    fn test_format_active_sessions() {
+        let text = format_active_sessions(Some("api"), &["web<1>".to_string()]);
+        assert!(text.contains("Active: <b>api</b>"));
+        assert!(text.contains("<code>web&lt;1&gt;</code>"));
+        assert!(text.contains("/use"));
+
+        assert!(format_active_sessions(None, &[]).contains("Not following"));
+        assert!(!format_active_sessions(Some("api"), &[]).contains("Following"));
+    }
+
+    #[test]
+    fn test_format_snooze_status() {
+        assert!(format_snooze_status(None, true).contains("not snoozed"));
+
+        let until = chrono::Utc::now() + chrono::Duration::hours(2);
+        let text = format_snooze_status(Some(until), true);
+        assert!(text.contains("snoozed until"));
+        assert!(text.contains("Critical alerts"));
+        assert!(!format_snooze_status(Some(until), false).contains("Critical alerts"));
+    }
+
+    #[test]
+    fn test_error_reply() {
+        let text = error_reply("Failed to connect", &TelegramError::NoToken);
+        assert!(text.starts_with("❌ Failed to connect: "));
+        assert!(text.contains("\nHint: "));
+    }
+
+    #[test]
+    fn test_menu_commands_skip_aliases() {
+        let commands = menu_commands();
+        assert!(commands.iter().any(|c| c.command == "connect"));
+        assert!(commands.iter().all(|c| c.command != "c" && c.command != "ls"));
+        // Telegram rejects the whole list if one command is malformed
+        assert!(commands.iter().all(|c| c.command.len() <= 32
+            && c.command.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+            && c.description.len() <= 256));
+    }
+
+    #[test]
+    fn test_connect_menu_keyboard() {
+        let names: Vec<String> = ["api", "web", "docs", &"x".repeat(60)].iter().map(|s| s.to_string()).collect();
+        let keyboard = connect_menu_keyboard(&names);
+        assert_eq!(keyboard.inline_keyboard.len(), 2);
+        assert_eq!(keyboard.inline_keyboard[0].len(), 2);
+        assert_eq!(keyboard.inline_keyboard[1].len(), 1);
+    }
+
+    #[test]
+    fn test_format_diff_summary() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-if a < b {}\n+if a > b {}\n";
+        let text = format_diff_summary("proj", diff);
+        assert!(text.contains("1 file changed, +1 -1"));
+        assert!(text.contains("+if a &gt; b {}"));
+        assert!(format_diff_summary("proj", "").contains("no uncommitted changes"));
+    }
+
+    #[test]
+    fn test_format_blocking_events() {
+        assert!(format_blocking_events(&[]).contains("No pending blocking events"));
+
+        let event = commander_models::Event::new(
+            "proj",
+            commander_models::EventType::Approval,
+            "Drop <table>?",
+        );
+        let text = format_blocking_events(std::slice::from_ref(&event));
+        assert!(text.contains(&format!("<code>{}</code>", &event.id.as_str()[..12])));
+        assert!(text.contains("Drop &lt;table&gt;?"));
+    }
+
+    #[test]
+    fn test_format_handoff_note_nothing_to_report() {
+        assert!(format_handoff_note("proj", &AttachOutcome::Attached, None).is_none());
+    }
+
+    #[test]
+    fn test_format_handoff_note_context_and_holder() {
+        let mut registry = commander_core::SessionRegistry::default();
+        registry.attach("proj", commander_core::Frontend::Tui, None);
+        let outcome = registry.attach("proj", commander_core::Frontend::Telegram, None);
+
+        let note = format_handoff_note("proj", &outcome, Some("[TUI] > fix <b>")).unwrap();
+        assert!(note.contains("[TUI] &gt; fix &lt;b&gt;"));
+        assert!(note.contains("attached in TUI"));
+        assert!(note.contains("/takeover"));
+    }
 
     #[test]
     fn test_extract_git_branch_parens() {
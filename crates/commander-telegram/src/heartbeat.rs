@@ -0,0 +1,257 @@
+//! Liveness reporting for the Telegram bot.
+//!
+//! The PID file only says the bot process exists. A running bot also
+//! rewrites `telegram-heartbeat.json` every [`HEARTBEAT_INTERVAL`] with its
+//! uptime, the last update it processed and its error count, so a bot whose
+//! event loop is stuck shows up as [`BotHealth::Stale`]. `commander telegram
+//! status` and the TUI status bar report the health, and the daemon restarts
+//! a hosted bot whose heartbeat goes stale.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use commander_core::config;
+
+use crate::daemon;
+
+/// How often a running bot rewrites its heartbeat.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Heartbeat age after which a bot counts as stale (three missed beats).
+pub const STALE_AFTER: Duration = Duration::from_secs(90);
+
+/// Contents of the heartbeat file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    /// Process running the bot (the standalone bot or the daemon).
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    /// When the heartbeat was last written.
+    pub updated_at: DateTime<Utc>,
+    /// When the bot last processed a Telegram update.
+    pub last_update_at: Option<DateTime<Utc>>,
+    /// Updates processed since start.
+    pub updates: u64,
+    /// Handler and listener errors since start.
+    pub errors: u64,
+    pub last_error: Option<String>,
+}
+
+impl Heartbeat {
+    /// The heartbeat of the running (or last) bot.
+    pub fn load() -> Option<Self> {
+        Self::load_from(&config::telegram_heartbeat_file())
+    }
+
+    fn load_from(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// How long the bot had been up when the heartbeat was written.
+    pub fn uptime(&self) -> Duration {
+        (self.updated_at - self.started_at).to_std().unwrap_or_default()
+    }
+
+    /// Time since the heartbeat was written.
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        (now - self.updated_at).to_std().unwrap_or_default()
+    }
+}
+
+/// Health of the Telegram bot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotHealth {
+    /// Running, with a recent heartbeat.
+    Healthy,
+    /// Running, but its heartbeat is missing or old.
+    Stale,
+    /// Not running.
+    Down,
+}
+
+impl fmt::Display for BotHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BotHealth::Healthy => "healthy",
+            BotHealth::Stale => "stale",
+            BotHealth::Down => "down",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Current health of the Telegram bot, wherever it runs.
+pub fn health() -> BotHealth {
+    match Heartbeat::load() {
+        Some(heartbeat) => {
+            let alive = daemon::is_process_running(heartbeat.pid);
+            classify(Some(&heartbeat), alive, Utc::now())
+        }
+        None => classify(None, daemon::is_running(), Utc::now()),
+    }
+}
+
+/// Health from a heartbeat and whether its process (or, without one, the
+/// PID file's process) is alive.
+fn classify(heartbeat: Option<&Heartbeat>, alive: bool, now: DateTime<Utc>) -> BotHealth {
+    match heartbeat {
+        _ if !alive => BotHealth::Down,
+        Some(heartbeat) if heartbeat.age(now) <= STALE_AFTER => BotHealth::Healthy,
+        _ => BotHealth::Stale,
+    }
+}
+
+/// Counters the bot keeps for its heartbeat.
+#[derive(Debug, Default)]
+pub struct HeartbeatStats {
+    updates: AtomicU64,
+    errors: AtomicU64,
+    last_update_at: Mutex<Option<DateTime<Utc>>>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl HeartbeatStats {
+    /// Count a processed update.
+    pub fn record_update(&self) {
+        self.updates.fetch_add(1, Ordering::Relaxed);
+        *self.last_update_at.lock().unwrap_or_else(|e| e.into_inner()) = Some(Utc::now());
+    }
+
+    /// Count an error.
+    pub fn record_error(&self, error: impl fmt::Display) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(error.to_string());
+    }
+
+    /// Heartbeat of a bot started at `started_at`, written now.
+    fn heartbeat(&self, started_at: DateTime<Utc>) -> Heartbeat {
+        Heartbeat {
+            pid: std::process::id(),
+            started_at,
+            updated_at: Utc::now(),
+            last_update_at: *self.last_update_at.lock().unwrap_or_else(|e| e.into_inner()),
+            updates: self.updates.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            last_error: self.last_error.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+}
+
+/// Heartbeat writer of a running bot. Dropping it stops the heartbeat and
+/// removes the file, also when the bot's future is dropped mid-run.
+pub struct HeartbeatTask(JoinHandle<()>);
+
+impl HeartbeatTask {
+    /// Start writing the heartbeat from `stats`.
+    pub fn spawn(stats: Arc<HeartbeatStats>) -> Self {
+        Self(tokio::spawn(heartbeat_loop(stats)))
+    }
+}
+
+impl Drop for HeartbeatTask {
+    fn drop(&mut self) {
+        self.0.abort();
+        clear();
+    }
+}
+
+/// Write the heartbeat every [`HEARTBEAT_INTERVAL`], forever.
+async fn heartbeat_loop(stats: Arc<HeartbeatStats>) {
+    let started_at = Utc::now();
+    let mut ticker = tokio::time::interval(HEARTBEAT_INTERVAL);
+    loop {
+        ticker.tick().await;
+        let heartbeat = stats.heartbeat(started_at);
+        let result = config::ensure_runtime_state_dir().and_then(|_| {
+            let json = serde_json::to_string_pretty(&heartbeat)?;
+            fs::write(config::telegram_heartbeat_file(), json)
+        });
+        match result {
+            Ok(()) => debug!(updates = heartbeat.updates, errors = heartbeat.errors, "Wrote heartbeat"),
+            Err(e) => warn!(error = %e, "Failed to write heartbeat"),
+        }
+    }
+}
+
+/// Remove the heartbeat of a bot that stopped.
+pub fn clear() {
+    let _ = fs::remove_file(config::telegram_heartbeat_file());
+}
+
+/// Resolve once the heartbeat of a bot running in this process goes stale,
+/// with its age. A heartbeat that never appears counts as stale once
+/// [`STALE_AFTER`] has passed.
+pub async fn wait_until_stale() -> Duration {
+    let watching_since = Instant::now();
+    loop {
+        tokio::time::sleep(HEARTBEAT_INTERVAL).await;
+        let age = match Heartbeat::load().filter(|h| h.pid == std::process::id()) {
+            Some(heartbeat) => heartbeat.age(Utc::now()),
+            None => watching_since.elapsed(),
+        };
+        if age > STALE_AFTER {
+            return age;
+        }
+    }
+}
+
+/// "3d 4h", "2h 5m", "12m" or "40s".
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_classify_health() {
+        let stats = HeartbeatStats::default();
+        stats.record_update();
+        stats.record_error("Bad Request: message is too long");
+        let heartbeat = stats.heartbeat(Utc::now() - chrono::Duration::hours(2));
+        assert_eq!(heartbeat.updates, 1);
+        assert_eq!(heartbeat.errors, 1);
+        assert_eq!(format_uptime(heartbeat.uptime()), "2h 0m");
+
+        let now = heartbeat.updated_at;
+        assert_eq!(classify(Some(&heartbeat), true, now), BotHealth::Healthy);
+        assert_eq!(classify(Some(&heartbeat), false, now), BotHealth::Down);
+        let later = now + chrono::Duration::seconds(120);
+        assert_eq!(classify(Some(&heartbeat), true, later), BotHealth::Stale);
+        assert_eq!(classify(None, true, now), BotHealth::Stale);
+        assert_eq!(classify(None, false, now), BotHealth::Down);
+    }
+
+    #[test]
+    fn test_heartbeat_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("telegram-heartbeat.json");
+        let heartbeat = HeartbeatStats::default().heartbeat(Utc::now());
+        fs::write(&path, serde_json::to_string(&heartbeat).unwrap()).unwrap();
+
+        assert_eq!(Heartbeat::load_from(&path), Some(heartbeat));
+        assert!(Heartbeat::load_from(&dir.path().join("missing.json")).is_none());
+    }
+}
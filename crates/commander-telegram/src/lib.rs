@@ -8,7 +8,9 @@
 //! - Connect to Commander projects from Telegram
 //! - Send messages to Claude Code and receive responses
 //! - Automatic response summarization via OpenRouter
-//! - ngrok integration for webhook tunneling
+//! - Webhook mode behind ngrok, a Cloudflare tunnel, Tailscale Funnel or a
+//!   reverse proxy (`[telegram.tunnel]` in `config.toml`)
+//! - Voice messages transcribed to text via a Whisper-compatible API
 //! - **AgentOrchestrator integration** (with `agents` feature): Routes messages
 //!   through LLM for intelligent interpretation and generates human-readable
 //!   notification summaries
@@ -22,11 +24,12 @@
 //!
 //! Required:
 //! - `TELEGRAM_BOT_TOKEN`: Bot token from @BotFather
-//! - `NGROK_AUTHTOKEN`: ngrok authentication token (for webhook mode)
+//! - `NGROK_AUTHTOKEN`: ngrok authentication token (for webhook mode via ngrok)
 //!
 //! Optional:
 //! - `OPENROUTER_API_KEY`: For response summarization (and agents feature)
 //! - `OPENROUTER_MODEL`: Model to use (default: anthropic/claude-sonnet-4)
+//! - `OPENAI_API_KEY`: For voice message transcription
 //! - `TELEGRAM_WEBHOOK_PORT`: Webhook port (default: 8443)
 //!
 //! # Example
@@ -44,7 +47,7 @@
 //!     // Start in polling mode (simpler, no ngrok needed)
 //!     bot.start_polling().await?;
 //!
-//!     // Or start with webhook (requires ngrok)
+//!     // Or start with webhook (through the configured tunnel)
 //!     // bot.start().await?;
 //!
 //!     Ok(())
@@ -66,15 +69,20 @@ pub mod error;
 pub mod event_consumer;
 pub mod features;
 pub mod handlers;
+pub mod heartbeat;
 pub mod ipc_client;
 pub mod ngrok;
-pub mod notifications;
-pub mod pairing;
+pub mod progress;
 pub mod session;
 pub mod session_log;
 pub mod state;
+pub mod tunnel;
 pub mod typing_throttle;
 pub mod version;
+pub mod voice;
+
+// Notification and pairing files are shared with other frontends via core
+pub use commander_core::{notifications, pairing};
 
 pub use bot::TelegramBot;
 pub use daemon::{
@@ -82,6 +90,7 @@ pub use daemon::{
     DaemonStatus, StartResult,
 };
 pub use error::{Result, TelegramError};
+pub use heartbeat::{BotHealth, Heartbeat};
 pub use ngrok::NgrokTunnel;
 pub use notifications::{
     get_unread_notifications, mark_notifications_read, notify_session_ready,
@@ -90,4 +99,5 @@ pub use notifications::{
 pub use pairing::{consume_pairing, create_pairing, generate_code};
 pub use session::UserSession;
 pub use state::{create_shared_state, TelegramState};
+pub use tunnel::{start_tunnel, Tunnel};
 pub use version::{check_rebuild, load_version, save_version, BotVersion};
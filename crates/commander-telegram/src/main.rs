@@ -6,8 +6,10 @@
 //! ```
 
 use clap::Parser;
-use commander_core::config;
+use commander_core::{config, SessionLogLayer};
 use commander_telegram::TelegramBot;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::EnvFilter;
 
 /// Commander Telegram Bot - interact with Claude Code from Telegram
@@ -15,7 +17,7 @@ use tracing_subscriber::EnvFilter;
 #[command(name = "commander-telegram")]
 #[command(about = "Telegram bot for Commander - interact with Claude Code remotely")]
 struct Args {
-    /// Use webhook mode with ngrok (default: polling mode)
+    /// Use webhook mode behind the [telegram.tunnel] backend (default: polling mode)
     #[arg(short, long)]
     webhook: bool,
 
@@ -49,8 +51,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ => "trace",
     };
 
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_new(filter).unwrap_or_else(|_| EnvFilter::new("info")))
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_filter(EnvFilter::try_new(filter).unwrap_or_else(|_| EnvFilter::new("info"))),
+        )
+        .with(SessionLogLayer::new(config::logs_dir(), "telegram").with_filter(LevelFilter::INFO))
         .init();
 
     // Get state directory
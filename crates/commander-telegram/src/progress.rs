@@ -0,0 +1,244 @@
+//! Single "working..." message streamed while a session runs.
+//!
+//! Instead of separate progress and summary messages, the poll loop keeps one
+//! message per waiting session and edits it as milestones (files changed,
+//! tests run, current step) and running summaries arrive. Edits are limited
+//! to one per [`MIN_EDIT_INTERVAL`] per message, and `Retry after N` responses
+//! from the API pause edits for that message; updates arriving in between are
+//! folded into the next edit rather than dropped.
+
+use std::time::{Duration, Instant};
+
+use commander_core::ProgressMilestones;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, LinkPreviewOptions, MessageId, ThreadId};
+use tracing::{debug, warn};
+
+use crate::typing_throttle::parse_retry_after;
+
+/// Minimum interval between edits of the same working message.
+///
+/// Telegram allows about 20 messages a minute in groups; edits count too.
+pub const MIN_EDIT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Changed files listed by name before the rest are counted.
+const MAX_LISTED_FILES: usize = 3;
+
+/// Milestone lines shown under the progress header.
+pub fn render_milestones(milestones: &ProgressMilestones) -> Vec<String> {
+    let mut lines = Vec::new();
+    let files = &milestones.files_changed;
+    if !files.is_empty() {
+        let mut listed = files
+            .iter()
+            .take(MAX_LISTED_FILES)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if files.len() > MAX_LISTED_FILES {
+            listed.push_str(&format!(" (+{} more)", files.len() - MAX_LISTED_FILES));
+        }
+        let noun = if files.len() == 1 { "file" } else { "files" };
+        lines.push(format!("✏️ {} {} changed: {}", files.len(), noun, listed));
+    }
+    if let Some(tests) = &milestones.tests {
+        let mut counts = format!("{} passed", tests.passed);
+        if tests.failed > 0 {
+            counts.push_str(&format!(", {} failed", tests.failed));
+        }
+        lines.push(format!("🧪 Tests: {}", counts));
+    }
+    if let Some(step) = &milestones.current_step {
+        lines.push(format!("▶️ {}", step));
+    }
+    lines
+}
+
+/// The working message of one waiting session.
+#[derive(Debug, Default)]
+pub struct WorkingMessage {
+    /// The sent message, once there is one.
+    pub message_id: Option<MessageId>,
+    /// Progress header and milestone lines.
+    progress: String,
+    /// Latest running summary.
+    summary: Option<String>,
+    /// Text of the last send or edit.
+    sent_text: String,
+    /// When the message was last sent or edited.
+    last_edit: Option<Instant>,
+    /// Edits are paused until this instant (from Retry-After).
+    suppressed_until: Option<Instant>,
+}
+
+impl WorkingMessage {
+    /// Create a working message that has not been sent yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the progress header and milestones.
+    pub fn set_progress(&mut self, progress: impl Into<String>) {
+        self.progress = progress.into();
+    }
+
+    /// Replace the running summary.
+    pub fn set_summary(&mut self, summary: impl Into<String>) {
+        self.summary = Some(summary.into());
+    }
+
+    /// The message text: progress, then the running summary.
+    pub fn render(&self) -> String {
+        match &self.summary {
+            Some(summary) if self.progress.is_empty() => summary.clone(),
+            Some(summary) => format!("{}\n\n{}", self.progress, summary),
+            None => self.progress.clone(),
+        }
+    }
+
+    /// The text to send now, if it changed and the rate limit allows an edit.
+    ///
+    /// Marks the text as sent; call [`Self::defer`] or [`Self::failed`] if
+    /// the request does not go through.
+    pub fn take_update(&mut self, now: Instant) -> Option<String> {
+        let text = self.render();
+        if text.is_empty() || text == self.sent_text {
+            return None;
+        }
+        if self.suppressed_until.is_some_and(|until| now < until) {
+            return None;
+        }
+        if self.last_edit.is_some_and(|last| now.duration_since(last) < MIN_EDIT_INTERVAL) {
+            return None;
+        }
+        self.last_edit = Some(now);
+        self.suppressed_until = None;
+        self.sent_text = text.clone();
+        Some(text)
+    }
+
+    /// Pause edits for `secs` seconds after a `Retry after` response.
+    pub fn defer(&mut self, now: Instant, secs: u64) {
+        self.suppressed_until = Some(now + Duration::from_secs(secs));
+        self.sent_text.clear();
+    }
+
+    /// Resend the current text on a later tick after a failed request.
+    pub fn failed(&mut self) {
+        self.sent_text.clear();
+    }
+
+    /// Send the message, or edit it, if an update is due.
+    pub async fn flush(&mut self, bot: &Bot, chat_id: ChatId, thread_id: Option<ThreadId>) {
+        let now = Instant::now();
+        let Some(text) = self.take_update(now) else {
+            return;
+        };
+
+        let result = match self.message_id {
+            Some(msg_id) => bot.edit_message_text(chat_id, msg_id, &text).await.map(|_| ()),
+            None => {
+                let mut req = bot
+                    .send_message(chat_id, &text)
+                    .disable_notification(true)
+                    .link_preview_options(no_preview());
+                if let Some(tid) = thread_id {
+                    req = req.message_thread_id(tid);
+                }
+                req.await.map(|sent| self.message_id = Some(sent.id))
+            }
+        };
+
+        if let Err(e) = result {
+            let err = e.to_string();
+            if let Some(secs) = parse_retry_after(&err) {
+                self.defer(now, secs);
+                warn!(chat_id = %chat_id.0, retry_after_secs = secs, "Telegram rate-limited working message; backing off");
+            } else if err.contains("message is not modified") {
+                debug!(chat_id = %chat_id.0, "Working message unchanged");
+            } else {
+                if err.contains("message to edit not found") {
+                    // Deleted by the user: send a fresh one next time.
+                    self.message_id = None;
+                }
+                self.failed();
+                warn!(chat_id = %chat_id.0, error = %e, "Failed to update working message");
+            }
+        }
+    }
+}
+
+/// Link preview options for working messages (no previews on status messages).
+fn no_preview() -> LinkPreviewOptions {
+    LinkPreviewOptions {
+        is_disabled: true,
+        url: None,
+        prefer_small_media: false,
+        prefer_large_media: false,
+        show_above_text: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commander_core::TestResult;
+
+    #[test]
+    fn test_render_milestones() {
+        let milestones = ProgressMilestones {
+            files_changed: vec!["a.rs", "b.rs", "c.rs", "d.rs", "e.rs"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            tests: Some(TestResult { passed: 12, failed: 1, skipped: 0 }),
+            current_step: Some("Bash(cargo test)".to_string()),
+        };
+        assert_eq!(
+            render_milestones(&milestones),
+            vec![
+                "✏️ 5 files changed: a.rs, b.rs, c.rs (+2 more)",
+                "🧪 Tests: 12 passed, 1 failed",
+                "▶️ Bash(cargo test)",
+            ]
+        );
+        assert!(render_milestones(&ProgressMilestones::default()).is_empty());
+    }
+
+    #[test]
+    fn test_working_message_rate_limit() {
+        let start = Instant::now();
+        let mut working = WorkingMessage::new();
+        assert_eq!(working.take_update(start), None);
+
+        working.set_progress("⏳ 5 lines");
+        assert_eq!(working.take_update(start).as_deref(), Some("⏳ 5 lines"));
+
+        // Updates inside the interval are held, then sent together.
+        working.set_progress("⏳ 10 lines");
+        working.set_summary("📝 Editing the parser");
+        assert_eq!(working.take_update(start + Duration::from_secs(1)), None);
+        assert_eq!(
+            working.take_update(start + MIN_EDIT_INTERVAL).as_deref(),
+            Some("⏳ 10 lines\n\n📝 Editing the parser")
+        );
+
+        // Unchanged text is not re-sent.
+        assert_eq!(working.take_update(start + MIN_EDIT_INTERVAL * 3), None);
+    }
+
+    #[test]
+    fn test_working_message_retry_after() {
+        let start = Instant::now();
+        let mut working = WorkingMessage::new();
+        working.set_progress("⏳ 5 lines");
+        assert!(working.take_update(start).is_some());
+
+        working.defer(start, 20);
+        assert_eq!(working.take_update(start + Duration::from_secs(10)), None);
+        assert_eq!(
+            working.take_update(start + Duration::from_secs(20)).as_deref(),
+            Some("⏳ 5 lines")
+        );
+    }
+}
@@ -10,10 +10,16 @@ use std::time::Duration;
 use commander_adapters::AdapterRegistry;
 use commander_core::{
     clean_response, clean_screen_preview, config::authorized_chats_file, find_new_lines,
-    is_claude_ready, is_mpm_ready, is_summarization_available, summarize_incremental_tiered,
-    summarize_with_fallback, config::runtime_state_dir,
+    is_claude_ready, is_mpm_ready, is_summarization_available, mark_seen, recap_async,
+    summarize_incremental_tiered,
+    summarize_with_fallback, config::runtime_state_dir, update_session_registry, AttachOutcome,
+    Attachment, Frontend, HandoffDirection, PauseMethod, PausedSession, ProgressMilestones,
+    VoiceSettings,
 };
+use commander_core::pause;
+use commander_core::send_lock::SEND_LOCK_TIMEOUT;
 use commander_persistence::StateStore;
+use commander_core::CommandSender;
 use commander_tmux::TmuxOrchestrator;
 use teloxide::payloads::SendMessageSetters;
 use teloxide::prelude::Requester;
@@ -24,11 +30,14 @@ use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
 #[cfg(feature = "agents")]
-use commander_orchestrator::AgentOrchestrator;
+use commander_core::ErrorDiagnosis;
+#[cfg(feature = "agents")]
+use commander_orchestrator::{AgentOrchestrator, PendingApproval};
 
 use crate::error::{Result, TelegramError};
 use crate::ipc_client::DaemonClient;
 use crate::pairing;
+use crate::progress::render_milestones;
 use crate::session::{PersistedSession, UserSession};
 use crate::session_log::SessionLogger;
 use crate::typing_throttle::TypingThrottle;
@@ -36,7 +45,7 @@ use crate::typing_throttle::TypingThrottle;
 /// Result from polling output - represents different stages of output collection.
 #[derive(Debug)]
 pub enum PollResult {
-    /// Progress update during output collection (line count increased).
+    /// Progress update during output collection: line count and milestones reached.
     Progress(String),
     /// Incremental summary of content collected so far (every 50 lines).
     IncrementalSummary(String),
@@ -75,6 +84,52 @@ pub struct GroupChatConfig {
     pub topics: HashMap<i32, TopicConfig>,
 }
 
+impl GroupChatConfig {
+    /// Thread of the topic bound to `session` (a tmux session or project name).
+    pub fn thread_for_session(&self, session: &str) -> Option<i32> {
+        let name = session.strip_prefix("commander-").unwrap_or(session);
+        self.topics
+            .values()
+            .filter(|t| t.tmux_session == session || t.session_name == name)
+            .map(|t| t.thread_id)
+            .min()
+    }
+}
+
+/// Sessions a chat follows besides its active one.
+///
+/// `/use` parks the active session here instead of dropping it, so switching
+/// back resumes it as it was.
+#[derive(Debug, Default)]
+struct FollowedSessions {
+    /// Parked sessions, in the order they were parked.
+    parked: Vec<UserSession>,
+    /// Project to make active again once an @-addressed response is delivered.
+    return_to: Option<String>,
+}
+
+impl FollowedSessions {
+    /// Parks `session`, replacing an older copy of the same project.
+    fn park(&mut self, session: UserSession) {
+        self.parked.retain(|s| s.project_name != session.project_name);
+        self.parked.push(session);
+    }
+
+    /// Takes the parked session named `name` (project or tmux session).
+    fn take(&mut self, name: &str) -> Option<UserSession> {
+        let index = self
+            .parked
+            .iter()
+            .position(|s| s.project_name == name || s.tmux_session == name)?;
+        Some(self.parked.remove(index))
+    }
+
+    /// Project names of the parked sessions.
+    fn names(&self) -> Vec<String> {
+        self.parked.iter().map(|s| s.project_name.clone()).collect()
+    }
+}
+
 /// Load group chat configs from disk.
 fn load_group_configs() -> HashMap<i64, GroupChatConfig> {
     let path = runtime_state_dir().join("group_configs.json");
@@ -278,7 +333,8 @@ const TMUX_CAPTURE_TIMEOUT: Duration = Duration::from_secs(2);
 const TMUX_SEND_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Call `TmuxOrchestrator::capture_output` on a blocking thread with a
-/// bounded timeout.
+/// bounded timeout. Secrets in the captured screen are redacted before it can
+/// reach a chat.
 ///
 /// This exists to protect the async runtime from a misbehaving tmux server
 /// (see `docs/research/handler-hang-2026-04-10.md`). Callers MUST NOT hold
@@ -293,7 +349,7 @@ async fn capture_output_safe(
     });
 
     match timeout(TMUX_CAPTURE_TIMEOUT, join).await {
-        Ok(Ok(Ok(output))) => Ok(output),
+        Ok(Ok(Ok(output))) => Ok(commander_core::redact(&output)),
         Ok(Ok(Err(e))) => Err(TelegramError::TmuxError(format!(
             "capture_output failed: {}",
             e
@@ -316,29 +372,30 @@ async fn capture_output_safe(
 }
 
 /// Call `TmuxOrchestrator::send_line` on a blocking thread with a bounded
-/// timeout. Same rules as `capture_output_safe`: no async locks held.
+/// timeout, once the session's send lock is taken (so a message from another
+/// frontend is not interleaved). Same rules as `capture_output_safe`: no
+/// async locks held.
 async fn send_line_safe(
     tmux: Arc<TmuxOrchestrator>,
     session_name: String,
     text: String,
 ) -> Result<()> {
     let join = tokio::task::spawn_blocking(move || {
+        let _lock = commander_core::acquire_send_lock(&session_name, "Telegram")
+            .map_err(|e| TelegramError::SessionError(e.to_string()))?;
         tmux.send_line(&session_name, None, &text)
+            .map_err(|e| TelegramError::TmuxError(format!("send_line failed: {}", e)))
     });
 
-    match timeout(TMUX_SEND_TIMEOUT, join).await {
-        Ok(Ok(Ok(()))) => Ok(()),
-        Ok(Ok(Err(e))) => Err(TelegramError::TmuxError(format!(
-            "send_line failed: {}",
-            e
-        ))),
+    match timeout(TMUX_SEND_TIMEOUT + SEND_LOCK_TIMEOUT, join).await {
+        Ok(Ok(result)) => result,
         Ok(Err(e)) => Err(TelegramError::TmuxError(format!(
             "send_line task join error: {}",
             e
         ))),
         Err(_) => Err(TelegramError::TmuxError(format!(
             "send_line timed out after {}s",
-            TMUX_SEND_TIMEOUT.as_secs()
+            (TMUX_SEND_TIMEOUT + SEND_LOCK_TIMEOUT).as_secs()
         ))),
     }
 }
@@ -349,6 +406,9 @@ pub struct TelegramState {
     /// For 1:1 chats: chat_id -> session
     /// For group topics: we use (chat_id, thread_id) key encoded as i64
     sessions: RwLock<HashMap<i64, UserSession>>,
+    /// Sessions each 1:1 chat follows besides its active one (chat_id -> parked).
+    /// Always locked after `sessions` when both are held.
+    followed: RwLock<HashMap<i64, FollowedSessions>>,
     /// Tmux orchestrator for session management.
     ///
     /// Wrapped in `Arc` so it can be cheaply cloned into `spawn_blocking`
@@ -378,15 +438,29 @@ pub struct TelegramState {
     /// Cache for /ls summaries: session_name -> (output_hash, cached_summary).
     /// Avoids redundant LLM calls when tmux output hasn't changed.
     ls_summary_cache: RwLock<HashMap<String, (u64, String)>>,
+    /// Speech-to-text settings for voice messages (`[telegram.voice]`).
+    voice_settings: RwLock<VoiceSettings>,
+    /// Low-confidence transcriptions awaiting confirmation:
+    /// (chat_id, confirmation message_id) → transcribed text.
+    pending_transcriptions: RwLock<HashMap<(i64, i32), String>>,
     /// Agent orchestrator for LLM-based message processing (feature-gated).
     #[cfg(feature = "agents")]
     orchestrator: RwLock<Option<AgentOrchestrator>>,
+    /// Completed responses showing an error, awaiting diagnosis:
+    /// session key → (tmux session, adapter type, raw output).
+    #[cfg(feature = "agents")]
+    error_outputs: RwLock<HashMap<i64, (String, String, String)>>,
+    /// Diagnoses whose card hasn't been answered yet, by diagnosis ID.
+    #[cfg(feature = "agents")]
+    pending_diagnoses: RwLock<HashMap<String, ErrorDiagnosis>>,
 }
 
 impl TelegramState {
     /// Create a new TelegramState instance.
     pub fn new(state_dir: &std::path::Path) -> Self {
-        let tmux = TmuxOrchestrator::new().ok().map(Arc::new);
+        let tmux = TmuxOrchestrator::new()
+            .ok()
+            .map(|tmux| Arc::new(tmux.with_sender(CommandSender::Telegram)));
         let adapters = AdapterRegistry::new();
         let store = StateStore::new(state_dir);
 
@@ -414,6 +488,7 @@ impl TelegramState {
 
         Self {
             sessions: RwLock::new(HashMap::new()),
+            followed: RwLock::new(HashMap::new()),
             tmux,
             adapters,
             store,
@@ -425,8 +500,14 @@ impl TelegramState {
             at_reply_map: Arc::new(RwLock::new(HashMap::new())),
             typing_throttle: TypingThrottle::new(),
             ls_summary_cache: RwLock::new(HashMap::new()),
+            voice_settings: RwLock::new(VoiceSettings::default()),
+            pending_transcriptions: RwLock::new(HashMap::new()),
             #[cfg(feature = "agents")]
             orchestrator: RwLock::new(None),
+            #[cfg(feature = "agents")]
+            error_outputs: RwLock::new(HashMap::new()),
+            #[cfg(feature = "agents")]
+            pending_diagnoses: RwLock::new(HashMap::new()),
         }
     }
 
@@ -475,6 +556,32 @@ impl TelegramState {
         cache.remove(session_name);
     }
 
+    /// Current speech-to-text settings for voice messages.
+    pub async fn voice_settings(&self) -> VoiceSettings {
+        self.voice_settings.read().await.clone()
+    }
+
+    /// Apply `[telegram.voice]` settings from `config.toml`.
+    pub async fn set_voice_settings(&self, settings: VoiceSettings) {
+        *self.voice_settings.write().await = settings;
+    }
+
+    /// Hold a transcription until the user confirms the message asking about it.
+    pub async fn hold_transcription(&self, chat_id: i64, message_id: MessageId, text: String) {
+        self.pending_transcriptions
+            .write()
+            .await
+            .insert((chat_id, message_id.0), text);
+    }
+
+    /// Take the transcription held for a confirmation message, if any.
+    pub async fn take_transcription(&self, chat_id: i64, message_id: MessageId) -> Option<String> {
+        self.pending_transcriptions
+            .write()
+            .await
+            .remove(&(chat_id, message_id.0))
+    }
+
     /// Initialize the agent orchestrator asynchronously (when agents feature is enabled).
     ///
     /// This should be called after state creation to enable LLM-based processing.
@@ -499,12 +606,120 @@ impl TelegramState {
         }
     }
 
+    /// Apply `[agents]` model settings to the orchestrator, if initialized.
+    #[cfg(feature = "agents")]
+    pub async fn apply_agent_settings(&self, settings: &commander_core::AgentSettings) {
+        if let Some(orchestrator) = self.orchestrator.write().await.as_mut() {
+            orchestrator.apply_agent_settings(settings);
+        }
+    }
+
     /// Check if the orchestrator is available.
     #[cfg(feature = "agents")]
     pub async fn has_orchestrator(&self) -> bool {
         self.orchestrator.read().await.is_some()
     }
 
+    /// Get the command awaiting guardrail approval, if any.
+    #[cfg(feature = "agents")]
+    pub async fn pending_approval(&self) -> Option<PendingApproval> {
+        self.orchestrator
+            .read()
+            .await
+            .as_ref()
+            .and_then(|o| o.pending_approval().cloned())
+    }
+
+    /// Approve or deny the command awaiting guardrail approval.
+    ///
+    /// Returns the resolved request, or `None` if nothing was pending.
+    #[cfg(feature = "agents")]
+    pub async fn resolve_pending_approval(&self, approve: bool) -> Option<PendingApproval> {
+        let mut orchestrator = self.orchestrator.write().await;
+        let orch = orchestrator.as_mut()?;
+        if approve {
+            orch.approve_pending()
+        } else {
+            orch.deny_pending()
+        }
+    }
+
+    /// Keep a completed response that shows an error, so the poll loop can
+    /// have it diagnosed after sending the response.
+    #[cfg(feature = "agents")]
+    async fn hold_error_output(&self, session_key: i64, tmux_session: &str, adapter_type: &str, output: &str) {
+        if commander_core::diagnosis::find_error_line(output).is_some() {
+            self.error_outputs.write().await.insert(
+                session_key,
+                (tmux_session.to_string(), adapter_type.to_string(), output.to_string()),
+            );
+        }
+    }
+
+    /// Diagnose the error held for a session's completed response, if any.
+    ///
+    /// The diagnosis is kept until its card is answered with
+    /// [`take_diagnosis`](Self::take_diagnosis).
+    #[cfg(feature = "agents")]
+    pub async fn diagnose_response(&self, session_key: i64) -> Option<ErrorDiagnosis> {
+        let (tmux_session, adapter_type, output) = self.error_outputs.write().await.remove(&session_key)?;
+        let result = {
+            let mut orchestrator = self.orchestrator.write().await;
+            orchestrator
+                .as_mut()?
+                .diagnose_session_error(&tmux_session, &adapter_type, &output)
+                .await
+        };
+        match result {
+            Ok(diagnosis) => {
+                let diagnosis = diagnosis?;
+                self.pending_diagnoses
+                    .write()
+                    .await
+                    .insert(diagnosis.id.clone(), diagnosis.clone());
+                Some(diagnosis)
+            }
+            Err(e) => {
+                warn!(session = %tmux_session, error = %e, "Error diagnosis failed");
+                None
+            }
+        }
+    }
+
+    /// Remove and return a pending diagnosis.
+    #[cfg(feature = "agents")]
+    pub async fn take_diagnosis(&self, id: &str) -> Option<ErrorDiagnosis> {
+        self.pending_diagnoses.write().await.remove(id)
+    }
+
+    /// Milestones of a session's task in progress, extracted by its
+    /// SessionAgent when the orchestrator is free.
+    ///
+    /// Never waits for the orchestrator lock: the poll loop holds the
+    /// sessions lock here, so a busy orchestrator falls back to extracting
+    /// the milestones from the output directly.
+    fn progress_milestones(&self, session_id: &str, lines: &[String]) -> ProgressMilestones {
+        #[cfg(feature = "agents")]
+        if let Ok(orchestrator) = self.orchestrator.try_read() {
+            if let Some(orch) = orchestrator.as_ref() {
+                return orch.session_progress(session_id, lines);
+            }
+        }
+        ProgressMilestones::from_output(lines)
+    }
+
+    /// Progress header for a session's working message, followed by the
+    /// milestones reached so far.
+    fn progress_message(&self, session: &mut UserSession) -> String {
+        let mut message = session.get_progress_message();
+        let milestones = self.progress_milestones(&session.tmux_session, &session.response_buffer);
+        for line in render_milestones(&milestones) {
+            message.push('\n');
+            message.push_str(&line);
+        }
+        message
+    }
+
     /// Check if tmux is available.
     pub fn has_tmux(&self) -> bool {
         self.tmux.is_some()
@@ -645,6 +860,15 @@ impl TelegramState {
         configs.get(&chat_id)?.topics.get(&thread_id).cloned()
     }
 
+    /// Forum topic of a chat bound to `session`, so its notifications post there.
+    ///
+    /// Only chats in group mode have topics.
+    pub async fn topic_thread_for_session(&self, chat_id: i64, session: &str) -> Option<ThreadId> {
+        let configs = self.group_configs.read().await;
+        let config = configs.get(&chat_id).filter(|c| c.is_enabled)?;
+        config.thread_for_session(session).map(|tid| ThreadId(MessageId(tid)))
+    }
+
     /// List all topics for a chat.
     pub async fn list_topics(&self, chat_id: i64) -> Vec<TopicConfig> {
         let configs = self.group_configs.read().await;
@@ -766,7 +990,7 @@ impl TelegramState {
         let screen_preview = self.tmux.as_ref().and_then(|tmux| {
             tmux.capture_output(&session.tmux_session, None, Some(50))
                 .ok()
-                .map(|output| clean_screen_preview(&output, 15))
+                .map(|output| clean_screen_preview(&commander_core::redact(&output), 15))
                 .filter(|s| !s.trim().is_empty())
         });
 
@@ -780,6 +1004,112 @@ impl TelegramState {
         ))
     }
 
+    /// Resolve `/pause` and `/resume` targets to (tmux session, project):
+    /// the named project, or the chat's connected session.
+    async fn pause_target(&self, chat_id: ChatId, arg: &str) -> Result<(String, String)> {
+        let arg = arg.trim();
+        if arg.is_empty() {
+            let sessions = self.sessions.read().await;
+            let session = sessions.get(&chat_id.0).ok_or(TelegramError::NotConnected)?;
+            return Ok((session.tmux_session.clone(), session.project_name.clone()));
+        }
+
+        // Bare name first, then the legacy commander- prefix
+        let bare = arg.strip_prefix("commander-").unwrap_or(arg);
+        let prefixed = format!("commander-{}", bare);
+        let exists = |name: &str| self.tmux.as_ref().is_some_and(|t| t.session_exists(name));
+        let session = if !exists(bare) && exists(&prefixed) { prefixed } else { bare.to_string() };
+        Ok((session, bare.to_string()))
+    }
+
+    /// Pause a session with its adapter's pause command, or by suspending
+    /// its processes when the adapter has none. Returns a description.
+    pub async fn pause_session(&self, chat_id: ChatId, arg: &str) -> Result<String> {
+        let tmux = self.tmux.as_ref().ok_or_else(|| {
+            TelegramError::TmuxError("tmux not available".to_string())
+        })?;
+        let (session, project) = self.pause_target(chat_id, arg).await?;
+        if !tmux.session_exists(&session) {
+            return Err(TelegramError::SessionError(format!("session '{}' not found", session)));
+        }
+        if pause::is_paused(&session) {
+            return Err(TelegramError::SessionError(format!("{} is already paused", project)));
+        }
+
+        let tool_id = self
+            .store
+            .load_all_projects()
+            .ok()
+            .and_then(|projects| {
+                projects
+                    .values()
+                    .find(|p| p.name == project)
+                    .and_then(|p| p.config.get("tool"))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            })
+            .unwrap_or_else(|| "claude-code".to_string());
+        let commands = self
+            .adapters
+            .get(&tool_id)
+            .and_then(|a| a.pause_commands().map(|(p, r)| (p.to_string(), r.to_string())));
+
+        let (method, description) = match commands {
+            Some((pause_command, resume_command)) => {
+                tmux.send_line(&session, None, &pause_command)
+                    .map_err(|e| TelegramError::TmuxError(e.to_string()))?;
+                (
+                    PauseMethod::Command { resume_command },
+                    format!("Paused {} with {}", project, pause_command),
+                )
+            }
+            None => {
+                let process_groups = tmux
+                    .suspend_session(&session)
+                    .map_err(|e| TelegramError::TmuxError(e.to_string()))?;
+                (
+                    PauseMethod::Suspended { process_groups },
+                    format!("Paused {} (processes suspended)", project),
+                )
+            }
+        };
+
+        pause::mark_paused(
+            &session,
+            PausedSession {
+                project,
+                method,
+                paused_at: chrono::Utc::now(),
+                by: "telegram".to_string(),
+            },
+        )
+        .map_err(|e| TelegramError::SessionError(format!("paused, but failed to record it: {}", e)))?;
+        info!(chat_id = %chat_id.0, session = %session, "Session paused");
+        Ok(description)
+    }
+
+    /// Resume a session paused with `/pause`, the way it was paused.
+    pub async fn resume_session(&self, chat_id: ChatId, arg: &str) -> Result<String> {
+        let tmux = self.tmux.as_ref().ok_or_else(|| {
+            TelegramError::TmuxError("tmux not available".to_string())
+        })?;
+        let (session, project) = self.pause_target(chat_id, arg).await?;
+        let Some(paused) = pause::paused_session(&session) else {
+            return Err(TelegramError::SessionError(format!("{} is not paused", project)));
+        };
+
+        match &paused.method {
+            PauseMethod::Command { resume_command } => tmux.send_line(&session, None, resume_command),
+            PauseMethod::Suspended { process_groups } => tmux.resume_processes(process_groups),
+        }
+        .map_err(|e| TelegramError::TmuxError(e.to_string()))?;
+
+        pause::mark_resumed(&session)
+            .map_err(|e| TelegramError::SessionError(format!("resumed, but failed to record it: {}", e)))?;
+        info!(chat_id = %chat_id.0, session = %session, "Session resumed");
+        Ok(format!("Resumed {}", paused.project))
+    }
+
     /// Connect a user to a project.
     /// Connect to an existing project. Returns (project_name, tool_id).
     pub async fn connect(&self, chat_id: ChatId, project_name: &str) -> Result<(String, String)> {
@@ -992,6 +1322,184 @@ impl TelegramState {
         Err(TelegramError::ProjectNotFound(project_name.to_string()))
     }
 
+    /// Make `name` the chat's active session without disconnecting the
+    /// current one, which is parked and keeps being followed.
+    ///
+    /// A followed session is resumed as it was; any other project is
+    /// connected. Returns the project name and whether it was already
+    /// followed.
+    pub async fn use_session(&self, chat_id: ChatId, name: &str) -> Result<(String, bool)> {
+        let base_name = name.strip_prefix("commander-").unwrap_or(name);
+
+        {
+            let mut sessions = self.sessions.write().await;
+            if let Some(active) = sessions.get(&chat_id.0) {
+                if active.project_name == base_name || active.tmux_session == name {
+                    return Ok((active.project_name.clone(), true));
+                }
+            }
+
+            let mut followed = self.followed.write().await;
+            let entry = followed.entry(chat_id.0).or_default();
+            entry.return_to = None;
+            if let Some(session) = entry.take(base_name).or_else(|| entry.take(name)) {
+                let project_name = session.project_name.clone();
+                if let Some(active) = sessions.insert(chat_id.0, session) {
+                    entry.park(active);
+                }
+                drop(followed);
+                drop(sessions);
+                self.save_sessions().await;
+                debug!(chat_id = %chat_id.0, project = %project_name, "Resumed followed session");
+                return Ok((project_name, true));
+            }
+        }
+
+        let previous = self.sessions.write().await.remove(&chat_id.0);
+        match self.connect(chat_id, name).await {
+            Ok((project_name, _)) => {
+                if let Some(previous) = previous {
+                    self.followed.write().await.entry(chat_id.0).or_default().park(previous);
+                }
+                Ok((project_name, false))
+            }
+            Err(e) => {
+                if let Some(previous) = previous {
+                    self.sessions.write().await.insert(chat_id.0, previous);
+                    self.save_sessions().await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// The chat's active project and the other projects it follows.
+    pub async fn followed_sessions(&self, chat_id: ChatId) -> (Option<String>, Vec<String>) {
+        let active = self
+            .sessions
+            .read()
+            .await
+            .get(&chat_id.0)
+            .map(|s| s.project_name.clone());
+        let parked = self
+            .followed
+            .read()
+            .await
+            .get(&chat_id.0)
+            .map(FollowedSessions::names)
+            .unwrap_or_default();
+        (active, parked)
+    }
+
+    /// Switch back to the project that was active before an @-addressed
+    /// message, once its response has been delivered.
+    pub async fn return_from_at_session(&self, chat_id: ChatId) {
+        let previous = self
+            .followed
+            .write()
+            .await
+            .get_mut(&chat_id.0)
+            .and_then(|f| f.return_to.take());
+        if let Some(previous) = previous {
+            if let Err(e) = self.use_session(chat_id, &previous).await {
+                warn!(chat_id = %chat_id.0, project = %previous, error = %e, "Failed to switch back after @-addressed message");
+            }
+        }
+    }
+
+    /// Attach a chat's connected session in the shared session registry.
+    ///
+    /// Returns the tmux session name, the attach outcome, and any recent
+    /// activity from other frontends so the conversation can be resumed.
+    pub async fn attach_handoff(
+        &self,
+        chat_id: ChatId,
+    ) -> Option<(String, AttachOutcome, Option<String>)> {
+        let session = self.get_current_tmux_session(chat_id.0).await?;
+        let client_id = chat_id.0.to_string();
+
+        let result = update_session_registry(|r| {
+            let outcome = r.attach(&session, Frontend::Telegram, Some(client_id));
+            let context = r
+                .get(&session)
+                .and_then(|rec| rec.resume_context(Frontend::Telegram));
+            (outcome, context)
+        });
+
+        match result {
+            Ok((outcome, context)) => Some((session, outcome, context)),
+            Err(e) => {
+                warn!(chat_id = %chat_id.0, error = %e, "Failed to update session registry");
+                None
+            }
+        }
+    }
+
+    /// Claim a chat's connected session from whichever frontend holds it.
+    ///
+    /// Returns the tmux session name and the previous holder, if any.
+    pub async fn takeover_session(&self, chat_id: ChatId) -> Result<(String, Option<Attachment>)> {
+        let session = self
+            .get_current_tmux_session(chat_id.0)
+            .await
+            .ok_or(TelegramError::NotConnected)?;
+        let client_id = chat_id.0.to_string();
+
+        let previous = update_session_registry(|r| {
+            r.takeover(&session, Frontend::Telegram, Some(client_id))
+        })
+        .map_err(|e| TelegramError::SessionError(format!("Failed to claim session: {}", e)))?;
+
+        Ok((session, previous))
+    }
+
+    /// Record a response delivered to Telegram in the shared session registry.
+    pub async fn record_handoff_response(&self, session_key: i64, response: &str) {
+        let Some(session) = self.get_current_tmux_session(session_key).await else {
+            return;
+        };
+        if let Err(e) = update_session_registry(|r| {
+            r.record_message(&session, Frontend::Telegram, HandoffDirection::Received, response)
+        }) {
+            debug!(session = %session, error = %e, "Failed to record response in session registry");
+        }
+    }
+
+    /// Record the screen the user saw with a response, for "Since you left"
+    /// digests.
+    pub async fn mark_seen(&self, session_key: i64) {
+        let (Some(tmux), Some(session)) = (self.tmux_arc(), self.get_current_tmux_session(session_key).await) else {
+            return;
+        };
+        let Ok(screen) = capture_output_safe(tmux, session.clone(), Some(200)).await else {
+            return;
+        };
+        if let Err(e) = mark_seen(&session, &screen) {
+            debug!(session = %session, error = %e, "Failed to record recap mark");
+        }
+    }
+
+    /// "Since you left" digest for the chat's connected session, if the
+    /// user was away long enough and something happened.
+    pub async fn recap(&self, chat_id: ChatId) -> Option<String> {
+        let tmux = self.tmux_arc()?;
+        let (session, project) = {
+            let sessions = self.sessions.read().await;
+            let session = sessions.get(&chat_id.0)?;
+            (session.tmux_session.clone(), session.project_name.clone())
+        };
+        let screen = capture_output_safe(tmux, session.clone(), Some(200)).await.ok()?;
+        recap_async(&session, &[&project], &screen).await
+    }
+
+    /// Test results last reported on the chat's connected session's screen.
+    pub async fn last_test_run(&self, chat_id: ChatId) -> Option<commander_adapters::TestRun> {
+        let tmux = self.tmux_arc()?;
+        let session = self.sessions.read().await.get(&chat_id.0)?.tmux_session.clone();
+        let screen = capture_output_safe(tmux, session, Some(300)).await.ok()?;
+        commander_adapters::parse_test_output(&screen)
+    }
+
     /// Disconnect a user from their current project.
     pub async fn disconnect(&self, chat_id: ChatId) -> Result<Option<String>> {
         let mut sessions = self.sessions.write().await;
@@ -1009,6 +1517,8 @@ impl TelegramState {
                     }
                 }
             }
+            let tmux_session = session.tmux_session.clone();
+            let _ = update_session_registry(|r| r.detach(&tmux_session, Frontend::Telegram));
             debug!(chat_id = %chat_id.0, project = %session.project_name, "User disconnected");
             Some(session.project_name)
         } else {
@@ -1417,6 +1927,13 @@ impl TelegramState {
             return Ok(PollResult::NoOutput);
         }
 
+        // Paused sessions are not analyzed until /resume; the pause does not
+        // count towards the inactivity timeout
+        if pause::is_paused(&session.tmux_session) {
+            session.last_output_time = Some(std::time::Instant::now());
+            return Ok(PollResult::NoOutput);
+        }
+
         // Fix 1: 5-minute inactivity timeout — if no new output has arrived for 5 minutes,
         // the session is likely stalled. Uses last_output_time (updated on every new content)
         // when available, so actively-streaming responses won't trigger the timeout.
@@ -1444,6 +1961,7 @@ impl TelegramState {
         // Capture current output
         let current_output = tmux
             .capture_output(&session.tmux_session, None, Some(200))
+            .map(|output| commander_core::redact(&output))
             .map_err(|e| TelegramError::TmuxError(e.to_string()))?;
 
         // Check for interactive selector before progress/completion logic
@@ -1502,7 +2020,7 @@ impl TelegramState {
 
             // Check if we should emit a progress update
             if session.should_emit_progress() {
-                let progress_msg = session.get_progress_message();
+                let progress_msg = self.progress_message(session);
                 return Ok(PollResult::Progress(progress_msg));
             }
         }
@@ -1594,6 +2112,9 @@ impl TelegramState {
             let query = session.pending_query.clone().unwrap_or_default();
             let message_id = session.pending_message_id;
             let sess_thread_id = session.thread_id;
+            #[cfg(feature = "agents")]
+            self.hold_error_output(session_key, &session.tmux_session, &session.adapter_type, &raw_response)
+                .await;
             // Capture logging context before reset clears it
             let log_chat_id = session.chat_id.0;
             let log_session_id = session.tmux_session.clone();
@@ -1662,6 +2183,9 @@ impl TelegramState {
         )
         .await
         .unwrap_or_default();
+        if let Err(e) = mark_seen(&tmux_session_name, &last_output) {
+            debug!(session = %tmux_session_name, error = %e, "Failed to record recap mark");
+        }
 
         // Send via daemon if available, otherwise fall back to tmux.
         if let (Some(ref daemon), Some(ref session_id)) =
@@ -1699,6 +2223,11 @@ impl TelegramState {
             message_id.map(|m| m.0).unwrap_or(0),
         );
 
+        // Record the query so other frontends can pick up the conversation.
+        let _ = update_session_registry(|r| {
+            r.record_query(&tmux_session_name, Frontend::Telegram, message)
+        });
+
         debug!(
             chat_id = %chat_id.0,
             project = %project_name,
@@ -1989,7 +2518,9 @@ impl TelegramState {
         sessions.get_mut(&session_key)?.at_session_name.take()
     }
 
-    /// Record that a bot message (chat_id, message_id) was an @-session response.
+    /// Record that a bot message (chat_id, message_id) came from a session, so
+    /// replying to it routes to that session: @-session responses, responses
+    /// of the connected session and session notifications.
     pub async fn record_at_reply(&self, chat_id: i64, message_id: MessageId, session_name: String) {
         let mut map = self.at_reply_map.write().await;
         map.insert((chat_id, message_id.0), session_name.clone());
@@ -2009,7 +2540,7 @@ impl TelegramState {
         );
     }
 
-    /// Look up whether a message ID was an @-session bot response. Returns session name if so.
+    /// Look up the session a bot message came from (see [`Self::record_at_reply`]).
     pub async fn lookup_at_reply(&self, chat_id: i64, message_id: MessageId) -> Option<String> {
         let map = self.at_reply_map.read().await;
         let result = map.get(&(chat_id, message_id.0)).cloned();
@@ -2026,10 +2557,13 @@ impl TelegramState {
     /// without changing the caller's active session.
     /// Sets at_session_name so the poll loop can record the reply ID for reply-chain routing.
     ///
+    /// The target is made active with [`Self::use_session`] while its response is
+    /// collected, and the previously active session is restored by
+    /// [`Self::return_from_at_session`] once the response is delivered.
+    ///
     /// For event-driven sessions, the message is dispatched through the event-driven
-    /// path (`try_send_event_driven_keyed`) instead of tmux. This requires that
-    /// `connect` has already created the `UserSession` (it does — `connect` is called
-    /// below if no session exists yet).
+    /// path (`try_send_event_driven_keyed`) instead of tmux, and the target stays
+    /// active since its background task owns the response.
     pub async fn send_to_named_session(
         &self,
         bot: Bot,
@@ -2055,7 +2589,7 @@ impl TelegramState {
         };
 
         if needs_connect {
-            self.connect(chat_id, session_name).await
+            self.use_session(chat_id, session_name).await
                 .map_err(|e| TelegramError::SessionError(format!("Cannot connect to '{}': {}", session_name, e)))?;
         }
 
@@ -2126,6 +2660,9 @@ impl TelegramState {
                 session.at_session_name = Some(base_name.to_string());
             }
         }
+        if let Some(previous) = already_connected.filter(|_| needs_connect) {
+            self.followed.write().await.entry(chat_id.0).or_default().return_to = Some(previous);
+        }
 
         debug!(
             chat_id = %chat_id.0,
@@ -2220,6 +2757,13 @@ impl TelegramState {
             return Ok(PollResult::NoOutput);
         }
 
+        // Paused sessions are not analyzed until /resume; the pause does not
+        // count towards the inactivity timeout
+        if pause::is_paused(&session.tmux_session) {
+            session.last_output_time = Some(std::time::Instant::now());
+            return Ok(PollResult::NoOutput);
+        }
+
         // Fix 1: 5-minute inactivity timeout — if no new output has arrived for 5 minutes,
         // the session is likely stalled. Uses last_output_time (updated on every new content)
         // when available, so actively-streaming responses won't trigger the timeout.
@@ -2246,6 +2790,7 @@ impl TelegramState {
         // Capture current output
         let current_output = tmux
             .capture_output(&session.tmux_session, None, Some(200))
+            .map(|output| commander_core::redact(&output))
             .map_err(|e| TelegramError::TmuxError(e.to_string()))?;
 
         // Check for interactive selector before progress/completion logic
@@ -2304,7 +2849,7 @@ impl TelegramState {
 
             // Check if we should emit a progress update
             if session.should_emit_progress() {
-                let progress_msg = session.get_progress_message();
+                let progress_msg = self.progress_message(session);
                 return Ok(PollResult::Progress(progress_msg));
             }
         }
@@ -2399,6 +2944,9 @@ impl TelegramState {
             let query = session.pending_query.clone().unwrap_or_default();
             let message_id = session.pending_message_id;
             let thread_id = session.thread_id;
+            #[cfg(feature = "agents")]
+            self.hold_error_output(chat_id.0, &session.tmux_session, &session.adapter_type, &raw_response)
+                .await;
             // Capture logging context before reset clears it
             let log_chat_id = session.chat_id.0;
             let log_session_id = session.tmux_session.clone();
@@ -2884,6 +3432,22 @@ mod tests {
         assert_eq!(cleaned, "Actual response");
     }
 
+    #[test]
+    fn test_followed_sessions_park_and_take() {
+        let session = |name: &str| UserSession::new(ChatId(1), "/tmp".into(), name.into(), name.into());
+        let mut followed = FollowedSessions::default();
+        followed.park(session("api"));
+        followed.park(session("web"));
+        let mut newer = session("api");
+        newer.is_waiting = true;
+        followed.park(newer);
+
+        assert_eq!(followed.names(), vec!["web", "api"]);
+        assert!(followed.take("api").unwrap().is_waiting);
+        assert!(followed.take("api").is_none());
+        assert_eq!(followed.names(), vec!["web"]);
+    }
+
     #[test]
     fn test_find_new_lines() {
         let prev = "line1\nline2";
@@ -2948,6 +3512,23 @@ mod tests {
         assert_eq!(parsed.project_path, Some("/path/to/project".to_string()));
     }
 
+    #[test]
+    fn test_thread_for_session() {
+        let mut config = GroupChatConfig::default();
+        for (thread_id, name) in [(10, "api"), (20, "web")] {
+            config.topics.insert(thread_id, TopicConfig {
+                thread_id,
+                session_name: name.to_string(),
+                tmux_session: format!("commander-{}", name),
+                project_path: None,
+            });
+        }
+
+        assert_eq!(config.thread_for_session("commander-web"), Some(20));
+        assert_eq!(config.thread_for_session("api"), Some(10));
+        assert_eq!(config.thread_for_session("docs"), None);
+    }
+
     #[test]
     fn test_group_chat_config_serialization() {
         let mut config = GroupChatConfig::default();
@@ -0,0 +1,299 @@
+//! Tunnels exposing the webhook endpoint to Telegram.
+//!
+//! The backend is chosen with `[telegram.tunnel] backend` in `config.toml`:
+//! ngrok (the default), a Cloudflare quick tunnel, Tailscale Funnel, or a
+//! reverse proxy the user runs in front of the webhook port.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use commander_core::{TunnelBackend, TunnelSettings};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::time::timeout;
+use tracing::{debug, info};
+
+use crate::error::{Result, TelegramError};
+use crate::ngrok::NgrokTunnel;
+
+/// How long `cloudflared` gets to print its tunnel URL.
+const CLOUDFLARE_START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A public HTTPS endpoint forwarding to the local webhook port.
+#[async_trait]
+pub trait Tunnel: Send + Sync {
+    /// Backend name, for logs.
+    fn name(&self) -> &'static str;
+
+    /// Public base URL, without a trailing slash.
+    fn public_url(&self) -> &str;
+
+    /// Whether the tunnel is still up.
+    async fn health_check(&mut self) -> bool;
+
+    /// Shut the tunnel down.
+    fn stop(&mut self) -> Result<()>;
+}
+
+/// Start the tunnel selected by `settings` for local `port`.
+pub async fn start_tunnel(settings: &TunnelSettings, port: u16) -> Result<Box<dyn Tunnel>> {
+    info!(backend = ?settings.backend, port, "Starting webhook tunnel");
+    Ok(match settings.backend {
+        TunnelBackend::Ngrok => Box::new(NgrokTunnel::start(port).await?),
+        TunnelBackend::Cloudflare => Box::new(CloudflareTunnel::start(port).await?),
+        TunnelBackend::Tailscale => Box::new(TailscaleFunnel::start(port).await?),
+        TunnelBackend::Proxy => Box::new(ProxyTunnel::new(settings.public_url.as_deref())?),
+    })
+}
+
+#[async_trait]
+impl Tunnel for NgrokTunnel {
+    fn name(&self) -> &'static str {
+        "ngrok"
+    }
+
+    fn public_url(&self) -> &str {
+        NgrokTunnel::public_url(self)
+    }
+
+    async fn health_check(&mut self) -> bool {
+        NgrokTunnel::health_check(self).await
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        NgrokTunnel::stop(self)
+    }
+}
+
+/// A `cloudflared` quick tunnel, which needs no Cloudflare account.
+pub struct CloudflareTunnel {
+    process: Child,
+    public_url: String,
+}
+
+impl CloudflareTunnel {
+    /// Run `cloudflared tunnel --url` and wait for the URL it is given.
+    pub async fn start(port: u16) -> Result<Self> {
+        let binary = find_binary("cloudflared")?;
+        let mut process = Command::new(binary)
+            .args(["tunnel", "--no-autoupdate", "--url", &format!("http://127.0.0.1:{}", port)])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| TelegramError::TunnelError(format!("Failed to start cloudflared: {}", e)))?;
+
+        let stderr = process
+            .stderr
+            .take()
+            .ok_or_else(|| TelegramError::TunnelError("cloudflared has no stderr".to_string()))?;
+        let mut lines = BufReader::new(stderr).lines();
+        let found = timeout(CLOUDFLARE_START_TIMEOUT, async {
+            while let Ok(Some(line)) = lines.next_line().await {
+                debug!(line = %line, "cloudflared");
+                if let Some(url) = parse_cloudflare_url(&line) {
+                    return Some(url);
+                }
+            }
+            None
+        })
+        .await;
+        let Ok(Some(public_url)) = found else {
+            let _ = process.start_kill();
+            return Err(TelegramError::TunnelError(
+                "cloudflared did not report a tunnel URL".to_string(),
+            ));
+        };
+
+        // Keep draining the log so cloudflared never blocks on a full pipe.
+        tokio::spawn(async move {
+            while let Ok(Some(line)) = lines.next_line().await {
+                debug!(line = %line, "cloudflared");
+            }
+        });
+
+        info!(url = %public_url, "Cloudflare tunnel established");
+        Ok(Self { process, public_url })
+    }
+}
+
+#[async_trait]
+impl Tunnel for CloudflareTunnel {
+    fn name(&self) -> &'static str {
+        "cloudflare"
+    }
+
+    fn public_url(&self) -> &str {
+        &self.public_url
+    }
+
+    async fn health_check(&mut self) -> bool {
+        matches!(self.process.try_wait(), Ok(None))
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        info!("Stopping Cloudflare tunnel");
+        self.process
+            .start_kill()
+            .map_err(|e| TelegramError::TunnelError(format!("Failed to stop cloudflared: {}", e)))
+    }
+}
+
+/// `tailscale funnel`, serving the port on the machine's `ts.net` name.
+///
+/// Funnel must be enabled for the tailnet, and only serves ports 443, 8443
+/// and 10000 publicly; the local port is published on 443.
+pub struct TailscaleFunnel {
+    process: Child,
+    public_url: String,
+}
+
+impl TailscaleFunnel {
+    /// Run `tailscale funnel <port>` in the foreground.
+    pub async fn start(port: u16) -> Result<Self> {
+        let binary = find_binary("tailscale")?;
+        let status = Command::new(&binary)
+            .args(["status", "--json"])
+            .output()
+            .await
+            .map_err(|e| TelegramError::TunnelError(format!("Failed to run tailscale status: {}", e)))?;
+        if !status.status.success() {
+            return Err(TelegramError::TunnelError(format!(
+                "tailscale status failed: {}",
+                String::from_utf8_lossy(&status.stderr).trim()
+            )));
+        }
+        let public_url = parse_tailscale_url(&String::from_utf8_lossy(&status.stdout)).ok_or_else(|| {
+            TelegramError::TunnelError("tailscale reported no DNS name; is MagicDNS enabled?".to_string())
+        })?;
+
+        let process = Command::new(&binary)
+            .args(["funnel", &port.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| TelegramError::TunnelError(format!("Failed to start tailscale funnel: {}", e)))?;
+
+        info!(url = %public_url, "Tailscale Funnel established");
+        Ok(Self { process, public_url })
+    }
+}
+
+#[async_trait]
+impl Tunnel for TailscaleFunnel {
+    fn name(&self) -> &'static str {
+        "tailscale"
+    }
+
+    fn public_url(&self) -> &str {
+        &self.public_url
+    }
+
+    async fn health_check(&mut self) -> bool {
+        matches!(self.process.try_wait(), Ok(None))
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        info!("Stopping Tailscale Funnel");
+        self.process
+            .start_kill()
+            .map_err(|e| TelegramError::TunnelError(format!("Failed to stop tailscale funnel: {}", e)))
+    }
+}
+
+/// A reverse proxy run by the user; nothing is started.
+pub struct ProxyTunnel {
+    public_url: String,
+}
+
+impl ProxyTunnel {
+    /// Use `public_url`, which must be an HTTPS URL.
+    pub fn new(public_url: Option<&str>) -> Result<Self> {
+        let public_url = public_url
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| {
+                TelegramError::TunnelError("proxy mode needs [telegram.tunnel] public_url".to_string())
+            })?;
+        if !public_url.starts_with("https://") {
+            return Err(TelegramError::TunnelError(format!(
+                "Telegram only delivers webhooks over HTTPS, got {}",
+                public_url
+            )));
+        }
+        Ok(Self {
+            public_url: public_url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Tunnel for ProxyTunnel {
+    fn name(&self) -> &'static str {
+        "proxy"
+    }
+
+    fn public_url(&self) -> &str {
+        &self.public_url
+    }
+
+    async fn health_check(&mut self) -> bool {
+        true
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Find `name` in `PATH`.
+fn find_binary(name: &str) -> Result<std::path::PathBuf> {
+    which::which(name).map_err(|_| TelegramError::TunnelError(format!("{} not found in PATH", name)))
+}
+
+/// The `https://*.trycloudflare.com` URL in a line of `cloudflared` output.
+fn parse_cloudflare_url(line: &str) -> Option<String> {
+    let start = line.find("https://")?;
+    let url = line[start..].split(|c: char| c.is_whitespace() || c == '|').next()?;
+    url.ends_with(".trycloudflare.com").then(|| url.to_string())
+}
+
+/// `https://<name>` from `tailscale status --json`.
+fn parse_tailscale_url(status: &str) -> Option<String> {
+    let status: serde_json::Value = serde_json::from_str(status).ok()?;
+    let name = status["Self"]["DNSName"].as_str()?.trim_end_matches('.');
+    (!name.is_empty()).then(|| format!("https://{}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cloudflare_url() {
+        let line = "2026-10-17T10:00:00Z INF |  https://calm-river-1234.trycloudflare.com                               |";
+        assert_eq!(
+            parse_cloudflare_url(line).as_deref(),
+            Some("https://calm-river-1234.trycloudflare.com")
+        );
+        assert_eq!(parse_cloudflare_url("INF Requesting new quick Tunnel on https://api.trycloudflare.com/"), None);
+        assert_eq!(parse_cloudflare_url("INF Starting metrics server"), None);
+    }
+
+    #[test]
+    fn test_parse_tailscale_url() {
+        let status = r#"{"Self": {"DNSName": "laptop.tail1234.ts.net.", "HostName": "laptop"}}"#;
+        assert_eq!(parse_tailscale_url(status).as_deref(), Some("https://laptop.tail1234.ts.net"));
+        assert_eq!(parse_tailscale_url(r#"{"Self": {"DNSName": ""}}"#), None);
+    }
+
+    #[test]
+    fn test_proxy_tunnel() {
+        let tunnel = ProxyTunnel::new(Some("https://bot.example.com/")).unwrap();
+        assert_eq!(tunnel.public_url(), "https://bot.example.com");
+        assert!(ProxyTunnel::new(None).is_err());
+        assert!(ProxyTunnel::new(Some("http://bot.example.com")).is_err());
+    }
+}
@@ -131,7 +131,7 @@ impl TypingThrottle {
 ///
 /// Telegram errors typically contain text like `"Retry after 12"` or
 /// `"retry_after: 12"`.
-fn parse_retry_after(err: &str) -> Option<u64> {
+pub(crate) fn parse_retry_after(err: &str) -> Option<u64> {
     // Try common patterns: "Retry after N" and "retry_after":N
     let lower = err.to_lowercase();
 
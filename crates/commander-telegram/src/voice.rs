@@ -0,0 +1,157 @@
+//! Voice message transcription.
+//!
+//! Voice notes are downloaded from Telegram (OGG/Opus) and sent to a
+//! speech-to-text endpoint that speaks the OpenAI Whisper API, configured by
+//! `[telegram.voice]` in `config.toml`. The API key is read from
+//! `OPENAI_API_KEY`. Confidence is derived from the per-segment log
+//! probabilities Whisper returns; transcriptions below the configured
+//! threshold are confirmed with the user before they reach a session.
+
+use std::time::Duration;
+
+use commander_core::secrets::{get_secret, OPENAI_API_KEY};
+use commander_core::VoiceSettings;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::error::{Result, TelegramError};
+
+/// Timeout for a transcription request.
+const TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Text transcribed from a voice message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcription {
+    /// Transcribed text, trimmed.
+    pub text: String,
+    /// Confidence from 0.0 to 1.0, if the endpoint reported segment scores.
+    pub confidence: Option<f64>,
+}
+
+impl Transcription {
+    /// Whether the user should confirm the text before it is sent.
+    ///
+    /// Transcriptions without a confidence score are always confirmed.
+    pub fn needs_confirmation(&self, min_confidence: u8) -> bool {
+        match self.confidence {
+            Some(confidence) => confidence * 100.0 < f64::from(min_confidence),
+            None => true,
+        }
+    }
+}
+
+/// `verbose_json` response of the transcription endpoint.
+#[derive(Debug, Deserialize)]
+struct WhisperResponse {
+    text: String,
+    #[serde(default)]
+    segments: Vec<WhisperSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperSegment {
+    avg_logprob: f64,
+    #[serde(default)]
+    no_speech_prob: f64,
+}
+
+impl From<WhisperResponse> for Transcription {
+    fn from(response: WhisperResponse) -> Self {
+        // Mean token probability per segment, discounted by the chance the
+        // segment is not speech at all
+        let confidence = (!response.segments.is_empty()).then(|| {
+            let total: f64 = response
+                .segments
+                .iter()
+                .map(|s| s.avg_logprob.exp().min(1.0) * (1.0 - s.no_speech_prob))
+                .sum();
+            total / response.segments.len() as f64
+        });
+        Self {
+            text: response.text.trim().to_string(),
+            confidence,
+        }
+    }
+}
+
+/// Transcribe `audio` (the contents of `file_name`) with the configured endpoint.
+pub async fn transcribe(
+    settings: &VoiceSettings,
+    audio: Vec<u8>,
+    file_name: &str,
+) -> Result<Transcription> {
+    let api_key = get_secret(OPENAI_API_KEY).ok_or_else(|| {
+        TelegramError::TranscriptionError(
+            "OPENAI_API_KEY not set. Run `commander secrets set OPENAI_API_KEY`.".to_string(),
+        )
+    })?;
+
+    let audio = Part::bytes(audio)
+        .file_name(file_name.to_string())
+        .mime_str("audio/ogg")?;
+    let mut form = Form::new()
+        .part("file", audio)
+        .text("model", settings.model.clone())
+        .text("response_format", "verbose_json");
+    if let Some(language) = &settings.language {
+        form = form.text("language", language.clone());
+    }
+
+    let response = reqwest::Client::new()
+        .post(&settings.endpoint)
+        .bearer_auth(api_key)
+        .multipart(form)
+        .timeout(TRANSCRIBE_TIMEOUT)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(TelegramError::TranscriptionError(format!(
+            "{}: {}",
+            status,
+            body.chars().take(200).collect::<String>()
+        )));
+    }
+
+    let transcription = Transcription::from(response.json::<WhisperResponse>().await?);
+    debug!(
+        chars = transcription.text.len(),
+        confidence = ?transcription.confidence,
+        "Voice message transcribed"
+    );
+    Ok(transcription)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Transcription {
+        serde_json::from_str::<WhisperResponse>(json).unwrap().into()
+    }
+
+    #[test]
+    fn test_confidence_from_segments() {
+        let transcription = parse(
+            r#"{"text": " run the tests ", "segments": [
+                {"avg_logprob": -0.1, "no_speech_prob": 0.0},
+                {"avg_logprob": -0.3, "no_speech_prob": 0.5}
+            ]}"#,
+        );
+        assert_eq!(transcription.text, "run the tests");
+        let confidence = transcription.confidence.unwrap();
+        assert!((confidence - (0.905 + 0.741 * 0.5) / 2.0).abs() < 0.01);
+        assert!(transcription.needs_confirmation(70));
+        assert!(!transcription.needs_confirmation(50));
+    }
+
+    #[test]
+    fn test_missing_segments_need_confirmation() {
+        let transcription = parse(r#"{"text": "deploy"}"#);
+        assert_eq!(transcription.confidence, None);
+        assert!(transcription.needs_confirmation(0));
+    }
+}
@@ -0,0 +1,211 @@
+//! Tmux control mode clients.
+//!
+//! A control mode client (`tmux -C attach-session`) receives a `%output`
+//! notification whenever a pane of its session writes, instead of the caller
+//! polling `capture-pane`. Clients attach read-only and ignore their size, so
+//! they never resize or type into the session they watch.
+//!
+//! Notifications are read on a background thread and handed to a callback.
+//! A client whose tmux lacks control mode (or whose session ends) reports
+//! [`ControlEvent::Exit`], after which callers fall back to polling.
+
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+
+use tracing::{debug, trace};
+
+use crate::Result;
+
+/// A notification from a control mode client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlEvent {
+    /// Tmux accepted the client; notifications follow.
+    Connected,
+    /// A pane wrote output.
+    Output {
+        /// Pane id, e.g. `%3`.
+        pane: String,
+        /// The output, unescaped.
+        data: String,
+    },
+    /// The client detached or could not attach, with tmux's reason if given.
+    Exit(Option<String>),
+}
+
+/// A control mode client attached to one session.
+///
+/// Dropping it detaches the client.
+#[derive(Debug)]
+pub struct ControlClient {
+    session: String,
+    child: Child,
+}
+
+impl ControlClient {
+    /// Attach to `session` with the tmux binary at `tmux_path`, calling
+    /// `on_event` from a background thread for every notification.
+    ///
+    /// Returns once the client is spawned; whether tmux accepted it is
+    /// reported as [`ControlEvent::Connected`] or [`ControlEvent::Exit`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `TmuxError::Io` if tmux cannot be spawned.
+    pub fn attach<F>(tmux_path: &str, session: &str, on_event: F) -> Result<Self>
+    where
+        F: FnMut(ControlEvent) + Send + 'static,
+    {
+        let mut command = Command::new(tmux_path);
+        command.args(attach_args(session));
+        Self::spawn(command, session, on_event)
+    }
+
+    /// Attach to `session` by running `command`, a tmux control mode
+    /// invocation such as one over SSH (see [`attach_args`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `TmuxError::Io` if the command cannot be spawned.
+    pub fn spawn<F>(mut command: Command, session: &str, mut on_event: F) -> Result<Self>
+    where
+        F: FnMut(ControlEvent) + Send + 'static,
+    {
+        // Stdin stays open for the client's lifetime: tmux exits when it closes
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        debug!(session = %session, pid = child.id(), "control mode client started");
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let name = session.to_string();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            let mut line = Vec::new();
+            let mut connected = false;
+            let mut exit = None;
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                let text = String::from_utf8_lossy(&line);
+                let text = text.trim_end_matches(['\r', '\n']);
+                if !connected && text.starts_with('%') {
+                    connected = true;
+                    on_event(ControlEvent::Connected);
+                }
+                match parse_notification(text) {
+                    Some(ControlEvent::Exit(reason)) => {
+                        exit = reason;
+                        break;
+                    }
+                    Some(event) => on_event(event),
+                    None => trace!(session = %name, line = %text, "ignored control mode line"),
+                }
+            }
+            debug!(session = %name, reason = ?exit, "control mode client exited");
+            on_event(ControlEvent::Exit(exit));
+        });
+
+        Ok(Self {
+            session: session.to_string(),
+            child,
+        })
+    }
+
+    /// The session this client is attached to.
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+}
+
+impl Drop for ControlClient {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Arguments of a read-only control mode client of `session`.
+pub fn attach_args(session: &str) -> [&str; 6] {
+    ["-C", "attach-session", "-f", "ignore-size,read-only", "-t", session]
+}
+
+/// Parse a control mode line. Returns `None` for lines that are not
+/// `%output` or `%exit` notifications (command replies, other notifications).
+pub fn parse_notification(line: &str) -> Option<ControlEvent> {
+    if let Some(rest) = line.strip_prefix("%output ") {
+        let (pane, data) = rest.split_once(' ').unwrap_or((rest, ""));
+        return Some(ControlEvent::Output {
+            pane: pane.to_string(),
+            data: unescape(data),
+        });
+    }
+    match line.strip_prefix("%exit") {
+        Some("") => Some(ControlEvent::Exit(None)),
+        Some(reason) if reason.starts_with(' ') => Some(ControlEvent::Exit(Some(reason.trim().to_string()))),
+        _ => None,
+    }
+}
+
+/// Undo tmux's escaping of output: control characters and backslashes are
+/// sent as three-digit octal escapes (`\015`).
+fn unescape(data: &str) -> String {
+    let bytes = data.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).filter(|d| d.iter().all(|b| (b'0'..=b'7').contains(b)));
+        match octal {
+            Some(digits) if bytes[i] == b'\\' => {
+                let value = digits.iter().fold(0u32, |n, d| n * 8 + u32::from(d - b'0'));
+                out.push(value as u8);
+                i += 4;
+            }
+            _ => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_notification() {
+        assert_eq!(
+            parse_notification(r"%output %3 $ cargo test\015\012"),
+            Some(ControlEvent::Output {
+                pane: "%3".into(),
+                data: "$ cargo test\r\n".into(),
+            })
+        );
+        assert_eq!(
+            parse_notification(r"%output %0 C:\134dir"),
+            Some(ControlEvent::Output { pane: "%0".into(), data: r"C:\dir".into() })
+        );
+        assert_eq!(parse_notification("%exit"), Some(ControlEvent::Exit(None)));
+        assert_eq!(
+            parse_notification("%exit no sessions"),
+            Some(ControlEvent::Exit(Some("no sessions".into())))
+        );
+        assert_eq!(parse_notification("%begin 1700000000 12 0"), None);
+        assert_eq!(parse_notification("%window-add @2"), None);
+        assert_eq!(parse_notification("%exiting"), None);
+    }
+
+    #[test]
+    fn test_unescape_keeps_utf8_and_stray_backslashes() {
+        assert_eq!(unescape("héllo ✓"), "héllo ✓");
+        assert_eq!(unescape(r"\033[1mbold"), "\x1b[1mbold");
+        assert_eq!(unescape(r"a\b\09"), r"a\b\09");
+    }
+}
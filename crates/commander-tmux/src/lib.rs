@@ -5,6 +5,8 @@
 //! - Create panes within sessions
 //! - Capture pane output
 //! - Send input to panes
+//! - Receive pane output as it is written (control mode)
+//! - Run sessions on remote hosts over multiplexed SSH connections
 //! - Handle missing tmux gracefully
 //!
 //! # Example
@@ -49,10 +51,14 @@
 //! }
 //! ```
 
+pub mod control;
 pub mod error;
 pub mod orchestrator;
 pub mod session;
+pub mod ssh;
 
+pub use control::{ControlClient, ControlEvent};
 pub use error::{Result, TmuxError};
 pub use orchestrator::TmuxOrchestrator;
 pub use session::{TmuxPane, TmuxSession};
+pub use ssh::{SshHost, SshTransport};
@@ -1,16 +1,40 @@
 //! Tmux orchestrator for session and pane management.
 
+use std::collections::HashMap;
 use std::process::{Command, Output};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
+use commander_core::command_audit::{self, CommandSender, InputKind};
 use tracing::{debug, trace, warn};
 
+use crate::control::{self, ControlClient, ControlEvent};
+use crate::ssh::{SshHost, SshTransport};
 use crate::{Result, TmuxError, TmuxPane, TmuxSession};
 
+/// Format of pane listings, parsed by [`TmuxPane::parse`].
+const PANE_FORMAT: &str = "#{pane_id}:#{pane_index}:#{pane_active}:#{pane_width}:#{pane_height}";
+
 /// Main tmux orchestrator for session and pane management.
+///
+/// Input sent with [`send_line`](Self::send_line) and
+/// [`send_keys`](Self::send_keys) is recorded in the command audit log
+/// under the orchestrator's sender (see [`commander_core::command_audit`]).
+///
+/// Sessions can live on remote hosts: once a session is given a host with
+/// [`set_session_host`](Self::set_session_host), every command for it runs
+/// there over SSH (see [`crate::ssh`]), so callers address local and remote
+/// sessions alike.
 #[derive(Debug)]
 pub struct TmuxOrchestrator {
     /// Path to tmux binary.
     tmux_path: String,
+    /// Recorded as the sender of input in the audit log.
+    sender: CommandSender,
+    /// Transports of sessions on remote hosts, by session name.
+    hosts: RwLock<HashMap<String, Arc<SshTransport>>>,
+    /// Transports of process groups stopped on remote hosts.
+    suspended: Mutex<HashMap<u32, Arc<SshTransport>>>,
 }
 
 impl TmuxOrchestrator {
@@ -24,7 +48,19 @@ impl TmuxOrchestrator {
     pub fn new() -> Result<Self> {
         let tmux_path = Self::find_tmux()?;
         debug!(path = %tmux_path, "tmux found");
-        Ok(Self { tmux_path })
+        Ok(Self {
+            tmux_path,
+            sender: CommandSender::User,
+            hosts: RwLock::default(),
+            suspended: Mutex::default(),
+        })
+    }
+
+    /// Record input sent by this orchestrator as coming from `sender`
+    /// (default [`CommandSender::User`]).
+    pub fn with_sender(mut self, sender: CommandSender) -> Self {
+        self.sender = sender;
+        self
     }
 
     /// Check if tmux is available in PATH.
@@ -47,10 +83,82 @@ impl TmuxOrchestrator {
         }
     }
 
-    /// Run a tmux command and return the output.
-    fn run_tmux(&self, args: &[&str]) -> Result<Output> {
-        trace!(args = ?args, "running tmux command");
-        let output = Command::new(&self.tmux_path).args(args).output()?;
+    // ==================== Remote Hosts ====================
+
+    /// Run `session` on `host` over SSH, or locally when `host` is `None`.
+    ///
+    /// Sessions on the same host share one transport, and so one connection.
+    pub fn set_session_host(&self, session: &str, host: Option<SshHost>) {
+        let mut hosts = self.hosts.write().unwrap_or_else(|e| e.into_inner());
+        let Some(host) = host else {
+            hosts.remove(session);
+            return;
+        };
+        let transport = hosts
+            .values()
+            .find(|t| *t.host() == host)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(SshTransport::new(host)));
+        debug!(session = %session, host = %transport.host(), "session runs on remote host");
+        hosts.insert(session.to_string(), transport);
+    }
+
+    /// Host `session` runs on; `None` when it is local.
+    pub fn session_host(&self, session: &str) -> Option<SshHost> {
+        self.transport(session).map(|t| t.host().clone())
+    }
+
+    /// Smoothed SSH round trip to the host of `session`, once measured.
+    pub fn session_latency(&self, session: &str) -> Option<Duration> {
+        self.transport(session).and_then(|t| t.latency())
+    }
+
+    /// How often to poll `session`, given the interval `base` used for local
+    /// sessions: longer for sessions on slow hosts.
+    pub fn poll_interval(&self, session: &str, base: Duration) -> Duration {
+        self.transport(session).map_or(base, |t| t.poll_interval(base))
+    }
+
+    fn transport(&self, session: &str) -> Option<Arc<SshTransport>> {
+        self.hosts
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(session)
+            .cloned()
+    }
+
+    /// One transport per remote host in use.
+    fn remote_hosts(&self) -> Vec<Arc<SshTransport>> {
+        let hosts = self.hosts.read().unwrap_or_else(|e| e.into_inner());
+        let mut transports: Vec<Arc<SshTransport>> = Vec::new();
+        for transport in hosts.values() {
+            if !transports.iter().any(|t| Arc::ptr_eq(t, transport)) {
+                transports.push(Arc::clone(transport));
+            }
+        }
+        transports
+    }
+
+    /// Run `program` on the host of `session`, or locally.
+    fn run_on(&self, session: &str, program: &str, args: &[&str]) -> Result<Output> {
+        match self.transport(session) {
+            Some(transport) => Ok(transport.output(program, args)?),
+            None => Ok(Command::new(program).args(args).output()?),
+        }
+    }
+
+    /// Run a tmux command for `session`, on its host, and return the output.
+    fn run_tmux(&self, session: &str, args: &[&str]) -> Result<Output> {
+        self.run_tmux_via(self.transport(session).as_deref(), args)
+    }
+
+    /// Run a tmux command over `transport`, or locally, and return the output.
+    fn run_tmux_via(&self, transport: Option<&SshTransport>, args: &[&str]) -> Result<Output> {
+        trace!(args = ?args, host = ?transport.map(|t| t.host().to_string()), "running tmux command");
+        let output = match transport {
+            Some(transport) => transport.output("tmux", args)?,
+            None => Command::new(&self.tmux_path).args(args).output()?,
+        };
         trace!(
             status = %output.status,
             stdout_len = output.stdout.len(),
@@ -60,9 +168,9 @@ impl TmuxOrchestrator {
         Ok(output)
     }
 
-    /// Run a tmux command and check for success.
-    fn run_tmux_checked(&self, args: &[&str]) -> Result<String> {
-        let output = self.run_tmux(args)?;
+    /// Run a tmux command for `session` and check for success.
+    fn run_tmux_checked(&self, session: &str, args: &[&str]) -> Result<String> {
+        let output = self.run_tmux(session, args)?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).to_string())
@@ -94,14 +202,48 @@ impl TmuxOrchestrator {
     ///
     /// Returns error if session already exists or tmux command fails.
     pub fn create_session_in_dir(&self, name: &str, dir: Option<&str>) -> Result<TmuxSession> {
-        debug!(name = %name, dir = ?dir, "creating tmux session");
+        self.create_session_with_env(name, dir, &[])
+    }
+
+    /// Create a new detached tmux session with environment variables.
+    ///
+    /// The variables are set in the session environment (`new-session -e`),
+    /// so the first shell and every pane opened later inherit them without
+    /// their values appearing in the pane.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if session already exists or tmux command fails.
+    pub fn create_session_with_env(
+        &self,
+        name: &str,
+        dir: Option<&str>,
+        env: &[(String, String)],
+    ) -> Result<TmuxSession> {
+        debug!(name = %name, dir = ?dir, env = env.len(), "creating tmux session");
 
+        let assignments: Vec<String> = env.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
         let mut args = vec!["new-session", "-d", "-s", name];
         if let Some(d) = dir {
             args.push("-c");
             args.push(d);
         }
-        self.run_tmux_checked(&args)?;
+        if assignments.is_empty() {
+            self.run_tmux_checked(name, &args)?;
+        } else {
+            for assignment in &assignments {
+                args.push("-e");
+                args.push(assignment);
+            }
+            // Run without tracing the arguments: values may be secrets
+            let output = match self.transport(name) {
+                Some(transport) => transport.output("tmux", &args)?,
+                None => Command::new(&self.tmux_path).args(&args).output()?,
+            };
+            if !output.status.success() {
+                return Err(TmuxError::CommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+            }
+        }
 
         // Verify session was created and get details
         let sessions = self.list_sessions()?;
@@ -125,17 +267,56 @@ impl TmuxOrchestrator {
             return Err(TmuxError::SessionNotFound(name.to_string()));
         }
 
-        self.run_tmux_checked(&["kill-session", "-t", name])?;
+        self.run_tmux_checked(name, &["kill-session", "-t", name])?;
         Ok(())
     }
 
-    /// List all tmux sessions.
+    /// List all tmux sessions, local and on the remote hosts in use.
+    ///
+    /// Sessions found on a remote host are then addressed on that host,
+    /// unless a local session has the same name. A host that cannot be
+    /// reached is skipped with a warning.
     pub fn list_sessions(&self) -> Result<Vec<TmuxSession>> {
-        let output = self.run_tmux(&[
-            "list-sessions",
-            "-F",
-            "#{session_name}:#{session_created}:#{session_group}",
-        ])?;
+        let mut sessions = self.session_list(None)?;
+        sessions.retain(|s| self.transport(&s.name).is_none());
+
+        for transport in self.remote_hosts() {
+            let remote = match self.session_list(Some(&transport)) {
+                Ok(remote) => remote,
+                Err(e) => {
+                    warn!(host = %transport.host(), error = %e, "failed to list remote sessions");
+                    continue;
+                }
+            };
+            let mut hosts = self.hosts.write().unwrap_or_else(|e| e.into_inner());
+            for session in remote {
+                if sessions.iter().any(|s| s.name == session.name) {
+                    continue;
+                }
+                let owner = hosts
+                    .entry(session.name.clone())
+                    .or_insert_with(|| Arc::clone(&transport));
+                if Arc::ptr_eq(owner, &transport) {
+                    sessions.push(session);
+                }
+            }
+        }
+
+        for session in &mut sessions {
+            if let Ok(panes) = self.list_panes(&session.name) {
+                session.panes = panes;
+            }
+        }
+        Ok(sessions)
+    }
+
+    /// Sessions of the local tmux server or the one behind `transport`,
+    /// without their panes.
+    fn session_list(&self, transport: Option<&SshTransport>) -> Result<Vec<TmuxSession>> {
+        let output = self.run_tmux_via(
+            transport,
+            &["list-sessions", "-F", "#{session_name}:#{session_created}:#{session_group}"],
+        )?;
 
         // If no sessions exist, tmux returns non-zero exit code
         if !output.status.success() {
@@ -155,13 +336,7 @@ impl TmuxOrchestrator {
                 continue;
             }
             match TmuxSession::parse(line) {
-                Ok(mut session) => {
-                    // Load panes for this session
-                    if let Ok(panes) = self.list_panes(&session.name) {
-                        session.panes = panes;
-                    }
-                    sessions.push(session);
-                }
+                Ok(session) => sessions.push(session),
                 Err(e) => {
                     warn!(line = %line, error = %e, "failed to parse session");
                 }
@@ -173,10 +348,74 @@ impl TmuxOrchestrator {
 
     /// Check if a session exists.
     pub fn session_exists(&self, name: &str) -> bool {
-        let output = self.run_tmux(&["has-session", "-t", name]);
+        let output = self.run_tmux(name, &["has-session", "-t", name]);
         matches!(output, Ok(o) if o.status.success())
     }
 
+    /// Suspend a session by stopping the foreground process of each of its
+    /// panes (the runtime, not the shell) with SIGSTOP.
+    ///
+    /// Returns the stopped process groups, to pass to
+    /// [`resume_processes`](Self::resume_processes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `TmuxError::SessionNotFound` if session doesn't exist.
+    pub fn suspend_session(&self, name: &str) -> Result<Vec<u32>> {
+        if !self.session_exists(name) {
+            return Err(TmuxError::SessionNotFound(name.to_string()));
+        }
+
+        let output = self.run_tmux_checked(name, &["list-panes", "-s", "-t", name, "-F", "#{pane_pid}"])?;
+        let mut groups = Vec::new();
+        for pane_pid in output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            // Foreground process group of the pane's terminal
+            let ps = self.run_on(name, "ps", &["-o", "tpgid=", "-p", pane_pid])?;
+            match String::from_utf8_lossy(&ps.stdout).trim().parse::<i64>() {
+                Ok(group) if group > 0 => groups.push(group as u32),
+                _ => warn!(pane_pid = %pane_pid, "no foreground process group for pane"),
+            }
+        }
+        groups.sort_unstable();
+        groups.dedup();
+
+        debug!(name = %name, groups = ?groups, "suspending tmux session");
+        let transport = self.transport(name);
+        signal_process_groups(transport.as_deref(), "STOP", &groups)?;
+        if let Some(transport) = transport {
+            let mut suspended = self.suspended.lock().unwrap_or_else(|e| e.into_inner());
+            for group in &groups {
+                suspended.insert(*group, Arc::clone(&transport));
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Continue process groups stopped by [`suspend_session`](Self::suspend_session),
+    /// on the host they were stopped on.
+    pub fn resume_processes(&self, groups: &[u32]) -> Result<()> {
+        debug!(groups = ?groups, "resuming suspended processes");
+        let mut local = Vec::new();
+        let mut remote: Vec<(Arc<SshTransport>, Vec<u32>)> = Vec::new();
+        {
+            let mut suspended = self.suspended.lock().unwrap_or_else(|e| e.into_inner());
+            for group in groups {
+                match suspended.remove(group) {
+                    Some(transport) => match remote.iter_mut().find(|(t, _)| Arc::ptr_eq(t, &transport)) {
+                        Some((_, host_groups)) => host_groups.push(*group),
+                        None => remote.push((transport, vec![*group])),
+                    },
+                    None => local.push(*group),
+                }
+            }
+        }
+        signal_process_groups(None, "CONT", &local)?;
+        for (transport, host_groups) in remote {
+            signal_process_groups(Some(&transport), "CONT", &host_groups)?;
+        }
+        Ok(())
+    }
+
     // ==================== Pane Management ====================
 
     /// Create a new pane in the session (splits the window).
@@ -192,7 +431,7 @@ impl TmuxOrchestrator {
         }
 
         // Split the window to create a new pane
-        self.run_tmux_checked(&["split-window", "-t", session])?;
+        self.run_tmux_checked(session, &["split-window", "-t", session])?;
 
         // Get the newly created pane (should be the active one)
         let panes = self.list_panes(session)?;
@@ -202,6 +441,30 @@ impl TmuxOrchestrator {
             .ok_or_else(|| TmuxError::CommandFailed("failed to find new pane".to_string()))
     }
 
+    /// Create a pane running in `dir` without making it active.
+    ///
+    /// Input sent without a pane ID keeps going to the previously active
+    /// pane, so auxiliary panes can be added next to an agent's pane.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TmuxError::SessionNotFound` if session doesn't exist.
+    pub fn create_background_pane(&self, session: &str, dir: Option<&str>) -> Result<TmuxPane> {
+        debug!(session = %session, dir = ?dir, "creating background pane");
+
+        if !self.session_exists(session) {
+            return Err(TmuxError::SessionNotFound(session.to_string()));
+        }
+
+        let mut args = vec!["split-window", "-d", "-P", "-F", PANE_FORMAT, "-t", session];
+        if let Some(d) = dir {
+            args.push("-c");
+            args.push(d);
+        }
+        let output = self.run_tmux_checked(session, &args)?;
+        TmuxPane::parse(output.trim())
+    }
+
     /// List all panes in a session.
     ///
     /// # Errors
@@ -212,12 +475,12 @@ impl TmuxOrchestrator {
             return Err(TmuxError::SessionNotFound(session.to_string()));
         }
 
-        let output = self.run_tmux_checked(&[
+        let output = self.run_tmux_checked(session, &[
             "list-panes",
             "-t",
             session,
             "-F",
-            "#{pane_id}:#{pane_index}:#{pane_active}:#{pane_width}:#{pane_height}",
+            PANE_FORMAT,
         ])?;
 
         let mut panes = Vec::new();
@@ -236,6 +499,28 @@ impl TmuxOrchestrator {
         Ok(panes)
     }
 
+    /// Name of the command in the foreground of a pane
+    /// (`#{pane_current_command}`), e.g. `claude`, or the shell once a
+    /// runtime has exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TmuxError::SessionNotFound` if session doesn't exist.
+    pub fn pane_command(&self, session: &str, pane: Option<&str>) -> Result<String> {
+        if !self.session_exists(session) {
+            return Err(TmuxError::SessionNotFound(session.to_string()));
+        }
+
+        let target = match pane {
+            Some(p) => format!("{}:{}", session, p),
+            None => session.to_string(),
+        };
+        let output = self.run_tmux_checked(session,
+            &["display-message", "-p", "-t", &target, "#{pane_current_command}"],
+        )?;
+        Ok(output.trim().to_string())
+    }
+
     // ==================== I/O Operations ====================
 
     /// Capture output from a pane.
@@ -273,7 +558,7 @@ impl TmuxOrchestrator {
             args.push(&lines_arg);
         }
 
-        let output = self.run_tmux_checked(&args)?;
+        let output = self.run_tmux_checked(session, &args)?;
 
         // Validate pane exists if specified
         if let Some(p) = pane {
@@ -309,7 +594,8 @@ impl TmuxOrchestrator {
             None => session.to_string(),
         };
 
-        self.run_tmux_checked(&["send-keys", "-t", &target, keys])?;
+        self.run_tmux_checked(session, &["send-keys", "-t", &target, keys])?;
+        command_audit::record(self.sender, session, pane, InputKind::Keys, keys);
         Ok(())
     }
 
@@ -338,10 +624,33 @@ impl TmuxOrchestrator {
 
         // Send text literally (-l flag prevents interpreting as key names)
         // Then send Enter separately to execute
-        self.run_tmux_checked(&["send-keys", "-t", &target, "-l", text])?;
-        self.run_tmux_checked(&["send-keys", "-t", &target, "Enter"])?;
+        self.run_tmux_checked(session, &["send-keys", "-t", &target, "-l", text])?;
+        self.run_tmux_checked(session, &["send-keys", "-t", &target, "Enter"])?;
+        command_audit::record(self.sender, session, pane, InputKind::Line, text);
         Ok(())
     }
+
+    /// Attach a control mode client to a session, to be notified of its
+    /// output instead of polling it (see [`ControlClient::attach`]). For a
+    /// remote session the client runs over SSH.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the session doesn't exist or tmux cannot be spawned.
+    pub fn control_client<F>(&self, session: &str, on_event: F) -> Result<ControlClient>
+    where
+        F: FnMut(ControlEvent) + Send + 'static,
+    {
+        if !self.session_exists(session) {
+            return Err(TmuxError::SessionNotFound(session.to_string()));
+        }
+        match self.transport(session) {
+            Some(transport) => {
+                ControlClient::spawn(transport.command("tmux", &control::attach_args(session)), session, on_event)
+            }
+            None => ControlClient::attach(&self.tmux_path, session, on_event),
+        }
+    }
 }
 
 impl Default for TmuxOrchestrator {
@@ -350,6 +659,28 @@ impl Default for TmuxOrchestrator {
     }
 }
 
+/// Send `signal` to each process group in `groups`, on the host behind
+/// `transport` or locally.
+fn signal_process_groups(transport: Option<&SshTransport>, signal: &str, groups: &[u32]) -> Result<()> {
+    for group in groups {
+        let target = format!("-{}", group);
+        let args = ["-s", signal, "--", target.as_str()];
+        let output = match transport {
+            Some(transport) => transport.output("kill", &args)?,
+            None => Command::new("kill").args(args).output()?,
+        };
+        if !output.status.success() {
+            return Err(TmuxError::CommandFailed(format!(
+                "kill -{} {}: {}",
+                signal,
+                group,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,6 +703,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_session_hosts() {
+        let tmux = TmuxOrchestrator {
+            tmux_path: "tmux".to_string(),
+            sender: CommandSender::User,
+            hosts: RwLock::default(),
+            suspended: Mutex::default(),
+        };
+        let devbox: SshHost = "dev@devbox".parse().unwrap();
+        tmux.set_session_host("api", Some(devbox.clone()));
+        tmux.set_session_host("web", Some(devbox.clone()));
+        tmux.set_session_host("docs", Some(SshHost::new("docs-host")));
+
+        assert_eq!(tmux.session_host("api"), Some(devbox));
+        assert_eq!(tmux.session_host("local"), None);
+        // Sessions on one host share its transport
+        assert_eq!(tmux.remote_hosts().len(), 2);
+        assert!(Arc::ptr_eq(&tmux.transport("api").unwrap(), &tmux.transport("web").unwrap()));
+
+        let base = Duration::from_millis(100);
+        assert_eq!(tmux.poll_interval("local", base), base);
+        assert_eq!(tmux.session_latency("api"), None);
+
+        tmux.set_session_host("docs", None);
+        assert_eq!(tmux.session_host("docs"), None);
+        assert_eq!(tmux.remote_hosts().len(), 1);
+    }
+
     // Integration tests that require actual tmux
     #[test]
     #[ignore]
@@ -400,6 +759,41 @@ mod tests {
         assert!(!tmux.session_exists(session_name));
     }
 
+    #[test]
+    #[ignore]
+    fn test_suspend_and_resume_session() {
+        let tmux = TmuxOrchestrator::new().unwrap();
+        let session_name = "test-commander-suspend";
+        let _ = tmux.destroy_session(session_name);
+
+        tmux.create_session(session_name).unwrap();
+        tmux.send_line(session_name, None, "sleep 30").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        let groups = tmux.suspend_session(session_name).unwrap();
+        assert!(!groups.is_empty());
+        tmux.resume_processes(&groups).unwrap();
+
+        tmux.destroy_session(session_name).unwrap();
+    }
+
+    #[test]
+    #[ignore]
+    fn test_create_session_with_env() {
+        let tmux = TmuxOrchestrator::new().unwrap();
+        let session_name = "test-commander-env";
+        let _ = tmux.destroy_session(session_name);
+
+        let env = vec![("COMMANDER_TEST_VAR".to_string(), "a b=c".to_string())];
+        tmux.create_session_with_env(session_name, None, &env).unwrap();
+        let shown = tmux
+            .run_tmux_checked(session_name, &["show-environment", "-t", session_name, "COMMANDER_TEST_VAR"])
+            .unwrap();
+        assert_eq!(shown.trim(), "COMMANDER_TEST_VAR=a b=c");
+
+        tmux.destroy_session(session_name).unwrap();
+    }
+
     #[test]
     #[ignore]
     fn test_destroy_nonexistent_session() {
@@ -436,6 +830,28 @@ mod tests {
         tmux.destroy_session(session_name).unwrap();
     }
 
+    #[test]
+    #[ignore]
+    fn test_create_background_pane() {
+        let tmux = TmuxOrchestrator::new().unwrap();
+        let session_name = "test-commander-bg-pane";
+
+        // Clean up any existing session
+        let _ = tmux.destroy_session(session_name);
+
+        tmux.create_session(session_name).unwrap();
+        let main = tmux.list_panes(session_name).unwrap().remove(0);
+
+        // The new pane does not take focus
+        let pane = tmux.create_background_pane(session_name, Some("/tmp")).unwrap();
+        assert!(!pane.active);
+        let panes = tmux.list_panes(session_name).unwrap();
+        assert!(panes.iter().any(|p| p.id == main.id && p.active));
+
+        // Clean up
+        tmux.destroy_session(session_name).unwrap();
+    }
+
     #[test]
     #[ignore]
     fn test_send_keys_and_capture() {
@@ -506,4 +922,42 @@ mod tests {
         let result = tmux.capture_output("nonexistent-session-12345", None, None);
         assert!(matches!(result, Err(TmuxError::SessionNotFound(_))));
     }
+
+    #[test]
+    #[ignore]
+    fn test_pane_command_nonexistent_session() {
+        let tmux = TmuxOrchestrator::new().unwrap();
+        let result = tmux.pane_command("nonexistent-session-12345", None);
+        assert!(matches!(result, Err(TmuxError::SessionNotFound(_))));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_control_client_receives_output() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let tmux = TmuxOrchestrator::new().unwrap();
+        let session_name = "test-commander-control";
+        let _ = tmux.destroy_session(session_name);
+        tmux.create_session(session_name).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let client = tmux
+            .control_client(session_name, move |event| {
+                let _ = tx.send(event);
+            })
+            .unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(2)), Ok(ControlEvent::Connected));
+
+        tmux.send_line(session_name, None, "echo control-mode").unwrap();
+        let saw_output = rx
+            .iter()
+            .take_while(|event| !matches!(event, ControlEvent::Exit(_)))
+            .any(|event| matches!(event, ControlEvent::Output { data, .. } if data.contains("control-mode")));
+        assert!(saw_output);
+
+        drop(client);
+        tmux.destroy_session(session_name).unwrap();
+    }
 }
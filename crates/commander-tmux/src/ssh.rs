@@ -0,0 +1,234 @@
+//! Running tmux on remote hosts over SSH.
+//!
+//! An [`SshTransport`] runs each command as `ssh <host> -- <command>`. The
+//! commands for one host share a single connection through OpenSSH
+//! multiplexing (`ControlMaster`/`ControlPersist`): the first command opens a
+//! master connection whose socket later commands reuse, so each costs one
+//! round trip instead of a handshake. The master stays up for a while after
+//! the last command.
+//!
+//! The round trip of every command feeds a smoothed latency, from which
+//! [`SshTransport::poll_interval`] spaces out polling of slow hosts.
+//! Authentication is non-interactive (`BatchMode`): keys, agents and
+//! `~/.ssh/config` entries apply as for a plain `ssh`.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use tracing::trace;
+
+use crate::TmuxError;
+
+/// How long an idle master connection stays open.
+const CONTROL_PERSIST: &str = "10m";
+
+/// Polling a remote session waits at least this many round trips.
+const POLL_ROUND_TRIPS: u32 = 4;
+
+/// Longest interval latency stretches polling to.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A remote host, as `[user@]host[:port]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SshHost {
+    /// `[user@]host`, or an alias from `~/.ssh/config`.
+    pub destination: String,
+    pub port: Option<u16>,
+}
+
+impl SshHost {
+    /// A host on the default port.
+    pub fn new(destination: impl Into<String>) -> Self {
+        Self {
+            destination: destination.into(),
+            port: None,
+        }
+    }
+}
+
+impl FromStr for SshHost {
+    type Err = TmuxError;
+
+    /// Parse `[ssh://][user@]host[:port]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spec = s.trim();
+        let spec = spec.strip_prefix("ssh://").unwrap_or(spec).trim_end_matches('/');
+        let invalid = || TmuxError::ParseError(format!("invalid SSH host '{}' (expected [user@]host[:port])", s));
+
+        let (destination, port) = match spec.rsplit_once(':') {
+            Some((destination, port)) => (destination, Some(port.parse::<u16>().map_err(|_| invalid())?)),
+            None => (spec, None),
+        };
+        let host = destination.rsplit('@').next().unwrap_or_default();
+        if host.is_empty() || destination.chars().any(|c| c.is_whitespace() || c == '/') {
+            return Err(invalid());
+        }
+        Ok(Self {
+            destination: destination.to_string(),
+            port,
+        })
+    }
+}
+
+impl fmt::Display for SshHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "{}:{}", self.destination, port),
+            None => write!(f, "{}", self.destination),
+        }
+    }
+}
+
+/// Runs commands on one host over a shared SSH connection.
+#[derive(Debug)]
+pub struct SshTransport {
+    host: SshHost,
+    /// Directory of the multiplexing sockets.
+    control_dir: PathBuf,
+    /// Smoothed round trip in microseconds; 0 until the first command.
+    latency_micros: AtomicU64,
+}
+
+impl SshTransport {
+    /// A transport to `host`, keeping its connection socket in the state
+    /// directory.
+    pub fn new(host: SshHost) -> Self {
+        Self::with_control_dir(host, commander_core::runtime_state_dir().join("ssh"))
+    }
+
+    /// A transport keeping its connection socket in `control_dir`.
+    pub fn with_control_dir(host: SshHost, control_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            host,
+            control_dir: control_dir.into(),
+            latency_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// The host commands run on.
+    pub fn host(&self) -> &SshHost {
+        &self.host
+    }
+
+    /// `ssh` invocation running `program` with `args` on the host.
+    pub fn command(&self, program: &str, args: &[&str]) -> Command {
+        let mut command = Command::new("ssh");
+        command
+            .args(["-o", "ControlMaster=auto"])
+            .arg("-o")
+            // %C is a hash of the connection, short enough for a socket path
+            .arg(format!("ControlPath={}/%C", self.control_dir.display()))
+            .arg("-o")
+            .arg(format!("ControlPersist={}", CONTROL_PERSIST))
+            .args(["-o", "BatchMode=yes", "-o", "ServerAliveInterval=15"]);
+        if let Some(port) = self.host.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        command
+            .arg(&self.host.destination)
+            .arg("--")
+            .arg(remote_command(program, args));
+        command
+    }
+
+    /// Run `program` with `args` on the host and wait for it, recording the
+    /// round trip.
+    pub fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        std::fs::create_dir_all(&self.control_dir)?;
+        let started = Instant::now();
+        let output = self.command(program, args).output()?;
+        self.record_latency(started.elapsed());
+        trace!(host = %self.host, program = %program, status = %output.status, "ssh command completed");
+        Ok(output)
+    }
+
+    /// Smoothed round trip of the host's commands, once one has run.
+    pub fn latency(&self) -> Option<Duration> {
+        match self.latency_micros.load(Ordering::Relaxed) {
+            0 => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// How often to poll a session on the host, given the interval `base`
+    /// used for local sessions: a few round trips at least, up to 5 seconds.
+    pub fn poll_interval(&self, base: Duration) -> Duration {
+        match self.latency() {
+            Some(latency) => base.max((latency * POLL_ROUND_TRIPS).min(MAX_POLL_INTERVAL)),
+            None => base,
+        }
+    }
+
+    fn record_latency(&self, sample: Duration) {
+        let sample = (sample.as_micros() as u64).max(1);
+        let _ = self
+            .latency_micros
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                // Exponential moving average, weighting new samples by 1/4
+                Some(if current == 0 { sample } else { (current * 3 + sample) / 4 })
+            });
+    }
+}
+
+/// Shell command line running `program` with `args` on the remote host.
+fn remote_command(program: &str, args: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().copied())
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Quote `word` for a POSIX shell, leaving plain words as they are.
+fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,%@+".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host() {
+        let host: SshHost = "dev@build-box:2222".parse().unwrap();
+        assert_eq!(host.destination, "dev@build-box");
+        assert_eq!(host.port, Some(2222));
+        assert_eq!(host.to_string(), "dev@build-box:2222");
+        assert_eq!("ssh://devbox/".parse::<SshHost>().unwrap(), SshHost::new("devbox"));
+        assert!("dev@".parse::<SshHost>().is_err());
+        assert!("devbox:ssh".parse::<SshHost>().is_err());
+        assert!("dev box".parse::<SshHost>().is_err());
+    }
+
+    #[test]
+    fn test_command_and_polling() {
+        let transport = SshTransport::with_control_dir("dev@devbox:2222".parse().unwrap(), "/tmp/ssh");
+        let command = transport.command("tmux", &["send-keys", "-t", "api", "-l", "echo 'hi' $HOME"]);
+        let args: Vec<String> = command.get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+        assert!(args.contains(&"ControlPath=/tmp/ssh/%C".to_string()));
+        assert_eq!(&args[args.len() - 4..args.len() - 1], ["2222", "dev@devbox", "--"]);
+        assert_eq!(args.last().unwrap(), r"tmux send-keys -t api -l 'echo '\''hi'\'' $HOME'");
+
+        let base = Duration::from_millis(100);
+        assert_eq!(transport.poll_interval(base), base);
+        transport.record_latency(Duration::from_millis(80));
+        transport.record_latency(Duration::from_millis(160));
+        assert_eq!(transport.latency(), Some(Duration::from_millis(100)));
+        assert_eq!(transport.poll_interval(base), Duration::from_millis(400));
+        transport.record_latency(Duration::from_secs(10));
+        assert_eq!(transport.poll_interval(base), MAX_POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,491 @@
+//! WorkGraph - dependency DAG of work items.
+//!
+//! Builds the graph formed by `WorkItem::depends_on`, rejecting cycles, and
+//! answers the questions a large delegated plan raises:
+//! - Which items are blocked on unfinished dependencies
+//! - Which chain of unfinished items bounds how soon the work can finish
+//!   (the critical path)
+//!
+//! The graph renders as indented ASCII for terminals, with a countdown on
+//! items that have a deadline, and as Graphviz DOT.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::Utc;
+use commander_models::{WorkId, WorkItem, WorkState};
+
+use crate::error::{Result, WorkError};
+use crate::sla::SlaPolicy;
+
+/// Characters of a work ID (after its `work-` prefix) shown in renderings.
+const SHORT_ID_LEN: usize = 8;
+
+/// Characters of an item's content shown as its title.
+const TITLE_LEN: usize = 60;
+
+/// Dependency graph of work items, in topological order.
+#[derive(Debug, Clone)]
+pub struct WorkGraph {
+    /// Items, dependencies before dependents.
+    items: Vec<WorkItem>,
+    /// Position of each item in `items`.
+    index: HashMap<WorkId, usize>,
+    /// Known dependencies of each item, as positions.
+    deps: Vec<Vec<usize>>,
+    /// Longest dependency chain above each item.
+    depth: Vec<usize>,
+    /// Critical path, as positions from first to last.
+    critical: Vec<usize>,
+}
+
+impl WorkGraph {
+    /// Builds the graph of `items`.
+    ///
+    /// Dependencies on items outside the set are not drawn, but still keep
+    /// the dependent blocked.
+    ///
+    /// # Errors
+    ///
+    /// `WorkError::DependencyCycle` if the dependencies form a cycle.
+    pub fn build(items: impl IntoIterator<Item = WorkItem>) -> Result<Self> {
+        // Oldest first, so independent items keep a stable order
+        let mut items: Vec<WorkItem> = items.into_iter().collect();
+        items.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.as_str().cmp(b.id.as_str())));
+        let index: HashMap<WorkId, usize> =
+            items.iter().enumerate().map(|(i, item)| (item.id.clone(), i)).collect();
+        let deps: Vec<Vec<usize>> = items
+            .iter()
+            .map(|item| {
+                let known: BTreeSet<usize> = item.depends_on.iter().filter_map(|d| index.get(d).copied()).collect();
+                known.into_iter().collect()
+            })
+            .collect();
+
+        let order = topological_order(&deps).map_err(|cycle| {
+            let path: Vec<&str> = cycle.iter().map(|&i| items[i].id.as_str()).collect();
+            WorkError::DependencyCycle(path.join(" -> "))
+        })?;
+
+        // Re-index everything in topological order
+        let position: HashMap<usize, usize> = order.iter().enumerate().map(|(new, &old)| (old, new)).collect();
+        let deps: Vec<Vec<usize>> = order
+            .iter()
+            .map(|&old| deps[old].iter().map(|d| position[d]).collect())
+            .collect();
+        let mut slots: Vec<Option<WorkItem>> = items.into_iter().map(Some).collect();
+        let items: Vec<WorkItem> = order.iter().map(|&old| slots[old].take().expect("each item once")).collect();
+        let index = items.iter().enumerate().map(|(i, item)| (item.id.clone(), i)).collect();
+
+        let mut depth = vec![0; items.len()];
+        for i in 0..items.len() {
+            depth[i] = deps[i].iter().map(|&d| depth[d] + 1).max().unwrap_or(0);
+        }
+
+        let critical = critical_path(&items, &deps);
+        Ok(Self {
+            items,
+            index,
+            deps,
+            depth,
+            critical,
+        })
+    }
+
+    /// Items in topological order (dependencies first).
+    pub fn items(&self) -> &[WorkItem] {
+        &self.items
+    }
+
+    /// Number of items in the graph.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the graph has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Gets an item by ID.
+    pub fn get(&self, id: &WorkId) -> Option<&WorkItem> {
+        self.index.get(id).map(|&i| &self.items[i])
+    }
+
+    /// Items that `id` depends on (within the graph).
+    pub fn dependencies(&self, id: &WorkId) -> Vec<&WorkItem> {
+        self.index
+            .get(id)
+            .map(|&i| self.deps[i].iter().map(|&d| &self.items[d]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Items that depend on `id`.
+    pub fn dependents(&self, id: &WorkId) -> Vec<&WorkItem> {
+        let Some(&target) = self.index.get(id) else {
+            return Vec::new();
+        };
+        (0..self.items.len())
+            .filter(|&i| self.deps[i].contains(&target))
+            .map(|i| &self.items[i])
+            .collect()
+    }
+
+    /// Length of the longest dependency chain above `id` (0 for roots).
+    pub fn depth(&self, id: &WorkId) -> usize {
+        self.index.get(id).map(|&i| self.depth[i]).unwrap_or(0)
+    }
+
+    /// Returns true if `id` is waiting on a dependency that has not completed,
+    /// or was marked blocked.
+    pub fn is_blocked(&self, id: &WorkId) -> bool {
+        self.index.get(id).is_some_and(|&i| self.blocked_at(i))
+    }
+
+    /// Items that are blocked, in topological order.
+    pub fn blocked(&self) -> Vec<&WorkItem> {
+        (0..self.items.len())
+            .filter(|&i| self.blocked_at(i))
+            .map(|i| &self.items[i])
+            .collect()
+    }
+
+    /// Longest chain of unfinished items, from the first to start to the last.
+    ///
+    /// Completed and cancelled items are off the path; it is empty when all
+    /// work is finished.
+    pub fn critical_path(&self) -> Vec<&WorkItem> {
+        self.critical.iter().map(|&i| &self.items[i]).collect()
+    }
+
+    /// Renders the graph as indented ASCII lines, one per item.
+    ///
+    /// Items are indented by depth, marked with their state (`[#]` for
+    /// blocked) and starred when on the critical path. Open items with a
+    /// deadline end with their countdown, e.g. `[overdue 2h 10m]`.
+    pub fn render_ascii(&self) -> Vec<String> {
+        let policy = SlaPolicy::default();
+        let now = Utc::now();
+        let mut lines = vec![
+            "[x] done  [>] running  [ ] ready  [#] blocked  [!] failed  [-] cancelled  * critical path"
+                .to_string(),
+        ];
+        for (i, item) in self.items.iter().enumerate() {
+            let star = if self.critical.contains(&i) { '*' } else { ' ' };
+            let mut line = format!(
+                "{} {} {}{} {}",
+                self.state_mark(i),
+                star,
+                "  ".repeat(self.depth[i]),
+                short_id(&item.id),
+                title(item)
+            );
+            if !self.deps[i].is_empty() {
+                let after: Vec<String> = self.deps[i].iter().map(|&d| short_id(&self.items[d].id)).collect();
+                line.push_str(&format!(" (after {})", after.join(", ")));
+            }
+            if let Some(tag) = policy.tag(item, now) {
+                line.push(' ');
+                line.push_str(&tag);
+            }
+            lines.push(line);
+        }
+
+        let blocked = self.blocked().len();
+        let overdue = self.items.iter().filter(|item| item.is_overdue(now)).count();
+        if overdue > 0 {
+            lines.push(format!("{} items, {} blocked, {} overdue", self.items.len(), blocked, overdue));
+        } else {
+            lines.push(format!("{} items, {} blocked", self.items.len(), blocked));
+        }
+        if !self.critical.is_empty() {
+            let path: Vec<String> = self.critical.iter().map(|&i| short_id(&self.items[i].id)).collect();
+            lines.push(format!(
+                "Critical path ({} remaining): {}",
+                self.critical.len(),
+                path.join(" -> ")
+            ));
+        }
+        lines
+    }
+
+    /// Renders the graph as Graphviz DOT.
+    ///
+    /// Nodes are filled by state (blocked items in yellow); the critical path
+    /// is outlined in red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph work {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+
+        for (i, item) in self.items.iter().enumerate() {
+            let fill = if self.blocked_at(i) {
+                "khaki"
+            } else {
+                match item.state {
+                    WorkState::Completed => "palegreen",
+                    WorkState::InProgress => "lightblue",
+                    WorkState::Failed => "salmon",
+                    WorkState::Cancelled => "lightgrey",
+                    _ => "white",
+                }
+            };
+            let critical = if self.critical.contains(&i) {
+                ", color=\"red\", penwidth=2"
+            } else {
+                ""
+            };
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\", fillcolor=\"{}\"{}];\n",
+                item.id,
+                short_id(&item.id),
+                escape_dot(&title(item)),
+                fill,
+                critical
+            ));
+        }
+
+        for (i, item) in self.items.iter().enumerate() {
+            for &d in &self.deps[i] {
+                let on_path = self
+                    .critical
+                    .windows(2)
+                    .any(|pair| pair[0] == d && pair[1] == i);
+                let style = if on_path { " [color=\"red\", penwidth=2]" } else { "" };
+                dot.push_str(&format!("    \"{}\" -> \"{}\"{};\n", self.items[d].id, item.id, style));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn blocked_at(&self, i: usize) -> bool {
+        let item = &self.items[i];
+        match item.state {
+            WorkState::Blocked => true,
+            WorkState::Pending | WorkState::Queued => item.depends_on.iter().any(|dep| {
+                self.index
+                    .get(dep)
+                    .is_none_or(|&d| self.items[d].state != WorkState::Completed)
+            }),
+            _ => false,
+        }
+    }
+
+    fn state_mark(&self, i: usize) -> &'static str {
+        if self.blocked_at(i) {
+            return "[#]";
+        }
+        match self.items[i].state {
+            WorkState::Completed => "[x]",
+            WorkState::InProgress => "[>]",
+            WorkState::Failed => "[!]",
+            WorkState::Cancelled => "[-]",
+            _ => "[ ]",
+        }
+    }
+}
+
+/// Orders nodes so dependencies come first, preferring lower positions among
+/// nodes that are ready together.
+///
+/// Returns the nodes of one cycle (first node repeated at the end) if the
+/// dependencies are not acyclic.
+fn topological_order(deps: &[Vec<usize>]) -> std::result::Result<Vec<usize>, Vec<usize>> {
+    let n = deps.len();
+    let mut remaining: Vec<usize> = deps.iter().map(Vec::len).collect();
+    let mut dependents = vec![Vec::new(); n];
+    for (i, node_deps) in deps.iter().enumerate() {
+        for &d in node_deps {
+            dependents[d].push(i);
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..n).filter(|&i| remaining[i] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(i) = ready.pop_first() {
+        order.push(i);
+        for &dependent in &dependents[i] {
+            remaining[dependent] -= 1;
+            if remaining[dependent] == 0 {
+                ready.insert(dependent);
+            }
+        }
+    }
+    if order.len() == n {
+        return Ok(order);
+    }
+
+    // Every unordered node waits on another unordered node, so following
+    // those dependencies must revisit a node
+    let start = (0..n).find(|&i| remaining[i] > 0).expect("an unordered node");
+    let mut path = vec![start];
+    let mut seen = HashMap::from([(start, 0)]);
+    let mut node = start;
+    loop {
+        node = *deps[node]
+            .iter()
+            .find(|&&d| remaining[d] > 0)
+            .expect("unordered node waits on an unordered dependency");
+        if let Some(&at) = seen.get(&node) {
+            let mut cycle: Vec<usize> = path[at..].iter().rev().copied().collect();
+            cycle.push(cycle[0]);
+            return Err(cycle);
+        }
+        seen.insert(node, path.len());
+        path.push(node);
+    }
+}
+
+/// Longest chain of unfinished items over topologically ordered `deps`.
+fn critical_path(items: &[WorkItem], deps: &[Vec<usize>]) -> Vec<usize> {
+    let unfinished =
+        |i: usize| !matches!(items[i].state, WorkState::Completed | WorkState::Cancelled);
+
+    // Length of the longest unfinished chain ending at each item
+    let mut length = vec![0usize; items.len()];
+    let mut previous: Vec<Option<usize>> = vec![None; items.len()];
+    for i in 0..items.len() {
+        if !unfinished(i) {
+            continue;
+        }
+        let best = deps[i].iter().copied().filter(|&d| unfinished(d)).max_by_key(|&d| (length[d], std::cmp::Reverse(d)));
+        length[i] = 1 + best.map_or(0, |d| length[d]);
+        previous[i] = best;
+    }
+
+    let Some(end) = (0..items.len())
+        .filter(|&i| length[i] > 0)
+        .max_by_key(|&i| (length[i], std::cmp::Reverse(i)))
+    else {
+        return Vec::new();
+    };
+    let mut path = vec![end];
+    while let Some(p) = previous[*path.last().expect("path is not empty")] {
+        path.push(p);
+    }
+    path.reverse();
+    path
+}
+
+/// Short form of a work ID for display.
+fn short_id(id: &WorkId) -> String {
+    let id = id.as_str();
+    id.strip_prefix("work-").unwrap_or(id).chars().take(SHORT_ID_LEN).collect()
+}
+
+/// First line of an item's content, truncated for display.
+fn title(item: &WorkItem) -> String {
+    let line = item.content.lines().next().unwrap_or("").trim();
+    if line.chars().count() > TITLE_LEN {
+        format!("{}...", line.chars().take(TITLE_LEN - 3).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Escapes text for a quoted DOT string.
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    /// Items `a`..: created in order, depending on the given indices.
+    fn items(spec: &[(&str, &[usize], WorkState)]) -> Vec<WorkItem> {
+        let base = Utc::now();
+        let mut items: Vec<WorkItem> = Vec::new();
+        for (n, (name, deps, state)) in spec.iter().enumerate() {
+            let mut item = WorkItem::new("proj", *name);
+            item.id = WorkId::from_string(format!("work-{}", name));
+            item.created_at = base + Duration::seconds(n as i64);
+            item.state = *state;
+            item.depends_on = deps.iter().map(|&d| WorkId::from_string(format!("work-{}", spec[d].0))).collect();
+            items.push(item);
+        }
+        items
+    }
+
+    fn ids(items: &[&WorkItem]) -> Vec<String> {
+        items.iter().map(|item| item.content.clone()).collect()
+    }
+
+    #[test]
+    fn test_topological_order_and_depth() {
+        // c depends on b, b on a; created out of dependency order
+        let graph = WorkGraph::build(items(&[
+            ("c", &[1], WorkState::Queued),
+            ("b", &[2], WorkState::Queued),
+            ("a", &[], WorkState::Queued),
+            ("d", &[], WorkState::Queued),
+        ]))
+        .unwrap();
+
+        let order: Vec<&str> = graph.items().iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(order, vec!["a", "b", "c", "d"]);
+        assert_eq!(graph.depth(&WorkId::from_string("work-c")), 2);
+        assert_eq!(ids(&graph.dependents(&WorkId::from_string("work-a"))), vec!["b"]);
+    }
+
+    #[test]
+    fn test_cycle_detected() {
+        let err = WorkGraph::build(items(&[
+            ("a", &[2], WorkState::Queued),
+            ("b", &[0], WorkState::Queued),
+            ("c", &[1], WorkState::Queued),
+            ("d", &[], WorkState::Queued),
+        ]))
+        .unwrap_err();
+
+        match err {
+            WorkError::DependencyCycle(path) => {
+                assert_eq!(path.split(" -> ").count(), 4);
+                assert!(path.contains("work-a") && path.contains("work-b") && path.contains("work-c"));
+                assert!(!path.contains("work-d"));
+            }
+            other => panic!("expected cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_blocked_and_critical_path() {
+        // a done; b and c ready; d needs b and c; e needs d; f needs a missing item
+        let mut spec = items(&[
+            ("a", &[], WorkState::Completed),
+            ("b", &[0], WorkState::InProgress),
+            ("c", &[0], WorkState::Queued),
+            ("d", &[1, 2], WorkState::Queued),
+            ("e", &[3], WorkState::Queued),
+            ("f", &[], WorkState::Queued),
+        ]);
+        spec[5].depends_on.push(WorkId::from_string("work-gone"));
+        let graph = WorkGraph::build(spec).unwrap();
+
+        assert_eq!(ids(&graph.blocked()), vec!["d", "e", "f"]);
+        assert!(!graph.is_blocked(&WorkId::from_string("work-c")));
+        assert_eq!(ids(&graph.critical_path()), vec!["b", "d", "e"]);
+
+        let ascii = graph.render_ascii();
+        assert!(ascii.iter().any(|l| l == "[#] *     d d (after b, c)"));
+        assert!(ascii.iter().any(|l| l == "Critical path (3 remaining): b -> d -> e"));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"work-b\" -> \"work-d\" [color=\"red\", penwidth=2];"));
+        assert!(dot.contains("\"work-c\" -> \"work-d\";"));
+        assert!(dot.contains("\"work-d\" [label=\"d\\nd\", fillcolor=\"khaki\", color=\"red\", penwidth=2];"));
+    }
+
+    #[test]
+    fn test_finished_work_has_no_critical_path() {
+        let graph = WorkGraph::build(items(&[
+            ("a", &[], WorkState::Completed),
+            ("b", &[0], WorkState::Cancelled),
+        ]))
+        .unwrap();
+        assert!(graph.critical_path().is_empty());
+        assert!(graph.blocked().is_empty());
+    }
+}
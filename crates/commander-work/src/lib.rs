@@ -4,6 +4,8 @@
 //! - Thread-safe queue using `Arc<Mutex<T>>`
 //! - Priority ordering using `BinaryHeap` with custom `Ord`
 //! - Dependency tracking (items blocked until dependencies complete)
+//! - Dependency graphs with cycle detection and critical path (`WorkGraph`)
+//! - SLA deadlines with escalating due-soon/overdue/breached levels (`SlaPolicy`)
 //! - Persistence integration with `WorkStore`
 //!
 //! # Example
@@ -30,8 +32,12 @@
 
 pub mod error;
 pub mod filter;
+pub mod graph;
 pub mod queue;
+pub mod sla;
 
 pub use error::{WorkError, Result};
 pub use filter::WorkFilter;
+pub use graph::WorkGraph;
 pub use queue::WorkQueue;
+pub use sla::{parse_sla, SlaEscalation, SlaPolicy};
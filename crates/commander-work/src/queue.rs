@@ -10,11 +10,13 @@ use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use chrono::{DateTime, Utc};
-use commander_models::{ProjectId, WorkId, WorkItem, WorkPriority, WorkState};
+use commander_models::{ProjectId, SlaStatus, WorkId, WorkItem, WorkPriority, WorkState};
 use commander_persistence::WorkStore;
 
 use crate::error::{Result, WorkError};
 use crate::filter::WorkFilter;
+use crate::graph::WorkGraph;
+use crate::sla::{SlaEscalation, SlaPolicy};
 
 /// Wrapper for WorkItem that implements custom ordering for BinaryHeap.
 ///
@@ -182,6 +184,14 @@ impl WorkQueue {
         Ok(())
     }
 
+    /// Loads the work items of every project in the store.
+    pub fn load_all(&self) -> Result<()> {
+        for project_id in self.store.list_projects()? {
+            self.load_project(&project_id)?;
+        }
+        Ok(())
+    }
+
     /// Adds a work item to the queue.
     ///
     /// # Returns
@@ -287,6 +297,167 @@ impl WorkQueue {
         state.items.get(id).cloned()
     }
 
+    /// Starts a specific work item, taking it out of the queue.
+    ///
+    /// Unlike `dequeue`, the caller picks the item (for example when it is
+    /// delegated to a session). Its dependencies must be completed.
+    pub fn start(&self, id: &WorkId) -> Result<WorkItem> {
+        let item_clone = {
+            let mut state = self
+                .state
+                .lock()
+                .map_err(|e| WorkError::LockPoisoned(e.to_string()))?;
+
+            let item = state
+                .items
+                .get(id)
+                .ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+            if !matches!(item.state, WorkState::Pending | WorkState::Queued) {
+                return Err(WorkError::InvalidState(format!(
+                    "cannot start item in {:?} state",
+                    item.state
+                )));
+            }
+            if !item.can_start(&state.completed) {
+                return Err(WorkError::InvalidState(
+                    "dependencies are not completed".to_string(),
+                ));
+            }
+
+            state.heap.retain(|pw| &pw.item.id != id);
+            let item = state.items.get_mut(id).unwrap();
+            item.start();
+            item.clone()
+        };
+
+        self.store.save_work(&item_clone)?;
+
+        Ok(item_clone)
+    }
+
+    /// Sets a metadata entry on a work item and persists it.
+    pub fn set_metadata(&self, id: &WorkId, key: &str, value: serde_json::Value) -> Result<()> {
+        let item_clone = {
+            let mut state = self
+                .state
+                .lock()
+                .map_err(|e| WorkError::LockPoisoned(e.to_string()))?;
+
+            let item = state
+                .items
+                .get_mut(id)
+                .ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+            item.metadata.insert(key.to_string(), value.clone());
+            let item_clone = item.clone();
+
+            // Keep the queued copy in step so a later dequeue returns it
+            if let Some(mut pw) = state.heap.iter().find(|pw| &pw.item.id == id).cloned() {
+                state.heap.retain(|pw| &pw.item.id != id);
+                pw.item.metadata.insert(key.to_string(), value);
+                state.heap.push(pw);
+            }
+            item_clone
+        };
+
+        self.store.save_work(&item_clone)?;
+
+        Ok(())
+    }
+
+    /// Sets or clears the deadline of a work item and persists it.
+    ///
+    /// The recorded SLA status is reset, so the new deadline escalates from
+    /// the start.
+    pub fn set_deadline(&self, id: &WorkId, deadline: Option<DateTime<Utc>>) -> Result<()> {
+        let item = self.update(id, |item| {
+            item.deadline = deadline;
+            item.sla_status = None;
+        })?;
+        self.store.save_work(&item)?;
+        Ok(())
+    }
+
+    /// Records the SLA level of every open item with a deadline.
+    ///
+    /// Returns the items whose level rose to due soon or beyond since the
+    /// last check, most urgent first. Items whose level fell (for example
+    /// after a deadline was extended) are updated silently.
+    pub fn check_sla(&self, policy: &SlaPolicy, now: DateTime<Utc>) -> Result<Vec<SlaEscalation>> {
+        let changed: Vec<(WorkId, SlaStatus)> = {
+            let state = self
+                .state
+                .lock()
+                .map_err(|e| WorkError::LockPoisoned(e.to_string()))?;
+            state
+                .items
+                .values()
+                .filter_map(|item| {
+                    let status = policy.status(item, now)?;
+                    (item.sla_status != Some(status)).then(|| (item.id.clone(), status))
+                })
+                .collect()
+        };
+
+        let mut escalations = Vec::new();
+        for (id, status) in changed {
+            let mut previous = None;
+            let item = self.update(&id, |item| {
+                previous = item.sla_status;
+                item.sla_status = Some(status);
+            })?;
+            self.store.save_work(&item)?;
+            if status > SlaStatus::OnTrack && previous.is_none_or(|previous| status > previous) {
+                escalations.push(SlaEscalation { item, status });
+            }
+        }
+        escalations.sort_by(|a, b| b.status.cmp(&a.status).then_with(|| a.item.deadline.cmp(&b.item.deadline)));
+        Ok(escalations)
+    }
+
+    /// Returns the open items past their deadline at `now`, most overdue first.
+    pub fn overdue(&self, now: DateTime<Utc>) -> Vec<WorkItem> {
+        let state = match self.state.lock() {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut overdue: Vec<WorkItem> = state
+            .items
+            .values()
+            .filter(|item| item.is_overdue(now))
+            .cloned()
+            .collect();
+        overdue.sort_by(|a, b| {
+            a.deadline
+                .cmp(&b.deadline)
+                .then_with(|| b.priority.cmp(&a.priority))
+        });
+        overdue
+    }
+
+    /// Applies `f` to a work item, and to its queued copy if any, returning
+    /// the updated item.
+    fn update(&self, id: &WorkId, f: impl FnOnce(&mut WorkItem)) -> Result<WorkItem> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| WorkError::LockPoisoned(e.to_string()))?;
+
+        let item = state
+            .items
+            .get_mut(id)
+            .ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        f(item);
+        let item_clone = item.clone();
+
+        // Keep the queued copy in step so a later dequeue returns it
+        if state.heap.iter().any(|pw| &pw.item.id == id) {
+            state.heap.retain(|pw| &pw.item.id != id);
+            state.heap.push(PrioritizedWork::new(item_clone.clone()));
+        }
+        Ok(item_clone)
+    }
+
     /// Marks a work item as completed.
     ///
     /// This:
@@ -460,6 +631,19 @@ impl WorkQueue {
         ready
     }
 
+    /// Builds the dependency graph of all items in the queue.
+    ///
+    /// # Errors
+    ///
+    /// `WorkError::DependencyCycle` if item dependencies form a cycle.
+    pub fn graph(&self) -> Result<WorkGraph> {
+        let state = self
+            .state
+            .lock()
+            .map_err(|e| WorkError::LockPoisoned(e.to_string()))?;
+        WorkGraph::build(state.items.values().cloned())
+    }
+
     /// Returns the number of items in the queue (all states).
     pub fn len(&self) -> usize {
         self.state
@@ -745,6 +929,25 @@ mod tests {
         assert_eq!(ready[0].content, "Ready");
     }
 
+    #[test]
+    fn test_graph() {
+        let queue = make_queue();
+
+        let dep_id = queue.enqueue(make_item("p1", "Dependency")).unwrap();
+        let mut item = make_item("p1", "Dependent");
+        item.depends_on = vec![dep_id.clone()];
+        let item_id = queue.enqueue(item).unwrap();
+
+        let graph = queue.graph().unwrap();
+        assert_eq!(graph.len(), 2);
+        assert!(graph.is_blocked(&item_id));
+        assert_eq!(graph.critical_path().len(), 2);
+
+        let dep = queue.dequeue().unwrap();
+        queue.complete(&dep.id).unwrap();
+        assert!(!queue.graph().unwrap().is_blocked(&item_id));
+    }
+
     #[test]
     fn test_peek() {
         let queue = make_queue();
@@ -962,4 +1165,91 @@ mod tests {
         let completed = queue.get(&dequeued.id).unwrap();
         assert_eq!(completed.result, Some("Success!".to_string()));
     }
+
+    #[test]
+    fn test_start_specific_item() {
+        let queue = make_queue();
+
+        let first = queue.enqueue(make_item("p1", "A")).unwrap();
+        let second = queue.enqueue(make_item("p1", "B")).unwrap();
+
+        let started = queue.start(&second).unwrap();
+        assert_eq!(started.state, WorkState::InProgress);
+        assert!(queue.start(&second).is_err());
+        assert_eq!(queue.dequeue().unwrap().id, first);
+        assert!(queue.dequeue().is_none());
+
+        let mut blocked = make_item("p1", "C");
+        blocked.depends_on = vec![first];
+        let blocked = queue.enqueue(blocked).unwrap();
+        assert!(matches!(queue.start(&blocked), Err(WorkError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_set_metadata() {
+        let queue = make_queue();
+
+        let id = queue.enqueue(make_item("p1", "Task")).unwrap();
+        queue.set_metadata(&id, "issue", serde_json::json!(12)).unwrap();
+
+        assert_eq!(queue.get(&id).unwrap().metadata["issue"], 12);
+        assert_eq!(queue.dequeue().unwrap().metadata["issue"], 12);
+        assert!(queue.set_metadata(&WorkId::new(), "issue", serde_json::json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_check_sla_escalates_once_per_level() {
+        let queue = make_queue();
+        let policy = SlaPolicy::default();
+        let now = Utc::now();
+
+        let mut item = make_item("p1", "Fix login");
+        item.deadline = Some(now + chrono::Duration::minutes(30));
+        let id = queue.enqueue(item).unwrap();
+        queue.enqueue(make_item("p1", "No SLA")).unwrap();
+
+        let escalations = queue.check_sla(&policy, now).unwrap();
+        assert_eq!(escalations.len(), 1);
+        assert_eq!(escalations[0].status, SlaStatus::DueSoon);
+        assert_eq!(queue.get(&id).unwrap().sla_status, Some(SlaStatus::DueSoon));
+        assert!(queue.check_sla(&policy, now).unwrap().is_empty());
+
+        let later = now + chrono::Duration::hours(1);
+        let escalations = queue.check_sla(&policy, later).unwrap();
+        assert_eq!(escalations[0].status, SlaStatus::Overdue);
+        assert_eq!(queue.overdue(later).len(), 1);
+
+        // Extending the deadline starts over without an escalation
+        queue.set_deadline(&id, Some(later + chrono::Duration::days(1))).unwrap();
+        assert!(queue.check_sla(&policy, later).unwrap().is_empty());
+        assert!(queue.overdue(later).is_empty());
+        assert_eq!(queue.dequeue().unwrap().sla_status, Some(SlaStatus::OnTrack));
+    }
+
+    #[test]
+    fn test_overdue_most_overdue_first() {
+        let queue = make_queue();
+        let now = Utc::now();
+
+        for (content, hours) in [("Recent", -1), ("Oldest", -5), ("Future", 2)] {
+            let mut item = make_item("p1", content);
+            item.deadline = Some(now + chrono::Duration::hours(hours));
+            queue.enqueue(item).unwrap();
+        }
+
+        let overdue: Vec<String> = queue.overdue(now).into_iter().map(|i| i.content).collect();
+        assert_eq!(overdue, vec!["Oldest", "Recent"]);
+    }
+
+    #[test]
+    fn test_load_all() {
+        let dir = tempdir().unwrap();
+        let queue = WorkQueue::new(WorkStore::new(dir.path()));
+        queue.enqueue(make_item("api", "A")).unwrap();
+        queue.enqueue(make_item("web", "B")).unwrap();
+
+        let reloaded = WorkQueue::new(WorkStore::new(dir.path()));
+        reloaded.load_all().unwrap();
+        assert_eq!(reloaded.len(), 2);
+    }
 }
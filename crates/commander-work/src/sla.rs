@@ -0,0 +1,210 @@
+//! SLA deadlines of work items.
+//!
+//! A work item with a `deadline` moves through [`SlaStatus`] levels as time
+//! passes: on track, due soon (within `SlaPolicy::due_soon` of the deadline),
+//! overdue, and breached (overdue by more than `SlaPolicy::breach_after`).
+//! The SLA checker records the level reached on each item and reports every
+//! rise as an [`SlaEscalation`], so each level is announced once.
+//!
+//! Countdowns are rendered as tags such as `[due in 3h 05m]` or
+//! `[overdue 2h 10m]`; [`tag_status`] reads the level back from a rendered
+//! line so frontends can color it.
+
+use chrono::{DateTime, Duration, Utc};
+use commander_models::{EventPriority, SlaStatus, WorkItem};
+
+/// Characters of an item's content shown in escalation titles.
+const TITLE_LEN: usize = 60;
+
+/// Thresholds between SLA levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlaPolicy {
+    /// Time before the deadline from which an item is due soon.
+    pub due_soon: Duration,
+    /// Time after the deadline from which an item is breached.
+    pub breach_after: Duration,
+}
+
+impl Default for SlaPolicy {
+    fn default() -> Self {
+        Self {
+            due_soon: Duration::hours(1),
+            breach_after: Duration::hours(4),
+        }
+    }
+}
+
+impl SlaPolicy {
+    /// SLA level of `item` at `now`.
+    ///
+    /// `None` if the item has no deadline or is no longer open.
+    pub fn status(&self, item: &WorkItem, now: DateTime<Utc>) -> Option<SlaStatus> {
+        let left = item.time_remaining(now)?;
+        Some(if left < -self.breach_after {
+            SlaStatus::Breached
+        } else if left < Duration::zero() {
+            SlaStatus::Overdue
+        } else if left <= self.due_soon {
+            SlaStatus::DueSoon
+        } else {
+            SlaStatus::OnTrack
+        })
+    }
+
+    /// Countdown tag of `item` at `now`, e.g. `[due in 3h 05m]`.
+    pub fn tag(&self, item: &WorkItem, now: DateTime<Utc>) -> Option<String> {
+        let status = self.status(item, now)?;
+        let left = item.time_remaining(now)?;
+        Some(match status {
+            SlaStatus::OnTrack => format!("[due in {}]", format_duration(left)),
+            SlaStatus::DueSoon => format!("[due soon: {} left]", format_duration(left)),
+            SlaStatus::Overdue => format!("[overdue {}]", format_duration(-left)),
+            SlaStatus::Breached => format!("[breached: overdue {}]", format_duration(-left)),
+        })
+    }
+}
+
+/// An item that reached a higher SLA level than last recorded.
+#[derive(Debug, Clone)]
+pub struct SlaEscalation {
+    /// The item, with the new level recorded.
+    pub item: WorkItem,
+    /// Level reached.
+    pub status: SlaStatus,
+}
+
+impl SlaEscalation {
+    /// One-line description, e.g. "Work overdue 2h 10m: Fix login".
+    pub fn title(&self, now: DateTime<Utc>) -> String {
+        let left = self.item.time_remaining(now).unwrap_or_else(Duration::zero);
+        let when = match self.status {
+            SlaStatus::OnTrack | SlaStatus::DueSoon => format!("due in {}", format_duration(left)),
+            SlaStatus::Overdue => format!("overdue {}", format_duration(-left)),
+            SlaStatus::Breached => format!("SLA breached, overdue {}", format_duration(-left)),
+        };
+        let content: String = self.item.content.lines().next().unwrap_or_default().chars().take(TITLE_LEN).collect();
+        format!("Work {}: {}", when, content)
+    }
+
+    /// Priority of the event announcing the escalation.
+    pub fn priority(&self) -> EventPriority {
+        match self.status {
+            SlaStatus::OnTrack => EventPriority::Info,
+            SlaStatus::DueSoon => EventPriority::Normal,
+            SlaStatus::Overdue => EventPriority::High,
+            SlaStatus::Breached => EventPriority::Critical,
+        }
+    }
+}
+
+/// SLA level of a line rendered with an [`SlaPolicy::tag`].
+pub fn tag_status(line: &str) -> Option<SlaStatus> {
+    if line.contains("[breached: ") {
+        Some(SlaStatus::Breached)
+    } else if line.contains("[overdue ") {
+        Some(SlaStatus::Overdue)
+    } else if line.contains("[due soon: ") {
+        Some(SlaStatus::DueSoon)
+    } else if line.contains("[due in ") {
+        Some(SlaStatus::OnTrack)
+    } else {
+        None
+    }
+}
+
+/// Parse an SLA such as "45m", "4h", "1h30m", "2d" or "1w".
+pub fn parse_sla(text: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let n: i64 = number.parse().ok()?;
+        number.clear();
+        total += match c {
+            'm' => Duration::minutes(n),
+            'h' => Duration::hours(n),
+            'd' => Duration::days(n),
+            'w' => Duration::weeks(n),
+            _ => return None,
+        };
+    }
+    (number.is_empty() && total > Duration::zero()).then_some(total)
+}
+
+/// Short form of `duration`, e.g. "45m", "3h 05m" or "2d 4h".
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    match (minutes / (24 * 60), (minutes / 60) % 24, minutes % 60) {
+        (0, 0, m) => format!("{}m", m),
+        (0, h, m) => format!("{}h {:02}m", h, m),
+        (d, h, _) => format!("{}d {}h", d, h),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item_due_in(minutes: i64, now: DateTime<Utc>) -> WorkItem {
+        let mut item = WorkItem::new("p1", "Fix login\nwith details");
+        item.deadline = Some(now + Duration::minutes(minutes));
+        item
+    }
+
+    #[test]
+    fn test_status_levels() {
+        let policy = SlaPolicy::default();
+        let now = Utc::now();
+        assert_eq!(policy.status(&WorkItem::new("p1", "No SLA"), now), None);
+        assert_eq!(policy.status(&item_due_in(120, now), now), Some(SlaStatus::OnTrack));
+        assert_eq!(policy.status(&item_due_in(30, now), now), Some(SlaStatus::DueSoon));
+        assert_eq!(policy.status(&item_due_in(-30, now), now), Some(SlaStatus::Overdue));
+        assert_eq!(policy.status(&item_due_in(-300, now), now), Some(SlaStatus::Breached));
+
+        let mut done = item_due_in(-300, now);
+        done.complete(None);
+        assert_eq!(policy.status(&done, now), None);
+    }
+
+    #[test]
+    fn test_tags_round_trip() {
+        let policy = SlaPolicy::default();
+        let now = Utc::now();
+        for (minutes, tag, status) in [
+            (185, "[due in 3h 05m]", SlaStatus::OnTrack),
+            (45, "[due soon: 45m left]", SlaStatus::DueSoon),
+            (-130, "[overdue 2h 10m]", SlaStatus::Overdue),
+            (-1680, "[breached: overdue 1d 4h]", SlaStatus::Breached),
+        ] {
+            let rendered = policy.tag(&item_due_in(minutes, now), now).unwrap();
+            assert_eq!(rendered, tag);
+            assert_eq!(tag_status(&format!("[ ]   abc Task {}", rendered)), Some(status));
+        }
+        assert_eq!(tag_status("[ ]   abc Task"), None);
+    }
+
+    #[test]
+    fn test_parse_sla() {
+        assert_eq!(parse_sla("45m"), Some(Duration::minutes(45)));
+        assert_eq!(parse_sla("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_sla(" 2d "), Some(Duration::days(2)));
+        assert_eq!(parse_sla("1w"), Some(Duration::weeks(1)));
+        for invalid in ["", "3", "0h", "h", "2x", "1.5h"] {
+            assert_eq!(parse_sla(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_escalation_title() {
+        let now = Utc::now();
+        let escalation = SlaEscalation {
+            item: item_due_in(-130, now),
+            status: SlaStatus::Overdue,
+        };
+        assert_eq!(escalation.title(now), "Work overdue 2h 10m: Fix login");
+        assert_eq!(escalation.priority(), EventPriority::High);
+    }
+}